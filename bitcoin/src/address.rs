@@ -1,7 +1,9 @@
+use crate::bech32m::{self, Variant};
 use crate::format::BitcoinFormat;
-use crate::network::BitcoinNetwork;
+use crate::network::{BitcoinNetwork, Mainnet, Regtest, Testnet};
 use crate::private_key::BitcoinPrivateKey;
 use crate::public_key::BitcoinPublicKey;
+use crate::script::BitcoinScript;
 use crate::witness_program::WitnessProgram;
 use wagyu_model::no_std::*;
 use wagyu_model::{
@@ -43,6 +45,11 @@ impl<N: BitcoinNetwork> Address for BitcoinAddress<N> {
             }
             BitcoinFormat::P2SH_P2WPKH => Self::p2sh_p2wpkh(&public_key),
             BitcoinFormat::Bech32 => Self::bech32(&public_key),
+            BitcoinFormat::P2TR => Self::p2tr(&public_key),
+            BitcoinFormat::FutureWitness => Err(AddressError::IncompatibleFormats(
+                String::from("public key"),
+                String::from("future witness address"),
+            )),
         }
     }
 
@@ -58,6 +65,11 @@ impl<N: BitcoinNetwork> Address for BitcoinAddress<N> {
             }
             BitcoinFormat::P2SH_P2WPKH => Self::p2sh_p2wpkh(public_key),
             BitcoinFormat::Bech32 => Self::bech32(public_key),
+            BitcoinFormat::P2TR => Self::p2tr(public_key),
+            BitcoinFormat::FutureWitness => Err(AddressError::IncompatibleFormats(
+                String::from("public key"),
+                String::from("future witness address"),
+            )),
         }
     }
 }
@@ -70,79 +82,148 @@ impl<N: BitcoinNetwork> BitcoinAddress<N> {
             false => public_key.to_secp256k1_public_key().serialize().to_vec(),
         };
 
-        let mut address = [0u8; 25];
-        address[0] = N::to_address_prefix(&BitcoinFormat::P2PKH)[0];
-        address[1..21].copy_from_slice(&hash160(&public_key));
+        Ok(Self::from_hash(BitcoinFormat::P2PKH, &hash160(&public_key)))
+    }
 
-        let sum = &checksum(&address[0..21])[0..4];
-        address[21..25].copy_from_slice(sum);
+    /// Returns a P2WSH address in Bech32 format from a given Bitcoin witness script.
+    pub fn p2wsh(original_script: &[u8]) -> Result<Self, AddressError> {
+        // Get the SHA256 hash of the script
+        Self::from_witness_v0(BitcoinFormat::P2WSH, &Sha256::digest(original_script))
+    }
 
-        Ok(Self {
-            address: address.to_base58(),
-            format: BitcoinFormat::P2PKH,
-            _network: PhantomData,
-        })
+    /// Returns a P2SH_P2WPKH address from a given Bitcoin public key.
+    pub fn p2sh_p2wpkh(public_key: &<Self as Address>::PublicKey) -> Result<Self, AddressError> {
+        Self::p2sh(&Self::create_redeem_script(public_key))
     }
 
-    // Returns a P2WSH address in Bech32 format from a given Bitcoin script
-    pub fn p2wsh(original_script: &Vec<u8>) -> Result<Self, AddressError> {
-        let script = Sha256::digest(&original_script).to_vec();
+    /// Returns a P2SH address from a given Bitcoin redeem script.
+    /// P2SH addresses share their prefix, and thereby their format, with P2SH_P2WPKH addresses.
+    pub fn p2sh(redeem_script: &[u8]) -> Result<Self, AddressError> {
+        Ok(Self::from_hash(BitcoinFormat::P2SH_P2WPKH, &hash160(redeem_script)))
+    }
 
-        // Organize as a hash
-        let v = N::to_address_prefix(&BitcoinFormat::P2WSH)[0];
-        let version = u5::try_from_u8(v)?;
+    /// Returns a Bech32 address from a given Bitcoin public key.
+    pub fn bech32(public_key: &<Self as Address>::PublicKey) -> Result<Self, AddressError> {
+        let redeem_script = Self::create_redeem_script(public_key);
+        Self::from_witness_v0(BitcoinFormat::Bech32, &redeem_script[2..])
+    }
 
-        let mut data = vec![version];
-        // Get the SHA256 hash of the script
-        data.extend_from_slice(&script.to_vec().to_base32());
+    /// Returns a P2TR address in Bech32m format from a given Bitcoin public key,
+    /// using the public key as the internal key without a script tree.
+    pub fn p2tr(public_key: &<Self as Address>::PublicKey) -> Result<Self, AddressError> {
+        Self::p2tr_output_key(&public_key.to_taproot_output_key()?)
+    }
 
-        let bech32 = Bech32::new(String::from_utf8(N::to_address_prefix(&BitcoinFormat::Bech32))?, data)?;
+    /// Returns a P2TR address in Bech32m format from a given x-only Taproot output key.
+    pub fn p2tr_output_key(output_key: &[u8; 32]) -> Result<Self, AddressError> {
+        let mut data = vec![u5::try_from_u8(1)?];
+        data.extend_from_slice(&output_key.to_base32());
 
         Ok(Self {
-            address: bech32.to_string(),
-            format: BitcoinFormat::P2WSH,
+            address: bech32m::encode(
+                &String::from_utf8(N::to_address_prefix(&BitcoinFormat::P2TR))?,
+                &data,
+                Variant::Bech32m,
+            ),
+            format: BitcoinFormat::P2TR,
             _network: PhantomData,
         })
     }
 
-    /// Returns a P2SH_P2WPKH address from a given Bitcoin public key.
-    pub fn p2sh_p2wpkh(public_key: &<Self as Address>::PublicKey) -> Result<Self, AddressError> {
+    /// Returns the address of the given scriptPubKey, if it is a P2PKH, P2SH, P2WPKH, P2WSH or P2TR script.
+    pub fn from_script_pub_key(script_pub_key: &BitcoinScript) -> Result<Self, AddressError> {
+        match script_pub_key.to_bytes().as_slice() {
+            // OP_DUP OP_HASH160 <20 bytes> OP_EQUALVERIFY OP_CHECKSIG
+            [0x76, 0xa9, 0x14, hash @ .., 0x88, 0xac] if hash.len() == 20 => {
+                Ok(Self::from_hash(BitcoinFormat::P2PKH, hash))
+            }
+            // OP_HASH160 <20 bytes> OP_EQUAL
+            [0xa9, 0x14, hash @ .., 0x87] if hash.len() == 20 => Ok(Self::from_hash(BitcoinFormat::P2SH_P2WPKH, hash)),
+            // OP_0 <20 bytes>
+            [0x00, 0x14, program @ ..] if program.len() == 20 => Self::from_witness_v0(BitcoinFormat::Bech32, program),
+            // OP_0 <32 bytes>
+            [0x00, 0x20, program @ ..] if program.len() == 32 => Self::from_witness_v0(BitcoinFormat::P2WSH, program),
+            // OP_1 <32 bytes>
+            [0x51, 0x20, program @ ..] if program.len() == 32 => {
+                let mut output_key = [0u8; 32];
+                output_key.copy_from_slice(program);
+                Self::p2tr_output_key(&output_key)
+            }
+            script => Err(AddressError::InvalidAddress(format!(
+                "no address for script public key {}",
+                hex::encode(script)
+            ))),
+        }
+    }
+
+    /// Returns the scriptPubKey of the address.
+    pub fn to_script_pub_key(&self) -> Result<BitcoinScript, AddressError> {
+        match self.format {
+            BitcoinFormat::P2PKH | BitcoinFormat::P2SH_P2WPKH => {
+                let bytes = self.address.from_base58()?;
+                let mut hash = [0u8; 20];
+                hash.copy_from_slice(&bytes[1..21]);
+
+                match self.format {
+                    BitcoinFormat::P2PKH => Ok(BitcoinScript::p2pkh(&hash)),
+                    _ => Ok(BitcoinScript::p2sh(&hash)),
+                }
+            }
+            BitcoinFormat::P2WSH | BitcoinFormat::Bech32 | BitcoinFormat::P2TR | BitcoinFormat::FutureWitness => {
+                Ok(BitcoinScript::new(&self.to_witness_program()?.to_scriptpubkey()))
+            }
+        }
+    }
+
+    /// Returns the witness program of a Bech32 or P2TR address.
+    /// The checksum variant must correspond to the witness version, as defined in BIP350.
+    pub(crate) fn to_witness_program(&self) -> Result<WitnessProgram, AddressError> {
+        let (_, data, variant) = bech32m::decode(&self.address)?;
+        if data.is_empty() || variant != Variant::from_witness_version(data[0].to_u8()) {
+            return Err(AddressError::InvalidAddress(self.address.clone()));
+        }
+
+        let mut program = Vec::from_base32(&data[1..])?;
+        let mut bytes = vec![data[0].to_u8(), program.len() as u8];
+        bytes.append(&mut program);
+        Ok(WitnessProgram::new(&bytes)?)
+    }
+
+    /// Returns the format of the Bitcoin address.
+    pub fn format(&self) -> BitcoinFormat {
+        self.format.clone()
+    }
+
+    /// Returns a Base58Check address of the given format from a given hash of a public key or script.
+    fn from_hash(format: BitcoinFormat, hash: &[u8]) -> Self {
         let mut address = [0u8; 25];
-        address[0] = N::to_address_prefix(&BitcoinFormat::P2SH_P2WPKH)[0];
-        address[1..21].copy_from_slice(&hash160(&Self::create_redeem_script(public_key)));
+        address[0] = N::to_address_prefix(&format)[0];
+        address[1..21].copy_from_slice(hash);
 
         let sum = &checksum(&address[0..21])[0..4];
         address[21..25].copy_from_slice(sum);
 
-        Ok(Self {
+        Self {
             address: address.to_base58(),
-            format: BitcoinFormat::P2SH_P2WPKH,
+            format,
             _network: PhantomData,
-        })
+        }
     }
 
-    /// Returns a Bech32 address from a given Bitcoin public key.
-    pub fn bech32(public_key: &<Self as Address>::PublicKey) -> Result<Self, AddressError> {
-        let redeem_script = Self::create_redeem_script(public_key);
-        let version = u5::try_from_u8(redeem_script[0])?;
-
-        let mut data = vec![version];
-        data.extend_from_slice(&redeem_script[2..].to_vec().to_base32());
+    /// Returns a Bech32 address of the given format from a given version 0 witness program.
+    fn from_witness_v0(format: BitcoinFormat, program: &[u8]) -> Result<Self, AddressError> {
+        let mut data = vec![u5::try_from_u8(0)?];
+        data.extend_from_slice(&program.to_base32());
 
         let bech32 = Bech32::new(String::from_utf8(N::to_address_prefix(&BitcoinFormat::Bech32))?, data)?;
 
         Ok(Self {
             address: bech32.to_string(),
-            format: BitcoinFormat::Bech32,
+            format,
             _network: PhantomData,
         })
     }
 
-    /// Returns the format of the Bitcoin address.
-    pub fn format(&self) -> BitcoinFormat {
-        self.format.clone()
-    }
-
     /// Returns a redeem script for a given Bitcoin public key.
     fn create_redeem_script(public_key: &<Self as Address>::PublicKey) -> [u8; 22] {
         let mut redeem = [0u8; 22];
@@ -168,32 +249,41 @@ impl<N: BitcoinNetwork> FromStr for BitcoinAddress<N> {
             return Err(AddressError::InvalidCharacterLength(address.len()));
         }
 
-        let prefix = &address.to_lowercase()[0..2];
+        let lowercase = address.to_lowercase();
+        let prefix = &lowercase[0..2];
+        let hrp = String::from_utf8(N::to_address_prefix(&BitcoinFormat::Bech32))?;
 
-        if let Ok(format) = BitcoinFormat::from_address_prefix(prefix.as_bytes()) {
+        let format = match lowercase.starts_with(&format!("{}1", hrp)) {
+            true => Ok(BitcoinFormat::Bech32),
+            false => BitcoinFormat::from_address_prefix(prefix.as_bytes()),
+        };
+        if let Ok(format) = format {
             if BitcoinFormat::Bech32 == format {
-                let bech32 = Bech32::from_str(&address)?;
-                if bech32.data().is_empty() {
-                    return Err(AddressError::InvalidAddress(address.to_owned()));
-                }
-
-                let data = bech32.data();
-                let version = data[0].to_u8();
-                let mut program = Vec::from_base32(&data[1..])?;
-
-                let mut data = vec![version, program.len() as u8];
-                data.append(&mut program);
-
-                // Check that the witness program is valid.
-                let _ = WitnessProgram::new(data.as_slice())?;
-                // Check that the address prefix corresponds to the correct network.
-                let _ = N::from_address_prefix(prefix.as_bytes())?;
-
-                return Ok(Self {
+                let mut bitcoin_address = Self {
                     address: address.to_owned(),
-                    format: BitcoinFormat::Bech32,
+                    format,
                     _network: PhantomData,
-                });
+                };
+
+                // Check that the witness program and its checksum variant are valid.
+                // Other programs of version 1 and programs of versions 2 to 16 are reserved for future upgrades.
+                let witness_program = bitcoin_address.to_witness_program()?;
+                bitcoin_address.format = match (witness_program.version, witness_program.program.len()) {
+                    (0, 20) => BitcoinFormat::Bech32,
+                    (0, 32) => BitcoinFormat::P2WSH,
+                    (0, length) => return Err(AddressError::InvalidByteLength(length)),
+                    (1, 32) => BitcoinFormat::P2TR,
+                    _ => BitcoinFormat::FutureWitness,
+                };
+                // Check that the address prefix corresponds to the correct network.
+                let address_hrp = &lowercase.as_bytes()[0..lowercase.rfind('1').unwrap_or(2)];
+                let _ = N::from_address_prefix(address_hrp)?;
+                // The human-readable part must match exactly, as "bc" is a prefix of "bcrt".
+                if address_hrp != hrp.as_bytes() {
+                    return Err(AddressError::InvalidPrefix(address_hrp.to_vec()));
+                }
+
+                return Ok(bitcoin_address);
             }
         }
 
@@ -202,9 +292,21 @@ impl<N: BitcoinNetwork> FromStr for BitcoinAddress<N> {
             return Err(AddressError::InvalidByteLength(data.len()));
         }
 
+        let expected = &data[21..25];
+        let checksum = &checksum(&data[0..21])[0..4];
+        if *expected != *checksum {
+            let expected = expected.to_base58();
+            let found = checksum.to_base58();
+            return Err(AddressError::InvalidChecksum(expected, found));
+        }
+
         // Check that the address prefix corresponds to the correct network.
         let _ = N::from_address_prefix(&data[0..2])?;
-        let format = BitcoinFormat::from_address_prefix(&data[0..2])?;
+        let format = match data[0] {
+            version if version == N::to_address_prefix(&BitcoinFormat::P2PKH)[0] => BitcoinFormat::P2PKH,
+            version if version == N::to_address_prefix(&BitcoinFormat::P2SH_P2WPKH)[0] => BitcoinFormat::P2SH_P2WPKH,
+            _ => return Err(AddressError::InvalidPrefix(data[0..2].to_vec())),
+        };
 
         Ok(Self {
             address: address.into(),
@@ -214,6 +316,20 @@ impl<N: BitcoinNetwork> FromStr for BitcoinAddress<N> {
     }
 }
 
+/// Returns the format and network name of the given Bitcoin address,
+/// detected from its encoding and prefix.
+/// Testnet and signet share their prefixes, as do testnet and regtest base58 addresses,
+/// so these addresses are detected as testnet addresses.
+pub fn detect_address(address: &str) -> Result<(BitcoinFormat, &'static str), AddressError> {
+    fn detect<N: BitcoinNetwork>(address: &str) -> Result<(BitcoinFormat, &'static str), AddressError> {
+        BitcoinAddress::<N>::from_str(address).map(|address| (address.format(), N::NAME))
+    }
+
+    detect::<Mainnet>(address)
+        .or_else(|error| detect::<Testnet>(address).map_err(|_| error))
+        .or_else(|error| detect::<Regtest>(address).map_err(|_| error))
+}
+
 impl<N: BitcoinNetwork> fmt::Display for BitcoinAddress<N> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self.address)
@@ -839,6 +955,202 @@ mod tests {
         }
     }
 
+    mod bech32_signet {
+        use super::*;
+
+        type N = Signet;
+
+        const KEYPAIRS: [(&str, &str); 2] = [
+            (
+                "cVQmTtLoCjDJAXVj778xyww1ZbpJQt7Vq9sDt8Mdmw97Rg7TaNes",
+                "tb1qmkvfprg8pkr3apv9gyykmhe26fexyla076ss0g",
+            ),
+            (
+                "cTxHRG8MgrnSQstuMs5VnQcFBjrs67NmiJGo1kevnJDS7QFGLUAi",
+                "tb1qfe0dnfpxp4c9lfdjzvmf5q72jg83emgknmcxxd",
+            ),
+        ];
+
+        #[test]
+        fn from_private_key() {
+            KEYPAIRS.iter().for_each(|(private_key, address)| {
+                let private_key = BitcoinPrivateKey::<N>::from_str(private_key).unwrap();
+                test_from_private_key(address, &private_key, &BitcoinFormat::Bech32);
+            });
+        }
+
+        #[test]
+        fn from_str() {
+            KEYPAIRS.iter().for_each(|(_, address)| {
+                test_from_str::<N>(address, &BitcoinFormat::Bech32);
+            });
+        }
+
+        #[test]
+        fn from_mainnet_address() {
+            let address = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4";
+            assert!(BitcoinAddress::<N>::from_str(address).is_err());
+        }
+    }
+
+    mod bech32_regtest {
+        use super::*;
+
+        type N = Regtest;
+
+        const KEYPAIRS: [(&str, &str); 2] = [
+            (
+                "cVQmTtLoCjDJAXVj778xyww1ZbpJQt7Vq9sDt8Mdmw97Rg7TaNes",
+                "bcrt1qmkvfprg8pkr3apv9gyykmhe26fexyla0unfacp",
+            ),
+            (
+                "cTxHRG8MgrnSQstuMs5VnQcFBjrs67NmiJGo1kevnJDS7QFGLUAi",
+                "bcrt1qfe0dnfpxp4c9lfdjzvmf5q72jg83emgk3jpt3y",
+            ),
+        ];
+
+        #[test]
+        fn from_private_key() {
+            KEYPAIRS.iter().for_each(|(private_key, address)| {
+                let private_key = BitcoinPrivateKey::<N>::from_str(private_key).unwrap();
+                test_from_private_key(address, &private_key, &BitcoinFormat::Bech32);
+            });
+        }
+
+        #[test]
+        fn from_str() {
+            KEYPAIRS.iter().for_each(|(_, address)| {
+                test_from_str::<N>(address, &BitcoinFormat::Bech32);
+            });
+        }
+
+        #[test]
+        fn from_other_network_address() {
+            KEYPAIRS.iter().for_each(|(_, address)| {
+                assert!(BitcoinAddress::<Mainnet>::from_str(address).is_err());
+                assert!(BitcoinAddress::<Testnet>::from_str(address).is_err());
+            });
+            let address = "tb1qmkvfprg8pkr3apv9gyykmhe26fexyla076ss0g";
+            assert!(BitcoinAddress::<N>::from_str(address).is_err());
+        }
+    }
+
+    mod p2tr_mainnet {
+        use super::*;
+
+        type N = Mainnet;
+
+        const ADDRESSES: [&str; 3] = [
+            "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr",
+            "bc1p4qhjn9zdvkux4e44uhx8tc55attvtyu358kutcqkudyccelu0was9fqzwh",
+            "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0",
+        ];
+
+        const INVALID: [&str; 4] = [
+            "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqh2y7hd", // Bech32 instead of Bech32m
+            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kemeawh",                     // Bech32m instead of Bech32
+            "tb1pqqqqp399et2xygdj5xreqhjjvcmzhxw4aywxecjdzew6hylgvsesf3hn0c", // invalid network
+            "bc1pw5dgrnzv",                                                   // invalid program length
+        ];
+
+        #[test]
+        fn from_str() {
+            ADDRESSES.iter().for_each(|address| {
+                test_from_str::<N>(address, &BitcoinFormat::P2TR);
+            });
+        }
+
+        #[test]
+        fn from_invalid_address() {
+            INVALID.iter().for_each(|invalid_address| {
+                assert!(BitcoinAddress::<N>::from_str(invalid_address).is_err());
+            });
+        }
+
+        #[test]
+        fn to_witness_program() {
+            let address = BitcoinAddress::<N>::from_str(ADDRESSES[0]).unwrap();
+            assert_eq!(
+                "5120a60869f0dbcf1dc659c9cecbaf8050135ea9e8cdc487053f1dc6880949dc684c",
+                hex::encode(address.to_witness_program().unwrap().to_scriptpubkey())
+            );
+        }
+    }
+
+    mod bip350_mainnet {
+        use super::*;
+
+        type N = Mainnet;
+
+        // https://github.com/bitcoin/bips/blob/master/bip-0350.mediawiki#test-vectors-for-v0-v16-native-segregated-witness-addresses
+        const VALID: [(&str, &str, BitcoinFormat); 5] = [
+            (
+                "BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4",
+                "0014751e76e8199196d454941c45d1b3a323f1433bd6",
+                BitcoinFormat::Bech32,
+            ),
+            (
+                "bc1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7kt5nd6y",
+                "5128751e76e8199196d454941c45d1b3a323f1433bd6751e76e8199196d454941c45d1b3a323f1433bd6",
+                BitcoinFormat::FutureWitness,
+            ),
+            ("BC1SW50QGDZ25J", "6002751e", BitcoinFormat::FutureWitness),
+            (
+                "bc1zw508d6qejxtdg4y5r3zarvaryvaxxpcs",
+                "5210751e76e8199196d454941c45d1b3a323",
+                BitcoinFormat::FutureWitness,
+            ),
+            (
+                "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0",
+                "512079be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+                BitcoinFormat::P2TR,
+            ),
+        ];
+
+        const INVALID: [&str; 7] = [
+            "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqh2y7hd", // Bech32 instead of Bech32m
+            "BC1S0XLXVLHEMJA6C4DQV22UAPCTQUPFHLXM9H8Z3K2E72Q4K9HCZ7VQ54WELL", // Bech32 instead of Bech32m
+            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kemeawh",                     // Bech32m instead of Bech32
+            "bc1p38j9r5y49hruaue7wxjce0updqjuyyx0kh56v8s25huc6995vvpql3jow4", // invalid character
+            "BC130XLXVLHEMJA6C4DQV22UAPCTQUPFHLXM9H8Z3K2E72Q4K9HCZ7VQ7ZWS8R", // invalid witness version
+            "bc1pw5dgrnzv",                                                   // invalid program length
+            "BC1QR508D6QEJXTDG4Y5R3ZARVARYV98GJ9P",                           // invalid program length for version 0
+        ];
+
+        #[test]
+        fn from_str() {
+            VALID.iter().for_each(|(address, script_pub_key, format)| {
+                let address = BitcoinAddress::<N>::from_str(address).unwrap();
+                assert_eq!(*format, address.format());
+                assert_eq!(
+                    *script_pub_key,
+                    hex::encode(address.to_witness_program().unwrap().to_scriptpubkey())
+                );
+            });
+        }
+
+        #[test]
+        fn from_invalid_address() {
+            INVALID.iter().for_each(|invalid_address| {
+                assert!(BitcoinAddress::<N>::from_str(invalid_address).is_err());
+            });
+        }
+    }
+
+    mod p2tr_testnet {
+        use super::*;
+
+        type N = Testnet;
+
+        #[test]
+        fn from_str() {
+            test_from_str::<N>(
+                "tb1pqqqqp399et2xygdj5xreqhjjvcmzhxw4aywxecjdzew6hylgvsesf3hn0c",
+                &BitcoinFormat::P2TR,
+            );
+        }
+    }
+
     mod p2wsh_testnet {
         use super::*;
 
@@ -872,5 +1184,167 @@ mod tests {
                 test_to_str(expected_address, &address);
             });
         }
+
+        #[test]
+        fn from_str_format() {
+            SCRIPTPAIRS.iter().for_each(|(_, address)| {
+                test_from_str::<N>(address, &BitcoinFormat::P2WSH);
+            });
+        }
+    }
+
+    mod script_pub_key {
+        use super::*;
+
+        const MAINNET: [(&str, &str); 4] = [
+            (
+                "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH",
+                "76a914751e76e8199196d454941c45d1b3a323f1433bd688ac",
+            ),
+            (
+                "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+                "0014751e76e8199196d454941c45d1b3a323f1433bd6",
+            ),
+            (
+                "bc1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3qccfmv3",
+                "00201863143c14c5166804bd19203356da136c985678cd4d27a1b8c6329604903262",
+            ),
+            (
+                "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0",
+                "512079be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+            ),
+        ];
+
+        const ADDRESSES: [&str; 4] = [
+            "38EMCierP738rgYVHjj1qJANHKgx1166TN",
+            "mwCDgjeRgGpfTMY1waYAJF2dGz4Q5XAx6w",
+            "2N9e892o8DNZs25xHBwRPZLsrZK3dBsrH3d",
+            "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7",
+        ];
+
+        const INVALID: [&str; 5] = [
+            // P2PK
+            "210279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798ac",
+            // OP_RETURN
+            "6a0568656c6c6f",
+            // Witness version 2
+            "5210751e76e8199196d454941c45d1b3a323",
+            // Version 0 witness program of 21 bytes
+            "0015751e76e8199196d454941c45d1b3a323f1433bd600",
+            // P2PKH with a trailing opcode
+            "76a914751e76e8199196d454941c45d1b3a323f1433bd688ac00",
+        ];
+
+        fn test_round_trip<N: BitcoinNetwork>(address: &str) {
+            let address = BitcoinAddress::<N>::from_str(address).unwrap();
+            let script_pub_key = address.to_script_pub_key().unwrap();
+            assert_eq!(
+                address,
+                BitcoinAddress::<N>::from_script_pub_key(&script_pub_key).unwrap()
+            );
+        }
+
+        #[test]
+        fn to_script_pub_key() {
+            MAINNET.iter().for_each(|(address, script_pub_key)| {
+                let address = BitcoinAddress::<Mainnet>::from_str(address).unwrap();
+                assert_eq!(*script_pub_key, address.to_script_pub_key().unwrap().to_string());
+            });
+        }
+
+        #[test]
+        fn from_script_pub_key() {
+            MAINNET.iter().for_each(|(address, script_pub_key)| {
+                let script_pub_key = BitcoinScript::from_str(script_pub_key).unwrap();
+                let expected = BitcoinAddress::<Mainnet>::from_str(address).unwrap();
+                let address = BitcoinAddress::<Mainnet>::from_script_pub_key(&script_pub_key).unwrap();
+                assert_eq!(expected.to_string(), address.to_string());
+                assert_eq!(expected.format(), address.format());
+            });
+        }
+
+        #[test]
+        fn round_trip() {
+            test_round_trip::<Mainnet>(ADDRESSES[0]);
+            ADDRESSES[1..]
+                .iter()
+                .for_each(|address| test_round_trip::<Testnet>(address));
+        }
+
+        #[test]
+        fn invalid() {
+            INVALID.iter().for_each(|script_pub_key| {
+                let script_pub_key = BitcoinScript::from_str(script_pub_key).unwrap();
+                assert!(BitcoinAddress::<Mainnet>::from_script_pub_key(&script_pub_key).is_err());
+            });
+        }
+    }
+
+    mod detect_address {
+        use super::*;
+
+        const VALID: [(&str, BitcoinFormat, &str); 9] = [
+            ("1NoZQSmjYHUZMbqLerwmT4xfe8A6mAo8TT", BitcoinFormat::P2PKH, "mainnet"),
+            (
+                "38EMCierP738rgYVHjj1qJANHKgx1166TN",
+                BitcoinFormat::P2SH_P2WPKH,
+                "mainnet",
+            ),
+            (
+                "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+                BitcoinFormat::Bech32,
+                "mainnet",
+            ),
+            (
+                "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0",
+                BitcoinFormat::P2TR,
+                "mainnet",
+            ),
+            ("mwCDgjeRgGpfTMY1waYAJF2dGz4Q5XAx6w", BitcoinFormat::P2PKH, "testnet"),
+            (
+                "2N9e892o8DNZs25xHBwRPZLsrZK3dBsrH3d",
+                BitcoinFormat::P2SH_P2WPKH,
+                "testnet",
+            ),
+            (
+                "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7",
+                BitcoinFormat::P2WSH,
+                "testnet",
+            ),
+            (
+                "tb1pqqqqp399et2xygdj5xreqhjjvcmzhxw4aywxecjdzew6hylgvsesf3hn0c",
+                BitcoinFormat::P2TR,
+                "testnet",
+            ),
+            (
+                "bcrt1qmkvfprg8pkr3apv9gyykmhe26fexyla0unfacp",
+                BitcoinFormat::Bech32,
+                "regtest",
+            ),
+        ];
+
+        const INVALID: [&str; 4] = [
+            "1NoZQSmjYHUZMbqLerwmT4xfe8A6mAo8TU",
+            "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqh2y7hd",
+            "ltc1qw508d6qejxtdg4y5r3zarvary0c5xw7kgmn4n9",
+            "LVg2kJoFNg45Nbpy53h7Fe1wKyeXVRhMH9",
+        ];
+
+        #[test]
+        fn detect() {
+            VALID.iter().for_each(|(address, format, network)| {
+                assert_eq!(
+                    (format.clone(), *network),
+                    super::super::detect_address(address).unwrap()
+                );
+            });
+        }
+
+        #[test]
+        fn detect_invalid() {
+            INVALID.iter().for_each(|address| {
+                assert!(super::super::detect_address(address).is_err());
+            });
+        }
     }
 }
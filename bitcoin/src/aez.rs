@@ -0,0 +1,472 @@
+//! # AEZ
+//!
+//! The AEZ v5 authenticated enciphering scheme, as specified in
+//! http://web.cs.ucdavis.edu/~rogaway/aez/aez.pdf and used by the aezeed cipher seed format.
+
+use aes::hazmat::cipher_round;
+use aes::Block;
+use wagyu_model::no_std::*;
+
+type Bytes = [u8; 16];
+
+/// Represents an AEZ cipher with an extracted key.
+///
+/// Authenticators (`tau`) of at most 16 bytes are supported, which covers its use by aezeed.
+pub(crate) struct Aez {
+    i: Bytes,
+    j: Bytes,
+    l: Bytes,
+}
+
+impl Aez {
+    /// Returns the cipher for the given key, extracted with BLAKE2b unless it is 48 bytes long.
+    pub(crate) fn new(key: &[u8]) -> Self {
+        let mut extracted = [0u8; 48];
+        match key.len() {
+            48 => extracted.copy_from_slice(key),
+            _ => extracted.copy_from_slice(blake2b_simd::Params::new().hash_length(48).hash(key).as_bytes()),
+        };
+
+        let mut aez = Self {
+            i: [0u8; 16],
+            j: [0u8; 16],
+            l: [0u8; 16],
+        };
+        aez.i.copy_from_slice(&extracted[..16]);
+        aez.j.copy_from_slice(&extracted[16..32]);
+        aez.l.copy_from_slice(&extracted[32..]);
+        aez
+    }
+
+    /// Returns the ciphertext of the plaintext with `tau` bytes of authenticator.
+    pub(crate) fn encrypt(&self, nonce: &[u8], data: &[&[u8]], tau: usize, plaintext: &[u8]) -> Vec<u8> {
+        let delta = self.hash(nonce, data, tau);
+        if plaintext.is_empty() {
+            return self.prf(&delta, tau);
+        }
+
+        let mut input = plaintext.to_vec();
+        input.resize(plaintext.len() + tau, 0);
+        match input.len() < 32 {
+            true => self.encipher_tiny(&delta, &input, false),
+            false => self.encipher_core(&delta, &input, false),
+        }
+    }
+
+    /// Returns the plaintext of the ciphertext with `tau` bytes of authenticator,
+    /// or `None` if the ciphertext is not authentic.
+    pub(crate) fn decrypt(&self, nonce: &[u8], data: &[&[u8]], tau: usize, ciphertext: &[u8]) -> Option<Vec<u8>> {
+        if ciphertext.len() < tau {
+            return None;
+        }
+
+        let delta = self.hash(nonce, data, tau);
+        if ciphertext.len() == tau {
+            let difference = self
+                .prf(&delta, tau)
+                .iter()
+                .zip(ciphertext)
+                .fold(0u8, |difference, (a, b)| difference | (a ^ b));
+            return match difference {
+                0 => Some(vec![]),
+                _ => None,
+            };
+        }
+
+        let output = match ciphertext.len() < 32 {
+            true => self.encipher_tiny(&delta, ciphertext, true),
+            false => self.encipher_core(&delta, ciphertext, true),
+        };
+        let (plaintext, authenticator) = output.split_at(ciphertext.len() - tau);
+        match authenticator.iter().fold(0u8, |difference, byte| difference | byte) {
+            0 => Some(plaintext.to_vec()),
+            _ => None,
+        }
+    }
+
+    /// Returns the AEZ-hash of the authenticator length, nonce, and associated data.
+    fn hash(&self, nonce: &[u8], data: &[&[u8]], tau: usize) -> Bytes {
+        let mut tau_block = [0u8; 16];
+        tau_block[12..].copy_from_slice(&((tau * 8) as u32).to_be_bytes());
+
+        let mut delta = self.e(3, 1, &tau_block);
+        for (index, string) in [nonce].iter().chain(data).enumerate() {
+            let j = 4 + index;
+            match string.is_empty() {
+                true => xor_into(&mut delta, &self.e(j, 0, &pad(&[]))),
+                false => string.chunks(16).enumerate().for_each(|(i, chunk)| match chunk.len() {
+                    16 => xor_into(&mut delta, &self.e(j, i + 1, &to_block(chunk))),
+                    _ => xor_into(&mut delta, &self.e(j, 0, &pad(chunk))),
+                }),
+            }
+        }
+        delta
+    }
+
+    /// Returns the AEZ-prf output of `tau` bytes, the ciphertext of an empty plaintext.
+    fn prf(&self, delta: &Bytes, tau: usize) -> Vec<u8> {
+        self.e_prf(3, delta)[..tau].to_vec()
+    }
+
+    /// Returns the output of AEZ-tiny for messages of less than 32 bytes.
+    fn encipher_tiny(&self, delta: &Bytes, input: &[u8], decrypt: bool) -> Vec<u8> {
+        let length = input.len();
+        let half = length / 2;
+
+        let mut buffer = [0u8; 32];
+        buffer[..length].copy_from_slice(input);
+        let mut left = to_block(&buffer[..16]);
+        let mut right = to_block(&buffer[half..half + 16]);
+
+        // Masks the halves to their bits, followed by 10* padding.
+        let mut mask_ff = [0u8; 16];
+        let mut mask_10 = [0u8; 16];
+        mask_ff[..half].iter_mut().for_each(|byte| *byte = 0xff);
+        mask_10[half] = 0x80;
+        if length % 2 == 1 {
+            mask_ff[half] = 0xf0;
+            mask_10[half] = 0x08;
+            right = shift_left_4(&right);
+        }
+        let mask = |block: &mut Bytes| {
+            block.iter_mut().zip(&mask_ff).for_each(|(byte, mask)| *byte &= mask);
+            xor_into(block, &mask_10);
+        };
+        mask(&mut right);
+
+        let (offset, rounds) = match length {
+            16..=31 => (6, 8),
+            3..=15 => (7, 10),
+            2 => (7, 16),
+            _ => (7, 24),
+        };
+        let mut tweak = *delta;
+        xor_into(&mut tweak, &self.offset(0, offset));
+
+        if decrypt && length < 16 {
+            let mut block = left;
+            block[0] |= 0x80;
+            xor_into(&mut block, delta);
+            left[0] ^= self.e(0, 3, &block)[0] & 0x80;
+        }
+
+        for round in (0..rounds).step_by(2) {
+            let (first, second) = match decrypt {
+                true => (rounds - 1 - round, rounds - 2 - round),
+                false => (round, round + 1),
+            };
+
+            let mut block = tweak;
+            xor_into(&mut block, &right);
+            block[15] ^= first as u8;
+            xor_into(&mut left, &self.aes4(&block));
+            mask(&mut left);
+
+            let mut block = tweak;
+            xor_into(&mut block, &left);
+            block[15] ^= second as u8;
+            xor_into(&mut right, &self.aes4(&block));
+            mask(&mut right);
+        }
+
+        buffer[..16].copy_from_slice(&right);
+        if length % 2 == 1 {
+            left = shift_right_4(&left);
+            left[0] |= buffer[half] & 0xf0;
+        }
+        buffer[half..half + 16].copy_from_slice(&left);
+
+        if !decrypt && length < 16 {
+            let mut block = [0u8; 16];
+            block[..length].copy_from_slice(&buffer[..length]);
+            block[0] |= 0x80;
+            xor_into(&mut block, delta);
+            buffer[0] ^= self.e(0, 3, &block)[0] & 0x80;
+        }
+
+        buffer[..length].to_vec()
+    }
+
+    /// Returns the output of AEZ-core for messages of at least 32 bytes.
+    fn encipher_core(&self, delta: &Bytes, input: &[u8], decrypt: bool) -> Vec<u8> {
+        let length = input.len();
+        let fragment_length = length % 32;
+        let initial_length = length - fragment_length - 32;
+        let mut output = vec![0u8; length];
+
+        // The first pass over the pairs of blocks
+        let mut x = [0u8; 16];
+        for (index, pair) in input[..initial_length].chunks(32).enumerate() {
+            let mut w = self.e(1, index + 1, &to_block(&pair[16..]));
+            xor_into(&mut w, &pair[..16]);
+            let mut x_i = self.e(0, 0, &w);
+            xor_into(&mut x_i, &pair[16..]);
+            xor_into(&mut x, &x_i);
+            output[index * 32..index * 32 + 16].copy_from_slice(&w);
+            output[index * 32 + 16..index * 32 + 32].copy_from_slice(&x_i);
+        }
+
+        let fragment = &input[initial_length..initial_length + fragment_length];
+        let (mut fragment_0, mut fragment_1) = match fragment_length {
+            0 => ([0u8; 16], [0u8; 16]),
+            1..=15 => (pad(fragment), [0u8; 16]),
+            _ => (to_block(&fragment[..16]), pad(&fragment[16..])),
+        };
+        if fragment_length > 0 {
+            xor_into(&mut x, &self.e(0, 4, &fragment_0));
+        }
+        if fragment_length >= 16 {
+            xor_into(&mut x, &self.e(0, 5, &fragment_1));
+        }
+
+        // The final pair of blocks
+        let d = decrypt as usize;
+        let mut final_0 = to_block(&input[length - 32..length - 16]);
+        xor_into(&mut final_0, &x);
+        xor_into(&mut final_0, delta);
+        let mut final_1 = to_block(&input[length - 16..]);
+        xor_into(&mut final_0, &self.e(0, 1 + d, &final_1));
+        xor_into(&mut final_1, &self.e_prf(1 + d, &final_0));
+        let mut s = final_0;
+        xor_into(&mut s, &final_1);
+        xor_into(&mut final_0, &self.e_prf(2 - d, &final_1));
+        xor_into(&mut final_1, &self.e(0, 2 - d, &final_0));
+
+        // The second pass over the pairs of blocks
+        let mut y = [0u8; 16];
+        for index in 0..initial_length / 32 {
+            let pair = &mut output[index * 32..index * 32 + 32];
+            let s_i = self.e(2, index + 1, &s);
+            let mut y_i = to_block(&pair[..16]);
+            xor_into(&mut y_i, &s_i);
+            xor_into(&mut y, &y_i);
+            let mut z = to_block(&pair[16..]);
+            xor_into(&mut z, &s_i);
+            let mut c_i = self.e(0, 0, &z);
+            xor_into(&mut c_i, &y_i);
+            let mut c = self.e(1, index + 1, &c_i);
+            xor_into(&mut c, &z);
+            pair[..16].copy_from_slice(&c);
+            pair[16..].copy_from_slice(&c_i);
+        }
+
+        let fragment = &mut output[initial_length..initial_length + fragment_length];
+        if fragment_length > 0 {
+            xor_into(&mut fragment_0, &self.e_prf(4, &s));
+            if fragment_length < 16 {
+                fragment_0 = pad(&fragment_0[..fragment_length]);
+                fragment.copy_from_slice(&fragment_0[..fragment_length]);
+            } else {
+                fragment[..16].copy_from_slice(&fragment_0);
+            }
+            xor_into(&mut y, &self.e(0, 4, &fragment_0));
+        }
+        if fragment_length >= 16 {
+            xor_into(&mut fragment_1, &self.e_prf(5, &s));
+            fragment_1 = pad(&fragment_1[..fragment_length - 16]);
+            fragment[16..].copy_from_slice(&fragment_1[..fragment_length - 16]);
+            xor_into(&mut y, &self.e(0, 5, &fragment_1));
+        }
+
+        xor_into(&mut final_1, &y);
+        xor_into(&mut final_1, delta);
+        output[length - 32..length - 16].copy_from_slice(&final_1);
+        output[length - 16..].copy_from_slice(&final_0);
+        output
+    }
+
+    /// Returns the tweakable block cipher E^{j,i} of the block, for j >= 0.
+    fn e(&self, j: usize, i: usize, block: &Bytes) -> Bytes {
+        let mut block = *block;
+        xor_into(&mut block, &self.offset(j, i));
+        self.aes4(&block)
+    }
+
+    /// Returns the tweakable block cipher E^{-1,i} of the block.
+    fn e_prf(&self, i: usize, block: &Bytes) -> Bytes {
+        let mut block = *block;
+        xor_into(&mut block, &multiply(&self.l, i));
+        [
+            self.i, self.j, self.l, self.i, self.j, self.l, self.i, self.j, self.l, self.i,
+        ]
+        .iter()
+        .fold(block, |block, key| round(&block, key))
+    }
+
+    /// Returns the offset of E^{j,i}, which is j * J xor 2^ceil(i / 8) * I xor (i mod 8) * L.
+    fn offset(&self, j: usize, i: usize) -> Bytes {
+        let mut offset = multiply(&self.j, j);
+        let mut i_doubled = self.i;
+        (0..i.div_ceil(8)).for_each(|_| i_doubled = double(&i_doubled));
+        xor_into(&mut offset, &i_doubled);
+        xor_into(&mut offset, &multiply(&self.l, i % 8));
+        offset
+    }
+
+    /// Returns the four AES rounds of the block with the round keys J, I, L, and zero.
+    fn aes4(&self, block: &Bytes) -> Bytes {
+        [self.j, self.i, self.l, [0u8; 16]]
+            .iter()
+            .fold(*block, |block, key| round(&block, key))
+    }
+}
+
+/// Returns the AES round of the block with the round key.
+fn round(block: &Bytes, key: &Bytes) -> Bytes {
+    let mut output = Block::from(*block);
+    cipher_round(&mut output, &Block::from(*key));
+    output.into()
+}
+
+/// Returns the doubling of the block in GF(2^128).
+fn double(block: &Bytes) -> Bytes {
+    let mut output = [0u8; 16];
+    (0..16).for_each(|index| {
+        let carry = match index {
+            15 => 0,
+            _ => block[index + 1] >> 7,
+        };
+        output[index] = block[index] << 1 | carry;
+    });
+    if block[0] & 0x80 != 0 {
+        output[15] ^= 0x87;
+    }
+    output
+}
+
+/// Returns the product of the block and a small integer in GF(2^128).
+fn multiply(block: &Bytes, mut factor: usize) -> Bytes {
+    let mut output = [0u8; 16];
+    let mut power = *block;
+    while factor > 0 {
+        if factor & 1 == 1 {
+            xor_into(&mut output, &power);
+        }
+        power = double(&power);
+        factor >>= 1;
+    }
+    output
+}
+
+/// Returns the block shifted left by 4 bits.
+fn shift_left_4(block: &Bytes) -> Bytes {
+    let mut output = [0u8; 16];
+    (0..16).for_each(|index| {
+        let carry = match index {
+            15 => 0,
+            _ => block[index + 1] >> 4,
+        };
+        output[index] = block[index] << 4 | carry;
+    });
+    output
+}
+
+/// Returns the block shifted right by 4 bits.
+fn shift_right_4(block: &Bytes) -> Bytes {
+    let mut output = [0u8; 16];
+    (0..16).for_each(|index| {
+        let carry = match index {
+            0 => 0,
+            _ => block[index - 1] << 4,
+        };
+        output[index] = block[index] >> 4 | carry;
+    });
+    output
+}
+
+/// Returns the bytes of less than 16 bytes padded to a block with 10*.
+fn pad(bytes: &[u8]) -> Bytes {
+    let mut block = [0u8; 16];
+    block[..bytes.len()].copy_from_slice(bytes);
+    block[bytes.len()] = 0x80;
+    block
+}
+
+/// Returns the block of the given 16 bytes.
+fn to_block(bytes: &[u8]) -> Bytes {
+    let mut block = [0u8; 16];
+    block.copy_from_slice(bytes);
+    block
+}
+
+/// XORs the given bytes into the target bytes.
+fn xor_into(target: &mut [u8], bytes: &[u8]) {
+    target.iter_mut().zip(bytes).for_each(|(target, byte)| *target ^= byte);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test vectors from the AEZ v5 reference implementation test suite
+    // (key, nonce, data, tau, message, ciphertext)
+    type Vector = (
+        &'static str,
+        &'static str,
+        &'static [&'static str],
+        usize,
+        &'static str,
+        &'static str,
+    );
+
+    const VECTORS: [Vector; 3] = [
+        (
+            "bca303d3e03bc59a7bfea4b82594ffb8aaada3587695d3511701ca682d697fcf6a31aadce27bcf5af3c0116f9c6e0074",
+            "23e61b1c45414be99cff481871b7bb02",
+            &["e0b6dcb20178e0c00a3e", "", "c4e211e8f4f43f2f25e4a05add78b7"],
+            16,
+            "",
+            "e78dfde6449ae4016a19cf4b25289b55",
+        ),
+        (
+            "772d745f91663b6b599a44f0b7f918217f41cebe50f5ac37d189d385ab3c203dfce91c6857bc3a19bc35ba4d99871c03",
+            "27d4c48213aad80b2f81d09221bbd50e",
+            &["b182570ae089aaeb8c2f", "", "c12e5d5fea70bc0d9b9d4bc3c2043f"],
+            16,
+            "8e3de4dde317d9",
+            "a3e4286cfb4ddd4f973d5b93e15d7fba2f6c690daecc09",
+        ),
+        (
+            "f98b2669a0e53bf20774a31c2486f1954db58cbdf6346cc0fedf2f7351d2815e082b168bcc64e609431fbb0c17711a27",
+            "310382a1adc4a0364156ef4d64dfa598",
+            &["f9adf36e976c3222b5de", "", "cd30cc9b51e3831fe90549e2efd159"],
+            16,
+            "702f82692eb72c09e9f9385246665421a8011a8fb1a88c82144e46798d478771d0",
+            "357e9c2c6b75a34a26d530d3a07b322ded8178faa4a2dc5f0a5dcf37622a420f18e48b86e3115f21b6d5628091dc40e7e1",
+        ),
+    ];
+
+    #[test]
+    fn encrypt() {
+        VECTORS.iter().for_each(|(key, nonce, data, tau, message, ciphertext)| {
+            let data = data.iter().map(|d| hex::decode(d).unwrap()).collect::<Vec<Vec<u8>>>();
+            let data = data.iter().map(|d| &d[..]).collect::<Vec<&[u8]>>();
+            let aez = Aez::new(&hex::decode(key).unwrap());
+            let result = aez.encrypt(
+                &hex::decode(nonce).unwrap(),
+                &data,
+                *tau,
+                &hex::decode(message).unwrap(),
+            );
+            assert_eq!(*ciphertext, hex::encode(result));
+        });
+    }
+
+    #[test]
+    fn decrypt() {
+        VECTORS.iter().for_each(|(key, nonce, data, tau, message, ciphertext)| {
+            let data = data.iter().map(|d| hex::decode(d).unwrap()).collect::<Vec<Vec<u8>>>();
+            let data = data.iter().map(|d| &d[..]).collect::<Vec<&[u8]>>();
+            let aez = Aez::new(&hex::decode(key).unwrap());
+            let mut ciphertext = hex::decode(ciphertext).unwrap();
+            let nonce = hex::decode(nonce).unwrap();
+            assert_eq!(
+                *message,
+                hex::encode(aez.decrypt(&nonce, &data, *tau, &ciphertext).unwrap())
+            );
+
+            ciphertext[0] ^= 1;
+            assert_eq!(None, aez.decrypt(&nonce, &data, *tau, &ciphertext));
+        });
+    }
+}
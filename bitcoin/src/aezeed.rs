@@ -0,0 +1,392 @@
+use crate::aez::Aez;
+use crate::extended_private_key::BitcoinExtendedPrivateKey;
+use crate::format::BitcoinFormat;
+use crate::network::BitcoinNetwork;
+use crate::wordlist::{BitcoinWordlist, English};
+use wagyu_model::{
+    no_std::*, CrateError, ExposeSecret, ExtendedPrivateKey, ExtendedPrivateKeyError, Redacted, WordlistError,
+};
+
+use core::{fmt, str::FromStr};
+use crc::crc32;
+use rand::Rng;
+use zeroize::Zeroize;
+
+/// The version of the aezeed cipher seed format
+pub const AEZEED_VERSION: u8 = 0;
+/// The passphrase used to encipher an aezeed without a user-specified passphrase
+const DEFAULT_PASSPHRASE: &str = "aezeed";
+/// The base 2 logarithm of the scrypt cost parameter N
+const SCRYPT_LOG_N: u8 = 15;
+/// The scrypt block size parameter r
+const SCRYPT_R: u32 = 8;
+/// The scrypt parallelization parameter p
+const SCRYPT_P: u32 = 1;
+/// The length of the enciphering key in bytes
+const KEY_LENGTH: usize = 32;
+/// The length of the salt in bytes
+const SALT_LENGTH: usize = 5;
+/// The length of the entropy in bytes
+const ENTROPY_LENGTH: usize = 16;
+/// The length of the AEZ authenticator in bytes
+const CIPHERTEXT_EXPANSION: usize = 4;
+/// The length of the enciphered seed in bytes
+const ENCIPHERED_LENGTH: usize = 33;
+/// The offset of the salt in the enciphered seed
+const SALT_OFFSET: usize = ENCIPHERED_LENGTH - CHECKSUM_LENGTH - SALT_LENGTH;
+/// The length of the checksum in bytes
+const CHECKSUM_LENGTH: usize = 4;
+/// The offset of the checksum in the enciphered seed
+const CHECKSUM_OFFSET: usize = ENCIPHERED_LENGTH - CHECKSUM_LENGTH;
+/// The number of words of an aezeed mnemonic
+const WORD_COUNT: usize = 24;
+/// The timestamp of the Bitcoin genesis block, from which birthdays are counted in days
+const GENESIS_TIMESTAMP: u64 = 1231006505;
+/// The number of seconds in a day
+const SECONDS_PER_DAY: u64 = 86400;
+
+/// Represents an aezeed cipher seed, the seed format of lnd.
+///
+/// The entropy of the seed is the BIP-32 seed of the wallet. It is enciphered with
+/// its version and birthday into a 24-word mnemonic under an optional passphrase.
+#[derive(Clone, PartialEq, Eq)]
+pub struct AezeedCipherSeed {
+    /// The internal version of the seed, which determines the derivation scheme of the wallet
+    pub internal_version: u8,
+    /// The number of days since the Bitcoin genesis block at which the wallet was created
+    pub birthday: u16,
+    /// The entropy of the seed
+    entropy: [u8; ENTROPY_LENGTH],
+    /// The random salt of the enciphering key
+    salt: [u8; SALT_LENGTH],
+}
+
+impl AezeedCipherSeed {
+    /// Returns a new cipher seed with random entropy and the given birthday.
+    pub fn new<R: Rng>(rng: &mut R, birthday: u16) -> Self {
+        Self::from_entropy(rng.gen(), birthday, rng.gen())
+    }
+
+    /// Returns the cipher seed of the given entropy, birthday, and salt.
+    pub fn from_entropy(entropy: [u8; ENTROPY_LENGTH], birthday: u16, salt: [u8; SALT_LENGTH]) -> Self {
+        Self {
+            internal_version: 0,
+            birthday,
+            entropy,
+            salt,
+        }
+    }
+
+    /// Returns the birthday, in days since the Bitcoin genesis block, of the given Unix timestamp.
+    pub fn birthday_from_timestamp(timestamp: u64) -> u16 {
+        let days = timestamp.saturating_sub(GENESIS_TIMESTAMP) / SECONDS_PER_DAY;
+        days.min(u16::MAX as u64) as u16
+    }
+
+    /// Returns the cipher seed deciphered from the given mnemonic and passphrase.
+    /// If the passphrase is empty, the default aezeed passphrase is used.
+    pub fn from_phrase(phrase: &str, passphrase: &str) -> Result<Self, AezeedError> {
+        let words = phrase.split_whitespace().collect::<Vec<&str>>();
+        if words.len() != WORD_COUNT {
+            return Err(AezeedError::InvalidWordCount(words.len()));
+        }
+
+        let mut enciphered = [0u8; ENCIPHERED_LENGTH];
+        for (index, word) in words.iter().enumerate() {
+            let value = English::get_index(word)?;
+            (0..11).for_each(|bit| {
+                if (value >> (10 - bit)) & 1 == 1 {
+                    let position = index * 11 + bit;
+                    enciphered[position / 8] |= 0x80 >> (position % 8);
+                }
+            });
+        }
+
+        let result = Self::decipher(&enciphered, passphrase);
+        enciphered.zeroize();
+        result
+    }
+
+    /// Returns the mnemonic of the cipher seed enciphered with the given passphrase.
+    /// If the passphrase is empty, the default aezeed passphrase is used.
+    pub fn to_phrase(&self, passphrase: &str) -> Result<String, AezeedError> {
+        let mut enciphered = self.encipher(passphrase)?;
+        let words = (0..WORD_COUNT)
+            .map(|index| {
+                let value = (0..11).fold(0usize, |value, bit| {
+                    let position = index * 11 + bit;
+                    value << 1 | ((enciphered[position / 8] >> (7 - position % 8)) & 1) as usize
+                });
+                English::get(value)
+            })
+            .collect::<Result<Vec<String>, WordlistError>>();
+        enciphered.zeroize();
+        Ok(words?.join(" "))
+    }
+
+    /// Returns the entropy of the cipher seed.
+    pub fn entropy(&self) -> &[u8] {
+        &self.entropy
+    }
+
+    /// Returns the salt of the cipher seed.
+    pub fn salt(&self) -> [u8; SALT_LENGTH] {
+        self.salt
+    }
+
+    /// Returns the master extended private key of the wallet of the cipher seed.
+    pub fn to_extended_private_key<N: BitcoinNetwork>(
+        &self,
+        format: &BitcoinFormat,
+    ) -> Result<BitcoinExtendedPrivateKey<N>, ExtendedPrivateKeyError> {
+        BitcoinExtendedPrivateKey::new_master(&self.entropy, format)
+    }
+
+    /// Returns the enciphered seed, consisting of the version, ciphertext, salt, and checksum.
+    fn encipher(&self, passphrase: &str) -> Result<[u8; ENCIPHERED_LENGTH], AezeedError> {
+        let mut plaintext = [0u8; 1 + 2 + ENTROPY_LENGTH];
+        plaintext[0] = self.internal_version;
+        plaintext[1..3].copy_from_slice(&self.birthday.to_be_bytes());
+        plaintext[3..].copy_from_slice(&self.entropy);
+
+        let mut key = derive_key(passphrase, &self.salt)?;
+        let ciphertext = Aez::new(&key).encrypt(&[], &[&to_data(&self.salt)], CIPHERTEXT_EXPANSION, &plaintext);
+        key.zeroize();
+        plaintext.zeroize();
+
+        let mut enciphered = [0u8; ENCIPHERED_LENGTH];
+        enciphered[0] = AEZEED_VERSION;
+        enciphered[1..SALT_OFFSET].copy_from_slice(&ciphertext);
+        enciphered[SALT_OFFSET..CHECKSUM_OFFSET].copy_from_slice(&self.salt);
+        let checksum = crc32::checksum_castagnoli(&enciphered[..CHECKSUM_OFFSET]);
+        enciphered[CHECKSUM_OFFSET..].copy_from_slice(&checksum.to_be_bytes());
+        Ok(enciphered)
+    }
+
+    /// Returns the cipher seed of the enciphered seed.
+    fn decipher(enciphered: &[u8; ENCIPHERED_LENGTH], passphrase: &str) -> Result<Self, AezeedError> {
+        if enciphered[0] != AEZEED_VERSION {
+            return Err(AezeedError::InvalidVersion(enciphered[0]));
+        }
+
+        let checksum = crc32::checksum_castagnoli(&enciphered[..CHECKSUM_OFFSET]);
+        if enciphered[CHECKSUM_OFFSET..] != checksum.to_be_bytes() {
+            return Err(AezeedError::InvalidChecksum);
+        }
+
+        let mut salt = [0u8; SALT_LENGTH];
+        salt.copy_from_slice(&enciphered[SALT_OFFSET..CHECKSUM_OFFSET]);
+
+        let mut key = derive_key(passphrase, &salt)?;
+        let plaintext = Aez::new(&key).decrypt(
+            &[],
+            &[&to_data(&salt)],
+            CIPHERTEXT_EXPANSION,
+            &enciphered[1..SALT_OFFSET],
+        );
+        key.zeroize();
+        let mut plaintext = plaintext.ok_or(AezeedError::InvalidPassphrase)?;
+
+        let mut entropy = [0u8; ENTROPY_LENGTH];
+        entropy.copy_from_slice(&plaintext[3..]);
+        let seed = Self {
+            internal_version: plaintext[0],
+            birthday: u16::from_be_bytes([plaintext[1], plaintext[2]]),
+            entropy,
+            salt,
+        };
+        plaintext.zeroize();
+        entropy.zeroize();
+        Ok(seed)
+    }
+}
+
+impl fmt::Debug for AezeedCipherSeed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AezeedCipherSeed")
+            .field("internal_version", &self.internal_version)
+            .field("birthday", &self.birthday)
+            .field("entropy", &Redacted)
+            .finish()
+    }
+}
+
+impl ExposeSecret for AezeedCipherSeed {
+    fn fmt_secret(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AezeedCipherSeed")
+            .field("internal_version", &self.internal_version)
+            .field("birthday", &self.birthday)
+            .field("entropy", &self.entropy)
+            .field("salt", &self.salt)
+            .finish()
+    }
+}
+
+impl FromStr for AezeedCipherSeed {
+    type Err = AezeedError;
+
+    /// Returns the cipher seed deciphered from the given mnemonic with the default passphrase.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_phrase(s, "")
+    }
+}
+
+impl Zeroize for AezeedCipherSeed {
+    fn zeroize(&mut self) {
+        self.entropy.zeroize();
+    }
+}
+
+impl Drop for AezeedCipherSeed {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+/// Returns the enciphering key derived with scrypt from the passphrase and salt.
+fn derive_key(passphrase: &str, salt: &[u8; SALT_LENGTH]) -> Result<[u8; KEY_LENGTH], AezeedError> {
+    let passphrase = match passphrase.is_empty() {
+        true => DEFAULT_PASSPHRASE,
+        false => passphrase,
+    };
+
+    let params = scrypt::ScryptParams::new(SCRYPT_LOG_N, SCRYPT_R, SCRYPT_P)
+        .map_err(|error| CrateError::Other("scrypt", format!("{:?}", error)))?;
+    let mut key = [0u8; KEY_LENGTH];
+    scrypt::scrypt(passphrase.as_bytes(), salt, &params, &mut key)
+        .map_err(|error| CrateError::Other("scrypt", format!("{:?}", error)))?;
+    Ok(key)
+}
+
+/// Returns the associated data of the ciphertext, consisting of the version and salt.
+fn to_data(salt: &[u8; SALT_LENGTH]) -> [u8; 1 + SALT_LENGTH] {
+    let mut data = [0u8; 1 + SALT_LENGTH];
+    data[0] = AEZEED_VERSION;
+    data[1..].copy_from_slice(salt);
+    data
+}
+
+#[derive(Debug, Fail)]
+pub enum AezeedError {
+    #[fail(display = "{}", _0)]
+    Crate(#[cause] CrateError),
+
+    #[fail(display = "invalid aezeed checksum")]
+    InvalidChecksum,
+
+    #[fail(display = "invalid aezeed passphrase")]
+    InvalidPassphrase,
+
+    #[fail(display = "invalid aezeed version: {}", _0)]
+    InvalidVersion(u8),
+
+    #[fail(display = "invalid aezeed word count: {}", _0)]
+    InvalidWordCount(usize),
+
+    #[fail(display = "{}", _0)]
+    WordlistError(#[cause] WordlistError),
+}
+
+impl From<CrateError> for AezeedError {
+    fn from(error: CrateError) -> Self {
+        AezeedError::Crate(error)
+    }
+}
+
+impl From<WordlistError> for AezeedError {
+    fn from(error: WordlistError) -> Self {
+        AezeedError::WordlistError(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::Mainnet;
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    const ENTROPY: [u8; ENTROPY_LENGTH] = [
+        0x81, 0xb6, 0x37, 0xd8, 0x63, 0x59, 0xe6, 0x96, 0x0d, 0xe7, 0x95, 0xe4, 0x1e, 0x0b, 0x4c, 0xfd,
+    ];
+    const SALT: [u8; SALT_LENGTH] = *b"salt1";
+
+    // (birthday, passphrase, mnemonic)
+    const VECTORS: [(u16, &str, &str); 2] = [
+        (
+            0,
+            "",
+            "above judge emerge veteran reform crunch system all snap please shoulder vault hurt city quarter cover enlist swear success suggest drink wagon enrich body",
+        ),
+        (
+            3365,
+            "!very_safe_55345_password*",
+            "absorb century submit father path glove gloom super divert garden ice mirror wisdom grass dice kit ugly castle success suggest drink monster congress flight",
+        ),
+    ];
+
+    #[test]
+    fn to_phrase() {
+        VECTORS.iter().for_each(|(birthday, passphrase, phrase)| {
+            let seed = AezeedCipherSeed::from_entropy(ENTROPY, *birthday, SALT);
+            assert_eq!(*phrase, seed.to_phrase(passphrase).unwrap());
+        });
+    }
+
+    #[test]
+    fn from_phrase() {
+        VECTORS.iter().for_each(|(birthday, passphrase, phrase)| {
+            let seed = AezeedCipherSeed::from_phrase(phrase, passphrase).unwrap();
+            assert_eq!(0, seed.internal_version);
+            assert_eq!(*birthday, seed.birthday);
+            assert_eq!(&ENTROPY[..], seed.entropy());
+            assert_eq!(SALT, seed.salt());
+        });
+    }
+
+    #[test]
+    fn new() {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        let seed = AezeedCipherSeed::new(rng, 3365);
+        let phrase = seed.to_phrase("passphrase").unwrap();
+        assert_eq!(seed, AezeedCipherSeed::from_phrase(&phrase, "passphrase").unwrap());
+        assert!(seed.to_extended_private_key::<Mainnet>(&BitcoinFormat::P2PKH).is_ok());
+    }
+
+    #[test]
+    fn birthday_from_timestamp() {
+        assert_eq!(0, AezeedCipherSeed::birthday_from_timestamp(GENESIS_TIMESTAMP));
+        assert_eq!(3365, AezeedCipherSeed::birthday_from_timestamp(1521799345));
+        assert_eq!(0, AezeedCipherSeed::birthday_from_timestamp(0));
+    }
+
+    #[test]
+    fn invalid() {
+        let (_, passphrase, phrase) = VECTORS[1];
+        assert!(matches!(
+            AezeedCipherSeed::from_phrase(phrase, "wrong passphrase"),
+            Err(AezeedError::InvalidPassphrase)
+        ));
+
+        let mut words = phrase.split(' ').collect::<Vec<&str>>();
+        words.swap(5, 6);
+        assert!(matches!(
+            AezeedCipherSeed::from_phrase(&words.join(" "), passphrase),
+            Err(AezeedError::InvalidChecksum)
+        ));
+
+        assert!(matches!(
+            AezeedCipherSeed::from_phrase(&words[1..].join(" "), passphrase),
+            Err(AezeedError::InvalidWordCount(23))
+        ));
+    }
+
+    #[test]
+    fn debug() {
+        let seed = AezeedCipherSeed::from_entropy(ENTROPY, 3365, SALT);
+        assert_eq!(
+            "AezeedCipherSeed { internal_version: 0, birthday: 3365, entropy: [REDACTED] }",
+            format!("{:?}", seed)
+        );
+    }
+}
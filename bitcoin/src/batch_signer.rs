@@ -0,0 +1,284 @@
+//!
+//! Batch Signer
+//!
+//! This module signs many independent transactions, such as a batch of exchange withdrawals, with a
+//! set of private keys derived once and reused for every transaction. The transactions are signed in
+//! parallel if the standard library is available, and returned in the order they were given.
+//!
+
+use crate::address::BitcoinAddress;
+use crate::derivation_path::BitcoinDerivationPath;
+use crate::extended_private_key::BitcoinExtendedPrivateKey;
+use crate::format::BitcoinFormat;
+use crate::network::BitcoinNetwork;
+use crate::private_key::BitcoinPrivateKey;
+use crate::transaction::{create_script_pub_key, BitcoinTransaction};
+use wagyu_model::no_std::*;
+use wagyu_model::{ExtendedPrivateKey, PrivateKey, Transaction, TransactionError};
+
+#[cfg(feature = "std")]
+use rayon::prelude::*;
+
+/// The address formats of a single key, whose scriptPubKeys identify the inputs it signs
+const KEY_FORMATS: [BitcoinFormat; 4] = [
+    BitcoinFormat::P2PKH,
+    BitcoinFormat::P2SH_P2WPKH,
+    BitcoinFormat::Bech32,
+    BitcoinFormat::P2TR,
+];
+
+/// Represents a set of private keys signing a batch of independent transactions
+#[derive(Debug, Clone)]
+pub struct BitcoinBatchSigner<N: BitcoinNetwork> {
+    /// The private keys, in the order they sign each transaction
+    private_keys: Vec<BitcoinPrivateKey<N>>,
+    /// The scriptPubKeys of the addresses of the private keys and the index of their key, sorted by scriptPubKey
+    script_pub_keys: Vec<(Vec<u8>, usize)>,
+}
+
+impl<N: BitcoinNetwork> BitcoinBatchSigner<N> {
+    /// Returns a batch signer of the given private keys.
+    pub fn new(private_keys: &[BitcoinPrivateKey<N>]) -> Result<Self, TransactionError> {
+        #[cfg(feature = "std")]
+        let keys = private_keys.par_iter();
+        #[cfg(not(feature = "std"))]
+        let keys = private_keys.iter();
+
+        let script_pub_keys: Vec<Vec<Vec<u8>>> = keys
+            .map(|private_key| {
+                // Uncompressed keys have no SegWit or Taproot addresses
+                KEY_FORMATS
+                    .iter()
+                    .filter_map(|format| private_key.to_address(format).ok())
+                    .map(|address| create_script_pub_key(&address))
+                    .collect()
+            })
+            .collect::<Vec<Result<_, _>>>()
+            .into_iter()
+            .collect::<Result<_, _>>()?;
+
+        let mut script_pub_keys: Vec<(Vec<u8>, usize)> = script_pub_keys
+            .into_iter()
+            .enumerate()
+            .flat_map(|(index, script_pub_keys)| script_pub_keys.into_iter().map(move |script| (script, index)))
+            .collect();
+        script_pub_keys.sort();
+
+        Ok(Self {
+            private_keys: private_keys.to_vec(),
+            script_pub_keys,
+        })
+    }
+
+    /// Returns a batch signer of the private keys derived from the master extended private key
+    /// at the given derivation paths, each derived once for the whole batch.
+    pub fn from_extended_private_key(
+        master_extended_private_key: &BitcoinExtendedPrivateKey<N>,
+        paths: &[BitcoinDerivationPath<N>],
+    ) -> Result<Self, TransactionError> {
+        #[cfg(feature = "std")]
+        let paths = paths.par_iter();
+        #[cfg(not(feature = "std"))]
+        let paths = paths.iter();
+
+        let private_keys = paths
+            .map(|path| Ok(master_extended_private_key.derive(path)?.to_private_key()))
+            .collect::<Vec<Result<_, TransactionError>>>()
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::new(&private_keys)
+    }
+
+    /// Returns the private keys of the batch signer.
+    pub fn private_keys(&self) -> &[BitcoinPrivateKey<N>] {
+        &self.private_keys
+    }
+
+    /// Returns the transactions signed by every private key controlling their inputs, in the order
+    /// of the given transactions. If any transaction fails to sign, returns the error of the first.
+    pub fn sign(&self, transactions: &[BitcoinTransaction<N>]) -> Result<Vec<BitcoinTransaction<N>>, TransactionError> {
+        #[cfg(feature = "std")]
+        let transactions = transactions.par_iter();
+        #[cfg(not(feature = "std"))]
+        let transactions = transactions.iter();
+
+        // The results are collected before their errors, so the first error is returned on every run
+        transactions
+            .map(|transaction| self.sign_transaction(transaction))
+            .collect::<Vec<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// Returns the transaction signed by every private key controlling its inputs.
+    fn sign_transaction(&self, transaction: &BitcoinTransaction<N>) -> Result<BitcoinTransaction<N>, TransactionError> {
+        let mut signers = vec![false; self.private_keys.len()];
+        for input in &transaction.parameters().inputs {
+            let address = match &input.outpoint.address {
+                Some(address) => address,
+                None => {
+                    // P2PK and bare multisig inputs may be signed by any key
+                    signers.iter_mut().for_each(|signer| *signer = true);
+                    continue;
+                }
+            };
+
+            match self.signer(address)? {
+                Some(index) => signers[index] = true,
+                // P2WSH inputs and Taproot inputs spent by a script may be signed by any key
+                None if [BitcoinFormat::P2WSH, BitcoinFormat::P2TR].contains(&address.format()) => {
+                    signers.iter_mut().for_each(|signer| *signer = true)
+                }
+                None => (),
+            }
+        }
+
+        let mut transaction = transaction.clone();
+        for (private_key, _) in self.private_keys.iter().zip(signers).filter(|(_, signer)| *signer) {
+            transaction = transaction.sign(private_key)?;
+        }
+        Ok(transaction)
+    }
+
+    /// Returns the index of the private key of the given address, if any.
+    fn signer(&self, address: &BitcoinAddress<N>) -> Result<Option<usize>, TransactionError> {
+        let script_pub_key = create_script_pub_key(address)?;
+        Ok(self
+            .script_pub_keys
+            .binary_search_by(|(script, _)| script.as_slice().cmp(&script_pub_key))
+            .ok()
+            .map(|position| self.script_pub_keys[position].1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::amount::BitcoinAmount;
+    use crate::network::*;
+    use crate::transaction::{BitcoinTransactionInput, BitcoinTransactionOutput, BitcoinTransactionParameters};
+    use crate::SignatureHash;
+
+    use core::str::FromStr;
+
+    type N = Mainnet;
+
+    const PRIVATE_KEY: &str = "KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73sVHnoWn";
+    const MASTER_EXTENDED_PRIVATE_KEY: &str = "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi";
+    const PATH: &str = "m/0'/1";
+    const P2WSH_ADDRESS: &str = "bc1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3qccfmv3";
+    const DESTINATION: &str = "1NoZQSmjYHUZMbqLerwmT4xfe8A6mAo8TT";
+    const TRANSACTION_ID: &str = "61d520ccb74288c96bc1a2b20ea1c0d5a704776dd0164a396efec3ea7040349d";
+
+    // Returns an unsigned transaction spending the given addresses, at consecutive outputs of the same transaction
+    fn transaction(addresses: &[BitcoinAddress<N>], amount: i64) -> BitcoinTransaction<N> {
+        let inputs = addresses
+            .iter()
+            .enumerate()
+            .map(|(vout, address)| {
+                BitcoinTransactionInput::<N>::new(
+                    hex::decode(TRANSACTION_ID).unwrap(),
+                    vout as u32,
+                    Some(address.clone()),
+                    Some(BitcoinAmount(100_000)),
+                    None,
+                    None,
+                    None,
+                    SignatureHash::SIGHASH_ALL,
+                )
+                .unwrap()
+            })
+            .collect();
+        let parameters = BitcoinTransactionParameters::<N> {
+            version: 2,
+            inputs,
+            outputs: vec![BitcoinTransactionOutput::new(
+                &BitcoinAddress::<N>::from_str(DESTINATION).unwrap(),
+                BitcoinAmount(amount),
+            )
+            .unwrap()],
+            lock_time: 0,
+            segwit_flag: false,
+        };
+        BitcoinTransaction::<N>::new(&parameters).unwrap()
+    }
+
+    fn private_keys() -> Vec<BitcoinPrivateKey<N>> {
+        let master = BitcoinExtendedPrivateKey::<N>::from_str(MASTER_EXTENDED_PRIVATE_KEY).unwrap();
+        vec![
+            BitcoinPrivateKey::<N>::from_str(PRIVATE_KEY).unwrap(),
+            master
+                .derive(&BitcoinDerivationPath::from_str(PATH).unwrap())
+                .unwrap()
+                .to_private_key(),
+        ]
+    }
+
+    // Returns a batch of withdrawals spending the P2PKH, Bech32, and P2TR addresses of the keys
+    fn withdrawals() -> Vec<BitcoinTransaction<N>> {
+        let private_keys = private_keys();
+        let formats = [BitcoinFormat::P2PKH, BitcoinFormat::Bech32, BitcoinFormat::P2TR];
+        (0..16)
+            .map(|i| {
+                let format = &formats[i % formats.len()];
+                let mut addresses = vec![private_keys[i % 2].to_address(format).unwrap()];
+                if i % 3 == 0 {
+                    addresses.push(private_keys[(i + 1) % 2].to_address(&BitcoinFormat::Bech32).unwrap());
+                }
+                transaction(&addresses, 50_000 + i as i64)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sign() {
+        let private_keys = private_keys();
+        let withdrawals = withdrawals();
+        let signed = BitcoinBatchSigner::new(&private_keys)
+            .unwrap()
+            .sign(&withdrawals)
+            .unwrap();
+
+        assert_eq!(withdrawals.len(), signed.len());
+        for (withdrawal, signed) in withdrawals.iter().zip(&signed) {
+            let expected = private_keys
+                .iter()
+                .fold(withdrawal.clone(), |transaction, private_key| {
+                    transaction.sign(private_key).unwrap()
+                });
+            assert!(signed.parameters().inputs.iter().all(|input| input.is_signed));
+            assert_eq!(
+                expected.to_transaction_bytes().unwrap(),
+                signed.to_transaction_bytes().unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn from_extended_private_key() {
+        let master = BitcoinExtendedPrivateKey::<N>::from_str(MASTER_EXTENDED_PRIVATE_KEY).unwrap();
+        let paths = vec![BitcoinDerivationPath::from_str(PATH).unwrap()];
+        let signer = BitcoinBatchSigner::from_extended_private_key(&master, &paths).unwrap();
+        assert_eq!(&private_keys()[1..], signer.private_keys());
+
+        // The inputs of the other key are left unsigned
+        let withdrawals = withdrawals();
+        let signed = signer.sign(&withdrawals).unwrap();
+        for (i, signed) in signed.iter().enumerate() {
+            assert_eq!(i % 2 == 1, signed.parameters().inputs[0].is_signed);
+        }
+    }
+
+    #[test]
+    fn invalid_batch() {
+        let signer = BitcoinBatchSigner::new(&private_keys()).unwrap();
+        assert!(signer.sign(&[]).unwrap().is_empty());
+
+        // A P2WSH input without its witness script cannot be signed
+        let mut withdrawals = withdrawals();
+        let mut p2wsh = withdrawals[1].parameters().clone();
+        p2wsh.inputs[0].outpoint.address = Some(BitcoinAddress::<N>::from_str(P2WSH_ADDRESS).unwrap());
+        withdrawals.insert(3, BitcoinTransaction::<N>::new(&p2wsh).unwrap());
+        assert!(signer.sign(&withdrawals).is_err());
+    }
+}
@@ -0,0 +1,180 @@
+//!
+//! Bech32m
+//!
+//! This module contains the Bech32 and Bech32m checksums of witness program addresses.
+//! Witness version 0 is encoded with Bech32, and witness versions 1 and above are encoded
+//! with Bech32m, which replaces the checksum constant of Bech32 to address its mutability weakness.
+//! https://github.com/bitcoin/bips/blob/master/bip-0173.mediawiki
+//! https://github.com/bitcoin/bips/blob/master/bip-0350.mediawiki
+//!
+
+use wagyu_model::no_std::*;
+use wagyu_model::AddressError;
+
+use bech32::u5;
+
+/// The Bech32 character set for encoding
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+/// The Bech32 generator coefficients
+const GENERATOR: [u32; 5] = [0x3b6a_57b2, 0x2650_8e6d, 0x1ea1_19fa, 0x3d42_33dd, 0x2a14_62b3];
+/// The maximum length of a Bech32 string
+const MAX_LENGTH: usize = 90;
+
+/// Represents the checksum variant of a Bech32 string
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Variant {
+    /// Bech32, for witness version 0
+    Bech32,
+    /// Bech32m, for witness versions 1 and above
+    Bech32m,
+}
+
+impl Variant {
+    /// Returns the checksum variant of the given witness version.
+    pub fn from_witness_version(version: u8) -> Self {
+        match version {
+            0 => Variant::Bech32,
+            _ => Variant::Bech32m,
+        }
+    }
+
+    /// Returns the checksum constant of the variant.
+    fn constant(&self) -> u32 {
+        match self {
+            Variant::Bech32 => 1,
+            Variant::Bech32m => 0x2bc8_30a3,
+        }
+    }
+}
+
+/// Returns the string of the human-readable part and data with the checksum of the given variant.
+pub fn encode(hrp: &str, data: &[u5], variant: Variant) -> String {
+    let mut values = expand_hrp(hrp);
+    values.extend(data.iter().map(|value| value.to_u8()));
+    values.extend_from_slice(&[0u8; 6]);
+    let polymod = polymod(&values) ^ variant.constant();
+
+    let mut encoded = format!("{}1", hrp.to_lowercase());
+    encoded.extend(data.iter().map(|value| CHARSET[value.to_u8() as usize] as char));
+    encoded.extend((0..6).map(|i| CHARSET[((polymod >> (5 * (5 - i))) & 31) as usize] as char));
+    encoded
+}
+
+/// Returns the human-readable part, data, and checksum variant of the string, after verifying its checksum.
+pub fn decode(encoded: &str) -> Result<(String, Vec<u5>, Variant), AddressError> {
+    let invalid = || AddressError::InvalidAddress(encoded.to_string());
+
+    if encoded.len() > MAX_LENGTH || (encoded.to_lowercase() != encoded && encoded.to_uppercase() != encoded) {
+        return Err(invalid());
+    }
+    let encoded_lowercase = encoded.to_lowercase();
+
+    let separator = encoded_lowercase.rfind('1').ok_or_else(invalid)?;
+    let (hrp, data) = (&encoded_lowercase[..separator], &encoded_lowercase[separator + 1..]);
+    if hrp.is_empty() || data.len() < 6 || hrp.bytes().any(|c| !(33..=126).contains(&c)) {
+        return Err(invalid());
+    }
+
+    let data = data
+        .bytes()
+        .map(|c| match CHARSET.iter().position(|value| *value == c) {
+            Some(value) => Ok(value as u8),
+            None => Err(invalid()),
+        })
+        .collect::<Result<Vec<u8>, AddressError>>()?;
+
+    let mut values = expand_hrp(hrp);
+    values.extend_from_slice(&data);
+    let variant = match polymod(&values) {
+        checksum if checksum == Variant::Bech32.constant() => Variant::Bech32,
+        checksum if checksum == Variant::Bech32m.constant() => Variant::Bech32m,
+        _ => return Err(invalid()),
+    };
+
+    let data = data[..data.len() - 6]
+        .iter()
+        .map(|value| u5::try_from_u8(*value))
+        .collect::<Result<Vec<u5>, bech32::Error>>()?;
+    Ok((hrp.to_string(), data, variant))
+}
+
+/// Returns the human-readable part expanded for checksum computation.
+fn expand_hrp(hrp: &str) -> Vec<u8> {
+    let hrp = hrp.to_lowercase();
+    let mut expanded: Vec<u8> = hrp.bytes().map(|c| c >> 5).collect();
+    expanded.push(0);
+    expanded.extend(hrp.bytes().map(|c| c & 31));
+    expanded
+}
+
+/// Returns the BCH checksum of the values.
+fn polymod(values: &[u8]) -> u32 {
+    values.iter().fold(1u32, |checksum, value| {
+        let top = checksum >> 25;
+        let checksum = ((checksum & 0x01ff_ffff) << 5) ^ u32::from(*value);
+        GENERATOR
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| (top >> i) & 1 == 1)
+            .fold(checksum, |checksum, (_, generator)| checksum ^ generator)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // https://github.com/bitcoin/bips/blob/master/bip-0173.mediawiki#test-vectors
+    const VALID_BECH32: [&str; 4] = [
+        "A12UEL5L",
+        "a12uel5l",
+        "abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw",
+        "split1checkupstagehandshakeupstreamerranterredcaperred2y9e3w",
+    ];
+
+    // https://github.com/bitcoin/bips/blob/master/bip-0350.mediawiki#test-vectors-for-bech32m
+    const VALID_BECH32M: [&str; 4] = [
+        "A1LQFN3A",
+        "a1lqfn3a",
+        "abcdef1l7aum6echk45nj3s0wdvt2fg8x9yrzpqzd3ryx",
+        "split1checkupstagehandshakeupstreamerranterredcaperredlc445v",
+    ];
+
+    const INVALID: [&str; 6] = [
+        "M1VUXWEZ",      // checksum calculated with uppercase form of HRP
+        "qyrz8wqd2c9m",  // no separator character
+        "1qyrz8wqd2c9m", // empty HRP
+        "in1muywd",      // too short checksum
+        "li1dgmt3",      // too short checksum
+        "A1G7SGD8",      // checksum calculated with uppercase form of HRP
+    ];
+
+    fn test_decode_encode(encoded: &str, expected_variant: Variant) {
+        let (hrp, data, variant) = decode(encoded).unwrap();
+        assert_eq!(expected_variant, variant);
+        assert_eq!(encoded.to_lowercase(), encode(&hrp, &data, variant));
+    }
+
+    #[test]
+    fn decode_encode() {
+        VALID_BECH32
+            .iter()
+            .for_each(|encoded| test_decode_encode(encoded, Variant::Bech32));
+        VALID_BECH32M
+            .iter()
+            .for_each(|encoded| test_decode_encode(encoded, Variant::Bech32m));
+    }
+
+    #[test]
+    fn decode_invalid() {
+        INVALID.iter().for_each(|encoded| assert!(decode(encoded).is_err()));
+        assert!(decode(&format!("a1{}", "q".repeat(MAX_LENGTH))).is_err());
+    }
+
+    #[test]
+    fn from_witness_version() {
+        assert_eq!(Variant::Bech32, Variant::from_witness_version(0));
+        assert_eq!(Variant::Bech32m, Variant::from_witness_version(1));
+        assert_eq!(Variant::Bech32m, Variant::from_witness_version(16));
+    }
+}
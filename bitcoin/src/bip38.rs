@@ -0,0 +1,649 @@
+use crate::address::BitcoinAddress;
+use crate::format::BitcoinFormat;
+use crate::network::BitcoinNetwork;
+use crate::private_key::BitcoinPrivateKey;
+use crate::public_key::BitcoinPublicKey;
+use wagyu_model::{
+    crypto::{checksum, secp256k1_public_key},
+    no_std::*,
+    AddressError, CrateError, PrivateKey, PrivateKeyError,
+};
+
+use aes::cipher::{generic_array::GenericArray, BlockDecrypt, BlockEncrypt, KeyInit};
+use aes::Aes256;
+use base58::{FromBase58, ToBase58};
+use core::{fmt, marker::PhantomData, str::FromStr};
+use rand::Rng;
+use zeroize::Zeroize;
+
+/// The prefix of an encrypted private key without EC multiplication
+const PREFIX_NON_EC_MULTIPLY: [u8; 2] = [0x01, 0x42];
+/// The prefix of an encrypted private key with EC multiplication
+const PREFIX_EC_MULTIPLY: [u8; 2] = [0x01, 0x43];
+/// The magic bytes of an intermediate code, whose last byte is 0x51 if it has a lot and sequence number
+const INTERMEDIATE_CODE_MAGIC: [u8; 7] = [0x2C, 0xE9, 0xB3, 0xE1, 0xFF, 0x39, 0xE2];
+/// The flag bits of the non-EC-multiplied mode, which must always be set in its flag byte
+const FLAG_NON_EC_MULTIPLY: u8 = 0xC0;
+/// The flag bit of a compressed public key
+const FLAG_COMPRESSED: u8 = 0x20;
+/// The flag bit of an owner entropy containing a lot and sequence number
+const FLAG_LOT_SEQUENCE: u8 = 0x04;
+/// The length of an encrypted private key in bytes, excluding its checksum
+const ENCRYPTED_LENGTH: usize = 39;
+/// The length of an intermediate code in bytes, excluding its checksum
+const INTERMEDIATE_CODE_LENGTH: usize = 49;
+/// The maximum lot number of an intermediate code
+pub const MAX_LOT: u32 = 1048575;
+/// The maximum sequence number of an intermediate code
+pub const MAX_SEQUENCE: u32 = 4095;
+
+/// Represents a BIP38 intermediate code, which lets a third party generate encrypted private keys
+/// for the holder of the passphrase without learning the passphrase or the private keys.
+/// https://github.com/bitcoin/bips/blob/master/bip-0038.mediawiki#encryption-when-ec-multiply-mode-is-used
+#[derive(Clone, PartialEq, Eq)]
+pub struct Bip38IntermediateCode {
+    /// The owner salt, followed by the lot and sequence number if `lot_sequence` is set
+    owner_entropy: [u8; 8],
+    /// The compressed public key of the passfactor
+    pass_point: [u8; 33],
+    /// If true, the owner entropy contains a lot and sequence number
+    lot_sequence: bool,
+}
+
+impl Bip38IntermediateCode {
+    /// Returns a new intermediate code for the passphrase with a random owner salt and,
+    /// if specified, a lot and sequence number.
+    pub fn new<R: Rng>(rng: &mut R, passphrase: &str, lot_sequence: Option<(u32, u32)>) -> Result<Self, Bip38Error> {
+        let mut owner_entropy: [u8; 8] = rng.gen();
+        if let Some((lot, sequence)) = lot_sequence {
+            if lot > MAX_LOT || sequence > MAX_SEQUENCE {
+                return Err(Bip38Error::InvalidLotSequence(lot, sequence));
+            }
+            owner_entropy[4..].copy_from_slice(&(lot * 4096 + sequence).to_be_bytes());
+        }
+        Self::from_owner_entropy(passphrase, owner_entropy, lot_sequence.is_some())
+    }
+
+    /// Returns the intermediate code for the passphrase with the given owner entropy, which is
+    /// a 4-byte owner salt and a 4-byte lot and sequence number if `lot_sequence` is set.
+    pub fn from_owner_entropy(
+        passphrase: &str,
+        owner_entropy: [u8; 8],
+        lot_sequence: bool,
+    ) -> Result<Self, Bip38Error> {
+        let mut pass_factor = to_pass_factor(passphrase, &owner_entropy, lot_sequence)?;
+        let secret_key = secp256k1::SecretKey::parse(&pass_factor).map_err(CrateError::from);
+        pass_factor.zeroize();
+
+        Ok(Self {
+            owner_entropy,
+            pass_point: secp256k1_public_key(&secret_key?).serialize_compressed(),
+            lot_sequence,
+        })
+    }
+
+    /// Returns the lot and sequence number, if the intermediate code has them.
+    pub fn lot_sequence(&self) -> Option<(u32, u32)> {
+        match self.lot_sequence {
+            true => {
+                let number = u32::from_be_bytes([
+                    self.owner_entropy[4],
+                    self.owner_entropy[5],
+                    self.owner_entropy[6],
+                    self.owner_entropy[7],
+                ]);
+                Some((number / 4096, number % 4096))
+            }
+            false => None,
+        }
+    }
+}
+
+impl FromStr for Bip38IntermediateCode {
+    type Err = Bip38Error;
+
+    fn from_str(code: &str) -> Result<Self, Self::Err> {
+        let data = from_base58_check(code, INTERMEDIATE_CODE_LENGTH)?;
+        if data[0..7] != INTERMEDIATE_CODE_MAGIC || (data[7] != 0x51 && data[7] != 0x53) {
+            return Err(Bip38Error::InvalidPrefix(data[0..8].to_vec()));
+        }
+
+        let mut owner_entropy = [0u8; 8];
+        owner_entropy.copy_from_slice(&data[8..16]);
+        let mut pass_point = [0u8; 33];
+        pass_point.copy_from_slice(&data[16..49]);
+        secp256k1::PublicKey::parse_compressed(&pass_point).map_err(CrateError::from)?;
+
+        Ok(Self {
+            owner_entropy,
+            pass_point,
+            lot_sequence: data[7] == 0x51,
+        })
+    }
+}
+
+impl fmt::Display for Bip38IntermediateCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut data = [0u8; INTERMEDIATE_CODE_LENGTH];
+        data[0..7].copy_from_slice(&INTERMEDIATE_CODE_MAGIC);
+        data[7] = match self.lot_sequence {
+            true => 0x51,
+            false => 0x53,
+        };
+        data[8..16].copy_from_slice(&self.owner_entropy);
+        data[16..49].copy_from_slice(&self.pass_point);
+        f.write_str(&to_base58_check(&data))
+    }
+}
+
+impl fmt::Debug for Bip38IntermediateCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Bip38IntermediateCode({})", self)
+    }
+}
+
+/// Represents a Bitcoin private key encrypted with a passphrase, as defined in BIP38.
+/// https://github.com/bitcoin/bips/blob/master/bip-0038.mediawiki
+///
+/// The passphrase should be normalized to Unicode NFC, which leaves ASCII passphrases unchanged.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Bip38EncryptedPrivateKey<N: BitcoinNetwork> {
+    /// The serialized encrypted private key, excluding its checksum
+    data: [u8; ENCRYPTED_LENGTH],
+    /// PhantomData
+    _network: PhantomData<N>,
+}
+
+impl<N: BitcoinNetwork> Bip38EncryptedPrivateKey<N> {
+    /// Returns the private key encrypted with the passphrase, without EC multiplication.
+    pub fn encrypt(private_key: &BitcoinPrivateKey<N>, passphrase: &str) -> Result<Self, Bip38Error> {
+        let address_hash = to_address_hash(&private_key.to_address(&BitcoinFormat::P2PKH)?);
+
+        let mut derived = [0u8; 64];
+        scrypt(passphrase.as_bytes(), &address_hash, 14, 8, 8, &mut derived)?;
+        let cipher = Aes256::new(GenericArray::from_slice(&derived[32..]));
+
+        let mut secret_key = private_key.to_secp256k1_secret_key().serialize();
+        let mut data = [0u8; ENCRYPTED_LENGTH];
+        data[0..2].copy_from_slice(&PREFIX_NON_EC_MULTIPLY);
+        data[2] = FLAG_NON_EC_MULTIPLY | compressed_flag(private_key.is_compressed());
+        data[3..7].copy_from_slice(&address_hash);
+        for half in 0..2 {
+            let range = half * 16..(half + 1) * 16;
+            let mut block = GenericArray::clone_from_slice(&xor(&secret_key[range.clone()], &derived[range]));
+            cipher.encrypt_block(&mut block);
+            data[7 + half * 16..23 + half * 16].copy_from_slice(&block);
+            block.zeroize();
+        }
+        secret_key.zeroize();
+        derived.zeroize();
+
+        Ok(Self {
+            data,
+            _network: PhantomData,
+        })
+    }
+
+    /// Returns a new encrypted private key generated from the intermediate code with a random seed,
+    /// along with its P2PKH address. The private key is only known to the holder of the passphrase.
+    pub fn from_intermediate_code<R: Rng>(
+        rng: &mut R,
+        intermediate_code: &Bip38IntermediateCode,
+        compressed: bool,
+    ) -> Result<(Self, BitcoinAddress<N>), Bip38Error> {
+        Self::from_intermediate_code_seed(intermediate_code, rng.gen(), compressed)
+    }
+
+    /// Returns the encrypted private key generated from the intermediate code with the given 24-byte seed,
+    /// along with its P2PKH address.
+    pub fn from_intermediate_code_seed(
+        intermediate_code: &Bip38IntermediateCode,
+        seed: [u8; 24],
+        compressed: bool,
+    ) -> Result<(Self, BitcoinAddress<N>), Bip38Error> {
+        let factor = secp256k1::SecretKey::parse_slice(&checksum(&seed)).map_err(CrateError::from)?;
+        let mut public_key =
+            secp256k1::PublicKey::parse_compressed(&intermediate_code.pass_point).map_err(CrateError::from)?;
+        public_key.tweak_mul_assign(&factor).map_err(CrateError::from)?;
+        let address = BitcoinAddress::<N>::p2pkh(&BitcoinPublicKey::from_secp256k1_public_key(public_key, compressed))?;
+        let address_hash = to_address_hash(&address);
+
+        let mut salt = [0u8; 12];
+        salt[0..4].copy_from_slice(&address_hash);
+        salt[4..].copy_from_slice(&intermediate_code.owner_entropy);
+        let mut derived = [0u8; 64];
+        scrypt(&intermediate_code.pass_point, &salt, 10, 1, 1, &mut derived)?;
+        let cipher = Aes256::new(GenericArray::from_slice(&derived[32..]));
+
+        let mut part1 = GenericArray::clone_from_slice(&xor(&seed[0..16], &derived[0..16]));
+        cipher.encrypt_block(&mut part1);
+        let mut part2 = [0u8; 16];
+        part2[0..8].copy_from_slice(&part1[8..16]);
+        part2[8..16].copy_from_slice(&seed[16..24]);
+        let mut part2 = GenericArray::clone_from_slice(&xor(&part2, &derived[16..32]));
+        cipher.encrypt_block(&mut part2);
+        derived.zeroize();
+
+        let mut data = [0u8; ENCRYPTED_LENGTH];
+        data[0..2].copy_from_slice(&PREFIX_EC_MULTIPLY);
+        data[2] = compressed_flag(compressed)
+            | match intermediate_code.lot_sequence {
+                true => FLAG_LOT_SEQUENCE,
+                false => 0,
+            };
+        data[3..7].copy_from_slice(&address_hash);
+        data[7..15].copy_from_slice(&intermediate_code.owner_entropy);
+        data[15..23].copy_from_slice(&part1[0..8]);
+        data[23..39].copy_from_slice(&part2);
+
+        Ok((
+            Self {
+                data,
+                _network: PhantomData,
+            },
+            address,
+        ))
+    }
+
+    /// Returns the private key decrypted with the passphrase.
+    /// Errors if the passphrase does not match the address hash of the encrypted private key.
+    pub fn decrypt(&self, passphrase: &str) -> Result<BitcoinPrivateKey<N>, Bip38Error> {
+        let mut secret_key = match self.is_ec_multiplied() {
+            true => self.decrypt_ec_multiplied(passphrase)?,
+            false => self.decrypt_non_ec_multiplied(passphrase)?,
+        };
+        let private_key = BitcoinPrivateKey::from_secp256k1_secret_key(
+            &secp256k1::SecretKey::parse(&secret_key).map_err(CrateError::from)?,
+            self.is_compressed(),
+        );
+        secret_key.zeroize();
+
+        match to_address_hash(&private_key.to_address(&BitcoinFormat::P2PKH)?) == self.data[3..7] {
+            true => Ok(private_key),
+            false => Err(Bip38Error::InvalidPassphrase),
+        }
+    }
+
+    /// Returns `true` if the private key was generated from an intermediate code with EC multiplication.
+    pub fn is_ec_multiplied(&self) -> bool {
+        self.data[0..2] == PREFIX_EC_MULTIPLY
+    }
+
+    /// Returns `true` if the private key corresponds to a compressed public key.
+    pub fn is_compressed(&self) -> bool {
+        self.data[2] & FLAG_COMPRESSED != 0
+    }
+
+    /// Returns the secret key decrypted from the two encrypted halves.
+    fn decrypt_non_ec_multiplied(&self, passphrase: &str) -> Result<[u8; 32], Bip38Error> {
+        let mut derived = [0u8; 64];
+        scrypt(passphrase.as_bytes(), &self.data[3..7], 14, 8, 8, &mut derived)?;
+        let cipher = Aes256::new(GenericArray::from_slice(&derived[32..]));
+
+        let mut secret_key = [0u8; 32];
+        for half in 0..2 {
+            let mut block = GenericArray::clone_from_slice(&self.data[7 + half * 16..23 + half * 16]);
+            cipher.decrypt_block(&mut block);
+            let range = half * 16..(half + 1) * 16;
+            secret_key[range.clone()].copy_from_slice(&xor(&block, &derived[range]));
+            block.zeroize();
+        }
+        derived.zeroize();
+        Ok(secret_key)
+    }
+
+    /// Returns the secret key, the product of the passfactor and the factor of the decrypted seed.
+    fn decrypt_ec_multiplied(&self, passphrase: &str) -> Result<[u8; 32], Bip38Error> {
+        let mut owner_entropy = [0u8; 8];
+        owner_entropy.copy_from_slice(&self.data[7..15]);
+        let lot_sequence = self.data[2] & FLAG_LOT_SEQUENCE != 0;
+        let intermediate_code = Bip38IntermediateCode::from_owner_entropy(passphrase, owner_entropy, lot_sequence)?;
+
+        let mut derived = [0u8; 64];
+        scrypt(&intermediate_code.pass_point, &self.data[3..15], 10, 1, 1, &mut derived)?;
+        let cipher = Aes256::new(GenericArray::from_slice(&derived[32..]));
+
+        let mut part2 = GenericArray::clone_from_slice(&self.data[23..39]);
+        cipher.decrypt_block(&mut part2);
+        let part2 = xor(&part2, &derived[16..32]);
+        let mut part1 = [0u8; 16];
+        part1[0..8].copy_from_slice(&self.data[15..23]);
+        part1[8..16].copy_from_slice(&part2[0..8]);
+        let mut part1 = GenericArray::clone_from_slice(&part1);
+        cipher.decrypt_block(&mut part1);
+
+        let mut seed = [0u8; 24];
+        seed[0..16].copy_from_slice(&xor(&part1, &derived[0..16]));
+        seed[16..24].copy_from_slice(&part2[8..16]);
+        let factor = secp256k1::SecretKey::parse_slice(&checksum(&seed)).map_err(CrateError::from);
+        seed.zeroize();
+        derived.zeroize();
+
+        let mut pass_factor = to_pass_factor(passphrase, &owner_entropy, lot_sequence)?;
+        let mut secret_key = secp256k1::SecretKey::parse(&pass_factor).map_err(CrateError::from)?;
+        pass_factor.zeroize();
+        secret_key.tweak_mul_assign(&factor?).map_err(CrateError::from)?;
+        Ok(secret_key.serialize())
+    }
+}
+
+impl<N: BitcoinNetwork> FromStr for Bip38EncryptedPrivateKey<N> {
+    type Err = Bip38Error;
+
+    fn from_str(encrypted: &str) -> Result<Self, Self::Err> {
+        let bytes = from_base58_check(encrypted, ENCRYPTED_LENGTH)?;
+        let valid = match [bytes[0], bytes[1]] {
+            PREFIX_NON_EC_MULTIPLY => bytes[2] & !FLAG_COMPRESSED == FLAG_NON_EC_MULTIPLY,
+            PREFIX_EC_MULTIPLY => bytes[2] & !(FLAG_COMPRESSED | FLAG_LOT_SEQUENCE) == 0,
+            _ => return Err(Bip38Error::InvalidPrefix(bytes[0..2].to_vec())),
+        };
+        if !valid {
+            return Err(Bip38Error::InvalidFlag(bytes[2]));
+        }
+
+        let mut data = [0u8; ENCRYPTED_LENGTH];
+        data.copy_from_slice(&bytes);
+        Ok(Self {
+            data,
+            _network: PhantomData,
+        })
+    }
+}
+
+impl<N: BitcoinNetwork> fmt::Display for Bip38EncryptedPrivateKey<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&to_base58_check(&self.data))
+    }
+}
+
+impl<N: BitcoinNetwork> fmt::Debug for Bip38EncryptedPrivateKey<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Bip38EncryptedPrivateKey({})", self)
+    }
+}
+
+/// Returns the passfactor of the passphrase and owner entropy, from which the pass point is derived.
+fn to_pass_factor(passphrase: &str, owner_entropy: &[u8; 8], lot_sequence: bool) -> Result<[u8; 32], Bip38Error> {
+    let owner_salt = match lot_sequence {
+        true => &owner_entropy[0..4],
+        false => &owner_entropy[..],
+    };
+
+    let mut pre_factor = [0u8; 32];
+    scrypt(passphrase.as_bytes(), owner_salt, 14, 8, 8, &mut pre_factor)?;
+
+    let mut pass_factor = [0u8; 32];
+    match lot_sequence {
+        true => {
+            let mut data = [0u8; 40];
+            data[0..32].copy_from_slice(&pre_factor);
+            data[32..].copy_from_slice(owner_entropy);
+            pass_factor.copy_from_slice(&checksum(&data));
+            data.zeroize();
+        }
+        false => pass_factor.copy_from_slice(&pre_factor),
+    };
+    pre_factor.zeroize();
+    Ok(pass_factor)
+}
+
+/// Returns the first 4 bytes of the double SHA256 of the address, which verifies the passphrase.
+fn to_address_hash<N: BitcoinNetwork>(address: &BitcoinAddress<N>) -> [u8; 4] {
+    let mut address_hash = [0u8; 4];
+    address_hash.copy_from_slice(&checksum(address.to_string().as_bytes())[0..4]);
+    address_hash
+}
+
+/// Returns the flag bit of the public key compression.
+fn compressed_flag(compressed: bool) -> u8 {
+    match compressed {
+        true => FLAG_COMPRESSED,
+        false => 0,
+    }
+}
+
+/// Returns the bytewise XOR of two slices of equal length.
+fn xor(a: &[u8], b: &[u8]) -> Vec<u8> {
+    a.iter().zip(b).map(|(a, b)| a ^ b).collect()
+}
+
+/// Derives the output key with scrypt, given the base 2 logarithm of its cost parameter N.
+fn scrypt(password: &[u8], salt: &[u8], log_n: u8, r: u32, p: u32, output: &mut [u8]) -> Result<(), Bip38Error> {
+    let params =
+        scrypt::ScryptParams::new(log_n, r, p).map_err(|error| CrateError::Other("scrypt", format!("{:?}", error)))?;
+    scrypt::scrypt(password, salt, &params, output)
+        .map_err(|error| CrateError::Other("scrypt", format!("{:?}", error)))?;
+    Ok(())
+}
+
+/// Returns the data of a Base58Check string with the expected length, excluding its checksum.
+fn from_base58_check(string: &str, length: usize) -> Result<Vec<u8>, Bip38Error> {
+    let data = string.from_base58().map_err(|_| Bip38Error::InvalidBase58)?;
+    if data.len() != length + 4 {
+        return Err(Bip38Error::InvalidByteLength(data.len()));
+    }
+    if checksum(&data[..length])[0..4] != data[length..] {
+        return Err(Bip38Error::InvalidChecksum);
+    }
+    Ok(data[..length].to_vec())
+}
+
+/// Returns the Base58Check string of the data.
+fn to_base58_check(data: &[u8]) -> String {
+    let mut bytes = data.to_vec();
+    bytes.extend_from_slice(&checksum(data)[0..4]);
+    bytes.to_base58()
+}
+
+#[derive(Debug, Fail)]
+pub enum Bip38Error {
+    #[fail(display = "{}", _0)]
+    AddressError(#[cause] AddressError),
+
+    #[fail(display = "{}", _0)]
+    Crate(#[cause] CrateError),
+
+    #[fail(display = "invalid base58 encoding")]
+    InvalidBase58,
+
+    #[fail(display = "invalid byte length: {}", _0)]
+    InvalidByteLength(usize),
+
+    #[fail(display = "invalid checksum")]
+    InvalidChecksum,
+
+    #[fail(display = "invalid flag byte: {:#04x}", _0)]
+    InvalidFlag(u8),
+
+    #[fail(display = "invalid lot {} and sequence {}", _0, _1)]
+    InvalidLotSequence(u32, u32),
+
+    #[fail(display = "invalid passphrase")]
+    InvalidPassphrase,
+
+    #[fail(display = "invalid prefix: {:?}", _0)]
+    InvalidPrefix(Vec<u8>),
+
+    #[fail(display = "{}", _0)]
+    PrivateKeyError(#[cause] PrivateKeyError),
+}
+
+impl From<AddressError> for Bip38Error {
+    fn from(error: AddressError) -> Self {
+        Bip38Error::AddressError(error)
+    }
+}
+
+impl From<CrateError> for Bip38Error {
+    fn from(error: CrateError) -> Self {
+        Bip38Error::Crate(error)
+    }
+}
+
+impl From<PrivateKeyError> for Bip38Error {
+    fn from(error: PrivateKeyError) -> Self {
+        Bip38Error::PrivateKeyError(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::*;
+
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    type N = Mainnet;
+
+    // https://github.com/bitcoin/bips/blob/master/bip-0038.mediawiki#test-vectors
+    // (passphrase, encrypted_private_key, private_key)
+    const NON_EC_MULTIPLY: [(&str, &str, &str); 4] = [
+        (
+            "TestingOneTwoThree",
+            "6PRVWUbkzzsbcVac2qwfssoUJAN1Xhrg6bNk8J7Nzm5H7kxEbn2Nh2ZoGg",
+            "5KN7MzqK5wt2TP1fQCYyHBtDrXdJuXbUzm4A9rKAteGu3Qi5CVR",
+        ),
+        (
+            "Satoshi",
+            "6PRNFFkZc2NZ6dJqFfhRoFNMR9Lnyj7dYGrzdgXXVMXcxoKTePPX1dWByq",
+            "5HtasZ6ofTHP6HCwTqTkLDuLQisYPah7aUnSKfC7h4hMUVw2gi5",
+        ),
+        (
+            "TestingOneTwoThree",
+            "6PYNKZ1EAgYgmQfmNVamxyXVWHzK5s6DGhwP4J5o44cvXdoY7sRzhtpUeo",
+            "L44B5gGEpqEDRS9vVPz7QT35jcBG2r3CZwSwQ4fCewXAhAhqGVpP",
+        ),
+        (
+            "Satoshi",
+            "6PYLtMnXvfG3oJde97zRyLYFZCYizPU5T3LwgdYJz1fRhh16bU7u6PPmY7",
+            "KwYgW8gcxj1JWJXhPSu4Fqwzfhp5Yfi42mdYmMa4XqK7NJxXUSK7",
+        ),
+    ];
+
+    // (passphrase, intermediate_code, encrypted_private_key, address, private_key)
+    const EC_MULTIPLY: [(&str, &str, &str, &str, &str); 3] = [
+        (
+            "TestingOneTwoThree",
+            "passphrasepxFy57B9v8HtUsszJYKReoNDV6VHjUSGt8EVJmux9n1J3Ltf1gRxyDGXqnf9qm",
+            "6PfQu77ygVyJLZjfvMLyhLMQbYnu5uguoJJ4kMCLqWwPEdfpwANVS76gTX",
+            "1PE6TQi6HTVNz5DLwB1LcpMBALubfuN2z2",
+            "5K4caxezwjGCGfnoPTZ8tMcJBLB7Jvyjv4xxeacadhq8nLisLR2",
+        ),
+        (
+            "Satoshi",
+            "passphraseoRDGAXTWzbp72eVbtUDdn1rwpgPUGjNZEc6CGBo8i5EC1FPW8wcnLdq4ThKzAS",
+            "6PfLGnQs6VZnrNpmVKfjotbnQuaJK4KZoPFrAjx1JMJUa1Ft8gnf5WxfKd",
+            "1CqzrtZC6mXSAhoxtFwVjz8LtwLJjDYU3V",
+            "5KJ51SgxWaAYR13zd9ReMhJpwrcX47xTJh2D3fGPG9CM8vkv5sH",
+        ),
+        (
+            "MOLON LABE",
+            "passphraseaB8feaLQDENqCgr4gKZpmf4VoaT6qdjJNJiv7fsKvjqavcJxvuR1hy25aTu5sX",
+            "6PgNBNNzDkKdhkT6uJntUXwwzQV8Rr2tZcbkDcuC9DZRsS6AtHts4Ypo1j",
+            "1Jscj8ALrYu2y9TD8NrpvDBugPedmbj4Yh",
+            "5JLdxTtcTHcfYcmJsNVy1v2PMDx432JPoYcBTVVRHpPaxUrdtf8",
+        ),
+    ];
+
+    #[test]
+    fn encrypt() {
+        NON_EC_MULTIPLY
+            .iter()
+            .for_each(|(passphrase, expected_encrypted_private_key, private_key)| {
+                let private_key = BitcoinPrivateKey::<N>::from_str(private_key).unwrap();
+                let encrypted_private_key = Bip38EncryptedPrivateKey::encrypt(&private_key, passphrase).unwrap();
+                assert_eq!(*expected_encrypted_private_key, encrypted_private_key.to_string());
+                assert_eq!(private_key.is_compressed(), encrypted_private_key.is_compressed());
+                assert!(!encrypted_private_key.is_ec_multiplied());
+            });
+    }
+
+    #[test]
+    fn decrypt() {
+        NON_EC_MULTIPLY
+            .iter()
+            .for_each(|(passphrase, encrypted_private_key, expected_private_key)| {
+                let encrypted_private_key = Bip38EncryptedPrivateKey::<N>::from_str(encrypted_private_key).unwrap();
+                let private_key = encrypted_private_key.decrypt(passphrase).unwrap();
+                assert_eq!(*expected_private_key, private_key.to_string());
+            });
+    }
+
+    #[test]
+    fn decrypt_ec_multiplied() {
+        EC_MULTIPLY.iter().for_each(
+            |(passphrase, _, encrypted_private_key, address, expected_private_key)| {
+                let encrypted_private_key = Bip38EncryptedPrivateKey::<N>::from_str(encrypted_private_key).unwrap();
+                assert!(encrypted_private_key.is_ec_multiplied());
+                let private_key = encrypted_private_key.decrypt(passphrase).unwrap();
+                assert_eq!(*expected_private_key, private_key.to_string());
+                assert_eq!(
+                    *address,
+                    private_key.to_address(&BitcoinFormat::P2PKH).unwrap().to_string()
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn intermediate_code() {
+        EC_MULTIPLY.iter().for_each(|(passphrase, code, _, _, _)| {
+            let intermediate_code = Bip38IntermediateCode::from_str(code).unwrap();
+            assert_eq!(*code, intermediate_code.to_string());
+            assert_eq!(
+                intermediate_code,
+                Bip38IntermediateCode::from_owner_entropy(
+                    passphrase,
+                    intermediate_code.owner_entropy,
+                    intermediate_code.lot_sequence
+                )
+                .unwrap()
+            );
+        });
+
+        let intermediate_code = Bip38IntermediateCode::from_str(EC_MULTIPLY[2].1).unwrap();
+        assert_eq!(Some((263183, 1)), intermediate_code.lot_sequence());
+    }
+
+    #[test]
+    fn from_intermediate_code() {
+        let rng = &mut XorShiftRng::seed_from_u64(38);
+        [None, Some((263183, 1))].iter().for_each(|lot_sequence| {
+            let intermediate_code = Bip38IntermediateCode::new(rng, "wagyu", *lot_sequence).unwrap();
+            assert_eq!(*lot_sequence, intermediate_code.lot_sequence());
+
+            [false, true].iter().for_each(|compressed| {
+                let (encrypted_private_key, address) =
+                    Bip38EncryptedPrivateKey::<N>::from_intermediate_code(rng, &intermediate_code, *compressed)
+                        .unwrap();
+                assert_eq!(*compressed, encrypted_private_key.is_compressed());
+
+                let private_key = encrypted_private_key.decrypt("wagyu").unwrap();
+                assert_eq!(address, private_key.to_address(&BitcoinFormat::P2PKH).unwrap());
+                assert_eq!(
+                    encrypted_private_key,
+                    Bip38EncryptedPrivateKey::from_str(&encrypted_private_key.to_string()).unwrap()
+                );
+            });
+        });
+    }
+
+    #[test]
+    fn invalid_passphrase() {
+        let (_, encrypted_private_key, _) = NON_EC_MULTIPLY[0];
+        let encrypted_private_key = Bip38EncryptedPrivateKey::<N>::from_str(encrypted_private_key).unwrap();
+        assert!(encrypted_private_key.decrypt("Satoshi").is_err());
+
+        let (_, _, encrypted_private_key, _, _) = EC_MULTIPLY[0];
+        let encrypted_private_key = Bip38EncryptedPrivateKey::<N>::from_str(encrypted_private_key).unwrap();
+        assert!(encrypted_private_key.decrypt("Satoshi").is_err());
+    }
+
+    #[test]
+    fn invalid_lot_sequence() {
+        let rng = &mut XorShiftRng::seed_from_u64(38);
+        assert!(Bip38IntermediateCode::new(rng, "wagyu", Some((MAX_LOT + 1, 0))).is_err());
+        assert!(Bip38IntermediateCode::new(rng, "wagyu", Some((0, MAX_SEQUENCE + 1))).is_err());
+    }
+}
@@ -0,0 +1,947 @@
+use crate::address::BitcoinAddress;
+use crate::amount::BitcoinAmount;
+use crate::derivation_path::BitcoinDerivationPath;
+use crate::extended_public_key::BitcoinExtendedPublicKey;
+use crate::format::BitcoinFormat;
+use crate::network::BitcoinNetwork;
+use crate::transaction::{
+    create_script_pub_key, dust_threshold, input_weight, weight_to_fee, BitcoinTransactionInput,
+    BitcoinTransactionOutput, BitcoinTransactionParameters, SignatureHash,
+};
+use wagyu_model::derivation_path::{
+    ChildIndex, ChildIndexRange, DerivationPath, DerivationPathError, RelativeDerivationPath,
+};
+use wagyu_model::no_std::*;
+use wagyu_model::{ExtendedPublicKey, ExtendedPublicKeyError, TransactionError};
+
+use core::cmp::Reverse;
+use rand::Rng;
+
+/// The weight of the version, input count, output count and lock time of a transaction
+const TRANSACTION_OVERHEAD_WEIGHT: u64 = 40;
+/// The weight of the SegWit marker and flag
+const SEGWIT_OVERHEAD_WEIGHT: u64 = 2;
+/// The maximum number of branches explored by a Branch-and-Bound search
+const BRANCH_AND_BOUND_TRIES: usize = 100_000;
+/// The number of random subsets explored by a knapsack search
+const KNAPSACK_ITERATIONS: usize = 1_000;
+
+/// Represents an unspent transaction output available to fund a transaction
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd)]
+pub struct BitcoinUtxo<N: BitcoinNetwork> {
+    /// The id of the transaction which created the output
+    pub transaction_id: Vec<u8>,
+    /// The index of the output in its transaction
+    pub index: u32,
+    /// The amount of the output
+    pub amount: BitcoinAmount,
+    /// The address the output pays to
+    pub address: BitcoinAddress<N>,
+    /// The script public key of the output
+    pub script_pub_key: Vec<u8>,
+    /// The redeem script of the output (for P2SH-P2WPKH outputs)
+    pub redeem_script: Option<Vec<u8>>,
+    /// The derivation path of the key controlling the output, if it belongs to an HD wallet
+    pub derivation_path: Option<BitcoinDerivationPath<N>>,
+    /// The height of the block which confirmed the output, or `None` if the output is unconfirmed
+    pub height: Option<u32>,
+}
+
+impl<N: BitcoinNetwork> BitcoinUtxo<N> {
+    /// Returns a new unconfirmed unspent transaction output paying to the given address.
+    pub fn new(
+        transaction_id: Vec<u8>,
+        index: u32,
+        amount: BitcoinAmount,
+        address: BitcoinAddress<N>,
+    ) -> Result<Self, TransactionError> {
+        Ok(Self {
+            transaction_id,
+            index,
+            amount,
+            script_pub_key: create_script_pub_key(&address)?,
+            address,
+            redeem_script: None,
+            derivation_path: None,
+            height: None,
+        })
+    }
+
+    /// Sets the redeem script of the output.
+    pub fn with_redeem_script(mut self, redeem_script: &[u8]) -> Self {
+        self.redeem_script = Some(redeem_script.to_vec());
+        self
+    }
+
+    /// Sets the derivation path of the key controlling the output.
+    pub fn with_derivation_path(mut self, derivation_path: &BitcoinDerivationPath<N>) -> Self {
+        self.derivation_path = Some(derivation_path.clone());
+        self
+    }
+
+    /// Sets the height of the block which confirmed the output.
+    pub fn with_height(mut self, height: u32) -> Self {
+        self.height = Some(height);
+        self
+    }
+
+    /// Returns `true` if the output was created by the given outpoint.
+    pub fn is_outpoint(&self, transaction_id: &[u8], index: u32) -> bool {
+        self.transaction_id == transaction_id && self.index == index
+    }
+
+    /// Returns the number of confirmations of the output at the given height of the chain tip.
+    pub fn confirmations(&self, tip_height: u32) -> u32 {
+        match self.height {
+            Some(height) if height <= tip_height => tip_height - height + 1,
+            _ => 0,
+        }
+    }
+
+    /// Returns the unsigned transaction input spending the output with the given signature hash.
+    pub fn to_input(&self, sighash: SignatureHash) -> Result<BitcoinTransactionInput<N>, TransactionError> {
+        BitcoinTransactionInput::<N>::new(
+            self.transaction_id.clone(),
+            self.index,
+            Some(self.address.clone()),
+            Some(self.amount),
+            self.redeem_script.clone(),
+            Some(self.script_pub_key.clone()),
+            None,
+            sighash,
+        )
+    }
+}
+
+/// Represents the algorithms used to select the inputs of a transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CoinSelectionAlgorithm {
+    /// Searches for a set of inputs matching the target without change, falling back to knapsack
+    BranchAndBound,
+    /// Approximates the smallest set of inputs exceeding the target over random subsets
+    Knapsack,
+    /// Selects the largest inputs first until the target is reached
+    LargestFirst,
+    /// Selects only the required inputs, which must reach the target
+    Manual,
+}
+
+/// Represents the inputs selected to fund a transaction, with its change output and fee
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitcoinCoinSelection<N: BitcoinNetwork> {
+    /// The selected unspent transaction outputs
+    pub inputs: Vec<BitcoinUtxo<N>>,
+    /// The change output, if the excess of the inputs is not dust
+    pub change: Option<BitcoinTransactionOutput>,
+    /// The derivation path of the change address, if the change output pays to an account
+    pub change_derivation_path: Option<BitcoinDerivationPath<N>>,
+    /// The fee of the transaction
+    pub fee: BitcoinAmount,
+}
+
+impl<N: BitcoinNetwork> BitcoinCoinSelection<N> {
+    /// Returns the unsigned transaction parameters spending the selected inputs with the given
+    /// signature hash to the given outputs, followed by the change output if any.
+    pub fn to_transaction_parameters(
+        &self,
+        outputs: &[BitcoinTransactionOutput],
+        sighash: SignatureHash,
+    ) -> Result<BitcoinTransactionParameters<N>, TransactionError> {
+        Ok(BitcoinTransactionParameters::<N> {
+            version: 2,
+            inputs: self
+                .inputs
+                .iter()
+                .map(|utxo| utxo.to_input(sighash))
+                .collect::<Result<_, _>>()?,
+            outputs: outputs.iter().chain(&self.change).cloned().collect(),
+            lock_time: 0,
+            segwit_flag: false,
+        })
+    }
+}
+
+/// Represents a selector of the inputs of a transaction from a set of unspent transaction outputs
+#[derive(Debug, Clone)]
+pub struct BitcoinCoinSelector<N: BitcoinNetwork> {
+    /// The unspent transaction outputs available for selection
+    utxos: Vec<BitcoinUtxo<N>>,
+    /// The fee rate (in Satoshi per virtual byte)
+    fee_rate: u64,
+    /// The address of the change output
+    change_address: BitcoinAddress<N>,
+    /// The derivation path of the change address, if it is derived from an account
+    change_derivation_path: Option<BitcoinDerivationPath<N>>,
+    /// The outpoints of the unspent transaction outputs which may not be selected
+    frozen: Vec<(Vec<u8>, u32)>,
+    /// The outpoints of the unspent transaction outputs which must be selected
+    required: Vec<(Vec<u8>, u32)>,
+    /// The least number of confirmations of a selected output, and the height of the chain tip
+    min_confirmations: Option<(u32, u32)>,
+}
+
+impl<N: BitcoinNetwork> BitcoinCoinSelector<N> {
+    /// Returns a new coin selector paying the given fee rate (in Satoshi per virtual byte) and
+    /// any change to the given address.
+    pub fn new(
+        utxos: &[BitcoinUtxo<N>],
+        fee_rate: u64,
+        change_address: &BitcoinAddress<N>,
+    ) -> Result<Self, CoinSelectionError> {
+        for format in utxos
+            .iter()
+            .map(|utxo| utxo.address.format())
+            .chain(Some(change_address.format()))
+        {
+            if input_weight(&format).is_none() {
+                return Err(CoinSelectionError::UnsupportedFormat(format.to_string()));
+            }
+        }
+
+        Ok(Self {
+            utxos: utxos.to_vec(),
+            fee_rate,
+            change_address: change_address.clone(),
+            change_derivation_path: None,
+            frozen: vec![],
+            required: vec![],
+            min_confirmations: None,
+        })
+    }
+
+    /// Returns a new coin selector paying the given fee rate (in Satoshi per virtual byte) and any change
+    /// to the next unused address of the internal chain of the given account, as given by `next_change_address`.
+    pub fn from_account(
+        utxos: &[BitcoinUtxo<N>],
+        fee_rate: u64,
+        account: &BitcoinExtendedPublicKey<N>,
+        used: &[BitcoinAddress<N>],
+    ) -> Result<Self, CoinSelectionError> {
+        let (change_address, change_derivation_path) = next_change_address(account, used)?;
+        Ok(Self {
+            change_derivation_path: Some(change_derivation_path),
+            ..Self::new(utxos, fee_rate, &change_address)?
+        })
+    }
+
+    /// Freezes the unspent transaction output of the given outpoint, which will not be selected.
+    pub fn with_frozen(mut self, transaction_id: &[u8], index: u32) -> Result<Self, CoinSelectionError> {
+        let outpoint = self.find_outpoint(transaction_id, index)?;
+        if self.required.contains(&outpoint) {
+            return Err(CoinSelectionError::FrozenUtxo(hex::encode(transaction_id), index));
+        }
+        self.frozen.push(outpoint);
+        Ok(self)
+    }
+
+    /// Requires the unspent transaction output of the given outpoint to be selected,
+    /// regardless of its confirmations and effective value.
+    pub fn with_required(mut self, transaction_id: &[u8], index: u32) -> Result<Self, CoinSelectionError> {
+        let outpoint = self.find_outpoint(transaction_id, index)?;
+        if self.frozen.contains(&outpoint) {
+            return Err(CoinSelectionError::FrozenUtxo(hex::encode(transaction_id), index));
+        }
+        self.required.push(outpoint);
+        Ok(self)
+    }
+
+    /// Excludes the unspent transaction outputs with fewer than the given number of confirmations
+    /// at the given height of the chain tip, unless they are required.
+    pub fn with_min_confirmations(mut self, min_confirmations: u32, tip_height: u32) -> Self {
+        self.min_confirmations = Some((min_confirmations, tip_height));
+        self
+    }
+
+    /// Returns `true` if the unspent transaction output may be selected by the algorithms,
+    /// being neither frozen, required, nor short of the minimum number of confirmations.
+    pub fn is_spendable(&self, utxo: &BitcoinUtxo<N>) -> bool {
+        let is_listed = |outpoints: &Vec<(Vec<u8>, u32)>| {
+            outpoints
+                .iter()
+                .any(|(transaction_id, index)| utxo.is_outpoint(transaction_id, *index))
+        };
+        let is_confirmed = match self.min_confirmations {
+            Some((min_confirmations, tip_height)) => utxo.confirmations(tip_height) >= min_confirmations,
+            None => true,
+        };
+        !is_listed(&self.frozen) && !is_listed(&self.required) && is_confirmed
+    }
+
+    /// Returns the outpoint if it is one of the unspent transaction outputs of the selector.
+    fn find_outpoint(&self, transaction_id: &[u8], index: u32) -> Result<(Vec<u8>, u32), CoinSelectionError> {
+        match self.utxos.iter().any(|utxo| utxo.is_outpoint(transaction_id, index)) {
+            true => Ok((transaction_id.to_vec(), index)),
+            false => Err(CoinSelectionError::UnknownUtxo(hex::encode(transaction_id), index)),
+        }
+    }
+
+    /// Returns the fee paid for the given weight at the fee rate of the selector.
+    pub fn fee(&self, weight: u64) -> BitcoinAmount {
+        weight_to_fee(weight, self.fee_rate)
+    }
+
+    /// Returns the amount of the unspent transaction output less the fee of spending it.
+    pub fn effective_value(&self, utxo: &BitcoinUtxo<N>) -> BitcoinAmount {
+        let weight = input_weight(&utxo.address.format()).unwrap_or_default();
+        BitcoinAmount(utxo.amount.0 - self.fee(weight).0)
+    }
+
+    /// Returns the inputs funding the given outputs with the given algorithm, with the change
+    /// output and fee of the resulting transaction.
+    pub fn select<R: Rng>(
+        &self,
+        rng: &mut R,
+        outputs: &[BitcoinTransactionOutput],
+        algorithm: CoinSelectionAlgorithm,
+    ) -> Result<BitcoinCoinSelection<N>, CoinSelectionError> {
+        if outputs.is_empty() {
+            return Err(CoinSelectionError::MissingOutputs);
+        }
+
+        let required: Vec<usize> = self
+            .utxos
+            .iter()
+            .enumerate()
+            .filter(|(_, utxo)| {
+                self.required
+                    .iter()
+                    .any(|(transaction_id, index)| utxo.is_outpoint(transaction_id, *index))
+            })
+            .map(|(index, _)| index)
+            .collect();
+
+        let segwit_weight = match self
+            .utxos
+            .iter()
+            .filter(|utxo| self.is_spendable(utxo))
+            .chain(required.iter().map(|index| &self.utxos[*index]))
+            .any(|utxo| utxo.address.format() != BitcoinFormat::P2PKH)
+        {
+            true => SEGWIT_OVERHEAD_WEIGHT,
+            false => 0,
+        };
+        let mut base_weight = TRANSACTION_OVERHEAD_WEIGHT + segwit_weight;
+        for output in outputs {
+            base_weight += output.weight()?;
+        }
+
+        let output_value: i64 = outputs.iter().map(|output| output.amount.0).sum();
+        let target = output_value + self.fee(base_weight).0;
+
+        let change_format = self.change_address.format();
+        let change_fee = self.fee(BitcoinTransactionOutput::new(&self.change_address, BitcoinAmount(0))?.weight()?);
+        let change_spend_fee = self.fee(input_weight(&change_format).unwrap_or_default());
+        let dust = dust_threshold(&self.change_address)?;
+
+        // Candidates with a non-positive effective value cost more to spend than they contribute
+        let mut candidates: Vec<(usize, i64)> = self
+            .utxos
+            .iter()
+            .map(|utxo| self.effective_value(utxo).0)
+            .enumerate()
+            .filter(|(index, value)| *value > 0 && self.is_spendable(&self.utxos[*index]))
+            .collect();
+        candidates.sort_by_key(|(_, value)| Reverse(*value));
+
+        let cost_of_change = change_fee.0 + change_spend_fee.0;
+        let min_change = change_fee.0 + dust.0;
+
+        // The required inputs are selected first, and the algorithms fund the remaining target
+        let required_value: i64 = required
+            .iter()
+            .map(|index| self.effective_value(&self.utxos[*index]).0)
+            .sum();
+        let remaining_target = target - required_value;
+
+        let selected = match algorithm {
+            _ if remaining_target <= 0 => Some(vec![]),
+            CoinSelectionAlgorithm::BranchAndBound => branch_and_bound(&candidates, remaining_target, cost_of_change)
+                .or_else(|| knapsack(rng, &candidates, remaining_target, min_change)),
+            CoinSelectionAlgorithm::Knapsack => knapsack(rng, &candidates, remaining_target, min_change),
+            CoinSelectionAlgorithm::LargestFirst => largest_first(&candidates, remaining_target),
+            CoinSelectionAlgorithm::Manual => None,
+        };
+
+        let mut selected = match selected {
+            Some(selected) => [required, selected].concat(),
+            None => {
+                let available = match algorithm {
+                    CoinSelectionAlgorithm::Manual => required_value,
+                    _ => required_value + candidates.iter().map(|(_, value)| value).sum::<i64>(),
+                };
+                return Err(CoinSelectionError::InsufficientFunds(available, target));
+            }
+        };
+        selected.sort_unstable();
+
+        let inputs: Vec<BitcoinUtxo<N>> = selected.iter().map(|index| self.utxos[*index].clone()).collect();
+        let input_value: i64 = inputs.iter().map(|input| input.amount.0).sum();
+        let effective_value: i64 = inputs.iter().map(|input| self.effective_value(input).0).sum();
+
+        let change_value = effective_value - target - change_fee.0;
+        let change = match change_value >= dust.0 {
+            true => Some(BitcoinTransactionOutput::new(
+                &self.change_address,
+                BitcoinAmount(change_value),
+            )?),
+            false => None,
+        };
+
+        let change_value = change.as_ref().map_or(0, |change| change.amount.0);
+        let change_derivation_path = change.as_ref().and(self.change_derivation_path.clone());
+        Ok(BitcoinCoinSelection {
+            inputs,
+            change,
+            change_derivation_path,
+            fee: BitcoinAmount(input_value - output_value - change_value),
+        })
+    }
+}
+
+/// Returns the next unused address of the internal (change) chain `1/{index}` of the given BIP44, BIP49,
+/// BIP84, or BIP86 account extended public key, the first address which is not one of the given used
+/// addresses, with its derivation path from the master key.
+pub fn next_change_address<N: BitcoinNetwork>(
+    account: &BitcoinExtendedPublicKey<N>,
+    used: &[BitcoinAddress<N>],
+) -> Result<(BitcoinAddress<N>, BitcoinDerivationPath<N>), CoinSelectionError> {
+    let account_index = match (account.depth(), account.child_index()) {
+        (3, ChildIndex::Hardened(account_index)) => account_index,
+        _ => return Err(CoinSelectionError::InvalidAccount(account.to_string())),
+    };
+    let format = account.format();
+
+    // At most all of the used addresses precede the first unused address
+    let chain = account.derive_relative(&RelativeDerivationPath::from_vec(&vec![ChildIndex::normal(1)?])?)?;
+    let children = chain.derive_children(ChildIndexRange::from_count(
+        ChildIndex::normal(0)?,
+        used.len() as u32 + 1,
+    )?)?;
+    for (index, child) in children.iter().enumerate() {
+        let address = child.to_address(&format).map_err(TransactionError::from)?;
+        if !used.contains(&address) {
+            let path = BitcoinDerivationPath::from_format(&format, account_index, 1, index as u32)?;
+            return Ok((address, path));
+        }
+    }
+    Err(CoinSelectionError::InvalidAccount(account.to_string()))
+}
+
+/// Returns the candidates whose total value lies within the cost of change above the target,
+/// minimizing the excess, by a depth-first search over the candidates in descending order of value.
+/// https://murch.one/wp-content/uploads/2016/11/erhardt2016coinselection.pdf
+fn branch_and_bound(candidates: &[(usize, i64)], target: i64, cost_of_change: i64) -> Option<Vec<usize>> {
+    let mut available: i64 = candidates.iter().map(|(_, value)| value).sum();
+    if available < target {
+        return None;
+    }
+
+    let mut value = 0;
+    let mut selection: Vec<bool> = vec![];
+    let mut best: Option<(Vec<bool>, i64)> = None;
+
+    for _ in 0..BRANCH_AND_BOUND_TRIES {
+        let backtrack = if value + available < target || value > target + cost_of_change {
+            true
+        } else if value >= target {
+            let excess = value - target;
+            if best.as_ref().map_or(true, |(_, best_excess)| excess < *best_excess) {
+                best = Some((selection.clone(), excess));
+            }
+            true
+        } else {
+            false
+        };
+
+        if backtrack {
+            // Restore the omitted candidates, then omit the last included candidate
+            while let Some(false) = selection.last() {
+                selection.pop();
+                available += candidates[selection.len()].1;
+            }
+            match selection.last_mut() {
+                Some(included) => *included = false,
+                None => break,
+            };
+            value -= candidates[selection.len() - 1].1;
+        } else {
+            let candidate = candidates[selection.len()].1;
+            available -= candidate;
+            value += candidate;
+            selection.push(true);
+        }
+    }
+
+    best.map(|(selection, _)| {
+        selection
+            .iter()
+            .zip(candidates)
+            .filter(|(included, _)| **included)
+            .map(|(_, (index, _))| *index)
+            .collect()
+    })
+}
+
+/// Returns the candidate matching the target, the smallest candidate exceeding the target with
+/// change, or the best of random subsets of the smaller candidates, as in Bitcoin Core.
+fn knapsack<R: Rng>(rng: &mut R, candidates: &[(usize, i64)], target: i64, min_change: i64) -> Option<Vec<usize>> {
+    let mut lower = vec![];
+    let mut lower_value = 0;
+    let mut lowest_larger: Option<(usize, i64)> = None;
+
+    for (index, value) in candidates {
+        if *value == target {
+            return Some(vec![*index]);
+        } else if *value < target + min_change {
+            lower.push((*index, *value));
+            lower_value += value;
+        } else if lowest_larger.map_or(true, |(_, lowest)| *value < lowest) {
+            lowest_larger = Some((*index, *value));
+        }
+    }
+
+    if lower_value == target {
+        return Some(lower.iter().map(|(index, _)| *index).collect());
+    }
+    if lower_value < target {
+        return lowest_larger.map(|(index, _)| vec![index]);
+    }
+
+    let values: Vec<i64> = lower.iter().map(|(_, value)| *value).collect();
+    let (mut best, mut best_value) = approximate_best_subset(rng, &values, lower_value, target);
+    if best_value != target && lower_value >= target + min_change {
+        let (subset, subset_value) = approximate_best_subset(rng, &values, lower_value, target + min_change);
+        best = subset;
+        best_value = subset_value;
+    }
+
+    match lowest_larger {
+        Some((index, value)) if (best_value != target && best_value < target + min_change) || value <= best_value => {
+            Some(vec![index])
+        }
+        _ => Some(
+            best.iter()
+                .zip(&lower)
+                .filter(|(included, _)| **included)
+                .map(|(_, (index, _))| *index)
+                .collect(),
+        ),
+    }
+}
+
+/// Returns the subset of the values with the smallest total reaching the target over random
+/// subsets, each completed in a second pass over the values it omitted.
+fn approximate_best_subset<R: Rng>(rng: &mut R, values: &[i64], total: i64, target: i64) -> (Vec<bool>, i64) {
+    let mut best = vec![true; values.len()];
+    let mut best_value = total;
+
+    for _ in 0..KNAPSACK_ITERATIONS {
+        if best_value == target {
+            break;
+        }
+
+        let mut included = vec![false; values.len()];
+        let mut value = 0;
+        let mut reached_target = false;
+
+        for pass in 0..2 {
+            if reached_target {
+                break;
+            }
+            for (i, candidate) in values.iter().enumerate() {
+                let include = match pass {
+                    0 => rng.gen::<bool>(),
+                    _ => !included[i],
+                };
+                if include {
+                    value += candidate;
+                    included[i] = true;
+                    if value >= target {
+                        reached_target = true;
+                        if value < best_value {
+                            best_value = value;
+                            best = included.clone();
+                        }
+                        value -= candidate;
+                        included[i] = false;
+                    }
+                }
+            }
+        }
+    }
+
+    (best, best_value)
+}
+
+/// Returns the largest candidates until their total value reaches the target.
+fn largest_first(candidates: &[(usize, i64)], target: i64) -> Option<Vec<usize>> {
+    let mut selected = vec![];
+    let mut value = 0;
+    for (index, candidate) in candidates {
+        if value >= target {
+            break;
+        }
+        selected.push(*index);
+        value += candidate;
+    }
+
+    match value >= target {
+        true => Some(selected),
+        false => None,
+    }
+}
+
+#[derive(Debug, Fail)]
+pub enum CoinSelectionError {
+    #[fail(display = "{}", _0)]
+    ExtendedPublicKeyError(#[cause] ExtendedPublicKeyError),
+
+    #[fail(display = "unspent output {}:{} is both frozen and required", _0, _1)]
+    FrozenUtxo(String, u32),
+
+    #[fail(display = "insufficient funds: {} available, {} required", _0, _1)]
+    InsufficientFunds(i64, i64),
+
+    #[fail(display = "invalid account extended public key: {}", _0)]
+    InvalidAccount(String),
+
+    #[fail(display = "missing outputs")]
+    MissingOutputs,
+
+    #[fail(display = "{}", _0)]
+    TransactionError(#[cause] TransactionError),
+
+    #[fail(display = "unknown unspent output {}:{}", _0, _1)]
+    UnknownUtxo(String, u32),
+
+    #[fail(display = "unsupported format: {}", _0)]
+    UnsupportedFormat(String),
+}
+
+impl From<DerivationPathError> for CoinSelectionError {
+    fn from(error: DerivationPathError) -> Self {
+        CoinSelectionError::ExtendedPublicKeyError(ExtendedPublicKeyError::from(error))
+    }
+}
+
+impl From<ExtendedPublicKeyError> for CoinSelectionError {
+    fn from(error: ExtendedPublicKeyError) -> Self {
+        CoinSelectionError::ExtendedPublicKeyError(error)
+    }
+}
+
+impl From<TransactionError> for CoinSelectionError {
+    fn from(error: TransactionError) -> Self {
+        CoinSelectionError::TransactionError(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extended_private_key::BitcoinExtendedPrivateKey;
+    use crate::network::*;
+    use wagyu_model::ExtendedPrivateKey;
+
+    use core::str::FromStr;
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    type N = Mainnet;
+
+    const BECH32_ADDRESS: &str = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4";
+    const P2WSH_ADDRESS: &str = "bc1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3qccfmv3";
+
+    fn address(address: &str) -> BitcoinAddress<N> {
+        BitcoinAddress::<N>::from_str(address).unwrap()
+    }
+
+    fn utxos(amounts: &[i64]) -> Vec<BitcoinUtxo<N>> {
+        amounts
+            .iter()
+            .enumerate()
+            .map(|(i, amount)| {
+                BitcoinUtxo::new(
+                    vec![i as u8; 32],
+                    i as u32,
+                    BitcoinAmount(*amount),
+                    address(BECH32_ADDRESS),
+                )
+                .unwrap()
+            })
+            .collect()
+    }
+
+    fn outputs(amount: i64) -> Vec<BitcoinTransactionOutput> {
+        vec![BitcoinTransactionOutput::new(&address(BECH32_ADDRESS), BitcoinAmount(amount)).unwrap()]
+    }
+
+    fn select(
+        amounts: &[i64],
+        fee_rate: u64,
+        target: i64,
+        algorithm: CoinSelectionAlgorithm,
+    ) -> Result<BitcoinCoinSelection<N>, CoinSelectionError> {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        let selector = BitcoinCoinSelector::new(&utxos(amounts), fee_rate, &address(BECH32_ADDRESS)).unwrap();
+        selector.select(rng, &outputs(target), algorithm)
+    }
+
+    fn selected_amounts(selection: &BitcoinCoinSelection<N>) -> Vec<i64> {
+        selection.inputs.iter().map(|input| input.amount.0).collect()
+    }
+
+    #[test]
+    fn branch_and_bound_without_change() {
+        let selection = select(
+            &[500_000, 300_000, 200_000, 100_000],
+            0,
+            400_000,
+            CoinSelectionAlgorithm::BranchAndBound,
+        )
+        .unwrap();
+        assert_eq!(vec![300_000, 100_000], selected_amounts(&selection));
+        assert_eq!(None, selection.change);
+        assert_eq!(BitcoinAmount(0), selection.fee);
+    }
+
+    #[test]
+    fn branch_and_bound_falls_back_to_knapsack() {
+        let selection = select(&[500_000, 300_000], 0, 400_000, CoinSelectionAlgorithm::BranchAndBound).unwrap();
+        assert_eq!(vec![500_000], selected_amounts(&selection));
+        assert_eq!(BitcoinAmount(100_000), selection.change.unwrap().amount);
+    }
+
+    #[test]
+    fn knapsack() {
+        let amounts = [10_000, 20_000, 30_000, 40_000, 50_000, 1_000_000];
+        let selection = select(&amounts, 0, 60_000, CoinSelectionAlgorithm::Knapsack).unwrap();
+        assert_eq!(60_000, selected_amounts(&selection).iter().sum::<i64>());
+        assert_eq!(None, selection.change);
+
+        let selection = select(&amounts, 0, 200_000, CoinSelectionAlgorithm::Knapsack).unwrap();
+        assert_eq!(vec![1_000_000], selected_amounts(&selection));
+        assert_eq!(BitcoinAmount(800_000), selection.change.unwrap().amount);
+    }
+
+    #[test]
+    fn largest_first() {
+        let selection = select(
+            &[100_000, 300_000, 200_000],
+            0,
+            400_000,
+            CoinSelectionAlgorithm::LargestFirst,
+        )
+        .unwrap();
+        assert_eq!(vec![300_000, 200_000], selected_amounts(&selection));
+        assert_eq!(BitcoinAmount(100_000), selection.change.unwrap().amount);
+    }
+
+    #[test]
+    fn fee_and_change() {
+        // (base 166 + input 272 + change 124) weight at 10 sat/vB
+        let selection = select(&[100_000], 10, 50_000, CoinSelectionAlgorithm::LargestFirst).unwrap();
+        assert_eq!(BitcoinAmount(1_405), selection.fee);
+        assert_eq!(BitcoinAmount(48_595), selection.change.unwrap().amount);
+    }
+
+    #[test]
+    fn dust_change_is_paid_as_fee() {
+        let selection = select(&[51_500], 10, 50_000, CoinSelectionAlgorithm::LargestFirst).unwrap();
+        assert_eq!(None, selection.change);
+        assert_eq!(BitcoinAmount(1_500), selection.fee);
+    }
+
+    #[test]
+    fn uneconomical_utxos_are_skipped() {
+        let selection = select(&[500, 100_000], 10, 50_000, CoinSelectionAlgorithm::LargestFirst).unwrap();
+        assert_eq!(vec![100_000], selected_amounts(&selection));
+
+        match select(&[500, 50_000], 10, 50_000, CoinSelectionAlgorithm::Knapsack) {
+            Err(CoinSelectionError::InsufficientFunds(available, required)) => {
+                assert_eq!(49_320, available);
+                assert_eq!(50_415, required);
+            }
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+
+    #[test]
+    fn frozen_and_required_utxos() {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        let utxos = utxos(&[500_000, 300_000, 200_000, 100_000]);
+        let selector = BitcoinCoinSelector::new(&utxos, 0, &address(BECH32_ADDRESS))
+            .unwrap()
+            .with_frozen(&[1; 32], 1)
+            .unwrap()
+            .with_required(&[3; 32], 3)
+            .unwrap();
+        assert!(!selector.is_spendable(&utxos[1]));
+        assert!(!selector.is_spendable(&utxos[3]));
+
+        // The frozen 300,000 would otherwise match the remaining target without change
+        let selection = selector
+            .select(rng, &outputs(400_000), CoinSelectionAlgorithm::BranchAndBound)
+            .unwrap();
+        assert_eq!(vec![500_000, 100_000], selected_amounts(&selection));
+
+        let selection = selector
+            .select(rng, &outputs(50_000), CoinSelectionAlgorithm::LargestFirst)
+            .unwrap();
+        assert_eq!(vec![100_000], selected_amounts(&selection));
+        assert_eq!(BitcoinAmount(50_000), selection.change.unwrap().amount);
+
+        match selector.select(rng, &outputs(1_000_000), CoinSelectionAlgorithm::LargestFirst) {
+            Err(CoinSelectionError::InsufficientFunds(800_000, 1_000_000)) => (),
+            result => panic!("unexpected result {:?}", result),
+        };
+
+        let selector = BitcoinCoinSelector::new(&utxos, 0, &address(BECH32_ADDRESS)).unwrap();
+        assert!(selector.clone().with_frozen(&[4; 32], 4).is_err());
+        assert!(selector
+            .with_frozen(&[0; 32], 0)
+            .unwrap()
+            .with_required(&[0; 32], 0)
+            .is_err());
+    }
+
+    #[test]
+    fn manual_selection() {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        let selector = BitcoinCoinSelector::new(&utxos(&[500_000, 300_000, 200_000]), 0, &address(BECH32_ADDRESS))
+            .unwrap()
+            .with_required(&[2; 32], 2)
+            .unwrap()
+            .with_required(&[1; 32], 1)
+            .unwrap();
+
+        let selection = selector
+            .select(rng, &outputs(400_000), CoinSelectionAlgorithm::Manual)
+            .unwrap();
+        assert_eq!(vec![300_000, 200_000], selected_amounts(&selection));
+        assert_eq!(BitcoinAmount(100_000), selection.change.unwrap().amount);
+
+        match selector.select(rng, &outputs(600_000), CoinSelectionAlgorithm::Manual) {
+            Err(CoinSelectionError::InsufficientFunds(500_000, 600_000)) => (),
+            result => panic!("unexpected result {:?}", result),
+        };
+    }
+
+    #[test]
+    fn min_confirmations() {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        let mut utxos = utxos(&[500_000, 300_000, 200_000]);
+        utxos[0] = utxos[0].clone().with_height(800_000);
+        utxos[1] = utxos[1].clone().with_height(799_995);
+        assert_eq!(1, utxos[0].confirmations(800_000));
+        assert_eq!(6, utxos[1].confirmations(800_000));
+        assert_eq!(0, utxos[2].confirmations(800_000));
+
+        let selector = BitcoinCoinSelector::new(&utxos, 0, &address(BECH32_ADDRESS))
+            .unwrap()
+            .with_min_confirmations(6, 800_000);
+        let selection = selector
+            .select(rng, &outputs(200_000), CoinSelectionAlgorithm::LargestFirst)
+            .unwrap();
+        assert_eq!(vec![300_000], selected_amounts(&selection));
+
+        // Required outputs are selected regardless of their confirmations
+        let selection = selector
+            .with_required(&[2; 32], 2)
+            .unwrap()
+            .select(rng, &outputs(400_000), CoinSelectionAlgorithm::LargestFirst)
+            .unwrap();
+        assert_eq!(vec![300_000, 200_000], selected_amounts(&selection));
+    }
+
+    #[test]
+    fn transaction_parameters() {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        let path = BitcoinDerivationPath::<N>::bip84(0, 0, 5).unwrap();
+        let utxos = vec![
+            BitcoinUtxo::new(vec![7; 32], 1, BitcoinAmount(100_000), address(BECH32_ADDRESS))
+                .unwrap()
+                .with_derivation_path(&path)
+                .with_height(800_000),
+        ];
+        assert_eq!(
+            "0014751e76e8199196d454941c45d1b3a323f1433bd6",
+            hex::encode(&utxos[0].script_pub_key)
+        );
+        assert_eq!(Some(path), utxos[0].derivation_path);
+
+        let selection = BitcoinCoinSelector::new(&utxos, 10, &address(BECH32_ADDRESS))
+            .unwrap()
+            .select(rng, &outputs(50_000), CoinSelectionAlgorithm::LargestFirst)
+            .unwrap();
+        let parameters = selection
+            .to_transaction_parameters(&outputs(50_000), SignatureHash::SIGHASH_ALL)
+            .unwrap();
+        assert_eq!(1, parameters.inputs.len());
+        assert_eq!(1, parameters.inputs[0].outpoint.index);
+        assert_eq!(Some(BitcoinAmount(100_000)), parameters.inputs[0].outpoint.amount);
+        assert_eq!(2, parameters.outputs.len());
+        assert_eq!(selection.fee, parameters.fee().unwrap());
+    }
+
+    #[test]
+    fn change_from_account() {
+        let master = BitcoinExtendedPrivateKey::<N>::new_master(&[7u8; 32], &BitcoinFormat::Bech32).unwrap();
+        let account = master
+            .derive(&BitcoinDerivationPath::from_str("m/84'/0'/0'").unwrap())
+            .unwrap()
+            .to_extended_public_key();
+        let change_address = |index: u32| {
+            let path = BitcoinDerivationPath::<N>::bip84(0, 1, index).unwrap();
+            (
+                master
+                    .derive(&path)
+                    .unwrap()
+                    .to_address(&BitcoinFormat::Bech32)
+                    .unwrap(),
+                path,
+            )
+        };
+
+        assert_eq!(change_address(0), next_change_address(&account, &[]).unwrap());
+        let used = vec![change_address(0).0, address(BECH32_ADDRESS), change_address(1).0];
+        assert_eq!(change_address(2), next_change_address(&account, &used).unwrap());
+
+        // The change output pays to the next unused address, and records its derivation path
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        let selection = BitcoinCoinSelector::from_account(&utxos(&[100_000]), 10, &account, &used)
+            .unwrap()
+            .select(rng, &outputs(50_000), CoinSelectionAlgorithm::LargestFirst)
+            .unwrap();
+        let (address, path) = change_address(2);
+        assert_eq!(
+            create_script_pub_key(&address).unwrap(),
+            selection.change.unwrap().script_pub_key
+        );
+        assert_eq!(Some(path), selection.change_derivation_path);
+
+        // Without change, there is no change derivation path
+        let selection = BitcoinCoinSelector::from_account(&utxos(&[100_000]), 0, &account, &used)
+            .unwrap()
+            .select(rng, &outputs(100_000), CoinSelectionAlgorithm::LargestFirst)
+            .unwrap();
+        assert_eq!(None, selection.change);
+        assert_eq!(None, selection.change_derivation_path);
+
+        // The extended public key must be that of an account
+        let master = master.to_extended_public_key();
+        assert!(next_change_address(&master, &[]).is_err());
+    }
+
+    #[test]
+    fn invalid_selections() {
+        let selector = BitcoinCoinSelector::new(&utxos(&[100_000]), 1, &address(BECH32_ADDRESS)).unwrap();
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        assert!(selector.select(rng, &[], CoinSelectionAlgorithm::LargestFirst).is_err());
+
+        let mut unsupported = utxos(&[100_000]);
+        unsupported[0].address = address(P2WSH_ADDRESS);
+        assert!(BitcoinCoinSelector::new(&unsupported, 1, &address(BECH32_ADDRESS)).is_err());
+        assert!(BitcoinCoinSelector::new(&utxos(&[100_000]), 1, &address(P2WSH_ADDRESS)).is_err());
+    }
+}
@@ -0,0 +1,259 @@
+//!
+//! Core Import
+//!
+//! This module contains the requests of the Bitcoin Core `importdescriptors` and `importmulti` RPCs,
+//! which import output descriptors into a Bitcoin Core wallet to watch their addresses.
+//! https://github.com/bitcoin/bitcoin/blob/master/doc/descriptors.md
+//!
+
+use crate::descriptor::{BitcoinDescriptor, DescriptorError};
+use crate::extended_public_key::BitcoinExtendedPublicKey;
+use crate::format::BitcoinFormat;
+use crate::network::BitcoinNetwork;
+use wagyu_model::no_std::*;
+use wagyu_model::KeyOrigin;
+
+use core::fmt;
+use serde_json::{json, Map, Value};
+
+/// The default end of the range of a ranged descriptor, for the 1000 addresses Bitcoin Core derives by default
+pub const DEFAULT_IMPORT_RANGE_END: u32 = 999;
+
+/// Represents the Bitcoin Core RPC which imports descriptors
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ImportRpc {
+    /// `importdescriptors`, for descriptor wallets
+    ImportDescriptors,
+    /// `importmulti`, for legacy wallets
+    ImportMulti,
+}
+
+impl fmt::Display for ImportRpc {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ImportRpc::ImportDescriptors => write!(f, "importdescriptors"),
+            ImportRpc::ImportMulti => write!(f, "importmulti"),
+        }
+    }
+}
+
+/// Represents the time of the earliest transaction of an imported descriptor,
+/// from which Bitcoin Core rescans the blockchain
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ImportTimestamp {
+    /// The time of the import, skipping the rescan, e.g. for a new wallet
+    Now,
+    /// The given time in seconds since the Unix epoch, e.g. 0 to rescan the entire blockchain
+    Time(u64),
+}
+
+impl ImportTimestamp {
+    /// Returns the timestamp as a JSON value, either `"now"` or the number of seconds.
+    fn to_json(self) -> Value {
+        match self {
+            ImportTimestamp::Now => json!("now"),
+            ImportTimestamp::Time(time) => json!(time),
+        }
+    }
+}
+
+/// Represents a request to import a descriptor into a Bitcoin Core wallet, watching its addresses
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitcoinCoreImport<N: BitcoinNetwork> {
+    /// The descriptor to import
+    descriptor: BitcoinDescriptor<N>,
+    /// The time from which to rescan the blockchain
+    timestamp: ImportTimestamp,
+    /// The inclusive range of child indices to import, if the descriptor is ranged
+    range: Option<(u32, u32)>,
+    /// If true, the addresses of the descriptor are change addresses
+    internal: bool,
+}
+
+impl<N: BitcoinNetwork> BitcoinCoreImport<N> {
+    /// Returns a request to import the descriptor, with the default range if the descriptor is ranged.
+    pub fn new(descriptor: BitcoinDescriptor<N>, timestamp: ImportTimestamp, internal: bool) -> Self {
+        let range = match descriptor.is_range() {
+            true => Some((0, DEFAULT_IMPORT_RANGE_END)),
+            false => None,
+        };
+        Self {
+            descriptor,
+            timestamp,
+            range,
+            internal,
+        }
+    }
+
+    /// Returns the requests to import the receive and change descriptors of the account
+    /// of the given extended public key, e.g. `[d34db33f/84'/0'/0']xpub.../0/*` and `.../1/*`.
+    pub fn from_account(
+        extended_public_key: &BitcoinExtendedPublicKey<N>,
+        origin: Option<KeyOrigin>,
+        format: &BitcoinFormat,
+        timestamp: ImportTimestamp,
+    ) -> Result<Vec<Self>, DescriptorError> {
+        [false, true]
+            .iter()
+            .map(|internal| {
+                let chain = *internal as u32;
+                let descriptor =
+                    BitcoinDescriptor::from_extended_public_key(extended_public_key, origin.clone(), format, chain)?;
+                Ok(Self::new(descriptor, timestamp, *internal))
+            })
+            .collect()
+    }
+
+    /// Returns the request with the given inclusive range of child indices to import,
+    /// which only applies to ranged descriptors.
+    pub fn with_range(mut self, start: u32, end: u32) -> Result<Self, DescriptorError> {
+        if !self.descriptor.is_range() || start > end || end >= 1 << 31 {
+            return Err(DescriptorError::InvalidRange(start, end));
+        }
+        self.range = Some((start, end));
+        Ok(self)
+    }
+
+    /// Returns the descriptor of the request.
+    pub fn descriptor(&self) -> &BitcoinDescriptor<N> {
+        &self.descriptor
+    }
+
+    /// Returns the request as a JSON object of the given RPC. Requests of `importdescriptors` make ranged
+    /// descriptors the active descriptors of the wallet, and requests of `importmulti` import watch-only
+    /// addresses into the keypool, which requires a wallet with private keys disabled.
+    pub fn to_json(&self, rpc: ImportRpc) -> Value {
+        let mut request = Map::new();
+        request.insert("desc".into(), json!(self.descriptor.to_string()));
+        request.insert("timestamp".into(), self.timestamp.to_json());
+        if let Some((start, end)) = self.range {
+            request.insert("range".into(), json!([start, end]));
+        }
+        request.insert("internal".into(), json!(self.internal));
+
+        match rpc {
+            ImportRpc::ImportDescriptors => {
+                if self.range.is_some() {
+                    request.insert("active".into(), json!(true));
+                }
+            }
+            ImportRpc::ImportMulti => {
+                request.insert("watchonly".into(), json!(true));
+                request.insert("keypool".into(), json!(true));
+            }
+        }
+        Value::Object(request)
+    }
+}
+
+/// Returns the JSON array of the requests for the given RPC, the argument of `bitcoin-cli importdescriptors`
+/// or `bitcoin-cli importmulti`.
+pub fn to_import_json<N: BitcoinNetwork>(requests: &[BitcoinCoreImport<N>], rpc: ImportRpc) -> Value {
+    Value::Array(requests.iter().map(|request| request.to_json(rpc)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::*;
+    use core::str::FromStr;
+
+    type N = Mainnet;
+
+    // The BIP84 account of the "abandon ... about" mnemonic, with master fingerprint 73c5da0a
+    // https://github.com/bitcoin/bips/blob/master/bip-0084.mediawiki#test-vectors
+    const ORIGIN: &str = "[73c5da0a/84'/0'/0']";
+    const ZPUB: &str = "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs";
+
+    fn account() -> Vec<BitcoinCoreImport<N>> {
+        BitcoinCoreImport::from_account(
+            &BitcoinExtendedPublicKey::from_str(ZPUB).unwrap(),
+            Some(KeyOrigin::from_str(ORIGIN).unwrap()),
+            &BitcoinFormat::Bech32,
+            ImportTimestamp::Now,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn from_account() {
+        let requests = account();
+        assert_eq!(2, requests.len());
+        assert_eq!(
+            "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu",
+            requests[0].descriptor().to_address(0).unwrap().to_string()
+        );
+        assert_eq!(
+            "bc1q8c6fshw2dlwun7ekn9qwf37cu2rn755upcp6el",
+            requests[1].descriptor().to_address(0).unwrap().to_string()
+        );
+
+        // Descriptors use the xpub serialization of the account key
+        let descriptor = requests[0].descriptor().to_string();
+        assert!(descriptor.starts_with("wpkh([73c5da0a/84'/0'/0']xpub6CatWdiZiodmUeTDp8LT5or8nmbKNcuyvz7WyksVFkKB4RHwCD3XyuvPEbvqAQY3rAPshWcMLoP2fMFMKHPJ4ZeZXYVUhLv1VMrjPC7PW6V/0/*)#"));
+    }
+
+    #[test]
+    fn import_descriptors() {
+        let requests = account();
+        let json = to_import_json(&requests, ImportRpc::ImportDescriptors);
+        assert_eq!(
+            json!([
+                {
+                    "desc": requests[0].descriptor().to_string(),
+                    "timestamp": "now",
+                    "range": [0, 999],
+                    "internal": false,
+                    "active": true,
+                },
+                {
+                    "desc": requests[1].descriptor().to_string(),
+                    "timestamp": "now",
+                    "range": [0, 999],
+                    "internal": true,
+                    "active": true,
+                },
+            ]),
+            json
+        );
+    }
+
+    #[test]
+    fn import_multi() {
+        let descriptor = BitcoinDescriptor::<N>::from_str(
+            "sh(wpkh([73c5da0a/49'/0'/0'/0/0]039b3b694b8fc5b5e07fb069c783cac754f5d38c3e08bed1960e31fdb1dda35c24))",
+        )
+        .unwrap();
+        let request = BitcoinCoreImport::new(descriptor.clone(), ImportTimestamp::Time(1_231_006_505), false);
+        assert_eq!(
+            json!({
+                "desc": descriptor.to_string(),
+                "timestamp": 1_231_006_505,
+                "internal": false,
+                "watchonly": true,
+                "keypool": true,
+            }),
+            request.to_json(ImportRpc::ImportMulti)
+        );
+    }
+
+    #[test]
+    fn with_range() {
+        let requests = account();
+        let request = requests[0].clone().with_range(100, 199).unwrap();
+        assert_eq!(
+            json!([100, 199]),
+            request.to_json(ImportRpc::ImportDescriptors)["range"]
+        );
+
+        assert!(requests[0].clone().with_range(2, 1).is_err());
+        assert!(requests[0].clone().with_range(0, 1 << 31).is_err());
+
+        let descriptor =
+            BitcoinDescriptor::<N>::from_str("tr(cc8a4bc64d897bddc5fbc2f670f7a8ba0b386779106cf1223c6fc5d7cd6fc115)")
+                .unwrap();
+        let request = BitcoinCoreImport::new(descriptor, ImportTimestamp::Now, false);
+        assert!(request.clone().with_range(0, 1).is_err());
+        assert!(request.to_json(ImportRpc::ImportDescriptors).get("active").is_none());
+    }
+}
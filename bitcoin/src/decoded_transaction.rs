@@ -0,0 +1,285 @@
+//!
+//! Decoded Transaction
+//!
+//! This module contains the decoding of a raw Bitcoin transaction, in its legacy or SegWit serialization,
+//! into its inputs, outputs, witnesses and lock time, as returned by the Bitcoin Core `decoderawtransaction` RPC.
+//! https://github.com/bitcoin/bips/blob/master/bip-0144.mediawiki
+//!
+
+use crate::address::BitcoinAddress;
+use crate::amount::BitcoinAmount;
+use crate::network::BitcoinNetwork;
+use crate::script::{BitcoinScript, ScriptType};
+use crate::transaction::{read_variable_length_integer, BitcoinTransaction};
+use wagyu_model::no_std::*;
+use wagyu_model::{Transaction, TransactionError};
+
+use core::str::FromStr;
+use serde::Serialize;
+
+/// Represents a decoded input of a Bitcoin transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DecodedTransactionInput {
+    /// The id of the transaction of the spent output
+    pub transaction_id: String,
+    /// The index of the spent output in its transaction
+    pub index: u32,
+    /// The script signature, in hex
+    pub script_sig: String,
+    /// The script signature, in its assembly form, unless it is not a valid script
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub script_sig_asm: Option<String>,
+    /// The items of the witness stack, in hex
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub witness: Vec<String>,
+    /// The sequence number
+    pub sequence: u32,
+}
+
+/// Represents a decoded output of a Bitcoin transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DecodedTransactionOutput {
+    /// The index of the output in the transaction
+    pub index: u32,
+    /// The amount (in Satoshi)
+    pub amount: BitcoinAmount,
+    /// The script public key, in hex
+    pub script_pub_key: String,
+    /// The script public key, in its assembly form, unless it is not a valid script
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub script_pub_key_asm: Option<String>,
+    /// The type of the script public key
+    pub script_type: ScriptType,
+    /// The address of the script public key, if it has one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+}
+
+/// Represents a decoded Bitcoin transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BitcoinDecodedTransaction {
+    /// The transaction id, committing to the transaction without its witnesses
+    pub transaction_id: String,
+    /// The witness transaction id, committing to the transaction with its witnesses
+    pub witness_transaction_id: String,
+    /// The version number
+    pub version: u32,
+    /// The size of the serialized transaction (in bytes)
+    pub size: u64,
+    /// The virtual size of the transaction (in virtual bytes)
+    pub virtual_size: u64,
+    /// The weight of the transaction
+    pub weight: u64,
+    /// The lock time
+    pub lock_time: u32,
+    /// If true, an input signals BIP125 replace-by-fee
+    pub replaceable: bool,
+    /// The inputs
+    pub inputs: Vec<DecodedTransactionInput>,
+    /// The outputs
+    pub outputs: Vec<DecodedTransactionOutput>,
+}
+
+impl BitcoinDecodedTransaction {
+    /// Returns the decoding of the given transaction, with the addresses of its outputs on network `N`.
+    pub fn new<N: BitcoinNetwork>(transaction: &BitcoinTransaction<N>) -> Result<Self, TransactionError> {
+        let parameters = transaction.parameters();
+        let transaction_id = transaction.to_transaction_id()?;
+        let size = transaction.size()?;
+
+        let inputs = parameters
+            .inputs
+            .iter()
+            .map(|input| {
+                let mut transaction_id = input.outpoint.reverse_transaction_id.clone();
+                transaction_id.reverse();
+
+                // The witness items are kept with the prefix of their length
+                let witness = input
+                    .witnesses
+                    .iter()
+                    .map(|item| {
+                        let mut reader = &item[..];
+                        read_variable_length_integer(&mut reader)?;
+                        Ok(hex::encode(reader))
+                    })
+                    .collect::<Result<Vec<_>, TransactionError>>()?;
+
+                Ok(DecodedTransactionInput {
+                    transaction_id: hex::encode(transaction_id),
+                    index: input.outpoint.index,
+                    script_sig: hex::encode(&input.script_sig),
+                    script_sig_asm: BitcoinScript::new(&input.script_sig).to_asm().ok(),
+                    witness,
+                    sequence: input.to_sequence(),
+                })
+            })
+            .collect::<Result<Vec<_>, TransactionError>>()?;
+
+        let outputs = parameters
+            .outputs
+            .iter()
+            .enumerate()
+            .map(|(index, output)| {
+                let script = BitcoinScript::new(&output.script_pub_key);
+                DecodedTransactionOutput {
+                    index: index as u32,
+                    amount: output.amount,
+                    script_pub_key: script.to_string(),
+                    script_pub_key_asm: script.to_asm().ok(),
+                    script_type: script.to_script_type(),
+                    address: BitcoinAddress::<N>::from_script_pub_key(&script)
+                        .ok()
+                        .map(|address| address.to_string()),
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            transaction_id: transaction_id.to_string(),
+            witness_transaction_id: transaction_id.to_witness_transaction_id(),
+            version: parameters.version,
+            size: size.size(),
+            virtual_size: size.virtual_size(),
+            weight: size.weight(),
+            lock_time: parameters.lock_time,
+            replaceable: parameters.is_replaceable(),
+            inputs,
+            outputs,
+        })
+    }
+
+    /// Returns the decoding of the given raw transaction hex, in its legacy or SegWit serialization,
+    /// with the addresses of its outputs on network `N`.
+    pub fn from_hex<N: BitcoinNetwork>(transaction_hex: &str) -> Result<Self, TransactionError> {
+        Self::new(&BitcoinTransaction::<N>::from_str(transaction_hex)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::*;
+
+    type N = Mainnet;
+
+    // A P2SH-P2WPKH input spent to P2PKH and P2WPKH outputs
+    const SEGWIT_TRANSACTION: &str = "01000000000101b62af4592179f55b13611bbd3bf023dd0698e71be7b3aee797ba7ab2af6f76a50000000017160014b5ccbe3c5a285af4afada113a8619827fb30b2eeffffffff02b8880000000000001976a91442cd2c7460acc561c96b11c4aa96d0346b84db7f88acb888000000000000160014c4242ad7193d651d51e04f0b63f9646c447bd404024730440220449ca32ff3f8da3c17c1813dac91010cb1fea7a77b2f63065184b8318e1b9ed70220315da34cfeae62c26557c40f5ac5cde46b2801349e6677fc96597b4bfee04b0b012102973e9145ca85357b06de3009a12db171d70bae8a648dc8188e49723a2a46459100000000";
+
+    // A P2PKH input spent to P2SH and P2PKH outputs
+    const LEGACY_TRANSACTION: &str = "01000000012cf96b853ef09cfd5a6266c2a0ca3522fc3f421c6dad0039c5d9532cb85e8060000000006a473044022079471aadca4be014260a4788e7dc7d7168712c8f21c536f326caccb843569ab802206c7b464e3fbe0518f147ee7c5fa39c05e04e7ed17fbe464a2773b179fe0ef35401210384faa5d9710f727523906f6d2fe781b40cf58a3139d02eeaad293dd03be7b69cffffffff02e02e00000000000017a914f7146aaa6f24a1012528c1d27cfe49d256d5a70187e02e0000000000001976a914797f9c80ef57ba7f30b31598383683923a5a7a7c88ac00000000";
+
+    #[test]
+    fn decode_segwit_transaction() {
+        let decoded = BitcoinDecodedTransaction::from_hex::<N>(SEGWIT_TRANSACTION).unwrap();
+        assert_eq!(
+            "60805eb82c53d9c53900ad6d1c423ffc2235caa0c266625afd9cf03e856bf92c",
+            decoded.transaction_id
+        );
+        assert_ne!(decoded.transaction_id, decoded.witness_transaction_id);
+        assert_eq!(1, decoded.version);
+        assert_eq!(0, decoded.lock_time);
+        assert!(!decoded.replaceable);
+        assert_eq!(SEGWIT_TRANSACTION.len() as u64 / 2, decoded.size);
+        assert_eq!(decoded.weight.div_ceil(4), decoded.virtual_size);
+        assert!(decoded.virtual_size < decoded.size);
+
+        let input = &decoded.inputs[0];
+        assert_eq!(
+            "a5766fafb27aba97e7aeb3e71be79806dd23f03bbd1b61135bf5792159f42ab6",
+            input.transaction_id
+        );
+        assert_eq!(0, input.index);
+        assert_eq!("160014b5ccbe3c5a285af4afada113a8619827fb30b2ee", input.script_sig);
+        assert_eq!(
+            Some("0014b5ccbe3c5a285af4afada113a8619827fb30b2ee".to_string()),
+            input.script_sig_asm
+        );
+        assert_eq!(2, input.witness.len());
+        assert_eq!(
+            "02973e9145ca85357b06de3009a12db171d70bae8a648dc8188e49723a2a464591",
+            input.witness[1]
+        );
+        assert!(input.witness[0].starts_with("30440220") && input.witness[0].ends_with("01"));
+        assert_eq!(0xffff_ffff, input.sequence);
+
+        assert_eq!(2, decoded.outputs.len());
+        let output = &decoded.outputs[0];
+        assert_eq!(0, output.index);
+        assert_eq!(BitcoinAmount(35000), output.amount);
+        assert_eq!(ScriptType::PubKeyHash, output.script_type);
+        assert_eq!(Some("176DPNootfp2bSiE7KQUZp1VZj5EyGQeCt".to_string()), output.address);
+        assert_eq!(
+            Some("OP_DUP OP_HASH160 42cd2c7460acc561c96b11c4aa96d0346b84db7f OP_EQUALVERIFY OP_CHECKSIG".to_string()),
+            output.script_pub_key_asm
+        );
+        let output = &decoded.outputs[1];
+        assert_eq!(1, output.index);
+        assert_eq!(ScriptType::WitnessV0KeyHash, output.script_type);
+        assert_eq!(
+            Some("bc1qcsjz44ce84j3650qfu9k87tyd3z8h4qyxz470n".to_string()),
+            output.address
+        );
+    }
+
+    #[test]
+    fn decode_legacy_transaction() {
+        let decoded = BitcoinDecodedTransaction::from_hex::<N>(LEGACY_TRANSACTION).unwrap();
+        assert_eq!(
+            "76ef90fa70e4c10adc358432a979683a2cf1855ff545f88c5022dea8863ed5ab",
+            decoded.transaction_id
+        );
+        assert_eq!(decoded.transaction_id, decoded.witness_transaction_id);
+        assert_eq!(LEGACY_TRANSACTION.len() as u64 / 2, decoded.size);
+        assert_eq!(decoded.size * 4, decoded.weight);
+        assert_eq!(decoded.size, decoded.virtual_size);
+        assert!(decoded.inputs[0].witness.is_empty());
+        assert_eq!(2, decoded.inputs[0].script_sig_asm.as_ref().unwrap().split(' ').count());
+        assert_eq!(ScriptType::ScriptHash, decoded.outputs[0].script_type);
+        assert_eq!(
+            Some("3QDTHVyuJrHixUhhsdZXQ7M8P9MQngmw1P".to_string()),
+            decoded.outputs[0].address
+        );
+
+        // The addresses are encoded for the given network
+        let decoded = BitcoinDecodedTransaction::from_hex::<Testnet>(LEGACY_TRANSACTION).unwrap();
+        assert!(decoded.outputs[1]
+            .address
+            .as_ref()
+            .unwrap()
+            .starts_with(&['m', 'n'][..]));
+    }
+
+    #[test]
+    fn decode_to_json() {
+        let decoded = BitcoinDecodedTransaction::from_hex::<N>(SEGWIT_TRANSACTION).unwrap();
+        let json = serde_json::to_value(&decoded).unwrap();
+        assert_eq!(json["version"], 1);
+        assert_eq!(json["inputs"][0]["witness"].as_array().unwrap().len(), 2);
+        assert_eq!(json["outputs"][0]["amount"], 35000);
+        assert_eq!(json["outputs"][0]["script_type"], "pubkeyhash");
+        assert_eq!(json["outputs"][1]["script_type"], "witness_v0_keyhash");
+
+        // Inputs without a witness omit it
+        let decoded = BitcoinDecodedTransaction::from_hex::<N>(LEGACY_TRANSACTION).unwrap();
+        let json = serde_json::to_value(&decoded).unwrap();
+        assert!(json["inputs"][0].get("witness").is_none());
+    }
+
+    #[test]
+    fn decode_invalid_transactions() {
+        // Truncated, with trailing bytes, and not hex
+        let truncated = &SEGWIT_TRANSACTION[..SEGWIT_TRANSACTION.len() - 10];
+        assert!(BitcoinDecodedTransaction::from_hex::<N>(truncated).is_err());
+        match BitcoinDecodedTransaction::from_hex::<N>(&format!("{}00", LEGACY_TRANSACTION)) {
+            Err(TransactionError::TrailingBytes(1)) => (),
+            result => panic!("unexpected result {:?}", result),
+        };
+        assert!(BitcoinDecodedTransaction::from_hex::<N>("zz").is_err());
+        assert!(BitcoinDecodedTransaction::from_hex::<N>("").is_err());
+
+        // An invalid SegWit flag
+        let invalid_flag = SEGWIT_TRANSACTION.replacen("01000000000101", "01000000000201", 1);
+        assert!(BitcoinDecodedTransaction::from_hex::<N>(&invalid_flag).is_err());
+    }
+}
@@ -1,8 +1,10 @@
+use crate::format::BitcoinFormat;
 use crate::network::BitcoinNetwork;
-use wagyu_model::derivation_path::{ChildIndex, DerivationPath, DerivationPathError};
+use wagyu_model::derivation_path::{ChildIndex, DerivationPath, DerivationPathError, MAX_DEPTH};
 use wagyu_model::no_std::*;
 
-use core::{convert::TryFrom, fmt, marker::PhantomData, str::FromStr};
+use core::{cmp::Ordering, convert::TryFrom, fmt, marker::PhantomData, str::FromStr};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
 /// Represents a Bitcoin derivation path
 #[derive(Clone, PartialEq, Eq)]
@@ -16,13 +18,105 @@ pub enum BitcoinDerivationPath<N: BitcoinNetwork> {
     /// BIP49 - m/49'/{0', 1'}/{account}'/{change}/{index} - SegWit Pay-to-Witness-Public-Key Hash
     /// https://github.com/bitcoin/bips/blob/master/bip-0049.mediawiki
     BIP49([ChildIndex; 3]),
+    /// BIP84 - m/84'/{0', 1'}/{account}'/{change}/{index} - SegWit Bech32 Pay-to-Witness-Public-Key Hash
+    /// https://github.com/bitcoin/bips/blob/master/bip-0084.mediawiki
+    BIP84([ChildIndex; 3]),
+    /// BIP86 - m/86'/{0', 1'}/{account}'/{change}/{index} - Taproot Pay-to-Taproot
+    /// https://github.com/bitcoin/bips/blob/master/bip-0086.mediawiki
+    BIP86([ChildIndex; 3]),
+}
+
+impl<N: BitcoinNetwork> BitcoinDerivationPath<N> {
+    /// Returns a BIP44 derivation path given the account, change, and address index.
+    /// The account is hardened, and the change and address index are normal.
+    pub fn bip44(account: u32, change: u32, index: u32) -> Result<Self, DerivationPathError> {
+        Ok(BitcoinDerivationPath::BIP44([
+            ChildIndex::hardened(account)?,
+            ChildIndex::normal(change)?,
+            ChildIndex::normal(index)?,
+        ]))
+    }
+
+    /// Returns a BIP49 derivation path given the account, change, and address index.
+    /// The account is hardened, and the change and address index are normal.
+    pub fn bip49(account: u32, change: u32, index: u32) -> Result<Self, DerivationPathError> {
+        Ok(BitcoinDerivationPath::BIP49([
+            ChildIndex::hardened(account)?,
+            ChildIndex::normal(change)?,
+            ChildIndex::normal(index)?,
+        ]))
+    }
+
+    /// Returns a BIP84 derivation path given the account, change, and address index.
+    /// The account is hardened, and the change and address index are normal.
+    pub fn bip84(account: u32, change: u32, index: u32) -> Result<Self, DerivationPathError> {
+        Ok(BitcoinDerivationPath::BIP84([
+            ChildIndex::hardened(account)?,
+            ChildIndex::normal(change)?,
+            ChildIndex::normal(index)?,
+        ]))
+    }
+
+    /// Returns a BIP86 derivation path given the account, change, and address index.
+    /// The account is hardened, and the change and address index are normal.
+    pub fn bip86(account: u32, change: u32, index: u32) -> Result<Self, DerivationPathError> {
+        Ok(BitcoinDerivationPath::BIP86([
+            ChildIndex::hardened(account)?,
+            ChildIndex::normal(change)?,
+            ChildIndex::normal(index)?,
+        ]))
+    }
+
+    /// Returns a BIP47 derivation path of the payment code of the given account,
+    /// m/47'/{0', 1'}/{account}'.
+    /// https://github.com/bitcoin/bips/blob/master/bip-0047.mediawiki#key-derivation
+    pub fn bip47(account: u32) -> Result<Self, DerivationPathError> {
+        Ok(BitcoinDerivationPath::BIP32(
+            vec![
+                ChildIndex::hardened(47)?,
+                N::HD_COIN_TYPE,
+                ChildIndex::hardened(account)?,
+            ],
+            PhantomData,
+        ))
+    }
+
+    /// Returns the standard derivation path for the given address format.
+    /// P2PKH implies BIP44, P2SH_P2WPKH implies BIP49, Bech32 implies BIP84, and P2TR implies BIP86.
+    pub fn from_format(
+        format: &BitcoinFormat,
+        account: u32,
+        change: u32,
+        index: u32,
+    ) -> Result<Self, DerivationPathError> {
+        match format {
+            BitcoinFormat::P2PKH => Self::bip44(account, change, index),
+            BitcoinFormat::P2SH_P2WPKH => Self::bip49(account, change, index),
+            BitcoinFormat::Bech32 => Self::bip84(account, change, index),
+            BitcoinFormat::P2TR => Self::bip86(account, change, index),
+            BitcoinFormat::P2WSH | BitcoinFormat::FutureWitness => Err(DerivationPathError::InvalidDerivationPath(
+                format!("no standard derivation path for {}", format),
+            )),
+        }
+    }
+
+    /// Returns the address format implied by the purpose of the derivation path.
+    pub fn to_format(&self) -> Option<BitcoinFormat> {
+        match self {
+            BitcoinDerivationPath::BIP32(..) => None,
+            BitcoinDerivationPath::BIP44(_) => Some(BitcoinFormat::P2PKH),
+            BitcoinDerivationPath::BIP49(_) => Some(BitcoinFormat::P2SH_P2WPKH),
+            BitcoinDerivationPath::BIP84(_) => Some(BitcoinFormat::Bech32),
+            BitcoinDerivationPath::BIP86(_) => Some(BitcoinFormat::P2TR),
+        }
+    }
 }
 
 impl<N: BitcoinNetwork> DerivationPath for BitcoinDerivationPath<N> {
     /// Returns a child index vector given the derivation path.
     fn to_vec(&self) -> Result<Vec<ChildIndex>, DerivationPathError> {
         match self {
-            BitcoinDerivationPath::BIP32(path, _) => match path.len() < 256 {
+            BitcoinDerivationPath::BIP32(path, _) => match path.len() <= MAX_DEPTH {
                 true => Ok(path.clone()),
                 false => Err(DerivationPathError::ExpectedBIP32Path),
             },
@@ -50,11 +144,39 @@ impl<N: BitcoinNetwork> DerivationPath for BitcoinDerivationPath<N> {
                     false => Err(DerivationPathError::ExpectedBIP49Path),
                 }
             }
+            BitcoinDerivationPath::BIP84(path) => {
+                match path[0].is_hardened() && path[1].is_normal() && path[2].is_normal() {
+                    true => Ok(vec![
+                        ChildIndex::Hardened(84),
+                        N::HD_COIN_TYPE,
+                        path[0],
+                        path[1],
+                        path[2],
+                    ]),
+                    false => Err(DerivationPathError::ExpectedBIP84Path),
+                }
+            }
+            BitcoinDerivationPath::BIP86(path) => {
+                match path[0].is_hardened() && path[1].is_normal() && path[2].is_normal() {
+                    true => Ok(vec![
+                        ChildIndex::Hardened(86),
+                        N::HD_COIN_TYPE,
+                        path[0],
+                        path[1],
+                        path[2],
+                    ]),
+                    false => Err(DerivationPathError::ExpectedBIP86Path),
+                }
+            }
         }
     }
 
     /// Returns a derivation path given the child index vector.
     fn from_vec(path: &Vec<ChildIndex>) -> Result<Self, DerivationPathError> {
+        if path.len() > MAX_DEPTH {
+            return Err(DerivationPathError::MaximumDepthExceeded(path.len()));
+        }
+
         if path.len() == 5 {
             // Path length 5 - BIP44
             if path[0] == ChildIndex::Hardened(44)
@@ -74,7 +196,25 @@ impl<N: BitcoinNetwork> DerivationPath for BitcoinDerivationPath<N> {
             {
                 return Ok(BitcoinDerivationPath::BIP49([path[2], path[3], path[4]]));
             }
-            // Path length 5 - BIP32 (non-BIP44 & non-BIP49 compliant)
+            // Path length 5 - BIP84
+            if path[0] == ChildIndex::Hardened(84)
+                && path[1] == N::HD_COIN_TYPE
+                && path[2].is_hardened()
+                && path[3].is_normal()
+                && path[4].is_normal()
+            {
+                return Ok(BitcoinDerivationPath::BIP84([path[2], path[3], path[4]]));
+            }
+            // Path length 5 - BIP86
+            if path[0] == ChildIndex::Hardened(86)
+                && path[1] == N::HD_COIN_TYPE
+                && path[2].is_hardened()
+                && path[3].is_normal()
+                && path[4].is_normal()
+            {
+                return Ok(BitcoinDerivationPath::BIP86([path[2], path[3], path[4]]));
+            }
+            // Path length 5 - BIP32 (non-BIP44, non-BIP49, non-BIP84 & non-BIP86 compliant)
             return Ok(BitcoinDerivationPath::BIP32(path.to_vec(), PhantomData));
         } else {
             // Path length 0 - BIP32 root key
@@ -115,6 +255,30 @@ impl<'a, N: BitcoinNetwork> TryFrom<&'a [ChildIndex]> for BitcoinDerivationPath<
     }
 }
 
+impl<N: BitcoinNetwork> PartialOrd for BitcoinDerivationPath<N> {
+    /// Returns the lexicographic ordering of the child index vectors of the derivation paths,
+    /// or `None` if either derivation path is invalid.
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self.to_vec(), other.to_vec()) {
+            (Ok(path), Ok(other)) => path.partial_cmp(&other),
+            _ => None,
+        }
+    }
+}
+
+impl<N: BitcoinNetwork> Serialize for BitcoinDerivationPath<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de, N: BitcoinNetwork> Deserialize<'de> for BitcoinDerivationPath<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let path = String::deserialize(deserializer)?;
+        Self::from_str(&path).map_err(de::Error::custom)
+    }
+}
+
 impl<N: BitcoinNetwork> fmt::Debug for BitcoinDerivationPath<N> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         fmt::Display::fmt(&self, f)
@@ -395,6 +559,504 @@ mod tests {
         );
     }
 
+    #[test]
+    fn bip84_mainnet() {
+        use super::*;
+
+        type N = Mainnet;
+
+        assert_eq!(
+            BitcoinDerivationPath::<N>::BIP84([ChildIndex::Hardened(0), ChildIndex::Normal(0), ChildIndex::Normal(0)]),
+            BitcoinDerivationPath::<N>::from_str("m/84'/0'/0'/0/0").unwrap()
+        );
+        assert_eq!(
+            BitcoinDerivationPath::<N>::BIP84([ChildIndex::Hardened(0), ChildIndex::Normal(0), ChildIndex::Normal(1)]),
+            BitcoinDerivationPath::<N>::from_str("m/84'/0'/0'/0/1").unwrap()
+        );
+        assert_eq!(
+            BitcoinDerivationPath::<N>::BIP84([ChildIndex::Hardened(0), ChildIndex::Normal(1), ChildIndex::Normal(0)]),
+            BitcoinDerivationPath::<N>::from_str("m/84'/0'/0'/1/0").unwrap()
+        );
+        assert_eq!(
+            BitcoinDerivationPath::<N>::BIP84([ChildIndex::Hardened(0), ChildIndex::Normal(1), ChildIndex::Normal(1)]),
+            BitcoinDerivationPath::<N>::from_str("m/84'/0'/0'/1/1").unwrap()
+        );
+        assert_eq!(
+            BitcoinDerivationPath::<N>::BIP84([ChildIndex::Hardened(1), ChildIndex::Normal(0), ChildIndex::Normal(0)]),
+            BitcoinDerivationPath::<N>::from_str("m/84'/0'/1'/0/0").unwrap()
+        );
+        assert_eq!(
+            BitcoinDerivationPath::<N>::BIP84([ChildIndex::Hardened(1), ChildIndex::Normal(0), ChildIndex::Normal(1)]),
+            BitcoinDerivationPath::<N>::from_str("m/84'/0'/1'/0/1").unwrap()
+        );
+        assert_eq!(
+            BitcoinDerivationPath::<N>::BIP84([ChildIndex::Hardened(1), ChildIndex::Normal(1), ChildIndex::Normal(0)]),
+            BitcoinDerivationPath::<N>::from_str("m/84'/0'/1'/1/0").unwrap()
+        );
+        assert_eq!(
+            BitcoinDerivationPath::<N>::BIP84([ChildIndex::Hardened(1), ChildIndex::Normal(1), ChildIndex::Normal(1)]),
+            BitcoinDerivationPath::<N>::from_str("m/84'/0'/1'/1/1").unwrap()
+        );
+    }
+
+    #[test]
+    fn bip84_testnet() {
+        use super::*;
+
+        type N = Testnet;
+
+        assert_eq!(
+            BitcoinDerivationPath::<N>::BIP84([ChildIndex::Hardened(0), ChildIndex::Normal(0), ChildIndex::Normal(0)]),
+            BitcoinDerivationPath::<N>::from_str("m/84'/1'/0'/0/0").unwrap()
+        );
+        assert_eq!(
+            BitcoinDerivationPath::<N>::BIP84([ChildIndex::Hardened(0), ChildIndex::Normal(0), ChildIndex::Normal(1)]),
+            BitcoinDerivationPath::<N>::from_str("m/84'/1'/0'/0/1").unwrap()
+        );
+        assert_eq!(
+            BitcoinDerivationPath::<N>::BIP84([ChildIndex::Hardened(0), ChildIndex::Normal(1), ChildIndex::Normal(0)]),
+            BitcoinDerivationPath::<N>::from_str("m/84'/1'/0'/1/0").unwrap()
+        );
+        assert_eq!(
+            BitcoinDerivationPath::<N>::BIP84([ChildIndex::Hardened(0), ChildIndex::Normal(1), ChildIndex::Normal(1)]),
+            BitcoinDerivationPath::<N>::from_str("m/84'/1'/0'/1/1").unwrap()
+        );
+        assert_eq!(
+            BitcoinDerivationPath::<N>::BIP84([ChildIndex::Hardened(1), ChildIndex::Normal(0), ChildIndex::Normal(0)]),
+            BitcoinDerivationPath::<N>::from_str("m/84'/1'/1'/0/0").unwrap()
+        );
+        assert_eq!(
+            BitcoinDerivationPath::<N>::BIP84([ChildIndex::Hardened(1), ChildIndex::Normal(0), ChildIndex::Normal(1)]),
+            BitcoinDerivationPath::<N>::from_str("m/84'/1'/1'/0/1").unwrap()
+        );
+        assert_eq!(
+            BitcoinDerivationPath::<N>::BIP84([ChildIndex::Hardened(1), ChildIndex::Normal(1), ChildIndex::Normal(0)]),
+            BitcoinDerivationPath::<N>::from_str("m/84'/1'/1'/1/0").unwrap()
+        );
+        assert_eq!(
+            BitcoinDerivationPath::<N>::BIP84([ChildIndex::Hardened(1), ChildIndex::Normal(1), ChildIndex::Normal(1)]),
+            BitcoinDerivationPath::<N>::from_str("m/84'/1'/1'/1/1").unwrap()
+        );
+    }
+
+    #[test]
+    fn constructors() {
+        use super::*;
+
+        type N = Mainnet;
+
+        assert_eq!(
+            BitcoinDerivationPath::<N>::from_str("m/44'/0'/1'/0/2").unwrap(),
+            BitcoinDerivationPath::<N>::bip44(1, 0, 2).unwrap()
+        );
+        assert_eq!(
+            BitcoinDerivationPath::<N>::from_str("m/49'/0'/1'/1/2").unwrap(),
+            BitcoinDerivationPath::<N>::bip49(1, 1, 2).unwrap()
+        );
+        assert_eq!(
+            BitcoinDerivationPath::<N>::from_str("m/84'/0'/0'/0/3").unwrap(),
+            BitcoinDerivationPath::<N>::bip84(0, 0, 3).unwrap()
+        );
+        assert_eq!(
+            "m/84'/1'/0'/0/0",
+            BitcoinDerivationPath::<Testnet>::bip84(0, 0, 0).unwrap().to_string()
+        );
+        assert_eq!(
+            BitcoinDerivationPath::<N>::from_str("m/86'/0'/0'/1/0").unwrap(),
+            BitcoinDerivationPath::<N>::bip86(0, 1, 0).unwrap()
+        );
+        assert_eq!(
+            "m/86'/1'/0'/0/0",
+            BitcoinDerivationPath::<Testnet>::bip86(0, 0, 0).unwrap().to_string()
+        );
+        assert_eq!(
+            Err(DerivationPathError::InvalidChildNumber(2147483648)),
+            BitcoinDerivationPath::<N>::bip44(2147483648, 0, 0)
+        );
+        assert_eq!(
+            Err(DerivationPathError::InvalidChildNumber(2147483648)),
+            BitcoinDerivationPath::<N>::bip84(0, 0, 2147483648)
+        );
+    }
+
+    #[test]
+    fn format() {
+        use super::*;
+
+        type N = Mainnet;
+
+        assert_eq!(
+            BitcoinDerivationPath::<N>::bip44(0, 0, 0).unwrap(),
+            BitcoinDerivationPath::<N>::from_format(&BitcoinFormat::P2PKH, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            BitcoinDerivationPath::<N>::bip49(0, 0, 0).unwrap(),
+            BitcoinDerivationPath::<N>::from_format(&BitcoinFormat::P2SH_P2WPKH, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            BitcoinDerivationPath::<N>::bip84(0, 0, 0).unwrap(),
+            BitcoinDerivationPath::<N>::from_format(&BitcoinFormat::Bech32, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            BitcoinDerivationPath::<N>::bip86(0, 0, 0).unwrap(),
+            BitcoinDerivationPath::<N>::from_format(&BitcoinFormat::P2TR, 0, 0, 0).unwrap()
+        );
+        assert!(BitcoinDerivationPath::<N>::from_format(&BitcoinFormat::P2WSH, 0, 0, 0).is_err());
+
+        assert_eq!(
+            None,
+            BitcoinDerivationPath::<N>::from_str("m/0'/0'/0'").unwrap().to_format()
+        );
+        assert_eq!(
+            Some(BitcoinFormat::Bech32),
+            BitcoinDerivationPath::<N>::from_str("m/84'/0'/0'/0/0")
+                .unwrap()
+                .to_format()
+        );
+        assert_eq!(
+            Some(BitcoinFormat::P2TR),
+            BitcoinDerivationPath::<N>::from_str("m/86'/0'/0'/0/0")
+                .unwrap()
+                .to_format()
+        );
+    }
+
+    #[test]
+    fn manipulation() {
+        use super::*;
+
+        type N = Mainnet;
+
+        let account = BitcoinDerivationPath::<N>::from_str("m/44'/0'/0'").unwrap();
+
+        let mut path = account.clone();
+        path.push(ChildIndex::Normal(0)).unwrap();
+        assert_eq!(BitcoinDerivationPath::<N>::from_str("m/44'/0'/0'/0").unwrap(), path);
+        assert_eq!(
+            BitcoinDerivationPath::<N>::bip44(0, 0, 5).unwrap(),
+            path.child(ChildIndex::Normal(5)).unwrap()
+        );
+
+        let mut path = account.clone();
+        path.extend(vec![ChildIndex::Normal(1), ChildIndex::Normal(2)]).unwrap();
+        assert_eq!(BitcoinDerivationPath::<N>::bip44(0, 1, 2).unwrap(), path);
+        assert_eq!(Some(ChildIndex::Hardened(44)), path.get(0));
+        assert_eq!(Some(ChildIndex::Hardened(0)), path.get(1));
+        assert_eq!(Some(ChildIndex::Normal(2)), path.get(4));
+        assert_eq!(None, path.get(5));
+
+        assert_eq!(account, path.parent().unwrap().parent().unwrap());
+        assert_eq!(
+            BitcoinDerivationPath::<N>::from_str("m").unwrap(),
+            BitcoinDerivationPath::<N>::from_str("m/0'").unwrap().parent().unwrap()
+        );
+        assert_eq!(
+            Err(DerivationPathError::MissingParentPath),
+            BitcoinDerivationPath::<N>::from_str("m").unwrap().parent()
+        );
+    }
+
+    #[test]
+    fn harden() {
+        use super::*;
+
+        type N = Mainnet;
+
+        let path = BitcoinDerivationPath::<N>::bip44(0, 1, 2).unwrap();
+        assert_eq!(
+            BitcoinDerivationPath::<N>::from_str("m/44'/0'/0'/1'/2'").unwrap(),
+            path.harden().unwrap()
+        );
+        assert_eq!(
+            BitcoinDerivationPath::<N>::from_str("m/44/0/0/1/2").unwrap(),
+            path.soften().unwrap()
+        );
+        assert_eq!(
+            BitcoinDerivationPath::<N>::from_str("m/0'/1'").unwrap(),
+            BitcoinDerivationPath::<N>::from_str("m/0/1'")
+                .unwrap()
+                .harden()
+                .unwrap()
+        );
+        assert_eq!(
+            BitcoinDerivationPath::<N>::from_str("m").unwrap(),
+            BitcoinDerivationPath::<N>::from_str("m").unwrap().soften().unwrap()
+        );
+    }
+
+    #[test]
+    fn validate_bip44() {
+        use super::*;
+
+        type N = Mainnet;
+
+        for path in [
+            "m",
+            "m/44'",
+            "m/44'/0'",
+            "m/44'/0'/0'",
+            "m/44'/0'/0'/0",
+            "m/44'/0'/0'/0/0",
+            "m/84'/0'/3'/1/9",
+        ]
+        .iter()
+        {
+            assert_eq!(
+                Ok(()),
+                BitcoinDerivationPath::<N>::from_str(path).unwrap().validate_bip44()
+            );
+        }
+        for path in ["m/44", "m/44'/0", "m/44'/0'/0", "m/44/0'/0'/0/0"].iter() {
+            assert_eq!(
+                Err(DerivationPathError::ExpectedHardenedPath),
+                BitcoinDerivationPath::<N>::from_str(path).unwrap().validate_bip44()
+            );
+        }
+        for path in ["m/44'/0'/0'/0'", "m/44'/0'/0'/0/0'"].iter() {
+            assert_eq!(
+                Err(DerivationPathError::ExpectedNormalPath),
+                BitcoinDerivationPath::<N>::from_str(path).unwrap().validate_bip44()
+            );
+        }
+        assert_eq!(
+            Err(DerivationPathError::ExpectedBIP44Path),
+            BitcoinDerivationPath::<N>::from_str("m/44'/0'/0'/0/0/0")
+                .unwrap()
+                .validate_bip44()
+        );
+    }
+
+    #[test]
+    fn prefix() {
+        use super::*;
+
+        type N = Mainnet;
+
+        let account = BitcoinDerivationPath::<N>::from_str("m/44'/0'/0'").unwrap();
+        let address = BitcoinDerivationPath::<N>::bip44(0, 1, 7).unwrap();
+
+        assert!(account.is_prefix_of(&address));
+        assert!(account.is_prefix_of(&account));
+        assert!(BitcoinDerivationPath::<N>::from_str("m")
+            .unwrap()
+            .is_prefix_of(&address));
+        assert!(!address.is_prefix_of(&account));
+        assert_eq!(
+            Some(vec![ChildIndex::Normal(1), ChildIndex::Normal(7)]),
+            address.strip_prefix(&account)
+        );
+        assert_eq!(Some(vec![]), account.strip_prefix(&account));
+        assert_eq!(None, account.strip_prefix(&address));
+
+        // Hardened and normal indices with the same number do not match
+        let normal_account = BitcoinDerivationPath::<N>::from_str("m/44'/0'/0").unwrap();
+        assert!(!normal_account.is_prefix_of(&address));
+        assert_eq!(None, address.strip_prefix(&normal_account));
+        let hardened_change = BitcoinDerivationPath::<N>::from_str("m/44'/0'/0'/1'").unwrap();
+        assert!(!hardened_change.is_prefix_of(&address));
+        assert_eq!(None, address.strip_prefix(&hardened_change));
+
+        // A different account does not match
+        let other_account = BitcoinDerivationPath::<N>::from_str("m/44'/0'/1'").unwrap();
+        assert!(!other_account.is_prefix_of(&address));
+    }
+
+    #[test]
+    fn partial_cmp() {
+        use super::*;
+
+        type N = Mainnet;
+
+        let account = BitcoinDerivationPath::<N>::from_str("m/44'/0'/0'").unwrap();
+        assert!(account < BitcoinDerivationPath::<N>::bip44(0, 0, 0).unwrap());
+        assert!(
+            BitcoinDerivationPath::<N>::bip44(0, 0, 1).unwrap() < BitcoinDerivationPath::<N>::bip44(0, 1, 0).unwrap()
+        );
+        assert!(
+            BitcoinDerivationPath::<N>::bip44(0, 1, 0).unwrap() < BitcoinDerivationPath::<N>::bip44(1, 0, 0).unwrap()
+        );
+        assert!(
+            BitcoinDerivationPath::<N>::bip44(1, 0, 0).unwrap() < BitcoinDerivationPath::<N>::bip49(0, 0, 0).unwrap()
+        );
+        // Normal indices are ordered before hardened indices
+        assert!(
+            BitcoinDerivationPath::<N>::from_str("m/2147483647").unwrap()
+                < BitcoinDerivationPath::<N>::from_str("m/0'").unwrap()
+        );
+        assert_eq!(
+            Some(Ordering::Equal),
+            account.partial_cmp(&BitcoinDerivationPath::<N>::from_str("m/44h/0h/0h").unwrap())
+        );
+    }
+
+    #[test]
+    fn to_string_alternate() {
+        use super::*;
+
+        type N = Mainnet;
+
+        let path = BitcoinDerivationPath::<N>::bip84(0, 1, 2).unwrap();
+        assert_eq!("m/84'/0'/0'/1/2", path.to_string());
+        assert_eq!("m/84h/0h/0h/1/2", format!("{:#}", path));
+        assert_eq!(
+            path,
+            BitcoinDerivationPath::<N>::from_str(&format!("{:#}", path)).unwrap()
+        );
+    }
+
+    #[test]
+    fn serde() {
+        use super::*;
+
+        type N = Mainnet;
+
+        let paths = vec![
+            BitcoinDerivationPath::<N>::bip49(0, 0, 1).unwrap(),
+            BitcoinDerivationPath::<N>::from_str("m/0'/1").unwrap(),
+        ];
+        let json = serde_json::to_string(&paths).unwrap();
+        assert_eq!("[\"m/49'/0'/0'/0/1\",\"m/0'/1\"]", json);
+        assert_eq!(
+            paths,
+            serde_json::from_str::<Vec<BitcoinDerivationPath<N>>>(&json).unwrap()
+        );
+        assert_eq!(
+            BitcoinDerivationPath::<N>::bip84(0, 0, 0).unwrap(),
+            serde_json::from_str("\"m/84h/0h/0h/0/0\"").unwrap()
+        );
+        assert!(serde_json::from_str::<BitcoinDerivationPath<N>>("\"0/1\"").is_err());
+    }
+
+    #[test]
+    fn depth() {
+        use super::*;
+
+        type N = Mainnet;
+
+        assert_eq!(Ok(0), BitcoinDerivationPath::<N>::from_str("m").unwrap().depth());
+        assert_eq!(
+            Ok(3),
+            BitcoinDerivationPath::<N>::from_str("m/0'/0'/0'").unwrap().depth()
+        );
+        assert_eq!(Ok(5), BitcoinDerivationPath::<N>::bip49(0, 0, 0).unwrap().depth());
+
+        let path = format!("m{}", "/0".repeat(255));
+        assert_eq!(Ok(255), BitcoinDerivationPath::<N>::from_str(&path).unwrap().depth());
+
+        let path = format!("m{}", "/0".repeat(256));
+        assert_eq!(
+            Err(DerivationPathError::MaximumDepthExceeded(256)),
+            BitcoinDerivationPath::<N>::from_str(&path)
+        );
+        assert_eq!(
+            Err(DerivationPathError::MaximumDepthExceeded(300)),
+            BitcoinDerivationPath::<N>::from_vec(&vec![ChildIndex::Normal(0); 300])
+        );
+    }
+
+    #[test]
+    fn from_str_strict() {
+        use super::*;
+
+        type N = Mainnet;
+
+        for path in ["m", "m/0", "m/44'/0'/0'/0/10", "m/84h/0h/0h/1/0", "m/2147483647'/0"].iter() {
+            assert_eq!(
+                BitcoinDerivationPath::<N>::from_str(path).unwrap(),
+                BitcoinDerivationPath::<N>::from_str_strict(path).unwrap()
+            );
+        }
+
+        assert_eq!(
+            Err(DerivationPathError::InvalidPathComponent(1, "007".into())),
+            BitcoinDerivationPath::<N>::from_str_strict("m/007")
+        );
+        assert_eq!(
+            Err(DerivationPathError::InvalidPathComponent(4, "00".into())),
+            BitcoinDerivationPath::<N>::from_str_strict("m/44'/0'/0'/00/0")
+        );
+        assert_eq!(
+            Err(DerivationPathError::InvalidPathComponent(1, "".into())),
+            BitcoinDerivationPath::<N>::from_str_strict("m/")
+        );
+        assert_eq!(
+            Err(DerivationPathError::PathComponentOverflow(5, "4294967296".into())),
+            BitcoinDerivationPath::<N>::from_str_strict("m/44'/0'/0'/0/4294967296")
+        );
+        assert_eq!(
+            Err(DerivationPathError::InvalidDerivationPath("0/1".into())),
+            BitcoinDerivationPath::<N>::from_str_strict("0/1")
+        );
+    }
+
+    #[test]
+    fn coin_type() {
+        use super::*;
+        use wagyu_model::coin_type::CoinType;
+
+        assert_eq!(
+            Ok(CoinType::Bitcoin),
+            BitcoinDerivationPath::<Mainnet>::bip84(0, 0, 0).unwrap().coin_type()
+        );
+        assert_eq!(
+            Ok(CoinType::Testnet),
+            BitcoinDerivationPath::<Testnet>::bip49(0, 0, 0).unwrap().coin_type()
+        );
+        assert_eq!(
+            Err(DerivationPathError::MissingCoinType),
+            BitcoinDerivationPath::<Mainnet>::from_str("m/0'/0'")
+                .unwrap()
+                .coin_type()
+        );
+        assert_eq!(
+            Err(DerivationPathError::UnexpectedCoinType(
+                CoinType::Bitcoin,
+                CoinType::Ethereum
+            )),
+            BitcoinDerivationPath::<Mainnet>::from_str("m/44'/60'/0'/0/0")
+                .unwrap()
+                .validate_coin_type(CoinType::Bitcoin)
+        );
+    }
+
+    #[test]
+    fn range() {
+        use super::*;
+        use wagyu_model::derivation_path::DerivationPathRange;
+
+        type N = Mainnet;
+
+        let paths: Vec<BitcoinDerivationPath<N>> = DerivationPathRange::from_str("m/44'/0'/0'/0-1/0-2")
+            .unwrap()
+            .paths()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        let expected: Vec<BitcoinDerivationPath<N>> = vec![(0, 0), (0, 1), (0, 2), (1, 0), (1, 1), (1, 2)]
+            .into_iter()
+            .map(|(change, index)| BitcoinDerivationPath::bip44(0, change, index).unwrap())
+            .collect();
+        assert_eq!(expected, paths);
+
+        let mut paths = DerivationPathRange::from_str("m/84'/0'/0'/0/*")
+            .unwrap()
+            .paths::<BitcoinDerivationPath<N>>();
+        assert_eq!(BitcoinDerivationPath::bip84(0, 0, 0), paths.next().unwrap());
+        assert_eq!(BitcoinDerivationPath::bip84(0, 0, 1), paths.next().unwrap());
+
+        let paths: Vec<BitcoinDerivationPath<N>> = DerivationPathRange::from_str("m/84'/0'/0'/0/2147483646-2147483647")
+            .unwrap()
+            .paths()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            vec![
+                BitcoinDerivationPath::bip84(0, 0, 2147483646).unwrap(),
+                BitcoinDerivationPath::bip84(0, 0, 2147483647).unwrap()
+            ],
+            paths
+        );
+    }
+
     #[test]
     fn valid_path() {
         use super::*;
@@ -0,0 +1,669 @@
+//!
+//! Descriptor
+//!
+//! This module contains the output script descriptors of Bitcoin wallets, which describe
+//! the scriptPubKeys, and thereby the addresses, of a wallet in a single string.
+//! Supported are `wpkh`, `sh(wpkh)`, `wsh(multi)`, `wsh(sortedmulti)`, and key path `tr` descriptors,
+//! with key origins, extended public keys, and wildcard ranges.
+//! https://github.com/bitcoin/bips/blob/master/bip-0380.mediawiki
+//!
+
+use crate::address::BitcoinAddress;
+use crate::derivation_path::BitcoinDerivationPath;
+use crate::extended_public_key::BitcoinExtendedPublicKey;
+use crate::format::BitcoinFormat;
+use crate::multisig::{BitcoinMultisig, MAX_MULTISIG_PUBLIC_KEYS};
+use crate::network::BitcoinNetwork;
+use crate::public_key::BitcoinPublicKey;
+use wagyu_model::no_std::*;
+use wagyu_model::{
+    AddressError, ChildIndex, ChildIndexRange, DerivationPathError, ExtendedPublicKey, ExtendedPublicKeyError,
+    KeyOrigin, PublicKey, PublicKeyError,
+};
+
+use core::{convert::TryFrom, fmt, str::FromStr};
+
+/// The character set of descriptors, ordered for checksum computation
+const INPUT_CHARSET: &str =
+    "0123456789()[],'/*abcdefgh@:$%{}IJKLMNOPQRSTUVWXYZ&+-.;<=>?!^_|~ijklmnopqrstuvwxyzABCDEFGH`#\"\\ ";
+/// The character set of descriptor checksums
+const CHECKSUM_CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+/// The descriptor checksum generator coefficients
+const GENERATOR: [u64; 5] = [
+    0xf5_dee5_1989,
+    0xa9_fdca_3312,
+    0x1b_ab10_e32d,
+    0x37_06b1_677a,
+    0x64_4d62_6ffd,
+];
+/// The length of a descriptor checksum
+const CHECKSUM_LENGTH: usize = 8;
+
+/// Returns the checksum of the descriptor, as defined in BIP380.
+pub fn descriptor_checksum(descriptor: &str) -> Result<String, DescriptorError> {
+    let mut symbols = vec![];
+    let mut groups = vec![];
+    for c in descriptor.chars() {
+        let value = match INPUT_CHARSET.find(c) {
+            Some(value) => value as u64,
+            None => return Err(DescriptorError::InvalidCharacter(c)),
+        };
+        symbols.push(value & 31);
+        groups.push(value >> 5);
+        if groups.len() == 3 {
+            symbols.push(groups[0] * 9 + groups[1] * 3 + groups[2]);
+            groups.clear();
+        }
+    }
+    match groups.len() {
+        1 => symbols.push(groups[0]),
+        2 => symbols.push(groups[0] * 3 + groups[1]),
+        _ => (),
+    };
+    symbols.extend_from_slice(&[0; CHECKSUM_LENGTH]);
+
+    let checksum = polymod(&symbols) ^ 1;
+    Ok((0..CHECKSUM_LENGTH)
+        .map(|i| CHECKSUM_CHARSET[((checksum >> (5 * (CHECKSUM_LENGTH - 1 - i))) & 31) as usize] as char)
+        .collect())
+}
+
+/// Returns the BCH checksum of the symbols.
+fn polymod(symbols: &[u64]) -> u64 {
+    symbols.iter().fold(1u64, |checksum, value| {
+        let top = checksum >> 35;
+        let checksum = ((checksum & 0x07_ffff_ffff) << 5) ^ value;
+        GENERATOR
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| (top >> i) & 1 == 1)
+            .fold(checksum, |checksum, (_, generator)| checksum ^ generator)
+    })
+}
+
+/// Returns the arguments of the descriptor function with the given name, e.g. `KEY` for `wpkh(KEY)`.
+fn arguments<'a>(descriptor: &'a str, name: &str) -> Option<&'a str> {
+    match descriptor.starts_with(name) && descriptor[name.len()..].starts_with('(') && descriptor.ends_with(')') {
+        true => Some(&descriptor[name.len() + 1..descriptor.len() - 1]),
+        false => None,
+    }
+}
+
+/// Represents a key expression of a Bitcoin output descriptor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BitcoinDescriptorKey<N: BitcoinNetwork> {
+    /// A compressed public key, e.g. `[d34db33f/44'/0'/0']03a34b...`
+    Single {
+        origin: Option<KeyOrigin>,
+        public_key: BitcoinPublicKey<N>,
+    },
+    /// An x-only public key, valid in `tr` descriptors, with the even public key of its x-coordinate
+    XOnly {
+        origin: Option<KeyOrigin>,
+        public_key: BitcoinPublicKey<N>,
+    },
+    /// An extended public key, the normal derivation path to its children, and whether it ends in a wildcard,
+    /// e.g. `[d34db33f/84'/0'/0']xpub.../0/*`
+    Extended {
+        origin: Option<KeyOrigin>,
+        extended_public_key: BitcoinExtendedPublicKey<N>,
+        path: Vec<ChildIndex>,
+        wildcard: bool,
+    },
+}
+
+impl<N: BitcoinNetwork> BitcoinDescriptorKey<N> {
+    /// Returns the key origin, if specified.
+    pub fn origin(&self) -> Option<&KeyOrigin> {
+        match self {
+            BitcoinDescriptorKey::Single { origin, .. }
+            | BitcoinDescriptorKey::XOnly { origin, .. }
+            | BitcoinDescriptorKey::Extended { origin, .. } => origin.as_ref(),
+        }
+    }
+
+    /// Returns `true` if the key ends in a wildcard, deriving a child for each index.
+    pub fn is_wildcard(&self) -> bool {
+        match self {
+            BitcoinDescriptorKey::Extended { wildcard, .. } => *wildcard,
+            _ => false,
+        }
+    }
+
+    /// Returns the public key at the given index, which only applies to keys ending in a wildcard.
+    pub fn to_public_key(&self, index: u32) -> Result<BitcoinPublicKey<N>, DescriptorError> {
+        match self {
+            BitcoinDescriptorKey::Single { public_key, .. } | BitcoinDescriptorKey::XOnly { public_key, .. } => {
+                Ok(public_key.clone())
+            }
+            BitcoinDescriptorKey::Extended {
+                extended_public_key,
+                path,
+                wildcard,
+                ..
+            } => {
+                let mut path = path.clone();
+                if *wildcard {
+                    path.push(ChildIndex::normal(index)?);
+                }
+                let path = BitcoinDerivationPath::<N>::try_from(path)?;
+                Ok(extended_public_key.derive(&path)?.to_public_key())
+            }
+        }
+    }
+
+    /// Returns the key expression, allowing x-only public keys if specified.
+    fn parse(key: &str, allow_x_only: bool) -> Result<Self, DescriptorError> {
+        let invalid = || DescriptorError::InvalidKey(key.to_string());
+
+        let (origin, key) = match key.starts_with('[') {
+            true => match key.find(']') {
+                Some(end) => (Some(KeyOrigin::from_str(&key[..=end])?), &key[end + 1..]),
+                None => return Err(invalid()),
+            },
+            false => (None, key),
+        };
+
+        if !key.is_empty() && key.chars().all(|c| c.is_ascii_hexdigit()) {
+            return match key.len() {
+                66 => Ok(BitcoinDescriptorKey::Single {
+                    origin,
+                    public_key: BitcoinPublicKey::from_str(key)?,
+                }),
+                64 if allow_x_only => Ok(BitcoinDescriptorKey::XOnly {
+                    origin,
+                    public_key: BitcoinPublicKey::from_str(&format!("02{}", key))?,
+                }),
+                _ => Err(invalid()),
+            };
+        }
+
+        let mut components = key.split('/').collect::<Vec<&str>>();
+        let extended_public_key = BitcoinExtendedPublicKey::<N>::from_str(components.remove(0))?;
+        let wildcard = match components.last() {
+            Some(&"*") => components.pop().is_some(),
+            Some(&"*'") | Some(&"*h") => return Err(DescriptorError::HardenedDerivation(key.to_string())),
+            _ => false,
+        };
+
+        let path = components
+            .iter()
+            .map(|component| ChildIndex::from_str(component))
+            .collect::<Result<Vec<ChildIndex>, DerivationPathError>>()?;
+        if path.iter().any(ChildIndex::is_hardened) {
+            return Err(DescriptorError::HardenedDerivation(key.to_string()));
+        }
+
+        Ok(BitcoinDescriptorKey::Extended {
+            origin,
+            extended_public_key,
+            path,
+            wildcard,
+        })
+    }
+}
+
+impl<N: BitcoinNetwork> FromStr for BitcoinDescriptorKey<N> {
+    type Err = DescriptorError;
+
+    fn from_str(key: &str) -> Result<Self, Self::Err> {
+        Self::parse(key, false)
+    }
+}
+
+impl<N: BitcoinNetwork> fmt::Display for BitcoinDescriptorKey<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(origin) = self.origin() {
+            write!(f, "{}", origin)?;
+        }
+        match self {
+            BitcoinDescriptorKey::Single { public_key, .. } => write!(f, "{}", public_key),
+            BitcoinDescriptorKey::XOnly { public_key, .. } => {
+                write!(f, "{}", hex::encode(public_key.to_x_only_public_key()))
+            }
+            BitcoinDescriptorKey::Extended {
+                extended_public_key,
+                path,
+                wildcard,
+                ..
+            } => {
+                write!(f, "{}", extended_public_key)?;
+                for index in path {
+                    write!(f, "/{}", index)?;
+                }
+                match wildcard {
+                    true => write!(f, "/*"),
+                    false => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+/// Represents a Bitcoin output script descriptor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BitcoinDescriptor<N: BitcoinNetwork> {
+    /// `wpkh(KEY)`, a P2WPKH output
+    Wpkh(BitcoinDescriptorKey<N>),
+    /// `sh(wpkh(KEY))`, a P2SH-P2WPKH output
+    ShWpkh(BitcoinDescriptorKey<N>),
+    /// `wsh(multi(k,KEY,...))`, or `wsh(sortedmulti(k,KEY,...))` if sorted, a P2WSH multisig output
+    WshMulti {
+        threshold: usize,
+        keys: Vec<BitcoinDescriptorKey<N>>,
+        sorted: bool,
+    },
+    /// `tr(KEY)`, a P2TR output spendable by its key path only
+    Tr(BitcoinDescriptorKey<N>),
+}
+
+impl<N: BitcoinNetwork> BitcoinDescriptor<N> {
+    /// Returns the descriptor of the addresses of the given format on a chain of an account,
+    /// e.g. `wpkh([d34db33f/84'/0'/0']xpub.../0/*)` for the receive chain of a BIP84 account.
+    /// The extended public key is given by its xpub serialization, as its format is given by the descriptor.
+    pub fn from_extended_public_key(
+        extended_public_key: &BitcoinExtendedPublicKey<N>,
+        origin: Option<KeyOrigin>,
+        format: &BitcoinFormat,
+        chain: u32,
+    ) -> Result<Self, DescriptorError> {
+        let key = BitcoinDescriptorKey::Extended {
+            origin,
+            extended_public_key: extended_public_key.with_format(&BitcoinFormat::P2PKH),
+            path: vec![ChildIndex::normal(chain)?],
+            wildcard: true,
+        };
+        match format {
+            BitcoinFormat::Bech32 => Ok(BitcoinDescriptor::Wpkh(key)),
+            BitcoinFormat::P2SH_P2WPKH => Ok(BitcoinDescriptor::ShWpkh(key)),
+            BitcoinFormat::P2TR => Ok(BitcoinDescriptor::Tr(key)),
+            format => Err(DescriptorError::UnsupportedFormat(format.to_string())),
+        }
+    }
+
+    /// Returns the key expressions of the descriptor.
+    pub fn keys(&self) -> Vec<&BitcoinDescriptorKey<N>> {
+        match self {
+            BitcoinDescriptor::Wpkh(key) | BitcoinDescriptor::ShWpkh(key) | BitcoinDescriptor::Tr(key) => vec![key],
+            BitcoinDescriptor::WshMulti { keys, .. } => keys.iter().collect(),
+        }
+    }
+
+    /// Returns `true` if the descriptor describes a range of addresses, by a key ending in a wildcard.
+    pub fn is_range(&self) -> bool {
+        self.keys().iter().any(|key| key.is_wildcard())
+    }
+
+    /// Returns the format of the addresses of the descriptor.
+    pub fn format(&self) -> BitcoinFormat {
+        match self {
+            BitcoinDescriptor::Wpkh(_) => BitcoinFormat::Bech32,
+            BitcoinDescriptor::ShWpkh(_) => BitcoinFormat::P2SH_P2WPKH,
+            BitcoinDescriptor::WshMulti { .. } => BitcoinFormat::P2WSH,
+            BitcoinDescriptor::Tr(_) => BitcoinFormat::P2TR,
+        }
+    }
+
+    /// Returns the address at the given index, which only applies to descriptors with a wildcard.
+    pub fn to_address(&self, index: u32) -> Result<BitcoinAddress<N>, DescriptorError> {
+        match self {
+            BitcoinDescriptor::Wpkh(key) | BitcoinDescriptor::ShWpkh(key) | BitcoinDescriptor::Tr(key) => {
+                Ok(key.to_public_key(index)?.to_address(&self.format())?)
+            }
+            BitcoinDescriptor::WshMulti {
+                threshold,
+                keys,
+                sorted,
+            } => {
+                let public_keys = keys
+                    .iter()
+                    .map(|key| key.to_public_key(index))
+                    .collect::<Result<Vec<BitcoinPublicKey<N>>, DescriptorError>>()?;
+                let multisig = match sorted {
+                    true => BitcoinMultisig::new(*threshold, &public_keys)?,
+                    false => BitcoinMultisig::with_key_order(*threshold, &public_keys)?,
+                };
+                Ok(multisig.to_p2wsh_address()?)
+            }
+        }
+    }
+
+    /// Returns the addresses for the given range of normal child indices, e.g. `0..=19` for a gap limit scan.
+    pub fn to_addresses(&self, range: ChildIndexRange) -> Result<Vec<BitcoinAddress<N>>, DescriptorError> {
+        range
+            .map(|index| match index {
+                ChildIndex::Normal(index) => self.to_address(index),
+                ChildIndex::Hardened(_) => Err(DescriptorError::HardenedDerivation(index.to_string())),
+            })
+            .collect()
+    }
+
+    /// Returns the descriptor without its checksum.
+    fn to_descriptor_string(&self) -> String {
+        match self {
+            BitcoinDescriptor::Wpkh(key) => format!("wpkh({})", key),
+            BitcoinDescriptor::ShWpkh(key) => format!("sh(wpkh({}))", key),
+            BitcoinDescriptor::WshMulti {
+                threshold,
+                keys,
+                sorted,
+            } => format!(
+                "wsh({}({},{}))",
+                if *sorted { "sortedmulti" } else { "multi" },
+                threshold,
+                keys.iter()
+                    .map(|key| key.to_string())
+                    .collect::<Vec<String>>()
+                    .join(",")
+            ),
+            BitcoinDescriptor::Tr(key) => format!("tr({})", key),
+        }
+    }
+}
+
+impl<N: BitcoinNetwork> FromStr for BitcoinDescriptor<N> {
+    type Err = DescriptorError;
+
+    /// Returns the descriptor, verifying its checksum if one is appended.
+    fn from_str(descriptor: &str) -> Result<Self, Self::Err> {
+        let descriptor = match descriptor.rfind('#') {
+            Some(position) => {
+                let (descriptor, checksum) = (&descriptor[..position], &descriptor[position + 1..]);
+                let expected = descriptor_checksum(descriptor)?;
+                if expected != checksum {
+                    return Err(DescriptorError::InvalidChecksum(expected, checksum.to_string()));
+                }
+                descriptor
+            }
+            None => descriptor,
+        };
+        let invalid = || DescriptorError::InvalidDescriptor(descriptor.to_string());
+
+        if let Some(key) = arguments(descriptor, "wpkh") {
+            Ok(BitcoinDescriptor::Wpkh(BitcoinDescriptorKey::parse(key, false)?))
+        } else if let Some(key) = arguments(descriptor, "sh").and_then(|script| arguments(script, "wpkh")) {
+            Ok(BitcoinDescriptor::ShWpkh(BitcoinDescriptorKey::parse(key, false)?))
+        } else if let Some(script) = arguments(descriptor, "wsh") {
+            let (multi, sorted) = match (arguments(script, "multi"), arguments(script, "sortedmulti")) {
+                (Some(multi), _) => (multi, false),
+                (_, Some(multi)) => (multi, true),
+                _ => return Err(invalid()),
+            };
+
+            let mut multi = multi.split(',');
+            let threshold = multi
+                .next()
+                .and_then(|threshold| threshold.parse::<usize>().ok())
+                .ok_or_else(invalid)?;
+            let keys = multi
+                .map(|key| BitcoinDescriptorKey::parse(key, false))
+                .collect::<Result<Vec<BitcoinDescriptorKey<N>>, DescriptorError>>()?;
+            if threshold == 0 || threshold > keys.len() || keys.len() > MAX_MULTISIG_PUBLIC_KEYS {
+                return Err(invalid());
+            }
+
+            Ok(BitcoinDescriptor::WshMulti {
+                threshold,
+                keys,
+                sorted,
+            })
+        } else if let Some(key) = arguments(descriptor, "tr") {
+            // Script path spends, given as a script tree after the key, are not supported
+            if key.contains(',') {
+                return Err(invalid());
+            }
+            Ok(BitcoinDescriptor::Tr(BitcoinDescriptorKey::parse(key, true)?))
+        } else {
+            Err(invalid())
+        }
+    }
+}
+
+impl<N: BitcoinNetwork> fmt::Display for BitcoinDescriptor<N> {
+    /// Returns the descriptor followed by its checksum.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let descriptor = self.to_descriptor_string();
+        match descriptor_checksum(&descriptor) {
+            Ok(checksum) => write!(f, "{}#{}", descriptor, checksum),
+            Err(_) => Err(fmt::Error),
+        }
+    }
+}
+
+#[derive(Debug, Fail)]
+pub enum DescriptorError {
+    #[fail(display = "{}", _0)]
+    AddressError(#[cause] AddressError),
+
+    #[fail(display = "{}", _0)]
+    DerivationPathError(#[cause] DerivationPathError),
+
+    #[fail(display = "{}", _0)]
+    ExtendedPublicKeyError(#[cause] ExtendedPublicKeyError),
+
+    #[fail(display = "hardened derivation requires a private key: {}", _0)]
+    HardenedDerivation(String),
+
+    #[fail(display = "invalid descriptor character: {}", _0)]
+    InvalidCharacter(char),
+
+    #[fail(display = "invalid descriptor checksum: {{ expected: {:?}, found: {:?} }}", _0, _1)]
+    InvalidChecksum(String, String),
+
+    #[fail(display = "invalid descriptor: {}", _0)]
+    InvalidDescriptor(String),
+
+    #[fail(display = "invalid descriptor key: {}", _0)]
+    InvalidKey(String),
+
+    #[fail(display = "invalid descriptor range: {{ start: {:?}, end: {:?} }}", _0, _1)]
+    InvalidRange(u32, u32),
+
+    #[fail(display = "{}", _0)]
+    PublicKeyError(#[cause] PublicKeyError),
+
+    #[fail(display = "unsupported descriptor format: {}", _0)]
+    UnsupportedFormat(String),
+}
+
+impl From<AddressError> for DescriptorError {
+    fn from(error: AddressError) -> Self {
+        DescriptorError::AddressError(error)
+    }
+}
+
+impl From<DerivationPathError> for DescriptorError {
+    fn from(error: DerivationPathError) -> Self {
+        DescriptorError::DerivationPathError(error)
+    }
+}
+
+impl From<ExtendedPublicKeyError> for DescriptorError {
+    fn from(error: ExtendedPublicKeyError) -> Self {
+        DescriptorError::ExtendedPublicKeyError(error)
+    }
+}
+
+impl From<PublicKeyError> for DescriptorError {
+    fn from(error: PublicKeyError) -> Self {
+        DescriptorError::PublicKeyError(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::*;
+
+    type N = Mainnet;
+
+    // The account keys of the "abandon ... about" mnemonic, with master fingerprint 73c5da0a
+    // https://github.com/bitcoin/bips/blob/master/bip-0084.mediawiki#test-vectors
+    const BIP84_ACCOUNT: &str = "[73c5da0a/84'/0'/0']xpub6CatWdiZiodmUeTDp8LT5or8nmbKNcuyvz7WyksVFkKB4RHwCD3XyuvPEbvqAQY3rAPshWcMLoP2fMFMKHPJ4ZeZXYVUhLv1VMrjPC7PW6V";
+    // https://github.com/bitcoin/bips/blob/master/bip-0086.mediawiki#test-vectors
+    const BIP86_ACCOUNT: &str = "[73c5da0a/86'/0'/0']xpub6BgBgsespWvERF3LHQu6CnqdvfEvtMcQjYrcRzx53QJjSxarj2afYWcLteoGVky7D3UKDP9QyrLprQ3VCECoY49yfdDEHGCtMMj92pReUsQ";
+
+    // https://github.com/bitcoin/bips/blob/master/bip-0067.mediawiki#test-vectors
+    const MULTISIG_KEYS: [&str; 3] = [
+        "02e2cc6bd5f45edd43bebe7cb9b675f0ce9ed3efe613b177588290ad188d11b404",
+        "027735a29bae7780a9755fae7a1c4374c656ac6a69ea9f3697fda61bb99a4f3e77",
+        "02632b12f4ac5b1d1b72b2a3b508c19172de44f6f46bcee50ba33f3f9291e47ed0",
+    ];
+
+    #[test]
+    fn checksum() {
+        // https://github.com/bitcoin/bips/blob/master/bip-0380.mediawiki#test-vectors
+        assert_eq!("89f8spxm", descriptor_checksum("raw(deadbeef)").unwrap());
+        assert_eq!(
+            "02wpgw69",
+            descriptor_checksum("addr(mkmZxiEcEd8ZqjQWVZuC6so5dFMKEFpN2j)").unwrap()
+        );
+        assert!(descriptor_checksum("wpkh(\u{e9})").is_err());
+    }
+
+    #[test]
+    fn wpkh() {
+        let receive = BitcoinDescriptor::<N>::from_str(&format!("wpkh({}/0/*)", BIP84_ACCOUNT)).unwrap();
+        let change = BitcoinDescriptor::<N>::from_str(&format!("wpkh({}/1/*)", BIP84_ACCOUNT)).unwrap();
+        assert!(receive.is_range());
+        assert_eq!(
+            "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu",
+            receive.to_address(0).unwrap().to_string()
+        );
+        assert_eq!(
+            "bc1qnjg0jd8228aq7egyzacy8cys3knf9xvrerkf9g",
+            receive.to_address(1).unwrap().to_string()
+        );
+        assert_eq!(
+            "bc1q8c6fshw2dlwun7ekn9qwf37cu2rn755upcp6el",
+            change.to_address(0).unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn sh_wpkh() {
+        let descriptor = BitcoinDescriptor::<N>::from_str(
+            "sh(wpkh([73c5da0a/49'/0'/0'/0/0]039b3b694b8fc5b5e07fb069c783cac754f5d38c3e08bed1960e31fdb1dda35c24))",
+        )
+        .unwrap();
+        assert!(!descriptor.is_range());
+        assert_eq!(
+            "37VucYSaXLCAsxYyAPfbSi9eh4iEcbShgf",
+            descriptor.to_address(0).unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn wsh_multi() {
+        let multi = BitcoinDescriptor::<N>::from_str(&format!("wsh(multi(2,{}))", MULTISIG_KEYS.join(","))).unwrap();
+        let sorted =
+            BitcoinDescriptor::<N>::from_str(&format!("wsh(sortedmulti(2,{}))", MULTISIG_KEYS.join(","))).unwrap();
+        assert_eq!(
+            "bc1qud6dmdcc27eg8s5hsy6a075gs49w65l6xtc4cplp6m2d4ggh43wqew2vqs",
+            sorted.to_address(0).unwrap().to_string()
+        );
+        assert_ne!(sorted.to_address(0).unwrap(), multi.to_address(0).unwrap());
+
+        let public_keys = MULTISIG_KEYS
+            .iter()
+            .map(|public_key| BitcoinPublicKey::<N>::from_str(public_key).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            BitcoinMultisig::with_key_order(2, &public_keys)
+                .unwrap()
+                .to_p2wsh_address()
+                .unwrap(),
+            multi.to_address(0).unwrap()
+        );
+    }
+
+    #[test]
+    fn tr() {
+        let descriptor = BitcoinDescriptor::<N>::from_str(&format!("tr({}/0/*)", BIP86_ACCOUNT)).unwrap();
+        let addresses = descriptor
+            .to_addresses(ChildIndexRange::from_count(ChildIndex::Normal(0), 2).unwrap())
+            .unwrap();
+        assert_eq!(
+            vec![
+                "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr",
+                "bc1p4qhjn9zdvkux4e44uhx8tc55attvtyu358kutcqkudyccelu0was9fqzwh",
+            ],
+            addresses.iter().map(|address| address.to_string()).collect::<Vec<_>>()
+        );
+
+        let x_only =
+            BitcoinDescriptor::<N>::from_str("tr(cc8a4bc64d897bddc5fbc2f670f7a8ba0b386779106cf1223c6fc5d7cd6fc115)")
+                .unwrap();
+        assert_eq!(addresses[0], x_only.to_address(0).unwrap());
+    }
+
+    #[test]
+    fn from_extended_public_key() {
+        let (origin, key) = BIP84_ACCOUNT.split_at(BIP84_ACCOUNT.find(']').unwrap() + 1);
+        let origin = KeyOrigin::from_str(origin).unwrap();
+        let key = BitcoinExtendedPublicKey::<N>::from_str(key)
+            .unwrap()
+            .with_format(&BitcoinFormat::Bech32);
+
+        for chain in 0..2 {
+            let expected = BitcoinDescriptor::<N>::from_str(&format!("wpkh({}/{}/*)", BIP84_ACCOUNT, chain)).unwrap();
+            let descriptor =
+                BitcoinDescriptor::from_extended_public_key(&key, Some(origin.clone()), &BitcoinFormat::Bech32, chain)
+                    .unwrap();
+            assert_eq!(expected.to_string(), descriptor.to_string());
+            assert_eq!(expected.to_address(0).unwrap(), descriptor.to_address(0).unwrap());
+        }
+
+        let descriptor = BitcoinDescriptor::from_extended_public_key(&key, None, &BitcoinFormat::P2TR, 0).unwrap();
+        assert_eq!(BitcoinFormat::P2TR, descriptor.format());
+        assert!(descriptor.keys()[0].origin().is_none());
+
+        assert!(BitcoinDescriptor::from_extended_public_key(&key, None, &BitcoinFormat::P2PKH, 0).is_err());
+        assert!(BitcoinDescriptor::from_extended_public_key(&key, None, &BitcoinFormat::P2WSH, 0).is_err());
+    }
+
+    #[test]
+    fn to_string() {
+        let descriptors = [
+            format!("wpkh({}/0/*)", BIP84_ACCOUNT),
+            format!("wsh(sortedmulti(2,{}))", MULTISIG_KEYS.join(",")),
+            format!("tr({}/1/*)", BIP86_ACCOUNT),
+            "tr(cc8a4bc64d897bddc5fbc2f670f7a8ba0b386779106cf1223c6fc5d7cd6fc115)".to_string(),
+        ];
+        descriptors.iter().for_each(|descriptor| {
+            let expected = format!("{}#{}", descriptor, descriptor_checksum(descriptor).unwrap());
+            let parsed = BitcoinDescriptor::<N>::from_str(&expected).unwrap();
+            assert_eq!(expected, parsed.to_string());
+        });
+    }
+
+    #[test]
+    fn invalid_checksum() {
+        let descriptor = format!("wpkh({}/0/*)", BIP84_ACCOUNT);
+        let checksum = descriptor_checksum(&descriptor).unwrap();
+        let mut invalid = checksum.clone().into_bytes();
+        invalid[0] = if invalid[0] == b'q' { b'p' } else { b'q' };
+        let invalid = String::from_utf8(invalid).unwrap();
+
+        assert!(BitcoinDescriptor::<N>::from_str(&format!("{}#{}", descriptor, checksum)).is_ok());
+        assert!(BitcoinDescriptor::<N>::from_str(&format!("{}#{}", descriptor, invalid)).is_err());
+        assert!(BitcoinDescriptor::<N>::from_str(&format!("{}#", descriptor)).is_err());
+    }
+
+    #[test]
+    fn invalid_descriptor() {
+        let descriptors = [
+            "pkh(039b3b694b8fc5b5e07fb069c783cac754f5d38c3e08bed1960e31fdb1dda35c24)".to_string(),
+            "wpkh(cc8a4bc64d897bddc5fbc2f670f7a8ba0b386779106cf1223c6fc5d7cd6fc115)".to_string(),
+            format!("wpkh({}/0/*')", BIP84_ACCOUNT),
+            format!("wpkh({}/0'/*)", BIP84_ACCOUNT),
+            format!("wsh(multi(3,{},{}))", MULTISIG_KEYS[0], MULTISIG_KEYS[1]),
+            format!("wsh(multi(0,{}))", MULTISIG_KEYS[0]),
+            format!("tr({},pk({}))", MULTISIG_KEYS[0], MULTISIG_KEYS[1]),
+            format!("wpkh({}", MULTISIG_KEYS[0]),
+        ];
+        descriptors
+            .iter()
+            .for_each(|descriptor| assert!(BitcoinDescriptor::<N>::from_str(descriptor).is_err()));
+    }
+}
@@ -0,0 +1,195 @@
+//!
+//! Electrum
+//!
+//! This module contains the wallet files of Electrum standard wallets, which store a single
+//! BIP32 keystore whose SLIP-132 extended key version determines the script type of the wallet.
+//! https://electrum.readthedocs.io/en/latest/xpub_version_bytes.html
+//!
+
+use crate::extended_private_key::BitcoinExtendedPrivateKey;
+use crate::extended_public_key::BitcoinExtendedPublicKey;
+use crate::format::BitcoinFormat;
+use crate::network::BitcoinNetwork;
+use wagyu_model::no_std::*;
+use wagyu_model::{ExtendedPrivateKey, KeyOrigin};
+
+use core::fmt;
+use serde_json::{json, Map, Value};
+
+/// The wallet file version of Electrum 3.3, which newer versions of Electrum upgrade when opening the wallet
+pub const ELECTRUM_SEED_VERSION: u32 = 18;
+
+/// Represents an Electrum standard wallet file of a BIP32 account
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ElectrumWallet {
+    /// The extended public key of the account, in the SLIP-132 format of the script type
+    xpub: String,
+    /// The extended private key of the account, if the wallet is not watch-only
+    xprv: Option<String>,
+    /// The origin of the account key, if known
+    origin: Option<KeyOrigin>,
+}
+
+impl ElectrumWallet {
+    /// Returns the watch-only wallet of the account of the given extended public key, for the given address format.
+    pub fn from_extended_public_key<N: BitcoinNetwork>(
+        extended_public_key: &BitcoinExtendedPublicKey<N>,
+        origin: Option<KeyOrigin>,
+        format: &BitcoinFormat,
+    ) -> Result<Self, ElectrumError> {
+        Self::check_format(format)?;
+        Ok(Self {
+            xpub: extended_public_key.with_format(format).to_string(),
+            xprv: None,
+            origin,
+        })
+    }
+
+    /// Returns the wallet of the account of the given extended private key, for the given address format.
+    pub fn from_extended_private_key<N: BitcoinNetwork>(
+        extended_private_key: &BitcoinExtendedPrivateKey<N>,
+        origin: Option<KeyOrigin>,
+        format: &BitcoinFormat,
+    ) -> Result<Self, ElectrumError> {
+        Self::check_format(format)?;
+        let extended_private_key = extended_private_key.with_format(format);
+        Ok(Self {
+            xpub: extended_private_key.to_extended_public_key().to_string(),
+            xprv: Some(extended_private_key.to_string()),
+            origin,
+        })
+    }
+
+    /// Returns true if the wallet has no extended private key.
+    pub fn is_watch_only(&self) -> bool {
+        self.xprv.is_none()
+    }
+
+    /// Returns the wallet file as JSON, an unencrypted wallet which Electrum opens from its `wallets` directory
+    /// or with `electrum -w <wallet file>`.
+    pub fn to_json(&self) -> Value {
+        let mut keystore = Map::new();
+        keystore.insert("type".into(), json!("bip32"));
+        keystore.insert("xpub".into(), json!(self.xpub));
+        if let Some(xprv) = &self.xprv {
+            keystore.insert("xprv".into(), json!(xprv));
+        }
+        if let Some(origin) = &self.origin {
+            let path = origin.path().to_string();
+            let derivation = match path.is_empty() {
+                true => "m".to_string(),
+                false => format!("m/{}", path),
+            };
+            let fingerprint: String = origin
+                .fingerprint()
+                .iter()
+                .map(|byte| format!("{:02x}", byte))
+                .collect();
+            keystore.insert("derivation".into(), json!(derivation));
+            keystore.insert("root_fingerprint".into(), json!(fingerprint));
+        }
+        keystore.insert("pw_hash_version".into(), json!(1));
+
+        json!({
+            "keystore": Value::Object(keystore),
+            "wallet_type": "standard",
+            "use_encryption": false,
+            "seed_version": ELECTRUM_SEED_VERSION,
+        })
+    }
+
+    /// Returns an error if the address format has no Electrum standard wallet script type.
+    fn check_format(format: &BitcoinFormat) -> Result<(), ElectrumError> {
+        match format {
+            BitcoinFormat::P2PKH | BitcoinFormat::P2SH_P2WPKH | BitcoinFormat::Bech32 => Ok(()),
+            _ => Err(ElectrumError::UnsupportedFormat(format.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for ElectrumWallet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_json())
+    }
+}
+
+#[derive(Debug, Fail)]
+pub enum ElectrumError {
+    #[fail(display = "unsupported Electrum wallet format: {}", _0)]
+    UnsupportedFormat(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::*;
+    use core::str::FromStr;
+
+    type N = Mainnet;
+
+    // The BIP84 account of the "abandon ... about" mnemonic, with master fingerprint 73c5da0a
+    // https://github.com/bitcoin/bips/blob/master/bip-0084.mediawiki#test-vectors
+    const ORIGIN: &str = "[73c5da0a/84'/0'/0']";
+    const ZPRV: &str = "zprvAdG4iTXWBoARxkkzNpNh8r6Qag3irQB8PzEMkAFeTRXxHpbF9z4QgEvBRmfvqWvGp42t42nvgGpNgYSJA9iefm1yYNZKEm7z6qUWCroSQnE";
+    const ZPUB: &str = "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs";
+    const XPUB: &str = "xpub6CatWdiZiodmUeTDp8LT5or8nmbKNcuyvz7WyksVFkKB4RHwCD3XyuvPEbvqAQY3rAPshWcMLoP2fMFMKHPJ4ZeZXYVUhLv1VMrjPC7PW6V";
+
+    #[test]
+    fn from_extended_private_key() {
+        let extended_private_key = BitcoinExtendedPrivateKey::<N>::from_str(ZPRV).unwrap();
+        let origin = KeyOrigin::from_str(ORIGIN).unwrap();
+        let wallet =
+            ElectrumWallet::from_extended_private_key(&extended_private_key, Some(origin), &BitcoinFormat::Bech32)
+                .unwrap();
+        assert!(!wallet.is_watch_only());
+        assert_eq!(
+            json!({
+                "keystore": {
+                    "type": "bip32",
+                    "xpub": ZPUB,
+                    "xprv": ZPRV,
+                    "derivation": "m/84'/0'/0'",
+                    "root_fingerprint": "73c5da0a",
+                    "pw_hash_version": 1,
+                },
+                "wallet_type": "standard",
+                "use_encryption": false,
+                "seed_version": 18,
+            }),
+            wallet.to_json()
+        );
+    }
+
+    #[test]
+    fn from_extended_public_key() {
+        // The version of the extended public key is replaced by the version of the format
+        let extended_public_key = BitcoinExtendedPublicKey::<N>::from_str(XPUB).unwrap();
+        let wallet =
+            ElectrumWallet::from_extended_public_key(&extended_public_key, None, &BitcoinFormat::Bech32).unwrap();
+        assert!(wallet.is_watch_only());
+        assert_eq!(
+            json!({
+                "keystore": {
+                    "type": "bip32",
+                    "xpub": ZPUB,
+                    "pw_hash_version": 1,
+                },
+                "wallet_type": "standard",
+                "use_encryption": false,
+                "seed_version": 18,
+            }),
+            wallet.to_json()
+        );
+
+        let wallet =
+            ElectrumWallet::from_extended_public_key(&extended_public_key, None, &BitcoinFormat::P2SH_P2WPKH).unwrap();
+        assert!(wallet.to_string().contains("\"xpub\":\"ypub"));
+    }
+
+    #[test]
+    fn unsupported_format() {
+        let extended_public_key = BitcoinExtendedPublicKey::<N>::from_str(XPUB).unwrap();
+        assert!(ElectrumWallet::from_extended_public_key(&extended_public_key, None, &BitcoinFormat::P2TR).is_err());
+        assert!(ElectrumWallet::from_extended_public_key(&extended_public_key, None, &BitcoinFormat::P2WSH).is_err());
+    }
+}
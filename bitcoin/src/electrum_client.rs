@@ -0,0 +1,393 @@
+//!
+//! Electrum Client
+//!
+//! This module contains a minimal client of the Electrum protocol, which fetches the balances and
+//! unspent outputs of addresses from an Electrum server and broadcasts signed transactions, so the
+//! transactions of an air-gapped wallet can be funded and relayed from an online machine.
+//! https://electrumx.readthedocs.io/en/latest/protocol.html
+//!
+
+use crate::address::BitcoinAddress;
+use crate::amount::BitcoinAmount;
+use crate::coin_selection::BitcoinUtxo;
+use crate::network::BitcoinNetwork;
+use crate::transaction::{create_script_pub_key, BitcoinTransaction};
+use wagyu_model::no_std::*;
+use wagyu_model::{CrateError, Transaction, TransactionError};
+
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+/// The name of the client, sent to the server in its version negotiation
+pub const ELECTRUM_CLIENT_NAME: &str = "wagyu";
+/// The version of the Electrum protocol spoken by the client
+pub const ELECTRUM_PROTOCOL_VERSION: &str = "1.4";
+/// The time the client waits to connect to the server and for each of its responses
+pub const ELECTRUM_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Represents the confirmed and unconfirmed balance of an address
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ElectrumBalance {
+    /// The sum of the confirmed outputs of the address
+    pub confirmed: BitcoinAmount,
+    /// The net amount of the unconfirmed transactions of the address, which may be negative
+    pub unconfirmed: BitcoinAmount,
+}
+
+/// Represents a client of an Electrum server over a TCP connection, or any other stream
+pub struct ElectrumClient<S: Read + Write> {
+    /// The connection to the server, buffered to read its responses line by line
+    stream: BufReader<S>,
+    /// The id of the next request
+    id: u64,
+}
+
+impl ElectrumClient<TcpStream> {
+    /// Returns a client connected to the Electrum server at the given address, e.g. `127.0.0.1:50001`,
+    /// after negotiating the protocol version. Connections over TLS are not supported.
+    pub fn connect(server: &str) -> Result<Self, ElectrumClientError> {
+        let address = match server.to_socket_addrs()?.next() {
+            Some(address) => address,
+            None => return Err(ElectrumClientError::InvalidServer(server.into())),
+        };
+        let stream = TcpStream::connect_timeout(&address, ELECTRUM_TIMEOUT)?;
+        stream.set_read_timeout(Some(ELECTRUM_TIMEOUT))?;
+        stream.set_write_timeout(Some(ELECTRUM_TIMEOUT))?;
+
+        let mut client = Self::new(stream);
+        client.server_version()?;
+        Ok(client)
+    }
+}
+
+impl<S: Read + Write> ElectrumClient<S> {
+    /// Returns a client of the Electrum server at the other end of the given stream.
+    pub fn new(stream: S) -> Self {
+        Self {
+            stream: BufReader::new(stream),
+            id: 0,
+        }
+    }
+
+    /// Returns the software and protocol version of the server, negotiating the protocol version of the client.
+    pub fn server_version(&mut self) -> Result<(String, String), ElectrumClientError> {
+        let response = self.request(
+            "server.version",
+            json!([ELECTRUM_CLIENT_NAME, ELECTRUM_PROTOCOL_VERSION]),
+        )?;
+        match (response[0].as_str(), response[1].as_str()) {
+            (Some(software), Some(protocol)) => Ok((software.into(), protocol.into())),
+            _ => Err(ElectrumClientError::InvalidResponse(response.to_string())),
+        }
+    }
+
+    /// Returns the balance of the given address.
+    pub fn get_balance<N: BitcoinNetwork>(
+        &mut self,
+        address: &BitcoinAddress<N>,
+    ) -> Result<ElectrumBalance, ElectrumClientError> {
+        let response = self.request("blockchain.scripthash.get_balance", json!([script_hash(address)?]))?;
+        match (response["confirmed"].as_i64(), response["unconfirmed"].as_i64()) {
+            (Some(confirmed), Some(unconfirmed)) => Ok(ElectrumBalance {
+                confirmed: BitcoinAmount(confirmed),
+                unconfirmed: BitcoinAmount(unconfirmed),
+            }),
+            _ => Err(ElectrumClientError::InvalidResponse(response.to_string())),
+        }
+    }
+
+    /// Returns the unspent outputs of the given address, confirmed or in the mempool,
+    /// ready for coin selection.
+    pub fn list_unspent<N: BitcoinNetwork>(
+        &mut self,
+        address: &BitcoinAddress<N>,
+    ) -> Result<Vec<BitcoinUtxo<N>>, ElectrumClientError> {
+        let response = self.request("blockchain.scripthash.listunspent", json!([script_hash(address)?]))?;
+        let outputs = match response.as_array() {
+            Some(outputs) => outputs,
+            None => return Err(ElectrumClientError::InvalidResponse(response.to_string())),
+        };
+
+        outputs
+            .iter()
+            .map(|output| {
+                let (transaction_id, index, amount, height) = match (
+                    output["tx_hash"].as_str(),
+                    output["tx_pos"].as_u64(),
+                    output["value"].as_i64(),
+                    output["height"].as_i64(),
+                ) {
+                    (Some(transaction_id), Some(index), Some(amount), Some(height)) => {
+                        (transaction_id, index, amount, height)
+                    }
+                    _ => return Err(ElectrumClientError::InvalidResponse(output.to_string())),
+                };
+
+                let mut utxo = BitcoinUtxo::new(
+                    hex::decode(transaction_id).map_err(TransactionError::from)?,
+                    index as u32,
+                    BitcoinAmount(amount),
+                    address.clone(),
+                )?;
+                // Unconfirmed outputs have a height of 0, or -1 if they spend unconfirmed outputs
+                if height > 0 {
+                    utxo.height = Some(height as u32);
+                }
+                Ok(utxo)
+            })
+            .collect()
+    }
+
+    /// Broadcasts the given signed transaction and returns its transaction id.
+    pub fn broadcast<N: BitcoinNetwork>(
+        &mut self,
+        transaction: &BitcoinTransaction<N>,
+    ) -> Result<String, ElectrumClientError> {
+        let transaction_hex = hex::encode(transaction.to_transaction_bytes()?);
+        let response = self.request("blockchain.transaction.broadcast", json!([transaction_hex]))?;
+        match response.as_str() {
+            Some(transaction_id) => Ok(transaction_id.into()),
+            None => Err(ElectrumClientError::InvalidResponse(response.to_string())),
+        }
+    }
+
+    /// Returns the result of the given JSON-RPC method, skipping any notifications sent before its response.
+    fn request(&mut self, method: &str, params: Value) -> Result<Value, ElectrumClientError> {
+        self.id += 1;
+        let request = json!({ "jsonrpc": "2.0", "id": self.id, "method": method, "params": params });
+        let stream = self.stream.get_mut();
+        stream.write_all(format!("{}\n", request).as_bytes())?;
+        stream.flush()?;
+
+        loop {
+            let mut line = String::new();
+            if self.stream.read_line(&mut line)? == 0 {
+                return Err(ElectrumClientError::ConnectionClosed);
+            }
+
+            let mut response: Value = serde_json::from_str(&line).map_err(CrateError::from)?;
+            if response["id"].as_u64() != Some(self.id) {
+                continue;
+            }
+            if !response["error"].is_null() {
+                let error = &response["error"];
+                let message = error["message"]
+                    .as_str()
+                    .map(String::from)
+                    .unwrap_or_else(|| error.to_string());
+                return Err(ElectrumClientError::ServerError(message));
+            }
+            return Ok(response["result"].take());
+        }
+    }
+}
+
+/// Returns the Electrum script hash of the given address, the reversed SHA256 hash of its scriptPubKey.
+pub fn script_hash<N: BitcoinNetwork>(address: &BitcoinAddress<N>) -> Result<String, TransactionError> {
+    let mut hash = Sha256::digest(&create_script_pub_key(address)?).to_vec();
+    hash.reverse();
+    Ok(hex::encode(hash))
+}
+
+#[derive(Debug, Fail)]
+pub enum ElectrumClientError {
+    #[fail(display = "the Electrum server closed the connection")]
+    ConnectionClosed,
+
+    #[fail(display = "{}", _0)]
+    Crate(#[cause] CrateError),
+
+    #[fail(display = "invalid Electrum response: {}", _0)]
+    InvalidResponse(String),
+
+    #[fail(display = "invalid Electrum server: {}", _0)]
+    InvalidServer(String),
+
+    #[fail(display = "Electrum server error: {}", _0)]
+    ServerError(String),
+
+    #[fail(display = "{}", _0)]
+    TransactionError(#[cause] TransactionError),
+}
+
+impl From<CrateError> for ElectrumClientError {
+    fn from(error: CrateError) -> Self {
+        ElectrumClientError::Crate(error)
+    }
+}
+
+impl From<std::io::Error> for ElectrumClientError {
+    fn from(error: std::io::Error) -> Self {
+        ElectrumClientError::Crate(CrateError::from(error))
+    }
+}
+
+impl From<TransactionError> for ElectrumClientError {
+    fn from(error: TransactionError) -> Self {
+        ElectrumClientError::TransactionError(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::*;
+    use crate::private_key::BitcoinPrivateKey;
+    use crate::transaction::{BitcoinTransactionInput, BitcoinTransactionOutput, BitcoinTransactionParameters};
+    use crate::SignatureHash;
+
+    use core::str::FromStr;
+    use std::io::Cursor;
+
+    type N = Mainnet;
+
+    const PRIVATE_KEY: &str = "KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73sVHnoWn";
+    const ADDRESS: &str = "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH";
+    // The script hash of the P2PKH address of the private key of 1
+    const SCRIPT_HASH: &str = "8bd2c4f79944cd6a3cb1730cf92c513ae259eb271d81918457f3753eebe14a3f";
+    const TRANSACTION_ID: &str = "61d520ccb74288c96bc1a2b20ea1c0d5a704776dd0164a396efec3ea7040349d";
+
+    // Represents an Electrum server replying with the given responses, recording the requests of the client
+    struct MockServer {
+        responses: Cursor<Vec<u8>>,
+        requests: Vec<u8>,
+    }
+
+    impl MockServer {
+        fn new(responses: &[Value]) -> Self {
+            let responses: String = responses.iter().map(|response| format!("{}\n", response)).collect();
+            Self {
+                responses: Cursor::new(responses.into_bytes()),
+                requests: vec![],
+            }
+        }
+    }
+
+    impl Read for MockServer {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.responses.read(buf)
+        }
+    }
+
+    impl Write for MockServer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.requests.write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn requests(client: ElectrumClient<MockServer>) -> Vec<Value> {
+        let requests = String::from_utf8(client.stream.into_inner().requests).unwrap();
+        requests
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn script_hash() {
+        let address = BitcoinAddress::<N>::from_str(ADDRESS).unwrap();
+        assert_eq!(SCRIPT_HASH, super::script_hash(&address).unwrap());
+    }
+
+    #[test]
+    fn get_balance_and_list_unspent() {
+        let address = BitcoinAddress::<N>::from_str(ADDRESS).unwrap();
+        let mut client = ElectrumClient::new(MockServer::new(&[
+            json!({ "jsonrpc": "2.0", "id": 1, "result": ["ElectrumX 1.16.0", "1.4"] }),
+            json!({ "jsonrpc": "2.0", "id": 2, "result": { "confirmed": 100_000, "unconfirmed": -20_000 } }),
+            // A notification is skipped
+            json!({ "jsonrpc": "2.0", "method": "blockchain.headers.subscribe", "params": [] }),
+            json!({ "jsonrpc": "2.0", "id": 3, "result": [
+                { "tx_hash": TRANSACTION_ID, "tx_pos": 1, "height": 800_000, "value": 100_000 },
+                { "tx_hash": TRANSACTION_ID, "tx_pos": 2, "height": 0, "value": 30_000 },
+            ] }),
+        ]));
+
+        assert_eq!(
+            ("ElectrumX 1.16.0".to_string(), "1.4".to_string()),
+            client.server_version().unwrap()
+        );
+        assert_eq!(
+            ElectrumBalance {
+                confirmed: BitcoinAmount(100_000),
+                unconfirmed: BitcoinAmount(-20_000),
+            },
+            client.get_balance(&address).unwrap()
+        );
+
+        let utxos = client.list_unspent(&address).unwrap();
+        assert_eq!(2, utxos.len());
+        assert_eq!(hex::decode(TRANSACTION_ID).unwrap(), utxos[0].transaction_id);
+        assert_eq!(
+            (1, BitcoinAmount(100_000), Some(800_000)),
+            (utxos[0].index, utxos[0].amount, utxos[0].height)
+        );
+        assert_eq!(
+            (2, BitcoinAmount(30_000), None),
+            (utxos[1].index, utxos[1].amount, utxos[1].height)
+        );
+        assert_eq!(address, utxos[1].address);
+
+        let requests = requests(client);
+        assert_eq!(json!(["wagyu", "1.4"]), requests[0]["params"]);
+        assert_eq!("blockchain.scripthash.get_balance", requests[1]["method"]);
+        assert_eq!(json!([SCRIPT_HASH]), requests[1]["params"]);
+        assert_eq!("blockchain.scripthash.listunspent", requests[2]["method"]);
+        assert_eq!(json!(3), requests[2]["id"]);
+    }
+
+    #[test]
+    fn broadcast() {
+        let address = BitcoinAddress::<N>::from_str(ADDRESS).unwrap();
+        let input = BitcoinTransactionInput::<N>::new(
+            hex::decode(TRANSACTION_ID).unwrap(),
+            0,
+            Some(address.clone()),
+            Some(BitcoinAmount(100_000)),
+            None,
+            None,
+            None,
+            SignatureHash::SIGHASH_ALL,
+        )
+        .unwrap();
+        let parameters = BitcoinTransactionParameters::<N> {
+            version: 2,
+            inputs: vec![input],
+            outputs: vec![BitcoinTransactionOutput::new(&address, BitcoinAmount(90_000)).unwrap()],
+            lock_time: 0,
+            segwit_flag: false,
+        };
+        let transaction = BitcoinTransaction::<N>::new(&parameters)
+            .unwrap()
+            .sign(&BitcoinPrivateKey::from_str(PRIVATE_KEY).unwrap())
+            .unwrap();
+        let transaction_id = transaction.to_transaction_id().unwrap().to_string();
+
+        let mut client = ElectrumClient::new(MockServer::new(&[
+            json!({ "jsonrpc": "2.0", "id": 1, "result": transaction_id }),
+            json!({ "jsonrpc": "2.0", "id": 2, "error": { "code": 1, "message": "bad-txns-inputs-missingorspent" } }),
+        ]));
+        assert_eq!(transaction_id, client.broadcast(&transaction).unwrap());
+        match client.broadcast(&transaction) {
+            Err(ElectrumClientError::ServerError(message)) => assert_eq!("bad-txns-inputs-missingorspent", message),
+            result => panic!("unexpected result {:?}", result),
+        };
+        match client.broadcast(&transaction) {
+            Err(ElectrumClientError::ConnectionClosed) => (),
+            result => panic!("unexpected result {:?}", result),
+        };
+
+        let requests = requests(client);
+        assert_eq!(
+            json!([hex::encode(transaction.to_transaction_bytes().unwrap())]),
+            requests[0]["params"]
+        );
+    }
+}
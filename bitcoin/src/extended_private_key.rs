@@ -6,21 +6,22 @@ use crate::network::BitcoinNetwork;
 use crate::private_key::BitcoinPrivateKey;
 use crate::public_key::BitcoinPublicKey;
 use wagyu_model::{
-    crypto::{checksum, hash160},
-    AddressError, ChildIndex, DerivationPath, ExtendedPrivateKey, ExtendedPrivateKeyError, ExtendedPublicKey,
-    PrivateKey,
+    crypto::{checksum, hash160, secp256k1_public_key},
+    AddressError, ChildIndex, ChildIndexRange, DerivationPath, DerivationPathError, ExposeSecret, ExtendedPrivateKey,
+    ExtendedPrivateKeyError, ExtendedPublicKey, PrivateKey, Redacted,
 };
 
 use base58::{FromBase58, ToBase58};
 use core::{convert::TryFrom, fmt, fmt::Display, str::FromStr};
 use hmac::{Hmac, Mac};
-use secp256k1::{PublicKey, SecretKey};
+use secp256k1::SecretKey;
 use sha2::Sha512;
+use zeroize::Zeroize;
 
 type HmacSha512 = Hmac<Sha512>;
 
 /// Represents a Bitcoin extended private key
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq)]
 pub struct BitcoinExtendedPrivateKey<N: BitcoinNetwork> {
     /// The address format
     pub(super) format: BitcoinFormat,
@@ -75,55 +76,31 @@ impl<N: BitcoinNetwork> ExtendedPrivateKey for BitcoinExtendedPrivateKey<N> {
             return Err(ExtendedPrivateKeyError::MaximumChildDepthReached(self.depth));
         }
 
+        let indices = path.to_vec()?;
+        if self.depth as usize + indices.len() > 255 {
+            return Err(ExtendedPrivateKeyError::MaximumChildDepthReached(self.depth));
+        }
+
+        let format = Self::path_format(path, &self.format);
         let mut extended_private_key = self.clone();
 
-        for index in path.to_vec()?.into_iter() {
-            let public_key = &PublicKey::from_secret_key(&extended_private_key.private_key.to_secp256k1_secret_key())
-                .serialize_compressed()[..];
-
-            let mut mac = HmacSha512::new_varkey(&extended_private_key.chain_code)?;
-            match index {
-                // HMAC-SHA512(Key = cpar, Data = serP(point(kpar)) || ser32(i)).
-                ChildIndex::Normal(_) => mac.input(public_key),
-                // HMAC-SHA512(Key = cpar, Data = 0x00 || ser256(kpar) || ser32(i))
-                // (Note: The 0x00 pads the private key to make it 33 bytes long.)
-                ChildIndex::Hardened(_) => {
-                    mac.input(&[0u8]);
-                    mac.input(&extended_private_key.private_key.to_secp256k1_secret_key().serialize());
-                }
-            }
-            // Append the child index in big-endian format
-            mac.input(&u32::from(index).to_be_bytes());
-            let hmac = mac.result().code();
-
-            let mut secret_key = SecretKey::parse_slice(&hmac[0..32])?;
-            secret_key.tweak_add_assign(&extended_private_key.private_key.to_secp256k1_secret_key())?;
-            let private_key = Self::PrivateKey::from_secp256k1_secret_key(&secret_key, true);
-
-            let mut chain_code = [0u8; 32];
-            chain_code[0..32].copy_from_slice(&hmac[32..]);
-
-            let mut parent_fingerprint = [0u8; 4];
-            parent_fingerprint.copy_from_slice(&hash160(public_key)[0..4]);
-
-            let format = match path {
-                BitcoinDerivationPath::BIP49(_) => BitcoinFormat::P2SH_P2WPKH,
-                _ => extended_private_key.format.clone(),
-            };
-
-            extended_private_key = Self {
-                format,
-                depth: extended_private_key.depth + 1,
-                parent_fingerprint,
-                child_index: index,
-                chain_code,
-                private_key,
-            }
+        for index in indices.into_iter() {
+            let public_key = secp256k1_public_key(&extended_private_key.private_key.to_secp256k1_secret_key())
+                .serialize_compressed();
+            let parent_fingerprint = Self::to_fingerprint(&public_key);
+            extended_private_key =
+                extended_private_key.derive_child(index, &public_key, parent_fingerprint, &format)?;
         }
 
         Ok(extended_private_key)
     }
 
+    /// Returns the extended private keys of the children in the given range of child indices,
+    /// computing the public key of this extended private key once for all normal children.
+    fn derive_children(&self, range: ChildIndexRange) -> Result<Vec<Self>, ExtendedPrivateKeyError> {
+        self.derive_children_with_format(range, &self.format)
+    }
+
     /// Returns the extended public key of the corresponding extended private key.
     fn to_extended_public_key(&self) -> Self::ExtendedPublicKey {
         Self::ExtendedPublicKey::from_extended_private_key(&self)
@@ -146,10 +123,126 @@ impl<N: BitcoinNetwork> ExtendedPrivateKey for BitcoinExtendedPrivateKey<N> {
 }
 
 impl<N: BitcoinNetwork> BitcoinExtendedPrivateKey<N> {
+    /// Returns the extended private keys of the given number of consecutive children, beginning at the
+    /// last index of the given path, e.g. `m/84'/0'/0'/0/0` to `m/84'/0'/0'/0/19` for a path of
+    /// `m/84'/0'/0'/0/0` and a count of 20. The parent key of the children is derived once.
+    pub fn derive_batch(
+        &self,
+        path: &BitcoinDerivationPath<N>,
+        count: u32,
+    ) -> Result<Vec<Self>, ExtendedPrivateKeyError> {
+        let mut indices = path.to_vec()?;
+        let start = indices
+            .pop()
+            .ok_or_else(|| DerivationPathError::InvalidDerivationPath(path.to_string()))?;
+        let parent = self.derive(&BitcoinDerivationPath::from_vec(&indices)?)?;
+        let range = ChildIndexRange::from_count(start, count)?;
+        parent.derive_children_with_format(range, &Self::path_format(path, &self.format))
+    }
+
     /// Returns the format of the Bitcoin extended private key.
     pub fn format(&self) -> BitcoinFormat {
         self.format.clone()
     }
+
+    /// Returns the depth of key derivation, e.g. 0 for the master key.
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// Returns the first 32 bits of the key identifier of the parent key, or zeros for the master key.
+    pub fn parent_fingerprint(&self) -> [u8; 4] {
+        self.parent_fingerprint
+    }
+
+    /// Returns the child index of the key, or 0 for the master key.
+    pub fn child_index(&self) -> ChildIndex {
+        self.child_index
+    }
+
+    /// Returns the extended private key with the version bytes of the given format, e.g. to convert
+    /// an xprv to a zprv, preserving its key material.
+    pub fn with_format(&self, format: &BitcoinFormat) -> Self {
+        let mut extended_private_key = self.clone();
+        extended_private_key.format = format.clone();
+        extended_private_key
+    }
+
+    /// Returns the extended private keys of the children in the given range of child indices, with the given format.
+    fn derive_children_with_format(
+        &self,
+        range: ChildIndexRange,
+        format: &BitcoinFormat,
+    ) -> Result<Vec<Self>, ExtendedPrivateKeyError> {
+        let public_key = secp256k1_public_key(&self.private_key.to_secp256k1_secret_key()).serialize_compressed();
+        let parent_fingerprint = Self::to_fingerprint(&public_key);
+        range
+            .map(|index| self.derive_child(index, &public_key, parent_fingerprint, format))
+            .collect()
+    }
+
+    /// Returns the child extended private key of the given index, with the given format,
+    /// from the serialized compressed public key and the fingerprint of this extended private key.
+    fn derive_child(
+        &self,
+        index: ChildIndex,
+        public_key: &[u8],
+        parent_fingerprint: [u8; 4],
+        format: &BitcoinFormat,
+    ) -> Result<Self, ExtendedPrivateKeyError> {
+        if self.depth == 255 {
+            return Err(ExtendedPrivateKeyError::MaximumChildDepthReached(self.depth));
+        }
+
+        let mut mac = HmacSha512::new_varkey(&self.chain_code)?;
+        match index {
+            // HMAC-SHA512(Key = cpar, Data = serP(point(kpar)) || ser32(i)).
+            ChildIndex::Normal(_) => mac.input(public_key),
+            // HMAC-SHA512(Key = cpar, Data = 0x00 || ser256(kpar) || ser32(i))
+            // (Note: The 0x00 pads the private key to make it 33 bytes long.)
+            ChildIndex::Hardened(_) => {
+                mac.input(&[0u8]);
+                mac.input(&self.private_key.to_secp256k1_secret_key().serialize());
+            }
+        }
+        // Append the child index in big-endian format
+        mac.input(&u32::from(index).to_be_bytes());
+        let hmac = mac.result().code();
+
+        let mut secret_key = SecretKey::parse_slice(&hmac[0..32])?;
+        secret_key.tweak_add_assign(&self.private_key.to_secp256k1_secret_key())?;
+        let private_key = BitcoinPrivateKey::from_secp256k1_secret_key(&secret_key, true);
+
+        let mut chain_code = [0u8; 32];
+        chain_code[0..32].copy_from_slice(&hmac[32..]);
+
+        Ok(Self {
+            format: format.clone(),
+            depth: self.depth + 1,
+            parent_fingerprint,
+            child_index: index,
+            chain_code,
+            private_key,
+        })
+    }
+
+    /// Returns the first 32 bits of the key identifier of the given serialized compressed public key.
+    fn to_fingerprint(public_key: &[u8]) -> [u8; 4] {
+        let mut fingerprint = [0u8; 4];
+        fingerprint.copy_from_slice(&hash160(public_key)[0..4]);
+        fingerprint
+    }
+
+    /// Returns the address format of keys derived along the given path, or the given format
+    /// if the path is not a BIP49, BIP84, or BIP86 path.
+    fn path_format(path: &BitcoinDerivationPath<N>, format: &BitcoinFormat) -> BitcoinFormat {
+        match path {
+            BitcoinDerivationPath::BIP49(_) => BitcoinFormat::P2SH_P2WPKH,
+            BitcoinDerivationPath::BIP84(_) => BitcoinFormat::Bech32,
+            BitcoinDerivationPath::BIP86(_) => BitcoinFormat::P2TR,
+            _ => format.clone(),
+        }
+    }
 }
 
 impl<N: BitcoinNetwork> FromStr for BitcoinExtendedPrivateKey<N> {
@@ -163,7 +256,9 @@ impl<N: BitcoinNetwork> FromStr for BitcoinExtendedPrivateKey<N> {
 
         // Check that the version bytes correspond with the correct network.
         let _ = N::from_extended_private_key_version_bytes(&data[0..4])?;
-        let format = BitcoinFormat::from_extended_private_key_version_bytes(&data[0..4])?;
+        // Custom networks share their version bytes across formats, which deserialize as P2PKH.
+        let format =
+            BitcoinFormat::from_extended_private_key_version_bytes(&data[0..4]).unwrap_or(BitcoinFormat::P2PKH);
 
         let depth = data[4];
 
@@ -215,7 +310,48 @@ impl<N: BitcoinNetwork> Display for BitcoinExtendedPrivateKey<N> {
         let checksum = &checksum(&result[0..78])[0..4];
         result[78..82].copy_from_slice(&checksum);
 
-        fmt.write_str(&result.to_base58())
+        let output = result.to_base58();
+        result.zeroize();
+        fmt.write_str(&output)
+    }
+}
+
+impl<N: BitcoinNetwork> fmt::Debug for BitcoinExtendedPrivateKey<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BitcoinExtendedPrivateKey")
+            .field("format", &self.format)
+            .field("depth", &self.depth)
+            .field("parent_fingerprint", &self.parent_fingerprint)
+            .field("child_index", &self.child_index)
+            .field("chain_code", &Redacted)
+            .field("private_key", &self.private_key)
+            .finish()
+    }
+}
+
+impl<N: BitcoinNetwork> ExposeSecret for BitcoinExtendedPrivateKey<N> {
+    fn fmt_secret(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BitcoinExtendedPrivateKey")
+            .field("format", &self.format)
+            .field("depth", &self.depth)
+            .field("parent_fingerprint", &self.parent_fingerprint)
+            .field("child_index", &self.child_index)
+            .field("chain_code", &self.chain_code)
+            .field("private_key", &self.private_key.expose_secret())
+            .finish()
+    }
+}
+
+impl<N: BitcoinNetwork> Zeroize for BitcoinExtendedPrivateKey<N> {
+    fn zeroize(&mut self) {
+        self.chain_code.zeroize();
+        self.private_key.zeroize();
+    }
+}
+
+impl<N: BitcoinNetwork> Drop for BitcoinExtendedPrivateKey<N> {
+    fn drop(&mut self) {
+        self.zeroize();
     }
 }
 
@@ -669,6 +805,64 @@ mod tests {
                 test_to_string::<N>(extended_private_key);
             });
         }
+
+        #[test]
+        fn fingerprint() {
+            KEYPAIRS
+                .iter()
+                .filter(|(path, _, _, _, _, _, _, _)| *path != "m")
+                .for_each(|(path, seed, _, _, _, parent_fingerprint, _, _)| {
+                    let parent_path = BitcoinDerivationPath::<N>::from_str(path).unwrap().parent().unwrap();
+                    let parent = BitcoinExtendedPrivateKey::<N>::new(
+                        &hex::decode(seed).unwrap(),
+                        &BitcoinFormat::P2PKH,
+                        &parent_path,
+                    )
+                    .unwrap();
+                    assert_eq!(*parent_fingerprint, hex::encode(parent.fingerprint()));
+                    assert_eq!(parent.fingerprint(), parent.to_extended_public_key().fingerprint());
+                });
+        }
+
+        #[test]
+        fn metadata() {
+            KEYPAIRS.iter().for_each(
+                |(path, _, child_index, _, _, parent_fingerprint, extended_private_key, extended_public_key)| {
+                    let extended_private_key = BitcoinExtendedPrivateKey::<N>::from_str(extended_private_key).unwrap();
+                    let extended_public_key = BitcoinExtendedPublicKey::<N>::from_str(extended_public_key).unwrap();
+                    let depth = path.split('/').count() as u8 - 1;
+                    for (key_depth, key_parent_fingerprint, key_child_index) in [
+                        (
+                            extended_private_key.depth(),
+                            extended_private_key.parent_fingerprint(),
+                            extended_private_key.child_index(),
+                        ),
+                        (
+                            extended_public_key.depth(),
+                            extended_public_key.parent_fingerprint(),
+                            extended_public_key.child_index(),
+                        ),
+                    ]
+                    .iter()
+                    {
+                        assert_eq!(depth, *key_depth);
+                        assert_eq!(*parent_fingerprint, hex::encode(key_parent_fingerprint));
+                        assert_eq!(child_index.parse::<u32>().unwrap(), u32::from(*key_child_index));
+                    }
+                },
+            );
+        }
+
+        #[test]
+        fn key_origin() {
+            let (_, seed, _, _, _, _, _, _) = KEYPAIRS[0];
+            let master =
+                BitcoinExtendedPrivateKey::<N>::new_master(&hex::decode(seed).unwrap(), &BitcoinFormat::P2PKH).unwrap();
+            let path = BitcoinDerivationPath::from_str("m/0'/1").unwrap();
+            let origin = master.key_origin(&path).unwrap();
+            assert_eq!("[3442193e/0'/1]", origin.to_string());
+            assert_eq!(origin, master.to_extended_public_key().key_origin(&path).unwrap());
+        }
     }
 
     mod p2sh_p2wpkh_mainnet {
@@ -830,6 +1024,256 @@ mod tests {
         }
     }
 
+    mod bech32_mainnet {
+        use super::*;
+
+        type N = Mainnet;
+
+        // BIP84 test vector - "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"
+        const SEED: &str = "5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc19a5ac40b389cd370d086206dec8aa6c43daea6690f20ad3d8d48b2d2ce9e38e4";
+
+        // (path, extended_private_key, extended_public_key)
+        const KEYPAIRS: [(&str, &str, &str); 2] = [
+            (
+                "m",
+                "zprvAWgYBBk7JR8Gjrh4UJQ2uJdG1r3WNRRfURiABBE3RvMXYSrRJL62XuezvGdPvG6GFBZduosCc1YP5wixPox7zhZLfiUm8aunE96BBa4Kei5",
+                "zpub6jftahH18ngZxLmXaKw3GSZzZsszmt9WqedkyZdezFtWRFBZqsQH5hyUmb4pCEeZGmVfQuP5bedXTB8is6fTv19U1GQRyQUKQGUTzyHACMF"
+            ),
+            (
+                "m/84'/0'/0'",
+                "zprvAdG4iTXWBoARxkkzNpNh8r6Qag3irQB8PzEMkAFeTRXxHpbF9z4QgEvBRmfvqWvGp42t42nvgGpNgYSJA9iefm1yYNZKEm7z6qUWCroSQnE",
+                "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs"
+            ),
+        ];
+
+        #[test]
+        fn to_extended_public_key() {
+            KEYPAIRS.iter().for_each(|(path, _, expected_public_key)| {
+                test_to_extended_public_key::<N>(
+                    expected_public_key,
+                    SEED,
+                    &BitcoinFormat::Bech32,
+                    &BitcoinDerivationPath::from_str(path).unwrap(),
+                );
+            });
+        }
+
+        #[test]
+        fn to_string() {
+            KEYPAIRS.iter().for_each(|(_, extended_private_key, _)| {
+                test_to_string::<N>(extended_private_key);
+            });
+        }
+
+        #[test]
+        fn maximum_depth() {
+            use wagyu_model::{ExtendedPublicKey, ExtendedPublicKeyError};
+
+            let master =
+                BitcoinExtendedPrivateKey::<N>::new_master(&hex::decode(SEED).unwrap(), &BitcoinFormat::P2PKH).unwrap();
+            let extended_private_key = master
+                .derive(&BitcoinDerivationPath::from_vec(&vec![ChildIndex::Normal(0); 254]).unwrap())
+                .unwrap();
+
+            let path = BitcoinDerivationPath::from_str("m/0/0").unwrap();
+            match extended_private_key.derive(&path) {
+                Err(ExtendedPrivateKeyError::MaximumChildDepthReached(254)) => (),
+                _ => panic!("expected maximum child depth reached"),
+            }
+            match extended_private_key.to_extended_public_key().derive(&path) {
+                Err(ExtendedPublicKeyError::MaximumChildDepthReached(254)) => (),
+                _ => panic!("expected maximum child depth reached"),
+            }
+
+            let path = BitcoinDerivationPath::from_str("m/0").unwrap();
+            assert!(extended_private_key.derive(&path).is_ok());
+            assert!(extended_private_key.to_extended_public_key().derive(&path).is_ok());
+        }
+
+        #[test]
+        fn bip84_derivation_path() {
+            let master =
+                BitcoinExtendedPrivateKey::<N>::new_master(&hex::decode(SEED).unwrap(), &BitcoinFormat::P2PKH).unwrap();
+            let extended_private_key = master.derive(&BitcoinDerivationPath::bip84(0, 0, 0).unwrap()).unwrap();
+            assert_eq!(BitcoinFormat::Bech32, extended_private_key.format());
+            assert_eq!(
+                "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu",
+                extended_private_key
+                    .to_address(&BitcoinFormat::Bech32)
+                    .unwrap()
+                    .to_string()
+            );
+        }
+
+        #[test]
+        fn derive_batch() {
+            let master =
+                BitcoinExtendedPrivateKey::<N>::new_master(&hex::decode(SEED).unwrap(), &BitcoinFormat::P2PKH).unwrap();
+            let path = BitcoinDerivationPath::bip84(0, 0, 0).unwrap();
+            let children = master.derive_batch(&path, 3).unwrap();
+            assert_eq!(3, children.len());
+
+            // Each child matches the child derived along its full path
+            children.iter().enumerate().for_each(|(index, child)| {
+                let path = BitcoinDerivationPath::bip84(0, 0, index as u32).unwrap();
+                assert_eq!(&master.derive(&path).unwrap(), child);
+            });
+            assert_eq!(
+                "bc1qnjg0jd8228aq7egyzacy8cys3knf9xvrerkf9g",
+                children[1].to_address(&BitcoinFormat::Bech32).unwrap().to_string()
+            );
+
+            let master_path = BitcoinDerivationPath::from_str("m").unwrap();
+            assert!(master.derive_batch(&master_path, 3).is_err());
+        }
+    }
+
+    mod p2tr_mainnet {
+        use super::*;
+
+        type N = Mainnet;
+
+        // BIP86 test vector - "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"
+        const SEED: &str = "5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc19a5ac40b389cd370d086206dec8aa6c43daea6690f20ad3d8d48b2d2ce9e38e4";
+
+        // (path, internal_key, output_key, address)
+        const KEYPAIRS: [(&str, &str, &str, &str); 3] = [
+            (
+                "m/86'/0'/0'/0/0",
+                "cc8a4bc64d897bddc5fbc2f670f7a8ba0b386779106cf1223c6fc5d7cd6fc115",
+                "a60869f0dbcf1dc659c9cecbaf8050135ea9e8cdc487053f1dc6880949dc684c",
+                "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr",
+            ),
+            (
+                "m/86'/0'/0'/0/1",
+                "83dfe85a3151d2517290da461fe2815591ef69f2b18a2ce63f01697a8b313145",
+                "a82f29944d65b86ae6b5e5cc75e294ead6c59391a1edc5e016e3498c67fc7bbb",
+                "bc1p4qhjn9zdvkux4e44uhx8tc55attvtyu358kutcqkudyccelu0was9fqzwh",
+            ),
+            (
+                "m/86'/0'/0'/1/0",
+                "399f1b2f4393f29a18c937859c5dd8a77350103157eb880f02e8c08214277cef",
+                "882d74e5d0572d5a816cef0041a96b6c1de832f6f9676d9605c44d5e9a97d3dc",
+                "bc1p3qkhfews2uk44qtvauqyr2ttdsw7svhkl9nkm9s9c3x4ax5h60wqwruhk7",
+            ),
+        ];
+
+        #[test]
+        fn to_address() {
+            let master =
+                BitcoinExtendedPrivateKey::<N>::new_master(&hex::decode(SEED).unwrap(), &BitcoinFormat::P2PKH).unwrap();
+            KEYPAIRS.iter().for_each(|(path, internal_key, output_key, address)| {
+                let extended_private_key = master.derive(&BitcoinDerivationPath::from_str(path).unwrap()).unwrap();
+                let public_key = extended_private_key.to_public_key();
+                assert_eq!(BitcoinFormat::P2TR, extended_private_key.format());
+                assert_eq!(*internal_key, hex::encode(public_key.to_x_only_public_key()));
+                assert_eq!(*output_key, hex::encode(public_key.to_taproot_output_key().unwrap()));
+                assert_eq!(
+                    *address,
+                    extended_private_key
+                        .to_address(&BitcoinFormat::P2TR)
+                        .unwrap()
+                        .to_string()
+                );
+            });
+        }
+
+        #[test]
+        fn bip86_derivation_path() {
+            let master =
+                BitcoinExtendedPrivateKey::<N>::new_master(&hex::decode(SEED).unwrap(), &BitcoinFormat::P2PKH).unwrap();
+            let path = BitcoinDerivationPath::bip86(0, 0, 0).unwrap();
+            let extended_private_key = master.derive(&path).unwrap();
+            assert_eq!(
+                KEYPAIRS[0].3,
+                extended_private_key
+                    .to_address(&extended_private_key.format())
+                    .unwrap()
+                    .to_string()
+            );
+            assert_eq!(
+                "xprv9xgqHN7yz9MwCkxsBPN5qetuNdQSUttZNKw1dcYTV4mkaAFiBVGQziHs3NRSWMkCzvgjEe3n9xV8oYywvM8at9yRqyaZVz6TYYhX98VjsUk",
+                master
+                    .derive(&BitcoinDerivationPath::from_str("m/86'/0'/0'").unwrap())
+                    .unwrap()
+                    .to_string()
+            );
+        }
+    }
+
+    mod slip132 {
+        use super::*;
+
+        const SEED: &str = "000102030405060708090a0b0c0d0e0f";
+
+        fn test_version_bytes<N: BitcoinNetwork>(format: &BitcoinFormat, private_prefix: &str, public_prefix: &str) {
+            let extended_private_key =
+                BitcoinExtendedPrivateKey::<N>::new_master(&hex::decode(SEED).unwrap(), format).unwrap();
+            let extended_public_key = extended_private_key.to_extended_public_key();
+
+            let private = extended_private_key.to_string();
+            let public = extended_public_key.to_string();
+            assert!(private.starts_with(private_prefix));
+            assert!(public.starts_with(public_prefix));
+
+            assert_eq!(
+                extended_private_key,
+                BitcoinExtendedPrivateKey::<N>::from_str(&private).unwrap()
+            );
+            assert_eq!(
+                extended_public_key,
+                BitcoinExtendedPublicKey::<N>::from_str(&public).unwrap()
+            );
+            assert_eq!(
+                *format,
+                BitcoinExtendedPublicKey::<N>::from_str(&public).unwrap().format()
+            );
+        }
+
+        #[test]
+        fn mainnet() {
+            test_version_bytes::<Mainnet>(&BitcoinFormat::P2PKH, "xprv", "xpub");
+            test_version_bytes::<Mainnet>(&BitcoinFormat::P2SH_P2WPKH, "yprv", "ypub");
+            test_version_bytes::<Mainnet>(&BitcoinFormat::Bech32, "zprv", "zpub");
+            test_version_bytes::<Mainnet>(&BitcoinFormat::P2WSH, "Zprv", "Zpub");
+        }
+
+        #[test]
+        fn testnet() {
+            test_version_bytes::<Testnet>(&BitcoinFormat::P2PKH, "tprv", "tpub");
+            test_version_bytes::<Testnet>(&BitcoinFormat::P2SH_P2WPKH, "uprv", "upub");
+            test_version_bytes::<Testnet>(&BitcoinFormat::Bech32, "vprv", "vpub");
+            test_version_bytes::<Testnet>(&BitcoinFormat::P2WSH, "Vprv", "Vpub");
+        }
+
+        #[test]
+        fn with_format() {
+            let extended_private_key =
+                BitcoinExtendedPrivateKey::<Mainnet>::new_master(&hex::decode(SEED).unwrap(), &BitcoinFormat::P2PKH)
+                    .unwrap();
+            let zprv = extended_private_key.with_format(&BitcoinFormat::Bech32);
+            assert!(zprv.to_string().starts_with("zprv"));
+            assert_eq!(
+                extended_private_key.to_extended_public_key().to_public_key(),
+                zprv.to_extended_public_key().to_public_key()
+            );
+            assert_eq!(
+                extended_private_key.to_string(),
+                zprv.with_format(&BitcoinFormat::P2PKH).to_string()
+            );
+        }
+
+        #[test]
+        fn network_mismatch() {
+            let extended_private_key =
+                BitcoinExtendedPrivateKey::<Testnet>::new_master(&hex::decode(SEED).unwrap(), &BitcoinFormat::P2WSH)
+                    .unwrap();
+            let public = extended_private_key.to_extended_public_key().to_string();
+            assert!(BitcoinExtendedPrivateKey::<Mainnet>::from_str(&extended_private_key.to_string()).is_err());
+            assert!(BitcoinExtendedPublicKey::<Mainnet>::from_str(&public).is_err());
+        }
+    }
+
     mod test_invalid {
         use super::*;
 
@@ -841,7 +1285,7 @@ mod tests {
         const VALID_EXTENDED_PRIVATE_KEY: &str = "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi";
 
         #[test]
-        #[should_panic(expected = "Crate(\"libsecp256k1\", \"InvalidSecretKey\")")]
+        #[should_panic(expected = "Crate(Secp256k1(InvalidSecretKey))")]
         fn from_str_invalid_secret_key() {
             let _result =
                 BitcoinExtendedPrivateKey::<N>::from_str(INVALID_EXTENDED_PRIVATE_KEY_SECP256K1_SECRET_KEY).unwrap();
@@ -6,7 +6,8 @@ use crate::network::BitcoinNetwork;
 use crate::public_key::BitcoinPublicKey;
 use wagyu_model::{
     crypto::{checksum, hash160},
-    AddressError, ChildIndex, DerivationPath, ExtendedPrivateKey, ExtendedPublicKey, ExtendedPublicKeyError, PublicKey,
+    AddressError, ChildIndex, ChildIndexRange, DerivationPath, ExtendedPrivateKey, ExtendedPublicKey,
+    ExtendedPublicKeyError, PublicKey,
 };
 
 use base58::{FromBase58, ToBase58};
@@ -59,47 +60,35 @@ impl<N: BitcoinNetwork> ExtendedPublicKey for BitcoinExtendedPublicKey<N> {
             return Err(ExtendedPublicKeyError::MaximumChildDepthReached(self.depth));
         }
 
+        let indices = path.to_vec()?;
+        if self.depth as usize + indices.len() > 255 {
+            return Err(ExtendedPublicKeyError::MaximumChildDepthReached(self.depth));
+        }
+
         let mut extended_public_key = self.clone();
 
-        for index in path.to_vec()?.into_iter() {
-            let public_key_serialized = &self.public_key.to_secp256k1_public_key().serialize_compressed()[..];
-
-            let mut mac = HmacSha512::new_varkey(&self.chain_code)?;
-            match index {
-                // HMAC-SHA512(Key = cpar, Data = serP(Kpar) || ser32(i))
-                ChildIndex::Normal(_) => mac.input(public_key_serialized),
-                // Return failure
-                ChildIndex::Hardened(_) => {
-                    return Err(ExtendedPublicKeyError::InvalidChildNumber(1 << 31, u32::from(index)))
-                }
-            }
-            // Append the child index in big-endian format
-            mac.input(&u32::from(index).to_be_bytes());
-            let hmac = mac.result().code();
-
-            let mut chain_code = [0u8; 32];
-            chain_code[0..32].copy_from_slice(&hmac[32..]);
-
-            let mut public_key = self.public_key.to_secp256k1_public_key();
-            public_key.tweak_add_assign(&SecretKey::parse_slice(&hmac[..32])?)?;
-            let public_key = Self::PublicKey::from_secp256k1_public_key(public_key, true);
-
-            let mut parent_fingerprint = [0u8; 4];
-            parent_fingerprint.copy_from_slice(&hash160(public_key_serialized)[0..4]);
-
-            extended_public_key = Self {
-                format: extended_public_key.format.clone(),
-                depth: extended_public_key.depth + 1,
-                parent_fingerprint,
-                child_index: index,
-                chain_code,
-                public_key,
-            };
+        for index in indices.into_iter() {
+            let public_key = extended_public_key
+                .public_key
+                .to_secp256k1_public_key()
+                .serialize_compressed();
+            let parent_fingerprint = Self::to_fingerprint(&public_key);
+            extended_public_key = extended_public_key.derive_child(index, &public_key, parent_fingerprint)?;
         }
 
         Ok(extended_public_key)
     }
 
+    /// Returns the extended public keys of the children in the given range of normal child indices,
+    /// computing the serialized public key and fingerprint of this extended public key once for all children.
+    fn derive_children(&self, range: ChildIndexRange) -> Result<Vec<Self>, ExtendedPublicKeyError> {
+        let public_key = self.public_key.to_secp256k1_public_key().serialize_compressed();
+        let parent_fingerprint = Self::to_fingerprint(&public_key);
+        range
+            .map(|index| self.derive_child(index, &public_key, parent_fingerprint))
+            .collect()
+    }
+
     /// Returns the public key of the corresponding extended public key.
     fn to_public_key(&self) -> Self::PublicKey {
         self.public_key.clone()
@@ -109,6 +98,11 @@ impl<N: BitcoinNetwork> ExtendedPublicKey for BitcoinExtendedPublicKey<N> {
     fn to_address(&self, format: &Self::Format) -> Result<Self::Address, AddressError> {
         self.public_key.to_address(format)
     }
+
+    /// Returns the first 32 bits of the key identifier, hash160(compressed_public_key).
+    fn fingerprint(&self) -> [u8; 4] {
+        Self::to_fingerprint(&self.public_key.to_secp256k1_public_key().serialize_compressed())
+    }
 }
 
 impl<N: BitcoinNetwork> BitcoinExtendedPublicKey<N> {
@@ -116,6 +110,109 @@ impl<N: BitcoinNetwork> BitcoinExtendedPublicKey<N> {
     pub fn format(&self) -> BitcoinFormat {
         self.format.clone()
     }
+
+    /// Returns the depth of key derivation, e.g. 0 for the master key.
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// Returns the first 32 bits of the key identifier of the parent key, or zeros for the master key.
+    pub fn parent_fingerprint(&self) -> [u8; 4] {
+        self.parent_fingerprint
+    }
+
+    /// Returns the child index of the key, or 0 for the master key.
+    pub fn child_index(&self) -> ChildIndex {
+        self.child_index
+    }
+
+    /// Returns the chain code of the extended public key.
+    pub fn chain_code(&self) -> [u8; 32] {
+        self.chain_code
+    }
+
+    /// Returns a master extended public key of the given public key and chain code,
+    /// e.g. to derive the child keys of a BIP47 payment code.
+    pub fn from_chain_code(public_key: &BitcoinPublicKey<N>, chain_code: [u8; 32], format: &BitcoinFormat) -> Self {
+        Self {
+            format: format.clone(),
+            depth: 0,
+            parent_fingerprint: [0u8; 4],
+            child_index: ChildIndex::Normal(0),
+            chain_code,
+            public_key: public_key.clone(),
+        }
+    }
+
+    /// Returns the 78-byte BIP32 serialization of the extended public key, without the checksum,
+    /// as used by the global extended public keys of a PSBT.
+    pub fn to_bytes(&self) -> Result<[u8; 78], ExtendedPublicKeyError> {
+        let mut result = [0u8; 78];
+        result[0..4].copy_from_slice(&N::to_extended_public_key_version_bytes(&self.format)?);
+        result[4] = self.depth;
+        result[5..9].copy_from_slice(&self.parent_fingerprint[..]);
+        result[9..13].copy_from_slice(&u32::from(self.child_index).to_be_bytes());
+        result[13..45].copy_from_slice(&self.chain_code[..]);
+        result[45..78].copy_from_slice(&self.public_key.to_secp256k1_public_key().serialize_compressed()[..]);
+        Ok(result)
+    }
+
+    /// Returns the extended public key with the version bytes of the given format, e.g. to convert
+    /// a zpub to an xpub, preserving its key material.
+    pub fn with_format(&self, format: &BitcoinFormat) -> Self {
+        Self {
+            format: format.clone(),
+            ..self.clone()
+        }
+    }
+    /// Returns the child extended public key of the given normal index, from the serialized
+    /// compressed public key and the fingerprint of this extended public key.
+    fn derive_child(
+        &self,
+        index: ChildIndex,
+        public_key: &[u8],
+        parent_fingerprint: [u8; 4],
+    ) -> Result<Self, ExtendedPublicKeyError> {
+        if self.depth == 255 {
+            return Err(ExtendedPublicKeyError::MaximumChildDepthReached(self.depth));
+        }
+
+        let mut mac = HmacSha512::new_varkey(&self.chain_code)?;
+        match index {
+            // HMAC-SHA512(Key = cpar, Data = serP(Kpar) || ser32(i))
+            ChildIndex::Normal(_) => mac.input(public_key),
+            // Return failure
+            ChildIndex::Hardened(_) => {
+                return Err(ExtendedPublicKeyError::InvalidChildNumber(1 << 31, u32::from(index)))
+            }
+        }
+        // Append the child index in big-endian format
+        mac.input(&u32::from(index).to_be_bytes());
+        let hmac = mac.result().code();
+
+        let mut chain_code = [0u8; 32];
+        chain_code[0..32].copy_from_slice(&hmac[32..]);
+
+        let mut child_public_key = self.public_key.to_secp256k1_public_key();
+        child_public_key.tweak_add_assign(&SecretKey::parse_slice(&hmac[..32])?)?;
+        let child_public_key = BitcoinPublicKey::from_secp256k1_public_key(child_public_key, true);
+
+        Ok(Self {
+            format: self.format.clone(),
+            depth: self.depth + 1,
+            parent_fingerprint,
+            child_index: index,
+            chain_code,
+            public_key: child_public_key,
+        })
+    }
+
+    /// Returns the first 32 bits of the key identifier of the given serialized compressed public key.
+    fn to_fingerprint(public_key: &[u8]) -> [u8; 4] {
+        let mut fingerprint = [0u8; 4];
+        fingerprint.copy_from_slice(&hash160(public_key)[0..4]);
+        fingerprint
+    }
 }
 
 impl<N: BitcoinNetwork> FromStr for BitcoinExtendedPublicKey<N> {
@@ -129,7 +226,8 @@ impl<N: BitcoinNetwork> FromStr for BitcoinExtendedPublicKey<N> {
 
         // Check that the version bytes correspond with the correct network.
         let _ = N::from_extended_public_key_version_bytes(&data[0..4])?;
-        let format = BitcoinFormat::from_extended_public_key_version_bytes(&data[0..4])?;
+        // Custom networks share their version bytes across formats, which deserialize as P2PKH.
+        let format = BitcoinFormat::from_extended_public_key_version_bytes(&data[0..4]).unwrap_or(BitcoinFormat::P2PKH);
 
         let mut version = [0u8; 4];
         version.copy_from_slice(&data[0..4]);
@@ -171,15 +269,10 @@ impl<N: BitcoinNetwork> fmt::Display for BitcoinExtendedPublicKey<N> {
     /// https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki#serialization-format
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         let mut result = [0u8; 82];
-        result[0..4].copy_from_slice(match &N::to_extended_public_key_version_bytes(&self.format) {
-            Ok(version) => version,
+        result[0..78].copy_from_slice(match &self.to_bytes() {
+            Ok(bytes) => bytes,
             Err(_) => return Err(fmt::Error),
         });
-        result[4] = self.depth;
-        result[5..9].copy_from_slice(&self.parent_fingerprint[..]);
-        result[9..13].copy_from_slice(&u32::from(self.child_index).to_be_bytes());
-        result[13..45].copy_from_slice(&self.chain_code[..]);
-        result[45..78].copy_from_slice(&self.public_key.to_secp256k1_public_key().serialize_compressed()[..]);
 
         let sum = &checksum(&result[0..78])[0..4];
         result[78..82].copy_from_slice(sum);
@@ -462,6 +555,98 @@ mod tests {
                 test_to_string::<N>(extended_public_key);
             });
         }
+
+        #[test]
+        fn to_bytes() {
+            KEYPAIRS.iter().for_each(|(_, _, _, _, _, _, _, extended_public_key)| {
+                let bytes = extended_public_key.from_base58().unwrap();
+                let extended_public_key = BitcoinExtendedPublicKey::<N>::from_str(extended_public_key).unwrap();
+                assert_eq!(&bytes[0..78], &extended_public_key.to_bytes().unwrap()[..]);
+            });
+        }
+    }
+
+    mod bech32_mainnet {
+        use super::*;
+        use wagyu_model::derivation_path::{ChildIndexRange, RelativeDerivationPath};
+
+        type N = Mainnet;
+
+        // BIP84 test vector - account 0 of "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"
+        const ACCOUNT_EXTENDED_PRIVATE_KEY: &str = "zprvAdG4iTXWBoARxkkzNpNh8r6Qag3irQB8PzEMkAFeTRXxHpbF9z4QgEvBRmfvqWvGp42t42nvgGpNgYSJA9iefm1yYNZKEm7z6qUWCroSQnE";
+        const ACCOUNT_EXTENDED_PUBLIC_KEY: &str = "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs";
+
+        // (relative path, address)
+        const ADDRESSES: [(&str, &str); 3] = [
+            ("0/0", "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu"),
+            ("0/1", "bc1qnjg0jd8228aq7egyzacy8cys3knf9xvrerkf9g"),
+            ("1/0", "bc1q8c6fshw2dlwun7ekn9qwf37cu2rn755upcp6el"),
+        ];
+
+        #[test]
+        fn derive_relative() {
+            let extended_private_key = BitcoinExtendedPrivateKey::<N>::from_str(ACCOUNT_EXTENDED_PRIVATE_KEY).unwrap();
+            let extended_public_key = BitcoinExtendedPublicKey::<N>::from_str(ACCOUNT_EXTENDED_PUBLIC_KEY).unwrap();
+            assert_eq!(extended_public_key, extended_private_key.to_extended_public_key());
+
+            ADDRESSES.iter().for_each(|(path, expected_address)| {
+                let path = RelativeDerivationPath::from_str(path).unwrap();
+
+                let child_public_key = extended_public_key.derive_relative(&path).unwrap();
+                assert_eq!(
+                    *expected_address,
+                    child_public_key.to_address(&BitcoinFormat::Bech32).unwrap().to_string()
+                );
+
+                let child_private_key = extended_private_key.derive_relative(&path).unwrap();
+                assert_eq!(child_public_key, child_private_key.to_extended_public_key());
+            });
+        }
+
+        #[test]
+        fn with_format() {
+            let extended_public_key = BitcoinExtendedPublicKey::<N>::from_str(ACCOUNT_EXTENDED_PUBLIC_KEY).unwrap();
+            let xpub = extended_public_key.with_format(&BitcoinFormat::P2PKH);
+            assert_eq!(
+                "xpub6CatWdiZiodmUeTDp8LT5or8nmbKNcuyvz7WyksVFkKB4RHwCD3XyuvPEbvqAQY3rAPshWcMLoP2fMFMKHPJ4ZeZXYVUhLv1VMrjPC7PW6V",
+                xpub.to_string()
+            );
+            assert_eq!(extended_public_key.to_public_key(), xpub.to_public_key());
+            assert_eq!(
+                ACCOUNT_EXTENDED_PUBLIC_KEY,
+                xpub.with_format(&BitcoinFormat::Bech32).to_string()
+            );
+        }
+
+        #[test]
+        fn derive_children() {
+            let extended_public_key = BitcoinExtendedPublicKey::<N>::from_str(ACCOUNT_EXTENDED_PUBLIC_KEY).unwrap();
+            let chain = extended_public_key
+                .derive_relative(&RelativeDerivationPath::from_str("0").unwrap())
+                .unwrap();
+            let range = ChildIndexRange::from_count(ChildIndex::Normal(0), 2).unwrap();
+            let children = chain.derive_children(range).unwrap();
+            assert_eq!(2, children.len());
+            children
+                .iter()
+                .zip(&ADDRESSES[..2])
+                .for_each(|(child, (_, expected_address))| {
+                    assert_eq!(
+                        *expected_address,
+                        child.to_address(&BitcoinFormat::Bech32).unwrap().to_string()
+                    );
+                });
+
+            let range = ChildIndexRange::from_count(ChildIndex::Hardened(0), 2).unwrap();
+            assert!(chain.derive_children(range).is_err());
+        }
+
+        #[test]
+        fn derive_relative_hardened() {
+            let extended_public_key = BitcoinExtendedPublicKey::<N>::from_str(ACCOUNT_EXTENDED_PUBLIC_KEY).unwrap();
+            let path = RelativeDerivationPath::from_str("0/1'").unwrap();
+            assert!(extended_public_key.derive_relative(&path).is_err());
+        }
     }
 
     mod test_invalid {
@@ -475,7 +660,7 @@ mod tests {
         const VALID_EXTENDED_PUBLIC_KEY: &str = "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8";
 
         #[test]
-        #[should_panic(expected = "Crate(\"libsecp256k1\", \"InvalidPublicKey\")")]
+        #[should_panic(expected = "Crate(Secp256k1(InvalidPublicKey))")]
         fn from_str_invalid_secret_key() {
             let _result =
                 BitcoinExtendedPublicKey::<N>::from_str(INVALID_EXTENDED_PUBLIC_KEY_SECP256K1_PUBLIC_KEY).unwrap();
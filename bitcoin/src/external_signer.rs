@@ -0,0 +1,539 @@
+//!
+//! External Signer
+//!
+//! This module exports the signature hash of each input of an unsigned transaction, with the preimage
+//! it is computed from, for hardware wallets and HSMs to sign outside of the library, and completes
+//! the transaction with the signatures they return.
+//! https://github.com/bitcoin/bips/blob/master/bip-0143.mediawiki
+//!
+
+use crate::address::BitcoinAddress;
+use crate::format::BitcoinFormat;
+use crate::network::BitcoinNetwork;
+use crate::psbt::{BitcoinPsbt, PsbtError};
+use crate::public_key::BitcoinPublicKey;
+use crate::script::{BitcoinScript, BitcoinScriptInstruction};
+use crate::taproot::{TaprootError, TaprootSpend};
+use crate::transaction::{variable_length_integer, BitcoinTransaction, SignatureHash};
+use wagyu_model::crypto::tagged_hash;
+use wagyu_model::no_std::*;
+use wagyu_model::{PublicKey, Transaction, TransactionError};
+
+use core::fmt;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+/// Represents the signature algorithm of an input, which determines its signature hash
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignatureVersion {
+    /// An ECDSA signature of the original signature hash, for P2PKH and bare script inputs
+    Legacy,
+    /// An ECDSA signature of the BIP143 signature hash, for SegWit v0 inputs
+    WitnessV0,
+    /// A BIP340 Schnorr signature of the BIP341 signature hash, for Taproot inputs
+    Taproot,
+}
+
+impl fmt::Display for SignatureVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SignatureVersion::Legacy => write!(f, "legacy"),
+            SignatureVersion::WitnessV0 => write!(f, "witness_v0"),
+            SignatureVersion::Taproot => write!(f, "taproot"),
+        }
+    }
+}
+
+/// Represents the signature hash of an input for an external signer to sign
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BitcoinSignatureRequest {
+    /// The index of the input
+    pub vin: usize,
+    /// The signature algorithm of the input
+    pub version: SignatureVersion,
+    /// The signature hash type the signature commits to
+    pub sighash: SignatureHash,
+    /// The leaf hash of a Taproot script path spend, or `None` for a key path spend
+    pub leaf_hash: Option<[u8; 32]>,
+    /// The serialized transaction data the signature hash is computed from
+    pub preimage: Vec<u8>,
+    /// The signature hash, the message to sign
+    pub digest: [u8; 32],
+}
+
+impl fmt::Display for BitcoinSignatureRequest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "input {} ({}, {}): {}",
+            self.vin,
+            self.version,
+            self.sighash,
+            hex::encode(self.digest)
+        )
+    }
+}
+
+impl<N: BitcoinNetwork> BitcoinTransaction<N> {
+    /// Returns the signature hash of the input at the given index, and the preimage it is computed from.
+    /// A P2TR input is signed through the key path unless it has a script path spend.
+    pub fn signature_request(&self, vin: usize) -> Result<BitcoinSignatureRequest, TransactionError> {
+        let input = match self.parameters().inputs.get(vin) {
+            Some(input) => input,
+            None => return Err(TransactionError::InvalidInputs(format!("input {}", vin))),
+        };
+        let sighash = input.sighash_code;
+
+        let (version, leaf_hash, preimage) = match input.outpoint.address.as_ref().map(BitcoinAddress::format) {
+            Some(BitcoinFormat::P2PKH) | None => {
+                (SignatureVersion::Legacy, None, self.p2pkh_hash_preimage(vin, sighash)?)
+            }
+            Some(BitcoinFormat::P2TR) => {
+                let leaf_hash = match &input.taproot_spend {
+                    Some(TaprootSpend::ScriptPath(leaf, _)) => Some(leaf.to_leaf_hash()?),
+                    _ => None,
+                };
+                let preimage = self.taproot_hash_preimage(vin, sighash, leaf_hash.as_ref())?;
+                (SignatureVersion::Taproot, leaf_hash, preimage)
+            }
+            Some(_) => (
+                SignatureVersion::WitnessV0,
+                None,
+                self.segwit_hash_preimage(vin, sighash)?,
+            ),
+        };
+
+        let digest = match version {
+            SignatureVersion::Taproot => tagged_hash(b"TapSighash", &preimage),
+            _ => {
+                let mut digest = [0u8; 32];
+                digest.copy_from_slice(&Sha256::digest(&Sha256::digest(&preimage)));
+                digest
+            }
+        };
+
+        Ok(BitcoinSignatureRequest {
+            vin,
+            version,
+            sighash,
+            leaf_hash,
+            preimage,
+            digest,
+        })
+    }
+
+    /// Returns the signature hashes of the inputs which are not signed.
+    pub fn signature_requests(&self) -> Result<Vec<BitcoinSignatureRequest>, TransactionError> {
+        let mut requests = vec![];
+        for (vin, input) in self.parameters().inputs.iter().enumerate() {
+            if !input.is_signed {
+                requests.push(self.signature_request(vin)?);
+            }
+        }
+        Ok(requests)
+    }
+
+    /// Returns the transaction with the input at the given index signed by the given signature of
+    /// the public key, made by an external signer over the signature hash of the input.
+    /// ECDSA signatures are DER encoded and followed by the signature hash type, and Schnorr signatures
+    /// are followed by the signature hash type unless it is SIGHASH_DEFAULT. For a P2TR key path spend,
+    /// the public key is the internal key and the signature is made by its tweaked private key.
+    /// The signatures of P2WSH and bare multisig inputs are collected with a PSBT instead.
+    pub fn add_signature(
+        &self,
+        vin: usize,
+        public_key: &BitcoinPublicKey<N>,
+        signature: &[u8],
+    ) -> Result<Self, TransactionError> {
+        let request = self.signature_request(vin)?;
+        let input = &self.parameters().inputs[vin];
+
+        let (sighash_type, signature_bytes) = match (request.version, signature.len()) {
+            (SignatureVersion::Taproot, 64) => (SignatureHash::SIGHASH_DEFAULT as u8, signature),
+            (_, length) if length > 1 => (signature[length - 1], &signature[..length - 1]),
+            _ => return Err(TransactionError::InvalidSignature(vin)),
+        };
+        if sighash_type != request.sighash as u8 {
+            return Err(TransactionError::InvalidSignature(vin));
+        }
+
+        let secp256k1_public_key = public_key.to_secp256k1_public_key();
+        let verified = match request.version {
+            SignatureVersion::Taproot => {
+                let mut schnorr_signature = [0u8; 64];
+                if signature_bytes.len() != 64 {
+                    return Err(TransactionError::InvalidSignature(vin));
+                }
+                schnorr_signature.copy_from_slice(signature_bytes);
+                let signer = match &input.taproot_spend {
+                    Some(TaprootSpend::ScriptPath(..)) => public_key.clone(),
+                    Some(TaprootSpend::KeyPath(merkle_root)) => BitcoinPublicKey::from_x_only_public_key(
+                        &public_key
+                            .to_taproot_tweaked_key(merkle_root.as_ref())
+                            .map_err(TaprootError::from)?
+                            .0,
+                    )
+                    .map_err(TaprootError::from)?,
+                    None => BitcoinPublicKey::from_x_only_public_key(
+                        &public_key.to_taproot_output_key().map_err(TaprootError::from)?,
+                    )
+                    .map_err(TaprootError::from)?,
+                };
+                signer.verify_schnorr(&request.digest, &schnorr_signature)
+            }
+            _ => match secp256k1::Signature::parse_der(signature_bytes) {
+                Ok(parsed) => secp256k1::verify(
+                    &secp256k1::Message::parse_slice(&request.digest)?,
+                    &parsed,
+                    &secp256k1_public_key,
+                ),
+                Err(_) => false,
+            },
+        };
+        if !verified {
+            return Err(TransactionError::InvalidSignature(vin));
+        }
+
+        let public_key_bytes = match public_key.is_compressed() {
+            true => secp256k1_public_key.serialize_compressed().to_vec(),
+            false => secp256k1_public_key.serialize().to_vec(),
+        };
+
+        let mut transaction = self.clone();
+        let (script_sig, witnesses) = match input.outpoint.address.as_ref() {
+            Some(address) if address.format() == BitcoinFormat::P2TR => {
+                let mut output_key = [0u8; 32];
+                match &input.outpoint.script_pub_key {
+                    Some(script_pub_key) if script_pub_key.len() == 34 => {
+                        output_key.copy_from_slice(&script_pub_key[2..])
+                    }
+                    Some(_) => return Err(TransactionError::InvalidScriptPubKey("P2TR".into())),
+                    None => return Err(TransactionError::MissingOutpointScriptPublicKey),
+                };
+                match &input.taproot_spend {
+                    Some(TaprootSpend::ScriptPath(leaf, control_block)) => {
+                        if !contains_push(leaf.script(), &public_key.to_x_only_public_key()) {
+                            return Err(TransactionError::InvalidSignature(vin));
+                        }
+                        if control_block.leaf_version() != leaf.version()
+                            || !control_block.verify(&output_key, leaf.script())
+                        {
+                            return Err(TransactionError::InvalidInputs("P2TR control block".into()));
+                        }
+                        (
+                            vec![],
+                            vec![signature.to_vec(), leaf.script().to_vec(), control_block.to_bytes()],
+                        )
+                    }
+                    Some(TaprootSpend::KeyPath(merkle_root)) => {
+                        if public_key
+                            .to_taproot_tweaked_key(merkle_root.as_ref())
+                            .map_err(TaprootError::from)?
+                            .0
+                            != output_key
+                        {
+                            return Err(TransactionError::InvalidSignature(vin));
+                        }
+                        (vec![], vec![signature.to_vec()])
+                    }
+                    None => {
+                        if public_key.to_taproot_output_key().map_err(TaprootError::from)? != output_key {
+                            return Err(TransactionError::InvalidSignature(vin));
+                        }
+                        (vec![], vec![signature.to_vec()])
+                    }
+                }
+            }
+            Some(address) => {
+                let format = address.format();
+                if format == BitcoinFormat::P2WSH {
+                    return Err(TransactionError::UnsupportedPreimage("P2WSH".into()));
+                }
+                if address != &public_key.to_address(&format)? {
+                    return Err(TransactionError::InvalidSignature(vin));
+                }
+                match format {
+                    BitcoinFormat::P2PKH => {
+                        let script_sig = BitcoinScript::builder()
+                            .push_data(signature)
+                            .push_data(&public_key_bytes)
+                            .into_script()
+                            .to_bytes();
+                        (script_sig, vec![])
+                    }
+                    BitcoinFormat::P2SH_P2WPKH => {
+                        let redeem_script = match &input.outpoint.redeem_script {
+                            Some(redeem_script) => redeem_script.clone(),
+                            None => return Err(TransactionError::InvalidInputs("P2SH_P2WPKH".into())),
+                        };
+                        let script_sig = BitcoinScript::builder()
+                            .push_data(&redeem_script)
+                            .into_script()
+                            .to_bytes();
+                        (script_sig, vec![signature.to_vec(), public_key_bytes])
+                    }
+                    _ => (vec![], vec![signature.to_vec(), public_key_bytes]),
+                }
+            }
+            None => {
+                let script_pub_key = match &input.outpoint.script_pub_key {
+                    Some(script_pub_key) => BitcoinScript::new(script_pub_key),
+                    None => return Err(TransactionError::MissingOutpointScriptPublicKey),
+                };
+                match script_pub_key.to_p2pk_public_key() {
+                    Some(p2pk_public_key) if p2pk_public_key == public_key_bytes => (
+                        BitcoinScript::builder().push_data(signature).into_script().to_bytes(),
+                        vec![],
+                    ),
+                    Some(_) => return Err(TransactionError::InvalidSignature(vin)),
+                    None => return Err(TransactionError::UnsupportedPreimage("bare multisig".into())),
+                }
+            }
+        };
+
+        let input = &mut transaction.parameters.inputs[vin];
+        input.script_sig = script_sig;
+        if !witnesses.is_empty() {
+            input.witnesses = vec![];
+            for witness in witnesses {
+                input
+                    .witnesses
+                    .push([variable_length_integer(witness.len() as u64)?, witness].concat());
+            }
+            transaction.parameters.segwit_flag = true;
+        }
+        transaction.parameters.inputs[vin].is_signed = true;
+        Ok(transaction)
+    }
+}
+
+impl<N: BitcoinNetwork> BitcoinPsbt<N> {
+    /// Returns the signature hash of the input at the given index, with the sighash type of the input,
+    /// for an external signer whose signature is added with `add_signature`.
+    pub fn signature_request(&self, vin: usize) -> Result<BitcoinSignatureRequest, PsbtError> {
+        let input = self.inputs().get(vin).ok_or(PsbtError::MissingInput(vin))?;
+        let mut parameters = self.unsigned_transaction()?;
+        parameters.inputs[vin].outpoint = self.to_outpoint(vin)?;
+        if let Some(sighash) = input.sighash_type.and_then(SignatureHash::from_type) {
+            parameters.inputs[vin].sighash_code = sighash;
+        }
+        Ok(BitcoinTransaction::new(&parameters)?.signature_request(vin)?)
+    }
+}
+
+/// Returns true if the script pushes the given data.
+fn contains_push(script: &[u8], data: &[u8]) -> bool {
+    match BitcoinScript::new(script).to_instructions() {
+        Ok(instructions) => instructions.iter().any(|instruction| match instruction {
+            BitcoinScriptInstruction::Push(push) => push[..] == data[..],
+            _ => false,
+        }),
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::amount::BitcoinAmount;
+    use crate::network::*;
+    use crate::private_key::BitcoinPrivateKey;
+    use crate::transaction::{BitcoinTransactionInput, BitcoinTransactionOutput, BitcoinTransactionParameters};
+    use wagyu_model::crypto::{hash160, secp256k1_sign};
+    use wagyu_model::PrivateKey;
+
+    use core::str::FromStr;
+
+    type N = Mainnet;
+
+    const PRIVATE_KEY: &str = "KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73sVHnoWn";
+    const DESTINATION: &str = "1NoZQSmjYHUZMbqLerwmT4xfe8A6mAo8TT";
+    const TRANSACTION_ID: &str = "61d520ccb74288c96bc1a2b20ea1c0d5a704776dd0164a396efec3ea7040349d";
+
+    // Returns an unsigned transaction spending one output of each format controlled by the private key
+    fn transaction(private_key: &BitcoinPrivateKey<N>) -> BitcoinTransaction<N> {
+        let public_key = private_key
+            .to_public_key()
+            .to_secp256k1_public_key()
+            .serialize_compressed();
+        let redeem_script = [vec![0x00, 0x14], hash160(&public_key)].concat();
+
+        let formats = [
+            BitcoinFormat::P2PKH,
+            BitcoinFormat::Bech32,
+            BitcoinFormat::P2SH_P2WPKH,
+            BitcoinFormat::P2TR,
+        ];
+        let inputs = formats
+            .iter()
+            .enumerate()
+            .map(|(vout, format)| {
+                let redeem_script = match format {
+                    BitcoinFormat::P2SH_P2WPKH => Some(redeem_script.clone()),
+                    _ => None,
+                };
+                BitcoinTransactionInput::<N>::new(
+                    hex::decode(TRANSACTION_ID).unwrap(),
+                    vout as u32,
+                    Some(private_key.to_address(format).unwrap()),
+                    Some(BitcoinAmount(100_000)),
+                    redeem_script,
+                    None,
+                    None,
+                    SignatureHash::SIGHASH_ALL,
+                )
+                .unwrap()
+            })
+            .collect();
+
+        let parameters = BitcoinTransactionParameters::<N> {
+            version: 2,
+            inputs,
+            outputs: vec![BitcoinTransactionOutput::new(
+                &BitcoinAddress::<N>::from_str(DESTINATION).unwrap(),
+                BitcoinAmount(390_000),
+            )
+            .unwrap()],
+            lock_time: 0,
+            segwit_flag: false,
+        };
+        BitcoinTransaction::<N>::new(&parameters).unwrap()
+    }
+
+    // Returns the signature of the signature hash as made by an external signer
+    fn external_signature(request: &BitcoinSignatureRequest, private_key: &BitcoinPrivateKey<N>) -> Vec<u8> {
+        match request.version {
+            SignatureVersion::Taproot => {
+                let private_key = private_key.to_taproot_tweaked_private_key(None).unwrap();
+                let mut signature = private_key.sign_schnorr(&request.digest, &[0u8; 32]).unwrap().to_vec();
+                if request.sighash != SignatureHash::SIGHASH_DEFAULT {
+                    signature.push(request.sighash as u8);
+                }
+                signature
+            }
+            _ => {
+                let (signature, _) = secp256k1_sign(
+                    &secp256k1::Message::parse_slice(&request.digest).unwrap(),
+                    &private_key.to_secp256k1_secret_key(),
+                );
+                let mut signature = signature.serialize_der().as_ref().to_vec();
+                signature.push(request.sighash as u8);
+                signature
+            }
+        }
+    }
+
+    #[test]
+    fn signature_requests() {
+        let private_key = BitcoinPrivateKey::<N>::from_str(PRIVATE_KEY).unwrap();
+        let transaction = transaction(&private_key);
+        let requests = transaction.signature_requests().unwrap();
+
+        let versions: Vec<SignatureVersion> = requests.iter().map(|request| request.version).collect();
+        assert_eq!(
+            vec![
+                SignatureVersion::Legacy,
+                SignatureVersion::WitnessV0,
+                SignatureVersion::WitnessV0,
+                SignatureVersion::Taproot
+            ],
+            versions
+        );
+        assert_eq!(
+            transaction.p2pkh_hash_preimage(0, SignatureHash::SIGHASH_ALL).unwrap(),
+            requests[0].preimage
+        );
+        assert_eq!(
+            transaction.segwit_hash_preimage(1, SignatureHash::SIGHASH_ALL).unwrap(),
+            requests[1].preimage
+        );
+        assert_eq!(tagged_hash(b"TapSighash", &requests[3].preimage), requests[3].digest);
+        assert!(requests.iter().all(|request| request.leaf_hash.is_none()));
+
+        // Signed inputs have no signature requests
+        let signed = transaction.sign(&private_key).unwrap();
+        assert!(signed.signature_requests().unwrap().is_empty());
+        assert!(transaction.signature_request(4).is_err());
+    }
+
+    #[test]
+    fn add_external_signatures() {
+        let private_key = BitcoinPrivateKey::<N>::from_str(PRIVATE_KEY).unwrap();
+        let public_key = private_key.to_public_key();
+        let mut transaction = transaction(&private_key);
+
+        for request in transaction.signature_requests().unwrap() {
+            let signature = external_signature(&request, &private_key);
+            transaction = transaction.add_signature(request.vin, &public_key, &signature).unwrap();
+        }
+
+        let signed = self::transaction(&private_key).sign(&private_key).unwrap();
+        assert_eq!(
+            signed.to_transaction_bytes().unwrap(),
+            transaction.to_transaction_bytes().unwrap()
+        );
+    }
+
+    #[test]
+    fn reject_invalid_signatures() {
+        let private_key = BitcoinPrivateKey::<N>::from_str(PRIVATE_KEY).unwrap();
+        let other_private_key = BitcoinPrivateKey::<N>::new(&mut rand::thread_rng()).unwrap();
+        let transaction = transaction(&private_key);
+
+        for request in transaction.signature_requests().unwrap() {
+            // A signature of the public key by another private key
+            let signature = external_signature(&request, &other_private_key);
+            match transaction.add_signature(request.vin, &private_key.to_public_key(), &signature) {
+                Err(TransactionError::InvalidSignature(vin)) => assert_eq!(request.vin, vin),
+                result => panic!("unexpected result {:?}", result),
+            };
+
+            // A signature of another public key, which cannot spend the input
+            assert!(transaction
+                .add_signature(request.vin, &other_private_key.to_public_key(), &signature)
+                .is_err());
+
+            // A signature with a different signature hash type
+            let mut signature = external_signature(&request, &private_key);
+            let last = signature.len() - 1;
+            signature[last] = SignatureHash::SIGHASH_NONE as u8;
+            assert!(transaction
+                .add_signature(request.vin, &private_key.to_public_key(), &signature)
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn psbt_signature_request() {
+        let private_key = BitcoinPrivateKey::<N>::from_str(PRIVATE_KEY).unwrap();
+        let mut parameters = transaction(&private_key).parameters().clone();
+        parameters.inputs = vec![parameters.inputs[1].clone()];
+        let transaction = BitcoinTransaction::<N>::new(&parameters).unwrap();
+
+        let mut psbt = BitcoinPsbt::<N>::new(&parameters).unwrap();
+        assert!(psbt.signature_request(0).is_err());
+        psbt.inputs_mut()[0].witness_utxo = Some(
+            BitcoinTransactionOutput::new(
+                &private_key.to_address(&BitcoinFormat::Bech32).unwrap(),
+                BitcoinAmount(100_000),
+            )
+            .unwrap(),
+        );
+
+        let request = psbt.signature_request(0).unwrap();
+        assert_eq!(transaction.signature_request(0).unwrap(), request);
+        let public_key = private_key
+            .to_public_key()
+            .to_secp256k1_public_key()
+            .serialize_compressed();
+        psbt.add_signature(0, &public_key, &external_signature(&request, &private_key))
+            .unwrap();
+        psbt.finalize().unwrap();
+        assert_eq!(
+            transaction.sign(&private_key).unwrap().to_transaction_bytes().unwrap(),
+            psbt.extract().unwrap().to_transaction_bytes().unwrap()
+        );
+        assert!(psbt.signature_request(1).is_err());
+    }
+}
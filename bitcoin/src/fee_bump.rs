@@ -0,0 +1,269 @@
+//!
+//! Fee Bump
+//!
+//! This module raises the fee rate of a signed transaction which is stuck unconfirmed, either by
+//! replacing it with a transaction spending the same inputs with a smaller change output (BIP125),
+//! or by spending one of its outputs in a child transaction which pays for both (CPFP).
+//! https://github.com/bitcoin/bips/blob/master/bip-0125.mediawiki
+//!
+
+use crate::address::BitcoinAddress;
+use crate::amount::BitcoinAmount;
+use crate::network::BitcoinNetwork;
+use crate::policy::INCREMENTAL_RELAY_FEE_RATE;
+use crate::transaction::{
+    create_script_pub_key, weight_to_fee, BitcoinTransaction, BitcoinTransactionInput, BitcoinTransactionOutput,
+    BitcoinTransactionParameters, SignatureHash,
+};
+use wagyu_model::no_std::*;
+use wagyu_model::TransactionError;
+
+use core::cmp::max;
+
+impl<N: BitcoinNetwork> BitcoinTransaction<N> {
+    /// Returns the unsigned replacement of the transaction paying the given fee rate (in Satoshi per
+    /// virtual byte), which spends the same inputs and pays the additional fee from the change output
+    /// at the given index. The inputs must have the outpoint amounts and addresses of the transaction.
+    pub fn to_replacement(
+        &self,
+        change_vout: usize,
+        fee_rate: u64,
+    ) -> Result<BitcoinTransactionParameters<N>, TransactionError> {
+        let fee = self.parameters().fee()?;
+
+        let mut parameters = self.parameters().clone();
+        for input in parameters.inputs.iter_mut() {
+            input.script_sig = vec![];
+            input.witnesses = vec![];
+            input.is_signed = false;
+        }
+        parameters.segwit_flag = false;
+        let mut parameters = parameters.with_replace_by_fee();
+
+        // The replacement pays for its own relay on top of the fee of the replaced transaction
+        let weight = parameters.estimate_weight()?;
+        let min_fee = fee.0 + weight_to_fee(weight, INCREMENTAL_RELAY_FEE_RATE).0;
+        let replacement_fee = max(weight_to_fee(weight, fee_rate).0, min_fee);
+
+        let change = match parameters.outputs.get_mut(change_vout) {
+            Some(change) => change,
+            None => return Err(TransactionError::InvalidInputs(format!("output {}", change_vout))),
+        };
+        let amount = change.amount.0 - (replacement_fee - fee.0);
+        let dust = change.dust_threshold()?;
+        if amount < dust.0 {
+            return Err(TransactionError::DustChange(amount, dust.0));
+        }
+        change.amount = BitcoinAmount(amount);
+
+        Ok(parameters)
+    }
+
+    /// Returns the unsigned child transaction spending the output at the given index, controlled by
+    /// the given address, to the destination address, with a fee raising the fee rate of the transaction
+    /// and its child together to the given fee rate (in Satoshi per virtual byte).
+    /// The inputs of the transaction must have their outpoint amounts.
+    pub fn to_child(
+        &self,
+        vout: u32,
+        address: &BitcoinAddress<N>,
+        redeem_script: Option<Vec<u8>>,
+        destination: &BitcoinAddress<N>,
+        fee_rate: u64,
+    ) -> Result<BitcoinTransactionParameters<N>, TransactionError> {
+        let output = match self.parameters().outputs.get(vout as usize) {
+            Some(output) => output,
+            None => return Err(TransactionError::InvalidInputs(format!("output {}", vout))),
+        };
+        if output.script_pub_key != create_script_pub_key(address)? {
+            return Err(TransactionError::InvalidScriptPubKey(address.format().to_string()));
+        }
+
+        let input = BitcoinTransactionInput::<N>::new(
+            self.txid()?,
+            vout,
+            Some(address.clone()),
+            Some(output.amount),
+            redeem_script,
+            None,
+            None,
+            SignatureHash::SIGHASH_ALL,
+        )?;
+        let mut child = BitcoinTransactionParameters::<N> {
+            version: 2,
+            inputs: vec![input],
+            outputs: vec![BitcoinTransactionOutput::new(destination, BitcoinAmount(0))?],
+            lock_time: 0,
+            segwit_flag: false,
+        };
+
+        // The child pays the fee the transaction lacks at the fee rate, and at least its own fee
+        let package_weight = self.size()?.weight() + child.estimate_weight()?;
+        let package_fee = weight_to_fee(package_weight, fee_rate).0 - self.parameters().fee()?.0;
+        let fee = max(package_fee, child.estimate_fee(fee_rate)?.0);
+
+        let amount = output.amount.0 - fee;
+        if amount < 0 {
+            return Err(TransactionError::InsufficientFunds(output.amount.0, fee));
+        }
+        let dust = child.outputs[0].dust_threshold()?;
+        if amount < dust.0 {
+            return Err(TransactionError::DustOutput(0, amount, dust.0));
+        }
+        child.outputs[0].amount = BitcoinAmount(amount);
+
+        Ok(child)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::*;
+    use crate::private_key::BitcoinPrivateKey;
+    use crate::transaction::Outpoint;
+    use wagyu_model::Transaction;
+
+    use core::str::FromStr;
+
+    type N = Mainnet;
+
+    const PRIVATE_KEY: &str = "KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73sVHnoWn";
+    const BECH32_ADDRESS: &str = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4";
+    const P2PKH_ADDRESS: &str = "1NoZQSmjYHUZMbqLerwmT4xfe8A6mAo8TT";
+    const TRANSACTION_ID: &str = "61d520ccb74288c96bc1a2b20ea1c0d5a704776dd0164a396efec3ea7040349d";
+
+    fn address(address: &str) -> BitcoinAddress<N> {
+        BitcoinAddress::<N>::from_str(address).unwrap()
+    }
+
+    // Returns a signed transaction spending 100,000 to 50,000 and the change at 1 sat/vB
+    fn transaction() -> BitcoinTransaction<N> {
+        let input = BitcoinTransactionInput::<N>::new(
+            hex::decode(TRANSACTION_ID).unwrap(),
+            0,
+            Some(address(BECH32_ADDRESS)),
+            Some(BitcoinAmount(100_000)),
+            None,
+            None,
+            None,
+            SignatureHash::SIGHASH_ALL,
+        )
+        .unwrap();
+        let parameters = BitcoinTransactionParameters::<N> {
+            version: 2,
+            inputs: vec![input],
+            outputs: vec![BitcoinTransactionOutput::new(&address(P2PKH_ADDRESS), BitcoinAmount(50_000)).unwrap()],
+            lock_time: 0,
+            segwit_flag: false,
+        }
+        .with_replace_by_fee()
+        .with_change(&address(BECH32_ADDRESS), 1)
+        .unwrap();
+
+        let private_key = BitcoinPrivateKey::<N>::from_str(PRIVATE_KEY).unwrap();
+        BitcoinTransaction::<N>::new(&parameters)
+            .unwrap()
+            .sign(&private_key)
+            .unwrap()
+    }
+
+    #[test]
+    fn replace_by_fee() {
+        let transaction = transaction();
+        let fee = transaction.parameters().fee().unwrap();
+        assert_eq!(BitcoinAmount(144), fee);
+
+        let replacement = transaction.to_replacement(1, 10).unwrap();
+        assert_eq!(
+            transaction.parameters().inputs[0].outpoint,
+            replacement.inputs[0].outpoint
+        );
+        assert!(replacement.inputs[0].witnesses.is_empty());
+        assert!(replacement.is_replaceable());
+        assert_eq!(transaction.parameters().outputs[0], replacement.outputs[0]);
+        assert_eq!(replacement.estimate_fee(10).unwrap(), replacement.fee().unwrap());
+        assert_eq!(
+            transaction.parameters().outputs[1].amount.0 - (replacement.fee().unwrap().0 - fee.0),
+            replacement.outputs[1].amount.0
+        );
+
+        // A fee rate at most that of the replaced transaction pays the incremental relay fee
+        let replacement = transaction.to_replacement(1, 1).unwrap();
+        assert_eq!(BitcoinAmount(288), replacement.fee().unwrap());
+
+        let private_key = BitcoinPrivateKey::<N>::from_str(PRIVATE_KEY).unwrap();
+        let signed = BitcoinTransaction::<N>::new(&replacement)
+            .unwrap()
+            .sign(&private_key)
+            .unwrap();
+        assert_ne!(
+            transaction.to_transaction_id().unwrap().to_string(),
+            signed.to_transaction_id().unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn invalid_replacements() {
+        let transaction = transaction();
+        assert!(transaction.to_replacement(2, 10).is_err());
+        match transaction.to_replacement(1, 1_000) {
+            Err(TransactionError::DustChange(_, 294)) => (),
+            result => panic!("unexpected result {:?}", result),
+        };
+
+        // The amounts of the inputs are unknown once the transaction is parsed
+        let parsed =
+            BitcoinTransaction::<N>::from_str(&hex::encode(transaction.to_transaction_bytes().unwrap())).unwrap();
+        assert!(parsed.to_replacement(1, 10).is_err());
+        let outpoint = transaction.parameters().inputs[0].outpoint.clone();
+        let parsed = parsed.update_outpoint(
+            Outpoint::<N>::new(
+                outpoint.reverse_transaction_id,
+                outpoint.index,
+                outpoint.address,
+                outpoint.amount,
+                None,
+                None,
+            )
+            .unwrap(),
+        );
+        assert_eq!(
+            transaction.to_replacement(1, 10).unwrap(),
+            parsed.to_replacement(1, 10).unwrap()
+        );
+    }
+
+    #[test]
+    fn child_pays_for_parent() {
+        let transaction = transaction();
+        let change = &transaction.parameters().outputs[1];
+        let child = transaction
+            .to_child(1, &address(BECH32_ADDRESS), None, &address(BECH32_ADDRESS), 10)
+            .unwrap();
+
+        assert_eq!(1, child.inputs.len());
+        assert_eq!(Some(change.amount), child.inputs[0].outpoint.amount);
+        let mut transaction_id = child.inputs[0].outpoint.reverse_transaction_id.clone();
+        transaction_id.reverse();
+        assert_eq!(
+            transaction.to_transaction_id().unwrap().to_string(),
+            hex::encode(transaction_id)
+        );
+
+        // The parent and child together pay 10 sat/vB
+        let package_weight = transaction.size().unwrap().weight() + child.estimate_weight().unwrap();
+        let package_fee = transaction.parameters().fee().unwrap().0 + child.fee().unwrap().0;
+        assert_eq!(weight_to_fee(package_weight, 10).0, package_fee);
+
+        assert!(transaction
+            .to_child(1, &address(P2PKH_ADDRESS), None, &address(BECH32_ADDRESS), 10)
+            .is_err());
+        assert!(transaction
+            .to_child(2, &address(BECH32_ADDRESS), None, &address(BECH32_ADDRESS), 10)
+            .is_err());
+        assert!(transaction
+            .to_child(1, &address(BECH32_ADDRESS), None, &address(BECH32_ADDRESS), 1_000)
+            .is_err());
+    }
+}
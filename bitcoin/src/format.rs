@@ -15,8 +15,12 @@ pub enum BitcoinFormat {
     P2WSH,
     /// SegWit Pay-to-Witness-Public-Key Hash, e.g. 34AgLJhwXrvmkZS1o5TrcdeevMt22Nar53
     P2SH_P2WPKH,
-    /// Bech32, e.g. bc1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7k7grplx
+    /// Bech32, e.g. bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4
     Bech32,
+    /// Taproot Pay-to-Taproot, e.g. bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr
+    P2TR,
+    /// Pay-to-Witness-Program of a future witness version, e.g. bc1zw508d6qejxtdg4y5r3zarvaryvaxxpcs
+    FutureWitness,
 }
 
 impl Format for BitcoinFormat {}
@@ -40,22 +44,26 @@ impl BitcoinFormat {
         }
     }
 
-    /// Returns the network of the given extended private key version bytes.
+    /// Returns the format of the given extended private key version bytes.
     /// https://github.com/satoshilabs/slips/blob/master/slip-0132.md
     pub fn from_extended_private_key_version_bytes(prefix: &[u8]) -> Result<Self, ExtendedPrivateKeyError> {
         match prefix[0..4] {
             [0x04, 0x88, 0xAD, 0xE4] | [0x04, 0x35, 0x83, 0x94] => Ok(BitcoinFormat::P2PKH),
             [0x04, 0x9D, 0x78, 0x78] | [0x04, 0x4A, 0x4E, 0x28] => Ok(BitcoinFormat::P2SH_P2WPKH),
+            [0x04, 0xB2, 0x43, 0x0C] | [0x04, 0x5F, 0x18, 0xBC] => Ok(BitcoinFormat::Bech32),
+            [0x02, 0xAA, 0x7A, 0x99] | [0x02, 0x57, 0x50, 0x48] => Ok(BitcoinFormat::P2WSH),
             _ => Err(ExtendedPrivateKeyError::InvalidVersionBytes(prefix.to_vec())),
         }
     }
 
-    /// Returns the network of the given extended public key version bytes.
+    /// Returns the format of the given extended public key version bytes.
     /// https://github.com/satoshilabs/slips/blob/master/slip-0132.md
     pub fn from_extended_public_key_version_bytes(prefix: &[u8]) -> Result<Self, ExtendedPublicKeyError> {
         match prefix[0..4] {
             [0x04, 0x88, 0xB2, 0x1E] | [0x04, 0x35, 0x87, 0xCF] => Ok(BitcoinFormat::P2PKH),
             [0x04, 0x9D, 0x7C, 0xB2] | [0x04, 0x4A, 0x52, 0x62] => Ok(BitcoinFormat::P2SH_P2WPKH),
+            [0x04, 0xB2, 0x47, 0x46] | [0x04, 0x5F, 0x1C, 0xF6] => Ok(BitcoinFormat::Bech32),
+            [0x02, 0xAA, 0x7E, 0xD3] | [0x02, 0x57, 0x54, 0x83] => Ok(BitcoinFormat::P2WSH),
             _ => Err(ExtendedPublicKeyError::InvalidVersionBytes(prefix.to_vec())),
         }
     }
@@ -68,6 +76,8 @@ impl fmt::Display for BitcoinFormat {
             BitcoinFormat::P2WSH => write!(f, "p2wsh"),
             BitcoinFormat::P2SH_P2WPKH => write!(f, "p2sh_p2wpkh"),
             BitcoinFormat::Bech32 => write!(f, "bech32"),
+            BitcoinFormat::P2TR => write!(f, "p2tr"),
+            BitcoinFormat::FutureWitness => write!(f, "future_witness"),
         }
     }
 }
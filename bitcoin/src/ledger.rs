@@ -0,0 +1,1117 @@
+//!
+//! Ledger
+//!
+//! This module contains a client of the Bitcoin app of Ledger devices over USB HID, which displays the
+//! addresses derived by wagyu on the device for verification and signs the PSBTs built by wagyu. The app
+//! requests the policy of the wallet and the maps of the PSBT by their hashes and Merkle roots, which the
+//! client answers from the data it committed to.
+//! https://github.com/LedgerHQ/app-bitcoin-new/blob/develop/doc/bitcoin.md
+//! https://github.com/LedgerHQ/app-bitcoin-new/blob/develop/doc/wallet.md
+//!
+
+use crate::address::BitcoinAddress;
+use crate::derivation_path::BitcoinDerivationPath;
+use crate::extended_public_key::BitcoinExtendedPublicKey;
+use crate::format::BitcoinFormat;
+use crate::network::BitcoinNetwork;
+use crate::psbt::{BitcoinPsbt, PsbtError};
+use crate::transaction::{read_variable_length_integer, variable_length_integer};
+use wagyu_model::no_std::*;
+use wagyu_model::{
+    AddressError, CrateError, DerivationPath, DerivationPathError, ExtendedPublicKeyError, KeyOrigin, TransactionError,
+};
+
+use core::str::FromStr;
+use sha2::{Digest, Sha256};
+use std::collections::VecDeque;
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::Path;
+
+/// The class of the commands of the Bitcoin app
+pub const LEDGER_CLA: u8 = 0xe1;
+/// The class of the commands of the framework of the Bitcoin app, which continue interrupted commands
+pub const LEDGER_FRAMEWORK_CLA: u8 = 0xf8;
+/// The version of the protocol of the Bitcoin app spoken by the client
+pub const LEDGER_PROTOCOL_VERSION: u8 = 0x01;
+/// The USB vendor id of Ledger devices
+pub const LEDGER_VENDOR_ID: u16 = 0x2c97;
+
+const INS_GET_EXTENDED_PUBKEY: u8 = 0x00;
+const INS_GET_WALLET_ADDRESS: u8 = 0x03;
+const INS_SIGN_PSBT: u8 = 0x04;
+const INS_GET_MASTER_FINGERPRINT: u8 = 0x05;
+const INS_CONTINUE_INTERRUPTED: u8 = 0x01;
+
+const CCMD_YIELD: u8 = 0x10;
+const CCMD_GET_PREIMAGE: u8 = 0x40;
+const CCMD_GET_MERKLE_LEAF_PROOF: u8 = 0x41;
+const CCMD_GET_MERKLE_LEAF_INDEX: u8 = 0x42;
+const CCMD_GET_MORE_ELEMENTS: u8 = 0xa0;
+
+const SW_OK: u16 = 0x9000;
+const SW_INTERRUPTED_EXECUTION: u16 = 0xe000;
+const SW_DENIED_BY_USER: u16 = 0x6985;
+const SW_LOCKED: u16 = 0x5515;
+const SW_CLA_NOT_SUPPORTED: u16 = 0x6e00;
+const SW_INS_NOT_SUPPORTED: u16 = 0x6d00;
+
+/// The size of the HID reports of Ledger devices
+const HID_PACKET_SIZE: usize = 64;
+/// The channel of the APDUs exchanged over HID
+const HID_CHANNEL: u16 = 0x0101;
+/// The tag of the HID packets carrying APDUs
+const HID_TAG_APDU: u8 = 0x05;
+
+/// The maximum length of the data of an APDU or of a reply to a client command
+const MAX_DATA_LENGTH: usize = 255;
+
+/// Represents a connection to a Ledger device, which exchanges an APDU for the response data and status word
+pub trait LedgerTransport {
+    /// Returns the data and status word of the response of the device to the given APDU.
+    fn exchange(&mut self, apdu: &[u8]) -> Result<(Vec<u8>, u16), LedgerError>;
+}
+
+/// Represents a Ledger device connected over USB HID, e.g. the hidraw device of Linux
+pub struct LedgerHid<D: Read + Write> {
+    device: D,
+}
+
+impl<D: Read + Write> LedgerHid<D> {
+    /// Returns a transport over the given HID device.
+    pub fn new(device: D) -> Self {
+        Self { device }
+    }
+}
+
+impl LedgerHid<File> {
+    /// Returns a transport over the HID device at the given path, e.g. `/dev/hidraw0`.
+    pub fn open_path<P: AsRef<Path>>(path: P) -> Result<Self, LedgerError> {
+        Ok(Self::new(OpenOptions::new().read(true).write(true).open(path)?))
+    }
+
+    /// Returns a transport over the first Ledger device connected over USB.
+    #[cfg(target_os = "linux")]
+    pub fn open() -> Result<Self, LedgerError> {
+        let hid_id = format!(":0000{:04X}:", LEDGER_VENDOR_ID);
+        let devices = match std::fs::read_dir("/sys/class/hidraw") {
+            Ok(devices) => devices,
+            Err(_) => return Err(LedgerError::DeviceNotFound),
+        };
+        let mut devices = devices
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .collect::<Vec<_>>();
+        devices.sort();
+
+        for device in devices {
+            let uevent = std::fs::read_to_string(device.join("device/uevent")).unwrap_or_default();
+            // The APDUs are exchanged over the first interface, the others being U2F and WebUSB
+            let interface = std::fs::read_to_string(device.join("device/../bInterfaceNumber")).unwrap_or_default();
+            let is_ledger = uevent
+                .lines()
+                .any(|line| line.starts_with("HID_ID=") && line.to_uppercase().contains(&hid_id));
+            if let (true, "00", Some(name)) = (is_ledger, interface.trim(), device.file_name()) {
+                return Self::open_path(Path::new("/dev").join(name));
+            }
+        }
+        Err(LedgerError::DeviceNotFound)
+    }
+
+    /// Returns a transport over the first Ledger device connected over USB. Devices are only
+    /// discovered on Linux, so the path of the device must be given on other platforms.
+    #[cfg(not(target_os = "linux"))]
+    pub fn open() -> Result<Self, LedgerError> {
+        Err(LedgerError::DeviceNotFound)
+    }
+}
+
+impl<D: Read + Write> LedgerTransport for LedgerHid<D> {
+    /// Writes the APDU in HID packets, prefixed by its length, and reads the packets of the response.
+    fn exchange(&mut self, apdu: &[u8]) -> Result<(Vec<u8>, u16), LedgerError> {
+        let data = [(apdu.len() as u16).to_be_bytes().to_vec(), apdu.to_vec()].concat();
+        for (sequence, chunk) in data.chunks(HID_PACKET_SIZE - 5).enumerate() {
+            // Each report is prefixed by the report id 0
+            let mut packet = vec![0x00];
+            packet.extend(&HID_CHANNEL.to_be_bytes());
+            packet.push(HID_TAG_APDU);
+            packet.extend(&(sequence as u16).to_be_bytes());
+            packet.extend(chunk);
+            packet.resize(HID_PACKET_SIZE + 1, 0);
+            self.device.write_all(&packet)?;
+        }
+
+        let mut response = vec![];
+        let mut length = 0;
+        let mut sequence = 0u16;
+        loop {
+            let mut packet = [0u8; HID_PACKET_SIZE];
+            self.device.read_exact(&mut packet)?;
+            if packet[..2] != HID_CHANNEL.to_be_bytes()
+                || packet[2] != HID_TAG_APDU
+                || packet[3..5] != sequence.to_be_bytes()
+            {
+                return Err(LedgerError::InvalidResponse(format!(
+                    "unexpected HID packet {}",
+                    sequence
+                )));
+            }
+
+            match sequence {
+                0 => {
+                    length = u16::from_be_bytes([packet[5], packet[6]]) as usize;
+                    response.extend(&packet[7..]);
+                }
+                _ => response.extend(&packet[5..]),
+            }
+            if response.len() >= length {
+                break;
+            }
+            sequence += 1;
+        }
+
+        if length < 2 {
+            return Err(LedgerError::InvalidResponse("missing status word".into()));
+        }
+        response.truncate(length);
+        let status = u16::from_be_bytes([response[length - 2], response[length - 1]]);
+        response.truncate(length - 2);
+        Ok((response, status))
+    }
+}
+
+/// Represents the policy of a wallet registered in the Bitcoin app, as a descriptor template over a list of keys
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LedgerWalletPolicy {
+    /// The name of the wallet, which is empty for the default single-signature wallets
+    name: String,
+    /// The descriptor template, e.g. `wpkh(@0/**)`
+    descriptor_template: String,
+    /// The keys of the descriptor template, each with its key origin, e.g. `[f5acc2fd/84'/0'/0']xpub...`
+    keys: Vec<String>,
+}
+
+impl LedgerWalletPolicy {
+    /// Returns a wallet policy given its name, descriptor template, and keys.
+    pub fn new(name: &str, descriptor_template: &str, keys: &[String]) -> Self {
+        Self {
+            name: name.into(),
+            descriptor_template: descriptor_template.into(),
+            keys: keys.to_vec(),
+        }
+    }
+
+    /// Returns the default single-signature wallet policy of the given address format,
+    /// given the account extended public key and its key origin.
+    pub fn single_sig<N: BitcoinNetwork>(
+        format: &BitcoinFormat,
+        origin: &KeyOrigin,
+        extended_public_key: &BitcoinExtendedPublicKey<N>,
+    ) -> Result<Self, LedgerError> {
+        let descriptor_template = match format {
+            BitcoinFormat::P2PKH => "pkh(@0/**)",
+            BitcoinFormat::P2SH_P2WPKH => "sh(wpkh(@0/**))",
+            BitcoinFormat::Bech32 => "wpkh(@0/**)",
+            BitcoinFormat::P2TR => "tr(@0/**)",
+            format => return Err(LedgerError::UnsupportedFormat(format.to_string())),
+        };
+        // The app only accepts the xpub and tpub versions of extended public keys
+        let key = extended_public_key.with_format(&BitcoinFormat::P2PKH);
+        Ok(Self::new("", descriptor_template, &[format!("{}{}", origin, key)]))
+    }
+
+    /// Returns the name of the wallet.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the descriptor template of the wallet.
+    pub fn descriptor_template(&self) -> &str {
+        &self.descriptor_template
+    }
+
+    /// Returns the keys of the wallet.
+    pub fn keys(&self) -> &[String] {
+        &self.keys
+    }
+
+    /// Returns the serialization of the wallet policy, which commits to its descriptor template and keys.
+    pub fn serialize(&self) -> Result<Vec<u8>, LedgerError> {
+        let keys = self.keys.iter().map(|key| key.as_bytes().to_vec()).collect::<Vec<_>>();
+        Ok([
+            vec![0x02],
+            variable_length_integer(self.name.len() as u64)?,
+            self.name.as_bytes().to_vec(),
+            variable_length_integer(self.descriptor_template.len() as u64)?,
+            Sha256::digest(self.descriptor_template.as_bytes()).to_vec(),
+            variable_length_integer(self.keys.len() as u64)?,
+            merkle_root(&keys.iter().map(|key| element_hash(key)).collect::<Vec<_>>()).to_vec(),
+        ]
+        .concat())
+    }
+
+    /// Returns the id of the wallet policy, the SHA256 hash of its serialization.
+    pub fn id(&self) -> Result<[u8; 32], LedgerError> {
+        Ok(sha256(&self.serialize()?))
+    }
+}
+
+/// Represents a signature of an input of a PSBT made by a Ledger device
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LedgerSignature {
+    /// The index of the signed input
+    pub input_index: usize,
+    /// The public key of the signature, compressed, or x-only for Taproot
+    pub public_key: Vec<u8>,
+    /// The signature, DER encoded with its sighash type, or a Schnorr signature for Taproot
+    pub signature: Vec<u8>,
+}
+
+/// Represents a client of the Bitcoin app of a Ledger device
+pub struct LedgerClient<T: LedgerTransport> {
+    transport: T,
+}
+
+impl<T: LedgerTransport> LedgerClient<T> {
+    /// Returns a client of the Bitcoin app over the given transport.
+    pub fn new(transport: T) -> Self {
+        Self { transport }
+    }
+
+    /// Returns the fingerprint of the master key of the device.
+    pub fn master_fingerprint(&mut self) -> Result<[u8; 4], LedgerError> {
+        let response = self.execute(
+            INS_GET_MASTER_FINGERPRINT,
+            &[],
+            &mut ClientCommandInterpreter::default(),
+        )?;
+        match response.len() {
+            4 => Ok([response[0], response[1], response[2], response[3]]),
+            _ => Err(LedgerError::InvalidResponse("invalid master fingerprint".into())),
+        }
+    }
+
+    /// Returns the extended public key of the device at the given derivation path,
+    /// which is displayed on the device for verification if `display` is set.
+    pub fn extended_public_key<N: BitcoinNetwork>(
+        &mut self,
+        path: &BitcoinDerivationPath<N>,
+        display: bool,
+    ) -> Result<BitcoinExtendedPublicKey<N>, LedgerError> {
+        let path = path.to_vec()?;
+        let mut data = vec![display as u8, path.len() as u8];
+        path.iter()
+            .for_each(|index| data.extend(&u32::from(*index).to_be_bytes()));
+
+        let response = self.execute(INS_GET_EXTENDED_PUBKEY, &data, &mut ClientCommandInterpreter::default())?;
+        Ok(BitcoinExtendedPublicKey::from_str(&String::from_utf8(response)?)?)
+    }
+
+    /// Returns the default single-signature wallet policy of the given address format and account,
+    /// with the account extended public key of the device.
+    pub fn account_policy<N: BitcoinNetwork>(
+        &mut self,
+        format: &BitcoinFormat,
+        account: u32,
+    ) -> Result<LedgerWalletPolicy, LedgerError> {
+        let path = BitcoinDerivationPath::<N>::from_format(format, account, 0, 0)?.to_vec()?;
+        let path = BitcoinDerivationPath::<N>::from_vec(&path[..3].to_vec())?;
+        let origin = KeyOrigin::new(self.master_fingerprint()?, &path)?;
+        let extended_public_key = self.extended_public_key(&path, false)?;
+        LedgerWalletPolicy::single_sig(format, &origin, &extended_public_key)
+    }
+
+    /// Returns the address of the wallet at the given change and index, which is displayed on the device for
+    /// verification if `display` is set. The wallets of default policies have no HMAC of their registration.
+    pub fn wallet_address<N: BitcoinNetwork>(
+        &mut self,
+        policy: &LedgerWalletPolicy,
+        hmac: Option<[u8; 32]>,
+        change: bool,
+        index: u32,
+        display: bool,
+    ) -> Result<BitcoinAddress<N>, LedgerError> {
+        let data = [
+            vec![display as u8],
+            policy.id()?.to_vec(),
+            hmac.unwrap_or_default().to_vec(),
+            vec![change as u8],
+            index.to_be_bytes().to_vec(),
+        ]
+        .concat();
+
+        let mut interpreter = ClientCommandInterpreter::default();
+        interpreter.add_wallet_policy(policy)?;
+        let response = self.execute(INS_GET_WALLET_ADDRESS, &data, &mut interpreter)?;
+        Ok(BitcoinAddress::from_str(&String::from_utf8(response)?)?)
+    }
+
+    /// Returns the address of the wallet at the given change and index, once it is displayed on the device
+    /// and verified against the given address derived by wagyu.
+    pub fn verify_address<N: BitcoinNetwork>(
+        &mut self,
+        policy: &LedgerWalletPolicy,
+        hmac: Option<[u8; 32]>,
+        change: bool,
+        index: u32,
+        address: &BitcoinAddress<N>,
+    ) -> Result<BitcoinAddress<N>, LedgerError> {
+        let device_address = self.wallet_address(policy, hmac, change, index, true)?;
+        match &device_address == address {
+            true => Ok(device_address),
+            false => Err(LedgerError::MismatchedAddress(
+                address.to_string(),
+                device_address.to_string(),
+            )),
+        }
+    }
+
+    /// Returns the signatures of the inputs of the PSBT controlled by the wallet, once the transaction
+    /// is approved on the device.
+    pub fn sign_psbt<N: BitcoinNetwork>(
+        &mut self,
+        psbt: &BitcoinPsbt<N>,
+        policy: &LedgerWalletPolicy,
+        hmac: Option<[u8; 32]>,
+    ) -> Result<Vec<LedgerSignature>, LedgerError> {
+        let (global, inputs, outputs) = psbt.to_maps()?;
+
+        let mut interpreter = ClientCommandInterpreter::default();
+        interpreter.add_wallet_policy(policy)?;
+        let global = interpreter.add_known_mapping(&global)?;
+        let inputs = inputs
+            .iter()
+            .map(|input| interpreter.add_known_mapping(input))
+            .collect::<Result<Vec<_>, _>>()?;
+        let outputs = outputs
+            .iter()
+            .map(|output| interpreter.add_known_mapping(output))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let data = [
+            global,
+            variable_length_integer(inputs.len() as u64)?,
+            interpreter.add_known_list(&inputs).to_vec(),
+            variable_length_integer(outputs.len() as u64)?,
+            interpreter.add_known_list(&outputs).to_vec(),
+            policy.id()?.to_vec(),
+            hmac.unwrap_or_default().to_vec(),
+        ]
+        .concat();
+        self.execute(INS_SIGN_PSBT, &data, &mut interpreter)?;
+
+        interpreter
+            .yielded
+            .iter()
+            .map(|signature| {
+                let mut reader = &signature[..];
+                let input_index = read_variable_length_integer(&mut reader)?;
+                let length = match reader.first() {
+                    Some(length) => *length as usize,
+                    None => return Err(LedgerError::InvalidResponse("missing public key".into())),
+                };
+                if input_index >= inputs.len() || reader.len() <= length + 1 {
+                    return Err(LedgerError::InvalidResponse("invalid signature".into()));
+                }
+                Ok(LedgerSignature {
+                    input_index,
+                    public_key: reader[1..=length].to_vec(),
+                    signature: reader[length + 1..].to_vec(),
+                })
+            })
+            .collect()
+    }
+
+    /// Adds the signatures of the device to the inputs of the PSBT, once they are verified,
+    /// and returns the number of signatures added.
+    pub fn sign<N: BitcoinNetwork>(
+        &mut self,
+        psbt: &mut BitcoinPsbt<N>,
+        policy: &LedgerWalletPolicy,
+        hmac: Option<[u8; 32]>,
+    ) -> Result<usize, LedgerError> {
+        let signatures = self.sign_psbt(psbt, policy, hmac)?;
+        // Taproot signatures are made by x-only public keys, which are not partial signatures
+        if let Some(signature) = signatures.iter().find(|signature| signature.public_key.len() != 33) {
+            return Err(LedgerError::UnsupportedSignature(signature.input_index));
+        }
+        for signature in &signatures {
+            psbt.add_signature(signature.input_index, &signature.public_key, &signature.signature)?;
+        }
+        Ok(signatures.len())
+    }
+
+    /// Returns the response of the given command, answering the client commands which interrupt it.
+    fn execute(
+        &mut self,
+        ins: u8,
+        data: &[u8],
+        interpreter: &mut ClientCommandInterpreter,
+    ) -> Result<Vec<u8>, LedgerError> {
+        let mut apdu = apdu(LEDGER_CLA, ins, LEDGER_PROTOCOL_VERSION, data)?;
+        loop {
+            let (response, status) = self.transport.exchange(&apdu)?;
+            match status {
+                SW_OK => return Ok(response),
+                SW_INTERRUPTED_EXECUTION => {
+                    let reply = interpreter.execute(&response)?;
+                    apdu = apdu_continue(&reply)?;
+                }
+                status => return Err(LedgerError::from_status(status)),
+            }
+        }
+    }
+}
+
+/// Returns the APDU of the given instruction of the Bitcoin app.
+fn apdu(cla: u8, ins: u8, p2: u8, data: &[u8]) -> Result<Vec<u8>, LedgerError> {
+    if data.len() > MAX_DATA_LENGTH {
+        return Err(LedgerError::InvalidLength(data.len()));
+    }
+    Ok([vec![cla, ins, 0x00, p2, data.len() as u8], data.to_vec()].concat())
+}
+
+/// Returns the APDU continuing an interrupted command with the given reply to its client command.
+fn apdu_continue(reply: &[u8]) -> Result<Vec<u8>, LedgerError> {
+    apdu(LEDGER_FRAMEWORK_CLA, INS_CONTINUE_INTERRUPTED, 0x00, reply)
+}
+
+/// Returns the SHA256 hash of the given bytes.
+fn sha256(bytes: &[u8]) -> [u8; 32] {
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&Sha256::digest(bytes));
+    hash
+}
+
+/// Returns the hash of a leaf of a Merkle tree.
+fn element_hash(element: &[u8]) -> [u8; 32] {
+    sha256(&[&[0x00], element].concat())
+}
+
+/// Returns the hash of an inner node of a Merkle tree given its children.
+fn combine_hashes(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    sha256(&[&[0x01], &left[..], &right[..]].concat())
+}
+
+/// Returns the size of the left subtree of a Merkle tree of the given size,
+/// the largest power of 2 smaller than the size.
+fn left_subtree_size(size: usize) -> usize {
+    let mut left = 1;
+    while left * 2 < size {
+        left *= 2;
+    }
+    left
+}
+
+/// Returns the root of the Merkle tree of the given leaf hashes, which is zero for an empty tree.
+fn merkle_root(leaves: &[[u8; 32]]) -> [u8; 32] {
+    match leaves.len() {
+        0 => [0u8; 32],
+        1 => leaves[0],
+        size => {
+            let (left, right) = leaves.split_at(left_subtree_size(size));
+            combine_hashes(&merkle_root(left), &merkle_root(right))
+        }
+    }
+}
+
+/// Returns the hashes of the siblings of the leaf at the given index, from the leaf to the root.
+fn merkle_proof(leaves: &[[u8; 32]], index: usize) -> Vec<[u8; 32]> {
+    if leaves.len() <= 1 {
+        return vec![];
+    }
+    let (left, right) = leaves.split_at(left_subtree_size(leaves.len()));
+    match index < left.len() {
+        true => [merkle_proof(left, index), vec![merkle_root(right)]].concat(),
+        false => [merkle_proof(right, index - left.len()), vec![merkle_root(left)]].concat(),
+    }
+}
+
+/// Represents the answers to the client commands of the Bitcoin app, from the preimages
+/// and Merkle trees the client committed to in its command
+#[derive(Default)]
+struct ClientCommandInterpreter {
+    /// The known preimages and their SHA256 hashes
+    preimages: Vec<([u8; 32], Vec<u8>)>,
+    /// The leaf hashes of the known Merkle trees and their roots
+    trees: Vec<([u8; 32], Vec<[u8; 32]>)>,
+    /// The elements of a reply too long for a single response, sent on request
+    queue: VecDeque<Vec<u8>>,
+    /// The data yielded by the device, such as signatures
+    yielded: Vec<Vec<u8>>,
+}
+
+impl ClientCommandInterpreter {
+    /// Adds the given preimage, requested by its SHA256 hash.
+    fn add_known_preimage(&mut self, preimage: &[u8]) {
+        self.preimages.push((sha256(preimage), preimage.to_vec()));
+    }
+
+    /// Adds the Merkle tree of the given elements and their preimages, and returns its root.
+    fn add_known_list(&mut self, elements: &[Vec<u8>]) -> [u8; 32] {
+        let leaves = elements.iter().map(|element| element_hash(element)).collect::<Vec<_>>();
+        elements
+            .iter()
+            .for_each(|element| self.add_known_preimage(&[&[0x00], &element[..]].concat()));
+        let root = merkle_root(&leaves);
+        self.trees.push((root, leaves));
+        root
+    }
+
+    /// Adds the Merkle trees of the keys, in sorted order, and values of the given map, and returns
+    /// the commitment to the map.
+    fn add_known_mapping(&mut self, map: &[(Vec<u8>, Vec<u8>)]) -> Result<Vec<u8>, LedgerError> {
+        let mut map = map.to_vec();
+        map.sort();
+        let (keys, values): (Vec<_>, Vec<_>) = map.into_iter().unzip();
+        Ok([
+            variable_length_integer(keys.len() as u64)?,
+            self.add_known_list(&keys).to_vec(),
+            self.add_known_list(&values).to_vec(),
+        ]
+        .concat())
+    }
+
+    /// Adds the serialization, descriptor template, and keys of the given wallet policy.
+    fn add_wallet_policy(&mut self, policy: &LedgerWalletPolicy) -> Result<(), LedgerError> {
+        self.add_known_preimage(&policy.serialize()?);
+        self.add_known_preimage(policy.descriptor_template.as_bytes());
+        let keys = policy
+            .keys
+            .iter()
+            .map(|key| key.as_bytes().to_vec())
+            .collect::<Vec<_>>();
+        self.add_known_list(&keys);
+        Ok(())
+    }
+
+    /// Returns the leaf hashes of the known Merkle tree of the given root.
+    fn tree(&self, root: &[u8]) -> Result<&[[u8; 32]], LedgerError> {
+        match self.trees.iter().find(|(other, _)| &other[..] == root) {
+            Some((_, leaves)) => Ok(leaves),
+            None => Err(LedgerError::UnknownHash(hex::encode(root))),
+        }
+    }
+
+    /// Returns the reply to the given client command.
+    fn execute(&mut self, command: &[u8]) -> Result<Vec<u8>, LedgerError> {
+        let (code, request) = match command.split_first() {
+            Some((code, request)) => (*code, request),
+            None => return Err(LedgerError::InvalidResponse("empty client command".into())),
+        };
+
+        match code {
+            CCMD_YIELD => {
+                self.yielded.push(request.to_vec());
+                Ok(vec![])
+            }
+            CCMD_GET_PREIMAGE => {
+                if request.len() != 33 || request[0] != 0x00 {
+                    return Err(LedgerError::InvalidResponse("invalid preimage request".into()));
+                }
+                let preimage = match self.preimages.iter().find(|(hash, _)| hash[..] == request[1..]) {
+                    Some((_, preimage)) => preimage.clone(),
+                    None => return Err(LedgerError::UnknownHash(hex::encode(&request[1..]))),
+                };
+
+                // The bytes of the preimage which do not fit in the reply are queued one by one
+                let length = variable_length_integer(preimage.len() as u64)?;
+                let payload_size = core::cmp::min(MAX_DATA_LENGTH - length.len() - 1, preimage.len());
+                self.queue = preimage[payload_size..].iter().map(|byte| vec![*byte]).collect();
+                Ok([length, vec![payload_size as u8], preimage[..payload_size].to_vec()].concat())
+            }
+            CCMD_GET_MERKLE_LEAF_PROOF => {
+                if request.len() < 32 {
+                    return Err(LedgerError::InvalidResponse("invalid Merkle leaf proof request".into()));
+                }
+                let mut reader = &request[32..];
+                let size = read_variable_length_integer(&mut reader)?;
+                let index = read_variable_length_integer(&mut reader)?;
+                let leaves = self.tree(&request[..32])?;
+                if leaves.len() != size || index >= size {
+                    return Err(LedgerError::InvalidResponse("invalid Merkle leaf proof request".into()));
+                }
+
+                // The hashes of the proof which do not fit in the reply are queued
+                let proof = merkle_proof(leaves, index);
+                let hashes = core::cmp::min((MAX_DATA_LENGTH - 32 - 2) / 32, proof.len());
+                let reply = [
+                    leaves[index].to_vec(),
+                    vec![proof.len() as u8, hashes as u8],
+                    proof[..hashes].concat(),
+                ]
+                .concat();
+                self.queue = proof[hashes..].iter().map(|hash| hash.to_vec()).collect();
+                Ok(reply)
+            }
+            CCMD_GET_MERKLE_LEAF_INDEX => {
+                if request.len() != 64 {
+                    return Err(LedgerError::InvalidResponse("invalid Merkle leaf index request".into()));
+                }
+                let leaves = self.tree(&request[..32])?;
+                match leaves.iter().position(|leaf| leaf[..] == request[32..]) {
+                    Some(index) => Ok([vec![0x01], variable_length_integer(index as u64)?].concat()),
+                    None => Ok(vec![0x00, 0x00]),
+                }
+            }
+            CCMD_GET_MORE_ELEMENTS => {
+                let length = match self.queue.front() {
+                    Some(element) => element.len(),
+                    None => return Err(LedgerError::InvalidResponse("no more elements".into())),
+                };
+                let mut elements = vec![];
+                while 2 + (elements.len() + 1) * length <= MAX_DATA_LENGTH {
+                    match self.queue.pop_front() {
+                        Some(element) => elements.push(element),
+                        None => break,
+                    }
+                }
+                Ok([vec![elements.len() as u8, length as u8], elements.concat()].concat())
+            }
+            code => Err(LedgerError::UnsupportedCommand(code)),
+        }
+    }
+}
+
+#[derive(Debug, Fail)]
+pub enum LedgerError {
+    #[fail(display = "{}", _0)]
+    AddressError(#[cause] AddressError),
+
+    #[fail(display = "{}", _0)]
+    Crate(#[cause] CrateError),
+
+    #[fail(display = "the request was denied on the Ledger device")]
+    DeniedByUser,
+
+    #[fail(display = "{}", _0)]
+    DerivationPathError(#[cause] DerivationPathError),
+
+    #[fail(display = "no Ledger device found")]
+    DeviceNotFound,
+
+    #[fail(display = "{}", _0)]
+    ExtendedPublicKeyError(#[cause] ExtendedPublicKeyError),
+
+    #[fail(display = "invalid length of APDU data: {}", _0)]
+    InvalidLength(usize),
+
+    #[fail(display = "invalid Ledger response: {}", _0)]
+    InvalidResponse(String),
+
+    #[fail(display = "the Ledger device is locked")]
+    Locked,
+
+    #[fail(
+        display = "the address {} does not match the address {} of the Ledger device",
+        _0, _1
+    )]
+    MismatchedAddress(String, String),
+
+    #[fail(display = "{}", _0)]
+    PsbtError(#[cause] PsbtError),
+
+    #[fail(display = "the Ledger device returned status {:#06x}", _0)]
+    Status(u16),
+
+    #[fail(display = "{}", _0)]
+    TransactionError(#[cause] TransactionError),
+
+    #[fail(display = "unknown preimage or Merkle root of hash {}", _0)]
+    UnknownHash(String),
+
+    #[fail(display = "unsupported client command: {:#04x}", _0)]
+    UnsupportedCommand(u8),
+
+    #[fail(display = "unsupported address format: {}", _0)]
+    UnsupportedFormat(String),
+
+    #[fail(display = "unsupported Taproot signature of input {}", _0)]
+    UnsupportedSignature(usize),
+
+    #[fail(display = "the Bitcoin app is not open on the Ledger device")]
+    WrongApp,
+}
+
+impl LedgerError {
+    /// Returns the error of the given status word of the device.
+    fn from_status(status: u16) -> Self {
+        match status {
+            SW_DENIED_BY_USER => LedgerError::DeniedByUser,
+            SW_LOCKED => LedgerError::Locked,
+            SW_CLA_NOT_SUPPORTED | SW_INS_NOT_SUPPORTED => LedgerError::WrongApp,
+            status => LedgerError::Status(status),
+        }
+    }
+}
+
+impl From<AddressError> for LedgerError {
+    fn from(error: AddressError) -> Self {
+        LedgerError::AddressError(error)
+    }
+}
+
+impl From<CrateError> for LedgerError {
+    fn from(error: CrateError) -> Self {
+        LedgerError::Crate(error)
+    }
+}
+
+impl From<DerivationPathError> for LedgerError {
+    fn from(error: DerivationPathError) -> Self {
+        LedgerError::DerivationPathError(error)
+    }
+}
+
+impl From<ExtendedPublicKeyError> for LedgerError {
+    fn from(error: ExtendedPublicKeyError) -> Self {
+        LedgerError::ExtendedPublicKeyError(error)
+    }
+}
+
+impl From<FromUtf8Error> for LedgerError {
+    fn from(error: FromUtf8Error) -> Self {
+        LedgerError::Crate(CrateError::from(error))
+    }
+}
+
+impl From<PsbtError> for LedgerError {
+    fn from(error: PsbtError) -> Self {
+        LedgerError::PsbtError(error)
+    }
+}
+
+impl From<std::io::Error> for LedgerError {
+    fn from(error: std::io::Error) -> Self {
+        LedgerError::Crate(CrateError::from(error))
+    }
+}
+
+impl From<TransactionError> for LedgerError {
+    fn from(error: TransactionError) -> Self {
+        LedgerError::TransactionError(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::amount::BitcoinAmount;
+    use crate::extended_private_key::BitcoinExtendedPrivateKey;
+    use crate::network::*;
+    use crate::transaction::{BitcoinTransactionInput, BitcoinTransactionOutput, BitcoinTransactionParameters};
+    use crate::SignatureHash;
+    use wagyu_model::ExtendedPrivateKey;
+
+    use std::io::Cursor;
+
+    type N = Mainnet;
+
+    const MASTER_EXTENDED_PRIVATE_KEY: &str = "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi";
+    const ACCOUNT_PATH: &str = "m/84'/0'/0'";
+    const TRANSACTION_ID: &str = "61d520ccb74288c96bc1a2b20ea1c0d5a704776dd0164a396efec3ea7040349d";
+    const OUTPUT_ADDRESS: &str = "3QDTHVyuJrHixUhhsdZXQ7M8P9MQngmw1P";
+
+    // Represents a Ledger device answering each APDU with the given closure
+    struct MockDevice<F: FnMut(&[u8]) -> (Vec<u8>, u16)>(F);
+
+    impl<F: FnMut(&[u8]) -> (Vec<u8>, u16)> LedgerTransport for MockDevice<F> {
+        fn exchange(&mut self, apdu: &[u8]) -> Result<(Vec<u8>, u16), LedgerError> {
+            Ok((self.0)(apdu))
+        }
+    }
+
+    // Represents a HID device replying with the given reports, recording the reports written to it
+    struct MockHid {
+        reports: Cursor<Vec<u8>>,
+        written: Vec<u8>,
+    }
+
+    impl Read for MockHid {
+        fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+            self.reports.read(buffer)
+        }
+    }
+
+    impl Write for MockHid {
+        fn write(&mut self, buffer: &[u8]) -> std::io::Result<usize> {
+            self.written.extend(buffer);
+            Ok(buffer.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn master() -> BitcoinExtendedPrivateKey<N> {
+        BitcoinExtendedPrivateKey::from_str(MASTER_EXTENDED_PRIVATE_KEY).unwrap()
+    }
+
+    fn policy() -> LedgerWalletPolicy {
+        let path = BitcoinDerivationPath::from_str(ACCOUNT_PATH).unwrap();
+        let origin = KeyOrigin::new(master().fingerprint(), &path).unwrap();
+        let extended_public_key = master().derive(&path).unwrap().to_extended_public_key();
+        LedgerWalletPolicy::single_sig(&BitcoinFormat::Bech32, &origin, &extended_public_key).unwrap()
+    }
+
+    // Returns the first receiving address of the account
+    fn address() -> BitcoinAddress<N> {
+        let path = BitcoinDerivationPath::from_str("m/84'/0'/0'/0/0").unwrap();
+        master()
+            .derive(&path)
+            .unwrap()
+            .to_address(&BitcoinFormat::Bech32)
+            .unwrap()
+    }
+
+    // Returns the PSBT spending an output of the first receiving address of the account
+    fn psbt() -> BitcoinPsbt<N> {
+        let input = BitcoinTransactionInput::<N>::new(
+            hex::decode(TRANSACTION_ID).unwrap(),
+            1,
+            None,
+            None,
+            None,
+            None,
+            None,
+            SignatureHash::SIGHASH_ALL,
+        )
+        .unwrap();
+        let output = BitcoinTransactionOutput::new(
+            &BitcoinAddress::<N>::from_str(OUTPUT_ADDRESS).unwrap(),
+            BitcoinAmount(40000),
+        )
+        .unwrap();
+        let mut psbt = BitcoinPsbt::new(&BitcoinTransactionParameters {
+            version: 2,
+            inputs: vec![input],
+            outputs: vec![output],
+            lock_time: 0,
+            segwit_flag: false,
+        })
+        .unwrap();
+        psbt.inputs_mut()[0].witness_utxo =
+            Some(BitcoinTransactionOutput::new(&address(), BitcoinAmount(50000)).unwrap());
+        psbt
+    }
+
+    // Returns the root of the Merkle tree of the given size from a leaf and its proof
+    fn root_from_proof(leaf: [u8; 32], index: usize, size: usize, proof: &[[u8; 32]]) -> [u8; 32] {
+        let (sibling, proof) = match proof.split_last() {
+            Some(split) => split,
+            None => return leaf,
+        };
+        let left = left_subtree_size(size);
+        match index < left {
+            true => combine_hashes(&root_from_proof(leaf, index, left, proof), sibling),
+            false => combine_hashes(sibling, &root_from_proof(leaf, index - left, size - left, proof)),
+        }
+    }
+
+    #[test]
+    fn merkle_tree() {
+        let leaves = (0..9u8).map(|i| element_hash(&[i])).collect::<Vec<_>>();
+        assert_eq!([0u8; 32], merkle_root(&[]));
+        assert_eq!(leaves[0], merkle_root(&leaves[..1]));
+        assert_eq!(
+            combine_hashes(&combine_hashes(&leaves[0], &leaves[1]), &leaves[2]),
+            merkle_root(&leaves[..3])
+        );
+
+        for size in 1..=leaves.len() {
+            let root = merkle_root(&leaves[..size]);
+            for index in 0..size {
+                let proof = merkle_proof(&leaves[..size], index);
+                assert_eq!(root, root_from_proof(leaves[index], index, size, &proof));
+            }
+        }
+    }
+
+    #[test]
+    fn client_commands() {
+        let mut interpreter = ClientCommandInterpreter::default();
+
+        // A preimage longer than a reply is completed by its queued bytes
+        let preimage = (0..300).map(|i| i as u8).collect::<Vec<_>>();
+        interpreter.add_known_preimage(&preimage);
+        let reply = interpreter
+            .execute(&[&[CCMD_GET_PREIMAGE, 0x00], &sha256(&preimage)[..]].concat())
+            .unwrap();
+        assert_eq!([0xfd, 0x2c, 0x01, 251], reply[..4]);
+        assert_eq!(preimage[..251], reply[4..]);
+        let reply = interpreter.execute(&[CCMD_GET_MORE_ELEMENTS]).unwrap();
+        assert_eq!([49, 1], reply[..2]);
+        assert_eq!(preimage[251..], reply[2..]);
+
+        // A proof longer than a reply is completed by its queued hashes
+        let elements = (0..100u8).map(|i| vec![i]).collect::<Vec<_>>();
+        let root = interpreter.add_known_list(&elements);
+        let request = [&[CCMD_GET_MERKLE_LEAF_PROOF], &root[..], &[100, 42]].concat();
+        let reply = interpreter.execute(&request).unwrap();
+        assert_eq!(element_hash(&[42]), reply[..32]);
+        assert_eq!([7, 6], reply[32..34]);
+        let more = interpreter.execute(&[CCMD_GET_MORE_ELEMENTS]).unwrap();
+        assert_eq!([1, 32], more[..2]);
+        let proof = [&reply[34..], &more[2..]]
+            .concat()
+            .chunks(32)
+            .map(|hash| {
+                let mut sibling = [0u8; 32];
+                sibling.copy_from_slice(hash);
+                sibling
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(root, root_from_proof(element_hash(&[42]), 42, 100, &proof));
+
+        let request = [&[CCMD_GET_MERKLE_LEAF_INDEX], &root[..], &element_hash(&[42])[..]].concat();
+        assert_eq!(vec![0x01, 42], interpreter.execute(&request).unwrap());
+        let request = [&[CCMD_GET_MERKLE_LEAF_INDEX], &root[..], &element_hash(&[100])[..]].concat();
+        assert_eq!(vec![0x00, 0x00], interpreter.execute(&request).unwrap());
+
+        assert!(interpreter.execute(&[CCMD_GET_MORE_ELEMENTS]).is_err());
+        assert!(interpreter.execute(&[CCMD_GET_PREIMAGE, 0x00, 0x00]).is_err());
+        assert!(interpreter
+            .execute(&[&[CCMD_GET_PREIMAGE, 0x00], &[0u8; 32][..]].concat())
+            .is_err());
+        assert!(interpreter.execute(&[0x77]).is_err());
+    }
+
+    #[test]
+    fn hid() {
+        let response = [vec![0x42; 100], vec![0x90, 0x00]].concat();
+        let data = [(response.len() as u16).to_be_bytes().to_vec(), response.clone()].concat();
+        let reports = data
+            .chunks(HID_PACKET_SIZE - 5)
+            .enumerate()
+            .flat_map(|(sequence, chunk)| {
+                let mut packet = [
+                    vec![0x01, 0x01, 0x05],
+                    (sequence as u16).to_be_bytes().to_vec(),
+                    chunk.to_vec(),
+                ]
+                .concat();
+                packet.resize(HID_PACKET_SIZE, 0);
+                packet
+            })
+            .collect();
+        let mut hid = LedgerHid::new(MockHid {
+            reports: Cursor::new(reports),
+            written: vec![],
+        });
+
+        let apdu = vec![0x17; 70];
+        assert_eq!((vec![0x42; 100], SW_OK), hid.exchange(&apdu).unwrap());
+
+        let written = &hid.device.written;
+        assert_eq!(2 * (HID_PACKET_SIZE + 1), written.len());
+        assert_eq!([0x00, 0x01, 0x01, 0x05, 0x00, 0x00, 0x00, 70], written[..8]);
+        assert_eq!(apdu[..57], written[8..65]);
+        assert_eq!([0x00, 0x01, 0x01, 0x05, 0x00, 0x01], written[65..71]);
+        assert_eq!(apdu[57..], written[71..84]);
+        assert!(written[84..].iter().all(|byte| *byte == 0));
+
+        // A response of another channel is rejected
+        let mut hid = LedgerHid::new(MockHid {
+            reports: Cursor::new(vec![0x02; HID_PACKET_SIZE]),
+            written: vec![],
+        });
+        assert!(hid.exchange(&apdu).is_err());
+    }
+
+    #[test]
+    fn wallet_policy() {
+        let policy = policy();
+        let fingerprint = hex::encode(master().fingerprint());
+        assert_eq!("wpkh(@0/**)", policy.descriptor_template());
+        assert!(policy.keys()[0].starts_with(&format!("[{}/84'/0'/0']xpub", fingerprint)));
+
+        let serialization = policy.serialize().unwrap();
+        assert_eq!([0x02, 0x00, 11], serialization[..3]);
+        assert_eq!(Sha256::digest(b"wpkh(@0/**)")[..], serialization[3..35]);
+        assert_eq!(0x01, serialization[35]);
+        assert_eq!(element_hash(policy.keys()[0].as_bytes()), serialization[36..]);
+        assert_eq!(sha256(&serialization), policy.id().unwrap());
+
+        let path = BitcoinDerivationPath::from_str(ACCOUNT_PATH).unwrap();
+        let origin = KeyOrigin::new(master().fingerprint(), &path).unwrap();
+        let extended_public_key = master().derive(&path).unwrap().to_extended_public_key();
+        assert!(LedgerWalletPolicy::single_sig(&BitcoinFormat::P2WSH, &origin, &extended_public_key).is_err());
+    }
+
+    #[test]
+    fn verify_address() {
+        let policy = policy();
+        let id = policy.id().unwrap();
+        let mut client = LedgerClient::new(MockDevice(|apdu: &[u8]| {
+            assert_eq!(
+                [LEDGER_CLA, INS_GET_WALLET_ADDRESS, 0x00, LEDGER_PROTOCOL_VERSION, 70],
+                apdu[..5]
+            );
+            assert_eq!(0x01, apdu[5]);
+            assert_eq!(id, apdu[6..38]);
+            assert_eq!([0u8; 32], apdu[38..70]);
+            assert_eq!([0x00, 0x00, 0x00, 0x00, 0x00], apdu[70..]);
+            (address().to_string().into_bytes(), SW_OK)
+        }));
+        assert_eq!(
+            address(),
+            client.verify_address(&policy, None, false, 0, &address()).unwrap()
+        );
+
+        let other = BitcoinAddress::<N>::from_str(OUTPUT_ADDRESS).unwrap();
+        match client.verify_address(&policy, None, false, 0, &other) {
+            Err(LedgerError::MismatchedAddress(..)) => (),
+            result => panic!("unexpected result {:?}", result),
+        }
+
+        let mut client = LedgerClient::new(MockDevice(|_: &[u8]| (vec![], SW_DENIED_BY_USER)));
+        match client.wallet_address::<N>(&policy, None, false, 0, true) {
+            Err(LedgerError::DeniedByUser) => (),
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+
+    #[test]
+    fn sign_psbt() {
+        let policy = policy();
+        let private_key = master()
+            .derive(&BitcoinDerivationPath::from_str("m/84'/0'/0'/0/0").unwrap())
+            .unwrap()
+            .to_private_key();
+        let mut signed = psbt();
+        signed.sign(&private_key).unwrap();
+        let (public_key, signature) = signed.inputs()[0].partial_signatures[0].clone();
+
+        // The device requests the serialization of the wallet policy, then yields the signature of the input
+        let serialization = policy.serialize().unwrap();
+        let mut step = 0;
+        let mut client = LedgerClient::new(MockDevice(|apdu: &[u8]| {
+            step += 1;
+            match step {
+                1 => {
+                    assert_eq!([LEDGER_CLA, INS_SIGN_PSBT], apdu[..2]);
+                    let id = &apdu[apdu.len() - 64..apdu.len() - 32];
+                    ([&[CCMD_GET_PREIMAGE, 0x00], id].concat(), SW_INTERRUPTED_EXECUTION)
+                }
+                2 => {
+                    assert_eq!([LEDGER_FRAMEWORK_CLA, INS_CONTINUE_INTERRUPTED], apdu[..2]);
+                    assert_eq!(serialization, apdu[7..]);
+                    let signature = [&[CCMD_YIELD, 0x00, 33], &public_key[..], &signature[..]].concat();
+                    (signature, SW_INTERRUPTED_EXECUTION)
+                }
+                _ => {
+                    assert_eq!(
+                        [LEDGER_FRAMEWORK_CLA, INS_CONTINUE_INTERRUPTED, 0x00, 0x00, 0x00],
+                        apdu[..]
+                    );
+                    (vec![], SW_OK)
+                }
+            }
+        }));
+
+        let mut psbt = psbt();
+        assert_eq!(1, client.sign(&mut psbt, &policy, None).unwrap());
+        assert_eq!(signed, psbt);
+    }
+}
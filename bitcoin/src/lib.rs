@@ -12,37 +12,129 @@ extern crate failure;
 pub mod address;
 pub use self::address::*;
 
+mod aez;
+
+pub mod aezeed;
+pub use self::aezeed::*;
+
 pub mod amount;
 pub use self::amount::*;
 
+pub mod batch_signer;
+pub use self::batch_signer::*;
+
+pub mod bip38;
+pub use self::bip38::*;
+
+mod bech32m;
+
+pub mod coin_selection;
+pub use self::coin_selection::*;
+
+pub mod core_import;
+pub use self::core_import::*;
+
+pub mod decoded_transaction;
+pub use self::decoded_transaction::*;
+
 pub mod derivation_path;
 pub use self::derivation_path::*;
 
+pub mod descriptor;
+pub use self::descriptor::*;
+
+pub mod electrum;
+pub use self::electrum::*;
+
+#[cfg(feature = "online")]
+pub mod electrum_client;
+#[cfg(feature = "online")]
+pub use self::electrum_client::*;
+
 pub mod extended_private_key;
 pub use self::extended_private_key::*;
 
 pub mod extended_public_key;
 pub use self::extended_public_key::*;
 
+pub mod external_signer;
+pub use self::external_signer::*;
+
+pub mod fee_bump;
+
 pub mod format;
 pub use self::format::*;
 
+#[cfg(feature = "ledger")]
+pub mod ledger;
+#[cfg(feature = "ledger")]
+pub use self::ledger::*;
+
+pub mod message;
+pub use self::message::*;
+
+pub mod miniscript;
+pub use self::miniscript::*;
+
 pub mod mnemonic;
 pub use self::mnemonic::*;
 
+pub mod multisig;
+pub use self::multisig::*;
+
+pub mod musig2;
+pub use self::musig2::*;
+
 pub mod network;
 pub use self::network::*;
 
+pub mod payment_code;
+pub use self::payment_code::*;
+
+pub mod policy;
+pub use self::policy::*;
+
 pub mod private_key;
 pub use self::private_key::*;
 
+pub mod psbt;
+pub use self::psbt::*;
+
 pub mod public_key;
 pub use self::public_key::*;
 
+pub mod script;
+pub use self::script::*;
+
+pub mod taproot;
+pub use self::taproot::*;
+
+pub mod timelock;
+pub use self::timelock::*;
+
 pub mod transaction;
 pub use self::transaction::*;
 
+pub mod transaction_exchange;
+pub use self::transaction_exchange::*;
+
+#[cfg(feature = "trezor")]
+pub mod trezor;
+#[cfg(feature = "trezor")]
+pub use self::trezor::*;
+
+pub mod uri;
+pub use self::uri::*;
+
+#[cfg(feature = "std")]
+pub mod vanity;
+#[cfg(feature = "std")]
+pub use self::vanity::*;
+
 mod witness_program;
 
+pub mod witness_script;
+pub use self::witness_script::*;
+
 pub mod wordlist;
 pub use self::wordlist::*;
@@ -0,0 +1,490 @@
+use crate::address::BitcoinAddress;
+use crate::format::BitcoinFormat;
+use crate::network::BitcoinNetwork;
+use crate::private_key::BitcoinPrivateKey;
+use crate::public_key::BitcoinPublicKey;
+use crate::transaction::{create_script_pub_key, read_variable_length_integer, variable_length_integer, Opcode};
+use wagyu_model::{
+    crypto::{checksum, hash160, secp256k1_sign, tagged_hash},
+    no_std::*,
+    AddressError, CrateError, PrivateKey, PrivateKeyError, PublicKeyError, TransactionError,
+};
+
+use base64ct::{Base64, Encoding};
+use hmac::{Hmac, Mac};
+use secp256k1::{
+    self,
+    curve::{Scalar, ECMULT_GEN_CONTEXT},
+};
+use sha2::{Digest, Sha256};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// The prefix of a message signed in the legacy format of Bitcoin Core's `signmessage`
+const MESSAGE_MAGIC: &[u8] = b"\x18Bitcoin Signed Message:\n";
+/// The header byte offset of a legacy signature of a P2PKH address with an uncompressed public key
+const HEADER_P2PKH_UNCOMPRESSED: u8 = 27;
+/// The header byte offset of a legacy signature of a P2PKH address with a compressed public key
+const HEADER_P2PKH_COMPRESSED: u8 = 31;
+/// The header byte offset of a BIP137 signature of a P2SH_P2WPKH address
+const HEADER_P2SH_P2WPKH: u8 = 35;
+/// The header byte offset of a BIP137 signature of a Bech32 address
+const HEADER_BECH32: u8 = 39;
+
+/// Returns the hash of a message signed in the legacy format,
+/// the double SHA256 of the message magic and the length-prefixed message.
+pub fn to_message_hash(message: &[u8]) -> Result<[u8; 32], MessageError> {
+    let mut preimage = MESSAGE_MAGIC.to_vec();
+    preimage.extend(variable_length_integer(message.len() as u64)?);
+    preimage.extend_from_slice(message);
+
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&checksum(&preimage));
+    Ok(hash)
+}
+
+/// Returns the BIP322 virtual transaction which spends to the script public key of the address,
+/// committing to the message, and the transaction id of the virtual transaction.
+/// https://github.com/bitcoin/bips/blob/master/bip-0322.mediawiki#full
+fn to_spend<N: BitcoinNetwork>(
+    address: &BitcoinAddress<N>,
+    message: &[u8],
+) -> Result<(Vec<u8>, [u8; 32]), MessageError> {
+    let script_pub_key = create_script_pub_key(address)?;
+
+    let mut transaction = vec![0u8; 4];
+    transaction.push(1);
+    transaction.extend_from_slice(&[0u8; 32]);
+    transaction.extend_from_slice(&[0xFF; 4]);
+    transaction.extend_from_slice(&[34, Opcode::OP_0 as u8, 32]);
+    transaction.extend_from_slice(&tagged_hash(b"BIP0322-signed-message", message));
+    transaction.extend_from_slice(&[0u8; 4]);
+    transaction.push(1);
+    transaction.extend_from_slice(&[0u8; 8]);
+    transaction.extend(variable_length_integer(script_pub_key.len() as u64)?);
+    transaction.extend(script_pub_key.clone());
+    transaction.extend_from_slice(&[0u8; 4]);
+
+    let mut transaction_id = [0u8; 32];
+    transaction_id.copy_from_slice(&checksum(&transaction));
+    Ok((script_pub_key, transaction_id))
+}
+
+/// Returns the signature hash of the BIP322 virtual transaction which spends the output of `to_spend`
+/// to an OP_RETURN output, for a BIP143 P2WPKH spend of the public key hash.
+fn to_sign_segwit_hash(to_spend_id: &[u8; 32], public_key_hash: &[u8]) -> Result<[u8; 32], MessageError> {
+    let mut preimage = vec![0u8; 4];
+    preimage.extend(checksum(&[&to_spend_id[..], &[0u8; 4]].concat()));
+    preimage.extend(checksum(&[0u8; 4]));
+    preimage.extend_from_slice(to_spend_id);
+    preimage.extend_from_slice(&[0u8; 4]);
+    preimage.extend_from_slice(&[0x19, Opcode::OP_DUP as u8, Opcode::OP_HASH160 as u8, 0x14]);
+    preimage.extend_from_slice(public_key_hash);
+    preimage.extend_from_slice(&[Opcode::OP_EQUALVERIFY as u8, Opcode::OP_CHECKSIG as u8]);
+    preimage.extend_from_slice(&[0u8; 8]);
+    preimage.extend_from_slice(&[0u8; 4]);
+    preimage.extend(checksum(&[&[0u8; 8][..], &[1, Opcode::OP_RETURN as u8]].concat()));
+    preimage.extend_from_slice(&[0u8; 4]);
+    preimage.extend_from_slice(&1u32.to_le_bytes());
+
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&checksum(&preimage));
+    Ok(hash)
+}
+
+/// Returns the signature hash of the BIP322 virtual transaction which spends the output of `to_spend`
+/// to an OP_RETURN output, for a BIP341 Taproot key path spend with the given hash type.
+fn to_sign_taproot_hash(
+    to_spend_id: &[u8; 32],
+    script_pub_key: &[u8],
+    hash_type: u8,
+) -> Result<[u8; 32], MessageError> {
+    let sha256 = |data: &[u8]| Sha256::digest(data).to_vec();
+
+    let mut preimage = vec![0x00, hash_type];
+    preimage.extend_from_slice(&[0u8; 4]);
+    preimage.extend_from_slice(&[0u8; 4]);
+    preimage.extend(sha256(&[&to_spend_id[..], &[0u8; 4]].concat()));
+    preimage.extend(sha256(&[0u8; 8]));
+    preimage.extend(sha256(
+        &[
+            variable_length_integer(script_pub_key.len() as u64)?,
+            script_pub_key.to_vec(),
+        ]
+        .concat(),
+    ));
+    preimage.extend(sha256(&[0u8; 4]));
+    preimage.extend(sha256(&[&[0u8; 8][..], &[1, Opcode::OP_RETURN as u8]].concat()));
+    preimage.push(0x00);
+    preimage.extend_from_slice(&[0u8; 4]);
+    Ok(tagged_hash(b"TapSighash", &preimage))
+}
+
+/// Returns the ECDSA signature of the hash with an RFC6979 nonce, grinding the nonce with an extra
+/// entropy counter until the signature has a low R value, as Bitcoin Core does when signing.
+fn sign_low_r(hash: &[u8; 32], secret_key: &secp256k1::SecretKey) -> Result<secp256k1::Signature, MessageError> {
+    let message = secp256k1::Message::parse(hash);
+    let (mut signature, _) = secp256k1_sign(&message, secret_key);
+
+    let secret_scalar: Scalar = secret_key.clone().into();
+    let secret_key = secret_key.serialize();
+    let mut counter = 0u32;
+    while signature.r.b32()[0] >= 0x80 {
+        counter += 1;
+        let mut extra_entropy = [0u8; 32];
+        extra_entropy[..4].copy_from_slice(&counter.to_le_bytes());
+
+        // RFC6979 HMAC-DRBG, seeded with the secret key, the hash and the extra entropy.
+        let hmac = |key: &[u8], data: &[&[u8]]| -> Result<[u8; 32], MessageError> {
+            let mut mac = HmacSha256::new_varkey(key).map_err(CrateError::from)?;
+            data.iter().for_each(|data| mac.input(data));
+            let mut result = [0u8; 32];
+            result.copy_from_slice(&mac.result().code());
+            Ok(result)
+        };
+        let seed = [&secret_key[..], &hash[..], &extra_entropy[..]].concat();
+        let mut k = hmac(&[0u8; 32], &[&[1u8; 32], &[0x00], &seed])?;
+        let mut v = hmac(&k, &[&[1u8; 32]])?;
+        k = hmac(&k, &[&v, &[0x01], &seed])?;
+        v = hmac(&k, &[&v])?;
+
+        loop {
+            v = hmac(&k, &[&v])?;
+            let mut nonce = Scalar::default();
+            if !bool::from(nonce.set_b32(&v)) && !nonce.is_zero() {
+                if let Ok((r, s, _)) = ECMULT_GEN_CONTEXT.sign_raw(&secret_scalar, &message.0, &nonce) {
+                    signature = secp256k1::Signature { r, s };
+                    break;
+                }
+            }
+            k = hmac(&k, &[&v, &[0x00]])?;
+            v = hmac(&k, &[&v])?;
+        }
+    }
+    Ok(signature)
+}
+
+impl<N: BitcoinNetwork> BitcoinPrivateKey<N> {
+    /// Returns the base64 signature of the message for the address of the private key in the given format.
+    /// P2PKH and P2SH_P2WPKH addresses are signed in the legacy format of `signmessage`, with BIP137
+    /// header bytes for P2SH_P2WPKH, and Bech32 and P2TR addresses are signed with a BIP322 simple signature.
+    pub fn sign_message(&self, message: &[u8], format: &BitcoinFormat) -> Result<String, MessageError> {
+        match format {
+            BitcoinFormat::P2PKH => self.sign_legacy_message(message, self.is_compressed(), HEADER_P2PKH_COMPRESSED),
+            BitcoinFormat::P2SH_P2WPKH => self.sign_legacy_message(message, true, HEADER_P2SH_P2WPKH),
+            BitcoinFormat::Bech32 => {
+                let public_key = self.to_public_key().to_secp256k1_public_key().serialize_compressed();
+                let (_, to_spend_id) = to_spend(&BitcoinAddress::<N>::bech32(&self.to_public_key())?, message)?;
+                let hash = to_sign_segwit_hash(&to_spend_id, &hash160(&public_key))?;
+
+                let signature = sign_low_r(&hash, &self.to_secp256k1_secret_key())?;
+                let mut signature = signature.serialize_der().as_ref().to_vec();
+                signature.push(0x01);
+
+                let mut witness = vec![2];
+                witness.extend(variable_length_integer(signature.len() as u64)?);
+                witness.extend(signature);
+                witness.extend(variable_length_integer(public_key.len() as u64)?);
+                witness.extend_from_slice(&public_key);
+                Ok(Base64::encode_string(&witness))
+            }
+            BitcoinFormat::P2TR => {
+                let address = BitcoinAddress::<N>::p2tr(&self.to_public_key())?;
+                let (script_pub_key, to_spend_id) = to_spend(&address, message)?;
+                let hash = to_sign_taproot_hash(&to_spend_id, &script_pub_key, 0x00)?;
+
+                let signature = self
+                    .to_taproot_tweaked_private_key(None)?
+                    .sign_schnorr(&hash, &[0u8; 32])?;
+                let mut witness = vec![1, 64];
+                witness.extend_from_slice(&signature);
+                Ok(Base64::encode_string(&witness))
+            }
+            BitcoinFormat::P2WSH | BitcoinFormat::FutureWitness => {
+                Err(MessageError::UnsupportedFormat(format.to_string()))
+            }
+        }
+    }
+
+    /// Returns the base64 compact signature of the message hash, with a header byte
+    /// of the given offset, the recovery id and, for P2PKH addresses, the compression flag.
+    fn sign_legacy_message(&self, message: &[u8], compressed: bool, header: u8) -> Result<String, MessageError> {
+        let hash = to_message_hash(message)?;
+        let (signature, recovery_id) =
+            secp256k1_sign(&secp256k1::Message::parse(&hash), &self.to_secp256k1_secret_key());
+
+        let header = match compressed {
+            true => header + recovery_id.serialize(),
+            false => HEADER_P2PKH_UNCOMPRESSED + recovery_id.serialize(),
+        };
+        Ok(Base64::encode_string(&[&[header][..], &signature.serialize()].concat()))
+    }
+}
+
+impl<N: BitcoinNetwork> BitcoinAddress<N> {
+    /// Returns `true` if the base64 signature is a valid signature of the message for the address,
+    /// in the legacy format of `verifymessage` with BIP137 header bytes, or as a BIP322 simple signature
+    /// for Bech32 and P2TR addresses.
+    pub fn verify_message(&self, message: &[u8], signature: &str) -> Result<bool, MessageError> {
+        let signature = Base64::decode_vec(signature).map_err(|_| MessageError::InvalidBase64)?;
+        if signature.len() == 65 && (HEADER_P2PKH_UNCOMPRESSED..HEADER_BECH32 + 4).contains(&signature[0]) {
+            return self.verify_legacy_message(message, &signature);
+        }
+
+        match self.format() {
+            BitcoinFormat::Bech32 => self.verify_segwit_message(message, &to_witness_stack(&signature)?),
+            BitcoinFormat::P2TR => self.verify_taproot_message(message, &to_witness_stack(&signature)?),
+            format => Err(MessageError::UnsupportedFormat(format.to_string())),
+        }
+    }
+
+    /// Returns `true` if the compact signature recovers a public key with the address.
+    fn verify_legacy_message(&self, message: &[u8], signature: &[u8]) -> Result<bool, MessageError> {
+        let header = signature[0];
+        let (offset, compressed, format) = match header {
+            27..=30 => (HEADER_P2PKH_UNCOMPRESSED, false, BitcoinFormat::P2PKH),
+            31..=34 => (HEADER_P2PKH_COMPRESSED, true, self.format()),
+            35..=38 => (HEADER_P2SH_P2WPKH, true, BitcoinFormat::P2SH_P2WPKH),
+            _ => (HEADER_BECH32, true, BitcoinFormat::Bech32),
+        };
+
+        let hash = to_message_hash(message)?;
+        let recovery_id = secp256k1::RecoveryId::parse(header - offset).map_err(CrateError::from)?;
+        let signature = secp256k1::Signature::parse_slice(&signature[1..]).map_err(CrateError::from)?;
+        let public_key = match secp256k1::recover(&secp256k1::Message::parse(&hash), &signature, &recovery_id) {
+            Ok(public_key) => BitcoinPublicKey::<N>::from_secp256k1_public_key(public_key, compressed),
+            Err(_) => return Ok(false),
+        };
+
+        Ok(match format {
+            BitcoinFormat::P2PKH => Self::p2pkh(&public_key)? == *self,
+            BitcoinFormat::P2SH_P2WPKH => Self::p2sh_p2wpkh(&public_key)? == *self,
+            BitcoinFormat::Bech32 => Self::bech32(&public_key)? == *self,
+            _ => false,
+        })
+    }
+
+    /// Returns `true` if the witness of a BIP322 simple signature spends the P2WPKH address.
+    fn verify_segwit_message(&self, message: &[u8], witness: &[Vec<u8>]) -> Result<bool, MessageError> {
+        let program = self.to_witness_program()?;
+        if witness.len() != 2 || program.version != 0 || program.program.len() != 20 {
+            return Ok(false);
+        }
+
+        let (signature, public_key) = (&witness[0], &witness[1]);
+        if public_key.len() != 33 || hash160(public_key) != program.program || signature.last() != Some(&0x01) {
+            return Ok(false);
+        }
+
+        let (_, to_spend_id) = to_spend(self, message)?;
+        let hash = to_sign_segwit_hash(&to_spend_id, &program.program)?;
+        let signature = match secp256k1::Signature::parse_der(&signature[..signature.len() - 1]) {
+            Ok(signature) => signature,
+            Err(_) => return Ok(false),
+        };
+        let public_key = secp256k1::PublicKey::parse_slice(public_key, None).map_err(CrateError::from)?;
+        Ok(secp256k1::verify(
+            &secp256k1::Message::parse(&hash),
+            &signature,
+            &public_key,
+        ))
+    }
+
+    /// Returns `true` if the witness of a BIP322 simple signature spends the P2TR address
+    /// through its key path.
+    fn verify_taproot_message(&self, message: &[u8], witness: &[Vec<u8>]) -> Result<bool, MessageError> {
+        let program = self.to_witness_program()?;
+        if witness.len() != 1 || program.version != 1 || program.program.len() != 32 {
+            return Ok(false);
+        }
+
+        // A signature with an explicit hash type may only use SIGHASH_ALL, as the outputs are fixed.
+        let hash_type = match witness[0].len() {
+            64 => 0x00,
+            65 if witness[0][64] == 0x01 => 0x01,
+            _ => return Ok(false),
+        };
+
+        let (script_pub_key, to_spend_id) = to_spend(self, message)?;
+        let hash = to_sign_taproot_hash(&to_spend_id, &script_pub_key, hash_type)?;
+
+        let mut output_key = [0u8; 32];
+        output_key.copy_from_slice(&program.program);
+        let mut signature = [0u8; 64];
+        signature.copy_from_slice(&witness[0][..64]);
+        Ok(BitcoinPublicKey::<N>::from_x_only_public_key(&output_key)?.verify_schnorr(&hash, &signature))
+    }
+}
+
+/// Returns the items of a consensus-serialized witness stack.
+fn to_witness_stack(witness: &[u8]) -> Result<Vec<Vec<u8>>, MessageError> {
+    let mut reader = witness;
+    let count = read_variable_length_integer(&mut reader)?;
+    let mut stack = vec![];
+    for _ in 0..count {
+        let length = read_variable_length_integer(&mut reader)?;
+        if reader.len() < length {
+            return Err(MessageError::InvalidWitness);
+        }
+        stack.push(reader[..length].to_vec());
+        reader = &reader[length..];
+    }
+
+    match reader.is_empty() {
+        true => Ok(stack),
+        false => Err(MessageError::InvalidWitness),
+    }
+}
+
+#[derive(Debug, Fail)]
+pub enum MessageError {
+    #[fail(display = "{}", _0)]
+    AddressError(#[cause] AddressError),
+
+    #[fail(display = "{}", _0)]
+    Crate(#[cause] CrateError),
+
+    #[fail(display = "invalid base64 encoding")]
+    InvalidBase64,
+
+    #[fail(display = "invalid witness")]
+    InvalidWitness,
+
+    #[fail(display = "{}", _0)]
+    PrivateKeyError(#[cause] PrivateKeyError),
+
+    #[fail(display = "{}", _0)]
+    PublicKeyError(#[cause] PublicKeyError),
+
+    #[fail(display = "{}", _0)]
+    TransactionError(#[cause] TransactionError),
+
+    #[fail(display = "unsupported format: {}", _0)]
+    UnsupportedFormat(String),
+}
+
+impl From<AddressError> for MessageError {
+    fn from(error: AddressError) -> Self {
+        MessageError::AddressError(error)
+    }
+}
+
+impl From<CrateError> for MessageError {
+    fn from(error: CrateError) -> Self {
+        MessageError::Crate(error)
+    }
+}
+
+impl From<PrivateKeyError> for MessageError {
+    fn from(error: PrivateKeyError) -> Self {
+        MessageError::PrivateKeyError(error)
+    }
+}
+
+impl From<PublicKeyError> for MessageError {
+    fn from(error: PublicKeyError) -> Self {
+        MessageError::PublicKeyError(error)
+    }
+}
+
+impl From<TransactionError> for MessageError {
+    fn from(error: TransactionError) -> Self {
+        MessageError::TransactionError(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::*;
+
+    use core::str::FromStr;
+
+    #[test]
+    fn legacy() {
+        type N = Testnet;
+
+        // https://github.com/bitcoin/bitcoin/blob/master/test/functional/rpc_signmessage.py
+        let private_key =
+            BitcoinPrivateKey::<N>::from_str("cUeKHd5orzT3mz8P9pxyREHfsWtVfgsfDjiZZBcjUBAaGk1BTj7N").unwrap();
+        let address = BitcoinAddress::<N>::from_str("mpLQjfK79b7CCV4VMJWEWAj5Mpx8Up5zxB").unwrap();
+        let message = b"This is just a test message";
+        let expected_signature =
+            "INbVnW4e6PeRmsv2Qgu8NuopvrVjkcxob+sX8OcZG0SALhWybUjzMLPdAsXI46YZGb0KQTRii+wWIQzRpG/U+S0=";
+
+        let signature = private_key.sign_message(message, &BitcoinFormat::P2PKH).unwrap();
+        assert_eq!(expected_signature, signature);
+        assert!(address.verify_message(message, &signature).unwrap());
+        assert!(!address
+            .verify_message(b"This is just a test message.", &signature)
+            .unwrap());
+    }
+
+    #[test]
+    fn bip137() {
+        type N = Mainnet;
+
+        let private_key =
+            BitcoinPrivateKey::<N>::from_str("L3VFeEujGtevx9w18HD1fhRbCH67Az2dpCymeRE1SoPK6XQtaN2k").unwrap();
+        let address = private_key.to_address(&BitcoinFormat::P2SH_P2WPKH).unwrap();
+        let signature = private_key.sign_message(b"", &BitcoinFormat::P2SH_P2WPKH).unwrap();
+        assert!(address.verify_message(b"", &signature).unwrap());
+
+        let other_address = private_key.to_address(&BitcoinFormat::P2PKH).unwrap();
+        assert!(!other_address.verify_message(b"", &signature).unwrap());
+    }
+
+    #[test]
+    fn bip322() {
+        type N = Mainnet;
+
+        // https://github.com/bitcoin/bips/blob/master/bip-0322.mediawiki#test-vectors
+        let private_key =
+            BitcoinPrivateKey::<N>::from_str("L3VFeEujGtevx9w18HD1fhRbCH67Az2dpCymeRE1SoPK6XQtaN2k").unwrap();
+        let address = BitcoinAddress::<N>::from_str("bc1q9vza2e8x573nczrlzms0wvx3gsqjx7vavgkx0l").unwrap();
+        assert_eq!(address, private_key.to_address(&BitcoinFormat::Bech32).unwrap());
+
+        // (message, signature)
+        let vectors = [
+            (
+                "",
+                "AkcwRAIgM2gBAQqvZX15ZiysmKmQpDrG83avLIT492QBzLnQIxYCIBaTpOaD20qRlEylyxFSeEA2ba9YOixpX8z46TSDtS40ASECx/EgAxlkQpQ9hYjgGu6EBCPMVPwVIVJqO4XCsMvViHI=",
+            ),
+            (
+                "Hello World",
+                "AkcwRAIgZRfIY3p7/DoVTty6YZbWS71bc5Vct9p9Fia83eRmw2QCICK/ENGfwLtptFluMGs2KsqoNSk89pO7F29zJLUx9a/sASECx/EgAxlkQpQ9hYjgGu6EBCPMVPwVIVJqO4XCsMvViHI=",
+            ),
+        ];
+        vectors.iter().for_each(|(message, expected_signature)| {
+            let signature = private_key
+                .sign_message(message.as_bytes(), &BitcoinFormat::Bech32)
+                .unwrap();
+            assert_eq!(*expected_signature, signature);
+            assert!(address.verify_message(message.as_bytes(), &signature).unwrap());
+        });
+        assert!(!address.verify_message(b"Hello World", vectors[0].1).unwrap());
+
+        let address =
+            BitcoinAddress::<N>::from_str("bc1ppv609nr0vr25u07u95waq5lucwfm6tde4nydujnu8npg4q75mr5sxq8lt3").unwrap();
+        assert_eq!(address, private_key.to_address(&BitcoinFormat::P2TR).unwrap());
+        let signature = "AUHd69PrJQEv+oKTfZ8l+WROBHuy9HKrbFCJu7U1iK2iiEy1vMU5EfMtjc+VSHM7aU0SDbak5IUZRVno2P5mjSafAQ==";
+        assert!(address.verify_message(b"Hello World", signature).unwrap());
+
+        let signature = private_key.sign_message(b"Hello World", &BitcoinFormat::P2TR).unwrap();
+        assert!(address.verify_message(b"Hello World", &signature).unwrap());
+        assert!(!address.verify_message(b"", &signature).unwrap());
+    }
+
+    #[test]
+    fn message_hash() {
+        // https://github.com/bitcoin/bips/blob/master/bip-0322.mediawiki#message-hashing
+        assert_eq!(
+            "c90c269c4f8fcbe6880f72a721ddfbf1914268a794cbb21cfafee13770ae19f1",
+            hex::encode(tagged_hash(b"BIP0322-signed-message", b""))
+        );
+        assert_eq!(
+            "f0eb03b1a75ac6d9847f55c624a99169b5dccba2a31f5b23bea77ba270de0a7a",
+            hex::encode(tagged_hash(b"BIP0322-signed-message", b"Hello World"))
+        );
+    }
+}
@@ -0,0 +1,768 @@
+//!
+//! Miniscript
+//!
+//! This module contains the compilation of spending policies into Miniscript, a structured
+//! representation of Bitcoin scripts which can be analyzed for its satisfaction costs.
+//! Policies are compiled without optimization: `and` compiles to `and_v`, `or` to `or_i`,
+//! and `thresh` to `multi` for keys or to `thresh` otherwise, producing P2WSH witness scripts.
+//! https://bitcoin.sipa.be/miniscript/
+//!
+
+use crate::address::BitcoinAddress;
+use crate::multisig::{BitcoinMultisig, MAX_MULTISIG_PUBLIC_KEYS};
+use crate::network::BitcoinNetwork;
+use crate::public_key::BitcoinPublicKey;
+use crate::transaction::{variable_length_integer, Opcode};
+use crate::witness_script::BitcoinWitnessScript;
+use wagyu_model::no_std::*;
+use wagyu_model::{AddressError, PublicKeyError};
+
+use core::{fmt, str::FromStr};
+
+/// The maximum size of an ECDSA signature with its sighash byte, and its length prefix
+const SIGNATURE_SIZE: usize = 73;
+/// The size of a 32-byte hash preimage, and its length prefix
+const PREIMAGE_SIZE: usize = 33;
+/// The largest timelock value, as timelocks with the disable flag (1 << 31) set are invalid
+const MAX_TIMELOCK: u32 = 0x7fff_ffff;
+
+/// Represents a spending policy, e.g. `or(pk(A),and(pk(B),older(144)))`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BitcoinPolicy<N: BitcoinNetwork> {
+    /// `pk(KEY)`, a signature of the compressed public key
+    Key(BitcoinPublicKey<N>),
+    /// `after(n)`, an absolute timelock of the block height or timestamp
+    After(u32),
+    /// `older(n)`, a relative timelock of the number of blocks or time units
+    Older(u32),
+    /// `sha256(h)`, a preimage of the SHA256 hash
+    Sha256([u8; 32]),
+    /// `hash256(h)`, a preimage of the double SHA256 hash
+    Hash256([u8; 32]),
+    /// `ripemd160(h)`, a preimage of the RIPEMD160 hash
+    Ripemd160([u8; 20]),
+    /// `hash160(h)`, a preimage of the RIPEMD160 of SHA256 hash
+    Hash160([u8; 20]),
+    /// `and(X,Y)`, both policies
+    And(Box<BitcoinPolicy<N>>, Box<BitcoinPolicy<N>>),
+    /// `or(X,Y)`, either policy
+    Or(Box<BitcoinPolicy<N>>, Box<BitcoinPolicy<N>>),
+    /// `thresh(k,X,...)`, at least k of the policies
+    Threshold(usize, Vec<BitcoinPolicy<N>>),
+}
+
+impl<N: BitcoinNetwork> BitcoinPolicy<N> {
+    /// Returns the miniscript compiled from the policy.
+    pub fn compile(&self) -> Result<BitcoinMiniscript<N>, MiniscriptError> {
+        Ok(match self {
+            BitcoinPolicy::Key(public_key) => {
+                BitcoinMiniscript::Check(Box::new(BitcoinMiniscript::PublicKey(public_key.clone())))
+            }
+            BitcoinPolicy::After(n) => BitcoinMiniscript::After(*n),
+            BitcoinPolicy::Older(n) => BitcoinMiniscript::Older(*n),
+            BitcoinPolicy::Sha256(hash) => BitcoinMiniscript::Sha256(*hash),
+            BitcoinPolicy::Hash256(hash) => BitcoinMiniscript::Hash256(*hash),
+            BitcoinPolicy::Ripemd160(hash) => BitcoinMiniscript::Ripemd160(*hash),
+            BitcoinPolicy::Hash160(hash) => BitcoinMiniscript::Hash160(*hash),
+            BitcoinPolicy::And(x, y) => BitcoinMiniscript::AndV(
+                Box::new(BitcoinMiniscript::Verify(Box::new(x.compile()?))),
+                Box::new(y.compile()?),
+            ),
+            BitcoinPolicy::Or(x, y) => BitcoinMiniscript::OrI(Box::new(x.compile()?), Box::new(y.compile()?)),
+            BitcoinPolicy::Threshold(k, policies) => {
+                let public_keys = policies
+                    .iter()
+                    .filter_map(|policy| match policy {
+                        BitcoinPolicy::Key(public_key) => Some(public_key.clone()),
+                        _ => None,
+                    })
+                    .collect::<Vec<BitcoinPublicKey<N>>>();
+
+                match public_keys.len() == policies.len() && public_keys.len() <= MAX_MULTISIG_PUBLIC_KEYS {
+                    true => BitcoinMiniscript::Multi(*k, public_keys),
+                    false => BitcoinMiniscript::Thresh(
+                        *k,
+                        policies
+                            .iter()
+                            .enumerate()
+                            .map(|(i, policy)| {
+                                let miniscript = policy.compile()?.into_dissatisfiable_unit();
+                                Ok(match i {
+                                    0 => miniscript,
+                                    _ => BitcoinMiniscript::Alt(Box::new(miniscript)),
+                                })
+                            })
+                            .collect::<Result<Vec<BitcoinMiniscript<N>>, MiniscriptError>>()?,
+                    ),
+                }
+            }
+        })
+    }
+
+    /// Returns the policy of the expression, e.g. `pk(KEY)` or `and(X,Y)`.
+    fn parse(policy: &str) -> Result<Self, MiniscriptError> {
+        let invalid = || MiniscriptError::InvalidPolicy(policy.to_string());
+
+        let (name, arguments) = match (policy.find('('), policy.ends_with(')')) {
+            (Some(start), true) => (&policy[..start], split_arguments(&policy[start + 1..policy.len() - 1])?),
+            _ => return Err(invalid()),
+        };
+
+        match (name, arguments.as_slice()) {
+            ("pk", [public_key]) => {
+                let public_key = BitcoinPublicKey::from_str(public_key)?;
+                match public_key.is_compressed() {
+                    true => Ok(BitcoinPolicy::Key(public_key)),
+                    false => Err(invalid()),
+                }
+            }
+            ("after", [n]) => Ok(BitcoinPolicy::After(parse_timelock(n)?)),
+            ("older", [n]) => Ok(BitcoinPolicy::Older(parse_timelock(n)?)),
+            ("sha256", [hash]) => Ok(BitcoinPolicy::Sha256(parse_hash(hash)?)),
+            ("hash256", [hash]) => Ok(BitcoinPolicy::Hash256(parse_hash(hash)?)),
+            ("ripemd160", [hash]) => Ok(BitcoinPolicy::Ripemd160(parse_hash(hash)?)),
+            ("hash160", [hash]) => Ok(BitcoinPolicy::Hash160(parse_hash(hash)?)),
+            ("and", [x, y]) => Ok(BitcoinPolicy::And(Box::new(Self::parse(x)?), Box::new(Self::parse(y)?))),
+            // Probabilities of the branches, e.g. `or(9@pk(A),1@pk(B))`, do not affect compilation
+            ("or", [x, y]) => Ok(BitcoinPolicy::Or(
+                Box::new(Self::parse(without_probability(x))?),
+                Box::new(Self::parse(without_probability(y))?),
+            )),
+            ("thresh", [k, policies @ ..]) => {
+                let k = k.parse::<usize>().map_err(|_| invalid())?;
+                if k == 0 || k > policies.len() {
+                    return Err(MiniscriptError::InvalidThreshold(k, policies.len()));
+                }
+                Ok(BitcoinPolicy::Threshold(
+                    k,
+                    policies
+                        .iter()
+                        .map(|policy| Self::parse(policy))
+                        .collect::<Result<Vec<Self>, MiniscriptError>>()?,
+                ))
+            }
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl<N: BitcoinNetwork> FromStr for BitcoinPolicy<N> {
+    type Err = MiniscriptError;
+
+    fn from_str(policy: &str) -> Result<Self, Self::Err> {
+        Self::parse(&policy.split_whitespace().collect::<String>())
+    }
+}
+
+/// Returns the top-level, comma-separated arguments of a policy expression.
+fn split_arguments(arguments: &str) -> Result<Vec<&str>, MiniscriptError> {
+    let mut split = vec![];
+    let (mut depth, mut start) = (0usize, 0);
+    for (i, c) in arguments.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => match depth.checked_sub(1) {
+                Some(value) => depth = value,
+                None => return Err(MiniscriptError::InvalidPolicy(arguments.to_string())),
+            },
+            ',' if depth == 0 => {
+                split.push(&arguments[start..i]);
+                start = i + 1;
+            }
+            _ => (),
+        }
+    }
+    if depth != 0 {
+        return Err(MiniscriptError::InvalidPolicy(arguments.to_string()));
+    }
+    split.push(&arguments[start..]);
+    Ok(split)
+}
+
+/// Returns the policy expression without its probability prefix, e.g. `pk(A)` for `9@pk(A)`.
+fn without_probability(policy: &str) -> &str {
+    match policy.find('@') {
+        Some(position) if policy[..position].chars().all(|c| c.is_ascii_digit()) => &policy[position + 1..],
+        _ => policy,
+    }
+}
+
+/// Returns the timelock value, which must be within [1, 2^31 - 1].
+fn parse_timelock(n: &str) -> Result<u32, MiniscriptError> {
+    match n.parse::<u32>() {
+        Ok(n) if (1..=MAX_TIMELOCK).contains(&n) => Ok(n),
+        _ => Err(MiniscriptError::InvalidTimelock(n.to_string())),
+    }
+}
+
+/// Returns the hash of the given hex, which must have the length of the hash.
+fn parse_hash<T: Default + AsMut<[u8]>>(hash: &str) -> Result<T, MiniscriptError> {
+    let mut result = T::default();
+    match hex::decode(hash) {
+        Ok(bytes) if bytes.len() == result.as_mut().len() => {
+            result.as_mut().copy_from_slice(&bytes);
+            Ok(result)
+        }
+        _ => Err(MiniscriptError::InvalidHash(hash.to_string())),
+    }
+}
+
+/// Represents a miniscript fragment, compiled from a policy
+/// https://bitcoin.sipa.be/miniscript/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BitcoinMiniscript<N: BitcoinNetwork> {
+    /// `1`
+    True,
+    /// `0`
+    False,
+    /// `pk_k(KEY)`, a push of the public key
+    PublicKey(BitcoinPublicKey<N>),
+    /// `multi(k,KEY,...)`, a CHECKMULTISIG of the public keys in the given order
+    Multi(usize, Vec<BitcoinPublicKey<N>>),
+    /// `after(n)`
+    After(u32),
+    /// `older(n)`
+    Older(u32),
+    /// `sha256(h)`
+    Sha256([u8; 32]),
+    /// `hash256(h)`
+    Hash256([u8; 32]),
+    /// `ripemd160(h)`
+    Ripemd160([u8; 20]),
+    /// `hash160(h)`
+    Hash160([u8; 20]),
+    /// `and_v(X,Y)`, X followed by Y
+    AndV(Box<BitcoinMiniscript<N>>, Box<BitcoinMiniscript<N>>),
+    /// `or_i(X,Y)`, X if the satisfaction selects it, otherwise Y
+    OrI(Box<BitcoinMiniscript<N>>, Box<BitcoinMiniscript<N>>),
+    /// `thresh(k,X,...)`, the sum of the satisfied fragments equal to k
+    Thresh(usize, Vec<BitcoinMiniscript<N>>),
+    /// `a:X`, X evaluated on the alt stack
+    Alt(Box<BitcoinMiniscript<N>>),
+    /// `c:X`, a CHECKSIG of the public key X
+    Check(Box<BitcoinMiniscript<N>>),
+    /// `v:X`, X followed by a VERIFY
+    Verify(Box<BitcoinMiniscript<N>>),
+}
+
+impl<N: BitcoinNetwork> BitcoinMiniscript<N> {
+    /// Returns the script of the miniscript.
+    pub fn to_script(&self) -> Vec<u8> {
+        let mut script = vec![];
+        self.encode(&mut script);
+        script
+    }
+
+    /// Returns the witness script of the miniscript.
+    pub fn to_witness_script(&self) -> Result<BitcoinWitnessScript, AddressError> {
+        BitcoinWitnessScript::new(&self.to_script())
+    }
+
+    /// Returns the P2WSH address of the miniscript.
+    pub fn to_address(&self) -> Result<BitcoinAddress<N>, AddressError> {
+        self.to_witness_script()?.to_address::<N>()
+    }
+
+    /// Returns the maximum size, in bytes, of the witness stack satisfying the miniscript,
+    /// or `None` if the miniscript cannot be satisfied.
+    pub fn max_satisfaction_size(&self) -> Option<usize> {
+        self.max_satisfaction().map(|(_, size)| size)
+    }
+
+    /// Returns the maximum weight, in weight units, of the witness spending the P2WSH output of the miniscript,
+    /// including the witness script, or `None` if the miniscript cannot be satisfied.
+    pub fn max_satisfaction_weight(&self) -> Option<usize> {
+        let (elements, size) = self.max_satisfaction()?;
+        let script_size = self.to_script().len();
+        Some(
+            variable_length_integer(elements as u64 + 1).ok()?.len()
+                + size
+                + variable_length_integer(script_size as u64).ok()?.len()
+                + script_size,
+        )
+    }
+
+    /// Returns `true` if the miniscript can be dissatisfied, leaving a zero on the stack.
+    fn is_dissatisfiable(&self) -> bool {
+        match self {
+            BitcoinMiniscript::False | BitcoinMiniscript::Multi(..) | BitcoinMiniscript::Thresh(..) => true,
+            BitcoinMiniscript::Sha256(_)
+            | BitcoinMiniscript::Hash256(_)
+            | BitcoinMiniscript::Ripemd160(_)
+            | BitcoinMiniscript::Hash160(_) => true,
+            BitcoinMiniscript::Check(x) | BitcoinMiniscript::Alt(x) => x.is_dissatisfiable(),
+            BitcoinMiniscript::PublicKey(_) => true,
+            BitcoinMiniscript::OrI(x, y) => x.is_dissatisfiable() || y.is_dissatisfiable(),
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if the miniscript leaves exactly one or zero on the stack, when satisfied or dissatisfied.
+    fn is_unit(&self) -> bool {
+        match self {
+            BitcoinMiniscript::True | BitcoinMiniscript::False => true,
+            BitcoinMiniscript::Multi(..) | BitcoinMiniscript::Thresh(..) | BitcoinMiniscript::Check(_) => true,
+            BitcoinMiniscript::Sha256(_)
+            | BitcoinMiniscript::Hash256(_)
+            | BitcoinMiniscript::Ripemd160(_)
+            | BitcoinMiniscript::Hash160(_) => true,
+            BitcoinMiniscript::AndV(_, y) => y.is_unit(),
+            BitcoinMiniscript::OrI(x, y) => x.is_unit() && y.is_unit(),
+            BitcoinMiniscript::Alt(x) => x.is_unit(),
+            _ => false,
+        }
+    }
+
+    /// Returns the miniscript, wrapped to be dissatisfiable and to leave exactly one or zero on the stack,
+    /// as required of the fragments of `thresh`.
+    fn into_dissatisfiable_unit(self) -> Self {
+        if self.is_dissatisfiable() && self.is_unit() {
+            return self;
+        }
+        let unit = match self.is_unit() {
+            true => self,
+            false => BitcoinMiniscript::AndV(
+                Box::new(BitcoinMiniscript::Verify(Box::new(self))),
+                Box::new(BitcoinMiniscript::True),
+            ),
+        };
+        BitcoinMiniscript::OrI(Box::new(unit), Box::new(BitcoinMiniscript::False))
+    }
+
+    /// Returns the number of elements and size of the largest witness satisfying the miniscript.
+    fn max_satisfaction(&self) -> Option<(usize, usize)> {
+        match self {
+            BitcoinMiniscript::True | BitcoinMiniscript::After(_) | BitcoinMiniscript::Older(_) => Some((0, 0)),
+            BitcoinMiniscript::False | BitcoinMiniscript::PublicKey(_) => None,
+            // The signatures, preceded by the empty element consumed by CHECKMULTISIG
+            BitcoinMiniscript::Multi(k, _) => Some((k + 1, 1 + k * SIGNATURE_SIZE)),
+            BitcoinMiniscript::Sha256(_)
+            | BitcoinMiniscript::Hash256(_)
+            | BitcoinMiniscript::Ripemd160(_)
+            | BitcoinMiniscript::Hash160(_) => Some((1, PREIMAGE_SIZE)),
+            BitcoinMiniscript::Check(x) => match **x {
+                BitcoinMiniscript::PublicKey(_) => Some((1, SIGNATURE_SIZE)),
+                _ => x.max_satisfaction(),
+            },
+            BitcoinMiniscript::Alt(x) | BitcoinMiniscript::Verify(x) => x.max_satisfaction(),
+            BitcoinMiniscript::AndV(x, y) => {
+                let (x, y) = (x.max_satisfaction()?, y.max_satisfaction()?);
+                Some((x.0 + y.0, x.1 + y.1))
+            }
+            // The branch is selected by a one (2 bytes) for X, or an empty element (1 byte) for Y
+            BitcoinMiniscript::OrI(x, y) => {
+                let x = x.max_satisfaction().map(|(elements, size)| (elements + 1, size + 2));
+                let y = y.max_satisfaction().map(|(elements, size)| (elements + 1, size + 1));
+                match (x, y) {
+                    (Some(x), Some(y)) => Some(if x.1 >= y.1 { x } else { y }),
+                    (x, y) => x.or(y),
+                }
+            }
+            // The k satisfactions that add the most to the dissatisfactions of every fragment
+            BitcoinMiniscript::Thresh(k, subs) => {
+                let dissatisfactions = subs
+                    .iter()
+                    .map(|sub| sub.max_dissatisfaction())
+                    .collect::<Option<Vec<(usize, usize)>>>()?;
+                let mut differences = subs
+                    .iter()
+                    .zip(dissatisfactions.iter())
+                    .filter_map(|(sub, dissatisfaction)| {
+                        sub.max_satisfaction().map(|satisfaction| {
+                            (
+                                satisfaction.0 as isize - dissatisfaction.0 as isize,
+                                satisfaction.1 as isize - dissatisfaction.1 as isize,
+                            )
+                        })
+                    })
+                    .collect::<Vec<(isize, isize)>>();
+                if differences.len() < *k {
+                    return None;
+                }
+                differences.sort_by_key(|difference| core::cmp::Reverse(difference.1));
+
+                let (elements, size) = dissatisfactions
+                    .iter()
+                    .fold((0, 0), |(elements, size), dissatisfaction| {
+                        (elements + dissatisfaction.0, size + dissatisfaction.1)
+                    });
+                let (elements, size) = differences[..*k]
+                    .iter()
+                    .fold((elements as isize, size as isize), |(elements, size), difference| {
+                        (elements + difference.0, size + difference.1)
+                    });
+                Some((elements as usize, size as usize))
+            }
+        }
+    }
+
+    /// Returns the number of elements and size of the largest witness dissatisfying the miniscript.
+    fn max_dissatisfaction(&self) -> Option<(usize, usize)> {
+        match self {
+            BitcoinMiniscript::False => Some((0, 0)),
+            // Empty signatures, preceded by the empty element consumed by CHECKMULTISIG
+            BitcoinMiniscript::Multi(k, _) => Some((k + 1, k + 1)),
+            // A 32-byte element that is not the preimage
+            BitcoinMiniscript::Sha256(_)
+            | BitcoinMiniscript::Hash256(_)
+            | BitcoinMiniscript::Ripemd160(_)
+            | BitcoinMiniscript::Hash160(_) => Some((1, PREIMAGE_SIZE)),
+            BitcoinMiniscript::Check(x) => match **x {
+                BitcoinMiniscript::PublicKey(_) => Some((1, 1)),
+                _ => x.max_dissatisfaction(),
+            },
+            BitcoinMiniscript::Alt(x) => x.max_dissatisfaction(),
+            BitcoinMiniscript::OrI(x, y) => {
+                let x = x.max_dissatisfaction().map(|(elements, size)| (elements + 1, size + 2));
+                let y = y.max_dissatisfaction().map(|(elements, size)| (elements + 1, size + 1));
+                match (x, y) {
+                    (Some(x), Some(y)) => Some(if x.1 >= y.1 { x } else { y }),
+                    (x, y) => x.or(y),
+                }
+            }
+            BitcoinMiniscript::Thresh(_, subs) => subs.iter().try_fold((0, 0), |(elements, size), sub| {
+                let dissatisfaction = sub.max_dissatisfaction()?;
+                Some((elements + dissatisfaction.0, size + dissatisfaction.1))
+            }),
+            _ => None,
+        }
+    }
+
+    /// Appends the script of the miniscript to the given script.
+    fn encode(&self, script: &mut Vec<u8>) {
+        match self {
+            BitcoinMiniscript::True => script.push(Opcode::OP_1 as u8),
+            BitcoinMiniscript::False => script.push(Opcode::OP_0 as u8),
+            BitcoinMiniscript::PublicKey(public_key) => {
+                script.push(33);
+                script.extend_from_slice(&public_key.to_secp256k1_public_key().serialize_compressed());
+            }
+            BitcoinMiniscript::Multi(k, public_keys) => match BitcoinMultisig::with_key_order(*k, public_keys) {
+                Ok(multisig) => script.extend(multisig.to_redeem_script()),
+                Err(_) => script.push(Opcode::OP_0 as u8),
+            },
+            BitcoinMiniscript::After(n) => {
+                push_number(script, *n);
+                script.push(Opcode::OP_CHECKLOCKTIMEVERIFY as u8);
+            }
+            BitcoinMiniscript::Older(n) => {
+                push_number(script, *n);
+                script.push(Opcode::OP_CHECKSEQUENCEVERIFY as u8);
+            }
+            BitcoinMiniscript::Sha256(hash) => encode_hash(script, Opcode::OP_SHA256, hash),
+            BitcoinMiniscript::Hash256(hash) => encode_hash(script, Opcode::OP_HASH256, hash),
+            BitcoinMiniscript::Ripemd160(hash) => encode_hash(script, Opcode::OP_RIPEMD160, hash),
+            BitcoinMiniscript::Hash160(hash) => encode_hash(script, Opcode::OP_HASH160, hash),
+            BitcoinMiniscript::AndV(x, y) => {
+                x.encode(script);
+                y.encode(script);
+            }
+            BitcoinMiniscript::OrI(x, y) => {
+                script.push(Opcode::OP_IF as u8);
+                x.encode(script);
+                script.push(Opcode::OP_ELSE as u8);
+                y.encode(script);
+                script.push(Opcode::OP_ENDIF as u8);
+            }
+            BitcoinMiniscript::Thresh(k, subs) => {
+                for (i, sub) in subs.iter().enumerate() {
+                    sub.encode(script);
+                    if i > 0 {
+                        script.push(Opcode::OP_ADD as u8);
+                    }
+                }
+                push_number(script, *k as u32);
+                script.push(Opcode::OP_EQUAL as u8);
+            }
+            BitcoinMiniscript::Alt(x) => {
+                script.push(Opcode::OP_TOALTSTACK as u8);
+                x.encode(script);
+                script.push(Opcode::OP_FROMALTSTACK as u8);
+            }
+            BitcoinMiniscript::Check(x) => {
+                x.encode(script);
+                script.push(Opcode::OP_CHECKSIG as u8);
+            }
+            // The final CHECKSIG, CHECKMULTISIG, or EQUAL of X is replaced by its VERIFY opcode
+            BitcoinMiniscript::Verify(x) => {
+                x.encode(script);
+                let verify = match **x {
+                    BitcoinMiniscript::Check(_) => Some(Opcode::OP_CHECKSIGVERIFY),
+                    BitcoinMiniscript::Multi(..) => Some(Opcode::OP_CHECKMULTISIGVERIFY),
+                    BitcoinMiniscript::Sha256(_)
+                    | BitcoinMiniscript::Hash256(_)
+                    | BitcoinMiniscript::Ripemd160(_)
+                    | BitcoinMiniscript::Hash160(_)
+                    | BitcoinMiniscript::Thresh(..) => Some(Opcode::OP_EQUALVERIFY),
+                    _ => None,
+                };
+                match verify {
+                    Some(opcode) => {
+                        script.pop();
+                        script.push(opcode as u8);
+                    }
+                    None => script.push(Opcode::OP_VERIFY as u8),
+                }
+            }
+        }
+    }
+
+    /// Writes the miniscript, prefixed by the given wrappers, e.g. `v:` or `ac:`.
+    fn write(&self, f: &mut fmt::Formatter, wrappers: &str) -> fmt::Result {
+        let prefix = |f: &mut fmt::Formatter| match wrappers.is_empty() {
+            true => Ok(()),
+            false => write!(f, "{}:", wrappers),
+        };
+        match self {
+            BitcoinMiniscript::Alt(x) => x.write(f, &format!("{}a", wrappers)),
+            BitcoinMiniscript::Verify(x) => x.write(f, &format!("{}v", wrappers)),
+            BitcoinMiniscript::Check(x) => match &**x {
+                BitcoinMiniscript::PublicKey(public_key) => {
+                    prefix(f)?;
+                    write!(f, "pk({})", public_key)
+                }
+                _ => x.write(f, &format!("{}c", wrappers)),
+            },
+            _ => {
+                prefix(f)?;
+                match self {
+                    BitcoinMiniscript::True => write!(f, "1"),
+                    BitcoinMiniscript::False => write!(f, "0"),
+                    BitcoinMiniscript::PublicKey(public_key) => write!(f, "pk_k({})", public_key),
+                    BitcoinMiniscript::Multi(k, public_keys) => write!(
+                        f,
+                        "multi({},{})",
+                        k,
+                        public_keys
+                            .iter()
+                            .map(|public_key| public_key.to_string())
+                            .collect::<Vec<String>>()
+                            .join(",")
+                    ),
+                    BitcoinMiniscript::After(n) => write!(f, "after({})", n),
+                    BitcoinMiniscript::Older(n) => write!(f, "older({})", n),
+                    BitcoinMiniscript::Sha256(hash) => write!(f, "sha256({})", hex::encode(hash)),
+                    BitcoinMiniscript::Hash256(hash) => write!(f, "hash256({})", hex::encode(hash)),
+                    BitcoinMiniscript::Ripemd160(hash) => write!(f, "ripemd160({})", hex::encode(hash)),
+                    BitcoinMiniscript::Hash160(hash) => write!(f, "hash160({})", hex::encode(hash)),
+                    BitcoinMiniscript::AndV(x, y) => write!(f, "and_v({},{})", x, y),
+                    BitcoinMiniscript::OrI(x, y) => write!(f, "or_i({},{})", x, y),
+                    BitcoinMiniscript::Thresh(k, subs) => write!(
+                        f,
+                        "thresh({},{})",
+                        k,
+                        subs.iter()
+                            .map(|sub| sub.to_string())
+                            .collect::<Vec<String>>()
+                            .join(",")
+                    ),
+                    _ => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+impl<N: BitcoinNetwork> fmt::Display for BitcoinMiniscript<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write(f, "")
+    }
+}
+
+/// Appends the minimal push of the number to the script.
+fn push_number(script: &mut Vec<u8>, n: u32) {
+    match n {
+        0 => script.push(Opcode::OP_0 as u8),
+        1..=16 => script.push(Opcode::OP_1 as u8 + n as u8 - 1),
+        _ => {
+            let mut bytes = n.to_le_bytes().to_vec();
+            while bytes.last() == Some(&0) {
+                bytes.pop();
+            }
+            // Script numbers are signed, so a set high bit requires an additional byte
+            if matches!(bytes.last(), Some(byte) if byte & 0x80 != 0) {
+                bytes.push(0);
+            }
+            script.push(bytes.len() as u8);
+            script.extend(bytes);
+        }
+    }
+}
+
+/// Appends the check of a 32-byte preimage of the hash, SIZE <32> EQUALVERIFY <HASH> <h> EQUAL, to the script.
+fn encode_hash(script: &mut Vec<u8>, opcode: Opcode, hash: &[u8]) {
+    script.extend_from_slice(&[
+        Opcode::OP_SIZE as u8,
+        0x01,
+        0x20,
+        Opcode::OP_EQUALVERIFY as u8,
+        opcode as u8,
+    ]);
+    script.push(hash.len() as u8);
+    script.extend_from_slice(hash);
+    script.push(Opcode::OP_EQUAL as u8);
+}
+
+#[derive(Debug, Fail)]
+pub enum MiniscriptError {
+    #[fail(display = "invalid policy hash: {}", _0)]
+    InvalidHash(String),
+
+    #[fail(display = "invalid policy: {}", _0)]
+    InvalidPolicy(String),
+
+    #[fail(display = "invalid policy threshold of {} for {} policies", _0, _1)]
+    InvalidThreshold(usize, usize),
+
+    #[fail(display = "invalid policy timelock: {}", _0)]
+    InvalidTimelock(String),
+
+    #[fail(display = "{}", _0)]
+    PublicKeyError(#[cause] PublicKeyError),
+}
+
+impl From<PublicKeyError> for MiniscriptError {
+    fn from(error: PublicKeyError) -> Self {
+        MiniscriptError::PublicKeyError(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::*;
+
+    type N = Mainnet;
+
+    const KEYS: [&str; 3] = [
+        "02e2cc6bd5f45edd43bebe7cb9b675f0ce9ed3efe613b177588290ad188d11b404",
+        "027735a29bae7780a9755fae7a1c4374c656ac6a69ea9f3697fda61bb99a4f3e77",
+        "02632b12f4ac5b1d1b72b2a3b508c19172de44f6f46bcee50ba33f3f9291e47ed0",
+    ];
+    const HASH: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+    fn compile(policy: &str) -> BitcoinMiniscript<N> {
+        BitcoinPolicy::<N>::from_str(policy).unwrap().compile().unwrap()
+    }
+
+    #[test]
+    fn compile_key() {
+        let miniscript = compile(&format!("pk({})", KEYS[0]));
+        assert_eq!(format!("pk({})", KEYS[0]), miniscript.to_string());
+        assert_eq!(format!("21{}ac", KEYS[0]), hex::encode(miniscript.to_script()));
+        assert_eq!(Some(SIGNATURE_SIZE), miniscript.max_satisfaction_size());
+        // 1 (elements) + 73 (signature) + 1 (script length) + 35 (script)
+        assert_eq!(Some(110), miniscript.max_satisfaction_weight());
+    }
+
+    #[test]
+    fn compile_or_and() {
+        let miniscript = compile(&format!("or(99@pk({}),1@and(pk({}),older(144)))", KEYS[0], KEYS[1]));
+        assert_eq!(
+            format!("or_i(pk({}),and_v(v:pk({}),older(144)))", KEYS[0], KEYS[1]),
+            miniscript.to_string()
+        );
+        assert_eq!(
+            format!("6321{}ac6721{}ad029000b268", KEYS[0], KEYS[1]),
+            hex::encode(miniscript.to_script())
+        );
+        // The signature of the first key and the one selecting its branch
+        assert_eq!(Some(SIGNATURE_SIZE + 2), miniscript.max_satisfaction_size());
+    }
+
+    #[test]
+    fn compile_hash() {
+        let miniscript = compile(&format!("and(sha256({}),after(500000))", HASH));
+        assert_eq!(
+            format!("and_v(v:sha256({}),after(500000))", HASH),
+            miniscript.to_string()
+        );
+        assert_eq!(
+            format!("82012088a820{}880320a107b1", HASH),
+            hex::encode(miniscript.to_script())
+        );
+        assert_eq!(Some(PREIMAGE_SIZE), miniscript.max_satisfaction_size());
+    }
+
+    #[test]
+    fn compile_multi() {
+        let miniscript = compile(&format!("thresh(2,pk({}),pk({}),pk({}))", KEYS[0], KEYS[1], KEYS[2]));
+        assert_eq!(format!("multi(2,{})", KEYS.join(",")), miniscript.to_string());
+
+        let public_keys = KEYS
+            .iter()
+            .map(|public_key| BitcoinPublicKey::<N>::from_str(public_key).unwrap())
+            .collect::<Vec<_>>();
+        let multisig = BitcoinMultisig::with_key_order(2, &public_keys).unwrap();
+        assert_eq!(multisig.to_redeem_script(), miniscript.to_script());
+        assert_eq!(multisig.to_p2wsh_address().unwrap(), miniscript.to_address().unwrap());
+        assert_eq!(Some(1 + 2 * SIGNATURE_SIZE), miniscript.max_satisfaction_size());
+    }
+
+    #[test]
+    fn compile_thresh() {
+        let miniscript = compile(&format!("thresh(2,pk({}),pk({}),after(1000))", KEYS[0], KEYS[1]));
+        assert_eq!(
+            format!(
+                "thresh(2,pk({}),a:pk({}),a:or_i(and_v(v:after(1000),1),0))",
+                KEYS[0], KEYS[1]
+            ),
+            miniscript.to_string()
+        );
+        assert_eq!(
+            format!("21{}ac6b21{}ac6c936b6302e803b169516700686c935287", KEYS[0], KEYS[1]),
+            hex::encode(miniscript.to_script())
+        );
+        // Both signatures and the dissatisfied timelock, or either signature, its empty dissatisfaction,
+        // and the satisfied timelock: 73 + 73 + 1 = 147 against 73 + 1 + 2 = 76
+        assert_eq!(Some(147), miniscript.max_satisfaction_size());
+    }
+
+    #[test]
+    fn to_address() {
+        let miniscript = compile(&format!("pk({})", KEYS[0]));
+        let witness_script = BitcoinWitnessScript::new(&miniscript.to_script()).unwrap();
+        assert_eq!(
+            witness_script.to_address::<N>().unwrap(),
+            miniscript.to_address().unwrap()
+        );
+        assert!(miniscript.to_address().unwrap().to_string().starts_with("bc1q"));
+    }
+
+    #[test]
+    fn push_numbers() {
+        [
+            (1, "51"),
+            (16, "60"),
+            (17, "0111"),
+            (144, "029000"),
+            (500000, "0320a107"),
+            (MAX_TIMELOCK, "04ffffff7f"),
+        ]
+        .iter()
+        .for_each(|(n, expected)| {
+            let mut script = vec![];
+            push_number(&mut script, *n);
+            assert_eq!(*expected, hex::encode(script));
+        });
+    }
+
+    #[test]
+    fn invalid_policy() {
+        let policies = [
+            "pk()".to_string(),
+            "after(0)".to_string(),
+            "older(2147483648)".to_string(),
+            format!("sha256({})", &HASH[2..]),
+            format!("and(pk({}))", KEYS[0]),
+            format!("or(pk({}),pk({})", KEYS[0], KEYS[1]),
+            format!("thresh(3,pk({}),pk({}))", KEYS[0], KEYS[1]),
+            format!("thresh(0,pk({}))", KEYS[0]),
+            format!("multi(1,{})", KEYS[0]),
+            "pk(0479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8)".to_string(),
+        ];
+        policies
+            .iter()
+            .for_each(|policy| assert!(BitcoinPolicy::<N>::from_str(policy).is_err()));
+    }
+}
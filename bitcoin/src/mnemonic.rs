@@ -7,19 +7,25 @@ use crate::private_key::BitcoinPrivateKey;
 use crate::public_key::BitcoinPublicKey;
 use crate::wordlist::BitcoinWordlist;
 use wagyu_model::no_std::*;
-use wagyu_model::{ExtendedPrivateKey, Mnemonic, MnemonicCount, MnemonicError, MnemonicExtended};
+use wagyu_model::{
+    crypto::pbkdf2_hmac_sha512, ExposeSecret, ExtendedPrivateKey, Mnemonic, MnemonicCount, MnemonicEntropy,
+    MnemonicError, MnemonicExtended, Redacted, WordSuggestion, WordlistError,
+};
 
 use bitvec::prelude::*;
 use core::{fmt, marker::PhantomData, ops::Div, str, str::FromStr};
-use hmac::Hmac;
-use pbkdf2::pbkdf2;
 use rand::Rng;
-use sha2::{Digest, Sha256, Sha512};
+#[cfg(feature = "std")]
+use rayon::prelude::*;
+use sha2::{Digest, Sha256};
+use zeroize::Zeroize;
 
-const PBKDF2_ROUNDS: usize = 2048;
+const PBKDF2_ROUNDS: u32 = 2048;
 const PBKDF2_BYTES: usize = 64;
+/// The maximum number of unknown words of a partial phrase.
+const MAX_UNKNOWN_WORDS: usize = 2;
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 /// Represents a Bitcoin mnemonic
 pub struct BitcoinMnemonic<N: BitcoinNetwork, W: BitcoinWordlist> {
     /// Initial entropy in multiples of 32 bits
@@ -52,6 +58,25 @@ impl<N: BitcoinNetwork, W: BitcoinWordlist> MnemonicCount for BitcoinMnemonic<N,
     }
 }
 
+impl<N: BitcoinNetwork, W: BitcoinWordlist> MnemonicEntropy for BitcoinMnemonic<N, W> {
+    /// Returns the mnemonic for the given entropy.
+    fn from_entropy(entropy: &[u8]) -> Result<Self, MnemonicError> {
+        match entropy.len() {
+            16 | 20 | 24 | 28 | 32 => Ok(Self {
+                entropy: entropy.to_vec(),
+                _network: PhantomData,
+                _wordlist: PhantomData,
+            }),
+            entropy_len => Err(MnemonicError::InvalidEntropyLength(entropy_len)),
+        }
+    }
+
+    /// Returns the entropy of the corresponding mnemonic.
+    fn to_entropy(&self) -> Vec<u8> {
+        self.entropy.clone()
+    }
+}
+
 impl<N: BitcoinNetwork, W: BitcoinWordlist> Mnemonic for BitcoinMnemonic<N, W> {
     type Address = BitcoinAddress<N>;
     type Format = BitcoinFormat;
@@ -81,6 +106,18 @@ impl<N: BitcoinNetwork, W: BitcoinWordlist> Mnemonic for BitcoinMnemonic<N, W> {
             wc => return Err(MnemonicError::InvalidWordCount(wc as u8)),
         };
 
+        // Ensures every word is in the wordlist, suggesting the nearest words for those that are not.
+        let wordlist = W::get_all();
+        let invalid_words = mnemonic
+            .iter()
+            .enumerate()
+            .filter(|(_, word)| !wordlist.contains(word))
+            .map(|(index, word)| WordSuggestion::new(index + 1, word, &wordlist))
+            .collect::<Vec<WordSuggestion>>();
+        if !invalid_words.is_empty() {
+            return Err(WordlistError::InvalidWords(invalid_words).into());
+        }
+
         let mut entropy: BitVec<Msb0, u8> = BitVec::new();
 
         for word in mnemonic {
@@ -115,6 +152,8 @@ impl<N: BitcoinNetwork, W: BitcoinWordlist> Mnemonic for BitcoinMnemonic<N, W> {
             entropy_len => return Err(MnemonicError::InvalidEntropyLength(entropy_len)),
         };
 
+        W::validate()?;
+
         // Compute the checksum by taking the first ENT / 32 bits of the SHA256 hash
         let mut sha256 = Sha256::new();
         sha256.input(self.entropy.as_slice());
@@ -168,10 +207,20 @@ impl<N: BitcoinNetwork, W: BitcoinWordlist> MnemonicExtended for BitcoinMnemonic
 
     /// Returns the extended private key of the corresponding mnemonic.
     fn to_extended_private_key(&self, password: Option<&str>) -> Result<Self::ExtendedPrivateKey, MnemonicError> {
-        Ok(Self::ExtendedPrivateKey::new_master(
-            self.to_seed(password)?.as_slice(),
-            &BitcoinFormat::P2PKH,
-        )?)
+        self.to_extended_private_key_with_progress(password, &mut |_, _| {})
+    }
+
+    /// Returns the extended private key of the corresponding mnemonic, calling the progress callback
+    /// with the number of completed and total iterations of its seed derivation.
+    fn to_extended_private_key_with_progress(
+        &self,
+        password: Option<&str>,
+        progress: &mut dyn FnMut(u32, u32),
+    ) -> Result<Self::ExtendedPrivateKey, MnemonicError> {
+        let mut seed = self.to_seed_with_progress(password, progress)?;
+        let extended_private_key = Self::ExtendedPrivateKey::new_master(seed.as_slice(), &BitcoinFormat::P2PKH);
+        seed.zeroize();
+        Ok(extended_private_key?)
     }
 
     /// Returns the extended public key of the corresponding mnemonic.
@@ -186,11 +235,112 @@ impl<N: BitcoinNetwork, W: BitcoinWordlist> BitcoinMnemonic<N, W> {
         Self::from_phrase(phrase).is_ok()
     }
 
+    /// Returns every mnemonic with a valid checksum for the given partial phrase,
+    /// where up to two unknown words are marked by "?".
+    pub fn from_partial_phrase(phrase: &str) -> Result<Vec<Self>, MnemonicError> {
+        W::validate()?;
+
+        let words = phrase.split(" ").collect::<Vec<&str>>();
+        match words.len() {
+            12 | 15 | 18 | 21 | 24 => (),
+            wc => return Err(MnemonicError::InvalidWordCount(wc as u8)),
+        };
+
+        let unknown = words
+            .iter()
+            .enumerate()
+            .filter(|(_, word)| **word == "?")
+            .map(|(position, _)| position)
+            .collect::<Vec<usize>>();
+        if unknown.is_empty() || unknown.len() > MAX_UNKNOWN_WORDS {
+            return Err(MnemonicError::InvalidUnknownWordCount(unknown.len()));
+        }
+
+        let mut indices = words
+            .iter()
+            .map(|word| match *word {
+                "?" => Ok(0),
+                word => W::get_index(word),
+            })
+            .collect::<Result<Vec<usize>, _>>()?;
+
+        let mut mnemonics = vec![];
+        for mut candidate in 0..2048usize.pow(unknown.len() as u32) {
+            for position in &unknown {
+                indices[*position] = candidate % 2048;
+                candidate /= 2048;
+            }
+            if let Some(mnemonic) = Self::from_indices(&indices) {
+                mnemonics.push(mnemonic);
+            }
+        }
+        Ok(mnemonics)
+    }
+
+    /// Returns the mnemonic for the given wordlist indices, if its checksum is valid.
+    fn from_indices(indices: &[usize]) -> Option<Self> {
+        // Pack the 11 bit indices into bytes, whose leading ENT bits are the entropy
+        let mut encoding = vec![0u8; indices.len() * 11 / 8 + 1];
+        for (i, index) in indices.iter().enumerate() {
+            for bit in 0..11 {
+                if (index >> (10 - bit)) & 1 == 1 {
+                    let position = i * 11 + bit;
+                    encoding[position / 8] |= 1 << (7 - position % 8);
+                }
+            }
+        }
+
+        let mut entropy = encoding[..indices.len() * 4 / 3].to_vec();
+        encoding.zeroize();
+
+        // The trailing ENT / 32 bits of the last index are the checksum of the entropy
+        let checksum_length = indices.len() / 3;
+        let checksum = indices[indices.len() - 1] & ((1 << checksum_length) - 1);
+
+        let mut sha256 = Sha256::new();
+        sha256.input(&entropy);
+        match (sha256.result()[0] >> (8 - checksum_length)) as usize == checksum {
+            true => Some(Self {
+                entropy,
+                _network: PhantomData,
+                _wordlist: PhantomData,
+            }),
+            false => {
+                entropy.zeroize();
+                None
+            }
+        }
+    }
+
     /// Returns a seed using the given password and mnemonic.
-    fn to_seed(&self, password: Option<&str>) -> Result<Vec<u8>, MnemonicError> {
+    pub fn to_seed(&self, password: Option<&str>) -> Result<Vec<u8>, MnemonicError> {
+        self.to_seed_with_progress(password, &mut |_, _| {})
+    }
+
+    /// Returns the seeds of the given mnemonics using the given password,
+    /// derived in parallel if the standard library is available.
+    pub fn to_seeds(mnemonics: &[Self], password: Option<&str>) -> Result<Vec<Vec<u8>>, MnemonicError> {
+        #[cfg(feature = "std")]
+        let mnemonics = mnemonics.par_iter();
+        #[cfg(not(feature = "std"))]
+        let mnemonics = mnemonics.iter();
+
+        mnemonics.map(|mnemonic| mnemonic.to_seed(password)).collect()
+    }
+
+    /// Returns a seed using the given password and mnemonic, calling the progress callback
+    /// with the number of completed and total PBKDF2 iterations.
+    pub fn to_seed_with_progress(
+        &self,
+        password: Option<&str>,
+        progress: &mut dyn FnMut(u32, u32),
+    ) -> Result<Vec<u8>, MnemonicError> {
         let mut seed = vec![0u8; PBKDF2_BYTES];
-        let salt = format!("mnemonic{}", password.unwrap_or(""));
-        pbkdf2::<Hmac<Sha512>>(&self.to_phrase()?.as_bytes(), salt.as_bytes(), PBKDF2_ROUNDS, &mut seed);
+        let mut phrase = self.to_phrase()?;
+        let mut salt = format!("mnemonic{}", password.unwrap_or(""));
+        pbkdf2_hmac_sha512(phrase.as_bytes(), salt.as_bytes(), PBKDF2_ROUNDS, &mut seed, progress);
+        phrase.zeroize();
+        salt.zeroize();
         Ok(seed)
     }
 }
@@ -216,6 +366,32 @@ impl<N: BitcoinNetwork, W: BitcoinWordlist> fmt::Display for BitcoinMnemonic<N,
     }
 }
 
+impl<N: BitcoinNetwork, W: BitcoinWordlist> fmt::Debug for BitcoinMnemonic<N, W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BitcoinMnemonic").field("entropy", &Redacted).finish()
+    }
+}
+
+impl<N: BitcoinNetwork, W: BitcoinWordlist> ExposeSecret for BitcoinMnemonic<N, W> {
+    fn fmt_secret(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BitcoinMnemonic")
+            .field("entropy", &self.entropy)
+            .finish()
+    }
+}
+
+impl<N: BitcoinNetwork, W: BitcoinWordlist> Zeroize for BitcoinMnemonic<N, W> {
+    fn zeroize(&mut self) {
+        self.entropy.zeroize();
+    }
+}
+
+impl<N: BitcoinNetwork, W: BitcoinWordlist> Drop for BitcoinMnemonic<N, W> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -248,6 +424,12 @@ mod tests {
         assert_eq!(expected_phrase, mnemonic.to_phrase().unwrap());
     }
 
+    fn test_from_entropy<N: BitcoinNetwork, W: BitcoinWordlist>(expected_phrase: &str, entropy: &[u8]) {
+        let mnemonic = BitcoinMnemonic::<N, W>::from_entropy(entropy).unwrap();
+        assert_eq!(expected_phrase, mnemonic.to_phrase().unwrap());
+        assert_eq!(entropy, &mnemonic.to_entropy()[..]);
+    }
+
     fn test_verify_phrase<N: BitcoinNetwork, W: BitcoinWordlist>(phrase: &str) {
         assert!(BitcoinMnemonic::<N, W>::verify_phrase(phrase));
     }
@@ -281,7 +463,7 @@ mod tests {
         const NO_PASSWORD_STR: &str = "5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc19a5ac40b389cd370d086206dec8aa6c43daea6690f20ad3d8d48b2d2ce9e38e4";
 
         // (entropy, phrase, seed, extended_private_key)
-        const KEYPAIRS: [(&str, &str, &str, &str); 26] = [
+        const KEYPAIRS: [(&str, &str, &str, &str); 30] = [
             (
                 "00000000000000000000000000000000",
                 "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
@@ -437,6 +619,31 @@ mod tests {
                 "gauge enforce identify truth blossom uncle tank million banner put summer adjust slender naive erode pride turtle fantasy elbow jeans bar",
                 "79da8e9aaeea7b28f9045fb0e4763fef5a7aae300b34c9f32aa8bb9a4aacd99896943beb22bbf9b50646658fd72cdf993b16a7cb5b7a77d1b443cf41f5183067",
                 "xprv9s21ZrQH143K2Cy1ePyrB2tRcm97F6YFMzDZkhy9QS6PeCDtiDuZLrtt9WBfWhXEz8W5KbSnF7nWBKFzStfs8UPeyzbrCPPbHLC25HB8aFe"
+            ),
+            // Additional vectors for 160-bit and 224-bit entropy
+            (
+                "0000000000000000000000000000000000000000",
+                "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon address",
+                "fa08713f46bf5cb48728ceb70e3aae1bc53c5cb7b4e29c5610261d1cbb7be3bed4d805256fec515754d2be35974fc5da678168e9d9bb0cb70948026923b0def3",
+                "xprv9s21ZrQH143K2nE8x21M7hF4msPN7WC7b6yCn1usVKBA9myCGie4yQCqsw479qmbjXeeRZgMjnNwvWweSCbBeUSPADo2aZbfpkTvJ8de1Qi"
+            ),
+            (
+                "ffffffffffffffffffffffffffffffffffffffff",
+                "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo wrist",
+                "bfee6f9d2bcfa1331bd6482a24abca521e5f7e769498b9a0146672194c7356e4e409be22bc379c8b64fee2aa24b54d3ec20d10a083eaa5d1d6b4b365941ad37c",
+                "xprv9s21ZrQH143K4YQcGfMEpbEoWtj4dJ8aSkUPCXEbg3kWsR8kcdcmhGK3y4swwdjUpyGBfWT9bpizzQJrznSRuFx9K4FKeoNEYT45rLY11Hc"
+            ),
+            (
+                "00000000000000000000000000000000000000000000000000000000",
+                "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon admit",
+                "e7dadc189d2e8d07ac278d9ec98a1d2d327e4a6b7df494c00cbf2cbf2d3543dac7000fc72d4ada8d9997dc8db388ff22c6d79f604a7455f2df5534a28eee04c6",
+                "xprv9s21ZrQH143K3CXWs34yKD87zsm1AVExnEEUnH8rKWqWVguXRprSz8bFFZDbKY7oai19nPTZ68HnW8xX62HFr8WxsNL4CEYmGLcTxAyKNHd"
+            ),
+            (
+                "ffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+                "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo veteran",
+                "4aa0af4ca02ef1d9fa675cd02aa06d318425564e7fadd3d51b6165cc56d77398f28d8522073cd036c2a4a24a83e919211c84500d96cb120084e613ff5fcd96c1",
+                "xprv9s21ZrQH143K4DvgrFvRv4J7m2qehW4MCdmLfRrpAJQFzgVFGi8L9QaapqJPQLyX9SAeGWATu6vXNYmo542LR8yMf4W7DMF1Xgsp96RYWDy"
             )
         ];
 
@@ -464,6 +671,145 @@ mod tests {
             })
         }
 
+        #[test]
+        fn from_partial_phrase() {
+            KEYPAIRS.iter().for_each(|(entropy_str, phrase, _, _)| {
+                let mut words = phrase.split(" ").collect::<Vec<&str>>();
+                words[1] = "?";
+                let candidates = BitcoinMnemonic::<N, W>::from_partial_phrase(&words.join(" ")).unwrap();
+                assert!(candidates
+                    .iter()
+                    .any(|candidate| hex::encode(candidate.to_entropy()) == *entropy_str));
+            })
+        }
+
+        #[test]
+        fn from_partial_phrase_last_word() {
+            KEYPAIRS.iter().for_each(|(_, phrase, _, _)| {
+                let mut words = phrase.split(" ").collect::<Vec<&str>>();
+                let length = words.len();
+                words[length - 1] = "?";
+
+                // The last word completes 11 - (ENT / 32) bits of entropy, followed by the checksum
+                let candidates = BitcoinMnemonic::<N, W>::from_partial_phrase(&words.join(" ")).unwrap();
+                assert_eq!(1 << (11 - length / 3), candidates.len());
+                assert!(candidates
+                    .iter()
+                    .any(|candidate| candidate.to_phrase().unwrap() == *phrase));
+            })
+        }
+
+        #[test]
+        fn from_partial_phrase_two_words() {
+            let (entropy_str, phrase, _, _) = KEYPAIRS[1];
+            let mut words = phrase.split(" ").collect::<Vec<&str>>();
+            words[0] = "?";
+            words[6] = "?";
+            let candidates = BitcoinMnemonic::<N, W>::from_partial_phrase(&words.join(" ")).unwrap();
+            assert!(candidates
+                .iter()
+                .any(|candidate| hex::encode(candidate.to_entropy()) == entropy_str));
+        }
+
+        #[test]
+        fn from_partial_phrase_invalid_unknown_word_count() {
+            let (_, phrase, _, _) = KEYPAIRS[0];
+            match BitcoinMnemonic::<N, W>::from_partial_phrase(phrase) {
+                Err(MnemonicError::InvalidUnknownWordCount(0)) => (),
+                other => panic!("expected an invalid unknown word count, found {:?}", other),
+            }
+
+            let mut words = phrase.split(" ").collect::<Vec<&str>>();
+            words[0] = "?";
+            words[1] = "?";
+            words[2] = "?";
+            match BitcoinMnemonic::<N, W>::from_partial_phrase(&words.join(" ")) {
+                Err(MnemonicError::InvalidUnknownWordCount(3)) => (),
+                other => panic!("expected an invalid unknown word count, found {:?}", other),
+            }
+        }
+
+        #[test]
+        fn from_entropy() {
+            KEYPAIRS.iter().for_each(|(entropy_str, phrase, _, _)| {
+                let entropy = hex::decode(entropy_str).unwrap();
+                test_from_entropy::<N, W>(phrase, &entropy);
+            })
+        }
+
+        #[test]
+        fn to_entropy_reencode() {
+            KEYPAIRS.iter().for_each(|(entropy_str, phrase, _, _)| {
+                let mnemonic = BitcoinMnemonic::<N, W>::from_phrase(phrase).unwrap();
+                let french = BitcoinMnemonic::<N, French>::from_entropy(&mnemonic.to_entropy()).unwrap();
+                let french = BitcoinMnemonic::<N, French>::from_phrase(&french.to_phrase().unwrap()).unwrap();
+                assert_eq!(*entropy_str, hex::encode(french.to_entropy()));
+            })
+        }
+
+        #[test]
+        fn seed_xor_combine() {
+            // Test vector from https://seedxor.com
+            let parts = [
+                "romance wink lottery autumn shop bring dawn tongue range crater truth ability miss spice fitness easy legal release recall obey exchange recycle dragon room",
+                "lion misery divide hurry latin fluid camp advance illegal lab pyramid unaware eager fringe sick camera series noodle toy crowd jeans select depth lounge",
+                "vault nominee cradle silk own frown throw leg cactus recall talent worry gadget surface shy planet purpose coffee drip few seven term squeeze educate",
+            ]
+            .iter()
+            .map(|phrase| BitcoinMnemonic::<N, W>::from_phrase(phrase).unwrap())
+            .collect::<Vec<_>>();
+            let expected_phrase = "silent toe meat possible chair blossom wait occur this worth option bag nurse find fish scene bench asthma bike wage world quit primary indoor";
+            let mnemonic = wagyu_model::seed_xor_combine(&parts).unwrap();
+            assert_eq!(expected_phrase, mnemonic.to_phrase().unwrap());
+        }
+
+        #[test]
+        fn seed_xor_split() {
+            let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+            KEYPAIRS.iter().for_each(|(_, phrase, _, _)| {
+                let mnemonic = BitcoinMnemonic::<N, W>::from_phrase(phrase).unwrap();
+                for count in 2..=3 {
+                    let parts = wagyu_model::seed_xor_split(rng, &mnemonic, count).unwrap();
+                    assert_eq!(count, parts.len());
+                    parts.iter().for_each(|part| {
+                        assert!(BitcoinMnemonic::<N, W>::verify_phrase(&part.to_phrase().unwrap()));
+                    });
+                    assert_eq!(mnemonic, wagyu_model::seed_xor_combine(&parts).unwrap());
+                }
+            });
+        }
+
+        #[test]
+        fn seed_qr() {
+            // Test vectors from the SeedSigner SeedQR specification
+            let vectors = [
+                (
+                    "attack pizza motion avocado network gather crop fresh patrol unusual wild holiday candy pony ranch winter theme error hybrid van cereal salon goddess expire",
+                    "011513251154012711900771041507421289190620080870026613431420201617920614089619290300152408010643",
+                    "0e74b64107f94cc0ccfae6a13dcbec3662154fec67e0e00999c07892597d190a",
+                ),
+                (
+                    "forum undo fragile fade shy sign arrest garment culture tube off merit",
+                    "073318950739065415961602009907670428187212261116",
+                    "5bbd9d71a8ec7990831aff359d426545",
+                ),
+            ];
+            vectors.iter().for_each(|(phrase, digits, compact)| {
+                let mnemonic = BitcoinMnemonic::<N, W>::from_phrase(phrase).unwrap();
+                assert_eq!(*digits, wagyu_model::seed_qr_encode(&mnemonic).unwrap());
+                assert_eq!(
+                    *compact,
+                    hex::encode(wagyu_model::compact_seed_qr_encode(&mnemonic).unwrap())
+                );
+
+                let standard = wagyu_model::seed_qr_decode::<BitcoinMnemonic<N, W>>(digits).unwrap();
+                assert_eq!(*phrase, standard.to_phrase().unwrap());
+                let compact = hex::decode(compact).unwrap();
+                let compact = wagyu_model::compact_seed_qr_decode::<BitcoinMnemonic<N, W>>(&compact).unwrap();
+                assert_eq!(*phrase, compact.to_phrase().unwrap());
+            });
+        }
+
         #[test]
         fn verify_phrase() {
             KEYPAIRS.iter().for_each(|(_, phrase, _, _)| {
@@ -496,6 +842,251 @@ mod tests {
             test_to_seed::<N, W>(NO_PASSWORD_STR, None, mnemonic);
         }
 
+        #[test]
+        fn to_seeds() {
+            let mnemonics = KEYPAIRS
+                .iter()
+                .map(|(_, phrase, _, _)| BitcoinMnemonic::<N, W>::from_phrase(phrase).unwrap())
+                .collect::<Vec<_>>();
+            let seeds = BitcoinMnemonic::to_seeds(&mnemonics, Some(PASSWORD)).unwrap();
+            assert_eq!(KEYPAIRS.len(), seeds.len());
+            KEYPAIRS.iter().zip(seeds).for_each(|((_, _, expected_seed, _), seed)| {
+                assert_eq!(*expected_seed, hex::encode(seed));
+            });
+        }
+
+        #[test]
+        fn to_seed_with_progress() {
+            let (_, phrase, expected_seed, _) = KEYPAIRS[0];
+            let mnemonic = BitcoinMnemonic::<N, W>::from_phrase(phrase).unwrap();
+            let mut iterations = vec![];
+            let seed = mnemonic
+                .to_seed_with_progress(Some(PASSWORD), &mut |completed, total| {
+                    iterations.push((completed, total))
+                })
+                .unwrap();
+            assert_eq!(expected_seed, hex::encode(seed));
+            assert_eq!(PBKDF2_ROUNDS as usize, iterations.len());
+            assert_eq!(Some(&(PBKDF2_ROUNDS, PBKDF2_ROUNDS)), iterations.last());
+        }
+
+        #[test]
+        fn to_extended_private_key() {
+            KEYPAIRS
+                .iter()
+                .for_each(|(_, phrase, _, expected_extended_private_key)| {
+                    test_to_extended_private_key::<N, W>(expected_extended_private_key, Some(PASSWORD), phrase);
+                });
+        }
+
+        #[test]
+        fn new_with_entropy() {
+            use wagyu_model::EntropySource;
+
+            /// A deterministic entropy source counting up from zero.
+            struct CountingSource(u8);
+
+            impl EntropySource for CountingSource {
+                fn fill_entropy(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+                    dest.iter_mut().for_each(|byte| {
+                        *byte = self.0;
+                        self.0 = self.0.wrapping_add(1);
+                    });
+                    Ok(())
+                }
+            }
+
+            // Each byte of the entropy is drawn from a separate 32-bit word of the source
+            let mnemonic = BitcoinMnemonic::<N, W>::new_with_count_and_entropy(&mut CountingSource(0), 12).unwrap();
+            assert_eq!("0004080c1014181c2024282c3034383c", hex::encode(mnemonic.to_entropy()));
+            assert_eq!(
+                "abandon cake adapt cage dose asthma letter lunar club like asthma journey",
+                mnemonic.to_phrase().unwrap()
+            );
+            assert_eq!(
+                "xprv9s21ZrQH143K2xFsH4RibRFUXgFme7KHUvKQxsrqYWwsQpoCek2uQ69VD21Nm5tz9i2GFMaVzfPk6vijXuK3z9LrKLxLkUaRDiJkzwSgrXs",
+                mnemonic.to_extended_private_key(None).unwrap().to_string()
+            );
+        }
+
+        #[test]
+        fn zeroize() {
+            let mut mnemonic = BitcoinMnemonic::<N, W>::from_phrase(KEYPAIRS[0].1).unwrap();
+            mnemonic.zeroize();
+            assert!(mnemonic.entropy.is_empty());
+        }
+
+        #[test]
+        fn debug() {
+            let mnemonic = BitcoinMnemonic::<N, W>::from_phrase(KEYPAIRS[0].1).unwrap();
+            assert_eq!("BitcoinMnemonic { entropy: [REDACTED] }", format!("{:?}", mnemonic));
+            assert_eq!(
+                format!("BitcoinMnemonic {{ entropy: {:?} }}", mnemonic.entropy),
+                format!("{:?}", mnemonic.expose_secret())
+            );
+        }
+    }
+
+    /// Test vectors of the official Czech wordlist, with the password of the Trezor test vectors
+    mod czech {
+        use super::*;
+
+        type N = Mainnet;
+        type W = Czech;
+
+        const PASSWORD: &str = "TREZOR";
+
+        // (entropy, phrase, seed, extended_private_key)
+        const KEYPAIRS: [(&str, &str, &str, &str); 4] = [
+            (
+                "00000000000000000000000000000000",
+                "abdikace abdikace abdikace abdikace abdikace abdikace abdikace abdikace abdikace abdikace abdikace agrese",
+                "872501bed75c98fbf943a67907bf394995f337e9adfa23687282d1135c262421715a0bcccfe2d3f5f8b72c8e2fa12a7a7267f8047b744557f4a9d49d11ccc75f",
+                "xprv9s21ZrQH143K3rnjkVvSaFkwgg1J2tnUAeqv8SCEWTWdLVZiJsjM6Z5ieeUnrR1Ws6sDb8Guqp43CXVRmPooUs2cjwefSXh3EyXx2vmzZ96"
+            ),
+            (
+                "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+                "obrazec znak uznat zubovina zeman skupina zrcadlo vzchopit obrazec znak uznat zubovina zeman skupina zrcadlo vzchopit obrazec zmije",
+                "93ff13dee31715a6568609df3f7ea295d58728a65611ea03620d2105a0efbbaa39d8b6541b3b5a57a25dbdfd5006f0c58779a7ed196e25a1a97d1442e3f080fa",
+                "xprv9s21ZrQH143K3SW9C3X4jc47HaXuDHjRvpCDxoo7PgTpMG5EZoFkDCkCzhs2yMvaUQidKKXBDChe2ns524iFEbrJFqNCPFvR1bjyRmq5rxj"
+            ),
+            (
+                "808080808080808080808080808080808080808080808080",
+                "obvinit bageta doma amputace bidlo jedle arogance butik obvinit bageta doma amputace bidlo jedle arogance butik obvinit bezinka",
+                "1843be39a115dad287e10d256d2e9bb81244cefda2b7ead8a762f53033512abc7b6db26e2ebe8053fb82e313c24bcf62ae84ba4aa2900ca0fcdcb1affc38887a",
+                "xprv9s21ZrQH143K3uYc2wzd6UfPLXqfQKsYJVyWL2HeCZ1YKhqd3jt5y4w9bfLTf4bAqvD3NbJ8ifa6vbTvHL2t87wsgi22RXd8c8Moknbzdcc"
+            ),
+            (
+                "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+                "zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zvyk zavolat",
+                "3991e1ccc78af78d58cae577b786f7c950e1f23311d0c5f6d51b884d6142a6b4fc91a227c895313bf3d35731682678653101f51546717d60438d54dead32f834",
+                "xprv9s21ZrQH143K26BAmMqZUKy3rpA3bYvGiip1tjQ2gnLwKrm9zucVU6n5PSJ9FLWeW1JjQJyZJ52QAV3HtevGNQ3BnTh91mAWgaacd6UenQ7"
+            )
+        ];
+
+        #[test]
+        fn from_phrase() {
+            KEYPAIRS.iter().for_each(|(entropy_str, phrase, _, _)| {
+                let entropy: Vec<u8> = Vec::from(hex::decode(entropy_str).unwrap());
+                test_from_phrase::<N, W>(&entropy, phrase);
+            })
+        }
+
+        #[test]
+        fn to_phrase() {
+            KEYPAIRS.iter().for_each(|(entropy_str, phrase, _, _)| {
+                let entropy: Vec<u8> = Vec::from(hex::decode(entropy_str).unwrap());
+                test_to_phrase::<N, W>(phrase, &entropy);
+            })
+        }
+
+        #[test]
+        fn from_entropy() {
+            KEYPAIRS.iter().for_each(|(entropy_str, phrase, _, _)| {
+                test_from_entropy::<N, W>(phrase, &hex::decode(entropy_str).unwrap());
+            })
+        }
+
+        #[test]
+        fn verify_phrase() {
+            KEYPAIRS.iter().for_each(|(_, phrase, _, _)| {
+                test_verify_phrase::<N, W>(phrase);
+            });
+        }
+
+        #[test]
+        fn to_seed() {
+            KEYPAIRS.iter().for_each(|(_, phrase, expected_seed, _)| {
+                let mnemonic = BitcoinMnemonic::<N, W>::from_phrase(phrase).unwrap();
+                test_to_seed::<N, W>(expected_seed, Some(PASSWORD), mnemonic);
+            });
+        }
+
+        #[test]
+        fn to_extended_private_key() {
+            KEYPAIRS
+                .iter()
+                .for_each(|(_, phrase, _, expected_extended_private_key)| {
+                    test_to_extended_private_key::<N, W>(expected_extended_private_key, Some(PASSWORD), phrase);
+                });
+        }
+    }
+
+    /// Test vectors of the official Portuguese wordlist, with the password of the Trezor test vectors
+    mod portuguese {
+        use super::*;
+
+        type N = Mainnet;
+        type W = Portuguese;
+
+        const PASSWORD: &str = "TREZOR";
+
+        // (entropy, phrase, seed, extended_private_key)
+        const KEYPAIRS: [(&str, &str, &str, &str); 4] = [
+            (
+                "00000000000000000000000000000000",
+                "abacate abacate abacate abacate abacate abacate abacate abacate abacate abacate abacate abater",
+                "ab9742b024a1e8bd241b76f8b3a157e9d442da60277bc8f36b8b23afe163de79414fb49fd1a8dd26f4ea7f0dc965c760b3b80727557bdca61e1f0b0f069952f2",
+                "xprv9s21ZrQH143K4ayH97er9xirrbGL9hEywmMfh1LdQjwtrsnrvENN7c2yKs82HypXo4GAMt4wpnna9doa1FDFYXQTSFReWDY4XAf4imN5m6s"
+            ),
+            (
+                "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+                "imitador vinheta sogro xerife veleiro pomar volumoso tratador imitador vinheta sogro xerife veleiro pomar volumoso tratador imitador viga",
+                "17040704dd985478b7d0666c7078201e3cd7d1fd1aca0d7d47c98a91ec7845500c611d987339a1d4c12bc506feb7c486eef0aa8ce679b1d184db5ca40fe8ef67",
+                "xprv9s21ZrQH143K3XAGBJxF36qFzxkFaSEsNjqKvCtT3s7WfzgMBRcv8G9K2CRGXXhLYPWKSipBrd9dyLSRuTzQWbAJkhghki7Nn13QfHqCSBq"
+            ),
+            (
+                "808080808080808080808080808080808080808080808080",
+                "inalador acirrar barulho abotoar afivelar coruja abutre amostra inalador acirrar barulho abotoar afivelar coruja abutre amostra inalador afastar",
+                "0f637bf3a487c26fb73f6a464f62ef1f6ca73a6ae083e220374c82881bf4ed2dafd874956ce368c4441e6269759c5864197e87421fbcdb7f6d63df17b4f7df81",
+                "xprv9s21ZrQH143K25VJsZdwtDCmzZ5C2LkJyNDKrHiFfHHBEmtvn77g7qrBRGHTum98TqU7sWCGFZ8vjtcPVAfgGcV51vi2BrPR66KC7wNxb3z"
+            ),
+            (
+                "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+                "zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido zumbido validade",
+                "739a6edd208d09e28fa97f8e8709aaf185e173125b1b427c04d1539173c88b78e81610a759e14f97a2038dcdc2c466a072788e3d7c88cc9bf36b96cb29510e77",
+                "xprv9s21ZrQH143K45qUnDL5tCHKGpht6amXS4d278iTXWRJXthNTufwnbnvr6rU8kd4Nb5s9aBWdYFtfcdGkg4GdL91jRhGRtP8mZbjtTvpGsy"
+            )
+        ];
+
+        #[test]
+        fn from_phrase() {
+            KEYPAIRS.iter().for_each(|(entropy_str, phrase, _, _)| {
+                let entropy: Vec<u8> = Vec::from(hex::decode(entropy_str).unwrap());
+                test_from_phrase::<N, W>(&entropy, phrase);
+            })
+        }
+
+        #[test]
+        fn to_phrase() {
+            KEYPAIRS.iter().for_each(|(entropy_str, phrase, _, _)| {
+                let entropy: Vec<u8> = Vec::from(hex::decode(entropy_str).unwrap());
+                test_to_phrase::<N, W>(phrase, &entropy);
+            })
+        }
+
+        #[test]
+        fn from_entropy() {
+            KEYPAIRS.iter().for_each(|(entropy_str, phrase, _, _)| {
+                test_from_entropy::<N, W>(phrase, &hex::decode(entropy_str).unwrap());
+            })
+        }
+
+        #[test]
+        fn verify_phrase() {
+            KEYPAIRS.iter().for_each(|(_, phrase, _, _)| {
+                test_verify_phrase::<N, W>(phrase);
+            });
+        }
+
+        #[test]
+        fn to_seed() {
+            KEYPAIRS.iter().for_each(|(_, phrase, expected_seed, _)| {
+                let mnemonic = BitcoinMnemonic::<N, W>::from_phrase(phrase).unwrap();
+                test_to_seed::<N, W>(expected_seed, Some(PASSWORD), mnemonic);
+            });
+        }
+
         #[test]
         fn to_extended_private_key() {
             KEYPAIRS
@@ -528,11 +1119,19 @@ mod tests {
         }
 
         #[test]
-        #[should_panic(expected = "InvalidWord(\"abandoz\")")]
+        #[should_panic(
+            expected = "InvalidWords([WordSuggestion { position: 1, word: \"abandoz\", suggestions: [\"abandon\"] }])"
+        )]
         fn from_phrase_invalid_word() {
             let _mnemonic = BitcoinMnemonic::<N, W>::from_phrase(INVALID_PHRASE_WORD).unwrap();
         }
 
+        #[test]
+        #[should_panic(expected = "InvalidEntropyLength(17)")]
+        fn from_entropy_invalid_length() {
+            let _mnemonic = BitcoinMnemonic::<N, W>::from_entropy(&[0u8; 17]).unwrap();
+        }
+
         #[test]
         #[should_panic(expected = "InvalidWordCount(13)")]
         fn from_phrase_invalid_length() {
@@ -0,0 +1,195 @@
+use crate::address::BitcoinAddress;
+use crate::network::BitcoinNetwork;
+use crate::public_key::BitcoinPublicKey;
+use crate::script::BitcoinScript;
+use crate::witness_script::BitcoinWitnessScript;
+use wagyu_model::no_std::*;
+use wagyu_model::AddressError;
+
+/// The maximum number of public keys of a multisig script, bounded by the 520-byte P2SH redeem script limit
+pub const MAX_MULTISIG_PUBLIC_KEYS: usize = 15;
+
+/// Represents an M-of-N Bitcoin multisig script, with its public keys sorted as defined in BIP67
+/// unless constructed with their order preserved
+/// https://github.com/bitcoin/bips/blob/master/bip-0067.mediawiki
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitcoinMultisig<N: BitcoinNetwork> {
+    /// The number of signatures required to spend
+    threshold: usize,
+    /// The compressed public keys, in lexicographic order of their serialization by default
+    public_keys: Vec<BitcoinPublicKey<N>>,
+}
+
+impl<N: BitcoinNetwork> BitcoinMultisig<N> {
+    /// Returns a multisig script requiring the threshold number of signatures of the given public keys.
+    pub fn new(threshold: usize, public_keys: &[BitcoinPublicKey<N>]) -> Result<Self, AddressError> {
+        let mut multisig = Self::with_key_order(threshold, public_keys)?;
+        multisig
+            .public_keys
+            .sort_by_key(|public_key| public_key.to_secp256k1_public_key().serialize_compressed().to_vec());
+        Ok(multisig)
+    }
+
+    /// Returns a multisig script with the public keys in the given order, as in `multi` descriptors.
+    pub fn with_key_order(threshold: usize, public_keys: &[BitcoinPublicKey<N>]) -> Result<Self, AddressError> {
+        if threshold == 0 || threshold > public_keys.len() || public_keys.len() > MAX_MULTISIG_PUBLIC_KEYS {
+            return Err(AddressError::Message(format!(
+                "invalid multisig threshold of {} for {} public keys",
+                threshold,
+                public_keys.len()
+            )));
+        }
+        if public_keys.iter().any(|public_key| !public_key.is_compressed()) {
+            return Err(AddressError::Message("multisig public keys must be compressed".into()));
+        }
+
+        Ok(Self {
+            threshold,
+            public_keys: public_keys.to_vec(),
+        })
+    }
+
+    /// Returns the number of signatures required to spend.
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Returns the public keys in script order.
+    pub fn public_keys(&self) -> &[BitcoinPublicKey<N>] {
+        &self.public_keys
+    }
+
+    /// Returns the redeem script, OP_M <public keys> OP_N OP_CHECKMULTISIG.
+    pub fn to_redeem_script(&self) -> Vec<u8> {
+        BitcoinScript::multisig(self.threshold, &self.public_keys).to_bytes()
+    }
+
+    /// Returns the witness script, which is identical to the redeem script.
+    pub fn to_witness_script(&self) -> Result<BitcoinWitnessScript, AddressError> {
+        BitcoinWitnessScript::new(&self.to_redeem_script())
+    }
+
+    /// Returns the P2SH address of the redeem script.
+    pub fn to_p2sh_address(&self) -> Result<BitcoinAddress<N>, AddressError> {
+        BitcoinAddress::<N>::p2sh(&self.to_redeem_script())
+    }
+
+    /// Returns the P2SH-P2WSH address, the P2SH address of the P2WSH scriptPubKey of the witness script.
+    pub fn to_p2sh_p2wsh_address(&self) -> Result<BitcoinAddress<N>, AddressError> {
+        BitcoinAddress::<N>::p2sh(&self.to_witness_script()?.to_script_pub_key())
+    }
+
+    /// Returns the P2WSH address of the witness script.
+    pub fn to_p2wsh_address(&self) -> Result<BitcoinAddress<N>, AddressError> {
+        self.to_witness_script()?.to_address::<N>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::*;
+    use crate::transaction::create_script_pub_key;
+
+    use core::str::FromStr;
+
+    type N = Mainnet;
+
+    // https://github.com/bitcoin/bips/blob/master/bip-0067.mediawiki#test-vectors
+    // (threshold, public_keys, redeem_script, p2sh_address)
+    const VECTORS: [(usize, &[&str], &str, &str); 2] = [
+        (
+            2,
+            &[
+                "02ff12471208c14bd580709cb2358d98975247d8765f92bc25eab3b2763ed605f8",
+                "02fe6f0a5a297eb38c391581c4413e084773ea23954d93f7753db7dc0adc188b2f",
+            ],
+            "522102fe6f0a5a297eb38c391581c4413e084773ea23954d93f7753db7dc0adc188b2f2102ff12471208c14bd580709cb2358d98975247d8765f92bc25eab3b2763ed605f852ae",
+            "39bgKC7RFbpoCRbtD5KEdkYKtNyhpsNa3Z",
+        ),
+        (
+            2,
+            &[
+                "02632b12f4ac5b1d1b72b2a3b508c19172de44f6f46bcee50ba33f3f9291e47ed0",
+                "027735a29bae7780a9755fae7a1c4374c656ac6a69ea9f3697fda61bb99a4f3e77",
+                "02e2cc6bd5f45edd43bebe7cb9b675f0ce9ed3efe613b177588290ad188d11b404",
+            ],
+            "522102632b12f4ac5b1d1b72b2a3b508c19172de44f6f46bcee50ba33f3f9291e47ed021027735a29bae7780a9755fae7a1c4374c656ac6a69ea9f3697fda61bb99a4f3e772102e2cc6bd5f45edd43bebe7cb9b675f0ce9ed3efe613b177588290ad188d11b40453ae",
+            "3CKHTjBKxCARLzwABMu9yD85kvtm7WnMfH",
+        ),
+    ];
+
+    fn to_public_keys(public_keys: &[&str]) -> Vec<BitcoinPublicKey<N>> {
+        public_keys
+            .iter()
+            .map(|public_key| BitcoinPublicKey::from_str(public_key).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn to_redeem_script() {
+        VECTORS
+            .iter()
+            .for_each(|(threshold, public_keys, redeem_script, p2sh_address)| {
+                let multisig = BitcoinMultisig::new(*threshold, &to_public_keys(public_keys)).unwrap();
+                assert_eq!(*redeem_script, hex::encode(multisig.to_redeem_script()));
+                assert_eq!(*p2sh_address, multisig.to_p2sh_address().unwrap().to_string());
+            });
+    }
+
+    #[test]
+    fn sorted() {
+        let (threshold, public_keys, redeem_script, _) = VECTORS[1];
+        let mut reversed = to_public_keys(public_keys);
+        reversed.reverse();
+        let multisig = BitcoinMultisig::new(threshold, &reversed).unwrap();
+        assert_eq!(redeem_script, hex::encode(multisig.to_redeem_script()));
+    }
+
+    #[test]
+    fn with_key_order() {
+        let (threshold, public_keys, redeem_script, _) = VECTORS[0];
+        let multisig = BitcoinMultisig::with_key_order(threshold, &to_public_keys(public_keys)).unwrap();
+        assert_ne!(redeem_script, hex::encode(multisig.to_redeem_script()));
+        assert_eq!(to_public_keys(public_keys), multisig.public_keys());
+    }
+
+    #[test]
+    fn to_segwit_address() {
+        let (threshold, public_keys, _, _) = VECTORS[1];
+        let multisig = BitcoinMultisig::new(threshold, &to_public_keys(public_keys)).unwrap();
+        let witness_script = multisig.to_witness_script().unwrap();
+
+        let p2wsh_address = multisig.to_p2wsh_address().unwrap();
+        assert_eq!(
+            witness_script.to_script_pub_key(),
+            create_script_pub_key(&p2wsh_address).unwrap()
+        );
+
+        let p2sh_p2wsh_address = multisig.to_p2sh_p2wsh_address().unwrap();
+        assert_eq!(
+            BitcoinAddress::<N>::p2sh(&witness_script.to_script_pub_key()).unwrap(),
+            p2sh_p2wsh_address
+        );
+        assert!(p2sh_p2wsh_address.to_string().starts_with('3'));
+    }
+
+    #[test]
+    fn invalid_threshold() {
+        let public_keys = to_public_keys(VECTORS[1].1);
+        assert!(BitcoinMultisig::new(0, &public_keys).is_err());
+        assert!(BitcoinMultisig::new(4, &public_keys).is_err());
+
+        let public_keys = vec![public_keys[0].clone(); MAX_MULTISIG_PUBLIC_KEYS + 1];
+        assert!(BitcoinMultisig::new(1, &public_keys).is_err());
+    }
+
+    #[test]
+    fn invalid_uncompressed() {
+        let public_key = BitcoinPublicKey::<N>::from_secp256k1_public_key(
+            to_public_keys(VECTORS[0].1)[0].to_secp256k1_public_key(),
+            false,
+        );
+        assert!(BitcoinMultisig::new(1, &[public_key]).is_err());
+    }
+}
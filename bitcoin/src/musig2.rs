@@ -0,0 +1,779 @@
+use crate::address::BitcoinAddress;
+use crate::network::BitcoinNetwork;
+use crate::private_key::BitcoinPrivateKey;
+use crate::public_key::BitcoinPublicKey;
+use wagyu_model::{
+    crypto::{secp256k1_public_key, tagged_hash},
+    no_std::*,
+    AddressError, CrateError,
+};
+
+use core::{fmt, marker::PhantomData, str::FromStr};
+use rand::Rng;
+use secp256k1::{self, curve::Scalar};
+
+/// The length of a public nonce in bytes
+const PUBLIC_NONCE_LENGTH: usize = 66;
+/// The length of a partial signature in bytes
+const PARTIAL_SIGNATURE_LENGTH: usize = 32;
+/// The length of a serialized tweak in bytes, the tweak followed by its x-only flag
+const TWEAK_LENGTH: usize = 33;
+
+/// Represents a MuSig2 key aggregation context, the ordered public keys of the signers
+/// and the tweaks applied to their aggregate public key.
+/// https://github.com/bitcoin/bips/blob/master/bip-0327.mediawiki#key-generation-and-aggregation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MuSig2KeyAggContext<N: BitcoinNetwork> {
+    /// The compressed public keys of the signers, in signing order
+    public_keys: Vec<[u8; 33]>,
+    /// The tweaks of the aggregate public key, each with its x-only flag
+    tweaks: Vec<([u8; 32], bool)>,
+    /// PhantomData
+    _network: PhantomData<N>,
+}
+
+impl<N: BitcoinNetwork> MuSig2KeyAggContext<N> {
+    /// Returns the key aggregation context of the given public keys, in the given order.
+    pub fn new(public_keys: &[BitcoinPublicKey<N>]) -> Result<Self, MuSig2Error> {
+        let context = Self {
+            public_keys: public_keys
+                .iter()
+                .map(|public_key| public_key.to_secp256k1_public_key().serialize_compressed())
+                .collect(),
+            tweaks: vec![],
+            _network: PhantomData,
+        };
+        context.to_values()?;
+        Ok(context)
+    }
+
+    /// Returns the given public keys sorted lexicographically by their compressed encoding,
+    /// which makes the aggregate public key independent of the order the keys were exchanged in.
+    pub fn sort_public_keys(public_keys: &[BitcoinPublicKey<N>]) -> Vec<BitcoinPublicKey<N>> {
+        let mut public_keys = public_keys.to_vec();
+        public_keys.sort_by_key(|public_key| public_key.to_secp256k1_public_key().serialize_compressed().to_vec());
+        public_keys
+    }
+
+    /// Returns a new context with the given tweak applied to the aggregate public key.
+    /// An x-only tweak is applied to the even-y lift of the aggregate public key, as in BIP341.
+    pub fn apply_tweak(&self, tweak: &[u8; 32], is_x_only: bool) -> Result<Self, MuSig2Error> {
+        let mut context = self.clone();
+        context.tweaks.push((*tweak, is_x_only));
+        context.to_values()?;
+        Ok(context)
+    }
+
+    /// Returns a new context tweaked to the Taproot output key of the aggregate public key,
+    /// without a script tree, for signing a key path spend.
+    pub fn apply_taproot_tweak(&self) -> Result<Self, MuSig2Error> {
+        self.apply_tweak(&tagged_hash(b"TapTweak", &self.to_x_only_public_key()), true)
+    }
+
+    /// Returns the aggregate public key, including any applied tweaks.
+    pub fn to_public_key(&self) -> BitcoinPublicKey<N> {
+        // The context is validated on construction, so its aggregate public key exists.
+        let (aggregate_public_key, _, _) = self.to_values().expect("valid key aggregation context");
+        BitcoinPublicKey::from_secp256k1_public_key(aggregate_public_key, true)
+    }
+
+    /// Returns the BIP340 x-only aggregate public key, which verifies the aggregate signatures.
+    pub fn to_x_only_public_key(&self) -> [u8; 32] {
+        self.to_public_key().to_x_only_public_key()
+    }
+
+    /// Returns the P2TR address whose internal key is the aggregate public key.
+    pub fn to_address(&self) -> Result<BitcoinAddress<N>, AddressError> {
+        BitcoinAddress::p2tr(&self.to_public_key())
+    }
+
+    /// Returns the aggregate public key, the accumulated sign and the accumulated tweak
+    /// of the context.
+    fn to_values(&self) -> Result<(secp256k1::PublicKey, Scalar, Scalar), MuSig2Error> {
+        if self.public_keys.is_empty() {
+            return Err(MuSig2Error::MissingPublicKeys);
+        }
+
+        let mut points = vec![];
+        for public_key in &self.public_keys {
+            let point = secp256k1::PublicKey::parse_compressed(public_key)?;
+            points.push(mul(&point, &self.to_coefficient(public_key)));
+        }
+        let mut aggregate_public_key = add(&points).ok_or(MuSig2Error::InvalidAggregatePublicKey)?;
+
+        let mut sign = Scalar::from_int(1);
+        let mut accumulated_tweak = Scalar::default();
+        for (tweak, is_x_only) in &self.tweaks {
+            let tweak = to_scalar(tweak).ok_or(MuSig2Error::InvalidTweak)?;
+
+            // Q' = gQ + tG, where g negates an odd Q under an x-only tweak.
+            let mut g = Scalar::from_int(1);
+            if *is_x_only && !has_even_y(&aggregate_public_key) {
+                g = -g;
+            }
+            aggregate_public_key =
+                add(&[mul(&aggregate_public_key, &g), mul_base(&tweak)]).ok_or(MuSig2Error::InvalidTweak)?;
+            sign = g.clone() * sign;
+            accumulated_tweak = tweak + g * accumulated_tweak;
+        }
+
+        Ok((aggregate_public_key, sign, accumulated_tweak))
+    }
+
+    /// Returns the key aggregation coefficient of the given public key.
+    fn to_coefficient(&self, public_key: &[u8; 33]) -> Scalar {
+        // The first key that differs from the first public key has a coefficient of 1.
+        let second_public_key = self.public_keys.iter().find(|key| **key != self.public_keys[0]);
+        if Some(public_key) == second_public_key {
+            return Scalar::from_int(1);
+        }
+
+        let list_hash = tagged_hash(b"KeyAgg list", &self.public_keys.concat());
+        hash_to_scalar(b"KeyAgg coefficient", &[&list_hash[..], &public_key[..]].concat())
+    }
+}
+
+/// Represents a MuSig2 secret nonce, which must be used for exactly one partial signature.
+/// Signing consumes the secret nonce, and it is cleared from memory when dropped.
+#[derive(Debug)]
+pub struct MuSig2SecretNonce {
+    /// The two secret nonce scalars
+    k: (Scalar, Scalar),
+    /// The compressed public key of the signer
+    public_key: [u8; 33],
+}
+
+impl MuSig2SecretNonce {
+    /// Returns a new secret nonce and its public nonce for the signer of the private key,
+    /// bound to the aggregate public key of the context and, if known, the message.
+    /// https://github.com/bitcoin/bips/blob/master/bip-0327.mediawiki#nonce-generation
+    pub fn new<N: BitcoinNetwork, R: Rng>(
+        rng: &mut R,
+        private_key: &BitcoinPrivateKey<N>,
+        context: &MuSig2KeyAggContext<N>,
+        message: Option<&[u8]>,
+    ) -> Result<(Self, MuSig2PublicNonce), MuSig2Error> {
+        let secret_key = private_key.to_secp256k1_secret_key();
+        let public_key = secp256k1_public_key(&secret_key).serialize_compressed();
+        let aggregate_public_key = context.to_x_only_public_key();
+
+        let mut random = tagged_hash(b"MuSig/aux", &rng.gen::<[u8; 32]>());
+        random
+            .iter_mut()
+            .zip(secret_key.serialize().iter())
+            .for_each(|(random, secret)| *random ^= secret);
+
+        let message_prefixed = match message {
+            Some(message) => [&[1u8][..], &(message.len() as u64).to_be_bytes(), message].concat(),
+            None => vec![0u8],
+        };
+
+        let to_nonce = |index: u8| {
+            hash_to_scalar(
+                b"MuSig/nonce",
+                &[
+                    &random[..],
+                    &[public_key.len() as u8],
+                    &public_key,
+                    &[aggregate_public_key.len() as u8],
+                    &aggregate_public_key,
+                    &message_prefixed,
+                    &0u32.to_be_bytes(),
+                    &[index],
+                ]
+                .concat(),
+            )
+        };
+        let k = (to_nonce(0), to_nonce(1));
+
+        let mut public_nonce = [0u8; PUBLIC_NONCE_LENGTH];
+        for (i, k) in [&k.0, &k.1].iter().enumerate() {
+            let point = mul_base(k).ok_or(MuSig2Error::InvalidNonce)?;
+            public_nonce[i * 33..(i + 1) * 33].copy_from_slice(&point.serialize_compressed());
+        }
+
+        Ok((Self { k, public_key }, MuSig2PublicNonce(public_nonce)))
+    }
+}
+
+/// Represents a MuSig2 public nonce, the two nonce points a signer shares before signing
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MuSig2PublicNonce([u8; PUBLIC_NONCE_LENGTH]);
+
+impl MuSig2PublicNonce {
+    /// Returns the two nonce points of the public nonce.
+    fn to_points(self) -> Result<(secp256k1::PublicKey, secp256k1::PublicKey), MuSig2Error> {
+        Ok((
+            secp256k1::PublicKey::parse_slice(&self.0[..33], None)?,
+            secp256k1::PublicKey::parse_slice(&self.0[33..], None)?,
+        ))
+    }
+}
+
+impl FromStr for MuSig2PublicNonce {
+    type Err = MuSig2Error;
+
+    fn from_str(public_nonce: &str) -> Result<Self, Self::Err> {
+        let mut bytes = [0u8; PUBLIC_NONCE_LENGTH];
+        decode_hex(public_nonce, &mut bytes)?;
+        Self(bytes).to_points()?;
+        Ok(Self(bytes))
+    }
+}
+
+impl fmt::Display for MuSig2PublicNonce {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", hex::encode(&self.0[..]))
+    }
+}
+
+/// Represents a MuSig2 aggregate nonce, the sum of the public nonces of all signers.
+/// Either of its points may be the point at infinity, encoded as 33 zero bytes.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MuSig2AggregateNonce([u8; PUBLIC_NONCE_LENGTH]);
+
+impl MuSig2AggregateNonce {
+    /// Returns the aggregate nonce of the public nonces of all signers.
+    pub fn new(public_nonces: &[MuSig2PublicNonce]) -> Result<Self, MuSig2Error> {
+        let mut first_points = vec![];
+        let mut second_points = vec![];
+        for public_nonce in public_nonces {
+            let (first_point, second_point) = public_nonce.to_points()?;
+            first_points.push(Some(first_point));
+            second_points.push(Some(second_point));
+        }
+
+        let mut aggregate_nonce = [0u8; PUBLIC_NONCE_LENGTH];
+        for (i, points) in [first_points, second_points].iter().enumerate() {
+            if let Some(point) = add(points) {
+                aggregate_nonce[i * 33..(i + 1) * 33].copy_from_slice(&point.serialize_compressed());
+            }
+        }
+        Ok(Self(aggregate_nonce))
+    }
+
+    /// Returns the two nonce points of the aggregate nonce, where `None` is the point at infinity.
+    fn to_points(self) -> Result<(Option<secp256k1::PublicKey>, Option<secp256k1::PublicKey>), MuSig2Error> {
+        let to_point = |bytes: &[u8]| -> Result<_, MuSig2Error> {
+            match bytes.iter().all(|byte| *byte == 0) {
+                true => Ok(None),
+                false => Ok(Some(secp256k1::PublicKey::parse_slice(bytes, None)?)),
+            }
+        };
+        Ok((to_point(&self.0[..33])?, to_point(&self.0[33..])?))
+    }
+}
+
+impl FromStr for MuSig2AggregateNonce {
+    type Err = MuSig2Error;
+
+    fn from_str(aggregate_nonce: &str) -> Result<Self, Self::Err> {
+        let mut bytes = [0u8; PUBLIC_NONCE_LENGTH];
+        decode_hex(aggregate_nonce, &mut bytes)?;
+        Self(bytes).to_points()?;
+        Ok(Self(bytes))
+    }
+}
+
+impl fmt::Display for MuSig2AggregateNonce {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", hex::encode(&self.0[..]))
+    }
+}
+
+/// Represents a MuSig2 partial signature of a single signer
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MuSig2PartialSignature([u8; PARTIAL_SIGNATURE_LENGTH]);
+
+impl FromStr for MuSig2PartialSignature {
+    type Err = MuSig2Error;
+
+    fn from_str(partial_signature: &str) -> Result<Self, Self::Err> {
+        let mut bytes = [0u8; PARTIAL_SIGNATURE_LENGTH];
+        decode_hex(partial_signature, &mut bytes)?;
+        Ok(Self(bytes))
+    }
+}
+
+impl fmt::Display for MuSig2PartialSignature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", hex::encode(&self.0[..]))
+    }
+}
+
+/// Represents a MuSig2 signing session, the key aggregation context, aggregate nonce and message
+/// shared by all signers. The session serializes to hex, so it can be carried to air-gapped
+/// signers and back between the nonce and signing rounds.
+/// https://github.com/bitcoin/bips/blob/master/bip-0327.mediawiki#signing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MuSig2Session<N: BitcoinNetwork> {
+    /// The key aggregation context of the signers
+    context: MuSig2KeyAggContext<N>,
+    /// The aggregate nonce of the signers
+    aggregate_nonce: MuSig2AggregateNonce,
+    /// The message to sign
+    message: Vec<u8>,
+}
+
+/// The values derived from a session, shared by signing, verification and aggregation
+struct SessionValues {
+    /// The aggregate public key
+    aggregate_public_key: secp256k1::PublicKey,
+    /// The accumulated sign of the tweaks
+    sign: Scalar,
+    /// The accumulated tweak
+    tweak: Scalar,
+    /// The nonce coefficient
+    b: Scalar,
+    /// The final nonce point
+    r: secp256k1::PublicKey,
+    /// The BIP340 challenge
+    e: Scalar,
+}
+
+impl<N: BitcoinNetwork> MuSig2Session<N> {
+    /// Returns a new signing session of the message for the given context and aggregate nonce.
+    pub fn new(context: &MuSig2KeyAggContext<N>, aggregate_nonce: &MuSig2AggregateNonce, message: &[u8]) -> Self {
+        Self {
+            context: context.clone(),
+            aggregate_nonce: *aggregate_nonce,
+            message: message.to_vec(),
+        }
+    }
+
+    /// Returns the key aggregation context of the session.
+    pub fn context(&self) -> &MuSig2KeyAggContext<N> {
+        &self.context
+    }
+
+    /// Returns the message of the session.
+    pub fn message(&self) -> &[u8] {
+        &self.message
+    }
+
+    /// Returns the partial signature of the signer of the private key, consuming its secret nonce.
+    pub fn sign(
+        &self,
+        secret_nonce: MuSig2SecretNonce,
+        private_key: &BitcoinPrivateKey<N>,
+    ) -> Result<MuSig2PartialSignature, MuSig2Error> {
+        let values = self.to_values()?;
+
+        let secret_key = private_key.to_secp256k1_secret_key();
+        let public_key = secp256k1_public_key(&secret_key).serialize_compressed();
+        if public_key != secret_nonce.public_key || !self.context.public_keys.contains(&public_key) {
+            return Err(MuSig2Error::InvalidSigner(hex::encode(&public_key[..])));
+        }
+
+        // Negate the nonces if the final nonce point has an odd y-coordinate.
+        let (mut k1, mut k2) = (secret_nonce.k.0.clone(), secret_nonce.k.1.clone());
+        if !has_even_y(&values.r) {
+            k1 = -k1;
+            k2 = -k2;
+        }
+
+        // Negate the secret key if the aggregate public key has an odd y-coordinate.
+        let mut d: Scalar = secret_key.into();
+        d = values.sign.clone() * d;
+        if !has_even_y(&values.aggregate_public_key) {
+            d = -d;
+        }
+
+        let s = k1 + values.b * k2 + values.e * self.context.to_coefficient(&public_key) * d;
+        Ok(MuSig2PartialSignature(s.b32()))
+    }
+
+    /// Returns `true` if the partial signature is valid for the public nonce and public key of a signer.
+    pub fn verify_partial_signature(
+        &self,
+        partial_signature: &MuSig2PartialSignature,
+        public_nonce: &MuSig2PublicNonce,
+        public_key: &BitcoinPublicKey<N>,
+    ) -> bool {
+        let (values, (r1, r2), s) = match (
+            self.to_values(),
+            public_nonce.to_points(),
+            to_scalar(&partial_signature.0),
+        ) {
+            (Ok(values), Ok(points), Some(s)) => (values, points, s),
+            _ => return false,
+        };
+
+        let point = public_key.to_secp256k1_public_key();
+        let public_key = point.serialize_compressed();
+        if !self.context.public_keys.contains(&public_key) {
+            return false;
+        }
+
+        // The effective nonce of the signer, negated if the final nonce point has an odd y-coordinate.
+        let mut nonce = add(&[Some(r1), mul(&r2, &values.b)]);
+        if !has_even_y(&values.r) {
+            nonce = nonce.as_ref().and_then(|nonce| mul(nonce, &-Scalar::from_int(1)));
+        }
+
+        let mut g = values.sign;
+        if !has_even_y(&values.aggregate_public_key) {
+            g = -g;
+        }
+
+        // sG = R + e * a * g * P
+        let expected = add(&[
+            nonce,
+            mul(&point, &(values.e * self.context.to_coefficient(&public_key) * g)),
+        ]);
+        match (mul_base(&s), expected) {
+            (Some(lhs), Some(rhs)) => lhs.serialize_compressed() == rhs.serialize_compressed(),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+
+    /// Returns the BIP340 Schnorr signature of the message for the x-only aggregate public key
+    /// from the partial signatures of all signers.
+    pub fn aggregate(&self, partial_signatures: &[MuSig2PartialSignature]) -> Result<[u8; 64], MuSig2Error> {
+        let values = self.to_values()?;
+
+        let mut s = Scalar::default();
+        for (i, partial_signature) in partial_signatures.iter().enumerate() {
+            s += to_scalar(&partial_signature.0).ok_or(MuSig2Error::InvalidPartialSignature(i))?;
+        }
+
+        let mut g = Scalar::from_int(1);
+        if !has_even_y(&values.aggregate_public_key) {
+            g = -g;
+        }
+        s += values.e * g * values.tweak;
+
+        let mut signature = [0u8; 64];
+        signature[..32].copy_from_slice(&values.r.serialize_compressed()[1..]);
+        signature[32..].copy_from_slice(&s.b32());
+        Ok(signature)
+    }
+
+    /// Returns the values derived from the session.
+    fn to_values(&self) -> Result<SessionValues, MuSig2Error> {
+        let (aggregate_public_key, sign, tweak) = self.context.to_values()?;
+        let x_only_public_key = &aggregate_public_key.serialize_compressed()[1..];
+
+        let b = hash_to_scalar(
+            b"MuSig/noncecoef",
+            &[&self.aggregate_nonce.0[..], x_only_public_key, &self.message].concat(),
+        );
+
+        // R = R1 + bR2, or the generator if R is the point at infinity.
+        let (r1, r2) = self.aggregate_nonce.to_points()?;
+        let r = match add(&[r1, r2.and_then(|r2| mul(&r2, &b))]) {
+            Some(r) => r,
+            None => mul_base(&Scalar::from_int(1)).ok_or(MuSig2Error::InvalidNonce)?,
+        };
+
+        let e = hash_to_scalar(
+            b"BIP0340/challenge",
+            &[&r.serialize_compressed()[1..], x_only_public_key, &self.message].concat(),
+        );
+
+        Ok(SessionValues {
+            aggregate_public_key,
+            sign,
+            tweak,
+            b,
+            r,
+            e,
+        })
+    }
+}
+
+impl<N: BitcoinNetwork> FromStr for MuSig2Session<N> {
+    type Err = MuSig2Error;
+
+    /// Returns a session from its hex serialization, the number of public keys, the public keys,
+    /// the number of tweaks, the tweaks, the aggregate nonce and the message.
+    fn from_str(session: &str) -> Result<Self, Self::Err> {
+        let bytes = hex::decode(session).map_err(CrateError::from)?;
+        let mut offset = 0;
+        let mut read = |length: usize| match bytes.get(offset..offset + length) {
+            Some(slice) => {
+                offset += length;
+                Ok(slice)
+            }
+            None => Err(MuSig2Error::InvalidByteLength(bytes.len())),
+        };
+
+        let mut public_keys = vec![];
+        for _ in 0..read(1)?[0] {
+            let mut public_key = [0u8; 33];
+            public_key.copy_from_slice(read(33)?);
+            public_keys.push(public_key);
+        }
+
+        let mut tweaks = vec![];
+        for _ in 0..read(1)?[0] {
+            let tweak = read(TWEAK_LENGTH)?;
+            let mut bytes = [0u8; 32];
+            bytes.copy_from_slice(&tweak[..32]);
+            match tweak[32] {
+                0 | 1 => tweaks.push((bytes, tweak[32] == 1)),
+                flag => return Err(MuSig2Error::InvalidTweakFlag(flag)),
+            }
+        }
+
+        let mut aggregate_nonce = [0u8; PUBLIC_NONCE_LENGTH];
+        aggregate_nonce.copy_from_slice(read(PUBLIC_NONCE_LENGTH)?);
+        let aggregate_nonce = MuSig2AggregateNonce(aggregate_nonce);
+        aggregate_nonce.to_points()?;
+
+        let context = MuSig2KeyAggContext {
+            public_keys,
+            tweaks,
+            _network: PhantomData,
+        };
+        context.to_values()?;
+
+        Ok(Self {
+            context,
+            aggregate_nonce,
+            message: bytes[offset..].to_vec(),
+        })
+    }
+}
+
+impl<N: BitcoinNetwork> fmt::Display for MuSig2Session<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut bytes = vec![self.context.public_keys.len() as u8];
+        self.context
+            .public_keys
+            .iter()
+            .for_each(|public_key| bytes.extend_from_slice(public_key));
+        bytes.push(self.context.tweaks.len() as u8);
+        self.context.tweaks.iter().for_each(|(tweak, is_x_only)| {
+            bytes.extend_from_slice(tweak);
+            bytes.push(*is_x_only as u8);
+        });
+        bytes.extend_from_slice(&self.aggregate_nonce.0);
+        bytes.extend_from_slice(&self.message);
+        write!(f, "{}", hex::encode(bytes))
+    }
+}
+
+/// Returns the scalar of the given bytes, or `None` if it is not less than the curve order.
+fn to_scalar(bytes: &[u8; 32]) -> Option<Scalar> {
+    let mut scalar = Scalar::default();
+    match bool::from(scalar.set_b32(bytes)) {
+        true => None,
+        false => Some(scalar),
+    }
+}
+
+/// Returns the tagged hash of the message as a scalar, reduced modulo the curve order.
+fn hash_to_scalar(tag: &[u8], message: &[u8]) -> Scalar {
+    let mut scalar = Scalar::default();
+    let _ = scalar.set_b32(&tagged_hash(tag, message));
+    scalar
+}
+
+/// Decodes the given hex string into the fixed-length byte array.
+fn decode_hex(hex_string: &str, array: &mut [u8]) -> Result<(), MuSig2Error> {
+    let bytes = hex::decode(hex_string).map_err(CrateError::from)?;
+    if bytes.len() != array.len() {
+        return Err(MuSig2Error::InvalidByteLength(bytes.len()));
+    }
+    array.copy_from_slice(&bytes);
+    Ok(())
+}
+
+/// Returns `true` if the point has an even y-coordinate.
+fn has_even_y(point: &secp256k1::PublicKey) -> bool {
+    point.serialize_compressed()[0] == secp256k1::util::TAG_PUBKEY_EVEN
+}
+
+/// Returns the point aG, or `None` if it is the point at infinity.
+fn mul_base(a: &Scalar) -> Option<secp256k1::PublicKey> {
+    let a = secp256k1::SecretKey::parse(&a.b32()).ok()?;
+    Some(secp256k1_public_key(&a))
+}
+
+/// Returns the point aP, or `None` if it is the point at infinity.
+fn mul(point: &secp256k1::PublicKey, a: &Scalar) -> Option<secp256k1::PublicKey> {
+    let a = secp256k1::SecretKey::parse(&a.b32()).ok()?;
+    let mut point = point.clone();
+    point.tweak_mul_assign(&a).ok()?;
+    Some(point)
+}
+
+/// Returns the sum of the points, where `None` is the point at infinity.
+fn add(points: &[Option<secp256k1::PublicKey>]) -> Option<secp256k1::PublicKey> {
+    let points: Vec<_> = points.iter().flatten().cloned().collect();
+    match points.is_empty() {
+        true => None,
+        false => secp256k1::PublicKey::combine(&points).ok(),
+    }
+}
+
+#[derive(Debug, Fail)]
+pub enum MuSig2Error {
+    #[fail(display = "{}", _0)]
+    Crate(#[cause] CrateError),
+
+    #[fail(display = "invalid aggregate public key")]
+    InvalidAggregatePublicKey,
+
+    #[fail(display = "invalid byte length: {}", _0)]
+    InvalidByteLength(usize),
+
+    #[fail(display = "invalid nonce")]
+    InvalidNonce,
+
+    #[fail(display = "invalid partial signature at index {}", _0)]
+    InvalidPartialSignature(usize),
+
+    #[fail(display = "invalid signer: {}", _0)]
+    InvalidSigner(String),
+
+    #[fail(display = "invalid tweak")]
+    InvalidTweak,
+
+    #[fail(display = "invalid tweak flag: {}", _0)]
+    InvalidTweakFlag(u8),
+
+    #[fail(display = "missing public keys")]
+    MissingPublicKeys,
+}
+
+impl From<CrateError> for MuSig2Error {
+    fn from(error: CrateError) -> Self {
+        MuSig2Error::Crate(error)
+    }
+}
+
+impl From<secp256k1::Error> for MuSig2Error {
+    fn from(error: secp256k1::Error) -> Self {
+        MuSig2Error::Crate(CrateError::from(error))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::*;
+    use wagyu_model::PrivateKey;
+
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    type N = Mainnet;
+
+    fn public_key(public_key: &str) -> BitcoinPublicKey<N> {
+        BitcoinPublicKey::from_str(public_key).unwrap()
+    }
+
+    #[test]
+    fn key_aggregation() {
+        // https://github.com/bitcoin/bips/blob/master/bip-0327/vectors/key_agg_vectors.json
+        let x1 = public_key("02f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9");
+        let x2 = public_key("03dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659");
+        let x3 = public_key("023590a94e768f8e1815c2f24b4d80a8e3149316c3518ce7b7ad338368d038ca66");
+
+        // (public_keys, x_only_aggregate_public_key)
+        let vectors = [
+            (
+                vec![x1.clone(), x2.clone(), x3.clone()],
+                "90539eede565f5d054f32cc0c220126889ed1e5d193baf15aef344fe59d4610c",
+            ),
+            (
+                vec![x3.clone(), x2.clone(), x1.clone()],
+                "6204de8b083426dc6eaf9502d27024d53fc826bf7d2012148a0575435df54b2b",
+            ),
+            (
+                vec![x1.clone(), x1.clone(), x1.clone()],
+                "b436e3bad62b8cd409969a224731c193d051162d8c5ae8b109306127da3aa935",
+            ),
+            (
+                vec![x1.clone(), x1.clone(), x2.clone(), x2.clone()],
+                "69bc22bfa5d106306e48a20679de1d7389386124d07571d0d872686028c26a3e",
+            ),
+        ];
+
+        vectors.iter().for_each(|(public_keys, expected)| {
+            let context = MuSig2KeyAggContext::new(public_keys).unwrap();
+            assert_eq!(*expected, hex::encode(context.to_x_only_public_key()));
+        });
+
+        assert_eq!(
+            vec![x3.clone(), x1.clone(), x2.clone()],
+            MuSig2KeyAggContext::sort_public_keys(&[x1, x2, x3])
+        );
+        assert!(MuSig2KeyAggContext::<N>::new(&[]).is_err());
+    }
+
+    #[test]
+    fn sign_and_aggregate() {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        let message = b"MuSig2 key path spend";
+
+        let private_keys: Vec<_> = (0..3).map(|_| BitcoinPrivateKey::<N>::new(rng).unwrap()).collect();
+        let public_keys: Vec<_> = private_keys.iter().map(|key| key.to_public_key()).collect();
+        let untweaked = MuSig2KeyAggContext::new(&MuSig2KeyAggContext::sort_public_keys(&public_keys)).unwrap();
+
+        for context in [untweaked.clone(), untweaked.apply_taproot_tweak().unwrap()].iter() {
+            let (secret_nonces, public_nonces): (Vec<_>, Vec<_>) = private_keys
+                .iter()
+                .map(|private_key| MuSig2SecretNonce::new(rng, private_key, context, Some(message)).unwrap())
+                .unzip();
+            let aggregate_nonce = MuSig2AggregateNonce::new(&public_nonces).unwrap();
+
+            // Each signer receives the session serialized.
+            let session = MuSig2Session::new(context, &aggregate_nonce, message).to_string();
+            let session = MuSig2Session::<N>::from_str(&session).unwrap();
+            assert_eq!(context, session.context());
+
+            let partial_signatures: Vec<_> = secret_nonces
+                .into_iter()
+                .zip(private_keys.iter())
+                .map(|(secret_nonce, private_key)| session.sign(secret_nonce, private_key).unwrap())
+                .collect();
+
+            for i in 0..3 {
+                let partial_signature = MuSig2PartialSignature::from_str(&partial_signatures[i].to_string()).unwrap();
+                assert!(session.verify_partial_signature(&partial_signature, &public_nonces[i], &public_keys[i]));
+                assert!(!session.verify_partial_signature(
+                    &partial_signature,
+                    &public_nonces[(i + 1) % 3],
+                    &public_keys[i]
+                ));
+            }
+
+            let signature = session.aggregate(&partial_signatures).unwrap();
+            let aggregate_public_key = BitcoinPublicKey::<N>::from_x_only_public_key(&context.to_x_only_public_key());
+            assert!(aggregate_public_key.unwrap().verify_schnorr(message, &signature));
+        }
+
+        // The Taproot tweaked key is the output key of the P2TR address of the aggregate public key.
+        let output_key = untweaked.apply_taproot_tweak().unwrap().to_x_only_public_key();
+        assert_eq!(output_key, untweaked.to_public_key().to_taproot_output_key().unwrap());
+        assert!(untweaked.to_address().unwrap().to_string().starts_with("bc1p"));
+    }
+
+    #[test]
+    fn invalid_signer() {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        let private_keys: Vec<_> = (0..3).map(|_| BitcoinPrivateKey::<N>::new(rng).unwrap()).collect();
+        let public_keys: Vec<_> = private_keys[..2].iter().map(|key| key.to_public_key()).collect();
+        let context = MuSig2KeyAggContext::new(&public_keys).unwrap();
+
+        let (secret_nonce, public_nonce) = MuSig2SecretNonce::new(rng, &private_keys[2], &context, None).unwrap();
+        let aggregate_nonce = MuSig2AggregateNonce::new(&[public_nonce]).unwrap();
+        let session = MuSig2Session::new(&context, &aggregate_nonce, b"message");
+        assert!(session.sign(secret_nonce, &private_keys[2]).is_err());
+
+        let (secret_nonce, _) = MuSig2SecretNonce::new(rng, &private_keys[0], &context, None).unwrap();
+        assert!(session.sign(secret_nonce, &private_keys[1]).is_err());
+    }
+
+    #[test]
+    fn invalid_session() {
+        assert!(MuSig2Session::<N>::from_str("").is_err());
+        assert!(MuSig2Session::<N>::from_str("00").is_err());
+        assert!(MuSig2PublicNonce::from_str("02f9308a").is_err());
+        assert!(MuSig2PartialSignature::from_str(&"ff".repeat(33)).is_err());
+    }
+}
@@ -0,0 +1,207 @@
+use crate::format::BitcoinFormat;
+use crate::network::BitcoinNetwork;
+use wagyu_model::no_std::*;
+use wagyu_model::{
+    AddressError, ChildIndex, ExtendedPrivateKeyError, ExtendedPublicKeyError, Network, NetworkError, PrivateKeyError,
+};
+
+use core::{fmt, fmt::Debug, hash::Hash, marker::PhantomData, str::FromStr};
+
+/// The parameters of a user-defined Bitcoin-like network.
+///
+/// Extended keys of a custom network share a single pair of version bytes across formats,
+/// so they deserialize with the P2PKH format.
+pub trait CustomNetworkParameters: Copy + Clone + Debug + Send + Sync + 'static + Eq + Ord + Sized + Hash {
+    /// The name of the network
+    const NAME: &'static str;
+    /// The SLIP-44 coin type of the network
+    const HD_COIN_TYPE: ChildIndex;
+    /// The version byte of P2PKH addresses
+    const P2PKH_PREFIX: u8;
+    /// The version byte of P2SH addresses
+    const P2SH_PREFIX: u8;
+    /// The human-readable part of Bech32 addresses
+    const BECH32_HRP: &'static str;
+    /// The version byte of WIF private keys
+    const WIF_PREFIX: u8;
+    /// The version bytes of extended private keys
+    const EXTENDED_PRIVATE_KEY_VERSION: [u8; 4];
+    /// The version bytes of extended public keys
+    const EXTENDED_PUBLIC_KEY_VERSION: [u8; 4];
+}
+
+/// Represents a Bitcoin-like network with user-defined parameters
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CustomNetwork<P: CustomNetworkParameters>(PhantomData<P>);
+
+impl<P: CustomNetworkParameters> Network for CustomNetwork<P> {
+    const NAME: &'static str = P::NAME;
+}
+
+impl<P: CustomNetworkParameters> BitcoinNetwork for CustomNetwork<P> {
+    const HD_COIN_TYPE: ChildIndex = P::HD_COIN_TYPE;
+
+    /// Returns the address prefix of the given network.
+    fn to_address_prefix(format: &BitcoinFormat) -> Vec<u8> {
+        match format {
+            BitcoinFormat::P2PKH => vec![P::P2PKH_PREFIX],
+            BitcoinFormat::P2WSH => vec![0x00],
+            BitcoinFormat::P2SH_P2WPKH => vec![P::P2SH_PREFIX],
+            BitcoinFormat::Bech32 | BitcoinFormat::P2TR | BitcoinFormat::FutureWitness => {
+                P::BECH32_HRP.as_bytes().to_vec()
+            }
+        }
+    }
+
+    /// Returns the network of the given address prefix.
+    fn from_address_prefix(prefix: &[u8]) -> Result<Self, AddressError> {
+        match prefix {
+            [version, ..] if *version == P::P2PKH_PREFIX || *version == P::P2SH_PREFIX => Ok(Self(PhantomData)),
+            hrp if hrp.eq_ignore_ascii_case(P::BECH32_HRP.as_bytes()) => Ok(Self(PhantomData)),
+            _ => Err(AddressError::InvalidPrefix(prefix.to_owned())),
+        }
+    }
+
+    /// Returns the wif prefix of the given network.
+    fn to_private_key_prefix() -> u8 {
+        P::WIF_PREFIX
+    }
+
+    /// Returns the network of the given wif prefix.
+    fn from_private_key_prefix(prefix: u8) -> Result<Self, PrivateKeyError> {
+        match prefix == P::WIF_PREFIX {
+            true => Ok(Self(PhantomData)),
+            false => Err(PrivateKeyError::InvalidPrefix(vec![prefix])),
+        }
+    }
+
+    /// Returns the extended private key version bytes of the given network.
+    fn to_extended_private_key_version_bytes(_: &BitcoinFormat) -> Result<Vec<u8>, ExtendedPrivateKeyError> {
+        Ok(P::EXTENDED_PRIVATE_KEY_VERSION.to_vec())
+    }
+
+    /// Returns the network of the given extended private key version bytes.
+    fn from_extended_private_key_version_bytes(prefix: &[u8]) -> Result<Self, ExtendedPrivateKeyError> {
+        match prefix[0..4] == P::EXTENDED_PRIVATE_KEY_VERSION {
+            true => Ok(Self(PhantomData)),
+            false => Err(ExtendedPrivateKeyError::InvalidVersionBytes(prefix.to_vec())),
+        }
+    }
+
+    /// Returns the extended public key version bytes of the given network.
+    fn to_extended_public_key_version_bytes(_: &BitcoinFormat) -> Result<Vec<u8>, ExtendedPublicKeyError> {
+        Ok(P::EXTENDED_PUBLIC_KEY_VERSION.to_vec())
+    }
+
+    /// Returns the network of the given extended public key version bytes.
+    fn from_extended_public_key_version_bytes(prefix: &[u8]) -> Result<Self, ExtendedPublicKeyError> {
+        match prefix[0..4] == P::EXTENDED_PUBLIC_KEY_VERSION {
+            true => Ok(Self(PhantomData)),
+            false => Err(ExtendedPublicKeyError::InvalidVersionBytes(prefix.to_vec())),
+        }
+    }
+}
+
+impl<P: CustomNetworkParameters> FromStr for CustomNetwork<P> {
+    type Err = NetworkError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s == P::NAME {
+            true => Ok(Self(PhantomData)),
+            false => Err(NetworkError::InvalidNetwork(s.into())),
+        }
+    }
+}
+
+impl<P: CustomNetworkParameters> fmt::Display for CustomNetwork<P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", P::NAME)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::address::BitcoinAddress;
+    use crate::extended_private_key::BitcoinExtendedPrivateKey;
+    use crate::private_key::BitcoinPrivateKey;
+    use wagyu_model::{ExtendedPrivateKey, PrivateKey};
+
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    struct Litecoin;
+
+    impl CustomNetworkParameters for Litecoin {
+        const NAME: &'static str = "litecoin";
+        const HD_COIN_TYPE: ChildIndex = ChildIndex::Hardened(2);
+        const P2PKH_PREFIX: u8 = 0x30;
+        const P2SH_PREFIX: u8 = 0x32;
+        const BECH32_HRP: &'static str = "ltc";
+        const WIF_PREFIX: u8 = 0xB0;
+        const EXTENDED_PRIVATE_KEY_VERSION: [u8; 4] = [0x01, 0x9D, 0x9C, 0xFE];
+        const EXTENDED_PUBLIC_KEY_VERSION: [u8; 4] = [0x01, 0x9D, 0xA4, 0x62];
+    }
+
+    type N = CustomNetwork<Litecoin>;
+
+    // (wif, p2pkh_address, p2sh_p2wpkh_address, bech32_address)
+    const KEYPAIR: (&str, &str, &str, &str) = (
+        "TBXrPpvjyZwnpavw7yoRu8tuDJ3jivS7MajbsHoC6o1NwdwSxbXD",
+        "LL8KcK7Z1PSMiq7n3Way5pZhpc6Va4mjxz",
+        "MTcaa6HHSJBzqgMgmzVMqBNzXov9mXcbvf",
+        "ltc1qp8nh9ll6kgtmc9j0hz623uvn2t6wv8gjughhp3",
+    );
+
+    #[test]
+    fn private_key() {
+        let (wif, p2pkh_address, p2sh_p2wpkh_address, bech32_address) = KEYPAIR;
+        let private_key = BitcoinPrivateKey::<N>::from_str(wif).unwrap();
+        assert_eq!(wif, private_key.to_string());
+
+        let address = private_key.to_address(&BitcoinFormat::P2PKH).unwrap();
+        assert_eq!(p2pkh_address, address.to_string());
+        let address = private_key.to_address(&BitcoinFormat::P2SH_P2WPKH).unwrap();
+        assert_eq!(p2sh_p2wpkh_address, address.to_string());
+        let address = private_key.to_address(&BitcoinFormat::Bech32).unwrap();
+        assert_eq!(bech32_address, address.to_string());
+    }
+
+    #[test]
+    fn address_from_str() {
+        let (_, p2pkh_address, p2sh_p2wpkh_address, bech32_address) = KEYPAIR;
+        [
+            (p2pkh_address, BitcoinFormat::P2PKH),
+            (p2sh_p2wpkh_address, BitcoinFormat::P2SH_P2WPKH),
+            (bech32_address, BitcoinFormat::Bech32),
+        ]
+        .iter()
+        .for_each(|(address, format)| {
+            let address = BitcoinAddress::<N>::from_str(address).unwrap();
+            assert_eq!(*format, address.format());
+        });
+
+        assert!(BitcoinAddress::<N>::from_str("1KKKK6N21XKo48zWKuQKXdvSsCf95ibHFa").is_err());
+        assert!(BitcoinAddress::<N>::from_str("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4").is_err());
+    }
+
+    #[test]
+    fn extended_private_key() {
+        let seed = [7u8; 32];
+        let extended_private_key = BitcoinExtendedPrivateKey::<N>::new_master(&seed, &BitcoinFormat::P2PKH).unwrap();
+        let serialized = extended_private_key.to_string();
+        assert!(serialized.starts_with("Ltpv"));
+        assert_eq!(
+            extended_private_key,
+            BitcoinExtendedPrivateKey::<N>::from_str(&serialized).unwrap()
+        );
+
+        let extended_public_key = extended_private_key.to_extended_public_key().to_string();
+        assert!(extended_public_key.starts_with("Ltub"));
+    }
+
+    #[test]
+    fn network() {
+        assert_eq!("litecoin", N::NAME);
+        assert_eq!("litecoin", N::from_str("litecoin").unwrap().to_string());
+        assert!(N::from_str("mainnet").is_err());
+    }
+}
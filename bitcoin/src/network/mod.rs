@@ -4,9 +4,18 @@ use wagyu_model::{
     AddressError, ChildIndex, ExtendedPrivateKeyError, ExtendedPublicKeyError, Network, PrivateKeyError,
 };
 
+pub mod custom;
+pub use self::custom::*;
+
 pub mod mainnet;
 pub use self::mainnet::*;
 
+pub mod regtest;
+pub use self::regtest::*;
+
+pub mod signet;
+pub use self::signet::*;
+
 pub mod testnet;
 pub use self::testnet::*;
 
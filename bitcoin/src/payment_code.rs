@@ -0,0 +1,356 @@
+use crate::address::BitcoinAddress;
+use crate::derivation_path::BitcoinDerivationPath;
+use crate::extended_private_key::BitcoinExtendedPrivateKey;
+use crate::extended_public_key::BitcoinExtendedPublicKey;
+use crate::format::BitcoinFormat;
+use crate::network::BitcoinNetwork;
+use crate::private_key::BitcoinPrivateKey;
+use crate::public_key::BitcoinPublicKey;
+use wagyu_model::{
+    crypto::checksum, no_std::*, Address, AddressError, ChildIndex, CrateError, DerivationPathError,
+    ExtendedPrivateKey, ExtendedPrivateKeyError, ExtendedPublicKey, ExtendedPublicKeyError,
+};
+
+use base58::{FromBase58, ToBase58};
+use core::{fmt, marker::PhantomData, str::FromStr};
+use sha2::{Digest, Sha256};
+
+/// The base58 prefix of a serialized payment code, for which the encoding starts with "PM8T"
+const PAYMENT_CODE_PREFIX: u8 = 0x47;
+/// The version of the payment code
+const PAYMENT_CODE_VERSION: u8 = 0x01;
+/// The length of a payment code in bytes, excluding its prefix and checksum
+const PAYMENT_CODE_LENGTH: usize = 80;
+
+/// Represents a BIP47 reusable payment code, the public key and chain code of a BIP47 account,
+/// from which a sender and receiver derive a private sequence of addresses.
+/// https://github.com/bitcoin/bips/blob/master/bip-0047.mediawiki
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitcoinPaymentCode<N: BitcoinNetwork> {
+    /// The public key of the account
+    public_key: BitcoinPublicKey<N>,
+    /// The chain code of the account
+    chain_code: [u8; 32],
+}
+
+impl<N: BitcoinNetwork> BitcoinPaymentCode<N> {
+    /// Returns the payment code of the given account of a master extended private key.
+    pub fn new(
+        master_extended_private_key: &BitcoinExtendedPrivateKey<N>,
+        account: u32,
+    ) -> Result<Self, PaymentCodeError> {
+        let account_extended_private_key = Self::to_account_extended_private_key(master_extended_private_key, account)?;
+        Ok(Self::from_extended_public_key(
+            &account_extended_private_key.to_extended_public_key(),
+        ))
+    }
+
+    /// Returns the payment code of a BIP47 account extended public key, m/47'/{0', 1'}/{account}'.
+    pub fn from_extended_public_key(account_extended_public_key: &BitcoinExtendedPublicKey<N>) -> Self {
+        Self {
+            public_key: account_extended_public_key.to_public_key(),
+            chain_code: account_extended_public_key.chain_code(),
+        }
+    }
+
+    /// Returns the BIP47 account extended private key of a master extended private key,
+    /// which derives the sending and receiving addresses of the payment code.
+    pub fn to_account_extended_private_key(
+        master_extended_private_key: &BitcoinExtendedPrivateKey<N>,
+        account: u32,
+    ) -> Result<BitcoinExtendedPrivateKey<N>, PaymentCodeError> {
+        Ok(master_extended_private_key.derive(&BitcoinDerivationPath::bip47(account)?)?)
+    }
+
+    /// Returns the public key of the payment code.
+    pub fn to_public_key(&self) -> BitcoinPublicKey<N> {
+        self.public_key.clone()
+    }
+
+    /// Returns the notification address of the payment code, which a sender pays to announce
+    /// its own payment code before its first payment.
+    pub fn to_notification_address(&self) -> Result<BitcoinAddress<N>, PaymentCodeError> {
+        Ok(BitcoinAddress::p2pkh(&self.to_child_public_key(0)?)?)
+    }
+
+    /// Returns the address at the given index that the owner of the account extended private key
+    /// sends to, to pay the owner of this payment code.
+    pub fn to_send_address(
+        &self,
+        account_extended_private_key: &BitcoinExtendedPrivateKey<N>,
+        index: u32,
+        format: &BitcoinFormat,
+    ) -> Result<BitcoinAddress<N>, PaymentCodeError> {
+        let private_key = Self::to_child_private_key(account_extended_private_key, 0)?;
+        let public_key = self.to_child_public_key(index)?;
+        let shared_secret = Self::to_shared_secret(&private_key, &public_key, index)?;
+
+        let mut send_public_key = public_key.to_secp256k1_public_key();
+        send_public_key
+            .tweak_add_assign(&shared_secret)
+            .map_err(CrateError::from)?;
+        Ok(BitcoinAddress::from_public_key(
+            &BitcoinPublicKey::from_secp256k1_public_key(send_public_key, true),
+            format,
+        )?)
+    }
+
+    /// Returns the private key of the address at the given index that the owner of the account
+    /// extended private key receives to, when paid by the owner of this payment code.
+    pub fn to_receive_private_key(
+        &self,
+        account_extended_private_key: &BitcoinExtendedPrivateKey<N>,
+        index: u32,
+    ) -> Result<BitcoinPrivateKey<N>, PaymentCodeError> {
+        let private_key = Self::to_child_private_key(account_extended_private_key, index)?;
+        let public_key = self.to_child_public_key(0)?;
+        let shared_secret = Self::to_shared_secret(&private_key, &public_key, index)?;
+
+        let mut receive_secret_key = private_key.to_secp256k1_secret_key();
+        receive_secret_key
+            .tweak_add_assign(&shared_secret)
+            .map_err(CrateError::from)?;
+        Ok(BitcoinPrivateKey::from_secp256k1_secret_key(&receive_secret_key, true))
+    }
+
+    /// Returns the address at the given index that the owner of the account extended private key
+    /// receives to, when paid by the owner of this payment code.
+    pub fn to_receive_address(
+        &self,
+        account_extended_private_key: &BitcoinExtendedPrivateKey<N>,
+        index: u32,
+        format: &BitcoinFormat,
+    ) -> Result<BitcoinAddress<N>, PaymentCodeError> {
+        let private_key = self.to_receive_private_key(account_extended_private_key, index)?;
+        Ok(BitcoinAddress::from_private_key(&private_key, format)?)
+    }
+
+    /// Returns the public key of the payment code at the given child index.
+    fn to_child_public_key(&self, index: u32) -> Result<BitcoinPublicKey<N>, PaymentCodeError> {
+        let extended_public_key =
+            BitcoinExtendedPublicKey::from_chain_code(&self.public_key, self.chain_code, &BitcoinFormat::P2PKH);
+        let path = BitcoinDerivationPath::BIP32(vec![ChildIndex::normal(index)?], PhantomData);
+        Ok(extended_public_key.derive(&path)?.to_public_key())
+    }
+
+    /// Returns the private key of an account extended private key at the given child index.
+    fn to_child_private_key(
+        account_extended_private_key: &BitcoinExtendedPrivateKey<N>,
+        index: u32,
+    ) -> Result<BitcoinPrivateKey<N>, PaymentCodeError> {
+        let path = BitcoinDerivationPath::BIP32(vec![ChildIndex::normal(index)?], PhantomData);
+        Ok(account_extended_private_key.derive(&path)?.to_private_key())
+    }
+
+    /// Returns the shared secret SHA256(Sx) of the Diffie-Hellman point S of the private key and public key.
+    fn to_shared_secret(
+        private_key: &BitcoinPrivateKey<N>,
+        public_key: &BitcoinPublicKey<N>,
+        index: u32,
+    ) -> Result<secp256k1::SecretKey, PaymentCodeError> {
+        let mut point = public_key.to_secp256k1_public_key();
+        point
+            .tweak_mul_assign(&private_key.to_secp256k1_secret_key())
+            .map_err(CrateError::from)?;
+
+        // A shared secret outside of the curve order is skipped by both parties.
+        let shared_secret = Sha256::digest(&point.serialize_compressed()[1..]);
+        secp256k1::SecretKey::parse_slice(&shared_secret).map_err(|_| PaymentCodeError::InvalidSharedSecret(index))
+    }
+}
+
+impl<N: BitcoinNetwork> FromStr for BitcoinPaymentCode<N> {
+    type Err = PaymentCodeError;
+
+    fn from_str(payment_code: &str) -> Result<Self, Self::Err> {
+        let data = payment_code
+            .from_base58()
+            .map_err(|_| PaymentCodeError::InvalidBase58)?;
+        if data.len() != PAYMENT_CODE_LENGTH + 5 {
+            return Err(PaymentCodeError::InvalidByteLength(data.len()));
+        }
+
+        let (payload, expected_checksum) = data.split_at(PAYMENT_CODE_LENGTH + 1);
+        if checksum(payload)[0..4] != *expected_checksum {
+            return Err(PaymentCodeError::InvalidChecksum);
+        }
+        if payload[0] != PAYMENT_CODE_PREFIX {
+            return Err(PaymentCodeError::InvalidPrefix(payload[0]));
+        }
+        if payload[1] != PAYMENT_CODE_VERSION {
+            return Err(PaymentCodeError::InvalidVersion(payload[1]));
+        }
+
+        let mut chain_code = [0u8; 32];
+        chain_code.copy_from_slice(&payload[36..68]);
+
+        Ok(Self {
+            public_key: BitcoinPublicKey::from_secp256k1_public_key(
+                secp256k1::PublicKey::parse_slice(&payload[3..36], None).map_err(CrateError::from)?,
+                true,
+            ),
+            chain_code,
+        })
+    }
+}
+
+impl<N: BitcoinNetwork> fmt::Display for BitcoinPaymentCode<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // The version and features bytes, followed by the public key, the chain code and 13 reserved bytes.
+        let mut payload = vec![PAYMENT_CODE_PREFIX, PAYMENT_CODE_VERSION, 0x00];
+        payload.extend_from_slice(&self.public_key.to_secp256k1_public_key().serialize_compressed());
+        payload.extend_from_slice(&self.chain_code);
+        payload.extend_from_slice(&[0u8; 13]);
+
+        let sum = checksum(&payload);
+        payload.extend_from_slice(&sum[0..4]);
+        write!(f, "{}", payload.to_base58())
+    }
+}
+
+#[derive(Debug, Fail)]
+pub enum PaymentCodeError {
+    #[fail(display = "{}", _0)]
+    AddressError(#[cause] AddressError),
+
+    #[fail(display = "{}", _0)]
+    Crate(#[cause] CrateError),
+
+    #[fail(display = "{}", _0)]
+    DerivationPathError(#[cause] DerivationPathError),
+
+    #[fail(display = "{}", _0)]
+    ExtendedPrivateKeyError(#[cause] ExtendedPrivateKeyError),
+
+    #[fail(display = "{}", _0)]
+    ExtendedPublicKeyError(#[cause] ExtendedPublicKeyError),
+
+    #[fail(display = "invalid base58 encoding")]
+    InvalidBase58,
+
+    #[fail(display = "invalid byte length: {}", _0)]
+    InvalidByteLength(usize),
+
+    #[fail(display = "invalid checksum")]
+    InvalidChecksum,
+
+    #[fail(display = "invalid prefix: {:#04x}", _0)]
+    InvalidPrefix(u8),
+
+    #[fail(display = "invalid shared secret at index {}", _0)]
+    InvalidSharedSecret(u32),
+
+    #[fail(display = "invalid version: {}", _0)]
+    InvalidVersion(u8),
+}
+
+impl From<AddressError> for PaymentCodeError {
+    fn from(error: AddressError) -> Self {
+        PaymentCodeError::AddressError(error)
+    }
+}
+
+impl From<CrateError> for PaymentCodeError {
+    fn from(error: CrateError) -> Self {
+        PaymentCodeError::Crate(error)
+    }
+}
+
+impl From<DerivationPathError> for PaymentCodeError {
+    fn from(error: DerivationPathError) -> Self {
+        PaymentCodeError::DerivationPathError(error)
+    }
+}
+
+impl From<ExtendedPrivateKeyError> for PaymentCodeError {
+    fn from(error: ExtendedPrivateKeyError) -> Self {
+        PaymentCodeError::ExtendedPrivateKeyError(error)
+    }
+}
+
+impl From<ExtendedPublicKeyError> for PaymentCodeError {
+    fn from(error: ExtendedPublicKeyError) -> Self {
+        PaymentCodeError::ExtendedPublicKeyError(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::*;
+
+    type N = Mainnet;
+
+    // https://gist.github.com/SamouraiDev/6aad669604c5930864bd
+    // (seed, payment_code, notification_address)
+    const ALICE: (&str, &str, &str) = (
+        "64dca76abc9c6f0cf3d212d248c380c4622c8f93b2c425ec6a5567fd5db57e10d3e6f94a2f6af4ac2edb8998072aad92098db73558c323777abf5bd1082d970a",
+        "PM8TJTLJbPRGxSbc8EJi42Wrr6QbNSaSSVJ5Y3E4pbCYiTHUskHg13935Ubb7q8tx9GVbh2UuRnBc3WSyJHhUrw8KhprKnn9eDznYGieTzFcwQRya4GA",
+        "1JDdmqFLhpzcUwPeinhJbUPw4Co3aWLyzW",
+    );
+    const BOB: (&str, &str, &str) = (
+        "87eaaac5a539ab028df44d9110defbef3797ddb805ca309f61a69ff96dbaa7ab5b24038cf029edec5235d933110f0aea8aeecf939ed14fc20730bba71e4b1110",
+        "PM8TJS2JxQ5ztXUpBBRnpTbcUXbUHy2T1abfrb3KkAAtMEGNbey4oumH7Hc578WgQJhPjBxteQ5GHHToTYHE3A1w6p7tU6KSoFmWBVbFGjKPisZDbP97",
+        "1ChvUUvht2hUQufHBXF8NgLhW8SwE2ecGV",
+    );
+    // The first addresses Alice sends to and Bob receives to
+    const ADDRESSES: [&str; 4] = [
+        "141fi7TY3h936vRUKh1qfUZr8rSBuYbVBK",
+        "12u3Uued2fuko2nY4SoSFGCoGLCBUGPkk6",
+        "1FsBVhT5dQutGwaPePTYMe5qvYqqjxyftc",
+        "1CZAmrbKL6fJ7wUxb99aETwXhcGeG3CpeA",
+    ];
+
+    fn master_extended_private_key(seed: &str) -> BitcoinExtendedPrivateKey<N> {
+        BitcoinExtendedPrivateKey::new_master(&hex::decode(seed).unwrap(), &BitcoinFormat::P2PKH).unwrap()
+    }
+
+    #[test]
+    fn new() {
+        [ALICE, BOB]
+            .iter()
+            .for_each(|(seed, payment_code, notification_address)| {
+                let master_extended_private_key = master_extended_private_key(seed);
+                let expected = BitcoinPaymentCode::new(&master_extended_private_key, 0).unwrap();
+                assert_eq!(*payment_code, expected.to_string());
+                assert_eq!(expected, BitcoinPaymentCode::from_str(payment_code).unwrap());
+                assert_eq!(
+                    *notification_address,
+                    expected.to_notification_address().unwrap().to_string()
+                );
+            });
+    }
+
+    #[test]
+    fn send_and_receive_addresses() {
+        let alice = BitcoinPaymentCode::<N>::from_str(ALICE.1).unwrap();
+        let bob = BitcoinPaymentCode::<N>::from_str(BOB.1).unwrap();
+        let alice_account =
+            BitcoinPaymentCode::to_account_extended_private_key(&master_extended_private_key(ALICE.0), 0).unwrap();
+        let bob_account =
+            BitcoinPaymentCode::to_account_extended_private_key(&master_extended_private_key(BOB.0), 0).unwrap();
+
+        ADDRESSES.iter().enumerate().for_each(|(index, address)| {
+            let index = index as u32;
+            let send_address = bob.to_send_address(&alice_account, index, &BitcoinFormat::P2PKH);
+            assert_eq!(*address, send_address.unwrap().to_string());
+            let receive_address = alice.to_receive_address(&bob_account, index, &BitcoinFormat::P2PKH);
+            assert_eq!(*address, receive_address.unwrap().to_string());
+        });
+
+        // Segwit addresses of the same keys are derived consistently by both parties.
+        let send_address = bob.to_send_address(&alice_account, 7, &BitcoinFormat::Bech32).unwrap();
+        let receive_address = alice
+            .to_receive_address(&bob_account, 7, &BitcoinFormat::Bech32)
+            .unwrap();
+        assert_eq!(send_address, receive_address);
+    }
+
+    #[test]
+    fn invalid() {
+        let payment_code = ALICE.1;
+        assert!(BitcoinPaymentCode::<N>::from_str(&payment_code[1..]).is_err());
+        assert!(BitcoinPaymentCode::<N>::from_str(&payment_code.replace("PM8TJT", "PM8TJS")).is_err());
+        assert!(BitcoinPaymentCode::<N>::from_str("xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8").is_err());
+    }
+}
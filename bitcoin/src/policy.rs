@@ -0,0 +1,322 @@
+//!
+//! Policy
+//!
+//! This module contains the standardness rules of the default relay policy of Bitcoin Core, which nodes
+//! apply to unconfirmed transactions on top of the consensus rules, so that a transaction built offline
+//! can be checked before it is signed rather than rejected at broadcast.
+//! https://github.com/bitcoin/bitcoin/blob/master/src/policy/policy.cpp
+//!
+
+use crate::network::BitcoinNetwork;
+use crate::script::{BitcoinScript, BitcoinScriptInstruction, ScriptType};
+use crate::transaction::BitcoinTransactionParameters;
+use wagyu_model::no_std::*;
+use wagyu_model::TransactionError;
+
+use core::fmt;
+
+/// The greatest standard transaction version
+pub const MAX_STANDARD_VERSION: u32 = 3;
+/// The greatest standard weight of a transaction
+pub const MAX_STANDARD_TX_WEIGHT: u64 = 400_000;
+/// The smallest standard size of a transaction without its witness data (in bytes)
+pub const MIN_STANDARD_TX_NONWITNESS_SIZE: u64 = 65;
+/// The greatest standard size of a script signature (in bytes)
+pub const MAX_STANDARD_SCRIPT_SIG_SIZE: usize = 1_650;
+/// The greatest standard number of public keys of a bare multisig output
+pub const MAX_STANDARD_MULTISIG_KEYS: usize = 3;
+/// The greatest standard size of the script public key of an OP_RETURN output (in bytes)
+pub const MAX_STANDARD_OP_RETURN_SIZE: usize = 83;
+/// The fee rate a replacement pays for its own relay on top of the fee it replaces (in Satoshi per virtual byte)
+pub const INCREMENTAL_RELAY_FEE_RATE: u64 = 1;
+
+/// Represents a reason a transaction is not standard, and would not be relayed by default
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum StandardnessWarning {
+    /// The transaction version is not standard
+    Version(u32),
+    /// The estimated weight of the signed transaction exceeds the standard maximum
+    Weight(u64),
+    /// The estimated size of the signed transaction without its witness data is below the standard minimum
+    NonWitnessSize(u64),
+    /// The script signature of the input at the given index exceeds the standard size
+    ScriptSigSize(usize, usize),
+    /// The script signature of the input at the given index has opcodes other than pushes of data
+    ScriptSigNotPushOnly(usize),
+    /// The output at the given index has a non-standard script public key
+    NonStandardScript(usize),
+    /// The bare multisig output at the given index has more than the standard number of public keys
+    BareMultisigKeys(usize, usize),
+    /// The script public key of the OP_RETURN output at the given index exceeds the standard size
+    OpReturnSize(usize, usize),
+    /// The transaction has more than one OP_RETURN output
+    MultipleOpReturns(usize),
+    /// The output at the given index has an amount below its dust threshold
+    Dust(usize, i64, i64),
+}
+
+impl fmt::Display for StandardnessWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StandardnessWarning::Version(version) => write!(f, "transaction version {} is not standard", version),
+            StandardnessWarning::Weight(weight) => write!(
+                f,
+                "transaction weight {} exceeds the standard maximum of {}",
+                weight, MAX_STANDARD_TX_WEIGHT
+            ),
+            StandardnessWarning::NonWitnessSize(size) => write!(
+                f,
+                "transaction size without witness {} is below the standard minimum of {}",
+                size, MIN_STANDARD_TX_NONWITNESS_SIZE
+            ),
+            StandardnessWarning::ScriptSigSize(vin, size) => write!(
+                f,
+                "input {} script signature size {} exceeds the standard maximum of {}",
+                vin, size, MAX_STANDARD_SCRIPT_SIG_SIZE
+            ),
+            StandardnessWarning::ScriptSigNotPushOnly(vin) => {
+                write!(f, "input {} script signature is not push only", vin)
+            }
+            StandardnessWarning::NonStandardScript(vout) => {
+                write!(f, "output {} script public key is not standard", vout)
+            }
+            StandardnessWarning::BareMultisigKeys(vout, keys) => write!(
+                f,
+                "output {} bare multisig has {} public keys, above the standard maximum of {}",
+                vout, keys, MAX_STANDARD_MULTISIG_KEYS
+            ),
+            StandardnessWarning::OpReturnSize(vout, size) => write!(
+                f,
+                "output {} OP_RETURN script size {} exceeds the standard maximum of {}",
+                vout, size, MAX_STANDARD_OP_RETURN_SIZE
+            ),
+            StandardnessWarning::MultipleOpReturns(count) => {
+                write!(f, "transaction has {} OP_RETURN outputs, above the standard 1", count)
+            }
+            StandardnessWarning::Dust(vout, amount, threshold) => write!(
+                f,
+                "output {} amount {} is below the dust threshold of {}",
+                vout, amount, threshold
+            ),
+        }
+    }
+}
+
+impl<N: BitcoinNetwork> BitcoinTransactionParameters<N> {
+    /// Returns an error if the amount of an output is below its dust threshold.
+    pub fn check_dust(&self) -> Result<(), TransactionError> {
+        for (vout, output) in self.outputs.iter().enumerate() {
+            if output.is_dust()? {
+                return Err(TransactionError::DustOutput(
+                    vout,
+                    output.amount.0,
+                    output.dust_threshold()?.0,
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the reasons the transaction is not standard, estimating the size of the signed transaction
+    /// unless an input spends an unknown script, or an empty list if the transaction is standard.
+    pub fn standardness_warnings(&self) -> Result<Vec<StandardnessWarning>, TransactionError> {
+        let mut warnings = vec![];
+
+        if self.version < 1 || self.version > MAX_STANDARD_VERSION {
+            warnings.push(StandardnessWarning::Version(self.version));
+        }
+        if let Ok(size) = self.estimate_size() {
+            if size.weight() > MAX_STANDARD_TX_WEIGHT {
+                warnings.push(StandardnessWarning::Weight(size.weight()));
+            }
+            if size.base_size < MIN_STANDARD_TX_NONWITNESS_SIZE {
+                warnings.push(StandardnessWarning::NonWitnessSize(size.base_size));
+            }
+        }
+
+        for (vin, input) in self.inputs.iter().enumerate() {
+            if input.script_sig.len() > MAX_STANDARD_SCRIPT_SIG_SIZE {
+                warnings.push(StandardnessWarning::ScriptSigSize(vin, input.script_sig.len()));
+            }
+            // The opcodes up to OP_16 push data or numbers
+            let push_only = match BitcoinScript::new(&input.script_sig).to_instructions() {
+                Ok(instructions) => instructions.iter().all(|instruction| match instruction {
+                    BitcoinScriptInstruction::Opcode(opcode) => *opcode <= 0x60,
+                    BitcoinScriptInstruction::Push(_) => true,
+                }),
+                Err(_) => false,
+            };
+            if !push_only {
+                warnings.push(StandardnessWarning::ScriptSigNotPushOnly(vin));
+            }
+        }
+
+        let mut op_returns = 0;
+        for (vout, output) in self.outputs.iter().enumerate() {
+            let script = BitcoinScript::new(&output.script_pub_key);
+            match script.to_script_type() {
+                ScriptType::NonStandard => warnings.push(StandardnessWarning::NonStandardScript(vout)),
+                ScriptType::Multisig => match script.to_multisig() {
+                    Some((_, public_keys)) if public_keys.len() > MAX_STANDARD_MULTISIG_KEYS => {
+                        warnings.push(StandardnessWarning::BareMultisigKeys(vout, public_keys.len()))
+                    }
+                    _ => {}
+                },
+                ScriptType::NullData => {
+                    op_returns += 1;
+                    if script.len() > MAX_STANDARD_OP_RETURN_SIZE {
+                        warnings.push(StandardnessWarning::OpReturnSize(vout, script.len()));
+                    }
+                }
+                _ => {}
+            };
+
+            if output.is_dust()? {
+                warnings.push(StandardnessWarning::Dust(
+                    vout,
+                    output.amount.0,
+                    output.dust_threshold()?.0,
+                ));
+            }
+        }
+        if op_returns > 1 {
+            warnings.push(StandardnessWarning::MultipleOpReturns(op_returns));
+        }
+
+        Ok(warnings)
+    }
+
+    /// Returns `true` if the transaction is standard, as checked by `standardness_warnings`.
+    pub fn is_standard(&self) -> Result<bool, TransactionError> {
+        Ok(self.standardness_warnings()?.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::address::BitcoinAddress;
+    use crate::amount::BitcoinAmount;
+    use crate::network::*;
+    use crate::public_key::BitcoinPublicKey;
+    use crate::transaction::{BitcoinTransactionInput, BitcoinTransactionOutput, SignatureHash};
+
+    use core::str::FromStr;
+
+    type N = Mainnet;
+
+    const BECH32_ADDRESS: &str = "bc1qcsjz44ce84j3650qfu9k87tyd3z8h4qyxz470n";
+    const P2PKH_ADDRESS: &str = "176DPNootfp2bSiE7KQUZp1VZj5EyGQeCt";
+
+    fn output(address: &str, amount: i64) -> BitcoinTransactionOutput {
+        BitcoinTransactionOutput::new(&BitcoinAddress::<N>::from_str(address).unwrap(), BitcoinAmount(amount)).unwrap()
+    }
+
+    fn parameters(outputs: Vec<BitcoinTransactionOutput>) -> BitcoinTransactionParameters<N> {
+        let input = BitcoinTransactionInput::<N>::new(
+            vec![0xab; 32],
+            0,
+            Some(BitcoinAddress::<N>::from_str(BECH32_ADDRESS).unwrap()),
+            Some(BitcoinAmount(1_000_000)),
+            None,
+            None,
+            None,
+            SignatureHash::SIGHASH_ALL,
+        )
+        .unwrap();
+        BitcoinTransactionParameters::<N> {
+            version: 2,
+            inputs: vec![input],
+            outputs,
+            lock_time: 0,
+            segwit_flag: false,
+        }
+    }
+
+    #[test]
+    fn standard_transaction() {
+        let parameters = parameters(vec![
+            output(P2PKH_ADDRESS, 546),
+            output(BECH32_ADDRESS, 294),
+            BitcoinTransactionOutput::op_return(&[0xab; 80]).unwrap(),
+        ]);
+        assert!(parameters.check_dust().is_ok());
+        assert_eq!(
+            Vec::<StandardnessWarning>::new(),
+            parameters.standardness_warnings().unwrap()
+        );
+        assert!(parameters.is_standard().unwrap());
+    }
+
+    #[test]
+    fn dust_outputs() {
+        let parameters = parameters(vec![output(BECH32_ADDRESS, 294), output(P2PKH_ADDRESS, 545)]);
+        match parameters.check_dust() {
+            Err(TransactionError::DustOutput(1, 545, 546)) => (),
+            result => panic!("unexpected result {:?}", result),
+        };
+        assert_eq!(
+            vec![StandardnessWarning::Dust(1, 545, 546)],
+            parameters.standardness_warnings().unwrap()
+        );
+        assert_eq!(
+            "output 1 amount 545 is below the dust threshold of 546",
+            StandardnessWarning::Dust(1, 545, 546).to_string()
+        );
+    }
+
+    #[test]
+    fn non_standard_outputs() {
+        let public_key =
+            BitcoinPublicKey::<N>::from_str("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")
+                .unwrap();
+        let multisig = BitcoinScript::multisig(1, &vec![public_key; 4]);
+        let parameters = parameters(vec![
+            BitcoinTransactionOutput::from_script(&BitcoinScript::new(&[0xac]), BitcoinAmount(1_000)),
+            BitcoinTransactionOutput::from_script(&multisig, BitcoinAmount(10_000)),
+            BitcoinTransactionOutput::from_script(&BitcoinScript::op_return(&[0xab; 81]), BitcoinAmount(0)),
+            BitcoinTransactionOutput::op_return(b"wagyu").unwrap(),
+        ]);
+        assert_eq!(
+            vec![
+                StandardnessWarning::NonStandardScript(0),
+                StandardnessWarning::BareMultisigKeys(1, 4),
+                StandardnessWarning::OpReturnSize(2, 84),
+                StandardnessWarning::MultipleOpReturns(2),
+            ],
+            parameters.standardness_warnings().unwrap()
+        );
+        assert!(!parameters.is_standard().unwrap());
+        // OP_RETURN outputs are never dust
+        assert!(parameters.check_dust().is_ok());
+    }
+
+    #[test]
+    fn non_standard_sizes() {
+        let mut parameters = parameters(vec![]);
+        parameters.version = 4;
+        parameters.inputs[0].script_sig = vec![0x6a];
+        assert_eq!(
+            vec![
+                StandardnessWarning::Version(4),
+                StandardnessWarning::NonWitnessSize(51),
+                StandardnessWarning::ScriptSigNotPushOnly(0),
+            ],
+            parameters.standardness_warnings().unwrap()
+        );
+
+        let mut parameters = self::parameters(vec![output(BECH32_ADDRESS, 10_000)]);
+        parameters.inputs[0].script_sig = vec![0x4c; 1_651];
+        assert!(parameters
+            .standardness_warnings()
+            .unwrap()
+            .contains(&StandardnessWarning::ScriptSigSize(0, 1_651)));
+
+        // Outputs beyond the standard weight
+        let parameters = self::parameters(vec![output(BECH32_ADDRESS, 10_000); 3_300]);
+        match parameters.standardness_warnings().unwrap().as_slice() {
+            [StandardnessWarning::Weight(weight)] => assert!(*weight > MAX_STANDARD_TX_WEIGHT),
+            warnings => panic!("unexpected warnings {:?}", warnings),
+        };
+    }
+}
@@ -1,16 +1,20 @@
 use crate::address::BitcoinAddress;
 use crate::format::BitcoinFormat;
-use crate::network::BitcoinNetwork;
+use crate::network::{BitcoinNetwork, Mainnet, Testnet};
 use crate::public_key::BitcoinPublicKey;
-use wagyu_model::{crypto::checksum, Address, AddressError, PrivateKey, PrivateKeyError, PublicKey};
+use wagyu_model::{
+    crypto::{checksum, secp256k1_public_key, tagged_hash},
+    Address, AddressError, ExposeSecret, Network, PrivateKey, PrivateKeyError, PublicKey, Redacted,
+};
 
 use base58::{FromBase58, ToBase58};
 use core::{fmt, fmt::Display, marker::PhantomData, str::FromStr};
 use rand::Rng;
-use secp256k1;
+use secp256k1::{self, curve::Scalar};
+use zeroize::Zeroize;
 
 /// Represents a Bitcoin private key
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq)]
 pub struct BitcoinPrivateKey<N: BitcoinNetwork> {
     /// The ECDSA private key
     secret_key: secp256k1::SecretKey,
@@ -64,6 +68,74 @@ impl<N: BitcoinNetwork> BitcoinPrivateKey<N> {
     pub fn is_compressed(&self) -> bool {
         self.compressed
     }
+
+    /// Returns the BIP340 Schnorr signature of the message, using the given auxiliary randomness
+    /// to derive the nonce.
+    /// https://github.com/bitcoin/bips/blob/master/bip-0340.mediawiki#default-signing
+    pub fn sign_schnorr(&self, message: &[u8], aux_rand: &[u8; 32]) -> Result<[u8; 64], PrivateKeyError> {
+        let public_key = secp256k1_public_key(&self.secret_key).serialize_compressed();
+        let x_only_public_key = &public_key[1..];
+
+        // Negate the secret key if its public key has an odd y-coordinate.
+        let mut d: Scalar = self.secret_key.clone().into();
+        if public_key[0] == secp256k1::util::TAG_PUBKEY_ODD {
+            d = -d;
+        }
+
+        let mut t = d.b32();
+        t.iter_mut()
+            .zip(tagged_hash(b"BIP0340/aux", aux_rand).iter())
+            .for_each(|(t, aux)| *t ^= aux);
+
+        let mut k = Scalar::default();
+        let _ = k.set_b32(&tagged_hash(
+            b"BIP0340/nonce",
+            &[&t[..], x_only_public_key, message].concat(),
+        ));
+        t.zeroize();
+        if k.is_zero() {
+            return Err(PrivateKeyError::Message("invalid schnorr nonce".into()));
+        }
+
+        // Negate the nonce if its point R has an odd y-coordinate.
+        let r = secp256k1_public_key(&secp256k1::SecretKey::parse(&k.b32())?).serialize_compressed();
+        if r[0] == secp256k1::util::TAG_PUBKEY_ODD {
+            k = -k;
+        }
+
+        let mut e = Scalar::default();
+        let _ = e.set_b32(&tagged_hash(
+            b"BIP0340/challenge",
+            &[&r[1..], x_only_public_key, message].concat(),
+        ));
+
+        let mut signature = [0u8; 64];
+        signature[..32].copy_from_slice(&r[1..]);
+        signature[32..].copy_from_slice(&(k + e * d).b32());
+        Ok(signature)
+    }
+
+    /// Returns the private key tweaked to the BIP341 Taproot output key of its public key as the
+    /// internal key, committing to the Merkle root of a script tree if given.
+    /// https://github.com/bitcoin/bips/blob/master/bip-0341.mediawiki#constructing-and-spending-taproot-outputs
+    pub fn to_taproot_tweaked_private_key(&self, merkle_root: Option<&[u8; 32]>) -> Result<Self, PrivateKeyError> {
+        let public_key = self.to_public_key();
+        let mut secret_key: Scalar = self.secret_key.clone().into();
+        if public_key.to_secp256k1_public_key().serialize_compressed()[0] == secp256k1::util::TAG_PUBKEY_ODD {
+            secret_key = -secret_key;
+        }
+
+        let mut tweak_message = public_key.to_x_only_public_key().to_vec();
+        if let Some(merkle_root) = merkle_root {
+            tweak_message.extend_from_slice(merkle_root);
+        }
+        let mut tweak = Scalar::default();
+        if bool::from(tweak.set_b32(&tagged_hash(b"TapTweak", &tweak_message))) {
+            return Err(PrivateKeyError::Message("invalid taproot tweak".into()));
+        }
+        let secret_key = secp256k1::SecretKey::parse(&(secret_key + tweak).b32())?;
+        Ok(Self::from_secp256k1_secret_key(&secret_key, self.compressed))
+    }
 }
 
 impl<N: BitcoinNetwork> FromStr for BitcoinPrivateKey<N> {
@@ -71,6 +143,55 @@ impl<N: BitcoinNetwork> FromStr for BitcoinPrivateKey<N> {
 
     /// Returns a Bitcoin private key from a given WIF.
     fn from_str(wif: &str) -> Result<Self, Self::Err> {
+        let (data, compressed) = WifMetadata::decode(wif)?;
+
+        // Check that the wif prefix corresponds to the correct network.
+        if N::from_private_key_prefix(data[0]).is_err() {
+            return match WifMetadata::to_network(data[0]) {
+                Ok(network) => Err(PrivateKeyError::InvalidNetwork(N::NAME.into(), network.into())),
+                Err(error) => Err(error),
+            };
+        }
+
+        Ok(Self {
+            secret_key: secp256k1::SecretKey::parse_slice(&data[1..33])?,
+            compressed,
+            _network: PhantomData,
+        })
+    }
+}
+
+/// Represents the network and compression flag encoded in a WIF
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct WifMetadata {
+    /// The name of the network of the WIF prefix
+    pub network: &'static str,
+    /// If true, the WIF encodes a compressed private key
+    pub compressed: bool,
+}
+
+impl WifMetadata {
+    /// Returns the network and compression flag of a given WIF, without
+    /// requiring its network to be known in advance.
+    pub fn from_wif(wif: &str) -> Result<Self, PrivateKeyError> {
+        let (data, compressed) = Self::decode(wif)?;
+        Ok(Self {
+            network: Self::to_network(data[0])?,
+            compressed,
+        })
+    }
+
+    /// Returns the name of the network of a given WIF prefix.
+    fn to_network(prefix: u8) -> Result<&'static str, PrivateKeyError> {
+        match prefix {
+            prefix if Mainnet::from_private_key_prefix(prefix).is_ok() => Ok(Mainnet::NAME),
+            prefix if Testnet::from_private_key_prefix(prefix).is_ok() => Ok(Testnet::NAME),
+            prefix => Err(PrivateKeyError::InvalidPrefix(vec![prefix])),
+        }
+    }
+
+    /// Returns the decoded bytes and compression flag of a given WIF, after verifying its checksum.
+    fn decode(wif: &str) -> Result<(Vec<u8>, bool), PrivateKeyError> {
         let data = wif.from_base58()?;
         let len = data.len();
         if len != 37 && len != 38 {
@@ -85,14 +206,16 @@ impl<N: BitcoinNetwork> FromStr for BitcoinPrivateKey<N> {
             return Err(PrivateKeyError::InvalidChecksum(expected, found));
         }
 
-        // Check that the wif prefix corresponds to the correct network.
-        let _ = N::from_private_key_prefix(data[0])?;
+        // A compressed WIF appends the 0x01 flag byte to the secret key.
+        let compressed = len == 38;
+        if compressed && data[33] != 0x01 {
+            return Err(PrivateKeyError::Message(format!(
+                "invalid compression flag: {:#04x}",
+                data[33]
+            )));
+        }
 
-        Ok(Self {
-            secret_key: secp256k1::SecretKey::parse_slice(&data[1..33])?,
-            compressed: len == 38,
-            _network: PhantomData,
-        })
+        Ok((data, compressed))
     }
 }
 
@@ -113,10 +236,36 @@ impl<N: BitcoinNetwork> Display for BitcoinPrivateKey<N> {
             wif[..37].to_base58()
         };
 
+        wif.zeroize();
         write!(f, "{}", output)
     }
 }
 
+impl<N: BitcoinNetwork> fmt::Debug for BitcoinPrivateKey<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BitcoinPrivateKey")
+            .field("secret_key", &Redacted)
+            .field("compressed", &self.compressed)
+            .finish()
+    }
+}
+
+impl<N: BitcoinNetwork> ExposeSecret for BitcoinPrivateKey<N> {
+    fn fmt_secret(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BitcoinPrivateKey")
+            .field("secret_key", &self.secret_key)
+            .field("compressed", &self.compressed)
+            .finish()
+    }
+}
+
+impl<N: BitcoinNetwork> Zeroize for BitcoinPrivateKey<N> {
+    /// Replaces the secret key with a placeholder, clearing the previous secret key on drop.
+    fn zeroize(&mut self) {
+        self.secret_key = secp256k1::SecretKey::default();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -275,6 +424,17 @@ mod tests {
             });
         }
 
+        #[test]
+        fn debug() {
+            let private_key = BitcoinPrivateKey::<N>::from_str(KEYPAIRS[0].0).unwrap();
+            assert_eq!(
+                "BitcoinPrivateKey { secret_key: [REDACTED], compressed: true }",
+                format!("{:?}", private_key)
+            );
+            let exposed = format!("{:?}", private_key.expose_secret());
+            assert!(exposed.starts_with("BitcoinPrivateKey { secret_key: SecretKey("));
+        }
+
         #[test]
         fn test_invalid() {
             // Invalid WIF length
@@ -785,4 +945,143 @@ mod tests {
             });
         }
     }
+
+    mod wif_metadata {
+        use super::*;
+
+        // (wif, network, compressed)
+        const WIFS: [(&str, &str, bool); 4] = [
+            ("L5hax5dZaByC3kJ4aLrZgnMXGSQReqRDYNqM1VAeXpqDRkRjX42H", "mainnet", true),
+            ("5KV26gjX4sYAkXvDnqZZuEyFUh1DKjgZ8wTKL7Fpm4ppJ8kpZQu", "mainnet", false),
+            ("cNB6GpygWSZNRG5hotKjdAaNfgrzx984QYb2uj9rHpaCDkyy2aAz", "testnet", true),
+            ("93W1kMkD1kAfevtDQ3LWortt8zjKqSSqonLxPvWFqg57arcwUru", "testnet", false),
+        ];
+
+        #[test]
+        fn from_wif() {
+            WIFS.iter().for_each(|(wif, network, compressed)| {
+                let metadata = WifMetadata::from_wif(wif).unwrap();
+                assert_eq!(*network, metadata.network);
+                assert_eq!(*compressed, metadata.compressed);
+            });
+        }
+
+        #[test]
+        fn network_mismatch() {
+            match BitcoinPrivateKey::<Mainnet>::from_str(WIFS[2].0) {
+                Err(PrivateKeyError::InvalidNetwork(expected, found)) => {
+                    assert_eq!("mainnet", expected);
+                    assert_eq!("testnet", found);
+                }
+                _ => panic!("expected an invalid network error"),
+            }
+            assert!(BitcoinPrivateKey::<Testnet>::from_str(WIFS[1].0).is_err());
+        }
+
+        #[test]
+        fn invalid_compression_flag() {
+            let mut data = WIFS[0].0.from_base58().unwrap();
+            data[33] = 0x02;
+            let sum = checksum(&data[0..34]);
+            data[34..].copy_from_slice(&sum[0..4]);
+            assert!(WifMetadata::from_wif(&data.to_base58()).is_err());
+        }
+    }
+
+    mod bip340 {
+        use super::*;
+
+        type N = Mainnet;
+
+        // https://github.com/bitcoin/bips/blob/master/bip-0340/test-vectors.csv
+        // (secret_key, x_only_public_key, aux_rand, message, signature)
+        const VECTORS: [(&str, &str, &str, &str, &str); 4] = [
+            (
+                "0000000000000000000000000000000000000000000000000000000000000003",
+                "f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "e907831f80848d1069a5371b402410364bdf1c5f8307b0084c55f1ce2dca821525f66a4a85ea8b71e482a74f382d2ce5ebeee8fdb2172f477df4900d310536c0",
+            ),
+            (
+                "b7e151628aed2a6abf7158809cf4f3c762e7160f38b4da56a784d9045190cfef",
+                "dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659",
+                "0000000000000000000000000000000000000000000000000000000000000001",
+                "243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89",
+                "6896bd60eeae296db48a229ff71dfe071bde413e6d43f917dc8dcf8c78de33418906d11ac976abccb20b091292bff4ea897efcb639ea871cfa95f6de339e4b0a",
+            ),
+            (
+                "c90fdaa22168c234c4c6628b80dc1cd129024e088a67cc74020bbea63b14e5c9",
+                "dd308afec5777e13121fa72b9cc1b7cc0139715309b086c960e18fd969774eb8",
+                "c87aa53824b4d7ae2eb035a2b5bbbccc080e76cdc6d1692c4b0b62d798e6d906",
+                "7e2d58d8b3bcdf1abadec7829054f90dda9805aab56c77333024b9d0a508b75c",
+                "5831aaeed7b44bb74e5eab94ba9d4294c49bcf2a60728d8b4c200f50dd313c1bab745879a5ad954a72c45a91c3a51d3c7adea98d82f8481e0e1e03674a6f3fb7",
+            ),
+            (
+                "0b432b2677937381aef05bb02a66ecd012773062cf3fa2549e44f58ed2401710",
+                "25d1dff95105f5253c4022f628a996ad3a0d95fbf21d468a1b33f8c160d8f517",
+                "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+                "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+                "7eb0509757e246f19449885651611cb965ecc1a187dd51b64fda1edc9637d5ec97582b9cb13db3933705b32ba982af5af25fd78881ebb32771fc5922efc66ea3",
+            ),
+        ];
+
+        #[test]
+        fn sign_schnorr() {
+            VECTORS.iter().for_each(
+                |(secret_key, x_only_public_key, aux_rand, message, expected_signature)| {
+                    let secret_key = secp256k1::SecretKey::parse_slice(&hex::decode(secret_key).unwrap()).unwrap();
+                    let private_key = BitcoinPrivateKey::<N>::from_secp256k1_secret_key(&secret_key, true);
+                    assert_eq!(
+                        *x_only_public_key,
+                        hex::encode(private_key.to_public_key().to_x_only_public_key())
+                    );
+
+                    let mut aux = [0u8; 32];
+                    aux.copy_from_slice(&hex::decode(aux_rand).unwrap());
+                    let message = hex::decode(message).unwrap();
+                    let signature = private_key.sign_schnorr(&message, &aux).unwrap();
+                    assert_eq!(*expected_signature, hex::encode(&signature[..]));
+                    assert!(private_key.to_public_key().verify_schnorr(&message, &signature));
+                },
+            );
+        }
+    }
+
+    mod new_with_entropy {
+        use super::*;
+        use wagyu_model::EntropySource;
+
+        type N = Mainnet;
+
+        /// A deterministic entropy source counting up from zero.
+        struct CountingSource(u8);
+
+        impl EntropySource for CountingSource {
+            fn fill_entropy(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+                dest.iter_mut().for_each(|byte| {
+                    *byte = self.0;
+                    self.0 = self.0.wrapping_add(1);
+                });
+                Ok(())
+            }
+        }
+
+        #[test]
+        fn new_with_entropy() {
+            let private_key = BitcoinPrivateKey::<N>::new_with_entropy(&mut CountingSource(0)).unwrap();
+            assert_eq!(
+                "KwDidQJHSE67VJ6MWRvbBKAxhD3F48DvqRT6JRqrjd7MHLBjGF7V",
+                private_key.to_string()
+            );
+            assert_eq!(
+                "036d6caac248af96f6afa7f904f550253a0f3ef3f5aa2fe6838a95b216691468e2",
+                private_key.to_public_key().to_string()
+            );
+            assert_eq!(
+                "16ZbRYV2f1NNuNQ9FDYyUMC2d1cjGS2G3L",
+                private_key.to_address(&BitcoinFormat::P2PKH).unwrap().to_string()
+            );
+        }
+    }
 }
@@ -0,0 +1,2006 @@
+//!
+//! PSBT
+//!
+//! This module contains partially signed Bitcoin transactions (PSBTs), which carry an unsigned transaction
+//! and the metadata of its inputs and outputs between the roles of creating, updating, signing, combining,
+//! finalizing, and extracting the transaction. Version 2 PSBTs carry the fields of the transaction in the maps
+//! of its inputs and outputs, so that inputs and outputs can be added while their flags are modifiable.
+//! https://github.com/bitcoin/bips/blob/master/bip-0174.mediawiki
+//! https://github.com/bitcoin/bips/blob/master/bip-0370.mediawiki
+//!
+
+use crate::address::BitcoinAddress;
+use crate::amount::BitcoinAmount;
+use crate::extended_private_key::BitcoinExtendedPrivateKey;
+use crate::format::BitcoinFormat;
+use crate::network::BitcoinNetwork;
+use crate::private_key::BitcoinPrivateKey;
+use crate::script::{BitcoinScript, BitcoinScriptInstruction};
+use crate::transaction::{
+    input_size, variable_length_integer, weight_to_fee, BitcoinTransaction, BitcoinTransactionInput,
+    BitcoinTransactionOutput, BitcoinTransactionParameters, BitcoinTransactionSize, Opcode, Outpoint, SignatureHash,
+    LOCK_TIME_THRESHOLD, MAX_SIGNATURE_LENGTH,
+};
+use wagyu_model::no_std::*;
+use wagyu_model::{
+    crypto::{hash160, secp256k1_sign},
+    AddressError, ChildIndex, DerivationPath, DerivationPathError, ExtendedPrivateKey, ExtendedPrivateKeyError,
+    KeyOrigin, PrivateKey, RelativeDerivationPath, Transaction, TransactionError,
+};
+
+use base64ct::{Base64, Encoding};
+use core::{fmt, ops::RangeInclusive, str::FromStr};
+use sha2::{Digest, Sha256};
+
+/// The magic bytes which begin a PSBT, "psbt" followed by 0xff
+pub const PSBT_MAGIC: [u8; 5] = [0x70, 0x73, 0x62, 0x74, 0xff];
+
+/// The flag of a version 2 PSBT whose inputs can be added or removed
+pub const PSBT_MODIFIABLE_INPUTS: u8 = 0x01;
+/// The flag of a version 2 PSBT whose outputs can be added or removed
+pub const PSBT_MODIFIABLE_OUTPUTS: u8 = 0x02;
+/// The flag of a version 2 PSBT which has a signature with SIGHASH_SINGLE
+pub const PSBT_MODIFIABLE_SIGHASH_SINGLE: u8 = 0x04;
+
+/// The key-value pairs of a map of a PSBT
+type PsbtMap = Vec<(Vec<u8>, Vec<u8>)>;
+
+// The key types of the global map
+const PSBT_GLOBAL_UNSIGNED_TX: u8 = 0x00;
+const PSBT_GLOBAL_TX_VERSION: u8 = 0x02;
+const PSBT_GLOBAL_FALLBACK_LOCKTIME: u8 = 0x03;
+const PSBT_GLOBAL_INPUT_COUNT: u8 = 0x04;
+const PSBT_GLOBAL_OUTPUT_COUNT: u8 = 0x05;
+const PSBT_GLOBAL_TX_MODIFIABLE: u8 = 0x06;
+const PSBT_GLOBAL_VERSION: u8 = 0xfb;
+
+// The key types of an input map
+const PSBT_IN_NON_WITNESS_UTXO: u8 = 0x00;
+const PSBT_IN_WITNESS_UTXO: u8 = 0x01;
+const PSBT_IN_PARTIAL_SIG: u8 = 0x02;
+const PSBT_IN_SIGHASH_TYPE: u8 = 0x03;
+const PSBT_IN_REDEEM_SCRIPT: u8 = 0x04;
+const PSBT_IN_WITNESS_SCRIPT: u8 = 0x05;
+const PSBT_IN_BIP32_DERIVATION: u8 = 0x06;
+const PSBT_IN_FINAL_SCRIPTSIG: u8 = 0x07;
+const PSBT_IN_FINAL_SCRIPTWITNESS: u8 = 0x08;
+const PSBT_IN_PREVIOUS_TXID: u8 = 0x0e;
+const PSBT_IN_OUTPUT_INDEX: u8 = 0x0f;
+const PSBT_IN_SEQUENCE: u8 = 0x10;
+const PSBT_IN_REQUIRED_TIME_LOCKTIME: u8 = 0x11;
+const PSBT_IN_REQUIRED_HEIGHT_LOCKTIME: u8 = 0x12;
+
+// The key types of an output map
+const PSBT_OUT_REDEEM_SCRIPT: u8 = 0x00;
+const PSBT_OUT_WITNESS_SCRIPT: u8 = 0x01;
+const PSBT_OUT_BIP32_DERIVATION: u8 = 0x02;
+const PSBT_OUT_AMOUNT: u8 = 0x03;
+const PSBT_OUT_SCRIPT: u8 = 0x04;
+
+/// Represents the metadata of an input of a PSBT
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PsbtInput {
+    /// The serialized transaction of the spent output, for an input spending a non-witness output
+    pub non_witness_utxo: Option<Vec<u8>>,
+    /// The spent output, for an input spending a witness output
+    pub witness_utxo: Option<BitcoinTransactionOutput>,
+    /// The signatures of the input with their sighash type byte, by the public keys which made them
+    pub partial_signatures: Vec<(Vec<u8>, Vec<u8>)>,
+    /// The sighash type of the signatures of the input, or SIGHASH_ALL if none is given
+    pub sighash_type: Option<u32>,
+    /// The redeem script of a P2SH output
+    pub redeem_script: Option<Vec<u8>>,
+    /// The witness script of a P2WSH output
+    pub witness_script: Option<Vec<u8>>,
+    /// The key origins of the public keys of the input
+    pub bip32_derivations: Vec<(Vec<u8>, KeyOrigin)>,
+    /// The scriptSig of the finalized input
+    pub final_script_sig: Option<Vec<u8>>,
+    /// The witness stack of the finalized input
+    pub final_script_witness: Option<Vec<Vec<u8>>>,
+    /// The Unix time which the lock time of the transaction must be at least, in a version 2 PSBT
+    pub required_time_lock_time: Option<u32>,
+    /// The block height which the lock time of the transaction must be at least, in a version 2 PSBT
+    pub required_height_lock_time: Option<u32>,
+    /// The key-value pairs of unknown types, which are passed through
+    pub unknown: PsbtMap,
+}
+
+impl PsbtInput {
+    /// Returns true if the input has a final scriptSig or witness stack.
+    pub fn is_finalized(&self) -> bool {
+        self.final_script_sig.is_some() || self.final_script_witness.is_some()
+    }
+
+    /// Adds the key origin of the given public key, replacing any previous key origin of the public key.
+    pub fn add_bip32_derivation(&mut self, public_key: &[u8], origin: KeyOrigin) {
+        insert(&mut self.bip32_derivations, public_key.to_vec(), origin);
+    }
+
+    /// Returns the signature of the given public key, if the input has one.
+    fn signature(&self, public_key: &[u8]) -> Option<&Vec<u8>> {
+        self.partial_signatures
+            .iter()
+            .find(|(key, _)| key.as_slice() == public_key)
+            .map(|(_, signature)| signature)
+    }
+
+    /// Returns the signatures of the P2PK or multisig script, in the order of its public keys,
+    /// preceded by the dummy element of OP_CHECKMULTISIG, or `None` if the input has too few signatures.
+    fn script_signatures(&self, vin: usize, script: &[u8]) -> Result<Option<Vec<Vec<u8>>>, PsbtError> {
+        let script = BitcoinScript::new(script);
+        if let Some(public_key) = script.to_p2pk_public_key() {
+            return Ok(self.signature(&public_key).map(|signature| vec![signature.clone()]));
+        }
+        match script.to_multisig() {
+            Some((threshold, public_keys)) => {
+                let signatures: Vec<Vec<u8>> = public_keys
+                    .iter()
+                    .filter_map(|public_key| self.signature(public_key).cloned())
+                    .take(threshold)
+                    .collect();
+                match signatures.len() == threshold {
+                    true => Ok(Some([vec![vec![]], signatures].concat())),
+                    false => Ok(None),
+                }
+            }
+            None => Err(PsbtError::UnsupportedScript(vin)),
+        }
+    }
+
+    /// Reads the input from the key-value pairs of its map.
+    fn read(pairs: PsbtMap) -> Result<Self, PsbtError> {
+        let mut input = Self::default();
+        for (key, value) in pairs {
+            match (key[0], key.len()) {
+                (PSBT_IN_NON_WITNESS_UTXO, 1) => input.non_witness_utxo = Some(value),
+                (PSBT_IN_WITNESS_UTXO, 1) => {
+                    let mut reader = &value[..];
+                    input.witness_utxo = Some(BitcoinTransactionOutput::read(&mut reader)?);
+                }
+                (PSBT_IN_PARTIAL_SIG, 34) | (PSBT_IN_PARTIAL_SIG, 66) => {
+                    input.partial_signatures.push((key[1..].to_vec(), value))
+                }
+                (PSBT_IN_SIGHASH_TYPE, 1) => input.sighash_type = Some(read_u32(&key, &value)?),
+                (PSBT_IN_REDEEM_SCRIPT, 1) => input.redeem_script = Some(value),
+                (PSBT_IN_WITNESS_SCRIPT, 1) => input.witness_script = Some(value),
+                (PSBT_IN_BIP32_DERIVATION, 34) | (PSBT_IN_BIP32_DERIVATION, 66) => input
+                    .bip32_derivations
+                    .push((key[1..].to_vec(), read_key_origin(&key, &value)?)),
+                (PSBT_IN_FINAL_SCRIPTSIG, 1) => input.final_script_sig = Some(value),
+                (PSBT_IN_FINAL_SCRIPTWITNESS, 1) => input.final_script_witness = Some(read_witness(&key, &value)?),
+                (PSBT_IN_REQUIRED_TIME_LOCKTIME, 1) => match read_u32(&key, &value)? {
+                    lock_time if lock_time >= LOCK_TIME_THRESHOLD => input.required_time_lock_time = Some(lock_time),
+                    _ => return Err(PsbtError::InvalidValue(hex::encode(key))),
+                },
+                (PSBT_IN_REQUIRED_HEIGHT_LOCKTIME, 1) => match read_u32(&key, &value)? {
+                    lock_time if lock_time > 0 && lock_time < LOCK_TIME_THRESHOLD => {
+                        input.required_height_lock_time = Some(lock_time)
+                    }
+                    _ => return Err(PsbtError::InvalidValue(hex::encode(key))),
+                },
+                (PSBT_IN_NON_WITNESS_UTXO..=PSBT_IN_FINAL_SCRIPTWITNESS, _)
+                | (PSBT_IN_PREVIOUS_TXID..=PSBT_IN_REQUIRED_HEIGHT_LOCKTIME, _) => {
+                    return Err(PsbtError::InvalidKey(hex::encode(key)))
+                }
+                _ => input.unknown.push((key, value)),
+            }
+        }
+        Ok(input)
+    }
+
+    /// Writes the key-value pairs of the input map, with the given key-value pairs of the transaction
+    /// of a version 2 PSBT, followed by the separator of the map.
+    fn write(&self, psbt: &mut Vec<u8>, transaction_pairs: &[(Vec<u8>, Vec<u8>)]) -> Result<(), PsbtError> {
+        if let Some(transaction) = &self.non_witness_utxo {
+            write_pair(psbt, &[PSBT_IN_NON_WITNESS_UTXO], transaction)?;
+        }
+        if let Some(output) = &self.witness_utxo {
+            write_pair(psbt, &[PSBT_IN_WITNESS_UTXO], &output.serialize()?)?;
+        }
+        for (public_key, signature) in &self.partial_signatures {
+            write_pair(psbt, &[&[PSBT_IN_PARTIAL_SIG], &public_key[..]].concat(), signature)?;
+        }
+        if let Some(sighash_type) = self.sighash_type {
+            write_pair(psbt, &[PSBT_IN_SIGHASH_TYPE], &sighash_type.to_le_bytes())?;
+        }
+        if let Some(redeem_script) = &self.redeem_script {
+            write_pair(psbt, &[PSBT_IN_REDEEM_SCRIPT], redeem_script)?;
+        }
+        if let Some(witness_script) = &self.witness_script {
+            write_pair(psbt, &[PSBT_IN_WITNESS_SCRIPT], witness_script)?;
+        }
+        for (public_key, origin) in &self.bip32_derivations {
+            let key = [&[PSBT_IN_BIP32_DERIVATION], &public_key[..]].concat();
+            write_pair(psbt, &key, &write_key_origin(origin)?)?;
+        }
+        if let Some(script_sig) = &self.final_script_sig {
+            write_pair(psbt, &[PSBT_IN_FINAL_SCRIPTSIG], script_sig)?;
+        }
+        if let Some(witness) = &self.final_script_witness {
+            write_pair(psbt, &[PSBT_IN_FINAL_SCRIPTWITNESS], &write_witness(witness)?)?;
+        }
+        for (key, value) in transaction_pairs {
+            write_pair(psbt, key, value)?;
+        }
+        if let Some(lock_time) = self.required_time_lock_time {
+            write_pair(psbt, &[PSBT_IN_REQUIRED_TIME_LOCKTIME], &lock_time.to_le_bytes())?;
+        }
+        if let Some(lock_time) = self.required_height_lock_time {
+            write_pair(psbt, &[PSBT_IN_REQUIRED_HEIGHT_LOCKTIME], &lock_time.to_le_bytes())?;
+        }
+        for (key, value) in &self.unknown {
+            write_pair(psbt, key, value)?;
+        }
+        psbt.push(0x00);
+        Ok(())
+    }
+
+    /// Adds the metadata of the other input which this input does not have.
+    fn combine(&mut self, other: &Self) {
+        self.non_witness_utxo = self.non_witness_utxo.take().or_else(|| other.non_witness_utxo.clone());
+        self.witness_utxo = self.witness_utxo.take().or_else(|| other.witness_utxo.clone());
+        self.sighash_type = self.sighash_type.or(other.sighash_type);
+        self.redeem_script = self.redeem_script.take().or_else(|| other.redeem_script.clone());
+        self.witness_script = self.witness_script.take().or_else(|| other.witness_script.clone());
+        self.final_script_sig = self.final_script_sig.take().or_else(|| other.final_script_sig.clone());
+        self.final_script_witness = self
+            .final_script_witness
+            .take()
+            .or_else(|| other.final_script_witness.clone());
+        self.required_time_lock_time = self.required_time_lock_time.or(other.required_time_lock_time);
+        self.required_height_lock_time = self.required_height_lock_time.or(other.required_height_lock_time);
+        combine(&mut self.partial_signatures, &other.partial_signatures);
+        combine(&mut self.bip32_derivations, &other.bip32_derivations);
+        combine(&mut self.unknown, &other.unknown);
+    }
+}
+
+/// Represents the progress of the signatures of an input of a PSBT towards finalizing it
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PsbtSigningStatus {
+    /// The number of signatures required to finalize the input, or zero if it is finalized
+    pub threshold: usize,
+    /// The public keys which have signed the input
+    pub signed: Vec<Vec<u8>>,
+    /// The public keys which can sign the input and have not, as far as they are known
+    pub unsigned: Vec<Vec<u8>>,
+}
+
+impl PsbtSigningStatus {
+    /// Returns true if the input has the signatures required to finalize it.
+    pub fn is_complete(&self) -> bool {
+        self.signed.len() >= self.threshold
+    }
+}
+
+/// Represents the metadata of an output of a PSBT
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PsbtOutput {
+    /// The redeem script of a P2SH output
+    pub redeem_script: Option<Vec<u8>>,
+    /// The witness script of a P2WSH output
+    pub witness_script: Option<Vec<u8>>,
+    /// The key origins of the public keys of the output, e.g. to identify a change output
+    pub bip32_derivations: Vec<(Vec<u8>, KeyOrigin)>,
+    /// The key-value pairs of unknown types, which are passed through
+    pub unknown: PsbtMap,
+}
+
+impl PsbtOutput {
+    /// Adds the key origin of the given public key, replacing any previous key origin of the public key.
+    pub fn add_bip32_derivation(&mut self, public_key: &[u8], origin: KeyOrigin) {
+        insert(&mut self.bip32_derivations, public_key.to_vec(), origin);
+    }
+
+    /// Reads the output from the key-value pairs of its map.
+    fn read(pairs: PsbtMap) -> Result<Self, PsbtError> {
+        let mut output = Self::default();
+        for (key, value) in pairs {
+            match (key[0], key.len()) {
+                (PSBT_OUT_REDEEM_SCRIPT, 1) => output.redeem_script = Some(value),
+                (PSBT_OUT_WITNESS_SCRIPT, 1) => output.witness_script = Some(value),
+                (PSBT_OUT_BIP32_DERIVATION, 34) | (PSBT_OUT_BIP32_DERIVATION, 66) => output
+                    .bip32_derivations
+                    .push((key[1..].to_vec(), read_key_origin(&key, &value)?)),
+                (PSBT_OUT_REDEEM_SCRIPT..=PSBT_OUT_SCRIPT, _) => return Err(PsbtError::InvalidKey(hex::encode(key))),
+                _ => output.unknown.push((key, value)),
+            }
+        }
+        Ok(output)
+    }
+
+    /// Writes the key-value pairs of the output map, with the given key-value pairs of the transaction
+    /// of a version 2 PSBT, followed by the separator of the map.
+    fn write(&self, psbt: &mut Vec<u8>, transaction_pairs: &[(Vec<u8>, Vec<u8>)]) -> Result<(), PsbtError> {
+        if let Some(redeem_script) = &self.redeem_script {
+            write_pair(psbt, &[PSBT_OUT_REDEEM_SCRIPT], redeem_script)?;
+        }
+        if let Some(witness_script) = &self.witness_script {
+            write_pair(psbt, &[PSBT_OUT_WITNESS_SCRIPT], witness_script)?;
+        }
+        for (public_key, origin) in &self.bip32_derivations {
+            let key = [&[PSBT_OUT_BIP32_DERIVATION], &public_key[..]].concat();
+            write_pair(psbt, &key, &write_key_origin(origin)?)?;
+        }
+        for (key, value) in transaction_pairs {
+            write_pair(psbt, key, value)?;
+        }
+        for (key, value) in &self.unknown {
+            write_pair(psbt, key, value)?;
+        }
+        psbt.push(0x00);
+        Ok(())
+    }
+
+    /// Adds the metadata of the other output which this output does not have.
+    fn combine(&mut self, other: &Self) {
+        self.redeem_script = self.redeem_script.take().or_else(|| other.redeem_script.clone());
+        self.witness_script = self.witness_script.take().or_else(|| other.witness_script.clone());
+        combine(&mut self.bip32_derivations, &other.bip32_derivations);
+        combine(&mut self.unknown, &other.unknown);
+    }
+}
+
+/// Represents a partially signed Bitcoin transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitcoinPsbt<N: BitcoinNetwork> {
+    /// The version of the PSBT, either 0 or 2
+    version: u32,
+    /// The unsigned transaction, with empty scriptSigs and witnesses, whose lock time
+    /// is the fallback lock time of a version 2 PSBT
+    transaction: BitcoinTransactionParameters<N>,
+    /// The flags of a version 2 PSBT whose inputs, outputs, or signature hash types can be modified
+    modifiable: u8,
+    /// The key-value pairs of the global map of unknown types, e.g. extended public keys, which are passed through
+    unknown: PsbtMap,
+    /// The metadata of each input of the transaction
+    inputs: Vec<PsbtInput>,
+    /// The metadata of each output of the transaction
+    outputs: Vec<PsbtOutput>,
+}
+
+impl<N: BitcoinNetwork> BitcoinPsbt<N> {
+    /// Returns a new PSBT of the given unsigned transaction, with no metadata.
+    pub fn new(transaction: &BitcoinTransactionParameters<N>) -> Result<Self, PsbtError> {
+        if transaction
+            .inputs
+            .iter()
+            .any(|input| !input.script_sig.is_empty() || !input.witnesses.is_empty())
+        {
+            return Err(PsbtError::SignedTransaction);
+        }
+        // The unsigned transaction only commits to the outpoint, sequence, and outputs of each input,
+        // so each input is normalized to the input read from its serialization
+        let transaction = BitcoinTransactionParameters {
+            inputs: transaction
+                .inputs
+                .iter()
+                .map(|input| BitcoinTransactionInput::read(&mut &input.serialize(true)?[..]))
+                .collect::<Result<_, _>>()?,
+            segwit_flag: false,
+            ..transaction.clone()
+        };
+
+        Ok(Self {
+            version: 0,
+            inputs: vec![PsbtInput::default(); transaction.inputs.len()],
+            outputs: vec![PsbtOutput::default(); transaction.outputs.len()],
+            transaction,
+            modifiable: 0,
+            unknown: vec![],
+        })
+    }
+
+    /// Returns the version of the PSBT, either 0 or 2.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Returns the PSBT in the given version. A version 0 PSBT has the lock time of its transaction,
+    /// and a version 2 PSBT has the lock time of its transaction as its fallback lock time.
+    pub fn to_version(&self, version: u32) -> Result<Self, PsbtError> {
+        let mut psbt = self.clone();
+        match version {
+            0 => {
+                psbt.transaction.lock_time = self.lock_time()?;
+                psbt.modifiable = 0;
+                psbt.inputs.iter_mut().for_each(|input| {
+                    input.required_time_lock_time = None;
+                    input.required_height_lock_time = None;
+                });
+            }
+            2 if self.transaction.version < 2 => {
+                return Err(PsbtError::InvalidTransactionVersion(self.transaction.version))
+            }
+            2 => psbt.unknown.retain(|(key, _)| key[..] != [PSBT_GLOBAL_VERSION]),
+            version => return Err(PsbtError::UnsupportedVersion(version)),
+        };
+        psbt.version = version;
+        Ok(psbt)
+    }
+
+    /// Returns the flags of the version 2 PSBT whose inputs, outputs, or signature hash types can be modified.
+    pub fn modifiable(&self) -> u8 {
+        self.modifiable
+    }
+
+    /// Sets the flags of the version 2 PSBT whose inputs, outputs, or signature hash types can be modified,
+    /// e.g. `PSBT_MODIFIABLE_INPUTS | PSBT_MODIFIABLE_OUTPUTS` for a transaction constructed incrementally.
+    pub fn set_modifiable(&mut self, modifiable: u8) -> Result<(), PsbtError> {
+        match self.version {
+            2 => {
+                self.modifiable = modifiable;
+                Ok(())
+            }
+            version => Err(PsbtError::UnsupportedVersion(version)),
+        }
+    }
+
+    /// Adds the given unsigned input and its metadata to the version 2 PSBT, if its inputs are modifiable
+    /// and the required lock time of the input is compatible with the required lock times of the other inputs.
+    pub fn add_input(&mut self, input: &BitcoinTransactionInput<N>, metadata: PsbtInput) -> Result<(), PsbtError> {
+        if self.version != 2 {
+            return Err(PsbtError::UnsupportedVersion(self.version));
+        }
+        if self.modifiable & PSBT_MODIFIABLE_INPUTS == 0 {
+            return Err(PsbtError::UnmodifiableInputs);
+        }
+        if !input.script_sig.is_empty() || !input.witnesses.is_empty() {
+            return Err(PsbtError::SignedTransaction);
+        }
+
+        let input = BitcoinTransactionInput::<N>::read(&mut &input.serialize(true)?[..])?;
+        self.transaction.inputs.push(input);
+        self.inputs.push(metadata);
+        if let Err(error) = self.lock_time() {
+            self.transaction.inputs.pop();
+            self.inputs.pop();
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    /// Adds the given output and its metadata to the version 2 PSBT, if its outputs are modifiable.
+    pub fn add_output(&mut self, output: &BitcoinTransactionOutput, metadata: PsbtOutput) -> Result<(), PsbtError> {
+        if self.version != 2 {
+            return Err(PsbtError::UnsupportedVersion(self.version));
+        }
+        if self.modifiable & PSBT_MODIFIABLE_OUTPUTS == 0 {
+            return Err(PsbtError::UnmodifiableOutputs);
+        }
+        self.transaction.outputs.push(output.clone());
+        self.outputs.push(metadata);
+        Ok(())
+    }
+
+    /// Returns the lock time of the transaction. The lock time of a version 2 PSBT is the greatest
+    /// required lock time of its inputs, preferring block heights if every input allows one,
+    /// or its fallback lock time if no input requires a lock time.
+    pub fn lock_time(&self) -> Result<u32, PsbtError> {
+        let required: Vec<&PsbtInput> = self
+            .inputs
+            .iter()
+            .filter(|input| input.required_time_lock_time.is_some() || input.required_height_lock_time.is_some())
+            .collect();
+        if self.version == 0 || required.is_empty() {
+            return Ok(self.transaction.lock_time);
+        }
+
+        let heights: Option<Vec<u32>> = required.iter().map(|input| input.required_height_lock_time).collect();
+        let times: Option<Vec<u32>> = required.iter().map(|input| input.required_time_lock_time).collect();
+        match (heights, times) {
+            (Some(heights), _) => Ok(heights.into_iter().max().unwrap_or(0)),
+            (None, Some(times)) => Ok(times.into_iter().max().unwrap_or(0)),
+            (None, None) => Err(PsbtError::InvalidLockTime),
+        }
+    }
+
+    /// Returns the unsigned transaction of the PSBT, with its lock time.
+    pub fn unsigned_transaction(&self) -> Result<BitcoinTransactionParameters<N>, PsbtError> {
+        Ok(BitcoinTransactionParameters {
+            lock_time: self.lock_time()?,
+            ..self.transaction.clone()
+        })
+    }
+
+    /// Returns the metadata of the inputs.
+    pub fn inputs(&self) -> &[PsbtInput] {
+        &self.inputs
+    }
+
+    /// Returns the metadata of the inputs, for an updater to add to.
+    pub fn inputs_mut(&mut self) -> &mut [PsbtInput] {
+        &mut self.inputs
+    }
+
+    /// Returns the metadata of the outputs.
+    pub fn outputs(&self) -> &[PsbtOutput] {
+        &self.outputs
+    }
+
+    /// Returns the metadata of the outputs, for an updater to add to.
+    pub fn outputs_mut(&mut self) -> &mut [PsbtOutput] {
+        &mut self.outputs
+    }
+
+    /// Signs each input which the private key can sign for and which has the metadata to sign it,
+    /// and returns the number of inputs signed.
+    pub fn sign(&mut self, private_key: &BitcoinPrivateKey<N>) -> Result<usize, PsbtError> {
+        let mut signed = 0;
+        for vin in 0..self.inputs.len() {
+            if self.sign_input(vin, private_key)? {
+                signed += 1;
+            }
+        }
+        Ok(signed)
+    }
+
+    /// Signs each input with the keys derived from the given master key along the BIP32 derivations
+    /// of the input with the fingerprint of the master key, and returns the number of signatures made.
+    pub fn sign_with_extended_private_key(
+        &mut self,
+        master_extended_private_key: &BitcoinExtendedPrivateKey<N>,
+    ) -> Result<usize, PsbtError> {
+        let fingerprint = master_extended_private_key.fingerprint();
+        let mut signed = 0;
+        for vin in 0..self.inputs.len() {
+            let paths: Vec<RelativeDerivationPath> = self.inputs[vin]
+                .bip32_derivations
+                .iter()
+                .filter(|(_, origin)| origin.fingerprint() == fingerprint)
+                .map(|(_, origin)| origin.path().clone())
+                .collect();
+            for path in paths {
+                let private_key = master_extended_private_key.derive_relative(&path)?.to_private_key();
+                if self.sign_input(vin, &private_key)? {
+                    signed += 1;
+                }
+            }
+        }
+        Ok(signed)
+    }
+
+    /// Adds the metadata and signatures of the other PSBT of the same unsigned transaction.
+    pub fn combine(&mut self, other: &Self) -> Result<(), PsbtError> {
+        if self.version != other.version || self.transaction != other.transaction {
+            return Err(PsbtError::MismatchedTransaction);
+        }
+        // Inputs and outputs remain modifiable only if neither PSBT has a signature committing to them
+        self.modifiable = (self.modifiable & other.modifiable & (PSBT_MODIFIABLE_INPUTS | PSBT_MODIFIABLE_OUTPUTS))
+            | ((self.modifiable | other.modifiable) & PSBT_MODIFIABLE_SIGHASH_SINGLE);
+        self.inputs
+            .iter_mut()
+            .zip(&other.inputs)
+            .for_each(|(input, other)| input.combine(other));
+        self.outputs
+            .iter_mut()
+            .zip(&other.outputs)
+            .for_each(|(output, other)| output.combine(other));
+        combine(&mut self.unknown, &other.unknown);
+        Ok(())
+    }
+
+    /// Adds the signature of the given public key to the input at the given index, such as the signature
+    /// of a cosigner of a multisig input, once it is verified against the signature hash of the input.
+    pub fn add_signature(&mut self, vin: usize, public_key: &[u8], signature: &[u8]) -> Result<(), PsbtError> {
+        if vin >= self.inputs.len() {
+            return Err(PsbtError::MissingInput(vin));
+        }
+        if self.inputs[vin].is_finalized() {
+            return Ok(());
+        }
+        let sighash = self.verify_signature(vin, public_key, signature)?;
+        insert(
+            &mut self.inputs[vin].partial_signatures,
+            public_key.to_vec(),
+            signature.to_vec(),
+        );
+        self.commit_signature(sighash);
+        Ok(())
+    }
+
+    /// Returns an error if a signature of an input which is not finalized is not made by a public key
+    /// which can sign the input, or does not verify against the signature hash of the input.
+    pub fn verify_signatures(&self) -> Result<(), PsbtError> {
+        for (vin, input) in self.inputs.iter().enumerate() {
+            if input.is_finalized() {
+                continue;
+            }
+            for (public_key, signature) in &input.partial_signatures {
+                self.verify_signature(vin, public_key, signature)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the public keys which have signed the input at the given index and which can still sign it,
+    /// with the number of signatures required to finalize it. The public key of a P2PKH, P2WPKH, or
+    /// P2SH-P2WPKH input is known once it has a signature or a key origin.
+    pub fn signing_status(&self, vin: usize) -> Result<PsbtSigningStatus, PsbtError> {
+        let input = self.inputs.get(vin).ok_or(PsbtError::MissingInput(vin))?;
+        if input.is_finalized() {
+            return Ok(PsbtSigningStatus::default());
+        }
+
+        let outpoint = self.to_outpoint(vin)?;
+        let (threshold, public_keys) = match (
+            outpoint.address.as_ref().map(BitcoinAddress::format),
+            &outpoint.redeem_script,
+            &outpoint.script_pub_key,
+        ) {
+            (Some(BitcoinFormat::P2WSH), Some(script), _) | (None, _, Some(script)) => {
+                let script = BitcoinScript::new(script);
+                match (script.to_p2pk_public_key(), script.to_multisig()) {
+                    (Some(public_key), _) => (1, vec![public_key]),
+                    (None, Some(multisig)) => multisig,
+                    (None, None) => return Err(PsbtError::UnsupportedScript(vin)),
+                }
+            }
+            _ => {
+                let mut public_keys: Vec<Vec<u8>> = vec![];
+                for public_key in input
+                    .partial_signatures
+                    .iter()
+                    .map(|(public_key, _)| public_key)
+                    .chain(input.bip32_derivations.iter().map(|(public_key, _)| public_key))
+                {
+                    if is_signer(&outpoint, public_key) && !public_keys.contains(public_key) {
+                        public_keys.push(public_key.clone());
+                    }
+                }
+                (1, public_keys)
+            }
+        };
+
+        let (signed, unsigned) = public_keys
+            .into_iter()
+            .partition(|public_key| input.signature(public_key).is_some());
+        Ok(PsbtSigningStatus {
+            threshold,
+            signed,
+            unsigned,
+        })
+    }
+
+    /// Finalizes each input which has the signatures to complete its scriptSig and witness stack,
+    /// removing the metadata which is no longer needed from the finalized inputs.
+    pub fn finalize(&mut self) -> Result<(), PsbtError> {
+        for vin in 0..self.inputs.len() {
+            self.finalize_input(vin)?;
+        }
+        Ok(())
+    }
+
+    /// Returns true if every input is finalized.
+    pub fn is_finalized(&self) -> bool {
+        self.inputs.iter().all(PsbtInput::is_finalized)
+    }
+
+    /// Returns the signed transaction of the finalized PSBT.
+    pub fn extract(&self) -> Result<BitcoinTransaction<N>, PsbtError> {
+        let mut transaction = self.unsigned_transaction()?;
+        for (vin, (input, psbt_input)) in transaction.inputs.iter_mut().zip(&self.inputs).enumerate() {
+            if !psbt_input.is_finalized() {
+                return Err(PsbtError::UnfinalizedInput(vin));
+            }
+            input.script_sig = psbt_input.final_script_sig.clone().unwrap_or_default();
+            input.witnesses = psbt_input
+                .final_script_witness
+                .iter()
+                .flatten()
+                .map(|item| Ok([variable_length_integer(item.len() as u64)?, item.clone()].concat()))
+                .collect::<Result<Vec<_>, TransactionError>>()?;
+            input.is_signed = true;
+        }
+        transaction.segwit_flag = transaction.inputs.iter().any(|input| !input.witnesses.is_empty());
+        Ok(BitcoinTransaction::new(&transaction)?)
+    }
+
+    /// Returns the size of the extracted transaction once every input is finalized, estimating
+    /// the signatures of the inputs which are not, so that the fee can be decided before signing.
+    pub fn estimate_size(&self) -> Result<BitcoinTransactionSize, PsbtError> {
+        let input_sizes = (0..self.inputs.len())
+            .map(|vin| self.estimate_input_size(vin))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(self.unsigned_transaction()?.estimate_size_with_inputs(&input_sizes)?)
+    }
+
+    /// Returns the fee of the extracted transaction at the given fee rate (in Satoshi per virtual byte),
+    /// as estimated by `estimate_size`.
+    pub fn estimate_fee(&self, fee_rate: u64) -> Result<BitcoinAmount, PsbtError> {
+        Ok(weight_to_fee(self.estimate_size()?.weight(), fee_rate))
+    }
+
+    /// Returns the PSBT from its serialization, in version 0 or version 2.
+    pub fn from_bytes(psbt: &[u8]) -> Result<Self, PsbtError> {
+        if psbt.len() < PSBT_MAGIC.len() || psbt[..PSBT_MAGIC.len()] != PSBT_MAGIC {
+            return Err(PsbtError::InvalidMagic);
+        }
+        let mut reader = PsbtReader(&psbt[PSBT_MAGIC.len()..]);
+
+        let mut global = reader.read_map()?;
+        let version = match global.iter().find(|(key, _)| key[..] == [PSBT_GLOBAL_VERSION]) {
+            Some((key, value)) => read_u32(key, value)?,
+            None => 0,
+        };
+        let (mut transaction, input_count, output_count, modifiable) = match version {
+            0 => {
+                reject_keys(&global, &[PSBT_GLOBAL_TX_VERSION..=PSBT_GLOBAL_TX_MODIFIABLE])?;
+                let transaction = take_value(&mut global, PSBT_GLOBAL_UNSIGNED_TX)?;
+                let transaction = transaction.ok_or(PsbtError::MissingUnsignedTransaction)?;
+                let transaction = BitcoinTransactionParameters::<N>::read(&transaction[..])?;
+                let (input_count, output_count) = (transaction.inputs.len(), transaction.outputs.len());
+                (transaction, input_count, output_count, 0)
+            }
+            2 => {
+                reject_keys(&global, &[PSBT_GLOBAL_UNSIGNED_TX..=PSBT_GLOBAL_UNSIGNED_TX])?;
+                take_value(&mut global, PSBT_GLOBAL_VERSION)?;
+                let transaction = BitcoinTransactionParameters {
+                    version: read_u32(
+                        &[PSBT_GLOBAL_TX_VERSION],
+                        &require_value(&mut global, PSBT_GLOBAL_TX_VERSION)?,
+                    )?,
+                    inputs: vec![],
+                    outputs: vec![],
+                    lock_time: match take_value(&mut global, PSBT_GLOBAL_FALLBACK_LOCKTIME)? {
+                        Some(value) => read_u32(&[PSBT_GLOBAL_FALLBACK_LOCKTIME], &value)?,
+                        None => 0,
+                    },
+                    segwit_flag: false,
+                };
+                if transaction.version < 2 {
+                    return Err(PsbtError::InvalidTransactionVersion(transaction.version));
+                }
+                let input_count = require_value(&mut global, PSBT_GLOBAL_INPUT_COUNT)?;
+                let output_count = require_value(&mut global, PSBT_GLOBAL_OUTPUT_COUNT)?;
+                let modifiable = match take_value(&mut global, PSBT_GLOBAL_TX_MODIFIABLE)? {
+                    Some(ref value) if value.len() == 1 => value[0],
+                    Some(_) => return Err(PsbtError::InvalidValue(hex::encode([PSBT_GLOBAL_TX_MODIFIABLE]))),
+                    None => 0,
+                };
+                (
+                    transaction,
+                    read_count(PSBT_GLOBAL_INPUT_COUNT, &input_count)?,
+                    read_count(PSBT_GLOBAL_OUTPUT_COUNT, &output_count)?,
+                    modifiable,
+                )
+            }
+            version => return Err(PsbtError::UnsupportedVersion(version)),
+        };
+
+        let mut inputs = vec![];
+        for _ in 0..input_count {
+            let mut pairs = reader.read_map()?;
+            match version {
+                0 => reject_keys(&pairs, &[PSBT_IN_PREVIOUS_TXID..=PSBT_IN_REQUIRED_HEIGHT_LOCKTIME])?,
+                _ => {
+                    let transaction_id = require_value(&mut pairs, PSBT_IN_PREVIOUS_TXID)?;
+                    let index = require_value(&mut pairs, PSBT_IN_OUTPUT_INDEX)?;
+                    let sequence = take_value(&mut pairs, PSBT_IN_SEQUENCE)?.unwrap_or_else(|| vec![0xff; 4]);
+                    if transaction_id.len() != 32 {
+                        return Err(PsbtError::InvalidValue(hex::encode([PSBT_IN_PREVIOUS_TXID])));
+                    }
+                    read_u32(&[PSBT_IN_OUTPUT_INDEX], &index)?;
+                    read_u32(&[PSBT_IN_SEQUENCE], &sequence)?;
+                    let input = [transaction_id, index, vec![0x00], sequence].concat();
+                    transaction.inputs.push(BitcoinTransactionInput::read(&mut &input[..])?);
+                }
+            }
+            inputs.push(PsbtInput::read(pairs)?);
+        }
+        let mut outputs = vec![];
+        for _ in 0..output_count {
+            let mut pairs = reader.read_map()?;
+            if version == 2 {
+                let amount = require_value(&mut pairs, PSBT_OUT_AMOUNT)?;
+                let script_pub_key = require_value(&mut pairs, PSBT_OUT_SCRIPT)?;
+                if amount.len() != 8 {
+                    return Err(PsbtError::InvalidValue(hex::encode([PSBT_OUT_AMOUNT])));
+                }
+                let output = [
+                    amount,
+                    variable_length_integer(script_pub_key.len() as u64)?,
+                    script_pub_key,
+                ]
+                .concat();
+                transaction
+                    .outputs
+                    .push(BitcoinTransactionOutput::read(&mut &output[..])?);
+            }
+            outputs.push(PsbtOutput::read(pairs)?);
+        }
+        if !reader.0.is_empty() {
+            return Err(PsbtError::InvalidLength);
+        }
+
+        let mut psbt = Self::new(&transaction)?;
+        psbt.version = version;
+        psbt.modifiable = modifiable;
+        psbt.unknown = global;
+        psbt.inputs = inputs;
+        psbt.outputs = outputs;
+        psbt.lock_time()?;
+        Ok(psbt)
+    }
+
+    /// Returns the serialization of the PSBT, in its version.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, PsbtError> {
+        let mut psbt = PSBT_MAGIC.to_vec();
+        match self.version {
+            0 => {
+                for input in &self.inputs {
+                    if input.required_time_lock_time.is_some() {
+                        return Err(PsbtError::InvalidKey(hex::encode([PSBT_IN_REQUIRED_TIME_LOCKTIME])));
+                    }
+                    if input.required_height_lock_time.is_some() {
+                        return Err(PsbtError::InvalidKey(hex::encode([PSBT_IN_REQUIRED_HEIGHT_LOCKTIME])));
+                    }
+                }
+                let transaction = BitcoinTransaction::new(&self.transaction)?.to_transaction_bytes()?;
+                write_pair(&mut psbt, &[PSBT_GLOBAL_UNSIGNED_TX], &transaction)?;
+            }
+            _ => {
+                write_pair(
+                    &mut psbt,
+                    &[PSBT_GLOBAL_TX_VERSION],
+                    &self.transaction.version.to_le_bytes(),
+                )?;
+                if self.transaction.lock_time != 0 {
+                    let lock_time = self.transaction.lock_time.to_le_bytes();
+                    write_pair(&mut psbt, &[PSBT_GLOBAL_FALLBACK_LOCKTIME], &lock_time)?;
+                }
+                let input_count = variable_length_integer(self.inputs.len() as u64)?;
+                write_pair(&mut psbt, &[PSBT_GLOBAL_INPUT_COUNT], &input_count)?;
+                let output_count = variable_length_integer(self.outputs.len() as u64)?;
+                write_pair(&mut psbt, &[PSBT_GLOBAL_OUTPUT_COUNT], &output_count)?;
+                if self.modifiable != 0 {
+                    write_pair(&mut psbt, &[PSBT_GLOBAL_TX_MODIFIABLE], &[self.modifiable])?;
+                }
+                write_pair(&mut psbt, &[PSBT_GLOBAL_VERSION], &self.version.to_le_bytes())?;
+            }
+        }
+        for (key, value) in &self.unknown {
+            write_pair(&mut psbt, key, value)?;
+        }
+        psbt.push(0x00);
+
+        for (input, psbt_input) in self.transaction.inputs.iter().zip(&self.inputs) {
+            let mut pairs = vec![];
+            if self.version == 2 {
+                pairs.push((
+                    vec![PSBT_IN_PREVIOUS_TXID],
+                    input.outpoint.reverse_transaction_id.clone(),
+                ));
+                pairs.push((vec![PSBT_IN_OUTPUT_INDEX], input.outpoint.index.to_le_bytes().to_vec()));
+                if input.sequence != [0xff; 4] {
+                    pairs.push((vec![PSBT_IN_SEQUENCE], input.sequence.clone()));
+                }
+            }
+            psbt_input.write(&mut psbt, &pairs)?;
+        }
+        for (output, psbt_output) in self.transaction.outputs.iter().zip(&self.outputs) {
+            let mut pairs = vec![];
+            if self.version == 2 {
+                pairs.push((vec![PSBT_OUT_AMOUNT], output.amount.0.to_le_bytes().to_vec()));
+                pairs.push((vec![PSBT_OUT_SCRIPT], output.script_pub_key.clone()));
+            }
+            psbt_output.write(&mut psbt, &pairs)?;
+        }
+        Ok(psbt)
+    }
+
+    /// Returns the key-value pairs of the global map and of the map of each input and output of the PSBT
+    /// in version 2, regardless of the version of the PSBT, for a signer which commits to the maps.
+    #[cfg(feature = "ledger")]
+    pub(crate) fn to_maps(&self) -> Result<(PsbtMap, Vec<PsbtMap>, Vec<PsbtMap>), PsbtError> {
+        let mut psbt = self.clone();
+        psbt.version = 2;
+        psbt.unknown.retain(|(key, _)| key[..] != [PSBT_GLOBAL_VERSION]);
+        let psbt = psbt.to_bytes()?;
+
+        let mut reader = PsbtReader(&psbt[PSBT_MAGIC.len()..]);
+        let global = reader.read_map()?;
+        let inputs = self.inputs.iter().map(|_| reader.read_map()).collect::<Result<_, _>>()?;
+        let outputs = self.outputs.iter().map(|_| reader.read_map()).collect::<Result<_, _>>()?;
+        Ok((global, inputs, outputs))
+    }
+
+    /// Returns the output spent by the input at the given index, from its witness UTXO
+    /// or from its non-witness UTXO if the transaction matches the outpoint of the input.
+    fn spent_output(&self, vin: usize) -> Result<BitcoinTransactionOutput, PsbtError> {
+        let input = &self.inputs[vin];
+        if let Some(output) = &input.witness_utxo {
+            return Ok(output.clone());
+        }
+
+        let transaction = input.non_witness_utxo.as_ref().ok_or(PsbtError::MissingUtxo(vin))?;
+        let transaction = BitcoinTransactionParameters::<N>::read(&transaction[..])?;
+        let transaction_id = BitcoinTransaction::new(&transaction)?.to_transaction_id()?.to_string();
+
+        let outpoint = &self.transaction.inputs[vin].outpoint;
+        let mut outpoint_transaction_id = outpoint.reverse_transaction_id.clone();
+        outpoint_transaction_id.reverse();
+        match hex::encode(outpoint_transaction_id) == transaction_id {
+            true => transaction
+                .outputs
+                .get(outpoint.index as usize)
+                .cloned()
+                .ok_or(PsbtError::InvalidUtxo(vin)),
+            false => Err(PsbtError::InvalidUtxo(vin)),
+        }
+    }
+
+    /// Returns the size of the input at the given index, from its final scriptSig and witness stack
+    /// if it is finalized, or from its spent output and scripts otherwise.
+    fn estimate_input_size(&self, vin: usize) -> Result<BitcoinTransactionSize, PsbtError> {
+        let input = &self.inputs[vin];
+        if input.is_finalized() {
+            let script_sig_length = input.final_script_sig.as_ref().map_or(0, Vec::len);
+            let witness_lengths: Vec<usize> = input.final_script_witness.iter().flatten().map(Vec::len).collect();
+            return Ok(BitcoinTransactionSize::input(script_sig_length, &witness_lengths)?);
+        }
+
+        let output = self.spent_output(vin)?;
+        let address = BitcoinAddress::<N>::from_script_pub_key(&BitcoinScript::new(&output.script_pub_key))
+            .map_err(|_| PsbtError::UnsupportedScript(vin))?;
+        let size = match address.format() {
+            BitcoinFormat::P2SH_P2WPKH => match input
+                .redeem_script
+                .as_ref()
+                .map(|script| (script.len(), script.first()))
+            {
+                Some((22, Some(0x00))) => input_size(&BitcoinFormat::P2SH_P2WPKH),
+                Some(_) => None,
+                None => return Err(PsbtError::MissingScript(vin)),
+            },
+            BitcoinFormat::P2WSH => {
+                let witness_script = input.witness_script.as_ref().ok_or(PsbtError::MissingScript(vin))?;
+                let script = BitcoinScript::new(witness_script);
+                // The signatures of the script, preceded by the dummy element of OP_CHECKMULTISIG
+                let mut witness_lengths = match (script.to_p2pk_public_key(), script.to_multisig()) {
+                    (Some(_), _) => vec![MAX_SIGNATURE_LENGTH],
+                    (None, Some((threshold, _))) => [vec![0], vec![MAX_SIGNATURE_LENGTH; threshold]].concat(),
+                    (None, None) => return Err(PsbtError::UnsupportedScript(vin)),
+                };
+                witness_lengths.push(witness_script.len());
+                Some(BitcoinTransactionSize::input(0, &witness_lengths)?)
+            }
+            BitcoinFormat::P2TR => {
+                // A Schnorr signature, followed by the sighash type unless it is SIGHASH_DEFAULT
+                let signature_length = match input.sighash_type {
+                    None | Some(0) => 64,
+                    Some(_) => 65,
+                };
+                Some(BitcoinTransactionSize::input(0, &[signature_length])?)
+            }
+            format => input_size(&format),
+        };
+        size.ok_or(PsbtError::UnsupportedScript(vin))
+    }
+
+    /// Returns the outpoint of the input at the given index with the address, amount, and scripts of its
+    /// spent output, as used to compute the signature hash of the input.
+    pub(crate) fn to_outpoint(&self, vin: usize) -> Result<Outpoint<N>, PsbtError> {
+        let input = &self.inputs[vin];
+        let output = self.spent_output(vin)?;
+        let address = BitcoinAddress::<N>::from_script_pub_key(&BitcoinScript::new(&output.script_pub_key)).ok();
+
+        let redeem_script = match address.as_ref().map(BitcoinAddress::format) {
+            Some(BitcoinFormat::P2SH_P2WPKH) => {
+                let redeem_script = input.redeem_script.as_ref().ok_or(PsbtError::MissingScript(vin))?;
+                let script = BitcoinScript::new(redeem_script);
+                match (redeem_script.len(), redeem_script.first()) {
+                    (22, Some(0x00)) if script.to_p2sh_address::<N>().ok() == address => Some(redeem_script.clone()),
+                    _ => return Err(PsbtError::UnsupportedScript(vin)),
+                }
+            }
+            Some(BitcoinFormat::P2WSH) => {
+                let witness_script = input.witness_script.as_ref().ok_or(PsbtError::MissingScript(vin))?;
+                match BitcoinAddress::<N>::p2wsh(witness_script).ok() == address {
+                    true => Some(witness_script.clone()),
+                    false => return Err(PsbtError::UnsupportedScript(vin)),
+                }
+            }
+            Some(BitcoinFormat::P2TR) => return Err(PsbtError::UnsupportedScript(vin)),
+            _ => None,
+        };
+
+        let outpoint = &self.transaction.inputs[vin].outpoint;
+        Outpoint::new(
+            outpoint.reverse_transaction_id.clone(),
+            outpoint.index,
+            address,
+            Some(output.amount),
+            redeem_script,
+            Some(output.script_pub_key),
+        )
+        .map_err(|_| PsbtError::UnsupportedScript(vin))
+    }
+
+    /// Signs the input at the given index with its sighash type if the private key can sign for it,
+    /// and returns true if a signature was added.
+    fn sign_input(&mut self, vin: usize, private_key: &BitcoinPrivateKey<N>) -> Result<bool, PsbtError> {
+        if self.inputs[vin].is_finalized() {
+            return Ok(false);
+        }
+        let sighash = match self.inputs[vin].sighash_type {
+            None => SignatureHash::SIGHASH_ALL,
+            Some(sighash_type) => match SignatureHash::from_type(sighash_type) {
+                Some(sighash) => sighash,
+                None => return Err(PsbtError::UnsupportedSighashType(sighash_type)),
+            },
+        };
+
+        let outpoint = self.to_outpoint(vin)?;
+        let public_key = private_key.to_public_key().to_secp256k1_public_key();
+        let public_key = match private_key.is_compressed() {
+            true => public_key.serialize_compressed().to_vec(),
+            false => public_key.serialize().to_vec(),
+        };
+        if !is_signer(&outpoint, &public_key) {
+            return Ok(false);
+        }
+
+        let message = self.signature_message(vin, outpoint, sighash)?;
+        let (signature, _) = secp256k1_sign(&message, &private_key.to_secp256k1_secret_key());
+        let mut signature = signature.serialize_der().as_ref().to_vec();
+        signature.push(sighash as u8);
+
+        insert(&mut self.inputs[vin].partial_signatures, public_key, signature);
+        self.commit_signature(sighash);
+        Ok(true)
+    }
+
+    /// Returns the message signed by a signature of the input at the given index with the given sighash,
+    /// the signature hash of the input spending the given outpoint.
+    fn signature_message(
+        &self,
+        vin: usize,
+        outpoint: Outpoint<N>,
+        sighash: SignatureHash,
+    ) -> Result<secp256k1::Message, PsbtError> {
+        let format = outpoint.address.as_ref().map(BitcoinAddress::format);
+        let mut transaction = self.unsigned_transaction()?;
+        transaction.inputs[vin].outpoint = outpoint;
+        let transaction = BitcoinTransaction::new(&transaction)?;
+        let preimage = match format {
+            Some(BitcoinFormat::P2PKH) | None => transaction.p2pkh_hash_preimage(vin, sighash)?,
+            Some(_) => transaction.segwit_hash_preimage(vin, sighash)?,
+        };
+        Ok(
+            secp256k1::Message::parse_slice(&Sha256::digest(&Sha256::digest(&preimage)))
+                .map_err(TransactionError::from)?,
+        )
+    }
+
+    /// Returns the sighash of the signature of the given public key if the public key can sign the input
+    /// at the given index, the sighash is the sighash type of the input, and the signature verifies.
+    fn verify_signature(&self, vin: usize, public_key: &[u8], signature: &[u8]) -> Result<SignatureHash, PsbtError> {
+        let outpoint = self.to_outpoint(vin)?;
+        if !is_signer(&outpoint, public_key) {
+            return Err(PsbtError::UnknownSigner(vin, hex::encode(public_key)));
+        }
+
+        let (sighash_type, der) = signature.split_last().ok_or(PsbtError::InvalidSignature(vin))?;
+        let sighash = match SignatureHash::from_type(*sighash_type as u32) {
+            Some(sighash) if self.inputs[vin].sighash_type.unwrap_or(sighash as u32) == sighash as u32 => sighash,
+            _ => return Err(PsbtError::InvalidSignature(vin)),
+        };
+        let message = self.signature_message(vin, outpoint, sighash)?;
+        let signature = secp256k1::Signature::parse_der(der).map_err(|_| PsbtError::InvalidSignature(vin))?;
+        let public_key =
+            secp256k1::PublicKey::parse_slice(public_key, None).map_err(|_| PsbtError::InvalidSignature(vin))?;
+        match secp256k1::verify(&message, &signature, &public_key) {
+            true => Ok(sighash),
+            false => Err(PsbtError::InvalidSignature(vin)),
+        }
+    }
+
+    /// Updates the modifiable flags for a new signature with the given sighash.
+    fn commit_signature(&mut self, sighash: SignatureHash) {
+        // The inputs remain modifiable if the signature commits to no other input,
+        // and the outputs remain modifiable if the signature commits to no output
+        if !sighash.is_anyone_can_pay() {
+            self.modifiable &= !PSBT_MODIFIABLE_INPUTS;
+        }
+        if sighash.base() != SignatureHash::SIGHASH_NONE {
+            self.modifiable &= !PSBT_MODIFIABLE_OUTPUTS;
+        }
+        if sighash.base() == SignatureHash::SIGHASH_SINGLE {
+            self.modifiable |= PSBT_MODIFIABLE_SIGHASH_SINGLE;
+        }
+    }
+
+    /// Finalizes the input at the given index if it has the signatures to complete it.
+    fn finalize_input(&mut self, vin: usize) -> Result<(), PsbtError> {
+        if self.inputs[vin].is_finalized() {
+            return Ok(());
+        }
+
+        let outpoint = self.to_outpoint(vin)?;
+        let input = &self.inputs[vin];
+        let (script_sig, witness) = match (
+            outpoint.address.as_ref().map(BitcoinAddress::format),
+            &outpoint.redeem_script,
+        ) {
+            (Some(format @ BitcoinFormat::P2PKH), _)
+            | (Some(format @ BitcoinFormat::Bech32), _)
+            | (Some(format @ BitcoinFormat::P2SH_P2WPKH), _) => {
+                // The signature of the public key whose hash is committed to by the script
+                let key_signature = input
+                    .partial_signatures
+                    .iter()
+                    .find(|(public_key, _)| is_signer(&outpoint, public_key));
+                let (public_key, signature) = match key_signature {
+                    Some(key_signature) => key_signature.clone(),
+                    None => return Ok(()),
+                };
+                match (format, outpoint.redeem_script.clone()) {
+                    (BitcoinFormat::P2PKH, _) => (
+                        BitcoinScript::builder()
+                            .push_data(&signature)
+                            .push_data(&public_key)
+                            .into_script()
+                            .to_bytes(),
+                        vec![],
+                    ),
+                    (BitcoinFormat::P2SH_P2WPKH, Some(redeem_script)) => (
+                        BitcoinScript::builder()
+                            .push_data(&redeem_script)
+                            .into_script()
+                            .to_bytes(),
+                        vec![signature, public_key],
+                    ),
+                    _ => (vec![], vec![signature, public_key]),
+                }
+            }
+            (Some(BitcoinFormat::P2WSH), Some(witness_script)) => match input.script_signatures(vin, witness_script)? {
+                Some(signatures) => (vec![], [signatures, vec![witness_script.clone()]].concat()),
+                None => return Ok(()),
+            },
+            (None, _) => match &outpoint.script_pub_key {
+                Some(script_pub_key) => match input.script_signatures(vin, script_pub_key)? {
+                    Some(signatures) => {
+                        let script_sig = signatures.iter().fold(BitcoinScript::builder(), |builder, signature| {
+                            match signature.is_empty() {
+                                true => builder.push_opcode(Opcode::OP_0),
+                                false => builder.push_data(signature),
+                            }
+                        });
+                        (script_sig.into_script().to_bytes(), vec![])
+                    }
+                    None => return Ok(()),
+                },
+                None => return Err(PsbtError::MissingUtxo(vin)),
+            },
+            _ => return Err(PsbtError::UnsupportedScript(vin)),
+        };
+
+        // The finalized input keeps its UTXO and unknown key-value pairs
+        let input = &mut self.inputs[vin];
+        *input = PsbtInput {
+            non_witness_utxo: input.non_witness_utxo.take(),
+            witness_utxo: input.witness_utxo.take(),
+            final_script_sig: Some(script_sig).filter(|script_sig| !script_sig.is_empty()),
+            final_script_witness: Some(witness).filter(|witness| !witness.is_empty()),
+            required_time_lock_time: input.required_time_lock_time,
+            required_height_lock_time: input.required_height_lock_time,
+            unknown: input.unknown.clone(),
+            ..Default::default()
+        };
+        Ok(())
+    }
+}
+
+impl<N: BitcoinNetwork> FromStr for BitcoinPsbt<N> {
+    type Err = PsbtError;
+
+    /// Returns the PSBT from its Base64 encoding.
+    fn from_str(psbt: &str) -> Result<Self, Self::Err> {
+        Self::from_bytes(&Base64::decode_vec(psbt.trim()).map_err(|_| PsbtError::InvalidBase64)?)
+    }
+}
+
+impl<N: BitcoinNetwork> fmt::Display for BitcoinPsbt<N> {
+    /// Writes the Base64 encoding of the PSBT.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.to_bytes() {
+            Ok(psbt) => write!(f, "{}", Base64::encode_string(&psbt)),
+            Err(_) => Err(fmt::Error),
+        }
+    }
+}
+
+/// A reader of the key-value maps of a serialized PSBT
+struct PsbtReader<'a>(&'a [u8]);
+
+impl<'a> PsbtReader<'a> {
+    /// Returns the given number of bytes, advancing the reader.
+    fn read_bytes(&mut self, length: usize) -> Result<&'a [u8], PsbtError> {
+        if self.0.len() < length {
+            return Err(PsbtError::InvalidLength);
+        }
+        let (bytes, remaining) = self.0.split_at(length);
+        self.0 = remaining;
+        Ok(bytes)
+    }
+
+    /// Returns the value of a compact size unsigned integer, advancing the reader.
+    fn read_compact_size(&mut self) -> Result<usize, PsbtError> {
+        let size = match self.read_bytes(1)?[0] {
+            0xfd => {
+                let size = self.read_bytes(2)?;
+                u16::from_le_bytes([size[0], size[1]]) as usize
+            }
+            0xfe => {
+                let mut size = [0u8; 4];
+                size.copy_from_slice(self.read_bytes(4)?);
+                u32::from_le_bytes(size) as usize
+            }
+            0xff => {
+                let mut size = [0u8; 8];
+                size.copy_from_slice(self.read_bytes(8)?);
+                u64::from_le_bytes(size) as usize
+            }
+            size => size as usize,
+        };
+        Ok(size)
+    }
+
+    /// Returns the key-value pairs of a map, advancing the reader past its separator.
+    /// Errors if a key occurs more than once.
+    fn read_map(&mut self) -> Result<PsbtMap, PsbtError> {
+        let mut pairs: PsbtMap = vec![];
+        loop {
+            let key = match self.read_compact_size()? {
+                0 => return Ok(pairs),
+                length => self.read_bytes(length)?.to_vec(),
+            };
+            let length = self.read_compact_size()?;
+            let value = self.read_bytes(length)?.to_vec();
+            if pairs.iter().any(|(other, _)| other == &key) {
+                return Err(PsbtError::DuplicateKey(hex::encode(key)));
+            }
+            pairs.push((key, value));
+        }
+    }
+}
+
+/// Writes the key-value pair with the compact size lengths of its key and value.
+fn write_pair(psbt: &mut Vec<u8>, key: &[u8], value: &[u8]) -> Result<(), PsbtError> {
+    psbt.extend(variable_length_integer(key.len() as u64)?);
+    psbt.extend(key);
+    psbt.extend(variable_length_integer(value.len() as u64)?);
+    psbt.extend(value);
+    Ok(())
+}
+
+/// Returns the 32-bit little-endian value of the key.
+fn read_u32(key: &[u8], value: &[u8]) -> Result<u32, PsbtError> {
+    match value.len() {
+        4 => Ok(u32::from_le_bytes([value[0], value[1], value[2], value[3]])),
+        _ => Err(PsbtError::InvalidValue(hex::encode(key))),
+    }
+}
+
+/// Removes the key-value pair of the given key type from the map, returning its value.
+fn take_value(pairs: &mut PsbtMap, key_type: u8) -> Result<Option<Vec<u8>>, PsbtError> {
+    match pairs.iter().position(|(key, _)| key[0] == key_type) {
+        Some(index) if pairs[index].0.len() == 1 => Ok(Some(pairs.remove(index).1)),
+        Some(index) => Err(PsbtError::InvalidKey(hex::encode(&pairs[index].0))),
+        None => Ok(None),
+    }
+}
+
+/// Removes the key-value pair of the given key type from the map, returning its value.
+/// Errors if the map does not contain the key type.
+fn require_value(pairs: &mut PsbtMap, key_type: u8) -> Result<Vec<u8>, PsbtError> {
+    take_value(pairs, key_type)?.ok_or_else(|| PsbtError::MissingKey(hex::encode([key_type])))
+}
+
+/// Errors if the map contains a key whose type is in the given ranges,
+/// such as the key types of a version 2 PSBT in a version 0 PSBT.
+fn reject_keys(pairs: &PsbtMap, key_types: &[RangeInclusive<u8>]) -> Result<(), PsbtError> {
+    match pairs
+        .iter()
+        .find(|(key, _)| key_types.iter().any(|range| range.contains(&key[0])))
+    {
+        Some((key, _)) => Err(PsbtError::InvalidKey(hex::encode(key))),
+        None => Ok(()),
+    }
+}
+
+/// Returns the compact size unsigned integer value of the input or output count.
+fn read_count(key_type: u8, value: &[u8]) -> Result<usize, PsbtError> {
+    let mut reader = PsbtReader(value);
+    match (reader.read_compact_size(), reader.0.is_empty()) {
+        (Ok(count), true) => Ok(count),
+        _ => Err(PsbtError::InvalidValue(hex::encode([key_type]))),
+    }
+}
+
+/// Returns the key origin of a BIP32 derivation value, the master key fingerprint
+/// followed by the 32-bit little-endian child indices of the derivation path.
+fn read_key_origin(key: &[u8], value: &[u8]) -> Result<KeyOrigin, PsbtError> {
+    if value.len() < 4 || !value.len().is_multiple_of(4) {
+        return Err(PsbtError::InvalidValue(hex::encode(key)));
+    }
+    let mut fingerprint = [0u8; 4];
+    fingerprint.copy_from_slice(&value[..4]);
+    let path: Vec<ChildIndex> = value[4..]
+        .chunks(4)
+        .map(|index| ChildIndex::from(u32::from_le_bytes([index[0], index[1], index[2], index[3]])))
+        .collect();
+    Ok(KeyOrigin::new(fingerprint, &RelativeDerivationPath::from_vec(&path)?)?)
+}
+
+/// Returns the BIP32 derivation value of the key origin.
+fn write_key_origin(origin: &KeyOrigin) -> Result<Vec<u8>, PsbtError> {
+    let mut value = origin.fingerprint().to_vec();
+    for index in origin.path().to_vec()? {
+        value.extend(&u32::from(index).to_le_bytes());
+    }
+    Ok(value)
+}
+
+/// Returns the items of a serialized witness stack.
+fn read_witness(key: &[u8], value: &[u8]) -> Result<Vec<Vec<u8>>, PsbtError> {
+    let mut reader = PsbtReader(value);
+    let witness = (0..reader.read_compact_size()?)
+        .map(|_| {
+            let length = reader.read_compact_size()?;
+            Ok(reader.read_bytes(length)?.to_vec())
+        })
+        .collect::<Result<Vec<Vec<u8>>, PsbtError>>()?;
+    match reader.0.is_empty() {
+        true => Ok(witness),
+        false => Err(PsbtError::InvalidValue(hex::encode(key))),
+    }
+}
+
+/// Returns the serialization of the items of a witness stack.
+fn write_witness(witness: &[Vec<u8>]) -> Result<Vec<u8>, PsbtError> {
+    let mut value = variable_length_integer(witness.len() as u64)?;
+    for item in witness {
+        value.extend(variable_length_integer(item.len() as u64)?);
+        value.extend(item);
+    }
+    Ok(value)
+}
+
+/// Returns true if the public key can sign an input spending the outpoint, as the public key whose hash
+/// is committed to by a P2PKH, P2WPKH, or P2SH-P2WPKH output, or a public key of a P2WSH or bare script.
+fn is_signer<N: BitcoinNetwork>(outpoint: &Outpoint<N>, public_key: &[u8]) -> bool {
+    let mut hash = [0u8; 20];
+    hash.copy_from_slice(&hash160(public_key));
+    let p2wpkh = Some(BitcoinScript::p2wpkh(&hash).to_bytes());
+    match (
+        outpoint.address.as_ref().map(BitcoinAddress::format),
+        &outpoint.redeem_script,
+        &outpoint.script_pub_key,
+    ) {
+        (Some(BitcoinFormat::P2WSH), Some(script), _) | (None, _, Some(script)) => {
+            contains_public_key(script, public_key)
+        }
+        (Some(BitcoinFormat::P2PKH), _, script_pub_key) => {
+            script_pub_key == &Some(BitcoinScript::p2pkh(&hash).to_bytes())
+        }
+        (Some(BitcoinFormat::Bech32), _, script_pub_key) => script_pub_key == &p2wpkh,
+        (Some(BitcoinFormat::P2SH_P2WPKH), redeem_script, _) => redeem_script == &p2wpkh,
+        _ => false,
+    }
+}
+
+/// Returns true if the script pushes the given public key.
+fn contains_public_key(script: &[u8], public_key: &[u8]) -> bool {
+    match BitcoinScript::new(script).to_instructions() {
+        Ok(instructions) => instructions
+            .iter()
+            .any(|instruction| instruction == &BitcoinScriptInstruction::Push(public_key.to_vec())),
+        Err(_) => false,
+    }
+}
+
+/// Inserts the key-value pair, replacing the value of the key if it is present.
+fn insert<V>(pairs: &mut Vec<(Vec<u8>, V)>, key: Vec<u8>, value: V) {
+    match pairs.iter_mut().find(|(other, _)| other == &key) {
+        Some(pair) => pair.1 = value,
+        None => pairs.push((key, value)),
+    }
+}
+
+/// Adds the key-value pairs of the other map whose keys are not present.
+fn combine<V: Clone>(pairs: &mut Vec<(Vec<u8>, V)>, other: &[(Vec<u8>, V)]) {
+    for (key, value) in other {
+        if !pairs.iter().any(|(other, _)| other == key) {
+            pairs.push((key.clone(), value.clone()));
+        }
+    }
+}
+
+#[derive(Debug, Fail)]
+pub enum PsbtError {
+    #[fail(display = "{}", _0)]
+    AddressError(#[cause] AddressError),
+
+    #[fail(display = "{}", _0)]
+    DerivationPathError(#[cause] DerivationPathError),
+
+    #[fail(display = "duplicate key: {}", _0)]
+    DuplicateKey(String),
+
+    #[fail(display = "{}", _0)]
+    ExtendedPrivateKeyError(#[cause] ExtendedPrivateKeyError),
+
+    #[fail(display = "invalid base64 encoding")]
+    InvalidBase64,
+
+    #[fail(display = "invalid key: {}", _0)]
+    InvalidKey(String),
+
+    #[fail(display = "invalid length")]
+    InvalidLength,
+
+    #[fail(display = "the required lock times of the inputs are incompatible")]
+    InvalidLockTime,
+
+    #[fail(display = "invalid magic bytes")]
+    InvalidMagic,
+
+    #[fail(display = "invalid signature of input {}", _0)]
+    InvalidSignature(usize),
+
+    #[fail(display = "the UTXO of input {} does not match its outpoint", _0)]
+    InvalidUtxo(usize),
+
+    #[fail(display = "invalid transaction version of a version 2 PSBT: {}", _0)]
+    InvalidTransactionVersion(u32),
+
+    #[fail(display = "invalid value of key: {}", _0)]
+    InvalidValue(String),
+
+    #[fail(display = "the PSBTs have different unsigned transactions")]
+    MismatchedTransaction,
+
+    #[fail(display = "missing input {}", _0)]
+    MissingInput(usize),
+
+    #[fail(display = "missing key: {}", _0)]
+    MissingKey(String),
+
+    #[fail(display = "missing redeem or witness script of input {}", _0)]
+    MissingScript(usize),
+
+    #[fail(display = "missing unsigned transaction")]
+    MissingUnsignedTransaction,
+
+    #[fail(display = "missing UTXO of input {}", _0)]
+    MissingUtxo(usize),
+
+    #[fail(display = "the unsigned transaction has a scriptSig or witness")]
+    SignedTransaction,
+
+    #[fail(display = "{}", _0)]
+    TransactionError(#[cause] TransactionError),
+
+    #[fail(display = "input {} is not finalized", _0)]
+    UnfinalizedInput(usize),
+
+    #[fail(display = "public key {} cannot sign input {}", _1, _0)]
+    UnknownSigner(usize, String),
+
+    #[fail(display = "the inputs of the PSBT are not modifiable")]
+    UnmodifiableInputs,
+
+    #[fail(display = "the outputs of the PSBT are not modifiable")]
+    UnmodifiableOutputs,
+
+    #[fail(display = "unsupported script of input {}", _0)]
+    UnsupportedScript(usize),
+
+    #[fail(display = "unsupported sighash type: {}", _0)]
+    UnsupportedSighashType(u32),
+
+    #[fail(display = "unsupported PSBT version: {}", _0)]
+    UnsupportedVersion(u32),
+}
+
+impl From<AddressError> for PsbtError {
+    fn from(error: AddressError) -> Self {
+        PsbtError::AddressError(error)
+    }
+}
+
+impl From<DerivationPathError> for PsbtError {
+    fn from(error: DerivationPathError) -> Self {
+        PsbtError::DerivationPathError(error)
+    }
+}
+
+impl From<ExtendedPrivateKeyError> for PsbtError {
+    fn from(error: ExtendedPrivateKeyError) -> Self {
+        PsbtError::ExtendedPrivateKeyError(error)
+    }
+}
+
+impl From<TransactionError> for PsbtError {
+    fn from(error: TransactionError) -> Self {
+        PsbtError::TransactionError(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::amount::BitcoinAmount;
+    use crate::network::*;
+    use crate::public_key::BitcoinPublicKey;
+
+    type N = Mainnet;
+
+    const PRIVATE_KEYS: [&str; 2] = [
+        "L5BsLN6keEWUuF1JxfG6w5U1FDHs29faMpr9QX2MMVuQt7ymTorX",
+        "Kxxkik2L9KgrGgvdkEvYSkgAxaY4qPGfvxe1M1KBVBB7Ls3xDD8o",
+    ];
+    const TRANSACTION_ID: &str = "61d520ccb74288c96bc1a2b20ea1c0d5a704776dd0164a396efec3ea7040349d";
+    const OUTPUT_ADDRESS: &str = "3QDTHVyuJrHixUhhsdZXQ7M8P9MQngmw1P";
+    const AMOUNT: BitcoinAmount = BitcoinAmount(50000);
+
+    fn private_key(index: usize) -> BitcoinPrivateKey<N> {
+        BitcoinPrivateKey::from_str(PRIVATE_KEYS[index]).unwrap()
+    }
+
+    // Returns the transaction spending the output of the given address,
+    // with the outpoint of the input if it is to be signed without a PSBT.
+    fn transaction(
+        address: Option<&BitcoinAddress<N>>,
+        redeem_script: Option<Vec<u8>>,
+    ) -> BitcoinTransactionParameters<N> {
+        let input = BitcoinTransactionInput::<N>::new(
+            hex::decode(TRANSACTION_ID).unwrap(),
+            1,
+            address.cloned(),
+            address.map(|_| AMOUNT),
+            redeem_script,
+            None,
+            None,
+            SignatureHash::SIGHASH_ALL,
+        )
+        .unwrap();
+        let output = BitcoinTransactionOutput::new(
+            &BitcoinAddress::<N>::from_str(OUTPUT_ADDRESS).unwrap(),
+            BitcoinAmount(40000),
+        )
+        .unwrap();
+        BitcoinTransactionParameters {
+            version: 2,
+            inputs: vec![input],
+            outputs: vec![output],
+            lock_time: 0,
+            segwit_flag: false,
+        }
+    }
+
+    // Returns the PSBT of the transaction with the witness UTXO of its input.
+    fn psbt(address: &BitcoinAddress<N>) -> BitcoinPsbt<N> {
+        let mut psbt = BitcoinPsbt::new(&transaction(None, None)).unwrap();
+        psbt.inputs_mut()[0].witness_utxo = Some(BitcoinTransactionOutput::new(address, AMOUNT).unwrap());
+        psbt
+    }
+
+    // Signs, finalizes, and extracts the PSBT, and compares the extracted transaction
+    // to the transaction signed directly by the private key.
+    fn test_sign(
+        mut psbt: BitcoinPsbt<N>,
+        expected: BitcoinTransactionParameters<N>,
+        private_key: &BitcoinPrivateKey<N>,
+    ) {
+        let estimate = psbt.estimate_size().unwrap();
+        assert_eq!(1, psbt.sign(private_key).unwrap());
+        assert_eq!(1, psbt.inputs()[0].partial_signatures.len());
+        assert!(!psbt.is_finalized());
+        assert!(psbt.extract().is_err());
+
+        psbt.finalize().unwrap();
+        assert!(psbt.is_finalized());
+        assert!(psbt.inputs()[0].partial_signatures.is_empty());
+
+        let expected = BitcoinTransaction::new(&expected).unwrap().sign(private_key).unwrap();
+        assert_eq!(
+            hex::encode(expected.to_transaction_bytes().unwrap()),
+            hex::encode(psbt.extract().unwrap().to_transaction_bytes().unwrap())
+        );
+
+        // The size of the finalized PSBT is exact, and the estimate before signing is at most a byte
+        // longer for the signature
+        let size = psbt.estimate_size().unwrap();
+        assert_eq!(expected.to_transaction_bytes().unwrap().len() as u64, size.size());
+        assert!(estimate.weight() >= size.weight() && estimate.weight() - size.weight() <= 4);
+        assert_eq!(psbt, BitcoinPsbt::from_str(&psbt.to_string()).unwrap());
+    }
+
+    #[test]
+    fn bip174_round_trip() {
+        // The PSBT of the creator test vector of BIP174
+        let encoded = "cHNidP8BAJoCAAAAAljoeiG1ba8MI76OcHBFbDNvfLqlyHV5JPVFiHuyq911AAAAAAD/////g40EJ9DsZQpoqka7CwmK6kQiwHGyyng1Kgd5WdB86h0BAAAAAP////8CcKrwCAAAAAAWABTYXCtx0AYLCcmIauuBXlCZHdoSTQDh9QUAAAAAFgAUAK6pouXw+HaliN9VRuh0LR2HAI8AAAAAAAAAAAA=";
+        let psbt = BitcoinPsbt::<N>::from_str(encoded).unwrap();
+        assert_eq!(2, psbt.inputs().len());
+        assert_eq!(2, psbt.outputs().len());
+        assert_eq!(encoded, psbt.to_string());
+    }
+
+    #[test]
+    fn sign_p2pkh() {
+        let private_key = private_key(0);
+        let address = private_key.to_address(&BitcoinFormat::P2PKH).unwrap();
+        test_sign(psbt(&address), transaction(Some(&address), None), &private_key);
+    }
+
+    #[test]
+    fn sign_p2wpkh() {
+        let private_key = private_key(0);
+        let address = private_key.to_address(&BitcoinFormat::Bech32).unwrap();
+        test_sign(psbt(&address), transaction(Some(&address), None), &private_key);
+
+        // A private key of another address signs nothing
+        let mut psbt = psbt(&address);
+        assert_eq!(0, psbt.sign(&self::private_key(1)).unwrap());
+    }
+
+    #[test]
+    fn sign_p2sh_p2wpkh() {
+        let private_key = private_key(0);
+        let address = private_key.to_address(&BitcoinFormat::P2SH_P2WPKH).unwrap();
+        let public_key = private_key
+            .to_public_key()
+            .to_secp256k1_public_key()
+            .serialize_compressed();
+        let redeem_script = [vec![0x00, 0x14], hash160(&public_key)].concat();
+
+        let mut psbt = psbt(&address);
+        assert!(psbt.sign(&private_key).is_err());
+        psbt.inputs_mut()[0].redeem_script = Some(redeem_script.clone());
+        test_sign(psbt, transaction(Some(&address), Some(redeem_script)), &private_key);
+    }
+
+    #[test]
+    fn non_witness_utxo() {
+        let private_key = private_key(0);
+        let address = private_key.to_address(&BitcoinFormat::P2PKH).unwrap();
+
+        // The previous transaction pays the address in its output at index 1
+        let output = BitcoinTransactionOutput::new(&address, AMOUNT).unwrap();
+        let mut previous = transaction(Some(&address), None);
+        previous.inputs[0].outpoint = Outpoint::new(vec![0u8; 32], 0, None, None, None, None).unwrap();
+        previous.outputs = vec![output.clone(), output];
+        let previous = BitcoinTransaction::new(&previous).unwrap();
+        let previous_id = previous.to_transaction_id().unwrap().to_string();
+
+        let mut transaction = transaction(Some(&address), None);
+        transaction.inputs[0].outpoint.reverse_transaction_id = hex::decode(&previous_id).unwrap();
+        transaction.inputs[0].outpoint.reverse_transaction_id.reverse();
+        let mut psbt = BitcoinPsbt::new(&transaction).unwrap();
+        psbt.inputs_mut()[0].non_witness_utxo = Some(previous.to_transaction_bytes().unwrap());
+        assert_eq!(1, psbt.sign(&private_key).unwrap());
+
+        // The previous transaction must match the outpoint of the input
+        let mut psbt = BitcoinPsbt::new(&self::transaction(Some(&address), None)).unwrap();
+        psbt.inputs_mut()[0].non_witness_utxo = Some(previous.to_transaction_bytes().unwrap());
+        assert!(psbt.sign(&private_key).is_err());
+    }
+
+    #[test]
+    fn combine_p2wsh_multisig() {
+        let public_keys: Vec<BitcoinPublicKey<N>> = (0..2).map(|index| private_key(index).to_public_key()).collect();
+        let witness_script = BitcoinScript::multisig(2, &public_keys).to_bytes();
+        let address = BitcoinAddress::<N>::p2wsh(&witness_script).unwrap();
+
+        let mut unsigned = psbt(&address);
+        assert!(unsigned.sign(&private_key(0)).is_err());
+        assert!(unsigned.estimate_size().is_err());
+        unsigned.inputs_mut()[0].witness_script = Some(witness_script.clone());
+        let estimate = unsigned.estimate_size().unwrap();
+
+        let mut first = unsigned.clone();
+        let mut second = unsigned.clone();
+        assert_eq!(1, first.sign(&private_key(0)).unwrap());
+        assert_eq!(1, second.sign(&private_key(1)).unwrap());
+
+        // A single signature does not finalize the input
+        first.finalize().unwrap();
+        assert!(!first.is_finalized());
+
+        first.combine(&second).unwrap();
+        assert_eq!(2, first.inputs()[0].partial_signatures.len());
+        first.finalize().unwrap();
+        assert!(first.is_finalized());
+
+        let witness = first.inputs()[0].final_script_witness.clone().unwrap();
+        assert_eq!(4, witness.len());
+        assert!(witness[0].is_empty());
+        assert_eq!(witness_script, witness[3]);
+        assert!(first.extract().is_ok());
+
+        let size = first.estimate_size().unwrap();
+        assert_eq!(
+            first.extract().unwrap().to_transaction_bytes().unwrap().len() as u64,
+            size.size()
+        );
+        assert_eq!(estimate.base_size, size.base_size);
+        assert!(estimate.witness_size >= size.witness_size && estimate.witness_size - size.witness_size <= 2);
+        assert_eq!(BitcoinAmount(size.weight() as i64), first.estimate_fee(4).unwrap());
+
+        // PSBTs of different transactions are not combined
+        let other = BitcoinPsbt::new(&BitcoinTransactionParameters {
+            lock_time: 1,
+            ..transaction(None, None)
+        })
+        .unwrap();
+        assert!(unsigned.combine(&other).is_err());
+    }
+
+    #[test]
+    fn collect_multisig_signatures() {
+        let public_keys: Vec<BitcoinPublicKey<N>> = (0..2).map(|index| private_key(index).to_public_key()).collect();
+        let serialized: Vec<Vec<u8>> = public_keys
+            .iter()
+            .map(|public_key| public_key.to_secp256k1_public_key().serialize_compressed().to_vec())
+            .collect();
+        let witness_script = BitcoinScript::multisig(2, &public_keys).to_bytes();
+        let address = BitcoinAddress::<N>::p2wsh(&witness_script).unwrap();
+
+        let mut psbt = psbt(&address);
+        psbt.inputs_mut()[0].witness_script = Some(witness_script);
+        let status = psbt.signing_status(0).unwrap();
+        assert_eq!(2, status.threshold);
+        assert!(status.signed.is_empty());
+        assert_eq!(serialized, status.unsigned);
+        assert!(!status.is_complete());
+
+        // Each cosigner signs its own copy, and sends back its signature
+        let mut cosigner = psbt.clone();
+        cosigner.sign(&private_key(1)).unwrap();
+        let (public_key, signature) = cosigner.inputs()[0].partial_signatures[0].clone();
+
+        psbt.sign(&private_key(0)).unwrap();
+        let status = psbt.signing_status(0).unwrap();
+        assert_eq!(vec![serialized[0].clone()], status.signed);
+        assert_eq!(vec![serialized[1].clone()], status.unsigned);
+
+        // A signature is only accepted from a key of the script, over the signature hash of the input
+        let own_signature = psbt.inputs()[0].partial_signatures[0].1.clone();
+        match psbt.add_signature(0, &public_key, &own_signature) {
+            Err(PsbtError::InvalidSignature(0)) => (),
+            result => panic!("unexpected result {:?}", result),
+        };
+        let other_key = private_key(0)
+            .to_public_key()
+            .to_secp256k1_public_key()
+            .serialize()
+            .to_vec();
+        match psbt.add_signature(0, &other_key, &signature) {
+            Err(PsbtError::UnknownSigner(0, _)) => (),
+            result => panic!("unexpected result {:?}", result),
+        };
+        assert!(psbt.add_signature(1, &public_key, &signature).is_err());
+
+        psbt.add_signature(0, &public_key, &signature).unwrap();
+        assert!(psbt.verify_signatures().is_ok());
+        assert!(psbt.signing_status(0).unwrap().is_complete());
+        psbt.finalize().unwrap();
+        assert!(psbt.is_finalized());
+        assert_eq!(PsbtSigningStatus::default(), psbt.signing_status(0).unwrap());
+
+        // A combined PSBT with a forged signature does not verify
+        let mut forged = cosigner.clone();
+        forged.inputs_mut()[0].partial_signatures[0].1 = own_signature;
+        assert!(forged.verify_signatures().is_err());
+    }
+
+    #[test]
+    fn single_key_signing_status() {
+        let private_key = private_key(0);
+        let address = private_key.to_address(&BitcoinFormat::Bech32).unwrap();
+        let mut psbt = psbt(&address);
+        let status = psbt.signing_status(0).unwrap();
+        assert_eq!(1, status.threshold);
+        assert!(status.signed.is_empty() && status.unsigned.is_empty());
+
+        psbt.sign(&private_key).unwrap();
+        let status = psbt.signing_status(0).unwrap();
+        assert_eq!(
+            vec![private_key
+                .to_public_key()
+                .to_secp256k1_public_key()
+                .serialize_compressed()
+                .to_vec()],
+            status.signed
+        );
+        assert!(status.is_complete());
+        assert!(psbt.verify_signatures().is_ok());
+    }
+
+    #[test]
+    fn sign_with_extended_private_key() {
+        let master = BitcoinExtendedPrivateKey::<N>::new_master(&[7u8; 32], &BitcoinFormat::Bech32).unwrap();
+        let path = RelativeDerivationPath::from_str("84'/0'/0'/0/0").unwrap();
+        let private_key = master.derive_relative(&path).unwrap().to_private_key();
+        let public_key = private_key
+            .to_public_key()
+            .to_secp256k1_public_key()
+            .serialize_compressed();
+        let address = private_key.to_address(&BitcoinFormat::Bech32).unwrap();
+
+        let mut psbt = psbt(&address);
+        assert_eq!(0, psbt.sign_with_extended_private_key(&master).unwrap());
+
+        let origin = KeyOrigin::new(master.fingerprint(), &path).unwrap();
+        psbt.inputs_mut()[0].add_bip32_derivation(&public_key, origin);
+        assert_eq!(psbt, BitcoinPsbt::from_str(&psbt.to_string()).unwrap());
+        assert_eq!(1, psbt.sign_with_extended_private_key(&master).unwrap());
+        psbt.finalize().unwrap();
+        assert!(psbt.is_finalized());
+    }
+
+    #[test]
+    fn invalid_psbt() {
+        let psbt = psbt(&private_key(0).to_address(&BitcoinFormat::Bech32).unwrap());
+        let bytes = psbt.to_bytes().unwrap();
+
+        // Invalid magic bytes
+        assert!(BitcoinPsbt::<N>::from_bytes(&bytes[1..]).is_err());
+        assert!(BitcoinPsbt::<N>::from_str("cHNidA==").is_err());
+
+        // Truncated and trailing data
+        assert!(BitcoinPsbt::<N>::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert!(BitcoinPsbt::<N>::from_bytes(&[&bytes[..], &[0x00]].concat()).is_err());
+
+        // A duplicate key of the input map
+        let mut duplicate = bytes.clone();
+        let witness_utxo = [
+            &[0x01, PSBT_IN_WITNESS_UTXO][..],
+            &bytes[bytes.len() - 2 - 41..bytes.len() - 2],
+        ]
+        .concat();
+        duplicate.splice(bytes.len() - 2..bytes.len() - 2, witness_utxo);
+        assert!(BitcoinPsbt::<N>::from_bytes(&duplicate).is_err());
+
+        // A transaction with a scriptSig is not an unsigned transaction
+        let mut transaction = transaction(None, None);
+        transaction.inputs[0].script_sig = vec![0x51];
+        assert!(BitcoinPsbt::new(&transaction).is_err());
+
+        // Only version 0 and version 2 PSBTs are supported
+        let version = [
+            &bytes[..PSBT_MAGIC.len()],
+            &[0x01, PSBT_GLOBAL_VERSION, 0x04, 0x01, 0x00, 0x00, 0x00],
+            &bytes[PSBT_MAGIC.len()..],
+        ]
+        .concat();
+        assert!(BitcoinPsbt::<N>::from_bytes(&version).is_err());
+
+        // A version 0 PSBT has no keys of version 2 PSBTs
+        let transaction_version = [
+            &bytes[..PSBT_MAGIC.len()],
+            &[0x01, PSBT_GLOBAL_TX_VERSION, 0x04, 0x02, 0x00, 0x00, 0x00],
+            &bytes[PSBT_MAGIC.len()..],
+        ]
+        .concat();
+        assert!(BitcoinPsbt::<N>::from_bytes(&transaction_version).is_err());
+
+        // A version 2 PSBT has no unsigned transaction
+        let version = [
+            &bytes[..PSBT_MAGIC.len()],
+            &[0x01, PSBT_GLOBAL_VERSION, 0x04, 0x02, 0x00, 0x00, 0x00],
+            &bytes[PSBT_MAGIC.len()..],
+        ]
+        .concat();
+        assert!(BitcoinPsbt::<N>::from_bytes(&version).is_err());
+    }
+
+    #[test]
+    fn version_2_round_trip() {
+        let private_key = private_key(0);
+        let address = private_key.to_address(&BitcoinFormat::Bech32).unwrap();
+        let psbt = psbt(&address);
+
+        let version_2 = psbt.to_version(2).unwrap();
+        assert_eq!(2, version_2.version());
+        assert_eq!(version_2, BitcoinPsbt::from_str(&version_2.to_string()).unwrap());
+        assert_eq!(psbt, version_2.to_version(0).unwrap());
+        test_sign(version_2, transaction(Some(&address), None), &private_key);
+
+        // A version 2 PSBT has a transaction of version 2 or greater
+        let version_1 = BitcoinPsbt::<N>::new(&BitcoinTransactionParameters {
+            version: 1,
+            ..transaction(None, None)
+        })
+        .unwrap();
+        assert!(version_1.to_version(2).is_err());
+        assert!(psbt.to_version(1).is_err());
+    }
+
+    #[test]
+    fn version_2_lock_time() {
+        let mut psbt = psbt(&private_key(0).to_address(&BitcoinFormat::Bech32).unwrap());
+        assert!(psbt.set_modifiable(PSBT_MODIFIABLE_INPUTS).is_err());
+        psbt.inputs_mut()[0].required_height_lock_time = Some(100);
+        assert!(psbt.to_bytes().is_err());
+
+        // The greatest required height of the inputs
+        let mut psbt = psbt.to_version(2).unwrap();
+        psbt.set_modifiable(PSBT_MODIFIABLE_INPUTS).unwrap();
+        assert_eq!(100, psbt.lock_time().unwrap());
+        let mut input = transaction(None, None).inputs[0].clone();
+        input.outpoint.index = 2;
+        let required = PsbtInput {
+            required_time_lock_time: Some(LOCK_TIME_THRESHOLD),
+            required_height_lock_time: Some(200),
+            ..PsbtInput::default()
+        };
+        psbt.add_input(&input, required).unwrap();
+        assert_eq!(200, psbt.lock_time().unwrap());
+        assert_eq!(psbt, BitcoinPsbt::from_str(&psbt.to_string()).unwrap());
+
+        // An input requiring only a time is incompatible with an input requiring only a height
+        input.outpoint.index = 3;
+        let required = PsbtInput {
+            required_time_lock_time: Some(LOCK_TIME_THRESHOLD),
+            ..PsbtInput::default()
+        };
+        assert!(psbt.add_input(&input, required).is_err());
+        assert_eq!(2, psbt.inputs().len());
+        assert_eq!(2, psbt.unsigned_transaction().unwrap().inputs.len());
+
+        // A version 0 PSBT has the lock time in its transaction
+        let version_0 = psbt.to_version(0).unwrap();
+        assert_eq!(200, version_0.unsigned_transaction().unwrap().lock_time);
+        assert!(version_0
+            .inputs()
+            .iter()
+            .all(|input| input.required_height_lock_time.is_none()));
+    }
+
+    #[test]
+    fn version_2_modifiable() {
+        let mut psbt = psbt(&private_key(0).to_address(&BitcoinFormat::Bech32).unwrap())
+            .to_version(2)
+            .unwrap();
+        let mut input = transaction(None, None).inputs[0].clone();
+        input.outpoint.index = 2;
+        let output = transaction(None, None).outputs[0].clone();
+        assert!(psbt.add_input(&input, PsbtInput::default()).is_err());
+        assert!(psbt.add_output(&output, PsbtOutput::default()).is_err());
+
+        psbt.set_modifiable(PSBT_MODIFIABLE_INPUTS | PSBT_MODIFIABLE_OUTPUTS)
+            .unwrap();
+        psbt.add_output(&output, PsbtOutput::default()).unwrap();
+        assert_eq!(2, psbt.outputs().len());
+        assert_eq!(psbt, BitcoinPsbt::from_str(&psbt.to_string()).unwrap());
+
+        // A signature with ANYONECANPAY commits to no other input, and with SIGHASH_NONE to no output
+        for (sighash_type, modifiable) in [
+            (0x81, PSBT_MODIFIABLE_INPUTS),
+            (0x82, PSBT_MODIFIABLE_INPUTS | PSBT_MODIFIABLE_OUTPUTS),
+            (0x03, PSBT_MODIFIABLE_SIGHASH_SINGLE),
+        ]
+        .iter()
+        {
+            let mut psbt = psbt.clone();
+            psbt.inputs_mut()[0].sighash_type = Some(*sighash_type);
+            assert_eq!(1, psbt.sign(&private_key(0)).unwrap());
+            assert_eq!(*modifiable, psbt.modifiable());
+            let signature = psbt.inputs()[0].partial_signatures[0].1.clone();
+            assert_eq!(*sighash_type as u8, signature[signature.len() - 1]);
+        }
+        let mut unsupported = psbt.clone();
+        unsupported.inputs_mut()[0].sighash_type = Some(0x04);
+        assert!(unsupported.sign(&private_key(0)).is_err());
+
+        // A signature with SIGHASH_ALL commits to the inputs and outputs
+        assert_eq!(1, psbt.sign(&private_key(0)).unwrap());
+        assert_eq!(0, psbt.modifiable());
+        assert!(psbt.add_input(&input, PsbtInput::default()).is_err());
+    }
+}
@@ -2,10 +2,14 @@ use crate::address::BitcoinAddress;
 use crate::format::BitcoinFormat;
 use crate::network::BitcoinNetwork;
 use crate::private_key::BitcoinPrivateKey;
-use wagyu_model::{Address, AddressError, PublicKey, PublicKeyError};
+use crate::taproot::to_output_key;
+use wagyu_model::{
+    crypto::{secp256k1_public_key, tagged_hash},
+    Address, AddressError, PublicKey, PublicKeyError,
+};
 
 use core::{fmt, fmt::Display, marker::PhantomData, str::FromStr};
-use secp256k1;
+use secp256k1::{self, curve::Scalar};
 
 /// Represents a Bitcoin public key
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -26,7 +30,7 @@ impl<N: BitcoinNetwork> PublicKey for BitcoinPublicKey<N> {
     /// Returns the address corresponding to the given public key.
     fn from_private_key(private_key: &Self::PrivateKey) -> Self {
         Self {
-            public_key: secp256k1::PublicKey::from_secret_key(&private_key.to_secp256k1_secret_key()),
+            public_key: secp256k1_public_key(&private_key.to_secp256k1_secret_key()),
             compressed: private_key.is_compressed(),
             _network: PhantomData,
         }
@@ -57,6 +61,80 @@ impl<N: BitcoinNetwork> BitcoinPublicKey<N> {
     pub fn is_compressed(&self) -> bool {
         self.compressed
     }
+
+    /// Returns the BIP340 x-only public key, the x-coordinate of the public key.
+    pub fn to_x_only_public_key(&self) -> [u8; 32] {
+        let mut x_only_public_key = [0u8; 32];
+        x_only_public_key.copy_from_slice(&self.public_key.serialize_compressed()[1..]);
+        x_only_public_key
+    }
+
+    /// Returns the public key with an even y-coordinate for the given BIP340 x-only public key.
+    pub fn from_x_only_public_key(x_only_public_key: &[u8; 32]) -> Result<Self, PublicKeyError> {
+        let mut public_key = [secp256k1::util::TAG_PUBKEY_EVEN; 33];
+        public_key[1..].copy_from_slice(x_only_public_key);
+        Ok(Self::from_secp256k1_public_key(
+            secp256k1::PublicKey::parse_compressed(&public_key)?,
+            true,
+        ))
+    }
+
+    /// Returns `true` if the signature is a valid BIP340 Schnorr signature of the message
+    /// for the x-only public key.
+    /// https://github.com/bitcoin/bips/blob/master/bip-0340.mediawiki#verification
+    pub fn verify_schnorr(&self, message: &[u8], signature: &[u8; 64]) -> bool {
+        let x_only_public_key = self.to_x_only_public_key();
+        let public_key = match Self::from_x_only_public_key(&x_only_public_key) {
+            Ok(public_key) => public_key.public_key,
+            Err(_) => return false,
+        };
+
+        let mut s_bytes = [0u8; 32];
+        s_bytes.copy_from_slice(&signature[32..]);
+        let mut s = Scalar::default();
+        if bool::from(s.set_b32(&s_bytes)) {
+            return false;
+        }
+
+        let mut e = Scalar::default();
+        let _ = e.set_b32(&tagged_hash(
+            b"BIP0340/challenge",
+            &[&signature[..32], &x_only_public_key[..], message].concat(),
+        ));
+
+        // R = sG - eP, which must not be the point at infinity.
+        let mut points = vec![];
+        if let Ok(s) = secp256k1::SecretKey::parse(&s.b32()) {
+            points.push(secp256k1_public_key(&s));
+        }
+        if let Ok(negated_e) = secp256k1::SecretKey::parse(&(-e).b32()) {
+            let mut point = public_key;
+            if point.tweak_mul_assign(&negated_e).is_ok() {
+                points.push(point);
+            }
+        }
+        match secp256k1::PublicKey::combine(&points) {
+            Ok(r) => {
+                let r = r.serialize_compressed();
+                r[0] == secp256k1::util::TAG_PUBKEY_EVEN && r[1..] == signature[..32]
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Returns the BIP341 Taproot output key of the public key as the internal key, without a script tree.
+    /// The output key is Q = P + int(hashTapTweak(bytes(P)))G, where P is the point with an even y-coordinate.
+    /// https://github.com/bitcoin/bips/blob/master/bip-0086.mediawiki#address-derivation
+    pub fn to_taproot_output_key(&self) -> Result<[u8; 32], PublicKeyError> {
+        Ok(self.to_taproot_tweaked_key(None)?.0)
+    }
+
+    /// Returns the BIP341 Taproot output key of the public key as the internal key, committing to
+    /// the Merkle root of a script tree if given, and whether the output key has an odd y-coordinate.
+    /// https://github.com/bitcoin/bips/blob/master/bip-0341.mediawiki#constructing-and-spending-taproot-outputs
+    pub fn to_taproot_tweaked_key(&self, merkle_root: Option<&[u8; 32]>) -> Result<([u8; 32], bool), PublicKeyError> {
+        to_output_key(&self.to_x_only_public_key(), merkle_root)
+    }
 }
 
 impl<N: BitcoinNetwork> FromStr for BitcoinPublicKey<N> {
@@ -567,4 +645,68 @@ mod tests {
         let public_key = "02468791fee1444df3a6e786e2f9da79198f8902387e1fa5a2c051950c4df51ab402468791fee1444df3a6e786e2f9da79198f8902387e1fa5a2c051950c4df51ab4";
         assert!(BitcoinPublicKey::<N>::from_str(public_key).is_err());
     }
+
+    #[test]
+    fn verify_schnorr() {
+        type N = Mainnet;
+
+        // https://github.com/bitcoin/bips/blob/master/bip-0340/test-vectors.csv
+        // (x_only_public_key, message, signature, is_valid)
+        const VECTORS: [(&str, &str, &str, bool); 5] = [
+            (
+                "d69c3509bb99e412e68b0fe8544e72837dfa30746d8be2aa65975f29d22dc7b9",
+                "4df3c3f68fcc83b27e9d42c90431a72499f17875c81a599b566c9889b9696703",
+                "00000000000000000000003b78ce563f89a0ed9414f5aa28ad0d96d6795f9c6376afb1548af603b3eb45c9f8207dee1060cb71c04e80f593060b07d28308d7f4",
+                true,
+            ),
+            (
+                // has_even_y(R) is false
+                "dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659",
+                "243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89",
+                "fff97bd5755eeea420453a14355235d382f6472f8568a18b2f057a14602975563cc27944640ac607cd107ae10923d9ef7a73c643e166be5ebeafa34b1ac553e2",
+                false,
+            ),
+            (
+                // negated message
+                "dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659",
+                "243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89",
+                "1fa62e331edbc21c394792d2ab1100a7b432b013df3f6ff4f99fcb33e0e1515f28890b3edb6e7189b630448b515ce4f8622a954cfe545735aaea5134fccdb2bd",
+                false,
+            ),
+            (
+                // sG - eP is infinite
+                "dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659",
+                "243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89",
+                "0000000000000000000000000000000000000000000000000000000000000000123dda8328af9c23a94c1feecfd123ba4fb73476f0d594dcb65c6425bd186051",
+                false,
+            ),
+            (
+                // s is equal to the curve order
+                "dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659",
+                "243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89",
+                "6cff5c3ba86c69ea4b7376f31a9bcb4f74c1976089b2d9963da2e5543e177769fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+                false,
+            ),
+        ];
+
+        VECTORS
+            .iter()
+            .for_each(|(x_only_public_key, message, signature, is_valid)| {
+                let mut x_only = [0u8; 32];
+                x_only.copy_from_slice(&hex::decode(x_only_public_key).unwrap());
+                let public_key = BitcoinPublicKey::<N>::from_x_only_public_key(&x_only).unwrap();
+                assert_eq!(*x_only_public_key, hex::encode(public_key.to_x_only_public_key()));
+
+                let mut sig = [0u8; 64];
+                sig.copy_from_slice(&hex::decode(signature).unwrap());
+                let message = hex::decode(message).unwrap();
+                assert_eq!(*is_valid, public_key.verify_schnorr(&message, &sig));
+            });
+
+        // The public key is not on the curve.
+        let x_only = hex::decode("eefdea4cdb677750a420fee807eacf21eb9898ae79b9768766e4faa04a2d4a34").unwrap();
+        let mut public_key = [0u8; 32];
+        public_key.copy_from_slice(&x_only);
+        assert!(BitcoinPublicKey::<N>::from_x_only_public_key(&public_key).is_err());
+    }
 }
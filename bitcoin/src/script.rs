@@ -0,0 +1,710 @@
+use crate::address::BitcoinAddress;
+use crate::network::BitcoinNetwork;
+use crate::public_key::BitcoinPublicKey;
+use crate::transaction::Opcode;
+use crate::witness_script::BitcoinWitnessScript;
+use wagyu_model::no_std::*;
+use wagyu_model::{AddressError, TransactionError};
+
+use core::{fmt, str::FromStr};
+use serde::Serialize;
+
+/// Represents a Bitcoin script, e.g. a scriptPubKey or a redeem script
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BitcoinScript {
+    /// The serialized script
+    script: Vec<u8>,
+}
+
+/// Represents an instruction of a Bitcoin script, either an opcode or a push of data
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum BitcoinScriptInstruction {
+    /// An opcode other than a push of data, given as its byte
+    Opcode(u8),
+    /// A push of the given data
+    Push(Vec<u8>),
+}
+
+/// Represents the type of a scriptPubKey, named as by Bitcoin Core
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub enum ScriptType {
+    /// <public key> OP_CHECKSIG
+    #[serde(rename = "pubkey")]
+    PubKey,
+    /// OP_DUP OP_HASH160 <public key hash> OP_EQUALVERIFY OP_CHECKSIG
+    #[serde(rename = "pubkeyhash")]
+    PubKeyHash,
+    /// OP_HASH160 <script hash> OP_EQUAL
+    #[serde(rename = "scripthash")]
+    ScriptHash,
+    /// OP_M <public keys> OP_N OP_CHECKMULTISIG
+    #[serde(rename = "multisig")]
+    Multisig,
+    /// OP_RETURN <data>
+    #[serde(rename = "nulldata")]
+    NullData,
+    /// OP_0 <public key hash>
+    #[serde(rename = "witness_v0_keyhash")]
+    WitnessV0KeyHash,
+    /// OP_0 <script hash>
+    #[serde(rename = "witness_v0_scripthash")]
+    WitnessV0ScriptHash,
+    /// OP_1 <output key>
+    #[serde(rename = "witness_v1_taproot")]
+    WitnessV1Taproot,
+    /// Any other script
+    #[serde(rename = "nonstandard")]
+    NonStandard,
+}
+
+impl fmt::Display for ScriptType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScriptType::PubKey => write!(f, "pubkey"),
+            ScriptType::PubKeyHash => write!(f, "pubkeyhash"),
+            ScriptType::ScriptHash => write!(f, "scripthash"),
+            ScriptType::Multisig => write!(f, "multisig"),
+            ScriptType::NullData => write!(f, "nulldata"),
+            ScriptType::WitnessV0KeyHash => write!(f, "witness_v0_keyhash"),
+            ScriptType::WitnessV0ScriptHash => write!(f, "witness_v0_scripthash"),
+            ScriptType::WitnessV1Taproot => write!(f, "witness_v1_taproot"),
+            ScriptType::NonStandard => write!(f, "nonstandard"),
+        }
+    }
+}
+
+impl BitcoinScript {
+    /// Returns a script given its serialized bytes.
+    pub fn new(script: &[u8]) -> Self {
+        Self {
+            script: script.to_vec(),
+        }
+    }
+
+    /// Returns a builder for a new script.
+    pub fn builder() -> BitcoinScriptBuilder {
+        BitcoinScriptBuilder::new()
+    }
+
+    /// Returns the scriptPubKey of the given address.
+    pub fn from_address<N: BitcoinNetwork>(address: &BitcoinAddress<N>) -> Result<Self, TransactionError> {
+        Ok(address.to_script_pub_key()?)
+    }
+
+    /// Returns the P2PK scriptPubKey, <public key> OP_CHECKSIG.
+    pub fn p2pk<N: BitcoinNetwork>(public_key: &BitcoinPublicKey<N>) -> Self {
+        Self::builder()
+            .push_public_key(public_key)
+            .push_opcode(Opcode::OP_CHECKSIG)
+            .into_script()
+    }
+
+    /// Returns the P2PKH scriptPubKey, OP_DUP OP_HASH160 <public key hash> OP_EQUALVERIFY OP_CHECKSIG.
+    pub fn p2pkh(public_key_hash: &[u8; 20]) -> Self {
+        Self::builder()
+            .push_opcode(Opcode::OP_DUP)
+            .push_opcode(Opcode::OP_HASH160)
+            .push_data(public_key_hash)
+            .push_opcode(Opcode::OP_EQUALVERIFY)
+            .push_opcode(Opcode::OP_CHECKSIG)
+            .into_script()
+    }
+
+    /// Returns the P2SH scriptPubKey, OP_HASH160 <script hash> OP_EQUAL.
+    pub fn p2sh(script_hash: &[u8; 20]) -> Self {
+        Self::builder()
+            .push_opcode(Opcode::OP_HASH160)
+            .push_data(script_hash)
+            .push_opcode(Opcode::OP_EQUAL)
+            .into_script()
+    }
+
+    /// Returns the P2WPKH scriptPubKey, OP_0 <public key hash>.
+    pub fn p2wpkh(public_key_hash: &[u8; 20]) -> Self {
+        Self::builder()
+            .push_opcode(Opcode::OP_0)
+            .push_data(public_key_hash)
+            .into_script()
+    }
+
+    /// Returns the P2WSH scriptPubKey, OP_0 <script hash>.
+    pub fn p2wsh(script_hash: &[u8; 32]) -> Self {
+        Self::builder()
+            .push_opcode(Opcode::OP_0)
+            .push_data(script_hash)
+            .into_script()
+    }
+
+    /// Returns the P2TR scriptPubKey, OP_1 <x-only output key>.
+    pub fn p2tr(output_key: &[u8; 32]) -> Self {
+        Self::builder()
+            .push_opcode(Opcode::OP_1)
+            .push_data(output_key)
+            .into_script()
+    }
+
+    /// Returns the provably unspendable data carrier script, OP_RETURN <data>.
+    pub fn op_return(data: &[u8]) -> Self {
+        Self::builder()
+            .push_opcode(Opcode::OP_RETURN)
+            .push_data(data)
+            .into_script()
+    }
+
+    /// Returns the multisig script, OP_M <public keys> OP_N OP_CHECKMULTISIG, with the public keys in the given order.
+    pub fn multisig<N: BitcoinNetwork>(threshold: usize, public_keys: &[BitcoinPublicKey<N>]) -> Self {
+        public_keys
+            .iter()
+            .fold(Self::builder().push_int(threshold as i64), |builder, public_key| {
+                builder.push_public_key(public_key)
+            })
+            .push_int(public_keys.len() as i64)
+            .push_opcode(Opcode::OP_CHECKMULTISIG)
+            .into_script()
+    }
+
+    /// Returns the instructions of the script.
+    pub fn to_instructions(&self) -> Result<Vec<BitcoinScriptInstruction>, ScriptError> {
+        let script = &self.script;
+        let mut instructions = vec![];
+        let mut index = 0;
+        while index < script.len() {
+            let opcode = script[index];
+            let (length_size, length) = match opcode {
+                0x01..=0x4b => (0, opcode as usize),
+                0x4c..=0x4e => {
+                    let size = 1 << (opcode - 0x4c);
+                    let bytes = script
+                        .get(index + 1..index + 1 + size)
+                        .ok_or(ScriptError::InvalidPush(index))?;
+                    let length = bytes
+                        .iter()
+                        .rev()
+                        .fold(0usize, |length, byte| (length << 8) | *byte as usize);
+                    (size, length)
+                }
+                _ => {
+                    instructions.push(BitcoinScriptInstruction::Opcode(opcode));
+                    index += 1;
+                    continue;
+                }
+            };
+
+            let start = index + 1 + length_size;
+            let data = script
+                .get(start..start + length)
+                .ok_or(ScriptError::InvalidPush(index))?;
+            instructions.push(BitcoinScriptInstruction::Push(data.to_vec()));
+            index = start + length;
+        }
+        Ok(instructions)
+    }
+
+    /// Returns the serialized public key of the script if it is a P2PK script, <public key> OP_CHECKSIG.
+    pub fn to_p2pk_public_key(&self) -> Option<Vec<u8>> {
+        match self.to_instructions().ok()?.as_slice() {
+            [BitcoinScriptInstruction::Push(public_key), BitcoinScriptInstruction::Opcode(opcode)]
+                if *opcode == Opcode::OP_CHECKSIG as u8 && is_public_key(public_key) =>
+            {
+                Some(public_key.clone())
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the threshold and the serialized public keys of the script in order
+    /// if it is a multisig script, OP_M <public keys> OP_N OP_CHECKMULTISIG.
+    pub fn to_multisig(&self) -> Option<(usize, Vec<Vec<u8>>)> {
+        let instructions = self.to_instructions().ok()?;
+        let (threshold, rest) = instructions.split_first()?;
+        let (checkmultisig, rest) = rest.split_last()?;
+        let (total, keys) = rest.split_last()?;
+
+        let small_integer = |instruction: &BitcoinScriptInstruction| match instruction {
+            BitcoinScriptInstruction::Opcode(opcode @ 0x51..=0x60) => Some((opcode - 0x50) as usize),
+            _ => None,
+        };
+        let (threshold, total) = (small_integer(threshold)?, small_integer(total)?);
+        if *checkmultisig != BitcoinScriptInstruction::Opcode(Opcode::OP_CHECKMULTISIG as u8)
+            || total != keys.len()
+            || threshold > total
+        {
+            return None;
+        }
+
+        let public_keys = keys
+            .iter()
+            .map(|key| match key {
+                BitcoinScriptInstruction::Push(public_key) if is_public_key(public_key) => Some(public_key.clone()),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some((threshold, public_keys))
+    }
+
+    /// Returns the type of the script as a scriptPubKey.
+    pub fn to_script_type(&self) -> ScriptType {
+        match self.script.as_slice() {
+            [0x76, 0xa9, 0x14, hash @ .., 0x88, 0xac] if hash.len() == 20 => return ScriptType::PubKeyHash,
+            [0xa9, 0x14, hash @ .., 0x87] if hash.len() == 20 => return ScriptType::ScriptHash,
+            [0x00, 0x14, program @ ..] if program.len() == 20 => return ScriptType::WitnessV0KeyHash,
+            [0x00, 0x20, program @ ..] if program.len() == 32 => return ScriptType::WitnessV0ScriptHash,
+            [0x51, 0x20, program @ ..] if program.len() == 32 => return ScriptType::WitnessV1Taproot,
+            _ => {}
+        };
+
+        if self.to_p2pk_public_key().is_some() {
+            return ScriptType::PubKey;
+        }
+        if self.to_multisig().is_some() {
+            return ScriptType::Multisig;
+        }
+        // OP_RETURN followed only by pushes of data
+        match self.to_instructions() {
+            Ok(instructions) => match instructions.split_first() {
+                Some((BitcoinScriptInstruction::Opcode(opcode), data))
+                    if *opcode == Opcode::OP_RETURN as u8
+                        && data
+                            .iter()
+                            .all(|instruction| matches!(instruction, BitcoinScriptInstruction::Push(_))) =>
+                {
+                    ScriptType::NullData
+                }
+                _ => ScriptType::NonStandard,
+            },
+            Err(_) => ScriptType::NonStandard,
+        }
+    }
+
+    /// Returns the script in its human-readable assembly form, e.g. OP_DUP OP_HASH160 <hex> OP_EQUALVERIFY OP_CHECKSIG.
+    pub fn to_asm(&self) -> Result<String, ScriptError> {
+        let instructions = self.to_instructions()?;
+        Ok(instructions
+            .iter()
+            .map(|instruction| match instruction {
+                BitcoinScriptInstruction::Push(data) => hex::encode(data),
+                BitcoinScriptInstruction::Opcode(byte @ 0x52..=0x60) => format!("OP_{}", byte - 0x50),
+                BitcoinScriptInstruction::Opcode(byte) => match Opcode::from_byte(*byte) {
+                    Some(opcode) => opcode.to_string(),
+                    None => format!("OP_UNKNOWN_{:#04x}", byte),
+                },
+            })
+            .collect::<Vec<_>>()
+            .join(" "))
+    }
+
+    /// Returns the P2SH address of the script as a redeem script.
+    pub fn to_p2sh_address<N: BitcoinNetwork>(&self) -> Result<BitcoinAddress<N>, AddressError> {
+        BitcoinAddress::<N>::p2sh(&self.script)
+    }
+
+    /// Returns the script as a witness script, spendable as a P2WSH output.
+    pub fn to_witness_script(&self) -> Result<BitcoinWitnessScript, AddressError> {
+        BitcoinWitnessScript::new(&self.script)
+    }
+
+    /// Returns the serialized script.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.script.clone()
+    }
+
+    /// Returns the length of the serialized script in bytes.
+    pub fn len(&self) -> usize {
+        self.script.len()
+    }
+
+    /// Returns `true` if the script is empty.
+    pub fn is_empty(&self) -> bool {
+        self.script.is_empty()
+    }
+}
+
+/// Returns `true` if the data has the length and prefix of a serialized public key.
+fn is_public_key(data: &[u8]) -> bool {
+    match data.len() {
+        33 => data[0] == 0x02 || data[0] == 0x03,
+        65 => data[0] == 0x04,
+        _ => false,
+    }
+}
+
+impl FromStr for BitcoinScript {
+    type Err = ScriptError;
+
+    fn from_str(script: &str) -> Result<Self, Self::Err> {
+        match hex::decode(script) {
+            Ok(script) => Ok(Self::new(&script)),
+            Err(_) => Err(ScriptError::InvalidHex(script.into())),
+        }
+    }
+}
+
+impl fmt::Display for BitcoinScript {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", hex::encode(&self.script))
+    }
+}
+
+/// Represents a builder of a Bitcoin script, appending opcodes and pushes of data and numbers in order
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BitcoinScriptBuilder {
+    /// The serialized script built so far
+    script: Vec<u8>,
+}
+
+impl BitcoinScriptBuilder {
+    /// Returns a builder for a new script.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends the given opcode.
+    pub fn push_opcode(mut self, opcode: Opcode) -> Self {
+        self.script.push(opcode as u8);
+        self
+    }
+
+    /// Appends a push of the given data, using the smallest push opcode for its length.
+    pub fn push_data(mut self, data: &[u8]) -> Self {
+        match data.len() {
+            length @ 0..=0x4b => self.script.push(length as u8),
+            length @ 0x4c..=0xff => self
+                .script
+                .extend_from_slice(&[Opcode::OP_PUSHDATA1 as u8, length as u8]),
+            length @ 0x100..=0xffff => {
+                self.script.push(Opcode::OP_PUSHDATA2 as u8);
+                self.script.extend_from_slice(&(length as u16).to_le_bytes());
+            }
+            length => {
+                self.script.push(Opcode::OP_PUSHDATA4 as u8);
+                self.script.extend_from_slice(&(length as u32).to_le_bytes());
+            }
+        }
+        self.script.extend_from_slice(data);
+        self
+    }
+
+    /// Appends a push of the given number, as OP_0, OP_1NEGATE, or OP_1 to OP_16 where possible,
+    /// and otherwise as its minimal encoding as a script number.
+    pub fn push_int(mut self, n: i64) -> Self {
+        match n {
+            0 => self.push_opcode(Opcode::OP_0),
+            -1 => self.push_opcode(Opcode::OP_1NEGATE),
+            1..=16 => {
+                self.script.push(Opcode::OP_1 as u8 + n as u8 - 1);
+                self
+            }
+            _ => {
+                let mut bytes = n.unsigned_abs().to_le_bytes().to_vec();
+                while bytes.last() == Some(&0) {
+                    bytes.pop();
+                }
+                // Script numbers are in sign-magnitude form, so a set high bit requires an additional byte
+                match bytes.last() {
+                    Some(byte) if byte & 0x80 != 0 => bytes.push(if n < 0 { 0x80 } else { 0x00 }),
+                    _ if n < 0 => *bytes.last_mut().unwrap() |= 0x80,
+                    _ => (),
+                }
+                self.push_data(&bytes)
+            }
+        }
+    }
+
+    /// Appends a push of the serialized public key, compressed or uncompressed as the public key is.
+    pub fn push_public_key<N: BitcoinNetwork>(self, public_key: &BitcoinPublicKey<N>) -> Self {
+        match public_key.is_compressed() {
+            true => self.push_data(&public_key.to_secp256k1_public_key().serialize_compressed()),
+            false => self.push_data(&public_key.to_secp256k1_public_key().serialize()),
+        }
+    }
+
+    /// Appends the serialized script as is.
+    pub fn push_script(mut self, script: &BitcoinScript) -> Self {
+        self.script.extend_from_slice(&script.script);
+        self
+    }
+
+    /// Returns the built script.
+    pub fn into_script(self) -> BitcoinScript {
+        BitcoinScript { script: self.script }
+    }
+}
+
+#[derive(Debug, Fail)]
+pub enum ScriptError {
+    #[fail(display = "invalid script hex: {}", _0)]
+    InvalidHex(String),
+
+    #[fail(display = "invalid data push at byte {}", _0)]
+    InvalidPush(usize),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::multisig::BitcoinMultisig;
+    use crate::network::*;
+    use wagyu_model::crypto::hash160;
+
+    type N = Mainnet;
+
+    #[test]
+    fn templates() {
+        // https://github.com/bitcoin/bips/blob/master/bip-0173.mediawiki#examples
+        let public_key =
+            BitcoinPublicKey::<N>::from_str("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")
+                .unwrap();
+        let public_key_hash = hash160(&public_key.to_secp256k1_public_key().serialize_compressed());
+        let mut hash = [0u8; 20];
+        hash.copy_from_slice(&public_key_hash);
+
+        let address = BitcoinAddress::<N>::p2pkh(&public_key).unwrap();
+        assert_eq!(
+            BitcoinScript::from_address(&address).unwrap(),
+            BitcoinScript::p2pkh(&hash)
+        );
+        assert_eq!(
+            "76a914751e76e8199196d454941c45d1b3a323f1433bd688ac",
+            BitcoinScript::p2pkh(&hash).to_string()
+        );
+
+        let address = BitcoinAddress::<N>::bech32(&public_key).unwrap();
+        assert_eq!(
+            BitcoinScript::from_address(&address).unwrap(),
+            BitcoinScript::p2wpkh(&hash)
+        );
+        assert_eq!(
+            "0014751e76e8199196d454941c45d1b3a323f1433bd6",
+            BitcoinScript::p2wpkh(&hash).to_string()
+        );
+
+        let redeem_script = BitcoinScript::p2wpkh(&hash);
+        let mut script_hash = [0u8; 20];
+        script_hash.copy_from_slice(&hash160(&redeem_script.to_bytes()));
+        let address = BitcoinAddress::<N>::p2sh_p2wpkh(&public_key).unwrap();
+        assert_eq!(address, redeem_script.to_p2sh_address::<N>().unwrap());
+        assert_eq!(
+            BitcoinScript::from_address(&address).unwrap(),
+            BitcoinScript::p2sh(&script_hash)
+        );
+
+        let witness_script = BitcoinScript::builder()
+            .push_public_key(&public_key)
+            .push_opcode(Opcode::OP_CHECKSIG)
+            .into_script()
+            .to_witness_script()
+            .unwrap();
+        let address = witness_script.to_address::<N>().unwrap();
+        assert_eq!(
+            BitcoinScript::from_address(&address).unwrap(),
+            BitcoinScript::p2wsh(&witness_script.to_script_hash())
+        );
+
+        let address = BitcoinAddress::<N>::p2tr(&public_key).unwrap();
+        let output_key = public_key.to_taproot_output_key().unwrap();
+        assert_eq!(
+            BitcoinScript::from_address(&address).unwrap(),
+            BitcoinScript::p2tr(&output_key)
+        );
+
+        assert_eq!("6a0568656c6c6f", BitcoinScript::op_return(b"hello").to_string());
+    }
+
+    #[test]
+    fn multisig() {
+        // https://github.com/bitcoin/bips/blob/master/bip-0067.mediawiki#test-vectors
+        let public_keys = [
+            "02fe6f0a5a297eb38c391581c4413e084773ea23954d93f7753db7dc0adc188b2f",
+            "02ff12471208c14bd580709cb2358d98975247d8765f92bc25eab3b2763ed605f8",
+        ]
+        .iter()
+        .map(|public_key| BitcoinPublicKey::<N>::from_str(public_key).unwrap())
+        .collect::<Vec<_>>();
+
+        let script = BitcoinScript::multisig(2, &public_keys);
+        assert_eq!(
+            "522102fe6f0a5a297eb38c391581c4413e084773ea23954d93f7753db7dc0adc188b2f2102ff12471208c14bd580709cb2358d98975247d8765f92bc25eab3b2763ed605f852ae",
+            script.to_string()
+        );
+        assert_eq!(
+            BitcoinMultisig::new(2, &public_keys).unwrap().to_redeem_script(),
+            script.to_bytes()
+        );
+        assert_eq!(
+            "39bgKC7RFbpoCRbtD5KEdkYKtNyhpsNa3Z",
+            script.to_p2sh_address::<N>().unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn bare_scripts() {
+        let public_key =
+            BitcoinPublicKey::<N>::from_str("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")
+                .unwrap();
+        let serialized_public_key = public_key.to_secp256k1_public_key().serialize_compressed().to_vec();
+
+        let script = BitcoinScript::p2pk(&public_key);
+        assert_eq!(
+            "210279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798ac",
+            script.to_string()
+        );
+        assert_eq!(Some(serialized_public_key.clone()), script.to_p2pk_public_key());
+        assert_eq!(None, script.to_multisig());
+
+        let script = BitcoinScript::multisig(1, &[public_key.clone(), public_key.clone()]);
+        assert_eq!(
+            Some((1, vec![serialized_public_key.clone(), serialized_public_key])),
+            script.to_multisig()
+        );
+        assert_eq!(None, script.to_p2pk_public_key());
+
+        // A threshold above the number of public keys, and a mismatched number of public keys
+        assert_eq!(
+            None,
+            BitcoinScript::multisig(3, &[public_key.clone(), public_key.clone()]).to_multisig()
+        );
+        let script = BitcoinScript::builder()
+            .push_int(1)
+            .push_public_key(&public_key)
+            .push_int(2)
+            .push_opcode(Opcode::OP_CHECKMULTISIG)
+            .into_script();
+        assert_eq!(None, script.to_multisig());
+
+        // Pushes which are not public keys
+        assert_eq!(None, BitcoinScript::from_str("0201ffac").unwrap().to_p2pk_public_key());
+        let script = BitcoinScript::from_str("76a914751e76e8199196d454941c45d1b3a323f1433bd688ac").unwrap();
+        assert_eq!(None, script.to_p2pk_public_key());
+        assert_eq!(None, script.to_multisig());
+    }
+
+    #[test]
+    fn script_types() {
+        let script_type = |script: &str| BitcoinScript::from_str(script).unwrap().to_script_type();
+        let public_key =
+            BitcoinPublicKey::<N>::from_str("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")
+                .unwrap();
+
+        assert_eq!(
+            ScriptType::PubKeyHash,
+            script_type("76a914751e76e8199196d454941c45d1b3a323f1433bd688ac")
+        );
+        assert_eq!(
+            ScriptType::ScriptHash,
+            script_type("a914f7146aaa6f24a1012528c1d27cfe49d256d5a70187")
+        );
+        assert_eq!(
+            ScriptType::WitnessV0KeyHash,
+            script_type("0014751e76e8199196d454941c45d1b3a323f1433bd6")
+        );
+        assert_eq!(
+            ScriptType::WitnessV0ScriptHash,
+            script_type(&format!("0020{}", "ab".repeat(32)))
+        );
+        assert_eq!(
+            ScriptType::WitnessV1Taproot,
+            script_type(&format!("5120{}", "ab".repeat(32)))
+        );
+        assert_eq!(ScriptType::PubKey, BitcoinScript::p2pk(&public_key).to_script_type());
+        assert_eq!(
+            ScriptType::Multisig,
+            BitcoinScript::multisig(1, &[public_key]).to_script_type()
+        );
+        assert_eq!(
+            ScriptType::NullData,
+            BitcoinScript::op_return(b"wagyu").to_script_type()
+        );
+        assert_eq!(ScriptType::NullData, script_type("6a"));
+
+        // Truncated templates, an opcode after OP_RETURN, and an invalid push
+        assert_eq!(
+            ScriptType::NonStandard,
+            script_type("76a914751e76e8199196d454941c45d1b3a323f1433b88ac")
+        );
+        assert_eq!(ScriptType::NonStandard, script_type("6a0101ac"));
+        assert_eq!(ScriptType::NonStandard, script_type("6a02ff"));
+        assert_eq!("witness_v0_keyhash", ScriptType::WitnessV0KeyHash.to_string());
+    }
+
+    #[test]
+    fn push_data() {
+        let push = |length: usize| {
+            BitcoinScript::builder()
+                .push_data(&vec![0xab; length])
+                .into_script()
+                .to_bytes()
+        };
+
+        assert_eq!(vec![0x00], push(0));
+        assert_eq!(vec![0x01, 0xab], push(1));
+        assert_eq!(&[0x4b, 0xab], &push(75)[0..2]);
+        assert_eq!(&[0x4c, 0x4c, 0xab], &push(76)[0..3]);
+        assert_eq!(&[0x4c, 0xff, 0xab], &push(255)[0..3]);
+        assert_eq!(&[0x4d, 0x00, 0x01, 0xab], &push(256)[0..4]);
+        assert_eq!(&[0x4e, 0x00, 0x00, 0x01, 0x00, 0xab], &push(65536)[0..6]);
+        assert_eq!(65536 + 5, push(65536).len());
+    }
+
+    #[test]
+    fn push_int() {
+        let push = |n: i64| hex::encode(BitcoinScript::builder().push_int(n).into_script().to_bytes());
+
+        assert_eq!("00", push(0));
+        assert_eq!("4f", push(-1));
+        assert_eq!("51", push(1));
+        assert_eq!("60", push(16));
+        assert_eq!("0111", push(17));
+        assert_eq!("0182", push(-2));
+        assert_eq!("017f", push(127));
+        assert_eq!("028000", push(128));
+        assert_eq!("028080", push(-128));
+        assert_eq!("02ff00", push(255));
+        assert_eq!("03a08601", push(100_000));
+        assert_eq!("0400e1f585", push(-100_000_000));
+        assert_eq!("050000008080", push(-(1 << 31)));
+    }
+
+    #[test]
+    fn to_asm() {
+        let script = BitcoinScript::from_str("76a914751e76e8199196d454941c45d1b3a323f1433bd688ac").unwrap();
+        assert_eq!(
+            "OP_DUP OP_HASH160 751e76e8199196d454941c45d1b3a323f1433bd6 OP_EQUALVERIFY OP_CHECKSIG",
+            script.to_asm().unwrap()
+        );
+
+        let script = BitcoinScript::builder()
+            .push_int(2)
+            .push_int(3)
+            .push_opcode(Opcode::OP_ADD)
+            .push_int(5)
+            .push_opcode(Opcode::OP_NUMEQUAL)
+            .push_data(&[0xcd; 80])
+            .push_opcode(Opcode::OP_DROP)
+            .into_script();
+        assert_eq!(
+            format!("OP_2 OP_3 OP_ADD OP_5 OP_NUMEQUAL {} OP_DROP", "cd".repeat(80)),
+            script.to_asm().unwrap()
+        );
+        assert_eq!(
+            vec![
+                BitcoinScriptInstruction::Opcode(0x52),
+                BitcoinScriptInstruction::Opcode(0x53),
+                BitcoinScriptInstruction::Opcode(Opcode::OP_ADD as u8),
+                BitcoinScriptInstruction::Opcode(0x55),
+                BitcoinScriptInstruction::Opcode(Opcode::OP_NUMEQUAL as u8),
+                BitcoinScriptInstruction::Push(vec![0xcd; 80]),
+                BitcoinScriptInstruction::Opcode(Opcode::OP_DROP as u8),
+            ],
+            script.to_instructions().unwrap()
+        );
+    }
+
+    #[test]
+    fn invalid_push() {
+        assert!(BitcoinScript::from_str("0201").unwrap().to_instructions().is_err());
+        assert!(BitcoinScript::from_str("4c").unwrap().to_instructions().is_err());
+        assert!(BitcoinScript::from_str("4d0100").unwrap().to_instructions().is_err());
+        assert!(BitcoinScript::from_str("76a9").unwrap().to_instructions().is_ok());
+        assert!(BitcoinScript::from_str("zz").is_err());
+    }
+}
@@ -0,0 +1,521 @@
+use crate::address::BitcoinAddress;
+use crate::network::BitcoinNetwork;
+use crate::public_key::BitcoinPublicKey;
+use crate::transaction::variable_length_integer;
+use wagyu_model::{crypto::tagged_hash, no_std::*, AddressError, CrateError, PublicKeyError, TransactionError};
+
+/// The leaf version of BIP342 tapscript
+pub const TAPSCRIPT_LEAF_VERSION: u8 = 0xC0;
+/// The maximum depth of a script tree, bounded by the size of a control block
+pub const TAPROOT_CONTROL_MAX_DEPTH: usize = 128;
+/// The length of a control block in bytes, excluding its Merkle path
+const CONTROL_BLOCK_BASE_LENGTH: usize = 33;
+
+/// Returns the x-only Taproot output key of the x-only internal key, committing to the Merkle root
+/// of a script tree if given, and whether the output key has an odd y-coordinate.
+pub(crate) fn to_output_key(
+    internal_key: &[u8; 32],
+    merkle_root: Option<&[u8; 32]>,
+) -> Result<([u8; 32], bool), PublicKeyError> {
+    let mut even_public_key = [secp256k1::util::TAG_PUBKEY_EVEN; 33];
+    even_public_key[1..].copy_from_slice(internal_key);
+    let mut output_key = secp256k1::PublicKey::parse_compressed(&even_public_key)?;
+
+    let tweak = match merkle_root {
+        Some(merkle_root) => tagged_hash(b"TapTweak", &[&internal_key[..], &merkle_root[..]].concat()),
+        None => tagged_hash(b"TapTweak", internal_key),
+    };
+    output_key.tweak_add_assign(&secp256k1::SecretKey::parse(&tweak)?)?;
+
+    let output_key = output_key.serialize_compressed();
+    let mut x_only_output_key = [0u8; 32];
+    x_only_output_key.copy_from_slice(&output_key[1..]);
+    Ok((x_only_output_key, output_key[0] == secp256k1::util::TAG_PUBKEY_ODD))
+}
+
+/// Returns the hash of a branch of a script tree, which commits to its children in lexicographic order.
+fn to_branch_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    match left <= right {
+        true => tagged_hash(b"TapBranch", &[&left[..], &right[..]].concat()),
+        false => tagged_hash(b"TapBranch", &[&right[..], &left[..]].concat()),
+    }
+}
+
+/// Represents a leaf of a Taproot script tree
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TaprootLeaf {
+    /// The leaf version of the script
+    version: u8,
+    /// The script of the leaf
+    script: Vec<u8>,
+}
+
+impl TaprootLeaf {
+    /// Returns a leaf of the script with the given leaf version.
+    pub fn new(version: u8, script: &[u8]) -> Result<Self, TaprootError> {
+        // The lowest bit stores the output key parity in control blocks, and 0x50 is reserved for the annex.
+        if version & 0x01 != 0 || version == 0x50 {
+            return Err(TaprootError::InvalidLeafVersion(version));
+        }
+        Ok(Self {
+            version,
+            script: script.to_vec(),
+        })
+    }
+
+    /// Returns a tapscript leaf of the script.
+    pub fn tapscript(script: &[u8]) -> Self {
+        Self {
+            version: TAPSCRIPT_LEAF_VERSION,
+            script: script.to_vec(),
+        }
+    }
+
+    /// Returns the leaf version of the leaf.
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// Returns the script of the leaf.
+    pub fn script(&self) -> &[u8] {
+        &self.script
+    }
+
+    /// Returns the leaf hash, which commits to the leaf version and the script.
+    pub fn to_leaf_hash(&self) -> Result<[u8; 32], TaprootError> {
+        let mut preimage = vec![self.version];
+        preimage.extend(variable_length_integer(self.script.len() as u64)?);
+        preimage.extend_from_slice(&self.script);
+        Ok(tagged_hash(b"TapLeaf", &preimage))
+    }
+}
+
+/// Represents a Taproot script tree, a binary tree with script leaves
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TaprootScriptTree {
+    Leaf(TaprootLeaf),
+    Branch(Box<TaprootScriptTree>, Box<TaprootScriptTree>),
+}
+
+impl TaprootScriptTree {
+    /// Returns a script tree of a single tapscript leaf.
+    pub fn leaf(script: &[u8]) -> Self {
+        TaprootScriptTree::Leaf(TaprootLeaf::tapscript(script))
+    }
+
+    /// Returns a script tree with the given subtrees as children.
+    pub fn branch(left: Self, right: Self) -> Self {
+        TaprootScriptTree::Branch(Box::new(left), Box::new(right))
+    }
+
+    /// Returns a balanced script tree of tapscript leaves, pairing adjacent subtrees
+    /// level by level in the given order.
+    pub fn from_scripts<S: AsRef<[u8]>>(scripts: &[S]) -> Result<Self, TaprootError> {
+        let mut subtrees: Vec<Self> = scripts.iter().map(|script| Self::leaf(script.as_ref())).collect();
+        if subtrees.is_empty() {
+            return Err(TaprootError::MissingScripts);
+        }
+
+        while subtrees.len() > 1 {
+            let mut level = vec![];
+            let mut iterator = subtrees.into_iter();
+            while let Some(left) = iterator.next() {
+                match iterator.next() {
+                    Some(right) => level.push(Self::branch(left, right)),
+                    None => level.push(left),
+                }
+            }
+            subtrees = level;
+        }
+
+        let tree = subtrees.remove(0);
+        tree.validate()?;
+        Ok(tree)
+    }
+
+    /// Returns the leaves of the script tree, from left to right.
+    pub fn leaves(&self) -> Vec<&TaprootLeaf> {
+        match self {
+            TaprootScriptTree::Leaf(leaf) => vec![leaf],
+            TaprootScriptTree::Branch(left, right) => {
+                let mut leaves = left.leaves();
+                leaves.extend(right.leaves());
+                leaves
+            }
+        }
+    }
+
+    /// Returns the Merkle root of the script tree.
+    pub fn to_merkle_root(&self) -> Result<[u8; 32], TaprootError> {
+        match self {
+            TaprootScriptTree::Leaf(leaf) => leaf.to_leaf_hash(),
+            TaprootScriptTree::Branch(left, right) => {
+                Ok(to_branch_hash(&left.to_merkle_root()?, &right.to_merkle_root()?))
+            }
+        }
+    }
+
+    /// Returns the Merkle path of the leaf, the hashes of its siblings from the leaf up to the root,
+    /// or `None` if the leaf is not in the script tree.
+    pub fn to_merkle_path(&self, leaf: &TaprootLeaf) -> Result<Option<Vec<[u8; 32]>>, TaprootError> {
+        match self {
+            TaprootScriptTree::Leaf(candidate) => Ok(match candidate == leaf {
+                true => Some(vec![]),
+                false => None,
+            }),
+            TaprootScriptTree::Branch(left, right) => {
+                for (child, sibling) in [(left, right), (right, left)].iter() {
+                    if let Some(mut path) = child.to_merkle_path(leaf)? {
+                        path.push(sibling.to_merkle_root()?);
+                        return Ok(Some(path));
+                    }
+                }
+                Ok(None)
+            }
+        }
+    }
+
+    /// Returns the depth of the deepest leaf of the script tree.
+    fn depth(&self) -> usize {
+        match self {
+            TaprootScriptTree::Leaf(_) => 0,
+            TaprootScriptTree::Branch(left, right) => 1 + left.depth().max(right.depth()),
+        }
+    }
+
+    /// Checks that every leaf of the script tree can be spent with a control block.
+    pub fn validate(&self) -> Result<(), TaprootError> {
+        match self.depth() {
+            depth if depth > TAPROOT_CONTROL_MAX_DEPTH => Err(TaprootError::InvalidDepth(depth)),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Represents the spending data of a Taproot output, its internal key and optional script tree
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaprootSpendInfo<N: BitcoinNetwork> {
+    /// The internal key of the output
+    internal_key: BitcoinPublicKey<N>,
+    /// The script tree of the output
+    script_tree: Option<TaprootScriptTree>,
+    /// The x-only output key
+    output_key: [u8; 32],
+    /// If true, the output key has an odd y-coordinate
+    output_key_parity: bool,
+}
+
+impl<N: BitcoinNetwork> TaprootSpendInfo<N> {
+    /// Returns the spending data of the Taproot output with the given internal key and script tree.
+    pub fn new(
+        internal_key: &BitcoinPublicKey<N>,
+        script_tree: Option<TaprootScriptTree>,
+    ) -> Result<Self, TaprootError> {
+        let merkle_root = match &script_tree {
+            Some(script_tree) => {
+                script_tree.validate()?;
+                Some(script_tree.to_merkle_root()?)
+            }
+            None => None,
+        };
+        let (output_key, output_key_parity) = internal_key.to_taproot_tweaked_key(merkle_root.as_ref())?;
+
+        Ok(Self {
+            internal_key: internal_key.clone(),
+            script_tree,
+            output_key,
+            output_key_parity,
+        })
+    }
+
+    /// Returns the script tree of the output.
+    pub fn script_tree(&self) -> Option<&TaprootScriptTree> {
+        self.script_tree.as_ref()
+    }
+
+    /// Returns the x-only output key.
+    pub fn output_key(&self) -> [u8; 32] {
+        self.output_key
+    }
+
+    /// Returns the P2TR address of the output key.
+    pub fn to_address(&self) -> Result<BitcoinAddress<N>, AddressError> {
+        BitcoinAddress::p2tr_output_key(&self.output_key)
+    }
+
+    /// Returns the control block for spending the output through the script path of the leaf.
+    pub fn to_control_block(&self, leaf: &TaprootLeaf) -> Result<TaprootControlBlock, TaprootError> {
+        let merkle_path = match &self.script_tree {
+            Some(script_tree) => script_tree.to_merkle_path(leaf)?,
+            None => None,
+        };
+
+        Ok(TaprootControlBlock {
+            leaf_version: leaf.version,
+            output_key_parity: self.output_key_parity,
+            internal_key: self.internal_key.to_x_only_public_key(),
+            merkle_path: merkle_path.ok_or(TaprootError::MissingLeaf)?,
+        })
+    }
+
+    /// Returns the spend of the output through its key path.
+    pub fn to_key_path_spend(&self) -> Result<TaprootSpend, TaprootError> {
+        match &self.script_tree {
+            Some(script_tree) => Ok(TaprootSpend::KeyPath(Some(script_tree.to_merkle_root()?))),
+            None => Ok(TaprootSpend::KeyPath(None)),
+        }
+    }
+
+    /// Returns the spend of the output through the script path of the leaf.
+    pub fn to_script_path_spend(&self, leaf: &TaprootLeaf) -> Result<TaprootSpend, TaprootError> {
+        Ok(TaprootSpend::ScriptPath(leaf.clone(), self.to_control_block(leaf)?))
+    }
+}
+
+/// Represents how a Taproot output is spent, either through its key path with the internal key
+/// tweaked by the Merkle root of its script tree, if any, or through the script path of a leaf
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TaprootSpend {
+    /// Spends the output with a signature of the tweaked internal key
+    KeyPath(Option<[u8; 32]>),
+    /// Spends the output with the leaf script and the control block proving its commitment
+    ScriptPath(TaprootLeaf, TaprootControlBlock),
+}
+
+/// Represents a BIP341 control block, the witness element which proves that a script
+/// is committed to by the output key of a script path spend
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TaprootControlBlock {
+    /// The leaf version of the script
+    leaf_version: u8,
+    /// If true, the output key has an odd y-coordinate
+    output_key_parity: bool,
+    /// The x-only internal key
+    internal_key: [u8; 32],
+    /// The Merkle path from the leaf to the root
+    merkle_path: Vec<[u8; 32]>,
+}
+
+impl TaprootControlBlock {
+    /// Returns a control block from its serialization.
+    pub fn new(control_block: &[u8]) -> Result<Self, TaprootError> {
+        let length = control_block.len();
+        if length < CONTROL_BLOCK_BASE_LENGTH
+            || !(length - CONTROL_BLOCK_BASE_LENGTH).is_multiple_of(32)
+            || (length - CONTROL_BLOCK_BASE_LENGTH) / 32 > TAPROOT_CONTROL_MAX_DEPTH
+        {
+            return Err(TaprootError::InvalidControlBlockLength(length));
+        }
+
+        let mut internal_key = [0u8; 32];
+        internal_key.copy_from_slice(&control_block[1..CONTROL_BLOCK_BASE_LENGTH]);
+
+        Ok(Self {
+            leaf_version: control_block[0] & 0xFE,
+            output_key_parity: control_block[0] & 0x01 == 0x01,
+            internal_key,
+            merkle_path: control_block[CONTROL_BLOCK_BASE_LENGTH..]
+                .chunks(32)
+                .map(|chunk| {
+                    let mut hash = [0u8; 32];
+                    hash.copy_from_slice(chunk);
+                    hash
+                })
+                .collect(),
+        })
+    }
+
+    /// Returns the leaf version of the script.
+    pub fn leaf_version(&self) -> u8 {
+        self.leaf_version
+    }
+
+    /// Returns the serialization of the control block.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut control_block = vec![self.leaf_version | self.output_key_parity as u8];
+        control_block.extend_from_slice(&self.internal_key);
+        self.merkle_path
+            .iter()
+            .for_each(|hash| control_block.extend_from_slice(hash));
+        control_block
+    }
+
+    /// Returns `true` if the control block proves that the script is committed to by the output key.
+    /// https://github.com/bitcoin/bips/blob/master/bip-0341.mediawiki#script-validation-rules
+    pub fn verify(&self, output_key: &[u8; 32], script: &[u8]) -> bool {
+        let leaf = TaprootLeaf {
+            version: self.leaf_version,
+            script: script.to_vec(),
+        };
+        let merkle_root = match leaf.to_leaf_hash() {
+            Ok(leaf_hash) => self
+                .merkle_path
+                .iter()
+                .fold(leaf_hash, |hash, sibling| to_branch_hash(&hash, sibling)),
+            Err(_) => return false,
+        };
+
+        match to_output_key(&self.internal_key, Some(&merkle_root)) {
+            Ok((expected, parity)) => expected == *output_key && parity == self.output_key_parity,
+            Err(_) => false,
+        }
+    }
+}
+
+#[derive(Debug, Fail)]
+pub enum TaprootError {
+    #[fail(display = "{}", _0)]
+    AddressError(#[cause] AddressError),
+
+    #[fail(display = "invalid control block length: {}", _0)]
+    InvalidControlBlockLength(usize),
+
+    #[fail(display = "invalid script tree depth: {}", _0)]
+    InvalidDepth(usize),
+
+    #[fail(display = "invalid leaf version: {:#04x}", _0)]
+    InvalidLeafVersion(u8),
+
+    #[fail(display = "leaf not found in the script tree")]
+    MissingLeaf,
+
+    #[fail(display = "missing scripts")]
+    MissingScripts,
+
+    #[fail(display = "{}", _0)]
+    PublicKeyError(#[cause] PublicKeyError),
+
+    #[fail(display = "{}", _0)]
+    TransactionError(#[cause] TransactionError),
+}
+
+impl From<AddressError> for TaprootError {
+    fn from(error: AddressError) -> Self {
+        TaprootError::AddressError(error)
+    }
+}
+
+impl From<PublicKeyError> for TaprootError {
+    fn from(error: PublicKeyError) -> Self {
+        TaprootError::PublicKeyError(error)
+    }
+}
+
+impl From<TransactionError> for TaprootError {
+    fn from(error: TransactionError) -> Self {
+        TaprootError::TransactionError(error)
+    }
+}
+
+impl From<TaprootError> for TransactionError {
+    fn from(error: TaprootError) -> Self {
+        match error {
+            TaprootError::TransactionError(error) => error,
+            error => TransactionError::Crate(CrateError::Other("Taproot", format!("{:?}", error))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::*;
+
+    use core::str::FromStr;
+
+    type N = Mainnet;
+
+    fn internal_key(x_only_public_key: &str) -> BitcoinPublicKey<N> {
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(&hex::decode(x_only_public_key).unwrap());
+        BitcoinPublicKey::from_x_only_public_key(&bytes).unwrap()
+    }
+
+    #[test]
+    fn key_path_only() {
+        // https://github.com/bitcoin/bips/blob/master/bip-0341/wallet-test-vectors.json
+        let spend_info = TaprootSpendInfo::new(
+            &internal_key("d6889cb081036e0faefa3a35157ad71086b123b2b144b649798b494c300a961d"),
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            "53a1f6e454df1aa2776a2814a721372d6258050de330b3c6d10ee8f4e0dda343",
+            hex::encode(spend_info.output_key())
+        );
+        assert_eq!(
+            "bc1p2wsldez5mud2yam29q22wgfh9439spgduvct83k3pm50fcxa5dps59h4z5",
+            spend_info.to_address().unwrap().to_string()
+        );
+        assert!(spend_info.to_control_block(&TaprootLeaf::tapscript(&[])).is_err());
+    }
+
+    #[test]
+    fn single_leaf() {
+        // https://github.com/bitcoin/bips/blob/master/bip-0341/wallet-test-vectors.json
+        let script = hex::decode("20d85a959b0290bf19bb89ed43c916be835475d013da4b362117393e25a48229b8ac").unwrap();
+        let script_tree = TaprootScriptTree::from_scripts(&[&script]).unwrap();
+        assert_eq!(
+            "5b75adecf53548f3ec6ad7d78383bf84cc57b55a3127c72b9a2481752dd88b21",
+            hex::encode(script_tree.to_merkle_root().unwrap())
+        );
+
+        let spend_info = TaprootSpendInfo::new(
+            &internal_key("187791b6f712a8ea41c8ecdd0ee77fab3e85263b37e1ec18a3651926b3a6cf27"),
+            Some(script_tree),
+        )
+        .unwrap();
+        assert_eq!(
+            "147c9c57132f6e7ecddba9800bb0c4449251c92a1e60371ee77557b6620f3ea3",
+            hex::encode(spend_info.output_key())
+        );
+        assert_eq!(
+            "bc1pz37fc4cn9ah8anwm4xqqhvxygjf9rjf2resrw8h8w4tmvcs0863sa2e586",
+            spend_info.to_address().unwrap().to_string()
+        );
+
+        let control_block = spend_info.to_control_block(&TaprootLeaf::tapscript(&script)).unwrap();
+        assert_eq!(
+            "c1187791b6f712a8ea41c8ecdd0ee77fab3e85263b37e1ec18a3651926b3a6cf27",
+            hex::encode(control_block.to_bytes())
+        );
+        assert!(control_block.verify(&spend_info.output_key(), &script));
+    }
+
+    #[test]
+    fn script_paths() {
+        let internal_key =
+            BitcoinPublicKey::<N>::from_str("0250929b74c1a04954b78b4b6035e97a5e078a5a0f28ec96d547bfee9ace803ac0")
+                .unwrap();
+        let scripts: Vec<Vec<u8>> = (1..=5u8).map(|i| vec![0x50 + i]).collect();
+        let script_tree = TaprootScriptTree::from_scripts(&scripts).unwrap();
+        assert_eq!(5, script_tree.leaves().len());
+
+        let spend_info = TaprootSpendInfo::new(&internal_key, Some(script_tree)).unwrap();
+        for (i, script) in scripts.iter().enumerate() {
+            let control_block = spend_info.to_control_block(&TaprootLeaf::tapscript(script)).unwrap();
+            assert_eq!(
+                control_block,
+                TaprootControlBlock::new(&control_block.to_bytes()).unwrap()
+            );
+            assert!(control_block.verify(&spend_info.output_key(), script));
+            assert!(!control_block.verify(&spend_info.output_key(), &scripts[(i + 1) % scripts.len()]));
+        }
+
+        // The fifth leaf sits directly below the root of a balanced tree of five leaves.
+        let control_block = spend_info
+            .to_control_block(&TaprootLeaf::tapscript(&scripts[4]))
+            .unwrap();
+        assert_eq!(33 + 32, control_block.to_bytes().len());
+        assert!(spend_info.to_control_block(&TaprootLeaf::tapscript(&[0x00])).is_err());
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(TaprootScriptTree::from_scripts::<Vec<u8>>(&[]).is_err());
+        assert!(TaprootLeaf::new(0xC1, &[]).is_err());
+        assert!(TaprootLeaf::new(0x50, &[]).is_err());
+        assert!(TaprootControlBlock::new(&[0xC0; 32]).is_err());
+        assert!(TaprootControlBlock::new(&[0xC0; 34]).is_err());
+    }
+}
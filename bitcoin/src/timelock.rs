@@ -0,0 +1,378 @@
+//!
+//! Timelock
+//!
+//! This module contains the witness scripts of common timelock contracts: a key locked until an
+//! absolute lock time (BIP65 OP_CHECKLOCKTIMEVERIFY) or for a relative lock time after its output
+//! confirms (BIP112 OP_CHECKSEQUENCEVERIFY), and hash timelock contracts (HTLCs), which pay a
+//! recipient who reveals the preimage of a hash, or refund the sender once the timelock expires.
+//! https://github.com/bitcoin/bips/blob/master/bip-0065.mediawiki
+//! https://github.com/bitcoin/bips/blob/master/bip-0112.mediawiki
+//!
+
+use crate::address::BitcoinAddress;
+use crate::network::BitcoinNetwork;
+use crate::public_key::BitcoinPublicKey;
+use crate::script::{BitcoinScript, BitcoinScriptBuilder};
+use crate::transaction::{LockTime, Opcode, RelativeLockTime, SEQUENCE_ENABLE_LOCK_TIME, SEQUENCE_FINAL};
+use crate::witness_script::BitcoinWitnessScript;
+use wagyu_model::no_std::*;
+use wagyu_model::AddressError;
+
+use core::fmt;
+
+/// The size of the preimage of the hash of a hash timelock contract
+pub const HTLC_PREIMAGE_SIZE: usize = 32;
+
+/// Represents the timelock of a script, either absolute or relative to the confirmation of its output
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Timelock {
+    /// Spendable once the block height or Unix time is reached, checked by OP_CHECKLOCKTIMEVERIFY
+    Absolute(LockTime),
+    /// Spendable once the output has confirmed for the relative lock time, checked by OP_CHECKSEQUENCEVERIFY
+    Relative(RelativeLockTime),
+}
+
+impl Timelock {
+    /// Appends the check of the timelock, <n> OP_CHECKLOCKTIMEVERIFY OP_DROP or
+    /// <n> OP_CHECKSEQUENCEVERIFY OP_DROP, to the script.
+    fn push_check(&self, builder: BitcoinScriptBuilder) -> BitcoinScriptBuilder {
+        match self {
+            Timelock::Absolute(lock_time) => builder
+                .push_int(lock_time.to_consensus() as i64)
+                .push_opcode(Opcode::OP_CHECKLOCKTIMEVERIFY),
+            Timelock::Relative(lock_time) => builder
+                .push_int(lock_time.to_sequence() as i64)
+                .push_opcode(Opcode::OP_CHECKSEQUENCEVERIFY),
+        }
+        .push_opcode(Opcode::OP_DROP)
+    }
+}
+
+impl fmt::Display for Timelock {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Timelock::Absolute(LockTime::Height(height)) => write!(f, "after block height {}", height),
+            Timelock::Absolute(LockTime::Time(time)) => write!(f, "after Unix time {}", time),
+            Timelock::Relative(RelativeLockTime::Blocks(blocks)) => {
+                write!(f, "{} blocks after the output confirms", blocks)
+            }
+            Timelock::Relative(RelativeLockTime::Intervals(intervals)) => {
+                write!(f, "{} seconds after the output confirms", *intervals as u32 * 512)
+            }
+        }
+    }
+}
+
+/// Represents a timelock script, spendable as a P2WSH output
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BitcoinTimelockScript<N: BitcoinNetwork> {
+    /// Spendable by the key once the timelock expires
+    /// <n> OP_CHECKLOCKTIMEVERIFY|OP_CHECKSEQUENCEVERIFY OP_DROP <public key> OP_CHECKSIG
+    Timelock(Timelock, BitcoinPublicKey<N>),
+    /// Spendable by the recipient with the preimage of the SHA256 hash, or by the refund key
+    /// once the timelock expires
+    /// OP_IF OP_SIZE <32> OP_EQUALVERIFY OP_SHA256 <hash> OP_EQUALVERIFY <recipient>
+    /// OP_ELSE <n> OP_CHECKLOCKTIMEVERIFY|OP_CHECKSEQUENCEVERIFY OP_DROP <refund> OP_ENDIF OP_CHECKSIG
+    HashTimelock {
+        hash: [u8; 32],
+        recipient: BitcoinPublicKey<N>,
+        refund: BitcoinPublicKey<N>,
+        timelock: Timelock,
+    },
+}
+
+impl<N: BitcoinNetwork> BitcoinTimelockScript<N> {
+    /// Returns the script spendable by the key once the absolute lock time is reached.
+    pub fn absolute(lock_time: LockTime, public_key: &BitcoinPublicKey<N>) -> Self {
+        BitcoinTimelockScript::Timelock(Timelock::Absolute(lock_time), public_key.clone())
+    }
+
+    /// Returns the script spendable by the key once its output has confirmed for the relative lock time.
+    pub fn relative(lock_time: RelativeLockTime, public_key: &BitcoinPublicKey<N>) -> Self {
+        BitcoinTimelockScript::Timelock(Timelock::Relative(lock_time), public_key.clone())
+    }
+
+    /// Returns the hash timelock contract paying the recipient who reveals the preimage of the SHA256 hash,
+    /// or refunding the refund key once the timelock expires.
+    pub fn hash_timelock(
+        hash: [u8; 32],
+        recipient: &BitcoinPublicKey<N>,
+        refund: &BitcoinPublicKey<N>,
+        timelock: Timelock,
+    ) -> Self {
+        BitcoinTimelockScript::HashTimelock {
+            hash,
+            recipient: recipient.clone(),
+            refund: refund.clone(),
+            timelock,
+        }
+    }
+
+    /// Returns the script.
+    pub fn to_script(&self) -> BitcoinScript {
+        match self {
+            BitcoinTimelockScript::Timelock(timelock, public_key) => timelock
+                .push_check(BitcoinScript::builder())
+                .push_public_key(public_key)
+                .push_opcode(Opcode::OP_CHECKSIG)
+                .into_script(),
+            BitcoinTimelockScript::HashTimelock {
+                hash,
+                recipient,
+                refund,
+                timelock,
+            } => {
+                let builder = BitcoinScript::builder()
+                    .push_opcode(Opcode::OP_IF)
+                    .push_opcode(Opcode::OP_SIZE)
+                    .push_int(HTLC_PREIMAGE_SIZE as i64)
+                    .push_opcode(Opcode::OP_EQUALVERIFY)
+                    .push_opcode(Opcode::OP_SHA256)
+                    .push_data(hash)
+                    .push_opcode(Opcode::OP_EQUALVERIFY)
+                    .push_public_key(recipient)
+                    .push_opcode(Opcode::OP_ELSE);
+                timelock
+                    .push_check(builder)
+                    .push_public_key(refund)
+                    .push_opcode(Opcode::OP_ENDIF)
+                    .push_opcode(Opcode::OP_CHECKSIG)
+                    .into_script()
+            }
+        }
+    }
+
+    /// Returns the witness script.
+    pub fn to_witness_script(&self) -> Result<BitcoinWitnessScript, AddressError> {
+        self.to_script().to_witness_script()
+    }
+
+    /// Returns the P2SH-P2WSH address, the P2SH address of the P2WSH scriptPubKey of the witness script.
+    pub fn to_p2sh_p2wsh_address(&self) -> Result<BitcoinAddress<N>, AddressError> {
+        BitcoinAddress::<N>::p2sh(&self.to_witness_script()?.to_script_pub_key())
+    }
+
+    /// Returns the P2WSH address of the witness script.
+    pub fn to_p2wsh_address(&self) -> Result<BitcoinAddress<N>, AddressError> {
+        self.to_witness_script()?.to_address::<N>()
+    }
+
+    /// Returns the ways to spend the script, with the witness and the lock time and sequence
+    /// of the spending transaction each requires.
+    pub fn spend_paths(&self) -> Vec<TimelockSpendPath<N>> {
+        match self {
+            BitcoinTimelockScript::Timelock(timelock, public_key) => vec![TimelockSpendPath {
+                name: "timelock".into(),
+                public_key: public_key.clone(),
+                witness: vec!["<signature>".into()],
+                timelock: Some(*timelock),
+            }],
+            BitcoinTimelockScript::HashTimelock {
+                recipient,
+                refund,
+                timelock,
+                ..
+            } => vec![
+                TimelockSpendPath {
+                    name: "claim".into(),
+                    public_key: recipient.clone(),
+                    witness: vec!["<signature>".into(), "<preimage>".into(), "01".into()],
+                    timelock: None,
+                },
+                TimelockSpendPath {
+                    name: "refund".into(),
+                    public_key: refund.clone(),
+                    witness: vec!["<signature>".into(), "<empty>".into()],
+                    timelock: Some(*timelock),
+                },
+            ],
+        }
+    }
+}
+
+/// Represents a way to spend a timelock script, with a signature of its key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimelockSpendPath<N: BitcoinNetwork> {
+    /// The name of the spend path, e.g. `claim` or `refund`
+    pub name: String,
+    /// The public key whose signature spends the script
+    pub public_key: BitcoinPublicKey<N>,
+    /// The elements of the witness stack before the witness script
+    pub witness: Vec<String>,
+    /// The timelock which must expire before the script is spent, if any
+    pub timelock: Option<Timelock>,
+}
+
+impl<N: BitcoinNetwork> TimelockSpendPath<N> {
+    /// Returns the lock time of the spending transaction.
+    pub fn lock_time(&self) -> u32 {
+        match self.timelock {
+            Some(Timelock::Absolute(lock_time)) => lock_time.to_consensus(),
+            _ => 0,
+        }
+    }
+
+    /// Returns the sequence number of the spending input, which enables the lock time of the transaction
+    /// or sets the relative lock time of the input.
+    pub fn sequence(&self) -> u32 {
+        match self.timelock {
+            Some(Timelock::Absolute(_)) => SEQUENCE_ENABLE_LOCK_TIME,
+            Some(Timelock::Relative(lock_time)) => lock_time.to_sequence(),
+            None => SEQUENCE_FINAL,
+        }
+    }
+
+    /// Returns the minimum version of the spending transaction, 2 if it has a relative lock time (BIP68).
+    pub fn version(&self) -> u32 {
+        match self.timelock {
+            Some(Timelock::Relative(_)) => 2,
+            _ => 1,
+        }
+    }
+}
+
+impl<N: BitcoinNetwork> fmt::Display for TimelockSpendPath<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}: signed by {}, witness {}",
+            self.name,
+            self.public_key,
+            self.witness.join(" ")
+        )?;
+        match self.timelock {
+            Some(timelock) => write!(
+                f,
+                ", {} (version {}, lock time {}, sequence {:#010x})",
+                timelock,
+                self.version(),
+                self.lock_time(),
+                self.sequence()
+            ),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::*;
+    use crate::script::BitcoinScriptInstruction;
+
+    use core::str::FromStr;
+
+    type N = Mainnet;
+
+    const KEYS: [&str; 2] = [
+        "02e2cc6bd5f45edd43bebe7cb9b675f0ce9ed3efe613b177588290ad188d11b404",
+        "027735a29bae7780a9755fae7a1c4374c656ac6a69ea9f3697fda61bb99a4f3e77",
+    ];
+    const HASH: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+    fn public_key(index: usize) -> BitcoinPublicKey<N> {
+        BitcoinPublicKey::<N>::from_str(KEYS[index]).unwrap()
+    }
+
+    fn hash() -> [u8; 32] {
+        let mut hash = [0u8; 32];
+        hash.copy_from_slice(&hex::decode(HASH).unwrap());
+        hash
+    }
+
+    #[test]
+    fn absolute() {
+        let script = BitcoinTimelockScript::absolute(LockTime::from_height(800_000).unwrap(), &public_key(0));
+        // 800000 is pushed as the 3-byte script number 0x0c3500
+        assert_eq!(
+            format!("0300350cb17521{}ac", KEYS[0]),
+            hex::encode(script.to_script().to_bytes())
+        );
+        assert_eq!(
+            format!("00350c OP_CHECKLOCKTIMEVERIFY OP_DROP {} OP_CHECKSIG", KEYS[0]),
+            script.to_script().to_asm().unwrap()
+        );
+
+        let spend_paths = script.spend_paths();
+        assert_eq!(1, spend_paths.len());
+        assert_eq!(800_000, spend_paths[0].lock_time());
+        assert_eq!(SEQUENCE_ENABLE_LOCK_TIME, spend_paths[0].sequence());
+        assert_eq!(1, spend_paths[0].version());
+        assert_eq!(
+            format!(
+                "timelock: signed by {}, witness <signature>, after block height 800000 (version 1, lock time 800000, sequence 0xfffffffe)",
+                KEYS[0]
+            ),
+            spend_paths[0].to_string()
+        );
+    }
+
+    #[test]
+    fn relative() {
+        let script = BitcoinTimelockScript::relative(RelativeLockTime::Blocks(144), &public_key(0));
+        // 144 is pushed as the 2-byte script number 0x9000, as its high bit is set
+        assert_eq!(
+            format!("029000b27521{}ac", KEYS[0]),
+            hex::encode(script.to_script().to_bytes())
+        );
+
+        let spend_path = &script.spend_paths()[0];
+        assert_eq!(0, spend_path.lock_time());
+        assert_eq!(144, spend_path.sequence());
+        assert_eq!(2, spend_path.version());
+        assert_eq!(
+            Some(RelativeLockTime::Blocks(144)),
+            RelativeLockTime::from_sequence(spend_path.sequence())
+        );
+
+        // A time-based relative lock time is checked against the sequence with its type flag
+        let lock_time = RelativeLockTime::from_seconds(3600).unwrap();
+        let script = BitcoinTimelockScript::relative(lock_time, &public_key(0));
+        match &script.to_script().to_instructions().unwrap()[0] {
+            BitcoinScriptInstruction::Push(data) => assert_eq!(vec![0x08, 0x00, 0x40], *data),
+            instruction => panic!("unexpected instruction {:?}", instruction),
+        };
+        assert!(script.spend_paths()[0]
+            .to_string()
+            .contains("4096 seconds after the output confirms"));
+    }
+
+    #[test]
+    fn hash_timelock() {
+        let timelock = Timelock::Absolute(LockTime::from_height(800_000).unwrap());
+        let script = BitcoinTimelockScript::hash_timelock(hash(), &public_key(0), &public_key(1), timelock);
+        assert_eq!(
+            format!(
+                "OP_IF OP_SIZE 20 OP_EQUALVERIFY OP_SHA256 {} OP_EQUALVERIFY {} OP_ELSE 00350c OP_CHECKLOCKTIMEVERIFY OP_DROP {} OP_ENDIF OP_CHECKSIG",
+                HASH, KEYS[0], KEYS[1]
+            ),
+            script.to_script().to_asm().unwrap()
+        );
+
+        let spend_paths = script.spend_paths();
+        assert_eq!(2, spend_paths.len());
+        assert_eq!("claim", spend_paths[0].name);
+        assert_eq!(public_key(0), spend_paths[0].public_key);
+        assert_eq!(None, spend_paths[0].timelock);
+        assert_eq!(SEQUENCE_FINAL, spend_paths[0].sequence());
+        assert_eq!("refund", spend_paths[1].name);
+        assert_eq!(public_key(1), spend_paths[1].public_key);
+        assert_eq!(800_000, spend_paths[1].lock_time());
+
+        let address = script.to_p2wsh_address().unwrap();
+        assert_eq!(script.to_witness_script().unwrap().to_address::<N>().unwrap(), address);
+        assert!(address.to_string().starts_with("bc1q"));
+        assert!(script.to_p2sh_p2wsh_address().unwrap().to_string().starts_with('3'));
+        assert_ne!(
+            address,
+            BitcoinTimelockScript::hash_timelock(
+                hash(),
+                &public_key(0),
+                &public_key(1),
+                Timelock::Relative(RelativeLockTime::Blocks(144))
+            )
+            .to_p2wsh_address()
+            .unwrap()
+        );
+    }
+}
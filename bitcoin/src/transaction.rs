@@ -4,13 +4,17 @@ use crate::format::BitcoinFormat;
 use crate::network::BitcoinNetwork;
 use crate::private_key::BitcoinPrivateKey;
 use crate::public_key::BitcoinPublicKey;
-use crate::witness_program::WitnessProgram;
+use crate::script::{BitcoinScript, BitcoinScriptInstruction};
+use crate::taproot::{TaprootError, TaprootSpend};
+use wagyu_model::crypto::{secp256k1_sign, tagged_hash};
 use wagyu_model::no_std::{io::Read, *};
 use wagyu_model::{PrivateKey, Transaction, TransactionError, TransactionId};
 
-use base58::FromBase58;
-use bech32::{Bech32, FromBase32};
-use core::{fmt, str::FromStr};
+use core::{
+    fmt,
+    ops::{Add, AddAssign},
+    str::FromStr,
+};
 use secp256k1;
 use serde::Serialize;
 use sha2::{Digest, Sha256};
@@ -34,13 +38,13 @@ pub fn variable_length_integer(value: u64) -> Result<Vec<u8>, TransactionError>
 /// https://en.bitcoin.it/wiki/Protocol_documentation#Variable_length_integer
 pub fn read_variable_length_integer<R: Read>(mut reader: R) -> Result<usize, TransactionError> {
     let mut flag = [0u8; 1];
-    reader.read(&mut flag)?;
+    reader.read_exact(&mut flag)?;
 
     match flag[0] {
         0..=252 => Ok(flag[0] as usize),
         0xfd => {
             let mut size = [0u8; 2];
-            reader.read(&mut size)?;
+            reader.read_exact(&mut size)?;
             match u16::from_le_bytes(size) {
                 s if s < 253 => return Err(TransactionError::InvalidVariableSizeInteger(s as usize)),
                 s => Ok(s as usize),
@@ -48,7 +52,7 @@ pub fn read_variable_length_integer<R: Read>(mut reader: R) -> Result<usize, Tra
         }
         0xfe => {
             let mut size = [0u8; 4];
-            reader.read(&mut size)?;
+            reader.read_exact(&mut size)?;
             match u32::from_le_bytes(size) {
                 s if s < 65536 => return Err(TransactionError::InvalidVariableSizeInteger(s as usize)),
                 s => Ok(s as usize),
@@ -56,7 +60,7 @@ pub fn read_variable_length_integer<R: Read>(mut reader: R) -> Result<usize, Tra
         }
         _ => {
             let mut size = [0u8; 8];
-            reader.read(&mut size)?;
+            reader.read_exact(&mut size)?;
             match u64::from_le_bytes(size) {
                 s if s < 4294967296 => return Err(TransactionError::InvalidVariableSizeInteger(s as usize)),
                 s => Ok(s as usize),
@@ -92,56 +96,62 @@ impl BitcoinVector {
 
 /// Generate the script_pub_key of a corresponding address
 pub fn create_script_pub_key<N: BitcoinNetwork>(address: &BitcoinAddress<N>) -> Result<Vec<u8>, TransactionError> {
-    match address.format() {
-        BitcoinFormat::P2PKH => {
-            let bytes = &address.to_string().from_base58()?;
-            let pub_key_hash = bytes[1..(bytes.len() - 4)].to_vec();
-
-            let mut script = vec![];
-            script.push(Opcode::OP_DUP as u8);
-            script.push(Opcode::OP_HASH160 as u8);
-            script.extend(variable_length_integer(pub_key_hash.len() as u64)?);
-            script.extend(pub_key_hash);
-            script.push(Opcode::OP_EQUALVERIFY as u8);
-            script.push(Opcode::OP_CHECKSIG as u8);
-            Ok(script)
-        }
-        BitcoinFormat::P2WSH => {
-            let bech32 = Bech32::from_str(&address.to_string())?;
-            let (v, script) = bech32.data().split_at(1);
-            let script = Vec::from_base32(script)?;
-            let mut script_bytes = vec![v[0].to_u8(), script.len() as u8];
-            script_bytes.extend(script);
-            Ok(script_bytes)
-        }
-        BitcoinFormat::P2SH_P2WPKH => {
-            let script_bytes = &address.to_string().from_base58()?;
-            let script_hash = script_bytes[1..(script_bytes.len() - 4)].to_vec();
-
-            let mut script = vec![];
-            script.push(Opcode::OP_HASH160 as u8);
-            script.extend(variable_length_integer(script_hash.len() as u64)?);
-            script.extend(script_hash);
-            script.push(Opcode::OP_EQUAL as u8);
-            Ok(script)
-        }
-        BitcoinFormat::Bech32 => {
-            let bech32 = Bech32::from_str(&address.to_string())?;
-            let (v, program) = bech32.data().split_at(1);
-            let program = Vec::from_base32(program)?;
-            let mut program_bytes = vec![v[0].to_u8(), program.len() as u8];
-            program_bytes.extend(program);
-
-            Ok(WitnessProgram::new(&program_bytes)?.to_scriptpubkey())
+    Ok(address.to_script_pub_key()?.to_bytes())
+}
+
+/// Returns the estimated size of a signed input spending the given format, assuming compressed
+/// public keys, ECDSA signatures of the maximum length and Taproot key path spends with SIGHASH_DEFAULT,
+/// or `None` if the size depends on an unknown witness script.
+pub fn input_size(format: &BitcoinFormat) -> Option<BitcoinTransactionSize> {
+    match format {
+        // outpoint (36) + script length (1) + script signature (107) + sequence (4)
+        BitcoinFormat::P2PKH => Some(BitcoinTransactionSize::new(148, 0)),
+        // outpoint (36) + script length (1) + redeem script push (23) + sequence (4), witness (108)
+        BitcoinFormat::P2SH_P2WPKH => Some(BitcoinTransactionSize::new(64, 108)),
+        // outpoint (36) + script length (1) + sequence (4), witness (108)
+        BitcoinFormat::Bech32 => Some(BitcoinTransactionSize::new(41, 108)),
+        // outpoint (36) + script length (1) + sequence (4), witness (66)
+        BitcoinFormat::P2TR => Some(BitcoinTransactionSize::new(41, 66)),
+        BitcoinFormat::P2WSH | BitcoinFormat::FutureWitness => None,
+    }
+}
+
+/// Returns the estimated weight of a signed input spending the given format, as estimated by `input_size`.
+pub fn input_weight(format: &BitcoinFormat) -> Option<u64> {
+    input_size(format).map(|size| size.weight())
+}
+
+/// Returns the smallest amount an output paying to the given address may have to be relayed, as
+/// defined by the default dust relay fee rate of Bitcoin Core.
+pub fn dust_threshold<N: BitcoinNetwork>(address: &BitcoinAddress<N>) -> Result<BitcoinAmount, TransactionError> {
+    BitcoinTransactionOutput::new(address, BitcoinAmount(0))?.dust_threshold()
+}
+
+/// Returns `true` if the script is a witness program, a version opcode followed by a push of 2 to 40 bytes.
+fn is_witness_program(script: &[u8]) -> bool {
+    match script {
+        [version, length, program @ ..] => {
+            (*version == Opcode::OP_0 as u8 || (0x51..=0x60).contains(version))
+                && (2..=40).contains(&program.len())
+                && *length as usize == program.len()
         }
+        _ => false,
     }
 }
 
+/// Returns the fee paid for the given weight at the given fee rate (in Satoshi per virtual byte).
+pub fn weight_to_fee(weight: u64, fee_rate: u64) -> BitcoinAmount {
+    BitcoinAmount((weight * fee_rate).div_ceil(4) as i64)
+}
+
 /// Represents a Bitcoin signature hash
 /// https://en.bitcoin.it/wiki/OP_CHECKSIG
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
 #[allow(non_camel_case_types)]
 pub enum SignatureHash {
+    /// Signs all inputs and outputs, with a Taproot signature that omits the signature hash type.
+    /// (only valid for Taproot spends)
+    SIGHASH_DEFAULT = 0x00,
     /// Signs all inputs and outputs.
     SIGHASH_ALL = 0x01,
     /// Signs all inputs and none of the outputs.
@@ -166,6 +176,7 @@ pub enum SignatureHash {
 impl fmt::Display for SignatureHash {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
+            SignatureHash::SIGHASH_DEFAULT => write!(f, "SIGHASH_DEFAULT"),
             SignatureHash::SIGHASH_ALL => write!(f, "SIGHASH_ALL"),
             SignatureHash::SIGHASH_NONE => write!(f, "SIGHASH_NONE"),
             SignatureHash::SIGHASH_SINGLE => write!(f, "SIGHASH_SINGLE"),
@@ -176,7 +187,48 @@ impl fmt::Display for SignatureHash {
     }
 }
 
+impl FromStr for SignatureHash {
+    type Err = TransactionError;
+
+    /// Returns the signature hash of the given name, e.g. `SIGHASH_ALL`, `ALL|ANYONECANPAY`,
+    /// or `SIGHASH_SINGLE | SIGHASH_ANYONECANPAY`.
+    fn from_str(sighash: &str) -> Result<Self, Self::Err> {
+        let name = sighash.to_uppercase().replace(' ', "").replace("SIGHASH_", "");
+        match name.as_str() {
+            "DEFAULT" => Ok(SignatureHash::SIGHASH_DEFAULT),
+            "ALL" => Ok(SignatureHash::SIGHASH_ALL),
+            "NONE" => Ok(SignatureHash::SIGHASH_NONE),
+            "SINGLE" => Ok(SignatureHash::SIGHASH_SINGLE),
+            "ALL|ANYONECANPAY" => Ok(SignatureHash::SIGHASH_ALL_SIGHASH_ANYONECANPAY),
+            "NONE|ANYONECANPAY" => Ok(SignatureHash::SIGHASH_NONE_SIGHASH_ANYONECANPAY),
+            "SINGLE|ANYONECANPAY" => Ok(SignatureHash::SIGHASH_SINGLE_SIGHASH_ANYONECANPAY),
+            _ => Err(TransactionError::UnsupportedSignatureHash(sighash.into())),
+        }
+    }
+}
+
 impl SignatureHash {
+    /// Returns the signature hash of the given type, or `None` if the type is not a standard signature hash.
+    pub fn from_type(sighash_type: u32) -> Option<Self> {
+        match sighash_type {
+            0x00 => Some(SignatureHash::SIGHASH_DEFAULT),
+            0x01 | 0x02 | 0x03 | 0x81 | 0x82 | 0x83 => Some(Self::from_byte(&(sighash_type as u8))),
+            _ => None,
+        }
+    }
+
+    /// Returns the signature hash without the ANYONECANPAY modifier,
+    /// either SIGHASH_ALL, SIGHASH_NONE, or SIGHASH_SINGLE, with SIGHASH_DEFAULT signing as SIGHASH_ALL.
+    pub fn base(&self) -> Self {
+        Self::from_byte(&(*self as u8 & 0x1f))
+    }
+
+    /// Returns `true` if the signature hash has the ANYONECANPAY modifier,
+    /// committing to the input being signed and none of the other inputs.
+    pub fn is_anyone_can_pay(&self) -> bool {
+        *self as u8 & 0x80 != 0
+    }
+
     fn from_byte(byte: &u8) -> Self {
         match byte {
             0x01 => SignatureHash::SIGHASH_ALL,
@@ -190,6 +242,116 @@ impl SignatureHash {
     }
 }
 
+/// The sequence number of an input which disables its relative lock time, the lock time of the transaction,
+/// and replace-by-fee signaling
+pub const SEQUENCE_FINAL: u32 = 0xffff_ffff;
+/// The sequence number of an input which enables the lock time of the transaction, without signaling replace-by-fee
+pub const SEQUENCE_ENABLE_LOCK_TIME: u32 = 0xffff_fffe;
+/// The greatest sequence number of an input which signals replace-by-fee
+/// https://github.com/bitcoin/bips/blob/master/bip-0125.mediawiki
+pub const SEQUENCE_ENABLE_RBF: u32 = 0xffff_fffd;
+/// The lock time below which a lock time is a block height, and above which it is a Unix time
+pub const LOCK_TIME_THRESHOLD: u32 = 500_000_000;
+/// The maximum length of the data of a standard OP_RETURN output
+pub const MAX_OP_RETURN_DATA_LENGTH: usize = 80;
+/// The maximum length of a low-S DER encoded ECDSA signature with its signature hash type
+pub const MAX_SIGNATURE_LENGTH: usize = 72;
+
+// The flags and mask of a BIP68 relative lock time in a sequence number
+const SEQUENCE_LOCK_TIME_DISABLE_FLAG: u32 = 1 << 31;
+const SEQUENCE_LOCK_TIME_TYPE_FLAG: u32 = 1 << 22;
+const SEQUENCE_LOCK_TIME_MASK: u32 = 0xffff;
+/// The number of seconds of an interval of a relative lock time
+const SEQUENCE_LOCK_TIME_GRANULARITY: u32 = 512;
+/// The dust relay fee rate of Bitcoin Core (in Satoshi per virtual byte)
+const DUST_RELAY_FEE_RATE: u64 = 3;
+
+/// Represents the absolute lock time of a transaction,
+/// the block height or Unix time before which it cannot be included in a block
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub enum LockTime {
+    /// The block height
+    Height(u32),
+    /// The Unix time in seconds
+    Time(u32),
+}
+
+impl LockTime {
+    /// Returns the lock time of the given block height.
+    pub fn from_height(height: u32) -> Result<Self, TransactionError> {
+        match height < LOCK_TIME_THRESHOLD {
+            true => Ok(LockTime::Height(height)),
+            false => Err(TransactionError::InvalidLockTime(height)),
+        }
+    }
+
+    /// Returns the lock time of the given Unix time.
+    pub fn from_time(time: u32) -> Result<Self, TransactionError> {
+        match time >= LOCK_TIME_THRESHOLD {
+            true => Ok(LockTime::Time(time)),
+            false => Err(TransactionError::InvalidLockTime(time)),
+        }
+    }
+
+    /// Returns the lock time of the given lock time field of a transaction.
+    pub fn from_consensus(lock_time: u32) -> Self {
+        match lock_time < LOCK_TIME_THRESHOLD {
+            true => LockTime::Height(lock_time),
+            false => LockTime::Time(lock_time),
+        }
+    }
+
+    /// Returns the lock time field of a transaction with the lock time.
+    pub fn to_consensus(&self) -> u32 {
+        match self {
+            LockTime::Height(lock_time) | LockTime::Time(lock_time) => *lock_time,
+        }
+    }
+}
+
+/// Represents the relative lock time of an input, the number of blocks or of 512-second intervals
+/// after its outpoint is confirmed before it can be spent
+/// https://github.com/bitcoin/bips/blob/master/bip-0068.mediawiki
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub enum RelativeLockTime {
+    /// The number of blocks
+    Blocks(u16),
+    /// The number of 512-second intervals
+    Intervals(u16),
+}
+
+impl RelativeLockTime {
+    /// Returns the relative lock time of at least the given number of seconds, in 512-second intervals.
+    pub fn from_seconds(seconds: u32) -> Result<Self, TransactionError> {
+        let intervals = seconds.div_ceil(SEQUENCE_LOCK_TIME_GRANULARITY);
+        match intervals <= SEQUENCE_LOCK_TIME_MASK {
+            true => Ok(RelativeLockTime::Intervals(intervals as u16)),
+            false => Err(TransactionError::InvalidLockTime(seconds)),
+        }
+    }
+
+    /// Returns the relative lock time of the sequence number, or `None` if its relative lock time is disabled.
+    pub fn from_sequence(sequence: u32) -> Option<Self> {
+        let value = (sequence & SEQUENCE_LOCK_TIME_MASK) as u16;
+        match (
+            sequence & SEQUENCE_LOCK_TIME_DISABLE_FLAG != 0,
+            sequence & SEQUENCE_LOCK_TIME_TYPE_FLAG != 0,
+        ) {
+            (true, _) => None,
+            (false, true) => Some(RelativeLockTime::Intervals(value)),
+            (false, false) => Some(RelativeLockTime::Blocks(value)),
+        }
+    }
+
+    /// Returns the sequence number of the relative lock time, which also signals replace-by-fee.
+    pub fn to_sequence(&self) -> u32 {
+        match self {
+            RelativeLockTime::Blocks(blocks) => *blocks as u32,
+            RelativeLockTime::Intervals(intervals) => SEQUENCE_LOCK_TIME_TYPE_FLAG | *intervals as u32,
+        }
+    }
+}
+
 /// Represents the commonly used script opcodes
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
 #[allow(non_camel_case_types)]
@@ -199,6 +361,84 @@ pub enum Opcode {
     OP_CHECKSIG = 0xac,
     OP_EQUAL = 0x87,
     OP_EQUALVERIFY = 0x88,
+    OP_0 = 0x00,
+    OP_1 = 0x51,
+    OP_IF = 0x63,
+    OP_ELSE = 0x67,
+    OP_ENDIF = 0x68,
+    OP_VERIFY = 0x69,
+    OP_RETURN = 0x6a,
+    OP_TOALTSTACK = 0x6b,
+    OP_FROMALTSTACK = 0x6c,
+    OP_SIZE = 0x82,
+    OP_ADD = 0x93,
+    OP_RIPEMD160 = 0xa6,
+    OP_SHA256 = 0xa8,
+    OP_HASH256 = 0xaa,
+    OP_CHECKSIGVERIFY = 0xad,
+    OP_CHECKMULTISIG = 0xae,
+    OP_CHECKMULTISIGVERIFY = 0xaf,
+    OP_CHECKLOCKTIMEVERIFY = 0xb1,
+    OP_CHECKSEQUENCEVERIFY = 0xb2,
+    OP_PUSHDATA1 = 0x4c,
+    OP_PUSHDATA2 = 0x4d,
+    OP_PUSHDATA4 = 0x4e,
+    OP_1NEGATE = 0x4f,
+    OP_NOP = 0x61,
+    OP_NOTIF = 0x64,
+    OP_2DROP = 0x6d,
+    OP_DROP = 0x75,
+    OP_SWAP = 0x7c,
+    OP_NOT = 0x91,
+    OP_NUMEQUAL = 0x9c,
+    OP_NUMEQUALVERIFY = 0x9d,
+    OP_CHECKSIGADD = 0xba,
+}
+
+impl Opcode {
+    /// Returns the opcode of the given byte, if it is one of the commonly used opcodes.
+    pub fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0x00 => Some(Opcode::OP_0),
+            0x4c => Some(Opcode::OP_PUSHDATA1),
+            0x4d => Some(Opcode::OP_PUSHDATA2),
+            0x4e => Some(Opcode::OP_PUSHDATA4),
+            0x4f => Some(Opcode::OP_1NEGATE),
+            0x51 => Some(Opcode::OP_1),
+            0x61 => Some(Opcode::OP_NOP),
+            0x63 => Some(Opcode::OP_IF),
+            0x64 => Some(Opcode::OP_NOTIF),
+            0x67 => Some(Opcode::OP_ELSE),
+            0x68 => Some(Opcode::OP_ENDIF),
+            0x69 => Some(Opcode::OP_VERIFY),
+            0x6a => Some(Opcode::OP_RETURN),
+            0x6b => Some(Opcode::OP_TOALTSTACK),
+            0x6c => Some(Opcode::OP_FROMALTSTACK),
+            0x6d => Some(Opcode::OP_2DROP),
+            0x75 => Some(Opcode::OP_DROP),
+            0x76 => Some(Opcode::OP_DUP),
+            0x7c => Some(Opcode::OP_SWAP),
+            0x82 => Some(Opcode::OP_SIZE),
+            0x87 => Some(Opcode::OP_EQUAL),
+            0x88 => Some(Opcode::OP_EQUALVERIFY),
+            0x91 => Some(Opcode::OP_NOT),
+            0x93 => Some(Opcode::OP_ADD),
+            0x9c => Some(Opcode::OP_NUMEQUAL),
+            0x9d => Some(Opcode::OP_NUMEQUALVERIFY),
+            0xa6 => Some(Opcode::OP_RIPEMD160),
+            0xa8 => Some(Opcode::OP_SHA256),
+            0xa9 => Some(Opcode::OP_HASH160),
+            0xaa => Some(Opcode::OP_HASH256),
+            0xac => Some(Opcode::OP_CHECKSIG),
+            0xad => Some(Opcode::OP_CHECKSIGVERIFY),
+            0xae => Some(Opcode::OP_CHECKMULTISIG),
+            0xaf => Some(Opcode::OP_CHECKMULTISIGVERIFY),
+            0xb1 => Some(Opcode::OP_CHECKLOCKTIMEVERIFY),
+            0xb2 => Some(Opcode::OP_CHECKSEQUENCEVERIFY),
+            0xba => Some(Opcode::OP_CHECKSIGADD),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for Opcode {
@@ -209,6 +449,38 @@ impl fmt::Display for Opcode {
             Opcode::OP_CHECKSIG => write!(f, "OP_CHECKSIG"),
             Opcode::OP_EQUAL => write!(f, "OP_EQUAL"),
             Opcode::OP_EQUALVERIFY => write!(f, "OP_EQUALVERIFY"),
+            Opcode::OP_0 => write!(f, "OP_0"),
+            Opcode::OP_1 => write!(f, "OP_1"),
+            Opcode::OP_IF => write!(f, "OP_IF"),
+            Opcode::OP_ELSE => write!(f, "OP_ELSE"),
+            Opcode::OP_ENDIF => write!(f, "OP_ENDIF"),
+            Opcode::OP_VERIFY => write!(f, "OP_VERIFY"),
+            Opcode::OP_RETURN => write!(f, "OP_RETURN"),
+            Opcode::OP_TOALTSTACK => write!(f, "OP_TOALTSTACK"),
+            Opcode::OP_FROMALTSTACK => write!(f, "OP_FROMALTSTACK"),
+            Opcode::OP_SIZE => write!(f, "OP_SIZE"),
+            Opcode::OP_ADD => write!(f, "OP_ADD"),
+            Opcode::OP_RIPEMD160 => write!(f, "OP_RIPEMD160"),
+            Opcode::OP_SHA256 => write!(f, "OP_SHA256"),
+            Opcode::OP_HASH256 => write!(f, "OP_HASH256"),
+            Opcode::OP_CHECKSIGVERIFY => write!(f, "OP_CHECKSIGVERIFY"),
+            Opcode::OP_CHECKMULTISIG => write!(f, "OP_CHECKMULTISIG"),
+            Opcode::OP_CHECKMULTISIGVERIFY => write!(f, "OP_CHECKMULTISIGVERIFY"),
+            Opcode::OP_CHECKLOCKTIMEVERIFY => write!(f, "OP_CHECKLOCKTIMEVERIFY"),
+            Opcode::OP_CHECKSEQUENCEVERIFY => write!(f, "OP_CHECKSEQUENCEVERIFY"),
+            Opcode::OP_PUSHDATA1 => write!(f, "OP_PUSHDATA1"),
+            Opcode::OP_PUSHDATA2 => write!(f, "OP_PUSHDATA2"),
+            Opcode::OP_PUSHDATA4 => write!(f, "OP_PUSHDATA4"),
+            Opcode::OP_1NEGATE => write!(f, "OP_1NEGATE"),
+            Opcode::OP_NOP => write!(f, "OP_NOP"),
+            Opcode::OP_NOTIF => write!(f, "OP_NOTIF"),
+            Opcode::OP_2DROP => write!(f, "OP_2DROP"),
+            Opcode::OP_DROP => write!(f, "OP_DROP"),
+            Opcode::OP_SWAP => write!(f, "OP_SWAP"),
+            Opcode::OP_NOT => write!(f, "OP_NOT"),
+            Opcode::OP_NUMEQUAL => write!(f, "OP_NUMEQUAL"),
+            Opcode::OP_NUMEQUALVERIFY => write!(f, "OP_NUMEQUALVERIFY"),
+            Opcode::OP_CHECKSIGADD => write!(f, "OP_CHECKSIGADD"),
         }
     }
 }
@@ -276,11 +548,35 @@ impl<N: BitcoinNetwork> Outpoint<N> {
                         true => return Err(TransactionError::InvalidInputs("Bech32".into())),
                         false => None,
                     },
+                    BitcoinFormat::P2TR => match redeem_script {
+                        Some(_) => return Err(TransactionError::InvalidInputs("P2TR".into())),
+                        None => match script_pub_key.len() != 34
+                            || script_pub_key[0] != Opcode::OP_1 as u8
+                            || script_pub_key[1] != 0x20 // one [32-byte x-only output key]
+                        {
+                            true => return Err(TransactionError::InvalidScriptPubKey("P2TR".into())),
+                            false => None,
+                        },
+                    },
+                    BitcoinFormat::FutureWitness => {
+                        return Err(TransactionError::InvalidInputs("FutureWitness".into()))
+                    }
                 };
 
                 (Some(script_pub_key), redeem_script)
             }
-            None => (None, None),
+            // P2PK and bare multisig outputs have no address and are given by their script public key
+            None => match (script_pub_key, redeem_script) {
+                (Some(script_pub_key), None) => {
+                    let script = BitcoinScript::new(&script_pub_key);
+                    match script.to_p2pk_public_key().is_some() || script.to_multisig().is_some() {
+                        true => (Some(script_pub_key), None),
+                        false => return Err(TransactionError::InvalidScriptPubKey("P2PK or multisig".into())),
+                    }
+                }
+                (None, None) => (None, None),
+                (_, Some(_)) => return Err(TransactionError::InvalidInputs("P2PK or multisig".into())),
+            },
         };
 
         Ok(Self {
@@ -314,6 +610,8 @@ pub struct BitcoinTransactionInput<N: BitcoinNetwork> {
     pub additional_witness: Option<(Vec<u8>, bool)>,
     /// Option for additional witness stack script args
     pub witness_script_data: Option<Vec<u8>>,
+    /// The spend of a P2TR input, through the key path without a script tree unless specified
+    pub taproot_spend: Option<TaprootSpend>,
 }
 
 impl<N: BitcoinNetwork> BitcoinTransactionInput<N> {
@@ -357,6 +655,7 @@ impl<N: BitcoinNetwork> BitcoinTransactionInput<N> {
             is_signed: false,
             additional_witness: None,
             witness_script_data: None,
+            taproot_spend: None,
         })
     }
 
@@ -366,8 +665,8 @@ impl<N: BitcoinNetwork> BitcoinTransactionInput<N> {
         let mut vin = [0u8; 4];
         let mut sequence = [0u8; 4];
 
-        reader.read(&mut transaction_hash)?;
-        reader.read(&mut vin)?;
+        reader.read_exact(&mut transaction_hash)?;
+        reader.read_exact(&mut vin)?;
 
         let outpoint = Outpoint::<N>::new(
             transaction_hash.to_vec(),
@@ -380,16 +679,16 @@ impl<N: BitcoinNetwork> BitcoinTransactionInput<N> {
 
         let script_sig: Vec<u8> = BitcoinVector::read(&mut reader, |s| {
             let mut byte = [0u8; 1];
-            s.read(&mut byte)?;
+            s.read_exact(&mut byte)?;
             Ok(byte[0])
         })?;
 
-        reader.read(&mut sequence)?;
+        reader.read_exact(&mut sequence)?;
 
-        let script_sig_len = read_variable_length_integer(&script_sig[..])?;
-        let sighash_code = SignatureHash::from_byte(&match script_sig_len {
-            0 => 0x01,
-            length => script_sig[length],
+        // The signature hash is the last byte of the signature pushed first, if the script is a signature
+        let sighash_code = SignatureHash::from_byte(match script_sig.first() {
+            Some(&length) if length > 0 => script_sig.get(length as usize).unwrap_or(&0x01),
+            _ => &0x01,
         });
 
         Ok(Self {
@@ -401,9 +700,87 @@ impl<N: BitcoinNetwork> BitcoinTransactionInput<N> {
             is_signed: script_sig.len() > 0,
             additional_witness: None,
             witness_script_data: None,
+            taproot_spend: None,
         })
     }
 
+    /// Returns the sequence number of the input.
+    pub fn to_sequence(&self) -> u32 {
+        self.sequence
+            .iter()
+            .rev()
+            .fold(0, |sequence, byte| sequence << 8 | *byte as u32)
+    }
+
+    /// Returns `true` if the input signals BIP125 replace-by-fee.
+    pub fn is_replaceable(&self) -> bool {
+        self.to_sequence() <= SEQUENCE_ENABLE_RBF
+    }
+
+    /// Returns the BIP68 relative lock time of the input, or `None` if it is disabled.
+    /// (only enforced in transactions of version 2 or greater)
+    pub fn relative_lock_time(&self) -> Option<RelativeLockTime> {
+        RelativeLockTime::from_sequence(self.to_sequence())
+    }
+
+    /// Returns the size of the input, estimated from the format of its address and its Taproot spend
+    /// unless it is signed.
+    pub fn estimate_size(&self) -> Result<BitcoinTransactionSize, TransactionError> {
+        if self.is_signed {
+            let mut witness_size = 0;
+            if !self.witnesses.is_empty() {
+                witness_size = variable_length_integer(self.witnesses.len() as u64)?.len();
+                witness_size += self.witnesses.iter().map(|witness| witness.len()).sum::<usize>();
+            }
+            return Ok(BitcoinTransactionSize::new(
+                self.serialize(false)?.len() as u64,
+                witness_size as u64,
+            ));
+        }
+
+        let format = match &self.outpoint.address {
+            Some(address) => address.format(),
+            None => return Err(TransactionError::MissingOutpointAddress),
+        };
+        match format {
+            BitcoinFormat::P2TR => {
+                let signature_length = match self.sighash_code {
+                    SignatureHash::SIGHASH_DEFAULT => 64,
+                    _ => 65,
+                };
+                let witnesses = match &self.taproot_spend {
+                    Some(TaprootSpend::ScriptPath(leaf, control_block)) => {
+                        vec![signature_length, leaf.script().len(), control_block.to_bytes().len()]
+                    }
+                    _ => vec![signature_length],
+                };
+                BitcoinTransactionSize::input(0, &witnesses)
+            }
+            // The witness of a P2PK or multisig witness script has a signature for each required key
+            BitcoinFormat::P2WSH => {
+                let witness_script = match &self.outpoint.redeem_script {
+                    Some(witness_script) => BitcoinScript::new(witness_script),
+                    None => return Err(TransactionError::InvalidInputs("P2WSH witness".into())),
+                };
+                let witnesses = match (witness_script.to_p2pk_public_key(), witness_script.to_multisig()) {
+                    (Some(_), _) => vec![72, witness_script.len()],
+                    (None, Some((threshold, _))) => [vec![0], vec![72; threshold], vec![witness_script.len()]].concat(),
+                    _ => return Err(TransactionError::InvalidInputs("P2WSH witness".into())),
+                };
+                BitcoinTransactionSize::input(0, &witnesses)
+            }
+            format => match input_size(&format) {
+                Some(size) => Ok(size),
+                None => Err(TransactionError::InvalidInputs(format!("{} witness", format))),
+            },
+        }
+    }
+
+    /// Returns the weight of the input, as estimated by `estimate_size`.
+    pub fn estimate_weight(&self) -> Result<u64, TransactionError> {
+        Ok(self.estimate_size()?.weight())
+    }
+
     /// Returns the serialized transaction input.
     pub fn serialize(&self, raw: bool) -> Result<Vec<u8>, TransactionError> {
         let mut input = vec![];
@@ -417,6 +794,7 @@ impl<N: BitcoinNetwork> BitcoinTransactionInput<N> {
                     Some(address) => match address.format() {
                         BitcoinFormat::Bech32 => input.extend(vec![0x00]),
                         BitcoinFormat::P2WSH => input.extend(vec![0x00]),
+                        BitcoinFormat::P2TR => input.extend(vec![0x00]),
                         _ => {
                             let script_pub_key = match &self.outpoint.script_pub_key {
                                 Some(script) => script,
@@ -426,7 +804,13 @@ impl<N: BitcoinNetwork> BitcoinTransactionInput<N> {
                             input.extend(script_pub_key);
                         }
                     },
-                    None => input.extend(vec![0x00]),
+                    None => match &self.outpoint.script_pub_key {
+                        Some(script_pub_key) => {
+                            input.extend(variable_length_integer(script_pub_key.len() as u64)?);
+                            input.extend(script_pub_key);
+                        }
+                        None => input.extend(vec![0x00]),
+                    },
                 },
                 _ => {
                     input.extend(variable_length_integer(self.script_sig.len() as u64)?);
@@ -461,14 +845,48 @@ impl BitcoinTransactionOutput {
         })
     }
 
+    /// Returns a Bitcoin transaction output locked to the given script public key,
+    /// e.g. a P2PK or bare multisig script, which has no address.
+    pub fn from_script(script_pub_key: &BitcoinScript, amount: BitcoinAmount) -> Self {
+        Self {
+            amount,
+            script_pub_key: script_pub_key.to_bytes(),
+        }
+    }
+
+    /// Returns a provably unspendable Bitcoin transaction output, OP_RETURN <data>, which carries
+    /// the given data with an amount of zero, up to the standard length of 80 bytes.
+    pub fn op_return(data: &[u8]) -> Result<Self, TransactionError> {
+        if data.len() > MAX_OP_RETURN_DATA_LENGTH {
+            return Err(TransactionError::InvalidDataLength(data.len()));
+        }
+        Ok(Self::from_script(&BitcoinScript::op_return(data), BitcoinAmount(0)))
+    }
+
+    /// Returns the data of an OP_RETURN output, or `None` if the output is not an OP_RETURN output.
+    pub fn to_op_return_data(&self) -> Option<Vec<u8>> {
+        match BitcoinScript::new(&self.script_pub_key).to_instructions() {
+            Ok(instructions) => match instructions.as_slice() {
+                [BitcoinScriptInstruction::Opcode(opcode)] if *opcode == Opcode::OP_RETURN as u8 => Some(vec![]),
+                [BitcoinScriptInstruction::Opcode(opcode), BitcoinScriptInstruction::Push(data)]
+                    if *opcode == Opcode::OP_RETURN as u8 =>
+                {
+                    Some(data.clone())
+                }
+                _ => None,
+            },
+            Err(_) => None,
+        }
+    }
+
     /// Read and output a Bitcoin transaction output
     pub fn read<R: Read>(mut reader: &mut R) -> Result<Self, TransactionError> {
         let mut amount = [0u8; 8];
-        reader.read(&mut amount)?;
+        reader.read_exact(&mut amount)?;
 
         let script_pub_key: Vec<u8> = BitcoinVector::read(&mut reader, |s| {
             let mut byte = [0u8; 1];
-            s.read(&mut byte)?;
+            s.read_exact(&mut byte)?;
             Ok(byte[0])
         })?;
 
@@ -486,24 +904,124 @@ impl BitcoinTransactionOutput {
         output.extend(&self.script_pub_key);
         Ok(output)
     }
+
+    /// Returns the weight of the transaction output.
+    pub fn weight(&self) -> Result<u64, TransactionError> {
+        Ok(self.serialize()?.len() as u64 * 4)
+    }
+
+    /// Returns the smallest amount the output may have to be relayed, as defined by the default dust relay
+    /// fee rate of Bitcoin Core from the size of the output and of an input spending it, or zero if the
+    /// output is an OP_RETURN output, which is never spent.
+    pub fn dust_threshold(&self) -> Result<BitcoinAmount, TransactionError> {
+        if self.script_pub_key.first() == Some(&(Opcode::OP_RETURN as u8)) {
+            return Ok(BitcoinAmount(0));
+        }
+
+        let output_size = self.serialize()?.len() as u64;
+        let spend_size = match is_witness_program(&self.script_pub_key) {
+            // outpoint (36) + script length (1) + sequence (4) + witness (107) discounted by 4, rounded down
+            true => 67,
+            // outpoint (36) + script length (1) + script signature (107) + sequence (4)
+            false => 148,
+        };
+        Ok(BitcoinAmount((DUST_RELAY_FEE_RATE * (output_size + spend_size)) as i64))
+    }
+
+    /// Returns `true` if the amount of the output is below its dust threshold.
+    pub fn is_dust(&self) -> Result<bool, TransactionError> {
+        Ok(self.amount.0 < self.dust_threshold()?.0)
+    }
 }
 
 /// Represents an Bitcoin transaction id and witness transaction id
 /// https://github.com/bitcoin/bips/blob/master/bip-0141.mediawiki#transaction-id
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct BitcoinTransactionId {
-    txid: Vec<u8>,
-    wtxid: Vec<u8>,
+    pub(crate) txid: Vec<u8>,
+    pub(crate) wtxid: Vec<u8>,
 }
 
 impl TransactionId for BitcoinTransactionId {}
 
+impl BitcoinTransactionId {
+    /// Returns the witness transaction id, which also commits to the witnesses of the transaction.
+    pub fn to_witness_transaction_id(&self) -> String {
+        hex::encode(&self.wtxid)
+    }
+}
+
 impl fmt::Display for BitcoinTransactionId {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", &hex::encode(&self.txid))
     }
 }
 
+/// Represents the size of a Bitcoin transaction or transaction input, split into the non-witness
+/// data and the witness data discounted by SegWit
+/// https://github.com/bitcoin/bips/blob/master/bip-0141.mediawiki#transaction-size-calculations
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BitcoinTransactionSize {
+    /// The size of the non-witness data (in bytes)
+    pub base_size: u64,
+    /// The size of the witness data, including the SegWit marker and flag of a transaction (in bytes)
+    pub witness_size: u64,
+}
+
+impl BitcoinTransactionSize {
+    /// Returns a new size of the given non-witness and witness sizes.
+    pub fn new(base_size: u64, witness_size: u64) -> Self {
+        Self {
+            base_size,
+            witness_size,
+        }
+    }
+
+    /// Returns the size of an input with a script signature and witness stack of the given lengths.
+    pub fn input(script_sig_length: usize, witness_lengths: &[usize]) -> Result<Self, TransactionError> {
+        // outpoint (36) + script length + script signature + sequence (4)
+        let base_size = 40 + variable_length_integer(script_sig_length as u64)?.len() + script_sig_length;
+
+        let mut witness_size = 0;
+        if !witness_lengths.is_empty() {
+            witness_size = variable_length_integer(witness_lengths.len() as u64)?.len();
+            for length in witness_lengths {
+                witness_size += variable_length_integer(*length as u64)?.len() + length;
+            }
+        }
+        Ok(Self::new(base_size as u64, witness_size as u64))
+    }
+
+    /// Returns the total size of the serialization (in bytes).
+    pub fn size(&self) -> u64 {
+        self.base_size + self.witness_size
+    }
+
+    /// Returns the weight, the non-witness size counted four times and the witness size once.
+    pub fn weight(&self) -> u64 {
+        self.base_size * 4 + self.witness_size
+    }
+
+    /// Returns the virtual size (in virtual bytes), the weight divided by 4 and rounded up.
+    pub fn virtual_size(&self) -> u64 {
+        self.weight().div_ceil(4)
+    }
+}
+
+impl Add for BitcoinTransactionSize {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.base_size + other.base_size, self.witness_size + other.witness_size)
+    }
+}
+
+impl AddAssign for BitcoinTransactionSize {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
 /// Represents the Bitcoin transaction parameters
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct BitcoinTransactionParameters<N: BitcoinNetwork> {
@@ -523,14 +1041,14 @@ impl<N: BitcoinNetwork> BitcoinTransactionParameters<N> {
     /// Read and output the Bitcoin transaction parameters
     pub fn read<R: Read>(mut reader: R) -> Result<Self, TransactionError> {
         let mut version = [0u8; 4];
-        reader.read(&mut version)?;
+        reader.read_exact(&mut version)?;
 
         let mut inputs = BitcoinVector::read(&mut reader, BitcoinTransactionInput::<N>::read)?;
 
         let segwit_flag = match inputs.is_empty() {
             true => {
                 let mut flag = [0u8; 1];
-                reader.read(&mut flag)?;
+                reader.read_exact(&mut flag)?;
                 match flag[0] {
                     1 => {
                         inputs = BitcoinVector::read(&mut reader, BitcoinTransactionInput::<N>::read)?;
@@ -549,7 +1067,7 @@ impl<N: BitcoinNetwork> BitcoinTransactionParameters<N> {
                 let witnesses: Vec<Vec<u8>> = BitcoinVector::read(&mut reader, |s| {
                     let (size, witness) = BitcoinVector::read_witness(s, |sr| {
                         let mut byte = [0u8; 1];
-                        sr.read(&mut byte)?;
+                        sr.read_exact(&mut byte)?;
                         Ok(byte[0])
                     })?;
 
@@ -565,7 +1083,7 @@ impl<N: BitcoinNetwork> BitcoinTransactionParameters<N> {
         }
 
         let mut lock_time = [0u8; 4];
-        reader.read(&mut lock_time)?;
+        reader.read_exact(&mut lock_time)?;
 
         let transaction_parameters = BitcoinTransactionParameters::<N> {
             version: u32::from_le_bytes(version),
@@ -577,13 +1095,154 @@ impl<N: BitcoinNetwork> BitcoinTransactionParameters<N> {
 
         Ok(transaction_parameters)
     }
+
+    /// Sets the sequence number of the input at the given index.
+    pub fn with_sequence(mut self, vin: usize, sequence: u32) -> Result<Self, TransactionError> {
+        match self.inputs.get_mut(vin) {
+            Some(input) => input.sequence = sequence.to_le_bytes().to_vec(),
+            None => return Err(TransactionError::InvalidInputs(format!("input {}", vin))),
+        };
+        Ok(self)
+    }
+
+    /// Signals BIP125 replace-by-fee on the inputs whose sequence numbers do not already signal it.
+    pub fn with_replace_by_fee(mut self) -> Self {
+        for input in self.inputs.iter_mut().filter(|input| !input.is_replaceable()) {
+            input.sequence = SEQUENCE_ENABLE_RBF.to_le_bytes().to_vec();
+        }
+        self
+    }
+
+    /// Sets the lock time of the transaction, and enables it on the inputs with a final sequence number.
+    pub fn with_lock_time(mut self, lock_time: LockTime) -> Self {
+        self.lock_time = lock_time.to_consensus();
+        for input in self
+            .inputs
+            .iter_mut()
+            .filter(|input| input.to_sequence() == SEQUENCE_FINAL)
+        {
+            input.sequence = SEQUENCE_ENABLE_LOCK_TIME.to_le_bytes().to_vec();
+        }
+        self
+    }
+
+    /// Sets the relative lock time of the input at the given index,
+    /// which requires a transaction version of 2 or greater.
+    pub fn with_relative_lock_time(self, vin: usize, lock_time: RelativeLockTime) -> Result<Self, TransactionError> {
+        if self.version < 2 {
+            return Err(TransactionError::InvalidTransactionVersion(self.version));
+        }
+        self.with_sequence(vin, lock_time.to_sequence())
+    }
+
+    /// Adds an OP_RETURN output which carries the given data, up to the standard length of 80 bytes.
+    pub fn with_data(mut self, data: &[u8]) -> Result<Self, TransactionError> {
+        self.outputs.push(BitcoinTransactionOutput::op_return(data)?);
+        Ok(self)
+    }
+
+    /// Returns `true` if any input of the transaction signals BIP125 replace-by-fee.
+    pub fn is_replaceable(&self) -> bool {
+        self.inputs.iter().any(|input| input.is_replaceable())
+    }
+
+    /// Returns the size of the transaction once signed, estimating the signatures of its unsigned inputs.
+    pub fn estimate_size(&self) -> Result<BitcoinTransactionSize, TransactionError> {
+        let input_sizes = self
+            .inputs
+            .iter()
+            .map(|input| input.estimate_size())
+            .collect::<Result<Vec<_>, _>>()?;
+        self.estimate_size_with_inputs(&input_sizes)
+    }
+
+    /// Returns the size of the transaction with inputs of the given sizes.
+    pub(crate) fn estimate_size_with_inputs(
+        &self,
+        input_sizes: &[BitcoinTransactionSize],
+    ) -> Result<BitcoinTransactionSize, TransactionError> {
+        let counts = variable_length_integer(input_sizes.len() as u64)?.len()
+            + variable_length_integer(self.outputs.len() as u64)?.len();
+        let mut size = BitcoinTransactionSize::new(8 + counts as u64, 0);
+        for output in &self.outputs {
+            size.base_size += output.serialize()?.len() as u64;
+        }
+
+        let segwit = input_sizes.iter().any(|input| input.witness_size > 0);
+        if segwit {
+            // The marker and flag
+            size.witness_size += 2;
+        }
+        for input in input_sizes {
+            size += *input;
+            // Inputs without a witness have an empty witness in a SegWit transaction
+            if segwit && input.witness_size == 0 {
+                size.witness_size += 1;
+            }
+        }
+        Ok(size)
+    }
+
+    /// Returns the weight of the transaction once signed, as estimated by `estimate_size`.
+    pub fn estimate_weight(&self) -> Result<u64, TransactionError> {
+        Ok(self.estimate_size()?.weight())
+    }
+
+    /// Returns the virtual size of the transaction once signed, as estimated by `estimate_size`.
+    pub fn estimate_virtual_size(&self) -> Result<u64, TransactionError> {
+        Ok(self.estimate_size()?.virtual_size())
+    }
+
+    /// Returns the fee of the transaction once signed at the given fee rate (in Satoshi per virtual byte).
+    pub fn estimate_fee(&self, fee_rate: u64) -> Result<BitcoinAmount, TransactionError> {
+        Ok(weight_to_fee(self.estimate_weight()?, fee_rate))
+    }
+
+    /// Returns the fee of the transaction, the amount of its inputs less the amount of its outputs.
+    pub fn fee(&self) -> Result<BitcoinAmount, TransactionError> {
+        let mut fee = 0;
+        for input in &self.inputs {
+            match input.outpoint.amount {
+                Some(amount) => fee += amount.0,
+                None => return Err(TransactionError::MissingOutpointAmount),
+            };
+        }
+        fee -= self.outputs.iter().map(|output| output.amount.0).sum::<i64>();
+        Ok(BitcoinAmount(fee))
+    }
+
+    /// Adds a change output to the given address, with the amount of the inputs left once the outputs
+    /// and the estimated fee at the given fee rate (in Satoshi per virtual byte) are paid.
+    /// Returns an error if the change would be dust.
+    pub fn with_change(mut self, address: &BitcoinAddress<N>, fee_rate: u64) -> Result<Self, TransactionError> {
+        self.outputs
+            .push(BitcoinTransactionOutput::new(address, BitcoinAmount(0))?);
+
+        let output_value: i64 = self.outputs.iter().map(|output| output.amount.0).sum();
+        let available = self.fee()?.0 + output_value;
+        let fee = self.estimate_fee(fee_rate)?;
+        let change = available - output_value - fee.0;
+        if change < 0 {
+            return Err(TransactionError::InsufficientFunds(available, output_value + fee.0));
+        }
+
+        let dust = dust_threshold(address)?;
+        if change < dust.0 {
+            return Err(TransactionError::DustChange(change, dust.0));
+        }
+
+        if let Some(output) = self.outputs.last_mut() {
+            output.amount = BitcoinAmount(change);
+        }
+        Ok(self)
+    }
 }
 
 /// Represents a Bitcoin transaction
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct BitcoinTransaction<N: BitcoinNetwork> {
     /// The transaction parameters (version, inputs, outputs, lock_time, segwit_flag)
-    parameters: BitcoinTransactionParameters<N>,
+    pub(crate) parameters: BitcoinTransactionParameters<N>,
 }
 
 impl<N: BitcoinNetwork> Transaction for BitcoinTransaction<N> {
@@ -607,9 +1266,23 @@ impl<N: BitcoinNetwork> Transaction for BitcoinTransaction<N> {
         for (vin, input) in self.parameters.inputs.iter().enumerate() {
             let address = match &input.outpoint.address {
                 Some(address) => address,
-                None => continue,
+                None => {
+                    transaction.sign_bare_script(vin, private_key)?;
+                    continue;
+                }
             };
 
+            if address.format() == BitcoinFormat::P2TR {
+                transaction.sign_taproot(vin, private_key)?;
+                continue;
+            }
+
+            // P2WSH inputs without an additional witness are signed according to their witness script
+            if address.format() == BitcoinFormat::P2WSH && input.additional_witness.is_none() {
+                transaction.sign_witness_script(vin, private_key)?;
+                continue;
+            }
+
             let address_is_valid = match &address.format() {
                 BitcoinFormat::P2WSH => {
                     let input_script = match &input.outpoint.redeem_script {
@@ -631,7 +1304,7 @@ impl<N: BitcoinNetwork> Transaction for BitcoinTransaction<N> {
                 let transaction_hash = Sha256::digest(&Sha256::digest(&preimage));
 
                 // Signature
-                let (signature, _) = secp256k1::sign(
+                let (signature, _) = secp256k1_sign(
                     &secp256k1::Message::parse_slice(&transaction_hash)?,
                     &private_key.to_secp256k1_secret_key(),
                 );
@@ -710,6 +1383,7 @@ impl<N: BitcoinNetwork> Transaction for BitcoinTransaction<N> {
                             .append(&mut vec![signature.clone(), public_key]);
                         transaction.parameters.inputs[vin].is_signed = true;
                     }
+                    BitcoinFormat::P2TR | BitcoinFormat::FutureWitness => unreachable!(),
                 };
             }
         }
@@ -720,9 +1394,12 @@ impl<N: BitcoinNetwork> Transaction for BitcoinTransaction<N> {
     /// Returns a transaction given the transaction bytes.
     /// Note:: Raw transaction hex does not include enough
     fn from_transaction_bytes(transaction: &Vec<u8>) -> Result<Self, TransactionError> {
-        Ok(Self {
-            parameters: Self::TransactionParameters::read(&transaction[..])?,
-        })
+        let mut reader = &transaction[..];
+        let parameters = Self::TransactionParameters::read(&mut reader)?;
+        if !reader.is_empty() {
+            return Err(TransactionError::TrailingBytes(reader.len()));
+        }
+        Ok(Self { parameters })
     }
 
     /// Returns the transaction in bytes.
@@ -779,15 +1456,121 @@ impl<N: BitcoinNetwork> Transaction for BitcoinTransaction<N> {
 }
 
 impl<N: BitcoinNetwork> BitcoinTransaction<N> {
+    /// Returns the transaction parameters.
+    pub fn parameters(&self) -> &BitcoinTransactionParameters<N> {
+        &self.parameters
+    }
+
+    /// Returns the transaction id, the double SHA-256 hash of the transaction without its witnesses,
+    /// in the byte order it is displayed in and given to the inputs spending its outputs.
+    /// The transaction id is known before the transaction is signed if its unsigned inputs spend
+    /// SegWit outputs, whose signatures are in the witnesses, so that transactions spending its outputs
+    /// can be built before it is broadcast.
+    pub fn txid(&self) -> Result<Vec<u8>, TransactionError> {
+        let mut transaction = self.clone();
+        for (vin, input) in transaction.parameters.inputs.iter_mut().enumerate() {
+            if input.is_signed {
+                continue;
+            }
+            // The script signature of an unsigned input is serialized as it will be once signed
+            input.script_sig = match input.outpoint.address.as_ref().map(BitcoinAddress::format) {
+                Some(BitcoinFormat::Bech32) | Some(BitcoinFormat::P2WSH) | Some(BitcoinFormat::P2TR) => vec![],
+                Some(BitcoinFormat::P2SH_P2WPKH) => match &input.outpoint.redeem_script {
+                    Some(redeem_script) => [
+                        variable_length_integer(redeem_script.len() as u64)?,
+                        redeem_script.clone(),
+                    ]
+                    .concat(),
+                    None => return Err(TransactionError::InvalidInputs("P2SH_P2WPKH".into())),
+                },
+                _ => return Err(TransactionError::InvalidInputs(format!("signature of input {}", vin))),
+            };
+        }
+
+        let mut txid = Sha256::digest(&Sha256::digest(&transaction.to_transaction_bytes_without_witness()?)).to_vec();
+        txid.reverse();
+        Ok(txid)
+    }
+
+    /// Returns the witness transaction id, the double SHA-256 hash of the transaction with its witnesses,
+    /// in the byte order it is displayed in. The witness transaction id is known once every input is signed,
+    /// and is the transaction id if no input has a witness.
+    pub fn wtxid(&self) -> Result<Vec<u8>, TransactionError> {
+        if let Some(vin) = self.parameters.inputs.iter().position(|input| !input.is_signed) {
+            return Err(TransactionError::InvalidInputs(format!("signature of input {}", vin)));
+        }
+        Ok(self.to_transaction_id()?.wtxid)
+    }
+
+    /// Returns the size of the serialized transaction, with its witness data as signed so far.
+    pub fn size(&self) -> Result<BitcoinTransactionSize, TransactionError> {
+        let base_size = self.to_transaction_bytes_without_witness()?.len() as u64;
+        let size = self.to_transaction_bytes()?.len() as u64;
+        Ok(BitcoinTransactionSize::new(base_size, size - base_size))
+    }
+
     /// Return the P2PKH hash preimage of the raw transaction.
+    /// With SIGHASH_NONE or SIGHASH_SINGLE, the other inputs commit to no sequence and the outputs
+    /// are omitted or blanked, and with ANYONECANPAY, the other inputs are omitted.
     pub fn p2pkh_hash_preimage(&self, vin: usize, sighash: SignatureHash) -> Result<Vec<u8>, TransactionError> {
+        if sighash == SignatureHash::SIGHASH_DEFAULT {
+            return Err(TransactionError::UnsupportedSignatureHash(sighash.to_string()));
+        }
+
+        let base = sighash.base();
+        if base == SignatureHash::SIGHASH_SINGLE && vin >= self.parameters.outputs.len() {
+            return Err(TransactionError::InvalidInputs(format!(
+                "SIGHASH_SINGLE output {}",
+                vin
+            )));
+        }
+
         let mut preimage = self.parameters.version.to_le_bytes().to_vec();
-        preimage.extend(variable_length_integer(self.parameters.inputs.len() as u64)?);
-        for (index, input) in self.parameters.inputs.iter().enumerate() {
-            preimage.extend(input.serialize(index != vin)?);
+        let inputs = match sighash.is_anyone_can_pay() {
+            true => vec![(vin, &self.parameters.inputs[vin])],
+            false => self.parameters.inputs.iter().enumerate().collect(),
+        };
+        preimage.extend(variable_length_integer(inputs.len() as u64)?);
+        for (index, input) in inputs {
+            match index == vin {
+                // The input being signed commits to its script public key, not to any partial signatures
+                true => preimage.extend(
+                    BitcoinTransactionInput {
+                        script_sig: vec![],
+                        ..input.clone()
+                    }
+                    .serialize(false)?,
+                ),
+                // The other inputs commit to no sequence, so they can be updated independently
+                false if base != SignatureHash::SIGHASH_ALL => preimage.extend(
+                    BitcoinTransactionInput {
+                        sequence: vec![0u8; 4],
+                        ..input.clone()
+                    }
+                    .serialize(true)?,
+                ),
+                false => preimage.extend(input.serialize(true)?),
+            }
         }
-        preimage.extend(variable_length_integer(self.parameters.outputs.len() as u64)?);
-        for output in &self.parameters.outputs {
+
+        let outputs = match base {
+            SignatureHash::SIGHASH_NONE => vec![],
+            // The outputs preceding the output of the input being signed are blank, with an amount of -1
+            SignatureHash::SIGHASH_SINGLE => {
+                let mut outputs = vec![
+                    BitcoinTransactionOutput {
+                        amount: BitcoinAmount(-1),
+                        script_pub_key: vec![],
+                    };
+                    vin
+                ];
+                outputs.push(self.parameters.outputs[vin].clone());
+                outputs
+            }
+            _ => self.parameters.outputs.clone(),
+        };
+        preimage.extend(variable_length_integer(outputs.len() as u64)?);
+        for output in &outputs {
             preimage.extend(output.serialize()?);
         }
         preimage.extend(&self.parameters.lock_time.to_le_bytes());
@@ -798,6 +1581,10 @@ impl<N: BitcoinNetwork> BitcoinTransaction<N> {
     /// Return the SegWit hash preimage of the raw transaction
     /// https://github.com/bitcoin/bips/blob/master/bip-0143.mediawiki#specification
     pub fn segwit_hash_preimage(&self, vin: usize, sighash: SignatureHash) -> Result<Vec<u8>, TransactionError> {
+        if sighash == SignatureHash::SIGHASH_DEFAULT {
+            return Err(TransactionError::UnsupportedSignatureHash(sighash.to_string()));
+        }
+
         let mut prev_outputs = vec![];
         let mut prev_sequences = vec![];
         let mut outputs = vec![];
@@ -808,8 +1595,22 @@ impl<N: BitcoinNetwork> BitcoinTransaction<N> {
             prev_sequences.extend(&input.sequence);
         }
 
-        for output in &self.parameters.outputs {
-            outputs.extend(&output.serialize()?);
+        match sighash.base() {
+            SignatureHash::SIGHASH_NONE => (),
+            SignatureHash::SIGHASH_SINGLE => match self.parameters.outputs.get(vin) {
+                Some(output) => outputs.extend(&output.serialize()?),
+                None => {
+                    return Err(TransactionError::InvalidInputs(format!(
+                        "SIGHASH_SINGLE output {}",
+                        vin
+                    )))
+                }
+            },
+            _ => {
+                for output in &self.parameters.outputs {
+                    outputs.extend(&output.serialize()?);
+                }
+            }
         }
 
         let input = &self.parameters.inputs[vin];
@@ -832,6 +1633,8 @@ impl<N: BitcoinNetwork> BitcoinTransaction<N> {
                 None => return Err(TransactionError::InvalidInputs("P2SH_P2WPKH".into())),
             },
             BitcoinFormat::P2PKH => return Err(TransactionError::UnsupportedPreimage("P2PKH".into())),
+            BitcoinFormat::P2TR => return Err(TransactionError::UnsupportedPreimage("P2TR".into())),
+            BitcoinFormat::FutureWitness => return Err(TransactionError::UnsupportedPreimage("FutureWitness".into())),
         };
 
         let mut script_code = vec![];
@@ -845,9 +1648,17 @@ impl<N: BitcoinNetwork> BitcoinTransaction<N> {
             script_code.push(Opcode::OP_CHECKSIG as u8);
         }
         let script_code = [variable_length_integer(script_code.len() as u64)?, script_code].concat();
-        let hash_prev_outputs = Sha256::digest(&Sha256::digest(&prev_outputs));
-        let hash_sequence = Sha256::digest(&Sha256::digest(&prev_sequences));
-        let hash_outputs = Sha256::digest(&Sha256::digest(&outputs));
+        // The hashes of the parts of the transaction not committed to by the signature hash are zero
+        let double_sha256 = |bytes: &[u8], is_committed: bool| match is_committed {
+            true => Sha256::digest(&Sha256::digest(bytes)).to_vec(),
+            false => vec![0u8; 32],
+        };
+        let hash_prev_outputs = double_sha256(&prev_outputs, !sighash.is_anyone_can_pay());
+        let hash_sequence = double_sha256(
+            &prev_sequences,
+            !sighash.is_anyone_can_pay() && sighash.base() == SignatureHash::SIGHASH_ALL,
+        );
+        let hash_outputs = double_sha256(&outputs, sighash.base() != SignatureHash::SIGHASH_NONE);
         let outpoint_amount = match &input.outpoint.amount {
             Some(amount) => amount.0.to_le_bytes(),
             None => return Err(TransactionError::MissingOutpointAmount),
@@ -869,18 +1680,398 @@ impl<N: BitcoinNetwork> BitcoinTransaction<N> {
         Ok(preimage)
     }
 
-    /// Returns the transaction with the traditional serialization (no witness).
-    fn to_transaction_bytes_without_witness(&self) -> Result<Vec<u8>, TransactionError> {
-        let mut transaction = self.parameters.version.to_le_bytes().to_vec();
+    /// Return the Taproot hash preimage of the raw transaction, the epoch followed by the signature message
+    /// of a key path spend, or of a script path spend of the leaf with the given hash.
+    /// https://github.com/bitcoin/bips/blob/master/bip-0341.mediawiki#common-signature-message
+    pub fn taproot_hash_preimage(
+        &self,
+        vin: usize,
+        sighash: SignatureHash,
+        leaf_hash: Option<&[u8; 32]>,
+    ) -> Result<Vec<u8>, TransactionError> {
+        let base = sighash.base();
+        let single_output = match base {
+            SignatureHash::SIGHASH_SINGLE => match self.parameters.outputs.get(vin) {
+                Some(output) => Some(output.serialize()?),
+                None => {
+                    return Err(TransactionError::InvalidInputs(format!(
+                        "SIGHASH_SINGLE output {}",
+                        vin
+                    )))
+                }
+            },
+            _ => None,
+        };
 
-        transaction.extend(variable_length_integer(self.parameters.inputs.len() as u64)?);
-        for input in &self.parameters.inputs {
-            transaction.extend(input.serialize(false)?);
+        // Taproot signatures commit to the amounts and script public keys of the spent outputs
+        let spent_output = |input: &BitcoinTransactionInput<N>| -> Result<(Vec<u8>, Vec<u8>), TransactionError> {
+            let amount = match &input.outpoint.amount {
+                Some(amount) => amount.0.to_le_bytes().to_vec(),
+                None => return Err(TransactionError::MissingOutpointAmount),
+            };
+            let script_pub_key = match &input.outpoint.script_pub_key {
+                Some(script_pub_key) => [
+                    variable_length_integer(script_pub_key.len() as u64)?,
+                    script_pub_key.clone(),
+                ]
+                .concat(),
+                None => return Err(TransactionError::MissingOutpointScriptPublicKey),
+            };
+            Ok((amount, script_pub_key))
+        };
+
+        let mut preimage = vec![0x00, sighash as u8];
+        preimage.extend(&self.parameters.version.to_le_bytes());
+        preimage.extend(&self.parameters.lock_time.to_le_bytes());
+
+        if !sighash.is_anyone_can_pay() {
+            let mut prev_outputs = vec![];
+            let mut amounts = vec![];
+            let mut script_pub_keys = vec![];
+            let mut sequences = vec![];
+            for input in &self.parameters.inputs {
+                let (amount, script_pub_key) = spent_output(input)?;
+                prev_outputs.extend(&input.outpoint.reverse_transaction_id);
+                prev_outputs.extend(&input.outpoint.index.to_le_bytes());
+                amounts.extend(amount);
+                script_pub_keys.extend(script_pub_key);
+                sequences.extend(&input.sequence);
+            }
+            preimage.extend(Sha256::digest(&prev_outputs));
+            preimage.extend(Sha256::digest(&amounts));
+            preimage.extend(Sha256::digest(&script_pub_keys));
+            preimage.extend(Sha256::digest(&sequences));
         }
 
-        transaction.extend(variable_length_integer(self.parameters.outputs.len() as u64)?);
-        for output in &self.parameters.outputs {
-            transaction.extend(output.serialize()?);
+        if base == SignatureHash::SIGHASH_ALL {
+            let mut outputs = vec![];
+            for output in &self.parameters.outputs {
+                outputs.extend(output.serialize()?);
+            }
+            preimage.extend(Sha256::digest(&outputs));
+        }
+
+        // The spend type is 0 for key path spends and 2 for script path spends, without an annex
+        preimage.push(match leaf_hash {
+            Some(_) => 0x02,
+            None => 0x00,
+        });
+
+        let input = &self.parameters.inputs[vin];
+        match sighash.is_anyone_can_pay() {
+            true => {
+                let (amount, script_pub_key) = spent_output(input)?;
+                preimage.extend(&input.outpoint.reverse_transaction_id);
+                preimage.extend(&input.outpoint.index.to_le_bytes());
+                preimage.extend(amount);
+                preimage.extend(script_pub_key);
+                preimage.extend(&input.sequence);
+            }
+            false => preimage.extend(&(vin as u32).to_le_bytes()),
+        }
+
+        if let Some(single_output) = single_output {
+            preimage.extend(Sha256::digest(&single_output));
+        }
+
+        // Script path spends commit to the leaf, the key version 0, and no executed OP_CODESEPARATOR
+        if let Some(leaf_hash) = leaf_hash {
+            preimage.extend(leaf_hash);
+            preimage.push(0x00);
+            preimage.extend(&[0xff; 4]);
+        }
+
+        Ok(preimage)
+    }
+
+    /// Signs the input at the given index if it spends a P2TR output that the private key can sign for,
+    /// through the key path with the tweaked private key, or through the script path of a leaf
+    /// whose script contains the x-only public key of the private key.
+    fn sign_taproot(&mut self, vin: usize, private_key: &BitcoinPrivateKey<N>) -> Result<(), TransactionError> {
+        let input = &self.parameters.inputs[vin];
+        if input.is_signed {
+            return Ok(());
+        }
+
+        let mut output_key = [0u8; 32];
+        match &input.outpoint.script_pub_key {
+            Some(script_pub_key) if script_pub_key.len() == 34 => output_key.copy_from_slice(&script_pub_key[2..]),
+            Some(_) => return Err(TransactionError::InvalidScriptPubKey("P2TR".into())),
+            None => return Err(TransactionError::MissingOutpointScriptPublicKey),
+        };
+
+        let public_key = private_key.to_public_key();
+        let witnesses = match input.taproot_spend.as_ref().unwrap_or(&TaprootSpend::KeyPath(None)) {
+            TaprootSpend::KeyPath(merkle_root) => {
+                if public_key
+                    .to_taproot_tweaked_key(merkle_root.as_ref())
+                    .map_err(TaprootError::from)?
+                    .0
+                    != output_key
+                {
+                    return Ok(());
+                }
+
+                let private_key = private_key.to_taproot_tweaked_private_key(merkle_root.as_ref())?;
+                vec![self.taproot_signature(vin, input.sighash_code, None, &private_key)?]
+            }
+            TaprootSpend::ScriptPath(leaf, control_block) => {
+                let x_only_public_key = public_key.to_x_only_public_key();
+                let is_signer = match BitcoinScript::new(leaf.script()).to_instructions() {
+                    Ok(instructions) => instructions.iter().any(|instruction| match instruction {
+                        BitcoinScriptInstruction::Push(data) => data[..] == x_only_public_key[..],
+                        _ => false,
+                    }),
+                    Err(_) => false,
+                };
+                if !is_signer {
+                    return Ok(());
+                }
+                if control_block.leaf_version() != leaf.version() || !control_block.verify(&output_key, leaf.script()) {
+                    return Err(TransactionError::InvalidInputs("P2TR control block".into()));
+                }
+
+                let leaf_hash = leaf.to_leaf_hash()?;
+                vec![
+                    self.taproot_signature(vin, input.sighash_code, Some(&leaf_hash), private_key)?,
+                    leaf.script().to_vec(),
+                    control_block.to_bytes(),
+                ]
+            }
+        };
+
+        let mut witness_field = vec![];
+        for witness in witnesses {
+            witness_field.push([variable_length_integer(witness.len() as u64)?, witness].concat());
+        }
+        self.parameters.segwit_flag = true;
+        self.parameters.inputs[vin].script_sig = vec![];
+        self.parameters.inputs[vin].witnesses = witness_field;
+        self.parameters.inputs[vin].is_signed = true;
+        Ok(())
+    }
+
+    /// Signs the input at the given index if it spends a P2PK or bare multisig output
+    /// that the private key can sign for. Signatures on a multisig input are accumulated
+    /// across calls in the order of their public keys, until the threshold is reached.
+    fn sign_bare_script(&mut self, vin: usize, private_key: &BitcoinPrivateKey<N>) -> Result<(), TransactionError> {
+        let input = &self.parameters.inputs[vin];
+        let script_pub_key = match &input.outpoint.script_pub_key {
+            Some(script_pub_key) => BitcoinScript::new(script_pub_key),
+            None => return Ok(()),
+        };
+
+        let public_key = private_key.to_public_key().to_secp256k1_public_key();
+        let public_keys = [
+            public_key.serialize_compressed().to_vec(),
+            public_key.serialize().to_vec(),
+        ];
+
+        if let Some(p2pk_public_key) = script_pub_key.to_p2pk_public_key() {
+            if !input.is_signed && public_keys.contains(&p2pk_public_key) {
+                let signature = self.legacy_signature(vin, input.sighash_code, private_key)?;
+                self.parameters.inputs[vin].script_sig =
+                    BitcoinScript::builder().push_data(&signature).into_script().to_bytes();
+                self.parameters.inputs[vin].is_signed = true;
+            }
+        } else if let Some((threshold, multisig_public_keys)) = script_pub_key.to_multisig() {
+            // Match the existing signatures to their public keys, skipping the dummy element of OP_CHECKMULTISIG
+            let mut signatures: Vec<Option<Vec<u8>>> = vec![None; multisig_public_keys.len()];
+            let instructions = match BitcoinScript::new(&input.script_sig).to_instructions() {
+                Ok(instructions) => instructions,
+                Err(_) => return Err(TransactionError::InvalidInputs("multisig signatures".into())),
+            };
+            for instruction in instructions.iter().skip(1) {
+                let signature = match instruction {
+                    BitcoinScriptInstruction::Push(signature) if !signature.is_empty() => signature,
+                    _ => return Err(TransactionError::InvalidInputs("multisig signatures".into())),
+                };
+                let sighash = SignatureHash::from_byte(&signature[signature.len() - 1]);
+                let message = Sha256::digest(&Sha256::digest(&self.p2pkh_hash_preimage(vin, sighash)?));
+                let message = secp256k1::Message::parse_slice(&message)?;
+                let index = match secp256k1::Signature::parse_der(&signature[..signature.len() - 1]) {
+                    Ok(parsed) => multisig_public_keys.iter().position(|public_key| {
+                        match secp256k1::PublicKey::parse_slice(public_key, None) {
+                            Ok(public_key) => secp256k1::verify(&message, &parsed, &public_key),
+                            Err(_) => false,
+                        }
+                    }),
+                    Err(_) => None,
+                };
+                match index {
+                    Some(index) => signatures[index] = Some(signature.clone()),
+                    None => return Err(TransactionError::InvalidInputs("multisig signatures".into())),
+                }
+            }
+
+            let signed = signatures.iter().filter(|signature| signature.is_some()).count();
+            let index = signatures
+                .iter()
+                .zip(&multisig_public_keys)
+                .position(|(signature, public_key)| signature.is_none() && public_keys.contains(public_key));
+            if let (true, Some(index)) = (signed < threshold, index) {
+                signatures[index] = Some(self.legacy_signature(vin, input.sighash_code, private_key)?);
+                self.parameters.inputs[vin].script_sig = signatures
+                    .iter()
+                    .flatten()
+                    .fold(
+                        BitcoinScript::builder().push_opcode(Opcode::OP_0),
+                        |builder, signature| builder.push_data(signature),
+                    )
+                    .into_script()
+                    .to_bytes();
+                self.parameters.inputs[vin].is_signed = true;
+            }
+        }
+        Ok(())
+    }
+
+    /// Signs the input at the given index if it spends a P2WSH output whose witness script is a P2PK
+    /// or multisig script that the private key can sign for. Signatures on a multisig input are
+    /// accumulated across calls in the order of their public keys, until the threshold is reached.
+    fn sign_witness_script(&mut self, vin: usize, private_key: &BitcoinPrivateKey<N>) -> Result<(), TransactionError> {
+        let input = &self.parameters.inputs[vin];
+        let witness_script = match &input.outpoint.redeem_script {
+            Some(witness_script) => witness_script.clone(),
+            None => return Err(TransactionError::InvalidInputs("P2WSH".into())),
+        };
+        if input.outpoint.address != Some(BitcoinAddress::<N>::p2wsh(&witness_script)?) {
+            return Err(TransactionError::InvalidInputs("P2WSH witness script".into()));
+        }
+        let script = BitcoinScript::new(&witness_script);
+
+        let public_key = private_key.to_public_key().to_secp256k1_public_key();
+        let public_keys = [
+            public_key.serialize_compressed().to_vec(),
+            public_key.serialize().to_vec(),
+        ];
+
+        let signatures = if let Some(p2pk_public_key) = script.to_p2pk_public_key() {
+            if input.is_signed || !public_keys.contains(&p2pk_public_key) {
+                return Ok(());
+            }
+            vec![self.segwit_signature(vin, input.sighash_code, private_key)?]
+        } else if let Some((threshold, multisig_public_keys)) = script.to_multisig() {
+            // Match the existing signatures to their public keys, skipping the dummy element
+            // of OP_CHECKMULTISIG and the witness script
+            let mut signatures: Vec<Option<Vec<u8>>> = vec![None; multisig_public_keys.len()];
+            let length = input.witnesses.len();
+            for witness in input.witnesses.iter().take(length.saturating_sub(1)).skip(1) {
+                let mut signature = &witness[..];
+                read_variable_length_integer(&mut signature)?;
+                if signature.is_empty() {
+                    return Err(TransactionError::InvalidInputs("multisig signatures".into()));
+                }
+                let sighash = SignatureHash::from_byte(&signature[signature.len() - 1]);
+                let message = Sha256::digest(&Sha256::digest(&self.segwit_hash_preimage(vin, sighash)?));
+                let message = secp256k1::Message::parse_slice(&message)?;
+                let index = match secp256k1::Signature::parse_der(&signature[..signature.len() - 1]) {
+                    Ok(parsed) => multisig_public_keys.iter().position(|public_key| {
+                        match secp256k1::PublicKey::parse_slice(public_key, None) {
+                            Ok(public_key) => secp256k1::verify(&message, &parsed, &public_key),
+                            Err(_) => false,
+                        }
+                    }),
+                    Err(_) => None,
+                };
+                match index {
+                    Some(index) => signatures[index] = Some(signature.to_vec()),
+                    None => return Err(TransactionError::InvalidInputs("multisig signatures".into())),
+                }
+            }
+
+            let signed = signatures.iter().filter(|signature| signature.is_some()).count();
+            let index = signatures
+                .iter()
+                .zip(&multisig_public_keys)
+                .position(|(signature, public_key)| signature.is_none() && public_keys.contains(public_key));
+            match (signed < threshold, index) {
+                (true, Some(index)) => {
+                    signatures[index] = Some(self.segwit_signature(vin, input.sighash_code, private_key)?)
+                }
+                _ => return Ok(()),
+            };
+            [vec![vec![]], signatures.into_iter().flatten().collect()].concat()
+        } else {
+            return Ok(());
+        };
+
+        let mut witness_field = vec![];
+        for witness in signatures.into_iter().chain(vec![witness_script]) {
+            witness_field.push([variable_length_integer(witness.len() as u64)?, witness].concat());
+        }
+        self.parameters.segwit_flag = true;
+        self.parameters.inputs[vin].script_sig = vec![];
+        self.parameters.inputs[vin].witnesses = witness_field;
+        self.parameters.inputs[vin].is_signed = true;
+        Ok(())
+    }
+
+    /// Returns the DER signature of the legacy signature hash of the input at the given index,
+    /// followed by the signature hash type.
+    fn legacy_signature(
+        &self,
+        vin: usize,
+        sighash: SignatureHash,
+        private_key: &BitcoinPrivateKey<N>,
+    ) -> Result<Vec<u8>, TransactionError> {
+        let transaction_hash = Sha256::digest(&Sha256::digest(&self.p2pkh_hash_preimage(vin, sighash)?));
+        let (signature, _) = secp256k1_sign(
+            &secp256k1::Message::parse_slice(&transaction_hash)?,
+            &private_key.to_secp256k1_secret_key(),
+        );
+        let mut signature = signature.serialize_der().as_ref().to_vec();
+        signature.push((sighash as u32).to_le_bytes()[0]);
+        Ok(signature)
+    }
+
+    /// Returns the DER signature of the SegWit signature hash of the input at the given index,
+    /// followed by the signature hash type.
+    fn segwit_signature(
+        &self,
+        vin: usize,
+        sighash: SignatureHash,
+        private_key: &BitcoinPrivateKey<N>,
+    ) -> Result<Vec<u8>, TransactionError> {
+        let transaction_hash = Sha256::digest(&Sha256::digest(&self.segwit_hash_preimage(vin, sighash)?));
+        let (signature, _) = secp256k1_sign(
+            &secp256k1::Message::parse_slice(&transaction_hash)?,
+            &private_key.to_secp256k1_secret_key(),
+        );
+        let mut signature = signature.serialize_der().as_ref().to_vec();
+        signature.push((sighash as u32).to_le_bytes()[0]);
+        Ok(signature)
+    }
+
+    /// Returns the Schnorr signature of the Taproot signature hash of the input at the given index,
+    /// followed by the signature hash type unless it is SIGHASH_DEFAULT.
+    fn taproot_signature(
+        &self,
+        vin: usize,
+        sighash: SignatureHash,
+        leaf_hash: Option<&[u8; 32]>,
+        private_key: &BitcoinPrivateKey<N>,
+    ) -> Result<Vec<u8>, TransactionError> {
+        let transaction_hash = tagged_hash(b"TapSighash", &self.taproot_hash_preimage(vin, sighash, leaf_hash)?);
+        let mut signature = private_key.sign_schnorr(&transaction_hash, &[0u8; 32])?.to_vec();
+        if sighash != SignatureHash::SIGHASH_DEFAULT {
+            signature.push(sighash as u8);
+        }
+        Ok(signature)
+    }
+
+    /// Returns the transaction with the traditional serialization (no witness).
+    fn to_transaction_bytes_without_witness(&self) -> Result<Vec<u8>, TransactionError> {
+        let mut transaction = self.parameters.version.to_le_bytes().to_vec();
+
+        transaction.extend(variable_length_integer(self.parameters.inputs.len() as u64)?);
+        for input in &self.parameters.inputs {
+            transaction.extend(input.serialize(false)?);
+        }
+
+        transaction.extend(variable_length_integer(self.parameters.outputs.len() as u64)?);
+        for output in &self.parameters.outputs {
+            transaction.extend(output.serialize()?);
         }
 
         transaction.extend(&self.parameters.lock_time.to_le_bytes());
@@ -888,6 +2079,28 @@ impl<N: BitcoinNetwork> BitcoinTransaction<N> {
         Ok(transaction)
     }
 
+    /// Update the signature hash of a transaction's input, given the outpoint it spends
+    pub fn update_sighash(&self, reverse_transaction_id: &[u8], index: u32, sighash: SignatureHash) -> Self {
+        let mut new_transaction = self.clone();
+        for input in new_transaction.parameters.inputs.iter_mut() {
+            if input.outpoint.reverse_transaction_id == reverse_transaction_id && input.outpoint.index == index {
+                input.sighash_code = sighash;
+            }
+        }
+        new_transaction
+    }
+
+    /// Update the Taproot spend of a transaction's input, given the outpoint it spends
+    pub fn update_taproot_spend(&self, reverse_transaction_id: &[u8], index: u32, spend: TaprootSpend) -> Self {
+        let mut new_transaction = self.clone();
+        for input in new_transaction.parameters.inputs.iter_mut() {
+            if input.outpoint.reverse_transaction_id == reverse_transaction_id && input.outpoint.index == index {
+                input.taproot_spend = Some(spend.clone());
+            }
+        }
+        new_transaction
+    }
+
     /// Update a transaction's input outpoint
     #[allow(dead_code)]
     pub fn update_outpoint(&self, outpoint: Outpoint<N>) -> Self {
@@ -914,7 +2127,7 @@ impl<N: BitcoinNetwork> FromStr for BitcoinTransaction<N> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::Mainnet;
+    use crate::{Mainnet, Signet};
     use wagyu_model::crypto::hash160;
 
     pub struct TransactionTestCase<'a> {
@@ -1444,7 +2657,7 @@ mod tests {
                 ],
                 outputs: &[
                     Output {
-                        address: "bc1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7k7grplx", // witness version 1
+                        address: "bc1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7kt5nd6y", // witness version 1
                         amount: BitcoinAmount(100000000)
                     },
                     Output {
@@ -1736,6 +2949,83 @@ mod tests {
         }
     }
 
+    mod test_valid_signet_transactions {
+        use super::*;
+        type N = Signet;
+
+        // The first two mainnet test cases, with their keys and addresses re-encoded for signet
+        const TRANSACTIONS: [TransactionTestCase; 2] = [
+            TransactionTestCase { // p2pkh to p2pkh
+                version: 1,
+                lock_time: 0,
+                inputs: &[
+                    Input {
+                        private_key: "cSGyRzqKLLYkhJF8GL6df1148P3jJJuiByKfDvKcHfsTVmFfuwaS",
+                        address_format: BitcoinFormat::P2PKH,
+                        transaction_id: "61d520ccb74288c96bc1a2b20ea1c0d5a704776dd0164a396efec3ea7040349d",
+                        index: 0,
+                        redeem_script: None,
+                        script_pub_key: None,
+                        utxo_amount: BitcoinAmount(0),
+                        sequence: None,
+                        sighash_code: SignatureHash::SIGHASH_ALL
+                    },
+                ],
+                outputs: &[
+                    Output {
+                        address: "mg8Jz5776UdyiYcBb9Z873NTozEiADRW5H",
+                        amount: BitcoinAmount(12000)
+                    },
+                ],
+                expected_signed_transaction: "01000000019d344070eac3fe6e394a16d06d7704a7d5c0a10eb2a2c16bc98842b7cc20d561000000006b48304502210088828c0bdfcdca68d8ae0caeb6ec62cd3fd5f9b2191848edae33feb533df35d302202e0beadd35e17e7f83a733f5277028a9b453d525553e3f5d2d7a7aa8010a81d60121029f50f51d63b345039a290c94bffd3180c99ed659ff6ea6b1242bca47eb93b59fffffffff01e02e0000000000001976a91406afd46bcdfd22ef94ac122aa11f241244a37ecc88ac00000000",
+                expected_transaction_id: "7a68099c3f338fa61696a3c54404c88491e3b249e85574d6bbba01ac00ae33ff",
+            },
+            TransactionTestCase { // p2sh_p2wpkh to p2pkh
+                version: 1,
+                lock_time: 1170,
+                inputs: &[
+                    Input {
+                        private_key: "93NbSXqAMhKHjWoRHZkNXxj485ou2Qtfc1th5dGGimZByCs3Gc9",
+                        address_format: BitcoinFormat::P2SH_P2WPKH,
+                        transaction_id: "77541aeb3c4dac9260b68f74f44c973081a9d4cb2ebe8038b2d70faa201b6bdb",
+                        index: 1,
+                        redeem_script: None,
+                        script_pub_key: None,
+                        utxo_amount: BitcoinAmount(1000000000),
+                        sequence: Some([0xfe, 0xff, 0xff, 0xff]),
+                        sighash_code: SignatureHash::SIGHASH_ALL
+                    },
+                ],
+                outputs: &[
+                    Output {
+                        address: "mvVvBvBpq5f51q8bPygkcSAoVabq5heFTr",
+                        amount: BitcoinAmount(199996600)
+                    },
+                    Output {
+                        address: "n4bW2Nahtzqm4HfVgo9xbft9Z3Crw1veuJ",
+                        amount: BitcoinAmount(800000000)
+                    },
+                ],
+                expected_signed_transaction: "01000000000101db6b1b20aa0fd7b23880be2ecbd4a98130974cf4748fb66092ac4d3ceb1a5477010000001716001479091972186c449eb1ded22b78e40d009bdf0089feffffff02b8b4eb0b000000001976a914a457b684d7f0d539a46a45bbc043f35b59d0d96388ac0008af2f000000001976a914fd270b1ee6abcaea97fea7ad0402e8bd8ad6d77c88ac02473044022047ac8e878352d3ebbde1c94ce3a10d057c24175747116f8288e5d794d12d482f0220217f36a485cae903c713331d877c1f64677e3622ad4010726870540656fe9dcb012103ad1d8e89212f0b92c74d23bb710c00662ad1470198ac48c43f7d6f93a2a2687392040000",
+                expected_transaction_id: "ef48d9d0f595052e0f8cdcf825f7a5e50b6a388a81f206f3f4846e5ecd7a0c23",
+            },
+        ];
+
+        #[test]
+        fn test_signet_transactions() {
+            TRANSACTIONS.iter().for_each(|transaction| {
+                test_transaction::<N>(
+                    transaction.version,
+                    transaction.lock_time,
+                    transaction.inputs.to_vec(),
+                    transaction.outputs.to_vec(),
+                    transaction.expected_signed_transaction,
+                    transaction.expected_transaction_id,
+                );
+            });
+        }
+    }
+
     mod test_invalid_transactions {
         use super::*;
         type N = Mainnet;
@@ -1939,6 +3229,1490 @@ mod tests {
         }
     }
 
+    mod test_bare_script_transactions {
+        use super::*;
+        type N = Mainnet;
+
+        const PRIVATE_KEYS: [&str; 3] = [
+            "L5BsLN6keEWUuF1JxfG6w5U1FDHs29faMpr9QX2MMVuQt7ymTorX",
+            "Kxxkik2L9KgrGgvdkEvYSkgAxaY4qPGfvxe1M1KBVBB7Ls3xDD8o",
+            "L5TmwLMEyEqMAYj1qd7Fx9YRhNJTCvNn4ofr98ErbgHA99GjLBXC",
+        ];
+        const TRANSACTION_ID: &str = "61d520ccb74288c96bc1a2b20ea1c0d5a704776dd0164a396efec3ea7040349d";
+        const OUTPUT_ADDRESS: &str = "3QDTHVyuJrHixUhhsdZXQ7M8P9MQngmw1P";
+
+        fn verify(transaction: &BitcoinTransaction<N>, vin: usize, signature: &[u8], public_key: &[u8]) -> bool {
+            let sighash = SignatureHash::from_byte(&signature[signature.len() - 1]);
+            let preimage = transaction.p2pkh_hash_preimage(vin, sighash).unwrap();
+            let message = secp256k1::Message::parse_slice(&Sha256::digest(&Sha256::digest(&preimage))).unwrap();
+            let signature = secp256k1::Signature::parse_der(&signature[..signature.len() - 1]).unwrap();
+            let public_key = secp256k1::PublicKey::parse_slice(public_key, None).unwrap();
+            secp256k1::verify(&message, &signature, &public_key)
+        }
+
+        fn unsigned_transaction(script_pub_key: &BitcoinScript) -> BitcoinTransaction<N> {
+            let input = BitcoinTransactionInput::<N>::new(
+                hex::decode(TRANSACTION_ID).unwrap(),
+                1,
+                None,
+                Some(BitcoinAmount(50000)),
+                None,
+                Some(script_pub_key.to_bytes()),
+                None,
+                SignatureHash::SIGHASH_ALL,
+            )
+            .unwrap();
+            let output = BitcoinTransactionOutput::new(
+                &BitcoinAddress::<N>::from_str(OUTPUT_ADDRESS).unwrap(),
+                BitcoinAmount(40000),
+            )
+            .unwrap();
+            BitcoinTransaction::<N>::new(&BitcoinTransactionParameters {
+                version: 1,
+                inputs: vec![input],
+                outputs: vec![output],
+                lock_time: 0,
+                segwit_flag: false,
+            })
+            .unwrap()
+        }
+
+        #[test]
+        fn test_p2pk_preimage() {
+            // The first transaction between two parties, spending the P2PK coinbase output of block 9 in block 170
+            let transaction = BitcoinTransaction::<N>::from_str("0100000001c997a5e56e104102fa209c6a852dd90660a20b2d9c352423edce25857fcd3704000000004847304402204e45e16932b8af514961a1d3a1a25fdf3f4f7732e9d624c6c61548ab5fb8cd410220181522ec8eca07de4860a4acdd12909d831cc56cbbac4622082221a8768d1d0901ffffffff0200ca9a3b00000000434104ae1a62fe09c5f51b13905f07f06b99a2f7159b2225f374cd378d71302fa28414e7aab37397f554a7df5f142c21c1b7303b8a0626f1baded5c72a704f7e6cd84cac00286bee0000000043410411db93e1dcdb8a016b49840f8c53bc1eb68a382e97b1482ecad7b148a6909a5cb2e0eaddfb84ccf9744464f82e160bfa9b8b64f9d4c03f999b8643f656b412a3ac00000000").unwrap();
+            let script_pub_key = hex::decode("410411db93e1dcdb8a016b49840f8c53bc1eb68a382e97b1482ecad7b148a6909a5cb2e0eaddfb84ccf9744464f82e160bfa9b8b64f9d4c03f999b8643f656b412a3ac").unwrap();
+            let public_key = BitcoinScript::new(&script_pub_key).to_p2pk_public_key().unwrap();
+
+            let input = &transaction.parameters.inputs[0];
+            let outpoint = Outpoint::<N>::new(
+                input.outpoint.reverse_transaction_id.clone(),
+                0,
+                None,
+                None,
+                None,
+                Some(script_pub_key),
+            )
+            .unwrap();
+            let signature = BitcoinScript::new(&input.script_sig).to_instructions().unwrap();
+            let signature = match &signature[0] {
+                BitcoinScriptInstruction::Push(signature) => signature.clone(),
+                _ => panic!("expected a signature"),
+            };
+
+            let transaction = transaction.update_outpoint(outpoint);
+            assert!(verify(&transaction, 0, &signature, &public_key));
+            assert_eq!(
+                "f4184fc596403b9d638783cf57adfe4c75c605f6356fbc91338530e9831e9e16",
+                transaction.to_transaction_id().unwrap().to_string()
+            );
+        }
+
+        #[test]
+        fn test_p2pk_transaction() {
+            for compressed in [true, false].iter() {
+                let mut private_key = BitcoinPrivateKey::<N>::from_str(PRIVATE_KEYS[0]).unwrap();
+                if !compressed {
+                    private_key = BitcoinPrivateKey::<N>::from_secp256k1_secret_key(
+                        &private_key.to_secp256k1_secret_key(),
+                        false,
+                    );
+                }
+                let public_key = private_key.to_public_key();
+                let script_pub_key = BitcoinScript::p2pk(&public_key);
+                let public_key = BitcoinScript::new(&script_pub_key.to_bytes())
+                    .to_p2pk_public_key()
+                    .unwrap();
+
+                // Another private key leaves the input unsigned
+                let transaction = unsigned_transaction(&script_pub_key);
+                let other_private_key = BitcoinPrivateKey::<N>::from_str(PRIVATE_KEYS[1]).unwrap();
+                assert_eq!(transaction, transaction.sign(&other_private_key).unwrap());
+
+                let signed = transaction.sign(&private_key).unwrap();
+                let input = &signed.parameters.inputs[0];
+                assert!(input.is_signed);
+                let signature = match BitcoinScript::new(&input.script_sig)
+                    .to_instructions()
+                    .unwrap()
+                    .as_slice()
+                {
+                    [BitcoinScriptInstruction::Push(signature)] => signature.clone(),
+                    _ => panic!("expected a single signature"),
+                };
+                assert!(verify(&signed, 0, &signature, &public_key));
+
+                let transaction_hex = hex::encode(signed.to_transaction_bytes().unwrap());
+                let parsed = BitcoinTransaction::<N>::from_str(&transaction_hex).unwrap();
+                assert_eq!(transaction_hex, hex::encode(parsed.to_transaction_bytes().unwrap()));
+            }
+        }
+
+        #[test]
+        fn test_bare_multisig_transaction() {
+            let private_keys: Vec<BitcoinPrivateKey<N>> = PRIVATE_KEYS
+                .iter()
+                .map(|private_key| BitcoinPrivateKey::<N>::from_str(private_key).unwrap())
+                .collect();
+            let public_keys: Vec<BitcoinPublicKey<N>> = private_keys
+                .iter()
+                .map(|private_key| private_key.to_public_key())
+                .collect();
+            let script_pub_key = BitcoinScript::multisig(2, &public_keys);
+            let (threshold, serialized_public_keys) = script_pub_key.to_multisig().unwrap();
+            assert_eq!(2, threshold);
+
+            let output = BitcoinTransactionOutput::from_script(&script_pub_key, BitcoinAmount(50000));
+            assert_eq!(script_pub_key.to_bytes(), output.script_pub_key);
+
+            // Sign with the third and then the first key, out of the order of the public keys
+            let transaction = unsigned_transaction(&script_pub_key);
+            let partially_signed = transaction.sign(&private_keys[2]).unwrap();
+            assert!(partially_signed.parameters.inputs[0].is_signed);
+            let signed = partially_signed.sign(&private_keys[0]).unwrap();
+
+            let instructions = BitcoinScript::new(&signed.parameters.inputs[0].script_sig)
+                .to_instructions()
+                .unwrap();
+            match instructions.as_slice() {
+                [BitcoinScriptInstruction::Opcode(0x00), BitcoinScriptInstruction::Push(first), BitcoinScriptInstruction::Push(second)] =>
+                {
+                    assert!(verify(&signed, 0, first, &serialized_public_keys[0]));
+                    assert!(verify(&signed, 0, second, &serialized_public_keys[2]));
+                }
+                _ => panic!("expected the dummy element and two signatures"),
+            }
+
+            // The threshold is met, so a further signature is not added
+            assert_eq!(signed, signed.sign(&private_keys[1]).unwrap());
+            // Signing again with the same key does not add a signature
+            assert_eq!(partially_signed, partially_signed.sign(&private_keys[2]).unwrap());
+        }
+
+        #[test]
+        fn test_invalid_bare_script_outpoints() {
+            let private_key = BitcoinPrivateKey::<N>::from_str(PRIVATE_KEYS[0]).unwrap();
+            let address = private_key.to_address(&BitcoinFormat::P2PKH).unwrap();
+            let script_pub_key = BitcoinScript::from_address(&address).unwrap().to_bytes();
+            let transaction_id = hex::decode(TRANSACTION_ID).unwrap();
+
+            assert!(Outpoint::<N>::new(transaction_id.clone(), 0, None, None, None, Some(script_pub_key)).is_err());
+
+            let script_pub_key = BitcoinScript::p2pk(&private_key.to_public_key()).to_bytes();
+            assert!(Outpoint::<N>::new(
+                transaction_id.clone(),
+                0,
+                None,
+                None,
+                Some(script_pub_key.clone()),
+                Some(script_pub_key.clone())
+            )
+            .is_err());
+            assert!(Outpoint::<N>::new(transaction_id, 0, None, None, None, Some(script_pub_key)).is_ok());
+        }
+    }
+
+    mod test_witness_script_transactions {
+        use super::*;
+        type N = Mainnet;
+
+        const PRIVATE_KEYS: [&str; 3] = [
+            "L5BsLN6keEWUuF1JxfG6w5U1FDHs29faMpr9QX2MMVuQt7ymTorX",
+            "Kxxkik2L9KgrGgvdkEvYSkgAxaY4qPGfvxe1M1KBVBB7Ls3xDD8o",
+            "L5TmwLMEyEqMAYj1qd7Fx9YRhNJTCvNn4ofr98ErbgHA99GjLBXC",
+        ];
+        const TRANSACTION_ID: &str = "61d520ccb74288c96bc1a2b20ea1c0d5a704776dd0164a396efec3ea7040349d";
+        const OUTPUT_ADDRESS: &str = "3QDTHVyuJrHixUhhsdZXQ7M8P9MQngmw1P";
+
+        fn verify(transaction: &BitcoinTransaction<N>, vin: usize, signature: &[u8], public_key: &[u8]) -> bool {
+            let sighash = SignatureHash::from_byte(&signature[signature.len() - 1]);
+            let preimage = transaction.segwit_hash_preimage(vin, sighash).unwrap();
+            let message = secp256k1::Message::parse_slice(&Sha256::digest(&Sha256::digest(&preimage))).unwrap();
+            let signature = secp256k1::Signature::parse_der(&signature[..signature.len() - 1]).unwrap();
+            let public_key = secp256k1::PublicKey::parse_slice(public_key, None).unwrap();
+            secp256k1::verify(&message, &signature, &public_key)
+        }
+
+        // Returns the witness stack of the input without the length prefixes of its items
+        fn witness_stack(transaction: &BitcoinTransaction<N>, vin: usize) -> Vec<Vec<u8>> {
+            transaction.parameters.inputs[vin]
+                .witnesses
+                .iter()
+                .map(|witness| {
+                    let mut item = &witness[..];
+                    read_variable_length_integer(&mut item).unwrap();
+                    item.to_vec()
+                })
+                .collect()
+        }
+
+        fn unsigned_transaction(witness_script: &BitcoinScript) -> BitcoinTransaction<N> {
+            let input = BitcoinTransactionInput::<N>::new(
+                hex::decode(TRANSACTION_ID).unwrap(),
+                1,
+                Some(BitcoinAddress::<N>::p2wsh(&witness_script.to_bytes()).unwrap()),
+                Some(BitcoinAmount(50000)),
+                Some(witness_script.to_bytes()),
+                None,
+                None,
+                SignatureHash::SIGHASH_ALL,
+            )
+            .unwrap();
+            let output = BitcoinTransactionOutput::new(
+                &BitcoinAddress::<N>::from_str(OUTPUT_ADDRESS).unwrap(),
+                BitcoinAmount(40000),
+            )
+            .unwrap();
+            BitcoinTransaction::<N>::new(&BitcoinTransactionParameters {
+                version: 2,
+                inputs: vec![input],
+                outputs: vec![output],
+                lock_time: 0,
+                segwit_flag: false,
+            })
+            .unwrap()
+        }
+
+        #[test]
+        fn test_p2wsh_p2pk_transaction() {
+            let private_key = BitcoinPrivateKey::<N>::from_str(PRIVATE_KEYS[0]).unwrap();
+            let witness_script = BitcoinScript::p2pk(&private_key.to_public_key());
+            let public_key = witness_script.to_p2pk_public_key().unwrap();
+
+            // Another private key leaves the input unsigned
+            let transaction = unsigned_transaction(&witness_script);
+            let other_private_key = BitcoinPrivateKey::<N>::from_str(PRIVATE_KEYS[1]).unwrap();
+            assert_eq!(transaction, transaction.sign(&other_private_key).unwrap());
+
+            let estimate = transaction.parameters.estimate_weight().unwrap();
+            let signed = transaction.sign(&private_key).unwrap();
+            assert!(signed.parameters.inputs[0].is_signed);
+            assert!(signed.parameters.inputs[0].script_sig.is_empty());
+            match witness_stack(&signed, 0).as_slice() {
+                [signature, script] => {
+                    assert!(verify(&signed, 0, signature, &public_key));
+                    assert_eq!(&witness_script.to_bytes(), script);
+                }
+                _ => panic!("expected a signature and the witness script"),
+            }
+
+            // The estimate assumes a signature of the maximum length
+            let weight = signed.size().unwrap().weight();
+            assert!(estimate >= weight && estimate - weight <= 1);
+
+            let transaction_hex = hex::encode(signed.to_transaction_bytes().unwrap());
+            let parsed = BitcoinTransaction::<N>::from_str(&transaction_hex).unwrap();
+            assert_eq!(transaction_hex, hex::encode(parsed.to_transaction_bytes().unwrap()));
+        }
+
+        #[test]
+        fn test_p2wsh_multisig_transaction() {
+            let private_keys: Vec<BitcoinPrivateKey<N>> = PRIVATE_KEYS
+                .iter()
+                .map(|private_key| BitcoinPrivateKey::<N>::from_str(private_key).unwrap())
+                .collect();
+            let public_keys: Vec<BitcoinPublicKey<N>> = private_keys
+                .iter()
+                .map(|private_key| private_key.to_public_key())
+                .collect();
+            let witness_script = BitcoinScript::multisig(2, &public_keys);
+            let (_, serialized_public_keys) = witness_script.to_multisig().unwrap();
+
+            // Sign with the third and then the first key, out of the order of the public keys
+            let transaction = unsigned_transaction(&witness_script);
+            let estimate = transaction.parameters.estimate_weight().unwrap();
+            let partially_signed = transaction.sign(&private_keys[2]).unwrap();
+            assert_eq!(3, witness_stack(&partially_signed, 0).len());
+            let signed = partially_signed.sign(&private_keys[0]).unwrap();
+
+            match witness_stack(&signed, 0).as_slice() {
+                [dummy, first, second, script] => {
+                    assert!(dummy.is_empty());
+                    assert!(verify(&signed, 0, first, &serialized_public_keys[0]));
+                    assert!(verify(&signed, 0, second, &serialized_public_keys[2]));
+                    assert_eq!(&witness_script.to_bytes(), script);
+                }
+                _ => panic!("expected the dummy element, two signatures, and the witness script"),
+            }
+            let weight = signed.size().unwrap().weight();
+            assert!(estimate >= weight && estimate - weight <= 2);
+
+            // The threshold is met, so a further signature is not added
+            assert_eq!(signed, signed.sign(&private_keys[1]).unwrap());
+            // Signing again with the same key does not add a signature
+            assert_eq!(partially_signed, partially_signed.sign(&private_keys[2]).unwrap());
+        }
+
+        #[test]
+        fn test_invalid_witness_scripts() {
+            let private_key = BitcoinPrivateKey::<N>::from_str(PRIVATE_KEYS[0]).unwrap();
+            let witness_script = BitcoinScript::p2pk(&private_key.to_public_key());
+            let other_script = BitcoinScript::p2pk(
+                &BitcoinPrivateKey::<N>::from_str(PRIVATE_KEYS[1])
+                    .unwrap()
+                    .to_public_key(),
+            );
+
+            // The witness script must hash to the address of the input
+            let mut transaction = unsigned_transaction(&witness_script);
+            transaction.parameters.inputs[0].outpoint.redeem_script = Some(other_script.to_bytes());
+            assert!(transaction.sign(&private_key).is_err());
+
+            // The witness of a script other than P2PK or multisig cannot be estimated
+            let mut transaction = unsigned_transaction(&witness_script);
+            transaction.parameters.inputs[0].outpoint.redeem_script = Some(vec![Opcode::OP_1 as u8]);
+            assert!(transaction.parameters.estimate_weight().is_err());
+        }
+    }
+
+    mod test_signature_hashes {
+        use super::*;
+        type N = Mainnet;
+
+        const PRIVATE_KEY: &str = "L5BsLN6keEWUuF1JxfG6w5U1FDHs29faMpr9QX2MMVuQt7ymTorX";
+        const TRANSACTION_ID: &str = "61d520ccb74288c96bc1a2b20ea1c0d5a704776dd0164a396efec3ea7040349d";
+        const OUTPUT_ADDRESS: &str = "3QDTHVyuJrHixUhhsdZXQ7M8P9MQngmw1P";
+        const SIGNATURE_HASHES: [SignatureHash; 6] = [
+            SignatureHash::SIGHASH_ALL,
+            SignatureHash::SIGHASH_NONE,
+            SignatureHash::SIGHASH_SINGLE,
+            SignatureHash::SIGHASH_ALL_SIGHASH_ANYONECANPAY,
+            SignatureHash::SIGHASH_NONE_SIGHASH_ANYONECANPAY,
+            SignatureHash::SIGHASH_SINGLE_SIGHASH_ANYONECANPAY,
+        ];
+
+        // Returns the parameters of a transaction with two inputs spending the address
+        // of the private key in the given format, and two outputs.
+        fn parameters(format: &BitcoinFormat, sighash: SignatureHash) -> BitcoinTransactionParameters<N> {
+            let private_key = BitcoinPrivateKey::<N>::from_str(PRIVATE_KEY).unwrap();
+            let address = private_key.to_address(format).unwrap();
+            let inputs = (0..2)
+                .map(|index| {
+                    BitcoinTransactionInput::<N>::new(
+                        hex::decode(TRANSACTION_ID).unwrap(),
+                        index,
+                        Some(address.clone()),
+                        Some(BitcoinAmount(50000)),
+                        None,
+                        None,
+                        None,
+                        sighash,
+                    )
+                    .unwrap()
+                })
+                .collect();
+            let outputs = (0..2)
+                .map(|index| {
+                    let address = BitcoinAddress::<N>::from_str(OUTPUT_ADDRESS).unwrap();
+                    BitcoinTransactionOutput::new(&address, BitcoinAmount(40000 + index)).unwrap()
+                })
+                .collect();
+            BitcoinTransactionParameters {
+                version: 2,
+                inputs,
+                outputs,
+                lock_time: 0,
+                segwit_flag: false,
+            }
+        }
+
+        fn preimage(
+            parameters: &BitcoinTransactionParameters<N>,
+            vin: usize,
+            sighash: SignatureHash,
+            format: &BitcoinFormat,
+        ) -> Vec<u8> {
+            let transaction = BitcoinTransaction::<N>::new(parameters).unwrap();
+            match format {
+                BitcoinFormat::P2PKH => transaction.p2pkh_hash_preimage(vin, sighash).unwrap(),
+                _ => transaction.segwit_hash_preimage(vin, sighash).unwrap(),
+            }
+        }
+
+        // Checks which parts of the transaction the signature hash of the second input commits to.
+        fn test_commitments(format: &BitcoinFormat) {
+            for sighash in SIGNATURE_HASHES.iter() {
+                let parameters = parameters(format, *sighash);
+                let expected = preimage(&parameters, 1, *sighash, format);
+                let commits = |modify: &dyn Fn(&mut BitcoinTransactionParameters<N>) -> usize| {
+                    let mut modified = parameters.clone();
+                    let vin = modify(&mut modified);
+                    expected != preimage(&modified, vin, *sighash, format)
+                };
+
+                let other_sequence = commits(&|parameters| {
+                    parameters.inputs[0].sequence = vec![0u8; 4];
+                    1
+                });
+                let other_input = commits(&|parameters| {
+                    let mut input = parameters.inputs[0].clone();
+                    input.outpoint.index = 2;
+                    parameters.inputs.push(input);
+                    1
+                });
+                let other_output = commits(&|parameters| {
+                    parameters.outputs[0].amount = BitcoinAmount(1);
+                    1
+                });
+                let own_output = commits(&|parameters| {
+                    parameters.outputs[1].amount = BitcoinAmount(1);
+                    1
+                });
+
+                let base = sighash.base();
+                assert_eq!(
+                    !sighash.is_anyone_can_pay() && base == SignatureHash::SIGHASH_ALL,
+                    other_sequence
+                );
+                assert_eq!(!sighash.is_anyone_can_pay(), other_input);
+                assert_eq!(base == SignatureHash::SIGHASH_ALL, other_output);
+                assert_eq!(base != SignatureHash::SIGHASH_NONE, own_output);
+            }
+        }
+
+        #[test]
+        fn test_p2pkh_signature_hashes() {
+            test_commitments(&BitcoinFormat::P2PKH);
+        }
+
+        #[test]
+        fn test_segwit_signature_hashes() {
+            test_commitments(&BitcoinFormat::Bech32);
+        }
+
+        #[test]
+        fn test_sign_signature_hashes() {
+            let private_key = BitcoinPrivateKey::<N>::from_str(PRIVATE_KEY).unwrap();
+            let public_key = private_key.to_public_key().to_secp256k1_public_key();
+            for sighash in SIGNATURE_HASHES.iter() {
+                let parameters = parameters(&BitcoinFormat::Bech32, *sighash);
+                let transaction = BitcoinTransaction::<N>::new(&parameters).unwrap();
+                let signed = transaction.sign(&private_key).unwrap();
+
+                for vin in 0..2 {
+                    let witness = &signed.parameters.inputs[vin].witnesses[0];
+                    let signature = &witness[1..];
+                    assert_eq!(*sighash as u8, signature[signature.len() - 1]);
+
+                    let preimage = transaction.segwit_hash_preimage(vin, *sighash).unwrap();
+                    let message = Sha256::digest(&Sha256::digest(&preimage));
+                    let message = secp256k1::Message::parse_slice(&message).unwrap();
+                    let signature = secp256k1::Signature::parse_der(&signature[..signature.len() - 1]).unwrap();
+                    assert!(secp256k1::verify(&message, &signature, &public_key));
+                }
+            }
+
+            // SIGHASH_SINGLE requires an output at the index of the input
+            for format in [BitcoinFormat::P2PKH, BitcoinFormat::Bech32].iter() {
+                let mut parameters = parameters(format, SignatureHash::SIGHASH_SINGLE);
+                parameters.outputs.truncate(1);
+                let transaction = BitcoinTransaction::<N>::new(&parameters).unwrap();
+                assert!(transaction.sign(&private_key).is_err());
+            }
+        }
+
+        #[test]
+        fn test_signature_hash_names() {
+            for sighash in SIGNATURE_HASHES.iter() {
+                assert_eq!(*sighash, SignatureHash::from_str(&sighash.to_string()).unwrap());
+                assert_eq!(Some(*sighash), SignatureHash::from_type(*sighash as u32));
+            }
+            assert_eq!(
+                SignatureHash::SIGHASH_SINGLE_SIGHASH_ANYONECANPAY,
+                SignatureHash::from_str("single|anyonecanpay").unwrap()
+            );
+            assert_eq!(
+                SignatureHash::SIGHASH_DEFAULT,
+                SignatureHash::from_str("SIGHASH_DEFAULT").unwrap()
+            );
+            assert_eq!(Some(SignatureHash::SIGHASH_DEFAULT), SignatureHash::from_type(0x00));
+            assert!(SignatureHash::from_str("ANYONECANPAY").is_err());
+            assert_eq!(None, SignatureHash::from_type(0x04));
+        }
+    }
+
+    mod test_taproot_signatures {
+        use super::*;
+        use crate::taproot::{TaprootLeaf, TaprootScriptTree, TaprootSpendInfo};
+        type N = Mainnet;
+
+        const PRIVATE_KEY: &str = "L5BsLN6keEWUuF1JxfG6w5U1FDHs29faMpr9QX2MMVuQt7ymTorX";
+        const LEAF_PRIVATE_KEY: &str = "KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73sVHnoWn";
+        const TRANSACTION_ID: &str = "61d520ccb74288c96bc1a2b20ea1c0d5a704776dd0164a396efec3ea7040349d";
+        const OUTPUT_ADDRESS: &str = "3QDTHVyuJrHixUhhsdZXQ7M8P9MQngmw1P";
+        const SIGNATURE_HASHES: [SignatureHash; 7] = [
+            SignatureHash::SIGHASH_DEFAULT,
+            SignatureHash::SIGHASH_ALL,
+            SignatureHash::SIGHASH_NONE,
+            SignatureHash::SIGHASH_SINGLE,
+            SignatureHash::SIGHASH_ALL_SIGHASH_ANYONECANPAY,
+            SignatureHash::SIGHASH_NONE_SIGHASH_ANYONECANPAY,
+            SignatureHash::SIGHASH_SINGLE_SIGHASH_ANYONECANPAY,
+        ];
+
+        // https://github.com/bitcoin/bips/blob/master/bip-0341/wallet-test-vectors.json
+        const UNSIGNED_TRANSACTION: &str = "02000000097de20cbff686da83a54981d2b9bab3586f4ca7e48f57f5b55963115f3b334e9c010000000000000000d7b7cab57b1393ace2d064f4d4a2cb8af6def61273e127517d44759b6dafdd990000000000fffffffff8e1f583384333689228c5d28eac13366be082dc57441760d957275419a418420000000000fffffffff0689180aa63b30cb162a73c6d2a38b7eeda2a83ece74310fda0843ad604853b0100000000feffffffaa5202bdf6d8ccd2ee0f0202afbbb7461d9264a25e5bfd3c5a52ee1239e0ba6c0000000000feffffff956149bdc66faa968eb2be2d2faa29718acbfe3941215893a2a3446d32acd050000000000000000000e664b9773b88c09c32cb70a2a3e4da0ced63b7ba3b22f848531bbb1d5d5f4c94010000000000000000e9aa6b8e6c9de67619e6a3924ae25696bb7b694bb677a632a74ef7eadfd4eabf0000000000ffffffffa778eb6a263dc090464cd125c466b5a99667720b1c110468831d058aa1b82af10100000000ffffffff0200ca9a3b000000001976a91406afd46bcdfd22ef94ac122aa11f241244a37ecc88ac807840cb0000000020ac9a87f5594be208f8532db38cff670c450ed2fea8fcdefcc9a663f78bab962b0065cd1d";
+        const SPENT_OUTPUTS: [(&str, i64); 9] = [
+            (
+                "512053a1f6e454df1aa2776a2814a721372d6258050de330b3c6d10ee8f4e0dda343",
+                420000000,
+            ),
+            (
+                "5120147c9c57132f6e7ecddba9800bb0c4449251c92a1e60371ee77557b6620f3ea3",
+                462000000,
+            ),
+            ("76a914751e76e8199196d454941c45d1b3a323f1433bd688ac", 294000000),
+            (
+                "5120e4d810fd50586274face62b8a807eb9719cef49c04177cc6b76a9a4251d5450e",
+                504000000,
+            ),
+            (
+                "512091b64d5324723a985170e4dc5a0f84c041804f2cd12660fa5dec09fc21783605",
+                630000000,
+            ),
+            ("00147dd65592d0ab2fe0d0257d571abf032cd9db93dc", 378000000),
+            (
+                "512075169f4001aa68f15bbed28b218df1d0a62cbbcf1188c6665110c293c907b831",
+                672000000,
+            ),
+            (
+                "5120712447206d7a5238acc7ff53fbe94a3b64539ad291c7cdbc490b7577e4b17df5",
+                546000000,
+            ),
+            (
+                "512077e30a5522dd9f894c3f8b8bd4c4b2cf82ca7da8a3ea6a239655c39c050ab220",
+                588000000,
+            ),
+        ];
+
+        struct KeyPathSpend {
+            vin: usize,
+            internal_private_key: &'static str,
+            merkle_root: Option<&'static str>,
+            sighash_type: u32,
+            tweaked_private_key: &'static str,
+            sighash_message: &'static str,
+            sighash: &'static str,
+            witness: &'static str,
+        }
+
+        const KEY_PATH_SPENDS: [KeyPathSpend; 7] = [
+            KeyPathSpend {
+                vin: 0,
+                internal_private_key: "6b973d88838f27366ed61c9ad6367663045cb456e28335c109e30717ae0c6baa",
+                merkle_root: None,
+                sighash_type: 0x03,
+                tweaked_private_key: "2405b971772ad26915c8dcdf10f238753a9b837e5f8e6a86fd7c0cce5b7296d9",
+                sighash_message: "0003020000000065cd1de3b33bb4ef3a52ad1fffb555c0d82828eb22737036eaeb02a235d82b909c4c3f58a6964a4f5f8f0b642ded0a8a553be7622a719da71d1f5befcefcdee8e0fde623ad0f61ad2bca5ba6a7693f50fce988e17c3780bf2b1e720cfbb38fbdd52e2118959c7221ab5ce9e26c3cd67b22c24f8baa54bac281d8e6b05e400e6c3a957e0000000000d0418f0e9a36245b9a50ec87f8bf5be5bcae434337b87139c3a5b1f56e33cba0",
+                sighash: "2514a6272f85cfa0f45eb907fcb0d121b808ed37c6ea160a5a9046ed5526d555",
+                witness: "ed7c1647cb97379e76892be0cacff57ec4a7102aa24296ca39af7541246d8ff14d38958d4cc1e2e478e4d4a764bbfd835b16d4e314b72937b29833060b87276c03",
+            },
+            KeyPathSpend {
+                vin: 1,
+                internal_private_key: "1e4da49f6aaf4e5cd175fe08a32bb5cb4863d963921255f33d3bc31e1343907f",
+                merkle_root: Some("5b75adecf53548f3ec6ad7d78383bf84cc57b55a3127c72b9a2481752dd88b21"),
+                sighash_type: 0x83,
+                tweaked_private_key: "ea260c3b10e60f6de018455cd0278f2f5b7e454be1999572789e6a9565d26080",
+                sighash_message: "0083020000000065cd1d00d7b7cab57b1393ace2d064f4d4a2cb8af6def61273e127517d44759b6dafdd9900000000808f891b00000000225120147c9c57132f6e7ecddba9800bb0c4449251c92a1e60371ee77557b6620f3ea3ffffffffffcef8fb4ca7efc5433f591ecfc57391811ce1e186a3793024def5c884cba51d",
+                sighash: "325a644af47e8a5a2591cda0ab0723978537318f10e6a63d4eed783b96a71a4d",
+                witness: "052aedffc554b41f52b521071793a6b88d6dbca9dba94cf34c83696de0c1ec35ca9c5ed4ab28059bd606a4f3a657eec0bb96661d42921b5f50a95ad33675b54f83",
+            },
+            KeyPathSpend {
+                vin: 3,
+                internal_private_key: "d3c7af07da2d54f7a7735d3d0fc4f0a73164db638b2f2f7c43f711f6d4aa7e64",
+                merkle_root: Some("c525714a7f49c28aedbbba78c005931a81c234b2f6c99a73e4d06082adc8bf2b"),
+                sighash_type: 0x01,
+                tweaked_private_key: "97323385e57015b75b0339a549c56a948eb961555973f0951f555ae6039ef00d",
+                sighash_message: "0001020000000065cd1de3b33bb4ef3a52ad1fffb555c0d82828eb22737036eaeb02a235d82b909c4c3f58a6964a4f5f8f0b642ded0a8a553be7622a719da71d1f5befcefcdee8e0fde623ad0f61ad2bca5ba6a7693f50fce988e17c3780bf2b1e720cfbb38fbdd52e2118959c7221ab5ce9e26c3cd67b22c24f8baa54bac281d8e6b05e400e6c3a957ea2e6dab7c1f0dcd297c8d61647fd17d821541ea69c3cc37dcbad7f90d4eb4bc50003000000",
+                sighash: "bf013ea93474aa67815b1b6cc441d23b64fa310911d991e713cd34c7f5d46669",
+                witness: "ff45f742a876139946a149ab4d9185574b98dc919d2eb6754f8abaa59d18b025637a3aa043b91817739554f4ed2026cf8022dbd83e351ce1fabc272841d2510a01",
+            },
+            KeyPathSpend {
+                vin: 4,
+                internal_private_key: "f36bb07a11e469ce941d16b63b11b9b9120a84d9d87cff2c84a8d4affb438f4e",
+                merkle_root: Some("ccbd66c6f7e8fdab47b3a486f59d28262be857f30d4773f2d5ea47f7761ce0e2"),
+                sighash_type: 0x00,
+                tweaked_private_key: "a8e7aa924f0d58854185a490e6c41f6efb7b675c0f3331b7f14b549400b4d501",
+                sighash_message: "0000020000000065cd1de3b33bb4ef3a52ad1fffb555c0d82828eb22737036eaeb02a235d82b909c4c3f58a6964a4f5f8f0b642ded0a8a553be7622a719da71d1f5befcefcdee8e0fde623ad0f61ad2bca5ba6a7693f50fce988e17c3780bf2b1e720cfbb38fbdd52e2118959c7221ab5ce9e26c3cd67b22c24f8baa54bac281d8e6b05e400e6c3a957ea2e6dab7c1f0dcd297c8d61647fd17d821541ea69c3cc37dcbad7f90d4eb4bc50004000000",
+                sighash: "4f900a0bae3f1446fd48490c2958b5a023228f01661cda3496a11da502a7f7ef",
+                witness: "b4010dd48a617db09926f729e79c33ae0b4e94b79f04a1ae93ede6315eb3669de185a17d2b0ac9ee09fd4c64b678a0b61a0a86fa888a273c8511be83bfd6810f",
+            },
+            KeyPathSpend {
+                vin: 6,
+                internal_private_key: "415cfe9c15d9cea27d8104d5517c06e9de48e2f986b695e4f5ffebf230e725d8",
+                merkle_root: Some("2f6b2c5397b6d68ca18e09a3f05161668ffe93a988582d55c6f07bd5b3329def"),
+                sighash_type: 0x02,
+                tweaked_private_key: "241c14f2639d0d7139282aa6abde28dd8a067baa9d633e4e7230287ec2d02901",
+                sighash_message: "0002020000000065cd1de3b33bb4ef3a52ad1fffb555c0d82828eb22737036eaeb02a235d82b909c4c3f58a6964a4f5f8f0b642ded0a8a553be7622a719da71d1f5befcefcdee8e0fde623ad0f61ad2bca5ba6a7693f50fce988e17c3780bf2b1e720cfbb38fbdd52e2118959c7221ab5ce9e26c3cd67b22c24f8baa54bac281d8e6b05e400e6c3a957e0006000000",
+                sighash: "15f25c298eb5cdc7eb1d638dd2d45c97c4c59dcaec6679cfc16ad84f30876b85",
+                witness: "a3785919a2ce3c4ce26f298c3d51619bc474ae24014bcdd31328cd8cfbab2eff3395fa0a16fe5f486d12f22a9cedded5ae74feb4bbe5351346508c5405bcfee002",
+            },
+            KeyPathSpend {
+                vin: 7,
+                internal_private_key: "c7b0e81f0a9a0b0499e112279d718cca98e79a12e2f137c72ae5b213aad0d103",
+                merkle_root: Some("6c2dc106ab816b73f9d07e3cd1ef2c8c1256f519748e0813e4edd2405d277bef"),
+                sighash_type: 0x82,
+                tweaked_private_key: "65b6000cd2bfa6b7cf736767a8955760e62b6649058cbc970b7c0871d786346b",
+                sighash_message: "0082020000000065cd1d00e9aa6b8e6c9de67619e6a3924ae25696bb7b694bb677a632a74ef7eadfd4eabf00000000804c8b2000000000225120712447206d7a5238acc7ff53fbe94a3b64539ad291c7cdbc490b7577e4b17df5ffffffff",
+                sighash: "cd292de50313804dabe4685e83f923d2969577191a3e1d2882220dca88cbeb10",
+                witness: "ea0c6ba90763c2d3a296ad82ba45881abb4f426b3f87af162dd24d5109edc1cdd11915095ba47c3a9963dc1e6c432939872bc49212fe34c632cd3ab9fed429c482",
+            },
+            KeyPathSpend {
+                vin: 8,
+                internal_private_key: "77863416be0d0665e517e1c375fd6f75839544eca553675ef7fdf4949518ebaa",
+                merkle_root: Some("ab179431c28d3b68fb798957faf5497d69c883c6fb1e1cd9f81483d87bac90cc"),
+                sighash_type: 0x81,
+                tweaked_private_key: "ec18ce6af99f43815db543f47b8af5ff5df3b2cb7315c955aa4a86e8143d2bf5",
+                sighash_message: "0081020000000065cd1da2e6dab7c1f0dcd297c8d61647fd17d821541ea69c3cc37dcbad7f90d4eb4bc500a778eb6a263dc090464cd125c466b5a99667720b1c110468831d058aa1b82af101000000002b0c230000000022512077e30a5522dd9f894c3f8b8bd4c4b2cf82ca7da8a3ea6a239655c39c050ab220ffffffff",
+                sighash: "cccb739eca6c13a8a89e6e5cd317ffe55669bbda23f2fd37b0f18755e008edd2",
+                witness: "bbc9584a11074e83bc8c6759ec55401f0ae7b03ef290c3139814f545b58a9f8127258000874f44bc46db7646322107d4d86aec8e73b8719a61fff761d75b5dd981",
+            },
+        ];
+
+        // Returns the parameters of a transaction with two inputs spending the address, and two outputs.
+        fn parameters(
+            address: &BitcoinAddress<N>,
+            sighash: SignatureHash,
+            spend: Option<TaprootSpend>,
+        ) -> BitcoinTransactionParameters<N> {
+            let inputs = (0..2)
+                .map(|index| BitcoinTransactionInput::<N> {
+                    taproot_spend: spend.clone(),
+                    ..BitcoinTransactionInput::<N>::new(
+                        hex::decode(TRANSACTION_ID).unwrap(),
+                        index,
+                        Some(address.clone()),
+                        Some(BitcoinAmount(50000)),
+                        None,
+                        None,
+                        None,
+                        sighash,
+                    )
+                    .unwrap()
+                })
+                .collect();
+            let outputs = (0..2)
+                .map(|index| {
+                    let address = BitcoinAddress::<N>::from_str(OUTPUT_ADDRESS).unwrap();
+                    BitcoinTransactionOutput::new(&address, BitcoinAmount(40000 + index)).unwrap()
+                })
+                .collect();
+            BitcoinTransactionParameters {
+                version: 2,
+                inputs,
+                outputs,
+                lock_time: 0,
+                segwit_flag: false,
+            }
+        }
+
+        // Returns the script tree with a leaf that checks a signature of the leaf private key.
+        fn script_tree() -> (TaprootLeaf, TaprootScriptTree) {
+            let leaf_private_key = BitcoinPrivateKey::<N>::from_str(LEAF_PRIVATE_KEY).unwrap();
+            let script = BitcoinScript::builder()
+                .push_data(&leaf_private_key.to_public_key().to_x_only_public_key())
+                .push_opcode(Opcode::OP_CHECKSIG)
+                .into_script()
+                .to_bytes();
+            let leaf = TaprootLeaf::tapscript(&script);
+            let script_tree = TaprootScriptTree::branch(
+                TaprootScriptTree::Leaf(leaf.clone()),
+                TaprootScriptTree::leaf(&[Opcode::OP_1 as u8]),
+            );
+            (leaf, script_tree)
+        }
+
+        // Returns the signature of the witness item and the signature hash it signs.
+        fn signature(witness: &[u8], sighash: SignatureHash) -> [u8; 64] {
+            match sighash {
+                SignatureHash::SIGHASH_DEFAULT => assert_eq!(65, witness.len()),
+                _ => {
+                    assert_eq!(66, witness.len());
+                    assert_eq!(sighash as u8, witness[65]);
+                }
+            };
+            let mut signature = [0u8; 64];
+            signature.copy_from_slice(&witness[1..65]);
+            signature
+        }
+
+        #[test]
+        fn test_taproot_commitments() {
+            let private_key = BitcoinPrivateKey::<N>::from_str(PRIVATE_KEY).unwrap();
+            let address = private_key.to_address(&BitcoinFormat::P2TR).unwrap();
+            for sighash in SIGNATURE_HASHES.iter() {
+                let parameters = parameters(&address, *sighash, None);
+                let preimage = |parameters: &BitcoinTransactionParameters<N>, vin: usize| {
+                    let transaction = BitcoinTransaction::<N>::new(parameters).unwrap();
+                    transaction.taproot_hash_preimage(vin, *sighash, None).unwrap()
+                };
+                let expected = preimage(&parameters, 1);
+                let commits = |modify: &dyn Fn(&mut BitcoinTransactionParameters<N>) -> usize| {
+                    let mut modified = parameters.clone();
+                    let vin = modify(&mut modified);
+                    expected != preimage(&modified, vin)
+                };
+
+                let other_sequence = commits(&|parameters| {
+                    parameters.inputs[0].sequence = vec![0u8; 4];
+                    1
+                });
+                let other_amount = commits(&|parameters| {
+                    parameters.inputs[0].outpoint.amount = Some(BitcoinAmount(1));
+                    1
+                });
+                let other_input = commits(&|parameters| {
+                    let mut input = parameters.inputs[0].clone();
+                    input.outpoint.index = 2;
+                    parameters.inputs.push(input);
+                    1
+                });
+                let other_output = commits(&|parameters| {
+                    parameters.outputs[0].amount = BitcoinAmount(1);
+                    1
+                });
+                let own_output = commits(&|parameters| {
+                    parameters.outputs[1].amount = BitcoinAmount(1);
+                    1
+                });
+
+                // Unlike SegWit v0, Taproot signatures commit to the sequences of all inputs without ANYONECANPAY
+                let base = sighash.base();
+                assert_eq!(!sighash.is_anyone_can_pay(), other_sequence);
+                assert_eq!(!sighash.is_anyone_can_pay(), other_amount);
+                assert_eq!(!sighash.is_anyone_can_pay(), other_input);
+                assert_eq!(base == SignatureHash::SIGHASH_ALL, other_output);
+                assert_eq!(base != SignatureHash::SIGHASH_NONE, own_output);
+            }
+        }
+
+        #[test]
+        fn test_sign_key_path() {
+            let private_key = BitcoinPrivateKey::<N>::from_str(PRIVATE_KEY).unwrap();
+            let public_key = private_key.to_public_key();
+            let (_, script_tree) = script_tree();
+            let spend_infos = [
+                TaprootSpendInfo::new(&public_key, None).unwrap(),
+                TaprootSpendInfo::new(&public_key, Some(script_tree)).unwrap(),
+            ];
+
+            for spend_info in spend_infos.iter() {
+                let address = spend_info.to_address().unwrap();
+                let output_key = BitcoinPublicKey::<N>::from_x_only_public_key(&spend_info.output_key()).unwrap();
+                let spend = spend_info.to_key_path_spend().unwrap();
+                for sighash in SIGNATURE_HASHES.iter() {
+                    let transaction =
+                        BitcoinTransaction::<N>::new(&parameters(&address, *sighash, Some(spend.clone()))).unwrap();
+                    let signed = transaction.sign(&private_key).unwrap();
+                    assert!(signed.parameters.segwit_flag);
+
+                    for vin in 0..2 {
+                        let input = &signed.parameters.inputs[vin];
+                        assert!(input.is_signed && input.script_sig.is_empty());
+                        assert_eq!(1, input.witnesses.len());
+
+                        let preimage = transaction.taproot_hash_preimage(vin, *sighash, None).unwrap();
+                        let hash = tagged_hash(b"TapSighash", &preimage);
+                        assert!(output_key.verify_schnorr(&hash, &signature(&input.witnesses[0], *sighash)));
+                    }
+                }
+            }
+
+            // The default key path spend has no script tree
+            let address = private_key.to_address(&BitcoinFormat::P2TR).unwrap();
+            let transaction =
+                BitcoinTransaction::<N>::new(&parameters(&address, SignatureHash::SIGHASH_DEFAULT, None)).unwrap();
+            let signed = transaction.sign(&private_key).unwrap();
+            assert!(signed.parameters.inputs.iter().all(|input| input.is_signed));
+        }
+
+        #[test]
+        fn test_sign_script_path() {
+            let private_key = BitcoinPrivateKey::<N>::from_str(PRIVATE_KEY).unwrap();
+            let leaf_private_key = BitcoinPrivateKey::<N>::from_str(LEAF_PRIVATE_KEY).unwrap();
+            let leaf_public_key = leaf_private_key.to_public_key();
+            let (leaf, script_tree) = script_tree();
+            let spend_info = TaprootSpendInfo::new(&private_key.to_public_key(), Some(script_tree)).unwrap();
+            let address = spend_info.to_address().unwrap();
+            let spend = spend_info.to_script_path_spend(&leaf).unwrap();
+            let control_block = spend_info.to_control_block(&leaf).unwrap();
+
+            for sighash in SIGNATURE_HASHES.iter() {
+                let transaction =
+                    BitcoinTransaction::<N>::new(&parameters(&address, *sighash, Some(spend.clone()))).unwrap();
+
+                // The internal key does not sign a script path spend
+                let unsigned = transaction.sign(&private_key).unwrap();
+                assert!(unsigned.parameters.inputs.iter().all(|input| !input.is_signed));
+
+                let signed = transaction.sign(&leaf_private_key).unwrap();
+                for vin in 0..2 {
+                    let witnesses = &signed.parameters.inputs[vin].witnesses;
+                    assert_eq!(3, witnesses.len());
+                    assert_eq!(leaf.script(), &witnesses[1][1..]);
+                    assert_eq!(control_block.to_bytes(), witnesses[2][1..].to_vec());
+                    assert!(control_block.verify(&spend_info.output_key(), &witnesses[1][1..]));
+
+                    let leaf_hash = leaf.to_leaf_hash().unwrap();
+                    let preimage = transaction
+                        .taproot_hash_preimage(vin, *sighash, Some(&leaf_hash))
+                        .unwrap();
+                    assert_ne!(
+                        preimage,
+                        transaction.taproot_hash_preimage(vin, *sighash, None).unwrap()
+                    );
+                    let hash = tagged_hash(b"TapSighash", &preimage);
+                    assert!(leaf_public_key.verify_schnorr(&hash, &signature(&witnesses[0], *sighash)));
+                }
+
+                // The signed transaction round trips with its witnesses
+                let bytes = signed.to_transaction_bytes().unwrap();
+                let read = BitcoinTransaction::<N>::from_transaction_bytes(&bytes).unwrap();
+                assert_eq!(bytes, read.to_transaction_bytes().unwrap());
+                assert_eq!(
+                    signed.to_transaction_id().unwrap().txid,
+                    transaction.to_transaction_id().unwrap().txid
+                );
+            }
+        }
+
+        #[test]
+        fn test_invalid_taproot_spends() {
+            let private_key = BitcoinPrivateKey::<N>::from_str(PRIVATE_KEY).unwrap();
+            let leaf_private_key = BitcoinPrivateKey::<N>::from_str(LEAF_PRIVATE_KEY).unwrap();
+            let (leaf, script_tree) = script_tree();
+            let spend_info = TaprootSpendInfo::new(&private_key.to_public_key(), Some(script_tree)).unwrap();
+            let address = spend_info.to_address().unwrap();
+
+            // The key path spend of the wrong Merkle root does not sign
+            let transaction = BitcoinTransaction::<N>::new(&parameters(
+                &address,
+                SignatureHash::SIGHASH_DEFAULT,
+                Some(TaprootSpend::KeyPath(None)),
+            ))
+            .unwrap();
+            let signed = transaction.sign(&private_key).unwrap();
+            assert!(signed.parameters.inputs.iter().all(|input| !input.is_signed));
+
+            // The control block must commit to the leaf
+            let other_spend_info = TaprootSpendInfo::new(
+                &leaf_private_key.to_public_key(),
+                Some(TaprootScriptTree::Leaf(leaf.clone())),
+            )
+            .unwrap();
+            let spend = other_spend_info.to_script_path_spend(&leaf).unwrap();
+            let transaction =
+                BitcoinTransaction::<N>::new(&parameters(&address, SignatureHash::SIGHASH_DEFAULT, Some(spend)))
+                    .unwrap();
+            assert!(transaction.sign(&leaf_private_key).is_err());
+
+            // SIGHASH_SINGLE requires an output at the index of the input
+            let mut parameters = parameters(&address, SignatureHash::SIGHASH_SINGLE, None);
+            parameters.outputs.truncate(1);
+            let transaction = BitcoinTransaction::<N>::new(&parameters).unwrap();
+            assert!(transaction
+                .taproot_hash_preimage(1, SignatureHash::SIGHASH_SINGLE, None)
+                .is_err());
+
+            // SIGHASH_DEFAULT is only valid for Taproot spends
+            assert!(transaction
+                .p2pkh_hash_preimage(0, SignatureHash::SIGHASH_DEFAULT)
+                .is_err());
+            assert!(transaction
+                .segwit_hash_preimage(0, SignatureHash::SIGHASH_DEFAULT)
+                .is_err());
+        }
+
+        #[test]
+        fn test_key_path_vectors() {
+            let bytes = hex::decode(UNSIGNED_TRANSACTION).unwrap();
+            let mut transaction = BitcoinTransaction::<N>::from_transaction_bytes(&bytes).unwrap();
+            for (input, (script_pub_key, amount)) in transaction.parameters.inputs.iter_mut().zip(SPENT_OUTPUTS.iter())
+            {
+                input.outpoint.amount = Some(BitcoinAmount(*amount));
+                input.outpoint.script_pub_key = Some(hex::decode(script_pub_key).unwrap());
+            }
+
+            for spend in KEY_PATH_SPENDS.iter() {
+                let sighash = SignatureHash::from_type(spend.sighash_type).unwrap();
+                let preimage = transaction.taproot_hash_preimage(spend.vin, sighash, None).unwrap();
+                assert_eq!(spend.sighash_message, hex::encode(&preimage));
+                let hash = tagged_hash(b"TapSighash", &preimage);
+                assert_eq!(spend.sighash, hex::encode(hash));
+
+                let secret_key =
+                    secp256k1::SecretKey::parse_slice(&hex::decode(spend.internal_private_key).unwrap()).unwrap();
+                let private_key = BitcoinPrivateKey::<N>::from_secp256k1_secret_key(&secret_key, true);
+                let merkle_root = spend.merkle_root.map(|merkle_root| {
+                    let mut bytes = [0u8; 32];
+                    bytes.copy_from_slice(&hex::decode(merkle_root).unwrap());
+                    bytes
+                });
+                let tweaked_private_key = private_key
+                    .to_taproot_tweaked_private_key(merkle_root.as_ref())
+                    .unwrap();
+                assert_eq!(
+                    spend.tweaked_private_key,
+                    hex::encode(tweaked_private_key.to_secp256k1_secret_key().serialize())
+                );
+
+                // The tweaked key is the output key of the spent script public key
+                let (output_key, _) = private_key
+                    .to_public_key()
+                    .to_taproot_tweaked_key(merkle_root.as_ref())
+                    .unwrap();
+                assert_eq!(&SPENT_OUTPUTS[spend.vin].0[4..], hex::encode(output_key));
+
+                // The witness signatures are generated with zeroed auxiliary randomness
+                let mut witness = tweaked_private_key.sign_schnorr(&hash, &[0u8; 32]).unwrap().to_vec();
+                if sighash != SignatureHash::SIGHASH_DEFAULT {
+                    witness.push(sighash as u8);
+                }
+                assert_eq!(spend.witness, hex::encode(&witness));
+            }
+        }
+
+        #[test]
+        fn test_script_path_vectors() {
+            // https://github.com/bitcoin/bips/blob/master/bip-0341/wallet-test-vectors.json
+            let mut internal_key = [0u8; 32];
+            internal_key.copy_from_slice(
+                &hex::decode("e0dfe2300b0dd746a3f8674dfd4525623639042569d829c7f0eed9602d263e6f").unwrap(),
+            );
+            let scripts = [
+                "2072ea6adcf1d371dea8fba1035a09f3d24ed5a059799bae114084130ee5898e69ac",
+                "202352d137f2f3ab38d1eaa976758873377fa5ebb817372c71e2c542313d4abda8ac",
+                "207337c0dd4253cb86f2c43a2351aadd82cccb12a172cd120452b9bb8324f2186aac",
+            ];
+            let leaves: Vec<TaprootLeaf> = scripts
+                .iter()
+                .map(|script| TaprootLeaf::tapscript(&hex::decode(script).unwrap()))
+                .collect();
+            let script_tree = TaprootScriptTree::branch(
+                TaprootScriptTree::Leaf(leaves[0].clone()),
+                TaprootScriptTree::branch(
+                    TaprootScriptTree::Leaf(leaves[1].clone()),
+                    TaprootScriptTree::Leaf(leaves[2].clone()),
+                ),
+            );
+            assert_eq!(
+                "ccbd66c6f7e8fdab47b3a486f59d28262be857f30d4773f2d5ea47f7761ce0e2",
+                hex::encode(script_tree.to_merkle_root().unwrap())
+            );
+
+            let spend_info = TaprootSpendInfo::new(
+                &BitcoinPublicKey::<N>::from_x_only_public_key(&internal_key).unwrap(),
+                Some(script_tree),
+            )
+            .unwrap();
+            assert_eq!(
+                "91b64d5324723a985170e4dc5a0f84c041804f2cd12660fa5dec09fc21783605",
+                hex::encode(spend_info.output_key())
+            );
+            assert_eq!(
+                "bc1pjxmy65eywgafs5tsunw95ruycpqcqnev6ynxp7jaasylcgtcxczs6n332e",
+                spend_info.to_address().unwrap().to_string()
+            );
+
+            let control_blocks = [
+                "c0e0dfe2300b0dd746a3f8674dfd4525623639042569d829c7f0eed9602d263e6fffe578e9ea769027e4f5a3de40732f75a88a6353a09d767ddeb66accef85e553",
+                "c0e0dfe2300b0dd746a3f8674dfd4525623639042569d829c7f0eed9602d263e6f9e31407bffa15fefbf5090b149d53959ecdf3f62b1246780238c24501d5ceaf62645a02e0aac1fe69d69755733a9b7621b694bb5b5cde2bbfc94066ed62b9817",
+                "c0e0dfe2300b0dd746a3f8674dfd4525623639042569d829c7f0eed9602d263e6fba982a91d4fc552163cb1c0da03676102d5b7a014304c01f0c77b2b8e888de1c2645a02e0aac1fe69d69755733a9b7621b694bb5b5cde2bbfc94066ed62b9817",
+            ];
+            for (leaf, expected) in leaves.iter().zip(control_blocks.iter()) {
+                let control_block = spend_info.to_control_block(leaf).unwrap();
+                assert_eq!(*expected, hex::encode(control_block.to_bytes()));
+                assert!(control_block.verify(&spend_info.output_key(), leaf.script()));
+            }
+        }
+    }
+
+    mod test_lock_times {
+        use super::*;
+        type N = Mainnet;
+
+        const TRANSACTION_ID: &str = "61d520ccb74288c96bc1a2b20ea1c0d5a704776dd0164a396efec3ea7040349d";
+        const OUTPUT_ADDRESS: &str = "3QDTHVyuJrHixUhhsdZXQ7M8P9MQngmw1P";
+
+        // Returns the parameters of a transaction of the given version with two inputs of the default sequence.
+        fn parameters(version: u32) -> BitcoinTransactionParameters<N> {
+            let inputs = (0..2)
+                .map(|index| {
+                    BitcoinTransactionInput::<N>::new(
+                        hex::decode(TRANSACTION_ID).unwrap(),
+                        index,
+                        None,
+                        None,
+                        None,
+                        None,
+                        None,
+                        SignatureHash::SIGHASH_ALL,
+                    )
+                    .unwrap()
+                })
+                .collect();
+            let address = BitcoinAddress::<N>::from_str(OUTPUT_ADDRESS).unwrap();
+            BitcoinTransactionParameters {
+                version,
+                inputs,
+                outputs: vec![BitcoinTransactionOutput::new(&address, BitcoinAmount(40000)).unwrap()],
+                lock_time: 0,
+                segwit_flag: false,
+            }
+        }
+
+        fn sequences(parameters: &BitcoinTransactionParameters<N>) -> Vec<u32> {
+            parameters.inputs.iter().map(|input| input.to_sequence()).collect()
+        }
+
+        #[test]
+        fn test_lock_time() {
+            assert_eq!(LockTime::Height(0), LockTime::from_height(0).unwrap());
+            assert_eq!(
+                LockTime::Height(LOCK_TIME_THRESHOLD - 1),
+                LockTime::from_height(LOCK_TIME_THRESHOLD - 1).unwrap()
+            );
+            assert!(LockTime::from_height(LOCK_TIME_THRESHOLD).is_err());
+            assert_eq!(
+                LockTime::Time(LOCK_TIME_THRESHOLD),
+                LockTime::from_time(LOCK_TIME_THRESHOLD).unwrap()
+            );
+            assert!(LockTime::from_time(LOCK_TIME_THRESHOLD - 1).is_err());
+
+            for lock_time in [0, 840_000, LOCK_TIME_THRESHOLD, 1_700_000_000].iter() {
+                assert_eq!(*lock_time, LockTime::from_consensus(*lock_time).to_consensus());
+            }
+        }
+
+        #[test]
+        fn test_relative_lock_time() {
+            assert_eq!(
+                RelativeLockTime::Intervals(0),
+                RelativeLockTime::from_seconds(0).unwrap()
+            );
+            assert_eq!(
+                RelativeLockTime::Intervals(1),
+                RelativeLockTime::from_seconds(1).unwrap()
+            );
+            assert_eq!(
+                RelativeLockTime::Intervals(1),
+                RelativeLockTime::from_seconds(512).unwrap()
+            );
+            assert_eq!(
+                RelativeLockTime::Intervals(2),
+                RelativeLockTime::from_seconds(513).unwrap()
+            );
+            assert_eq!(
+                RelativeLockTime::Intervals(0xffff),
+                RelativeLockTime::from_seconds(512 * 0xffff).unwrap()
+            );
+            assert!(RelativeLockTime::from_seconds(512 * 0xffff + 1).is_err());
+
+            assert_eq!(144, RelativeLockTime::Blocks(144).to_sequence());
+            assert_eq!(0x0040_0001, RelativeLockTime::Intervals(1).to_sequence());
+            for lock_time in [RelativeLockTime::Blocks(144), RelativeLockTime::Intervals(0xffff)].iter() {
+                assert_eq!(
+                    Some(*lock_time),
+                    RelativeLockTime::from_sequence(lock_time.to_sequence())
+                );
+            }
+            for sequence in [SEQUENCE_FINAL, SEQUENCE_ENABLE_LOCK_TIME, SEQUENCE_ENABLE_RBF].iter() {
+                assert_eq!(None, RelativeLockTime::from_sequence(*sequence));
+            }
+        }
+
+        #[test]
+        fn test_transaction_lock_times() {
+            let parameters = parameters(2);
+            assert_eq!(vec![SEQUENCE_FINAL; 2], sequences(&parameters));
+            assert!(!parameters.is_replaceable());
+
+            // The lock time is enabled by a non-final sequence number, without signaling replace-by-fee
+            let locked = parameters
+                .clone()
+                .with_lock_time(LockTime::from_height(840_000).unwrap());
+            assert_eq!(840_000, locked.lock_time);
+            assert_eq!(vec![SEQUENCE_ENABLE_LOCK_TIME; 2], sequences(&locked));
+            assert!(!locked.is_replaceable());
+
+            let replaceable = locked.with_replace_by_fee();
+            assert_eq!(vec![SEQUENCE_ENABLE_RBF; 2], sequences(&replaceable));
+            assert!(replaceable.inputs.iter().all(|input| input.is_replaceable()));
+            let transaction = BitcoinTransaction::<N>::new(&replaceable).unwrap();
+            let transaction = hex::encode(transaction.to_transaction_bytes().unwrap());
+            assert!(transaction.contains("fdffffff"));
+            assert!(transaction.ends_with(&hex::encode(840_000u32.to_le_bytes())));
+
+            // A relative lock time signals replace-by-fee, and is kept when signaling it on the other inputs
+            let relative = parameters
+                .clone()
+                .with_relative_lock_time(1, RelativeLockTime::Blocks(144))
+                .unwrap();
+            assert_eq!(
+                Some(RelativeLockTime::Blocks(144)),
+                relative.inputs[1].relative_lock_time()
+            );
+            assert_eq!(None, relative.inputs[0].relative_lock_time());
+            assert!(relative.is_replaceable());
+            assert!(!relative.inputs[0].is_replaceable());
+            let relative = relative.with_replace_by_fee();
+            assert_eq!(vec![SEQUENCE_ENABLE_RBF, 144], sequences(&relative));
+
+            let sequenced = parameters.clone().with_sequence(0, 0x0040_0002).unwrap();
+            assert_eq!(
+                Some(RelativeLockTime::Intervals(2)),
+                sequenced.inputs[0].relative_lock_time()
+            );
+            assert!(parameters.clone().with_sequence(2, 0).is_err());
+
+            // Relative lock times are only enforced in transactions of version 2 or greater
+            assert!(self::parameters(1)
+                .with_relative_lock_time(0, RelativeLockTime::Blocks(144))
+                .is_err());
+        }
+    }
+
+    mod test_op_return_outputs {
+        use super::*;
+        type N = Mainnet;
+
+        const TRANSACTION_ID: &str = "61d520ccb74288c96bc1a2b20ea1c0d5a704776dd0164a396efec3ea7040349d";
+        const OUTPUT_ADDRESS: &str = "3QDTHVyuJrHixUhhsdZXQ7M8P9MQngmw1P";
+
+        #[test]
+        fn test_op_return_output() {
+            let output = BitcoinTransactionOutput::op_return(b"hello").unwrap();
+            assert_eq!(BitcoinAmount(0), output.amount);
+            assert_eq!("6a0568656c6c6f", hex::encode(&output.script_pub_key));
+            assert_eq!(Some(b"hello".to_vec()), output.to_op_return_data());
+
+            // Data longer than 75 bytes is pushed with OP_PUSHDATA1
+            let data = [0xab; MAX_OP_RETURN_DATA_LENGTH];
+            let output = BitcoinTransactionOutput::op_return(&data).unwrap();
+            assert_eq!("6a4c50", hex::encode(&output.script_pub_key[..3]));
+            assert_eq!(Some(data.to_vec()), output.to_op_return_data());
+            assert!(BitcoinTransactionOutput::op_return(&[0xab; MAX_OP_RETURN_DATA_LENGTH + 1]).is_err());
+
+            let output = BitcoinTransactionOutput::from_script(&BitcoinScript::new(&[0x6a]), BitcoinAmount(0));
+            assert_eq!(Some(vec![]), output.to_op_return_data());
+            let address = BitcoinAddress::<N>::from_str(OUTPUT_ADDRESS).unwrap();
+            let output = BitcoinTransactionOutput::new(&address, BitcoinAmount(40000)).unwrap();
+            assert_eq!(None, output.to_op_return_data());
+        }
+
+        #[test]
+        fn test_op_return_transaction() {
+            let input = BitcoinTransactionInput::<N>::new(
+                hex::decode(TRANSACTION_ID).unwrap(),
+                0,
+                None,
+                None,
+                None,
+                None,
+                None,
+                SignatureHash::SIGHASH_ALL,
+            )
+            .unwrap();
+            let address = BitcoinAddress::<N>::from_str(OUTPUT_ADDRESS).unwrap();
+            let parameters = BitcoinTransactionParameters::<N> {
+                version: 2,
+                inputs: vec![input],
+                outputs: vec![BitcoinTransactionOutput::new(&address, BitcoinAmount(40000)).unwrap()],
+                lock_time: 0,
+                segwit_flag: false,
+            }
+            .with_data(b"timestamp")
+            .unwrap();
+            assert_eq!(2, parameters.outputs.len());
+
+            let transaction = BitcoinTransaction::<N>::new(&parameters).unwrap();
+            let transaction_bytes = transaction.to_transaction_bytes().unwrap();
+            let transaction = BitcoinTransaction::<N>::from_transaction_bytes(&transaction_bytes).unwrap();
+            assert_eq!(
+                Some(b"timestamp".to_vec()),
+                transaction.parameters.outputs[1].to_op_return_data()
+            );
+            assert!(parameters.with_data(&[0u8; MAX_OP_RETURN_DATA_LENGTH + 1]).is_err());
+        }
+    }
+
+    mod test_transaction_ids {
+        use super::*;
+        type N = Mainnet;
+
+        const PRIVATE_KEY: &str = "L5HiUByNV6D4anzT5aMhheZpG9oKdcvoPXjWJopEPiEzFisNTM7X";
+        const TRANSACTION_ID: &str = "61d520ccb74288c96bc1a2b20ea1c0d5a704776dd0164a396efec3ea7040349d";
+        const OUTPUT_ADDRESS: &str = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4";
+
+        fn transaction(private_key: &BitcoinPrivateKey<N>, formats: &[BitcoinFormat]) -> BitcoinTransaction<N> {
+            let inputs = formats
+                .iter()
+                .enumerate()
+                .map(|(vout, format)| {
+                    let redeem_script = match format {
+                        BitcoinFormat::P2SH_P2WPKH => Some(
+                            [
+                                vec![0x00, 0x14],
+                                hash160(
+                                    &private_key
+                                        .to_public_key()
+                                        .to_secp256k1_public_key()
+                                        .serialize_compressed(),
+                                ),
+                            ]
+                            .concat(),
+                        ),
+                        _ => None,
+                    };
+                    BitcoinTransactionInput::<N>::new(
+                        hex::decode(TRANSACTION_ID).unwrap(),
+                        vout as u32,
+                        Some(private_key.to_address(format).unwrap()),
+                        Some(BitcoinAmount(100_000)),
+                        redeem_script,
+                        None,
+                        None,
+                        SignatureHash::SIGHASH_ALL,
+                    )
+                    .unwrap()
+                })
+                .collect();
+            BitcoinTransaction::<N>::new(&BitcoinTransactionParameters {
+                version: 2,
+                inputs,
+                outputs: vec![BitcoinTransactionOutput::new(
+                    &BitcoinAddress::<N>::from_str(OUTPUT_ADDRESS).unwrap(),
+                    BitcoinAmount(50_000),
+                )
+                .unwrap()],
+                lock_time: 0,
+                segwit_flag: false,
+            })
+            .unwrap()
+        }
+
+        #[test]
+        fn test_segwit_transaction_ids() {
+            let private_key = BitcoinPrivateKey::<N>::from_str(PRIVATE_KEY).unwrap();
+            let transaction = transaction(
+                &private_key,
+                &[BitcoinFormat::Bech32, BitcoinFormat::P2SH_P2WPKH, BitcoinFormat::P2TR],
+            );
+
+            // The transaction id of SegWit spends is known before signing, and the witness transaction id after
+            let txid = transaction.txid().unwrap();
+            assert!(transaction.wtxid().is_err());
+            let signed = transaction.sign(&private_key).unwrap();
+            let transaction_id = signed.to_transaction_id().unwrap();
+            assert_eq!(txid, signed.txid().unwrap());
+            assert_eq!(transaction_id.to_string(), hex::encode(&txid));
+            assert_eq!(
+                transaction_id.to_witness_transaction_id(),
+                hex::encode(signed.wtxid().unwrap())
+            );
+            assert_ne!(txid, signed.wtxid().unwrap());
+
+            // The outputs can be spent before the transaction is broadcast
+            let child =
+                BitcoinTransactionInput::<N>::new(txid, 0, None, None, None, None, None, SignatureHash::SIGHASH_ALL)
+                    .unwrap();
+            let mut reverse_transaction_id = child.outpoint.reverse_transaction_id.clone();
+            reverse_transaction_id.reverse();
+            assert_eq!(transaction_id.to_string(), hex::encode(reverse_transaction_id));
+        }
+
+        #[test]
+        fn test_legacy_transaction_ids() {
+            let private_key = BitcoinPrivateKey::<N>::from_str(PRIVATE_KEY).unwrap();
+            let transaction = transaction(&private_key, &[BitcoinFormat::Bech32, BitcoinFormat::P2PKH]);
+
+            // The script signature of a P2PKH input changes the transaction id
+            assert!(transaction.txid().is_err());
+
+            let signed = transaction.sign(&private_key).unwrap();
+            assert_eq!(
+                signed.to_transaction_id().unwrap().to_string(),
+                hex::encode(signed.txid().unwrap())
+            );
+
+            // A transaction without witnesses has the same witness transaction id
+            let transaction = self::transaction(&private_key, &[BitcoinFormat::P2PKH])
+                .sign(&private_key)
+                .unwrap();
+            assert_eq!(transaction.txid().unwrap(), transaction.wtxid().unwrap());
+        }
+    }
+
+    mod test_fee_estimation {
+        use super::*;
+        type N = Mainnet;
+
+        const PRIVATE_KEY: &str = "L5HiUByNV6D4anzT5aMhheZpG9oKdcvoPXjWJopEPiEzFisNTM7X";
+        const TRANSACTION_ID: &str = "61d520ccb74288c96bc1a2b20ea1c0d5a704776dd0164a396efec3ea7040349d";
+        const BECH32_ADDRESS: &str = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4";
+        const P2PKH_ADDRESS: &str = "1NoZQSmjYHUZMbqLerwmT4xfe8A6mAo8TT";
+        const P2TR_ADDRESS: &str = "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr";
+        const P2WSH_ADDRESS: &str = "bc1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3qccfmv3";
+
+        fn address(address: &str) -> BitcoinAddress<N> {
+            BitcoinAddress::<N>::from_str(address).unwrap()
+        }
+
+        fn input(address: &BitcoinAddress<N>, amount: i64, sighash: SignatureHash) -> BitcoinTransactionInput<N> {
+            BitcoinTransactionInput::<N>::new(
+                hex::decode(TRANSACTION_ID).unwrap(),
+                0,
+                Some(address.clone()),
+                Some(BitcoinAmount(amount)),
+                None,
+                None,
+                None,
+                sighash,
+            )
+            .unwrap()
+        }
+
+        fn parameters(inputs: Vec<BitcoinTransactionInput<N>>, amount: i64) -> BitcoinTransactionParameters<N> {
+            BitcoinTransactionParameters::<N> {
+                version: 2,
+                inputs,
+                outputs: vec![BitcoinTransactionOutput::new(&address(BECH32_ADDRESS), BitcoinAmount(amount)).unwrap()],
+                lock_time: 0,
+                segwit_flag: false,
+            }
+        }
+
+        #[test]
+        fn test_weights() {
+            assert_eq!(Some(592), input_weight(&BitcoinFormat::P2PKH));
+            assert_eq!(Some(364), input_weight(&BitcoinFormat::P2SH_P2WPKH));
+            assert_eq!(Some(272), input_weight(&BitcoinFormat::Bech32));
+            assert_eq!(Some(230), input_weight(&BitcoinFormat::P2TR));
+            assert_eq!(None, input_weight(&BitcoinFormat::P2WSH));
+
+            let output = BitcoinTransactionOutput::new(&address(BECH32_ADDRESS), BitcoinAmount(0)).unwrap();
+            assert_eq!(124, output.weight().unwrap());
+            assert_eq!(BitcoinAmount(1_405), weight_to_fee(562, 10));
+        }
+
+        #[test]
+        fn test_transaction_sizes() {
+            let size = BitcoinTransactionSize::new(10, 0) + BitcoinTransactionSize::new(31, 108);
+            assert_eq!(BitcoinTransactionSize::new(41, 108), size);
+            assert_eq!(149, size.size());
+            assert_eq!(272, size.weight());
+            assert_eq!(68, size.virtual_size());
+            assert_eq!(input_size(&BitcoinFormat::Bech32), Some(size));
+
+            // A Taproot script path spend of a signature, a 34-byte script and a 33-byte control block
+            let size = BitcoinTransactionSize::input(0, &[64, 34, 33]).unwrap();
+            assert_eq!(BitcoinTransactionSize::new(41, 135), size);
+            assert_eq!(
+                BitcoinTransactionSize::new(41, 0),
+                BitcoinTransactionSize::input(0, &[]).unwrap()
+            );
+        }
+
+        #[test]
+        fn test_dust_thresholds() {
+            assert_eq!(BitcoinAmount(546), dust_threshold(&address(P2PKH_ADDRESS)).unwrap());
+            assert_eq!(BitcoinAmount(294), dust_threshold(&address(BECH32_ADDRESS)).unwrap());
+            assert_eq!(BitcoinAmount(330), dust_threshold(&address(P2WSH_ADDRESS)).unwrap());
+            assert_eq!(BitcoinAmount(330), dust_threshold(&address(P2TR_ADDRESS)).unwrap());
+
+            let output = BitcoinTransactionOutput::new(&address(P2PKH_ADDRESS), BitcoinAmount(545)).unwrap();
+            assert!(output.is_dust().unwrap());
+            let output = BitcoinTransactionOutput::new(&address(P2PKH_ADDRESS), BitcoinAmount(546)).unwrap();
+            assert!(!output.is_dust().unwrap());
+            let output = BitcoinTransactionOutput::op_return(b"wagyu").unwrap();
+            assert_eq!(BitcoinAmount(0), output.dust_threshold().unwrap());
+            assert!(!output.is_dust().unwrap());
+        }
+
+        #[test]
+        fn test_estimate_weight() {
+            // overhead (40) + marker and flag (2) + input (272) + output (124)
+            let parameters = parameters(
+                vec![input(&address(BECH32_ADDRESS), 100_000, SignatureHash::SIGHASH_ALL)],
+                50_000,
+            );
+            let size = parameters.estimate_size().unwrap();
+            assert_eq!(BitcoinTransactionSize::new(82, 110), size);
+            assert_eq!(192, size.size());
+            assert_eq!(438, parameters.estimate_weight().unwrap());
+            assert_eq!(110, parameters.estimate_virtual_size().unwrap());
+            assert_eq!(BitcoinAmount(1_095), parameters.estimate_fee(10).unwrap());
+            assert_eq!(BitcoinAmount(50_000), parameters.fee().unwrap());
+
+            // A Taproot signature with an explicit signature hash type is one byte longer
+            let taproot = input(&address(P2TR_ADDRESS), 100_000, SignatureHash::SIGHASH_DEFAULT);
+            assert_eq!(230, taproot.estimate_weight().unwrap());
+            let taproot = input(&address(P2TR_ADDRESS), 100_000, SignatureHash::SIGHASH_ALL);
+            assert_eq!(231, taproot.estimate_weight().unwrap());
+
+            // The witness of a P2WSH input depends on its witness script
+            let mut p2wsh = input(&address(BECH32_ADDRESS), 100_000, SignatureHash::SIGHASH_ALL);
+            p2wsh.outpoint.address = Some(address(P2WSH_ADDRESS));
+            assert!(p2wsh.estimate_weight().is_err());
+        }
+
+        #[test]
+        fn test_estimate_signed_weight() {
+            let private_key = BitcoinPrivateKey::<N>::from_str(PRIVATE_KEY).unwrap();
+            let inputs = [BitcoinFormat::P2PKH, BitcoinFormat::Bech32]
+                .iter()
+                .map(|format| {
+                    let address = private_key.to_address(format).unwrap();
+                    input(&address, 100_000, SignatureHash::SIGHASH_ALL)
+                })
+                .collect();
+            let parameters = parameters(inputs, 150_000);
+            let estimate = parameters.estimate_weight().unwrap();
+
+            let transaction = BitcoinTransaction::<N>::new(&parameters).unwrap();
+            let transaction = transaction.sign(&private_key).unwrap().sign(&private_key).unwrap();
+            let weight = (transaction.to_transaction_bytes_without_witness().unwrap().len() * 3
+                + transaction.to_transaction_bytes().unwrap().len()) as u64;
+
+            // Signatures are estimated at their maximum length
+            assert!(estimate >= weight && estimate - weight <= 8);
+            assert_eq!(weight, transaction.parameters.estimate_weight().unwrap());
+            assert_eq!(
+                transaction.to_transaction_bytes().unwrap().len() as u64,
+                transaction.parameters.estimate_size().unwrap().size()
+            );
+        }
+
+        #[test]
+        fn test_with_change() {
+            let change_address = address(BECH32_ADDRESS);
+            let inputs = vec![input(&address(BECH32_ADDRESS), 100_000, SignatureHash::SIGHASH_ALL)];
+            let parameters = parameters(inputs, 50_000).with_change(&change_address, 10).unwrap();
+            assert_eq!(2, parameters.outputs.len());
+            assert_eq!(BitcoinAmount(48_595), parameters.outputs[1].amount);
+            assert_eq!(BitcoinAmount(1_405), parameters.fee().unwrap());
+            assert_eq!(parameters.estimate_fee(10).unwrap(), parameters.fee().unwrap());
+
+            let inputs = vec![input(&address(BECH32_ADDRESS), 51_500, SignatureHash::SIGHASH_ALL)];
+            match self::parameters(inputs, 50_000).with_change(&change_address, 10) {
+                Err(TransactionError::DustChange(95, 294)) => (),
+                result => panic!("unexpected result {:?}", result),
+            };
+
+            let inputs = vec![input(&address(BECH32_ADDRESS), 50_000, SignatureHash::SIGHASH_ALL)];
+            match self::parameters(inputs, 50_000).with_change(&change_address, 10) {
+                Err(TransactionError::InsufficientFunds(50_000, 51_405)) => (),
+                result => panic!("unexpected result {:?}", result),
+            };
+
+            let mut inputs = vec![input(&address(BECH32_ADDRESS), 100_000, SignatureHash::SIGHASH_ALL)];
+            inputs[0].outpoint.amount = None;
+            assert!(self::parameters(inputs, 50_000)
+                .with_change(&change_address, 10)
+                .is_err());
+        }
+    }
+
     mod test_helper_functions {
         use super::*;
 
@@ -0,0 +1,518 @@
+//!
+//! Transaction Exchange
+//!
+//! This module contains a versioned JSON format for unsigned and partially signed transactions,
+//! which carries the UTXO data, key origins, and metadata needed to sign each input. A watch-only
+//! wallet on an online machine exports the transaction, and an air-gapped machine imports and signs it.
+//!
+
+use crate::address::BitcoinAddress;
+use crate::amount::BitcoinAmount;
+use crate::extended_private_key::BitcoinExtendedPrivateKey;
+use crate::network::BitcoinNetwork;
+use crate::private_key::BitcoinPrivateKey;
+use crate::script::BitcoinScript;
+use crate::taproot::{TaprootControlBlock, TaprootLeaf, TaprootSpend};
+use crate::transaction::{
+    create_script_pub_key, read_variable_length_integer, variable_length_integer, BitcoinTransaction,
+    BitcoinTransactionInput, BitcoinTransactionOutput, BitcoinTransactionParameters, SignatureHash,
+};
+use wagyu_model::no_std::*;
+use wagyu_model::{ExtendedPrivateKey, KeyOrigin, Transaction, TransactionError};
+
+use core::{fmt, marker::PhantomData, str::FromStr};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// The version of the transaction exchange format
+pub const TRANSACTION_EXCHANGE_VERSION: u32 = 1;
+
+/// Represents an input of an exchanged transaction, with the UTXO it spends and its signatures, if any
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct ExchangeInput {
+    /// The transaction id of the UTXO, in RPC byte order
+    txid: String,
+    /// The output index of the UTXO
+    vout: u32,
+    /// The amount of the UTXO (in Satoshi)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    amount: Option<i64>,
+    /// The address of the UTXO, absent for P2PK and bare multisig outputs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    address: Option<String>,
+    /// The script public key of the UTXO
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    script_pub_key: Option<String>,
+    /// The redeem script of a P2SH input, or the witness script of a P2WSH input
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    redeem_script: Option<String>,
+    /// The sequence number
+    sequence: u32,
+    /// The signature hash type of the signatures of the input
+    sighash: String,
+    /// The origins of the keys which sign the input
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    key_origins: Vec<KeyOrigin>,
+    /// The Merkle root of the script tree of a Taproot key path spend
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    taproot_merkle_root: Option<String>,
+    /// The leaf script of a Taproot script path spend
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    taproot_leaf_script: Option<String>,
+    /// The control block of a Taproot script path spend
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    taproot_control_block: Option<String>,
+    /// The script signature
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    script_sig: Option<String>,
+    /// The elements of the witness stack
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    witness: Vec<String>,
+    /// If true, the input is fully signed
+    #[serde(default)]
+    signed: bool,
+}
+
+/// Represents an output of an exchanged transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct ExchangeOutput {
+    /// The amount (in Satoshi)
+    amount: i64,
+    /// The script public key
+    script_pub_key: String,
+    /// The address of the script public key, for review before signing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    address: Option<String>,
+}
+
+/// Represents an unsigned or partially signed Bitcoin transaction in the exchange format
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct BitcoinTransactionExchange<N: BitcoinNetwork> {
+    /// The version of the exchange format
+    format_version: u32,
+    /// The name of the network of the transaction
+    network: String,
+    /// The version of the transaction
+    version: u32,
+    /// The lock time of the transaction
+    lock_time: u32,
+    /// The inputs of the transaction
+    inputs: Vec<ExchangeInput>,
+    /// The outputs of the transaction
+    outputs: Vec<ExchangeOutput>,
+    /// The metadata of the transaction, e.g. a label or the wallet which created it
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    metadata: Map<String, Value>,
+    #[serde(skip)]
+    _network: PhantomData<N>,
+}
+
+impl<N: BitcoinNetwork> BitcoinTransactionExchange<N> {
+    /// Returns the exchange format of the given transaction, without key origins or metadata.
+    pub fn new(transaction: &BitcoinTransaction<N>) -> Result<Self, TransactionError> {
+        let parameters = transaction.parameters();
+
+        let mut inputs = vec![];
+        for input in &parameters.inputs {
+            let mut txid = input.outpoint.reverse_transaction_id.clone();
+            txid.reverse();
+
+            let (taproot_merkle_root, taproot_leaf_script, taproot_control_block) = match &input.taproot_spend {
+                Some(TaprootSpend::KeyPath(merkle_root)) => (merkle_root.map(hex::encode), None, None),
+                Some(TaprootSpend::ScriptPath(leaf, control_block)) => (
+                    None,
+                    Some(hex::encode(leaf.script())),
+                    Some(hex::encode(control_block.to_bytes())),
+                ),
+                None => (None, None, None),
+            };
+
+            // The witness of an input is stored with the length of each element
+            let mut witness = vec![];
+            for element in &input.witnesses {
+                let mut reader = &element[..];
+                read_variable_length_integer(&mut reader)?;
+                witness.push(hex::encode(reader));
+            }
+
+            inputs.push(ExchangeInput {
+                txid: hex::encode(txid),
+                vout: input.outpoint.index,
+                amount: input.outpoint.amount.map(|amount| amount.0),
+                address: input.outpoint.address.as_ref().map(|address| address.to_string()),
+                script_pub_key: input.outpoint.script_pub_key.as_ref().map(hex::encode),
+                redeem_script: input.outpoint.redeem_script.as_ref().map(hex::encode),
+                sequence: input.to_sequence(),
+                sighash: input.sighash_code.to_string(),
+                key_origins: vec![],
+                taproot_merkle_root,
+                taproot_leaf_script,
+                taproot_control_block,
+                script_sig: match input.script_sig.is_empty() {
+                    true => None,
+                    false => Some(hex::encode(&input.script_sig)),
+                },
+                witness,
+                signed: input.is_signed,
+            });
+        }
+
+        let outputs = parameters
+            .outputs
+            .iter()
+            .map(|output| ExchangeOutput {
+                amount: output.amount.0,
+                script_pub_key: hex::encode(&output.script_pub_key),
+                address: BitcoinAddress::<N>::from_script_pub_key(&BitcoinScript::new(&output.script_pub_key))
+                    .ok()
+                    .map(|address| address.to_string()),
+            })
+            .collect();
+
+        Ok(Self {
+            format_version: TRANSACTION_EXCHANGE_VERSION,
+            network: N::NAME.into(),
+            version: parameters.version,
+            lock_time: parameters.lock_time,
+            inputs,
+            outputs,
+            metadata: Map::new(),
+            _network: PhantomData,
+        })
+    }
+
+    /// Returns the exchanged transaction with the given origin of a key which signs the input at the given index.
+    pub fn with_key_origin(mut self, vin: usize, origin: KeyOrigin) -> Result<Self, TransactionError> {
+        match self.inputs.get_mut(vin) {
+            Some(input) => match input.key_origins.contains(&origin) {
+                true => Ok(self),
+                false => {
+                    input.key_origins.push(origin);
+                    Ok(self)
+                }
+            },
+            None => Err(TransactionError::InvalidInputs(format!("input {}", vin))),
+        }
+    }
+
+    /// Returns the exchanged transaction with the given metadata value.
+    pub fn with_metadata(mut self, key: &str, value: Value) -> Self {
+        self.metadata.insert(key.into(), value);
+        self
+    }
+
+    /// Returns the metadata of the exchanged transaction.
+    pub fn metadata(&self) -> &Map<String, Value> {
+        &self.metadata
+    }
+
+    /// Returns the origins of the keys which sign the input at the given index.
+    pub fn key_origins(&self, vin: usize) -> Result<&[KeyOrigin], TransactionError> {
+        match self.inputs.get(vin) {
+            Some(input) => Ok(&input.key_origins),
+            None => Err(TransactionError::InvalidInputs(format!("input {}", vin))),
+        }
+    }
+
+    /// Returns `true` if every input of the exchanged transaction is signed.
+    pub fn is_signed(&self) -> bool {
+        self.inputs.iter().all(|input| input.signed)
+    }
+
+    /// Returns the transaction of the exchange format.
+    pub fn to_transaction(&self) -> Result<BitcoinTransaction<N>, TransactionError> {
+        let mut inputs = vec![];
+        for input in &self.inputs {
+            let address = match &input.address {
+                Some(address) => Some(BitcoinAddress::<N>::from_str(address)?),
+                None => None,
+            };
+            let mut transaction_input = BitcoinTransactionInput::<N>::new(
+                hex::decode(&input.txid)?,
+                input.vout,
+                address,
+                input.amount.map(BitcoinAmount),
+                input.redeem_script.as_ref().map(hex::decode).transpose()?,
+                input.script_pub_key.as_ref().map(hex::decode).transpose()?,
+                Some(input.sequence.to_le_bytes().to_vec()),
+                SignatureHash::from_str(&input.sighash)?,
+            )?;
+
+            transaction_input.taproot_spend = match (
+                &input.taproot_merkle_root,
+                &input.taproot_leaf_script,
+                &input.taproot_control_block,
+            ) {
+                (None, None, None) => None,
+                (Some(merkle_root), None, None) => {
+                    let merkle_root = hex::decode(merkle_root)?;
+                    if merkle_root.len() != 32 {
+                        return Err(TransactionError::InvalidInputs("Taproot Merkle root".into()));
+                    }
+                    let mut root = [0u8; 32];
+                    root.copy_from_slice(&merkle_root);
+                    Some(TaprootSpend::KeyPath(Some(root)))
+                }
+                (None, Some(leaf_script), Some(control_block)) => {
+                    let control_block = TaprootControlBlock::new(&hex::decode(control_block)?)?;
+                    let leaf = TaprootLeaf::new(control_block.leaf_version(), &hex::decode(leaf_script)?)?;
+                    Some(TaprootSpend::ScriptPath(leaf, control_block))
+                }
+                _ => return Err(TransactionError::InvalidInputs("Taproot spend".into())),
+            };
+
+            if let Some(script_sig) = &input.script_sig {
+                transaction_input.script_sig = hex::decode(script_sig)?;
+            }
+            for element in &input.witness {
+                let element = hex::decode(element)?;
+                transaction_input
+                    .witnesses
+                    .push([variable_length_integer(element.len() as u64)?, element].concat());
+            }
+            transaction_input.is_signed = input.signed;
+            inputs.push(transaction_input);
+        }
+
+        let mut outputs = vec![];
+        for output in &self.outputs {
+            let script_pub_key = hex::decode(&output.script_pub_key)?;
+            // The address shown for review must be that of the script public key which is signed
+            if let Some(address) = &output.address {
+                if create_script_pub_key(&BitcoinAddress::<N>::from_str(address)?)? != script_pub_key {
+                    return Err(TransactionError::InvalidOutputAddress(address.clone()));
+                }
+            }
+            outputs.push(BitcoinTransactionOutput {
+                amount: BitcoinAmount(output.amount),
+                script_pub_key,
+            });
+        }
+
+        let segwit_flag = inputs.iter().any(|input| !input.witnesses.is_empty());
+        BitcoinTransaction::<N>::new(&BitcoinTransactionParameters {
+            version: self.version,
+            inputs,
+            outputs,
+            lock_time: self.lock_time,
+            segwit_flag,
+        })
+    }
+
+    /// Signs the inputs the private key can sign for, and returns the exchanged transaction
+    /// with the signatures.
+    pub fn sign(&self, private_key: &BitcoinPrivateKey<N>) -> Result<Self, TransactionError> {
+        self.update(&self.to_transaction()?.sign(private_key)?)
+    }
+
+    /// Signs each input with the keys derived from the given master key along the key origins
+    /// of the input with the fingerprint of the master key, and returns the exchanged transaction
+    /// with the signatures.
+    pub fn sign_with_extended_private_key(
+        &self,
+        master_extended_private_key: &BitcoinExtendedPrivateKey<N>,
+    ) -> Result<Self, TransactionError> {
+        let fingerprint = master_extended_private_key.fingerprint();
+        let mut transaction = self.to_transaction()?;
+        for input in &self.inputs {
+            for origin in input
+                .key_origins
+                .iter()
+                .filter(|origin| origin.fingerprint() == fingerprint)
+            {
+                let private_key = master_extended_private_key
+                    .derive_relative(origin.path())?
+                    .to_private_key();
+                transaction = transaction.sign(&private_key)?;
+            }
+        }
+        self.update(&transaction)
+    }
+
+    /// Returns the exchanged transaction with the signatures of the given transaction,
+    /// keeping its key origins and metadata.
+    fn update(&self, transaction: &BitcoinTransaction<N>) -> Result<Self, TransactionError> {
+        let mut exchange = Self::new(transaction)?;
+        exchange
+            .inputs
+            .iter_mut()
+            .zip(&self.inputs)
+            .for_each(|(input, other)| input.key_origins = other.key_origins.clone());
+        exchange.metadata = self.metadata.clone();
+        Ok(exchange)
+    }
+}
+
+impl<N: BitcoinNetwork> FromStr for BitcoinTransactionExchange<N> {
+    type Err = TransactionError;
+
+    /// Returns the exchanged transaction of the given JSON, if its format version and network are supported.
+    fn from_str(json: &str) -> Result<Self, Self::Err> {
+        let exchange: Self = serde_json::from_str(json)?;
+        if exchange.format_version != TRANSACTION_EXCHANGE_VERSION {
+            return Err(TransactionError::UnsupportedFormatVersion(exchange.format_version));
+        }
+        if exchange.network != N::NAME {
+            return Err(TransactionError::InvalidNetwork(N::NAME.into(), exchange.network));
+        }
+        // The transaction must be valid before it is handed to a signer
+        exchange.to_transaction()?;
+        Ok(exchange)
+    }
+}
+
+impl<N: BitcoinNetwork> fmt::Display for BitcoinTransactionExchange<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match serde_json::to_string_pretty(self) {
+            Ok(json) => write!(f, "{}", json),
+            Err(_) => Err(fmt::Error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::derivation_path::BitcoinDerivationPath;
+    use crate::format::BitcoinFormat;
+    use crate::network::*;
+    use wagyu_model::{ExtendedPrivateKey, PrivateKey};
+
+    use serde_json::json;
+
+    type N = Mainnet;
+
+    const MASTER_EXTENDED_PRIVATE_KEY: &str = "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi";
+    const TRANSACTION_ID: &str = "61d520ccb74288c96bc1a2b20ea1c0d5a704776dd0164a396efec3ea7040349d";
+    const DESTINATION: &str = "1NoZQSmjYHUZMbqLerwmT4xfe8A6mAo8TT";
+
+    // Returns the master key and the unsigned transaction spending its Bech32 and P2PKH addresses
+    // at m/84h/0h/0h/0/0 and m/44h/0h/0h/0/0 with their key origins
+    fn exchange() -> (BitcoinExtendedPrivateKey<N>, BitcoinTransactionExchange<N>) {
+        let master = BitcoinExtendedPrivateKey::<N>::from_str(MASTER_EXTENDED_PRIVATE_KEY).unwrap();
+        let paths = [
+            ("m/84'/0'/0'/0/0", BitcoinFormat::Bech32),
+            ("m/44'/0'/0'/0/0", BitcoinFormat::P2PKH),
+        ];
+
+        let mut inputs = vec![];
+        let mut origins = vec![];
+        for (vin, (path, format)) in paths.iter().enumerate() {
+            let path = BitcoinDerivationPath::<N>::from_str(path).unwrap();
+            let private_key = master.derive(&path).unwrap().to_private_key();
+            inputs.push(
+                BitcoinTransactionInput::<N>::new(
+                    hex::decode(TRANSACTION_ID).unwrap(),
+                    vin as u32,
+                    Some(private_key.to_address(format).unwrap()),
+                    Some(BitcoinAmount(100_000)),
+                    None,
+                    None,
+                    None,
+                    SignatureHash::SIGHASH_ALL,
+                )
+                .unwrap(),
+            );
+            origins.push(KeyOrigin::new(master.fingerprint(), &path).unwrap());
+        }
+        let parameters = BitcoinTransactionParameters::<N> {
+            version: 2,
+            inputs,
+            outputs: vec![BitcoinTransactionOutput::new(
+                &BitcoinAddress::<N>::from_str(DESTINATION).unwrap(),
+                BitcoinAmount(190_000),
+            )
+            .unwrap()],
+            lock_time: 0,
+            segwit_flag: false,
+        }
+        .with_replace_by_fee();
+
+        let mut exchange = BitcoinTransactionExchange::<N>::new(&BitcoinTransaction::<N>::new(&parameters).unwrap())
+            .unwrap()
+            .with_metadata("label", json!("rent"));
+        for (vin, origin) in origins.into_iter().enumerate() {
+            exchange = exchange.with_key_origin(vin, origin).unwrap();
+        }
+        (master, exchange)
+    }
+
+    #[test]
+    fn round_trip() {
+        let (_, exchange) = exchange();
+        assert!(!exchange.is_signed());
+
+        let json = exchange.to_string();
+        let value: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(json!(1), value["format_version"]);
+        assert_eq!(json!("mainnet"), value["network"]);
+        assert_eq!(json!(TRANSACTION_ID), value["inputs"][0]["txid"]);
+        assert_eq!(json!(100_000), value["inputs"][0]["amount"]);
+        assert_eq!(json!("SIGHASH_ALL"), value["inputs"][0]["sighash"]);
+        assert_eq!(json!(DESTINATION), value["outputs"][0]["address"]);
+        assert_eq!(json!("rent"), value["metadata"]["label"]);
+
+        let imported = BitcoinTransactionExchange::<N>::from_str(&json).unwrap();
+        assert_eq!(exchange, imported);
+        assert_eq!(exchange.to_transaction().unwrap(), imported.to_transaction().unwrap());
+        assert_eq!(1, imported.key_origins(1).unwrap().len());
+    }
+
+    #[test]
+    fn sign_air_gapped() {
+        let (master, exchange) = exchange();
+        let json = exchange.to_string();
+
+        // The air-gapped machine signs the first input with its private key, then the rest with the master key
+        let imported = BitcoinTransactionExchange::<N>::from_str(&json).unwrap();
+        let path = BitcoinDerivationPath::<N>::from_str("m/84'/0'/0'/0/0").unwrap();
+        let private_key = master.derive(&path).unwrap().to_private_key();
+        let partial = imported.sign(&private_key).unwrap();
+        assert!(!partial.is_signed());
+        assert!(partial.inputs[0].signed);
+        assert!(!partial.inputs[0].witness.is_empty());
+        assert_eq!(exchange.metadata(), partial.metadata());
+
+        let partial = BitcoinTransactionExchange::<N>::from_str(&partial.to_string()).unwrap();
+        let signed = partial.sign_with_extended_private_key(&master).unwrap();
+        assert!(signed.is_signed());
+        assert!(signed.inputs[1].script_sig.is_some());
+
+        // The exchanged signatures are those of the transaction signed at once
+        let mut expected = exchange.to_transaction().unwrap();
+        for path in ["m/84'/0'/0'/0/0", "m/44'/0'/0'/0/0"].iter() {
+            let path = BitcoinDerivationPath::<N>::from_str(path).unwrap();
+            expected = expected.sign(&master.derive(&path).unwrap().to_private_key()).unwrap();
+        }
+        let transaction = signed.to_transaction().unwrap();
+        assert_eq!(
+            expected.to_transaction_bytes().unwrap(),
+            transaction.to_transaction_bytes().unwrap()
+        );
+    }
+
+    #[test]
+    fn invalid_exchanges() {
+        let (_, exchange) = exchange();
+        let mut value: Value = serde_json::from_str(&exchange.to_string()).unwrap();
+
+        let mut version = value.clone();
+        version["format_version"] = json!(2);
+        match BitcoinTransactionExchange::<N>::from_str(&version.to_string()) {
+            Err(TransactionError::UnsupportedFormatVersion(2)) => (),
+            result => panic!("unexpected result {:?}", result),
+        };
+
+        assert!(BitcoinTransactionExchange::<Testnet>::from_str(&value.to_string()).is_err());
+
+        // An output address which is not that of its script public key is rejected
+        let mut output = value.clone();
+        output["outputs"][0]["address"] = json!("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+        assert!(BitcoinTransactionExchange::<N>::from_str(&output.to_string()).is_err());
+
+        value["inputs"][0]["txid"] = json!("00");
+        assert!(BitcoinTransactionExchange::<N>::from_str(&value.to_string()).is_err());
+        assert!(exchange.key_origins(2).is_err());
+    }
+}
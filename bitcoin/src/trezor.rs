@@ -0,0 +1,1442 @@
+//!
+//! Trezor
+//!
+//! This module contains a client of Trezor devices, over USB HID or over Trezor Bridge as used by Trezor
+//! Connect, which displays the addresses derived by wagyu on the device for verification and signs the
+//! transactions and PSBTs built by wagyu. The device requests the inputs and outputs of the transaction,
+//! and those of the previous transactions of its inputs, one by one, and returns the signed transaction.
+//! https://docs.trezor.io/trezor-firmware/common/communication/index.html
+//! https://docs.trezor.io/trezor-firmware/common/communication/bitcoin-signing.html
+//!
+
+use crate::address::BitcoinAddress;
+use crate::derivation_path::BitcoinDerivationPath;
+use crate::extended_public_key::BitcoinExtendedPublicKey;
+use crate::format::BitcoinFormat;
+use crate::network::BitcoinNetwork;
+use crate::psbt::{BitcoinPsbt, PsbtError};
+use crate::script::BitcoinScript;
+use crate::transaction::{BitcoinTransaction, BitcoinTransactionOutput, BitcoinTransactionParameters, Opcode};
+use wagyu_model::no_std::*;
+use wagyu_model::{
+    AddressError, CrateError, DerivationPath, DerivationPathError, ExtendedPublicKey, ExtendedPublicKeyError,
+    Transaction, TransactionError,
+};
+
+use core::str::FromStr;
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::path::Path;
+
+/// The address of Trezor Bridge on the local machine
+pub const TREZOR_BRIDGE_ADDRESS: &str = "127.0.0.1:21325";
+/// The USB vendor and product ids of Trezor devices connected over HID
+pub const TREZOR_HID_IDS: (u16, u16) = (0x534c, 0x0001);
+
+const MESSAGE_INITIALIZE: u16 = 0;
+const MESSAGE_FAILURE: u16 = 3;
+const MESSAGE_GET_PUBLIC_KEY: u16 = 11;
+const MESSAGE_PUBLIC_KEY: u16 = 12;
+const MESSAGE_SIGN_TX: u16 = 15;
+const MESSAGE_FEATURES: u16 = 17;
+const MESSAGE_PIN_MATRIX_REQUEST: u16 = 18;
+const MESSAGE_TX_REQUEST: u16 = 21;
+const MESSAGE_TX_ACK: u16 = 22;
+const MESSAGE_BUTTON_REQUEST: u16 = 26;
+const MESSAGE_BUTTON_ACK: u16 = 27;
+const MESSAGE_GET_ADDRESS: u16 = 29;
+const MESSAGE_ADDRESS: u16 = 30;
+const MESSAGE_PASSPHRASE_REQUEST: u16 = 41;
+const MESSAGE_PASSPHRASE_ACK: u16 = 42;
+
+const FAILURE_ACTION_CANCELLED: u64 = 4;
+const FAILURE_PIN_CANCELLED: u64 = 6;
+
+const REQUEST_TX_INPUT: u64 = 0;
+const REQUEST_TX_OUTPUT: u64 = 1;
+const REQUEST_TX_META: u64 = 2;
+const REQUEST_TX_FINISHED: u64 = 3;
+
+const OUTPUT_PAY_TO_ADDRESS: u64 = 0;
+const OUTPUT_PAY_TO_OP_RETURN: u64 = 2;
+
+/// The size of the HID reports of Trezor devices
+const HID_PACKET_SIZE: usize = 64;
+
+/// Represents a connection to a Trezor device, which exchanges a message for the response message
+pub trait TrezorTransport {
+    /// Returns the type and protobuf encoding of the response of the device to the given message.
+    fn call(&mut self, message_type: u16, message: &[u8]) -> Result<(u16, Vec<u8>), TrezorError>;
+}
+
+impl<T: TrezorTransport + ?Sized> TrezorTransport for Box<T> {
+    fn call(&mut self, message_type: u16, message: &[u8]) -> Result<(u16, Vec<u8>), TrezorError> {
+        (**self).call(message_type, message)
+    }
+}
+
+/// Represents a Trezor device connected over USB HID, e.g. the hidraw device of Linux
+pub struct TrezorHid<D: Read + Write> {
+    device: D,
+}
+
+impl<D: Read + Write> TrezorHid<D> {
+    /// Returns a transport over the given HID device.
+    pub fn new(device: D) -> Self {
+        Self { device }
+    }
+}
+
+impl TrezorHid<File> {
+    /// Returns a transport over the HID device at the given path, e.g. `/dev/hidraw0`.
+    pub fn open_path<P: AsRef<Path>>(path: P) -> Result<Self, TrezorError> {
+        Ok(Self::new(OpenOptions::new().read(true).write(true).open(path)?))
+    }
+
+    /// Returns a transport over the first Trezor device connected over USB HID.
+    #[cfg(target_os = "linux")]
+    pub fn open() -> Result<Self, TrezorError> {
+        let hid_id = format!(":0000{:04X}:0000{:04X}", TREZOR_HID_IDS.0, TREZOR_HID_IDS.1);
+        let devices = match std::fs::read_dir("/sys/class/hidraw") {
+            Ok(devices) => devices,
+            Err(_) => return Err(TrezorError::DeviceNotFound),
+        };
+        let mut devices = devices
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .collect::<Vec<_>>();
+        devices.sort();
+
+        for device in devices {
+            let uevent = std::fs::read_to_string(device.join("device/uevent")).unwrap_or_default();
+            // The messages are exchanged over the first interface, the other being U2F
+            let interface = std::fs::read_to_string(device.join("device/../bInterfaceNumber")).unwrap_or_default();
+            let is_trezor = uevent
+                .lines()
+                .any(|line| line.starts_with("HID_ID=") && line.to_uppercase().ends_with(&hid_id));
+            if let (true, "00", Some(name)) = (is_trezor, interface.trim(), device.file_name()) {
+                return Self::open_path(Path::new("/dev").join(name));
+            }
+        }
+        Err(TrezorError::DeviceNotFound)
+    }
+
+    /// Returns a transport over the first Trezor device connected over USB HID. Devices are only
+    /// discovered on Linux, so the path of the device must be given on other platforms.
+    #[cfg(not(target_os = "linux"))]
+    pub fn open() -> Result<Self, TrezorError> {
+        Err(TrezorError::DeviceNotFound)
+    }
+}
+
+impl<D: Read + Write> TrezorTransport for TrezorHid<D> {
+    /// Writes the message in HID reports, the first prefixed by its type and length, and reads the reports of the response.
+    fn call(&mut self, message_type: u16, message: &[u8]) -> Result<(u16, Vec<u8>), TrezorError> {
+        let data = [b"##".to_vec(), frame(message_type, message)].concat();
+        for chunk in data.chunks(HID_PACKET_SIZE - 1) {
+            // Each report is prefixed by the report id 0
+            let mut report = vec![0x00, b'?'];
+            report.extend(chunk);
+            report.resize(HID_PACKET_SIZE + 1, 0);
+            self.device.write_all(&report)?;
+        }
+
+        let mut report = [0u8; HID_PACKET_SIZE];
+        self.device.read_exact(&mut report)?;
+        if report[..3] != *b"?##" {
+            return Err(TrezorError::InvalidResponse("unexpected HID report".into()));
+        }
+        let mut response = report[3..].to_vec();
+        while response.len() < 6 || response.len() < 6 + message_length(&response) {
+            self.device.read_exact(&mut report)?;
+            if report[0] != b'?' {
+                return Err(TrezorError::InvalidResponse("unexpected HID report".into()));
+            }
+            response.extend(&report[1..]);
+        }
+        response.truncate(6 + message_length(&response));
+        unframe(&response)
+    }
+}
+
+/// Represents a Trezor device connected through Trezor Bridge, the local service used by Trezor Connect
+pub struct TrezorBridge {
+    /// The address of Trezor Bridge, e.g. `127.0.0.1:21325`
+    address: String,
+    /// The session of the device acquired by the client
+    session: String,
+}
+
+impl TrezorBridge {
+    /// Returns a transport over the first device of Trezor Bridge at the given address, once a session of the device is acquired.
+    pub fn connect(address: &str) -> Result<Self, TrezorError> {
+        let devices: serde_json::Value = serde_json::from_slice(&post(address, "/enumerate", "")?)?;
+        let device = match devices.as_array().and_then(|devices| devices.first()) {
+            Some(device) => device,
+            None => return Err(TrezorError::DeviceNotFound),
+        };
+        let path = match device["path"].as_str() {
+            Some(path) => path,
+            None => return Err(TrezorError::InvalidResponse("missing device path".into())),
+        };
+
+        // A session left by another client is taken over
+        let previous = device["session"].as_str().unwrap_or("null");
+        let acquired: serde_json::Value =
+            serde_json::from_slice(&post(address, &format!("/acquire/{}/{}", path, previous), "")?)?;
+        match acquired["session"].as_str() {
+            Some(session) => Ok(Self {
+                address: address.into(),
+                session: session.into(),
+            }),
+            None => Err(TrezorError::InvalidResponse("missing session".into())),
+        }
+    }
+}
+
+impl TrezorTransport for TrezorBridge {
+    /// Posts the hex encoding of the message, prefixed by its type and length, to the session of the device.
+    fn call(&mut self, message_type: u16, message: &[u8]) -> Result<(u16, Vec<u8>), TrezorError> {
+        let body = hex::encode(frame(message_type, message));
+        let response = post(&self.address, &format!("/call/{}", self.session), &body)?;
+        unframe(&hex::decode(String::from_utf8(response)?.trim())?)
+    }
+}
+
+impl Drop for TrezorBridge {
+    /// Releases the session of the device, so other clients can use it.
+    fn drop(&mut self) {
+        let _ = post(&self.address, &format!("/release/{}", self.session), "");
+    }
+}
+
+/// Returns the body of the response of Trezor Bridge to a POST request with the given body.
+fn post(address: &str, path: &str, body: &str) -> Result<Vec<u8>, TrezorError> {
+    // HTTP/1.0 responses are neither kept alive nor chunked, so they end with the connection
+    let mut stream = TcpStream::connect(address)?;
+    write!(
+        stream,
+        "POST {} HTTP/1.0\r\nHost: {}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\n\r\n{}",
+        path,
+        address,
+        body.len(),
+        body
+    )?;
+    read_http_response(stream)
+}
+
+/// Returns the body of an HTTP response, or an error with the body if its status is not 200.
+fn read_http_response<R: Read>(mut reader: R) -> Result<Vec<u8>, TrezorError> {
+    let mut response = vec![];
+    reader.read_to_end(&mut response)?;
+    let end = match response.windows(4).position(|window| window == b"\r\n\r\n") {
+        Some(end) => end,
+        None => return Err(TrezorError::InvalidResponse("invalid HTTP response".into())),
+    };
+    let body = response[end + 4..].to_vec();
+    match String::from_utf8_lossy(&response[..end]).split_whitespace().nth(1) {
+        Some("200") => Ok(body),
+        _ => Err(TrezorError::BridgeError(String::from_utf8_lossy(&body).trim().into())),
+    }
+}
+
+/// Returns the message prefixed by its type and length.
+fn frame(message_type: u16, message: &[u8]) -> Vec<u8> {
+    [
+        message_type.to_be_bytes().to_vec(),
+        (message.len() as u32).to_be_bytes().to_vec(),
+        message.to_vec(),
+    ]
+    .concat()
+}
+
+/// Returns the length of a message prefixed by its type and length.
+fn message_length(framed: &[u8]) -> usize {
+    u32::from_be_bytes([framed[2], framed[3], framed[4], framed[5]]) as usize
+}
+
+/// Returns the type and message of a message prefixed by its type and length.
+fn unframe(framed: &[u8]) -> Result<(u16, Vec<u8>), TrezorError> {
+    if framed.len() < 6 || framed.len() != 6 + message_length(framed) {
+        return Err(TrezorError::InvalidResponse("invalid message length".into()));
+    }
+    Ok((u16::from_be_bytes([framed[0], framed[1]]), framed[6..].to_vec()))
+}
+
+/// Represents the value of a field of a protobuf message
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ProtobufValue {
+    Varint(u64),
+    Bytes(Vec<u8>),
+}
+
+/// Represents a protobuf message, as its fields in order, with repeated fields occurring once per value
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct ProtobufMessage(Vec<(u32, ProtobufValue)>);
+
+impl ProtobufMessage {
+    /// Returns the message with the given unsigned integer, boolean, or enum field.
+    fn with_uint(mut self, field: u32, value: u64) -> Self {
+        self.0.push((field, ProtobufValue::Varint(value)));
+        self
+    }
+
+    /// Returns the message with the given bytes, string, or embedded message field.
+    fn with_bytes(mut self, field: u32, value: &[u8]) -> Self {
+        self.0.push((field, ProtobufValue::Bytes(value.to_vec())));
+        self
+    }
+
+    /// Returns the message with the given derivation path as a repeated field.
+    fn with_path<N: BitcoinNetwork>(self, field: u32, path: &BitcoinDerivationPath<N>) -> Result<Self, TrezorError> {
+        Ok(path
+            .to_vec()?
+            .into_iter()
+            .fold(self, |message, index| message.with_uint(field, u32::from(index) as u64)))
+    }
+
+    /// Returns the first value of the given unsigned integer field.
+    fn uint(&self, field: u32) -> Option<u64> {
+        self.0.iter().find_map(|(number, value)| match value {
+            ProtobufValue::Varint(value) if *number == field => Some(*value),
+            _ => None,
+        })
+    }
+
+    /// Returns the first value of the given bytes field.
+    fn bytes(&self, field: u32) -> Option<&[u8]> {
+        self.0.iter().find_map(|(number, value)| match value {
+            ProtobufValue::Bytes(value) if *number == field => Some(&value[..]),
+            _ => None,
+        })
+    }
+
+    /// Returns the first value of the given string field.
+    fn string(&self, field: u32) -> Result<Option<String>, TrezorError> {
+        Ok(match self.bytes(field) {
+            Some(value) => Some(String::from_utf8(value.to_vec())?),
+            None => None,
+        })
+    }
+
+    /// Returns the protobuf encoding of the message.
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut message = vec![];
+        for (field, value) in &self.0 {
+            match value {
+                ProtobufValue::Varint(value) => {
+                    write_varint(&mut message, (*field as u64) << 3);
+                    write_varint(&mut message, *value);
+                }
+                ProtobufValue::Bytes(value) => {
+                    write_varint(&mut message, (*field as u64) << 3 | 2);
+                    write_varint(&mut message, value.len() as u64);
+                    message.extend(value);
+                }
+            }
+        }
+        message
+    }
+
+    /// Returns the message of the given protobuf encoding, skipping its fixed-size fields.
+    fn from_bytes(mut message: &[u8]) -> Result<Self, TrezorError> {
+        let mut fields = vec![];
+        while !message.is_empty() {
+            let key = read_varint(&mut message)?;
+            let length = match key & 0x07 {
+                0 => {
+                    fields.push(((key >> 3) as u32, ProtobufValue::Varint(read_varint(&mut message)?)));
+                    continue;
+                }
+                1 => 8,
+                2 => read_varint(&mut message)? as usize,
+                5 => 4,
+                wire_type => {
+                    return Err(TrezorError::InvalidResponse(format!(
+                        "invalid protobuf wire type {}",
+                        wire_type
+                    )))
+                }
+            };
+            if message.len() < length {
+                return Err(TrezorError::InvalidResponse("invalid protobuf length".into()));
+            }
+            if key & 0x07 == 2 {
+                fields.push(((key >> 3) as u32, ProtobufValue::Bytes(message[..length].to_vec())));
+            }
+            message = &message[length..];
+        }
+        Ok(Self(fields))
+    }
+}
+
+/// Writes the given value as a protobuf varint, 7 bits at a time from the least significant.
+fn write_varint(buffer: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buffer.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    buffer.push(value as u8);
+}
+
+/// Returns the value of a protobuf varint, advancing the reader.
+fn read_varint(reader: &mut &[u8]) -> Result<u64, TrezorError> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let (byte, remaining) = match reader.split_first() {
+            Some(split) => split,
+            None => return Err(TrezorError::InvalidResponse("invalid protobuf varint".into())),
+        };
+        *reader = remaining;
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(TrezorError::InvalidResponse("invalid protobuf varint".into()))
+}
+
+/// Returns the name of the coin of the network in the firmware of Trezor devices.
+fn coin_name<N: BitcoinNetwork>() -> String {
+    match N::NAME {
+        "mainnet" => "Bitcoin".into(),
+        "testnet" | "signet" => "Testnet".into(),
+        "regtest" => "Regtest".into(),
+        name => name[..1].to_uppercase() + &name[1..],
+    }
+}
+
+/// Returns the script type of an input spending, or an output paying, an address of the given format.
+fn script_type(format: &BitcoinFormat) -> Result<u64, TrezorError> {
+    match format {
+        BitcoinFormat::P2PKH => Ok(0),
+        BitcoinFormat::Bech32 => Ok(3),
+        BitcoinFormat::P2SH_P2WPKH => Ok(4),
+        BitcoinFormat::P2TR => Ok(5),
+        format => Err(TrezorError::UnsupportedFormat(format.to_string())),
+    }
+}
+
+/// The DER signature of an input made by a Trezor device, without its sighash type
+type TrezorSignature = Vec<u8>;
+
+/// Represents the features of a Trezor device
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrezorFeatures {
+    /// The vendor of the device, e.g. `trezor.io`
+    pub vendor: String,
+    /// The version of the firmware of the device, e.g. `2.6.0`
+    pub version: String,
+    /// The model of the device, e.g. `T`
+    pub model: Option<String>,
+    /// The label of the device given by its owner
+    pub label: Option<String>,
+    /// If true, the device has a seed
+    pub initialized: bool,
+}
+
+/// Represents a client of a Trezor device
+pub struct TrezorClient<T: TrezorTransport> {
+    transport: T,
+    /// The passphrase of the hidden wallet, which is empty for the standard wallet
+    passphrase: String,
+}
+
+impl<T: TrezorTransport> TrezorClient<T> {
+    /// Returns a client of a Trezor device over the given transport, of its standard wallet if it asks for a passphrase.
+    pub fn new(transport: T) -> Self {
+        Self {
+            transport,
+            passphrase: String::new(),
+        }
+    }
+
+    /// Returns the client of the hidden wallet of the given passphrase.
+    pub fn with_passphrase(mut self, passphrase: &str) -> Self {
+        self.passphrase = passphrase.into();
+        self
+    }
+
+    /// Returns the features of the device, starting a new session.
+    pub fn features(&mut self) -> Result<TrezorFeatures, TrezorError> {
+        let features = self.call(MESSAGE_INITIALIZE, &ProtobufMessage::default(), MESSAGE_FEATURES)?;
+        let version = [2, 3, 4]
+            .iter()
+            .map(|field| features.uint(*field).unwrap_or(0).to_string())
+            .collect::<Vec<_>>();
+        Ok(TrezorFeatures {
+            vendor: features.string(1)?.unwrap_or_default(),
+            version: version.join("."),
+            model: features.string(21)?,
+            label: features.string(10)?,
+            initialized: features.uint(12) == Some(1),
+        })
+    }
+
+    /// Returns the fingerprint of the master key of the device.
+    pub fn master_fingerprint<N: BitcoinNetwork>(&mut self) -> Result<[u8; 4], TrezorError> {
+        let master = BitcoinDerivationPath::<N>::from_vec(&vec![])?;
+        Ok(self
+            .extended_public_key(&master, &BitcoinFormat::P2PKH, false)?
+            .fingerprint())
+    }
+
+    /// Returns the extended public key of the device at the given derivation path for the given address format,
+    /// which is displayed on the device for verification if `display` is set.
+    pub fn extended_public_key<N: BitcoinNetwork>(
+        &mut self,
+        path: &BitcoinDerivationPath<N>,
+        format: &BitcoinFormat,
+        display: bool,
+    ) -> Result<BitcoinExtendedPublicKey<N>, TrezorError> {
+        let request = ProtobufMessage::default()
+            .with_path(1, path)?
+            .with_uint(3, display as u64)
+            .with_bytes(4, coin_name::<N>().as_bytes())
+            .with_uint(5, script_type(format)?);
+        let response = self.call(MESSAGE_GET_PUBLIC_KEY, &request, MESSAGE_PUBLIC_KEY)?;
+        match response.string(2)? {
+            Some(key) => Ok(BitcoinExtendedPublicKey::from_str(&key)?.with_format(format)),
+            None => Err(TrezorError::InvalidResponse("missing extended public key".into())),
+        }
+    }
+
+    /// Returns the address of the given format of the device at the given derivation path,
+    /// which is displayed on the device for verification if `display` is set.
+    pub fn address<N: BitcoinNetwork>(
+        &mut self,
+        path: &BitcoinDerivationPath<N>,
+        format: &BitcoinFormat,
+        display: bool,
+    ) -> Result<BitcoinAddress<N>, TrezorError> {
+        let request = ProtobufMessage::default()
+            .with_path(1, path)?
+            .with_bytes(2, coin_name::<N>().as_bytes())
+            .with_uint(3, display as u64)
+            .with_uint(5, script_type(format)?);
+        let response = self.call(MESSAGE_GET_ADDRESS, &request, MESSAGE_ADDRESS)?;
+        match response.string(1)? {
+            Some(address) => Ok(BitcoinAddress::from_str(&address)?),
+            None => Err(TrezorError::InvalidResponse("missing address".into())),
+        }
+    }
+
+    /// Returns the address of the device at the given derivation path, once it is displayed on the device
+    /// and verified against the given address derived by wagyu.
+    pub fn verify_address<N: BitcoinNetwork>(
+        &mut self,
+        path: &BitcoinDerivationPath<N>,
+        address: &BitcoinAddress<N>,
+    ) -> Result<BitcoinAddress<N>, TrezorError> {
+        let device_address = self.address(path, &address.format(), true)?;
+        match &device_address == address {
+            true => Ok(device_address),
+            false => Err(TrezorError::MismatchedAddress(
+                address.to_string(),
+                device_address.to_string(),
+            )),
+        }
+    }
+
+    /// Returns the transaction signed by the device, once it is approved on the device.
+    ///
+    /// Each input is spent by the key at its derivation path, and must have its address and amount.
+    /// The outputs with a derivation path are change outputs, which are not displayed for approval.
+    /// The previous transactions of the inputs are verified by the device against their amounts.
+    pub fn sign_transaction<N: BitcoinNetwork>(
+        &mut self,
+        transaction: &BitcoinTransaction<N>,
+        input_paths: &[BitcoinDerivationPath<N>],
+        change_paths: &[Option<BitcoinDerivationPath<N>>],
+        previous_transactions: &[BitcoinTransaction<N>],
+    ) -> Result<BitcoinTransaction<N>, TrezorError> {
+        let (_, transaction) = self.sign(
+            transaction.parameters(),
+            input_paths,
+            change_paths,
+            previous_transactions,
+        )?;
+        Ok(BitcoinTransaction::from_transaction_bytes(&transaction)?)
+    }
+
+    /// Adds the signatures of the device to the inputs of the PSBT derived from its master key, once they are
+    /// verified, and returns the number of signatures added. The previous transaction of each input must be
+    /// in the PSBT, and the outputs derived from the master key are change outputs.
+    pub fn sign_psbt<N: BitcoinNetwork>(&mut self, psbt: &mut BitcoinPsbt<N>) -> Result<usize, TrezorError> {
+        let fingerprint = self.master_fingerprint::<N>()?;
+
+        let mut transaction = psbt.unsigned_transaction()?;
+        let mut public_keys = vec![];
+        let mut input_paths = vec![];
+        let mut previous_transactions = vec![];
+        for (vin, input) in psbt.inputs().iter().enumerate() {
+            let (public_key, path) = match input
+                .bip32_derivations
+                .iter()
+                .find(|(_, origin)| origin.fingerprint() == fingerprint)
+            {
+                Some((public_key, origin)) => (
+                    public_key.clone(),
+                    BitcoinDerivationPath::from_vec(&origin.path().to_vec()?)?,
+                ),
+                None => return Err(TrezorError::MissingPath(vin)),
+            };
+            public_keys.push(public_key);
+            input_paths.push(path);
+
+            // The address and amount of each input are those of its spent output
+            let outpoint = &mut transaction.inputs[vin].outpoint;
+            let previous_transaction = match &input.non_witness_utxo {
+                Some(previous_transaction) => {
+                    Some(BitcoinTransaction::<N>::from_transaction_bytes(previous_transaction)?)
+                }
+                None => None,
+            };
+            let spent_output = match (&input.witness_utxo, &previous_transaction) {
+                (Some(output), _) => output.clone(),
+                (None, Some(previous_transaction)) => {
+                    match previous_transaction.parameters().outputs.get(outpoint.index as usize) {
+                        Some(output) => output.clone(),
+                        None => return Err(TrezorError::PsbtError(PsbtError::InvalidUtxo(vin))),
+                    }
+                }
+                (None, None) => return Err(TrezorError::PsbtError(PsbtError::MissingUtxo(vin))),
+            };
+            outpoint.amount = Some(spent_output.amount);
+            outpoint.address = Some(BitcoinAddress::from_script_pub_key(&BitcoinScript::new(
+                &spent_output.script_pub_key,
+            ))?);
+            outpoint.script_pub_key = Some(spent_output.script_pub_key);
+            previous_transactions.extend(previous_transaction);
+        }
+
+        let change_paths = psbt
+            .outputs()
+            .iter()
+            .map(|output| {
+                match output
+                    .bip32_derivations
+                    .iter()
+                    .find(|(_, origin)| origin.fingerprint() == fingerprint)
+                {
+                    Some((_, origin)) => Ok(Some(BitcoinDerivationPath::from_vec(&origin.path().to_vec()?)?)),
+                    None => Ok(None),
+                }
+            })
+            .collect::<Result<Vec<_>, TrezorError>>()?;
+
+        let (signatures, _) = self.sign(&transaction, &input_paths, &change_paths, &previous_transactions)?;
+        // Taproot signatures are made by x-only public keys, which are not partial signatures
+        if let Some(vin) = signatures
+            .iter()
+            .position(|signature| signature.as_ref().map(Vec::len) == Some(64))
+        {
+            return Err(TrezorError::UnsupportedSignature(vin));
+        }
+        let mut count = 0;
+        for (vin, signature) in signatures.iter().enumerate() {
+            if let Some(signature) = signature {
+                // The signatures of the device are made with SIGHASH_ALL
+                let signature = [&signature[..], &[0x01]].concat();
+                psbt.add_signature(vin, &public_keys[vin], &signature)?;
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+
+    /// Returns the signatures of the inputs and the signed transaction, answering the requests of the device
+    /// for the inputs and outputs of the transaction and of the previous transactions.
+    fn sign<N: BitcoinNetwork>(
+        &mut self,
+        transaction: &BitcoinTransactionParameters<N>,
+        input_paths: &[BitcoinDerivationPath<N>],
+        change_paths: &[Option<BitcoinDerivationPath<N>>],
+        previous_transactions: &[BitcoinTransaction<N>],
+    ) -> Result<(Vec<Option<TrezorSignature>>, Vec<u8>), TrezorError> {
+        if input_paths.len() != transaction.inputs.len() {
+            return Err(TrezorError::MissingPath(input_paths.len()));
+        }
+        if change_paths.len() > transaction.outputs.len() {
+            return Err(TrezorError::InvalidChange(change_paths.len()));
+        }
+
+        let inputs = transaction
+            .inputs
+            .iter()
+            .zip(input_paths)
+            .enumerate()
+            .map(|(vin, (input, path))| {
+                let outpoint = &input.outpoint;
+                let address = match (&outpoint.address, &outpoint.script_pub_key) {
+                    (Some(address), _) => address.clone(),
+                    (None, Some(script_pub_key)) => {
+                        BitcoinAddress::from_script_pub_key(&BitcoinScript::new(script_pub_key))?
+                    }
+                    (None, None) => return Err(TrezorError::MissingAddress(vin)),
+                };
+                let amount = match outpoint.amount {
+                    Some(amount) => amount.0 as u64,
+                    None => return Err(TrezorError::MissingAmount(vin)),
+                };
+                let mut previous_hash = outpoint.reverse_transaction_id.clone();
+                previous_hash.reverse();
+                Ok(ProtobufMessage::default()
+                    .with_path(1, path)?
+                    .with_bytes(2, &previous_hash)
+                    .with_uint(3, outpoint.index as u64)
+                    .with_uint(5, sequence(&input.sequence) as u64)
+                    .with_uint(6, script_type(&address.format())?)
+                    .with_uint(8, amount))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let outputs = transaction
+            .outputs
+            .iter()
+            .enumerate()
+            .map(|(vout, output)| output_message(output, change_paths.get(vout).and_then(Option::as_ref)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let previous_transactions = previous_transactions
+            .iter()
+            .map(|transaction| Ok((transaction.to_transaction_id()?.txid, transaction.parameters())))
+            .collect::<Result<Vec<_>, TrezorError>>()?;
+
+        let request = ProtobufMessage::default()
+            .with_uint(1, transaction.outputs.len() as u64)
+            .with_uint(2, transaction.inputs.len() as u64)
+            .with_bytes(3, coin_name::<N>().as_bytes())
+            .with_uint(4, transaction.version as u64)
+            .with_uint(5, transaction.lock_time as u64);
+        let mut response = self.call(MESSAGE_SIGN_TX, &request, MESSAGE_TX_REQUEST)?;
+
+        let mut signatures = vec![None; inputs.len()];
+        let mut serialized = vec![];
+        loop {
+            if let Some(parts) = response.bytes(3) {
+                let parts = ProtobufMessage::from_bytes(parts)?;
+                if let Some(transaction) = parts.bytes(3) {
+                    serialized.extend(transaction);
+                }
+                if let (Some(vin), Some(signature)) = (parts.uint(1), parts.bytes(2)) {
+                    match signatures.get_mut(vin as usize) {
+                        Some(entry) => *entry = Some(signature.to_vec()),
+                        None => return Err(TrezorError::InvalidResponse("invalid signature index".into())),
+                    }
+                }
+            }
+
+            let details = ProtobufMessage::from_bytes(response.bytes(2).unwrap_or_default())?;
+            let index = details.uint(1).unwrap_or(0) as usize;
+            let request_type = response.uint(1).unwrap_or(REQUEST_TX_INPUT);
+            let transaction = match (request_type, details.bytes(2)) {
+                (REQUEST_TX_FINISHED, _) => return Ok((signatures, serialized)),
+                (REQUEST_TX_INPUT, None) => match inputs.get(index) {
+                    Some(input) => ProtobufMessage::default().with_bytes(2, &input.to_bytes()),
+                    None => return Err(TrezorError::InvalidResponse(format!("invalid input {}", index))),
+                },
+                (REQUEST_TX_OUTPUT, None) => match outputs.get(index) {
+                    Some(output) => ProtobufMessage::default().with_bytes(5, &output.to_bytes()),
+                    None => return Err(TrezorError::InvalidResponse(format!("invalid output {}", index))),
+                },
+                (request_type, Some(hash)) => {
+                    let previous = match previous_transactions.iter().find(|(txid, _)| txid[..] == *hash) {
+                        Some((_, previous)) => previous,
+                        None => return Err(TrezorError::MissingPreviousTransaction(hex::encode(hash))),
+                    };
+                    previous_message(previous, request_type, index)?
+                }
+                (request_type, None) => return Err(TrezorError::UnsupportedRequest(request_type)),
+            };
+
+            let ack = ProtobufMessage::default().with_bytes(1, &transaction.to_bytes());
+            response = self.call(MESSAGE_TX_ACK, &ack, MESSAGE_TX_REQUEST)?;
+        }
+    }
+
+    /// Returns the response of the expected type to the given message, once the requests of
+    /// the device for confirmation and for the passphrase are answered.
+    fn call(
+        &mut self,
+        message_type: u16,
+        message: &ProtobufMessage,
+        expected: u16,
+    ) -> Result<ProtobufMessage, TrezorError> {
+        let (mut response_type, mut response) = self.transport.call(message_type, &message.to_bytes())?;
+        loop {
+            let (message_type, message) = match response_type {
+                response_type if response_type == expected => return ProtobufMessage::from_bytes(&response),
+                MESSAGE_BUTTON_REQUEST => (MESSAGE_BUTTON_ACK, ProtobufMessage::default()),
+                MESSAGE_PASSPHRASE_REQUEST => (
+                    MESSAGE_PASSPHRASE_ACK,
+                    ProtobufMessage::default().with_bytes(1, self.passphrase.as_bytes()),
+                ),
+                // The PIN is entered on a scrambled matrix shown by the device, or on the device itself
+                MESSAGE_PIN_MATRIX_REQUEST => return Err(TrezorError::Locked),
+                MESSAGE_FAILURE => {
+                    let failure = ProtobufMessage::from_bytes(&response)?;
+                    return match failure.uint(1) {
+                        Some(FAILURE_ACTION_CANCELLED) | Some(FAILURE_PIN_CANCELLED) => Err(TrezorError::DeniedByUser),
+                        _ => Err(TrezorError::Failure(failure.string(2)?.unwrap_or_default())),
+                    };
+                }
+                response_type => return Err(TrezorError::UnexpectedMessage(response_type)),
+            };
+            let (next_type, next) = self.transport.call(message_type, &message.to_bytes())?;
+            response_type = next_type;
+            response = next;
+        }
+    }
+}
+
+/// Returns the sequence number of the given little-endian bytes.
+fn sequence(sequence: &[u8]) -> u32 {
+    let mut bytes = [0xff; 4];
+    bytes.iter_mut().zip(sequence).for_each(|(byte, value)| *byte = *value);
+    u32::from_le_bytes(bytes)
+}
+
+/// Returns the message of an output, paying the change address at the derivation path if given.
+fn output_message<N: BitcoinNetwork>(
+    output: &BitcoinTransactionOutput,
+    change_path: Option<&BitcoinDerivationPath<N>>,
+) -> Result<ProtobufMessage, TrezorError> {
+    let script = &output.script_pub_key;
+    let message = ProtobufMessage::default().with_uint(3, output.amount.0 as u64);
+    if script.first() == Some(&(Opcode::OP_RETURN as u8)) {
+        let data = match script.get(1) {
+            Some(0x4c) => script.get(3..).unwrap_or_default(),
+            Some(length) if *length <= 0x4b => script.get(2..).unwrap_or_default(),
+            _ => return Err(TrezorError::UnsupportedOutput(hex::encode(script))),
+        };
+        return Ok(message.with_uint(4, OUTPUT_PAY_TO_OP_RETURN).with_bytes(6, data));
+    }
+
+    let address = BitcoinAddress::<N>::from_script_pub_key(&BitcoinScript::new(script))?;
+    match change_path {
+        Some(path) => Ok(message
+            .with_path(2, path)?
+            .with_uint(4, script_type(&address.format())?)),
+        None => Ok(message
+            .with_bytes(1, address.to_string().as_bytes())
+            .with_uint(4, OUTPUT_PAY_TO_ADDRESS)),
+    }
+}
+
+/// Returns the message of the metadata, an input, or an output of a previous transaction, as requested by the device.
+fn previous_message<N: BitcoinNetwork>(
+    transaction: &BitcoinTransactionParameters<N>,
+    request_type: u64,
+    index: usize,
+) -> Result<ProtobufMessage, TrezorError> {
+    match request_type {
+        REQUEST_TX_META => Ok(ProtobufMessage::default()
+            .with_uint(1, transaction.version as u64)
+            .with_uint(4, transaction.lock_time as u64)
+            .with_uint(6, transaction.inputs.len() as u64)
+            .with_uint(7, transaction.outputs.len() as u64)),
+        REQUEST_TX_INPUT => match transaction.inputs.get(index) {
+            Some(input) => {
+                let mut previous_hash = input.outpoint.reverse_transaction_id.clone();
+                previous_hash.reverse();
+                let input = ProtobufMessage::default()
+                    .with_bytes(2, &previous_hash)
+                    .with_uint(3, input.outpoint.index as u64)
+                    .with_bytes(4, &input.script_sig)
+                    .with_uint(5, sequence(&input.sequence) as u64);
+                Ok(ProtobufMessage::default().with_bytes(2, &input.to_bytes()))
+            }
+            None => Err(TrezorError::InvalidResponse(format!(
+                "invalid previous input {}",
+                index
+            ))),
+        },
+        REQUEST_TX_OUTPUT => match transaction.outputs.get(index) {
+            Some(output) => {
+                let output = ProtobufMessage::default()
+                    .with_uint(1, output.amount.0 as u64)
+                    .with_bytes(2, &output.script_pub_key);
+                Ok(ProtobufMessage::default().with_bytes(3, &output.to_bytes()))
+            }
+            None => Err(TrezorError::InvalidResponse(format!(
+                "invalid previous output {}",
+                index
+            ))),
+        },
+        request_type => Err(TrezorError::UnsupportedRequest(request_type)),
+    }
+}
+
+#[derive(Debug, Fail)]
+pub enum TrezorError {
+    #[fail(display = "{}", _0)]
+    AddressError(#[cause] AddressError),
+
+    #[fail(display = "Trezor Bridge error: {}", _0)]
+    BridgeError(String),
+
+    #[fail(display = "{}", _0)]
+    Crate(#[cause] CrateError),
+
+    #[fail(display = "the request was cancelled on the Trezor device")]
+    DeniedByUser,
+
+    #[fail(display = "{}", _0)]
+    DerivationPathError(#[cause] DerivationPathError),
+
+    #[fail(display = "no Trezor device found")]
+    DeviceNotFound,
+
+    #[fail(display = "{}", _0)]
+    ExtendedPublicKeyError(#[cause] ExtendedPublicKeyError),
+
+    #[fail(display = "Trezor failure: {}", _0)]
+    Failure(String),
+
+    #[fail(display = "invalid number of change paths: {}", _0)]
+    InvalidChange(usize),
+
+    #[fail(display = "invalid Trezor response: {}", _0)]
+    InvalidResponse(String),
+
+    #[fail(display = "the Trezor device is locked, unlock it and retry")]
+    Locked,
+
+    #[fail(
+        display = "the address {} does not match the address {} of the Trezor device",
+        _0, _1
+    )]
+    MismatchedAddress(String, String),
+
+    #[fail(display = "missing address of input {}", _0)]
+    MissingAddress(usize),
+
+    #[fail(display = "missing amount of input {}", _0)]
+    MissingAmount(usize),
+
+    #[fail(display = "missing derivation path of input {}", _0)]
+    MissingPath(usize),
+
+    #[fail(display = "missing previous transaction {}", _0)]
+    MissingPreviousTransaction(String),
+
+    #[fail(display = "{}", _0)]
+    PsbtError(#[cause] PsbtError),
+
+    #[fail(display = "{}", _0)]
+    TransactionError(#[cause] TransactionError),
+
+    #[fail(display = "unexpected Trezor message: {}", _0)]
+    UnexpectedMessage(u16),
+
+    #[fail(display = "unsupported address format: {}", _0)]
+    UnsupportedFormat(String),
+
+    #[fail(display = "unsupported output script: {}", _0)]
+    UnsupportedOutput(String),
+
+    #[fail(display = "unsupported Trezor request: {}", _0)]
+    UnsupportedRequest(u64),
+
+    #[fail(display = "unsupported Taproot signature of input {}", _0)]
+    UnsupportedSignature(usize),
+}
+
+impl From<AddressError> for TrezorError {
+    fn from(error: AddressError) -> Self {
+        TrezorError::AddressError(error)
+    }
+}
+
+impl From<CrateError> for TrezorError {
+    fn from(error: CrateError) -> Self {
+        TrezorError::Crate(error)
+    }
+}
+
+impl From<DerivationPathError> for TrezorError {
+    fn from(error: DerivationPathError) -> Self {
+        TrezorError::DerivationPathError(error)
+    }
+}
+
+impl From<ExtendedPublicKeyError> for TrezorError {
+    fn from(error: ExtendedPublicKeyError) -> Self {
+        TrezorError::ExtendedPublicKeyError(error)
+    }
+}
+
+impl From<FromUtf8Error> for TrezorError {
+    fn from(error: FromUtf8Error) -> Self {
+        TrezorError::Crate(CrateError::from(error))
+    }
+}
+
+impl From<hex::FromHexError> for TrezorError {
+    fn from(error: hex::FromHexError) -> Self {
+        TrezorError::Crate(CrateError::from(error))
+    }
+}
+
+impl From<PsbtError> for TrezorError {
+    fn from(error: PsbtError) -> Self {
+        TrezorError::PsbtError(error)
+    }
+}
+
+impl From<serde_json::Error> for TrezorError {
+    fn from(error: serde_json::Error) -> Self {
+        TrezorError::Crate(CrateError::from(error))
+    }
+}
+
+impl From<std::io::Error> for TrezorError {
+    fn from(error: std::io::Error) -> Self {
+        TrezorError::Crate(CrateError::from(error))
+    }
+}
+
+impl From<TransactionError> for TrezorError {
+    fn from(error: TransactionError) -> Self {
+        TrezorError::TransactionError(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::amount::BitcoinAmount;
+    use crate::extended_private_key::BitcoinExtendedPrivateKey;
+    use crate::network::*;
+    use crate::transaction::BitcoinTransactionInput;
+    use crate::SignatureHash;
+    use wagyu_model::{ExtendedPrivateKey, KeyOrigin, PrivateKey};
+
+    use std::collections::VecDeque;
+    use std::io::Cursor;
+
+    type N = Mainnet;
+
+    const MASTER_EXTENDED_PRIVATE_KEY: &str = "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi";
+    const ADDRESS_PATH: &str = "m/84'/0'/0'/0/0";
+    const TRANSACTION_ID: &str = "61d520ccb74288c96bc1a2b20ea1c0d5a704776dd0164a396efec3ea7040349d";
+    const OUTPUT_ADDRESS: &str = "3QDTHVyuJrHixUhhsdZXQ7M8P9MQngmw1P";
+
+    // Represents a Trezor device answering with the given responses in order, recording the messages sent to it
+    struct MockDevice {
+        responses: VecDeque<(u16, Vec<u8>)>,
+        received: Vec<(u16, ProtobufMessage)>,
+    }
+
+    impl MockDevice {
+        fn new(responses: Vec<(u16, ProtobufMessage)>) -> Self {
+            Self {
+                responses: responses
+                    .into_iter()
+                    .map(|(message_type, message)| (message_type, message.to_bytes()))
+                    .collect(),
+                received: vec![],
+            }
+        }
+    }
+
+    impl TrezorTransport for MockDevice {
+        fn call(&mut self, message_type: u16, message: &[u8]) -> Result<(u16, Vec<u8>), TrezorError> {
+            self.received
+                .push((message_type, ProtobufMessage::from_bytes(message).unwrap()));
+            Ok(self.responses.pop_front().expect("unexpected message"))
+        }
+    }
+
+    // Represents a HID device replying with the given reports, recording the reports written to it
+    struct MockHid {
+        reports: Cursor<Vec<u8>>,
+        written: Vec<u8>,
+    }
+
+    impl Read for MockHid {
+        fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+            self.reports.read(buffer)
+        }
+    }
+
+    impl Write for MockHid {
+        fn write(&mut self, buffer: &[u8]) -> std::io::Result<usize> {
+            self.written.extend(buffer);
+            Ok(buffer.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn master() -> BitcoinExtendedPrivateKey<N> {
+        BitcoinExtendedPrivateKey::from_str(MASTER_EXTENDED_PRIVATE_KEY).unwrap()
+    }
+
+    fn path() -> BitcoinDerivationPath<N> {
+        BitcoinDerivationPath::from_str(ADDRESS_PATH).unwrap()
+    }
+
+    // Returns the first receiving address of the first account
+    fn address() -> BitcoinAddress<N> {
+        master()
+            .derive(&path())
+            .unwrap()
+            .to_address(&BitcoinFormat::Bech32)
+            .unwrap()
+    }
+
+    // Returns the previous transaction, paying 50000 satoshis to the address in its second output
+    fn previous_transaction() -> BitcoinTransaction<N> {
+        let input = BitcoinTransactionInput::<N>::new(
+            hex::decode(TRANSACTION_ID).unwrap(),
+            0,
+            None,
+            None,
+            None,
+            None,
+            None,
+            SignatureHash::SIGHASH_ALL,
+        )
+        .unwrap();
+        let other = BitcoinAddress::<N>::from_str(OUTPUT_ADDRESS).unwrap();
+        BitcoinTransaction::new(&BitcoinTransactionParameters {
+            version: 1,
+            inputs: vec![input],
+            outputs: vec![
+                BitcoinTransactionOutput::new(&other, BitcoinAmount(10000)).unwrap(),
+                BitcoinTransactionOutput::new(&address(), BitcoinAmount(50000)).unwrap(),
+            ],
+            lock_time: 0,
+            segwit_flag: false,
+        })
+        .unwrap()
+    }
+
+    // Returns the transaction spending the output of the previous transaction, with its address and amount if given
+    fn transaction(outpoint: bool) -> BitcoinTransactionParameters<N> {
+        let (address, amount) = match outpoint {
+            true => (Some(address()), Some(BitcoinAmount(50000))),
+            false => (None, None),
+        };
+        let input = BitcoinTransactionInput::<N>::new(
+            previous_transaction().to_transaction_id().unwrap().txid,
+            1,
+            address,
+            amount,
+            None,
+            None,
+            None,
+            SignatureHash::SIGHASH_ALL,
+        )
+        .unwrap();
+        let output = BitcoinTransactionOutput::new(
+            &BitcoinAddress::<N>::from_str(OUTPUT_ADDRESS).unwrap(),
+            BitcoinAmount(40000),
+        )
+        .unwrap();
+        BitcoinTransactionParameters {
+            version: 2,
+            inputs: vec![input],
+            outputs: vec![output],
+            lock_time: 0,
+            segwit_flag: false,
+        }
+    }
+
+    // Returns a request of the device for the transaction, or for the previous transaction of the given hash
+    fn tx_request(request_type: u64, index: u64, hash: Option<&[u8]>) -> (u16, ProtobufMessage) {
+        let mut details = ProtobufMessage::default().with_uint(1, index);
+        if let Some(hash) = hash {
+            details = details.with_bytes(2, hash);
+        }
+        let request = ProtobufMessage::default()
+            .with_uint(1, request_type)
+            .with_bytes(2, &details.to_bytes());
+        (MESSAGE_TX_REQUEST, request)
+    }
+
+    // Returns the requests of the device signing the transaction, ending with the signature and signed transaction
+    fn signing_responses(signature: &[u8], serialized: &[u8]) -> Vec<(u16, ProtobufMessage)> {
+        let hash = previous_transaction().to_transaction_id().unwrap().txid;
+        let parts = ProtobufMessage::default()
+            .with_uint(1, 0)
+            .with_bytes(2, signature)
+            .with_bytes(3, serialized);
+        let (_, finished) = tx_request(REQUEST_TX_FINISHED, 0, None);
+        vec![
+            tx_request(REQUEST_TX_INPUT, 0, None),
+            tx_request(REQUEST_TX_META, 0, Some(&hash)),
+            tx_request(REQUEST_TX_INPUT, 0, Some(&hash)),
+            tx_request(REQUEST_TX_OUTPUT, 1, Some(&hash)),
+            tx_request(REQUEST_TX_OUTPUT, 0, None),
+            (MESSAGE_TX_REQUEST, finished.with_bytes(3, &parts.to_bytes())),
+        ]
+    }
+
+    // Returns the transaction acknowledged by the given message
+    fn acknowledged(message: &(u16, ProtobufMessage)) -> ProtobufMessage {
+        assert_eq!(MESSAGE_TX_ACK, message.0);
+        ProtobufMessage::from_bytes(message.1.bytes(1).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn protobuf() {
+        let message = ProtobufMessage::default()
+            .with_uint(1, 300)
+            .with_uint(1, 1)
+            .with_bytes(2, b"Bitcoin")
+            .with_uint(3, u64::MAX);
+        let bytes = message.to_bytes();
+        assert_eq!([0x08, 0xac, 0x02, 0x08, 0x01, 0x12, 0x07], bytes[..7]);
+        assert_eq!(message, ProtobufMessage::from_bytes(&bytes).unwrap());
+        assert_eq!(Some(300), message.uint(1));
+        assert_eq!(Some("Bitcoin".into()), message.string(2).unwrap());
+        assert_eq!(None, message.bytes(4));
+
+        // Fixed-size fields are skipped
+        let bytes = [&[0x0d, 0x01, 0x02, 0x03, 0x04, 0x11][..], &[0u8; 8], &[0x18, 0x05]].concat();
+        assert_eq!(
+            ProtobufMessage::default().with_uint(3, 5),
+            ProtobufMessage::from_bytes(&bytes).unwrap()
+        );
+
+        assert!(ProtobufMessage::from_bytes(&[0x08, 0x80]).is_err());
+        assert!(ProtobufMessage::from_bytes(&[0x12, 0x05, 0x00]).is_err());
+        assert!(ProtobufMessage::from_bytes(&[0x0b]).is_err());
+    }
+
+    #[test]
+    fn hid() {
+        let response = vec![0x42; 100];
+        let data = [b"##".to_vec(), frame(MESSAGE_ADDRESS, &response)].concat();
+        let reports = data
+            .chunks(HID_PACKET_SIZE - 1)
+            .flat_map(|chunk| {
+                let mut report = [vec![b'?'], chunk.to_vec()].concat();
+                report.resize(HID_PACKET_SIZE, 0);
+                report
+            })
+            .collect();
+        let mut hid = TrezorHid::new(MockHid {
+            reports: Cursor::new(reports),
+            written: vec![],
+        });
+
+        let message = vec![0x17; 70];
+        assert_eq!(
+            (MESSAGE_ADDRESS, response),
+            hid.call(MESSAGE_GET_ADDRESS, &message).unwrap()
+        );
+
+        let written = &hid.device.written;
+        assert_eq!(2 * (HID_PACKET_SIZE + 1), written.len());
+        assert_eq!([0x00, b'?', b'#', b'#', 0x00, 29, 0x00, 0x00, 0x00, 70], written[..10]);
+        assert_eq!(message[..55], written[10..65]);
+        assert_eq!([0x00, b'?'], written[65..67]);
+        assert_eq!(message[55..], written[67..82]);
+        assert!(written[82..].iter().all(|byte| *byte == 0));
+
+        // A response without the header of a message is rejected
+        let mut hid = TrezorHid::new(MockHid {
+            reports: Cursor::new(vec![b'?'; HID_PACKET_SIZE]),
+            written: vec![],
+        });
+        assert!(hid.call(MESSAGE_GET_ADDRESS, &message).is_err());
+    }
+
+    #[test]
+    fn bridge() {
+        let response = "HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\n\r\n001e00000002";
+        assert_eq!(
+            b"001e00000002".to_vec(),
+            read_http_response(Cursor::new(response)).unwrap()
+        );
+        assert_eq!(
+            (MESSAGE_ADDRESS, vec![0x0a, 0x00]),
+            unframe(&hex::decode("001e000000020a00").unwrap()).unwrap()
+        );
+        assert!(unframe(&hex::decode("001e000000030a00").unwrap()).is_err());
+
+        let response = "HTTP/1.1 400 Bad Request\r\n\r\n{\"error\":\"device not found\"}\n";
+        match read_http_response(Cursor::new(response)) {
+            Err(TrezorError::BridgeError(error)) => assert_eq!("{\"error\":\"device not found\"}", error),
+            result => panic!("unexpected result {:?}", result),
+        }
+        assert!(read_http_response(Cursor::new("HTTP/1.1 200 OK\r\n")).is_err());
+    }
+
+    #[test]
+    fn verify_address() {
+        let response = ProtobufMessage::default().with_bytes(1, address().to_string().as_bytes());
+        let mut client = TrezorClient::new(MockDevice::new(vec![
+            (MESSAGE_BUTTON_REQUEST, ProtobufMessage::default()),
+            (MESSAGE_ADDRESS, response.clone()),
+            (MESSAGE_ADDRESS, response),
+        ]));
+        assert_eq!(address(), client.verify_address(&path(), &address()).unwrap());
+
+        let other = BitcoinAddress::<N>::from_str(OUTPUT_ADDRESS).unwrap();
+        match client.verify_address(&path(), &other) {
+            Err(TrezorError::MismatchedAddress(..)) => (),
+            result => panic!("unexpected result {:?}", result),
+        }
+
+        let received = &client.transport.received;
+        let request = ProtobufMessage::default()
+            .with_uint(1, 0x8000_0054)
+            .with_uint(1, 0x8000_0000)
+            .with_uint(1, 0x8000_0000)
+            .with_uint(1, 0)
+            .with_uint(1, 0)
+            .with_bytes(2, b"Bitcoin")
+            .with_uint(3, 1)
+            .with_uint(5, 3);
+        assert_eq!((MESSAGE_GET_ADDRESS, request), received[0]);
+        assert_eq!((MESSAGE_BUTTON_ACK, ProtobufMessage::default()), received[1]);
+        assert_eq!(Some(4), received[2].1.uint(5));
+
+        let cancelled = ProtobufMessage::default()
+            .with_uint(1, FAILURE_ACTION_CANCELLED)
+            .with_bytes(2, b"Cancelled");
+        let mut client = TrezorClient::new(MockDevice::new(vec![
+            (MESSAGE_FAILURE, cancelled),
+            (MESSAGE_PIN_MATRIX_REQUEST, ProtobufMessage::default()),
+        ]));
+        match client.address(&path(), &BitcoinFormat::Bech32, true) {
+            Err(TrezorError::DeniedByUser) => (),
+            result => panic!("unexpected result {:?}", result),
+        }
+        match client.address(&path(), &BitcoinFormat::Bech32, true) {
+            Err(TrezorError::Locked) => (),
+            result => panic!("unexpected result {:?}", result),
+        }
+        assert!(client.address(&path(), &BitcoinFormat::P2WSH, true).is_err());
+    }
+
+    #[test]
+    fn passphrase() {
+        let response =
+            ProtobufMessage::default().with_bytes(2, master().to_extended_public_key().to_string().as_bytes());
+        let mut client = TrezorClient::new(MockDevice::new(vec![
+            (MESSAGE_PASSPHRASE_REQUEST, ProtobufMessage::default()),
+            (MESSAGE_PUBLIC_KEY, response),
+        ]))
+        .with_passphrase("hidden");
+        assert_eq!(master().fingerprint(), client.master_fingerprint::<N>().unwrap());
+
+        let received = &client.transport.received;
+        assert_eq!(MESSAGE_GET_PUBLIC_KEY, received[0].0);
+        assert_eq!(None, received[0].1.uint(1));
+        assert_eq!(
+            (
+                MESSAGE_PASSPHRASE_ACK,
+                ProtobufMessage::default().with_bytes(1, b"hidden")
+            ),
+            received[1]
+        );
+    }
+
+    #[test]
+    fn sign_transaction() {
+        let private_key = master().derive(&path()).unwrap().to_private_key();
+        let signed = BitcoinTransaction::new(&transaction(true))
+            .unwrap()
+            .sign(&private_key)
+            .unwrap();
+        let serialized = signed.to_transaction_bytes().unwrap();
+
+        let mut client = TrezorClient::new(MockDevice::new(signing_responses(&[0x30; 71], &serialized)));
+        let transaction = BitcoinTransaction::new(&transaction(true)).unwrap();
+        let result = client
+            .sign_transaction(&transaction, &[path()], &[], &[previous_transaction()])
+            .unwrap();
+        assert_eq!(serialized, result.to_transaction_bytes().unwrap());
+
+        let received = &client.transport.received;
+        let request = ProtobufMessage::default()
+            .with_uint(1, 1)
+            .with_uint(2, 1)
+            .with_bytes(3, b"Bitcoin")
+            .with_uint(4, 2)
+            .with_uint(5, 0);
+        assert_eq!((MESSAGE_SIGN_TX, request), received[0]);
+
+        let input = ProtobufMessage::from_bytes(acknowledged(&received[1]).bytes(2).unwrap()).unwrap();
+        assert_eq!(Some(0x8000_0054), input.uint(1));
+        assert_eq!(
+            Some(&previous_transaction().to_transaction_id().unwrap().txid[..]),
+            input.bytes(2)
+        );
+        assert_eq!(Some(1), input.uint(3));
+        assert_eq!(Some(0xffff_ffff), input.uint(5));
+        assert_eq!(Some(3), input.uint(6));
+        assert_eq!(Some(50000), input.uint(8));
+
+        let meta = acknowledged(&received[2]);
+        assert_eq!((Some(1), Some(1), Some(2)), (meta.uint(1), meta.uint(6), meta.uint(7)));
+        let previous_input = ProtobufMessage::from_bytes(acknowledged(&received[3]).bytes(2).unwrap()).unwrap();
+        let previous_hash = hex::decode(TRANSACTION_ID).unwrap();
+        assert_eq!(Some(&previous_hash[..]), previous_input.bytes(2));
+        let previous_output = ProtobufMessage::from_bytes(acknowledged(&received[4]).bytes(3).unwrap()).unwrap();
+        assert_eq!(Some(50000), previous_output.uint(1));
+
+        let output = ProtobufMessage::from_bytes(acknowledged(&received[5]).bytes(5).unwrap()).unwrap();
+        assert_eq!(Some(OUTPUT_ADDRESS.into()), output.string(1).unwrap());
+        assert_eq!(
+            (Some(40000), Some(OUTPUT_PAY_TO_ADDRESS)),
+            (output.uint(3), output.uint(4))
+        );
+
+        // Each input must have its derivation path and its amount
+        let mut client = TrezorClient::new(MockDevice::new(vec![]));
+        assert!(client.sign_transaction(&transaction, &[], &[], &[]).is_err());
+        let transaction = BitcoinTransaction::new(&self::transaction(false)).unwrap();
+        assert!(client.sign_transaction(&transaction, &[path()], &[], &[]).is_err());
+    }
+
+    #[test]
+    fn sign_psbt() {
+        let private_key = master().derive(&path()).unwrap().to_private_key();
+        let public_key = private_key
+            .to_public_key()
+            .to_secp256k1_public_key()
+            .serialize_compressed()
+            .to_vec();
+        let origin = KeyOrigin::new(master().fingerprint(), &path()).unwrap();
+
+        let mut psbt = BitcoinPsbt::new(&transaction(false)).unwrap();
+        psbt.inputs_mut()[0].non_witness_utxo = Some(previous_transaction().to_transaction_bytes().unwrap());
+        psbt.inputs_mut()[0].bip32_derivations.push((public_key, origin));
+        let mut signed = psbt.clone();
+        signed.inputs_mut()[0].witness_utxo = Some(previous_transaction().parameters().outputs[1].clone());
+        signed.sign(&private_key).unwrap();
+        let (_, signature) = signed.inputs()[0].partial_signatures[0].clone();
+        signed.inputs_mut()[0].witness_utxo = None;
+
+        let master_key =
+            ProtobufMessage::default().with_bytes(2, master().to_extended_public_key().to_string().as_bytes());
+        let responses = [
+            vec![(MESSAGE_PUBLIC_KEY, master_key)],
+            signing_responses(&signature[..signature.len() - 1], &[]),
+        ]
+        .concat();
+        let mut client = TrezorClient::new(MockDevice::new(responses));
+        assert_eq!(1, client.sign_psbt(&mut psbt).unwrap());
+        assert_eq!(signed, psbt);
+
+        // An input without a derivation path from the master key of the device is not signed
+        let mut psbt = BitcoinPsbt::new(&transaction(false)).unwrap();
+        let master_key =
+            ProtobufMessage::default().with_bytes(2, master().to_extended_public_key().to_string().as_bytes());
+        let mut client = TrezorClient::new(MockDevice::new(vec![(MESSAGE_PUBLIC_KEY, master_key)]));
+        match client.sign_psbt(&mut psbt) {
+            Err(TrezorError::MissingPath(0)) => (),
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+}
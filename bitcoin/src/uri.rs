@@ -0,0 +1,442 @@
+//!
+//! URI
+//!
+//! This module contains the BIP21 payment URIs of Bitcoin addresses, e.g.
+//! `bitcoin:bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu?amount=0.001&label=Alice`.
+//! https://github.com/bitcoin/bips/blob/master/bip-0021.mediawiki
+//!
+
+use crate::address::BitcoinAddress;
+use crate::amount::BitcoinAmount;
+use crate::network::BitcoinNetwork;
+use wagyu_model::no_std::*;
+use wagyu_model::{AddressError, AmountError};
+
+use core::{fmt, str::FromStr};
+
+/// The URI scheme of Bitcoin payment requests
+pub const BITCOIN_URI_SCHEME: &str = "bitcoin";
+
+/// The number of decimal places of an amount in bitcoin
+const DECIMALS: usize = 8;
+
+/// Represents a BIP21 payment URI of a Bitcoin address
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitcoinUri<N: BitcoinNetwork> {
+    /// The address to pay
+    address: BitcoinAddress<N>,
+    /// The requested amount
+    amount: Option<BitcoinAmount>,
+    /// The label of the address, e.g. the name of the recipient
+    label: Option<String>,
+    /// The message describing the payment
+    message: Option<String>,
+    /// The other parameters of the URI, in order, which are passed through unchanged
+    parameters: Vec<(String, String)>,
+}
+
+impl<N: BitcoinNetwork> BitcoinUri<N> {
+    /// Returns a payment URI of the given address, without parameters.
+    pub fn new(address: BitcoinAddress<N>) -> Self {
+        Self {
+            address,
+            amount: None,
+            label: None,
+            message: None,
+            parameters: vec![],
+        }
+    }
+
+    /// Returns the payment URI with the given amount.
+    pub fn with_amount(mut self, amount: BitcoinAmount) -> Result<Self, UriError> {
+        if amount.0 < 0 {
+            return Err(UriError::InvalidAmount(amount.to_string()));
+        }
+        self.amount = Some(amount);
+        Ok(self)
+    }
+
+    /// Returns the payment URI with the given label.
+    pub fn with_label(mut self, label: &str) -> Self {
+        self.label = Some(label.to_string());
+        self
+    }
+
+    /// Returns the payment URI with the given message.
+    pub fn with_message(mut self, message: &str) -> Self {
+        self.message = Some(message.to_string());
+        self
+    }
+
+    /// Returns the payment URI with the given parameter, e.g. a `lightning` invoice.
+    /// Parameters prefixed by `req-` must be understood by the payer to pay the URI.
+    pub fn with_parameter(mut self, key: &str, value: &str) -> Result<Self, UriError> {
+        match key {
+            "amount" | "label" | "message" => return Err(UriError::InvalidParameter(key.to_string())),
+            _ if key.is_empty() || !key.chars().all(is_unreserved) => {
+                return Err(UriError::InvalidParameter(key.to_string()))
+            }
+            _ if self.parameters.iter().any(|(k, _)| k == key) => {
+                return Err(UriError::DuplicateParameter(key.to_string()))
+            }
+            _ => (),
+        };
+        self.parameters.push((key.to_string(), value.to_string()));
+        Ok(self)
+    }
+
+    /// Returns the address of the payment URI.
+    pub fn address(&self) -> &BitcoinAddress<N> {
+        &self.address
+    }
+
+    /// Returns the requested amount of the payment URI.
+    pub fn amount(&self) -> Option<BitcoinAmount> {
+        self.amount
+    }
+
+    /// Returns the label of the payment URI.
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /// Returns the message of the payment URI.
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+
+    /// Returns the other parameters of the payment URI, in order.
+    pub fn parameters(&self) -> &[(String, String)] {
+        &self.parameters
+    }
+}
+
+impl<N: BitcoinNetwork> FromStr for BitcoinUri<N> {
+    type Err = UriError;
+
+    fn from_str(uri: &str) -> Result<Self, Self::Err> {
+        // The scheme is case-insensitive
+        let scheme_length = BITCOIN_URI_SCHEME.len() + 1;
+        if uri.len() < scheme_length || !uri[..scheme_length].eq_ignore_ascii_case("bitcoin:") {
+            return Err(UriError::InvalidScheme(uri.to_string()));
+        }
+
+        let (address, query) = match uri[scheme_length..].find('?') {
+            Some(position) => (
+                &uri[scheme_length..scheme_length + position],
+                Some(&uri[scheme_length + position + 1..]),
+            ),
+            None => (&uri[scheme_length..], None),
+        };
+
+        // Bech32 addresses may be uppercase, for compact QR codes
+        let address = match address.chars().any(|c| c.is_ascii_lowercase()) {
+            true => BitcoinAddress::from_str(address)?,
+            false => BitcoinAddress::from_str(&address.to_lowercase())?,
+        };
+
+        let mut result = Self::new(address);
+        for parameter in query.unwrap_or("").split('&').filter(|parameter| !parameter.is_empty()) {
+            let (key, value) = match parameter.find('=') {
+                Some(position) => (&parameter[..position], &parameter[position + 1..]),
+                None => (parameter, ""),
+            };
+            let value = percent_decode(value)?;
+
+            match key {
+                "amount" if result.amount.is_none() => result.amount = Some(parse_amount(&value)?),
+                "label" if result.label.is_none() => result.label = Some(value),
+                "message" if result.message.is_none() => result.message = Some(value),
+                "amount" | "label" | "message" => return Err(UriError::DuplicateParameter(key.to_string())),
+                _ if key.starts_with("req-") => return Err(UriError::UnsupportedRequiredParameter(key.to_string())),
+                _ => result = result.with_parameter(key, &value)?,
+            };
+        }
+
+        Ok(result)
+    }
+}
+
+impl<N: BitcoinNetwork> fmt::Display for BitcoinUri<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut parameters = vec![];
+        if let Some(amount) = self.amount {
+            parameters.push(format!("amount={}", format_amount(amount)));
+        }
+        if let Some(label) = &self.label {
+            parameters.push(format!("label={}", percent_encode(label)));
+        }
+        if let Some(message) = &self.message {
+            parameters.push(format!("message={}", percent_encode(message)));
+        }
+        for (key, value) in &self.parameters {
+            parameters.push(format!("{}={}", key, percent_encode(value)));
+        }
+
+        write!(f, "{}:{}", BITCOIN_URI_SCHEME, self.address)?;
+        if !parameters.is_empty() {
+            write!(f, "?{}", parameters.join("&"))?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the amount in bitcoin as a decimal, without trailing zeros, e.g. `0.001`.
+fn format_amount(amount: BitcoinAmount) -> String {
+    let coin = 10i64.pow(DECIMALS as u32);
+    let fraction = format!("{:0width$}", amount.0 % coin, width = DECIMALS);
+    let fraction = fraction.trim_end_matches('0');
+    match fraction.is_empty() {
+        true => format!("{}", amount.0 / coin),
+        false => format!("{}.{}", amount.0 / coin, fraction),
+    }
+}
+
+/// Returns the amount of the given decimal in bitcoin, with up to 8 decimal places.
+fn parse_amount(amount: &str) -> Result<BitcoinAmount, UriError> {
+    let (whole, fraction) = match amount.find('.') {
+        Some(position) => (&amount[..position], &amount[position + 1..]),
+        None => (amount, ""),
+    };
+    let is_digits = |digits: &str| digits.chars().all(|c| c.is_ascii_digit());
+    if (whole.is_empty() && fraction.is_empty())
+        || !is_digits(whole)
+        || !is_digits(fraction)
+        || fraction.len() > DECIMALS
+        || whole.len() > 8
+    {
+        return Err(UriError::InvalidAmount(amount.to_string()));
+    }
+
+    let whole = match whole.is_empty() {
+        true => 0,
+        false => i64::from_str(whole).map_err(|_| UriError::InvalidAmount(amount.to_string()))?,
+    };
+    let fraction = format!("{:0<width$}", fraction, width = DECIMALS);
+    let fraction = i64::from_str(&fraction).map_err(|_| UriError::InvalidAmount(amount.to_string()))?;
+    Ok(BitcoinAmount::from_satoshi(
+        whole * 10i64.pow(DECIMALS as u32) + fraction,
+    )?)
+}
+
+/// Returns true if the character is unreserved in a URI, and so is never percent-encoded.
+fn is_unreserved(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "-._~".contains(c)
+}
+
+/// Returns the value with the UTF-8 bytes of its reserved characters percent-encoded, e.g. a space as `%20`.
+fn percent_encode(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| match is_unreserved(c) {
+            true => c.to_string(),
+            false => c.to_string().bytes().map(|byte| format!("%{:02X}", byte)).collect(),
+        })
+        .collect()
+}
+
+/// Returns the UTF-8 value of the percent-encoded value.
+fn percent_decode(value: &str) -> Result<String, UriError> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' => {
+                let byte = value
+                    .get(i + 1..i + 3)
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                    .ok_or_else(|| UriError::InvalidEncoding(value.to_string()))?;
+                decoded.push(byte);
+                i += 3;
+            }
+            byte => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded).map_err(|_| UriError::InvalidEncoding(value.to_string()))
+}
+
+#[derive(Debug, Fail)]
+pub enum UriError {
+    #[fail(display = "{}", _0)]
+    AddressError(#[cause] AddressError),
+
+    #[fail(display = "{}", _0)]
+    AmountError(#[cause] AmountError),
+
+    #[fail(display = "duplicate URI parameter: {}", _0)]
+    DuplicateParameter(String),
+
+    #[fail(display = "invalid URI amount: {}", _0)]
+    InvalidAmount(String),
+
+    #[fail(display = "invalid URI encoding: {}", _0)]
+    InvalidEncoding(String),
+
+    #[fail(display = "invalid URI parameter: {}", _0)]
+    InvalidParameter(String),
+
+    #[fail(display = "invalid URI scheme: expected a \"bitcoin:\" URI, found {}", _0)]
+    InvalidScheme(String),
+
+    #[fail(display = "unsupported required URI parameter: {}", _0)]
+    UnsupportedRequiredParameter(String),
+}
+
+impl From<AddressError> for UriError {
+    fn from(error: AddressError) -> Self {
+        UriError::AddressError(error)
+    }
+}
+
+impl From<AmountError> for UriError {
+    fn from(error: AmountError) -> Self {
+        UriError::AmountError(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::*;
+
+    type N = Mainnet;
+
+    const ADDRESS: &str = "1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2";
+
+    fn address() -> BitcoinAddress<N> {
+        BitcoinAddress::from_str(ADDRESS).unwrap()
+    }
+
+    #[test]
+    fn to_string() {
+        assert_eq!(
+            "bitcoin:1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2",
+            BitcoinUri::new(address()).to_string()
+        );
+
+        // The examples of BIP21, with a valid address
+        let uri = BitcoinUri::new(address()).with_label("Luke-Jr");
+        assert_eq!(
+            "bitcoin:1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2?label=Luke-Jr",
+            uri.to_string()
+        );
+
+        let uri = BitcoinUri::new(address())
+            .with_amount(BitcoinAmount::from_satoshi(2_030_000_000).unwrap())
+            .unwrap()
+            .with_label("Luke-Jr");
+        assert_eq!(
+            "bitcoin:1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2?amount=20.3&label=Luke-Jr",
+            uri.to_string()
+        );
+
+        let uri = BitcoinUri::new(address())
+            .with_amount(BitcoinAmount::from_satoshi(5_000_000_000).unwrap())
+            .unwrap()
+            .with_label("Luke-Jr")
+            .with_message("Donation for project xyz");
+        assert_eq!(
+            "bitcoin:1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2?amount=50&label=Luke-Jr&message=Donation%20for%20project%20xyz",
+            uri.to_string()
+        );
+
+        let uri = BitcoinUri::new(address())
+            .with_amount(BitcoinAmount::ONE_SAT)
+            .unwrap()
+            .with_parameter("somethingyoudontunderstand", "50")
+            .unwrap();
+        assert_eq!(
+            "bitcoin:1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2?amount=0.00000001&somethingyoudontunderstand=50",
+            uri.to_string()
+        );
+    }
+
+    #[test]
+    fn from_str() {
+        let uri = BitcoinUri::<N>::from_str(
+            "bitcoin:1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2?amount=50&label=Luke-Jr&message=Donation%20for%20project%20xyz",
+        )
+        .unwrap();
+        assert_eq!(&address(), uri.address());
+        assert_eq!(Some(BitcoinAmount(5_000_000_000)), uri.amount());
+        assert_eq!(Some("Luke-Jr"), uri.label());
+        assert_eq!(Some("Donation for project xyz"), uri.message());
+
+        // Unknown parameters are passed through, unless they are required
+        let uri = BitcoinUri::<N>::from_str(
+            "BITCOIN:1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2?somethingyoudontunderstand=50&somethingelseyoudontget=999",
+        )
+        .unwrap();
+        assert_eq!(
+            &[
+                ("somethingyoudontunderstand".to_string(), "50".to_string()),
+                ("somethingelseyoudontget".to_string(), "999".to_string())
+            ],
+            uri.parameters()
+        );
+        assert_eq!(
+            "bitcoin:1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2?somethingyoudontunderstand=50&somethingelseyoudontget=999",
+            uri.to_string()
+        );
+        assert!(BitcoinUri::<N>::from_str(
+            "bitcoin:1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2?req-somethingyoudontunderstand=50"
+        )
+        .is_err());
+
+        // Uppercase bech32 addresses, as encoded in QR codes
+        let uri = BitcoinUri::<N>::from_str("BITCOIN:BC1QCR8TE4KR609GCAWUTMRZA0J4XV80JY8Z306FYU?amount=.001").unwrap();
+        assert_eq!("bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu", uri.address().to_string());
+        assert_eq!(Some(BitcoinAmount(100_000)), uri.amount());
+
+        // UTF-8 values
+        let uri = BitcoinUri::new(address()).with_message("Café ☕ & more");
+        assert_eq!(
+            "bitcoin:1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2?message=Caf%C3%A9%20%E2%98%95%20%26%20more",
+            uri.to_string()
+        );
+        assert_eq!(uri, BitcoinUri::<N>::from_str(&uri.to_string()).unwrap());
+    }
+
+    #[test]
+    fn invalid() {
+        let invalid = [
+            "1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2",
+            "litecoin:1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2",
+            "bitcoin:tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx",
+            "bitcoin:1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2?amount=1.000000001",
+            "bitcoin:1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2?amount=-1",
+            "bitcoin:1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2?amount=1e3",
+            "bitcoin:1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2?amount=21000001",
+            "bitcoin:1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2?amount=1&amount=2",
+            "bitcoin:1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2?label=%ZZ",
+            "bitcoin:1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2?label=%C3",
+        ];
+        for uri in invalid.iter() {
+            assert!(BitcoinUri::<N>::from_str(uri).is_err(), "{}", uri);
+        }
+
+        assert!(BitcoinUri::new(address()).with_amount(BitcoinAmount(-1)).is_err());
+        assert!(BitcoinUri::new(address()).with_parameter("label", "x").is_err());
+        assert!(BitcoinUri::new(address()).with_parameter("a b", "x").is_err());
+    }
+
+    #[test]
+    fn amount() {
+        let amounts = [
+            ("0", 0),
+            ("1", 100_000_000),
+            ("0.1", 10_000_000),
+            ("20.3", 2_030_000_000),
+            ("0.00000001", 1),
+            ("21000000", 2_100_000_000_000_000),
+        ];
+        for (amount, satoshis) in amounts.iter() {
+            assert_eq!(BitcoinAmount(*satoshis), parse_amount(amount).unwrap());
+            assert_eq!(*amount, format_amount(BitcoinAmount(*satoshis)));
+        }
+    }
+}
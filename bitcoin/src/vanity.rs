@@ -0,0 +1,485 @@
+use crate::address::BitcoinAddress;
+use crate::format::BitcoinFormat;
+use crate::network::BitcoinNetwork;
+use crate::private_key::BitcoinPrivateKey;
+use wagyu_model::{no_std::*, AddressError, PrivateKey, PrivateKeyError};
+
+use core::{fmt, marker::PhantomData};
+use rand::{Rng, SeedableRng};
+use regex::Regex;
+use std::sync::{
+    atomic::{AtomicBool, AtomicU64, Ordering},
+    mpsc::{self, RecvTimeoutError},
+};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// The characters of a base58 encoding, in order
+const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+/// The characters of a bech32 encoding
+const BECH32_CHARSET: &str = "qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+/// The number of attempts a worker makes between updates of the shared attempt counter
+const ATTEMPTS_PER_UPDATE: u64 = 256;
+/// The interval between progress reports of a search
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+/// A private key and its address matching a vanity pattern
+type VanityMatch<N> = (BitcoinPrivateKey<N>, BitcoinAddress<N>);
+
+/// Represents the pattern a vanity address must match
+#[derive(Debug, Clone)]
+pub enum VanityPattern {
+    /// The address starts with the given prefix
+    Prefix(String),
+    /// The address matches the given regular expression
+    Regex(Regex),
+}
+
+impl fmt::Display for VanityPattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            VanityPattern::Prefix(prefix) => write!(f, "{}", prefix),
+            VanityPattern::Regex(regex) => write!(f, "{}", regex),
+        }
+    }
+}
+
+/// Represents the progress of a vanity address search
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VanityProgress {
+    /// The number of keys generated so far
+    pub attempts: u64,
+    /// The time elapsed since the search started
+    pub elapsed: Duration,
+}
+
+impl VanityProgress {
+    /// Returns the number of keys generated per second.
+    pub fn rate(&self) -> f64 {
+        match self.elapsed.as_secs_f64() {
+            seconds if seconds > 0.0 => self.attempts as f64 / seconds,
+            _ => 0.0,
+        }
+    }
+}
+
+/// Represents a search for a Bitcoin address matching a vanity pattern,
+/// by generating random private keys across worker threads.
+#[derive(Debug, Clone)]
+pub struct BitcoinVanitySearch<N: BitcoinNetwork> {
+    /// The pattern the address must match
+    pattern: VanityPattern,
+    /// The format of the address
+    format: BitcoinFormat,
+    /// The number of worker threads
+    threads: usize,
+    /// PhantomData
+    _network: PhantomData<N>,
+}
+
+impl<N: BitcoinNetwork> BitcoinVanitySearch<N> {
+    /// Returns a search for an address of the given format starting with the given prefix.
+    /// Bech32 prefixes are matched case-insensitively, as bech32 addresses are lowercase.
+    pub fn prefix(prefix: &str, format: &BitcoinFormat) -> Result<Self, VanityError> {
+        let prefix = match format {
+            BitcoinFormat::P2PKH | BitcoinFormat::P2SH_P2WPKH => {
+                Self::validate_base58_prefix(prefix, format)?;
+                prefix.to_string()
+            }
+            BitcoinFormat::Bech32 | BitcoinFormat::P2TR => {
+                let prefix = prefix.to_lowercase();
+                Self::validate_bech32_prefix(&prefix, format)?;
+                prefix
+            }
+            BitcoinFormat::P2WSH | BitcoinFormat::FutureWitness => {
+                return Err(VanityError::UnsupportedFormat(format.to_string()))
+            }
+        };
+        Self::new(VanityPattern::Prefix(prefix), format)
+    }
+
+    /// Returns a search for an address of the given format matching the given regular expression.
+    pub fn regex(regex: &str, format: &BitcoinFormat) -> Result<Self, VanityError> {
+        let regex = Regex::new(regex).map_err(|error| VanityError::InvalidRegex(error.to_string()))?;
+        Self::new(VanityPattern::Regex(regex), format)
+    }
+
+    fn new(pattern: VanityPattern, format: &BitcoinFormat) -> Result<Self, VanityError> {
+        if let BitcoinFormat::P2WSH = format {
+            return Err(VanityError::UnsupportedFormat(format.to_string()));
+        }
+        Ok(Self {
+            pattern,
+            format: format.clone(),
+            threads: thread::available_parallelism().map(usize::from).unwrap_or(1),
+            _network: PhantomData,
+        })
+    }
+
+    /// Returns the search with the given number of worker threads,
+    /// or with one worker thread per available core if the given number is zero.
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = match threads {
+            0 => thread::available_parallelism().map(usize::from).unwrap_or(1),
+            threads => threads,
+        };
+        self
+    }
+
+    /// Returns the pattern of the search.
+    pub fn pattern(&self) -> &VanityPattern {
+        &self.pattern
+    }
+
+    /// Returns the address format of the search.
+    pub fn format(&self) -> BitcoinFormat {
+        self.format.clone()
+    }
+
+    /// Returns the number of worker threads of the search.
+    pub fn threads(&self) -> usize {
+        self.threads
+    }
+
+    /// Returns the approximate number of keys expected to be generated before a match is found,
+    /// or `None` for a regular expression, whose difficulty cannot be estimated.
+    pub fn difficulty(&self) -> Option<f64> {
+        let prefix = match &self.pattern {
+            VanityPattern::Prefix(prefix) => prefix,
+            VanityPattern::Regex(_) => return None,
+        };
+        match self.format {
+            BitcoinFormat::P2PKH | BitcoinFormat::P2SH_P2WPKH => {
+                Some(1.0 / Self::base58_probability(prefix, &self.format))
+            }
+            _ => {
+                let fixed = Self::bech32_fixed_prefix(&self.format).len();
+                Some(32f64.powi(prefix.len().saturating_sub(fixed) as i32))
+            }
+        }
+    }
+
+    /// Returns the expected duration of the search at the given number of keys generated per second,
+    /// or `None` if the difficulty cannot be estimated or the rate is not positive.
+    pub fn expected_duration(&self, rate: f64) -> Option<Duration> {
+        match (self.difficulty(), rate > 0.0) {
+            (Some(difficulty), true) => Some(Duration::from_secs_f64(difficulty / rate)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the given address matches the pattern of the search.
+    pub fn matches(&self, address: &str) -> bool {
+        match &self.pattern {
+            VanityPattern::Prefix(prefix) => address.starts_with(prefix.as_str()),
+            VanityPattern::Regex(regex) => regex.is_match(address),
+        }
+    }
+
+    /// Searches for a private key whose address matches the pattern, seeding the random number
+    /// generator of each worker thread from the given one. The given closure is called with the
+    /// progress of the search periodically, until a match is found.
+    pub fn search<R: Rng + SeedableRng + Send, F: FnMut(&VanityProgress)>(
+        &self,
+        rng: &mut R,
+        mut progress: F,
+    ) -> Result<VanityMatch<N>, VanityError> {
+        let rngs = (0..self.threads.max(1))
+            .map(|_| R::from_rng(&mut *rng).map_err(|error| VanityError::Message(error.to_string())))
+            .collect::<Result<Vec<R>, _>>()?;
+
+        let found = AtomicBool::new(false);
+        let attempts = AtomicU64::new(0);
+        let start = Instant::now();
+
+        thread::scope(|scope| {
+            let (sender, receiver) = mpsc::channel();
+            for mut rng in rngs {
+                let sender = sender.clone();
+                let (found, attempts) = (&found, &attempts);
+                scope.spawn(move || {
+                    if let Some(result) = self.search_worker(&mut rng, found, attempts) {
+                        found.store(true, Ordering::Relaxed);
+                        let _ = sender.send(result);
+                    }
+                });
+            }
+            drop(sender);
+
+            loop {
+                match receiver.recv_timeout(PROGRESS_INTERVAL) {
+                    Ok(result) => return result,
+                    Err(RecvTimeoutError::Timeout) => progress(&VanityProgress {
+                        attempts: attempts.load(Ordering::Relaxed),
+                        elapsed: start.elapsed(),
+                    }),
+                    Err(RecvTimeoutError::Disconnected) => {
+                        return Err(VanityError::Message("all search threads stopped".into()))
+                    }
+                }
+            }
+        })
+    }
+
+    /// Generates private keys until one matches the pattern or another worker finds a match.
+    fn search_worker<R: Rng>(
+        &self,
+        rng: &mut R,
+        found: &AtomicBool,
+        attempts: &AtomicU64,
+    ) -> Option<Result<VanityMatch<N>, VanityError>> {
+        let mut count = 0;
+        while !found.load(Ordering::Relaxed) {
+            let result = BitcoinPrivateKey::<N>::new(rng)
+                .map_err(VanityError::from)
+                .and_then(|private_key| Ok((private_key.to_address(&self.format)?, private_key)));
+            let (address, private_key) = match result {
+                Ok(keypair) => keypair,
+                Err(error) => return Some(Err(error)),
+            };
+
+            count += 1;
+            if count == ATTEMPTS_PER_UPDATE {
+                attempts.fetch_add(count, Ordering::Relaxed);
+                count = 0;
+            }
+            if self.matches(&address.to_string()) {
+                attempts.fetch_add(count, Ordering::Relaxed);
+                return Some(Ok((private_key, address)));
+            }
+        }
+        None
+    }
+
+    /// Returns the approximate probability that a random base58 address of the given format starts
+    /// with the given prefix, by measuring the range of address values the prefix covers.
+    fn base58_probability(prefix: &str, format: &BitcoinFormat) -> f64 {
+        let digits = prefix
+            .chars()
+            .filter_map(|character| BASE58_ALPHABET.find(character))
+            .collect::<Vec<usize>>();
+        let version = N::to_address_prefix(format)[0];
+
+        // Each leading zero byte of an address is encoded as a leading '1',
+        // and the remaining bytes are encoded as a base58 number.
+        let (digits, probability, lowest, highest) = match version {
+            0 => {
+                if digits.first() != Some(&0) {
+                    return 0.0;
+                }
+                let zeros = digits.iter().take_while(|digit| **digit == 0).count() - 1;
+                let bits = 192i32 - 8 * zeros as i32;
+                (&digits[zeros + 1..], 256f64.powi(-(zeros as i32)), 0.0, 2f64.powi(bits))
+            }
+            version => {
+                let lowest = version as f64 * 2f64.powi(192);
+                (&digits[..], 1.0, lowest, lowest + 2f64.powi(192))
+            }
+        };
+        if digits.is_empty() {
+            return probability;
+        }
+        if digits[0] == 0 {
+            return 0.0;
+        }
+
+        // Sums the ranges of values of each length whose encoding starts with the prefix.
+        let value = digits.iter().fold(0.0, |value, digit| value * 58.0 + *digit as f64);
+        let matches = (0..=35 - digits.len() as i32)
+            .map(|length| {
+                let width = 58f64.powi(length);
+                let (start, end) = (value * width, (value + 1.0) * width);
+                match (start >= lowest, end <= highest) {
+                    (true, true) => width,
+                    _ => (end.min(highest) - start.max(lowest)).max(0.0),
+                }
+            })
+            .sum::<f64>();
+        probability * matches / (highest - lowest)
+    }
+
+    /// Returns the human-readable part, separator, and witness version every address of the given format starts with.
+    fn bech32_fixed_prefix(format: &BitcoinFormat) -> String {
+        let hrp = String::from_utf8(N::to_address_prefix(format)).unwrap_or_default();
+        match format {
+            BitcoinFormat::P2TR => format!("{}1p", hrp),
+            _ => format!("{}1q", hrp),
+        }
+    }
+
+    fn validate_base58_prefix(prefix: &str, format: &BitcoinFormat) -> Result<(), VanityError> {
+        if prefix.is_empty() {
+            return Err(VanityError::InvalidPrefix(prefix.into(), "the prefix is empty".into()));
+        }
+        if let Some(character) = prefix.chars().find(|character| !BASE58_ALPHABET.contains(*character)) {
+            return Err(VanityError::InvalidPrefix(
+                prefix.into(),
+                format!("'{}' is not a base58 character", character),
+            ));
+        }
+        if prefix.len() > 34 {
+            return Err(VanityError::InvalidPrefix(
+                prefix.into(),
+                "the prefix is too long".into(),
+            ));
+        }
+
+        match Self::base58_probability(prefix, format) > 0.0 {
+            true => Ok(()),
+            false => Err(VanityError::InvalidPrefix(
+                prefix.into(),
+                format!("no {} {} address starts with this prefix", N::NAME, format),
+            )),
+        }
+    }
+
+    fn validate_bech32_prefix(prefix: &str, format: &BitcoinFormat) -> Result<(), VanityError> {
+        let fixed = Self::bech32_fixed_prefix(format);
+        if !prefix.starts_with(&fixed) {
+            return Err(VanityError::InvalidPrefix(
+                prefix.into(),
+                format!("{} {} addresses start with '{}'", N::NAME, format, fixed),
+            ));
+        }
+        if let Some(character) = prefix[fixed.len()..]
+            .chars()
+            .find(|character| !BECH32_CHARSET.contains(*character))
+        {
+            return Err(VanityError::InvalidPrefix(
+                prefix.into(),
+                format!("'{}' is not a bech32 character", character),
+            ));
+        }
+        let length = match format {
+            BitcoinFormat::P2TR => fixed.len() + 58,
+            _ => fixed.len() + 38,
+        };
+        match prefix.len() > length {
+            true => Err(VanityError::InvalidPrefix(
+                prefix.into(),
+                "the prefix is too long".into(),
+            )),
+            false => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Fail)]
+pub enum VanityError {
+    #[fail(display = "{}", _0)]
+    AddressError(#[cause] AddressError),
+
+    #[fail(display = "invalid vanity prefix {}: {}", _0, _1)]
+    InvalidPrefix(String, String),
+
+    #[fail(display = "invalid vanity regex: {}", _0)]
+    InvalidRegex(String),
+
+    #[fail(display = "{}", _0)]
+    Message(String),
+
+    #[fail(display = "{}", _0)]
+    PrivateKeyError(#[cause] PrivateKeyError),
+
+    #[fail(display = "unsupported format: {}", _0)]
+    UnsupportedFormat(String),
+}
+
+impl From<AddressError> for VanityError {
+    fn from(error: AddressError) -> Self {
+        VanityError::AddressError(error)
+    }
+}
+
+impl From<PrivateKeyError> for VanityError {
+    fn from(error: PrivateKeyError) -> Self {
+        VanityError::PrivateKeyError(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::*;
+
+    use rand_xorshift::XorShiftRng;
+
+    fn test_search<N: BitcoinNetwork>(search: &BitcoinVanitySearch<N>) {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        let (private_key, address) = search.search(rng, |_| ()).unwrap();
+        assert!(search.matches(&address.to_string()));
+        assert_eq!(address, private_key.to_address(&search.format()).unwrap());
+    }
+
+    #[test]
+    fn prefix() {
+        test_search(&BitcoinVanitySearch::<Mainnet>::prefix("1A", &BitcoinFormat::P2PKH).unwrap());
+        test_search(&BitcoinVanitySearch::<Mainnet>::prefix("3Q", &BitcoinFormat::P2SH_P2WPKH).unwrap());
+        test_search(&BitcoinVanitySearch::<Mainnet>::prefix("BC1QZ", &BitcoinFormat::Bech32).unwrap());
+        test_search(&BitcoinVanitySearch::<Testnet>::prefix("tb1p0", &BitcoinFormat::P2TR).unwrap());
+        test_search(
+            &BitcoinVanitySearch::<Testnet>::prefix("n", &BitcoinFormat::P2PKH)
+                .unwrap()
+                .with_threads(1),
+        );
+    }
+
+    #[test]
+    fn regex() {
+        test_search(&BitcoinVanitySearch::<Mainnet>::regex("[0-9]{3}$", &BitcoinFormat::P2PKH).unwrap());
+        test_search(&BitcoinVanitySearch::<Mainnet>::regex("^bc1q.*xy", &BitcoinFormat::Bech32).unwrap());
+    }
+
+    #[test]
+    fn difficulty() {
+        let difficulty = |prefix: &str, format: &BitcoinFormat| {
+            BitcoinVanitySearch::<Mainnet>::prefix(prefix, format)
+                .unwrap()
+                .difficulty()
+                .unwrap()
+                .round()
+        };
+        assert_eq!(1.0, difficulty("1", &BitcoinFormat::P2PKH));
+        assert_eq!(23.0, difficulty("1A", &BitcoinFormat::P2PKH));
+        assert_eq!(256.0, difficulty("11", &BitcoinFormat::P2PKH));
+        assert_eq!(1.0, difficulty("3", &BitcoinFormat::P2SH_P2WPKH));
+        assert_eq!(23.0, difficulty("32", &BitcoinFormat::P2SH_P2WPKH));
+
+        let search = BitcoinVanitySearch::<Mainnet>::prefix("bc1qxy", &BitcoinFormat::Bech32).unwrap();
+        assert_eq!(Some(32f64.powi(2)), search.difficulty());
+        assert_eq!(Some(Duration::from_secs(2)), search.expected_duration(512.0));
+
+        let search = BitcoinVanitySearch::<Mainnet>::regex("xy", &BitcoinFormat::Bech32).unwrap();
+        assert_eq!(None, search.difficulty());
+        assert_eq!(None, search.expected_duration(512.0));
+    }
+
+    #[test]
+    fn invalid_prefix() {
+        type N = Mainnet;
+
+        assert!(BitcoinVanitySearch::<N>::prefix("", &BitcoinFormat::P2PKH).is_err());
+        assert!(BitcoinVanitySearch::<N>::prefix("1Love0", &BitcoinFormat::P2PKH).is_err());
+        assert!(BitcoinVanitySearch::<N>::prefix("3Love", &BitcoinFormat::P2PKH).is_err());
+        assert!(BitcoinVanitySearch::<N>::prefix("1Love", &BitcoinFormat::P2SH_P2WPKH).is_err());
+        assert!(BitcoinVanitySearch::<N>::prefix("3x", &BitcoinFormat::P2SH_P2WPKH).is_err());
+        assert!(BitcoinVanitySearch::<N>::prefix("bc1pqq", &BitcoinFormat::Bech32).is_err());
+        assert!(BitcoinVanitySearch::<N>::prefix("bc1qb", &BitcoinFormat::Bech32).is_err());
+        assert!(BitcoinVanitySearch::<N>::prefix("tb1q", &BitcoinFormat::Bech32).is_err());
+        assert!(BitcoinVanitySearch::<N>::prefix("1", &BitcoinFormat::P2WSH).is_err());
+        assert!(BitcoinVanitySearch::<N>::regex("(", &BitcoinFormat::P2PKH).is_err());
+    }
+
+    #[test]
+    fn progress() {
+        let search = BitcoinVanitySearch::<Mainnet>::prefix("1AA", &BitcoinFormat::P2PKH)
+            .unwrap()
+            .with_threads(2);
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+
+        let mut reports = vec![];
+        let (_, address) = search.search(rng, |progress| reports.push(*progress)).unwrap();
+        assert!(address.to_string().starts_with("1AA"));
+        assert!(reports.windows(2).all(|pair| pair[0].attempts <= pair[1].attempts));
+    }
+}
@@ -21,7 +21,7 @@
 //!
 
 use wagyu_model::no_std::*;
-use wagyu_model::{AddressError, TransactionError};
+use wagyu_model::{AddressError, CrateError, TransactionError};
 
 use core::str::FromStr;
 
@@ -45,13 +45,13 @@ pub enum WitnessProgramError {
 
 impl From<WitnessProgramError> for AddressError {
     fn from(error: WitnessProgramError) -> Self {
-        AddressError::Crate("WitnessProgram", format!("{:?}", error))
+        AddressError::Crate(CrateError::Other("WitnessProgram", format!("{:?}", error)))
     }
 }
 
 impl From<WitnessProgramError> for TransactionError {
     fn from(error: WitnessProgramError) -> Self {
-        TransactionError::Crate("WitnessProgram", format!("{:?}", error))
+        TransactionError::Crate(CrateError::Other("WitnessProgram", format!("{:?}", error)))
     }
 }
 
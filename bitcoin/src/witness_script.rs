@@ -0,0 +1,119 @@
+use crate::address::BitcoinAddress;
+use crate::network::BitcoinNetwork;
+use wagyu_model::no_std::*;
+use wagyu_model::AddressError;
+
+use core::{fmt, str::FromStr};
+use sha2::{Digest, Sha256};
+
+/// The maximum size of a witness script in bytes
+/// https://github.com/bitcoin/bips/blob/master/bip-0141.mediawiki#witness-program
+pub const MAX_WITNESS_SCRIPT_SIZE: usize = 10_000;
+
+/// Represents a Bitcoin witness script, e.g. a multisig or timelock script, spendable as a P2WSH output
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitcoinWitnessScript {
+    /// The serialized witness script
+    script: Vec<u8>,
+}
+
+impl BitcoinWitnessScript {
+    /// Returns a witness script given its serialized bytes.
+    pub fn new(script: &[u8]) -> Result<Self, AddressError> {
+        if script.is_empty() || script.len() > MAX_WITNESS_SCRIPT_SIZE {
+            return Err(AddressError::InvalidByteLength(script.len()));
+        }
+        Ok(Self {
+            script: script.to_vec(),
+        })
+    }
+
+    /// Returns the SHA256 hash of the witness script, the program of its P2WSH output.
+    pub fn to_script_hash(&self) -> [u8; 32] {
+        let mut script_hash = [0u8; 32];
+        script_hash.copy_from_slice(&Sha256::digest(&self.script));
+        script_hash
+    }
+
+    /// Returns the P2WSH address of the witness script for the given network.
+    pub fn to_address<N: BitcoinNetwork>(&self) -> Result<BitcoinAddress<N>, AddressError> {
+        BitcoinAddress::<N>::p2wsh(&self.script)
+    }
+
+    /// Returns the P2WSH scriptPubKey of the witness script, OP_0 followed by the push of its script hash.
+    pub fn to_script_pub_key(&self) -> Vec<u8> {
+        let mut script_pub_key = vec![0x00, 0x20];
+        script_pub_key.extend_from_slice(&self.to_script_hash());
+        script_pub_key
+    }
+
+    /// Returns the serialized witness script.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.script.clone()
+    }
+}
+
+impl FromStr for BitcoinWitnessScript {
+    type Err = AddressError;
+
+    fn from_str(script: &str) -> Result<Self, Self::Err> {
+        match hex::decode(script) {
+            Ok(script) => Self::new(&script),
+            Err(_) => Err(AddressError::Message(format!("invalid witness script hex: {}", script))),
+        }
+    }
+}
+
+impl fmt::Display for BitcoinWitnessScript {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", hex::encode(&self.script))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::*;
+    use crate::transaction::create_script_pub_key;
+
+    // https://github.com/bitcoin/bips/blob/master/bip-0173.mediawiki#examples
+    const SCRIPT: &str = "210279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798ac";
+    const SCRIPT_PUB_KEY: &str = "00201863143c14c5166804bd19203356da136c985678cd4d27a1b8c6329604903262";
+
+    #[test]
+    fn to_address() {
+        let witness_script = BitcoinWitnessScript::from_str(SCRIPT).unwrap();
+        assert_eq!(
+            "bc1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3qccfmv3",
+            witness_script.to_address::<Mainnet>().unwrap().to_string()
+        );
+        assert_eq!(
+            "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7",
+            witness_script.to_address::<Testnet>().unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn to_script_pub_key() {
+        let witness_script = BitcoinWitnessScript::from_str(SCRIPT).unwrap();
+        assert_eq!(SCRIPT_PUB_KEY, hex::encode(witness_script.to_script_pub_key()));
+
+        let address = witness_script.to_address::<Mainnet>().unwrap();
+        assert_eq!(
+            witness_script.to_script_pub_key(),
+            create_script_pub_key(&address).unwrap()
+        );
+    }
+
+    #[test]
+    fn to_string() {
+        assert_eq!(SCRIPT, BitcoinWitnessScript::from_str(SCRIPT).unwrap().to_string());
+    }
+
+    #[test]
+    fn invalid_length() {
+        assert!(BitcoinWitnessScript::new(&[]).is_err());
+        assert!(BitcoinWitnessScript::new(&[0x51; MAX_WITNESS_SCRIPT_SIZE]).is_ok());
+        assert!(BitcoinWitnessScript::new(&[0x51; MAX_WITNESS_SCRIPT_SIZE + 1]).is_err());
+    }
+}
@@ -36,6 +36,11 @@ mod tests {
         assert!(ChineseSimplified::get_index(INVALID_WORD).is_err());
     }
 
+    #[test]
+    fn validate() {
+        assert!(ChineseSimplified::validate().is_ok());
+    }
+
     #[test]
     fn get_all() {
         let list = ChineseSimplified::get_all();
@@ -36,6 +36,11 @@ mod tests {
         assert!(ChineseTraditional::get_index(INVALID_WORD).is_err());
     }
 
+    #[test]
+    fn validate() {
+        assert!(ChineseTraditional::validate().is_ok());
+    }
+
     #[test]
     fn get_all() {
         let list = ChineseTraditional::get_all();
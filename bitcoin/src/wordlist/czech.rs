@@ -0,0 +1,50 @@
+use crate::wordlist::BitcoinWordlist;
+use wagyu_model::{bip39::CZECH, wordlist::Wordlist};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Czech;
+
+impl Wordlist for Czech {}
+
+impl BitcoinWordlist for Czech {
+    /// The wordlist in original form.
+    const WORDLIST: &'static str = CZECH;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VALID_WORD: &str = "skica";
+    const VALID_WORD_INDEX: usize = 1523;
+    const INVALID_WORD: &str = "ahoj";
+    const INVALID_WORD_INDEX: usize = 3400;
+
+    #[test]
+    fn get() {
+        // Valid case
+        assert_eq!(VALID_WORD, Czech::get(VALID_WORD_INDEX).unwrap());
+        // Invalid case
+        assert!(Czech::get(INVALID_WORD_INDEX).is_err());
+    }
+
+    #[test]
+    fn get_index() {
+        // Valid case
+        assert_eq!(VALID_WORD_INDEX, Czech::get_index(VALID_WORD).unwrap());
+        // Invalid case
+        assert!(Czech::get_index(INVALID_WORD).is_err());
+    }
+
+    #[test]
+    fn validate() {
+        assert!(Czech::validate().is_ok());
+    }
+
+    #[test]
+    fn get_all() {
+        let list = Czech::get_all();
+        assert_eq!(2048, list.len());
+        assert_eq!(VALID_WORD, list[VALID_WORD_INDEX]);
+    }
+}
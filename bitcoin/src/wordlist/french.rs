@@ -36,6 +36,11 @@ mod tests {
         assert!(French::get_index(INVALID_WORD).is_err());
     }
 
+    #[test]
+    fn validate() {
+        assert!(French::validate().is_ok());
+    }
+
     #[test]
     fn get_all() {
         let list = French::get_all();
@@ -36,6 +36,11 @@ mod tests {
         assert!(Italian::get_index(INVALID_WORD).is_err());
     }
 
+    #[test]
+    fn validate() {
+        assert!(Italian::validate().is_ok());
+    }
+
     #[test]
     fn get_all() {
         let list = Italian::get_all();
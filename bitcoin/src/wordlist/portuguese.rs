@@ -0,0 +1,50 @@
+use crate::wordlist::BitcoinWordlist;
+use wagyu_model::{bip39::PORTUGUESE, wordlist::Wordlist};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Portuguese;
+
+impl Wordlist for Portuguese {}
+
+impl BitcoinWordlist for Portuguese {
+    /// The wordlist in original form.
+    const WORDLIST: &'static str = PORTUGUESE;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VALID_WORD: &str = "plumagem";
+    const VALID_WORD_INDEX: usize = 1523;
+    const INVALID_WORD: &str = "bomdia";
+    const INVALID_WORD_INDEX: usize = 3400;
+
+    #[test]
+    fn get() {
+        // Valid case
+        assert_eq!(VALID_WORD, Portuguese::get(VALID_WORD_INDEX).unwrap());
+        // Invalid case
+        assert!(Portuguese::get(INVALID_WORD_INDEX).is_err());
+    }
+
+    #[test]
+    fn get_index() {
+        // Valid case
+        assert_eq!(VALID_WORD_INDEX, Portuguese::get_index(VALID_WORD).unwrap());
+        // Invalid case
+        assert!(Portuguese::get_index(INVALID_WORD).is_err());
+    }
+
+    #[test]
+    fn validate() {
+        assert!(Portuguese::validate().is_ok());
+    }
+
+    #[test]
+    fn get_all() {
+        let list = Portuguese::get_all();
+        assert_eq!(2048, list.len());
+        assert_eq!(VALID_WORD, list[VALID_WORD_INDEX]);
+    }
+}
@@ -36,6 +36,11 @@ mod tests {
         assert!(Spanish::get_index(INVALID_WORD).is_err());
     }
 
+    #[test]
+    fn validate() {
+        assert!(Spanish::validate().is_ok());
+    }
+
     #[test]
     fn get_all() {
         let list = Spanish::get_all();
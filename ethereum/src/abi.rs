@@ -0,0 +1,654 @@
+use crate::address::EthereumAddress;
+use wagyu_model::AddressError;
+
+use core::{fmt, str::FromStr};
+use ethereum_types::U256;
+use serde_json::Value;
+use tiny_keccak::keccak256;
+
+/// Represents a type of the contract ABI
+/// https://docs.soliditylang.org/en/latest/abi-spec.html
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AbiType {
+    /// A 20-byte address
+    Address,
+    /// A boolean
+    Bool,
+    /// A dynamic sequence of bytes
+    Bytes,
+    /// A sequence of 1 to 32 bytes
+    FixedBytes(usize),
+    /// A signed integer of the number of bits
+    Int(usize),
+    /// A dynamic UTF-8 string
+    String,
+    /// An unsigned integer of the number of bits
+    Uint(usize),
+    /// A dynamic array of elements of the type
+    Array(Box<AbiType>),
+    /// An array of the number of elements of the type
+    FixedArray(Box<AbiType>, usize),
+    /// A tuple of the types
+    Tuple(Vec<AbiType>),
+}
+
+impl AbiType {
+    /// Returns `true` if the encoding of the type has a dynamic size, which places it in the tail.
+    pub fn is_dynamic(&self) -> bool {
+        match self {
+            AbiType::Bytes | AbiType::String | AbiType::Array(_) => true,
+            AbiType::FixedArray(element, _) => element.is_dynamic(),
+            AbiType::Tuple(components) => components.iter().any(AbiType::is_dynamic),
+            _ => false,
+        }
+    }
+
+    /// Returns the size of the type in the head of an encoding, which is an offset for a dynamic type.
+    fn head_size(&self) -> usize {
+        match self {
+            _ if self.is_dynamic() => 32,
+            AbiType::FixedArray(element, length) => element.head_size() * length,
+            AbiType::Tuple(components) => components.iter().map(AbiType::head_size).sum(),
+            _ => 32,
+        }
+    }
+
+    /// Returns the value of the type given its JSON representation.
+    /// Integers are decimal or `0x` hexadecimal strings or JSON numbers, bytes are hexadecimal strings,
+    /// and arrays and tuples are JSON arrays.
+    pub fn value_from_json(&self, json: &Value) -> Result<AbiValue, AbiError> {
+        let invalid = || AbiError::InvalidValue(self.to_string(), json.to_string());
+        let elements = |length: Option<usize>| match json.as_array() {
+            Some(elements) if length.map_or(true, |length| length == elements.len()) => Ok(elements),
+            _ => Err(invalid()),
+        };
+        match self {
+            AbiType::Address => match json.as_str() {
+                Some(address) => Ok(AbiValue::Address(EthereumAddress::from_str(address)?)),
+                None => Err(invalid()),
+            },
+            AbiType::Bool => match json {
+                Value::Bool(value) => Ok(AbiValue::Bool(*value)),
+                Value::String(value) => bool::from_str(value).map(AbiValue::Bool).map_err(|_| invalid()),
+                _ => Err(invalid()),
+            },
+            AbiType::Bytes | AbiType::FixedBytes(_) => {
+                let bytes = match json.as_str() {
+                    Some(hex) if hex.starts_with("0x") => hex::decode(&hex[2..]).map_err(|_| invalid())?,
+                    Some(hex) => hex::decode(hex).map_err(|_| invalid())?,
+                    None => return Err(invalid()),
+                };
+                match self {
+                    AbiType::FixedBytes(_) => Ok(AbiValue::FixedBytes(bytes)),
+                    _ => Ok(AbiValue::Bytes(bytes)),
+                }
+            }
+            AbiType::Int(_) => {
+                let (negative, magnitude) = match json {
+                    Value::Number(number) => match number.as_i64() {
+                        Some(value) => (value < 0, U256::from(value.unsigned_abs())),
+                        None => return Err(invalid()),
+                    },
+                    Value::String(value) if value.starts_with('-') => {
+                        (true, parse_uint(&value[1..]).ok_or_else(invalid)?)
+                    }
+                    Value::String(value) => (false, parse_uint(value).ok_or_else(invalid)?),
+                    _ => return Err(invalid()),
+                };
+                match negative {
+                    // The two's complement of the magnitude, which is rejected if it overflows to a positive integer
+                    true if magnitude > U256::one() << 255 => Err(invalid()),
+                    true => Ok(AbiValue::Int((!magnitude).overflowing_add(U256::one()).0)),
+                    false => Ok(AbiValue::Int(magnitude)),
+                }
+            }
+            AbiType::String => match json.as_str() {
+                Some(value) => Ok(AbiValue::String(value.to_string())),
+                None => Err(invalid()),
+            },
+            AbiType::Uint(_) => match json {
+                Value::Number(number) => number
+                    .as_u64()
+                    .map(|value| AbiValue::Uint(U256::from(value)))
+                    .ok_or_else(invalid),
+                Value::String(value) => parse_uint(value).map(AbiValue::Uint).ok_or_else(invalid),
+                _ => Err(invalid()),
+            },
+            AbiType::Array(element) => Ok(AbiValue::Array(
+                elements(None)?
+                    .iter()
+                    .map(|json| element.value_from_json(json))
+                    .collect::<Result<_, _>>()?,
+            )),
+            AbiType::FixedArray(element, length) => Ok(AbiValue::Array(
+                elements(Some(*length))?
+                    .iter()
+                    .map(|json| element.value_from_json(json))
+                    .collect::<Result<_, _>>()?,
+            )),
+            AbiType::Tuple(components) => Ok(AbiValue::Tuple(
+                components
+                    .iter()
+                    .zip(elements(Some(components.len()))?)
+                    .map(|(component, json)| component.value_from_json(json))
+                    .collect::<Result<_, _>>()?,
+            )),
+        }
+    }
+}
+
+impl FromStr for AbiType {
+    type Err = AbiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || AbiError::InvalidType(s.to_string());
+
+        // The last array suffix is the outermost array, so `uint256[2][]` is a dynamic array of `uint256[2]`
+        if s.ends_with(']') {
+            let open = s.rfind('[').ok_or_else(invalid)?;
+            let element = Box::new(AbiType::from_str(&s[..open])?);
+            return match &s[open + 1..s.len() - 1] {
+                "" => Ok(AbiType::Array(element)),
+                length => match usize::from_str(length) {
+                    Ok(size) if size > 0 && size.to_string() == length => Ok(AbiType::FixedArray(element, size)),
+                    _ => Err(invalid()),
+                },
+            };
+        }
+        if s.starts_with('(') && s.ends_with(')') {
+            return Ok(AbiType::Tuple(
+                split_components(&s[1..s.len() - 1])
+                    .ok_or_else(invalid)?
+                    .into_iter()
+                    .map(AbiType::from_str)
+                    .collect::<Result<_, _>>()?,
+            ));
+        }
+
+        let size = |prefix: &str, min: usize, max: usize, step: usize| match usize::from_str(&s[prefix.len()..]) {
+            Ok(size) if size >= min && size <= max && size % step == 0 && size.to_string() == s[prefix.len()..] => {
+                Ok(size)
+            }
+            _ => Err(invalid()),
+        };
+        match s {
+            "address" => Ok(AbiType::Address),
+            "bool" => Ok(AbiType::Bool),
+            "bytes" => Ok(AbiType::Bytes),
+            "int" => Ok(AbiType::Int(256)),
+            "string" => Ok(AbiType::String),
+            "uint" => Ok(AbiType::Uint(256)),
+            _ if s.starts_with("bytes") => Ok(AbiType::FixedBytes(size("bytes", 1, 32, 1)?)),
+            _ if s.starts_with("int") => Ok(AbiType::Int(size("int", 8, 256, 8)?)),
+            _ if s.starts_with("uint") => Ok(AbiType::Uint(size("uint", 8, 256, 8)?)),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl fmt::Display for AbiType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AbiType::Address => write!(f, "address"),
+            AbiType::Bool => write!(f, "bool"),
+            AbiType::Bytes => write!(f, "bytes"),
+            AbiType::FixedBytes(size) => write!(f, "bytes{}", size),
+            AbiType::Int(bits) => write!(f, "int{}", bits),
+            AbiType::String => write!(f, "string"),
+            AbiType::Uint(bits) => write!(f, "uint{}", bits),
+            AbiType::Array(element) => write!(f, "{}[]", element),
+            AbiType::FixedArray(element, length) => write!(f, "{}[{}]", element, length),
+            AbiType::Tuple(components) => {
+                let components = components.iter().map(AbiType::to_string).collect::<Vec<_>>();
+                write!(f, "({})", components.join(","))
+            }
+        }
+    }
+}
+
+/// Represents a value of the contract ABI
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AbiValue {
+    /// An address
+    Address(EthereumAddress),
+    /// A boolean
+    Bool(bool),
+    /// A dynamic sequence of bytes
+    Bytes(Vec<u8>),
+    /// A sequence of 1 to 32 bytes
+    FixedBytes(Vec<u8>),
+    /// A signed integer in two's complement
+    Int(U256),
+    /// A UTF-8 string
+    String(String),
+    /// An unsigned integer
+    Uint(U256),
+    /// The elements of a dynamic or fixed array
+    Array(Vec<AbiValue>),
+    /// The components of a tuple
+    Tuple(Vec<AbiValue>),
+}
+
+/// Represents a contract function, such as `transfer(address,uint256)`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AbiFunction {
+    /// The name of the function
+    pub name: String,
+    /// The types of the arguments of the function
+    pub inputs: Vec<AbiType>,
+}
+
+impl AbiFunction {
+    /// Returns the canonical signature of the function, which has no spaces or type aliases.
+    pub fn signature(&self) -> String {
+        format!("{}{}", self.name, AbiType::Tuple(self.inputs.clone()))
+    }
+
+    /// Returns the function selector, which is the first 4 bytes of the hash of the signature.
+    pub fn selector(&self) -> [u8; 4] {
+        let mut selector = [0u8; 4];
+        selector.copy_from_slice(&keccak256(self.signature().as_bytes())[..4]);
+        selector
+    }
+
+    /// Returns the calldata of the function given its arguments.
+    pub fn encode(&self, arguments: &[AbiValue]) -> Result<Vec<u8>, AbiError> {
+        Ok([self.selector().to_vec(), encode(&self.inputs, arguments)?].concat())
+    }
+
+    /// Returns the calldata of the function given the JSON representation of its arguments.
+    pub fn encode_json(&self, arguments: &[Value]) -> Result<Vec<u8>, AbiError> {
+        if arguments.len() != self.inputs.len() {
+            return Err(AbiError::InvalidArgumentCount(self.inputs.len(), arguments.len()));
+        }
+        let arguments = self
+            .inputs
+            .iter()
+            .zip(arguments)
+            .map(|(input, json)| input.value_from_json(json))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.encode(&arguments)
+    }
+}
+
+impl FromStr for AbiFunction {
+    type Err = AbiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let signature = s.split_whitespace().collect::<String>();
+        let open = signature
+            .find('(')
+            .ok_or_else(|| AbiError::InvalidSignature(s.to_string()))?;
+        let name = &signature[..open];
+        let mut characters = name.chars();
+        let is_identifier = characters
+            .next()
+            .map_or(false, |c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+            && characters.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+        match AbiType::from_str(&signature[open..]) {
+            Ok(AbiType::Tuple(inputs)) if is_identifier => Ok(Self {
+                name: name.to_string(),
+                inputs,
+            }),
+            Err(AbiError::InvalidType(input)) => Err(AbiError::InvalidType(input)),
+            _ => Err(AbiError::InvalidSignature(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for AbiFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.signature())
+    }
+}
+
+/// Returns the encoding of the values of the types, as in the arguments of a function call.
+pub fn encode(types: &[AbiType], values: &[AbiValue]) -> Result<Vec<u8>, AbiError> {
+    if types.len() != values.len() {
+        return Err(AbiError::InvalidArgumentCount(types.len(), values.len()));
+    }
+
+    // Static values are encoded in the head, and dynamic values are encoded in the tail at an offset in the head
+    let head_size: usize = types.iter().map(AbiType::head_size).sum();
+    let mut head = Vec::with_capacity(head_size);
+    let mut tail = vec![];
+    for (abi_type, value) in types.iter().zip(values) {
+        let encoding = encode_value(abi_type, value)?;
+        match abi_type.is_dynamic() {
+            true => {
+                head.extend(uint_word(U256::from(head_size + tail.len())));
+                tail.extend(encoding);
+            }
+            false => head.extend(encoding),
+        }
+    }
+    Ok([head, tail].concat())
+}
+
+/// Returns the encoding of a value of the type.
+fn encode_value(abi_type: &AbiType, value: &AbiValue) -> Result<Vec<u8>, AbiError> {
+    let invalid = || AbiError::InvalidValue(abi_type.to_string(), format!("{:?}", value));
+    match (abi_type, value) {
+        (AbiType::Address, AbiValue::Address(address)) => Ok([vec![0u8; 12], address.to_bytes()?].concat()),
+        (AbiType::Bool, AbiValue::Bool(value)) => Ok(uint_word(U256::from(*value as u8))),
+        (AbiType::Bytes, AbiValue::Bytes(bytes)) => Ok(bytes_with_length(bytes)),
+        (AbiType::FixedBytes(size), AbiValue::FixedBytes(bytes)) if *size == bytes.len() => {
+            Ok([bytes.clone(), vec![0u8; 32 - size]].concat())
+        }
+        (AbiType::Int(bits), AbiValue::Int(value)) => {
+            // The integer fits if it and its one's complement have no more than the bits less the sign bit
+            let magnitude = if value.bit(255) { !*value } else { *value };
+            match magnitude.bits() < *bits {
+                true => Ok(uint_word(*value)),
+                false => Err(invalid()),
+            }
+        }
+        (AbiType::String, AbiValue::String(value)) => Ok(bytes_with_length(value.as_bytes())),
+        (AbiType::Uint(bits), AbiValue::Uint(value)) if value.bits() <= *bits => Ok(uint_word(*value)),
+        (AbiType::Array(element), AbiValue::Array(elements)) => {
+            let types = vec![element.as_ref().clone(); elements.len()];
+            Ok([uint_word(U256::from(elements.len())), encode(&types, elements)?].concat())
+        }
+        (AbiType::FixedArray(element, length), AbiValue::Array(elements)) if *length == elements.len() => {
+            encode(&vec![element.as_ref().clone(); *length], elements)
+        }
+        (AbiType::Tuple(components), AbiValue::Tuple(values)) if components.len() == values.len() => {
+            encode(components, values)
+        }
+        _ => Err(invalid()),
+    }
+}
+
+/// Returns the integer as a big-endian 32-byte word.
+fn uint_word(value: U256) -> Vec<u8> {
+    let mut word = [0u8; 32];
+    value.to_big_endian(&mut word);
+    word.to_vec()
+}
+
+/// Returns the length of the bytes followed by the bytes right padded to a multiple of 32 bytes.
+fn bytes_with_length(bytes: &[u8]) -> Vec<u8> {
+    let mut encoding = uint_word(U256::from(bytes.len()));
+    encoding.extend_from_slice(bytes);
+    encoding.extend(vec![0u8; (32 - bytes.len() % 32) % 32]);
+    encoding
+}
+
+/// Returns a decimal or `0x` hexadecimal unsigned integer.
+fn parse_uint(value: &str) -> Option<U256> {
+    match value.starts_with("0x") {
+        true if value.len() > 2 && value.len() <= 66 => {
+            let bytes = hex::decode(format!("{:0>64}", &value[2..])).ok()?;
+            Some(U256::from_big_endian(&bytes))
+        }
+        true => None,
+        false if !value.is_empty() && value.chars().all(|c| c.is_ascii_digit()) => U256::from_dec_str(value).ok(),
+        false => None,
+    }
+}
+
+/// Returns the comma separated components of a tuple, which may themselves be tuples.
+fn split_components(components: &str) -> Option<Vec<&str>> {
+    if components.is_empty() {
+        return Some(vec![]);
+    }
+    let mut depth = 0usize;
+    let mut start = 0;
+    let mut split = vec![];
+    for (index, c) in components.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.checked_sub(1)?,
+            ',' if depth == 0 => {
+                split.push(&components[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    split.push(&components[start..]);
+    match depth {
+        0 => Some(split),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Fail)]
+pub enum AbiError {
+    #[fail(display = "{}", _0)]
+    AddressError(#[cause] AddressError),
+
+    #[fail(display = "invalid number of ABI arguments: expected {}, found {}", _0, _1)]
+    InvalidArgumentCount(usize, usize),
+
+    #[fail(display = "invalid ABI function signature: {}", _0)]
+    InvalidSignature(String),
+
+    #[fail(display = "invalid ABI type: {}", _0)]
+    InvalidType(String),
+
+    #[fail(display = "invalid {} value: {}", _0, _1)]
+    InvalidValue(String, String),
+}
+
+impl From<AddressError> for AbiError {
+    fn from(error: AddressError) -> Self {
+        AbiError::AddressError(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::erc20::Erc20Token;
+
+    use core::slice;
+    use serde_json::json;
+
+    fn function(signature: &str) -> AbiFunction {
+        AbiFunction::from_str(signature).unwrap()
+    }
+
+    fn uint(value: u64) -> AbiValue {
+        AbiValue::Uint(U256::from(value))
+    }
+
+    #[test]
+    fn parse_type() {
+        [
+            "address",
+            "bool",
+            "bytes",
+            "bytes1",
+            "bytes32",
+            "int8",
+            "int256",
+            "string",
+            "uint24",
+            "uint256[]",
+            "uint256[2][]",
+            "(address,uint256[])[3]",
+            "((bool,bytes),string)",
+            "()",
+        ]
+        .iter()
+        .for_each(|abi_type| assert_eq!(*abi_type, AbiType::from_str(abi_type).unwrap().to_string()));
+
+        assert_eq!(AbiType::Uint(256), AbiType::from_str("uint").unwrap());
+        assert_eq!(AbiType::Int(256), AbiType::from_str("int").unwrap());
+        assert_eq!(
+            AbiType::Array(Box::new(AbiType::FixedArray(Box::new(AbiType::Uint(256)), 2))),
+            AbiType::from_str("uint256[2][]").unwrap()
+        );
+
+        [
+            "uint7",
+            "uint264",
+            "uint08",
+            "bytes0",
+            "bytes33",
+            "address[0]",
+            "address[01]",
+            "(bool",
+            "(bool,)",
+            "foo",
+            "",
+        ]
+        .iter()
+        .for_each(|abi_type| assert!(AbiType::from_str(abi_type).is_err(), "{}", abi_type));
+    }
+
+    #[test]
+    fn parse_function() {
+        let transfer = function("transfer(address, uint)");
+        assert_eq!("transfer", transfer.name);
+        assert_eq!(vec![AbiType::Address, AbiType::Uint(256)], transfer.inputs);
+        assert_eq!("transfer(address,uint256)", transfer.signature());
+        assert_eq!("totalSupply()", function("totalSupply()").to_string());
+
+        assert!(AbiFunction::from_str("transfer").is_err());
+        assert!(AbiFunction::from_str("(address)").is_err());
+        assert!(AbiFunction::from_str("1transfer(address)").is_err());
+        assert!(AbiFunction::from_str("transfer(address)[]").is_err());
+        assert!(AbiFunction::from_str("transfer(address,uint7)").is_err());
+    }
+
+    #[test]
+    fn is_dynamic() {
+        ["bytes", "string", "uint256[]", "string[2]", "(uint256,bytes)"]
+            .iter()
+            .for_each(|abi_type| assert!(AbiType::from_str(abi_type).unwrap().is_dynamic()));
+        ["address", "bytes32", "uint256[2]", "(uint256,bool)[2]"]
+            .iter()
+            .for_each(|abi_type| assert!(!AbiType::from_str(abi_type).unwrap().is_dynamic()));
+    }
+
+    // https://docs.soliditylang.org/en/latest/abi-spec.html#examples
+
+    #[test]
+    fn encode_static() {
+        assert_eq!(
+            "cdcd77c000000000000000000000000000000000000000000000000000000000000000450000000000000000000000000000000000000000000000000000000000000001",
+            hex::encode(function("baz(uint32,bool)").encode(&[uint(69), AbiValue::Bool(true)]).unwrap())
+        );
+    }
+
+    #[test]
+    fn encode_dynamic() {
+        let calldata = function("sam(bytes,bool,uint256[])")
+            .encode(&[
+                AbiValue::Bytes(b"dave".to_vec()),
+                AbiValue::Bool(true),
+                AbiValue::Array(vec![uint(1), uint(2), uint(3)]),
+            ])
+            .unwrap();
+        assert_eq!(
+            "a5643bf20000000000000000000000000000000000000000000000000000000000000060000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000a0000000000000000000000000000000000000000000000000000000000000000464617665000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000003",
+            hex::encode(calldata)
+        );
+
+        let calldata = function("f(uint256,uint32[],bytes10,bytes)")
+            .encode(&[
+                uint(0x123),
+                AbiValue::Array(vec![uint(0x456), uint(0x789)]),
+                AbiValue::FixedBytes(b"1234567890".to_vec()),
+                AbiValue::Bytes(b"Hello, world!".to_vec()),
+            ])
+            .unwrap();
+        assert_eq!(
+            "8be6524600000000000000000000000000000000000000000000000000000000000001230000000000000000000000000000000000000000000000000000000000000080313233343536373839300000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000e0000000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000004560000000000000000000000000000000000000000000000000000000000000789000000000000000000000000000000000000000000000000000000000000000d48656c6c6f2c20776f726c642100000000000000000000000000000000000000",
+            hex::encode(calldata)
+        );
+    }
+
+    #[test]
+    fn encode_nested() {
+        let calldata = function("g(uint256[][],string[])")
+            .encode_json(&[json!([[1, 2], [3]]), json!(["one", "two", "three"])])
+            .unwrap();
+        assert_eq!(
+            "2289b18c000000000000000000000000000000000000000000000000000000000000004000000000000000000000000000000000000000000000000000000000000001400000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000004000000000000000000000000000000000000000000000000000000000000000a0000000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000030000000000000000000000000000000000000000000000000000000000000003000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000000a000000000000000000000000000000000000000000000000000000000000000e000000000000000000000000000000000000000000000000000000000000000036f6e650000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000374776f000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000057468726565000000000000000000000000000000000000000000000000000000",
+            hex::encode(calldata)
+        );
+    }
+
+    #[test]
+    fn encode_tuple() {
+        let calldata = function("submit((address,uint256[]),int8,bytes32)")
+            .encode_json(&[
+                json!(["0x52C3a8a79a521D10b25569847CB1a3FfB66550D6", ["0x1", "2"]]),
+                json!(-2),
+                json!(format!("0x{}", "ab".repeat(32))),
+            ])
+            .unwrap();
+        assert_eq!(
+            "deab6fc60000000000000000000000000000000000000000000000000000000000000060fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffeabababababababababababababababababababababababababababababababab00000000000000000000000052c3a8a79a521d10b25569847cb1a3ffb66550d60000000000000000000000000000000000000000000000000000000000000040000000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000002",
+            hex::encode(calldata)
+        );
+    }
+
+    #[test]
+    fn encode_erc20_transfer() {
+        let token = Erc20Token::new(
+            &EthereumAddress::from_str("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48").unwrap(),
+            6,
+        );
+        let recipient = EthereumAddress::from_str("0x52C3a8a79a521D10b25569847CB1a3FfB66550D6").unwrap();
+        assert_eq!(
+            token.transfer(&recipient, U256::from(1_500_000)).unwrap(),
+            function("transfer(address,uint256)")
+                .encode(&[AbiValue::Address(recipient), uint(1_500_000)])
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn int() {
+        let int8 = AbiType::Int(8);
+        [("127", "7f"), ("-128", "80"), ("-1", "ff")]
+            .iter()
+            .for_each(|(value, low_byte)| {
+                let value = int8.value_from_json(&json!(value)).unwrap();
+                let word = hex::encode(encode(slice::from_ref(&int8), &[value]).unwrap());
+                assert!(word.ends_with(low_byte));
+            });
+        assert_eq!(
+            "f".repeat(64),
+            hex::encode(
+                encode(
+                    &[AbiType::Int(256)],
+                    &[AbiType::Int(256).value_from_json(&json!(-1)).unwrap()]
+                )
+                .unwrap()
+            )
+        );
+        ["128", "-129"].iter().for_each(|value| {
+            let value = int8.value_from_json(&json!(value)).unwrap();
+            assert!(encode(slice::from_ref(&int8), &[value]).is_err());
+        });
+    }
+
+    #[test]
+    fn invalid_values() {
+        let uint8 = AbiType::Uint(8);
+        assert!(encode(slice::from_ref(&uint8), &[uint(256)]).is_err());
+        assert!(encode(slice::from_ref(&uint8), &[AbiValue::Bool(true)]).is_err());
+        assert!(encode(slice::from_ref(&uint8), &[]).is_err());
+        assert!(encode(&[AbiType::FixedBytes(4)], &[AbiValue::FixedBytes(vec![0u8; 3])]).is_err());
+        assert!(encode(
+            &[AbiType::FixedArray(Box::new(uint8.clone()), 2)],
+            &[AbiValue::Array(vec![uint(1)])]
+        )
+        .is_err());
+
+        assert!(uint8.value_from_json(&json!("-1")).is_err());
+        assert!(uint8.value_from_json(&json!("0x")).is_err());
+        assert!(uint8.value_from_json(&json!(1.5)).is_err());
+        assert!(AbiType::Address.value_from_json(&json!("0x1234")).is_err());
+        assert!(AbiType::Bytes.value_from_json(&json!("0xabc")).is_err());
+        assert!(AbiType::Bool.value_from_json(&json!(1)).is_err());
+        assert!(function("transfer(address,uint256)")
+            .encode_json(&[json!("0x52C3a8a79a521D10b25569847CB1a3FfB66550D6")])
+            .is_err());
+    }
+}
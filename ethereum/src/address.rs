@@ -4,7 +4,9 @@ use crate::public_key::EthereumPublicKey;
 use wagyu_model::{to_hex_string, Address, AddressError, PrivateKey};
 
 use core::{convert::TryFrom, fmt, str::FromStr};
+use ethereum_types::U256;
 use regex::Regex;
+use rlp::RlpStream;
 use serde::Serialize;
 use tiny_keccak::keccak256;
 
@@ -47,6 +49,28 @@ impl EthereumAddress {
 
         EthereumAddress(checksum_address)
     }
+
+    /// Returns the 20 bytes of the address.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, AddressError> {
+        Ok(hex::decode(&self.0[2..])?)
+    }
+
+    /// Returns the address of the contract deployed with `CREATE` by the sender at the given nonce,
+    /// which is the last 20 bytes of the hash of the RLP list of the sender and the nonce.
+    pub fn create_address(sender: &Self, nonce: U256) -> Result<Self, AddressError> {
+        let mut rlp = RlpStream::new_list(2);
+        rlp.append(&sender.to_bytes()?);
+        rlp.append(&nonce);
+        Self::from_str(&hex::encode(&keccak256(&rlp.out())[12..]))
+    }
+
+    /// Returns the address of the contract deployed with `CREATE2` by the deployer with the given salt and hash
+    /// of the init code, which is the last 20 bytes of the hash of `0xff`, the deployer, the salt, and the hash.
+    /// https://github.com/ethereum/EIPs/blob/master/EIPS/eip-1014.md
+    pub fn create2_address(deployer: &Self, salt: &[u8; 32], init_code_hash: &[u8; 32]) -> Result<Self, AddressError> {
+        let preimage = [&[0xff], deployer.to_bytes()?.as_slice(), salt, init_code_hash].concat();
+        Self::from_str(&hex::encode(&keccak256(&preimage)[12..]))
+    }
 }
 
 impl<'a> TryFrom<&'a str> for EthereumAddress {
@@ -172,6 +196,100 @@ mod tests {
         }
     }
 
+    mod create_address {
+        use super::*;
+
+        const SENDER: &str = "0x6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0";
+
+        const ADDRESSES: [(u64, &str); 8] = [
+            (0, "0xcd234a471b72ba2f1ccf0a70fcaba648a5eecd8d"),
+            (1, "0x343c43a37d37dff08ae8c4a11544c718abb4fcf8"),
+            (2, "0xf778b86fa74e846c4f0a1fbd1335fe81c00a0c91"),
+            (3, "0xfffd933a0bc612844eaf0c6fe3e5b8e9b6c1d19c"),
+            (127, "0x06d9a77f5e4b311bae8d559db9cdb4df94104aa0"),
+            (128, "0x08e190dcb7b73f5fcdabb43e102215c83659a76d"),
+            (256, "0x3837c1ae70354f670550c746580199ac6a73cb0a"),
+            (u64::MAX, "0x9bc924993b60399df164c3763a964301d3db95ca"),
+        ];
+
+        #[test]
+        fn create_address() {
+            let sender = EthereumAddress::from_str(SENDER).unwrap();
+            ADDRESSES.iter().for_each(|(nonce, address)| {
+                assert_eq!(
+                    EthereumAddress::from_str(address).unwrap(),
+                    EthereumAddress::create_address(&sender, U256::from(*nonce)).unwrap()
+                );
+            });
+        }
+    }
+
+    mod create2_address {
+        use super::*;
+
+        // https://github.com/ethereum/EIPs/blob/master/EIPS/eip-1014.md#examples
+        const ADDRESSES: [(&str, &str, &str, &str); 7] = [
+            (
+                "0x0000000000000000000000000000000000000000",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "00",
+                "0x4D1A2e2bB4F88F0250f26Ffff098B0b30B26BF38",
+            ),
+            (
+                "0xdeadbeef00000000000000000000000000000000",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "00",
+                "0xB928f69Bb1D91Cd65274e3c79d8986362984fDA3",
+            ),
+            (
+                "0xdeadbeef00000000000000000000000000000000",
+                "000000000000000000000000feed000000000000000000000000000000000000",
+                "00",
+                "0xD04116cDd17beBE565EB2422F2497E06cC1C9833",
+            ),
+            (
+                "0x0000000000000000000000000000000000000000",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "deadbeef",
+                "0x70f2b2914A2a4b783FaEFb75f459A580616Fcb5e",
+            ),
+            (
+                "0x00000000000000000000000000000000deadbeef",
+                "00000000000000000000000000000000000000000000000000000000cafebabe",
+                "deadbeef",
+                "0x60f3f640a8508fC6a86d45DF051962668E1e8AC7",
+            ),
+            (
+                "0x00000000000000000000000000000000deadbeef",
+                "00000000000000000000000000000000000000000000000000000000cafebabe",
+                "deadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeef",
+                "0x1d8bfDC5D46DC4f61D6b6115972536eBE6A8854C",
+            ),
+            (
+                "0x0000000000000000000000000000000000000000",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "",
+                "0xE33C0C7F7df4809055C3ebA6c09CFe4BaF1BD9e0",
+            ),
+        ];
+
+        #[test]
+        fn create2_address() {
+            ADDRESSES.iter().for_each(|(deployer, salt, init_code, address)| {
+                let deployer = EthereumAddress::from_str(deployer).unwrap();
+                let mut salt_bytes = [0u8; 32];
+                salt_bytes.copy_from_slice(&hex::decode(salt).unwrap());
+                let init_code_hash = keccak256(&hex::decode(init_code).unwrap());
+                assert_eq!(
+                    *address,
+                    EthereumAddress::create2_address(&deployer, &salt_bytes, &init_code_hash)
+                        .unwrap()
+                        .to_string()
+                );
+            });
+        }
+    }
+
     #[test]
     fn test_checksum_address_invalid() {
         // Mismatched keypair
@@ -1,4 +1,4 @@
-use wagyu_model::{Amount, AmountError};
+use wagyu_model::{Amount, AmountError, CrateError};
 
 use core::fmt;
 use ethereum_types::U256;
@@ -56,7 +56,7 @@ impl EthereumAmount {
     pub fn u256_from_str(val: &str) -> Result<U256, AmountError> {
         match U256::from_dec_str(val) {
             Ok(wei) => Ok(wei),
-            Err(error) => return Err(AmountError::Crate("uint", format!("{:?}", error))),
+            Err(error) => Err(AmountError::Crate(CrateError::from(error))),
         }
     }
 
@@ -0,0 +1,178 @@
+use wagyu_model::NetworkError;
+
+use serde::Serialize;
+use core::{fmt, str::FromStr};
+
+/// Represents an EVM chain with a known EIP-155 chain id.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub enum EthereumChain {
+    /// Arbitrum One
+    Arbitrum,
+    /// Avalanche C-Chain
+    Avalanche,
+    /// BNB Smart Chain
+    Bsc,
+    /// Ethereum test network (Holesky)
+    Holesky,
+    /// Ethereum main network
+    Mainnet,
+    /// OP Mainnet
+    Optimism,
+    /// Polygon PoS
+    Polygon,
+    /// Ethereum test network (Sepolia)
+    Sepolia,
+}
+
+impl EthereumChain {
+    /// The chains, in alphabetical order.
+    pub const ALL: [EthereumChain; 8] = [
+        EthereumChain::Arbitrum,
+        EthereumChain::Avalanche,
+        EthereumChain::Bsc,
+        EthereumChain::Holesky,
+        EthereumChain::Mainnet,
+        EthereumChain::Optimism,
+        EthereumChain::Polygon,
+        EthereumChain::Sepolia,
+    ];
+
+    /// Returns the name of the chain, which selects it.
+    pub fn name(&self) -> &'static str {
+        match self {
+            EthereumChain::Arbitrum => "arbitrum",
+            EthereumChain::Avalanche => "avalanche",
+            EthereumChain::Bsc => "bsc",
+            EthereumChain::Holesky => "holesky",
+            EthereumChain::Mainnet => "mainnet",
+            EthereumChain::Optimism => "optimism",
+            EthereumChain::Polygon => "polygon",
+            EthereumChain::Sepolia => "sepolia",
+        }
+    }
+
+    /// Returns the EIP-155 chain id of the chain.
+    pub fn chain_id(&self) -> u64 {
+        match self {
+            EthereumChain::Arbitrum => 42161,
+            EthereumChain::Avalanche => 43114,
+            EthereumChain::Bsc => 56,
+            EthereumChain::Holesky => 17000,
+            EthereumChain::Mainnet => 1,
+            EthereumChain::Optimism => 10,
+            EthereumChain::Polygon => 137,
+            EthereumChain::Sepolia => 11155111,
+        }
+    }
+
+    /// Returns the display name of the chain.
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            EthereumChain::Arbitrum => "Arbitrum One",
+            EthereumChain::Avalanche => "Avalanche C-Chain",
+            EthereumChain::Bsc => "BNB Smart Chain",
+            EthereumChain::Holesky => "Holesky",
+            EthereumChain::Mainnet => "Ethereum Mainnet",
+            EthereumChain::Optimism => "OP Mainnet",
+            EthereumChain::Polygon => "Polygon PoS",
+            EthereumChain::Sepolia => "Sepolia",
+        }
+    }
+
+    /// Returns the symbol of the native currency of the chain, which pays for gas.
+    pub fn currency(&self) -> &'static str {
+        match self {
+            EthereumChain::Avalanche => "AVAX",
+            EthereumChain::Bsc => "BNB",
+            EthereumChain::Polygon => "POL",
+            _ => "ETH",
+        }
+    }
+
+    /// Returns the URL of the block explorer of the chain.
+    pub fn explorer(&self) -> &'static str {
+        match self {
+            EthereumChain::Arbitrum => "https://arbiscan.io",
+            EthereumChain::Avalanche => "https://snowtrace.io",
+            EthereumChain::Bsc => "https://bscscan.com",
+            EthereumChain::Holesky => "https://holesky.etherscan.io",
+            EthereumChain::Mainnet => "https://etherscan.io",
+            EthereumChain::Optimism => "https://optimistic.etherscan.io",
+            EthereumChain::Polygon => "https://polygonscan.com",
+            EthereumChain::Sepolia => "https://sepolia.etherscan.io",
+        }
+    }
+
+    /// Returns `true` if the chain is a test network.
+    pub fn is_testnet(&self) -> bool {
+        matches!(self, EthereumChain::Holesky | EthereumChain::Sepolia)
+    }
+
+    /// Returns the chain with the given EIP-155 chain id, if it is known.
+    pub fn from_chain_id(chain_id: u64) -> Option<Self> {
+        Self::ALL.iter().find(|chain| chain.chain_id() == chain_id).copied()
+    }
+}
+
+impl FromStr for EthereumChain {
+    type Err = NetworkError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .find(|chain| chain.name() == s)
+            .copied()
+            .ok_or_else(|| NetworkError::InvalidNetwork(s.into()))
+    }
+}
+
+impl fmt::Display for EthereumChain {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::{EthereumNetwork, Mainnet};
+    use wagyu_model::Network;
+
+    #[test]
+    fn from_str() {
+        EthereumChain::ALL.iter().for_each(|chain| {
+            assert_eq!(*chain, EthereumChain::from_str(chain.name()).unwrap());
+            assert_eq!(chain.name(), chain.to_string());
+        });
+        assert!(EthereumChain::from_str("goerli").is_err());
+    }
+
+    #[test]
+    fn from_chain_id() {
+        EthereumChain::ALL.iter().for_each(|chain| {
+            assert_eq!(Some(*chain), EthereumChain::from_chain_id(chain.chain_id()));
+        });
+        assert_eq!(None, EthereumChain::from_chain_id(5));
+    }
+
+    #[test]
+    fn mainnet() {
+        let chain = EthereumChain::from_str(Mainnet::NAME).unwrap();
+        assert_eq!(u64::from(Mainnet::CHAIN_ID), chain.chain_id());
+        assert_eq!("ETH", chain.currency());
+        assert!(!chain.is_testnet());
+    }
+
+    #[test]
+    fn metadata() {
+        let chain = EthereumChain::from_str("polygon").unwrap();
+        assert_eq!(137, chain.chain_id());
+        assert_eq!("Polygon PoS", chain.display_name());
+        assert_eq!("POL", chain.currency());
+        assert_eq!("https://polygonscan.com", chain.explorer());
+
+        assert_eq!(11155111, EthereumChain::Sepolia.chain_id());
+        assert!(EthereumChain::Sepolia.is_testnet());
+        assert_eq!("AVAX", EthereumChain::Avalanche.currency());
+    }
+}
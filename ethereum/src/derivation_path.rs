@@ -1,8 +1,9 @@
 use crate::network::EthereumNetwork;
-use wagyu_model::derivation_path::{ChildIndex, DerivationPath, DerivationPathError};
+use wagyu_model::derivation_path::{ChildIndex, DerivationPath, DerivationPathError, MAX_DEPTH};
 
 use core::convert::TryFrom;
-use core::{fmt, marker::PhantomData, str::FromStr};
+use core::{cmp::Ordering, fmt, marker::PhantomData, str::FromStr};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
 /// Represents a Ethereum derivation path
 #[derive(Clone, PartialEq, Eq)]
@@ -36,6 +37,13 @@ pub enum EthereumDerivationPath<N: EthereumNetwork> {
     Custom(Vec<ChildIndex>, PhantomData<N>),
 }
 
+impl<N: EthereumNetwork> EthereumDerivationPath<N> {
+    /// Returns the standard BIP44 derivation path m/44'/60'/0'/0/{index} given the address index.
+    pub fn bip44(index: u32) -> Result<Self, DerivationPathError> {
+        Ok(EthereumDerivationPath::Ethereum(ChildIndex::normal(index)?))
+    }
+}
+
 impl<N: EthereumNetwork> DerivationPath for EthereumDerivationPath<N> {
     /// Returns a child index vector given the derivation path.
     fn to_vec(&self) -> Result<Vec<ChildIndex>, DerivationPathError> {
@@ -76,7 +84,7 @@ impl<N: EthereumNetwork> DerivationPath for EthereumDerivationPath<N> {
                 false => Err(DerivationPathError::ExpectedValidEthereumDerivationPath),
             },
 
-            EthereumDerivationPath::Custom(path, _) => match path.len() < 256 {
+            EthereumDerivationPath::Custom(path, _) => match path.len() <= MAX_DEPTH {
                 true => Ok(path.clone()),
                 false => Err(DerivationPathError::ExpectedValidEthereumDerivationPath),
             },
@@ -85,6 +93,10 @@ impl<N: EthereumNetwork> DerivationPath for EthereumDerivationPath<N> {
 
     /// Returns a derivation path given the child index vector.
     fn from_vec(path: &Vec<ChildIndex>) -> Result<Self, DerivationPathError> {
+        if path.len() > MAX_DEPTH {
+            return Err(DerivationPathError::MaximumDepthExceeded(path.len()));
+        }
+
         if path.len() == 4 {
             // Path length 4 - Electrum (default), imToken, LedgerLegacy
             if path[0] == N::HD_PURPOSE
@@ -153,6 +165,30 @@ impl<'a, N: EthereumNetwork> TryFrom<&'a [ChildIndex]> for EthereumDerivationPat
     }
 }
 
+impl<N: EthereumNetwork> PartialOrd for EthereumDerivationPath<N> {
+    /// Returns the lexicographic ordering of the child index vectors of the derivation paths,
+    /// or `None` if either derivation path is invalid.
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self.to_vec(), other.to_vec()) {
+            (Ok(path), Ok(other)) => path.partial_cmp(&other),
+            _ => None,
+        }
+    }
+}
+
+impl<N: EthereumNetwork> Serialize for EthereumDerivationPath<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de, N: EthereumNetwork> Deserialize<'de> for EthereumDerivationPath<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let path = String::deserialize(deserializer)?;
+        Self::from_str(&path).map_err(de::Error::custom)
+    }
+}
+
 impl<N: EthereumNetwork> fmt::Debug for EthereumDerivationPath<N> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         fmt::Display::fmt(&self, f)
@@ -184,6 +220,76 @@ mod tests {
     use std::convert::TryInto;
     use std::str::FromStr;
 
+    #[test]
+    fn coin_type() {
+        use wagyu_model::coin_type::CoinType;
+
+        assert_eq!(
+            Ok(CoinType::Ethereum),
+            EthereumDerivationPath::<Mainnet>::bip44(0).unwrap().coin_type()
+        );
+        assert_eq!(
+            Ok(CoinType::Testnet),
+            EthereumDerivationPath::<Ropsten>::bip44(0).unwrap().coin_type()
+        );
+        assert_eq!(
+            Err(DerivationPathError::UnexpectedCoinType(
+                CoinType::Ethereum,
+                CoinType::Bitcoin
+            )),
+            EthereumDerivationPath::<Mainnet>::from_str("m/44'/0'/0'/0/0")
+                .unwrap()
+                .validate_coin_type(CoinType::Ethereum)
+        );
+    }
+
+    #[test]
+    fn bip44() {
+        type N = Mainnet;
+
+        assert_eq!(
+            EthereumDerivationPath::<N>::from_str("m/44'/60'/0'/0/0").unwrap(),
+            EthereumDerivationPath::<N>::bip44(0).unwrap()
+        );
+        assert_eq!(
+            "m/44'/60'/0'/0/7",
+            EthereumDerivationPath::<N>::bip44(7).unwrap().to_string()
+        );
+        assert_eq!(
+            Err(DerivationPathError::InvalidChildNumber(2147483648)),
+            EthereumDerivationPath::<N>::bip44(2147483648)
+        );
+    }
+
+    #[test]
+    fn manipulation() {
+        type N = Mainnet;
+
+        let mut path = EthereumDerivationPath::<N>::from_str("m/44'/60'/0'/0").unwrap();
+        assert_eq!(
+            EthereumDerivationPath::<N>::bip44(3).unwrap(),
+            path.child(ChildIndex::Normal(3)).unwrap()
+        );
+        path.push(ChildIndex::Normal(1)).unwrap();
+        assert_eq!(EthereumDerivationPath::<N>::bip44(1).unwrap(), path);
+        assert_eq!(Some(ChildIndex::Hardened(60)), path.get(1));
+        assert_eq!(
+            EthereumDerivationPath::<N>::from_str("m/44'/60'").unwrap(),
+            path.parent().unwrap().parent().unwrap().parent().unwrap()
+        );
+    }
+
+    #[test]
+    fn serde() {
+        type N = Mainnet;
+
+        let path = EthereumDerivationPath::<N>::bip44(2).unwrap();
+        let json = serde_json::to_string(&path).unwrap();
+        assert_eq!("\"m/44'/60'/0'/0/2\"", json);
+        assert_eq!(path, serde_json::from_str(&json).unwrap());
+        assert!(serde_json::from_str::<EthereumDerivationPath<N>>("\"m/x\"").is_err());
+    }
+
     #[test]
     fn valid_path() {
         type N = Mainnet;
@@ -0,0 +1,229 @@
+use crate::address::EthereumAddress;
+use crate::amount::EthereumAmount;
+use crate::transaction::EthereumTransactionParameters;
+use wagyu_model::{AddressError, AmountError, CrateError};
+
+use ethereum_types::U256;
+
+/// The function selector of `transfer(address,uint256)`
+pub const ERC20_TRANSFER_SELECTOR: [u8; 4] = [0xa9, 0x05, 0x9c, 0xbb];
+/// The function selector of `approve(address,uint256)`
+pub const ERC20_APPROVE_SELECTOR: [u8; 4] = [0x09, 0x5e, 0xa7, 0xb3];
+
+/// Represents an ERC-20 token contract
+/// https://github.com/ethereum/EIPs/blob/master/EIPS/eip-20.md
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Erc20Token {
+    /// The address of the token contract
+    pub address: EthereumAddress,
+    /// The number of decimals of the token, which divide its base units for display
+    pub decimals: u8,
+}
+
+impl Erc20Token {
+    /// Returns a token given the address of its contract and its number of decimals.
+    pub fn new(address: &EthereumAddress, decimals: u8) -> Self {
+        Self {
+            address: address.clone(),
+            decimals,
+        }
+    }
+
+    /// Returns the amount in base units given a decimal amount of the token, such as `"1.5"`.
+    pub fn to_base_units(&self, amount: &str) -> Result<U256, AmountError> {
+        let (integer, fraction) = match amount.find('.') {
+            Some(index) => (&amount[..index], &amount[index + 1..]),
+            None => (amount, ""),
+        };
+        if (integer.is_empty() && fraction.is_empty())
+            || !integer.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit())
+        {
+            return Err(AmountError::InvalidAmount(amount.to_string()));
+        }
+        if fraction.len() > self.decimals as usize {
+            return Err(AmountError::InvalidAmount(format!(
+                "{} has more than {} decimals",
+                amount, self.decimals
+            )));
+        }
+
+        let units = format!(
+            "0{}{}{}",
+            integer,
+            fraction,
+            "0".repeat(self.decimals as usize - fraction.len())
+        );
+        U256::from_dec_str(&units).map_err(|error| AmountError::Crate(CrateError::from(error)))
+    }
+
+    /// Returns the decimal amount of the token given an amount in base units.
+    pub fn from_base_units(&self, units: U256) -> String {
+        let decimals = self.decimals as usize;
+        let units = format!("{:0>width$}", units.to_string(), width = decimals + 1);
+        let (integer, fraction) = units.split_at(units.len() - decimals);
+        match fraction.trim_end_matches('0') {
+            "" => integer.to_string(),
+            fraction => format!("{}.{}", integer, fraction),
+        }
+    }
+
+    /// Returns the calldata of `transfer(address,uint256)`, which sends an amount in base units to the recipient.
+    pub fn transfer(&self, recipient: &EthereumAddress, units: U256) -> Result<Vec<u8>, AddressError> {
+        encode_call(ERC20_TRANSFER_SELECTOR, recipient, units)
+    }
+
+    /// Returns the calldata of `approve(address,uint256)`, which allows the spender to transfer an amount in base units.
+    pub fn approve(&self, spender: &EthereumAddress, units: U256) -> Result<Vec<u8>, AddressError> {
+        encode_call(ERC20_APPROVE_SELECTOR, spender, units)
+    }
+
+    /// Returns the parameters of a transaction calling the token contract with the given calldata,
+    /// which keeps the gas, fees, nonce, and chain id of the given parameters.
+    pub fn to_transaction_parameters(
+        &self,
+        data: Vec<u8>,
+        parameters: &EthereumTransactionParameters,
+    ) -> EthereumTransactionParameters {
+        EthereumTransactionParameters {
+            receiver: Some(self.address.clone()),
+            amount: EthereumAmount::from_u256(U256::zero()),
+            data,
+            ..parameters.clone()
+        }
+    }
+}
+
+/// Returns the calldata of a function of an address and a uint256, each in a 32-byte word.
+fn encode_call(selector: [u8; 4], address: &EthereumAddress, units: U256) -> Result<Vec<u8>, AddressError> {
+    let mut data = selector.to_vec();
+    data.extend_from_slice(&[0u8; 12]);
+    data.extend(address.to_bytes()?);
+
+    let mut word = [0u8; 32];
+    units.to_big_endian(&mut word);
+    data.extend_from_slice(&word);
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::Mainnet;
+    use crate::private_key::EthereumPrivateKey;
+    use crate::transaction::EthereumTransaction;
+    use wagyu_model::Transaction;
+
+    use core::str::FromStr;
+    use tiny_keccak::keccak256;
+
+    const USDC: &str = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+    const DAI: &str = "0x6B175474E89094C44Da98b954EedeAC495271d0F";
+    const RECIPIENT: &str = "0x52C3a8a79a521D10b25569847CB1a3FfB66550D6";
+
+    fn token(address: &str, decimals: u8) -> Erc20Token {
+        Erc20Token::new(&EthereumAddress::from_str(address).unwrap(), decimals)
+    }
+
+    #[test]
+    fn selectors() {
+        assert_eq!(ERC20_TRANSFER_SELECTOR, keccak256(b"transfer(address,uint256)")[..4]);
+        assert_eq!(ERC20_APPROVE_SELECTOR, keccak256(b"approve(address,uint256)")[..4]);
+    }
+
+    #[test]
+    fn to_base_units() {
+        let usdc = token(USDC, 6);
+        assert_eq!(U256::from(1_500_000), usdc.to_base_units("1.5").unwrap());
+        assert_eq!(U256::from(1_500_000), usdc.to_base_units("1.500000").unwrap());
+        assert_eq!(U256::from(1), usdc.to_base_units("0.000001").unwrap());
+        assert_eq!(U256::from(500_000), usdc.to_base_units(".5").unwrap());
+        assert_eq!(U256::from(42_000_000), usdc.to_base_units("42").unwrap());
+        assert_eq!(U256::from(42), token(USDC, 0).to_base_units("42").unwrap());
+        assert_eq!(
+            U256::from_dec_str("2500000000000000000").unwrap(),
+            token(DAI, 18).to_base_units("2.5").unwrap()
+        );
+
+        assert!(usdc.to_base_units("0.0000001").is_err());
+        assert!(usdc.to_base_units("1,5").is_err());
+        assert!(usdc.to_base_units("-1").is_err());
+        assert!(usdc.to_base_units("1.2.3").is_err());
+        assert!(usdc.to_base_units(".").is_err());
+        assert!(usdc.to_base_units("").is_err());
+        assert!(token(DAI, 18).to_base_units(&"9".repeat(60)).is_err());
+    }
+
+    #[test]
+    fn from_base_units() {
+        let usdc = token(USDC, 6);
+        assert_eq!("1.5", usdc.from_base_units(U256::from(1_500_000)));
+        assert_eq!("0.000001", usdc.from_base_units(U256::from(1)));
+        assert_eq!("42", usdc.from_base_units(U256::from(42_000_000)));
+        assert_eq!("0", usdc.from_base_units(U256::zero()));
+        assert_eq!("42", token(USDC, 0).from_base_units(U256::from(42)));
+        ["1.5", "0.000001", "123456.789", "0"]
+            .iter()
+            .for_each(|amount| assert_eq!(*amount, usdc.from_base_units(usdc.to_base_units(amount).unwrap())));
+    }
+
+    #[test]
+    fn transfer() {
+        let usdc = token(USDC, 6);
+        let recipient = EthereumAddress::from_str(RECIPIENT).unwrap();
+        assert_eq!(
+            "a9059cbb00000000000000000000000052c3a8a79a521d10b25569847cb1a3ffb66550d6000000000000000000000000000000000000000000000000000000000016e360",
+            hex::encode(usdc.transfer(&recipient, usdc.to_base_units("1.5").unwrap()).unwrap())
+        );
+    }
+
+    #[test]
+    fn approve() {
+        let dai = token(DAI, 18);
+        let spender = EthereumAddress::from_str(RECIPIENT).unwrap();
+        assert_eq!(
+            "095ea7b300000000000000000000000052c3a8a79a521d10b25569847cb1a3ffb66550d600000000000000000000000000000000000000000000000022b1c8c1227a0000",
+            hex::encode(dai.approve(&spender, dai.to_base_units("2.5").unwrap()).unwrap())
+        );
+        assert_eq!(
+            format!("095ea7b3{:0>64}{}", &RECIPIENT[2..].to_lowercase(), "f".repeat(64)),
+            hex::encode(dai.approve(&spender, U256::max_value()).unwrap())
+        );
+    }
+
+    #[test]
+    fn sign_transfer() {
+        let usdc = token(USDC, 6);
+        let recipient = EthereumAddress::from_str(RECIPIENT).unwrap();
+        let parameters = EthereumTransactionParameters {
+            receiver: Some(recipient.clone()),
+            amount: EthereumAmount::from_wei("1000000000000000000").unwrap(),
+            gas: U256::from(65000),
+            gas_price: EthereumAmount::from_wei("0").unwrap(),
+            max_fee_per_gas: Some(EthereumAmount::from_wei("30000000000").unwrap()),
+            max_priority_fee_per_gas: Some(EthereumAmount::from_wei("1000000000").unwrap()),
+            access_list: None,
+            nonce: U256::from(4),
+            data: vec![],
+            chain_id: None,
+        };
+        let data = usdc.transfer(&recipient, usdc.to_base_units("1.5").unwrap()).unwrap();
+        let parameters = usdc.to_transaction_parameters(data, &parameters);
+        assert_eq!(Some(usdc.address), parameters.receiver);
+        assert_eq!(U256::zero(), parameters.amount.0);
+
+        let private_key =
+            EthereumPrivateKey::from_str("51ce358ffdcf208fadfb01a339f3ab715a89045a093777a44784d9e215277c1c").unwrap();
+        let signed_transaction = EthereumTransaction::<Mainnet>::new(&parameters)
+            .unwrap()
+            .sign(&private_key)
+            .unwrap();
+        assert_eq!(
+            "0x02f8b00104843b9aca008506fc23ac0082fde894a0b86991c6218b36c1d19d4a2e9eb0ce3606eb4880b844a9059cbb00000000000000000000000052c3a8a79a521d10b25569847cb1a3ffb66550d6000000000000000000000000000000000000000000000000000000000016e360c001a08681c929f3a8d4af1fccf736ad93f07c2c582e0c0b7098062d88d1776948539ba06c38f4f484b8298b70eadd8d18eb305ff413813c29db63c91b7309cb446361b1",
+            signed_transaction.to_string()
+        );
+        assert_eq!(
+            "0x47688ae0359dfee80eb1051f13b4e8a217a445afbc82a959ee02fa7bd47bb462",
+            signed_transaction.to_transaction_id().unwrap().to_string()
+        );
+    }
+}
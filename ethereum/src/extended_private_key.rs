@@ -6,21 +6,22 @@ use crate::network::EthereumNetwork;
 use crate::private_key::EthereumPrivateKey;
 use crate::public_key::EthereumPublicKey;
 use wagyu_model::{
-    crypto::{checksum, hash160},
-    AddressError, ChildIndex, DerivationPath, ExtendedPrivateKey, ExtendedPrivateKeyError, ExtendedPublicKey,
-    PrivateKey,
+    crypto::{checksum, hash160, secp256k1_public_key},
+    AddressError, ChildIndex, DerivationPath, ExposeSecret, ExtendedPrivateKey, ExtendedPrivateKeyError,
+    ExtendedPublicKey, PrivateKey, Redacted,
 };
 
 use base58::{FromBase58, ToBase58};
 use core::{convert::TryFrom, fmt, fmt::Display, marker::PhantomData, str::FromStr};
 use hmac::{Hmac, Mac};
-use secp256k1::{PublicKey, SecretKey};
+use secp256k1::SecretKey;
 use sha2::Sha512;
+use zeroize::Zeroize;
 
 type HmacSha512 = Hmac<Sha512>;
 
 /// Represents a Ethereum Extended Private Key
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq)]
 pub struct EthereumExtendedPrivateKey<N> {
     /// The depth of key derivation, e.g. 0x00 for master nodes, 0x01 for level-1 derived keys, ...
     pub(super) depth: u8,
@@ -75,10 +76,15 @@ impl<N: EthereumNetwork> ExtendedPrivateKey for EthereumExtendedPrivateKey<N> {
             return Err(ExtendedPrivateKeyError::MaximumChildDepthReached(self.depth));
         }
 
+        let indices = path.to_vec()?;
+        if self.depth as usize + indices.len() > 255 {
+            return Err(ExtendedPrivateKeyError::MaximumChildDepthReached(self.depth));
+        }
+
         let mut extended_private_key = self.clone();
 
-        for index in path.to_vec()?.into_iter() {
-            let public_key = &PublicKey::from_secret_key(&extended_private_key.private_key.to_secp256k1_secret_key())
+        for index in indices.into_iter() {
+            let public_key = &secp256k1_public_key(&extended_private_key.private_key.to_secp256k1_secret_key())
                 .serialize_compressed();
 
             let mut mac = HmacSha512::new_varkey(&extended_private_key.chain_code)?;
@@ -140,6 +146,23 @@ impl<N: EthereumNetwork> ExtendedPrivateKey for EthereumExtendedPrivateKey<N> {
     }
 }
 
+impl<N: EthereumNetwork> EthereumExtendedPrivateKey<N> {
+    /// Returns the depth of key derivation, e.g. 0 for the master key.
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// Returns the first 32 bits of the key identifier of the parent key, or zeros for the master key.
+    pub fn parent_fingerprint(&self) -> [u8; 4] {
+        self.parent_fingerprint
+    }
+
+    /// Returns the child index of the key, or 0 for the master key.
+    pub fn child_index(&self) -> ChildIndex {
+        self.child_index
+    }
+}
+
 impl<N: EthereumNetwork> FromStr for EthereumExtendedPrivateKey<N> {
     type Err = ExtendedPrivateKeyError;
 
@@ -201,7 +224,46 @@ impl<N: EthereumNetwork> Display for EthereumExtendedPrivateKey<N> {
         let checksum = &checksum(&result[0..78])[0..4];
         result[78..82].copy_from_slice(&checksum);
 
-        fmt.write_str(&result.to_base58())
+        let output = result.to_base58();
+        result.zeroize();
+        fmt.write_str(&output)
+    }
+}
+
+impl<N> fmt::Debug for EthereumExtendedPrivateKey<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("EthereumExtendedPrivateKey")
+            .field("depth", &self.depth)
+            .field("parent_fingerprint", &self.parent_fingerprint)
+            .field("child_index", &self.child_index)
+            .field("chain_code", &Redacted)
+            .field("private_key", &self.private_key)
+            .finish()
+    }
+}
+
+impl<N> ExposeSecret for EthereumExtendedPrivateKey<N> {
+    fn fmt_secret(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("EthereumExtendedPrivateKey")
+            .field("depth", &self.depth)
+            .field("parent_fingerprint", &self.parent_fingerprint)
+            .field("child_index", &self.child_index)
+            .field("chain_code", &self.chain_code)
+            .field("private_key", &self.private_key.expose_secret())
+            .finish()
+    }
+}
+
+impl<N> Zeroize for EthereumExtendedPrivateKey<N> {
+    fn zeroize(&mut self) {
+        self.chain_code.zeroize();
+        self.private_key.zeroize();
+    }
+}
+
+impl<N> Drop for EthereumExtendedPrivateKey<N> {
+    fn drop(&mut self) {
+        self.zeroize();
     }
 }
 
@@ -569,6 +631,36 @@ mod tests {
                 test_to_string::<N>(extended_private_key);
             });
         }
+
+        #[test]
+        fn fingerprint() {
+            KEYPAIRS
+                .iter()
+                .filter(|(path, _, _, _, _, _, _, _)| *path != "m")
+                .for_each(|(path, seed, _, _, _, parent_fingerprint, _, _)| {
+                    let parent_path = EthereumDerivationPath::<N>::from_str(path).unwrap().parent().unwrap();
+                    let parent = EthereumExtendedPrivateKey::<N>::new(
+                        &hex::decode(seed).unwrap(),
+                        &EthereumFormat::Standard,
+                        &parent_path,
+                    )
+                    .unwrap();
+                    assert_eq!(*parent_fingerprint, hex::encode(parent.fingerprint()));
+                });
+        }
+
+        #[test]
+        fn key_origin() {
+            let (_, seed, _, _, _, _, _, _) = KEYPAIRS[0];
+            let master =
+                EthereumExtendedPrivateKey::<N>::new_master(&hex::decode(seed).unwrap(), &EthereumFormat::Standard)
+                    .unwrap();
+            let path = EthereumDerivationPath::from_str("m/44'/60'/0'/0/0").unwrap();
+            assert_eq!(
+                "[3442193e/44'/60'/0'/0/0]",
+                master.key_origin(&path).unwrap().to_string()
+            );
+        }
     }
 
     mod bip44 {
@@ -603,7 +695,7 @@ mod tests {
         const VALID_EXTENDED_PRIVATE_KEY: &str = "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi";
 
         #[test]
-        #[should_panic(expected = "Crate(\"libsecp256k1\", \"InvalidSecretKey\")")]
+        #[should_panic(expected = "Crate(Secp256k1(InvalidSecretKey))")]
         fn from_str_invalid_secret_key() {
             let _result =
                 EthereumExtendedPrivateKey::<N>::from_str(INVALID_EXTENDED_PRIVATE_KEY_SECP256K1_SECRET_KEY).unwrap();
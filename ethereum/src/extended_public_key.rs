@@ -60,12 +60,20 @@ impl<N: EthereumNetwork> ExtendedPublicKey for EthereumExtendedPublicKey<N> {
             return Err(ExtendedPublicKeyError::MaximumChildDepthReached(self.depth));
         }
 
+        let indices = path.to_vec()?;
+        if self.depth as usize + indices.len() > 255 {
+            return Err(ExtendedPublicKeyError::MaximumChildDepthReached(self.depth));
+        }
+
         let mut extended_public_key = self.clone();
 
-        for index in path.to_vec()?.into_iter() {
-            let public_key_serialized = &self.public_key.to_secp256k1_public_key().serialize()[..];
+        for index in indices.into_iter() {
+            let public_key_serialized = &extended_public_key
+                .public_key
+                .to_secp256k1_public_key()
+                .serialize_compressed()[..];
 
-            let mut mac = HmacSha512::new_varkey(&self.chain_code)?;
+            let mut mac = HmacSha512::new_varkey(&extended_public_key.chain_code)?;
             match index {
                 // HMAC-SHA512(Key = cpar, Data = serP(Kpar) || ser32(i))
                 ChildIndex::Normal(_) => mac.input(public_key_serialized),
@@ -81,7 +89,7 @@ impl<N: EthereumNetwork> ExtendedPublicKey for EthereumExtendedPublicKey<N> {
             let mut chain_code = [0u8; 32];
             chain_code[0..32].copy_from_slice(&hmac[32..]);
 
-            let mut public_key = self.public_key.to_secp256k1_public_key();
+            let mut public_key = extended_public_key.public_key.to_secp256k1_public_key();
             public_key.tweak_add_assign(&SecretKey::parse_slice(&hmac[..32])?)?;
             let public_key = Self::PublicKey::from_secp256k1_public_key(public_key);
 
@@ -110,6 +118,42 @@ impl<N: EthereumNetwork> ExtendedPublicKey for EthereumExtendedPublicKey<N> {
     fn to_address(&self, _format: &Self::Format) -> Result<Self::Address, AddressError> {
         self.public_key.to_address(_format)
     }
+
+    /// Returns the first 32 bits of the key identifier, hash160(compressed_public_key).
+    fn fingerprint(&self) -> [u8; 4] {
+        let mut fingerprint = [0u8; 4];
+        fingerprint.copy_from_slice(&hash160(&self.public_key.to_secp256k1_public_key().serialize_compressed())[0..4]);
+        fingerprint
+    }
+}
+
+impl<N: EthereumNetwork> EthereumExtendedPublicKey<N> {
+    /// Returns the depth of key derivation, e.g. 0 for the master key.
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// Returns the first 32 bits of the key identifier of the parent key, or zeros for the master key.
+    pub fn parent_fingerprint(&self) -> [u8; 4] {
+        self.parent_fingerprint
+    }
+
+    /// Returns the child index of the key, or 0 for the master key.
+    pub fn child_index(&self) -> ChildIndex {
+        self.child_index
+    }
+
+    /// Returns the 78-byte BIP32 serialization of the extended public key, without the checksum.
+    pub fn to_bytes(&self) -> [u8; 78] {
+        let mut result = [0u8; 78];
+        result[0..4].copy_from_slice(&[0x04u8, 0x88, 0xB2, 0x1E][..]);
+        result[4] = self.depth;
+        result[5..9].copy_from_slice(&self.parent_fingerprint[..]);
+        result[9..13].copy_from_slice(&u32::from(self.child_index).to_be_bytes());
+        result[13..45].copy_from_slice(&self.chain_code[..]);
+        result[45..78].copy_from_slice(&self.public_key.to_secp256k1_public_key().serialize_compressed());
+        result
+    }
 }
 
 impl<N: EthereumNetwork> FromStr for EthereumExtendedPublicKey<N> {
@@ -163,12 +207,7 @@ impl<N: EthereumNetwork> fmt::Display for EthereumExtendedPublicKey<N> {
     /// https://github.com/ethereum/bips/blob/master/bip-0032.mediawiki#serialization-format
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         let mut result = [0u8; 82];
-        result[0..4].copy_from_slice(&[0x04u8, 0x88, 0xB2, 0x1E][..]);
-        result[4] = self.depth as u8;
-        result[5..9].copy_from_slice(&self.parent_fingerprint[..]);
-        result[9..13].copy_from_slice(&u32::from(self.child_index).to_be_bytes());
-        result[13..45].copy_from_slice(&self.chain_code[..]);
-        result[45..78].copy_from_slice(&self.public_key.to_secp256k1_public_key().serialize_compressed());
+        result[0..78].copy_from_slice(&self.to_bytes());
 
         let sum = &checksum(&result[0..78])[0..4];
         result[78..82].copy_from_slice(sum);
@@ -436,6 +475,42 @@ mod tests {
             });
         }
 
+        #[test]
+        fn derive_relative() {
+            use wagyu_model::derivation_path::RelativeDerivationPath;
+
+            // BIP32 test vector 1 - m/0'/1/2' -> m/0'/1/2'/2/1000000000
+            let extended_public_key = EthereumExtendedPublicKey::<N>::from_str("xpub6D4BDPcP2GT577Vvch3R8wDkScZWzQzMMUm3PWbmWvVJrZwQY4VUNgqFJPMM3No2dFDFGTsxxpG5uJh7n7epu4trkrX7x7DogT5Uv6fcLW5").unwrap();
+            let path = RelativeDerivationPath::from_str("2/1000000000").unwrap();
+            assert_eq!(
+                "xpub6H1LXWLaKsWFhvm6RVpEL9P4KfRZSW7abD2ttkWP3SSQvnyA8FSVqNTEcYFgJS2UaFcxupHiYkro49S8yGasTvXEYBVPamhGW6cFJodrTHy",
+                extended_public_key.derive_relative(&path).unwrap().to_string()
+            );
+        }
+
+        #[test]
+        fn derive_children() {
+            use wagyu_model::derivation_path::{ChildIndexRange, RelativeDerivationPath};
+
+            // BIP32 test vector 1 - m/0'/1/2' -> m/0'/1/2'/2
+            let extended_public_key = EthereumExtendedPublicKey::<N>::from_str("xpub6D4BDPcP2GT577Vvch3R8wDkScZWzQzMMUm3PWbmWvVJrZwQY4VUNgqFJPMM3No2dFDFGTsxxpG5uJh7n7epu4trkrX7x7DogT5Uv6fcLW5").unwrap();
+            let range = ChildIndexRange::from_count(ChildIndex::Normal(1), 2).unwrap();
+            let children = extended_public_key.derive_children(range).unwrap();
+            assert_eq!(
+                extended_public_key
+                    .derive_relative(&RelativeDerivationPath::from_str("2").unwrap())
+                    .unwrap(),
+                children[1]
+            );
+            assert_eq!(
+                "xpub6FHa3pjLCk84BayeJxFW2SP4XRrFd1JYnxeLeU8EqN3vDfZmbqBqaGJAyiLjTAwm6ZLRQUMv1ZACTj37sR62cfN7fe5JnJ7dh8zL4fiyLHV",
+                children[1].to_string()
+            );
+
+            let range = ChildIndexRange::from_count(ChildIndex::Hardened(0), 1).unwrap();
+            assert!(extended_public_key.derive_children(range).is_err());
+        }
+
         #[test]
         fn from_str() {
             KEYPAIRS.iter().for_each(
@@ -457,6 +532,26 @@ mod tests {
                 test_to_string::<N>(extended_public_key);
             });
         }
+
+        #[test]
+        fn to_bytes() {
+            KEYPAIRS.iter().for_each(
+                |(path, _, child_index, _, _, parent_fingerprint, _, extended_public_key)| {
+                    let bytes = extended_public_key.from_base58().unwrap();
+                    let extended_public_key = EthereumExtendedPublicKey::<N>::from_str(extended_public_key).unwrap();
+                    assert_eq!(&bytes[0..78], &extended_public_key.to_bytes()[..]);
+                    assert_eq!(path.split('/').count() as u8 - 1, extended_public_key.depth());
+                    assert_eq!(
+                        *parent_fingerprint,
+                        hex::encode(extended_public_key.parent_fingerprint())
+                    );
+                    assert_eq!(
+                        child_index.parse::<u32>().unwrap(),
+                        u32::from(extended_public_key.child_index())
+                    );
+                },
+            );
+        }
     }
 
     mod test_invalid {
@@ -470,7 +565,7 @@ mod tests {
         const VALID_EXTENDED_PUBLIC_KEY: &str = "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8";
 
         #[test]
-        #[should_panic(expected = "Crate(\"libsecp256k1\", \"InvalidPublicKey\")")]
+        #[should_panic(expected = "Crate(Secp256k1(InvalidPublicKey))")]
         fn from_str_invalid_secret_key() {
             let _result =
                 EthereumExtendedPublicKey::<N>::from_str(INVALID_EXTENDED_PUBLIC_KEY__SECP256K1_PUBLIC_KEY).unwrap();
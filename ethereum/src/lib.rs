@@ -6,15 +6,27 @@
 #![warn(unused_extern_crates, dead_code)]
 #![forbid(unsafe_code)]
 
+#[macro_use]
+extern crate failure;
+
+pub mod abi;
+pub use self::abi::*;
+
 pub mod address;
 pub use self::address::*;
 
 pub mod amount;
 pub use self::amount::*;
 
+pub mod chain;
+pub use self::chain::*;
+
 pub mod derivation_path;
 pub use self::derivation_path::*;
 
+pub mod erc20;
+pub use self::erc20::*;
+
 pub mod extended_private_key;
 pub use self::extended_private_key::*;
 
@@ -30,6 +42,9 @@ pub use self::mnemonic::*;
 pub mod network;
 pub use self::network::*;
 
+pub mod nft;
+pub use self::nft::*;
+
 pub mod private_key;
 pub use self::private_key::*;
 
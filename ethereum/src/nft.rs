@@ -0,0 +1,259 @@
+use crate::address::EthereumAddress;
+use crate::amount::EthereumAmount;
+use crate::transaction::EthereumTransactionParameters;
+use wagyu_model::AddressError;
+
+use ethereum_types::U256;
+
+/// The function selector of `safeTransferFrom(address,address,uint256)` of ERC-721
+pub const ERC721_SAFE_TRANSFER_FROM_SELECTOR: [u8; 4] = [0x42, 0x84, 0x2e, 0x0e];
+/// The function selector of `safeTransferFrom(address,address,uint256,bytes)` of ERC-721
+pub const ERC721_SAFE_TRANSFER_FROM_WITH_DATA_SELECTOR: [u8; 4] = [0xb8, 0x8d, 0x4f, 0xde];
+/// The function selector of `safeTransferFrom(address,address,uint256,uint256,bytes)` of ERC-1155
+pub const ERC1155_SAFE_TRANSFER_FROM_SELECTOR: [u8; 4] = [0xf2, 0x42, 0x43, 0x2a];
+
+/// Represents an ERC-721 non-fungible token contract
+/// https://github.com/ethereum/EIPs/blob/master/EIPS/eip-721.md
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Erc721Token {
+    /// The address of the token contract
+    pub address: EthereumAddress,
+}
+
+impl Erc721Token {
+    /// Returns a token given the address of its contract.
+    pub fn new(address: &EthereumAddress) -> Self {
+        Self {
+            address: address.clone(),
+        }
+    }
+
+    /// Returns the calldata of `safeTransferFrom`, which transfers the token id from its owner to the recipient.
+    /// The data is passed to the recipient if it is a contract, and selects the overload with a `bytes` argument
+    /// if it is not empty.
+    pub fn safe_transfer_from(
+        &self,
+        from: &EthereumAddress,
+        to: &EthereumAddress,
+        token_id: U256,
+        data: &[u8],
+    ) -> Result<Vec<u8>, AddressError> {
+        let mut calldata = match data.is_empty() {
+            true => ERC721_SAFE_TRANSFER_FROM_SELECTOR.to_vec(),
+            false => ERC721_SAFE_TRANSFER_FROM_WITH_DATA_SELECTOR.to_vec(),
+        };
+        calldata.extend(address_word(from)?);
+        calldata.extend(address_word(to)?);
+        calldata.extend(uint_word(token_id));
+        if !data.is_empty() {
+            calldata.extend(bytes_tail(4, data));
+        }
+        Ok(calldata)
+    }
+
+    /// Returns the parameters of a transaction calling the token contract with the given calldata,
+    /// which keeps the gas, fees, nonce, and chain id of the given parameters.
+    pub fn to_transaction_parameters(
+        &self,
+        data: Vec<u8>,
+        parameters: &EthereumTransactionParameters,
+    ) -> EthereumTransactionParameters {
+        to_transaction_parameters(&self.address, data, parameters)
+    }
+}
+
+/// Represents an ERC-1155 multi token contract
+/// https://github.com/ethereum/EIPs/blob/master/EIPS/eip-1155.md
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Erc1155Token {
+    /// The address of the token contract
+    pub address: EthereumAddress,
+}
+
+impl Erc1155Token {
+    /// Returns a token given the address of its contract.
+    pub fn new(address: &EthereumAddress) -> Self {
+        Self {
+            address: address.clone(),
+        }
+    }
+
+    /// Returns the calldata of `safeTransferFrom`, which transfers an amount of the token id from its owner
+    /// to the recipient. The data is passed to the recipient if it is a contract.
+    pub fn safe_transfer_from(
+        &self,
+        from: &EthereumAddress,
+        to: &EthereumAddress,
+        id: U256,
+        amount: U256,
+        data: &[u8],
+    ) -> Result<Vec<u8>, AddressError> {
+        let mut calldata = ERC1155_SAFE_TRANSFER_FROM_SELECTOR.to_vec();
+        calldata.extend(address_word(from)?);
+        calldata.extend(address_word(to)?);
+        calldata.extend(uint_word(id));
+        calldata.extend(uint_word(amount));
+        calldata.extend(bytes_tail(5, data));
+        Ok(calldata)
+    }
+
+    /// Returns the parameters of a transaction calling the token contract with the given calldata,
+    /// which keeps the gas, fees, nonce, and chain id of the given parameters.
+    pub fn to_transaction_parameters(
+        &self,
+        data: Vec<u8>,
+        parameters: &EthereumTransactionParameters,
+    ) -> EthereumTransactionParameters {
+        to_transaction_parameters(&self.address, data, parameters)
+    }
+}
+
+/// Returns the parameters of a transaction calling the contract with the given calldata and no ether.
+fn to_transaction_parameters(
+    contract: &EthereumAddress,
+    data: Vec<u8>,
+    parameters: &EthereumTransactionParameters,
+) -> EthereumTransactionParameters {
+    EthereumTransactionParameters {
+        receiver: Some(contract.clone()),
+        amount: EthereumAmount::from_u256(U256::zero()),
+        data,
+        ..parameters.clone()
+    }
+}
+
+/// Returns the address left padded to a 32-byte word.
+fn address_word(address: &EthereumAddress) -> Result<Vec<u8>, AddressError> {
+    Ok([vec![0u8; 12], address.to_bytes()?].concat())
+}
+
+/// Returns the integer as a big-endian 32-byte word.
+fn uint_word(value: U256) -> Vec<u8> {
+    let mut word = [0u8; 32];
+    value.to_big_endian(&mut word);
+    word.to_vec()
+}
+
+/// Returns the offset of the last argument after the given number of head words, followed by
+/// the length of the bytes and the bytes right padded to a multiple of 32 bytes.
+fn bytes_tail(head_words: usize, data: &[u8]) -> Vec<u8> {
+    let mut tail = uint_word(U256::from(head_words * 32));
+    tail.extend(uint_word(U256::from(data.len())));
+    tail.extend_from_slice(data);
+    tail.extend(vec![0u8; (32 - data.len() % 32) % 32]);
+    tail
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::str::FromStr;
+    use tiny_keccak::keccak256;
+
+    const CONTRACT: &str = "0xBC4CA0EdA7647A8aB7C2061c2E118A18a936f13D";
+    const FROM: &str = "0xB5D590A6aBf5E349C1b6C511Bc87CEAbFB3D7e65";
+    const TO: &str = "0x52C3a8a79a521D10b25569847CB1a3FfB66550D6";
+
+    fn addresses() -> (EthereumAddress, EthereumAddress, EthereumAddress) {
+        (
+            EthereumAddress::from_str(CONTRACT).unwrap(),
+            EthereumAddress::from_str(FROM).unwrap(),
+            EthereumAddress::from_str(TO).unwrap(),
+        )
+    }
+
+    #[test]
+    fn selectors() {
+        assert_eq!(
+            ERC721_SAFE_TRANSFER_FROM_SELECTOR,
+            keccak256(b"safeTransferFrom(address,address,uint256)")[..4]
+        );
+        assert_eq!(
+            ERC721_SAFE_TRANSFER_FROM_WITH_DATA_SELECTOR,
+            keccak256(b"safeTransferFrom(address,address,uint256,bytes)")[..4]
+        );
+        assert_eq!(
+            ERC1155_SAFE_TRANSFER_FROM_SELECTOR,
+            keccak256(b"safeTransferFrom(address,address,uint256,uint256,bytes)")[..4]
+        );
+    }
+
+    #[test]
+    fn erc721_safe_transfer_from() {
+        let (contract, from, to) = addresses();
+        let token = Erc721Token::new(&contract);
+        assert_eq!(
+            "42842e0e000000000000000000000000b5d590a6abf5e349c1b6c511bc87ceabfb3d7e6500000000000000000000000052c3a8a79a521d10b25569847cb1a3ffb66550d600000000000000000000000000000000000000000000000000000000000004d2",
+            hex::encode(token.safe_transfer_from(&from, &to, U256::from(1234), &[]).unwrap())
+        );
+        assert_eq!(
+            "b88d4fde000000000000000000000000b5d590a6abf5e349c1b6c511bc87ceabfb3d7e6500000000000000000000000052c3a8a79a521d10b25569847cb1a3ffb66550d600000000000000000000000000000000000000000000000000000000000004d20000000000000000000000000000000000000000000000000000000000000080000000000000000000000000000000000000000000000000000000000000003848656c6c6f2c204e46542072656365697665722120546869732064617461207370616e73206d6f7265207468616e206f6e6520776f72642e0000000000000000",
+            hex::encode(
+                token
+                    .safe_transfer_from(
+                        &from,
+                        &to,
+                        U256::from(1234),
+                        b"Hello, NFT receiver! This data spans more than one word."
+                    )
+                    .unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn erc1155_safe_transfer_from() {
+        let (contract, from, to) = addresses();
+        let token = Erc1155Token::new(&contract);
+        assert_eq!(
+            "f242432a000000000000000000000000b5d590a6abf5e349c1b6c511bc87ceabfb3d7e6500000000000000000000000052c3a8a79a521d10b25569847cb1a3ffb66550d60000000000000000000000000000000000000000000000000000000000000007000000000000000000000000000000000000000000000000000000000000001900000000000000000000000000000000000000000000000000000000000000a00000000000000000000000000000000000000000000000000000000000000000",
+            hex::encode(
+                token
+                    .safe_transfer_from(&from, &to, U256::from(7), U256::from(25), &[])
+                    .unwrap()
+            )
+        );
+        assert_eq!(
+            "f242432a000000000000000000000000b5d590a6abf5e349c1b6c511bc87ceabfb3d7e6500000000000000000000000052c3a8a79a521d10b25569847cb1a3ffb66550d68000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000a00000000000000000000000000000000000000000000000000000000000000002cafe000000000000000000000000000000000000000000000000000000000000",
+            hex::encode(
+                token
+                    .safe_transfer_from(
+                        &from,
+                        &to,
+                        (U256::one() << 255) + U256::one(),
+                        U256::one(),
+                        &[0xca, 0xfe]
+                    )
+                    .unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn to_transaction_parameters() {
+        let (contract, from, to) = addresses();
+        let parameters = EthereumTransactionParameters {
+            receiver: Some(to.clone()),
+            amount: EthereumAmount::from_wei("1000000000000000000").unwrap(),
+            gas: U256::from(100000),
+            gas_price: EthereumAmount::from_wei("20000000000").unwrap(),
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            access_list: None,
+            nonce: U256::from(9),
+            data: vec![],
+            chain_id: Some(137),
+        };
+        let token = Erc1155Token::new(&contract);
+        let data = token
+            .safe_transfer_from(&from, &to, U256::from(7), U256::from(25), &[])
+            .unwrap();
+        let parameters = token.to_transaction_parameters(data.clone(), &parameters);
+        assert_eq!(Some(contract), parameters.receiver);
+        assert_eq!(U256::zero(), parameters.amount.0);
+        assert_eq!(data, parameters.data);
+        assert_eq!(U256::from(9), parameters.nonce);
+        assert_eq!(Some(137), parameters.chain_id);
+    }
+}
@@ -1,14 +1,15 @@
 use crate::address::EthereumAddress;
 use crate::format::EthereumFormat;
 use crate::public_key::EthereumPublicKey;
-use wagyu_model::{Address, AddressError, PrivateKey, PrivateKeyError, PublicKey};
+use wagyu_model::{Address, AddressError, ExposeSecret, PrivateKey, PrivateKeyError, PublicKey, Redacted};
 
 use core::{fmt, fmt::Display, str::FromStr};
 use rand::Rng;
 use secp256k1;
+use zeroize::Zeroize;
 
 /// Represents an Ethereum private key
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq)]
 pub struct EthereumPrivateKey(secp256k1::SecretKey);
 
 impl PrivateKey for EthereumPrivateKey {
@@ -18,8 +19,10 @@ impl PrivateKey for EthereumPrivateKey {
 
     /// Returns a randomly-generated Ethereum private key.
     fn new<R: Rng>(rng: &mut R) -> Result<Self, PrivateKeyError> {
-        let random: [u8; 32] = rng.gen();
-        Ok(Self(secp256k1::SecretKey::parse_slice(&random)?))
+        let mut random: [u8; 32] = rng.gen();
+        let secret_key = secp256k1::SecretKey::parse_slice(&random);
+        random.zeroize();
+        Ok(Self(secret_key?))
     }
 
     /// Returns the public key of the corresponding Ethereum private key.
@@ -53,16 +56,40 @@ impl FromStr for EthereumPrivateKey {
             return Err(PrivateKeyError::InvalidCharacterLength(private_key.len()));
         }
 
-        let secret_key = hex::decode(private_key)?;
-        Ok(Self(secp256k1::SecretKey::parse_slice(&secret_key)?))
+        let mut secret_key = hex::decode(private_key)?;
+        let result = secp256k1::SecretKey::parse_slice(&secret_key);
+        secret_key.zeroize();
+        Ok(Self(result?))
     }
 }
 
 impl Display for EthereumPrivateKey {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let mut private_key = [0u8; 32];
-        private_key.copy_from_slice(&self.0.serialize());
-        write!(f, "{}", hex::encode(private_key).to_string())
+        let mut private_key = self.0.serialize();
+        let mut output = hex::encode(private_key);
+        private_key.zeroize();
+        let result = write!(f, "{}", output);
+        output.zeroize();
+        result
+    }
+}
+
+impl fmt::Debug for EthereumPrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("EthereumPrivateKey").field(&Redacted).finish()
+    }
+}
+
+impl ExposeSecret for EthereumPrivateKey {
+    fn fmt_secret(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("EthereumPrivateKey").field(&self.0).finish()
+    }
+}
+
+impl Zeroize for EthereumPrivateKey {
+    /// Replaces the secret key with a placeholder, clearing the previous secret key on drop.
+    fn zeroize(&mut self) {
+        self.0 = secp256k1::SecretKey::default();
     }
 }
 
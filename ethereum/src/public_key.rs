@@ -1,7 +1,7 @@
 use crate::address::EthereumAddress;
 use crate::format::EthereumFormat;
 use crate::private_key::EthereumPrivateKey;
-use wagyu_model::{Address, AddressError, PublicKey, PublicKeyError};
+use wagyu_model::{crypto::secp256k1_public_key, Address, AddressError, PublicKey, PublicKeyError};
 
 use core::{fmt, fmt::Display, str::FromStr};
 use secp256k1;
@@ -17,9 +17,7 @@ impl PublicKey for EthereumPublicKey {
 
     /// Returns the address corresponding to the given public key.
     fn from_private_key(private_key: &Self::PrivateKey) -> Self {
-        Self(secp256k1::PublicKey::from_secret_key(
-            &private_key.to_secp256k1_secret_key(),
-        ))
+        Self(secp256k1_public_key(&private_key.to_secp256k1_secret_key()))
     }
 
     /// Returns the address of the corresponding private key.
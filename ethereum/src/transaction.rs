@@ -1,62 +1,110 @@
 use crate::address::EthereumAddress;
 use crate::amount::EthereumAmount;
+use crate::chain::EthereumChain;
 use crate::format::EthereumFormat;
 use crate::network::EthereumNetwork;
 use crate::private_key::EthereumPrivateKey;
 use crate::public_key::EthereumPublicKey;
-use wagyu_model::{PrivateKey, PublicKey, Transaction, TransactionError, TransactionId};
+use wagyu_model::{crypto::secp256k1_sign, PrivateKey, PublicKey, Transaction, TransactionError, TransactionId};
 
 use core::{fmt, marker::PhantomData, str::FromStr};
 use ethereum_types::U256;
-use rlp::{decode_list, RlpStream};
+use rlp::{decode_list, Rlp, RlpStream};
 use secp256k1;
 use tiny_keccak::keccak256;
 
-pub fn to_bytes(value: u32) -> Result<Vec<u8>, TransactionError> {
-    match value {
-        // bounded by u8::max_value()
-        0..=255 => Ok(vec![value as u8]),
-        // bounded by u16::max_value()
-        256..=65535 => Ok((value as u16).to_le_bytes().to_vec()),
-        // bounded by u32::max_value()
-        _ => Ok(value.to_le_bytes().to_vec()),
-    }
+/// Returns the big-endian bytes of an RLP integer, without leading zeros.
+pub fn to_bytes(value: u64) -> Result<Vec<u8>, TransactionError> {
+    Ok(trim_leading_zeros(&value.to_be_bytes()))
 }
 
-pub fn from_bytes(value: &Vec<u8>) -> Result<u32, TransactionError> {
+/// Returns the value of the big-endian bytes of an RLP integer.
+pub fn from_bytes(value: &[u8]) -> Result<u64, TransactionError> {
     match value.len() {
-        0 => Ok(0u32),
-        1 => Ok(u32::from_le_bytes([value[0], 0, 0, 0])),
-        2 => Ok(u32::from_le_bytes([value[0], value[1], 0, 0])),
-        3 => Ok(u32::from_le_bytes([value[0], value[1], value[2], 0])),
-        4 => Ok(u32::from_le_bytes([value[0], value[1], value[2], value[3]])),
+        0..=8 => Ok(value.iter().fold(0u64, |result, byte| (result << 8) | u64::from(*byte))),
         _ => Err(TransactionError::Message(
-            "invalid byte length for u32 value".to_string(),
+            "invalid byte length for u64 value".to_string(),
         )),
     }
 }
 
+/// The EIP-2718 type of EIP-2930 transactions
+pub const EIP2930_TRANSACTION_TYPE: u8 = 0x01;
+/// The EIP-2718 type of EIP-1559 transactions
+pub const EIP1559_TRANSACTION_TYPE: u8 = 0x02;
+
+/// Represents the type of an Ethereum transaction
+/// https://github.com/ethereum/EIPs/blob/master/EIPS/eip-2718.md
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EthereumTransactionType {
+    /// A legacy transaction with a gas price, protected with a chain id (EIP-155)
+    Legacy,
+    /// An access list transaction with a gas price (EIP-2930)
+    Eip2930,
+    /// A dynamic fee transaction with a maximum fee and priority fee per gas (EIP-1559)
+    Eip1559,
+}
+
+/// Represents an entry of the access list of a transaction, an account and the storage keys the transaction accesses
+/// https://github.com/ethereum/EIPs/blob/master/EIPS/eip-2930.md
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EthereumAccessListItem {
+    /// The address of the account
+    pub address: EthereumAddress,
+    /// The storage keys of the account
+    pub storage_keys: Vec<[u8; 32]>,
+}
+
 /// Represents the parameters for an Ethereum transaction
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct EthereumTransactionParameters {
-    /// The address of the receiver
-    pub receiver: EthereumAddress,
+    /// The address of the receiver, or none for a contract deployment, whose data is the init code of the contract
+    pub receiver: Option<EthereumAddress>,
     /// The amount (in wei)
     pub amount: EthereumAmount,
     /// The transaction gas limit
     pub gas: U256,
     /// The transaction gas price in wei
     pub gas_price: EthereumAmount,
+    /// The maximum fee per gas in wei, in place of the gas price of an EIP-1559 transaction
+    pub max_fee_per_gas: Option<EthereumAmount>,
+    /// The maximum priority fee per gas in wei of an EIP-1559 transaction
+    pub max_priority_fee_per_gas: Option<EthereumAmount>,
+    /// The access list of an EIP-2930 transaction, or of an EIP-1559 transaction if it is not empty
+    pub access_list: Option<Vec<EthereumAccessListItem>>,
     /// The nonce of the Ethereum account
     pub nonce: U256,
     /// The transaction data
     pub data: Vec<u8>,
+    /// The EIP-155 chain id of the transaction, or the chain id of the network if none
+    pub chain_id: Option<u64>,
+}
+
+impl EthereumTransactionParameters {
+    /// Returns the type of the transaction, which is an EIP-1559 transaction if it has a maximum fee per gas,
+    /// or an EIP-2930 transaction if it only has an access list.
+    pub fn transaction_type(&self) -> Result<EthereumTransactionType, TransactionError> {
+        match (&self.max_fee_per_gas, &self.max_priority_fee_per_gas) {
+            (None, None) => match self.access_list {
+                Some(_) => Ok(EthereumTransactionType::Eip2930),
+                None => Ok(EthereumTransactionType::Legacy),
+            },
+            (Some(max_fee), Some(max_priority_fee)) => match max_priority_fee.0 <= max_fee.0 {
+                true => Ok(EthereumTransactionType::Eip1559),
+                false => Err(TransactionError::Message(
+                    "max priority fee per gas exceeds max fee per gas".to_string(),
+                )),
+            },
+            (Some(_), None) => Err(TransactionError::InvalidInputs("max priority fee per gas".to_string())),
+            (None, Some(_)) => Err(TransactionError::InvalidInputs("max fee per gas".to_string())),
+        }
+    }
 }
 
 /// Represents an Ethereum transaction signature
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 struct EthereumTransactionSignature {
-    /// The V field of the signature protected with a chain_id
+    /// The V field of the signature protected with a chain_id, or the y-parity of a typed transaction
     v: Vec<u8>,
     /// The R field of the signature
     r: Vec<u8>,
@@ -101,6 +149,7 @@ impl<N: EthereumNetwork> Transaction for EthereumTransaction<N> {
 
     /// Returns an unsigned transaction given the transaction parameters.
     fn new(parameters: &Self::TransactionParameters) -> Result<Self, TransactionError> {
+        parameters.transaction_type()?;
         Ok(Self {
             sender: None,
             parameters: parameters.clone(),
@@ -111,12 +160,13 @@ impl<N: EthereumNetwork> Transaction for EthereumTransaction<N> {
 
     /// Returns a signed transaction given the private key of the sender.
     /// https://github.com/ethereum/EIPs/blob/master/EIPS/eip-155.md
+    /// https://github.com/ethereum/EIPs/blob/master/EIPS/eip-2930.md
     fn sign(&self, private_key: &Self::PrivateKey) -> Result<Self, TransactionError> {
         match (&self.sender, &self.signature) {
             (Some(_), Some(_)) => Ok(self.clone()),
             (Some(_), None) | (None, Some(_)) => Err(TransactionError::InvalidTransactionState),
             (None, None) => {
-                let (signature, v) = secp256k1::sign(
+                let (signature, v) = secp256k1_sign(
                     &secp256k1::Message::parse_slice(&self.to_transaction_id()?.txid)?,
                     &private_key.to_secp256k1_secret_key(),
                 );
@@ -124,10 +174,26 @@ impl<N: EthereumNetwork> Transaction for EthereumTransaction<N> {
 
                 let mut transaction = self.clone();
                 transaction.sender = Some(private_key.to_address(&EthereumFormat::Standard)?);
-                transaction.signature = Some(EthereumTransactionSignature {
-                    v: to_bytes(Into::<i32>::into(v) as u32 + N::CHAIN_ID * 2 + 35)?, // EIP155
-                    r: signature[0..32].to_vec(),
-                    s: signature[32..64].to_vec(),
+                transaction.signature = Some(match self.parameters.transaction_type()? {
+                    EthereumTransactionType::Legacy => EthereumTransactionSignature {
+                        // EIP155
+                        v: to_bytes(
+                            self.chain_id()
+                                .checked_mul(2)
+                                .and_then(|chain_id| chain_id.checked_add(35 + u64::from(Into::<u8>::into(v))))
+                                .ok_or(TransactionError::InvalidChainId(self.chain_id()))?,
+                        )?,
+                        r: signature[0..32].to_vec(),
+                        s: signature[32..64].to_vec(),
+                    },
+                    // Typed transactions are signed with the y-parity, and their fields are RLP integers
+                    EthereumTransactionType::Eip2930 | EthereumTransactionType::Eip1559 => {
+                        EthereumTransactionSignature {
+                            v: trim_leading_zeros(&[Into::<u8>::into(v)]),
+                            r: trim_leading_zeros(&signature[0..32]),
+                            s: trim_leading_zeros(&signature[32..64]),
+                        }
+                    }
                 });
                 Ok(transaction)
             }
@@ -136,14 +202,26 @@ impl<N: EthereumNetwork> Transaction for EthereumTransaction<N> {
 
     /// Returns a transaction given the transaction bytes.
     /// https://github.com/ethereum/EIPs/blob/master/EIPS/eip-155.md
+    /// https://github.com/ethereum/EIPs/blob/master/EIPS/eip-2718.md
     fn from_transaction_bytes(transaction: &Vec<u8>) -> Result<Self, TransactionError> {
+        // A typed transaction starts with its type, and a legacy transaction with an RLP list
+        match transaction.first() {
+            Some(&EIP2930_TRANSACTION_TYPE) | Some(&EIP1559_TRANSACTION_TYPE) => {
+                return Self::from_typed_bytes(transaction[0], &transaction[1..])
+            }
+            Some(transaction_type) if *transaction_type < 0x80 => {
+                return Err(TransactionError::UnsupportedTransactionType(*transaction_type))
+            }
+            _ => (),
+        };
+
         let list: Vec<Vec<u8>> = decode_list(&transaction);
         if list.len() != 9 {
             return Err(TransactionError::InvalidRlpLength(list.len()));
         }
 
         let parameters = EthereumTransactionParameters {
-            receiver: EthereumAddress::from_str(&hex::encode(&list[3]))?,
+            receiver: receiver(&list[3])?,
             amount: match list[4].is_empty() {
                 true => EthereumAmount::from_u256(U256::zero()),
                 false => EthereumAmount::from_u256(U256::from(list[4].as_slice())),
@@ -156,27 +234,41 @@ impl<N: EthereumNetwork> Transaction for EthereumTransaction<N> {
                 true => EthereumAmount::from_u256(U256::zero()),
                 false => EthereumAmount::from_u256(U256::from(list[1].as_slice())),
             },
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            access_list: None,
             nonce: match list[0].is_empty() {
                 true => U256::zero(),
                 false => U256::from(list[0].as_slice()),
             },
             data: list[5].clone(),
+            chain_id: None,
         };
 
         match list[7].is_empty() && list[8].is_empty() {
             true => {
-                // Raw transaction
+                // Raw transaction, which holds its chain id in place of the V field
                 Ok(Self {
                     sender: None,
-                    parameters,
+                    parameters: EthereumTransactionParameters {
+                        chain_id: Some(from_bytes(&list[6])?),
+                        ..parameters
+                    },
                     signature: None,
                     _network: PhantomData,
                 })
             }
             false => {
-                // Signed transaction
+                // Signed transaction, whose V field is the recovery id protected with the chain id (EIP155)
                 let v = from_bytes(&list[6])?;
-                let recovery_id = secp256k1::RecoveryId::parse((v - N::CHAIN_ID * 2 - 35) as u8)?;
+                if v < 35 {
+                    return Err(TransactionError::InvalidChainId(v));
+                }
+                let parameters = EthereumTransactionParameters {
+                    chain_id: Some((v - 35) / 2),
+                    ..parameters
+                };
+                let recovery_id = secp256k1::RecoveryId::parse(((v - 35) % 2) as u8)?;
                 let mut signature = list[7].clone();
                 signature.extend_from_slice(&list[8]);
 
@@ -219,20 +311,21 @@ impl<N: EthereumNetwork> Transaction for EthereumTransaction<N> {
             transaction_rlp.append(&parameters.nonce);
             transaction_rlp.append(&parameters.gas_price.0);
             transaction_rlp.append(&parameters.gas);
-            transaction_rlp.append(&hex::decode(&parameters.receiver.to_string()[2..])?);
+            transaction_rlp.append(&receiver_bytes(&parameters.receiver)?);
             transaction_rlp.append(&parameters.amount.0);
             transaction_rlp.append(&parameters.data);
             Ok(())
         }
 
         // Returns the raw transaction (in RLP).
-        fn raw_transaction<N: EthereumNetwork>(
+        fn raw_transaction(
             parameters: &EthereumTransactionParameters,
+            chain_id: u64,
         ) -> Result<RlpStream, TransactionError> {
             let mut transaction_rlp = RlpStream::new();
             transaction_rlp.begin_list(9);
             encode_transaction(&mut transaction_rlp, parameters)?;
-            transaction_rlp.append(&to_bytes(N::CHAIN_ID)?);
+            transaction_rlp.append(&chain_id);
             transaction_rlp.append(&0u8);
             transaction_rlp.append(&0u8);
             Ok(transaction_rlp)
@@ -252,9 +345,74 @@ impl<N: EthereumNetwork> Transaction for EthereumTransaction<N> {
             Ok(transaction_rlp)
         }
 
-        match &self.signature {
-            Some(signature) => Ok(signed_transaction(&self.parameters, signature)?.out()),
-            None => Ok(raw_transaction::<N>(&self.parameters)?.out()),
+        // Returns the typed transaction prefixed by its type, which is signed without its signature.
+        // https://github.com/ethereum/EIPs/blob/master/EIPS/eip-2930.md
+        // https://github.com/ethereum/EIPs/blob/master/EIPS/eip-1559.md
+        fn typed_transaction(
+            transaction_type: u8,
+            parameters: &EthereumTransactionParameters,
+            chain_id: u64,
+            signature: Option<&EthereumTransactionSignature>,
+        ) -> Result<Vec<u8>, TransactionError> {
+            let fields = if transaction_type == EIP1559_TRANSACTION_TYPE {
+                9
+            } else {
+                8
+            };
+            let mut transaction_rlp = RlpStream::new();
+            transaction_rlp.begin_list(if signature.is_some() { fields + 3 } else { fields });
+            transaction_rlp.append(&chain_id);
+            transaction_rlp.append(&parameters.nonce);
+            match transaction_type {
+                EIP1559_TRANSACTION_TYPE => {
+                    transaction_rlp.append(&parameters.max_priority_fee_per_gas.map(|fee| fee.0).unwrap_or_default());
+                    transaction_rlp.append(&parameters.max_fee_per_gas.map(|fee| fee.0).unwrap_or_default());
+                }
+                _ => {
+                    transaction_rlp.append(&parameters.gas_price.0);
+                }
+            };
+            transaction_rlp.append(&parameters.gas);
+            transaction_rlp.append(&receiver_bytes(&parameters.receiver)?);
+            transaction_rlp.append(&parameters.amount.0);
+            transaction_rlp.append(&parameters.data);
+
+            let access_list = parameters.access_list.as_deref().unwrap_or(&[]);
+            transaction_rlp.begin_list(access_list.len());
+            for item in access_list {
+                transaction_rlp.begin_list(2);
+                transaction_rlp.append(&hex::decode(&item.address.to_string()[2..])?);
+                transaction_rlp.begin_list(item.storage_keys.len());
+                for storage_key in &item.storage_keys {
+                    transaction_rlp.append(&storage_key.to_vec());
+                }
+            }
+
+            if let Some(signature) = signature {
+                transaction_rlp.append(&signature.v);
+                transaction_rlp.append(&signature.r);
+                transaction_rlp.append(&signature.s);
+            }
+            Ok([vec![transaction_type], transaction_rlp.out()].concat())
+        }
+
+        match (self.parameters.transaction_type()?, &self.signature) {
+            (EthereumTransactionType::Eip2930, signature) => typed_transaction(
+                EIP2930_TRANSACTION_TYPE,
+                &self.parameters,
+                self.chain_id(),
+                signature.as_ref(),
+            ),
+            (EthereumTransactionType::Eip1559, signature) => typed_transaction(
+                EIP1559_TRANSACTION_TYPE,
+                &self.parameters,
+                self.chain_id(),
+                signature.as_ref(),
+            ),
+            (EthereumTransactionType::Legacy, Some(signature)) => {
+                Ok(signed_transaction(&self.parameters, signature)?.out())
+            }
+            (EthereumTransactionType::Legacy, None) => Ok(raw_transaction(&self.parameters, self.chain_id())?.out()),
         }
     }
 
@@ -267,6 +425,178 @@ impl<N: EthereumNetwork> Transaction for EthereumTransaction<N> {
     }
 }
 
+impl<N: EthereumNetwork> EthereumTransaction<N> {
+    /// Returns the EIP-155 chain id of the transaction, which is the chain id of the network if none is given.
+    pub fn chain_id(&self) -> u64 {
+        self.parameters.chain_id.unwrap_or_else(|| u64::from(N::CHAIN_ID))
+    }
+
+    /// Returns the chain of the transaction, if its chain id is known.
+    pub fn chain(&self) -> Option<EthereumChain> {
+        EthereumChain::from_chain_id(self.chain_id())
+    }
+
+    /// Returns the address of the contract deployed by the transaction, if it is a signed contract deployment.
+    pub fn contract_address(&self) -> Result<Option<EthereumAddress>, TransactionError> {
+        match (&self.parameters.receiver, &self.sender) {
+            (None, Some(sender)) => Ok(Some(EthereumAddress::create_address(sender, self.parameters.nonce)?)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Returns the typed transaction given its type and the RLP list following it.
+    /// https://github.com/ethereum/EIPs/blob/master/EIPS/eip-2930.md
+    /// https://github.com/ethereum/EIPs/blob/master/EIPS/eip-1559.md
+    fn from_typed_bytes(transaction_type: u8, transaction: &[u8]) -> Result<Self, TransactionError> {
+        // Returns the RLP integer at the given index of the list.
+        fn integer(list: &Rlp, index: usize) -> Result<U256, TransactionError> {
+            let integer = list.at(index)?.data()?;
+            match integer.len() {
+                0..=32 => Ok(U256::from(integer)),
+                length => Err(TransactionError::InvalidDataLength(length)),
+            }
+        }
+
+        // Returns the access list at the given index of the list.
+        fn access_list(list: &Rlp, index: usize) -> Result<Vec<EthereumAccessListItem>, TransactionError> {
+            list.at(index)?
+                .iter()
+                .map(|item| {
+                    if item.item_count()? != 2 {
+                        return Err(TransactionError::InvalidRlpLength(item.item_count()?));
+                    }
+                    let storage_keys = item
+                        .at(1)?
+                        .iter()
+                        .map(|storage_key| {
+                            let mut key = [0u8; 32];
+                            match storage_key.data()? {
+                                data if data.len() == 32 => key.copy_from_slice(data),
+                                data => return Err(TransactionError::InvalidDataLength(data.len())),
+                            };
+                            Ok(key)
+                        })
+                        .collect::<Result<Vec<_>, TransactionError>>()?;
+                    Ok(EthereumAccessListItem {
+                        address: EthereumAddress::from_str(&hex::encode(item.at(0)?.data()?))?,
+                        storage_keys,
+                    })
+                })
+                .collect()
+        }
+
+        // The fees of EIP-1559 transactions take one more field than the gas price
+        let offset = match transaction_type {
+            EIP1559_TRANSACTION_TYPE => 1,
+            _ => 0,
+        };
+        let list = Rlp::new(transaction);
+        let length = list.item_count()?;
+        if length != 8 + offset && length != 11 + offset {
+            return Err(TransactionError::InvalidRlpLength(length));
+        }
+
+        let chain_id = Some(from_bytes(list.at(0)?.data()?)?);
+
+        let access_list = access_list(&list, 7 + offset)?;
+        let parameters = match transaction_type {
+            EIP1559_TRANSACTION_TYPE => EthereumTransactionParameters {
+                receiver: receiver(list.at(5)?.data()?)?,
+                amount: EthereumAmount::from_u256(integer(&list, 6)?),
+                gas: integer(&list, 4)?,
+                gas_price: EthereumAmount::from_u256(U256::zero()),
+                max_fee_per_gas: Some(EthereumAmount::from_u256(integer(&list, 3)?)),
+                max_priority_fee_per_gas: Some(EthereumAmount::from_u256(integer(&list, 2)?)),
+                access_list: match access_list.is_empty() {
+                    true => None,
+                    false => Some(access_list),
+                },
+                nonce: integer(&list, 1)?,
+                data: list.at(7)?.data()?.to_vec(),
+                chain_id,
+            },
+            _ => EthereumTransactionParameters {
+                receiver: receiver(list.at(4)?.data()?)?,
+                amount: EthereumAmount::from_u256(integer(&list, 5)?),
+                gas: integer(&list, 3)?,
+                gas_price: EthereumAmount::from_u256(integer(&list, 2)?),
+                max_fee_per_gas: None,
+                max_priority_fee_per_gas: None,
+                access_list: Some(access_list),
+                nonce: integer(&list, 1)?,
+                data: list.at(6)?.data()?.to_vec(),
+                chain_id,
+            },
+        };
+        let raw_transaction = Self {
+            sender: None,
+            parameters: parameters.clone(),
+            signature: None,
+            _network: PhantomData,
+        };
+        if length == 8 + offset {
+            return Ok(raw_transaction);
+        }
+
+        // The signature is made by the sender over the transaction without its signature
+        let (v, r, s) = (
+            list.at(8 + offset)?.data()?,
+            list.at(9 + offset)?.data()?,
+            list.at(10 + offset)?.data()?,
+        );
+        let recovery_id = match v {
+            [] => secp256k1::RecoveryId::parse(0)?,
+            [parity] => secp256k1::RecoveryId::parse(*parity)?,
+            _ => return Err(TransactionError::InvalidDataLength(v.len())),
+        };
+        let mut signature = [0u8; 64];
+        if r.len() > 32 || s.len() > 32 {
+            return Err(TransactionError::InvalidDataLength(r.len().max(s.len())));
+        }
+        signature[32 - r.len()..32].copy_from_slice(r);
+        signature[64 - s.len()..].copy_from_slice(s);
+
+        let message = secp256k1::Message::parse_slice(&raw_transaction.to_transaction_id()?.txid)?;
+        let public_key = EthereumPublicKey::from_secp256k1_public_key(secp256k1::recover(
+            &message,
+            &secp256k1::Signature::parse_slice(&signature)?,
+            &recovery_id,
+        )?);
+
+        Ok(Self {
+            sender: Some(public_key.to_address(&EthereumFormat::Standard)?),
+            parameters,
+            signature: Some(EthereumTransactionSignature {
+                v: v.to_vec(),
+                r: r.to_vec(),
+                s: s.to_vec(),
+            }),
+            _network: PhantomData,
+        })
+    }
+}
+
+/// Returns the bytes of the receiver, which are empty for a contract deployment.
+fn receiver_bytes(receiver: &Option<EthereumAddress>) -> Result<Vec<u8>, TransactionError> {
+    match receiver {
+        Some(receiver) => Ok(receiver.to_bytes()?),
+        None => Ok(vec![]),
+    }
+}
+
+/// Returns the receiver given its bytes, which are empty for a contract deployment.
+fn receiver(bytes: &[u8]) -> Result<Option<EthereumAddress>, TransactionError> {
+    match bytes.is_empty() {
+        true => Ok(None),
+        false => Ok(Some(EthereumAddress::from_str(&hex::encode(bytes))?)),
+    }
+}
+
+/// Returns the given big-endian bytes without their leading zeros, as an RLP integer.
+fn trim_leading_zeros(bytes: &[u8]) -> Vec<u8> {
+    bytes.iter().skip_while(|byte| **byte == 0).cloned().collect()
+}
+
 impl<N: EthereumNetwork> FromStr for EthereumTransaction<N> {
     type Err = TransactionError;
 
@@ -313,12 +643,16 @@ mod tests {
         let expected_signed_transaction_hash = transaction.signed_transaction_hash;
         let private_key = EthereumPrivateKey::from_str(transaction.private_key).unwrap();
         let parameters = EthereumTransactionParameters {
-            receiver: EthereumAddress::from_str(transaction.to).unwrap(),
+            receiver: Some(EthereumAddress::from_str(transaction.to).unwrap()),
             amount: EthereumAmount::from_wei(transaction.value).unwrap(),
             gas: U256::from_dec_str(transaction.gas).unwrap(),
             gas_price: EthereumAmount::from_wei(transaction.gas_price).unwrap(),
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            access_list: None,
             nonce: U256::from_dec_str(transaction.nonce).unwrap(),
             data: transaction.data.as_bytes().to_vec(),
+            chain_id: None,
         };
 
         let transaction = EthereumTransaction::<N>::new(&parameters).unwrap();
@@ -334,12 +668,16 @@ mod tests {
         let expected_signed_transaction = transaction.signed_transaction;
         let private_key = EthereumPrivateKey::from_str(transaction.private_key).unwrap();
         let parameters = EthereumTransactionParameters {
-            receiver: EthereumAddress::from_str(transaction.to).unwrap(),
+            receiver: Some(EthereumAddress::from_str(transaction.to).unwrap()),
             amount: EthereumAmount::from_wei(transaction.value).unwrap(),
             gas: U256::from_dec_str(transaction.gas).unwrap(),
             gas_price: EthereumAmount::from_wei(transaction.gas_price).unwrap(),
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            access_list: None,
             nonce: U256::from_dec_str(transaction.nonce).unwrap(),
             data: transaction.data.as_bytes().to_vec(),
+            chain_id: None,
         };
 
         let transaction = EthereumTransaction::<N>::new(&parameters).unwrap();
@@ -358,17 +696,23 @@ mod tests {
     fn test_from_transaction_bytes<N: EthereumNetwork>(transaction: &TransactionTestCase) {
         let private_key = EthereumPrivateKey::from_str(transaction.private_key).unwrap();
         let expected_sender = Some(private_key.to_address(&EthereumFormat::Standard).unwrap());
+        let expected_chain_id = u64::from(transaction.chain_id);
         let expected_parameters = EthereumTransactionParameters {
-            receiver: EthereumAddress::from_str(transaction.to).unwrap(),
+            receiver: Some(EthereumAddress::from_str(transaction.to).unwrap()),
             amount: EthereumAmount::from_wei(transaction.value).unwrap(),
             gas: U256::from_dec_str(transaction.gas).unwrap(),
             gas_price: EthereumAmount::from_wei(transaction.gas_price).unwrap(),
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            access_list: None,
             nonce: U256::from_dec_str(transaction.nonce).unwrap(),
             data: transaction.data.as_bytes().to_vec(),
+            chain_id: Some(expected_chain_id),
         };
         let signed_transaction_bytes = hex::decode(&transaction.signed_transaction[2..]).unwrap();
 
         let transaction = EthereumTransaction::<N>::from_transaction_bytes(&signed_transaction_bytes).unwrap();
+        assert_eq!(expected_chain_id, transaction.chain_id());
         assert_eq!(expected_sender, transaction.sender);
         assert_eq!(expected_parameters, transaction.parameters);
         assert_eq!(signed_transaction_bytes, transaction.to_transaction_bytes().unwrap());
@@ -378,12 +722,16 @@ mod tests {
         let expected_signed_transaction_bytes = hex::decode(&transaction.signed_transaction[2..]).unwrap();
         let private_key = EthereumPrivateKey::from_str(transaction.private_key).unwrap();
         let parameters = EthereumTransactionParameters {
-            receiver: EthereumAddress::from_str(transaction.to).unwrap(),
+            receiver: Some(EthereumAddress::from_str(transaction.to).unwrap()),
             amount: EthereumAmount::from_wei(transaction.value).unwrap(),
             gas: U256::from_dec_str(transaction.gas).unwrap(),
             gas_price: EthereumAmount::from_wei(transaction.gas_price).unwrap(),
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            access_list: None,
             nonce: U256::from_dec_str(transaction.nonce).unwrap(),
             data: transaction.data.as_bytes().to_vec(),
+            chain_id: None,
         };
 
         let transaction = EthereumTransaction::<N>::new(&parameters).unwrap();
@@ -398,12 +746,16 @@ mod tests {
         let expected_signed_transaction_hash = transaction.signed_transaction_hash;
         let private_key = EthereumPrivateKey::from_str(transaction.private_key).unwrap();
         let parameters = EthereumTransactionParameters {
-            receiver: EthereumAddress::from_str(transaction.to).unwrap(),
+            receiver: Some(EthereumAddress::from_str(transaction.to).unwrap()),
             amount: EthereumAmount::from_wei(transaction.value).unwrap(),
             gas: U256::from_dec_str(transaction.gas).unwrap(),
             gas_price: EthereumAmount::from_wei(transaction.gas_price).unwrap(),
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            access_list: None,
             nonce: U256::from_dec_str(transaction.nonce).unwrap(),
             data: transaction.data.as_bytes().to_vec(),
+            chain_id: None,
         };
 
         let transaction = EthereumTransaction::<N>::new(&parameters).unwrap();
@@ -418,12 +770,16 @@ mod tests {
         let expected_signed_transaction = transaction.signed_transaction;
         let private_key = EthereumPrivateKey::from_str(transaction.private_key).unwrap();
         let parameters = EthereumTransactionParameters {
-            receiver: EthereumAddress::from_str(transaction.to).unwrap(),
+            receiver: Some(EthereumAddress::from_str(transaction.to).unwrap()),
             amount: EthereumAmount::from_wei(transaction.value).unwrap(),
             gas: U256::from_dec_str(transaction.gas).unwrap(),
             gas_price: EthereumAmount::from_wei(transaction.gas_price).unwrap(),
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            access_list: None,
             nonce: U256::from_dec_str(transaction.nonce).unwrap(),
             data: transaction.data.as_bytes().to_vec(),
+            chain_id: None,
         };
 
         let transaction = EthereumTransaction::<N>::new(&parameters).unwrap();
@@ -825,4 +1181,616 @@ mod tests {
                 .for_each(test_to_string::<N>);
         }
     }
+
+    mod eip1559 {
+        use super::*;
+
+        pub struct Eip1559TestCase {
+            pub nonce: &'static str,
+            pub max_priority_fee_per_gas: &'static str,
+            pub max_fee_per_gas: &'static str,
+            pub gas: &'static str,
+            pub to: &'static str,
+            pub value: &'static str,
+            pub data: &'static str,
+            pub private_key: &'static str,
+            pub raw_transaction: &'static str,
+            pub signed_transaction: &'static str,
+            pub signed_transaction_hash: &'static str,
+        }
+
+        fn parameters(transaction: &Eip1559TestCase) -> EthereumTransactionParameters {
+            EthereumTransactionParameters {
+                receiver: Some(EthereumAddress::from_str(transaction.to).unwrap()),
+                amount: EthereumAmount::from_wei(transaction.value).unwrap(),
+                gas: U256::from_dec_str(transaction.gas).unwrap(),
+                gas_price: EthereumAmount::from_wei("0").unwrap(),
+                max_fee_per_gas: Some(EthereumAmount::from_wei(transaction.max_fee_per_gas).unwrap()),
+                max_priority_fee_per_gas: Some(EthereumAmount::from_wei(transaction.max_priority_fee_per_gas).unwrap()),
+                access_list: None,
+                nonce: U256::from_dec_str(transaction.nonce).unwrap(),
+                data: transaction.data.as_bytes().to_vec(),
+                chain_id: None,
+            }
+        }
+
+        fn test_sign<N: EthereumNetwork>(transaction: &Eip1559TestCase) {
+            let private_key = EthereumPrivateKey::from_str(transaction.private_key).unwrap();
+            let parameters = parameters(transaction);
+
+            let raw_transaction = EthereumTransaction::<N>::new(&parameters).unwrap();
+            assert_eq!(transaction.raw_transaction, raw_transaction.to_string());
+
+            let signed_transaction = raw_transaction.sign(&private_key).unwrap();
+            assert_eq!(
+                private_key.to_address(&EthereumFormat::Standard).unwrap(),
+                signed_transaction.sender.clone().unwrap()
+            );
+            assert_eq!(transaction.signed_transaction, signed_transaction.to_string());
+            assert_eq!(
+                transaction.signed_transaction_hash,
+                signed_transaction.to_transaction_id().unwrap().to_string()
+            );
+        }
+
+        fn test_from_transaction_bytes<N: EthereumNetwork>(transaction: &Eip1559TestCase) {
+            let private_key = EthereumPrivateKey::from_str(transaction.private_key).unwrap();
+            let expected_sender = Some(private_key.to_address(&EthereumFormat::Standard).unwrap());
+
+            let expected_parameters = EthereumTransactionParameters {
+                chain_id: Some(u64::from(N::CHAIN_ID)),
+                ..parameters(transaction)
+            };
+
+            let raw_transaction = EthereumTransaction::<N>::from_str(&transaction.raw_transaction[2..]).unwrap();
+            assert_eq!(None, raw_transaction.sender);
+            assert_eq!(expected_parameters, raw_transaction.parameters);
+
+            let signed_transaction_bytes = hex::decode(&transaction.signed_transaction[2..]).unwrap();
+            let signed_transaction =
+                EthereumTransaction::<N>::from_transaction_bytes(&signed_transaction_bytes).unwrap();
+            assert_eq!(expected_sender, signed_transaction.sender);
+            assert_eq!(expected_parameters, signed_transaction.parameters);
+            assert_eq!(
+                signed_transaction_bytes,
+                signed_transaction.to_transaction_bytes().unwrap()
+            );
+            assert_eq!(raw_transaction.sign(&private_key).unwrap(), signed_transaction);
+        }
+
+        const MAINNET_TRANSACTIONS: [Eip1559TestCase; 2] = [
+            Eip1559TestCase {
+                nonce: "0",
+                max_priority_fee_per_gas: "1000000000",
+                max_fee_per_gas: "30000000000",
+                gas: "21000",
+                to: "0xB5D590A6aBf5E349C1b6C511Bc87CEAbFB3D7e65",
+                value: "1000000000000000000",
+                data: "",
+                private_key: "51ce358ffdcf208fadfb01a339f3ab715a89045a093777a44784d9e215277c1c",
+                raw_transaction: "0x02f00180843b9aca008506fc23ac0082520894b5d590a6abf5e349c1b6c511bc87ceabfb3d7e65880de0b6b3a764000080c0",
+                signed_transaction: "0x02f8730180843b9aca008506fc23ac0082520894b5d590a6abf5e349c1b6c511bc87ceabfb3d7e65880de0b6b3a764000080c001a0452e6ac1976443827a4d367a0dc3dc44b435c00fe4a1467a1d3dbaf7d165fe2ea04fa40d5ce44d59406876640116a2d64e8b7935d5236c761cddb20ae1f9daa438",
+                signed_transaction_hash: "0x4b15a06304342b95b0186a5327d21922483f059d2cbbdea0826de51f1c38f2c7"
+            },
+            Eip1559TestCase {
+                nonce: "12345",
+                max_priority_fee_per_gas: "2000000000",
+                max_fee_per_gas: "100000000000",
+                gas: "54000",
+                to: "0x52C3a8a79a521D10b25569847CB1a3FfB66550D6",
+                value: "1000000000000000000000",
+                data: "Send 1000 ETH",
+                private_key: "6cff516706e4eef887c3906f279efa86ac2eeb669b1a2a9f009e85c362fb640c",
+                raw_transaction: "0x02f84001823039847735940085174876e80082d2f09452c3a8a79a521d10b25569847cb1a3ffb66550d6893635c9adc5dea000008d53656e64203130303020455448c0",
+                signed_transaction: "0x02f88301823039847735940085174876e80082d2f09452c3a8a79a521d10b25569847cb1a3ffb66550d6893635c9adc5dea000008d53656e64203130303020455448c080a009368bce7505067835474c3fcf0453537051f783bd64740b6e32f9ddd9dbe101a050885ce602fa39b9862014be9ff434ffedf1b59c0a7d85b41f6649ad737d8973",
+                signed_transaction_hash: "0x713e4b0c95ed069e0adf0da35085317e464bfd562c9ebbe63f8b67db6e8815da"
+            },
+        ];
+
+        const GOERLI_TRANSACTIONS: [Eip1559TestCase; 1] = [
+            Eip1559TestCase {
+                nonce: "11",
+                max_priority_fee_per_gas: "1500000000",
+                max_fee_per_gas: "20000000000",
+                gas: "100000",
+                to: "0x52C3a8a79a521D10b25569847CB1a3FfB66550D6",
+                value: "5000000000000000000",
+                data: "Test Data",
+                private_key: "763459f13c14e02490e71590fe0ebb43cd8758c4adc9fb4bc084b0a798f557e7",
+                raw_transaction: "0x02f83a050b8459682f008504a817c800830186a09452c3a8a79a521d10b25569847cb1a3ffb66550d6884563918244f4000089546573742044617461c0",
+                signed_transaction: "0x02f87d050b8459682f008504a817c800830186a09452c3a8a79a521d10b25569847cb1a3ffb66550d6884563918244f4000089546573742044617461c001a0a11eb08d19e31f1e1034b055ef5fe7ca8ab137a115c240120fc2235c4bd320dea054cbac766051992bfe676bd679320e95919d5c7f3145b77113ac77f89baa3d20",
+                signed_transaction_hash: "0x39a47b869ea69f2bb8fb1a8222d3552535ec908b2c0ab79511aa5e7593b8a10a"
+            },
+        ];
+
+        #[test]
+        fn sign() {
+            MAINNET_TRANSACTIONS.iter().for_each(test_sign::<Mainnet>);
+            GOERLI_TRANSACTIONS.iter().for_each(test_sign::<Goerli>);
+        }
+
+        #[test]
+        fn from_transaction_bytes() {
+            MAINNET_TRANSACTIONS
+                .iter()
+                .for_each(test_from_transaction_bytes::<Mainnet>);
+            GOERLI_TRANSACTIONS
+                .iter()
+                .for_each(test_from_transaction_bytes::<Goerli>);
+        }
+
+        #[test]
+        fn invalid_transaction() {
+            let mut parameters = parameters(&MAINNET_TRANSACTIONS[0]);
+            parameters.max_priority_fee_per_gas = None;
+            assert!(EthereumTransaction::<Mainnet>::new(&parameters).is_err());
+
+            let mut parameters = self::parameters(&MAINNET_TRANSACTIONS[0]);
+            parameters.max_priority_fee_per_gas = Some(EthereumAmount::from_wei("30000000001").unwrap());
+            assert!(EthereumTransaction::<Mainnet>::new(&parameters).is_err());
+
+            let mut transaction = hex::decode(&MAINNET_TRANSACTIONS[0].raw_transaction[2..]).unwrap();
+            transaction[0] = 0x03;
+            match EthereumTransaction::<Mainnet>::from_transaction_bytes(&transaction) {
+                Err(TransactionError::UnsupportedTransactionType(0x03)) => (),
+                result => panic!("unexpected result {:?}", result),
+            }
+        }
+    }
+
+    mod eip2930 {
+        use super::*;
+
+        pub struct Eip2930TestCase {
+            pub nonce: &'static str,
+            pub gas_price: &'static str,
+            pub gas: &'static str,
+            pub to: &'static str,
+            pub value: &'static str,
+            pub data: &'static str,
+            pub access_list: &'static [(&'static str, &'static [&'static str])],
+            pub private_key: &'static str,
+            pub raw_transaction: &'static str,
+            pub signed_transaction: &'static str,
+            pub signed_transaction_hash: &'static str,
+        }
+
+        fn access_list(transaction: &Eip2930TestCase) -> Vec<EthereumAccessListItem> {
+            transaction
+                .access_list
+                .iter()
+                .map(|(address, storage_keys)| EthereumAccessListItem {
+                    address: EthereumAddress::from_str(address).unwrap(),
+                    storage_keys: storage_keys
+                        .iter()
+                        .map(|storage_key| {
+                            let mut key = [0u8; 32];
+                            key.copy_from_slice(&hex::decode(storage_key).unwrap());
+                            key
+                        })
+                        .collect(),
+                })
+                .collect()
+        }
+
+        fn parameters(transaction: &Eip2930TestCase) -> EthereumTransactionParameters {
+            EthereumTransactionParameters {
+                receiver: Some(EthereumAddress::from_str(transaction.to).unwrap()),
+                amount: EthereumAmount::from_wei(transaction.value).unwrap(),
+                gas: U256::from_dec_str(transaction.gas).unwrap(),
+                gas_price: EthereumAmount::from_wei(transaction.gas_price).unwrap(),
+                max_fee_per_gas: None,
+                max_priority_fee_per_gas: None,
+                access_list: Some(access_list(transaction)),
+                nonce: U256::from_dec_str(transaction.nonce).unwrap(),
+                data: transaction.data.as_bytes().to_vec(),
+                chain_id: None,
+            }
+        }
+
+        fn test_sign<N: EthereumNetwork>(transaction: &Eip2930TestCase) {
+            let private_key = EthereumPrivateKey::from_str(transaction.private_key).unwrap();
+            let parameters = parameters(transaction);
+
+            let raw_transaction = EthereumTransaction::<N>::new(&parameters).unwrap();
+            assert_eq!(transaction.raw_transaction, raw_transaction.to_string());
+
+            let signed_transaction = raw_transaction.sign(&private_key).unwrap();
+            assert_eq!(
+                private_key.to_address(&EthereumFormat::Standard).unwrap(),
+                signed_transaction.sender.clone().unwrap()
+            );
+            assert_eq!(transaction.signed_transaction, signed_transaction.to_string());
+            assert_eq!(
+                transaction.signed_transaction_hash,
+                signed_transaction.to_transaction_id().unwrap().to_string()
+            );
+        }
+
+        fn test_from_transaction_bytes<N: EthereumNetwork>(transaction: &Eip2930TestCase) {
+            let private_key = EthereumPrivateKey::from_str(transaction.private_key).unwrap();
+            let expected_sender = Some(private_key.to_address(&EthereumFormat::Standard).unwrap());
+
+            let expected_parameters = EthereumTransactionParameters {
+                chain_id: Some(u64::from(N::CHAIN_ID)),
+                ..parameters(transaction)
+            };
+
+            let raw_transaction = EthereumTransaction::<N>::from_str(&transaction.raw_transaction[2..]).unwrap();
+            assert_eq!(None, raw_transaction.sender);
+            assert_eq!(expected_parameters, raw_transaction.parameters);
+
+            let signed_transaction_bytes = hex::decode(&transaction.signed_transaction[2..]).unwrap();
+            let signed_transaction =
+                EthereumTransaction::<N>::from_transaction_bytes(&signed_transaction_bytes).unwrap();
+            assert_eq!(expected_sender, signed_transaction.sender);
+            assert_eq!(expected_parameters, signed_transaction.parameters);
+            assert_eq!(
+                signed_transaction_bytes,
+                signed_transaction.to_transaction_bytes().unwrap()
+            );
+            assert_eq!(raw_transaction.sign(&private_key).unwrap(), signed_transaction);
+        }
+
+        const ACCESS_LIST: &[(&str, &[&str])] = &[
+            (
+                "0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae",
+                &[
+                    "0000000000000000000000000000000000000000000000000000000000000003",
+                    "0000000000000000000000000000000000000000000000000000000000000007",
+                ],
+            ),
+            ("0xbb9bc244d798123fde783fcc1c72d3bb8c189413", &[]),
+        ];
+
+        const MAINNET_TRANSACTIONS: [Eip2930TestCase; 2] = [
+            Eip2930TestCase {
+                nonce: "0",
+                gas_price: "20000000000",
+                gas: "30000",
+                to: "0xB5D590A6aBf5E349C1b6C511Bc87CEAbFB3D7e65",
+                value: "1000000000000000000",
+                data: "",
+                access_list: ACCESS_LIST,
+                private_key: "51ce358ffdcf208fadfb01a339f3ab715a89045a093777a44784d9e215277c1c",
+                raw_transaction: "0x01f89e01808504a817c80082753094b5d590a6abf5e349c1b6c511bc87ceabfb3d7e65880de0b6b3a764000080f872f85994de0b295669a9fd93d5f28d9ec85e40f4cb697baef842a00000000000000000000000000000000000000000000000000000000000000003a00000000000000000000000000000000000000000000000000000000000000007d694bb9bc244d798123fde783fcc1c72d3bb8c189413c0",
+                signed_transaction: "0x01f8e101808504a817c80082753094b5d590a6abf5e349c1b6c511bc87ceabfb3d7e65880de0b6b3a764000080f872f85994de0b295669a9fd93d5f28d9ec85e40f4cb697baef842a00000000000000000000000000000000000000000000000000000000000000003a00000000000000000000000000000000000000000000000000000000000000007d694bb9bc244d798123fde783fcc1c72d3bb8c189413c001a02089ca2bd2097098a3e9adf6fb0dc3cdb21c83b7916a79633a34cf4fb6150dada010130aaac0230910cc52832232e6c428a911ef32d9e91215aaab85bb848b77a9",
+                signed_transaction_hash: "0xb5813d5ff7c50f14a067eff48f3db4c12f688acd5b5def2e3126b17d7453feef"
+            },
+            Eip2930TestCase {
+                nonce: "12345",
+                gas_price: "40000000000",
+                gas: "54000",
+                to: "0x52C3a8a79a521D10b25569847CB1a3FfB66550D6",
+                value: "1000000000000000000000",
+                data: "Send 1000 ETH",
+                access_list: &[],
+                private_key: "6cff516706e4eef887c3906f279efa86ac2eeb669b1a2a9f009e85c362fb640c",
+                raw_transaction: "0x01f83b018230398509502f900082d2f09452c3a8a79a521d10b25569847cb1a3ffb66550d6893635c9adc5dea000008d53656e64203130303020455448c0",
+                signed_transaction: "0x01f87e018230398509502f900082d2f09452c3a8a79a521d10b25569847cb1a3ffb66550d6893635c9adc5dea000008d53656e64203130303020455448c001a0907c6e97fec45fdae19eea1a5e23559085b99e3cf04dc4f70dbe0b6fd615fe79a04c9deb4f1f28152d9d9edf51037d9c525d316d2e4409a0024d077b0087dcfe0d",
+                signed_transaction_hash: "0x4ce2691ecd42dc1a5b1c6a907607a28c963b6fc7376c73d5cc1bc62abfa17675"
+            },
+        ];
+
+        const GOERLI_TRANSACTIONS: [Eip2930TestCase; 1] = [
+            Eip2930TestCase {
+                nonce: "11",
+                gas_price: "10000000000",
+                gas: "100000",
+                to: "0x52C3a8a79a521D10b25569847CB1a3FfB66550D6",
+                value: "5000000000000000000",
+                data: "Test Data",
+                access_list: &[(
+                    "0x52c3a8a79a521d10b25569847cb1a3ffb66550d6",
+                    &["290decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e563"],
+                )],
+                private_key: "763459f13c14e02490e71590fe0ebb43cd8758c4adc9fb4bc084b0a798f557e7",
+                raw_transaction: "0x01f86e050b8502540be400830186a09452c3a8a79a521d10b25569847cb1a3ffb66550d6884563918244f4000089546573742044617461f838f79452c3a8a79a521d10b25569847cb1a3ffb66550d6e1a0290decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e563",
+                signed_transaction: "0x01f8b1050b8502540be400830186a09452c3a8a79a521d10b25569847cb1a3ffb66550d6884563918244f4000089546573742044617461f838f79452c3a8a79a521d10b25569847cb1a3ffb66550d6e1a0290decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e56301a01e636fae2dfc7e22a577ae062f222a425c7de3a42127c38c97aa52b2557b3ce7a06b2524f64b1f52ff9225b64f71aad33f0d27cdba75d41804cae9bae6f8fd0cd9",
+                signed_transaction_hash: "0x0dab222f37a1928f327b145b9f32eb4bc93ece279d92b595e07bdbae65d46c93"
+            },
+        ];
+
+        #[test]
+        fn sign() {
+            MAINNET_TRANSACTIONS.iter().for_each(test_sign::<Mainnet>);
+            GOERLI_TRANSACTIONS.iter().for_each(test_sign::<Goerli>);
+        }
+
+        #[test]
+        fn from_transaction_bytes() {
+            MAINNET_TRANSACTIONS
+                .iter()
+                .for_each(test_from_transaction_bytes::<Mainnet>);
+            GOERLI_TRANSACTIONS
+                .iter()
+                .for_each(test_from_transaction_bytes::<Goerli>);
+        }
+
+        #[test]
+        fn eip1559_access_list() {
+            let private_key =
+                EthereumPrivateKey::from_str("51ce358ffdcf208fadfb01a339f3ab715a89045a093777a44784d9e215277c1c")
+                    .unwrap();
+            let mut parameters = parameters(&MAINNET_TRANSACTIONS[0]);
+            parameters.nonce = U256::from(7);
+            parameters.gas = U256::from(60000);
+            parameters.gas_price = EthereumAmount::from_wei("0").unwrap();
+            parameters.amount = EthereumAmount::from_wei("100000000000000000").unwrap();
+            parameters.max_fee_per_gas = Some(EthereumAmount::from_wei("30000000000").unwrap());
+            parameters.max_priority_fee_per_gas = Some(EthereumAmount::from_wei("1500000000").unwrap());
+            parameters.chain_id = Some(1);
+
+            let signed_transaction = EthereumTransaction::<Mainnet>::new(&parameters)
+                .unwrap()
+                .sign(&private_key)
+                .unwrap();
+            assert_eq!(
+                "0x02f8e601078459682f008506fc23ac0082ea6094b5d590a6abf5e349c1b6c511bc87ceabfb3d7e6588016345785d8a000080f872f85994de0b295669a9fd93d5f28d9ec85e40f4cb697baef842a00000000000000000000000000000000000000000000000000000000000000003a00000000000000000000000000000000000000000000000000000000000000007d694bb9bc244d798123fde783fcc1c72d3bb8c189413c080a0680ee98a2362d0feea1041da46ac60253a6359ea21b99b71bf76fa59034859f4a071fbc48811703e2e21941e9f3e8a7ef0661b5c36294d4ec4c6401512a5691be5",
+                signed_transaction.to_string()
+            );
+            assert_eq!(
+                "0x5f4c97ad4a31dd3998f9681d97b24420d3d4f9fc8cd367f82bf34b79e31cea54",
+                signed_transaction.to_transaction_id().unwrap().to_string()
+            );
+
+            let decoded_transaction =
+                EthereumTransaction::<Mainnet>::from_str(&signed_transaction.to_string()[2..]).unwrap();
+            assert_eq!(parameters, decoded_transaction.parameters);
+            assert_eq!(signed_transaction, decoded_transaction);
+        }
+
+        #[test]
+        fn invalid_access_list() {
+            // A storage key must be 32 bytes
+            let mut transaction = hex::decode(&GOERLI_TRANSACTIONS[0].raw_transaction[2..]).unwrap();
+            let length = transaction.len();
+            transaction[length - 33] = 0x9f;
+            assert!(EthereumTransaction::<Goerli>::from_transaction_bytes(&transaction).is_err());
+        }
+    }
+
+    mod chain_id {
+        use super::*;
+
+        struct ChainIdTestCase {
+            chain_id: u64,
+            parameters: fn() -> EthereumTransactionParameters,
+            private_key: &'static str,
+            raw_transaction: &'static str,
+            signed_transaction: &'static str,
+            signed_transaction_hash: &'static str,
+        }
+
+        const TRANSACTIONS: [ChainIdTestCase; 3] = [
+            ChainIdTestCase {
+                chain_id: 42161,
+                parameters: || EthereumTransactionParameters {
+                    receiver: Some(EthereumAddress::from_str("0xB5D590A6aBf5E349C1b6C511Bc87CEAbFB3D7e65").unwrap()),
+                    amount: EthereumAmount::from_wei("1000000000000000000").unwrap(),
+                    gas: U256::from(1000000),
+                    gas_price: EthereumAmount::from_wei("100000000").unwrap(),
+                    max_fee_per_gas: None,
+                    max_priority_fee_per_gas: None,
+                    access_list: None,
+                    nonce: U256::from(3),
+                    data: vec![],
+                    chain_id: Some(42161),
+                },
+                private_key: "51ce358ffdcf208fadfb01a339f3ab715a89045a093777a44784d9e215277c1c",
+                raw_transaction: "0xee038405f5e100830f424094b5d590a6abf5e349c1b6c511bc87ceabfb3d7e65880de0b6b3a76400008082a4b18080",
+                signed_transaction: "0xf86f038405f5e100830f424094b5d590a6abf5e349c1b6c511bc87ceabfb3d7e65880de0b6b3a76400008083014986a0e48195aeb8d2cd082092787fd803ba74943b47437912e64adf79d57481ed02d6a071775dcfe73b806fb7d3c290d18bb668ab9e6de04ed42138944acfc8a2c7d071",
+                signed_transaction_hash: "0xf614bce29cd2724133dc443a26d3bc152c39209ad39e7d2f4036bbe275da26b2",
+            },
+            ChainIdTestCase {
+                chain_id: 137,
+                parameters: || EthereumTransactionParameters {
+                    receiver: Some(EthereumAddress::from_str("0x52C3a8a79a521D10b25569847CB1a3FfB66550D6").unwrap()),
+                    amount: EthereumAmount::from_wei("1000000000000000000000").unwrap(),
+                    gas: U256::from(54000),
+                    gas_price: EthereumAmount::from_wei("30000000000").unwrap(),
+                    max_fee_per_gas: None,
+                    max_priority_fee_per_gas: None,
+                    access_list: None,
+                    nonce: U256::from(12345),
+                    data: "Send 1000 MATIC".as_bytes().to_vec(),
+                    chain_id: Some(137),
+                },
+                private_key: "6cff516706e4eef887c3906f279efa86ac2eeb669b1a2a9f009e85c362fb640c",
+                raw_transaction: "0xf83f8230398506fc23ac0082d2f09452c3a8a79a521d10b25569847cb1a3ffb66550d6893635c9adc5dea000008f53656e642031303030204d4154494381898080",
+                signed_transaction: "0xf8808230398506fc23ac0082d2f09452c3a8a79a521d10b25569847cb1a3ffb66550d6893635c9adc5dea000008f53656e642031303030204d41544943820135a0d2b7733cad36f4ffa38fe0190ed528a4df9a7ec91b794e0b0d9021c0a20acde5a04e4985a29424fbe9b9b09c5d728750ed9069b703ada08b6122db4cd14567674f",
+                signed_transaction_hash: "0x96e3bb0ec76e39390eb67329f11aa1cc69c30752efe69b084bab20484938080e",
+            },
+            ChainIdTestCase {
+                chain_id: 10,
+                parameters: || EthereumTransactionParameters {
+                    receiver: Some(EthereumAddress::from_str("0x52C3a8a79a521D10b25569847CB1a3FfB66550D6").unwrap()),
+                    amount: EthereumAmount::from_wei("5000000000000000000").unwrap(),
+                    gas: U256::from(100000),
+                    gas_price: EthereumAmount::from_wei("0").unwrap(),
+                    max_fee_per_gas: Some(EthereumAmount::from_wei("2000000000").unwrap()),
+                    max_priority_fee_per_gas: Some(EthereumAmount::from_wei("1000000").unwrap()),
+                    access_list: None,
+                    nonce: U256::from(11),
+                    data: "Test Data".as_bytes().to_vec(),
+                    chain_id: Some(10),
+                },
+                private_key: "763459f13c14e02490e71590fe0ebb43cd8758c4adc9fb4bc084b0a798f557e7",
+                raw_transaction: "0x02f8380a0b830f42408477359400830186a09452c3a8a79a521d10b25569847cb1a3ffb66550d6884563918244f4000089546573742044617461c0",
+                signed_transaction: "0x02f87b0a0b830f42408477359400830186a09452c3a8a79a521d10b25569847cb1a3ffb66550d6884563918244f4000089546573742044617461c080a07b4bd0338893d40d27d346c3b19f1a0a456541cde34a9fa2303d8e79d83d2171a0547a36ab4a778db445cc221afca8479f15fb1ac7226b12d23fed2a90713f643c",
+                signed_transaction_hash: "0xbf56b55883319443dcd63663cd285f010ce088da5b17532077958cb4b7b559da",
+            },
+        ];
+
+        #[test]
+        fn sign() {
+            for transaction in TRANSACTIONS.iter() {
+                let private_key = EthereumPrivateKey::from_str(transaction.private_key).unwrap();
+
+                // The chain id of the parameters overrides the chain id of the network
+                let raw_transaction = EthereumTransaction::<Mainnet>::new(&(transaction.parameters)()).unwrap();
+                assert_eq!(transaction.chain_id, raw_transaction.chain_id());
+                assert_eq!(
+                    Some(transaction.chain_id),
+                    raw_transaction.chain().map(|chain| chain.chain_id())
+                );
+                assert_eq!(transaction.raw_transaction, raw_transaction.to_string());
+
+                let signed_transaction = raw_transaction.sign(&private_key).unwrap();
+                assert_eq!(transaction.signed_transaction, signed_transaction.to_string());
+                assert_eq!(
+                    transaction.signed_transaction_hash,
+                    signed_transaction.to_transaction_id().unwrap().to_string()
+                );
+            }
+        }
+
+        #[test]
+        fn from_transaction_bytes() {
+            for transaction in TRANSACTIONS.iter() {
+                let private_key = EthereumPrivateKey::from_str(transaction.private_key).unwrap();
+                let expected_sender = Some(private_key.to_address(&EthereumFormat::Standard).unwrap());
+
+                let raw_transaction =
+                    EthereumTransaction::<Mainnet>::from_str(&transaction.raw_transaction[2..]).unwrap();
+                assert_eq!(transaction.chain_id, raw_transaction.chain_id());
+                assert_eq!((transaction.parameters)(), raw_transaction.parameters);
+
+                let signed_transaction =
+                    EthereumTransaction::<Mainnet>::from_str(&transaction.signed_transaction[2..]).unwrap();
+                assert_eq!(transaction.chain_id, signed_transaction.chain_id());
+                assert_eq!(expected_sender, signed_transaction.sender);
+                assert_eq!((transaction.parameters)(), signed_transaction.parameters);
+                assert_eq!(raw_transaction.sign(&private_key).unwrap(), signed_transaction);
+            }
+        }
+
+        #[test]
+        fn network_chain_id() {
+            let mut parameters = (TRANSACTIONS[0].parameters)();
+            parameters.chain_id = None;
+            assert_eq!(1, EthereumTransaction::<Mainnet>::new(&parameters).unwrap().chain_id());
+            assert_eq!(5, EthereumTransaction::<Goerli>::new(&parameters).unwrap().chain_id());
+
+            // A transaction signed without EIP-155 replay protection is invalid
+            let mut transaction = hex::decode(&TRANSACTIONS[0].signed_transaction[2..]).unwrap();
+            transaction.splice(43..47, vec![0x1b]);
+            transaction[1] = 0x6c;
+            match EthereumTransaction::<Mainnet>::from_transaction_bytes(&transaction) {
+                Err(TransactionError::InvalidChainId(27)) => (),
+                result => panic!("unexpected result {:?}", result),
+            }
+        }
+    }
+
+    mod deployment {
+        use super::*;
+
+        /// The init code of a contract whose runtime code returns 42
+        const INIT_CODE: &str = "600a600c600039600a6000f3602a60005260206000f3";
+
+        struct DeploymentTestCase {
+            parameters: fn() -> EthereumTransactionParameters,
+            private_key: &'static str,
+            contract_address: &'static str,
+            raw_transaction: &'static str,
+            signed_transaction: &'static str,
+            signed_transaction_hash: &'static str,
+        }
+
+        const TRANSACTIONS: [DeploymentTestCase; 2] = [
+            DeploymentTestCase {
+                parameters: || EthereumTransactionParameters {
+                    receiver: None,
+                    amount: EthereumAmount::from_wei("0").unwrap(),
+                    gas: U256::from(100000),
+                    gas_price: EthereumAmount::from_wei("20000000000").unwrap(),
+                    max_fee_per_gas: None,
+                    max_priority_fee_per_gas: None,
+                    access_list: None,
+                    nonce: U256::from(7),
+                    data: hex::decode(INIT_CODE).unwrap(),
+                    chain_id: Some(1),
+                },
+                private_key: "51ce358ffdcf208fadfb01a339f3ab715a89045a093777a44784d9e215277c1c",
+                contract_address: "0x2896c5491223d551bb854e384aae5953ff99560d",
+                raw_transaction: "0xe7078504a817c800830186a0808096600a600c600039600a6000f3602a60005260206000f3018080",
+                signed_transaction: "0xf867078504a817c800830186a0808096600a600c600039600a6000f3602a60005260206000f325a003a7b9d0c4607b98694eb6d46449eb5f52ff28d9e8da8dc66a3b0b024c368ce3a01ad1b037462568e33abea9654e3f8a95773a00e327b6392c54911ed032227f9d",
+                signed_transaction_hash: "0x7cc75cc632d38bb64d49c7790eff3266e16a71f40e4e4729ab23da133b6782f9",
+            },
+            DeploymentTestCase {
+                parameters: || EthereumTransactionParameters {
+                    receiver: None,
+                    amount: EthereumAmount::from_wei("10000000000000000").unwrap(),
+                    gas: U256::from(200000),
+                    gas_price: EthereumAmount::from_wei("0").unwrap(),
+                    max_fee_per_gas: Some(EthereumAmount::from_wei("30000000000").unwrap()),
+                    max_priority_fee_per_gas: Some(EthereumAmount::from_wei("1000000000").unwrap()),
+                    access_list: None,
+                    nonce: U256::from(42),
+                    data: hex::decode(INIT_CODE).unwrap(),
+                    chain_id: Some(11155111),
+                },
+                private_key: "763459f13c14e02490e71590fe0ebb43cd8758c4adc9fb4bc084b0a798f557e7",
+                contract_address: "0x8f131a2fd41a8e1177e00a69d7e4637146e15538",
+                raw_transaction: "0x02f583aa36a72a843b9aca008506fc23ac0083030d4080872386f26fc1000096600a600c600039600a6000f3602a60005260206000f3c0",
+                signed_transaction: "0x02f87883aa36a72a843b9aca008506fc23ac0083030d4080872386f26fc1000096600a600c600039600a6000f3602a60005260206000f3c001a0ba5c3ce1005e92ac5dfbe0e9758e092dc1c80f5616c177a8308816240315eae3a079ecb71971600006c6e7b3a5bec622017fc766b5b0882f84128ea976db51163c",
+                signed_transaction_hash: "0xcc0500b556c09988672a866554690bd7b614bfcdef73a12f831b0154028826b6",
+            },
+        ];
+
+        #[test]
+        fn sign() {
+            for transaction in TRANSACTIONS.iter() {
+                let private_key = EthereumPrivateKey::from_str(transaction.private_key).unwrap();
+
+                let raw_transaction = EthereumTransaction::<Mainnet>::new(&(transaction.parameters)()).unwrap();
+                assert_eq!(transaction.raw_transaction, raw_transaction.to_string());
+                assert_eq!(None, raw_transaction.contract_address().unwrap());
+
+                let signed_transaction = raw_transaction.sign(&private_key).unwrap();
+                assert_eq!(transaction.signed_transaction, signed_transaction.to_string());
+                assert_eq!(
+                    transaction.signed_transaction_hash,
+                    signed_transaction.to_transaction_id().unwrap().to_string()
+                );
+                assert_eq!(
+                    Some(EthereumAddress::from_str(transaction.contract_address).unwrap()),
+                    signed_transaction.contract_address().unwrap()
+                );
+            }
+        }
+
+        #[test]
+        fn from_transaction_bytes() {
+            for transaction in TRANSACTIONS.iter() {
+                let raw_transaction =
+                    EthereumTransaction::<Mainnet>::from_str(&transaction.raw_transaction[2..]).unwrap();
+                assert_eq!((transaction.parameters)(), raw_transaction.parameters);
+
+                let signed_transaction =
+                    EthereumTransaction::<Mainnet>::from_str(&transaction.signed_transaction[2..]).unwrap();
+                assert_eq!(None, signed_transaction.parameters.receiver);
+                assert_eq!((transaction.parameters)(), signed_transaction.parameters);
+                assert_eq!(
+                    Some(EthereumAddress::from_str(transaction.contract_address).unwrap()),
+                    signed_transaction.contract_address().unwrap()
+                );
+            }
+        }
+
+        #[test]
+        fn transfer_has_no_contract_address() {
+            let private_key = EthereumPrivateKey::from_str(TRANSACTIONS[0].private_key).unwrap();
+            let parameters = EthereumTransactionParameters {
+                receiver: Some(EthereumAddress::from_str("0xB5D590A6aBf5E349C1b6C511Bc87CEAbFB3D7e65").unwrap()),
+                ..(TRANSACTIONS[0].parameters)()
+            };
+            let signed_transaction = EthereumTransaction::<Mainnet>::new(&parameters)
+                .unwrap()
+                .sign(&private_key)
+                .unwrap();
+            assert_eq!(None, signed_transaction.contract_address().unwrap());
+        }
+    }
 }
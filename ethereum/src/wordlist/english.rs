@@ -36,6 +36,11 @@ mod tests {
         assert!(English::get_index(INVALID_WORD).is_err());
     }
 
+    #[test]
+    fn validate() {
+        assert!(English::validate().is_ok());
+    }
+
     #[test]
     fn get_all() {
         let list = English::get_all();
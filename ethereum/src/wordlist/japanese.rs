@@ -36,6 +36,11 @@ mod tests {
         assert!(Japanese::get_index(INVALID_WORD).is_err());
     }
 
+    #[test]
+    fn validate() {
+        assert!(Japanese::validate().is_ok());
+    }
+
     #[test]
     fn get_all() {
         let list = Japanese::get_all();
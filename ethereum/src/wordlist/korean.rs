@@ -36,6 +36,11 @@ mod tests {
         assert!(Korean::get_index(INVALID_WORD).is_err());
     }
 
+    #[test]
+    fn validate() {
+        assert!(Korean::validate().is_ok());
+    }
+
     #[test]
     fn get_all() {
         let list = Korean::get_all();
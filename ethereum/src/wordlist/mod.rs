@@ -6,6 +6,9 @@ pub use self::chinese_simplified::*;
 pub mod chinese_traditional;
 pub use self::chinese_traditional::*;
 
+pub mod czech;
+pub use self::czech::*;
+
 pub mod english;
 pub use self::english::*;
 
@@ -21,10 +24,14 @@ pub use self::japanese::*;
 pub mod korean;
 pub use self::korean::*;
 
+pub mod portuguese;
+pub use self::portuguese::*;
+
 pub mod spanish;
 pub use self::spanish::*;
 
 /// The interface for a Ethereum wordlist.
+/// Custom wordlists may implement it with a list of 2048 unique words, one per line.
 pub trait EthereumWordlist: Wordlist {
     /// The wordlist in original form.
     const WORDLIST: &'static str;
@@ -49,4 +56,103 @@ pub trait EthereumWordlist: Wordlist {
     fn get_all() -> Vec<&'static str> {
         Self::WORDLIST.lines().collect::<Vec<&str>>()
     }
+
+    /// Returns an error if the word list does not contain exactly 2048 unique words.
+    fn validate() -> Result<(), WordlistError> {
+        let mut wordlist = Self::get_all();
+        if wordlist.len() != 2048 {
+            return Err(WordlistError::InvalidLength(wordlist.len()));
+        }
+
+        wordlist.sort();
+        match wordlist.windows(2).find(|pair| pair[0] == pair[1]) {
+            Some(pair) => Err(WordlistError::DuplicateWord(pair[0].into())),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mnemonic::EthereumMnemonic;
+    use crate::network::Mainnet;
+    use wagyu_model::{bip39::ENGLISH, Mnemonic, MnemonicCount, MnemonicError};
+
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    struct Custom;
+
+    impl Wordlist for Custom {}
+
+    impl EthereumWordlist for Custom {
+        const WORDLIST: &'static str = ENGLISH;
+
+        fn get_all() -> Vec<&'static str> {
+            let mut wordlist = Self::WORDLIST.lines().collect::<Vec<&str>>();
+            wordlist.reverse();
+            wordlist
+        }
+    }
+
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    struct Short;
+
+    impl Wordlist for Short {}
+
+    impl EthereumWordlist for Short {
+        const WORDLIST: &'static str = "abandon\nability\nable";
+    }
+
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    struct Duplicate;
+
+    impl Wordlist for Duplicate {}
+
+    impl EthereumWordlist for Duplicate {
+        const WORDLIST: &'static str = ENGLISH;
+
+        fn get_all() -> Vec<&'static str> {
+            let mut wordlist = Self::WORDLIST.lines().collect::<Vec<&str>>();
+            wordlist[1] = wordlist[0];
+            wordlist
+        }
+    }
+
+    #[test]
+    fn custom_wordlist() {
+        assert!(Custom::validate().is_ok());
+        assert_eq!("zoo", Custom::get(0).unwrap());
+        assert_eq!(2047, Custom::get_index("abandon").unwrap());
+
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        let mnemonic = EthereumMnemonic::<Mainnet, Custom>::new_with_count(rng, 24).unwrap();
+        let phrase = mnemonic.to_phrase().unwrap();
+        assert_eq!(
+            mnemonic,
+            EthereumMnemonic::<Mainnet, Custom>::from_phrase(&phrase).unwrap()
+        );
+        assert!(EthereumMnemonic::<Mainnet, English>::from_phrase(&phrase).is_err());
+    }
+
+    #[test]
+    fn invalid_wordlist() {
+        match Short::validate() {
+            Err(WordlistError::InvalidLength(3)) => (),
+            other => panic!("expected an invalid length, found {:?}", other),
+        }
+        match Duplicate::validate() {
+            Err(WordlistError::DuplicateWord(word)) => assert_eq!("abandon", word),
+            other => panic!("expected a duplicate word, found {:?}", other),
+        }
+
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        let mnemonic = EthereumMnemonic::<Mainnet, Short>::new_with_count(rng, 12).unwrap();
+        match mnemonic.to_phrase() {
+            Err(MnemonicError::WordlistError(WordlistError::InvalidLength(3))) => (),
+            other => panic!("expected an invalid wordlist length, found {:?}", other),
+        }
+    }
 }
@@ -1,3 +1,4 @@
+use crate::error::CrateError;
 use crate::format::Format;
 use crate::private_key::{PrivateKey, PrivateKeyError};
 use crate::public_key::{PublicKey, PublicKeyError};
@@ -26,8 +27,8 @@ pub trait Address:
 
 #[derive(Debug, Fail)]
 pub enum AddressError {
-    #[fail(display = "{}: {}", _0, _1)]
-    Crate(&'static str, String),
+    #[fail(display = "{}", _0)]
+    Crate(#[cause] CrateError),
 
     #[fail(display = "invalid format conversion from {:?} to {:?}", _0, _1)]
     IncompatibleFormats(String, String),
@@ -60,21 +61,21 @@ pub enum AddressError {
     MissingPublicKey,
 
     #[fail(display = "{}", _0)]
-    PrivateKeyError(PrivateKeyError),
+    PrivateKeyError(#[cause] PrivateKeyError),
 
     #[fail(display = "{}", _0)]
-    PublicKeyError(PublicKeyError),
+    PublicKeyError(#[cause] PublicKeyError),
 }
 
 impl From<crate::no_std::io::Error> for AddressError {
     fn from(error: crate::no_std::io::Error) -> Self {
-        AddressError::Crate("crate::no_std::io", format!("{:?}", error))
+        AddressError::Crate(CrateError::from(error))
     }
 }
 
 impl From<crate::no_std::FromUtf8Error> for AddressError {
     fn from(error: crate::no_std::FromUtf8Error) -> Self {
-        AddressError::Crate("crate::no_std", format!("{:?}", error))
+        AddressError::Crate(CrateError::from(error))
     }
 }
 
@@ -96,38 +97,41 @@ impl From<PublicKeyError> for AddressError {
     }
 }
 
+#[cfg(feature = "std")]
 impl From<base58::FromBase58Error> for AddressError {
     fn from(error: base58::FromBase58Error) -> Self {
-        AddressError::Crate("base58", format!("{:?}", error))
+        AddressError::Crate(CrateError::from(error))
     }
 }
 
+#[cfg(feature = "std")]
 impl From<base58_monero::base58::Error> for AddressError {
     fn from(error: base58_monero::base58::Error) -> Self {
-        AddressError::Crate("base58_monero", format!("{:?}", error))
+        AddressError::Crate(CrateError::from(error))
     }
 }
 
+#[cfg(feature = "std")]
 impl From<bech32::Error> for AddressError {
     fn from(error: bech32::Error) -> Self {
-        AddressError::Crate("bech32", format!("{:?}", error))
+        AddressError::Crate(CrateError::from(error))
     }
 }
 
 impl From<core::str::Utf8Error> for AddressError {
     fn from(error: core::str::Utf8Error) -> Self {
-        AddressError::Crate("core::str", format!("{:?}", error))
+        AddressError::Crate(CrateError::from(error))
     }
 }
 
 impl From<hex::FromHexError> for AddressError {
     fn from(error: hex::FromHexError) -> Self {
-        AddressError::Crate("hex", format!("{:?}", error))
+        AddressError::Crate(CrateError::from(error))
     }
 }
 
 impl From<rand_core::Error> for AddressError {
     fn from(error: rand_core::Error) -> Self {
-        AddressError::Crate("rand", format!("{:?}", error))
+        AddressError::Crate(CrateError::from(error))
     }
 }
@@ -1,3 +1,5 @@
+use crate::error::CrateError;
+
 use crate::no_std::*;
 use core::{
     fmt::{Debug, Display},
@@ -12,8 +14,8 @@ pub enum AmountError {
     #[fail(display = "the amount: {} exceeds the supply bounds of {}", _0, _1)]
     AmountOutOfBounds(String, String),
 
-    #[fail(display = "{}: {}", _0, _1)]
-    Crate(&'static str, String),
+    #[fail(display = "{}", _0)]
+    Crate(#[cause] CrateError),
 
     #[fail(display = "invalid amount: {}", _0)]
     InvalidAmount(String),
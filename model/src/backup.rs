@@ -0,0 +1,451 @@
+use crate::error::CrateError;
+use crate::no_std::*;
+use crate::secret::{ExposeSecret, Redacted};
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, Payload},
+    XChaCha20Poly1305, XNonce,
+};
+use core::{fmt, str::FromStr};
+use rand::Rng;
+use zeroize::Zeroize;
+
+/// The magic bytes at the start of a backup
+const MAGIC: [u8; 4] = *b"WGYB";
+/// The current version of the backup format
+pub const BACKUP_VERSION: u8 = 1;
+/// The length of the Argon2id salt in bytes
+const SALT_LENGTH: usize = 16;
+/// The length of the XChaCha20-Poly1305 nonce in bytes
+const NONCE_LENGTH: usize = 24;
+/// The length of the XChaCha20-Poly1305 authentication tag in bytes
+const TAG_LENGTH: usize = 16;
+/// The length of the header, consisting of the magic bytes, version, kind, parameters, salt, and nonce
+const HEADER_LENGTH: usize = MAGIC.len() + 1 + 1 + 12 + SALT_LENGTH + NONCE_LENGTH;
+/// The maximum Argon2id memory size in kibibytes (1 GiB)
+const MAX_MEMORY_COST: u32 = 1024 * 1024;
+/// The maximum number of Argon2id iterations
+const MAX_TIME_COST: u32 = 16;
+/// The maximum degree of Argon2id parallelism
+const MAX_PARALLELISM: u32 = 16;
+
+/// Represents the Argon2id parameters of the key derived from a backup passphrase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BackupParameters {
+    /// The memory size in kibibytes
+    pub memory_cost: u32,
+    /// The number of iterations
+    pub time_cost: u32,
+    /// The degree of parallelism
+    pub parallelism: u32,
+}
+
+impl Default for BackupParameters {
+    /// Returns the parameters recommended by RFC 9106 for memory-constrained environments.
+    fn default() -> Self {
+        Self {
+            memory_cost: 64 * 1024,
+            time_cost: 3,
+            parallelism: 4,
+        }
+    }
+}
+
+impl BackupParameters {
+    /// Returns an error if any parameter exceeds its maximum, so that an untrusted
+    /// backup cannot force an unbounded key derivation.
+    fn check(&self) -> Result<(), BackupError> {
+        if self.memory_cost > MAX_MEMORY_COST {
+            return Err(BackupError::InvalidParameters(format!(
+                "memory cost {} exceeds {}",
+                self.memory_cost, MAX_MEMORY_COST
+            )));
+        }
+        if self.time_cost > MAX_TIME_COST {
+            return Err(BackupError::InvalidParameters(format!(
+                "time cost {} exceeds {}",
+                self.time_cost, MAX_TIME_COST
+            )));
+        }
+        if self.parallelism > MAX_PARALLELISM {
+            return Err(BackupError::InvalidParameters(format!(
+                "parallelism {} exceeds {}",
+                self.parallelism, MAX_PARALLELISM
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Represents the secret contents of a backup.
+#[derive(Clone, PartialEq, Eq)]
+pub enum BackupContents {
+    /// A mnemonic phrase
+    Mnemonic(String),
+    /// A raw seed
+    Seed(Vec<u8>),
+}
+
+impl BackupContents {
+    /// Returns the kind byte identifying the contents in the backup header.
+    fn kind(&self) -> u8 {
+        match self {
+            BackupContents::Mnemonic(_) => 0,
+            BackupContents::Seed(_) => 1,
+        }
+    }
+
+    /// Returns the bytes of the contents to encrypt.
+    fn as_bytes(&self) -> &[u8] {
+        match self {
+            BackupContents::Mnemonic(mnemonic) => mnemonic.as_bytes(),
+            BackupContents::Seed(seed) => seed,
+        }
+    }
+
+    /// Returns the contents of the given kind from the decrypted bytes.
+    fn from_bytes(kind: u8, bytes: Vec<u8>) -> Result<Self, BackupError> {
+        match kind {
+            0 => match String::from_utf8(bytes) {
+                Ok(mnemonic) => Ok(BackupContents::Mnemonic(mnemonic)),
+                Err(error) => {
+                    error.into_bytes().zeroize();
+                    Err(BackupError::InvalidContents)
+                }
+            },
+            1 => Ok(BackupContents::Seed(bytes)),
+            _ => Err(BackupError::InvalidKind(kind)),
+        }
+    }
+}
+
+impl fmt::Debug for BackupContents {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BackupContents::Mnemonic(_) => f.debug_tuple("Mnemonic").field(&Redacted).finish(),
+            BackupContents::Seed(_) => f.debug_tuple("Seed").field(&Redacted).finish(),
+        }
+    }
+}
+
+impl ExposeSecret for BackupContents {
+    fn fmt_secret(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BackupContents::Mnemonic(mnemonic) => f.debug_tuple("Mnemonic").field(mnemonic).finish(),
+            BackupContents::Seed(seed) => f.debug_tuple("Seed").field(seed).finish(),
+        }
+    }
+}
+
+impl Zeroize for BackupContents {
+    fn zeroize(&mut self) {
+        match self {
+            BackupContents::Mnemonic(mnemonic) => mnemonic.zeroize(),
+            BackupContents::Seed(seed) => seed.zeroize(),
+        }
+    }
+}
+
+impl Drop for BackupContents {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+/// Represents an encrypted backup of a mnemonic or seed.
+///
+/// The contents are encrypted with XChaCha20-Poly1305 under a key derived from a passphrase with Argon2id.
+/// The header, consisting of the magic bytes, version, kind, parameters, salt, and nonce,
+/// is authenticated as associated data, so tampering with any byte fails decryption.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Backup {
+    /// The version of the backup format
+    pub version: u8,
+    /// The kind of the contents
+    kind: u8,
+    /// The Argon2id parameters of the key
+    pub parameters: BackupParameters,
+    /// The Argon2id salt of the key
+    salt: [u8; SALT_LENGTH],
+    /// The XChaCha20-Poly1305 nonce
+    nonce: [u8; NONCE_LENGTH],
+    /// The encrypted contents, followed by the authentication tag
+    ciphertext: Vec<u8>,
+}
+
+impl Backup {
+    /// Returns the backup of the given contents, encrypted with the given passphrase.
+    pub fn encrypt<R: Rng>(
+        rng: &mut R,
+        contents: &BackupContents,
+        passphrase: &str,
+        parameters: BackupParameters,
+    ) -> Result<Self, BackupError> {
+        parameters.check()?;
+        let mut backup = Self {
+            version: BACKUP_VERSION,
+            kind: contents.kind(),
+            parameters,
+            salt: rng.gen(),
+            nonce: rng.gen(),
+            ciphertext: vec![],
+        };
+
+        let cipher = backup.cipher(passphrase)?;
+        let payload = Payload {
+            msg: contents.as_bytes(),
+            aad: &backup.header(),
+        };
+        backup.ciphertext = cipher
+            .encrypt(XNonce::from_slice(&backup.nonce), payload)
+            .map_err(|_| BackupError::Crate(CrateError::Other("chacha20poly1305", "encryption failed".into())))?;
+        Ok(backup)
+    }
+
+    /// Returns the contents of the backup, decrypted with the given passphrase.
+    pub fn decrypt(&self, passphrase: &str) -> Result<BackupContents, BackupError> {
+        let cipher = self.cipher(passphrase)?;
+        let payload = Payload {
+            msg: &self.ciphertext,
+            aad: &self.header(),
+        };
+        let plaintext = cipher
+            .decrypt(XNonce::from_slice(&self.nonce), payload)
+            .map_err(|_| BackupError::InvalidPassphrase)?;
+        BackupContents::from_bytes(self.kind, plaintext)
+    }
+
+    /// Returns the serialized backup.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.header();
+        bytes.extend_from_slice(&self.ciphertext);
+        bytes
+    }
+
+    /// Returns the backup of the given serialized backup.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BackupError> {
+        if bytes.len() < HEADER_LENGTH + TAG_LENGTH {
+            return Err(BackupError::InvalidLength(bytes.len()));
+        }
+        if bytes[..4] != MAGIC {
+            return Err(BackupError::InvalidMagic);
+        }
+        if bytes[4] != BACKUP_VERSION {
+            return Err(BackupError::UnsupportedVersion(bytes[4]));
+        }
+
+        let read_u32 = |offset: usize| {
+            let mut word = [0u8; 4];
+            word.copy_from_slice(&bytes[offset..offset + 4]);
+            u32::from_be_bytes(word)
+        };
+        let mut salt = [0u8; SALT_LENGTH];
+        salt.copy_from_slice(&bytes[18..18 + SALT_LENGTH]);
+        let mut nonce = [0u8; NONCE_LENGTH];
+        nonce.copy_from_slice(&bytes[18 + SALT_LENGTH..HEADER_LENGTH]);
+        let parameters = BackupParameters {
+            memory_cost: read_u32(6),
+            time_cost: read_u32(10),
+            parallelism: read_u32(14),
+        };
+        parameters.check()?;
+
+        Ok(Self {
+            version: bytes[4],
+            kind: bytes[5],
+            parameters,
+            salt,
+            nonce,
+            ciphertext: bytes[HEADER_LENGTH..].to_vec(),
+        })
+    }
+
+    /// Returns the header of the backup, authenticated as associated data.
+    fn header(&self) -> Vec<u8> {
+        let mut header = Vec::with_capacity(HEADER_LENGTH);
+        header.extend_from_slice(&MAGIC);
+        header.push(self.version);
+        header.push(self.kind);
+        header.extend_from_slice(&self.parameters.memory_cost.to_be_bytes());
+        header.extend_from_slice(&self.parameters.time_cost.to_be_bytes());
+        header.extend_from_slice(&self.parameters.parallelism.to_be_bytes());
+        header.extend_from_slice(&self.salt);
+        header.extend_from_slice(&self.nonce);
+        header
+    }
+
+    /// Returns the cipher keyed with the Argon2id hash of the given passphrase.
+    fn cipher(&self, passphrase: &str) -> Result<XChaCha20Poly1305, BackupError> {
+        let parameters = &self.parameters;
+        let params = Params::new(
+            parameters.memory_cost,
+            parameters.time_cost,
+            parameters.parallelism,
+            Some(32),
+        )
+        .map_err(|error| BackupError::InvalidParameters(error.to_string()))?;
+
+        let mut key = [0u8; 32];
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(passphrase.as_bytes(), &self.salt, &mut key)
+            .map_err(|error| BackupError::Crate(CrateError::Other("argon2", error.to_string())))?;
+        let cipher = XChaCha20Poly1305::new(&key.into());
+        key.zeroize();
+        Ok(cipher)
+    }
+}
+
+impl FromStr for Backup {
+    type Err = BackupError;
+
+    /// Returns the backup of the given hexadecimal string.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_bytes(&hex::decode(s.trim())?)
+    }
+}
+
+impl fmt::Display for Backup {
+    /// Writes the backup as a hexadecimal string.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", hex::encode(self.to_bytes()))
+    }
+}
+
+#[derive(Debug, Fail)]
+pub enum BackupError {
+    #[fail(display = "{}", _0)]
+    Crate(#[cause] CrateError),
+
+    #[fail(display = "invalid backup contents")]
+    InvalidContents,
+
+    #[fail(display = "invalid backup kind: {}", _0)]
+    InvalidKind(u8),
+
+    #[fail(display = "invalid backup length: {}", _0)]
+    InvalidLength(usize),
+
+    #[fail(display = "invalid backup magic bytes")]
+    InvalidMagic,
+
+    #[fail(display = "invalid backup parameters: {}", _0)]
+    InvalidParameters(String),
+
+    #[fail(display = "invalid backup passphrase or corrupted backup")]
+    InvalidPassphrase,
+
+    #[fail(display = "unsupported backup version: {}", _0)]
+    UnsupportedVersion(u8),
+}
+
+impl From<CrateError> for BackupError {
+    fn from(error: CrateError) -> Self {
+        BackupError::Crate(error)
+    }
+}
+
+impl From<hex::FromHexError> for BackupError {
+    fn from(error: hex::FromHexError) -> Self {
+        BackupError::Crate(CrateError::from(error))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    const PARAMETERS: BackupParameters = BackupParameters {
+        memory_cost: 8,
+        time_cost: 1,
+        parallelism: 1,
+    };
+    const MNEMONIC: &str =
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    fn encrypt(contents: &BackupContents) -> Backup {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        Backup::encrypt(rng, contents, "passphrase", PARAMETERS).unwrap()
+    }
+
+    #[test]
+    fn decrypt() {
+        let contents = BackupContents::Mnemonic(MNEMONIC.into());
+        let backup = encrypt(&contents);
+        assert_eq!(contents, backup.decrypt("passphrase").unwrap());
+
+        let contents = BackupContents::Seed(vec![7u8; 64]);
+        let backup = encrypt(&contents);
+        assert_eq!(contents, backup.decrypt("passphrase").unwrap());
+    }
+
+    #[test]
+    fn to_string() {
+        let backup = encrypt(&BackupContents::Mnemonic(MNEMONIC.into()));
+        let string = backup.to_string();
+        assert!(string.starts_with("5747594201000000000800000001000000"));
+        assert_eq!(2 * (HEADER_LENGTH + MNEMONIC.len() + TAG_LENGTH), string.len());
+        assert_eq!(backup, Backup::from_str(&string).unwrap());
+    }
+
+    #[test]
+    fn invalid() {
+        let backup = encrypt(&BackupContents::Mnemonic(MNEMONIC.into()));
+        assert!(matches!(backup.decrypt("wrong"), Err(BackupError::InvalidPassphrase)));
+
+        let bytes = backup.to_bytes();
+        (0..bytes.len()).step_by(7).for_each(|index| {
+            let mut bytes = bytes.clone();
+            bytes[index] ^= 1;
+            assert!(Backup::from_bytes(&bytes)
+                .and_then(|backup| backup.decrypt("passphrase"))
+                .is_err());
+        });
+
+        assert!(matches!(
+            Backup::from_bytes(&bytes[..HEADER_LENGTH]),
+            Err(BackupError::InvalidLength(_))
+        ));
+        let mut version = bytes.clone();
+        version[4] = 2;
+        assert!(matches!(
+            Backup::from_bytes(&version),
+            Err(BackupError::UnsupportedVersion(2))
+        ));
+    }
+
+    #[test]
+    fn oversized_parameters() {
+        let bytes = encrypt(&BackupContents::Mnemonic(MNEMONIC.into())).to_bytes();
+        [6, 10, 14].iter().for_each(|offset| {
+            let mut bytes = bytes.clone();
+            bytes[*offset..*offset + 4].copy_from_slice(&u32::MAX.to_be_bytes());
+            assert!(matches!(
+                Backup::from_str(&hex::encode(bytes)).and_then(|backup| backup.decrypt("passphrase")),
+                Err(BackupError::InvalidParameters(_))
+            ));
+        });
+
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        let parameters = BackupParameters {
+            memory_cost: MAX_MEMORY_COST + 1,
+            ..PARAMETERS
+        };
+        assert!(matches!(
+            Backup::encrypt(rng, &BackupContents::Seed(vec![7u8; 64]), "passphrase", parameters),
+            Err(BackupError::InvalidParameters(_))
+        ));
+    }
+
+    #[test]
+    fn debug() {
+        let contents = BackupContents::Mnemonic(MNEMONIC.into());
+        assert_eq!("Mnemonic([REDACTED])", format!("{:?}", contents));
+        assert_eq!(
+            format!("Mnemonic({:?})", MNEMONIC),
+            format!("{:?}", contents.expose_secret())
+        );
+    }
+}
@@ -0,0 +1,182 @@
+use crate::derivation_path::{ChildIndex, DerivationPathError};
+
+use crate::no_std::*;
+use core::{fmt, str::FromStr};
+
+/// Represents a registered SLIP-44 coin type, the second level of a BIP44-style derivation path.
+/// https://github.com/satoshilabs/slips/blob/master/slip-0044.md
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CoinType {
+    /// Bitcoin - 0'
+    Bitcoin,
+    /// Testnet (all coins) - 1'
+    Testnet,
+    /// Litecoin - 2'
+    Litecoin,
+    /// Dogecoin - 3'
+    Dogecoin,
+    /// Dash - 5'
+    Dash,
+    /// Ethereum - 60'
+    Ethereum,
+    /// Ethereum Classic - 61'
+    EthereumClassic,
+    /// Monero - 128'
+    Monero,
+    /// Zcash - 133'
+    Zcash,
+    /// Bitcoin Cash - 145'
+    BitcoinCash,
+}
+
+impl CoinType {
+    /// The registered coin types, in ascending order of their SLIP-44 index.
+    pub const ALL: [CoinType; 10] = [
+        CoinType::Bitcoin,
+        CoinType::Testnet,
+        CoinType::Litecoin,
+        CoinType::Dogecoin,
+        CoinType::Dash,
+        CoinType::Ethereum,
+        CoinType::EthereumClassic,
+        CoinType::Monero,
+        CoinType::Zcash,
+        CoinType::BitcoinCash,
+    ];
+
+    /// Returns the SLIP-44 index of the coin type.
+    pub fn index(&self) -> u32 {
+        match self {
+            CoinType::Bitcoin => 0,
+            CoinType::Testnet => 1,
+            CoinType::Litecoin => 2,
+            CoinType::Dogecoin => 3,
+            CoinType::Dash => 5,
+            CoinType::Ethereum => 60,
+            CoinType::EthereumClassic => 61,
+            CoinType::Monero => 128,
+            CoinType::Zcash => 133,
+            CoinType::BitcoinCash => 145,
+        }
+    }
+
+    /// Returns the ticker symbol of the coin type.
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            CoinType::Bitcoin => "BTC",
+            CoinType::Testnet => "TEST",
+            CoinType::Litecoin => "LTC",
+            CoinType::Dogecoin => "DOGE",
+            CoinType::Dash => "DASH",
+            CoinType::Ethereum => "ETH",
+            CoinType::EthereumClassic => "ETC",
+            CoinType::Monero => "XMR",
+            CoinType::Zcash => "ZEC",
+            CoinType::BitcoinCash => "BCH",
+        }
+    }
+
+    /// Returns the name of the coin type.
+    pub fn name(&self) -> &'static str {
+        match self {
+            CoinType::Bitcoin => "bitcoin",
+            CoinType::Testnet => "testnet",
+            CoinType::Litecoin => "litecoin",
+            CoinType::Dogecoin => "dogecoin",
+            CoinType::Dash => "dash",
+            CoinType::Ethereum => "ethereum",
+            CoinType::EthereumClassic => "ethereum classic",
+            CoinType::Monero => "monero",
+            CoinType::Zcash => "zcash",
+            CoinType::BitcoinCash => "bitcoin cash",
+        }
+    }
+
+    /// Returns the coin type of the given SLIP-44 index.
+    pub fn from_index(index: u32) -> Result<Self, DerivationPathError> {
+        CoinType::ALL
+            .iter()
+            .find(|coin_type| coin_type.index() == index)
+            .copied()
+            .ok_or(DerivationPathError::UnknownCoinType(index))
+    }
+
+    /// Returns the hardened child index of the coin type, as it appears in a derivation path.
+    pub fn to_child_index(&self) -> ChildIndex {
+        ChildIndex::Hardened(self.index())
+    }
+
+    /// Returns the coin type of the given child index, which must be hardened.
+    pub fn from_child_index(index: ChildIndex) -> Result<Self, DerivationPathError> {
+        match index {
+            ChildIndex::Hardened(index) => Self::from_index(index),
+            ChildIndex::Normal(_) => Err(DerivationPathError::ExpectedHardenedPath),
+        }
+    }
+}
+
+impl FromStr for CoinType {
+    type Err = DerivationPathError;
+
+    /// Returns the coin type of the given ticker symbol or name, ignoring case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        CoinType::ALL
+            .iter()
+            .find(|coin_type| coin_type.symbol().eq_ignore_ascii_case(s) || coin_type.name().eq_ignore_ascii_case(s))
+            .copied()
+            .ok_or_else(|| DerivationPathError::InvalidCoinType(s.to_string()))
+    }
+}
+
+impl fmt::Display for CoinType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn index() {
+        CoinType::ALL.iter().for_each(|coin_type| {
+            assert_eq!(*coin_type, CoinType::from_index(coin_type.index()).unwrap());
+            assert_eq!(
+                *coin_type,
+                CoinType::from_child_index(coin_type.to_child_index()).unwrap()
+            );
+        });
+        assert_eq!(CoinType::Ethereum, CoinType::from_index(60).unwrap());
+        assert_eq!(ChildIndex::Hardened(133), CoinType::Zcash.to_child_index());
+    }
+
+    #[test]
+    fn from_str() {
+        CoinType::ALL.iter().for_each(|coin_type| {
+            assert_eq!(*coin_type, CoinType::from_str(coin_type.symbol()).unwrap());
+            assert_eq!(*coin_type, CoinType::from_str(coin_type.name()).unwrap());
+        });
+        assert_eq!(CoinType::Bitcoin, CoinType::from_str("btc").unwrap());
+        assert_eq!(CoinType::Monero, CoinType::from_str("Monero").unwrap());
+    }
+
+    #[test]
+    fn to_string() {
+        assert_eq!("bitcoin", CoinType::Bitcoin.to_string());
+        assert_eq!("ethereum classic", CoinType::EthereumClassic.to_string());
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(Err(DerivationPathError::UnknownCoinType(4)), CoinType::from_index(4));
+        assert_eq!(
+            Err(DerivationPathError::ExpectedHardenedPath),
+            CoinType::from_child_index(ChildIndex::Normal(0))
+        );
+        assert_eq!(
+            Err(DerivationPathError::InvalidCoinType("btcc".into())),
+            CoinType::from_str("btcc")
+        );
+    }
+}
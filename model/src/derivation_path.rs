@@ -1,17 +1,197 @@
+use crate::coin_type::CoinType;
 use crate::no_std::*;
 use core::{
     fmt,
     fmt::{Debug, Display},
+    marker::PhantomData,
     str::FromStr,
 };
 
+/// The maximum depth of a BIP32 derivation path.
+pub const MAX_DEPTH: usize = 255;
+
+/// The purpose levels of derivation path schemes with a SLIP-44 coin type as their second level:
+/// ZIP32, BIP44, BIP49, BIP84, and BIP86.
+const COIN_TYPE_PURPOSES: [ChildIndex; 5] = [
+    ChildIndex::Hardened(32),
+    ChildIndex::Hardened(44),
+    ChildIndex::Hardened(49),
+    ChildIndex::Hardened(84),
+    ChildIndex::Hardened(86),
+];
+
 /// The interface for a generic derivation path.
-pub trait DerivationPath: Clone + Debug + Display + FromStr + Send + Sync + 'static + Eq + Sized {
+pub trait DerivationPath: Clone + Debug + Display + FromStr + Send + Sync + 'static + Eq + PartialOrd + Sized {
     /// Returns a child index vector given the derivation path.
     fn to_vec(&self) -> Result<Vec<ChildIndex>, DerivationPathError>;
 
     /// Returns a derivation path given the child index vector.
     fn from_vec(path: &Vec<ChildIndex>) -> Result<Self, DerivationPathError>;
+
+    /// Returns a derivation path given its string representation, e.g. `m/44'/0'/0'`.
+    ///
+    /// Unlike `from_str`, components with leading zeros (`m/007`) or signs (`m/+7`) are rejected,
+    /// and errors report the offending component with its position, where `m` is at position 0.
+    fn from_str_strict(path: &str) -> Result<Self, DerivationPathError> {
+        let mut parts = path.split('/');
+        if parts.next() != Some("m") {
+            return Err(DerivationPathError::InvalidDerivationPath(path.to_string()));
+        }
+        Self::from_vec(&parse_strict(parts)?)
+    }
+
+    /// Returns the depth of the derivation path, where the master path `m` has depth 0.
+    fn depth(&self) -> Result<u8, DerivationPathError> {
+        let path = self.to_vec()?;
+        match path.len() <= MAX_DEPTH {
+            true => Ok(path.len() as u8),
+            false => Err(DerivationPathError::MaximumDepthExceeded(path.len())),
+        }
+    }
+
+    /// Returns the child index at the given position in the path, or `None` if it is out of bounds.
+    fn get(&self, position: usize) -> Option<ChildIndex> {
+        self.to_vec().ok()?.get(position).copied()
+    }
+
+    /// Returns the derivation path of the child at the given child index.
+    fn child(&self, index: ChildIndex) -> Result<Self, DerivationPathError> {
+        let mut path = self.to_vec()?;
+        path.push(index);
+        Self::from_vec(&path)
+    }
+
+    /// Returns the derivation path of the parent, or errors if the path has no parent.
+    fn parent(&self) -> Result<Self, DerivationPathError> {
+        let mut path = self.to_vec()?;
+        match path.pop() {
+            Some(_) => Self::from_vec(&path),
+            None => Err(DerivationPathError::MissingParentPath),
+        }
+    }
+
+    /// Appends the given child index to the path.
+    fn push(&mut self, index: ChildIndex) -> Result<(), DerivationPathError> {
+        *self = self.child(index)?;
+        Ok(())
+    }
+
+    /// Appends the given child indices to the path.
+    fn extend<I: IntoIterator<Item = ChildIndex>>(&mut self, indices: I) -> Result<(), DerivationPathError> {
+        let mut path = self.to_vec()?;
+        path.extend(indices);
+        *self = Self::from_vec(&path)?;
+        Ok(())
+    }
+
+    /// Returns `true` if the derivation path is a prefix of (or equal to) the given path.
+    ///
+    /// Child indices must match exactly, so `m/0'` is not a prefix of `m/0/1`.
+    fn is_prefix_of(&self, path: &Self) -> bool {
+        match (self.to_vec(), path.to_vec()) {
+            (Ok(prefix), Ok(path)) => path.starts_with(&prefix),
+            _ => false,
+        }
+    }
+
+    /// Returns the child indices of the derivation path that follow the given prefix,
+    /// or `None` if the given path is not a prefix of the derivation path.
+    fn strip_prefix(&self, prefix: &Self) -> Option<Vec<ChildIndex>> {
+        let path = self.to_vec().ok()?;
+        let prefix = prefix.to_vec().ok()?;
+        match path.starts_with(&prefix) {
+            true => Some(path[prefix.len()..].to_vec()),
+            false => None,
+        }
+    }
+
+    /// Returns a copy of the derivation path with every child index hardened.
+    fn harden(&self) -> Result<Self, DerivationPathError> {
+        Self::from_vec(&self.to_vec()?.iter().map(ChildIndex::to_hardened).collect())
+    }
+
+    /// Returns a copy of the derivation path with every child index normal.
+    fn soften(&self) -> Result<Self, DerivationPathError> {
+        Self::from_vec(&self.to_vec()?.iter().map(ChildIndex::to_normal).collect())
+    }
+
+    /// Returns `Ok` if the derivation path is compliant with the BIP44 hierarchy -
+    /// m/{purpose}'/{coin_type}'/{account}'/{change}/{index}.
+    ///
+    /// The purpose, coin type, and account levels must be hardened, while the change and
+    /// address index levels must be normal. Partial paths (e.g. an account path) are accepted.
+    fn validate_bip44(&self) -> Result<(), DerivationPathError> {
+        let path = self.to_vec()?;
+        if path.len() > 5 {
+            return Err(DerivationPathError::ExpectedBIP44Path);
+        }
+        for (level, index) in path.iter().enumerate() {
+            match (level < 3, index.is_hardened()) {
+                (true, false) => return Err(DerivationPathError::ExpectedHardenedPath),
+                (false, true) => return Err(DerivationPathError::ExpectedNormalPath),
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the SLIP-44 coin type of the derivation path.
+    ///
+    /// The path must begin with the purpose level of a scheme that places the coin type
+    /// at the second level, e.g. m/44'/{coin_type}'/... for BIP44.
+    fn coin_type(&self) -> Result<CoinType, DerivationPathError> {
+        let path = self.to_vec()?;
+        match path.len() >= 2 && COIN_TYPE_PURPOSES.contains(&path[0]) {
+            true => CoinType::from_child_index(path[1]),
+            false => Err(DerivationPathError::MissingCoinType),
+        }
+    }
+
+    /// Returns an error if the SLIP-44 coin type of the derivation path is not the expected coin type.
+    fn validate_coin_type(&self, expected: CoinType) -> Result<(), DerivationPathError> {
+        let coin_type = self.coin_type()?;
+        match coin_type == expected {
+            true => Ok(()),
+            false => Err(DerivationPathError::UnexpectedCoinType(expected, coin_type)),
+        }
+    }
+}
+
+/// Returns the child indices of the given path components, starting at position 1,
+/// rejecting any component that is not in canonical form.
+fn parse_strict<'a, I: Iterator<Item = &'a str>>(components: I) -> Result<Vec<ChildIndex>, DerivationPathError> {
+    components
+        .enumerate()
+        .map(|(i, component)| {
+            let position = i + 1;
+            let (number, hardened) = match component.ends_with('\'') || component.ends_with('h') {
+                true => (&component[..component.len() - 1], true),
+                false => (component, false),
+            };
+
+            // Reject empty components, signs, and leading zeros
+            if number.is_empty()
+                || !number.bytes().all(|byte| byte.is_ascii_digit())
+                || (number.len() > 1 && number.starts_with('0'))
+            {
+                return Err(DerivationPathError::InvalidPathComponent(
+                    position,
+                    component.to_string(),
+                ));
+            }
+
+            match number.parse::<u32>() {
+                Ok(index) if index < (1 << 31) => Ok(match hardened {
+                    true => ChildIndex::Hardened(index),
+                    false => ChildIndex::Normal(index),
+                }),
+                _ => Err(DerivationPathError::PathComponentOverflow(
+                    position,
+                    component.to_string(),
+                )),
+            }
+        })
+        .collect()
 }
 
 #[derive(Debug, Fail, PartialEq, Eq)]
@@ -25,6 +205,12 @@ pub enum DerivationPathError {
     #[fail(display = "expected BIP49 path")]
     ExpectedBIP49Path,
 
+    #[fail(display = "expected BIP84 path")]
+    ExpectedBIP84Path,
+
+    #[fail(display = "expected BIP86 path")]
+    ExpectedBIP86Path,
+
     #[fail(display = "expected valid Ethereum derivation path")]
     ExpectedValidEthereumDerivationPath,
 
@@ -43,8 +229,44 @@ pub enum DerivationPathError {
     #[fail(display = "invalid child number format")]
     InvalidChildNumberFormat,
 
+    #[fail(display = "invalid coin type: {}", _0)]
+    InvalidCoinType(String),
+
     #[fail(display = "invalid derivation path: {}", _0)]
     InvalidDerivationPath(String),
+
+    #[fail(display = "invalid key origin: {}", _0)]
+    InvalidKeyOrigin(String),
+
+    #[fail(display = "invalid derivation path component at position {}: {}", _0, _1)]
+    InvalidPathComponent(usize, String),
+
+    #[fail(display = "invalid wallet preset: {}", _0)]
+    InvalidWalletPreset(String),
+
+    #[fail(display = "missing coin type")]
+    MissingCoinType,
+
+    #[fail(display = "missing parent path")]
+    MissingParentPath,
+
+    #[fail(display = "maximum derivation path depth exceeded: {}", _0)]
+    MaximumDepthExceeded(usize),
+
+    #[fail(
+        display = "derivation path component at position {} exceeds the maximum child index: {}",
+        _0, _1
+    )]
+    PathComponentOverflow(usize, String),
+
+    #[fail(display = "unexpected coin type: expected {}, found {}", _0, _1)]
+    UnexpectedCoinType(CoinType, CoinType),
+
+    #[fail(display = "unknown coin type: {}", _0)]
+    UnknownCoinType(u32),
+
+    #[fail(display = "unsupported wallet preset for {}: {}", _0, _1)]
+    UnsupportedWalletPreset(CoinType, String),
 }
 
 /// Represents a child index for a derivation path
@@ -88,6 +310,36 @@ impl ChildIndex {
         }
     }
 
+    /// Returns the [`Hardened`] child index with the same number.
+    pub fn to_hardened(&self) -> Self {
+        match *self {
+            ChildIndex::Hardened(number) | ChildIndex::Normal(number) => ChildIndex::Hardened(number),
+        }
+    }
+
+    /// Returns the [`Normal`] child index with the same number.
+    pub fn to_normal(&self) -> Self {
+        match *self {
+            ChildIndex::Hardened(number) | ChildIndex::Normal(number) => ChildIndex::Normal(number),
+        }
+    }
+
+    /// Increments the child index by one, or errors if the incremented number is not within [0, 2^31 - 1].
+    /// The child index remains [`Normal`] or [`Hardened`].
+    pub fn increment(&mut self) -> Result<(), DerivationPathError> {
+        *self = self.next()?;
+        Ok(())
+    }
+
+    /// Returns the next child index, or errors if the next number is not within [0, 2^31 - 1].
+    /// The next child index remains [`Normal`] or [`Hardened`].
+    pub fn next(&self) -> Result<Self, DerivationPathError> {
+        match *self {
+            ChildIndex::Normal(number) => Self::normal(number.wrapping_add(1)),
+            ChildIndex::Hardened(number) => Self::hardened(number.wrapping_add(1)),
+        }
+    }
+
     /// Returns the child index.
     pub fn to_index(&self) -> u32 {
         match self {
@@ -131,15 +383,316 @@ impl FromStr for ChildIndex {
     }
 }
 
+/// Formats a hardened child index with an apostrophe (`0'`), or with `h` (`0h`)
+/// if the alternate flag is specified, e.g. `format!("{:#}", index)`.
 impl fmt::Display for ChildIndex {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
-            ChildIndex::Hardened(number) => write!(f, "{}'", number),
+            ChildIndex::Hardened(number) => write!(f, "{}{}", number, hardened_marker(f)),
             ChildIndex::Normal(number) => write!(f, "{}", number),
         }
     }
 }
 
+/// Returns the hardened marker for the formatter - `h` if the alternate flag is specified, otherwise `'`.
+fn hardened_marker(f: &fmt::Formatter) -> char {
+    match f.alternate() {
+        true => 'h',
+        false => '\'',
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ChildIndex {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ChildIndex {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let index = String::deserialize(deserializer)?;
+        Self::from_str(&index).map_err(serde::de::Error::custom)
+    }
+}
+
+/// An iterator over an inclusive range of child indices, e.g. `0..=19` for a gap limit scan.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChildIndexRange {
+    /// The next child index to yield, or `None` if the range is exhausted
+    next: Option<ChildIndex>,
+    /// The last child index of the range
+    end: ChildIndex,
+}
+
+impl ChildIndexRange {
+    /// Returns an inclusive range of child indices from `start` to `end`.
+    /// Errors if the child indices are not both [`Normal`] or both [`Hardened`], or if `start` is after `end`.
+    pub fn new(start: ChildIndex, end: ChildIndex) -> Result<Self, DerivationPathError> {
+        if start.is_hardened() != end.is_hardened() || start > end {
+            return Err(DerivationPathError::InvalidDerivationPath(format!("{}-{}", start, end)));
+        }
+        Ok(Self { next: Some(start), end })
+    }
+
+    /// Returns a range of `count` child indices beginning at `start`.
+    /// Errors if the range exceeds the maximum child number of 2^31 - 1.
+    pub fn from_count(start: ChildIndex, count: u32) -> Result<Self, DerivationPathError> {
+        match count {
+            0 => Ok(Self { next: None, end: start }),
+            _ => {
+                let end = match start {
+                    ChildIndex::Normal(number) => ChildIndex::normal(number.saturating_add(count - 1))?,
+                    ChildIndex::Hardened(number) => ChildIndex::hardened(number.saturating_add(count - 1))?,
+                };
+                Self::new(start, end)
+            }
+        }
+    }
+}
+
+impl Iterator for ChildIndexRange {
+    type Item = ChildIndex;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next.take()?;
+        if current < self.end {
+            self.next = current.next().ok();
+        }
+        Some(current)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = match self.next {
+            Some(next) => (u32::from(self.end) - u32::from(next)) as usize + 1,
+            None => 0,
+        };
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for ChildIndexRange {}
+
+/// Represents a derivation path relative to an extended key, e.g. `0/15` from an account extended public key.
+/// Unlike a derivation path from the master key, a relative derivation path omits the leading `m`.
+#[derive(Clone, PartialEq, Eq, PartialOrd)]
+pub struct RelativeDerivationPath(Vec<ChildIndex>);
+
+impl DerivationPath for RelativeDerivationPath {
+    /// Returns a child index vector given the derivation path.
+    fn to_vec(&self) -> Result<Vec<ChildIndex>, DerivationPathError> {
+        Ok(self.0.clone())
+    }
+
+    /// Returns a derivation path given the child index vector.
+    fn from_vec(path: &Vec<ChildIndex>) -> Result<Self, DerivationPathError> {
+        if path.len() > MAX_DEPTH {
+            return Err(DerivationPathError::MaximumDepthExceeded(path.len()));
+        }
+        Ok(RelativeDerivationPath(path.to_vec()))
+    }
+
+    /// Returns a relative derivation path given its string representation, e.g. `0/15`,
+    /// rejecting components that are not in canonical form. The first component is at position 1.
+    fn from_str_strict(path: &str) -> Result<Self, DerivationPathError> {
+        match path.is_empty() {
+            true => Ok(RelativeDerivationPath(vec![])),
+            false => Self::from_vec(&parse_strict(path.split('/'))?),
+        }
+    }
+}
+
+impl FromStr for RelativeDerivationPath {
+    type Err = DerivationPathError;
+
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        if path.is_empty() {
+            return Ok(RelativeDerivationPath(vec![]));
+        }
+
+        if path.starts_with('m') {
+            return Err(DerivationPathError::InvalidDerivationPath(path.to_string()));
+        }
+
+        let path: Result<Vec<ChildIndex>, Self::Err> = path.split("/").map(str::parse).collect();
+        Self::from_vec(&path?)
+    }
+}
+
+impl fmt::Debug for RelativeDerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self, f)
+    }
+}
+
+impl fmt::Display for RelativeDerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, index) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str("/")?;
+            }
+            fmt::Display::fmt(index, f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for RelativeDerivationPath {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for RelativeDerivationPath {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let path = String::deserialize(deserializer)?;
+        Self::from_str(&path).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Represents a derivation path template, where each component is either a child index,
+/// an inclusive range of child indices (e.g. `0-99`), or a wildcard (`*`).
+///
+/// For example, `m/44'/0'/0'/0/*` describes every normal address index of a BIP44 account.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DerivationPathRange {
+    /// The inclusive (start, end) bounds of each path component
+    components: Vec<(ChildIndex, ChildIndex)>,
+}
+
+impl DerivationPathRange {
+    /// Returns an iterator over the concrete derivation paths described by the range,
+    /// in ascending order with the last component incrementing first.
+    pub fn paths<P: DerivationPath>(&self) -> DerivationPathIterator<P> {
+        DerivationPathIterator {
+            components: self.components.clone(),
+            next: Some(self.components.iter().map(|(start, _)| *start).collect()),
+            _path: PhantomData,
+        }
+    }
+}
+
+impl FromStr for DerivationPathRange {
+    type Err = DerivationPathError;
+
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        let mut parts = path.split("/");
+
+        if parts.next().unwrap() != "m" {
+            return Err(DerivationPathError::InvalidDerivationPath(path.to_string()));
+        }
+
+        let mut components = vec![];
+        for part in parts {
+            let (body, hardened) = match part.ends_with('\'') || part.ends_with('h') {
+                true => (&part[0..part.len() - 1], true),
+                false => (part, false),
+            };
+
+            let parse = |index: &str| -> Result<ChildIndex, DerivationPathError> {
+                let index = index
+                    .parse()
+                    .map_err(|_| DerivationPathError::InvalidChildNumberFormat)?;
+                match hardened {
+                    true => ChildIndex::hardened(index),
+                    false => ChildIndex::normal(index),
+                }
+            };
+
+            let component = match body {
+                "*" => (parse("0")?, parse("2147483647")?),
+                _ => {
+                    let mut bounds = body.splitn(2, '-');
+                    let start = parse(bounds.next().unwrap())?;
+                    let end = match bounds.next() {
+                        Some(end) => parse(end)?,
+                        None => start,
+                    };
+                    if start > end {
+                        return Err(DerivationPathError::InvalidDerivationPath(path.to_string()));
+                    }
+                    (start, end)
+                }
+            };
+            components.push(component);
+        }
+
+        if components.len() > MAX_DEPTH {
+            return Err(DerivationPathError::MaximumDepthExceeded(components.len()));
+        }
+
+        Ok(Self { components })
+    }
+}
+
+impl fmt::Display for DerivationPathRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("m")?;
+        for (start, end) in self.components.iter() {
+            f.write_str("/")?;
+            match (start, end) {
+                (ChildIndex::Normal(0), ChildIndex::Normal(0x7FFFFFFF)) => f.write_str("*")?,
+                (ChildIndex::Hardened(0), ChildIndex::Hardened(0x7FFFFFFF)) => write!(f, "*{}", hardened_marker(f))?,
+                (start, end) if start == end => fmt::Display::fmt(start, f)?,
+                (ChildIndex::Hardened(start), ChildIndex::Hardened(end)) => {
+                    write!(f, "{}-{}{}", start, end, hardened_marker(f))?
+                }
+                (start, end) => write!(f, "{}-{}", start, end)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for DerivationPathRange {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for DerivationPathRange {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let path = String::deserialize(deserializer)?;
+        Self::from_str(&path).map_err(serde::de::Error::custom)
+    }
+}
+
+/// An iterator over the concrete derivation paths of a [`DerivationPathRange`].
+#[derive(Clone, Debug)]
+pub struct DerivationPathIterator<P: DerivationPath> {
+    /// The inclusive (start, end) bounds of each path component
+    components: Vec<(ChildIndex, ChildIndex)>,
+    /// The next child index vector to yield, or `None` if the range is exhausted
+    next: Option<Vec<ChildIndex>>,
+    /// PhantomData
+    _path: PhantomData<P>,
+}
+
+impl<P: DerivationPath> Iterator for DerivationPathIterator<P> {
+    type Item = Result<P, DerivationPathError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next.take()?;
+
+        let mut next = current.clone();
+        for (i, (start, end)) in self.components.iter().enumerate().rev() {
+            if next[i] < *end {
+                next[i] = next[i].next().ok()?;
+                self.next = Some(next);
+                break;
+            }
+            next[i] = *start;
+        }
+
+        Some(P::from_vec(&current))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -224,5 +777,361 @@ mod tests {
                 assert_eq!(format!("{}\'", i), ChildIndex::Hardened(i).to_string());
             }
         }
+
+        #[test]
+        fn to_string_alternate() {
+            assert_eq!("0h", format!("{:#}", ChildIndex::Hardened(0)));
+            assert_eq!("2147483647h", format!("{:#}", ChildIndex::Hardened(2147483647)));
+            assert_eq!("0", format!("{:#}", ChildIndex::Normal(0)));
+            assert_eq!("0'", format!("{}", ChildIndex::Hardened(0)));
+        }
+
+        #[cfg(feature = "serde")]
+        #[test]
+        fn serde() {
+            for index in [
+                ChildIndex::Normal(0),
+                ChildIndex::Hardened(44),
+                ChildIndex::Normal(0x7FFFFFFF),
+            ]
+            .iter()
+            {
+                let json = serde_json::to_string(index).unwrap();
+                assert_eq!(format!("\"{}\"", index), json);
+                assert_eq!(*index, serde_json::from_str(&json).unwrap());
+            }
+            assert_eq!(ChildIndex::Hardened(1), serde_json::from_str("\"1h\"").unwrap());
+            assert!(serde_json::from_str::<ChildIndex>("\"2147483648\"").is_err());
+            assert!(serde_json::from_str::<ChildIndex>("0").is_err());
+        }
+
+        #[test]
+        fn next() {
+            assert_eq!(Ok(ChildIndex::Normal(1)), ChildIndex::Normal(0).next());
+            assert_eq!(Ok(ChildIndex::Hardened(1)), ChildIndex::Hardened(0).next());
+            assert_eq!(
+                Err(DerivationPathError::InvalidChildNumber(2147483648)),
+                ChildIndex::Normal(2147483647).next()
+            );
+            assert_eq!(
+                Err(DerivationPathError::InvalidChildNumber(2147483648)),
+                ChildIndex::Hardened(2147483647).next()
+            );
+        }
+
+        #[test]
+        fn increment() {
+            let mut index = ChildIndex::Normal(41);
+            index.increment().unwrap();
+            assert_eq!(ChildIndex::Normal(42), index);
+
+            let mut index = ChildIndex::Hardened(2147483647);
+            assert!(index.increment().is_err());
+            assert_eq!(ChildIndex::Hardened(2147483647), index);
+        }
+
+        #[test]
+        fn to_hardened() {
+            assert_eq!(ChildIndex::Hardened(0), ChildIndex::Normal(0).to_hardened());
+            assert_eq!(ChildIndex::Hardened(7), ChildIndex::Hardened(7).to_hardened());
+            assert_eq!(
+                ChildIndex::Hardened(0x7FFFFFFF),
+                ChildIndex::Normal(0x7FFFFFFF).to_hardened()
+            );
+        }
+
+        #[test]
+        fn to_normal() {
+            assert_eq!(ChildIndex::Normal(0), ChildIndex::Hardened(0).to_normal());
+            assert_eq!(ChildIndex::Normal(7), ChildIndex::Normal(7).to_normal());
+            assert_eq!(
+                ChildIndex::Normal(0x7FFFFFFF),
+                ChildIndex::Hardened(0x7FFFFFFF).to_normal()
+            );
+        }
+    }
+
+    mod child_index_range {
+        use super::*;
+
+        #[test]
+        fn new() {
+            let range = ChildIndexRange::new(ChildIndex::Normal(0), ChildIndex::Normal(2)).unwrap();
+            assert_eq!(3, range.len());
+            assert_eq!(
+                vec![ChildIndex::Normal(0), ChildIndex::Normal(1), ChildIndex::Normal(2)],
+                range.collect::<Vec<_>>()
+            );
+
+            let range = ChildIndexRange::new(ChildIndex::Hardened(5), ChildIndex::Hardened(5)).unwrap();
+            assert_eq!(vec![ChildIndex::Hardened(5)], range.collect::<Vec<_>>());
+
+            let range = ChildIndexRange::new(ChildIndex::Normal(2147483646), ChildIndex::Normal(2147483647)).unwrap();
+            assert_eq!(
+                vec![ChildIndex::Normal(2147483646), ChildIndex::Normal(2147483647)],
+                range.collect::<Vec<_>>()
+            );
+        }
+
+        #[test]
+        fn from_count() {
+            let range = ChildIndexRange::from_count(ChildIndex::Normal(20), 20).unwrap();
+            assert_eq!(20, range.len());
+            assert_eq!(
+                (20..40).map(ChildIndex::Normal).collect::<Vec<_>>(),
+                range.collect::<Vec<_>>()
+            );
+            assert_eq!(
+                0,
+                ChildIndexRange::from_count(ChildIndex::Normal(0), 0).unwrap().count()
+            );
+            assert_eq!(
+                Err(DerivationPathError::InvalidChildNumber(2147483648)),
+                ChildIndexRange::from_count(ChildIndex::Hardened(2147483647), 2)
+            );
+        }
+
+        #[test]
+        fn invalid_range() {
+            assert!(ChildIndexRange::new(ChildIndex::Normal(0), ChildIndex::Hardened(1)).is_err());
+            assert!(ChildIndexRange::new(ChildIndex::Hardened(0), ChildIndex::Normal(1)).is_err());
+            assert!(ChildIndexRange::new(ChildIndex::Normal(2), ChildIndex::Normal(1)).is_err());
+        }
+    }
+
+    mod relative_derivation_path {
+        use super::*;
+
+        #[test]
+        fn from_str() {
+            assert_eq!(
+                RelativeDerivationPath(vec![ChildIndex::Normal(0), ChildIndex::Normal(15)]),
+                RelativeDerivationPath::from_str("0/15").unwrap()
+            );
+            assert_eq!(
+                RelativeDerivationPath(vec![ChildIndex::Hardened(1), ChildIndex::Normal(2)]),
+                RelativeDerivationPath::from_str("1h/2").unwrap()
+            );
+            assert_eq!(
+                RelativeDerivationPath(vec![]),
+                RelativeDerivationPath::from_str("").unwrap()
+            );
+        }
+
+        #[test]
+        fn to_string() {
+            for path in ["", "0", "0/15", "44'/0'/0'/1/2"].iter() {
+                assert_eq!(*path, RelativeDerivationPath::from_str(path).unwrap().to_string());
+            }
+            assert_eq!(
+                "1h/2",
+                format!("{:#}", RelativeDerivationPath::from_str("1'/2").unwrap())
+            );
+        }
+
+        #[test]
+        fn invalid_path() {
+            assert_eq!(
+                Err(DerivationPathError::InvalidDerivationPath("m/0".into())),
+                RelativeDerivationPath::from_str("m/0")
+            );
+            assert_eq!(
+                Err(DerivationPathError::InvalidChildNumberFormat),
+                RelativeDerivationPath::from_str("/0")
+            );
+            assert_eq!(
+                Err(DerivationPathError::InvalidChildNumberFormat),
+                RelativeDerivationPath::from_str("0//1")
+            );
+            assert_eq!(
+                Err(DerivationPathError::InvalidChildNumber(2147483648)),
+                RelativeDerivationPath::from_str("2147483648")
+            );
+        }
+
+        #[test]
+        fn depth() {
+            assert_eq!(Ok(0), RelativeDerivationPath::from_str("").unwrap().depth());
+            assert_eq!(Ok(2), RelativeDerivationPath::from_str("0/15").unwrap().depth());
+            assert_eq!(
+                Err(DerivationPathError::MaximumDepthExceeded(256)),
+                RelativeDerivationPath::from_str(&vec!["0"; 256].join("/"))
+            );
+            assert_eq!(
+                Err(DerivationPathError::MaximumDepthExceeded(256)),
+                DerivationPathRange::from_str(&format!("m{}", "/*".repeat(256)))
+            );
+        }
+
+        #[test]
+        fn manipulation() {
+            let mut path = RelativeDerivationPath::from_str("0").unwrap();
+            path.push(ChildIndex::Normal(15)).unwrap();
+            assert_eq!(RelativeDerivationPath::from_str("0/15").unwrap(), path);
+            assert_eq!(RelativeDerivationPath::from_str("0").unwrap(), path.parent().unwrap());
+        }
+
+        #[test]
+        fn from_str_strict() {
+            for path in ["", "0", "0/15", "44'/0'/0'/1/2", "2147483647'"].iter() {
+                assert_eq!(
+                    RelativeDerivationPath::from_str(path).unwrap(),
+                    RelativeDerivationPath::from_str_strict(path).unwrap()
+                );
+            }
+            assert_eq!(
+                RelativeDerivationPath::from_str("1'/2").unwrap(),
+                RelativeDerivationPath::from_str_strict("1h/2").unwrap()
+            );
+        }
+
+        #[test]
+        fn invalid_path_strict() {
+            assert_eq!(
+                Err(DerivationPathError::InvalidPathComponent(2, "007".into())),
+                RelativeDerivationPath::from_str_strict("0/007")
+            );
+            assert_eq!(
+                Err(DerivationPathError::InvalidPathComponent(1, "+1".into())),
+                RelativeDerivationPath::from_str_strict("+1")
+            );
+            assert_eq!(
+                Err(DerivationPathError::InvalidPathComponent(1, "m".into())),
+                RelativeDerivationPath::from_str_strict("m/0")
+            );
+            assert_eq!(
+                Err(DerivationPathError::InvalidPathComponent(2, "".into())),
+                RelativeDerivationPath::from_str_strict("0//1")
+            );
+            assert_eq!(
+                Err(DerivationPathError::InvalidPathComponent(3, "'".into())),
+                RelativeDerivationPath::from_str_strict("0/1/'")
+            );
+            assert_eq!(
+                Err(DerivationPathError::PathComponentOverflow(2, "2147483648'".into())),
+                RelativeDerivationPath::from_str_strict("0/2147483648'")
+            );
+            assert_eq!(
+                Err(DerivationPathError::PathComponentOverflow(1, "99999999999".into())),
+                RelativeDerivationPath::from_str_strict("99999999999")
+            );
+        }
+
+        #[test]
+        fn coin_type() {
+            let path = RelativeDerivationPath::from_str("44'/60'/0'/0/0").unwrap();
+            assert_eq!(Ok(CoinType::Ethereum), path.coin_type());
+            assert_eq!(Ok(()), path.validate_coin_type(CoinType::Ethereum));
+            assert_eq!(
+                Err(DerivationPathError::UnexpectedCoinType(
+                    CoinType::Bitcoin,
+                    CoinType::Ethereum
+                )),
+                path.validate_coin_type(CoinType::Bitcoin)
+            );
+            assert_eq!(
+                Ok(CoinType::Zcash),
+                RelativeDerivationPath::from_str("32'/133'/0'").unwrap().coin_type()
+            );
+            assert_eq!(
+                Err(DerivationPathError::MissingCoinType),
+                RelativeDerivationPath::from_str("0'/60'").unwrap().coin_type()
+            );
+            assert_eq!(
+                Err(DerivationPathError::MissingCoinType),
+                RelativeDerivationPath::from_str("44'").unwrap().coin_type()
+            );
+            assert_eq!(
+                Err(DerivationPathError::UnknownCoinType(4)),
+                RelativeDerivationPath::from_str("44'/4'").unwrap().coin_type()
+            );
+        }
+    }
+
+    mod derivation_path_range {
+        use super::*;
+
+        #[test]
+        fn from_str() {
+            assert_eq!(
+                DerivationPathRange {
+                    components: vec![
+                        (ChildIndex::Hardened(44), ChildIndex::Hardened(44)),
+                        (ChildIndex::Hardened(60), ChildIndex::Hardened(60)),
+                        (ChildIndex::Hardened(0), ChildIndex::Hardened(0)),
+                        (ChildIndex::Normal(0), ChildIndex::Normal(0)),
+                        (ChildIndex::Normal(0), ChildIndex::Normal(99)),
+                    ]
+                },
+                DerivationPathRange::from_str("m/44'/60'/0'/0/0-99").unwrap()
+            );
+            assert_eq!(
+                DerivationPathRange {
+                    components: vec![
+                        (ChildIndex::Hardened(0), ChildIndex::Hardened(0x7FFFFFFF)),
+                        (ChildIndex::Normal(0), ChildIndex::Normal(0x7FFFFFFF)),
+                    ]
+                },
+                DerivationPathRange::from_str("m/*h/*").unwrap()
+            );
+            assert_eq!(
+                DerivationPathRange { components: vec![] },
+                DerivationPathRange::from_str("m").unwrap()
+            );
+        }
+
+        #[test]
+        fn to_string() {
+            for path in ["m", "m/44'/60'/0'/0/0-99", "m/44'/0'/0'/0/*", "m/0'/1-5'/*'", "m/0/0/0"].iter() {
+                assert_eq!(*path, DerivationPathRange::from_str(path).unwrap().to_string());
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        #[test]
+        fn serde() {
+            let path = DerivationPathRange::from_str("m/44'/0'/0'/0/*").unwrap();
+            let json = serde_json::to_string(&path).unwrap();
+            assert_eq!("\"m/44'/0'/0'/0/*\"", json);
+            assert_eq!(path, serde_json::from_str(&json).unwrap());
+            assert!(serde_json::from_str::<DerivationPathRange>("\"m/5-1\"").is_err());
+        }
+
+        #[test]
+        fn to_string_alternate() {
+            for (expected, path) in [
+                ("m", "m"),
+                ("m/44h/60h/0h/0/0-99", "m/44'/60'/0'/0/0-99"),
+                ("m/0h/1-5h/*h", "m/0'/1-5'/*'"),
+            ]
+            .iter()
+            {
+                assert_eq!(*expected, format!("{:#}", DerivationPathRange::from_str(path).unwrap()));
+            }
+        }
+
+        #[test]
+        fn invalid_path() {
+            assert_eq!(
+                Err(DerivationPathError::InvalidDerivationPath("0/0-1".into())),
+                DerivationPathRange::from_str("0/0-1")
+            );
+            assert_eq!(
+                Err(DerivationPathError::InvalidDerivationPath("m/5-1".into())),
+                DerivationPathRange::from_str("m/5-1")
+            );
+            assert_eq!(
+                Err(DerivationPathError::InvalidChildNumberFormat),
+                DerivationPathRange::from_str("m/0-")
+            );
+            assert_eq!(
+                Err(DerivationPathError::InvalidChildNumberFormat),
+                DerivationPathRange::from_str("m/**")
+            );
+            assert_eq!(
+                Err(DerivationPathError::InvalidChildNumber(2147483648)),
+                DerivationPathRange::from_str("m/0-2147483648")
+            );
+        }
     }
 }
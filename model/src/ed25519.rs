@@ -0,0 +1,300 @@
+use crate::derivation_path::{ChildIndex, DerivationPath, DerivationPathError};
+use crate::extended_private_key::ExtendedPrivateKeyError;
+use crate::utilities::crypto::hash160;
+
+use crate::no_std::*;
+use core::fmt;
+use curve25519_dalek::{constants::ED25519_BASEPOINT_TABLE, scalar::Scalar};
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha512};
+use zeroize::Zeroize;
+
+type HmacSha512 = Hmac<Sha512>;
+
+/// The HMAC key used to derive an ed25519 master key from a seed.
+/// https://github.com/satoshilabs/slips/blob/master/slip-0010.md#master-key-generation
+const ED25519_SEED_KEY: &[u8] = b"ed25519 seed";
+
+/// Represents an ed25519 extended private key, derived as specified by SLIP-10.
+/// https://github.com/satoshilabs/slips/blob/master/slip-0010.md
+///
+/// As ed25519 does not support public parent to public child derivation,
+/// every child index in a derivation path must be hardened.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Ed25519ExtendedPrivateKey {
+    /// The depth of key derivation, e.g. 0x00 for master nodes, 0x01 for level-1 derived keys, ...
+    depth: u8,
+    /// The first 32 bits of the key identifier (hash160(0x00 || public_key))
+    parent_fingerprint: [u8; 4],
+    /// The child index of the key (0 for master key)
+    child_index: ChildIndex,
+    /// The chain code for this extended private key
+    chain_code: [u8; 32],
+    /// The ed25519 private key
+    private_key: [u8; 32],
+}
+
+impl Ed25519ExtendedPrivateKey {
+    /// Returns a new ed25519 master extended private key given the seed.
+    pub fn new_master(seed: &[u8]) -> Result<Self, ExtendedPrivateKeyError> {
+        let mut mac = HmacSha512::new_varkey(ED25519_SEED_KEY)?;
+        mac.input(seed);
+        let hmac = mac.result().code();
+
+        let mut private_key = [0u8; 32];
+        private_key.copy_from_slice(&hmac[0..32]);
+        let mut chain_code = [0u8; 32];
+        chain_code.copy_from_slice(&hmac[32..]);
+
+        Ok(Self {
+            depth: 0,
+            parent_fingerprint: [0u8; 4],
+            child_index: ChildIndex::Normal(0),
+            chain_code,
+            private_key,
+        })
+    }
+
+    /// Returns a new ed25519 extended private key given the seed and derivation path.
+    pub fn new<P: DerivationPath>(seed: &[u8], path: &P) -> Result<Self, ExtendedPrivateKeyError> {
+        Self::new_master(seed)?.derive(path)
+    }
+
+    /// Returns the ed25519 extended private key of the given derivation path.
+    pub fn derive<P: DerivationPath>(&self, path: &P) -> Result<Self, ExtendedPrivateKeyError> {
+        if self.depth == 255 {
+            return Err(ExtendedPrivateKeyError::MaximumChildDepthReached(self.depth));
+        }
+
+        let indices = path.to_vec()?;
+        if self.depth as usize + indices.len() > 255 {
+            return Err(ExtendedPrivateKeyError::MaximumChildDepthReached(self.depth));
+        }
+
+        let mut extended_private_key = self.clone();
+
+        for index in indices.into_iter() {
+            if index.is_normal() {
+                return Err(DerivationPathError::ExpectedHardenedPath.into());
+            }
+
+            // HMAC-SHA512(Key = cpar, Data = 0x00 || ser256(kpar) || ser32(i))
+            let mut mac = HmacSha512::new_varkey(&extended_private_key.chain_code)?;
+            mac.input(&[0u8]);
+            mac.input(&extended_private_key.private_key);
+            mac.input(&u32::from(index).to_be_bytes());
+            let hmac = mac.result().code();
+
+            let mut private_key = [0u8; 32];
+            private_key.copy_from_slice(&hmac[0..32]);
+            let mut chain_code = [0u8; 32];
+            chain_code.copy_from_slice(&hmac[32..]);
+
+            extended_private_key = Self {
+                depth: extended_private_key.depth + 1,
+                parent_fingerprint: extended_private_key.fingerprint(),
+                child_index: index,
+                chain_code,
+                private_key,
+            };
+        }
+
+        Ok(extended_private_key)
+    }
+
+    /// Returns the ed25519 private key.
+    pub fn to_private_key(&self) -> [u8; 32] {
+        self.private_key
+    }
+
+    /// Returns the ed25519 public key of the corresponding private key.
+    pub fn to_public_key(&self) -> [u8; 32] {
+        let hash = Sha512::digest(&self.private_key);
+
+        let mut scalar = [0u8; 32];
+        scalar.copy_from_slice(&hash[0..32]);
+        scalar[0] &= 248;
+        scalar[31] &= 127;
+        scalar[31] |= 64;
+
+        (&Scalar::from_bits(scalar) * &ED25519_BASEPOINT_TABLE)
+            .compress()
+            .to_bytes()
+    }
+
+    /// Returns the first 32 bits of the key identifier, hash160(0x00 || public_key).
+    pub fn fingerprint(&self) -> [u8; 4] {
+        let mut public_key = vec![0u8];
+        public_key.extend_from_slice(&self.to_public_key());
+
+        let mut fingerprint = [0u8; 4];
+        fingerprint.copy_from_slice(&hash160(&public_key)[0..4]);
+        fingerprint
+    }
+
+    /// Returns the depth of the extended private key.
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// Returns the fingerprint of the parent extended private key.
+    pub fn parent_fingerprint(&self) -> [u8; 4] {
+        self.parent_fingerprint
+    }
+
+    /// Returns the child index of the extended private key.
+    pub fn child_index(&self) -> ChildIndex {
+        self.child_index
+    }
+
+    /// Returns the chain code of the extended private key.
+    pub fn chain_code(&self) -> [u8; 32] {
+        self.chain_code
+    }
+}
+
+impl fmt::Debug for Ed25519ExtendedPrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Ed25519ExtendedPrivateKey")
+            .field("depth", &self.depth)
+            .field("parent_fingerprint", &self.parent_fingerprint)
+            .field("child_index", &self.child_index)
+            .finish()
+    }
+}
+
+impl Zeroize for Ed25519ExtendedPrivateKey {
+    fn zeroize(&mut self) {
+        self.chain_code.zeroize();
+        self.private_key.zeroize();
+    }
+}
+
+impl Drop for Ed25519ExtendedPrivateKey {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::derivation_path::RelativeDerivationPath;
+
+    use core::str::FromStr;
+
+    fn test_new(
+        seed: &str,
+        path: &str,
+        expected_fingerprint: &str,
+        expected_chain_code: &str,
+        expected_private_key: &str,
+        expected_public_key: &str,
+    ) {
+        let path = RelativeDerivationPath::from_str(path).unwrap();
+        let extended_private_key = Ed25519ExtendedPrivateKey::new(&hex::decode(seed).unwrap(), &path).unwrap();
+
+        assert_eq!(path.depth().unwrap(), extended_private_key.depth());
+        assert_eq!(
+            expected_fingerprint,
+            hex::encode(extended_private_key.parent_fingerprint())
+        );
+        assert_eq!(expected_chain_code, hex::encode(extended_private_key.chain_code()));
+        assert_eq!(expected_private_key, hex::encode(extended_private_key.to_private_key()));
+        assert_eq!(
+            expected_public_key,
+            format!("00{}", hex::encode(extended_private_key.to_public_key()))
+        );
+    }
+
+    mod slip10_ed25519 {
+        use super::*;
+
+        // (seed, relative path, parent_fingerprint, chain_code, private_key, public_key)
+        // https://github.com/satoshilabs/slips/blob/master/slip-0010.md#test-vector-1-for-ed25519
+        const KEYPAIRS: [(&str, &str, &str, &str, &str, &str); 6] = [
+            (
+                "000102030405060708090a0b0c0d0e0f",
+                "",
+                "00000000",
+                "90046a93de5380a72b5e45010748567d5ea02bbf6522f979e05c0d8d8ca9fffb",
+                "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7",
+                "00a4b2856bfec510abab89753fac1ac0e1112364e7d250545963f135f2a33188ed",
+            ),
+            (
+                "000102030405060708090a0b0c0d0e0f",
+                "0'",
+                "ddebc675",
+                "8b59aa11380b624e81507a27fedda59fea6d0b779a778918a2fd3590e16e9c69",
+                "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3",
+                "008c8a13df77a28f3445213a0f432fde644acaa215fc72dcdf300d5efaa85d350c",
+            ),
+            (
+                "000102030405060708090a0b0c0d0e0f",
+                "0'/1'",
+                "13dab143",
+                "a320425f77d1b5c2505a6b1b27382b37368ee640e3557c315416801243552f14",
+                "b1d0bad404bf35da785a64ca1ac54b2617211d2777696fbffaf208f746ae84f2",
+                "001932a5270f335bed617d5b935c80aedb1a35bd9fc1e31acafd5372c30f5c1187",
+            ),
+            (
+                "000102030405060708090a0b0c0d0e0f",
+                "0'/1'/2'",
+                "ebe4cb29",
+                "2e69929e00b5ab250f49c3fb1c12f252de4fed2c1db88387094a0f8c4c9ccd6c",
+                "92a5b23c0b8a99e37d07df3fb9966917f5d06e02ddbd909c7e184371463e9fc9",
+                "00ae98736566d30ed0e9d2f4486a64bc95740d89c7db33f52121f8ea8f76ff0fc1",
+            ),
+            (
+                "000102030405060708090a0b0c0d0e0f",
+                "0'/1'/2'/2'",
+                "316ec1c6",
+                "8f6d87f93d750e0efccda017d662a1b31a266e4a6f5993b15f5c1f07f74dd5cc",
+                "30d1dc7e5fc04c31219ab25a27ae00b50f6fd66622f6e9c913253d6511d1e662",
+                "008abae2d66361c879b900d204ad2cc4984fa2aa344dd7ddc46007329ac76c429c",
+            ),
+            (
+                "000102030405060708090a0b0c0d0e0f",
+                "0'/1'/2'/2'/1000000000'",
+                "d6322ccd",
+                "68789923a0cac2cd5a29172a475fe9e0fb14cd6adb5ad98a3fa70333e7afa230",
+                "8f94d394a8e8fd6b1bc2f3f49f5c47e385281d5c17e65324b0f62483e37e8793",
+                "003c24da049451555d51a7014a37337aa4e12d41e485abccfa46b47dfb2af54b7a",
+            ),
+        ];
+
+        #[test]
+        fn new() {
+            KEYPAIRS.iter().for_each(
+                |(seed, path, parent_fingerprint, chain_code, private_key, public_key)| {
+                    test_new(seed, path, parent_fingerprint, chain_code, private_key, public_key);
+                },
+            );
+        }
+
+        #[test]
+        fn derive() {
+            let master = Ed25519ExtendedPrivateKey::new_master(&hex::decode(KEYPAIRS[0].0).unwrap()).unwrap();
+            let child = master
+                .derive(&RelativeDerivationPath::from_str("0'/1'").unwrap())
+                .unwrap()
+                .derive(&RelativeDerivationPath::from_str("2'").unwrap())
+                .unwrap();
+            assert_eq!(KEYPAIRS[3].4, hex::encode(child.to_private_key()));
+            assert_eq!(ChildIndex::Hardened(2), child.child_index());
+        }
+    }
+
+    mod test_invalid {
+        use super::*;
+
+        #[test]
+        fn derive_normal() {
+            let master = Ed25519ExtendedPrivateKey::new_master(&[0u8; 16]).unwrap();
+            match master.derive(&RelativeDerivationPath::from_str("0'/1").unwrap()) {
+                Err(ExtendedPrivateKeyError::DerivationPathError(DerivationPathError::ExpectedHardenedPath)) => (),
+                _ => panic!("expected hardened path"),
+            }
+        }
+    }
+}
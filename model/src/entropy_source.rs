@@ -0,0 +1,124 @@
+use core::num::NonZeroU32;
+use rand_core::{CryptoRng, Error, RngCore};
+
+/// The interface for a source of entropy for wallet generation.
+///
+/// Every cryptographically secure RNG, such as `OsRng`, is an entropy source. Integrators may
+/// implement this trait for hardware RNGs and audited DRBGs, or for deterministic sources in tests.
+pub trait EntropySource {
+    /// Fills the given bytes with entropy from the source.
+    fn fill_entropy(&mut self, dest: &mut [u8]) -> Result<(), Error>;
+}
+
+impl<R: RngCore + CryptoRng> EntropySource for R {
+    fn fill_entropy(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.try_fill_bytes(dest)
+    }
+}
+
+/// Represents a random number generator drawing from an entropy source,
+/// for the wallet generation methods generic over an RNG.
+pub struct EntropyRng<'a, S: EntropySource + ?Sized>(&'a mut S);
+
+impl<'a, S: EntropySource + ?Sized> EntropyRng<'a, S> {
+    /// Returns a random number generator drawing from the given entropy source.
+    pub fn new(source: &'a mut S) -> Self {
+        Self(source)
+    }
+}
+
+impl<'a, S: EntropySource + ?Sized> RngCore for EntropyRng<'a, S> {
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0u8; 4];
+        self.fill_bytes(&mut bytes);
+        u32::from_le_bytes(bytes)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0u8; 8];
+        self.fill_bytes(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        if let Err(error) = self.try_fill_bytes(dest) {
+            panic!("entropy source failed: {}", error);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.0.fill_entropy(dest)
+    }
+}
+
+impl<'a, S: EntropySource + ?Sized> CryptoRng for EntropyRng<'a, S> {}
+
+/// Returns the error of an entropy source with the given custom error code.
+pub fn entropy_source_error(code: u32) -> Error {
+    let code = Error::CUSTOM_START.saturating_add(code);
+    Error::from(NonZeroU32::new(code).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::Rng;
+
+    /// A deterministic entropy source counting up from zero.
+    struct CountingSource(u8);
+
+    impl EntropySource for CountingSource {
+        fn fill_entropy(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+            dest.iter_mut().for_each(|byte| {
+                *byte = self.0;
+                self.0 = self.0.wrapping_add(1);
+            });
+            Ok(())
+        }
+    }
+
+    /// An entropy source which always fails, such as a disconnected hardware RNG.
+    struct FailingSource;
+
+    impl EntropySource for FailingSource {
+        fn fill_entropy(&mut self, _dest: &mut [u8]) -> Result<(), Error> {
+            Err(entropy_source_error(1))
+        }
+    }
+
+    #[test]
+    fn deterministic() {
+        let source = &mut CountingSource(0);
+        let rng = &mut EntropyRng::new(source);
+        let mut bytes = [0u8; 4];
+        rng.fill(&mut bytes);
+        assert_eq!([0u8, 1, 2, 3], bytes);
+        assert_eq!(0x07060504, rng.next_u32());
+    }
+
+    #[test]
+    fn rng() {
+        // Every secure RNG is an entropy source, including an `EntropyRng` itself.
+        let source = &mut CountingSource(0);
+        let inner = &mut EntropyRng::new(source);
+        let rng = &mut EntropyRng::new(inner);
+        let mut bytes = [0u8; 4];
+        rng.fill(&mut bytes);
+        assert_eq!([0u8, 1, 2, 3], bytes);
+    }
+
+    #[test]
+    fn failing() {
+        let source = &mut FailingSource;
+        let mut bytes = [0u8; 32];
+        let error = EntropyRng::new(source).try_fill_bytes(&mut bytes).unwrap_err();
+        assert_eq!(Some(NonZeroU32::new(Error::CUSTOM_START + 1).unwrap()), error.code());
+    }
+
+    #[test]
+    #[should_panic(expected = "entropy source failed")]
+    fn failing_panic() {
+        let _ = EntropyRng::new(&mut FailingSource).gen::<u32>();
+    }
+}
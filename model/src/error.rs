@@ -0,0 +1,191 @@
+use crate::no_std::*;
+
+/// Represents an error returned by a dependency of a wallet crate.
+///
+/// Each variant retains the original error of the dependency, so consumers
+/// can match on the failure cause instead of its formatted message.
+#[derive(Debug, Fail)]
+pub enum CrateError {
+    #[cfg(feature = "std")]
+    #[fail(display = "base58: {:?}", _0)]
+    Base58(base58::FromBase58Error),
+
+    #[cfg(feature = "std")]
+    #[fail(display = "base58_monero: {:?}", _0)]
+    Base58Monero(base58_monero::base58::Error),
+
+    #[cfg(feature = "std")]
+    #[fail(display = "bech32: {:?}", _0)]
+    Bech32(bech32::Error),
+
+    #[cfg(feature = "ff")]
+    #[fail(display = "ff: {:?}", _0)]
+    Ff(ff::PrimeFieldDecodingError),
+
+    #[fail(display = "crate::no_std: {:?}", _0)]
+    FromUtf8(crate::no_std::FromUtf8Error),
+
+    #[fail(display = "hex: {:?}", _0)]
+    Hex(hex::FromHexError),
+
+    #[fail(display = "crypto-mac: {:?}", _0)]
+    InvalidKeyLength(crypto_mac::InvalidKeyLength),
+
+    #[fail(display = "crate::no_std::io: {:?}", _0)]
+    Io(crate::no_std::io::Error),
+
+    #[fail(display = "{}: {}", _0, _1)]
+    Other(&'static str, String),
+
+    #[fail(display = "core::str: {:?}", _0)]
+    ParseBool(core::str::ParseBoolError),
+
+    #[fail(display = "core::num: {:?}", _0)]
+    ParseInt(core::num::ParseIntError),
+
+    #[fail(display = "rand: {:?}", _0)]
+    Rand(rand_core::Error),
+
+    #[fail(display = "rlp: {:?}", _0)]
+    Rlp(rlp::DecoderError),
+
+    #[fail(display = "libsecp256k1: {:?}", _0)]
+    Secp256k1(secp256k1::Error),
+
+    #[fail(display = "serde_json: {:?}", _0)]
+    SerdeJson(serde_json::error::Error),
+
+    #[fail(display = "core::array: {:?}", _0)]
+    TryFromSlice(core::array::TryFromSliceError),
+
+    #[fail(display = "uint: {:?}", _0)]
+    Uint(uint::FromDecStrErr),
+
+    #[fail(display = "core::str: {:?}", _0)]
+    Utf8(core::str::Utf8Error),
+}
+
+#[cfg(feature = "std")]
+impl From<base58::FromBase58Error> for CrateError {
+    fn from(error: base58::FromBase58Error) -> Self {
+        CrateError::Base58(error)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<base58_monero::base58::Error> for CrateError {
+    fn from(error: base58_monero::base58::Error) -> Self {
+        CrateError::Base58Monero(error)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<bech32::Error> for CrateError {
+    fn from(error: bech32::Error) -> Self {
+        CrateError::Bech32(error)
+    }
+}
+
+#[cfg(feature = "ff")]
+impl From<ff::PrimeFieldDecodingError> for CrateError {
+    fn from(error: ff::PrimeFieldDecodingError) -> Self {
+        CrateError::Ff(error)
+    }
+}
+
+impl From<crate::no_std::FromUtf8Error> for CrateError {
+    fn from(error: crate::no_std::FromUtf8Error) -> Self {
+        CrateError::FromUtf8(error)
+    }
+}
+
+impl From<hex::FromHexError> for CrateError {
+    fn from(error: hex::FromHexError) -> Self {
+        CrateError::Hex(error)
+    }
+}
+
+impl From<crypto_mac::InvalidKeyLength> for CrateError {
+    fn from(error: crypto_mac::InvalidKeyLength) -> Self {
+        CrateError::InvalidKeyLength(error)
+    }
+}
+
+impl From<crate::no_std::io::Error> for CrateError {
+    fn from(error: crate::no_std::io::Error) -> Self {
+        CrateError::Io(error)
+    }
+}
+
+impl From<core::str::ParseBoolError> for CrateError {
+    fn from(error: core::str::ParseBoolError) -> Self {
+        CrateError::ParseBool(error)
+    }
+}
+
+impl From<core::num::ParseIntError> for CrateError {
+    fn from(error: core::num::ParseIntError) -> Self {
+        CrateError::ParseInt(error)
+    }
+}
+
+impl From<rand_core::Error> for CrateError {
+    fn from(error: rand_core::Error) -> Self {
+        CrateError::Rand(error)
+    }
+}
+
+impl From<rlp::DecoderError> for CrateError {
+    fn from(error: rlp::DecoderError) -> Self {
+        CrateError::Rlp(error)
+    }
+}
+
+impl From<secp256k1::Error> for CrateError {
+    fn from(error: secp256k1::Error) -> Self {
+        CrateError::Secp256k1(error)
+    }
+}
+
+impl From<serde_json::error::Error> for CrateError {
+    fn from(error: serde_json::error::Error) -> Self {
+        CrateError::SerdeJson(error)
+    }
+}
+
+impl From<core::array::TryFromSliceError> for CrateError {
+    fn from(error: core::array::TryFromSliceError) -> Self {
+        CrateError::TryFromSlice(error)
+    }
+}
+
+impl From<uint::FromDecStrErr> for CrateError {
+    fn from(error: uint::FromDecStrErr) -> Self {
+        CrateError::Uint(error)
+    }
+}
+
+impl From<core::str::Utf8Error> for CrateError {
+    fn from(error: core::str::Utf8Error) -> Self {
+        CrateError::Utf8(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::private_key::PrivateKeyError;
+
+    use failure::Fail;
+
+    #[test]
+    fn cause() {
+        let error = PrivateKeyError::from(secp256k1::Error::InvalidSecretKey);
+        assert_eq!("libsecp256k1: InvalidSecretKey", error.to_string());
+
+        match error.cause().and_then(|cause| cause.downcast_ref::<CrateError>()) {
+            Some(CrateError::Secp256k1(secp256k1::Error::InvalidSecretKey)) => (),
+            _ => panic!("expected a libsecp256k1 cause"),
+        }
+    }
+}
@@ -1,19 +1,25 @@
 use crate::address::{Address, AddressError};
-use crate::derivation_path::{DerivationPath, DerivationPathError};
+use crate::derivation_path::{ChildIndexRange, DerivationPath, DerivationPathError, RelativeDerivationPath};
+use crate::error::CrateError;
 use crate::extended_public_key::ExtendedPublicKey;
 use crate::format::Format;
+use crate::key_origin::KeyOrigin;
 use crate::network::NetworkError;
 use crate::private_key::PrivateKey;
 use crate::public_key::PublicKey;
+use crate::secret::ExposeSecret;
 
 use crate::no_std::*;
 use core::{
     fmt::{Debug, Display},
     str::FromStr,
 };
+use zeroize::Zeroize;
 
 /// The interface for a generic extended private key.
-pub trait ExtendedPrivateKey: Clone + Debug + Display + FromStr + Send + Sync + 'static + Eq + Sized {
+pub trait ExtendedPrivateKey:
+    Clone + Debug + Display + ExposeSecret + FromStr + Send + Sync + 'static + Eq + Sized + Zeroize
+{
     type Address: Address;
     type DerivationPath: DerivationPath;
     type ExtendedPublicKey: ExtendedPublicKey;
@@ -30,6 +36,19 @@ pub trait ExtendedPrivateKey: Clone + Debug + Display + FromStr + Send + Sync +
     /// Returns the extended private key of the given derivation path.
     fn derive(&self, path: &Self::DerivationPath) -> Result<Self, ExtendedPrivateKeyError>;
 
+    /// Returns the extended private key of the given derivation path, relative to this extended private key.
+    fn derive_relative(&self, path: &RelativeDerivationPath) -> Result<Self, ExtendedPrivateKeyError> {
+        self.derive(&Self::DerivationPath::from_vec(&path.to_vec()?)?)
+    }
+
+    /// Returns the extended private keys of the children in the given range of child indices,
+    /// e.g. the receiving keys `0..=19` of an external chain extended private key.
+    fn derive_children(&self, range: ChildIndexRange) -> Result<Vec<Self>, ExtendedPrivateKeyError> {
+        range
+            .map(|index| self.derive(&Self::DerivationPath::from_vec(&vec![index])?))
+            .collect()
+    }
+
     /// Returns the extended public key of the corresponding extended private key.
     fn to_extended_public_key(&self) -> Self::ExtendedPublicKey;
 
@@ -41,15 +60,25 @@ pub trait ExtendedPrivateKey: Clone + Debug + Display + FromStr + Send + Sync +
 
     /// Returns the address of the corresponding extended private key.
     fn to_address(&self, format: &Self::Format) -> Result<Self::Address, AddressError>;
+
+    /// Returns the first 32 bits of the key identifier of the extended private key.
+    fn fingerprint(&self) -> [u8; 4] {
+        self.to_extended_public_key().fingerprint()
+    }
+
+    /// Returns the key origin of the extended private key derived from this master key along the given path.
+    fn key_origin(&self, path: &Self::DerivationPath) -> Result<KeyOrigin, DerivationPathError> {
+        KeyOrigin::new(self.fingerprint(), path)
+    }
 }
 
 #[derive(Debug, Fail)]
 pub enum ExtendedPrivateKeyError {
-    #[fail(display = "{}: {}", _0, _1)]
-    Crate(&'static str, String),
+    #[fail(display = "{}", _0)]
+    Crate(#[cause] CrateError),
 
     #[fail(display = "{}", _0)]
-    DerivationPathError(DerivationPathError),
+    DerivationPathError(#[cause] DerivationPathError),
 
     #[fail(display = "invalid byte length: {}", _0)]
     InvalidByteLength(usize),
@@ -70,7 +99,7 @@ pub enum ExtendedPrivateKeyError {
     Message(String),
 
     #[fail(display = "{}", _0)]
-    NetworkError(NetworkError),
+    NetworkError(#[cause] NetworkError),
 
     #[fail(display = "unsupported format: {}", _0)]
     UnsupportedFormat(String),
@@ -78,7 +107,7 @@ pub enum ExtendedPrivateKeyError {
 
 impl From<crate::no_std::io::Error> for ExtendedPrivateKeyError {
     fn from(error: crate::no_std::io::Error) -> Self {
-        ExtendedPrivateKeyError::Crate("crate::no_std::io", format!("{:?}", error))
+        ExtendedPrivateKeyError::Crate(CrateError::from(error))
     }
 }
 
@@ -94,38 +123,40 @@ impl From<NetworkError> for ExtendedPrivateKeyError {
     }
 }
 
+#[cfg(feature = "std")]
 impl From<base58::FromBase58Error> for ExtendedPrivateKeyError {
     fn from(error: base58::FromBase58Error) -> Self {
-        ExtendedPrivateKeyError::Crate("base58", format!("{:?}", error))
+        ExtendedPrivateKeyError::Crate(CrateError::from(error))
     }
 }
 
+#[cfg(feature = "std")]
 impl From<bech32::Error> for ExtendedPrivateKeyError {
     fn from(error: bech32::Error) -> Self {
-        ExtendedPrivateKeyError::Crate("bech32", format!("{:?}", error))
+        ExtendedPrivateKeyError::Crate(CrateError::from(error))
     }
 }
 
 impl From<core::array::TryFromSliceError> for ExtendedPrivateKeyError {
     fn from(error: core::array::TryFromSliceError) -> Self {
-        ExtendedPrivateKeyError::Crate("core::array", format!("{:?}", error))
+        ExtendedPrivateKeyError::Crate(CrateError::from(error))
     }
 }
 
 impl From<core::num::ParseIntError> for ExtendedPrivateKeyError {
     fn from(error: core::num::ParseIntError) -> Self {
-        ExtendedPrivateKeyError::Crate("core::num", format!("{:?}", error))
+        ExtendedPrivateKeyError::Crate(CrateError::from(error))
     }
 }
 
 impl From<crypto_mac::InvalidKeyLength> for ExtendedPrivateKeyError {
     fn from(error: crypto_mac::InvalidKeyLength) -> Self {
-        ExtendedPrivateKeyError::Crate("crypto-mac", format!("{:?}", error))
+        ExtendedPrivateKeyError::Crate(CrateError::from(error))
     }
 }
 
 impl From<secp256k1::Error> for ExtendedPrivateKeyError {
     fn from(error: secp256k1::Error) -> Self {
-        ExtendedPrivateKeyError::Crate("libsecp256k1", format!("{:?}", error))
+        ExtendedPrivateKeyError::Crate(CrateError::from(error))
     }
 }
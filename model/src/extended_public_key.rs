@@ -1,7 +1,9 @@
 use crate::address::{Address, AddressError};
-use crate::derivation_path::{DerivationPath, DerivationPathError};
+use crate::derivation_path::{ChildIndexRange, DerivationPath, DerivationPathError, RelativeDerivationPath};
+use crate::error::CrateError;
 use crate::extended_private_key::ExtendedPrivateKey;
 use crate::format::Format;
+use crate::key_origin::KeyOrigin;
 use crate::network::NetworkError;
 use crate::public_key::{PublicKey, PublicKeyError};
 
@@ -25,20 +27,41 @@ pub trait ExtendedPublicKey: Clone + Debug + Display + FromStr + Send + Sync + '
     /// Returns the extended public key for the given derivation path.
     fn derive(&self, path: &Self::DerivationPath) -> Result<Self, ExtendedPublicKeyError>;
 
+    /// Returns the extended public key for the given derivation path, relative to this extended public key.
+    fn derive_relative(&self, path: &RelativeDerivationPath) -> Result<Self, ExtendedPublicKeyError> {
+        self.derive(&Self::DerivationPath::from_vec(&path.to_vec()?)?)
+    }
+
+    /// Returns the extended public keys of the children in the given range of normal child indices,
+    /// e.g. the receiving keys `0..=19` of an external chain extended public key.
+    fn derive_children(&self, range: ChildIndexRange) -> Result<Vec<Self>, ExtendedPublicKeyError> {
+        range
+            .map(|index| self.derive(&Self::DerivationPath::from_vec(&vec![index])?))
+            .collect()
+    }
+
     /// Returns the public key of the corresponding extended public key.
     fn to_public_key(&self) -> Self::PublicKey;
 
     /// Returns the address of the corresponding extended public key.
     fn to_address(&self, format: &Self::Format) -> Result<Self::Address, AddressError>;
+
+    /// Returns the first 32 bits of the key identifier of the extended public key.
+    fn fingerprint(&self) -> [u8; 4];
+
+    /// Returns the key origin of the extended public key derived from this master key along the given path.
+    fn key_origin(&self, path: &Self::DerivationPath) -> Result<KeyOrigin, DerivationPathError> {
+        KeyOrigin::new(self.fingerprint(), path)
+    }
 }
 
 #[derive(Debug, Fail)]
 pub enum ExtendedPublicKeyError {
-    #[fail(display = "{}: {}", _0, _1)]
-    Crate(&'static str, String),
+    #[fail(display = "{}", _0)]
+    Crate(#[cause] CrateError),
 
     #[fail(display = "{}", _0)]
-    DerivationPathError(DerivationPathError),
+    DerivationPathError(#[cause] DerivationPathError),
 
     #[fail(display = "invalid byte length: {}", _0)]
     InvalidByteLength(usize),
@@ -62,10 +85,10 @@ pub enum ExtendedPublicKeyError {
     Message(String),
 
     #[fail(display = "{}", _0)]
-    NetworkError(NetworkError),
+    NetworkError(#[cause] NetworkError),
 
     #[fail(display = "{}", _0)]
-    PublicKeyError(PublicKeyError),
+    PublicKeyError(#[cause] PublicKeyError),
 
     #[fail(display = "unsupported format: {}", _0)]
     UnsupportedFormat(String),
@@ -73,7 +96,7 @@ pub enum ExtendedPublicKeyError {
 
 impl From<crate::no_std::io::Error> for ExtendedPublicKeyError {
     fn from(error: crate::no_std::io::Error) -> Self {
-        ExtendedPublicKeyError::Crate("crate::no_std::io", format!("{:?}", error))
+        ExtendedPublicKeyError::Crate(CrateError::from(error))
     }
 }
 
@@ -95,38 +118,40 @@ impl From<PublicKeyError> for ExtendedPublicKeyError {
     }
 }
 
+#[cfg(feature = "std")]
 impl From<base58::FromBase58Error> for ExtendedPublicKeyError {
     fn from(error: base58::FromBase58Error) -> Self {
-        ExtendedPublicKeyError::Crate("base58", format!("{:?}", error))
+        ExtendedPublicKeyError::Crate(CrateError::from(error))
     }
 }
 
+#[cfg(feature = "std")]
 impl From<bech32::Error> for ExtendedPublicKeyError {
     fn from(error: bech32::Error) -> Self {
-        ExtendedPublicKeyError::Crate("bech32", format!("{:?}", error))
+        ExtendedPublicKeyError::Crate(CrateError::from(error))
     }
 }
 
 impl From<core::array::TryFromSliceError> for ExtendedPublicKeyError {
     fn from(error: core::array::TryFromSliceError) -> Self {
-        ExtendedPublicKeyError::Crate("core::array", format!("{:?}", error))
+        ExtendedPublicKeyError::Crate(CrateError::from(error))
     }
 }
 
 impl From<core::num::ParseIntError> for ExtendedPublicKeyError {
     fn from(error: core::num::ParseIntError) -> Self {
-        ExtendedPublicKeyError::Crate("core::num", format!("{:?}", error))
+        ExtendedPublicKeyError::Crate(CrateError::from(error))
     }
 }
 
 impl From<crypto_mac::InvalidKeyLength> for ExtendedPublicKeyError {
     fn from(error: crypto_mac::InvalidKeyLength) -> Self {
-        ExtendedPublicKeyError::Crate("crypto-mac", format!("{:?}", error))
+        ExtendedPublicKeyError::Crate(CrateError::from(error))
     }
 }
 
 impl From<secp256k1::Error> for ExtendedPublicKeyError {
     fn from(error: secp256k1::Error) -> Self {
-        ExtendedPublicKeyError::Crate("libsecp256k1", format!("{:?}", error))
+        ExtendedPublicKeyError::Crate(CrateError::from(error))
     }
 }
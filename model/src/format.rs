@@ -1,4 +1,5 @@
 use crate::derivation_path::DerivationPathError;
+use crate::error::CrateError;
 
 use crate::no_std::*;
 use core::{
@@ -11,11 +12,11 @@ pub trait Format: Clone + Debug + Display + Send + Sync + 'static + Eq + Ord + S
 
 #[derive(Debug, Fail)]
 pub enum FormatError {
-    #[fail(display = "{}: {}", _0, _1)]
-    Crate(&'static str, String),
+    #[fail(display = "{}", _0)]
+    Crate(#[cause] CrateError),
 
     #[fail(display = "{}", _0)]
-    DerivationPathError(DerivationPathError),
+    DerivationPathError(#[cause] DerivationPathError),
 
     #[fail(display = "invalid address prefix: {:?}", _0)]
     InvalidPrefix(Vec<u8>),
@@ -33,8 +34,9 @@ impl From<DerivationPathError> for FormatError {
     }
 }
 
+#[cfg(feature = "std")]
 impl From<base58_monero::base58::Error> for FormatError {
     fn from(error: base58_monero::base58::Error) -> Self {
-        FormatError::Crate("base58_monero", format!("{:?}", error))
+        FormatError::Crate(CrateError::from(error))
     }
 }
@@ -0,0 +1,189 @@
+use crate::derivation_path::{ChildIndex, DerivationPath, DerivationPathError, RelativeDerivationPath};
+
+use crate::no_std::*;
+use core::{fmt, str::FromStr};
+
+/// Represents the origin of an extended key, as the fingerprint of the master key
+/// it was derived from and the derivation path from that master key.
+///
+/// Key origins are rendered as `[fingerprint/path]`, e.g. `[d34db33f/44'/0'/0']`,
+/// as used by output script descriptors and PSBTs.
+/// https://github.com/bitcoin/bitcoin/blob/master/doc/descriptors.md#key-origin-identification
+#[derive(Clone, PartialEq, Eq)]
+pub struct KeyOrigin {
+    /// The first 32 bits of the master key identifier
+    fingerprint: [u8; 4],
+    /// The derivation path from the master key
+    path: RelativeDerivationPath,
+}
+
+impl KeyOrigin {
+    /// Returns a new key origin given the master key fingerprint and the derivation path from the master key.
+    pub fn new<P: DerivationPath>(fingerprint: [u8; 4], path: &P) -> Result<Self, DerivationPathError> {
+        Ok(Self {
+            fingerprint,
+            path: RelativeDerivationPath::from_vec(&path.to_vec()?)?,
+        })
+    }
+
+    /// Returns the fingerprint of the master key.
+    pub fn fingerprint(&self) -> [u8; 4] {
+        self.fingerprint
+    }
+
+    /// Returns the derivation path from the master key.
+    pub fn path(&self) -> &RelativeDerivationPath {
+        &self.path
+    }
+
+    /// Returns the key origin of the child at the given child index.
+    pub fn child(&self, index: ChildIndex) -> Result<Self, DerivationPathError> {
+        Ok(Self {
+            fingerprint: self.fingerprint,
+            path: self.path.child(index)?,
+        })
+    }
+}
+
+impl FromStr for KeyOrigin {
+    type Err = DerivationPathError;
+
+    fn from_str(origin: &str) -> Result<Self, Self::Err> {
+        if !origin.starts_with('[') || !origin.ends_with(']') || origin.len() < 10 {
+            return Err(DerivationPathError::InvalidKeyOrigin(origin.to_string()));
+        }
+
+        let inner = &origin[1..origin.len() - 1];
+        let (fingerprint, path) = match inner.find('/') {
+            Some(position) => (&inner[..position], &inner[position + 1..]),
+            None => (inner, ""),
+        };
+
+        if fingerprint.len() != 8 || !fingerprint.chars().all(|c| c.is_ascii_hexdigit()) || inner.ends_with('/') {
+            return Err(DerivationPathError::InvalidKeyOrigin(origin.to_string()));
+        }
+
+        let fingerprint = u32::from_str_radix(fingerprint, 16)
+            .map_err(|_| DerivationPathError::InvalidKeyOrigin(origin.to_string()))?;
+
+        Ok(Self {
+            fingerprint: fingerprint.to_be_bytes(),
+            path: RelativeDerivationPath::from_str(path)?,
+        })
+    }
+}
+
+impl fmt::Debug for KeyOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self, f)
+    }
+}
+
+impl fmt::Display for KeyOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[")?;
+        for byte in self.fingerprint.iter() {
+            write!(f, "{:02x}", byte)?;
+        }
+        if self.path.depth().unwrap_or(0) > 0 {
+            match f.alternate() {
+                true => write!(f, "/{:#}", self.path)?,
+                false => write!(f, "/{}", self.path)?,
+            }
+        }
+        write!(f, "]")
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for KeyOrigin {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for KeyOrigin {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let origin = String::deserialize(deserializer)?;
+        Self::from_str(&origin).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new() {
+        let path = RelativeDerivationPath::from_str("44'/0'/0'").unwrap();
+        let origin = KeyOrigin::new([0xd3, 0x4d, 0xb3, 0x3f], &path).unwrap();
+        assert_eq!([0xd3, 0x4d, 0xb3, 0x3f], origin.fingerprint());
+        assert_eq!(&path, origin.path());
+    }
+
+    #[test]
+    fn from_str() {
+        let origin = KeyOrigin::from_str("[d34db33f/44'/0'/0']").unwrap();
+        assert_eq!([0xd3, 0x4d, 0xb3, 0x3f], origin.fingerprint());
+        assert_eq!(&RelativeDerivationPath::from_str("44'/0'/0'").unwrap(), origin.path());
+
+        let origin = KeyOrigin::from_str("[D34DB33F/84h/0h/0h/1]").unwrap();
+        assert_eq!("[d34db33f/84'/0'/0'/1]", origin.to_string());
+
+        let origin = KeyOrigin::from_str("[d34db33f]").unwrap();
+        assert_eq!(Ok(0), origin.path().depth());
+    }
+
+    #[test]
+    fn to_string() {
+        for origin in ["[d34db33f]", "[d34db33f/0]", "[00000000/44'/0'/0'/0/1]"].iter() {
+            assert_eq!(*origin, KeyOrigin::from_str(origin).unwrap().to_string());
+        }
+        assert_eq!(
+            "[d34db33f/49h/0h/0h]",
+            format!("{:#}", KeyOrigin::from_str("[d34db33f/49'/0'/0']").unwrap())
+        );
+    }
+
+    #[test]
+    fn child() {
+        let origin = KeyOrigin::from_str("[d34db33f/44'/0'/0']").unwrap();
+        assert_eq!(
+            KeyOrigin::from_str("[d34db33f/44'/0'/0'/1]").unwrap(),
+            origin.child(ChildIndex::Normal(1)).unwrap()
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        for origin in [
+            "",
+            "[]",
+            "d34db33f/0",
+            "[d34db33f/0",
+            "[d34db3/0]",
+            "[d34db33g/0]",
+            "[d34db33f0]",
+            "[d34db33f/]",
+            "[d34db33f/0/]",
+        ]
+        .iter()
+        {
+            assert!(KeyOrigin::from_str(origin).is_err(), "{}", origin);
+        }
+        assert_eq!(
+            Err(DerivationPathError::InvalidDerivationPath("m/0".into())),
+            KeyOrigin::from_str("[d34db33f/m/0]")
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let origin = KeyOrigin::from_str("[d34db33f/44'/0'/0']").unwrap();
+        let json = serde_json::to_string(&origin).unwrap();
+        assert_eq!("\"[d34db33f/44'/0'/0']\"", json);
+        assert_eq!(origin, serde_json::from_str(&json).unwrap());
+    }
+}
@@ -23,9 +23,24 @@ pub use self::address::*;
 pub mod amount;
 pub use self::amount::*;
 
+pub mod backup;
+pub use self::backup::*;
+
+pub mod coin_type;
+pub use self::coin_type::*;
+
 pub mod derivation_path;
 pub use self::derivation_path::*;
 
+pub mod ed25519;
+pub use self::ed25519::*;
+
+pub mod entropy_source;
+pub use self::entropy_source::*;
+
+pub mod error;
+pub use self::error::*;
+
 pub mod extended_private_key;
 pub use self::extended_private_key::*;
 
@@ -35,6 +50,12 @@ pub use self::extended_public_key::*;
 pub mod format;
 pub use self::format::*;
 
+pub mod key_origin;
+pub use self::key_origin::*;
+
+pub mod manual_entropy;
+pub use self::manual_entropy::*;
+
 pub mod mnemonic;
 pub use self::mnemonic::*;
 
@@ -47,11 +68,29 @@ pub use self::private_key::*;
 pub mod public_key;
 pub use self::public_key::*;
 
+pub mod secret;
+pub use self::secret::*;
+
+pub mod seed_qr;
+pub use self::seed_qr::*;
+
+pub mod seed_xor;
+pub use self::seed_xor::*;
+
+pub mod shamir;
+pub use self::shamir::*;
+
+pub mod slip39;
+pub use self::slip39::*;
+
 pub mod transaction;
 pub use self::transaction::*;
 
 pub mod utilities;
 pub use self::utilities::*;
 
+pub mod wallet_preset;
+pub use self::wallet_preset::*;
+
 pub mod wordlist;
 pub use self::wordlist::*;
@@ -0,0 +1,126 @@
+use crate::mnemonic::MnemonicError;
+
+use crate::no_std::*;
+use sha2::{Digest, Sha256};
+use zeroize::Zeroize;
+
+/// The maximum length of the entropy of manual rolls in bytes
+const MAX_ENTROPY_LENGTH: usize = 32;
+
+/// Represents a physical source of entropy input manually, for users who distrust the OS RNG.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ManualEntropy {
+    /// Coin flips, each of `H` (or `1`) and `T` (or `0`)
+    Coin,
+    /// Rolls of a six-sided die, each of `1` to `6`
+    Dice,
+}
+
+impl ManualEntropy {
+    /// Returns the entropy of each roll in thousandths of a bit, rounded down.
+    fn millibits_per_roll(&self) -> usize {
+        match self {
+            ManualEntropy::Coin => 1000,
+            ManualEntropy::Dice => 2584,
+        }
+    }
+
+    /// Returns the minimum number of rolls providing the given length of entropy in bytes.
+    pub fn min_rolls(&self, length: usize) -> usize {
+        let millibits = length * 8 * 1000;
+        let millibits_per_roll = self.millibits_per_roll();
+        let rolls = millibits / millibits_per_roll;
+        match rolls * millibits_per_roll < millibits {
+            true => rolls + 1,
+            false => rolls,
+        }
+    }
+
+    /// Returns the entropy of the given length in bytes from the given rolls, ignoring whitespace and commas.
+    ///
+    /// The rolls are debiased by hashing, so the entropy is the first bytes of the SHA-256 digest
+    /// of the rolls, as computed by hardware wallets such as Coldcard.
+    pub fn to_entropy(&self, rolls: &str, length: usize) -> Result<Vec<u8>, MnemonicError> {
+        if length == 0 || length > MAX_ENTROPY_LENGTH {
+            return Err(MnemonicError::InvalidEntropyLength(length));
+        }
+
+        let mut normalized = rolls
+            .chars()
+            .filter(|roll| !roll.is_whitespace() && *roll != ',')
+            .map(|roll| self.normalize(roll))
+            .collect::<Result<String, MnemonicError>>()?;
+
+        let min_rolls = self.min_rolls(length);
+        if normalized.len() < min_rolls {
+            let found = normalized.len();
+            normalized.zeroize();
+            return Err(MnemonicError::InsufficientRolls(min_rolls, found));
+        }
+
+        let entropy = Sha256::digest(normalized.as_bytes())[..length].to_vec();
+        normalized.zeroize();
+        Ok(entropy)
+    }
+
+    /// Returns the canonical character of the given roll.
+    fn normalize(&self, roll: char) -> Result<char, MnemonicError> {
+        match (self, roll.to_ascii_uppercase()) {
+            (ManualEntropy::Coin, 'H') | (ManualEntropy::Coin, '1') => Ok('1'),
+            (ManualEntropy::Coin, 'T') | (ManualEntropy::Coin, '0') => Ok('0'),
+            (ManualEntropy::Dice, '1'..='6') => Ok(roll),
+            _ => Err(MnemonicError::InvalidRoll(roll)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn min_rolls() {
+        assert_eq!(128, ManualEntropy::Coin.min_rolls(16));
+        assert_eq!(256, ManualEntropy::Coin.min_rolls(32));
+        assert_eq!(50, ManualEntropy::Dice.min_rolls(16));
+        assert_eq!(100, ManualEntropy::Dice.min_rolls(32));
+    }
+
+    #[test]
+    fn to_entropy() {
+        let rolls =
+            "1234561234561234561234561234561234561234561234561234561234561234561234561234561234561234561234561234";
+        let entropy = ManualEntropy::Dice.to_entropy(rolls, 32).unwrap();
+        assert_eq!(hex::encode(Sha256::digest(rolls.as_bytes())), hex::encode(&entropy));
+        assert_eq!(entropy[..16], ManualEntropy::Dice.to_entropy(rolls, 16).unwrap()[..]);
+
+        let spaced = rolls
+            .as_bytes()
+            .chunks(6)
+            .map(|chunk| core::str::from_utf8(chunk).unwrap())
+            .collect::<Vec<&str>>()
+            .join(", ");
+        assert_eq!(entropy, ManualEntropy::Dice.to_entropy(&spaced, 32).unwrap());
+
+        let flips = "HT".repeat(64);
+        let bits = "10".repeat(64);
+        assert_eq!(
+            ManualEntropy::Coin.to_entropy(&bits, 16).unwrap(),
+            ManualEntropy::Coin.to_entropy(&flips.to_lowercase(), 16).unwrap()
+        );
+    }
+
+    #[test]
+    fn to_entropy_invalid() {
+        match ManualEntropy::Dice.to_entropy(&"1".repeat(99), 32) {
+            Err(MnemonicError::InsufficientRolls(100, 99)) => (),
+            other => panic!("expected insufficient rolls, found {:?}", other),
+        }
+        match ManualEntropy::Dice.to_entropy(&"7".repeat(100), 32) {
+            Err(MnemonicError::InvalidRoll('7')) => (),
+            other => panic!("expected an invalid roll, found {:?}", other),
+        }
+        assert!(ManualEntropy::Coin.to_entropy(&"2".repeat(256), 32).is_err());
+        assert!(ManualEntropy::Coin.to_entropy(&"H".repeat(512), 64).is_err());
+    }
+}
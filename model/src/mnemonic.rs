@@ -1,9 +1,12 @@
 use crate::address::{Address, AddressError};
+use crate::entropy_source::{EntropyRng, EntropySource};
+use crate::error::CrateError;
 use crate::extended_private_key::{ExtendedPrivateKey, ExtendedPrivateKeyError};
 use crate::extended_public_key::ExtendedPublicKey;
 use crate::format::Format;
 use crate::private_key::{PrivateKey, PrivateKeyError};
 use crate::public_key::PublicKey;
+use crate::secret::ExposeSecret;
 use crate::wordlist::WordlistError;
 
 use crate::no_std::*;
@@ -12,9 +15,12 @@ use core::{
     str::FromStr,
 };
 use rand::Rng;
+use zeroize::Zeroize;
 
 /// The interface for a generic mnemonic.
-pub trait Mnemonic: Clone + Debug + Display + FromStr + Send + Sync + 'static + Eq + Sized {
+pub trait Mnemonic:
+    Clone + Debug + Display + ExposeSecret + FromStr + Send + Sync + 'static + Eq + Sized + Zeroize
+{
     type Address: Address;
     type Format: Format;
     type PrivateKey: PrivateKey;
@@ -23,6 +29,11 @@ pub trait Mnemonic: Clone + Debug + Display + FromStr + Send + Sync + 'static +
     /// Returns a new mnemonic.
     fn new<R: Rng>(rng: &mut R) -> Result<Self, MnemonicError>;
 
+    /// Returns a new mnemonic drawing from the given entropy source.
+    fn new_with_entropy<E: EntropySource + ?Sized>(source: &mut E) -> Result<Self, MnemonicError> {
+        Self::new(&mut EntropyRng::new(source))
+    }
+
     /// Returns the mnemonic for the given phrase.
     fn from_phrase(phrase: &str) -> Result<Self, MnemonicError>;
 
@@ -43,6 +54,23 @@ pub trait Mnemonic: Clone + Debug + Display + FromStr + Send + Sync + 'static +
 pub trait MnemonicCount: Mnemonic {
     /// Returns a new mnemonic given the word count.
     fn new_with_count<R: Rng>(rng: &mut R, word_count: u8) -> Result<Self, MnemonicError>;
+
+    /// Returns a new mnemonic given the word count, drawing from the given entropy source.
+    fn new_with_count_and_entropy<E: EntropySource + ?Sized>(
+        source: &mut E,
+        word_count: u8,
+    ) -> Result<Self, MnemonicError> {
+        Self::new_with_count(&mut EntropyRng::new(source), word_count)
+    }
+}
+
+/// The interface for a generic mnemonic encoding raw entropy.
+pub trait MnemonicEntropy: Mnemonic {
+    /// Returns the mnemonic for the given entropy.
+    fn from_entropy(entropy: &[u8]) -> Result<Self, MnemonicError>;
+
+    /// Returns the entropy of the corresponding mnemonic.
+    fn to_entropy(&self) -> Vec<u8>;
 }
 
 /// The interface for a generic mnemonic for extended keys.
@@ -53,6 +81,14 @@ pub trait MnemonicExtended: Mnemonic {
     /// Returns the extended private key of the corresponding mnemonic.
     fn to_extended_private_key(&self, password: Option<&str>) -> Result<Self::ExtendedPrivateKey, MnemonicError>;
 
+    /// Returns the extended private key of the corresponding mnemonic, calling the progress callback
+    /// with the number of completed and total iterations of its seed derivation.
+    fn to_extended_private_key_with_progress(
+        &self,
+        password: Option<&str>,
+        progress: &mut dyn FnMut(u32, u32),
+    ) -> Result<Self::ExtendedPrivateKey, MnemonicError>;
+
     /// Returns the extended public key of the corresponding mnemonic.
     fn to_extended_public_key(&self, password: Option<&str>) -> Result<Self::ExtendedPublicKey, MnemonicError>;
 }
@@ -60,13 +96,16 @@ pub trait MnemonicExtended: Mnemonic {
 #[derive(Debug, Fail)]
 pub enum MnemonicError {
     #[fail(display = "{}", _0)]
-    AddressError(AddressError),
+    AddressError(#[cause] AddressError),
 
-    #[fail(display = "{}: {}", _0, _1)]
-    Crate(&'static str, String),
+    #[fail(display = "{}", _0)]
+    Crate(#[cause] CrateError),
 
     #[fail(display = "{}", _0)]
-    ExtendedPrivateKeyError(ExtendedPrivateKeyError),
+    ExtendedPrivateKeyError(#[cause] ExtendedPrivateKeyError),
+
+    #[fail(display = "Insufficient entropy rolls: {{ expected: {}, found: {} }}", _0, _1)]
+    InsufficientRolls(usize, usize),
 
     #[fail(display = "Invalid checksum word: {{ expected: {:?}, found: {:?} }}", _0, _1)]
     InvalidChecksumWord(String, String),
@@ -80,9 +119,21 @@ pub enum MnemonicError {
     #[fail(display = "Invalid wordlist index: {}", _0)]
     InvalidIndex(usize),
 
+    #[fail(display = "Invalid number of Seed XOR parts: {}", _0)]
+    InvalidPartCount(usize),
+
     #[fail(display = "Invalid phrase: {}", _0)]
     InvalidPhrase(String),
 
+    #[fail(display = "Invalid entropy roll: {}", _0)]
+    InvalidRoll(char),
+
+    #[fail(display = "Invalid SeedQR: {}", _0)]
+    InvalidSeedQR(String),
+
+    #[fail(display = "Invalid number of unknown words: {}", _0)]
+    InvalidUnknownWordCount(usize),
+
     #[fail(display = "Invalid word not found in monero: {}", _0)]
     InvalidWord(String),
 
@@ -96,15 +147,15 @@ pub enum MnemonicError {
     MissingWord,
 
     #[fail(display = "{}", _0)]
-    PrivateKeyError(PrivateKeyError),
+    PrivateKeyError(#[cause] PrivateKeyError),
 
     #[fail(display = "{}", _0)]
-    WordlistError(WordlistError),
+    WordlistError(#[cause] WordlistError),
 }
 
 impl From<crate::no_std::io::Error> for MnemonicError {
     fn from(error: crate::no_std::io::Error) -> Self {
-        MnemonicError::Crate("crate::no_std::io", format!("{:?}", error))
+        MnemonicError::Crate(CrateError::from(error))
     }
 }
 
@@ -134,6 +185,6 @@ impl From<WordlistError> for MnemonicError {
 
 impl From<rand_core::Error> for MnemonicError {
     fn from(error: rand_core::Error) -> Self {
-        MnemonicError::Crate("rand", format!("{:?}", error))
+        MnemonicError::Crate(CrateError::from(error))
     }
 }
@@ -1,6 +1,9 @@
 use crate::address::{Address, AddressError};
+use crate::entropy_source::{EntropyRng, EntropySource};
+use crate::error::CrateError;
 use crate::format::Format;
 use crate::public_key::PublicKey;
+use crate::secret::ExposeSecret;
 
 use crate::no_std::*;
 use core::{
@@ -8,9 +11,12 @@ use core::{
     str::FromStr,
 };
 use rand::Rng;
+use zeroize::Zeroize;
 
 /// The interface for a generic private key.
-pub trait PrivateKey: Clone + Debug + Display + FromStr + Send + Sync + 'static + Eq + Sized {
+pub trait PrivateKey:
+    Clone + Debug + Display + ExposeSecret + FromStr + Send + Sync + 'static + Eq + Sized + Zeroize
+{
     type Address: Address;
     type Format: Format;
     type PublicKey: PublicKey;
@@ -18,6 +24,11 @@ pub trait PrivateKey: Clone + Debug + Display + FromStr + Send + Sync + 'static
     /// Returns a randomly-generated private key.
     fn new<R: Rng>(rng: &mut R) -> Result<Self, PrivateKeyError>;
 
+    /// Returns a private key drawing from the given entropy source.
+    fn new_with_entropy<E: EntropySource + ?Sized>(source: &mut E) -> Result<Self, PrivateKeyError> {
+        Self::new(&mut EntropyRng::new(source))
+    }
+
     /// Returns the public key of the corresponding private key.
     fn to_public_key(&self) -> Self::PublicKey;
 
@@ -27,8 +38,8 @@ pub trait PrivateKey: Clone + Debug + Display + FromStr + Send + Sync + 'static
 
 #[derive(Debug, Fail)]
 pub enum PrivateKeyError {
-    #[fail(display = "{}: {}", _0, _1)]
-    Crate(&'static str, String),
+    #[fail(display = "{}", _0)]
+    Crate(#[cause] CrateError),
 
     #[fail(display = "invalid byte length: {}", _0)]
     InvalidByteLength(usize),
@@ -54,7 +65,7 @@ pub enum PrivateKeyError {
 
 impl From<crate::no_std::io::Error> for PrivateKeyError {
     fn from(error: crate::no_std::io::Error) -> Self {
-        PrivateKeyError::Crate("crate::no_std::io", format!("{:?}", error))
+        PrivateKeyError::Crate(CrateError::from(error))
     }
 }
 
@@ -64,32 +75,34 @@ impl From<&'static str> for PrivateKeyError {
     }
 }
 
+#[cfg(feature = "std")]
 impl From<base58::FromBase58Error> for PrivateKeyError {
     fn from(error: base58::FromBase58Error) -> Self {
-        PrivateKeyError::Crate("base58", format!("{:?}", error))
+        PrivateKeyError::Crate(CrateError::from(error))
     }
 }
 
+#[cfg(feature = "std")]
 impl From<bech32::Error> for PrivateKeyError {
     fn from(error: bech32::Error) -> Self {
-        PrivateKeyError::Crate("bech32", format!("{:?}", error))
+        PrivateKeyError::Crate(CrateError::from(error))
     }
 }
 
 impl From<hex::FromHexError> for PrivateKeyError {
     fn from(error: hex::FromHexError) -> Self {
-        PrivateKeyError::Crate("hex", format!("{:?}", error))
+        PrivateKeyError::Crate(CrateError::from(error))
     }
 }
 
 impl From<rand_core::Error> for PrivateKeyError {
     fn from(error: rand_core::Error) -> Self {
-        PrivateKeyError::Crate("rand", format!("{:?}", error))
+        PrivateKeyError::Crate(CrateError::from(error))
     }
 }
 
 impl From<secp256k1::Error> for PrivateKeyError {
     fn from(error: secp256k1::Error) -> Self {
-        PrivateKeyError::Crate("libsecp256k1", format!("{:?}", error))
+        PrivateKeyError::Crate(CrateError::from(error))
     }
 }
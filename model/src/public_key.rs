@@ -1,4 +1,5 @@
 use crate::address::{Address, AddressError};
+use crate::error::CrateError;
 use crate::format::Format;
 use crate::private_key::PrivateKey;
 
@@ -23,8 +24,8 @@ pub trait PublicKey: Clone + Debug + Display + FromStr + Send + Sync + 'static +
 
 #[derive(Debug, Fail)]
 pub enum PublicKeyError {
-    #[fail(display = "{}: {}", _0, _1)]
-    Crate(&'static str, String),
+    #[fail(display = "{}", _0)]
+    Crate(#[cause] CrateError),
 
     #[fail(display = "invalid byte length: {}", _0)]
     InvalidByteLength(usize),
@@ -44,30 +45,32 @@ pub enum PublicKeyError {
 
 impl From<crate::no_std::io::Error> for PublicKeyError {
     fn from(error: crate::no_std::io::Error) -> Self {
-        PublicKeyError::Crate("crate::no_std::io", format!("{:?}", error))
+        PublicKeyError::Crate(CrateError::from(error))
     }
 }
 
+#[cfg(feature = "std")]
 impl From<base58::FromBase58Error> for PublicKeyError {
     fn from(error: base58::FromBase58Error) -> Self {
-        PublicKeyError::Crate("base58", format!("{:?}", error))
+        PublicKeyError::Crate(CrateError::from(error))
     }
 }
 
+#[cfg(feature = "std")]
 impl From<bech32::Error> for PublicKeyError {
     fn from(error: bech32::Error) -> Self {
-        PublicKeyError::Crate("bech32", format!("{:?}", error))
+        PublicKeyError::Crate(CrateError::from(error))
     }
 }
 
 impl From<hex::FromHexError> for PublicKeyError {
     fn from(error: hex::FromHexError) -> Self {
-        PublicKeyError::Crate("hex", format!("{:?}", error))
+        PublicKeyError::Crate(CrateError::from(error))
     }
 }
 
 impl From<secp256k1::Error> for PublicKeyError {
     fn from(error: secp256k1::Error) -> Self {
-        PublicKeyError::Crate("libsecp256k1", format!("{:?}", error))
+        PublicKeyError::Crate(CrateError::from(error))
     }
 }
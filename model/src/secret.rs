@@ -0,0 +1,60 @@
+use core::fmt;
+
+/// Represents secret material omitted from `Debug` output, which is printed as `[REDACTED]`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Redacted;
+
+impl fmt::Debug for Redacted {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[REDACTED]")
+    }
+}
+
+/// The interface for a type holding secret material.
+///
+/// The `Debug` output of these types redacts their secret material, so the secret
+/// is only written when explicitly requested with `{:?}` on `expose_secret()`.
+pub trait ExposeSecret {
+    /// Writes the `Debug` output of the value, including its secret material.
+    fn fmt_secret(&self, f: &mut fmt::Formatter) -> fmt::Result;
+
+    /// Returns a wrapper of the value whose `Debug` output includes its secret material.
+    fn expose_secret(&self) -> Exposed<'_, Self> {
+        Exposed(self)
+    }
+}
+
+/// Represents a value whose `Debug` output includes its secret material.
+pub struct Exposed<'a, T: ExposeSecret + ?Sized>(&'a T);
+
+impl<'a, T: ExposeSecret + ?Sized> fmt::Debug for Exposed<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt_secret(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::no_std::*;
+
+    struct Secret(u8);
+
+    impl fmt::Debug for Secret {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.debug_tuple("Secret").field(&Redacted).finish()
+        }
+    }
+
+    impl ExposeSecret for Secret {
+        fn fmt_secret(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.debug_tuple("Secret").field(&self.0).finish()
+        }
+    }
+
+    #[test]
+    fn debug() {
+        assert_eq!("Secret([REDACTED])", format!("{:?}", Secret(7)));
+        assert_eq!("Secret(7)", format!("{:?}", Secret(7).expose_secret()));
+    }
+}
@@ -0,0 +1,184 @@
+use crate::mnemonic::{MnemonicEntropy, MnemonicError};
+
+use crate::no_std::*;
+use sha2::{Digest, Sha256};
+use zeroize::Zeroize;
+
+/// The number of bits of a wordlist index
+const INDEX_BITS: usize = 11;
+/// The number of decimal digits of a wordlist index in a Standard SeedQR
+const INDEX_DIGITS: usize = 4;
+/// The entropy lengths, in bytes, of the 12-word and 24-word mnemonics supported by SeedQR
+const ENTROPY_LENGTHS: [usize; 2] = [16, 32];
+
+/// Returns the Standard SeedQR digits of the mnemonic, compatible with SeedSigner and Keystone.
+/// Each word is encoded as its zero-padded, 4-digit wordlist index.
+pub fn seed_qr_encode<M: MnemonicEntropy>(mnemonic: &M) -> Result<String, MnemonicError> {
+    let mut entropy = mnemonic.to_entropy();
+    let indices = entropy_to_indices(&entropy);
+    entropy.zeroize();
+
+    let mut indices = indices?;
+    let digits = indices.iter().map(|index| format!("{:04}", index)).collect();
+    indices.zeroize();
+    Ok(digits)
+}
+
+/// Returns the mnemonic for the given Standard SeedQR digits.
+pub fn seed_qr_decode<M: MnemonicEntropy>(digits: &str) -> Result<M, MnemonicError> {
+    let mut indices = digits_to_indices(digits)?;
+    let entropy = indices_to_entropy(&indices);
+    indices.zeroize();
+
+    let mut entropy = entropy?;
+    let mnemonic = M::from_entropy(&entropy);
+    entropy.zeroize();
+    mnemonic
+}
+
+/// Returns the Compact SeedQR bytes of the mnemonic, compatible with SeedSigner and Keystone.
+/// The bytes are the raw entropy of the mnemonic, omitting its checksum.
+pub fn compact_seed_qr_encode<M: MnemonicEntropy>(mnemonic: &M) -> Result<Vec<u8>, MnemonicError> {
+    let entropy = mnemonic.to_entropy();
+    match ENTROPY_LENGTHS.contains(&entropy.len()) {
+        true => Ok(entropy),
+        false => Err(MnemonicError::InvalidEntropyLength(entropy.len())),
+    }
+}
+
+/// Returns the mnemonic for the given Compact SeedQR bytes.
+pub fn compact_seed_qr_decode<M: MnemonicEntropy>(bytes: &[u8]) -> Result<M, MnemonicError> {
+    match ENTROPY_LENGTHS.contains(&bytes.len()) {
+        true => M::from_entropy(bytes),
+        false => Err(MnemonicError::InvalidEntropyLength(bytes.len())),
+    }
+}
+
+/// Returns the wordlist indices of the entropy, followed by its checksum, as defined in BIP39.
+fn entropy_to_indices(entropy: &[u8]) -> Result<Vec<u16>, MnemonicError> {
+    if !ENTROPY_LENGTHS.contains(&entropy.len()) {
+        return Err(MnemonicError::InvalidEntropyLength(entropy.len()));
+    }
+
+    let mut bytes = entropy.to_vec();
+    bytes.push(Sha256::digest(entropy)[0]);
+
+    let word_count = (entropy.len() * 8 + entropy.len() / 4) / INDEX_BITS;
+    let indices = (0..word_count)
+        .map(|word| {
+            (0..INDEX_BITS).fold(0u16, |index, bit| {
+                let position = word * INDEX_BITS + bit;
+                (index << 1) | u16::from((bytes[position / 8] >> (7 - position % 8)) & 1)
+            })
+        })
+        .collect();
+    bytes.zeroize();
+
+    Ok(indices)
+}
+
+/// Returns the entropy of the wordlist indices, after verifying its checksum, as defined in BIP39.
+fn indices_to_entropy(indices: &[u16]) -> Result<Vec<u8>, MnemonicError> {
+    let entropy_length = match indices.len() {
+        12 => 16,
+        24 => 32,
+        word_count => {
+            return Err(MnemonicError::InvalidSeedQR(format!(
+                "expected 12 or 24 words, found {}",
+                word_count
+            )))
+        }
+    };
+
+    let mut bytes = vec![0u8; entropy_length + 1];
+    for (word, index) in indices.iter().enumerate() {
+        if *index >= 2048 {
+            return Err(MnemonicError::InvalidIndex(*index as usize));
+        }
+        (0..INDEX_BITS).for_each(|bit| {
+            let position = word * INDEX_BITS + bit;
+            bytes[position / 8] |= (((index >> (INDEX_BITS - 1 - bit)) & 1) as u8) << (7 - position % 8);
+        });
+    }
+
+    let checksum = bytes.pop().unwrap_or(0);
+    let checksum_bits = entropy_length / 4;
+    let mask = 0xffu8 << (8 - checksum_bits);
+    if (Sha256::digest(&bytes)[0] ^ checksum) & mask != 0 {
+        bytes.zeroize();
+        return Err(MnemonicError::InvalidSeedQR("invalid checksum".into()));
+    }
+
+    Ok(bytes)
+}
+
+/// Returns the wordlist indices of the given Standard SeedQR digits.
+fn digits_to_indices(digits: &str) -> Result<Vec<u16>, MnemonicError> {
+    let digits = digits.trim();
+    if !digits.bytes().all(|digit| digit.is_ascii_digit()) {
+        return Err(MnemonicError::InvalidSeedQR("expected decimal digits".into()));
+    }
+    if !digits.len().is_multiple_of(INDEX_DIGITS) {
+        return Err(MnemonicError::InvalidSeedQR(format!(
+            "expected a multiple of {} digits, found {}",
+            INDEX_DIGITS,
+            digits.len()
+        )));
+    }
+
+    Ok(digits
+        .as_bytes()
+        .chunks(INDEX_DIGITS)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .fold(0u16, |index, digit| index * 10 + u16::from(digit - b'0'))
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENTROPY: &str = "c6d4a073dd8a600cc674e1c9457b9e736e3bbc3b43f5ba6a24f019eb337f0c0c";
+
+    #[test]
+    fn indices_entropy() {
+        let entropy = hex::decode(ENTROPY).unwrap();
+        for length in ENTROPY_LENGTHS.iter() {
+            let indices = entropy_to_indices(&entropy[..*length]).unwrap();
+            assert_eq!(length * 3 / 4, indices.len());
+            assert!(indices.iter().all(|index| *index < 2048));
+            assert_eq!(entropy[..*length].to_vec(), indices_to_entropy(&indices).unwrap());
+        }
+    }
+
+    #[test]
+    fn digits() {
+        let indices = digits_to_indices("011513251154").unwrap();
+        assert_eq!(vec![115, 1325, 1154], indices);
+    }
+
+    #[test]
+    #[should_panic(expected = "InvalidSeedQR(\"invalid checksum\")")]
+    fn indices_invalid_checksum() {
+        let entropy = hex::decode(ENTROPY).unwrap();
+        let mut indices = entropy_to_indices(&entropy).unwrap();
+        indices[23] ^= 1;
+        let _ = indices_to_entropy(&indices).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "InvalidEntropyLength(20)")]
+    fn entropy_invalid_length() {
+        let entropy = hex::decode(ENTROPY).unwrap();
+        let _ = entropy_to_indices(&entropy[..20]).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "InvalidSeedQR(\"expected decimal digits\")")]
+    fn digits_invalid() {
+        let _ = digits_to_indices("01151325115a").unwrap();
+    }
+}
@@ -0,0 +1,119 @@
+use crate::mnemonic::{MnemonicEntropy, MnemonicError};
+
+use crate::no_std::*;
+use rand::Rng;
+use zeroize::Zeroize;
+
+/// The minimum number of Seed XOR parts
+const MIN_PART_COUNT: usize = 2;
+/// The maximum number of Seed XOR parts
+const MAX_PART_COUNT: usize = 4;
+
+/// Returns the given number of Seed XOR parts of the mnemonic, compatible with Coldcard.
+/// Each part is a valid mnemonic of the same length, and the entropy of the mnemonic
+/// is the XOR of the entropy of all parts.
+pub fn seed_xor_split<M: MnemonicEntropy, R: Rng>(
+    rng: &mut R,
+    mnemonic: &M,
+    count: usize,
+) -> Result<Vec<M>, MnemonicError> {
+    let mut entropy = mnemonic.to_entropy();
+    let parts = split_entropy(rng, &entropy, count);
+    entropy.zeroize();
+
+    let mut parts = parts?;
+    let mnemonics = parts.iter().map(|part| M::from_entropy(part)).collect();
+    parts.iter_mut().for_each(|part| part.zeroize());
+    mnemonics
+}
+
+/// Returns the mnemonic recombined from all of its given Seed XOR parts.
+pub fn seed_xor_combine<M: MnemonicEntropy>(parts: &[M]) -> Result<M, MnemonicError> {
+    let mut parts = parts.iter().map(|part| part.to_entropy()).collect::<Vec<Vec<u8>>>();
+    let entropy = combine_entropy(&parts);
+    parts.iter_mut().for_each(|part| part.zeroize());
+
+    let mut entropy = entropy?;
+    let mnemonic = M::from_entropy(&entropy);
+    entropy.zeroize();
+    mnemonic
+}
+
+/// Returns the given number of parts of the entropy, of which the last is the XOR of the entropy and the random others.
+fn split_entropy<R: Rng>(rng: &mut R, entropy: &[u8], count: usize) -> Result<Vec<Vec<u8>>, MnemonicError> {
+    if !(MIN_PART_COUNT..=MAX_PART_COUNT).contains(&count) {
+        return Err(MnemonicError::InvalidPartCount(count));
+    }
+
+    let mut parts = (1..count)
+        .map(|_| (0..entropy.len()).map(|_| rng.gen()).collect())
+        .collect::<Vec<Vec<u8>>>();
+    let mut last = entropy.to_vec();
+    parts.iter().for_each(|part| xor_into(&mut last, part));
+    parts.push(last);
+
+    Ok(parts)
+}
+
+/// Returns the entropy recombined as the XOR of all of its parts.
+fn combine_entropy(parts: &[Vec<u8>]) -> Result<Vec<u8>, MnemonicError> {
+    if !(MIN_PART_COUNT..=MAX_PART_COUNT).contains(&parts.len()) {
+        return Err(MnemonicError::InvalidPartCount(parts.len()));
+    }
+
+    let mut entropy = vec![0u8; parts[0].len()];
+    for part in parts {
+        if part.len() != entropy.len() {
+            return Err(MnemonicError::InvalidEntropyLength(part.len()));
+        }
+        xor_into(&mut entropy, part);
+    }
+
+    Ok(entropy)
+}
+
+/// XORs the given bytes into the target bytes.
+fn xor_into(target: &mut [u8], bytes: &[u8]) {
+    target.iter_mut().zip(bytes).for_each(|(target, byte)| *target ^= byte);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    const ENTROPY: &str = "c6d4a073dd8a600cc674e1c9457b9e736e3bbc3b43f5ba6a24f019eb337f0c0c";
+
+    #[test]
+    fn split_combine() {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        let entropy = hex::decode(ENTROPY).unwrap();
+
+        for count in MIN_PART_COUNT..=MAX_PART_COUNT {
+            let parts = split_entropy(rng, &entropy, count).unwrap();
+            assert_eq!(count, parts.len());
+            parts.iter().for_each(|part| {
+                assert_eq!(entropy.len(), part.len());
+                assert_ne!(entropy, *part);
+            });
+            assert_eq!(entropy, combine_entropy(&parts).unwrap());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "InvalidPartCount(1)")]
+    fn split_invalid_part_count() {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        let entropy = hex::decode(ENTROPY).unwrap();
+        let _ = split_entropy(rng, &entropy, 1).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "InvalidEntropyLength(16)")]
+    fn combine_invalid_entropy_length() {
+        let entropy = hex::decode(ENTROPY).unwrap();
+        let _ = combine_entropy(&[entropy.clone(), entropy[..16].to_vec()]).unwrap();
+    }
+}
@@ -0,0 +1,376 @@
+use crate::error::CrateError;
+use crate::secret::{ExposeSecret, Redacted};
+
+use crate::no_std::*;
+use core::{fmt, str::FromStr};
+use rand::Rng;
+use sha2::{Digest, Sha256};
+use zeroize::Zeroize;
+
+/// The length of the share identifier, threshold, and index in bytes
+const METADATA_LENGTH: usize = 4;
+/// The length of the share checksum in bytes
+const CHECKSUM_LENGTH: usize = 4;
+/// The index of the secret on the polynomial
+const SECRET_INDEX: u8 = 0;
+
+/// Represents a share of a secret split with Shamir's secret sharing over GF(256).
+///
+/// Unlike SLIP-39 shares, these shares are not encoded as mnemonics, and hold
+/// secrets of any length, such as raw seeds and private spend keys.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ShamirShare {
+    /// The random identifier shared by all shares of a secret
+    pub identifier: u16,
+    /// The number of shares required to reconstruct the secret
+    pub threshold: u8,
+    /// The index of the share, starting from 1
+    pub index: u8,
+    /// The share value
+    value: Vec<u8>,
+}
+
+impl ShamirShare {
+    /// Returns the given number of shares of the secret, of which the threshold number reconstructs the secret.
+    pub fn split<R: Rng>(rng: &mut R, secret: &[u8], threshold: u8, count: u8) -> Result<Vec<Self>, ShamirError> {
+        if secret.is_empty() {
+            return Err(ShamirError::InvalidSecretLength(secret.len()));
+        }
+        if threshold == 0 || threshold > count {
+            return Err(ShamirError::InvalidThreshold(threshold, count));
+        }
+
+        let identifier = rng.gen::<u16>();
+        let mut base_shares = vec![(SECRET_INDEX, secret.to_vec())];
+        base_shares.extend((1..threshold).map(|index| (index, (0..secret.len()).map(|_| rng.gen()).collect())));
+
+        let shares = (1..=count)
+            .map(|index| Self {
+                identifier,
+                threshold,
+                index,
+                value: interpolate(&base_shares, index),
+            })
+            .collect();
+        base_shares.iter_mut().for_each(|(_, value)| value.zeroize());
+
+        Ok(shares)
+    }
+
+    /// Returns the secret reconstructed from the given shares.
+    pub fn combine(shares: &[Self]) -> Result<Vec<u8>, ShamirError> {
+        let first = shares.first().ok_or(ShamirError::MissingShares)?;
+        if shares.iter().any(|share| {
+            share.identifier != first.identifier
+                || share.threshold != first.threshold
+                || share.value.len() != first.value.len()
+        }) {
+            return Err(ShamirError::MismatchedShares);
+        }
+
+        let mut points: Vec<(u8, Vec<u8>)> = vec![];
+        for share in shares {
+            match points.iter().find(|(index, _)| *index == share.index) {
+                Some((_, value)) if *value == share.value => continue,
+                Some(_) => return Err(ShamirError::DuplicateIndex(share.index)),
+                None => points.push((share.index, share.value.clone())),
+            }
+        }
+
+        if points.len() < first.threshold as usize {
+            return Err(ShamirError::InsufficientShares(first.threshold, points.len()));
+        }
+
+        let secret = interpolate(&points[..first.threshold as usize], SECRET_INDEX);
+        points.iter_mut().for_each(|(_, value)| value.zeroize());
+
+        Ok(secret)
+    }
+
+    /// Returns the share of the given bytes, ensuring its checksum is valid.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ShamirError> {
+        if bytes.len() <= METADATA_LENGTH + CHECKSUM_LENGTH {
+            return Err(ShamirError::InvalidShareLength(bytes.len()));
+        }
+
+        let (data, checksum) = bytes.split_at(bytes.len() - CHECKSUM_LENGTH);
+        if create_checksum(data) != checksum {
+            return Err(ShamirError::InvalidChecksum);
+        }
+
+        let (threshold, index) = (data[2], data[3]);
+        if threshold == 0 {
+            return Err(ShamirError::InvalidShareThreshold(threshold));
+        }
+        if index == SECRET_INDEX {
+            return Err(ShamirError::InvalidIndex(index));
+        }
+
+        Ok(Self {
+            identifier: (data[0] as u16) << 8 | data[1] as u16,
+            threshold,
+            index,
+            value: data[METADATA_LENGTH..].to_vec(),
+        })
+    }
+
+    /// Returns the bytes of the share, ending with its checksum.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(METADATA_LENGTH + self.value.len() + CHECKSUM_LENGTH);
+        bytes.extend(&self.identifier.to_be_bytes());
+        bytes.push(self.threshold);
+        bytes.push(self.index);
+        bytes.extend(&self.value);
+
+        let checksum = create_checksum(&bytes);
+        bytes.extend(checksum);
+        bytes
+    }
+}
+
+impl FromStr for ShamirShare {
+    type Err = ShamirError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut bytes = vec![0u8; s.len() / 2];
+        if let Err(error) = hex::decode_to_slice(s, &mut bytes) {
+            return Err(ShamirError::Crate(CrateError::from(error)));
+        }
+        let share = Self::from_bytes(&bytes);
+        bytes.zeroize();
+        share
+    }
+}
+
+impl fmt::Display for ShamirShare {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut bytes = self.to_bytes();
+        let result = bytes.iter().try_for_each(|byte| write!(f, "{:02x}", byte));
+        bytes.zeroize();
+        result
+    }
+}
+
+impl fmt::Debug for ShamirShare {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ShamirShare")
+            .field("identifier", &self.identifier)
+            .field("threshold", &self.threshold)
+            .field("index", &self.index)
+            .field("value", &Redacted)
+            .finish()
+    }
+}
+
+impl ExposeSecret for ShamirShare {
+    fn fmt_secret(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ShamirShare")
+            .field("identifier", &self.identifier)
+            .field("threshold", &self.threshold)
+            .field("index", &self.index)
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+impl Zeroize for ShamirShare {
+    fn zeroize(&mut self) {
+        self.value.zeroize();
+    }
+}
+
+impl Drop for ShamirShare {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+/// Returns the checksum of the share data, the first bytes of its SHA-256 digest.
+fn create_checksum(data: &[u8]) -> Vec<u8> {
+    Sha256::digest(data)[..CHECKSUM_LENGTH].to_vec()
+}
+
+/// Returns the logarithm and exponent tables of GF(256) with the Rijndael polynomial and generator 3.
+fn gf256_tables() -> ([u8; 256], [u8; 255]) {
+    let mut log = [0u8; 256];
+    let mut exp = [0u8; 255];
+    let mut polynomial = 1u16;
+    for (i, power) in exp.iter_mut().enumerate() {
+        *power = polynomial as u8;
+        log[polynomial as usize] = i as u8;
+        polynomial ^= polynomial << 1;
+        if polynomial & 0x100 != 0 {
+            polynomial ^= 0x11B;
+        }
+    }
+    (log, exp)
+}
+
+/// Returns the value at `x` of the polynomial over GF(256) passing through the given shares.
+pub(crate) fn interpolate(shares: &[(u8, Vec<u8>)], x: u8) -> Vec<u8> {
+    if let Some((_, value)) = shares.iter().find(|(index, _)| *index == x) {
+        return value.clone();
+    }
+
+    let (log, exp) = gf256_tables();
+    let log_product = shares
+        .iter()
+        .map(|(index, _)| log[(index ^ x) as usize] as i32)
+        .sum::<i32>();
+
+    let mut result = vec![0u8; shares[0].1.len()];
+    for (index, value) in shares {
+        let log_basis = (log_product
+            - log[(index ^ x) as usize] as i32
+            - shares
+                .iter()
+                .map(|(other, _)| log[(index ^ other) as usize] as i32)
+                .sum::<i32>())
+        .rem_euclid(255);
+
+        result
+            .iter_mut()
+            .zip(value)
+            .filter(|(_, byte)| **byte != 0)
+            .for_each(|(result, byte)| {
+                *result ^= exp[((log[*byte as usize] as i32 + log_basis) % 255) as usize];
+            });
+    }
+    result
+}
+
+#[derive(Debug, Fail)]
+pub enum ShamirError {
+    #[fail(display = "{}", _0)]
+    Crate(#[cause] CrateError),
+
+    #[fail(display = "duplicate share index: {}", _0)]
+    DuplicateIndex(u8),
+
+    #[fail(display = "insufficient shares: {{ expected: {}, found: {} }}", _0, _1)]
+    InsufficientShares(u8, usize),
+
+    #[fail(display = "invalid share checksum")]
+    InvalidChecksum,
+
+    #[fail(display = "invalid share index: {}", _0)]
+    InvalidIndex(u8),
+
+    #[fail(display = "invalid secret length: {}", _0)]
+    InvalidSecretLength(usize),
+
+    #[fail(display = "invalid share length: {}", _0)]
+    InvalidShareLength(usize),
+
+    #[fail(display = "invalid share threshold: {}", _0)]
+    InvalidShareThreshold(u8),
+
+    #[fail(display = "invalid threshold: {{ threshold: {}, count: {} }}", _0, _1)]
+    InvalidThreshold(u8, u8),
+
+    #[fail(display = "mismatched share parameters")]
+    MismatchedShares,
+
+    #[fail(display = "missing shares")]
+    MissingShares,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    const SEED: &str = "000102030405060708090a0b0c0d0e0f";
+    const PRIVATE_SPEND_KEY: &str = "08d34a7bd4b6c8d0e5a9a1a4fd9e2bce45a6b5d7ae5d2b0f2e1d2f8e3a2c4b05";
+
+    fn test_split_combine(secret: &str, threshold: u8, count: u8) {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        let secret = hex::decode(secret).unwrap();
+        let shares = ShamirShare::split(rng, &secret, threshold, count).unwrap();
+        assert_eq!(count as usize, shares.len());
+
+        for start in 0..=(count - threshold) as usize {
+            let subset = &shares[start..start + threshold as usize];
+            assert_eq!(secret, ShamirShare::combine(subset).unwrap());
+        }
+
+        let mut reversed = shares.clone();
+        reversed.reverse();
+        assert_eq!(secret, ShamirShare::combine(&reversed).unwrap());
+    }
+
+    #[test]
+    fn split_combine() {
+        test_split_combine(SEED, 1, 1);
+        test_split_combine(SEED, 1, 3);
+        test_split_combine(SEED, 2, 3);
+        test_split_combine(SEED, 3, 5);
+        test_split_combine(PRIVATE_SPEND_KEY, 2, 2);
+        test_split_combine(PRIVATE_SPEND_KEY, 4, 7);
+    }
+
+    #[test]
+    fn to_string() {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        let secret = hex::decode(SEED).unwrap();
+        let shares = ShamirShare::split(rng, &secret, 2, 3).unwrap();
+
+        let shares = shares
+            .iter()
+            .map(|share| ShamirShare::from_str(&share.to_string()).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(secret, ShamirShare::combine(&shares[1..]).unwrap());
+    }
+
+    #[test]
+    fn insufficient_shares() {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        let secret = hex::decode(PRIVATE_SPEND_KEY).unwrap();
+        let shares = ShamirShare::split(rng, &secret, 3, 5).unwrap();
+
+        let duplicates = [shares[0].clone(), shares[1].clone(), shares[1].clone()];
+        match ShamirShare::combine(&duplicates) {
+            Err(ShamirError::InsufficientShares(3, 2)) => (),
+            other => panic!("expected insufficient shares, found {:?}", other),
+        }
+    }
+
+    #[test]
+    fn invalid() {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        let secret = hex::decode(SEED).unwrap();
+        assert!(ShamirShare::split(rng, &[], 1, 1).is_err());
+        assert!(ShamirShare::split(rng, &secret, 0, 3).is_err());
+        assert!(ShamirShare::split(rng, &secret, 4, 3).is_err());
+
+        let shares = ShamirShare::split(rng, &secret, 2, 3).unwrap();
+        let others = ShamirShare::split(rng, &secret, 2, 3).unwrap();
+        assert!(ShamirShare::combine(&[]).is_err());
+        assert!(ShamirShare::combine(&[shares[0].clone(), others[1].clone()]).is_err());
+
+        // Invalid checksum
+        let mut bytes = shares[0].to_bytes();
+        bytes[METADATA_LENGTH] ^= 1;
+        assert!(ShamirShare::from_bytes(&bytes).is_err());
+        // Invalid share length
+        assert!(ShamirShare::from_str(&shares[0].to_string()[..16]).is_err());
+        // Invalid hex
+        assert!(ShamirShare::from_str("zz").is_err());
+    }
+
+    #[test]
+    fn debug() {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        let secret = hex::decode(SEED).unwrap();
+        let share = &ShamirShare::split(rng, &secret, 2, 3).unwrap()[0];
+        assert_eq!(
+            format!(
+                "ShamirShare {{ identifier: {}, threshold: 2, index: 1, value: [REDACTED] }}",
+                share.identifier
+            ),
+            format!("{:?}", share)
+        );
+    }
+}
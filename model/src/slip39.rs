@@ -0,0 +1,1080 @@
+use crate::error::CrateError;
+use crate::secret::{ExposeSecret, Redacted};
+use crate::shamir::interpolate;
+use crate::wordlist::{slip39::ENGLISH, WordlistError};
+
+use crate::no_std::*;
+use core::{fmt, str::FromStr};
+use hmac::{Hmac, Mac};
+use pbkdf2::pbkdf2;
+use rand::Rng;
+use sha2::Sha256;
+use zeroize::Zeroize;
+
+type HmacSha256 = Hmac<Sha256>;
+/// The index and value of each member share of a group
+type MemberShares = Vec<(u8, Vec<u8>)>;
+
+/// The customization string of the checksum of a share
+const CUSTOMIZATION_STRING: &[u8] = b"shamir";
+/// The customization string of the checksum of an extendable share
+const CUSTOMIZATION_STRING_EXTENDABLE: &[u8] = b"shamir_extendable";
+/// The number of words of the share checksum
+const CHECKSUM_WORDS: usize = 3;
+/// The number of words of the share identifier, iteration exponent, and group and member parameters
+const METADATA_WORDS: usize = 4;
+/// The minimum length of a master secret in bytes
+const MIN_SECRET_LENGTH: usize = 16;
+/// The maximum number of groups, and of members in a group
+const MAX_SHARE_COUNT: u8 = 16;
+/// The maximum iteration exponent
+const MAX_ITERATION_EXPONENT: u8 = 15;
+/// The number of PBKDF2 iterations of each Feistel round, before scaling by the iteration exponent
+const BASE_ITERATION_COUNT: usize = 2500;
+/// The number of Feistel rounds of the master secret encryption
+const ROUND_COUNT: u8 = 4;
+/// The index of the share holding the digest of the shared secret
+const DIGEST_INDEX: u8 = 254;
+/// The length of the digest of the shared secret in bytes
+const DIGEST_LENGTH: usize = 4;
+/// The index of the share holding the shared secret
+const SECRET_INDEX: u8 = 255;
+
+/// Represents the member threshold and member count of a group of SLIP-39 shares.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Slip39Group {
+    /// The number of member shares required to reconstruct the group share
+    pub threshold: u8,
+    /// The number of member shares of the group
+    pub count: u8,
+}
+
+impl Slip39Group {
+    /// Returns a group with the given member threshold and member count.
+    pub fn new(threshold: u8, count: u8) -> Self {
+        Self { threshold, count }
+    }
+}
+
+impl FromStr for Slip39Group {
+    type Err = Slip39Error;
+
+    /// Returns the group for the given string of the form `<threshold>of<count>`, e.g. `2of3`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let values = s.split("of").map(u8::from_str).collect::<Vec<_>>();
+        match values.as_slice() {
+            [Ok(threshold), Ok(count)] => Ok(Self::new(*threshold, *count)),
+            _ => Err(Slip39Error::InvalidGroup(s.into())),
+        }
+    }
+}
+
+impl fmt::Display for Slip39Group {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}of{}", self.threshold, self.count)
+    }
+}
+
+/// Represents a SLIP-39 share of a master secret
+#[derive(Clone, PartialEq, Eq)]
+pub struct Slip39Share {
+    /// The random identifier shared by all shares of a master secret
+    pub identifier: u16,
+    /// If true, the identifier is omitted from the encryption salt of the master secret
+    pub extendable: bool,
+    /// The exponent of the number of PBKDF2 iterations of the master secret encryption
+    pub iteration_exponent: u8,
+    /// The index of the group of the share
+    pub group_index: u8,
+    /// The number of groups required to reconstruct the master secret
+    pub group_threshold: u8,
+    /// The number of groups
+    pub group_count: u8,
+    /// The index of the share in its group
+    pub member_index: u8,
+    /// The number of member shares required to reconstruct the group share
+    pub member_threshold: u8,
+    /// The share value
+    value: Vec<u8>,
+}
+
+impl Slip39Share {
+    /// Returns the shares of each group for the given master secret, encrypted with the given passphrase.
+    ///
+    /// The master secret is reconstructed from the member threshold of shares of
+    /// each of the group threshold of groups.
+    pub fn split<R: Rng>(
+        rng: &mut R,
+        master_secret: &[u8],
+        passphrase: &str,
+        group_threshold: u8,
+        groups: &[Slip39Group],
+        iteration_exponent: u8,
+        extendable: bool,
+    ) -> Result<Vec<Vec<Self>>, Slip39Error> {
+        if master_secret.len() < MIN_SECRET_LENGTH || !master_secret.len().is_multiple_of(2) {
+            return Err(Slip39Error::InvalidSecretLength(master_secret.len()));
+        }
+        if iteration_exponent > MAX_ITERATION_EXPONENT {
+            return Err(Slip39Error::InvalidIterationExponent(iteration_exponent));
+        }
+        if groups.is_empty() || groups.len() > MAX_SHARE_COUNT as usize {
+            return Err(Slip39Error::InvalidGroupCount(groups.len()));
+        }
+        if group_threshold == 0 || group_threshold as usize > groups.len() {
+            return Err(Slip39Error::InvalidGroupThreshold(group_threshold, groups.len()));
+        }
+        for group in groups {
+            // A member threshold of 1 with more than one member share is disallowed in favor of 1-of-1 groups.
+            if group.threshold == 0
+                || group.threshold > group.count
+                || group.count > MAX_SHARE_COUNT
+                || (group.threshold == 1 && group.count > 1)
+            {
+                return Err(Slip39Error::InvalidMemberThreshold(group.threshold, group.count));
+            }
+        }
+        validate_passphrase(passphrase)?;
+
+        let identifier = rng.gen::<u16>() & 0x7FFF;
+        let mut encrypted_master_secret = feistel(
+            master_secret,
+            passphrase,
+            iteration_exponent,
+            identifier,
+            extendable,
+            0..ROUND_COUNT,
+        );
+
+        let mut group_shares = split_secret(rng, group_threshold, groups.len() as u8, &encrypted_master_secret)?;
+        encrypted_master_secret.zeroize();
+
+        let mut shares = Vec::with_capacity(groups.len());
+        for ((group_index, group_share), group) in group_shares.iter().zip(groups) {
+            let mut member_shares = split_secret(rng, group.threshold, group.count, group_share)?;
+            shares.push(
+                member_shares
+                    .iter()
+                    .map(|(member_index, value)| Self {
+                        identifier,
+                        extendable,
+                        iteration_exponent,
+                        group_index: *group_index,
+                        group_threshold,
+                        group_count: groups.len() as u8,
+                        member_index: *member_index,
+                        member_threshold: group.threshold,
+                        value: value.clone(),
+                    })
+                    .collect(),
+            );
+            member_shares.iter_mut().for_each(|(_, value)| value.zeroize());
+        }
+        group_shares.iter_mut().for_each(|(_, value)| value.zeroize());
+
+        Ok(shares)
+    }
+
+    /// Returns the master secret reconstructed from the given shares, decrypted with the given passphrase.
+    pub fn combine(shares: &[Self], passphrase: &str) -> Result<Vec<u8>, Slip39Error> {
+        let first = shares.first().ok_or(Slip39Error::MissingShares)?;
+        if shares.iter().any(|share| {
+            share.identifier != first.identifier
+                || share.extendable != first.extendable
+                || share.iteration_exponent != first.iteration_exponent
+                || share.group_threshold != first.group_threshold
+                || share.group_count != first.group_count
+                || share.value.len() != first.value.len()
+        }) {
+            return Err(Slip39Error::MismatchedShares);
+        }
+        validate_passphrase(passphrase)?;
+
+        // Collects the member threshold and member shares of each group.
+        let mut groups: Vec<(u8, u8, MemberShares)> = vec![];
+        for share in shares {
+            match groups.iter_mut().find(|(index, _, _)| *index == share.group_index) {
+                Some((_, threshold, members)) => {
+                    if *threshold != share.member_threshold {
+                        return Err(Slip39Error::MismatchedShares);
+                    }
+                    match members.iter().find(|(index, _)| *index == share.member_index) {
+                        Some((_, value)) if *value == share.value => continue,
+                        Some(_) => return Err(Slip39Error::DuplicateMemberIndex(share.member_index)),
+                        None => members.push((share.member_index, share.value.clone())),
+                    }
+                }
+                None => groups.push((
+                    share.group_index,
+                    share.member_threshold,
+                    vec![(share.member_index, share.value.clone())],
+                )),
+            }
+        }
+
+        let mut group_shares = vec![];
+        for (group_index, threshold, members) in groups.iter() {
+            if group_shares.len() < first.group_threshold as usize && members.len() >= *threshold as usize {
+                let group_share = recover_secret(*threshold, &members[..*threshold as usize])?;
+                group_shares.push((*group_index, group_share));
+            }
+        }
+        groups
+            .iter_mut()
+            .for_each(|(_, _, members)| members.iter_mut().for_each(|(_, value)| value.zeroize()));
+
+        if group_shares.len() < first.group_threshold as usize {
+            return Err(Slip39Error::InsufficientGroups(
+                first.group_threshold,
+                group_shares.len(),
+            ));
+        }
+
+        let mut encrypted_master_secret = recover_secret(first.group_threshold, &group_shares)?;
+        group_shares.iter_mut().for_each(|(_, value)| value.zeroize());
+
+        let master_secret = feistel(
+            &encrypted_master_secret,
+            passphrase,
+            first.iteration_exponent,
+            first.identifier,
+            first.extendable,
+            (0..ROUND_COUNT).rev(),
+        );
+        encrypted_master_secret.zeroize();
+
+        Ok(master_secret)
+    }
+
+    /// Returns the share for the given phrase.
+    pub fn from_phrase(phrase: &str) -> Result<Self, Slip39Error> {
+        let wordlist = ENGLISH.lines().collect::<Vec<&str>>();
+        let indices = phrase
+            .split_whitespace()
+            .map(|word| match wordlist.binary_search(&word) {
+                Ok(index) => Ok(index as u16),
+                Err(_) => Err(WordlistError::InvalidWord(word.into())),
+            })
+            .collect::<Result<Vec<u16>, WordlistError>>()?;
+
+        // The share value is padded with zeros to a multiple of 10 bits, and at most 8 bits.
+        let min_value_words = (MIN_SECRET_LENGTH * 8).div_ceil(10);
+        if indices.len() < METADATA_WORDS + min_value_words + CHECKSUM_WORDS
+            || ((indices.len() - METADATA_WORDS - CHECKSUM_WORDS) * 10) % 16 > 8
+        {
+            return Err(Slip39Error::InvalidShareLength(indices.len()));
+        }
+
+        let identifier_exponent = (indices[0] as u32) << 10 | indices[1] as u32;
+        let extendable = (identifier_exponent >> 4) & 1 == 1;
+        let customization = match extendable {
+            true => CUSTOMIZATION_STRING_EXTENDABLE,
+            false => CUSTOMIZATION_STRING,
+        };
+        if rs1024_polymod(customization, &indices) != 1 {
+            return Err(Slip39Error::InvalidChecksum);
+        }
+
+        let parameters = (indices[2] as u32) << 10 | indices[3] as u32;
+        let group_threshold = ((parameters >> 12) & 0xF) as u8 + 1;
+        let group_count = ((parameters >> 8) & 0xF) as u8 + 1;
+        if group_threshold > group_count {
+            return Err(Slip39Error::InvalidGroupThreshold(
+                group_threshold,
+                group_count as usize,
+            ));
+        }
+
+        let value = from_indices(&indices[METADATA_WORDS..indices.len() - CHECKSUM_WORDS])?;
+        if value.len() < MIN_SECRET_LENGTH || value.len() % 2 != 0 {
+            return Err(Slip39Error::InvalidSecretLength(value.len()));
+        }
+
+        Ok(Self {
+            identifier: (identifier_exponent >> 5) as u16,
+            extendable,
+            iteration_exponent: (identifier_exponent & 0xF) as u8,
+            group_index: ((parameters >> 16) & 0xF) as u8,
+            group_threshold,
+            group_count,
+            member_index: ((parameters >> 4) & 0xF) as u8,
+            member_threshold: (parameters & 0xF) as u8 + 1,
+            value,
+        })
+    }
+
+    /// Returns the phrase of the corresponding share.
+    pub fn to_phrase(&self) -> String {
+        let identifier_exponent =
+            (self.identifier as u32) << 5 | (self.extendable as u32) << 4 | self.iteration_exponent as u32;
+        let parameters = (self.group_index as u32) << 16
+            | (self.group_threshold as u32 - 1) << 12
+            | (self.group_count as u32 - 1) << 8
+            | (self.member_index as u32) << 4
+            | (self.member_threshold as u32 - 1);
+
+        let mut indices = vec![
+            (identifier_exponent >> 10) as u16,
+            (identifier_exponent & 0x3FF) as u16,
+            (parameters >> 10) as u16,
+            (parameters & 0x3FF) as u16,
+        ];
+        indices.extend(to_indices(&self.value));
+
+        let customization = match self.extendable {
+            true => CUSTOMIZATION_STRING_EXTENDABLE,
+            false => CUSTOMIZATION_STRING,
+        };
+        indices.extend(&[0; CHECKSUM_WORDS]);
+        let checksum = rs1024_polymod(customization, &indices) ^ 1;
+        let length = indices.len();
+        indices[length - CHECKSUM_WORDS..]
+            .iter_mut()
+            .enumerate()
+            .for_each(|(i, index)| *index = ((checksum >> (10 * (CHECKSUM_WORDS - 1 - i))) & 0x3FF) as u16);
+
+        let wordlist = ENGLISH.lines().collect::<Vec<&str>>();
+        let phrase = indices
+            .iter()
+            .map(|index| wordlist[*index as usize])
+            .collect::<Vec<&str>>();
+        indices.zeroize();
+
+        phrase.join(" ")
+    }
+}
+
+impl FromStr for Slip39Share {
+    type Err = Slip39Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_phrase(s)
+    }
+}
+
+impl fmt::Display for Slip39Share {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_phrase())
+    }
+}
+
+impl fmt::Debug for Slip39Share {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Slip39Share")
+            .field("identifier", &self.identifier)
+            .field("group_index", &self.group_index)
+            .field("member_index", &self.member_index)
+            .field("value", &Redacted)
+            .finish()
+    }
+}
+
+impl ExposeSecret for Slip39Share {
+    fn fmt_secret(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Slip39Share")
+            .field("identifier", &self.identifier)
+            .field("extendable", &self.extendable)
+            .field("iteration_exponent", &self.iteration_exponent)
+            .field("group_index", &self.group_index)
+            .field("group_threshold", &self.group_threshold)
+            .field("group_count", &self.group_count)
+            .field("member_index", &self.member_index)
+            .field("member_threshold", &self.member_threshold)
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+impl Zeroize for Slip39Share {
+    fn zeroize(&mut self) {
+        self.value.zeroize();
+    }
+}
+
+impl Drop for Slip39Share {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+/// Ensures the passphrase consists of printable ASCII characters.
+fn validate_passphrase(passphrase: &str) -> Result<(), Slip39Error> {
+    match passphrase.bytes().all(|byte| (32..=126).contains(&byte)) {
+        true => Ok(()),
+        false => Err(Slip39Error::InvalidPassphrase),
+    }
+}
+
+/// Returns the value after applying the Feistel rounds in the given order, which
+/// encrypts the value in ascending order and decrypts the value in descending order.
+fn feistel(
+    value: &[u8],
+    passphrase: &str,
+    iteration_exponent: u8,
+    identifier: u16,
+    extendable: bool,
+    rounds: impl Iterator<Item = u8>,
+) -> Vec<u8> {
+    let half = value.len() / 2;
+    let mut left = value[..half].to_vec();
+    let mut right = value[half..].to_vec();
+
+    let salt_prefix = match extendable {
+        true => vec![],
+        false => [CUSTOMIZATION_STRING, &identifier.to_be_bytes()].concat(),
+    };
+
+    for round in rounds {
+        let mut password = vec![round];
+        password.extend(passphrase.as_bytes());
+        let salt = [&salt_prefix[..], &right[..]].concat();
+
+        let mut output = vec![0u8; half];
+        pbkdf2::<HmacSha256>(
+            &password,
+            &salt,
+            BASE_ITERATION_COUNT << iteration_exponent,
+            &mut output,
+        );
+        left.iter_mut()
+            .zip(output.iter())
+            .for_each(|(left, output)| *left ^= output);
+        core::mem::swap(&mut left, &mut right);
+
+        password.zeroize();
+        output.zeroize();
+    }
+
+    right.extend(&left);
+    left.zeroize();
+    right
+}
+
+/// Returns the given number of shares of the secret, of which the threshold number reconstructs the secret.
+fn split_secret<R: Rng>(
+    rng: &mut R,
+    threshold: u8,
+    count: u8,
+    secret: &[u8],
+) -> Result<Vec<(u8, Vec<u8>)>, Slip39Error> {
+    if threshold == 1 {
+        return Ok((0..count).map(|index| (index, secret.to_vec())).collect());
+    }
+
+    let random_count = threshold - 2;
+    let mut shares = (0..random_count)
+        .map(|index| (index, (0..secret.len()).map(|_| rng.gen()).collect()))
+        .collect::<Vec<(u8, Vec<u8>)>>();
+
+    let random_part = (0..secret.len() - DIGEST_LENGTH)
+        .map(|_| rng.gen())
+        .collect::<Vec<u8>>();
+    let mut digest = create_digest(&random_part, secret)?;
+    digest.extend(random_part);
+
+    let mut base_shares = shares.clone();
+    base_shares.push((DIGEST_INDEX, digest));
+    base_shares.push((SECRET_INDEX, secret.to_vec()));
+
+    for index in random_count..count {
+        shares.push((index, interpolate(&base_shares, index)));
+    }
+    base_shares.iter_mut().for_each(|(_, value)| value.zeroize());
+
+    Ok(shares)
+}
+
+/// Returns the secret reconstructed from the threshold number of given shares.
+fn recover_secret(threshold: u8, shares: &[(u8, Vec<u8>)]) -> Result<Vec<u8>, Slip39Error> {
+    if threshold == 1 {
+        return Ok(shares[0].1.clone());
+    }
+
+    let secret = interpolate(shares, SECRET_INDEX);
+    let mut digest_share = interpolate(shares, DIGEST_INDEX);
+    let digest = create_digest(&digest_share[DIGEST_LENGTH..], &secret)?;
+    let is_valid = digest[..] == digest_share[..DIGEST_LENGTH];
+    digest_share.zeroize();
+
+    match is_valid {
+        true => Ok(secret),
+        false => Err(Slip39Error::InvalidDigest),
+    }
+}
+
+/// Returns the digest of the secret keyed with the random bytes.
+fn create_digest(random: &[u8], secret: &[u8]) -> Result<Vec<u8>, Slip39Error> {
+    let mut mac = HmacSha256::new_varkey(random)?;
+    mac.input(secret);
+    Ok(mac.result().code()[..DIGEST_LENGTH].to_vec())
+}
+
+/// Returns the RS1024 checksum polynomial of the customization string and the word indices.
+fn rs1024_polymod(customization: &[u8], indices: &[u16]) -> u32 {
+    const GENERATOR: [u32; 10] = [
+        0xE0E040, 0x1C1C080, 0x3838100, 0x7070200, 0xE0E0009, 0x1C0C2412, 0x38086C24, 0x3090FC48, 0x21B1F890, 0x3F3F120,
+    ];
+
+    let values = customization
+        .iter()
+        .map(|byte| *byte as u32)
+        .chain(indices.iter().map(|index| *index as u32));
+    values.fold(1, |checksum, value| {
+        let top = checksum >> 20;
+        (0..10)
+            .filter(|i| (top >> i) & 1 == 1)
+            .fold(((checksum & 0xFFFFF) << 10) ^ value, |checksum, i| {
+                checksum ^ GENERATOR[i]
+            })
+    })
+}
+
+/// Returns the 10-bit word indices of the bytes, left padded with zeros.
+fn to_indices(bytes: &[u8]) -> Vec<u16> {
+    let bit_length = bytes.len() * 8;
+    let padding = (10 - bit_length % 10) % 10;
+
+    let mut indices = Vec::with_capacity((bit_length + padding) / 10);
+    let (mut accumulator, mut accumulator_bits) = (0u32, padding);
+    for byte in bytes {
+        accumulator = accumulator << 8 | *byte as u32;
+        accumulator_bits += 8;
+        if accumulator_bits >= 10 {
+            accumulator_bits -= 10;
+            indices.push((accumulator >> accumulator_bits) as u16 & 0x3FF);
+            accumulator &= (1 << accumulator_bits) - 1;
+        }
+    }
+    indices
+}
+
+/// Returns the bytes of the 10-bit word indices, ensuring the left padding is zero.
+fn from_indices(indices: &[u16]) -> Result<Vec<u8>, Slip39Error> {
+    let bit_length = indices.len() * 10;
+    let padding = bit_length % 16;
+
+    let mut bytes = Vec::with_capacity((bit_length - padding) / 8);
+    let (mut accumulator, mut accumulator_bits) = (0u32, 0usize);
+    let mut skipped = false;
+    for index in indices {
+        accumulator = accumulator << 10 | *index as u32;
+        accumulator_bits += 10;
+        if !skipped {
+            if accumulator >> (accumulator_bits - padding) != 0 {
+                return Err(Slip39Error::InvalidPadding);
+            }
+            accumulator_bits -= padding;
+            accumulator &= (1 << accumulator_bits) - 1;
+            skipped = true;
+        }
+        while accumulator_bits >= 8 {
+            accumulator_bits -= 8;
+            bytes.push((accumulator >> accumulator_bits) as u8);
+            accumulator &= (1 << accumulator_bits) - 1;
+        }
+    }
+    Ok(bytes)
+}
+
+#[derive(Debug, Fail)]
+pub enum Slip39Error {
+    #[fail(display = "{}", _0)]
+    Crate(#[cause] CrateError),
+
+    #[fail(display = "duplicate member index: {}", _0)]
+    DuplicateMemberIndex(u8),
+
+    #[fail(display = "insufficient groups: {{ expected: {}, found: {} }}", _0, _1)]
+    InsufficientGroups(u8, usize),
+
+    #[fail(display = "invalid share checksum")]
+    InvalidChecksum,
+
+    #[fail(display = "invalid share digest")]
+    InvalidDigest,
+
+    #[fail(display = "invalid group: {}", _0)]
+    InvalidGroup(String),
+
+    #[fail(display = "invalid group count: {}", _0)]
+    InvalidGroupCount(usize),
+
+    #[fail(display = "invalid group threshold: {{ threshold: {}, count: {} }}", _0, _1)]
+    InvalidGroupThreshold(u8, usize),
+
+    #[fail(display = "invalid iteration exponent: {}", _0)]
+    InvalidIterationExponent(u8),
+
+    #[fail(display = "invalid member threshold: {{ threshold: {}, count: {} }}", _0, _1)]
+    InvalidMemberThreshold(u8, u8),
+
+    #[fail(display = "invalid share padding")]
+    InvalidPadding,
+
+    #[fail(display = "invalid passphrase: expected printable ASCII characters")]
+    InvalidPassphrase,
+
+    #[fail(display = "invalid secret length: {}", _0)]
+    InvalidSecretLength(usize),
+
+    #[fail(display = "invalid share word count: {}", _0)]
+    InvalidShareLength(usize),
+
+    #[fail(display = "mismatched share parameters")]
+    MismatchedShares,
+
+    #[fail(display = "missing shares")]
+    MissingShares,
+
+    #[fail(display = "{}", _0)]
+    WordlistError(#[cause] WordlistError),
+}
+
+impl From<crypto_mac::InvalidKeyLength> for Slip39Error {
+    fn from(error: crypto_mac::InvalidKeyLength) -> Self {
+        Slip39Error::Crate(CrateError::from(error))
+    }
+}
+
+impl From<WordlistError> for Slip39Error {
+    fn from(error: WordlistError) -> Self {
+        Slip39Error::WordlistError(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    const PASSPHRASE: &str = "TREZOR";
+
+    /// Test vectors from https://github.com/trezor/python-shamir-mnemonic/blob/master/vectors.json
+    // (shares, master secret)
+    const VECTORS: [(&[&str], &str); 10] = [
+        // 1. Valid mnemonic without sharing (128 bits)
+        (
+            &["duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision keyboard"],
+            "bb54aac4b89dc868ba37d9cc21b2cece",
+        ),
+        // 4. Basic sharing 2-of-3 (128 bits)
+        (
+            &[
+                "shadow pistol academic always adequate wildlife fancy gross oasis cylinder mustang wrist rescue view short owner flip making coding armed",
+                "shadow pistol academic acid actress prayer class unknown daughter sweater depict flip twice unkind craft early superior advocate guest smoking",
+            ],
+            "b43ceb7e57a0ea8766221624d01b0864",
+        ),
+        // 17. Threshold number of groups and members in each group (128 bits, case 1)
+        (
+            &[
+                "eraser senior decision roster beard treat identify grumpy salt index fake aviation theater cubic bike cause research dragon emphasis counter",
+                "eraser senior ceramic snake clay various huge numb argue hesitate auction category timber browser greatest hanger petition script leaf pickup",
+                "eraser senior ceramic shaft dynamic become junior wrist silver peasant force math alto coal amazing segment yelp velvet image paces",
+                "eraser senior ceramic round column hawk trust auction smug shame alive greatest sheriff living perfect corner chest sled fumes adequate",
+                "eraser senior decision smug corner ruin rescue cubic angel tackle skin skunk program roster trash rumor slush angel flea amazing",
+            ],
+            "7c3397a292a5941682d7a4ae2d898d11",
+        ),
+        // 18. Threshold number of groups and members in each group (128 bits, case 2)
+        (
+            &[
+                "eraser senior decision smug corner ruin rescue cubic angel tackle skin skunk program roster trash rumor slush angel flea amazing",
+                "eraser senior beard romp adorn nuclear spill corner cradle style ancient family general leader ambition exchange unusual garlic promise voice",
+                "eraser senior decision scared cargo theory device idea deliver modify curly include pancake both news skin realize vitamins away join",
+            ],
+            "7c3397a292a5941682d7a4ae2d898d11",
+        ),
+        // 19. Threshold number of groups and members in each group (128 bits, case 3)
+        (
+            &[
+                "eraser senior beard romp adorn nuclear spill corner cradle style ancient family general leader ambition exchange unusual garlic promise voice",
+                "eraser senior acrobat romp bishop medical gesture pumps secret alive ultimate quarter priest subject class dictate spew material endless market",
+            ],
+            "7c3397a292a5941682d7a4ae2d898d11",
+        ),
+        // 20. Valid mnemonic without sharing (256 bits)
+        (
+            &["theory painting academic academic armed sweater year military elder discuss acne wildlife boring employer fused large satoshi bundle carbon diagnose anatomy hamster leaves tracks paces beyond phantom capital marvel lips brave detect luck"],
+            "989baf9dcaad5b10ca33dfd8cc75e42477025dce88ae83e75a230086a0e00e92",
+        ),
+        // 23. Basic sharing 2-of-3 (256 bits)
+        (
+            &[
+                "humidity disease academic always aluminum jewelry energy woman receiver strategy amuse duckling lying evidence network walnut tactics forget hairy rebound impulse brother survive clothes stadium mailman rival ocean reward venture always armed unwrap",
+                "humidity disease academic agency actress jacket gross physics cylinder solution fake mortgage benefit public busy prepare sharp friar change work slow purchase ruler again tricycle involve viral wireless mixture anatomy desert cargo upgrade",
+            ],
+            "c938b319067687e990e05e0da0ecce1278f75ff58d9853f19dcaeed5de104aae",
+        ),
+        // 36. Threshold number of groups and members in each group (256 bits, case 1)
+        (
+            &[
+                "wildlife deal ceramic round aluminum pitch goat racism employer miracle percent math decision episode dramatic editor lily prospect program scene rebuild display sympathy have single mustang junction relate often chemical society wits estate",
+                "wildlife deal decision scared acne fatal snake paces obtain election dryer dominant romp tactics railroad marvel trust helpful flip peanut theory theater photo luck install entrance taxi step oven network dictate intimate listen",
+                "wildlife deal ceramic scatter argue equip vampire together ruin reject literary rival distance aquatic agency teammate rebound false argue miracle stay again blessing peaceful unknown cover beard acid island language debris industry idle",
+                "wildlife deal ceramic snake agree voter main lecture axis kitchen physics arcade velvet spine idea scroll promise platform firm sharp patrol divorce ancestor fantasy forbid goat ajar believe swimming cowboy symbolic plastic spelling",
+                "wildlife deal decision shadow analysis adjust bulb skunk muscle mandate obesity total guitar coal gravity carve slim jacket ruin rebuild ancestor numerous hour mortgage require herd maiden public ceiling pecan pickup shadow club",
+            ],
+            "5385577c8cfc6c1a8aa0f7f10ecde0a3318493262591e78b8c14c6686167123b",
+        ),
+        // 37. Threshold number of groups and members in each group (256 bits, case 2)
+        (
+            &[
+                "wildlife deal decision scared acne fatal snake paces obtain election dryer dominant romp tactics railroad marvel trust helpful flip peanut theory theater photo luck install entrance taxi step oven network dictate intimate listen",
+                "wildlife deal beard romp alcohol space mild usual clothes union nuclear testify course research heat listen task location thank hospital slice smell failure fawn helpful priest ambition average recover lecture process dough stadium",
+                "wildlife deal decision smug ancestor genuine move huge cubic strategy smell game costume extend swimming false desire fake traffic vegan senior twice timber submit leader payroll fraction apart exact forward pulse tidy install",
+            ],
+            "5385577c8cfc6c1a8aa0f7f10ecde0a3318493262591e78b8c14c6686167123b",
+        ),
+        // 38. Threshold number of groups and members in each group (256 bits, case 3)
+        (
+            &[
+                "wildlife deal beard romp alcohol space mild usual clothes union nuclear testify course research heat listen task location thank hospital slice smell failure fawn helpful priest ambition average recover lecture process dough stadium",
+                "wildlife deal acrobat romp anxiety axis starting require metric flexible geology game drove editor edge screw helpful have huge holy making pitch unknown carve holiday numb glasses survive already tenant adapt goat fangs",
+            ],
+            "5385577c8cfc6c1a8aa0f7f10ecde0a3318493262591e78b8c14c6686167123b",
+        ),
+    ];
+
+    /// Invalid test vectors from https://github.com/trezor/python-shamir-mnemonic/blob/master/vectors.json
+    // (shares, error)
+    const INVALID_VECTORS: [(&[&str], &str); 30] = [
+        // 2. Mnemonic with invalid checksum (128 bits)
+        (
+            &["duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision kidney"],
+            "invalid share checksum",
+        ),
+        // 3. Mnemonic with invalid padding (128 bits)
+        (
+            &["duckling enlarge academic academic email result length solution fridge kidney coal piece deal husband erode duke ajar music cargo fitness"],
+            "invalid share padding",
+        ),
+        // 5. Basic sharing 2-of-3 (128 bits)
+        (
+            &["shadow pistol academic always adequate wildlife fancy gross oasis cylinder mustang wrist rescue view short owner flip making coding armed"],
+            "insufficient groups: { expected: 1, found: 0 }",
+        ),
+        // 6. Mnemonics with different identifiers (128 bits)
+        (
+            &[
+                "adequate smoking academic acid debut wine petition glen cluster slow rhyme slow simple epidemic rumor junk tracks treat olympic tolerate",
+                "adequate stay academic agency agency formal party ting frequent learn upstairs remember smear leaf damage anatomy ladle market hush corner",
+            ],
+            "mismatched share parameters",
+        ),
+        // 7. Mnemonics with different iteration exponents (128 bits)
+        (
+            &[
+                "peasant leaves academic acid desert exact olympic math alive axle trial tackle drug deny decent smear dominant desert bucket remind",
+                "peasant leader academic agency cultural blessing percent network envelope medal junk primary human pumps jacket fragment payroll ticket evoke voice",
+            ],
+            "mismatched share parameters",
+        ),
+        // 8. Mnemonics with mismatching group thresholds (128 bits)
+        (
+            &[
+                "liberty category beard echo animal fawn temple briefing math username various wolf aviation fancy visual holy thunder yelp helpful payment",
+                "liberty category beard email beyond should fancy romp founder easel pink holy hairy romp loyalty material victim owner toxic custody",
+                "liberty category academic easy being hazard crush diminish oral lizard reaction cluster force dilemma deploy force club veteran expect photo",
+            ],
+            "mismatched share parameters",
+        ),
+        // 9. Mnemonics with mismatching group counts (128 bits)
+        (
+            &[
+                "average senior academic leaf broken teacher expect surface hour capture obesity desire negative dynamic dominant pistol mineral mailman iris aide",
+                "average senior academic agency curious pants blimp spew clothes slice script dress wrap firm shaft regular slavery negative theater roster",
+            ],
+            "mismatched share parameters",
+        ),
+        // 10. Mnemonics with greater group threshold than group counts (128 bits)
+        (
+            &[
+                "music husband acrobat acid artist finance center either graduate swimming object bike medical clothes station aspect spider maiden bulb welcome",
+                "music husband acrobat agency advance hunting bike corner density careful material civil evil tactics remind hawk discuss hobo voice rainbow",
+                "music husband beard academic black tricycle clock mayor estimate level photo episode exclude ecology papa source amazing salt verify divorce",
+            ],
+            "invalid group threshold: { threshold: 2, count: 1 }",
+        ),
+        // 11. Mnemonics with duplicate member indices (128 bits)
+        (
+            &[
+                "device stay academic always dive coal antenna adult black exceed stadium herald advance soldier busy dryer daughter evaluate minister laser",
+                "device stay academic always dwarf afraid robin gravity crunch adjust soul branch walnut coastal dream costume scholar mortgage mountain pumps",
+            ],
+            "duplicate member index: 2",
+        ),
+        // 12. Mnemonics with mismatching member thresholds (128 bits)
+        (
+            &[
+                "hour painting academic academic device formal evoke guitar random modern justice filter withdraw trouble identify mailman insect general cover oven",
+                "hour painting academic agency artist again daisy capital beaver fiber much enjoy suitable symbolic identify photo editor romp float echo",
+            ],
+            "mismatched share parameters",
+        ),
+        // 13. Mnemonics giving an invalid digest (128 bits)
+        (
+            &[
+                "guilt walnut academic acid deliver remove equip listen vampire tactics nylon rhythm failure husband fatigue alive blind enemy teaspoon rebound",
+                "guilt walnut academic agency brave hamster hobo declare herd taste alpha slim criminal mild arcade formal romp branch pink ambition",
+            ],
+            "invalid share digest",
+        ),
+        // 14. Insufficient number of groups (128 bits, case 1)
+        (
+            &["eraser senior beard romp adorn nuclear spill corner cradle style ancient family general leader ambition exchange unusual garlic promise voice"],
+            "insufficient groups: { expected: 2, found: 1 }",
+        ),
+        // 15. Insufficient number of groups (128 bits, case 2)
+        (
+            &[
+                "eraser senior decision scared cargo theory device idea deliver modify curly include pancake both news skin realize vitamins away join",
+                "eraser senior decision roster beard treat identify grumpy salt index fake aviation theater cubic bike cause research dragon emphasis counter",
+            ],
+            "insufficient groups: { expected: 2, found: 1 }",
+        ),
+        // 16. Threshold number of groups, but insufficient number of members in one group (128 bits)
+        (
+            &[
+                "eraser senior decision shadow artist work morning estate greatest pipeline plan ting petition forget hormone flexible general goat admit surface",
+                "eraser senior beard romp adorn nuclear spill corner cradle style ancient family general leader ambition exchange unusual garlic promise voice",
+            ],
+            "insufficient groups: { expected: 2, found: 1 }",
+        ),
+        // 21. Mnemonic with invalid checksum (256 bits)
+        (
+            &["theory painting academic academic armed sweater year military elder discuss acne wildlife boring employer fused large satoshi bundle carbon diagnose anatomy hamster leaves tracks paces beyond phantom capital marvel lips brave detect lunar"],
+            "invalid share checksum",
+        ),
+        // 22. Mnemonic with invalid padding (256 bits)
+        (
+            &["theory painting academic academic campus sweater year military elder discuss acne wildlife boring employer fused large satoshi bundle carbon diagnose anatomy hamster leaves tracks paces beyond phantom capital marvel lips facility obtain sister"],
+            "invalid share padding",
+        ),
+        // 24. Basic sharing 2-of-3 (256 bits)
+        (
+            &["humidity disease academic always aluminum jewelry energy woman receiver strategy amuse duckling lying evidence network walnut tactics forget hairy rebound impulse brother survive clothes stadium mailman rival ocean reward venture always armed unwrap"],
+            "insufficient groups: { expected: 1, found: 0 }",
+        ),
+        // 25. Mnemonics with different identifiers (256 bits)
+        (
+            &[
+                "smear husband academic acid deadline scene venture distance dive overall parking bracelet elevator justice echo burning oven chest duke nylon",
+                "smear isolate academic agency alpha mandate decorate burden recover guard exercise fatal force syndrome fumes thank guest drift dramatic mule",
+            ],
+            "mismatched share parameters",
+        ),
+        // 26. Mnemonics with different iteration exponents (256 bits)
+        (
+            &[
+                "finger trash academic acid average priority dish revenue academic hospital spirit western ocean fact calcium syndrome greatest plan losing dictate",
+                "finger traffic academic agency building lilac deny paces subject threaten diploma eclipse window unknown health slim piece dragon focus smirk",
+            ],
+            "mismatched share parameters",
+        ),
+        // 27. Mnemonics with mismatching group thresholds (256 bits)
+        (
+            &[
+                "flavor pink beard echo depart forbid retreat become frost helpful juice unwrap reunion credit math burning spine black capital lair",
+                "flavor pink beard email diet teaspoon freshman identify document rebound cricket prune headset loyalty smell emission skin often square rebound",
+                "flavor pink academic easy credit cage raisin crazy closet lobe mobile become drink human tactics valuable hand capture sympathy finger",
+            ],
+            "mismatched share parameters",
+        ),
+        // 28. Mnemonics with mismatching group counts (256 bits)
+        (
+            &[
+                "column flea academic leaf debut extra surface slow timber husky lawsuit game behavior husky swimming already paper episode tricycle scroll",
+                "column flea academic agency blessing garbage party software stadium verify silent umbrella therapy decorate chemical erode dramatic eclipse replace apart",
+            ],
+            "mismatched share parameters",
+        ),
+        // 29. Mnemonics with greater group threshold than group counts (256 bits)
+        (
+            &[
+                "smirk pink acrobat acid auction wireless impulse spine sprinkle fortune clogs elbow guest hush loyalty crush dictate tracks airport talent",
+                "smirk pink acrobat agency dwarf emperor ajar organize legs slice harvest plastic dynamic style mobile float bulb health coding credit",
+                "smirk pink beard academic alto strategy carve shame language rapids ruin smart location spray training acquire eraser endorse submit peaceful",
+            ],
+            "invalid group threshold: { threshold: 2, count: 1 }",
+        ),
+        // 30. Mnemonics with duplicate member indices (256 bits)
+        (
+            &[
+                "fishing recover academic always device craft trend snapshot gums skin downtown watch device sniff hour clock public maximum garlic born",
+                "fishing recover academic always aircraft view software cradle fangs amazing package plastic evaluate intend penalty epidemic anatomy quarter cage apart",
+            ],
+            "duplicate member index: 2",
+        ),
+        // 31. Mnemonics with mismatching member thresholds (256 bits)
+        (
+            &[
+                "evoke garden academic academic answer wolf scandal modern warmth station devote emerald market physics surface formal amazing aquatic gesture medical",
+                "evoke garden academic agency deal revenue knit reunion decrease magazine flexible company goat repair alarm military facility clogs aide mandate",
+            ],
+            "mismatched share parameters",
+        ),
+        // 32. Mnemonics giving an invalid digest (256 bits)
+        (
+            &[
+                "river deal academic acid average forbid pistol peanut custody bike class aunt hairy merit valid flexible learn ajar very easel",
+                "river deal academic agency camera amuse lungs numb isolate display smear piece traffic worthy year patrol crush fact fancy emission",
+            ],
+            "invalid share digest",
+        ),
+        // 33. Insufficient number of groups (256 bits, case 1)
+        (
+            &["wildlife deal beard romp alcohol space mild usual clothes union nuclear testify course research heat listen task location thank hospital slice smell failure fawn helpful priest ambition average recover lecture process dough stadium"],
+            "insufficient groups: { expected: 2, found: 1 }",
+        ),
+        // 34. Insufficient number of groups (256 bits, case 2)
+        (
+            &[
+                "wildlife deal decision scared acne fatal snake paces obtain election dryer dominant romp tactics railroad marvel trust helpful flip peanut theory theater photo luck install entrance taxi step oven network dictate intimate listen",
+                "wildlife deal decision smug ancestor genuine move huge cubic strategy smell game costume extend swimming false desire fake traffic vegan senior twice timber submit leader payroll fraction apart exact forward pulse tidy install",
+            ],
+            "insufficient groups: { expected: 2, found: 1 }",
+        ),
+        // 35. Threshold number of groups, but insufficient number of members in one group (256 bits)
+        (
+            &[
+                "wildlife deal decision shadow analysis adjust bulb skunk muscle mandate obesity total guitar coal gravity carve slim jacket ruin rebuild ancestor numerous hour mortgage require herd maiden public ceiling pecan pickup shadow club",
+                "wildlife deal beard romp alcohol space mild usual clothes union nuclear testify course research heat listen task location thank hospital slice smell failure fawn helpful priest ambition average recover lecture process dough stadium",
+            ],
+            "insufficient groups: { expected: 2, found: 1 }",
+        ),
+        // 39. Mnemonic with insufficient length
+        (
+            &["junk necklace academic academic acne isolate join hesitate lunar roster dough calcium chemical ladybug amount mobile glasses verify cylinder"],
+            "invalid share word count: 19",
+        ),
+        // 40. Mnemonic with invalid master secret length
+        (
+            &["fraction necklace academic academic award teammate mouse regular testify coding building member verdict purchase blind camera duration email prepare spirit quarter"],
+            "invalid share word count: 21",
+        ),
+    ];
+
+    fn test_combine(expected_master_secret: &str, phrases: &[&str]) {
+        let shares = phrases
+            .iter()
+            .map(|phrase| Slip39Share::from_phrase(phrase).unwrap())
+            .collect::<Vec<_>>();
+        let master_secret = Slip39Share::combine(&shares, PASSPHRASE).unwrap();
+        assert_eq!(expected_master_secret, hex::encode(master_secret));
+    }
+
+    fn test_to_phrase(phrase: &str) {
+        assert_eq!(phrase, Slip39Share::from_phrase(phrase).unwrap().to_phrase());
+    }
+
+    #[test]
+    fn combine() {
+        VECTORS
+            .iter()
+            .for_each(|(phrases, master_secret)| test_combine(master_secret, phrases));
+    }
+
+    #[test]
+    fn combine_invalid() {
+        INVALID_VECTORS.iter().for_each(|(phrases, expected_error)| {
+            let error = phrases
+                .iter()
+                .map(|phrase| Slip39Share::from_phrase(phrase))
+                .collect::<Result<Vec<_>, _>>()
+                .and_then(|shares| Slip39Share::combine(&shares, PASSPHRASE))
+                .unwrap_err();
+            assert_eq!(*expected_error, error.to_string());
+        });
+    }
+
+    #[test]
+    fn passphrase() {
+        // The master secret is decrypted with the passphrase, so another passphrase gives another master secret.
+        VECTORS.iter().for_each(|(phrases, master_secret)| {
+            let shares = phrases
+                .iter()
+                .map(|phrase| Slip39Share::from_phrase(phrase).unwrap())
+                .collect::<Vec<_>>();
+            assert_ne!(*master_secret, hex::encode(Slip39Share::combine(&shares, "").unwrap()));
+            assert!(matches!(
+                Slip39Share::combine(&shares, "TREZOR\n"),
+                Err(Slip39Error::InvalidPassphrase)
+            ));
+        });
+    }
+
+    #[test]
+    fn to_phrase() {
+        VECTORS
+            .iter()
+            .for_each(|(phrases, _)| phrases.iter().for_each(|phrase| test_to_phrase(phrase)));
+    }
+
+    #[test]
+    fn split() {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        let master_secret = hex::decode("b43ceb7e57a0ea8766221624d01b0864").unwrap();
+        let groups = [Slip39Group::new(2, 3), Slip39Group::new(1, 1), Slip39Group::new(3, 5)];
+        let shares = Slip39Share::split(rng, &master_secret, PASSPHRASE, 2, &groups, 0, true).unwrap();
+
+        assert_eq!(vec![3, 1, 5], shares.iter().map(Vec::len).collect::<Vec<_>>());
+
+        let phrases = [
+            &shares[0][2],
+            &shares[0][0],
+            &shares[2][4],
+            &shares[2][1],
+            &shares[2][3],
+        ]
+        .iter()
+        .map(|share| share.to_phrase())
+        .collect::<Vec<_>>();
+        let phrases = phrases.iter().map(String::as_str).collect::<Vec<_>>();
+        test_combine("b43ceb7e57a0ea8766221624d01b0864", &phrases);
+
+        let shares = [shares[1][0].clone(), shares[2][0].clone(), shares[2][2].clone()];
+        assert!(matches!(
+            Slip39Share::combine(&shares, PASSPHRASE),
+            Err(Slip39Error::InsufficientGroups(2, 1))
+        ));
+    }
+
+    #[test]
+    fn invalid() {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        let master_secret = [0u8; 16];
+        assert!(Slip39Share::split(rng, &master_secret[..15], "", 1, &[Slip39Group::new(1, 1)], 0, false).is_err());
+        assert!(Slip39Share::split(rng, &master_secret, "", 1, &[Slip39Group::new(1, 2)], 0, false).is_err());
+        assert!(Slip39Share::split(rng, &master_secret, "", 2, &[Slip39Group::new(2, 3)], 0, false).is_err());
+        assert!(Slip39Share::split(rng, &master_secret, "", 1, &[Slip39Group::new(4, 3)], 0, false).is_err());
+
+        // Invalid checksum
+        let phrase = "duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision kidney";
+        assert!(Slip39Share::from_phrase(phrase).is_err());
+        // Invalid word
+        let phrase = "duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision abandon";
+        assert!(Slip39Share::from_phrase(phrase).is_err());
+        // Invalid share word count
+        let phrase = "duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision";
+        assert!(Slip39Share::from_phrase(phrase).is_err());
+    }
+
+    #[test]
+    fn debug() {
+        let share = Slip39Share::from_phrase(VECTORS[0].0[0]).unwrap();
+        assert_eq!(
+            "Slip39Share { identifier: 7945, group_index: 0, member_index: 0, value: [REDACTED] }",
+            format!("{:?}", share)
+        );
+    }
+
+    #[test]
+    fn group_from_str() {
+        assert_eq!(Slip39Group::new(2, 3), Slip39Group::from_str("2of3").unwrap());
+        assert_eq!("3of5", Slip39Group::new(3, 5).to_string());
+        assert!(Slip39Group::from_str("2-3").is_err());
+    }
+}
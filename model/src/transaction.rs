@@ -1,5 +1,6 @@
 use crate::address::{Address, AddressError};
 use crate::amount::AmountError;
+use crate::error::CrateError;
 use crate::extended_private_key::ExtendedPrivateKeyError;
 use crate::format::Format;
 use crate::private_key::{PrivateKey, PrivateKeyError};
@@ -43,28 +44,40 @@ pub trait Transaction: Clone + Send + Sync + 'static {
 #[derive(Debug, Fail)]
 pub enum TransactionError {
     #[fail(display = "{}", _0)]
-    AddressError(AddressError),
+    AddressError(#[cause] AddressError),
 
     #[fail(display = "{}", _0)]
-    AmountError(AmountError),
+    AmountError(#[cause] AmountError),
 
     #[fail(display = "witnesses have a conflicting anchor")]
     ConflictingWitnessAnchors(),
 
-    #[fail(display = "{}: {}", _0, _1)]
-    Crate(&'static str, String),
+    #[fail(display = "{}", _0)]
+    Crate(#[cause] CrateError),
+
+    #[fail(display = "change amount {} is below the dust threshold of {}", _0, _1)]
+    DustChange(i64, i64),
+
+    #[fail(display = "output {} amount {} is below the dust threshold of {}", _0, _1, _2)]
+    DustOutput(usize, i64, i64),
 
     #[fail(display = "{}", _0)]
-    ExtendedPrivateKeyError(ExtendedPrivateKeyError),
+    ExtendedPrivateKeyError(#[cause] ExtendedPrivateKeyError),
 
     #[fail(display = "Failed note decryption for enc_cyphertext: {}", _0)]
     FailedNoteDecryption(String),
 
+    #[fail(display = "insufficient funds: {} available, {} required", _0, _1)]
+    InsufficientFunds(i64, i64),
+
     #[fail(display = "invalid binding signature for the transaction")]
     InvalidBindingSig(),
 
     #[fail(display = "invalid chain id {:?}", _0)]
-    InvalidChainId(u8),
+    InvalidChainId(u64),
+
+    #[fail(display = "invalid data output length {}", _0)]
+    InvalidDataLength(usize),
 
     #[fail(display = "invalid ephemeral key {}", _0)]
     InvalidEphemeralKey(String),
@@ -72,6 +85,12 @@ pub enum TransactionError {
     #[fail(display = "insufficient information to craft transaction. missing: {}", _0)]
     InvalidInputs(String),
 
+    #[fail(display = "invalid lock time {}", _0)]
+    InvalidLockTime(u32),
+
+    #[fail(display = "invalid network: expected {}, found {}", _0, _1)]
+    InvalidNetwork(String, String),
+
     #[fail(display = "invalid output address: {}", _0)]
     InvalidOutputAddress(String),
 
@@ -87,6 +106,9 @@ pub enum TransactionError {
     #[fail(display = "invalid segwit flag: {:?}", _0)]
     InvalidSegwitFlag(usize),
 
+    #[fail(display = "invalid signature for input {}", _0)]
+    InvalidSignature(usize),
+
     #[fail(display = "invalid spend description for address")]
     InvalidSpendDescription,
 
@@ -96,6 +118,9 @@ pub enum TransactionError {
     #[fail(display = "invalid transaction - either both sender and signature should be present, or neither")]
     InvalidTransactionState,
 
+    #[fail(display = "invalid transaction version {}", _0)]
+    InvalidTransactionVersion(u32),
+
     #[fail(display = "invalid variable size integer: {:?}", _0)]
     InvalidVariableSizeInteger(usize),
 
@@ -127,18 +152,30 @@ pub enum TransactionError {
     NullError(()),
 
     #[fail(display = "{}", _0)]
-    PrivateKeyError(PrivateKeyError),
+    PrivateKeyError(#[cause] PrivateKeyError),
+
+    #[fail(display = "transaction has {} trailing bytes", _0)]
+    TrailingBytes(usize),
+
+    #[fail(display = "unsupported format version {}", _0)]
+    UnsupportedFormatVersion(u32),
 
     #[fail(display = "Joinsplits are not supported")]
     UnsupportedJoinsplits,
 
     #[fail(display = "unsupported preimage operation on address format of {}", _0)]
     UnsupportedPreimage(String),
+
+    #[fail(display = "unsupported signature hash: {}", _0)]
+    UnsupportedSignatureHash(String),
+
+    #[fail(display = "unsupported transaction type {}", _0)]
+    UnsupportedTransactionType(u8),
 }
 
 impl From<crate::no_std::io::Error> for TransactionError {
     fn from(error: crate::no_std::io::Error) -> Self {
-        TransactionError::Crate("crate::no_std::io", format!("{:?}", error))
+        TransactionError::Crate(CrateError::from(error))
     }
 }
 
@@ -178,69 +215,72 @@ impl From<PrivateKeyError> for TransactionError {
     }
 }
 
+#[cfg(feature = "std")]
 impl From<base58::FromBase58Error> for TransactionError {
     fn from(error: base58::FromBase58Error) -> Self {
-        TransactionError::Crate("base58", format!("{:?}", error))
+        TransactionError::Crate(CrateError::from(error))
     }
 }
 
+#[cfg(feature = "std")]
 impl From<base58_monero::base58::Error> for TransactionError {
     fn from(error: base58_monero::base58::Error) -> Self {
-        TransactionError::Crate("base58_monero", format!("{:?}", error))
+        TransactionError::Crate(CrateError::from(error))
     }
 }
 
+#[cfg(feature = "std")]
 impl From<bech32::Error> for TransactionError {
     fn from(error: bech32::Error) -> Self {
-        TransactionError::Crate("bech32", format!("{:?}", error))
+        TransactionError::Crate(CrateError::from(error))
     }
 }
 
 impl From<core::num::ParseIntError> for TransactionError {
     fn from(error: core::num::ParseIntError) -> Self {
-        TransactionError::Crate("core::num", format!("{:?}", error))
+        TransactionError::Crate(CrateError::from(error))
     }
 }
 
 impl From<core::str::ParseBoolError> for TransactionError {
     fn from(error: core::str::ParseBoolError) -> Self {
-        TransactionError::Crate("core::str", format!("{:?}", error))
+        TransactionError::Crate(CrateError::from(error))
     }
 }
 
 #[cfg(feature = "ff")]
 impl From<ff::PrimeFieldDecodingError> for TransactionError {
     fn from(error: ff::PrimeFieldDecodingError) -> Self {
-        TransactionError::Crate("ff", format!("{:?}", error))
+        TransactionError::Crate(CrateError::from(error))
     }
 }
 
 impl From<hex::FromHexError> for TransactionError {
     fn from(error: hex::FromHexError) -> Self {
-        TransactionError::Crate("hex", format!("{:?}", error))
+        TransactionError::Crate(CrateError::from(error))
     }
 }
 
 impl From<rlp::DecoderError> for TransactionError {
     fn from(error: rlp::DecoderError) -> Self {
-        TransactionError::Crate("rlp", format!("{:?}", error))
+        TransactionError::Crate(CrateError::from(error))
     }
 }
 
 impl From<secp256k1::Error> for TransactionError {
     fn from(error: secp256k1::Error) -> Self {
-        TransactionError::Crate("libsecp256k1", format!("{:?}", error))
+        TransactionError::Crate(CrateError::from(error))
     }
 }
 
 impl From<serde_json::error::Error> for TransactionError {
     fn from(error: serde_json::error::Error) -> Self {
-        TransactionError::Crate("serde_json", format!("{:?}", error))
+        TransactionError::Crate(CrateError::from(error))
     }
 }
 
 impl From<uint::FromDecStrErr> for TransactionError {
     fn from(error: uint::FromDecStrErr) -> Self {
-        TransactionError::Crate("uint", format!("{:?}", error))
+        TransactionError::Crate(CrateError::from(error))
     }
 }
@@ -1,6 +1,8 @@
 use crate::no_std::*;
+use hmac::{Hmac, Mac};
 use ripemd160::Ripemd160;
-use sha2::{Digest, Sha256};
+use sha2::{Digest, Sha256, Sha512};
+use zeroize::Zeroize;
 
 pub fn checksum(data: &[u8]) -> Vec<u8> {
     Sha256::digest(&Sha256::digest(&data)).to_vec()
@@ -10,6 +12,96 @@ pub fn hash160(bytes: &[u8]) -> Vec<u8> {
     Ripemd160::digest(&Sha256::digest(&bytes)).to_vec()
 }
 
+/// Returns the BIP-340 tagged hash of the message, SHA256(SHA256(tag) || SHA256(tag) || message).
+pub fn tagged_hash(tag: &[u8], message: &[u8]) -> [u8; 32] {
+    let tag = Sha256::digest(tag);
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&Sha256::new().chain(tag).chain(tag).chain(message).result());
+    hash
+}
+
+/// Returns the secp256k1 public key of the secret key.
+#[cfg(not(feature = "secp256k1"))]
+pub fn secp256k1_public_key(secret_key: &secp256k1::SecretKey) -> secp256k1::PublicKey {
+    secp256k1::PublicKey::from_secret_key(secret_key)
+}
+
+/// Returns the secp256k1 public key of the secret key, computed with the libsecp256k1 C library.
+#[cfg(feature = "secp256k1")]
+pub fn secp256k1_public_key(secret_key: &secp256k1::SecretKey) -> secp256k1::PublicKey {
+    secp256k1_c::SecretKey::from_slice(&secret_key.serialize())
+        .ok()
+        .map(|secret_key| secp256k1_c::PublicKey::from_secret_key(secp256k1_c::SECP256K1, &secret_key))
+        .and_then(|public_key| secp256k1::PublicKey::parse(&public_key.serialize_uncompressed()).ok())
+        .unwrap_or_else(|| secp256k1::PublicKey::from_secret_key(secret_key))
+}
+
+/// Returns the recoverable RFC 6979 ECDSA signature of the message hash under the secret key.
+#[cfg(not(feature = "secp256k1"))]
+pub fn secp256k1_sign(
+    message: &secp256k1::Message,
+    secret_key: &secp256k1::SecretKey,
+) -> (secp256k1::Signature, secp256k1::RecoveryId) {
+    secp256k1::sign(message, secret_key)
+}
+
+/// Returns the recoverable RFC 6979 ECDSA signature of the message hash under the secret key,
+/// computed with the libsecp256k1 C library.
+#[cfg(feature = "secp256k1")]
+pub fn secp256k1_sign(
+    message: &secp256k1::Message,
+    secret_key: &secp256k1::SecretKey,
+) -> (secp256k1::Signature, secp256k1::RecoveryId) {
+    let sign = || {
+        let key = secp256k1_c::SecretKey::from_slice(&secret_key.serialize()).ok()?;
+        let message = secp256k1_c::Message::from_slice(&message.serialize()).ok()?;
+        let (recovery_id, signature) = secp256k1_c::SECP256K1
+            .sign_ecdsa_recoverable(&message, &key)
+            .serialize_compact();
+        let recovery_id = secp256k1::RecoveryId::parse(recovery_id.to_i32() as u8).ok()?;
+        Some((secp256k1::Signature::parse(&signature), recovery_id))
+    };
+    sign().unwrap_or_else(|| secp256k1::sign(message, secret_key))
+}
+
+/// Derives the output from the password and salt with PBKDF2-HMAC-SHA512, calling the progress
+/// callback with the number of completed and total iterations after every iteration.
+pub fn pbkdf2_hmac_sha512(
+    password: &[u8],
+    salt: &[u8],
+    iterations: u32,
+    output: &mut [u8],
+    progress: &mut dyn FnMut(u32, u32),
+) {
+    // HMAC accepts keys of any length
+    let prf = Hmac::<Sha512>::new_varkey(password).expect("invalid HMAC key length");
+    let total = iterations * output.len().div_ceil(64) as u32;
+    let mut completed = 0;
+
+    for (block, chunk) in output.chunks_mut(64).enumerate() {
+        let mut mac = prf.clone();
+        mac.input(salt);
+        mac.input(&(block as u32 + 1).to_be_bytes());
+        let mut u = mac.result().code();
+        let mut t = u;
+        completed += 1;
+        progress(completed, total);
+
+        for _ in 1..iterations {
+            let mut mac = prf.clone();
+            mac.input(&u);
+            u = mac.result().code();
+            t.iter_mut().zip(u.iter()).for_each(|(t, u)| *t ^= u);
+            completed += 1;
+            progress(completed, total);
+        }
+
+        chunk.copy_from_slice(&t[..chunk.len()]);
+        u.as_mut_slice().zeroize();
+        t.as_mut_slice().zeroize();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -43,4 +135,57 @@ mod tests {
         ];
         test_hash160(b"00000000000000000000000000000000", &expected_bytes)
     }
+
+    #[test]
+    fn test_functionality_tagged_hash() {
+        let mut message = Sha256::digest(b"TapTweak").to_vec();
+        message.extend_from_slice(&Sha256::digest(b"TapTweak"));
+        message.extend_from_slice(b"message");
+        assert_eq!(
+            Sha256::digest(&message).as_slice(),
+            tagged_hash(b"TapTweak", b"message")
+        );
+    }
+
+    #[test]
+    fn test_functionality_pbkdf2_hmac_sha512() {
+        for (iterations, length) in [(1, 64), (2048, 64), (3, 100)].iter() {
+            let mut expected = vec![0u8; *length];
+            pbkdf2::pbkdf2::<Hmac<Sha512>>(b"password", b"mnemonicsalt", *iterations as usize, &mut expected);
+
+            let mut calls = vec![];
+            let mut output = vec![0u8; *length];
+            pbkdf2_hmac_sha512(
+                b"password",
+                b"mnemonicsalt",
+                *iterations,
+                &mut output,
+                &mut |completed, total| calls.push((completed, total)),
+            );
+
+            let total = iterations * length.div_ceil(64) as u32;
+            assert_eq!(expected, output);
+            assert_eq!(total as usize, calls.len());
+            assert_eq!(Some(&(total, total)), calls.last());
+        }
+    }
+
+    #[cfg(feature = "secp256k1")]
+    #[test]
+    fn test_functionality_secp256k1_backend() {
+        for seed in 1u8..=16 {
+            let secret_key = secp256k1::SecretKey::parse(&Sha256::digest(&[seed]).into()).unwrap();
+            let message = secp256k1::Message::parse(&Sha256::digest(&[seed, seed]).into());
+
+            assert_eq!(
+                secp256k1::PublicKey::from_secret_key(&secret_key).serialize()[..],
+                secp256k1_public_key(&secret_key).serialize()[..]
+            );
+
+            let (expected_signature, expected_recovery_id) = secp256k1::sign(&message, &secret_key);
+            let (signature, recovery_id) = secp256k1_sign(&message, &secret_key);
+            assert_eq!(expected_signature.serialize()[..], signature.serialize()[..]);
+            assert_eq!(expected_recovery_id.serialize(), recovery_id.serialize());
+        }
+    }
 }
@@ -0,0 +1,182 @@
+use crate::coin_type::CoinType;
+use crate::derivation_path::DerivationPathError;
+
+use crate::no_std::*;
+use core::{fmt, str::FromStr};
+
+/// Represents a wallet with a known default derivation scheme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum WalletPreset {
+    /// Electrum (BIP39 seeds)
+    Electrum,
+    /// Ledger Live
+    LedgerLive,
+    /// MetaMask
+    MetaMask,
+    /// MyEtherWallet (legacy derivation path)
+    MyEtherWalletLegacy,
+    /// Trezor Suite
+    Trezor,
+}
+
+/// Represents the default derivation scheme and address format of a wallet for a currency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WalletScheme {
+    /// The derivation path template, where `{coin_type}`, `{account}`, and `{change}` are substituted,
+    /// and `{index}` is the level the wallet increments to generate new addresses
+    pub path: &'static str,
+    /// The address format, for currencies with more than one address format
+    pub format: Option<&'static str>,
+}
+
+impl WalletScheme {
+    /// Returns the derivation path of the scheme given the coin type, account, change, and address index.
+    pub fn to_path(&self, coin_type: CoinType, account: u32, change: u32, index: u32) -> String {
+        self.path
+            .replace("{coin_type}", &coin_type.index().to_string())
+            .replace("{account}", &account.to_string())
+            .replace("{change}", &change.to_string())
+            .replace("{index}", &index.to_string())
+    }
+}
+
+impl WalletPreset {
+    /// The wallet presets, in alphabetical order.
+    pub const ALL: [WalletPreset; 5] = [
+        WalletPreset::Electrum,
+        WalletPreset::LedgerLive,
+        WalletPreset::MetaMask,
+        WalletPreset::MyEtherWalletLegacy,
+        WalletPreset::Trezor,
+    ];
+
+    /// Returns the name of the wallet preset.
+    pub fn name(&self) -> &'static str {
+        match self {
+            WalletPreset::Electrum => "electrum",
+            WalletPreset::LedgerLive => "ledger-live",
+            WalletPreset::MetaMask => "metamask",
+            WalletPreset::MyEtherWalletLegacy => "myetherwallet-legacy",
+            WalletPreset::Trezor => "trezor",
+        }
+    }
+
+    /// Returns the default derivation scheme of the wallet for the given currency,
+    /// or `None` if the wallet does not support the currency.
+    pub fn scheme(&self, currency: CoinType) -> Option<WalletScheme> {
+        let (path, format) = match (self, currency) {
+            (WalletPreset::Electrum, CoinType::Bitcoin) => {
+                ("m/84'/{coin_type}'/{account}'/{change}/{index}", Some("bech32"))
+            }
+
+            (WalletPreset::LedgerLive, CoinType::Bitcoin) => {
+                ("m/84'/{coin_type}'/{account}'/{change}/{index}", Some("bech32"))
+            }
+            (WalletPreset::LedgerLive, CoinType::Ethereum) => ("m/44'/{coin_type}'/{index}'/0/0", None),
+
+            (WalletPreset::MetaMask, CoinType::Ethereum) => ("m/44'/{coin_type}'/0'/0/{index}", None),
+
+            (WalletPreset::MyEtherWalletLegacy, CoinType::Ethereum) => ("m/44'/{coin_type}'/0'/{index}", None),
+
+            (WalletPreset::Trezor, CoinType::Bitcoin) => {
+                ("m/84'/{coin_type}'/{account}'/{change}/{index}", Some("bech32"))
+            }
+            (WalletPreset::Trezor, CoinType::Ethereum) => ("m/44'/{coin_type}'/0'/0/{index}", None),
+
+            _ => return None,
+        };
+        Some(WalletScheme { path, format })
+    }
+
+    /// Returns the wallet presets with a default derivation scheme for the given currency.
+    pub fn supporting(currency: CoinType) -> Vec<WalletPreset> {
+        WalletPreset::ALL
+            .iter()
+            .filter(|preset| preset.scheme(currency).is_some())
+            .copied()
+            .collect()
+    }
+}
+
+impl FromStr for WalletPreset {
+    type Err = DerivationPathError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        WalletPreset::ALL
+            .iter()
+            .find(|preset| preset.name() == s)
+            .copied()
+            .ok_or_else(|| DerivationPathError::InvalidWalletPreset(s.to_string()))
+    }
+}
+
+impl fmt::Display for WalletPreset {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_str() {
+        WalletPreset::ALL.iter().for_each(|preset| {
+            assert_eq!(*preset, WalletPreset::from_str(preset.name()).unwrap());
+            assert_eq!(preset.name(), preset.to_string());
+        });
+        assert_eq!(
+            Err(DerivationPathError::InvalidWalletPreset("exodus".into())),
+            WalletPreset::from_str("exodus")
+        );
+    }
+
+    #[test]
+    fn scheme() {
+        let scheme = WalletPreset::LedgerLive.scheme(CoinType::Bitcoin).unwrap();
+        assert_eq!(Some("bech32"), scheme.format);
+        assert_eq!("m/84'/0'/2'/1/5", scheme.to_path(CoinType::Bitcoin, 2, 1, 5));
+        assert_eq!("m/84'/1'/0'/0/0", scheme.to_path(CoinType::Testnet, 0, 0, 0));
+
+        let scheme = WalletPreset::LedgerLive.scheme(CoinType::Ethereum).unwrap();
+        assert_eq!(None, scheme.format);
+        assert_eq!("m/44'/60'/3'/0/0", scheme.to_path(CoinType::Ethereum, 0, 0, 3));
+
+        assert_eq!(
+            "m/44'/60'/0'/0/7",
+            WalletPreset::MetaMask
+                .scheme(CoinType::Ethereum)
+                .unwrap()
+                .to_path(CoinType::Ethereum, 0, 0, 7)
+        );
+        assert_eq!(
+            "m/44'/60'/0'/7",
+            WalletPreset::MyEtherWalletLegacy
+                .scheme(CoinType::Ethereum)
+                .unwrap()
+                .to_path(CoinType::Ethereum, 0, 0, 7)
+        );
+        assert_eq!(None, WalletPreset::Electrum.scheme(CoinType::Ethereum));
+        assert_eq!(None, WalletPreset::MetaMask.scheme(CoinType::Bitcoin));
+        assert_eq!(None, WalletPreset::Trezor.scheme(CoinType::Monero));
+    }
+
+    #[test]
+    fn supporting() {
+        assert_eq!(
+            vec![WalletPreset::Electrum, WalletPreset::LedgerLive, WalletPreset::Trezor],
+            WalletPreset::supporting(CoinType::Bitcoin)
+        );
+        assert_eq!(
+            vec![
+                WalletPreset::LedgerLive,
+                WalletPreset::MetaMask,
+                WalletPreset::MyEtherWalletLegacy,
+                WalletPreset::Trezor
+            ],
+            WalletPreset::supporting(CoinType::Ethereum)
+        );
+        assert!(WalletPreset::supporting(CoinType::Zcash).is_empty());
+    }
+}
@@ -1,8 +1,140 @@
+use crate::no_std::*;
+use crate::wordlist::{WordSuggestion, WordlistError};
+
 pub const CHINESE_SIMPLIFIED: &'static str = include_str!("./bip39/chinese_simplified.txt");
 pub const CHINESE_TRADITIONAL: &'static str = include_str!("./bip39/chinese_traditional.txt");
+pub const CZECH: &'static str = include_str!("./bip39/czech.txt");
 pub const ENGLISH: &'static str = include_str!("./bip39/english.txt");
 pub const FRENCH: &'static str = include_str!("./bip39/french.txt");
 pub const ITALIAN: &'static str = include_str!("./bip39/italian.txt");
 pub const JAPANESE: &'static str = include_str!("./bip39/japanese.txt");
 pub const KOREAN: &'static str = include_str!("./bip39/korean.txt");
+pub const PORTUGUESE: &'static str = include_str!("./bip39/portuguese.txt");
 pub const SPANISH: &'static str = include_str!("./bip39/spanish.txt");
+
+/// The BIP-39 wordlists by language name.
+pub const LANGUAGES: [(&str, &str); 10] = [
+    ("chinese_simplified", CHINESE_SIMPLIFIED),
+    ("chinese_traditional", CHINESE_TRADITIONAL),
+    ("czech", CZECH),
+    ("english", ENGLISH),
+    ("french", FRENCH),
+    ("italian", ITALIAN),
+    ("japanese", JAPANESE),
+    ("korean", KOREAN),
+    ("portuguese", PORTUGUESE),
+    ("spanish", SPANISH),
+];
+
+/// Returns the names of the languages whose wordlist contains every word of the given phrase.
+pub fn detect_languages(phrase: &str) -> Vec<&'static str> {
+    let words = phrase.split_whitespace().collect::<Vec<&str>>();
+    if words.is_empty() {
+        return vec![];
+    }
+
+    LANGUAGES
+        .iter()
+        .filter(|(_, wordlist)| {
+            let wordlist = wordlist.lines().collect::<Vec<&str>>();
+            words.iter().all(|word| wordlist.contains(word))
+        })
+        .map(|(language, _)| *language)
+        .collect()
+}
+
+/// Returns the name of the language of the given phrase,
+/// or an error if the phrase matches no wordlist or more than one.
+/// If some words of the phrase are missing from every wordlist, the error suggests
+/// the nearest words from the wordlist that recognizes the most words of the phrase.
+pub fn detect_language(phrase: &str) -> Result<&'static str, WordlistError> {
+    match detect_languages(phrase).as_slice() {
+        [] => {
+            let words = phrase.split_whitespace().collect::<Vec<&str>>();
+            let closest = LANGUAGES
+                .iter()
+                .map(|(_, wordlist)| {
+                    let wordlist = wordlist.lines().collect::<Vec<&str>>();
+                    words
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, word)| !wordlist.contains(word))
+                        .map(|(index, word)| WordSuggestion::new(index + 1, word, &wordlist))
+                        .collect::<Vec<WordSuggestion>>()
+                })
+                .min_by_key(|invalid_words| invalid_words.len());
+
+            match closest {
+                Some(invalid_words) if invalid_words.len() < words.len() => {
+                    Err(WordlistError::InvalidWords(invalid_words))
+                }
+                _ => Err(WordlistError::UnknownLanguage),
+            }
+        }
+        [language] => Ok(language),
+        languages => Err(WordlistError::AmbiguousLanguage(
+            languages.iter().map(|language| language.to_string()).collect(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_language_unique() {
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        assert_eq!("english", detect_language(phrase).unwrap());
+
+        let phrase = "abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abeille";
+        assert_eq!("french", detect_language(phrase).unwrap());
+
+        let phrase = "的 一 是 在 不 了 有 和 人 这 中 大";
+        assert_eq!("chinese_simplified", detect_language(phrase).unwrap());
+        let phrase = "abdikace abdikace abdikace abdikace abdikace abdikace abdikace abdikace abdikace abdikace abdikace agrese";
+        assert_eq!("czech", detect_language(phrase).unwrap());
+
+        let phrase = "abacate abacate abacate abacate abacate abacate abacate abacate abacate abacate abacate abater";
+        assert_eq!("portuguese", detect_language(phrase).unwrap());
+    }
+
+    #[test]
+    fn detect_language_ambiguous() {
+        let phrase = "abandon animal angle badge bicycle bonus brave canal capable caution civil client";
+        match detect_language(phrase) {
+            Err(WordlistError::AmbiguousLanguage(languages)) => assert_eq!(vec!["english", "french"], languages),
+            other => panic!("expected an ambiguous language, found {:?}", other),
+        }
+
+        let phrase = "的 一 是 在 不 了 有 和 人 中 大 的";
+        assert_eq!(
+            vec!["chinese_simplified", "chinese_traditional"],
+            detect_languages(phrase)
+        );
+    }
+
+    #[test]
+    fn detect_language_invalid_words() {
+        let phrase = "abandon abandn abandon abandon abandon abandon abandon abandon abandon abandon abandoz about";
+        match detect_language(phrase) {
+            Err(WordlistError::InvalidWords(invalid_words)) => {
+                assert_eq!(2, invalid_words.len());
+                assert_eq!(2, invalid_words[0].position);
+                assert_eq!("abandn", invalid_words[0].word);
+                assert_eq!(vec!["abandon"], invalid_words[0].suggestions);
+                assert_eq!(11, invalid_words[1].position);
+                assert_eq!("abandoz", invalid_words[1].word);
+                assert_eq!(vec!["abandon"], invalid_words[1].suggestions);
+            }
+            other => panic!("expected invalid words, found {:?}", other),
+        }
+    }
+
+    #[test]
+    fn detect_language_unknown() {
+        assert!(detect_language("").is_err());
+        assert!(detect_language("abandon abracadabra").is_err());
+        assert!(detect_language("abandon 的").is_err());
+    }
+}
@@ -1,14 +1,163 @@
 use crate::no_std::*;
-use core::{fmt::Debug, hash::Hash};
+use core::{fmt, fmt::Debug, hash::Hash};
 
-/// The interface for a generic network.
+/// The maximum number of suggestions for a word not found in a wordlist.
+const MAX_SUGGESTIONS: usize = 3;
+/// The maximum edit distance of a suggestion from a word not found in a wordlist.
+const MAX_DISTANCE: usize = 2;
+
+/// The interface for a generic wordlist.
 pub trait Wordlist: Copy + Clone + Debug + Send + Sync + 'static + Eq + Ord + Sized + Hash {}
 
+/// Represents a word of a phrase not found in a wordlist, and the nearest words to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordSuggestion {
+    /// The position of the word in the phrase, starting from 1
+    pub position: usize,
+    /// The word not found in the wordlist
+    pub word: String,
+    /// The nearest words of the wordlist, closest first
+    pub suggestions: Vec<String>,
+}
+
+impl WordSuggestion {
+    /// Returns the suggestion for the word at the given position, using the given wordlist.
+    pub fn new(position: usize, word: &str, wordlist: &[&str]) -> Self {
+        Self {
+            position,
+            word: word.into(),
+            suggestions: suggest_words(word, wordlist),
+        }
+    }
+}
+
+impl fmt::Display for WordSuggestion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "word {} {:?} is not in the wordlist", self.position, self.word)?;
+        match self.suggestions.is_empty() {
+            true => Ok(()),
+            false => {
+                let suggestions = self
+                    .suggestions
+                    .iter()
+                    .map(|suggestion| format!("{:?}", suggestion))
+                    .collect::<Vec<String>>();
+                write!(f, ", did you mean {}?", suggestions.join(" or "))
+            }
+        }
+    }
+}
+
+/// Returns the nearest words of the given wordlist to the given word, closest first.
+/// Words beginning with the given word are suggested first, followed by words
+/// within a small edit distance of it.
+pub fn suggest_words(word: &str, wordlist: &[&str]) -> Vec<String> {
+    if word.is_empty() {
+        return vec![];
+    }
+
+    let mut candidates = wordlist
+        .iter()
+        .enumerate()
+        .filter_map(|(index, candidate)| {
+            let distance = match candidate.starts_with(word) {
+                true => 0,
+                false => edit_distance(word, candidate),
+            };
+            match distance <= MAX_DISTANCE {
+                true => Some((distance, index, *candidate)),
+                false => None,
+            }
+        })
+        .collect::<Vec<(usize, usize, &str)>>();
+    candidates.sort();
+
+    candidates
+        .iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, _, candidate)| candidate.to_string())
+        .collect()
+}
+
+/// Returns the Levenshtein distance between the characters of the given words.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<char>>();
+    let mut previous = (0..=b.len()).collect::<Vec<usize>>();
+
+    for (i, a) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, b) in b.iter().enumerate() {
+            let substitution = previous[j] + (a != *b) as usize;
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
 #[derive(Debug, Fail)]
 pub enum WordlistError {
+    #[fail(display = "ambiguous mnemonic language: {:?}", _0)]
+    AmbiguousLanguage(Vec<String>),
+
+    #[fail(display = "duplicate word in wordlist: {}", _0)]
+    DuplicateWord(String),
+
     #[fail(display = "invalid index: {}", _0)]
     InvalidIndex(usize),
 
+    #[fail(display = "invalid wordlist length: {}", _0)]
+    InvalidLength(usize),
+
     #[fail(display = "invalid word: {}", _0)]
     InvalidWord(String),
+
+    #[fail(display = "invalid words: {:?}", _0)]
+    InvalidWords(Vec<WordSuggestion>),
+
+    #[fail(display = "unknown mnemonic language")]
+    UnknownLanguage,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORDLIST: [&str; 6] = ["abandon", "ability", "able", "about", "above", "absent"];
+
+    #[test]
+    fn edit_distance() {
+        assert_eq!(0, super::edit_distance("about", "about"));
+        assert_eq!(1, super::edit_distance("abandn", "abandon"));
+        assert_eq!(1, super::edit_distance("abandoz", "abandon"));
+        assert_eq!(2, super::edit_distance("abuot", "about"));
+        assert_eq!(3, super::edit_distance("", "abc"));
+        assert_eq!(1, super::edit_distance("的", "一"));
+    }
+
+    #[test]
+    fn suggest_words() {
+        assert_eq!(vec!["abandon"], super::suggest_words("abandoz", &WORDLIST));
+        assert_eq!(vec!["abandon", "able"], super::suggest_words("aban", &WORDLIST));
+        assert_eq!(
+            vec!["abandon", "ability", "able"],
+            super::suggest_words("ab", &WORDLIST)
+        );
+        assert_eq!(vec!["above", "able", "about"], super::suggest_words("abote", &WORDLIST));
+        assert!(super::suggest_words("zoo", &WORDLIST).is_empty());
+        assert!(super::suggest_words("", &WORDLIST).is_empty());
+    }
+
+    #[test]
+    fn word_suggestion_display() {
+        let suggestion = WordSuggestion::new(3, "abandn", &WORDLIST);
+        assert_eq!(
+            "word 3 \"abandn\" is not in the wordlist, did you mean \"abandon\"?",
+            suggestion.to_string()
+        );
+
+        let suggestion = WordSuggestion::new(1, "zoo", &WORDLIST);
+        assert_eq!("word 1 \"zoo\" is not in the wordlist", suggestion.to_string());
+    }
 }
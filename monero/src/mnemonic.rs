@@ -7,15 +7,16 @@ use crate::wordlist::MoneroWordlist;
 use wagyu_model::no_std::vec;
 use wagyu_model::{
     no_std::{String, ToString, Vec},
-    Mnemonic, MnemonicError, PrivateKey,
+    ExposeSecret, Mnemonic, MnemonicError, PrivateKey, Redacted,
 };
 
 use core::{fmt, marker::PhantomData, str, str::FromStr};
 use crc::{crc32, Hasher32};
 use curve25519_dalek::scalar::Scalar;
 use rand::Rng;
+use zeroize::Zeroize;
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 /// Represents a Monero mnemonic
 pub struct MoneroMnemonic<N: MoneroNetwork, W: MoneroWordlist> {
     /// The initial 256-bit seed
@@ -129,10 +130,10 @@ impl<N: MoneroNetwork, W: MoneroWordlist> Mnemonic for MoneroMnemonic<N, W> {
 
     /// Returns the private key of the corresponding mnemonic.
     fn to_private_key(&self, _: Option<&str>) -> Result<Self::PrivateKey, MnemonicError> {
-        Ok(MoneroPrivateKey::from_seed(
-            hex::encode(&self.seed).as_str(),
-            &MoneroFormat::Standard,
-        )?)
+        let mut seed = hex::encode(self.seed);
+        let private_key = MoneroPrivateKey::from_seed(seed.as_str(), &MoneroFormat::Standard);
+        seed.zeroize();
+        Ok(private_key?)
     }
 
     /// Returns the public key of the corresponding mnemonic.
@@ -192,16 +193,41 @@ impl<N: MoneroNetwork, W: MoneroWordlist> fmt::Display for MoneroMnemonic<N, W>
     }
 }
 
+impl<N: MoneroNetwork, W: MoneroWordlist> fmt::Debug for MoneroMnemonic<N, W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MoneroMnemonic").field("seed", &Redacted).finish()
+    }
+}
+
+impl<N: MoneroNetwork, W: MoneroWordlist> ExposeSecret for MoneroMnemonic<N, W> {
+    fn fmt_secret(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MoneroMnemonic").field("seed", &self.seed).finish()
+    }
+}
+
+impl<N: MoneroNetwork, W: MoneroWordlist> Zeroize for MoneroMnemonic<N, W> {
+    fn zeroize(&mut self) {
+        self.seed.zeroize();
+    }
+}
+
+impl<N: MoneroNetwork, W: MoneroWordlist> Drop for MoneroMnemonic<N, W> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::network::*;
     use crate::wordlist::*;
     use hex;
+    use rand::{rngs::StdRng, SeedableRng};
 
     fn test_new<N: MoneroNetwork, W: MoneroWordlist>() {
-        let rng = &mut rand::thread_rng();
-        let mnemonic = MoneroMnemonic::<N, W>::new(rng).unwrap();
+        let source = &mut StdRng::seed_from_u64(1231275789u64);
+        let mnemonic = MoneroMnemonic::<N, W>::new_with_entropy(source).unwrap();
         test_to_phrase::<N, W>(&mnemonic.to_phrase().unwrap(), &mnemonic.seed);
     }
 
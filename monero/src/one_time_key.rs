@@ -3,8 +3,8 @@
 use crate::network::MoneroNetwork;
 use crate::private_key::MoneroPrivateKey;
 use crate::public_key::MoneroPublicKey;
-use wagyu_model::no_std::{vec, String, Vec};
-use wagyu_model::{PublicKeyError, TransactionError};
+use wagyu_model::no_std::{vec, Vec};
+use wagyu_model::{CrateError, PublicKeyError, TransactionError};
 
 use core::marker::PhantomData;
 use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsPoint};
@@ -13,17 +13,17 @@ use tiny_keccak::keccak256;
 
 #[derive(Debug, Fail)]
 pub enum OneTimeKeyError {
-    #[fail(display = "{}: {}", _0, _1)]
-    Crate(&'static str, String),
+    #[fail(display = "{}", _0)]
+    Crate(#[cause] CrateError),
 
     #[fail(display = "could not generate Edwards point from slice {:?}", _0)]
     EdwardsPointError([u8; 32]),
 
     #[fail(display = "{}", _0)]
-    PublicKeyError(PublicKeyError),
+    PublicKeyError(#[cause] PublicKeyError),
 
     #[fail(display = "{}", _0)]
-    TransactionError(TransactionError),
+    TransactionError(#[cause] TransactionError),
 }
 
 impl From<PublicKeyError> for OneTimeKeyError {
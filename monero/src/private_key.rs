@@ -2,16 +2,17 @@ use crate::address::MoneroAddress;
 use crate::format::MoneroFormat;
 use crate::network::MoneroNetwork;
 use crate::public_key::MoneroPublicKey;
-use wagyu_model::{no_std::Vec, Address, AddressError, PrivateKey, PrivateKeyError, PublicKey};
+use wagyu_model::{no_std::Vec, Address, AddressError, ExposeSecret, PrivateKey, PrivateKeyError, PublicKey, Redacted};
 
 use core::{fmt, fmt::Display, marker::PhantomData, str::FromStr};
 use curve25519_dalek::scalar::Scalar;
 use hex;
 use rand::Rng;
 use tiny_keccak::keccak256;
+use zeroize::Zeroize;
 
 /// Represents a Monero private key
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct MoneroPrivateKey<N: MoneroNetwork> {
     /// The private spending key
     spend_key: [u8; 32],
@@ -145,6 +146,39 @@ impl<N: MoneroNetwork> Display for MoneroPrivateKey<N> {
     }
 }
 
+impl<N: MoneroNetwork> fmt::Debug for MoneroPrivateKey<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MoneroPrivateKey")
+            .field("spend_key", &Redacted)
+            .field("view_key", &Redacted)
+            .field("format", &self.format)
+            .finish()
+    }
+}
+
+impl<N: MoneroNetwork> ExposeSecret for MoneroPrivateKey<N> {
+    fn fmt_secret(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MoneroPrivateKey")
+            .field("spend_key", &self.spend_key)
+            .field("view_key", &self.view_key)
+            .field("format", &self.format)
+            .finish()
+    }
+}
+
+impl<N: MoneroNetwork> Zeroize for MoneroPrivateKey<N> {
+    fn zeroize(&mut self) {
+        self.spend_key.zeroize();
+        self.view_key.zeroize();
+    }
+}
+
+impl<N: MoneroNetwork> Drop for MoneroPrivateKey<N> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -336,6 +370,14 @@ mod tests {
                     test_to_str(private_spend_key, private_view_key, &private_key);
                 });
         }
+
+        #[test]
+        fn zeroize() {
+            let mut private_key = MoneroPrivateKey::<N>::from_seed(KEYPAIRS[0].0, FORMAT).unwrap();
+            private_key.zeroize();
+            assert_eq!([0u8; 32], private_key.spend_key);
+            assert_eq!([0u8; 32], private_key.view_key);
+        }
     }
 
     mod integrated_mainnet {
@@ -1,4 +1,6 @@
-use wagyu_model::no_std::{String, Vec};
+use crate::mnemonic::MoneroMnemonic;
+use crate::network::Mainnet;
+use wagyu_model::no_std::{String, ToString, Vec};
 use wagyu_model::wordlist::{Wordlist, WordlistError};
 
 pub mod chinese_simplified;
@@ -89,3 +91,125 @@ pub trait MoneroWordlist: Wordlist {
         }
     }
 }
+
+/// Returns whether every word of the given phrase is in the wordlist, compared by its prefix.
+fn contains_words<W: MoneroWordlist>(words: &[&str]) -> bool {
+    let wordlist = W::get_all_trimmed();
+    words.iter().all(|word| wordlist.contains(&W::to_trimmed(word)))
+}
+
+/// Returns whether the given phrase decodes with the wordlist and has a valid checksum word.
+fn verify_phrase<W: MoneroWordlist>(phrase: &str) -> bool {
+    MoneroMnemonic::<Mainnet, W>::verify_phrase(phrase)
+}
+
+/// The name, word check, and phrase check of a Monero language.
+type Language = (&'static str, fn(&[&str]) -> bool, fn(&str) -> bool);
+
+/// The name, word check, and phrase check of each Monero language.
+const LANGUAGES: [Language; 13] = [
+    (
+        "chinese_simplified",
+        contains_words::<ChineseSimplified>,
+        verify_phrase::<ChineseSimplified>,
+    ),
+    ("dutch", contains_words::<Dutch>, verify_phrase::<Dutch>),
+    ("english", contains_words::<English>, verify_phrase::<English>),
+    ("english_old", contains_words::<EnglishOld>, verify_phrase::<EnglishOld>),
+    ("esperanto", contains_words::<Esperanto>, verify_phrase::<Esperanto>),
+    ("french", contains_words::<French>, verify_phrase::<French>),
+    ("german", contains_words::<German>, verify_phrase::<German>),
+    ("italian", contains_words::<Italian>, verify_phrase::<Italian>),
+    ("japanese", contains_words::<Japanese>, verify_phrase::<Japanese>),
+    ("lojban", contains_words::<Lojban>, verify_phrase::<Lojban>),
+    ("portuguese", contains_words::<Portuguese>, verify_phrase::<Portuguese>),
+    ("russian", contains_words::<Russian>, verify_phrase::<Russian>),
+    ("spanish", contains_words::<Spanish>, verify_phrase::<Spanish>),
+];
+
+/// Returns the names of the Monero languages whose wordlist contains every word of the given phrase.
+/// If several wordlists contain every word, only those whose checksum word matches the phrase are returned,
+/// unless none of them match.
+pub fn detect_languages(phrase: &str) -> Vec<&'static str> {
+    let words = phrase.split_whitespace().collect::<Vec<&str>>();
+    if words.is_empty() {
+        return Vec::new();
+    }
+
+    let candidates = LANGUAGES
+        .iter()
+        .filter(|(_, contains_words, _)| contains_words(&words))
+        .collect::<Vec<_>>();
+    let verified = candidates
+        .iter()
+        .filter(|(_, _, verify_phrase)| verify_phrase(&words.join(" ")))
+        .map(|(language, _, _)| *language)
+        .collect::<Vec<&str>>();
+
+    match verified.is_empty() {
+        true => candidates.iter().map(|(language, _, _)| *language).collect(),
+        false => verified,
+    }
+}
+
+/// Returns the name of the Monero language of the given phrase,
+/// or an error if the phrase matches no wordlist or, after comparing checksum words, more than one.
+pub fn detect_language(phrase: &str) -> Result<&'static str, WordlistError> {
+    match detect_languages(phrase).as_slice() {
+        [] => Err(WordlistError::UnknownLanguage),
+        [language] => Ok(language),
+        languages => Err(WordlistError::AmbiguousLanguage(
+            languages.iter().map(|language| language.to_string()).collect(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wagyu_model::Mnemonic;
+
+    const SEED: [u8; 32] = [
+        0xea, 0x11, 0x11, 0x87, 0xa5, 0x98, 0xd5, 0xab, 0x5f, 0xda, 0xbf, 0x8a, 0xdb, 0x27, 0xdf, 0x79, 0x00, 0x5a,
+        0x10, 0x6c, 0x7e, 0x3d, 0xc1, 0x17, 0x97, 0xd7, 0x7c, 0x4c, 0x48, 0xba, 0xce, 0x0b,
+    ];
+
+    fn test_detect_language<W: MoneroWordlist>(expected_language: &str) {
+        let mnemonic = MoneroMnemonic::<Mainnet, W>::from_private_spend_key(&SEED);
+        let phrase = mnemonic.to_phrase().unwrap();
+        assert_eq!(expected_language, detect_language(&phrase).unwrap());
+    }
+
+    #[test]
+    fn detect_language_unique() {
+        test_detect_language::<ChineseSimplified>("chinese_simplified");
+        test_detect_language::<Dutch>("dutch");
+        test_detect_language::<English>("english");
+        test_detect_language::<EnglishOld>("english_old");
+        test_detect_language::<Esperanto>("esperanto");
+        test_detect_language::<French>("french");
+        test_detect_language::<German>("german");
+        test_detect_language::<Italian>("italian");
+        test_detect_language::<Japanese>("japanese");
+        test_detect_language::<Lojban>("lojban");
+        test_detect_language::<Portuguese>("portuguese");
+        test_detect_language::<Russian>("russian");
+        test_detect_language::<Spanish>("spanish");
+    }
+
+    #[test]
+    fn detect_language_prefix() {
+        let phrase =
+            "fig hoi upt nib wom pep doe plo dol fug pop chl tur org amb peo hos led pup any gou cud bec can wom";
+        assert_eq!("english", detect_language(phrase).unwrap());
+    }
+
+    #[test]
+    fn detect_language_unknown() {
+        assert!(matches!(detect_language(""), Err(WordlistError::UnknownLanguage)));
+        assert!(matches!(
+            detect_language("qqqq xxxx zzzz"),
+            Err(WordlistError::UnknownLanguage)
+        ));
+    }
+}
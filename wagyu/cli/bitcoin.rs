@@ -1,14 +1,31 @@
+#[cfg(feature = "online")]
+use crate::bitcoin::ElectrumClient;
 use crate::bitcoin::{
-    format::BitcoinFormat, wordlist::*, BitcoinAddress, BitcoinAmount, BitcoinDerivationPath,
-    BitcoinExtendedPrivateKey, BitcoinExtendedPublicKey, BitcoinMnemonic, BitcoinNetwork, BitcoinPrivateKey,
-    BitcoinPublicKey, BitcoinTransaction, BitcoinTransactionInput, BitcoinTransactionOutput,
-    BitcoinTransactionParameters, BitcoinWordlist, Mainnet as BitcoinMainnet, Outpoint, SignatureHash,
-    Testnet as BitcoinTestnet,
+    format::BitcoinFormat, wordlist::*, AezeedCipherSeed, Bip38EncryptedPrivateKey, BitcoinAddress, BitcoinAmount,
+    BitcoinCoreImport, BitcoinDecodedTransaction, BitcoinDerivationPath, BitcoinExtendedPrivateKey,
+    BitcoinExtendedPublicKey, BitcoinMnemonic, BitcoinMultisig, BitcoinNetwork, BitcoinPrivateKey, BitcoinPsbt,
+    BitcoinPublicKey, BitcoinTimelockScript, BitcoinTransaction, BitcoinTransactionExchange, BitcoinTransactionInput,
+    BitcoinTransactionOutput, BitcoinTransactionParameters, BitcoinUri, BitcoinVanitySearch, BitcoinWordlist,
+    ElectrumWallet, ImportRpc, ImportTimestamp, LockTime, Mainnet as BitcoinMainnet, Outpoint, PsbtInput,
+    Regtest as BitcoinRegtest, RelativeLockTime, SignatureHash, Signet as BitcoinSignet, TaprootControlBlock,
+    TaprootLeaf, TaprootSpend, Testnet as BitcoinTestnet, Timelock, VanityProgress, WifMetadata,
+    DEFAULT_IMPORT_RANGE_END, PSBT_MODIFIABLE_INPUTS, PSBT_MODIFIABLE_OUTPUTS,
 };
-use crate::cli::{flag, option, subcommand, types::*, CLIError, CLI};
+#[cfg(feature = "ledger")]
+use crate::bitcoin::{LedgerClient, LedgerHid, LedgerWalletPolicy};
+#[cfg(feature = "trezor")]
+use crate::bitcoin::{TrezorBridge, TrezorClient, TrezorHid, TrezorTransport};
+use crate::cli::{
+    decode_seed_qr, decrypt_backup, encode_seed_qr, encrypt_backup, flag, option, print_seed_progress, prompt_password,
+    subcommand, types::*, CLIError, ResultExt, Share, CLI,
+};
+#[cfg(any(feature = "ledger", feature = "trezor"))]
+use crate::model::DerivationPath;
 use crate::model::{
-    crypto::hash160, ExtendedPrivateKey, ExtendedPublicKey, Mnemonic, MnemonicCount, MnemonicExtended, PrivateKey,
-    PublicKey, Transaction,
+    crypto::hash160, detect_language, ChildIndex, ChildIndexRange, CoinType, DerivationPathError, EntropyRng,
+    EntropySource, ExtendedPrivateKey, ExtendedPublicKey, KeyOrigin, ManualEntropy, Mnemonic, MnemonicCount,
+    MnemonicEntropy, MnemonicExtended, Network, PrivateKey, PublicKey, Slip39Group, Slip39Share, Transaction,
+    TransactionError, WalletPreset,
 };
 
 use clap::{ArgMatches, Values};
@@ -16,8 +33,11 @@ use colored::*;
 use core::{fmt, fmt::Display, str::FromStr};
 use rand::{rngs::StdRng, Rng};
 use rand_core::SeedableRng;
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use serde_json::from_str;
+use std::io::IsTerminal;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use crate::model::no_std::{format, vec, String, ToOwned, ToString, Vec};
 
@@ -27,33 +47,94 @@ struct BitcoinWallet {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub path: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub key_origin: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub password: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mnemonic: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub birthday: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backup: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed_qr: Option<String>,
+    #[serde(skip)]
+    pub qr_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_threshold: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shares: Option<Vec<Share>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub extended_private_key: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub extended_public_key: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub descriptors: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub core_import: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub electrum_wallet: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub private_key: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub encrypted_private_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub public_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub threshold: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub redeem_script: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub witness_script: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spend_paths: Option<Vec<String>>,
     pub address: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub uri: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub format: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub network: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub compressed: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub balance: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unconfirmed_balance: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub utxos: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verified: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub psbt: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finalized: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signatures: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub virtual_size: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warnings: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_exchange: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub transaction_hex: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub transaction_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decoded_transaction: Option<serde_json::Value>,
 }
 
 impl BitcoinWallet {
-    pub fn new<N: BitcoinNetwork, R: Rng>(rng: &mut R, format: &BitcoinFormat) -> Result<Self, CLIError> {
-        let private_key = BitcoinPrivateKey::<N>::new(rng)?;
+    pub fn new<N: BitcoinNetwork, E: EntropySource + ?Sized>(
+        source: &mut E,
+        format: &BitcoinFormat,
+    ) -> Result<Self, CLIError> {
+        let private_key = BitcoinPrivateKey::<N>::new_with_entropy(source)?;
         let public_key = private_key.to_public_key();
         let address = public_key.to_address(format)?;
         Ok(Self {
@@ -67,16 +148,134 @@ impl BitcoinWallet {
         })
     }
 
-    pub fn new_hd<N: BitcoinNetwork, W: BitcoinWordlist, R: Rng>(
-        rng: &mut R,
+    /// Returns a new wallet whose address matches the given vanity prefix or regular expression,
+    /// printing the progress of the search to the terminal.
+    pub fn new_vanity<N: BitcoinNetwork, E: EntropySource + ?Sized>(
+        source: &mut E,
+        pattern: &str,
+        is_regex: bool,
+        format: &BitcoinFormat,
+        threads: usize,
+    ) -> Result<Self, CLIError> {
+        let search = match is_regex {
+            true => BitcoinVanitySearch::<N>::regex(pattern, format)?,
+            false => BitcoinVanitySearch::<N>::prefix(pattern, format)?,
+        }
+        .with_threads(threads);
+
+        let rng = &mut StdRng::from_seed(EntropyRng::new(source).gen());
+        let result = search.search(rng, |progress| print_vanity_progress(&search, Some(progress)));
+        print_vanity_progress(&search, None);
+        let (private_key, address) = result?;
+
+        Ok(Self {
+            private_key: Some(private_key.to_string()),
+            public_key: Some(private_key.to_public_key().to_string()),
+            address: Some(address.to_string()),
+            network: Some(N::NAME.to_string()),
+            format: Some(address.format().to_string()),
+            compressed: private_key.is_compressed().into(),
+            ..Default::default()
+        })
+    }
+
+    /// Returns a new wallet with its private key encrypted by the given BIP38 passphrase.
+    pub fn new_bip38<N: BitcoinNetwork, E: EntropySource + ?Sized>(
+        source: &mut E,
+        format: &BitcoinFormat,
+        passphrase: &str,
+    ) -> Result<Self, CLIError> {
+        let private_key = BitcoinPrivateKey::<N>::new_with_entropy(source)?;
+        let encrypted_private_key = Bip38EncryptedPrivateKey::encrypt(&private_key, passphrase)?;
+        let address = private_key.to_address(format)?;
+        Ok(Self {
+            encrypted_private_key: Some(encrypted_private_key.to_string()),
+            public_key: Some(private_key.to_public_key().to_string()),
+            address: Some(address.to_string()),
+            network: Some(N::NAME.to_string()),
+            format: Some(address.format().to_string()),
+            compressed: private_key.is_compressed().into(),
+            ..Default::default()
+        })
+    }
+
+    /// Returns the given number of HD wallets for new mnemonics, deriving their seeds in parallel.
+    pub fn new_hd<N: BitcoinNetwork, W: BitcoinWordlist, E: EntropySource + ?Sized>(
+        source: &mut E,
+        count: usize,
+        word_count: u8,
+        password: Option<&str>,
+        path: &str,
+    ) -> Result<Vec<Self>, CLIError> {
+        let mnemonics = (0..count)
+            .map(|_| BitcoinMnemonic::<N, W>::new_with_count_and_entropy(source, word_count))
+            .collect::<Result<Vec<_>, _>>()?;
+        let seeds = match mnemonics.as_slice() {
+            [mnemonic] => vec![mnemonic.to_seed_with_progress(password, &mut print_seed_progress)?],
+            mnemonics => BitcoinMnemonic::to_seeds(mnemonics, password)?,
+        };
+        let derivation_path =
+            BitcoinDerivationPath::from_str(path).context_with_input("bitcoin", "parse derivation path", path)?;
+
+        mnemonics
+            .iter()
+            .zip(seeds)
+            .map(|(mnemonic, seed)| {
+                let master_extended_private_key =
+                    BitcoinExtendedPrivateKey::<N>::new_master(&seed, &BitcoinFormat::P2PKH)?;
+                let extended_private_key = master_extended_private_key.derive(&derivation_path)?;
+                let key_origin = master_extended_private_key.key_origin(&derivation_path)?;
+                let extended_public_key = extended_private_key.to_extended_public_key();
+                let private_key = extended_private_key.to_private_key();
+                let public_key = extended_public_key.to_public_key();
+                let address = public_key.to_address(&extended_private_key.format())?;
+                let compressed = private_key.is_compressed();
+                Ok(Self {
+                    path: Some(path.to_string()),
+                    key_origin: Some(key_origin.to_string()),
+                    password: password.map(String::from),
+                    mnemonic: Some(mnemonic.to_string()),
+                    extended_private_key: Some(extended_private_key.to_string()),
+                    extended_public_key: Some(extended_public_key.to_string()),
+                    private_key: Some(private_key.to_string()),
+                    public_key: Some(public_key.to_string()),
+                    address: Some(address.to_string()),
+                    format: Some(address.format().to_string()),
+                    network: Some(N::NAME.to_string()),
+                    compressed: Some(compressed),
+                    ..Default::default()
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the HD wallet of the mnemonic of the given word count, from the entropy of the given rolls.
+    pub fn new_hd_from_rolls<N: BitcoinNetwork, W: BitcoinWordlist>(
+        source: ManualEntropy,
+        rolls: &str,
         word_count: u8,
         password: Option<&str>,
         path: &str,
     ) -> Result<Self, CLIError> {
-        let mnemonic = BitcoinMnemonic::<N, W>::new_with_count(rng, word_count)?;
-        let master_extended_private_key = mnemonic.to_extended_private_key(password)?;
-        let derivation_path = BitcoinDerivationPath::from_str(path)?;
+        let entropy = source
+            .to_entropy(rolls, word_count as usize / 3 * 4)
+            .context("bitcoin", "read entropy rolls")?;
+        let mnemonic = BitcoinMnemonic::<N, W>::from_entropy(&entropy)?;
+        Self::from_mnemonic::<N, W>(&mnemonic.to_phrase()?, &password, path)
+    }
+
+    pub fn from_mnemonic<N: BitcoinNetwork, W: BitcoinWordlist>(
+        mnemonic: &str,
+        password: &Option<&str>,
+        path: &str,
+    ) -> Result<Self, CLIError> {
+        let mnemonic = BitcoinMnemonic::<N, W>::from_phrase(&mnemonic).context("bitcoin", "parse mnemonic")?;
+        let master_extended_private_key =
+            mnemonic.to_extended_private_key_with_progress(password.clone(), &mut print_seed_progress)?;
+        let derivation_path =
+            BitcoinDerivationPath::from_str(path).context_with_input("bitcoin", "parse derivation path", path)?;
         let extended_private_key = master_extended_private_key.derive(&derivation_path)?;
+        let key_origin = master_extended_private_key.key_origin(&derivation_path)?;
         let extended_public_key = extended_private_key.to_extended_public_key();
         let private_key = extended_private_key.to_private_key();
         let public_key = extended_public_key.to_public_key();
@@ -84,6 +283,7 @@ impl BitcoinWallet {
         let compressed = private_key.is_compressed();
         Ok(Self {
             path: Some(path.to_string()),
+            key_origin: Some(key_origin.to_string()),
             password: password.map(String::from),
             mnemonic: Some(mnemonic.to_string()),
             extended_private_key: Some(extended_private_key.to_string()),
@@ -98,15 +298,171 @@ impl BitcoinWallet {
         })
     }
 
-    pub fn from_mnemonic<N: BitcoinNetwork, W: BitcoinWordlist>(
+    /// Returns the wallets of the given number of consecutive indices of the mnemonic, beginning at the
+    /// last index of the given path, deriving the master key and the parent key of the wallets once.
+    pub fn from_mnemonic_children<N: BitcoinNetwork, W: BitcoinWordlist>(
+        mnemonic: &str,
+        password: &Option<&str>,
+        path: &str,
+        indices: u32,
+    ) -> Result<Vec<Self>, CLIError> {
+        let mnemonic = BitcoinMnemonic::<N, W>::from_phrase(&mnemonic).context("bitcoin", "parse mnemonic")?;
+        let master_extended_private_key =
+            mnemonic.to_extended_private_key_with_progress(password.clone(), &mut print_seed_progress)?;
+        let derivation_path =
+            BitcoinDerivationPath::from_str(path).context_with_input("bitcoin", "parse derivation path", path)?;
+        let children = master_extended_private_key.derive_batch(&derivation_path, indices)?;
+
+        let parent_path = path.rsplitn(2, '/').last().unwrap_or("m");
+        let parent_derivation_path = BitcoinDerivationPath::<N>::from_str(parent_path).context_with_input(
+            "bitcoin",
+            "parse derivation path",
+            parent_path,
+        )?;
+        let parent_key_origin = master_extended_private_key.key_origin(&parent_derivation_path)?;
+
+        children
+            .iter()
+            .map(|extended_private_key| {
+                let child_index = extended_private_key.child_index();
+                let extended_public_key = extended_private_key.to_extended_public_key();
+                let private_key = extended_private_key.to_private_key();
+                let public_key = extended_public_key.to_public_key();
+                let address = public_key.to_address(&extended_private_key.format())?;
+                Ok(Self {
+                    path: Some(format!("{}/{}", parent_path, child_index)),
+                    key_origin: Some(parent_key_origin.child(child_index)?.to_string()),
+                    password: password.map(String::from),
+                    mnemonic: Some(mnemonic.to_string()),
+                    extended_private_key: Some(extended_private_key.to_string()),
+                    extended_public_key: Some(extended_public_key.to_string()),
+                    private_key: Some(private_key.to_string()),
+                    public_key: Some(public_key.to_string()),
+                    address: Some(address.to_string()),
+                    format: Some(address.format().to_string()),
+                    network: Some(N::NAME.to_string()),
+                    compressed: Some(private_key.is_compressed()),
+                    ..Default::default()
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the wallets of the mnemonics completing the given partial mnemonic that derive the given address.
+    pub fn recover<N: BitcoinNetwork, W: BitcoinWordlist>(
+        mnemonic: &str,
+        password: &Option<&str>,
+        path: &str,
+        address: &str,
+    ) -> Result<Vec<Self>, CLIError> {
+        let candidates =
+            BitcoinMnemonic::<N, W>::from_partial_phrase(mnemonic).context("bitcoin", "parse partial mnemonic")?;
+        let wallets = candidates
+            .par_iter()
+            .map(|candidate| Self::from_mnemonic::<N, W>(&candidate.to_string(), password, path))
+            .filter(|wallet| match wallet {
+                Ok(wallet) => wallet.address.as_ref().map(String::as_str) == Some(address),
+                Err(_) => true,
+            })
+            .collect::<Result<Vec<Self>, CLIError>>()?;
+
+        match wallets.is_empty() {
+            true => Err(CLIError::UnrecoveredMnemonic(address.into())),
+            false => Ok(wallets),
+        }
+    }
+
+    pub fn new_hd_shares<N: BitcoinNetwork, E: EntropySource + ?Sized>(
+        source: &mut E,
+        group_threshold: u8,
+        groups: &[Slip39Group],
+        password: Option<&str>,
+        path: &str,
+    ) -> Result<Self, CLIError> {
+        let rng = &mut EntropyRng::new(source);
+        let master_secret: [u8; 16] = rng.gen();
+        let shares = Slip39Share::split(
+            rng,
+            &master_secret,
+            password.unwrap_or(""),
+            group_threshold,
+            groups,
+            0,
+            true,
+        )?;
+        let shares = shares.concat();
+        Ok(Self {
+            group_threshold: Some(group_threshold),
+            shares: Some(Share::from_shares(&shares)),
+            ..Self::from_master_secret::<N>(&master_secret, password, path)?
+        })
+    }
+
+    pub fn from_shares<N: BitcoinNetwork>(
+        shares: &[String],
+        password: &Option<&str>,
+        path: &str,
+    ) -> Result<Self, CLIError> {
+        let shares = shares
+            .iter()
+            .map(|share| Slip39Share::from_phrase(share).context("bitcoin", "parse share"))
+            .collect::<Result<Vec<Slip39Share>, CLIError>>()?;
+        let master_secret =
+            Slip39Share::combine(&shares, password.unwrap_or("")).context("bitcoin", "combine shares")?;
+        Ok(Self {
+            group_threshold: shares.first().map(|share| share.group_threshold),
+            shares: Some(Share::from_shares(&shares)),
+            ..Self::from_master_secret::<N>(&master_secret, *password, path)?
+        })
+    }
+
+    pub fn new_hd_aezeed<N: BitcoinNetwork, E: EntropySource + ?Sized>(
+        source: &mut E,
+        password: Option<&str>,
+        path: &str,
+    ) -> Result<Self, CLIError> {
+        let rng = &mut EntropyRng::new(source);
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or(0);
+        let seed = AezeedCipherSeed::new(rng, AezeedCipherSeed::birthday_from_timestamp(timestamp));
+        Self::from_aezeed_seed::<N>(&seed, password, path)
+    }
+
+    pub fn from_aezeed<N: BitcoinNetwork>(
         mnemonic: &str,
         password: &Option<&str>,
         path: &str,
     ) -> Result<Self, CLIError> {
-        let mnemonic = BitcoinMnemonic::<N, W>::from_phrase(&mnemonic)?;
-        let master_extended_private_key = mnemonic.to_extended_private_key(password.clone())?;
-        let derivation_path = BitcoinDerivationPath::from_str(path)?;
+        let seed = AezeedCipherSeed::from_phrase(mnemonic, password.unwrap_or(""))
+            .context("bitcoin", "decipher aezeed mnemonic")?;
+        Self::from_aezeed_seed::<N>(&seed, *password, path)
+    }
+
+    fn from_aezeed_seed<N: BitcoinNetwork>(
+        seed: &AezeedCipherSeed,
+        password: Option<&str>,
+        path: &str,
+    ) -> Result<Self, CLIError> {
+        Ok(Self {
+            mnemonic: Some(seed.to_phrase(password.unwrap_or(""))?),
+            birthday: Some(seed.birthday),
+            ..Self::from_master_secret::<N>(seed.entropy(), password, path)?
+        })
+    }
+
+    fn from_master_secret<N: BitcoinNetwork>(
+        master_secret: &[u8],
+        password: Option<&str>,
+        path: &str,
+    ) -> Result<Self, CLIError> {
+        let master_extended_private_key =
+            BitcoinExtendedPrivateKey::<N>::new_master(master_secret, &BitcoinFormat::P2PKH)?;
+        let derivation_path =
+            BitcoinDerivationPath::from_str(path).context_with_input("bitcoin", "parse derivation path", path)?;
         let extended_private_key = master_extended_private_key.derive(&derivation_path)?;
+        let key_origin = master_extended_private_key.key_origin(&derivation_path)?;
         let extended_public_key = extended_private_key.to_extended_public_key();
         let private_key = extended_private_key.to_private_key();
         let public_key = extended_public_key.to_public_key();
@@ -114,8 +470,8 @@ impl BitcoinWallet {
         let compressed = private_key.is_compressed();
         Ok(Self {
             path: Some(path.to_string()),
+            key_origin: Some(key_origin.to_string()),
             password: password.map(String::from),
-            mnemonic: Some(mnemonic.to_string()),
             extended_private_key: Some(extended_private_key.to_string()),
             extended_public_key: Some(extended_public_key.to_string()),
             private_key: Some(private_key.to_string()),
@@ -128,13 +484,40 @@ impl BitcoinWallet {
         })
     }
 
+    pub fn to_backup<N: BitcoinNetwork, W: BitcoinWordlist, E: EntropySource + ?Sized>(
+        source: &mut E,
+        mnemonic: &str,
+        password: &str,
+    ) -> Result<Self, CLIError> {
+        BitcoinMnemonic::<N, W>::from_phrase(mnemonic).context("bitcoin", "parse mnemonic")?;
+        Ok(Self {
+            backup: Some(encrypt_backup(source, mnemonic, password).context("bitcoin", "encrypt backup")?),
+            ..Default::default()
+        })
+    }
+
+    pub fn to_seed_qr<N: BitcoinNetwork, W: BitcoinWordlist>(mnemonic: &str, compact: bool) -> Result<Self, CLIError> {
+        let mnemonic = BitcoinMnemonic::<N, W>::from_phrase(mnemonic).context("bitcoin", "parse mnemonic")?;
+        let (seed_qr, qr_code) = encode_seed_qr(&mnemonic, compact).context("bitcoin", "encode SeedQR")?;
+        Ok(Self {
+            seed_qr: Some(seed_qr),
+            qr_code: Some(qr_code),
+            ..Default::default()
+        })
+    }
+
     pub fn from_extended_private_key<N: BitcoinNetwork>(
         extended_private_key: &str,
         path: &Option<String>,
     ) -> Result<Self, CLIError> {
-        let mut extended_private_key = BitcoinExtendedPrivateKey::<N>::from_str(extended_private_key)?;
+        let mut extended_private_key = BitcoinExtendedPrivateKey::<N>::from_str(extended_private_key)
+            .context("bitcoin", "parse extended private key")?;
         if let Some(derivation_path) = path {
-            let derivation_path = BitcoinDerivationPath::from_str(&derivation_path)?;
+            let derivation_path = BitcoinDerivationPath::from_str(&derivation_path).context_with_input(
+                "bitcoin",
+                "parse derivation path",
+                derivation_path,
+            )?;
             extended_private_key = extended_private_key.derive(&derivation_path)?;
         }
         let extended_public_key = extended_private_key.to_extended_public_key();
@@ -160,9 +543,17 @@ impl BitcoinWallet {
         extended_public_key: &str,
         path: &Option<String>,
     ) -> Result<Self, CLIError> {
-        let mut extended_public_key = BitcoinExtendedPublicKey::<N>::from_str(extended_public_key)?;
+        let mut extended_public_key = BitcoinExtendedPublicKey::<N>::from_str(extended_public_key).context_with_input(
+            "bitcoin",
+            "parse extended public key",
+            extended_public_key,
+        )?;
         if let Some(derivation_path) = path {
-            let derivation_path = BitcoinDerivationPath::from_str(&derivation_path)?;
+            let derivation_path = BitcoinDerivationPath::from_str(&derivation_path).context_with_input(
+                "bitcoin",
+                "parse derivation path",
+                derivation_path,
+            )?;
             extended_public_key = extended_public_key.derive(&derivation_path)?;
         }
         let public_key = extended_public_key.to_public_key();
@@ -180,8 +571,217 @@ impl BitcoinWallet {
         })
     }
 
+    pub fn from_extended_public_key_children<N: BitcoinNetwork>(
+        extended_public_key: &str,
+        path: &Option<String>,
+        index: u32,
+        indices: u32,
+    ) -> Result<Vec<Self>, CLIError> {
+        let mut extended_public_key = BitcoinExtendedPublicKey::<N>::from_str(extended_public_key).context_with_input(
+            "bitcoin",
+            "parse extended public key",
+            extended_public_key,
+        )?;
+        if let Some(derivation_path) = path {
+            let derivation_path = BitcoinDerivationPath::from_str(&derivation_path).context_with_input(
+                "bitcoin",
+                "parse derivation path",
+                derivation_path,
+            )?;
+            extended_public_key = extended_public_key.derive(&derivation_path)?;
+        }
+
+        let parent_path = path.as_ref().map(String::as_str).unwrap_or("m");
+        let range = ChildIndexRange::from_count(ChildIndex::normal(index)?, indices)?;
+        let children = extended_public_key.derive_children(range.clone())?;
+        range
+            .zip(children)
+            .map(|(child_index, child)| {
+                let public_key = child.to_public_key();
+                let address = public_key.to_address(&child.format())?;
+                Ok(Self {
+                    path: Some(format!("{}/{}", parent_path, child_index)),
+                    extended_public_key: Some(child.to_string()),
+                    public_key: Some(public_key.to_string()),
+                    address: Some(address.to_string()),
+                    format: Some(address.format().to_string()),
+                    network: Some(N::NAME.to_string()),
+                    compressed: Some(public_key.is_compressed()),
+                    ..Default::default()
+                })
+            })
+            .collect()
+    }
+
+    pub fn to_extended_public_key_format<N: BitcoinNetwork>(
+        extended_public_key: &str,
+        format: &BitcoinFormat,
+    ) -> Result<Self, CLIError> {
+        let extended_public_key = BitcoinExtendedPublicKey::<N>::from_str(extended_public_key).context_with_input(
+            "bitcoin",
+            "parse extended public key",
+            extended_public_key,
+        )?;
+        let extended_public_key = extended_public_key.with_format(format);
+        Ok(Self {
+            extended_public_key: Some(extended_public_key.to_string()),
+            format: Some(format.to_string()),
+            network: Some(N::NAME.to_string()),
+            ..Default::default()
+        })
+    }
+
+    /// Returns the wallet of the Bitcoin Core import requests for the receive and change descriptors
+    /// of the account of the given extended public key, in its format unless one is given.
+    pub fn to_core_import<N: BitcoinNetwork>(
+        extended_public_key: &str,
+        key_origin: &Option<String>,
+        format: Option<&BitcoinFormat>,
+        timestamp: ImportTimestamp,
+        range: u32,
+        rpc: ImportRpc,
+    ) -> Result<Self, CLIError> {
+        let extended_public_key = BitcoinExtendedPublicKey::<N>::from_str(extended_public_key).context_with_input(
+            "bitcoin",
+            "parse extended public key",
+            extended_public_key,
+        )?;
+        let key_origin = match key_origin {
+            Some(key_origin) => {
+                Some(KeyOrigin::from_str(key_origin).context_with_input("bitcoin", "parse key origin", key_origin)?)
+            }
+            None => None,
+        };
+        let format = format.cloned().unwrap_or(extended_public_key.format());
+        Self::from_core_import(extended_public_key, key_origin, &format, timestamp, range, rpc)
+    }
+
+    /// Returns the wallet of the Bitcoin Core import requests for the receive and change descriptors
+    /// of the account of the mnemonic at the given account derivation path.
+    pub fn to_core_import_from_mnemonic<N: BitcoinNetwork, W: BitcoinWordlist>(
+        mnemonic: &str,
+        password: &Option<&str>,
+        path: &str,
+        format: &BitcoinFormat,
+        timestamp: ImportTimestamp,
+        range: u32,
+        rpc: ImportRpc,
+    ) -> Result<Self, CLIError> {
+        let mnemonic = BitcoinMnemonic::<N, W>::from_phrase(&mnemonic).context("bitcoin", "parse mnemonic")?;
+        let master_extended_private_key =
+            mnemonic.to_extended_private_key_with_progress(password.clone(), &mut print_seed_progress)?;
+        let derivation_path =
+            BitcoinDerivationPath::from_str(path).context_with_input("bitcoin", "parse derivation path", path)?;
+        let extended_private_key = master_extended_private_key.derive(&derivation_path)?;
+        let key_origin = master_extended_private_key.key_origin(&derivation_path)?;
+        let extended_public_key = extended_private_key.to_extended_public_key();
+        Ok(Self {
+            path: Some(path.to_string()),
+            ..Self::from_core_import(extended_public_key, Some(key_origin), format, timestamp, range, rpc)?
+        })
+    }
+
+    fn from_core_import<N: BitcoinNetwork>(
+        extended_public_key: BitcoinExtendedPublicKey<N>,
+        key_origin: Option<KeyOrigin>,
+        format: &BitcoinFormat,
+        timestamp: ImportTimestamp,
+        range: u32,
+        rpc: ImportRpc,
+    ) -> Result<Self, CLIError> {
+        let requests = BitcoinCoreImport::from_account(&extended_public_key, key_origin.clone(), format, timestamp)?
+            .into_iter()
+            .map(|request| request.with_range(0, range))
+            .collect::<Result<Vec<_>, _>>()?;
+        let descriptors = requests
+            .iter()
+            .map(|request| request.descriptor().to_string())
+            .collect();
+        let core_import = requests.iter().map(|request| request.to_json(rpc)).collect();
+        Ok(Self {
+            key_origin: key_origin.map(|key_origin| key_origin.to_string()),
+            extended_public_key: Some(extended_public_key.with_format(format).to_string()),
+            descriptors: Some(descriptors),
+            core_import: Some(serde_json::Value::Array(core_import)),
+            format: Some(format.to_string()),
+            network: Some(N::NAME.to_string()),
+            ..Default::default()
+        })
+    }
+
+    /// Returns the watch-only Electrum wallet of the account of the given extended public key,
+    /// in its format unless one is given.
+    pub fn to_electrum_wallet<N: BitcoinNetwork>(
+        extended_public_key: &str,
+        key_origin: &Option<String>,
+        format: Option<&BitcoinFormat>,
+    ) -> Result<Self, CLIError> {
+        let extended_public_key = BitcoinExtendedPublicKey::<N>::from_str(extended_public_key).context_with_input(
+            "bitcoin",
+            "parse extended public key",
+            extended_public_key,
+        )?;
+        let key_origin = match key_origin {
+            Some(key_origin) => {
+                Some(KeyOrigin::from_str(key_origin).context_with_input("bitcoin", "parse key origin", key_origin)?)
+            }
+            None => None,
+        };
+        let format = format.cloned().unwrap_or(extended_public_key.format());
+        let electrum_wallet =
+            ElectrumWallet::from_extended_public_key(&extended_public_key, key_origin.clone(), &format)?;
+        Ok(Self {
+            key_origin: key_origin.map(|key_origin| key_origin.to_string()),
+            extended_public_key: Some(extended_public_key.with_format(&format).to_string()),
+            electrum_wallet: Some(electrum_wallet.to_json()),
+            format: Some(format.to_string()),
+            network: Some(N::NAME.to_string()),
+            ..Default::default()
+        })
+    }
+
+    /// Returns the Electrum wallet of the account of the mnemonic at the given account derivation path,
+    /// without its extended private key if `watch_only` is enabled.
+    pub fn to_electrum_wallet_from_mnemonic<N: BitcoinNetwork, W: BitcoinWordlist>(
+        mnemonic: &str,
+        password: &Option<&str>,
+        path: &str,
+        format: &BitcoinFormat,
+        watch_only: bool,
+    ) -> Result<Self, CLIError> {
+        let mnemonic = BitcoinMnemonic::<N, W>::from_phrase(&mnemonic).context("bitcoin", "parse mnemonic")?;
+        let master_extended_private_key =
+            mnemonic.to_extended_private_key_with_progress(password.clone(), &mut print_seed_progress)?;
+        let derivation_path =
+            BitcoinDerivationPath::from_str(path).context_with_input("bitcoin", "parse derivation path", path)?;
+        let extended_private_key = master_extended_private_key
+            .derive(&derivation_path)?
+            .with_format(format);
+        let key_origin = master_extended_private_key.key_origin(&derivation_path)?;
+        let extended_public_key = extended_private_key.to_extended_public_key();
+        let electrum_wallet = match watch_only {
+            true => ElectrumWallet::from_extended_public_key(&extended_public_key, Some(key_origin.clone()), format)?,
+            false => {
+                ElectrumWallet::from_extended_private_key(&extended_private_key, Some(key_origin.clone()), format)?
+            }
+        };
+        Ok(Self {
+            path: Some(path.to_string()),
+            key_origin: Some(key_origin.to_string()),
+            extended_private_key: match watch_only {
+                true => None,
+                false => Some(extended_private_key.to_string()),
+            },
+            extended_public_key: Some(extended_public_key.to_string()),
+            electrum_wallet: Some(electrum_wallet.to_json()),
+            format: Some(format.to_string()),
+            network: Some(N::NAME.to_string()),
+            ..Default::default()
+        })
+    }
+
     pub fn from_private_key<N: BitcoinNetwork>(private_key: &str, format: &BitcoinFormat) -> Result<Self, CLIError> {
-        let private_key = BitcoinPrivateKey::<N>::from_str(private_key)?;
+        let private_key = BitcoinPrivateKey::<N>::from_str(private_key).context("bitcoin", "parse private key")?;
         let public_key = private_key.to_public_key();
         let address = public_key.to_address(format)?;
         Ok(Self {
@@ -195,8 +795,27 @@ impl BitcoinWallet {
         })
     }
 
+    /// Returns a wallet for the given WIF on its encoded network. If `network_specified` is true,
+    /// the WIF must instead belong to the network `N`.
+    pub fn from_wif<N: BitcoinNetwork>(
+        private_key: &str,
+        format: &BitcoinFormat,
+        network_specified: bool,
+    ) -> Result<Self, CLIError> {
+        let metadata = WifMetadata::from_wif(private_key).context("bitcoin", "parse private key")?;
+        match (network_specified, metadata.network) {
+            (true, _) => Self::from_private_key::<N>(private_key, format),
+            (false, BitcoinTestnet::NAME) => Self::from_private_key::<BitcoinTestnet>(private_key, format),
+            (false, _) => Self::from_private_key::<BitcoinMainnet>(private_key, format),
+        }
+    }
+
     pub fn from_public_key<N: BitcoinNetwork>(public_key: &str, format: &BitcoinFormat) -> Result<Self, CLIError> {
-        let public_key = BitcoinPublicKey::<N>::from_str(public_key)?;
+        let public_key = BitcoinPublicKey::<N>::from_str(public_key).context_with_input(
+            "bitcoin",
+            "parse public key",
+            public_key,
+        )?;
         let address = public_key.to_address(format)?;
         Ok(Self {
             public_key: Some(public_key.to_string()),
@@ -209,59 +828,119 @@ impl BitcoinWallet {
     }
 
     pub fn from_address<N: BitcoinNetwork>(address: &str) -> Result<Self, CLIError> {
-        let address = BitcoinAddress::<N>::from_str(address)?;
+        let address = BitcoinAddress::<N>::from_str(address).context_with_input("bitcoin", "parse address", address)?;
+        Ok(Self {
+            address: Some(address.to_string()),
+            network: Some(N::NAME.to_string()),
+            format: Some(address.format().to_string()),
+            ..Default::default()
+        })
+    }
+
+    pub fn to_multisig<N: BitcoinNetwork>(threshold: usize, public_keys: &[String]) -> Result<Vec<Self>, CLIError> {
+        let public_keys = public_keys
+            .iter()
+            .map(|public_key| {
+                BitcoinPublicKey::<N>::from_str(public_key).context_with_input(
+                    "bitcoin",
+                    "parse public key",
+                    public_key,
+                )
+            })
+            .collect::<Result<Vec<_>, CLIError>>()?;
+        let multisig = BitcoinMultisig::<N>::new(threshold, &public_keys)?;
+        let threshold = format!("{}-of-{}", multisig.threshold(), multisig.public_keys().len());
+        let witness_script = multisig.to_witness_script()?.to_string();
+
+        Ok(vec![
+            Self {
+                threshold: Some(threshold.clone()),
+                redeem_script: Some(hex::encode(multisig.to_redeem_script())),
+                address: Some(multisig.to_p2sh_address()?.to_string()),
+                format: Some("p2sh".into()),
+                network: Some(N::NAME.to_string()),
+                ..Default::default()
+            },
+            Self {
+                threshold: Some(threshold.clone()),
+                witness_script: Some(witness_script.clone()),
+                address: Some(multisig.to_p2sh_p2wsh_address()?.to_string()),
+                format: Some("p2sh_p2wsh".into()),
+                network: Some(N::NAME.to_string()),
+                ..Default::default()
+            },
+            Self {
+                threshold: Some(threshold),
+                witness_script: Some(witness_script),
+                address: Some(multisig.to_p2wsh_address()?.to_string()),
+                format: Some("p2wsh".into()),
+                network: Some(N::NAME.to_string()),
+                ..Default::default()
+            },
+        ])
+    }
+
+    pub fn to_signed_message<N: BitcoinNetwork>(
+        private_key: &str,
+        message: &str,
+        format: &BitcoinFormat,
+    ) -> Result<Self, CLIError> {
+        let private_key = BitcoinPrivateKey::<N>::from_str(private_key).context("bitcoin", "parse private key")?;
+        let address = private_key.to_address(format)?;
+        let signature = private_key.sign_message(message.as_bytes(), format)?;
         Ok(Self {
             address: Some(address.to_string()),
+            format: Some(address.format().to_string()),
             network: Some(N::NAME.to_string()),
+            message: Some(message.to_string()),
+            signature: Some(signature),
+            ..Default::default()
+        })
+    }
+
+    pub fn to_verified_message<N: BitcoinNetwork>(
+        address: &str,
+        signature: &str,
+        message: &str,
+    ) -> Result<Self, CLIError> {
+        let address = BitcoinAddress::<N>::from_str(address).context_with_input("bitcoin", "parse address", address)?;
+        let verified = address.verify_message(message.as_bytes(), signature)?;
+        Ok(Self {
+            address: Some(address.to_string()),
             format: Some(address.format().to_string()),
+            network: Some(N::NAME.to_string()),
+            message: Some(message.to_string()),
+            signature: Some(signature.to_string()),
+            verified: Some(verified),
             ..Default::default()
         })
     }
 
+    /// Sets `uri` to the BIP21 payment URI of the address of the wallet, if it has one.
+    pub fn set_uri<N: BitcoinNetwork>(&mut self) -> Result<(), CLIError> {
+        if let Some(address) = &self.address {
+            let address = BitcoinAddress::<N>::from_str(address)?;
+            self.uri = Some(BitcoinUri::new(address).to_string());
+        }
+        Ok(())
+    }
+
     pub fn to_raw_transaction<N: BitcoinNetwork>(
         inputs: &Vec<BitcoinInput>,
         outputs: &Vec<&str>,
         version: u32,
         lock_time: u32,
+        replaceable: bool,
+        data: Option<&str>,
     ) -> Result<Self, CLIError> {
-        let mut transaction_inputs = vec![];
-        for input in inputs {
-            let transaction_input = BitcoinTransactionInput::<N>::new(
-                hex::decode(&input.txid)?,
-                input.vout,
-                None,
-                None,
-                None,
-                None,
-                None,
-                SignatureHash::SIGHASH_ALL,
-            )?;
-            transaction_inputs.push(transaction_input);
-        }
-
-        let mut transaction_outputs = vec![];
-        for output in outputs {
-            let values: Vec<&str> = output.split(":").collect();
-            let address = BitcoinAddress::<N>::from_str(values[0])?;
-            transaction_outputs.push(BitcoinTransactionOutput::new(
-                &address,
-                BitcoinAmount::from_satoshi(i64::from_str(values[1])?)?,
-            )?);
-        }
-
-        let transaction_parameters = BitcoinTransactionParameters::<N> {
-            version,
-            inputs: transaction_inputs,
-            outputs: transaction_outputs,
-            lock_time,
-            segwit_flag: false,
-        };
-
+        let transaction_parameters =
+            to_transaction_parameters::<N>(inputs, outputs, version, lock_time, replaceable, data)?;
         let transaction = BitcoinTransaction::<N>::new(&transaction_parameters)?;
         let raw_transaction_hex = hex::encode(&transaction.to_transaction_bytes()?);
 
         Ok(Self {
             transaction_hex: Some(raw_transaction_hex),
+            warnings: to_warnings(&transaction_parameters),
             ..Default::default()
         })
     }
@@ -273,13 +952,29 @@ impl BitcoinWallet {
         let mut transaction = BitcoinTransaction::<N>::from_transaction_bytes(&hex::decode(transaction_hex)?)?;
 
         for input in inputs {
+            if let Some(sighash) = &input.sighash {
+                let sighash = SignatureHash::from_str(sighash)?;
+                let mut reverse_transaction_id = hex::decode(&input.txid)?;
+                reverse_transaction_id.reverse();
+                transaction = transaction.update_sighash(&reverse_transaction_id, input.vout, sighash);
+            }
+
             match (input.amount.clone(), input.address.clone(), input.private_key.clone()) {
                 (Some(amount), Some(address), Some(private_key)) => {
-                    let private_key = BitcoinPrivateKey::<N>::from_str(&private_key)?;
-                    let address = BitcoinAddress::<N>::from_str(&address)?;
+                    let private_key =
+                        BitcoinPrivateKey::<N>::from_str(&private_key).context("bitcoin", "parse private key")?;
+                    let address = BitcoinAddress::<N>::from_str(&address).context_with_input(
+                        "bitcoin",
+                        "parse address",
+                        &address,
+                    )?;
 
                     let redeem_script = match (input.redeem_script.clone(), address.format()) {
                         (Some(script), _) => Some(hex::decode(script)?),
+                        (None, BitcoinFormat::P2WSH) => match &input.witness_script {
+                            Some(script) => Some(hex::decode(script)?),
+                            None => None,
+                        },
                         (None, BitcoinFormat::P2SH_P2WPKH) => {
                             let mut redeem_script = vec![0x00, 0x14];
                             redeem_script.extend(&hash160(
@@ -295,11 +990,27 @@ impl BitcoinWallet {
                         None => None,
                     };
 
+                    // P2TR inputs are spent through the script path of a leaf if given, or else the key path
+                    let taproot_spend = match (&input.leaf_script, &input.control_block) {
+                        (Some(leaf_script), Some(control_block)) => {
+                            let control_block = TaprootControlBlock::new(&hex::decode(control_block)?)?;
+                            let leaf = TaprootLeaf::new(control_block.leaf_version(), &hex::decode(leaf_script)?)?;
+                            Some(TaprootSpend::ScriptPath(leaf, control_block))
+                        }
+                        (None, None) => None,
+                        _ => {
+                            return Err(TransactionError::InvalidInputs(
+                                "P2TR: specify both leafScript and controlBlock".into(),
+                            )
+                            .into())
+                        }
+                    };
+
                     let mut reverse_transaction_id = hex::decode(&input.txid)?;
                     reverse_transaction_id.reverse();
 
                     let outpoint = Outpoint::<N>::new(
-                        reverse_transaction_id,
+                        reverse_transaction_id.clone(),
                         input.vout,
                         Some(address),
                         Some(BitcoinAmount::from_satoshi(amount as i64)?),
@@ -308,21 +1019,692 @@ impl BitcoinWallet {
                     )?;
 
                     transaction = transaction.update_outpoint(outpoint);
+                    if let Some(taproot_spend) = taproot_spend {
+                        transaction =
+                            transaction.update_taproot_spend(&reverse_transaction_id, input.vout, taproot_spend);
+                    }
                     transaction = transaction.sign(&private_key)?;
                 }
-                _ => {}
-            }
-        }
+                // P2PK and bare multisig inputs have no address and are given by their scriptPubKey
+                (amount, None, Some(private_key)) if input.script_pub_key.is_some() => {
+                    let private_key =
+                        BitcoinPrivateKey::<N>::from_str(&private_key).context("bitcoin", "parse private key")?;
+                    let script_pub_key = match &input.script_pub_key {
+                        Some(script) => Some(hex::decode(script)?),
+                        None => None,
+                    };
+                    let amount = match amount {
+                        Some(amount) => Some(BitcoinAmount::from_satoshi(amount as i64)?),
+                        None => None,
+                    };
 
-        Ok(Self {
-            transaction_id: Some(transaction.to_transaction_id()?.to_string()),
-            transaction_hex: Some(hex::encode(&transaction.to_transaction_bytes()?)),
-            ..Default::default()
+                    let mut reverse_transaction_id = hex::decode(&input.txid)?;
+                    reverse_transaction_id.reverse();
+
+                    let outpoint =
+                        Outpoint::<N>::new(reverse_transaction_id, input.vout, None, amount, None, script_pub_key)?;
+
+                    transaction = transaction.update_outpoint(outpoint);
+                    transaction = transaction.sign(&private_key)?;
+                }
+                _ => {}
+            }
+        }
+
+        Ok(Self {
+            transaction_id: Some(transaction.to_transaction_id()?.to_string()),
+            transaction_hex: Some(hex::encode(&transaction.to_transaction_bytes()?)),
+            warnings: to_warnings(transaction.parameters()),
+            ..Default::default()
+        })
+    }
+
+    /// Returns the wallet of an unsigned PSBT of the given inputs and outputs,
+    /// in the given PSBT version with the given modifiable flags.
+    pub fn to_psbt<N: BitcoinNetwork>(
+        inputs: &Vec<BitcoinInput>,
+        outputs: &Vec<&str>,
+        version: u32,
+        lock_time: u32,
+        replaceable: bool,
+        data: Option<&str>,
+        psbt_version: u32,
+        modifiable: u8,
+    ) -> Result<Self, CLIError> {
+        let transaction_parameters =
+            to_transaction_parameters::<N>(inputs, outputs, version, lock_time, replaceable, data)?;
+        let mut psbt = BitcoinPsbt::<N>::new(&transaction_parameters)?.to_version(psbt_version)?;
+        if modifiable != 0 {
+            psbt.set_modifiable(modifiable)?;
+        }
+        Ok(Self {
+            warnings: to_warnings(&transaction_parameters),
+            ..Self::from_psbt(&psbt)
+        })
+    }
+
+    /// Returns the wallet of the PSBT with the UTXOs, scripts, and key origins of the given inputs,
+    /// adding the inputs to a version 2 PSBT whose inputs are modifiable.
+    pub fn to_updated_psbt<N: BitcoinNetwork>(psbt: &str, inputs: &Vec<BitcoinInput>) -> Result<Self, CLIError> {
+        let mut psbt = BitcoinPsbt::<N>::from_str(psbt)?;
+
+        for input in inputs {
+            let mut reverse_transaction_id = hex::decode(&input.txid)?;
+            reverse_transaction_id.reverse();
+            let vin = match psbt
+                .unsigned_transaction()?
+                .inputs
+                .iter()
+                .position(|transaction_input| {
+                    transaction_input.outpoint.reverse_transaction_id == reverse_transaction_id
+                        && transaction_input.outpoint.index == input.vout
+                }) {
+                Some(vin) => vin,
+                None if psbt.version() == 2 && psbt.modifiable() & PSBT_MODIFIABLE_INPUTS != 0 => {
+                    let transaction_input = BitcoinTransactionInput::<N>::new(
+                        hex::decode(&input.txid)?,
+                        input.vout,
+                        None,
+                        None,
+                        None,
+                        None,
+                        None,
+                        SignatureHash::SIGHASH_ALL,
+                    )?;
+                    psbt.add_input(&transaction_input, PsbtInput::default())?;
+                    psbt.inputs().len() - 1
+                }
+                None => continue,
+            };
+
+            let script_pub_key = match (&input.script_pub_key, &input.address) {
+                (Some(script), _) => Some(hex::decode(script)?),
+                (None, Some(address)) => {
+                    let address = BitcoinAddress::<N>::from_str(address).context_with_input(
+                        "bitcoin",
+                        "parse address",
+                        address,
+                    )?;
+                    Some(BitcoinTransactionOutput::new(&address, BitcoinAmount(0))?.script_pub_key)
+                }
+                (None, None) => None,
+            };
+
+            let psbt_input = &mut psbt.inputs_mut()[vin];
+            if let Some(transaction) = &input.non_witness_utxo {
+                psbt_input.non_witness_utxo = Some(hex::decode(transaction)?);
+            }
+            if let (Some(amount), Some(script_pub_key)) = (input.amount, script_pub_key) {
+                psbt_input.witness_utxo = Some(BitcoinTransactionOutput {
+                    amount: BitcoinAmount::from_satoshi(amount as i64)?,
+                    script_pub_key,
+                });
+            }
+            if let Some(script) = &input.redeem_script {
+                psbt_input.redeem_script = Some(hex::decode(script)?);
+            }
+            if let Some(script) = &input.witness_script {
+                psbt_input.witness_script = Some(hex::decode(script)?);
+            }
+            if let Some(sighash) = &input.sighash {
+                psbt_input.sighash_type = Some(SignatureHash::from_str(sighash)? as u32);
+            }
+            if let (Some(public_key), Some(origin)) = (&input.public_key, &input.origin) {
+                let public_key = BitcoinPublicKey::<N>::from_str(public_key).context_with_input(
+                    "bitcoin",
+                    "parse public key",
+                    public_key,
+                )?;
+                let public_key = match public_key.is_compressed() {
+                    true => public_key.to_secp256k1_public_key().serialize_compressed().to_vec(),
+                    false => public_key.to_secp256k1_public_key().serialize().to_vec(),
+                };
+                let origin = KeyOrigin::from_str(origin).context_with_input("bitcoin", "parse key origin", origin)?;
+                psbt_input.add_bip32_derivation(&public_key, origin);
+            }
+        }
+
+        Ok(Self::from_psbt(&psbt))
+    }
+
+    /// Returns the wallet of the PSBT signed by the given private key or master extended private key.
+    pub fn to_signed_psbt<N: BitcoinNetwork>(psbt: &str, private_key: &str) -> Result<Self, CLIError> {
+        let mut psbt = BitcoinPsbt::<N>::from_str(psbt)?;
+        match BitcoinPrivateKey::<N>::from_str(private_key) {
+            Ok(private_key) => psbt.sign(&private_key)?,
+            Err(_) => {
+                let extended_private_key =
+                    BitcoinExtendedPrivateKey::<N>::from_str(private_key).context("bitcoin", "parse private key")?;
+                psbt.sign_with_extended_private_key(&extended_private_key)?
+            }
+        };
+        Ok(Self::from_psbt(&psbt))
+    }
+
+    /// Returns the wallet of the PSBT combining the signatures and metadata of the given PSBTs.
+    pub fn to_combined_psbt<N: BitcoinNetwork>(psbts: &[String]) -> Result<Self, CLIError> {
+        let mut psbts = psbts.iter().map(|psbt| BitcoinPsbt::<N>::from_str(psbt));
+        let mut combined = match psbts.next() {
+            Some(psbt) => psbt?,
+            None => return Ok(Default::default()),
+        };
+        for psbt in psbts {
+            combined.combine(&psbt?)?;
+        }
+        combined.verify_signatures()?;
+        Ok(Self::from_psbt(&combined))
+    }
+
+    /// Returns the wallet of the finalized PSBT, with its extracted transaction if every input is finalized.
+    pub fn to_finalized_psbt<N: BitcoinNetwork>(psbt: &str) -> Result<Self, CLIError> {
+        let mut psbt = BitcoinPsbt::<N>::from_str(psbt)?;
+        psbt.finalize()?;
+        let mut wallet = Self::from_psbt(&psbt);
+        if psbt.is_finalized() {
+            let transaction = psbt.extract()?;
+            wallet.transaction_id = Some(transaction.to_transaction_id()?.to_string());
+            wallet.transaction_hex = Some(hex::encode(&transaction.to_transaction_bytes()?));
+        }
+        Ok(wallet)
+    }
+
+    /// Returns the P2WSH and P2SH-P2WSH wallets of the timelock script of the public key, or of the hash timelock
+    /// contract if given, locked until the absolute lock time or for the relative lock time in blocks.
+    pub fn to_timelock<N: BitcoinNetwork>(
+        after: Option<u32>,
+        older: Option<u32>,
+        public_keys: &[String],
+        htlc: &Option<(String, String, String)>,
+    ) -> Result<Vec<Self>, CLIError> {
+        let parse_public_key = |public_key: &String| {
+            BitcoinPublicKey::<N>::from_str(public_key).context_with_input("bitcoin", "parse public key", public_key)
+        };
+        let timelock = match (after, older) {
+            (Some(lock_time), _) => Timelock::Absolute(LockTime::from_consensus(lock_time)),
+            (None, Some(blocks)) if blocks <= u16::MAX as u32 => {
+                Timelock::Relative(RelativeLockTime::Blocks(blocks as u16))
+            }
+            (None, Some(blocks)) => return Err(TransactionError::InvalidLockTime(blocks).into()),
+            (None, None) => return Ok(vec![]),
+        };
+
+        let script = match (htlc, public_keys.first()) {
+            (Some((hash, recipient, refund)), _) => {
+                let hash = hex::decode(hash)?;
+                if hash.len() != 32 {
+                    return Err(TransactionError::InvalidInputs("32-byte SHA256 hash".into()).into());
+                }
+                let mut payment_hash = [0u8; 32];
+                payment_hash.copy_from_slice(&hash);
+                BitcoinTimelockScript::<N>::hash_timelock(
+                    payment_hash,
+                    &parse_public_key(recipient)?,
+                    &parse_public_key(refund)?,
+                    timelock,
+                )
+            }
+            (None, Some(public_key)) => BitcoinTimelockScript::<N>::Timelock(timelock, parse_public_key(public_key)?),
+            (None, None) => return Ok(vec![]),
+        };
+        let witness_script = script.to_witness_script()?.to_string();
+        let spend_paths: Vec<String> = script.spend_paths().iter().map(|path| path.to_string()).collect();
+
+        Ok(vec![
+            Self {
+                witness_script: Some(witness_script.clone()),
+                spend_paths: Some(spend_paths.clone()),
+                address: Some(script.to_p2sh_p2wsh_address()?.to_string()),
+                format: Some("p2sh_p2wsh".into()),
+                network: Some(N::NAME.to_string()),
+                ..Default::default()
+            },
+            Self {
+                witness_script: Some(witness_script),
+                spend_paths: Some(spend_paths),
+                address: Some(script.to_p2wsh_address()?.to_string()),
+                format: Some("p2wsh".into()),
+                network: Some(N::NAME.to_string()),
+                ..Default::default()
+            },
+        ])
+    }
+
+    /// Returns the wallet of the unsigned transaction of the given inputs and outputs in the exchange format,
+    /// with the UTXOs, scripts, and key origins of the inputs for signing on another machine.
+    pub fn to_exported_transaction<N: BitcoinNetwork>(
+        inputs: &Vec<BitcoinInput>,
+        outputs: &Vec<&str>,
+        version: u32,
+        lock_time: u32,
+        replaceable: bool,
+        data: Option<&str>,
+    ) -> Result<Self, CLIError> {
+        let mut transaction_parameters =
+            to_transaction_parameters::<N>(inputs, outputs, version, lock_time, replaceable, data)?;
+
+        for (vin, input) in inputs.iter().enumerate() {
+            let address = match &input.address {
+                Some(address) => Some(BitcoinAddress::<N>::from_str(address).context_with_input(
+                    "bitcoin",
+                    "parse address",
+                    address,
+                )?),
+                None => None,
+            };
+            let amount = match input.amount {
+                Some(amount) => Some(BitcoinAmount::from_satoshi(amount as i64)?),
+                None => None,
+            };
+            let redeem_script = match (&input.redeem_script, &input.witness_script) {
+                (Some(script), _) | (None, Some(script)) => Some(hex::decode(script)?),
+                (None, None) => None,
+            };
+            let script_pub_key = match &input.script_pub_key {
+                Some(script) => Some(hex::decode(script)?),
+                None => None,
+            };
+
+            let transaction_input = &mut transaction_parameters.inputs[vin];
+            transaction_input.outpoint = Outpoint::<N>::new(
+                transaction_input.outpoint.reverse_transaction_id.clone(),
+                input.vout,
+                address,
+                amount,
+                redeem_script,
+                script_pub_key,
+            )?;
+            if let Some(sighash) = &input.sighash {
+                transaction_input.sighash_code = SignatureHash::from_str(sighash)?;
+            }
+            if let (Some(leaf_script), Some(control_block)) = (&input.leaf_script, &input.control_block) {
+                let control_block = TaprootControlBlock::new(&hex::decode(control_block)?)?;
+                let leaf = TaprootLeaf::new(control_block.leaf_version(), &hex::decode(leaf_script)?)?;
+                transaction_input.taproot_spend = Some(TaprootSpend::ScriptPath(leaf, control_block));
+            }
+        }
+
+        let transaction = BitcoinTransaction::<N>::new(&transaction_parameters)?;
+        let mut exchange = BitcoinTransactionExchange::<N>::new(&transaction)?;
+        for (vin, input) in inputs.iter().enumerate() {
+            if let Some(origin) = &input.origin {
+                let origin = KeyOrigin::from_str(origin).context_with_input("bitcoin", "parse key origin", origin)?;
+                exchange = exchange.with_key_origin(vin, origin)?;
+            }
+        }
+
+        Ok(Self {
+            transaction_exchange: Some(from_str(&exchange.to_string())?),
+            virtual_size: transaction_parameters
+                .estimate_size()
+                .ok()
+                .map(|size| size.virtual_size()),
+            warnings: to_warnings(&transaction_parameters),
+            ..Default::default()
+        })
+    }
+
+    /// Returns the wallet of the exchanged transaction signed with a specified private key or master
+    /// extended private key, with the signed transaction once every input is signed.
+    pub fn to_imported_transaction<N: BitcoinNetwork>(
+        transaction_exchange: &str,
+        private_key: &str,
+    ) -> Result<Self, CLIError> {
+        let exchange = BitcoinTransactionExchange::<N>::from_str(transaction_exchange)?;
+        let exchange = match BitcoinPrivateKey::<N>::from_str(private_key) {
+            Ok(private_key) => exchange.sign(&private_key)?,
+            Err(_) => {
+                let extended_private_key =
+                    BitcoinExtendedPrivateKey::<N>::from_str(private_key).context("bitcoin", "parse private key")?;
+                exchange.sign_with_extended_private_key(&extended_private_key)?
+            }
+        };
+
+        let mut wallet = Self {
+            transaction_exchange: Some(from_str(&exchange.to_string())?),
+            ..Default::default()
+        };
+        if exchange.is_signed() {
+            let transaction = exchange.to_transaction()?;
+            wallet.transaction_id = Some(transaction.to_transaction_id()?.to_string());
+            wallet.transaction_hex = Some(hex::encode(&transaction.to_transaction_bytes()?));
+        }
+        Ok(wallet)
+    }
+
+    /// Returns the wallet of the given address with its balance and unspent outputs,
+    /// fetched from the Electrum server.
+    #[cfg(feature = "online")]
+    pub fn to_electrum_balance<N: BitcoinNetwork>(server: &str, address: &str) -> Result<Self, CLIError> {
+        let address = BitcoinAddress::<N>::from_str(address)?;
+        let mut client = ElectrumClient::connect(server)?;
+        let balance = client.get_balance(&address)?;
+        let utxos = client
+            .list_unspent(&address)?
+            .iter()
+            .map(|utxo| {
+                let height = match utxo.height {
+                    Some(height) => format!("height {}", height),
+                    None => "unconfirmed".into(),
+                };
+                format!(
+                    "{}:{} {} ({})",
+                    hex::encode(&utxo.transaction_id),
+                    utxo.index,
+                    utxo.amount,
+                    height
+                )
+            })
+            .collect();
+
+        Ok(Self {
+            address: Some(address.to_string()),
+            format: Some(address.format().to_string()),
+            network: Some(N::NAME.to_string()),
+            balance: Some(balance.confirmed.0),
+            unconfirmed_balance: Some(balance.unconfirmed.0),
+            utxos: Some(utxos),
+            ..Default::default()
+        })
+    }
+
+    /// Returns the wallet of the transaction id of the given signed transaction, broadcast by the Electrum server.
+    #[cfg(feature = "online")]
+    pub fn to_electrum_broadcast<N: BitcoinNetwork>(server: &str, transaction_hex: &str) -> Result<Self, CLIError> {
+        let transaction = BitcoinTransaction::<N>::from_str(transaction_hex)?;
+        let transaction_id = ElectrumClient::connect(server)?.broadcast(&transaction)?;
+
+        Ok(Self {
+            transaction_id: Some(transaction_id),
+            network: Some(N::NAME.to_string()),
+            ..Default::default()
         })
     }
+
+    /// Returns the wallet of the address at the given chain and index of an account of the Ledger device,
+    /// derived from the account extended public key and verified against the address displayed on the device.
+    #[cfg(feature = "ledger")]
+    pub fn to_ledger_address<N: BitcoinNetwork>(
+        device: &Option<String>,
+        format: &BitcoinFormat,
+        account: u32,
+        chain: u32,
+        index: u32,
+    ) -> Result<Self, CLIError> {
+        let mut client = Self::ledger_client(device)?;
+        let path = BitcoinDerivationPath::<N>::from_format(format, account, chain, index)?;
+        let account_path = BitcoinDerivationPath::<N>::from_vec(&path.to_vec()?[..3].to_vec())?;
+        let key_origin = KeyOrigin::new(client.master_fingerprint()?, &account_path)?;
+        let extended_public_key = client.extended_public_key(&account_path, false)?;
+        let address = extended_public_key
+            .derive(&BitcoinDerivationPath::from_str(&format!("m/{}/{}", chain, index))?)?
+            .to_address(format)?;
+
+        let policy = LedgerWalletPolicy::single_sig(format, &key_origin, &extended_public_key)?;
+        client.verify_address(&policy, None, chain == 1, index, &address)?;
+
+        Ok(Self {
+            path: Some(path.to_string()),
+            key_origin: Some(key_origin.to_string()),
+            extended_public_key: Some(extended_public_key.with_format(format).to_string()),
+            address: Some(address.to_string()),
+            format: Some(format.to_string()),
+            network: Some(N::NAME.to_string()),
+            verified: Some(true),
+            ..Default::default()
+        })
+    }
+
+    /// Returns the given PSBT with the signatures of the inputs of an account of the Ledger device,
+    /// once the transaction is approved on the device.
+    #[cfg(feature = "ledger")]
+    pub fn to_ledger_signed_psbt<N: BitcoinNetwork>(
+        device: &Option<String>,
+        psbt: &str,
+        format: &BitcoinFormat,
+        account: u32,
+    ) -> Result<Self, CLIError> {
+        let mut psbt = BitcoinPsbt::<N>::from_str(psbt)?;
+        let mut client = Self::ledger_client(device)?;
+        let policy = client.account_policy::<N>(format, account)?;
+        client.sign(&mut psbt, &policy, None)?;
+        Ok(Self::from_psbt(&psbt))
+    }
+
+    /// Returns a client of the Ledger device at the given path, or of the first Ledger device found.
+    #[cfg(feature = "ledger")]
+    fn ledger_client(device: &Option<String>) -> Result<LedgerClient<LedgerHid<std::fs::File>>, CLIError> {
+        let transport = match device {
+            Some(path) => LedgerHid::open_path(path)?,
+            None => LedgerHid::open()?,
+        };
+        Ok(LedgerClient::new(transport))
+    }
+
+    /// Returns the wallet of the address at the given chain and index of an account of the Trezor device,
+    /// derived from the account extended public key and verified against the address displayed on the device.
+    #[cfg(feature = "trezor")]
+    pub fn to_trezor_address<N: BitcoinNetwork>(
+        device: &Option<String>,
+        bridge: &Option<String>,
+        passphrase: &Option<String>,
+        format: &BitcoinFormat,
+        account: u32,
+        chain: u32,
+        index: u32,
+    ) -> Result<Self, CLIError> {
+        let mut client = Self::trezor_client(device, bridge, passphrase)?;
+        let path = BitcoinDerivationPath::<N>::from_format(format, account, chain, index)?;
+        let account_path = BitcoinDerivationPath::<N>::from_vec(&path.to_vec()?[..3].to_vec())?;
+        let key_origin = KeyOrigin::new(client.master_fingerprint::<N>()?, &account_path)?;
+        let extended_public_key = client.extended_public_key(&account_path, format, false)?;
+        let address = extended_public_key
+            .derive(&BitcoinDerivationPath::from_str(&format!("m/{}/{}", chain, index))?)?
+            .to_address(format)?;
+
+        client.verify_address(&path, &address)?;
+
+        Ok(Self {
+            path: Some(path.to_string()),
+            key_origin: Some(key_origin.to_string()),
+            extended_public_key: Some(extended_public_key.to_string()),
+            address: Some(address.to_string()),
+            format: Some(format.to_string()),
+            network: Some(N::NAME.to_string()),
+            verified: Some(true),
+            ..Default::default()
+        })
+    }
+
+    /// Returns the given PSBT with the signatures of the inputs derived from the Trezor device,
+    /// once the transaction is approved on the device.
+    #[cfg(feature = "trezor")]
+    pub fn to_trezor_signed_psbt<N: BitcoinNetwork>(
+        device: &Option<String>,
+        bridge: &Option<String>,
+        passphrase: &Option<String>,
+        psbt: &str,
+    ) -> Result<Self, CLIError> {
+        let mut psbt = BitcoinPsbt::<N>::from_str(psbt)?;
+        let mut client = Self::trezor_client(device, bridge, passphrase)?;
+        client.sign_psbt(&mut psbt)?;
+        Ok(Self::from_psbt(&psbt))
+    }
+
+    /// Returns a client of the Trezor device through Trezor Bridge at the given address, at the given path,
+    /// or of the first Trezor device found, using the hidden wallet of the given passphrase.
+    #[cfg(feature = "trezor")]
+    fn trezor_client(
+        device: &Option<String>,
+        bridge: &Option<String>,
+        passphrase: &Option<String>,
+    ) -> Result<TrezorClient<Box<dyn TrezorTransport>>, CLIError> {
+        let transport: Box<dyn TrezorTransport> = match (bridge, device) {
+            (Some(address), _) => Box::new(TrezorBridge::connect(address)?),
+            (None, Some(path)) => Box::new(TrezorHid::open_path(path)?),
+            (None, None) => Box::new(TrezorHid::open()?),
+        };
+        let client = TrezorClient::new(transport);
+        Ok(match passphrase {
+            Some(passphrase) => client.with_passphrase(passphrase),
+            None => client,
+        })
+    }
+
+    /// Returns the decoding of the given raw transaction, with the addresses of its outputs on network `N`.
+    pub fn to_decoded_transaction<N: BitcoinNetwork>(transaction_hex: &str) -> Result<Self, CLIError> {
+        let decoded_transaction = BitcoinDecodedTransaction::from_hex::<N>(transaction_hex)?;
+        Ok(Self {
+            decoded_transaction: Some(serde_json::to_value(&decoded_transaction)?),
+            ..Default::default()
+        })
+    }
+
+    /// Returns the wallet of the given PSBT, with the signatures of the inputs which are not finalized.
+    fn from_psbt<N: BitcoinNetwork>(psbt: &BitcoinPsbt<N>) -> Self {
+        let signatures: Vec<String> = (0..psbt.inputs().len())
+            .filter(|vin| !psbt.inputs()[*vin].is_finalized())
+            .filter_map(|vin| {
+                let status = psbt.signing_status(vin).ok()?;
+                let awaiting = match status.is_complete() || status.unsigned.is_empty() {
+                    true => "".to_owned(),
+                    false => {
+                        let unsigned: Vec<String> = status.unsigned.iter().map(hex::encode).collect();
+                        format!(", awaiting {}", unsigned.join(", "))
+                    }
+                };
+                Some(format!(
+                    "input {}: {} of {} signatures{}",
+                    vin,
+                    status.signed.len(),
+                    status.threshold,
+                    awaiting
+                ))
+            })
+            .collect();
+        Self {
+            psbt: Some(psbt.to_string()),
+            finalized: Some(psbt.is_finalized()),
+            signatures: Some(signatures).filter(|signatures| !signatures.is_empty()),
+            virtual_size: psbt.estimate_size().ok().map(|size| size.virtual_size()),
+            ..Default::default()
+        }
+    }
+}
+
+/// Returns the unsigned transaction parameters of the given inputs and outputs.
+fn to_transaction_parameters<N: BitcoinNetwork>(
+    inputs: &Vec<BitcoinInput>,
+    outputs: &Vec<&str>,
+    version: u32,
+    lock_time: u32,
+    replaceable: bool,
+    data: Option<&str>,
+) -> Result<BitcoinTransactionParameters<N>, CLIError> {
+    let mut transaction_inputs = vec![];
+    for input in inputs {
+        let transaction_input = BitcoinTransactionInput::<N>::new(
+            hex::decode(&input.txid)?,
+            input.vout,
+            None,
+            None,
+            None,
+            None,
+            None,
+            SignatureHash::SIGHASH_ALL,
+        )?;
+        transaction_inputs.push(transaction_input);
+    }
+
+    let mut transaction_outputs = vec![];
+    for output in outputs {
+        let values: Vec<&str> = output.split(":").collect();
+        let address =
+            BitcoinAddress::<N>::from_str(values[0]).context_with_input("bitcoin", "parse address", values[0])?;
+        transaction_outputs.push(BitcoinTransactionOutput::new(
+            &address,
+            BitcoinAmount::from_satoshi(i64::from_str(values[1])?)?,
+        )?);
+    }
+
+    let mut transaction_parameters = BitcoinTransactionParameters::<N> {
+        version,
+        inputs: transaction_inputs,
+        outputs: transaction_outputs,
+        lock_time,
+        segwit_flag: false,
+    };
+    if let Some(data) = data {
+        transaction_parameters = transaction_parameters.with_data(&hex::decode(data)?)?;
+    }
+    if lock_time != 0 {
+        transaction_parameters = transaction_parameters.with_lock_time(LockTime::from_consensus(lock_time));
+    }
+    if replaceable {
+        transaction_parameters = transaction_parameters.with_replace_by_fee();
+    }
+    // The sequence numbers specified for the inputs take precedence
+    for (vin, input) in inputs.iter().enumerate() {
+        if let Some(sequence) = input.sequence {
+            transaction_parameters = transaction_parameters.with_sequence(vin, sequence)?;
+        }
+    }
+    transaction_parameters.check_dust()?;
+    Ok(transaction_parameters)
+}
+
+/// Returns the reasons the given transaction would not be relayed by default, or `None` if it is standard.
+fn to_warnings<N: BitcoinNetwork>(parameters: &BitcoinTransactionParameters<N>) -> Option<Vec<String>> {
+    match parameters.standardness_warnings() {
+        Ok(warnings) if !warnings.is_empty() => Some(warnings.iter().map(|warning| warning.to_string()).collect()),
+        _ => None,
+    }
 }
 
 #[cfg_attr(tarpaulin, skip)]
+/// Prints the progress of a vanity search to the terminal, with the expected duration of the search
+/// at its current rate, or clears the progress if none is given. Nothing is printed unless stderr is a terminal.
+fn print_vanity_progress<N: BitcoinNetwork>(search: &BitcoinVanitySearch<N>, progress: Option<&VanityProgress>) {
+    if !std::io::stderr().is_terminal() {
+        return;
+    }
+    match progress {
+        Some(progress) => {
+            let rate = progress.rate();
+            let expected = match search.expected_duration(rate) {
+                Some(duration) => format!(", expected time {}", format_duration(duration)),
+                None => "".to_owned(),
+            };
+            eprint!(
+                "\r\x1b[KSearching... {} keys ({:.0} keys/s){}",
+                progress.attempts, rate, expected
+            )
+        }
+        None => eprint!("\r\x1b[K"),
+    }
+}
+
+/// Returns the given duration in its two largest units, e.g. "3h 25m".
+fn format_duration(duration: Duration) -> String {
+    const UNITS: [(&str, u64); 5] = [("y", 31_557_600), ("d", 86_400), ("h", 3_600), ("m", 60), ("s", 1)];
+
+    let seconds = duration.as_secs();
+    let largest = UNITS
+        .iter()
+        .map(|(_, unit)| *unit)
+        .find(|unit| seconds >= *unit)
+        .unwrap_or(1);
+    UNITS
+        .iter()
+        .filter(|(_, unit)| *unit <= largest)
+        .take(2)
+        .map(|(name, unit)| match *unit == largest {
+            true => format!("{}{}", seconds / unit, name),
+            false => format!("{}{}", seconds % largest / unit, name),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 impl Display for BitcoinWallet {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let output = [
@@ -330,6 +1712,10 @@ impl Display for BitcoinWallet {
                 Some(path) => format!("      {}                 {}\n", "Path".cyan().bold(), path),
                 _ => "".to_owned(),
             },
+            match &self.key_origin {
+                Some(key_origin) => format!("      {}           {}\n", "Key Origin".cyan().bold(), key_origin),
+                _ => "".to_owned(),
+            },
             match &self.password {
                 Some(password) => format!("      {}             {}\n", "Password".cyan().bold(), password),
                 _ => "".to_owned(),
@@ -338,6 +1724,39 @@ impl Display for BitcoinWallet {
                 Some(mnemonic) => format!("      {}             {}\n", "Mnemonic".cyan().bold(), mnemonic),
                 _ => "".to_owned(),
             },
+            match &self.birthday {
+                Some(birthday) => format!("      {}             {}\n", "Birthday".cyan().bold(), birthday),
+                _ => "".to_owned(),
+            },
+            match &self.backup {
+                Some(backup) => format!("      {}               {}\n", "Backup".cyan().bold(), backup),
+                _ => "".to_owned(),
+            },
+            match &self.seed_qr {
+                Some(seed_qr) => format!("      {}               {}\n", "SeedQR".cyan().bold(), seed_qr),
+                _ => "".to_owned(),
+            },
+            match &self.qr_code {
+                Some(qr_code) => format!("\n{}\n", qr_code),
+                _ => "".to_owned(),
+            },
+            match &self.group_threshold {
+                Some(group_threshold) => {
+                    format!("      {}      {}\n", "Group Threshold".cyan().bold(), group_threshold)
+                }
+                _ => "".to_owned(),
+            },
+            match &self.shares {
+                Some(shares) => shares
+                    .iter()
+                    .map(|share| {
+                        let label = format!("Group {} Share {}", share.group, share.member);
+                        let padding = " ".repeat(21 - label.len());
+                        format!("      {}{}{}\n", label.cyan().bold(), padding, share.share)
+                    })
+                    .collect(),
+                _ => "".to_owned(),
+            },
             match &self.extended_private_key {
                 Some(extended_private_key) => format!(
                     "      {} {}\n",
@@ -354,18 +1773,71 @@ impl Display for BitcoinWallet {
                 ),
                 _ => "".to_owned(),
             },
+            match &self.descriptors {
+                Some(descriptors) => descriptors
+                    .iter()
+                    .map(|descriptor| format!("      {}           {}\n", "Descriptor".cyan().bold(), descriptor))
+                    .collect(),
+                _ => "".to_owned(),
+            },
+            match &self.core_import {
+                Some(core_import) => format!("      {}          {}\n", "Core Import".cyan().bold(), core_import),
+                _ => "".to_owned(),
+            },
+            match &self.electrum_wallet {
+                Some(electrum_wallet) => {
+                    format!("      {}      {}\n", "Electrum Wallet".cyan().bold(), electrum_wallet)
+                }
+                _ => "".to_owned(),
+            },
             match &self.private_key {
                 Some(private_key) => format!("      {}          {}\n", "Private Key".cyan().bold(), private_key),
                 _ => "".to_owned(),
             },
+            match &self.encrypted_private_key {
+                Some(encrypted_private_key) => {
+                    format!(
+                        "      {}        {}\n",
+                        "Encrypted Key".cyan().bold(),
+                        encrypted_private_key
+                    )
+                }
+                _ => "".to_owned(),
+            },
             match &self.public_key {
                 Some(public_key) => format!("      {}           {}\n", "Public Key".cyan().bold(), public_key),
                 _ => "".to_owned(),
             },
+            match &self.threshold {
+                Some(threshold) => format!("      {}            {}\n", "Threshold".cyan().bold(), threshold),
+                _ => "".to_owned(),
+            },
+            match &self.redeem_script {
+                Some(redeem_script) => format!("      {}        {}\n", "Redeem Script".cyan().bold(), redeem_script),
+                _ => "".to_owned(),
+            },
+            match &self.witness_script {
+                Some(witness_script) => {
+                    format!("      {}       {}\n", "Witness Script".cyan().bold(), witness_script)
+                }
+                _ => "".to_owned(),
+            },
+            match &self.spend_paths {
+                Some(spend_paths) => format!(
+                    "      {}          {}\n",
+                    "Spend Paths".cyan().bold(),
+                    spend_paths.join("; ")
+                ),
+                _ => "".to_owned(),
+            },
             match &self.address {
                 Some(address) => format!("      {}              {}\n", "Address".cyan().bold(), address),
                 _ => "".to_owned(),
             },
+            match &self.uri {
+                Some(uri) => format!("      {}                  {}\n", "URI".cyan().bold(), uri),
+                _ => "".to_owned(),
+            },
             match &self.format {
                 Some(format) => format!("      {}               {}\n", "Format".cyan().bold(), format),
                 _ => "".to_owned(),
@@ -378,6 +1850,70 @@ impl Display for BitcoinWallet {
                 Some(compressed) => format!("      {}           {}\n", "Compressed".cyan().bold(), compressed),
                 _ => "".to_owned(),
             },
+            match &self.balance {
+                Some(balance) => format!("      {}              {}\n", "Balance".cyan().bold(), balance),
+                _ => "".to_owned(),
+            },
+            match &self.unconfirmed_balance {
+                Some(unconfirmed_balance) => format!(
+                    "      {}  {}\n",
+                    "Unconfirmed Balance".cyan().bold(),
+                    unconfirmed_balance
+                ),
+                _ => "".to_owned(),
+            },
+            match &self.utxos {
+                Some(utxos) => format!("      {}                {}\n", "UTXOs".cyan().bold(), utxos.join("; ")),
+                _ => "".to_owned(),
+            },
+            match &self.message {
+                Some(message) => format!("      {}              {}\n", "Message".cyan().bold(), message),
+                _ => "".to_owned(),
+            },
+            match &self.signature {
+                Some(signature) => format!("      {}            {}\n", "Signature".cyan().bold(), signature),
+                _ => "".to_owned(),
+            },
+            match &self.verified {
+                Some(verified) => format!("      {}             {}\n", "Verified".cyan().bold(), verified),
+                _ => "".to_owned(),
+            },
+            match &self.psbt {
+                Some(psbt) => format!("      {}                 {}\n", "PSBT".cyan().bold(), psbt),
+                _ => "".to_owned(),
+            },
+            match &self.finalized {
+                Some(finalized) => format!("      {}            {}\n", "Finalized".cyan().bold(), finalized),
+                _ => "".to_owned(),
+            },
+            match &self.signatures {
+                Some(signatures) => format!(
+                    "      {}           {}\n",
+                    "Signatures".cyan().bold(),
+                    signatures.join("; ")
+                ),
+                _ => "".to_owned(),
+            },
+            match &self.virtual_size {
+                Some(virtual_size) => format!("      {}         {}\n", "Virtual Size".cyan().bold(), virtual_size),
+                _ => "".to_owned(),
+            },
+            match &self.warnings {
+                Some(warnings) => format!(
+                    "      {}             {}\n",
+                    "Warnings".cyan().bold(),
+                    warnings.join("; ")
+                ),
+                _ => "".to_owned(),
+            },
+            match &self.transaction_exchange {
+                Some(transaction_exchange) => format!(
+                    "      {} {}\n",
+                    "Transaction Exchange".cyan().bold(),
+                    transaction_exchange
+                ),
+                _ => "".to_owned(),
+            },
             match &self.transaction_id {
                 Some(transaction_id) => format!("      {}       {}\n", "Transaction Id".cyan().bold(), transaction_id),
                 _ => "".to_owned(),
@@ -388,6 +1924,14 @@ impl Display for BitcoinWallet {
                 }
                 _ => "".to_owned(),
             },
+            match &self.decoded_transaction {
+                Some(decoded_transaction) => format!(
+                    "      {}  {}\n",
+                    "Decoded Transaction".cyan().bold(),
+                    decoded_transaction
+                ),
+                _ => "".to_owned(),
+            },
         ]
         .concat();
 
@@ -410,12 +1954,26 @@ pub struct BitcoinInput {
     pub script_pub_key: Option<String>,
     #[serde(rename(deserialize = "redeemScript"))]
     pub redeem_script: Option<String>,
+    #[serde(rename(deserialize = "witnessScript"))]
+    pub witness_script: Option<String>,
+    #[serde(rename(deserialize = "nonWitnessUtxo"))]
+    pub non_witness_utxo: Option<String>,
+    #[serde(rename(deserialize = "publickey"))]
+    pub public_key: Option<String>,
+    pub origin: Option<String>,
+    pub sighash: Option<String>,
+    pub sequence: Option<u32>,
+    #[serde(rename(deserialize = "leafScript"))]
+    pub leaf_script: Option<String>,
+    #[serde(rename(deserialize = "controlBlock"))]
+    pub control_block: Option<String>,
 }
 
 /// Represents options for a Bitcoin wallet
 #[derive(Clone, Debug, Serialize)]
 pub struct BitcoinOptions {
     // Standard command
+    bip38: Option<String>,
     count: usize,
     format: BitcoinFormat,
     json: bool,
@@ -423,32 +1981,89 @@ pub struct BitcoinOptions {
     subcommand: Option<String>,
     // HD and Import HD subcommands
     account: u32,
+    aezeed: bool,
+    aezeed_mnemonic: Option<String>,
     chain: u32,
+    coin: Option<String>,
     derivation: String,
+    dice: Option<String>,
     extended_private_key: Option<String>,
     extended_public_key: Option<String>,
+    group_threshold: u8,
     index: u32,
+    indices: u32,
     language: String,
     mnemonic: Option<String>,
     password: Option<String>,
     path: Option<String>,
+    preset: Option<String>,
+    recover: Option<String>,
+    seed_qr: Option<String>,
+    shares: Vec<String>,
+    slip39: Option<String>,
+    watch_only: bool,
     word_count: u8,
     // Import subcommand
     address: Option<String>,
+    network_specified: bool,
     private: Option<String>,
     public: Option<String>,
+    // Export Backup and Import Backup subcommands
+    backup: Option<String>,
+    backup_password: Option<String>,
+    // Export Descriptors subcommand
+    format_specified: bool,
+    key_origin: Option<String>,
+    range: u32,
+    rpc: String,
+    timestamp: String,
+    // Export SeedQR subcommand
+    compact: bool,
+    // Electrum subcommand
+    server: Option<String>,
+    // Ledger and Trezor subcommands
+    device: Option<String>,
+    // Trezor subcommand
+    bridge: Option<String>,
+    passphrase: Option<String>,
+    // Message subcommand
+    message: Option<String>,
+    signature: Option<String>,
+    // Multisig subcommand
+    public_keys: Vec<String>,
+    threshold: Option<usize>,
+    // Timelock subcommand
+    after: Option<u32>,
+    htlc: Option<(String, String, String)>,
+    older: Option<u32>,
+    // Vanity subcommand
+    threads: usize,
+    vanity_prefix: Option<String>,
+    vanity_regex: Option<String>,
+    // PSBT subcommand
+    modifiable: u8,
+    psbt: Option<String>,
+    psbt_version: Option<u32>,
+    psbts: Vec<String>,
     // Transaction subcommand
+    data: Option<String>,
+    export: bool,
+    transaction_exchange: Option<String>,
     transaction_inputs: Option<String>,
     transaction_hex: Option<String>,
     transaction_outputs: Option<String>,
     lock_time: Option<u32>,
+    replaceable: bool,
     version: Option<u32>,
+    // URI flag
+    uri: bool,
 }
 
 impl Default for BitcoinOptions {
     fn default() -> Self {
         Self {
             // Standard command
+            bip38: None,
             count: 1,
             format: BitcoinFormat::P2PKH,
             json: false,
@@ -456,26 +2071,82 @@ impl Default for BitcoinOptions {
             subcommand: None,
             // HD and Import HD subcommands
             account: 0,
+            aezeed: false,
+            aezeed_mnemonic: None,
             chain: 0,
+            coin: None,
             derivation: "bip32".into(),
+            dice: None,
             extended_private_key: None,
             extended_public_key: None,
+            group_threshold: 1,
             index: 0,
+            indices: 1,
             language: "english".into(),
             mnemonic: None,
             password: None,
             path: None,
+            preset: None,
+            recover: None,
+            seed_qr: None,
+            shares: vec![],
+            slip39: None,
+            watch_only: false,
             word_count: 12,
             // Import subcommand
             address: None,
+            network_specified: false,
             private: None,
             public: None,
+            // Export Backup and Import Backup subcommands
+            backup: None,
+            backup_password: None,
+            // Export Descriptors subcommand
+            format_specified: false,
+            key_origin: None,
+            range: DEFAULT_IMPORT_RANGE_END,
+            rpc: "importdescriptors".into(),
+            timestamp: "now".into(),
+            // Export SeedQR subcommand
+            compact: false,
+            // Electrum subcommand
+            server: None,
+            // Ledger and Trezor subcommands
+            device: None,
+            // Trezor subcommand
+            bridge: None,
+            passphrase: None,
+            // Message subcommand
+            message: None,
+            signature: None,
+            // Multisig subcommand
+            public_keys: vec![],
+            threshold: None,
+            // Timelock subcommand
+            after: None,
+            htlc: None,
+            older: None,
+            // Vanity subcommand
+            threads: 0,
+            vanity_prefix: None,
+            vanity_regex: None,
+            // PSBT subcommand
+            modifiable: 0,
+            psbt: None,
+            psbt_version: None,
+            psbts: vec![],
             // Transaction subcommand
+            data: None,
+            export: false,
+            transaction_exchange: None,
             transaction_inputs: None,
             transaction_hex: None,
             transaction_outputs: None,
             lock_time: None,
+            replaceable: false,
             version: None,
+            // URI flag
+            uri: false,
         }
     }
 }
@@ -485,23 +2156,71 @@ impl BitcoinOptions {
         options.iter().for_each(|option| match *option {
             "account" => self.account(clap::value_t!(arguments.value_of(*option), u32).ok()),
             "address" => self.address(arguments.value_of(option)),
+            "after" => self.after(clap::value_t!(arguments.value_of(*option), u32).ok()),
+            "aezeed" => self.aezeed(arguments.is_present(option), arguments.value_of(option)),
+            "backup" => self.backup(arguments.value_of(option)),
+            "backup password" => self.backup_password(arguments.value_of(option)),
+            "bip38" => self.bip38(arguments.value_of(option)),
+            "bridge" => self.bridge(arguments.value_of(option)),
+            "broadcast" => self.transaction(arguments.value_of(option)),
             "chain" => self.chain(clap::value_t!(arguments.value_of(*option), u32).ok()),
+            "coin" => self.coin(arguments.value_of(option)),
+            "combine" => self.combine_psbt(arguments.values_of(option)),
+            "compact" => self.compact(arguments.is_present(option)),
             "count" => self.count(clap::value_t!(arguments.value_of(*option), usize).ok()),
+            "create" => self.create_raw_transaction(arguments.values_of(option)),
             "createrawtransaction" => self.create_raw_transaction(arguments.values_of(option)),
+            "data" => self.data(arguments.value_of(option)),
             "derivation" => self.derivation(arguments.value_of(option)),
+            "device" => self.device(arguments.value_of(option)),
+            "dice" => self.dice(arguments.value_of(option)),
+            "export" => self.export(arguments.is_present(option)),
             "extended private" => self.extended_private(arguments.value_of(option)),
             "extended public" => self.extended_public(arguments.value_of(option)),
+            "finalize" => self.finalize_psbt(arguments.value_of(option)),
             "format" => self.format(arguments.value_of(option)),
+            "group threshold" => self.group_threshold(clap::value_t!(arguments.value_of(*option), u8).ok()),
+            "htlc" => self.htlc(arguments.values_of(option)),
+            "import" => self.import_transaction(arguments.values_of(option)),
             "json" => self.json(arguments.is_present(option)),
             "index" => self.index(clap::value_t!(arguments.value_of(*option), u32).ok()),
+            "indices" => self.indices(clap::value_t!(arguments.value_of(*option), u32).ok()),
             "language" => self.language(arguments.value_of(option)),
             "lock time" => self.lock_time(clap::value_t!(arguments.value_of(*option), u32).ok()),
             "mnemonic" => self.mnemonic(arguments.value_of(option)),
+            "modifiable" => self.modifiable(arguments.values_of(option)),
             "network" => self.network(arguments.value_of(option)),
+            "older" => self.older(clap::value_t!(arguments.value_of(*option), u32).ok()),
+            "origin" => self.origin(arguments.value_of(option)),
             "password" => self.password(arguments.value_of(option)),
+            "prefix" => self.vanity_prefix(arguments.value_of(option)),
+            "preset" => self.preset(arguments.value_of(option)),
             "private" => self.private(arguments.value_of(option)),
+            "passphrase" => self.passphrase(arguments.value_of(option)),
+            "psbt" => self.psbt(arguments.value_of(option)),
             "public" => self.public(arguments.value_of(option)),
+            "psbt version" => self.psbt_version(clap::value_t!(arguments.value_of(*option), u32).ok()),
+            "public key" => self.public_key(arguments.values_of(option)),
+            "range" => self.range(clap::value_t!(arguments.value_of(*option), u32).ok()),
+            "recover" => self.recover(arguments.value_of(option)),
+            "regex" => self.vanity_regex(arguments.value_of(option)),
+            "replaceable" => self.replaceable(arguments.is_present(option)),
+            "rpc" => self.rpc(arguments.value_of(option)),
+            "seedqr" => self.seed_qr(arguments.value_of(option)),
+            "server" => self.server(arguments.value_of(option)),
+            "share" => self.share(arguments.values_of(option)),
+            "signmessage" => self.sign_message(arguments.values_of(option)),
+            "sign" => self.sign_psbt(arguments.values_of(option)),
             "signrawtransaction" => self.sign_raw_transaction(arguments.values_of(option)),
+            "slip39" => self.slip39(arguments.value_of(option)),
+            "threads" => self.threads(clap::value_t!(arguments.value_of(*option), usize).ok()),
+            "threshold" => self.threshold(clap::value_t!(arguments.value_of(*option), usize).ok()),
+            "transaction" => self.transaction(arguments.value_of(option)),
+            "update" => self.update_psbt(arguments.values_of(option)),
+            "uri" => self.uri(arguments.is_present(option)),
+            "timestamp" => self.timestamp(arguments.value_of(option)),
+            "verifymessage" => self.verify_message(arguments.values_of(option)),
+            "watch only" => self.watch_only(arguments.is_present(option)),
             "word count" => self.word_count(clap::value_t!(arguments.value_of(*option), u8).ok()),
             "version" => self.version(clap::value_t!(arguments.value_of(*option), u32).ok()),
             _ => (),
@@ -524,6 +2243,55 @@ impl BitcoinOptions {
         }
     }
 
+    /// Sets `after` to the specified absolute lock time of a timelock, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn after(&mut self, argument: Option<u32>) {
+        if let Some(lock_time) = argument {
+            self.after = Some(lock_time);
+        }
+    }
+
+    /// Sets `aezeed` to generate an aezeed cipher seed, or `aezeed_mnemonic` to the specified
+    /// aezeed mnemonic to import, overriding their previous state.
+    fn aezeed(&mut self, is_present: bool, argument: Option<&str>) {
+        self.aezeed = is_present;
+        if let Some(mnemonic) = argument {
+            self.aezeed_mnemonic = Some(mnemonic.to_string());
+        }
+    }
+
+    /// Imports a wallet for the specified encrypted backup, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn backup(&mut self, argument: Option<&str>) {
+        if let Some(backup) = argument {
+            self.backup = Some(backup.to_string());
+        }
+    }
+
+    /// Sets `backup_password` to the specified backup password, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn backup_password(&mut self, argument: Option<&str>) {
+        if let Some(password) = argument {
+            self.backup_password = Some(password.to_string());
+        }
+    }
+
+    /// Sets `bip38` to the specified BIP38 passphrase, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn bip38(&mut self, argument: Option<&str>) {
+        if let Some(passphrase) = argument {
+            self.bip38 = Some(passphrase.to_string());
+        }
+    }
+
+    /// Sets `bridge` to the specified address of Trezor Bridge, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn bridge(&mut self, argument: Option<&str>) {
+        if let Some(address) = argument {
+            self.bridge = Some(address.to_string());
+        }
+    }
+
     /// Sets `chain` to the specified chain index, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn chain(&mut self, argument: Option<u32>) {
@@ -532,6 +2300,27 @@ impl BitcoinOptions {
         }
     }
 
+    /// Sets `coin` to the specified coin flips, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn coin(&mut self, argument: Option<&str>) {
+        if let Some(flips) = argument {
+            self.coin = Some(flips.to_string());
+        }
+    }
+
+    /// Sets `psbts` to the specified PSBTs to combine, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn combine_psbt(&mut self, argument: Option<Values>) {
+        if let Some(psbts) = argument {
+            self.psbts = psbts.map(String::from).collect();
+        }
+    }
+
+    /// Sets `compact` to the specified boolean value, overriding its previous state.
+    fn compact(&mut self, argument: bool) {
+        self.compact = argument;
+    }
+
     /// Sets `count` to the specified count, overriding its previous state.
     fn count(&mut self, argument: Option<usize>) {
         if let Some(count) = argument {
@@ -549,138 +2338,454 @@ impl BitcoinOptions {
         }
     }
 
-    /// Sets `derivation` to the specified derivation, overriding its previous state.
-    /// If `derivation` is `\"custom\"`, then `path` is set to the specified path.
+    /// Sets `data` to the specified hex data of an OP_RETURN output, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn data(&mut self, argument: Option<&str>) {
+        if let Some(data) = argument {
+            self.data = Some(data.to_string());
+        }
+    }
+
+    /// Sets `derivation` to the specified derivation, overriding its previous state.
+    /// If `derivation` is `\"custom\"`, then `path` is set to the specified path.
+    /// If the specified argument is `None`, then no change occurs.
+    fn derivation(&mut self, argument: Option<&str>) {
+        match argument {
+            Some("bip32") => self.derivation = "bip32".into(),
+            Some("bip44") => self.derivation = "bip44".into(),
+            Some("bip49") => self.derivation = "bip49".into(),
+            Some("bip84") => self.derivation = "bip84".into(),
+            Some("bip86") => self.derivation = "bip86".into(),
+            Some(custom) => {
+                self.derivation = "custom".into();
+                self.path = Some(custom.to_string());
+            }
+            _ => (),
+        };
+    }
+
+    /// Sets `device` to the specified path of a Ledger or Trezor device, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn device(&mut self, argument: Option<&str>) {
+        if let Some(device) = argument {
+            self.device = Some(device.to_string());
+        }
+    }
+
+    /// Sets `dice` to the specified dice rolls, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn dice(&mut self, argument: Option<&str>) {
+        if let Some(rolls) = argument {
+            self.dice = Some(rolls.to_string());
+        }
+    }
+
+    /// Sets `export` to the specified boolean value, overriding its previous state.
+    fn export(&mut self, argument: bool) {
+        self.export = argument;
+    }
+
+    /// Sets `extended_private_key` to the specified extended private key, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn extended_private(&mut self, argument: Option<&str>) {
+        if let Some(extended_private_key) = argument {
+            self.extended_private_key = Some(extended_private_key.to_string());
+        }
+    }
+
+    /// Sets `extended_public_key` to the specified extended public key, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn extended_public(&mut self, argument: Option<&str>) {
+        if let Some(extended_public_key) = argument {
+            self.extended_public_key = Some(extended_public_key.to_string());
+        }
+    }
+
+    /// Sets `psbt` to the specified PSBT to finalize, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn finalize_psbt(&mut self, argument: Option<&str>) {
+        if let Some(psbt) = argument {
+            self.psbt = Some(psbt.to_string());
+        }
+    }
+
+    /// Sets `format` to the specified format, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn format(&mut self, argument: Option<&str>) {
+        match argument {
+            Some("legacy") => self.format = BitcoinFormat::P2PKH,
+            Some("segwit") => self.format = BitcoinFormat::P2SH_P2WPKH,
+            Some("bech32") => self.format = BitcoinFormat::Bech32,
+            Some("taproot") => self.format = BitcoinFormat::P2TR,
+            Some("xpub") => self.format = BitcoinFormat::P2PKH,
+            Some("ypub") => self.format = BitcoinFormat::P2SH_P2WPKH,
+            Some("zpub") => self.format = BitcoinFormat::Bech32,
+            Some("Zpub") => self.format = BitcoinFormat::P2WSH,
+            _ => (),
+        };
+    }
+
+    /// Sets `group_threshold` to the specified SLIP-39 group threshold, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn group_threshold(&mut self, argument: Option<u8>) {
+        if let Some(group_threshold) = argument {
+            self.group_threshold = group_threshold;
+        }
+    }
+
+    /// Sets `htlc` to the specified hash, recipient public key, and refund public key of a hash timelock contract,
+    /// overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn htlc(&mut self, argument: Option<Values>) {
+        if let Some(htlc_parameters) = argument {
+            let params: Vec<&str> = htlc_parameters.collect();
+            self.htlc = Some((params[0].to_string(), params[1].to_string(), params[2].to_string()));
+        }
+    }
+
+    /// Sets `transaction_exchange` and `private` to the specified exchanged transaction and private key,
+    /// overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn import_transaction(&mut self, argument: Option<Values>) {
+        if let Some(transaction_parameters) = argument {
+            let params: Vec<&str> = transaction_parameters.collect();
+            self.transaction_exchange = Some(params[0].to_string());
+            self.private = Some(params[1].to_string());
+        }
+    }
+
+    /// Sets `index` to the specified index, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn index(&mut self, argument: Option<u32>) {
+        if let Some(index) = argument {
+            self.index = index;
+        }
+    }
+
+    /// Sets `indices` to the specified number of indices, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn indices(&mut self, argument: Option<u32>) {
+        if let Some(indices) = argument {
+            self.indices = indices;
+        }
+    }
+
+    /// Sets `json` to the specified boolean value, overriding its previous state.
+    fn json(&mut self, argument: bool) {
+        self.json = argument;
+    }
+
+    /// Sets `language` to the specified language, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn language(&mut self, argument: Option<&str>) {
+        match argument {
+            Some("chinese_simplified") => self.language = "chinese_simplified".into(),
+            Some("chinese_traditional") => self.language = "chinese_traditional".into(),
+            Some("czech") => self.language = "czech".into(),
+            Some("english") => self.language = "english".into(),
+            Some("french") => self.language = "french".into(),
+            Some("italian") => self.language = "italian".into(),
+            Some("japanese") => self.language = "japanese".into(),
+            Some("korean") => self.language = "korean".into(),
+            Some("portuguese") => self.language = "portuguese".into(),
+            Some("spanish") => self.language = "spanish".into(),
+            _ => (),
+        };
+    }
+
+    /// Sets `lock_time` to the specified transaction lock time, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn lock_time(&mut self, argument: Option<u32>) {
+        if let Some(lock_time) = argument {
+            self.lock_time = Some(lock_time);
+        }
+    }
+
+    /// Sets `mnemonic` to the specified mnemonic, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn mnemonic(&mut self, argument: Option<&str>) {
+        if let Some(mnemonic) = argument {
+            self.mnemonic = Some(mnemonic.to_string());
+        }
+    }
+
+    /// Sets `modifiable` to the flags of the specified inputs or outputs, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn modifiable(&mut self, argument: Option<Values>) {
+        if let Some(modifiable) = argument {
+            self.modifiable = modifiable.fold(0, |flags, modifiable| match modifiable {
+                "inputs" => flags | PSBT_MODIFIABLE_INPUTS,
+                "outputs" => flags | PSBT_MODIFIABLE_OUTPUTS,
+                _ => flags,
+            });
+        }
+    }
+
+    /// Sets `network` to the specified network, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn network(&mut self, argument: Option<&str>) {
+        match argument {
+            Some("mainnet") => self.network = "mainnet".into(),
+            Some("regtest") => self.network = "regtest".into(),
+            Some("signet") => self.network = "signet".into(),
+            Some("testnet") => self.network = "testnet".into(),
+            _ => (),
+        };
+    }
+
+    /// Sets `older` to the specified relative lock time of a timelock in blocks, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn older(&mut self, argument: Option<u32>) {
+        if let Some(blocks) = argument {
+            self.older = Some(blocks);
+        }
+    }
+
+    /// Sets `key_origin` to the specified key origin, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn origin(&mut self, argument: Option<&str>) {
+        if let Some(key_origin) = argument {
+            self.key_origin = Some(key_origin.to_string());
+        }
+    }
+
+    /// Sets `passphrase` to the specified passphrase of a Trezor hidden wallet, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn passphrase(&mut self, argument: Option<&str>) {
+        if let Some(passphrase) = argument {
+            self.passphrase = Some(passphrase.to_string());
+        }
+    }
+
+    /// Sets `password` to the specified password, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn password(&mut self, argument: Option<&str>) {
+        if let Some(password) = argument {
+            self.password = Some(password.to_string());
+        }
+    }
+
+    /// Sets `derivation` to `\"preset\"` and `preset` to the specified wallet preset, overriding their previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn preset(&mut self, argument: Option<&str>) {
+        if let Some(preset) = argument {
+            self.derivation = "preset".into();
+            self.preset = Some(preset.to_string());
+        }
+    }
+
+    /// Imports a wallet for the specified private key, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn private(&mut self, argument: Option<&str>) {
+        if let Some(private_key) = argument {
+            self.private = Some(private_key.to_string());
+        }
+    }
+
+    /// Sets `psbt` to the specified PSBT to sign on a Ledger or Trezor device, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn psbt(&mut self, argument: Option<&str>) {
+        if let Some(psbt) = argument {
+            self.psbt = Some(psbt.to_string());
+        }
+    }
+
+    /// Sets `psbt_version` to the specified PSBT version, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn psbt_version(&mut self, argument: Option<u32>) {
+        if let Some(psbt_version) = argument {
+            self.psbt_version = Some(psbt_version);
+        }
+    }
+
+    /// Imports a wallet for the specified public key, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn public(&mut self, argument: Option<&str>) {
+        if let Some(public_key) = argument {
+            self.public = Some(public_key.to_string())
+        }
+    }
+
+    /// Sets `public_keys` to the specified multisig public keys, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn public_key(&mut self, argument: Option<Values>) {
+        if let Some(public_keys) = argument {
+            self.public_keys = public_keys.map(String::from).collect();
+        }
+    }
+
+    /// Sets `range` to the specified last index of the imported range, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn range(&mut self, argument: Option<u32>) {
+        if let Some(range) = argument {
+            self.range = range;
+        }
+    }
+
+    /// Sets `recover` to the specified address to recover a mnemonic for, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn recover(&mut self, argument: Option<&str>) {
+        if let Some(address) = argument {
+            self.recover = Some(address.to_string());
+        }
+    }
+
+    /// Sets `replaceable` to the specified boolean value, overriding its previous state.
+    fn replaceable(&mut self, argument: bool) {
+        self.replaceable = argument;
+    }
+
+    /// Sets `rpc` to the specified Bitcoin Core RPC, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn rpc(&mut self, argument: Option<&str>) {
+        match argument {
+            Some("importdescriptors") => self.rpc = "importdescriptors".into(),
+            Some("importmulti") => self.rpc = "importmulti".into(),
+            _ => (),
+        };
+    }
+
+    /// Imports a wallet for the specified SeedQR, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn seed_qr(&mut self, argument: Option<&str>) {
+        if let Some(seed_qr) = argument {
+            self.seed_qr = Some(seed_qr.to_string());
+        }
+    }
+
+    /// Sets `server` to the specified address of an Electrum server, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
-    fn derivation(&mut self, argument: Option<&str>) {
-        match argument {
-            Some("bip32") => self.derivation = "bip32".into(),
-            Some("bip44") => self.derivation = "bip44".into(),
-            Some("bip49") => self.derivation = "bip49".into(),
-            Some(custom) => {
-                self.derivation = "custom".into();
-                self.path = Some(custom.to_string());
-            }
-            _ => (),
-        };
+    fn server(&mut self, argument: Option<&str>) {
+        if let Some(server) = argument {
+            self.server = Some(server.to_string());
+        }
     }
 
-    /// Sets `extended_private_key` to the specified extended private key, overriding its previous state.
+    /// Sets `shares` to the specified SLIP-39 shares, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
-    fn extended_private(&mut self, argument: Option<&str>) {
-        if let Some(extended_private_key) = argument {
-            self.extended_private_key = Some(extended_private_key.to_string());
+    fn share(&mut self, argument: Option<Values>) {
+        if let Some(shares) = argument {
+            self.shares = shares.map(String::from).collect();
         }
     }
 
-    /// Sets `extended_public_key` to the specified extended public key, overriding its previous state.
+    /// Sets `private` and `message` to the specified private key and message, overriding their previous state.
     /// If the specified argument is `None`, then no change occurs.
-    fn extended_public(&mut self, argument: Option<&str>) {
-        if let Some(extended_public_key) = argument {
-            self.extended_public_key = Some(extended_public_key.to_string());
+    fn sign_message(&mut self, argument: Option<Values>) {
+        if let Some(message_parameters) = argument {
+            let params: Vec<&str> = message_parameters.collect();
+            self.private = Some(params[0].to_string());
+            self.message = Some(params[1].to_string());
         }
     }
 
-    /// Sets `format` to the specified format, overriding its previous state.
+    /// Sets `psbt` and `private` to the specified PSBT and private key, overriding their previous state.
     /// If the specified argument is `None`, then no change occurs.
-    fn format(&mut self, argument: Option<&str>) {
-        match argument {
-            Some("legacy") => self.format = BitcoinFormat::P2PKH,
-            Some("segwit") => self.format = BitcoinFormat::P2SH_P2WPKH,
-            Some("bech32") => self.format = BitcoinFormat::Bech32,
-            _ => (),
-        };
+    fn sign_psbt(&mut self, argument: Option<Values>) {
+        if let Some(psbt_parameters) = argument {
+            let params: Vec<&str> = psbt_parameters.collect();
+            self.psbt = Some(params[0].to_string());
+            self.private = Some(params[1].to_string());
+        }
     }
 
-    /// Sets `index` to the specified index, overriding its previous state.
+    /// Sets `transaction_hex` and `transaction_inputs` to the specified transaction values, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
-    fn index(&mut self, argument: Option<u32>) {
-        if let Some(index) = argument {
-            self.index = index;
+    fn sign_raw_transaction(&mut self, argument: Option<Values>) {
+        if let Some(transaction_parameters) = argument {
+            let params: Vec<&str> = transaction_parameters.collect();
+            self.transaction_hex = Some(params[0].to_string());
+            self.transaction_inputs = Some(params[1].to_string());
         }
     }
 
-    /// Sets `json` to the specified boolean value, overriding its previous state.
-    fn json(&mut self, argument: bool) {
-        self.json = argument;
+    /// Sets `slip39` to the specified SLIP-39 groups, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn slip39(&mut self, argument: Option<&str>) {
+        if let Some(groups) = argument {
+            self.slip39 = Some(groups.to_string());
+        }
     }
 
-    /// Sets `language` to the specified language, overriding its previous state.
+    /// Sets `threads` to the specified number of search threads, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
-    fn language(&mut self, argument: Option<&str>) {
-        match argument {
-            Some("chinese_simplified") => self.language = "chinese_simplified".into(),
-            Some("chinese_traditional") => self.language = "chinese_traditional".into(),
-            Some("english") => self.language = "english".into(),
-            Some("french") => self.language = "french".into(),
-            Some("italian") => self.language = "italian".into(),
-            Some("japanese") => self.language = "japanese".into(),
-            Some("korean") => self.language = "korean".into(),
-            Some("spanish") => self.language = "spanish".into(),
-            _ => (),
-        };
+    fn threads(&mut self, argument: Option<usize>) {
+        if let Some(threads) = argument {
+            self.threads = threads;
+        }
     }
 
-    /// Sets `lock_time` to the specified transaction lock time, overriding its previous state.
+    /// Sets `threshold` to the specified number of multisig signatures, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
-    fn lock_time(&mut self, argument: Option<u32>) {
-        if let Some(lock_time) = argument {
-            self.lock_time = Some(lock_time);
+    fn threshold(&mut self, argument: Option<usize>) {
+        if let Some(threshold) = argument {
+            self.threshold = Some(threshold);
         }
     }
 
-    /// Sets `mnemonic` to the specified mnemonic, overriding its previous state.
+    /// Sets `timestamp` to the specified rescan timestamp, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
-    fn mnemonic(&mut self, argument: Option<&str>) {
-        if let Some(mnemonic) = argument {
-            self.mnemonic = Some(mnemonic.to_string());
+    fn timestamp(&mut self, argument: Option<&str>) {
+        if let Some(timestamp) = argument {
+            self.timestamp = timestamp.to_string();
         }
     }
 
-    /// Sets `network` to the specified network, overriding its previous state.
+    /// Sets `transaction_hex` to the specified raw transaction to decode, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
-    fn network(&mut self, argument: Option<&str>) {
-        match argument {
-            Some("mainnet") => self.network = "mainnet".into(),
-            Some("testnet") => self.network = "testnet".into(),
-            _ => (),
-        };
+    fn transaction(&mut self, argument: Option<&str>) {
+        if let Some(transaction_hex) = argument {
+            self.transaction_hex = Some(transaction_hex.to_string());
+        }
     }
 
-    /// Sets `password` to the specified password, overriding its previous state.
+    /// Sets `psbt` and `transaction_inputs` to the specified PSBT and inputs, overriding their previous state.
     /// If the specified argument is `None`, then no change occurs.
-    fn password(&mut self, argument: Option<&str>) {
-        if let Some(password) = argument {
-            self.password = Some(password.to_string());
+    fn update_psbt(&mut self, argument: Option<Values>) {
+        if let Some(psbt_parameters) = argument {
+            let params: Vec<&str> = psbt_parameters.collect();
+            self.psbt = Some(params[0].to_string());
+            self.transaction_inputs = Some(params[1].to_string());
         }
     }
 
-    /// Imports a wallet for the specified private key, overriding its previous state.
+    /// Sets `uri` to the specified boolean value, overriding its previous state.
+    fn uri(&mut self, argument: bool) {
+        self.uri = argument;
+    }
+
+    /// Sets `address`, `signature`, and `message` to the specified message values, overriding their previous state.
     /// If the specified argument is `None`, then no change occurs.
-    fn private(&mut self, argument: Option<&str>) {
-        if let Some(private_key) = argument {
-            self.private = Some(private_key.to_string());
+    fn verify_message(&mut self, argument: Option<Values>) {
+        if let Some(message_parameters) = argument {
+            let params: Vec<&str> = message_parameters.collect();
+            self.address = Some(params[0].to_string());
+            self.signature = Some(params[1].to_string());
+            self.message = Some(params[2].to_string());
         }
     }
 
-    /// Imports a wallet for the specified public key, overriding its previous state.
+    /// Sets `vanity_prefix` to the specified address prefix, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
-    fn public(&mut self, argument: Option<&str>) {
-        if let Some(public_key) = argument {
-            self.public = Some(public_key.to_string())
+    fn vanity_prefix(&mut self, argument: Option<&str>) {
+        if let Some(prefix) = argument {
+            self.vanity_prefix = Some(prefix.to_string());
         }
     }
 
-    /// Sets `transaction_hex` and `transaction_inputs` to the specified transaction values, overriding its previous state.
+    /// Sets `vanity_regex` to the specified address regular expression, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
-    fn sign_raw_transaction(&mut self, argument: Option<Values>) {
-        if let Some(transaction_parameters) = argument {
-            let params: Vec<&str> = transaction_parameters.collect();
-            self.transaction_hex = Some(params[0].to_string());
-            self.transaction_inputs = Some(params[1].to_string());
+    fn vanity_regex(&mut self, argument: Option<&str>) {
+        if let Some(regex) = argument {
+            self.vanity_regex = Some(regex.to_string());
         }
     }
 
+    /// Sets `watch_only` to the specified boolean value, overriding its previous state.
+    fn watch_only(&mut self, argument: bool) {
+        self.watch_only = argument;
+    }
+
     /// Sets `word_count` to the specified word count, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn word_count(&mut self, argument: Option<u8>) {
@@ -689,19 +2794,76 @@ impl BitcoinOptions {
         }
     }
 
+    /// Returns the source and rolls of the specified coin flips or dice rolls.
+    fn to_rolls(&self) -> Option<(ManualEntropy, &str)> {
+        match (&self.coin, &self.dice) {
+            (Some(flips), _) => Some((ManualEntropy::Coin, flips)),
+            (None, Some(rolls)) => Some((ManualEntropy::Dice, rolls)),
+            (None, None) => None,
+        }
+    }
+
+    /// Returns the account derivation path and address format of the specified account and derivation.
+    /// If a format was specified, then return it instead of the format of the derivation.
+    fn to_account_path(&self) -> (String, BitcoinFormat) {
+        let (purpose, format) = match self.derivation.as_str() {
+            "bip44" => (44, BitcoinFormat::P2PKH),
+            "bip49" => (49, BitcoinFormat::P2SH_P2WPKH),
+            "bip86" => (86, BitcoinFormat::P2TR),
+            _ => (84, BitcoinFormat::Bech32),
+        };
+        let format = match self.format_specified {
+            true => self.format.clone(),
+            false => format,
+        };
+        (format!("m/{}'/0'/{}'", purpose, self.account), format)
+    }
+
+    /// Returns the Bitcoin Core RPC and rescan timestamp of the import requests.
+    fn to_core_import(&self) -> Result<(ImportRpc, ImportTimestamp), CLIError> {
+        let rpc = match self.rpc.as_str() {
+            "importmulti" => ImportRpc::ImportMulti,
+            _ => ImportRpc::ImportDescriptors,
+        };
+        let timestamp = match self.timestamp.as_str() {
+            "now" => ImportTimestamp::Now,
+            time => {
+                ImportTimestamp::Time(u64::from_str(time).context_with_input("bitcoin", "parse timestamp", time)?)
+            }
+        };
+        Ok((rpc, timestamp))
+    }
+
     /// Returns the derivation path with the specified account, chain, derivation, index, and path.
     /// If `default` is enabled, then return the default path if no derivation was provided.
-    fn to_derivation_path(&self, default: bool) -> Option<String> {
-        match self.derivation.as_str() {
+    fn to_derivation_path(&self, default: bool) -> Result<Option<String>, CLIError> {
+        Ok(match self.derivation.as_str() {
             "bip32" => Some(format!("m/0'/0'/{}'", self.index)),
             "bip44" => Some(format!("m/44'/0'/{}'/{}/{}", self.account, self.chain, self.index)),
             "bip49" => Some(format!("m/49'/0'/{}'/{}/{}", self.account, self.chain, self.index)),
+            "bip84" => Some(format!("m/84'/0'/{}'/{}/{}", self.account, self.chain, self.index)),
+            "bip86" => Some(format!("m/86'/0'/{}'/{}/{}", self.account, self.chain, self.index)),
             "custom" => self.path.clone(),
+            "preset" => match &self.preset {
+                Some(preset) => {
+                    let coin_type = match self.network.as_str() {
+                        "testnet" => CoinType::Testnet,
+                        _ => CoinType::Bitcoin,
+                    };
+                    let scheme = WalletPreset::from_str(preset)?
+                        .scheme(CoinType::Bitcoin)
+                        .ok_or_else(|| {
+                            DerivationPathError::UnsupportedWalletPreset(CoinType::Bitcoin, preset.clone())
+                        })?;
+                    Some(scheme.to_path(coin_type, self.account, self.chain, self.index))
+                }
+                None => None,
+            },
             _ => match default {
                 true => Some(format!("m/0'/0'/{}'", self.index)),
                 false => None,
             },
-        }
+        })
     }
 
     /// Sets `version` to the specified transaction version, overriding its previous state.
@@ -720,54 +2882,318 @@ impl CLI for BitcoinCLI {
 
     const NAME: NameType = "bitcoin";
     const ABOUT: AboutType = "Generates a Bitcoin wallet (include -h for more options)";
-    const FLAGS: &'static [FlagType] = &[flag::JSON];
-    const OPTIONS: &'static [OptionType] = &[option::COUNT, option::FORMAT_BITCOIN, option::NETWORK_BITCOIN];
+    const FLAGS: &'static [FlagType] = &[flag::JSON, flag::URI_BITCOIN];
+    const OPTIONS: &'static [OptionType] = &[
+        option::BIP38_BITCOIN,
+        option::COUNT,
+        option::FORMAT_BITCOIN,
+        option::NETWORK_BITCOIN,
+    ];
     const SUBCOMMANDS: &'static [SubCommandType] = &[
+        subcommand::CONVERT_XPUB_BITCOIN,
+        subcommand::DECODE_TX_BITCOIN,
+        #[cfg(feature = "online")]
+        subcommand::ELECTRUM_BITCOIN,
+        subcommand::EXPORT_BACKUP_BITCOIN,
+        subcommand::EXPORT_DESCRIPTORS_BITCOIN,
+        subcommand::EXPORT_ELECTRUM_BITCOIN,
+        subcommand::EXPORT_SEED_QR_BITCOIN,
         subcommand::HD_BITCOIN,
         subcommand::IMPORT_BITCOIN,
+        subcommand::IMPORT_BACKUP_BITCOIN,
         subcommand::IMPORT_HD_BITCOIN,
+        #[cfg(feature = "ledger")]
+        subcommand::LEDGER_BITCOIN,
+        subcommand::MESSAGE_BITCOIN,
+        subcommand::MULTISIG_BITCOIN,
+        subcommand::PSBT_BITCOIN,
+        subcommand::TIMELOCK_BITCOIN,
         subcommand::TRANSACTION_BITCOIN,
+        #[cfg(feature = "trezor")]
+        subcommand::TREZOR_BITCOIN,
+        subcommand::VANITY_BITCOIN,
     ];
 
     /// Handle all CLI arguments and flags for Bitcoin
     #[cfg_attr(tarpaulin, skip)]
     fn parse(arguments: &ArgMatches) -> Result<Self::Options, CLIError> {
         let mut options = BitcoinOptions::default();
-        options.parse(arguments, &["count", "format", "json", "network"]);
+        options.parse(arguments, &["bip38", "count", "format", "json", "network", "uri"]);
 
         match arguments.subcommand() {
+            ("convert-xpub", Some(arguments)) => {
+                options.subcommand = Some("convert-xpub".into());
+                options.parse(arguments, &["extended public", "format", "json"]);
+            }
+            ("decode-tx", Some(arguments)) => {
+                options.subcommand = Some("decode-tx".into());
+                options.parse(arguments, &["json", "network", "transaction"]);
+            }
+            #[cfg(feature = "online")]
+            ("electrum", Some(arguments)) => {
+                options.subcommand = Some("electrum".into());
+                options.parse(arguments, &["address", "broadcast", "json", "network", "server"]);
+            }
+            ("export-backup", Some(arguments)) => {
+                options.subcommand = Some("export-backup".into());
+                options.parse(arguments, &["json"]);
+                options.parse(arguments, &["backup password", "language", "mnemonic"]);
+
+                // Detects the mnemonic language, unless one is specified
+                if let (Some(mnemonic), None) = (&options.mnemonic, arguments.value_of("language")) {
+                    options.language = detect_language(mnemonic)
+                        .context("bitcoin", "detect mnemonic language")?
+                        .into();
+                }
+            }
+            ("export-descriptors", Some(arguments)) => {
+                options.subcommand = Some("export-descriptors".into());
+                options.derivation = "bip84".into();
+                options.format_specified = arguments.is_present("format");
+                options.parse(arguments, &["format", "json", "network"]);
+                options.parse(
+                    arguments,
+                    &[
+                        "account",
+                        "derivation",
+                        "extended public",
+                        "mnemonic",
+                        "origin",
+                        "password",
+                        "range",
+                        "rpc",
+                        "timestamp",
+                    ],
+                );
+
+                // Detects the mnemonic language
+                if let Some(mnemonic) = &options.mnemonic {
+                    options.language = detect_language(mnemonic)
+                        .context("bitcoin", "detect mnemonic language")?
+                        .into();
+                }
+            }
+            ("export-electrum", Some(arguments)) => {
+                options.subcommand = Some("export-electrum".into());
+                options.derivation = "bip84".into();
+                options.format_specified = arguments.is_present("format");
+                options.parse(arguments, &["format", "json", "network"]);
+                options.parse(
+                    arguments,
+                    &[
+                        "account",
+                        "derivation",
+                        "extended public",
+                        "mnemonic",
+                        "origin",
+                        "password",
+                        "watch only",
+                    ],
+                );
+
+                // Detects the mnemonic language
+                if let Some(mnemonic) = &options.mnemonic {
+                    options.language = detect_language(mnemonic)
+                        .context("bitcoin", "detect mnemonic language")?
+                        .into();
+                }
+            }
+            ("export-seedqr", Some(arguments)) => {
+                options.subcommand = Some("export-seedqr".into());
+                options.parse(arguments, &["json"]);
+                options.parse(arguments, &["compact", "language", "mnemonic"]);
+
+                // Detects the mnemonic language, unless one is specified
+                if let (Some(mnemonic), None) = (&options.mnemonic, arguments.value_of("language")) {
+                    options.language = detect_language(mnemonic)
+                        .context("bitcoin", "detect mnemonic language")?
+                        .into();
+                }
+            }
             ("hd", Some(arguments)) => {
                 options.subcommand = Some("hd".into());
-                options.parse(arguments, &["count", "json", "network"]);
-                options.parse(arguments, &["derivation", "language", "password", "word count"]);
+                options.parse(arguments, &["count", "json", "network", "uri"]);
+                options.parse(
+                    arguments,
+                    &[
+                        "aezeed",
+                        "coin",
+                        "derivation",
+                        "dice",
+                        "group threshold",
+                        "language",
+                        "password",
+                        "preset",
+                        "slip39",
+                        "word count",
+                    ],
+                );
+
+                // Prompts for the password from the terminal, if requested
+                if arguments.is_present("prompt password") {
+                    options.password = Some(prompt_password().context("bitcoin", "prompt password")?);
+                }
             }
             ("import", Some(arguments)) => {
                 options.subcommand = Some("import".into());
-                options.parse(arguments, &["format", "json", "network"]);
+                options.network_specified = arguments.is_present("network");
+                options.parse(arguments, &["format", "json", "network", "uri"]);
                 options.parse(arguments, &["address", "private", "public"]);
             }
+            ("import-backup", Some(arguments)) => {
+                options.subcommand = Some("import-backup".into());
+                options.parse(arguments, &["json", "network", "uri"]);
+                options.parse(
+                    arguments,
+                    &[
+                        "account",
+                        "backup",
+                        "backup password",
+                        "chain",
+                        "derivation",
+                        "index",
+                        "password",
+                        "preset",
+                    ],
+                );
+
+                // Decrypts the mnemonic of the backup and detects its language
+                if let (Some(backup), Some(password)) = (&options.backup, &options.backup_password) {
+                    let mnemonic = decrypt_backup(backup, password).context("bitcoin", "decrypt backup")?;
+                    options.language = detect_language(&mnemonic)
+                        .context("bitcoin", "detect mnemonic language")?
+                        .into();
+                    options.mnemonic = Some(mnemonic);
+                }
+            }
             ("import-hd", Some(arguments)) => {
                 options.subcommand = Some("import-hd".into());
-                options.parse(arguments, &["json", "network"]);
+                options.parse(arguments, &["json", "network", "uri"]);
                 options.parse(
                     arguments,
                     &[
                         "account",
+                        "aezeed",
                         "chain",
                         "derivation",
                         "extended private",
                         "extended public",
                         "index",
+                        "indices",
+                        "language",
                         "mnemonic",
                         "password",
+                        "preset",
+                        "recover",
+                        "seedqr",
+                        "share",
+                        "watch only",
                     ],
                 );
+
+                // Prompts for the password from the terminal, if requested
+                if arguments.is_present("prompt password") {
+                    options.password = Some(prompt_password().context("bitcoin", "prompt password")?);
+                }
+
+                // Detects the mnemonic language, unless one is specified
+                if let (Some(mnemonic), None) = (&options.mnemonic, arguments.value_of("language")) {
+                    // Ignores the unknown words, marked by "?", of a mnemonic to recover
+                    let mnemonic = match options.recover {
+                        Some(_) => mnemonic
+                            .split(" ")
+                            .filter(|word| *word != "?")
+                            .collect::<Vec<&str>>()
+                            .join(" "),
+                        None => mnemonic.clone(),
+                    };
+                    options.language = detect_language(&mnemonic)
+                        .context("bitcoin", "detect mnemonic language")?
+                        .into();
+                }
+            }
+            #[cfg(feature = "ledger")]
+            ("ledger", Some(arguments)) => {
+                options.subcommand = Some("ledger".into());
+                options.format_specified = arguments.is_present("format");
+                options.parse(arguments, &["json", "network", "uri"]);
+                options.parse(arguments, &["account", "chain", "device", "format", "index", "psbt"]);
+            }
+            ("message", Some(arguments)) => {
+                options.subcommand = Some("message".into());
+                options.parse(arguments, &["format", "json", "signmessage", "verifymessage"]);
+            }
+            ("multisig", Some(arguments)) => {
+                options.subcommand = Some("multisig".into());
+                options.parse(arguments, &["json", "network", "public key", "threshold", "uri"]);
+            }
+            ("psbt", Some(arguments)) => {
+                options.subcommand = Some("psbt".into());
+                options.parse(
+                    arguments,
+                    &[
+                        "combine",
+                        "create",
+                        "data",
+                        "finalize",
+                        "json",
+                        "lock time",
+                        "modifiable",
+                        "psbt version",
+                        "replaceable",
+                        "sign",
+                        "update",
+                        "version",
+                    ],
+                );
+            }
+            ("timelock", Some(arguments)) => {
+                options.subcommand = Some("timelock".into());
+                options.parse(
+                    arguments,
+                    &["after", "htlc", "json", "network", "older", "public key", "uri"],
+                );
             }
             ("transaction", Some(arguments)) => {
                 options.subcommand = Some("transaction".into());
                 options.parse(
                     arguments,
-                    &["createrawtransaction", "lock time", "signrawtransaction", "version"],
+                    &[
+                        "createrawtransaction",
+                        "data",
+                        "export",
+                        "import",
+                        "lock time",
+                        "replaceable",
+                        "signrawtransaction",
+                        "version",
+                    ],
+                );
+            }
+            #[cfg(feature = "trezor")]
+            ("trezor", Some(arguments)) => {
+                options.subcommand = Some("trezor".into());
+                options.format_specified = arguments.is_present("format");
+                options.parse(arguments, &["json", "network", "uri"]);
+                options.parse(
+                    arguments,
+                    &[
+                        "account",
+                        "bridge",
+                        "chain",
+                        "device",
+                        "format",
+                        "index",
+                        "passphrase",
+                        "psbt",
+                    ],
+                );
+            }
+            ("vanity", Some(arguments)) => {
+                options.subcommand = Some("vanity".into());
+                options.parse(
+                    arguments,
+                    &["format", "json", "network", "prefix", "regex", "threads", "uri"],
                 );
             }
             _ => {}
@@ -778,116 +3204,477 @@ impl CLI for BitcoinCLI {
 
     /// Generate the Bitcoin wallet and print the relevant fields
     #[cfg_attr(tarpaulin, skip)]
-    fn print(options: Self::Options) -> Result<(), CLIError> {
-        fn output<N: BitcoinNetwork, W: BitcoinWordlist>(options: BitcoinOptions) -> Result<(), CLIError> {
-            let wallets =
-                match options.subcommand.as_ref().map(String::as_str) {
-                    Some("hd") => match options.to_derivation_path(true) {
-                        Some(path) => (0..options.count)
-                            .flat_map(|_| {
-                                match BitcoinWallet::new_hd::<N, W, _>(
-                                    &mut StdRng::from_entropy(),
-                                    options.word_count,
+    fn print_with_entropy(options: Self::Options, source: &mut dyn EntropySource) -> Result<(), CLIError> {
+        /// Prints the wallets for the network `N`, parsing non-mainnet inputs for the test network `T`.
+        fn output<N: BitcoinNetwork, T: BitcoinNetwork, W: BitcoinWordlist>(
+            options: BitcoinOptions,
+            source: &mut dyn EntropySource,
+        ) -> Result<(), CLIError> {
+            let mut wallets = match options.subcommand.as_ref().map(String::as_str) {
+                Some("convert-xpub") => match &options.extended_public_key {
+                    Some(key) => {
+                        vec![
+                            BitcoinWallet::to_extended_public_key_format::<BitcoinMainnet>(key, &options.format)
+                                .or(BitcoinWallet::to_extended_public_key_format::<T>(key, &options.format))?,
+                        ]
+                    }
+                    None => vec![],
+                },
+                Some("decode-tx") => match &options.transaction_hex {
+                    Some(transaction_hex) => vec![BitcoinWallet::to_decoded_transaction::<N>(transaction_hex)?],
+                    None => vec![],
+                },
+                #[cfg(feature = "online")]
+                Some("electrum") => match (&options.server, &options.address, &options.transaction_hex) {
+                    // Selects the network of the address first, to report the errors of the server
+                    (Some(server), Some(address), _) => match BitcoinAddress::<BitcoinMainnet>::from_str(address) {
+                        Ok(_) => vec![BitcoinWallet::to_electrum_balance::<BitcoinMainnet>(server, address)?],
+                        Err(_) => vec![BitcoinWallet::to_electrum_balance::<T>(server, address)?],
+                    },
+                    (Some(server), None, Some(transaction_hex)) => {
+                        vec![BitcoinWallet::to_electrum_broadcast::<N>(server, transaction_hex)?]
+                    }
+                    _ => vec![],
+                },
+                Some("export-backup") => match (&options.mnemonic, &options.backup_password) {
+                    (Some(mnemonic), Some(password)) => {
+                        vec![BitcoinWallet::to_backup::<N, W, _>(source, mnemonic, password)?]
+                    }
+                    _ => vec![],
+                },
+                Some("export-descriptors") => {
+                    let (rpc, timestamp) = options.to_core_import()?;
+                    let range = options.range;
+
+                    if let Some(mnemonic) = &options.mnemonic {
+                        let password = &options.password.as_ref().map(String::as_str);
+                        let (path, format) = options.to_account_path();
+
+                        vec![BitcoinWallet::to_core_import_from_mnemonic::<N, W>(
+                            mnemonic, password, &path, &format, timestamp, range, rpc,
+                        )?]
+                    } else if let Some(key) = &options.extended_public_key {
+                        let origin = &options.key_origin;
+                        let format = match options.format_specified {
+                            true => Some(&options.format),
+                            false => None,
+                        };
+
+                        // Selects the network of the key first, to report the errors of its descriptors
+                        match BitcoinExtendedPublicKey::<BitcoinMainnet>::from_str(key) {
+                            Ok(_) => vec![BitcoinWallet::to_core_import::<BitcoinMainnet>(
+                                key, origin, format, timestamp, range, rpc,
+                            )?],
+                            Err(_) => vec![BitcoinWallet::to_core_import::<T>(
+                                key, origin, format, timestamp, range, rpc,
+                            )?],
+                        }
+                    } else {
+                        vec![]
+                    }
+                }
+                Some("export-electrum") => {
+                    if let Some(mnemonic) = &options.mnemonic {
+                        let password = &options.password.as_ref().map(String::as_str);
+                        let (path, format) = options.to_account_path();
+
+                        vec![BitcoinWallet::to_electrum_wallet_from_mnemonic::<N, W>(
+                            mnemonic,
+                            password,
+                            &path,
+                            &format,
+                            options.watch_only,
+                        )?]
+                    } else if let Some(key) = &options.extended_public_key {
+                        let origin = &options.key_origin;
+                        let format = match options.format_specified {
+                            true => Some(&options.format),
+                            false => None,
+                        };
+
+                        // Selects the network of the key first, to report the errors of its wallet
+                        match BitcoinExtendedPublicKey::<BitcoinMainnet>::from_str(key) {
+                            Ok(_) => vec![BitcoinWallet::to_electrum_wallet::<BitcoinMainnet>(
+                                key, origin, format,
+                            )?],
+                            Err(_) => vec![BitcoinWallet::to_electrum_wallet::<T>(key, origin, format)?],
+                        }
+                    } else {
+                        vec![]
+                    }
+                }
+                Some("export-seedqr") => match &options.mnemonic {
+                    Some(mnemonic) => vec![BitcoinWallet::to_seed_qr::<N, W>(mnemonic, options.compact)?],
+                    None => vec![],
+                },
+                Some("hd") => match (options.to_derivation_path(true)?, &options.slip39) {
+                    (Some(path), Some(groups)) => {
+                        let groups = groups
+                            .split(',')
+                            .map(|group| Slip39Group::from_str(group.trim()))
+                            .collect::<Result<Vec<Slip39Group>, _>>()?;
+                        (0..options.count)
+                            .map(|_| {
+                                BitcoinWallet::new_hd_shares::<N, _>(
+                                    source,
+                                    options.group_threshold,
+                                    &groups,
                                     options.password.as_ref().map(String::as_str),
                                     &path,
-                                ) {
-                                    Ok(wallet) => vec![wallet],
-                                    _ => vec![],
-                                }
+                                )
                             })
-                            .collect(),
-                        None => vec![],
-                    },
-                    Some("import") => {
-                        if let Some(private_key) = options.private {
-                            vec![
-                                BitcoinWallet::from_private_key::<BitcoinMainnet>(&private_key, &options.format).or(
-                                    BitcoinWallet::from_private_key::<BitcoinTestnet>(&private_key, &options.format),
-                                )?,
-                            ]
-                        } else if let Some(public_key) = options.public {
-                            vec![BitcoinWallet::from_public_key::<N>(&public_key, &options.format)?]
-                        } else if let Some(address) = options.address {
-                            vec![BitcoinWallet::from_address::<BitcoinMainnet>(&address)
-                                .or(BitcoinWallet::from_address::<BitcoinTestnet>(&address))?]
-                        } else {
-                            vec![]
-                        }
+                            .collect::<Result<Vec<BitcoinWallet>, CLIError>>()?
+                    }
+                    (Some(path), None) if options.aezeed => (0..options.count)
+                        .map(|_| {
+                            BitcoinWallet::new_hd_aezeed::<N, _>(
+                                source,
+                                options.password.as_ref().map(String::as_str),
+                                &path,
+                            )
+                        })
+                        .collect::<Result<Vec<BitcoinWallet>, CLIError>>()?,
+                    (Some(path), None) if options.to_rolls().is_some() => {
+                        let (source, rolls) = options.to_rolls().unwrap();
+                        vec![BitcoinWallet::new_hd_from_rolls::<N, W>(
+                            source,
+                            rolls,
+                            options.word_count,
+                            options.password.as_ref().map(String::as_str),
+                            &path,
+                        )?]
                     }
-                    Some("import-hd") => {
-                        if let Some(mnemonic) = options.mnemonic.clone() {
-                            let password = &options.password.as_ref().map(String::as_str);
+                    (Some(path), None) => BitcoinWallet::new_hd::<N, W, _>(
+                        source,
+                        options.count,
+                        options.word_count,
+                        options.password.as_ref().map(String::as_str),
+                        &path,
+                    )?,
+                    (None, _) => vec![],
+                },
+                Some("import") => {
+                    if let Some(private_key) = options.private {
+                        vec![BitcoinWallet::from_wif::<N>(
+                            &private_key,
+                            &options.format,
+                            options.network_specified,
+                        )?]
+                    } else if let Some(public_key) = options.public {
+                        vec![BitcoinWallet::from_public_key::<N>(&public_key, &options.format)?]
+                    } else if let Some(address) = options.address {
+                        vec![BitcoinWallet::from_address::<BitcoinMainnet>(&address)
+                            .or(BitcoinWallet::from_address::<T>(&address))?]
+                    } else {
+                        vec![]
+                    }
+                }
+                Some("import-hd") | Some("import-backup") => {
+                    if let (Some(mnemonic), Some(address)) = (options.mnemonic.clone(), options.recover.clone()) {
+                        let password = &options.password.as_ref().map(String::as_str);
 
-                            match options.to_derivation_path(true) {
-                                Some(path) => vec![BitcoinWallet::from_mnemonic::<N, ChineseSimplified>(
-                                    &mnemonic, password, &path,
-                                )
-                                .or(BitcoinWallet::from_mnemonic::<N, ChineseTraditional>(
-                                    &mnemonic, password, &path,
-                                ))
-                                .or(BitcoinWallet::from_mnemonic::<N, English>(&mnemonic, password, &path))
-                                .or(BitcoinWallet::from_mnemonic::<N, French>(&mnemonic, password, &path))
-                                .or(BitcoinWallet::from_mnemonic::<N, Italian>(&mnemonic, password, &path))
-                                .or(BitcoinWallet::from_mnemonic::<N, Japanese>(&mnemonic, password, &path))
-                                .or(BitcoinWallet::from_mnemonic::<N, Korean>(&mnemonic, password, &path))
-                                .or(BitcoinWallet::from_mnemonic::<N, Spanish>(&mnemonic, password, &path))?],
-                                None => vec![],
+                        match options.to_derivation_path(true)? {
+                            Some(path) => BitcoinWallet::recover::<N, W>(&mnemonic, password, &path, &address)?,
+                            None => vec![],
+                        }
+                    } else if let Some(mnemonic) = options.mnemonic.clone() {
+                        let password = &options.password.as_ref().map(String::as_str);
+
+                        match (options.to_derivation_path(true)?, options.indices) {
+                            (Some(path), 1) => vec![BitcoinWallet::from_mnemonic::<N, W>(&mnemonic, password, &path)?],
+                            // Generate the child wallets, from `index` to a number of specified `indices`
+                            (Some(path), indices) => {
+                                BitcoinWallet::from_mnemonic_children::<N, W>(&mnemonic, password, &path, indices)?
                             }
-                        } else if let Some(extended_private_key) = options.extended_private_key.clone() {
-                            let key = &extended_private_key;
-                            let path = &options.to_derivation_path(false);
-
-                            vec![BitcoinWallet::from_extended_private_key::<BitcoinMainnet>(key, path)
-                                .or(BitcoinWallet::from_extended_private_key::<BitcoinTestnet>(key, path))?]
-                        } else if let Some(extended_public_key) = options.extended_public_key.clone() {
-                            let key = &extended_public_key;
-                            let path = &options.to_derivation_path(false);
-
-                            vec![BitcoinWallet::from_extended_public_key::<BitcoinMainnet>(key, path)
-                                .or(BitcoinWallet::from_extended_public_key::<BitcoinTestnet>(key, path))?]
-                        } else {
-                            vec![]
+                            (None, _) => vec![],
+                        }
+                    } else if let Some(seed_qr) = options.seed_qr.clone() {
+                        let password = &options.password.as_ref().map(String::as_str);
+                        let mnemonic =
+                            decode_seed_qr::<BitcoinMnemonic<N, W>>(&seed_qr).context("bitcoin", "decode SeedQR")?;
+
+                        match options.to_derivation_path(true)? {
+                            Some(path) => vec![BitcoinWallet::from_mnemonic::<N, W>(
+                                &mnemonic.to_phrase()?,
+                                password,
+                                &path,
+                            )?],
+                            None => vec![],
+                        }
+                    } else if let Some(mnemonic) = options.aezeed_mnemonic.clone() {
+                        let password = &options.password.as_ref().map(String::as_str);
+
+                        match options.to_derivation_path(true)? {
+                            Some(path) => vec![BitcoinWallet::from_aezeed::<N>(&mnemonic, password, &path)?],
+                            None => vec![],
+                        }
+                    } else if !options.shares.is_empty() {
+                        let password = &options.password.as_ref().map(String::as_str);
+
+                        match options.to_derivation_path(true)? {
+                            Some(path) => vec![BitcoinWallet::from_shares::<N>(&options.shares, password, &path)?],
+                            None => vec![],
                         }
+                    } else if let Some(extended_private_key) = options.extended_private_key.clone() {
+                        let key = &extended_private_key;
+                        let path = &options.to_derivation_path(false)?;
+
+                        vec![BitcoinWallet::from_extended_private_key::<BitcoinMainnet>(key, path)
+                            .or(BitcoinWallet::from_extended_private_key::<T>(key, path))?]
+                    } else if let (Some(extended_public_key), true) =
+                        (options.extended_public_key.clone(), options.watch_only)
+                    {
+                        // Generate the child wallets, from `index` to a number of specified `indices`
+                        let key = &extended_public_key;
+                        let path = &options.to_derivation_path(false)?;
+                        let (index, indices) = (options.index, options.indices);
+
+                        BitcoinWallet::from_extended_public_key_children::<BitcoinMainnet>(key, path, index, indices)
+                            .or(BitcoinWallet::from_extended_public_key_children::<T>(
+                                key, path, index, indices,
+                            ))?
+                    } else if let Some(extended_public_key) = options.extended_public_key.clone() {
+                        let key = &extended_public_key;
+                        let path = &options.to_derivation_path(false)?;
+
+                        vec![BitcoinWallet::from_extended_public_key::<BitcoinMainnet>(key, path)
+                            .or(BitcoinWallet::from_extended_public_key::<T>(key, path))?]
+                    } else {
+                        vec![]
+                    }
+                }
+                #[cfg(feature = "ledger")]
+                Some("ledger") => {
+                    let format = match options.format_specified {
+                        true => options.format.clone(),
+                        false => BitcoinFormat::Bech32,
+                    };
+                    match &options.psbt {
+                        Some(psbt) => vec![BitcoinWallet::to_ledger_signed_psbt::<N>(
+                            &options.device,
+                            psbt,
+                            &format,
+                            options.account,
+                        )?],
+                        None => vec![BitcoinWallet::to_ledger_address::<N>(
+                            &options.device,
+                            &format,
+                            options.account,
+                            options.chain,
+                            options.index,
+                        )?],
+                    }
+                }
+                Some("message") => match (&options.private, &options.address, &options.signature, &options.message) {
+                    (Some(private_key), _, _, Some(message)) => vec![
+                        BitcoinWallet::to_signed_message::<BitcoinMainnet>(private_key, message, &options.format).or(
+                            BitcoinWallet::to_signed_message::<T>(private_key, message, &options.format),
+                        )?,
+                    ],
+                    (_, Some(address), Some(signature), Some(message)) => {
+                        vec![
+                            BitcoinWallet::to_verified_message::<BitcoinMainnet>(address, signature, message)
+                                .or(BitcoinWallet::to_verified_message::<T>(address, signature, message))?,
+                        ]
+                    }
+                    _ => vec![],
+                },
+                Some("multisig") => match options.threshold {
+                    Some(threshold) => BitcoinWallet::to_multisig::<N>(threshold, &options.public_keys)?,
+                    None => vec![],
+                },
+                Some("timelock") => match (options.after, options.older) {
+                    (None, None) => vec![],
+                    (after, older) => {
+                        BitcoinWallet::to_timelock::<N>(after, older, &options.public_keys, &options.htlc)?
                     }
-                    Some("transaction") => {
-                        if let (Some(transaction_inputs), Some(transaction_outputs)) =
-                            (options.transaction_inputs.clone(), options.transaction_outputs.clone())
-                        {
-                            let inputs: &Vec<BitcoinInput> = &from_str(&transaction_inputs)?;
-                            let outputs = transaction_outputs.replace(&['{', '}', '"', ' '][..], "");
-                            let outputs: &Vec<&str> = &outputs.split(",").collect();
-                            let version = options.version.unwrap_or(1);
-                            let lock_time = options.lock_time.unwrap_or(0);
-
-                            vec![BitcoinWallet::to_raw_transaction::<BitcoinMainnet>(
-                                inputs, outputs, version, lock_time,
+                },
+                Some("psbt") => {
+                    if !options.psbts.is_empty() {
+                        vec![BitcoinWallet::to_combined_psbt::<BitcoinMainnet>(&options.psbts)
+                            .or(BitcoinWallet::to_combined_psbt::<T>(&options.psbts))?]
+                    } else if let (Some(transaction_inputs), Some(transaction_outputs)) =
+                        (&options.transaction_inputs, &options.transaction_outputs)
+                    {
+                        let inputs: &Vec<BitcoinInput> = &from_str(transaction_inputs)?;
+                        let outputs = transaction_outputs.replace(&['{', '}', '"', ' '][..], "");
+                        let outputs: &Vec<&str> = &outputs.split(",").collect();
+                        let version = options.version.unwrap_or(2);
+                        let lock_time = options.lock_time.unwrap_or(0);
+                        let replaceable = options.replaceable;
+                        let data = options.data.as_deref();
+                        let modifiable = options.modifiable;
+                        let psbt_version = options.psbt_version.unwrap_or(match modifiable {
+                            0 => 0,
+                            _ => 2,
+                        });
+
+                        vec![BitcoinWallet::to_psbt::<BitcoinMainnet>(
+                            inputs,
+                            outputs,
+                            version,
+                            lock_time,
+                            replaceable,
+                            data,
+                            psbt_version,
+                            modifiable,
+                        )
+                        .or(BitcoinWallet::to_psbt::<T>(
+                            inputs,
+                            outputs,
+                            version,
+                            lock_time,
+                            replaceable,
+                            data,
+                            psbt_version,
+                            modifiable,
+                        ))?]
+                    } else if let (Some(psbt), Some(transaction_inputs)) = (&options.psbt, &options.transaction_inputs)
+                    {
+                        let inputs: &Vec<BitcoinInput> = &from_str(transaction_inputs)?;
+
+                        vec![BitcoinWallet::to_updated_psbt::<BitcoinMainnet>(psbt, inputs)
+                            .or(BitcoinWallet::to_updated_psbt::<T>(psbt, inputs))?]
+                    } else if let (Some(psbt), Some(private_key)) = (&options.psbt, &options.private) {
+                        vec![BitcoinWallet::to_signed_psbt::<BitcoinMainnet>(psbt, private_key)
+                            .or(BitcoinWallet::to_signed_psbt::<T>(psbt, private_key))?]
+                    } else if let Some(psbt) = &options.psbt {
+                        vec![BitcoinWallet::to_finalized_psbt::<BitcoinMainnet>(psbt)
+                            .or(BitcoinWallet::to_finalized_psbt::<T>(psbt))?]
+                    } else {
+                        vec![]
+                    }
+                }
+                Some("transaction") => {
+                    if let (Some(transaction_exchange), Some(private_key)) =
+                        (&options.transaction_exchange, &options.private)
+                    {
+                        vec![BitcoinWallet::to_imported_transaction::<BitcoinMainnet>(
+                            transaction_exchange,
+                            private_key,
+                        )
+                        .or(BitcoinWallet::to_imported_transaction::<T>(
+                            transaction_exchange,
+                            private_key,
+                        ))?]
+                    } else if let (Some(transaction_inputs), Some(transaction_outputs)) =
+                        (options.transaction_inputs.clone(), options.transaction_outputs.clone())
+                    {
+                        let inputs: &Vec<BitcoinInput> = &from_str(&transaction_inputs)?;
+                        let outputs = transaction_outputs.replace(&['{', '}', '"', ' '][..], "");
+                        let outputs: &Vec<&str> = &outputs.split(",").collect();
+                        let version = options.version.unwrap_or(1);
+                        let lock_time = options.lock_time.unwrap_or(0);
+                        let replaceable = options.replaceable;
+                        let data = options.data.as_deref();
+
+                        match options.export {
+                            true => vec![BitcoinWallet::to_exported_transaction::<BitcoinMainnet>(
+                                inputs,
+                                outputs,
+                                version,
+                                lock_time,
+                                replaceable,
+                                data,
                             )
-                            .or(BitcoinWallet::to_raw_transaction::<BitcoinTestnet>(
-                                inputs, outputs, version, lock_time,
-                            ))?]
-                        } else if let (Some(transaction_hex), Some(transaction_inputs)) =
-                            (options.transaction_hex.clone(), options.transaction_inputs.clone())
-                        {
-                            let inputs: &Vec<BitcoinInput> = &from_str(&transaction_inputs)?;
-
-                            vec![
-                                BitcoinWallet::to_signed_transaction::<BitcoinMainnet>(&transaction_hex, inputs).or(
-                                    BitcoinWallet::to_signed_transaction::<BitcoinTestnet>(&transaction_hex, inputs),
-                                )?,
-                            ]
-                        } else {
-                            vec![]
+                            .or(BitcoinWallet::to_exported_transaction::<T>(
+                                inputs,
+                                outputs,
+                                version,
+                                lock_time,
+                                replaceable,
+                                data,
+                            ))?],
+                            false => vec![BitcoinWallet::to_raw_transaction::<BitcoinMainnet>(
+                                inputs,
+                                outputs,
+                                version,
+                                lock_time,
+                                replaceable,
+                                data,
+                            )
+                            .or(BitcoinWallet::to_raw_transaction::<T>(
+                                inputs,
+                                outputs,
+                                version,
+                                lock_time,
+                                replaceable,
+                                data,
+                            ))?],
                         }
+                    } else if let (Some(transaction_hex), Some(transaction_inputs)) =
+                        (options.transaction_hex.clone(), options.transaction_inputs.clone())
+                    {
+                        let inputs: &Vec<BitcoinInput> = &from_str(&transaction_inputs)?;
+
+                        vec![
+                            BitcoinWallet::to_signed_transaction::<BitcoinMainnet>(&transaction_hex, inputs)
+                                .or(BitcoinWallet::to_signed_transaction::<T>(&transaction_hex, inputs))?,
+                        ]
+                    } else {
+                        vec![]
                     }
-                    _ => (0..options.count)
-                        .flat_map(
-                            |_| match BitcoinWallet::new::<N, _>(&mut StdRng::from_entropy(), &options.format) {
-                                Ok(wallet) => vec![wallet],
-                                _ => vec![],
-                            },
-                        )
+                }
+                #[cfg(feature = "trezor")]
+                Some("trezor") => {
+                    let format = match options.format_specified {
+                        true => options.format.clone(),
+                        false => BitcoinFormat::Bech32,
+                    };
+                    match &options.psbt {
+                        Some(psbt) => vec![BitcoinWallet::to_trezor_signed_psbt::<N>(
+                            &options.device,
+                            &options.bridge,
+                            &options.passphrase,
+                            psbt,
+                        )?],
+                        None => vec![BitcoinWallet::to_trezor_address::<N>(
+                            &options.device,
+                            &options.bridge,
+                            &options.passphrase,
+                            &format,
+                            options.account,
+                            options.chain,
+                            options.index,
+                        )?],
+                    }
+                }
+                Some("vanity") => match (&options.vanity_prefix, &options.vanity_regex) {
+                    (Some(prefix), _) => vec![BitcoinWallet::new_vanity::<N, _>(
+                        source,
+                        prefix,
+                        false,
+                        &options.format,
+                        options.threads,
+                    )?],
+                    (_, Some(regex)) => vec![BitcoinWallet::new_vanity::<N, _>(
+                        source,
+                        regex,
+                        true,
+                        &options.format,
+                        options.threads,
+                    )?],
+                    _ => vec![],
+                },
+                _ => match &options.bip38 {
+                    Some(passphrase) => (0..options.count)
+                        .map(|_| BitcoinWallet::new_bip38::<N, _>(source, &options.format, passphrase))
+                        .collect::<Result<Vec<_>, _>>()?,
+                    None => (0..options.count)
+                        .flat_map(|_| match BitcoinWallet::new::<N, _>(source, &options.format) {
+                            Ok(wallet) => vec![wallet],
+                            _ => vec![],
+                        })
                         .collect(),
-                };
+                },
+            };
+
+            // Adds the payment URI of each address, if requested
+            if options.uri {
+                wallets
+                    .iter_mut()
+                    .try_for_each(|wallet| wallet.set_uri::<BitcoinMainnet>().or_else(|_| wallet.set_uri::<T>()))?;
+            }
 
             match options.json {
                 true => println!("{}\n", serde_json::to_string_pretty(&wallets)?),
@@ -899,40 +3686,70 @@ impl CLI for BitcoinCLI {
 
         match options.language.as_str() {
             "chinese_simplified" => match options.network.as_str() {
-                "testnet" => output::<BitcoinTestnet, ChineseSimplified>(options),
-                _ => output::<BitcoinMainnet, ChineseSimplified>(options),
+                "regtest" => output::<BitcoinRegtest, BitcoinRegtest, ChineseSimplified>(options, source),
+                "signet" => output::<BitcoinSignet, BitcoinSignet, ChineseSimplified>(options, source),
+                "testnet" => output::<BitcoinTestnet, BitcoinTestnet, ChineseSimplified>(options, source),
+                _ => output::<BitcoinMainnet, BitcoinTestnet, ChineseSimplified>(options, source),
             },
             "chinese_traditional" => match options.network.as_str() {
-                "testnet" => output::<BitcoinTestnet, ChineseTraditional>(options),
-                _ => output::<BitcoinMainnet, ChineseTraditional>(options),
+                "regtest" => output::<BitcoinRegtest, BitcoinRegtest, ChineseTraditional>(options, source),
+                "signet" => output::<BitcoinSignet, BitcoinSignet, ChineseTraditional>(options, source),
+                "testnet" => output::<BitcoinTestnet, BitcoinTestnet, ChineseTraditional>(options, source),
+                _ => output::<BitcoinMainnet, BitcoinTestnet, ChineseTraditional>(options, source),
+            },
+            "czech" => match options.network.as_str() {
+                "regtest" => output::<BitcoinRegtest, BitcoinRegtest, Czech>(options, source),
+                "signet" => output::<BitcoinSignet, BitcoinSignet, Czech>(options, source),
+                "testnet" => output::<BitcoinTestnet, BitcoinTestnet, Czech>(options, source),
+                _ => output::<BitcoinMainnet, BitcoinTestnet, Czech>(options, source),
             },
             "english" => match options.network.as_str() {
-                "testnet" => output::<BitcoinTestnet, English>(options),
-                _ => output::<BitcoinMainnet, English>(options),
+                "regtest" => output::<BitcoinRegtest, BitcoinRegtest, English>(options, source),
+                "signet" => output::<BitcoinSignet, BitcoinSignet, English>(options, source),
+                "testnet" => output::<BitcoinTestnet, BitcoinTestnet, English>(options, source),
+                _ => output::<BitcoinMainnet, BitcoinTestnet, English>(options, source),
             },
             "french" => match options.network.as_str() {
-                "testnet" => output::<BitcoinTestnet, French>(options),
-                _ => output::<BitcoinMainnet, French>(options),
+                "regtest" => output::<BitcoinRegtest, BitcoinRegtest, French>(options, source),
+                "signet" => output::<BitcoinSignet, BitcoinSignet, French>(options, source),
+                "testnet" => output::<BitcoinTestnet, BitcoinTestnet, French>(options, source),
+                _ => output::<BitcoinMainnet, BitcoinTestnet, French>(options, source),
             },
             "italian" => match options.network.as_str() {
-                "testnet" => output::<BitcoinTestnet, Italian>(options),
-                _ => output::<BitcoinMainnet, Italian>(options),
+                "regtest" => output::<BitcoinRegtest, BitcoinRegtest, Italian>(options, source),
+                "signet" => output::<BitcoinSignet, BitcoinSignet, Italian>(options, source),
+                "testnet" => output::<BitcoinTestnet, BitcoinTestnet, Italian>(options, source),
+                _ => output::<BitcoinMainnet, BitcoinTestnet, Italian>(options, source),
             },
             "japanese" => match options.network.as_str() {
-                "testnet" => output::<BitcoinTestnet, Japanese>(options),
-                _ => output::<BitcoinMainnet, Japanese>(options),
+                "regtest" => output::<BitcoinRegtest, BitcoinRegtest, Japanese>(options, source),
+                "signet" => output::<BitcoinSignet, BitcoinSignet, Japanese>(options, source),
+                "testnet" => output::<BitcoinTestnet, BitcoinTestnet, Japanese>(options, source),
+                _ => output::<BitcoinMainnet, BitcoinTestnet, Japanese>(options, source),
             },
             "korean" => match options.network.as_str() {
-                "testnet" => output::<BitcoinTestnet, Korean>(options),
-                _ => output::<BitcoinMainnet, Korean>(options),
+                "regtest" => output::<BitcoinRegtest, BitcoinRegtest, Korean>(options, source),
+                "signet" => output::<BitcoinSignet, BitcoinSignet, Korean>(options, source),
+                "testnet" => output::<BitcoinTestnet, BitcoinTestnet, Korean>(options, source),
+                _ => output::<BitcoinMainnet, BitcoinTestnet, Korean>(options, source),
+            },
+            "portuguese" => match options.network.as_str() {
+                "regtest" => output::<BitcoinRegtest, BitcoinRegtest, Portuguese>(options, source),
+                "signet" => output::<BitcoinSignet, BitcoinSignet, Portuguese>(options, source),
+                "testnet" => output::<BitcoinTestnet, BitcoinTestnet, Portuguese>(options, source),
+                _ => output::<BitcoinMainnet, BitcoinTestnet, Portuguese>(options, source),
             },
             "spanish" => match options.network.as_str() {
-                "testnet" => output::<BitcoinTestnet, Spanish>(options),
-                _ => output::<BitcoinMainnet, Spanish>(options),
+                "regtest" => output::<BitcoinRegtest, BitcoinRegtest, Spanish>(options, source),
+                "signet" => output::<BitcoinSignet, BitcoinSignet, Spanish>(options, source),
+                "testnet" => output::<BitcoinTestnet, BitcoinTestnet, Spanish>(options, source),
+                _ => output::<BitcoinMainnet, BitcoinTestnet, Spanish>(options, source),
             },
             _ => match options.network.as_str() {
-                "testnet" => output::<BitcoinTestnet, English>(options),
-                _ => output::<BitcoinMainnet, English>(options),
+                "regtest" => output::<BitcoinRegtest, BitcoinRegtest, English>(options, source),
+                "signet" => output::<BitcoinSignet, BitcoinSignet, English>(options, source),
+                "testnet" => output::<BitcoinTestnet, BitcoinTestnet, English>(options, source),
+                _ => output::<BitcoinMainnet, BitcoinTestnet, English>(options, source),
             },
         }
     }
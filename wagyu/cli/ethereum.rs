@@ -1,20 +1,24 @@
-use crate::cli::{flag, option, subcommand, types::*, CLIError, CLI};
+use crate::cli::{
+    decode_seed_qr, decrypt_backup, encode_seed_qr, encrypt_backup, flag, option, print_seed_progress, prompt_password,
+    subcommand, types::*, CLIError, ResultExt, Share, CLI,
+};
 use crate::ethereum::{
-    wordlist::*, EthereumAddress, EthereumAmount, EthereumDerivationPath, EthereumExtendedPrivateKey,
-    EthereumExtendedPublicKey, EthereumFormat, EthereumMnemonic, EthereumNetwork, EthereumPrivateKey,
-    EthereumPublicKey, EthereumTransaction, EthereumTransactionParameters, Goerli, Kovan, Mainnet as EthereumMainnet,
-    Rinkeby, Ropsten,
+    wordlist::*, AbiFunction, Erc1155Token, Erc20Token, Erc721Token, EthereumAccessListItem, EthereumAddress,
+    EthereumAmount, EthereumChain, EthereumDerivationPath, EthereumExtendedPrivateKey, EthereumExtendedPublicKey,
+    EthereumFormat, EthereumMnemonic, EthereumNetwork, EthereumPrivateKey, EthereumPublicKey, EthereumTransaction,
+    EthereumTransactionParameters, Goerli, Kovan, Mainnet as EthereumMainnet, Rinkeby, Ropsten,
 };
 use crate::model::{
-    ExtendedPrivateKey, ExtendedPublicKey, Mnemonic, MnemonicCount, MnemonicExtended, Network, PrivateKey, PublicKey,
-    Transaction,
+    detect_language, ChildIndex, ChildIndexRange, CoinType, EntropyRng, EntropySource, ExtendedPrivateKey,
+    ExtendedPublicKey, ManualEntropy, Mnemonic, MnemonicCount, MnemonicEntropy, MnemonicExtended, Network, PrivateKey,
+    PublicKey, Slip39Group, Slip39Share, Transaction, TransactionError, WalletPreset,
 };
 
 use clap::{ArgMatches, Values};
 use colored::*;
 use core::{fmt, fmt::Display, str::FromStr};
-use rand::{rngs::StdRng, Rng};
-use rand_core::SeedableRng;
+use rand::Rng;
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use serde_json::from_str;
 
@@ -26,10 +30,22 @@ struct EthereumWallet {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub path: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub key_origin: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub password: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mnemonic: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub backup: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed_qr: Option<String>,
+    #[serde(skip)]
+    pub qr_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_threshold: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shares: Option<Vec<Share>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub extended_private_key: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub extended_public_key: Option<String>,
@@ -44,12 +60,18 @@ struct EthereumWallet {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub network: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain_id: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contract_address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub transaction_hex: Option<String>,
 }
 
 impl EthereumWallet {
-    pub fn new<R: Rng>(rng: &mut R) -> Result<Self, CLIError> {
-        let private_key = EthereumPrivateKey::new(rng)?;
+    pub fn new<E: EntropySource + ?Sized>(source: &mut E) -> Result<Self, CLIError> {
+        let private_key = EthereumPrivateKey::new_with_entropy(source)?;
         let public_key = private_key.to_public_key();
         let address = public_key.to_address(&EthereumFormat::Standard)?;
         Ok(Self {
@@ -60,22 +82,121 @@ impl EthereumWallet {
         })
     }
 
-    pub fn new_hd<N: EthereumNetwork, W: EthereumWordlist, R: Rng>(
-        rng: &mut R,
+    /// Returns the HD wallets for the given number of new mnemonics at each of the given paths,
+    /// deriving the seeds of the mnemonics in parallel.
+    pub fn new_hd<N: EthereumNetwork, W: EthereumWordlist, E: EntropySource + ?Sized>(
+        source: &mut E,
+        count: usize,
+        word_count: u8,
+        password: Option<&str>,
+        paths: &[String],
+    ) -> Result<Vec<Self>, CLIError> {
+        let mnemonics = (0..count)
+            .map(|_| EthereumMnemonic::<N, W>::new_with_count_and_entropy(source, word_count))
+            .collect::<Result<Vec<_>, _>>()?;
+        let seeds = match mnemonics.as_slice() {
+            [mnemonic] => vec![mnemonic.to_seed_with_progress(password, &mut print_seed_progress)?],
+            mnemonics => EthereumMnemonic::to_seeds(mnemonics, password)?,
+        };
+
+        let mut wallets = vec![];
+        for (mnemonic, seed) in mnemonics.iter().zip(seeds) {
+            let master_extended_private_key =
+                EthereumExtendedPrivateKey::<N>::new_master(&seed, &EthereumFormat::Standard)?;
+            for path in paths {
+                let derivation_path = EthereumDerivationPath::from_str(path).context_with_input(
+                    "ethereum",
+                    "parse derivation path",
+                    path,
+                )?;
+                let extended_private_key = master_extended_private_key.derive(&derivation_path)?;
+                let key_origin = master_extended_private_key.key_origin(&derivation_path)?;
+                let extended_public_key = extended_private_key.to_extended_public_key();
+                let private_key = extended_private_key.to_private_key();
+                let public_key = extended_public_key.to_public_key();
+                let address = public_key.to_address(&EthereumFormat::Standard)?;
+                wallets.push(Self {
+                    path: Some(path.to_string()),
+                    key_origin: Some(key_origin.to_string()),
+                    password: password.map(String::from),
+                    mnemonic: Some(mnemonic.to_string()),
+                    extended_private_key: Some(extended_private_key.to_string()),
+                    extended_public_key: Some(extended_public_key.to_string()),
+                    private_key: Some(private_key.to_string()),
+                    public_key: Some(public_key.to_string()),
+                    address: Some(address.to_string()),
+                    ..Default::default()
+                });
+            }
+        }
+        Ok(wallets)
+    }
+
+    /// Returns the HD wallet of the mnemonic of the given word count, from the entropy of the given rolls.
+    pub fn new_hd_from_rolls<N: EthereumNetwork, W: EthereumWordlist>(
+        source: ManualEntropy,
+        rolls: &str,
         word_count: u8,
         password: Option<&str>,
         path: &str,
     ) -> Result<Self, CLIError> {
-        let mnemonic = EthereumMnemonic::<N, W>::new_with_count(rng, word_count)?;
-        let master_extended_private_key = mnemonic.to_extended_private_key(password)?;
-        let derivation_path = EthereumDerivationPath::from_str(path)?;
+        let entropy = source
+            .to_entropy(rolls, word_count as usize / 3 * 4)
+            .context("ethereum", "read entropy rolls")?;
+        let mnemonic = EthereumMnemonic::<N, W>::from_entropy(&entropy)?;
+        Self::from_mnemonic::<N, W>(&mnemonic.to_phrase()?, password, path)
+    }
+
+    /// Returns the wallets of the mnemonics completing the given partial mnemonic that derive the given address.
+    pub fn recover<N: EthereumNetwork, W: EthereumWordlist>(
+        mnemonic: &str,
+        password: Option<&str>,
+        paths: &[String],
+        address: &str,
+    ) -> Result<Vec<Self>, CLIError> {
+        let candidates =
+            EthereumMnemonic::<N, W>::from_partial_phrase(mnemonic).context("ethereum", "parse partial mnemonic")?;
+        let wallets = candidates
+            .par_iter()
+            .flat_map(|candidate| {
+                paths
+                    .par_iter()
+                    .map(move |path| Self::from_mnemonic::<N, W>(&candidate.to_string(), password, path))
+            })
+            .filter(|wallet| match wallet {
+                Ok(wallet) => match &wallet.address {
+                    Some(wallet_address) => wallet_address.eq_ignore_ascii_case(address),
+                    None => false,
+                },
+                Err(_) => true,
+            })
+            .collect::<Result<Vec<Self>, CLIError>>()?;
+
+        match wallets.is_empty() {
+            true => Err(CLIError::UnrecoveredMnemonic(address.into())),
+            false => Ok(wallets),
+        }
+    }
+
+    pub fn from_mnemonic<N: EthereumNetwork, W: EthereumWordlist>(
+        mnemonic: &str,
+        password: Option<&str>,
+        path: &str,
+    ) -> Result<Self, CLIError> {
+        let mnemonic = EthereumMnemonic::<N, W>::from_phrase(&mnemonic).context("ethereum", "parse mnemonic")?;
+        let master_extended_private_key =
+            mnemonic.to_extended_private_key_with_progress(password, &mut print_seed_progress)?;
+        let derivation_path =
+            EthereumDerivationPath::from_str(path).context_with_input("ethereum", "parse derivation path", path)?;
         let extended_private_key = master_extended_private_key.derive(&derivation_path)?;
+        let key_origin = master_extended_private_key.key_origin(&derivation_path)?;
         let extended_public_key = extended_private_key.to_extended_public_key();
         let private_key = extended_private_key.to_private_key();
         let public_key = extended_public_key.to_public_key();
         let address = public_key.to_address(&EthereumFormat::Standard)?;
         Ok(Self {
             path: Some(path.to_string()),
+            key_origin: Some(key_origin.to_string()),
             password: password.map(String::from),
             mnemonic: Some(mnemonic.to_string()),
             extended_private_key: Some(extended_private_key.to_string()),
@@ -87,23 +208,69 @@ impl EthereumWallet {
         })
     }
 
-    pub fn from_mnemonic<N: EthereumNetwork, W: EthereumWordlist>(
-        mnemonic: &str,
+    pub fn new_hd_shares<N: EthereumNetwork, E: EntropySource + ?Sized>(
+        source: &mut E,
+        group_threshold: u8,
+        groups: &[Slip39Group],
         password: Option<&str>,
         path: &str,
     ) -> Result<Self, CLIError> {
-        let mnemonic = EthereumMnemonic::<N, W>::from_phrase(&mnemonic)?;
-        let master_extended_private_key = mnemonic.to_extended_private_key(password)?;
-        let derivation_path = EthereumDerivationPath::from_str(path)?;
+        let rng = &mut EntropyRng::new(source);
+        let master_secret: [u8; 16] = rng.gen();
+        let shares = Slip39Share::split(
+            rng,
+            &master_secret,
+            password.unwrap_or(""),
+            group_threshold,
+            groups,
+            0,
+            true,
+        )?;
+        let shares = shares.concat();
+        Ok(Self {
+            group_threshold: Some(group_threshold),
+            shares: Some(Share::from_shares(&shares)),
+            ..Self::from_master_secret::<N>(&master_secret, password, path)?
+        })
+    }
+
+    pub fn from_shares<N: EthereumNetwork>(
+        shares: &[String],
+        password: Option<&str>,
+        path: &str,
+    ) -> Result<Self, CLIError> {
+        let shares = shares
+            .iter()
+            .map(|share| Slip39Share::from_phrase(share).context("ethereum", "parse share"))
+            .collect::<Result<Vec<Slip39Share>, CLIError>>()?;
+        let master_secret =
+            Slip39Share::combine(&shares, password.unwrap_or("")).context("ethereum", "combine shares")?;
+        Ok(Self {
+            group_threshold: shares.first().map(|share| share.group_threshold),
+            shares: Some(Share::from_shares(&shares)),
+            ..Self::from_master_secret::<N>(&master_secret, password, path)?
+        })
+    }
+
+    fn from_master_secret<N: EthereumNetwork>(
+        master_secret: &[u8],
+        password: Option<&str>,
+        path: &str,
+    ) -> Result<Self, CLIError> {
+        let master_extended_private_key =
+            EthereumExtendedPrivateKey::<N>::new_master(master_secret, &EthereumFormat::Standard)?;
+        let derivation_path =
+            EthereumDerivationPath::from_str(path).context_with_input("ethereum", "parse derivation path", path)?;
         let extended_private_key = master_extended_private_key.derive(&derivation_path)?;
+        let key_origin = master_extended_private_key.key_origin(&derivation_path)?;
         let extended_public_key = extended_private_key.to_extended_public_key();
         let private_key = extended_private_key.to_private_key();
         let public_key = extended_public_key.to_public_key();
         let address = public_key.to_address(&EthereumFormat::Standard)?;
         Ok(Self {
             path: Some(path.to_string()),
+            key_origin: Some(key_origin.to_string()),
             password: password.map(String::from),
-            mnemonic: Some(mnemonic.to_string()),
             extended_private_key: Some(extended_private_key.to_string()),
             extended_public_key: Some(extended_public_key.to_string()),
             private_key: Some(private_key.to_string()),
@@ -113,13 +280,43 @@ impl EthereumWallet {
         })
     }
 
+    pub fn to_backup<N: EthereumNetwork, W: EthereumWordlist, E: EntropySource + ?Sized>(
+        source: &mut E,
+        mnemonic: &str,
+        password: &str,
+    ) -> Result<Self, CLIError> {
+        EthereumMnemonic::<N, W>::from_phrase(mnemonic).context("ethereum", "parse mnemonic")?;
+        Ok(Self {
+            backup: Some(encrypt_backup(source, mnemonic, password).context("ethereum", "encrypt backup")?),
+            ..Default::default()
+        })
+    }
+
+    pub fn to_seed_qr<N: EthereumNetwork, W: EthereumWordlist>(
+        mnemonic: &str,
+        compact: bool,
+    ) -> Result<Self, CLIError> {
+        let mnemonic = EthereumMnemonic::<N, W>::from_phrase(mnemonic).context("ethereum", "parse mnemonic")?;
+        let (seed_qr, qr_code) = encode_seed_qr(&mnemonic, compact).context("ethereum", "encode SeedQR")?;
+        Ok(Self {
+            seed_qr: Some(seed_qr),
+            qr_code: Some(qr_code),
+            ..Default::default()
+        })
+    }
+
     pub fn from_extended_private_key<N: EthereumNetwork>(
         extended_private_key: &str,
         path: &Option<String>,
     ) -> Result<Self, CLIError> {
-        let mut extended_private_key = EthereumExtendedPrivateKey::<N>::from_str(extended_private_key)?;
+        let mut extended_private_key = EthereumExtendedPrivateKey::<N>::from_str(extended_private_key)
+            .context("ethereum", "parse extended private key")?;
         if let Some(derivation_path) = path {
-            let derivation_path = EthereumDerivationPath::from_str(&derivation_path)?;
+            let derivation_path = EthereumDerivationPath::from_str(&derivation_path).context_with_input(
+                "ethereum",
+                "parse derivation path",
+                derivation_path,
+            )?;
             extended_private_key = extended_private_key.derive(&derivation_path)?;
         }
         let extended_public_key = extended_private_key.to_extended_public_key();
@@ -141,9 +338,14 @@ impl EthereumWallet {
         extended_public_key: &str,
         path: &Option<String>,
     ) -> Result<Self, CLIError> {
-        let mut extended_public_key = EthereumExtendedPublicKey::<N>::from_str(extended_public_key)?;
+        let mut extended_public_key = EthereumExtendedPublicKey::<N>::from_str(extended_public_key)
+            .context_with_input("ethereum", "parse extended public key", extended_public_key)?;
         if let Some(derivation_path) = path {
-            let derivation_path = EthereumDerivationPath::from_str(&derivation_path)?;
+            let derivation_path = EthereumDerivationPath::from_str(&derivation_path).context_with_input(
+                "ethereum",
+                "parse derivation path",
+                derivation_path,
+            )?;
             extended_public_key = extended_public_key.derive(&derivation_path)?;
         }
         let public_key = extended_public_key.to_public_key();
@@ -157,8 +359,44 @@ impl EthereumWallet {
         })
     }
 
+    pub fn from_extended_public_key_children<N: EthereumNetwork>(
+        extended_public_key: &str,
+        path: &Option<String>,
+        index: u32,
+        indices: u32,
+    ) -> Result<Vec<Self>, CLIError> {
+        let mut extended_public_key = EthereumExtendedPublicKey::<N>::from_str(extended_public_key)
+            .context_with_input("ethereum", "parse extended public key", extended_public_key)?;
+        if let Some(derivation_path) = path {
+            let derivation_path = EthereumDerivationPath::from_str(&derivation_path).context_with_input(
+                "ethereum",
+                "parse derivation path",
+                derivation_path,
+            )?;
+            extended_public_key = extended_public_key.derive(&derivation_path)?;
+        }
+
+        let parent_path = path.as_ref().map(String::as_str).unwrap_or("m");
+        let range = ChildIndexRange::from_count(ChildIndex::normal(index)?, indices)?;
+        let children = extended_public_key.derive_children(range.clone())?;
+        range
+            .zip(children)
+            .map(|(child_index, child)| {
+                let public_key = child.to_public_key();
+                let address = public_key.to_address(&EthereumFormat::Standard)?;
+                Ok(Self {
+                    path: Some(format!("{}/{}", parent_path, child_index)),
+                    extended_public_key: Some(child.to_string()),
+                    public_key: Some(public_key.to_string()),
+                    address: Some(address.to_string()),
+                    ..Default::default()
+                })
+            })
+            .collect()
+    }
+
     pub fn from_private_key(private_key: &str) -> Result<Self, CLIError> {
-        let private_key = EthereumPrivateKey::from_str(private_key)?;
+        let private_key = EthereumPrivateKey::from_str(private_key).context("ethereum", "parse private key")?;
         let public_key = private_key.to_public_key();
         let address = public_key.to_address(&EthereumFormat::Standard)?;
         Ok(Self {
@@ -170,7 +408,8 @@ impl EthereumWallet {
     }
 
     pub fn from_public_key(public_key: &str) -> Result<Self, CLIError> {
-        let public_key = EthereumPublicKey::from_str(public_key)?;
+        let public_key =
+            EthereumPublicKey::from_str(public_key).context_with_input("ethereum", "parse public key", public_key)?;
         let address = public_key.to_address(&EthereumFormat::Standard)?;
         Ok(Self {
             public_key: Some(public_key.to_string()),
@@ -180,7 +419,7 @@ impl EthereumWallet {
     }
 
     pub fn from_address(address: &str) -> Result<Self, CLIError> {
-        let address = EthereumAddress::from_str(address)?;
+        let address = EthereumAddress::from_str(address).context_with_input("ethereum", "parse address", address)?;
         Ok(Self {
             address: Some(address.to_string()),
             ..Default::default()
@@ -188,19 +427,77 @@ impl EthereumWallet {
     }
 
     pub fn to_raw_transaction<N: EthereumNetwork>(parameters: EthereumInput) -> Result<Self, CLIError> {
+        // EIP-1559 transactions have a maximum fee per gas in place of the gas price
+        let gas_price = match (&parameters.gas_price, &parameters.max_fee_per_gas) {
+            (Some(gas_price), _) => gas_price.as_str(),
+            (None, Some(_)) => "0",
+            (None, None) => return Err(TransactionError::InvalidInputs("gasPrice".to_string()).into()),
+        };
+        // Contract deployments have no receiver, and their data is the init code of the contract
+        let (receiver, amount, data) = match (&parameters.to, &parameters.init_code) {
+            (Some(to), None) => {
+                let (receiver, amount, data) = parameters.to_transfer(to)?;
+                (Some(receiver), amount, data)
+            }
+            (None, Some(init_code)) => match (&parameters.data, &parameters.call, parameters.has_token()) {
+                (None, None, false) => (None, EthereumAmount::from_wei(&parameters.value)?, from_hex(init_code)?),
+                _ => {
+                    return Err(TransactionError::Message(
+                        "a contract deployment has no data, call, or token field".to_string(),
+                    )
+                    .into())
+                }
+            },
+            (Some(_), Some(_)) => {
+                return Err(TransactionError::Message("a contract deployment has no to field".to_string()).into())
+            }
+            (None, None) => return Err(TransactionError::InvalidInputs("to".to_string()).into()),
+        };
+        // The sender and nonce of a contract deployment determine the address of the contract
+        let contract_address = match (&receiver, &parameters.from) {
+            (None, Some(from)) => Some(
+                EthereumAddress::create_address(
+                    &EthereumAddress::from_str(from)?,
+                    EthereumAmount::u256_from_str(&parameters.nonce.to_string())?,
+                )?
+                .to_string(),
+            ),
+            _ => None,
+        };
         let transaction_parameters = EthereumTransactionParameters {
-            receiver: EthereumAddress::from_str(&parameters.to)?,
-            amount: EthereumAmount::from_wei(&parameters.value)?,
+            receiver,
+            amount,
             gas: EthereumAmount::u256_from_str(&parameters.gas)?,
-            gas_price: EthereumAmount::from_wei(&parameters.gas_price)?,
+            gas_price: EthereumAmount::from_wei(gas_price)?,
+            max_fee_per_gas: match &parameters.max_fee_per_gas {
+                Some(fee) => Some(EthereumAmount::from_wei(fee)?),
+                None => None,
+            },
+            max_priority_fee_per_gas: match &parameters.max_priority_fee_per_gas {
+                Some(fee) => Some(EthereumAmount::from_wei(fee)?),
+                None => None,
+            },
+            access_list: match &parameters.access_list {
+                Some(access_list) => Some(
+                    access_list
+                        .iter()
+                        .map(EthereumAccessListInput::to_access_list_item)
+                        .collect::<Result<_, _>>()?,
+                ),
+                None => None,
+            },
             nonce: EthereumAmount::u256_from_str(&parameters.nonce.to_string())?,
-            data: parameters.data.unwrap_or("".to_string()).as_bytes().to_vec(),
+            data,
+            chain_id: parameters.chain_id,
         };
 
         let raw_transaction = EthereumTransaction::<N>::new(&transaction_parameters)?;
         let raw_transaction_hex = hex::encode(raw_transaction.to_transaction_bytes()?);
 
         Ok(Self {
+            chain: raw_transaction.chain().map(|chain| chain.display_name().to_string()),
+            chain_id: Some(raw_transaction.chain_id()),
+            contract_address,
             transaction_hex: Some(format!("0x{}", raw_transaction_hex)),
             ..Default::default()
         })
@@ -215,12 +512,15 @@ impl EthereumWallet {
             _ => hex::decode(&transaction_hex)?,
         };
 
-        let private_key = EthereumPrivateKey::from_str(&private_key)?;
+        let private_key = EthereumPrivateKey::from_str(&private_key).context("ethereum", "parse private key")?;
 
         let mut transaction = EthereumTransaction::<N>::from_transaction_bytes(&transaction_bytes)?;
         transaction = transaction.sign(&private_key)?;
 
         Ok(Self {
+            chain: transaction.chain().map(|chain| chain.display_name().to_string()),
+            chain_id: Some(transaction.chain_id()),
+            contract_address: transaction.contract_address()?.map(|address| address.to_string()),
             transaction_id: Some(transaction.to_transaction_id()?.to_string()),
             transaction_hex: Some(format!("0x{}", hex::encode(&transaction.to_transaction_bytes()?))),
             ..Default::default()
@@ -236,6 +536,10 @@ impl Display for EthereumWallet {
                 Some(path) => format!("      {}                 {}\n", "Path".cyan().bold(), path),
                 _ => "".to_owned(),
             },
+            match &self.key_origin {
+                Some(key_origin) => format!("      {}           {}\n", "Key Origin".cyan().bold(), key_origin),
+                _ => "".to_owned(),
+            },
             match &self.password {
                 Some(password) => format!("      {}             {}\n", "Password".cyan().bold(), password),
                 _ => "".to_owned(),
@@ -244,6 +548,35 @@ impl Display for EthereumWallet {
                 Some(mnemonic) => format!("      {}             {}\n", "Mnemonic".cyan().bold(), mnemonic),
                 _ => "".to_owned(),
             },
+            match &self.backup {
+                Some(backup) => format!("      {}               {}\n", "Backup".cyan().bold(), backup),
+                _ => "".to_owned(),
+            },
+            match &self.seed_qr {
+                Some(seed_qr) => format!("      {}               {}\n", "SeedQR".cyan().bold(), seed_qr),
+                _ => "".to_owned(),
+            },
+            match &self.qr_code {
+                Some(qr_code) => format!("\n{}\n", qr_code),
+                _ => "".to_owned(),
+            },
+            match &self.group_threshold {
+                Some(group_threshold) => {
+                    format!("      {}      {}\n", "Group Threshold".cyan().bold(), group_threshold)
+                }
+                _ => "".to_owned(),
+            },
+            match &self.shares {
+                Some(shares) => shares
+                    .iter()
+                    .map(|share| {
+                        let label = format!("Group {} Share {}", share.group, share.member);
+                        let padding = " ".repeat(21 - label.len());
+                        format!("      {}{}{}\n", label.cyan().bold(), padding, share.share)
+                    })
+                    .collect(),
+                _ => "".to_owned(),
+            },
             match &self.extended_private_key {
                 Some(extended_private_key) => format!(
                     "      {} {}\n",
@@ -280,6 +613,20 @@ impl Display for EthereumWallet {
                 Some(network) => format!("      {}              {}\n", "Network".cyan().bold(), network),
                 _ => "".to_owned(),
             },
+            match &self.chain {
+                Some(chain) => format!("      {}                {}\n", "Chain".cyan().bold(), chain),
+                _ => "".to_owned(),
+            },
+            match &self.chain_id {
+                Some(chain_id) => format!("      {}             {}\n", "Chain Id".cyan().bold(), chain_id),
+                _ => "".to_owned(),
+            },
+            match &self.contract_address {
+                Some(contract_address) => {
+                    format!("      {}     {}\n", "Contract Address".cyan().bold(), contract_address)
+                }
+                _ => "".to_owned(),
+            },
             match &self.transaction_hex {
                 Some(transaction_hex) => {
                     format!("      {}      {}\n", "Transaction Hex".cyan().bold(), transaction_hex)
@@ -298,13 +645,208 @@ impl Display for EthereumWallet {
 /// Represents parameters for an Ethereum transaction input
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct EthereumInput {
-    pub to: String,
+    #[serde(default)]
+    pub to: Option<String>,
     pub value: String,
     pub gas: String,
-    #[serde(rename(deserialize = "gasPrice"))]
-    pub gas_price: String,
+    #[serde(rename(deserialize = "gasPrice"), default)]
+    pub gas_price: Option<String>,
+    #[serde(rename(deserialize = "maxFeePerGas"), default)]
+    pub max_fee_per_gas: Option<String>,
+    #[serde(rename(deserialize = "maxPriorityFeePerGas"), default)]
+    pub max_priority_fee_per_gas: Option<String>,
     pub nonce: u64,
     pub data: Option<String>,
+    #[serde(rename(deserialize = "initCode"), default)]
+    pub init_code: Option<String>,
+    #[serde(default)]
+    pub from: Option<String>,
+    #[serde(rename(deserialize = "accessList"), default)]
+    pub access_list: Option<Vec<EthereumAccessListInput>>,
+    pub network: Option<String>,
+    #[serde(rename(deserialize = "chainId"), default)]
+    pub chain_id: Option<u64>,
+    #[serde(default)]
+    pub erc20: Option<EthereumErc20Input>,
+    #[serde(default)]
+    pub erc721: Option<EthereumNftInput>,
+    #[serde(default)]
+    pub erc1155: Option<EthereumNftInput>,
+    #[serde(default)]
+    pub call: Option<EthereumCallInput>,
+}
+
+impl EthereumInput {
+    /// Returns the receiver, amount, and data of a transaction to the given address,
+    /// which is the recipient of a token transaction or the contract of a call.
+    fn to_transfer(&self, to: &str) -> Result<(EthereumAddress, EthereumAmount, Vec<u8>), CLIError> {
+        // Token transactions call the token contract to send the token amount to the recipient
+        let token_call = match (&self.erc20, &self.erc721, &self.erc1155) {
+            (None, None, None) => None,
+            (Some(erc20), None, None) => Some(erc20.to_call(to, &self.value)?),
+            (None, Some(erc721), None) => Some(erc721.to_erc721_call(to, &self.value)?),
+            (None, None, Some(erc1155)) => Some(erc1155.to_erc1155_call(to, &self.value)?),
+            _ => {
+                return Err(
+                    TransactionError::Message("a transaction calls at most one token contract".to_string()).into(),
+                )
+            }
+        };
+        // Contract calls encode the function and its arguments as the data of the transaction
+        let call_data = match &self.call {
+            Some(call) => Some(AbiFunction::from_str(&call.function)?.encode_json(&call.args)?),
+            None => None,
+        };
+        match (token_call, call_data) {
+            (Some(_), _) | (_, Some(_)) if self.data.is_some() => {
+                Err(TransactionError::Message("a contract call has no data field".to_string()).into())
+            }
+            (Some(_), Some(_)) => {
+                Err(TransactionError::Message("a token transaction has no call field".to_string()).into())
+            }
+            (Some((token, data)), None) => Ok((token, EthereumAmount::from_wei("0")?, data)),
+            (None, Some(data)) => Ok((
+                EthereumAddress::from_str(to)?,
+                EthereumAmount::from_wei(&self.value)?,
+                data,
+            )),
+            (None, None) => Ok((
+                EthereumAddress::from_str(to)?,
+                EthereumAmount::from_wei(&self.value)?,
+                self.data.clone().unwrap_or_default().as_bytes().to_vec(),
+            )),
+        }
+    }
+
+    /// Returns `true` if the transaction calls a token contract.
+    fn has_token(&self) -> bool {
+        self.erc20.is_some() || self.erc721.is_some() || self.erc1155.is_some()
+    }
+}
+
+/// Represents the contract function of a call transaction input
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EthereumCallInput {
+    pub function: String,
+    #[serde(default)]
+    pub args: Vec<serde_json::Value>,
+}
+
+/// Represents the token of an ERC-20 transaction input
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EthereumErc20Input {
+    pub token: String,
+    pub decimals: u8,
+    #[serde(default)]
+    pub function: Option<String>,
+}
+
+impl EthereumErc20Input {
+    /// Returns the token contract and the calldata sending the decimal amount of the token to the recipient.
+    fn to_call(&self, recipient: &str, amount: &str) -> Result<(EthereumAddress, Vec<u8>), CLIError> {
+        let token = Erc20Token::new(&EthereumAddress::from_str(&self.token)?, self.decimals);
+        let recipient = EthereumAddress::from_str(recipient)?;
+        let units = token.to_base_units(amount)?;
+        let data = match self.function.as_ref().map(String::as_str) {
+            None | Some("transfer") => token.transfer(&recipient, units)?,
+            Some("approve") => token.approve(&recipient, units)?,
+            Some(function) => {
+                return Err(TransactionError::Message(format!("unsupported erc20 function {}", function)).into())
+            }
+        };
+        Ok((token.address, data))
+    }
+}
+
+/// Represents the token of an ERC-721 or ERC-1155 transaction input
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EthereumNftInput {
+    pub token: String,
+    pub from: String,
+    #[serde(rename(deserialize = "tokenId"))]
+    pub token_id: String,
+    #[serde(default)]
+    pub data: Option<String>,
+}
+
+impl EthereumNftInput {
+    /// Returns the token contract and the calldata transferring the token id to the recipient.
+    fn to_erc721_call(&self, recipient: &str, amount: &str) -> Result<(EthereumAddress, Vec<u8>), CLIError> {
+        if amount != "1" {
+            return Err(TransactionError::Message(format!("invalid erc721 amount {}, expected 1", amount)).into());
+        }
+        let token = Erc721Token::new(&EthereumAddress::from_str(&self.token)?);
+        let data = token.safe_transfer_from(
+            &EthereumAddress::from_str(&self.from)?,
+            &EthereumAddress::from_str(recipient)?,
+            EthereumAmount::u256_from_str(&self.token_id)?,
+            &self.to_data()?,
+        )?;
+        Ok((token.address, data))
+    }
+
+    /// Returns the token contract and the calldata transferring the amount of the token id to the recipient.
+    fn to_erc1155_call(&self, recipient: &str, amount: &str) -> Result<(EthereumAddress, Vec<u8>), CLIError> {
+        let token = Erc1155Token::new(&EthereumAddress::from_str(&self.token)?);
+        let data = token.safe_transfer_from(
+            &EthereumAddress::from_str(&self.from)?,
+            &EthereumAddress::from_str(recipient)?,
+            EthereumAmount::u256_from_str(&self.token_id)?,
+            EthereumAmount::u256_from_str(amount)?,
+            &self.to_data()?,
+        )?;
+        Ok((token.address, data))
+    }
+
+    /// Returns the bytes of the hex data passed to the recipient.
+    fn to_data(&self) -> Result<Vec<u8>, CLIError> {
+        match &self.data {
+            Some(data) => from_hex(data),
+            None => Ok(vec![]),
+        }
+    }
+}
+
+/// Returns the bytes of a hex string, which may start with `0x`.
+fn from_hex(value: &str) -> Result<Vec<u8>, CLIError> {
+    match value.starts_with("0x") {
+        true => Ok(hex::decode(&value[2..])?),
+        false => Ok(hex::decode(value)?),
+    }
+}
+
+/// Represents an entry of the access list of an Ethereum transaction input
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EthereumAccessListInput {
+    pub address: String,
+    #[serde(rename(deserialize = "storageKeys"), default)]
+    pub storage_keys: Vec<String>,
+}
+
+impl EthereumAccessListInput {
+    fn to_access_list_item(&self) -> Result<EthereumAccessListItem, CLIError> {
+        let storage_keys = self
+            .storage_keys
+            .iter()
+            .map(|storage_key| {
+                let storage_key = match storage_key.starts_with("0x") {
+                    true => hex::decode(&storage_key[2..])?,
+                    false => hex::decode(storage_key)?,
+                };
+                if storage_key.len() != 32 {
+                    return Err(TransactionError::InvalidInputs("storageKeys".to_string()).into());
+                }
+                let mut key = [0u8; 32];
+                key.copy_from_slice(&storage_key);
+                Ok(key)
+            })
+            .collect::<Result<_, CLIError>>()?;
+
+        Ok(EthereumAccessListItem {
+            address: EthereumAddress::from_str(&self.address)?,
+            storage_keys,
+        })
+    }
 }
 
 /// Represents options for an Ethereum wallet
@@ -315,24 +857,39 @@ pub struct EthereumOptions {
     json: bool,
     subcommand: Option<String>,
     // HD and Import HD subcommands
+    coin: Option<String>,
     derivation: String,
+    dice: Option<String>,
     extended_private_key: Option<String>,
     extended_public_key: Option<String>,
+    group_threshold: u8,
     index: u32,
     indices: u32,
     language: String,
     mnemonic: Option<String>,
     password: Option<String>,
     path: Option<String>,
+    preset: Option<String>,
+    recover: Option<String>,
+    seed_qr: Option<String>,
+    shares: Vec<String>,
+    slip39: Option<String>,
+    watch_only: bool,
     word_count: u8,
     // Import subcommand
     address: Option<String>,
     private: Option<String>,
     public: Option<String>,
+    // Export Backup and Import Backup subcommands
+    backup: Option<String>,
+    backup_password: Option<String>,
+    // Export SeedQR subcommand
+    compact: bool,
     // Transaction subcommand
     transaction_hex: Option<String>,
     transaction_parameters: Option<String>,
     transaction_private_key: Option<String>,
+    chain: Option<String>,
     network: Option<String>,
 }
 
@@ -344,24 +901,39 @@ impl Default for EthereumOptions {
             json: false,
             subcommand: None,
             // HD and Import HD subcommands
+            coin: None,
             derivation: "ethereum".into(),
+            dice: None,
             extended_private_key: None,
             extended_public_key: None,
+            group_threshold: 1,
             index: 0,
             indices: 1,
             language: "english".into(),
             mnemonic: None,
             password: None,
             path: None,
+            preset: None,
+            recover: None,
+            seed_qr: None,
+            shares: vec![],
+            slip39: None,
+            watch_only: false,
             word_count: 12,
             // Import subcommand
             address: None,
             private: None,
             public: None,
+            // Export Backup and Import Backup subcommands
+            backup: None,
+            backup_password: None,
+            // Export SeedQR subcommand
+            compact: false,
             // Transaction subcommand
             transaction_hex: None,
             transaction_parameters: None,
             transaction_private_key: None,
+            chain: None,
             network: None,
         }
     }
@@ -371,11 +943,18 @@ impl EthereumOptions {
     fn parse(&mut self, arguments: &ArgMatches, options: &[&str]) {
         options.iter().for_each(|option| match *option {
             "address" => self.address(arguments.value_of(option)),
+            "backup" => self.backup(arguments.value_of(option)),
+            "backup password" => self.backup_password(arguments.value_of(option)),
+            "chain" => self.chain(arguments.value_of(option)),
+            "coin" => self.coin(arguments.value_of(option)),
+            "compact" => self.compact(arguments.is_present(option)),
             "count" => self.count(clap::value_t!(arguments.value_of(*option), usize).ok()),
             "createrawtransaction" => self.create_raw_transaction(arguments.value_of(option)),
             "derivation" => self.derivation(arguments.value_of(option)),
+            "dice" => self.dice(arguments.value_of(option)),
             "extended private" => self.extended_private(arguments.value_of(option)),
             "extended public" => self.extended_public(arguments.value_of(option)),
+            "group threshold" => self.group_threshold(clap::value_t!(arguments.value_of(*option), u8).ok()),
             "json" => self.json(arguments.is_present(option)),
             "index" => self.index(clap::value_t!(arguments.value_of(*option), u32).ok()),
             "indices" => self.indices(clap::value_t!(arguments.value_of(*option), u32).ok()),
@@ -383,9 +962,15 @@ impl EthereumOptions {
             "mnemonic" => self.mnemonic(arguments.value_of(option)),
             "network" => self.network(arguments.value_of(option)),
             "password" => self.password(arguments.value_of(option)),
+            "preset" => self.preset(arguments.value_of(option)),
             "private" => self.private(arguments.value_of(option)),
             "public" => self.public(arguments.value_of(option)),
+            "recover" => self.recover(arguments.value_of(option)),
+            "seedqr" => self.seed_qr(arguments.value_of(option)),
+            "share" => self.share(arguments.values_of(option)),
             "signrawtransaction" => self.sign_raw_transaction(arguments.values_of(option)),
+            "slip39" => self.slip39(arguments.value_of(option)),
+            "watch only" => self.watch_only(arguments.is_present(option)),
             "word count" => self.word_count(clap::value_t!(arguments.value_of(*option), u8).ok()),
             _ => (),
         });
@@ -399,6 +984,43 @@ impl EthereumOptions {
         }
     }
 
+    /// Imports a wallet for the specified encrypted backup, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn backup(&mut self, argument: Option<&str>) {
+        if let Some(backup) = argument {
+            self.backup = Some(backup.to_string());
+        }
+    }
+
+    /// Sets `backup_password` to the specified backup password, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn backup_password(&mut self, argument: Option<&str>) {
+        if let Some(password) = argument {
+            self.backup_password = Some(password.to_string());
+        }
+    }
+
+    /// Sets `chain` to the specified chain, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn chain(&mut self, argument: Option<&str>) {
+        if let Some(chain) = argument {
+            self.chain = Some(chain.to_string());
+        }
+    }
+
+    /// Sets `coin` to the specified coin flips, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn coin(&mut self, argument: Option<&str>) {
+        if let Some(flips) = argument {
+            self.coin = Some(flips.to_string());
+        }
+    }
+
+    /// Sets `compact` to the specified boolean value, overriding its previous state.
+    fn compact(&mut self, argument: bool) {
+        self.compact = argument;
+    }
+
     /// Sets `count` to the specified count, overriding its previous state.
     fn count(&mut self, argument: Option<usize>) {
         if let Some(count) = argument {
@@ -432,6 +1054,14 @@ impl EthereumOptions {
         };
     }
 
+    /// Sets `dice` to the specified dice rolls, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn dice(&mut self, argument: Option<&str>) {
+        if let Some(rolls) = argument {
+            self.dice = Some(rolls.to_string());
+        }
+    }
+
     /// Sets `extended_private_key` to the specified extended private key, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn extended_private(&mut self, argument: Option<&str>) {
@@ -448,6 +1078,14 @@ impl EthereumOptions {
         }
     }
 
+    /// Sets `group_threshold` to the specified SLIP-39 group threshold, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn group_threshold(&mut self, argument: Option<u8>) {
+        if let Some(group_threshold) = argument {
+            self.group_threshold = group_threshold;
+        }
+    }
+
     /// Sets `index` to the specified index, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn index(&mut self, argument: Option<u32>) {
@@ -475,11 +1113,13 @@ impl EthereumOptions {
         match argument {
             Some("chinese_simplified") => self.language = "chinese_simplified".into(),
             Some("chinese_traditional") => self.language = "chinese_traditional".into(),
+            Some("czech") => self.language = "czech".into(),
             Some("english") => self.language = "english".into(),
             Some("french") => self.language = "french".into(),
             Some("italian") => self.language = "italian".into(),
             Some("japanese") => self.language = "japanese".into(),
             Some("korean") => self.language = "korean".into(),
+            Some("portuguese") => self.language = "portuguese".into(),
             Some("spanish") => self.language = "spanish".into(),
             _ => (),
         };
@@ -509,6 +1149,15 @@ impl EthereumOptions {
         }
     }
 
+    /// Sets `derivation` to `\"preset\"` and `preset` to the specified wallet preset, overriding their previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn preset(&mut self, argument: Option<&str>) {
+        if let Some(preset) = argument {
+            self.derivation = "preset".into();
+            self.preset = Some(preset.to_string());
+        }
+    }
+
     /// Imports a wallet for the specified private key, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn private(&mut self, argument: Option<&str>) {
@@ -525,6 +1174,30 @@ impl EthereumOptions {
         }
     }
 
+    /// Sets `recover` to the specified address to recover a mnemonic for, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn recover(&mut self, argument: Option<&str>) {
+        if let Some(address) = argument {
+            self.recover = Some(address.to_string());
+        }
+    }
+
+    /// Imports a wallet for the specified SeedQR, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn seed_qr(&mut self, argument: Option<&str>) {
+        if let Some(seed_qr) = argument {
+            self.seed_qr = Some(seed_qr.to_string());
+        }
+    }
+
+    /// Sets `shares` to the specified SLIP-39 shares, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn share(&mut self, argument: Option<Values>) {
+        if let Some(shares) = argument {
+            self.shares = shares.map(String::from).collect();
+        }
+    }
+
     /// Sets `transaction_hex` and `transaction_private_key` to the specified transaction values, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn sign_raw_transaction(&mut self, argument: Option<Values>) {
@@ -535,6 +1208,19 @@ impl EthereumOptions {
         }
     }
 
+    /// Sets `slip39` to the specified SLIP-39 groups, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn slip39(&mut self, argument: Option<&str>) {
+        if let Some(groups) = argument {
+            self.slip39 = Some(groups.to_string());
+        }
+    }
+
+    /// Sets `watch_only` to the specified boolean value, overriding its previous state.
+    fn watch_only(&mut self, argument: bool) {
+        self.watch_only = argument;
+    }
+
     /// Sets `word_count` to the specified word count, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn word_count(&mut self, argument: Option<u8>) {
@@ -543,6 +1229,15 @@ impl EthereumOptions {
         }
     }
 
+    /// Returns the source and rolls of the specified coin flips or dice rolls.
+    fn to_rolls(&self) -> Option<(ManualEntropy, &str)> {
+        match (&self.coin, &self.dice) {
+            (Some(flips), _) => Some((ManualEntropy::Coin, flips)),
+            (None, Some(rolls)) => Some((ManualEntropy::Dice, rolls)),
+            (None, None) => None,
+        }
+    }
+
     /// Returns the derivation path with the specified account, chain, derivation, index, and path.
     /// If `default` is enabled, then return the default path if no derivation was provided.
     fn to_derivation_path(&self, default: bool) -> Option<String> {
@@ -553,6 +1248,11 @@ impl EthereumOptions {
             "ledger-live" => Some(format!("m/44'/60'/{}'/0/0", self.index)),
             "trezor" => Some(format!("m/44'/60'/0'/{}", self.index)),
             "custom" => self.path.clone(),
+            "preset" => {
+                let preset = WalletPreset::from_str(self.preset.as_ref()?).ok()?;
+                let scheme = preset.scheme(CoinType::Ethereum)?;
+                Some(scheme.to_path(CoinType::Ethereum, 0, 0, self.index))
+            }
             _ => match default {
                 true => Some(format!("m/44'/60'/0'/0/{}", self.index)),
                 false => None,
@@ -587,8 +1287,11 @@ impl CLI for EthereumCLI {
     const NAME: NameType = "ethereum";
     const OPTIONS: &'static [OptionType] = &[option::COUNT];
     const SUBCOMMANDS: &'static [SubCommandType] = &[
+        subcommand::EXPORT_BACKUP_ETHEREUM,
+        subcommand::EXPORT_SEED_QR_ETHEREUM,
         subcommand::HD_ETHEREUM,
         subcommand::IMPORT_ETHEREUM,
+        subcommand::IMPORT_BACKUP_ETHEREUM,
         subcommand::IMPORT_HD_ETHEREUM,
         subcommand::TRANSACTION_ETHEREUM,
     ];
@@ -600,19 +1303,85 @@ impl CLI for EthereumCLI {
         options.parse(arguments, &["count", "json"]);
 
         match arguments.subcommand() {
+            ("export-backup", Some(arguments)) => {
+                options.subcommand = Some("export-backup".into());
+                options.parse(arguments, &["json"]);
+                options.parse(arguments, &["backup password", "language", "mnemonic"]);
+
+                // Detects the mnemonic language, unless one is specified
+                if let (Some(mnemonic), None) = (&options.mnemonic, arguments.value_of("language")) {
+                    options.language = detect_language(mnemonic)
+                        .context("ethereum", "detect mnemonic language")?
+                        .into();
+                }
+            }
+            ("export-seedqr", Some(arguments)) => {
+                options.subcommand = Some("export-seedqr".into());
+                options.parse(arguments, &["json"]);
+                options.parse(arguments, &["compact", "language", "mnemonic"]);
+
+                // Detects the mnemonic language, unless one is specified
+                if let (Some(mnemonic), None) = (&options.mnemonic, arguments.value_of("language")) {
+                    options.language = detect_language(mnemonic)
+                        .context("ethereum", "detect mnemonic language")?
+                        .into();
+                }
+            }
             ("hd", Some(arguments)) => {
                 options.subcommand = Some("hd".into());
                 options.parse(arguments, &["count", "json"]);
                 options.parse(
                     arguments,
-                    &["derivation", "index", "indices", "language", "password", "word count"],
+                    &[
+                        "coin",
+                        "derivation",
+                        "dice",
+                        "group threshold",
+                        "index",
+                        "indices",
+                        "language",
+                        "password",
+                        "preset",
+                        "slip39",
+                        "word count",
+                    ],
                 );
+
+                // Prompts for the password from the terminal, if requested
+                if arguments.is_present("prompt password") {
+                    options.password = Some(prompt_password().context("ethereum", "prompt password")?);
+                }
             }
             ("import", Some(arguments)) => {
                 options.subcommand = Some("import".into());
                 options.parse(arguments, &["json"]);
                 options.parse(arguments, &["address", "private", "public"]);
             }
+            ("import-backup", Some(arguments)) => {
+                options.subcommand = Some("import-backup".into());
+                options.parse(arguments, &["json"]);
+                options.parse(
+                    arguments,
+                    &[
+                        "backup",
+                        "backup password",
+                        "derivation",
+                        "index",
+                        "indices",
+                        "password",
+                        "preset",
+                    ],
+                );
+
+                // Decrypts the mnemonic of the backup and detects its language
+                if let (Some(backup), Some(password)) = (&options.backup, &options.backup_password) {
+                    let mnemonic = decrypt_backup(backup, password).context("ethereum", "decrypt backup")?;
+                    options.language = detect_language(&mnemonic)
+                        .context("ethereum", "detect mnemonic language")?
+                        .into();
+                    options.mnemonic = Some(mnemonic);
+                }
+            }
             ("import-hd", Some(arguments)) => {
                 options.subcommand = Some("import-hd".into());
                 options.parse(arguments, &["json"]);
@@ -626,14 +1395,44 @@ impl CLI for EthereumCLI {
                         "extended public",
                         "index",
                         "indices",
+                        "language",
                         "mnemonic",
                         "password",
+                        "preset",
+                        "recover",
+                        "seedqr",
+                        "share",
+                        "watch only",
                     ],
                 );
+
+                // Prompts for the password from the terminal, if requested
+                if arguments.is_present("prompt password") {
+                    options.password = Some(prompt_password().context("ethereum", "prompt password")?);
+                }
+
+                // Detects the mnemonic language, unless one is specified
+                if let (Some(mnemonic), None) = (&options.mnemonic, arguments.value_of("language")) {
+                    // Ignores the unknown words, marked by "?", of a mnemonic to recover
+                    let mnemonic = match options.recover {
+                        Some(_) => mnemonic
+                            .split(" ")
+                            .filter(|word| *word != "?")
+                            .collect::<Vec<&str>>()
+                            .join(" "),
+                        None => mnemonic.clone(),
+                    };
+                    options.language = detect_language(&mnemonic)
+                        .context("ethereum", "detect mnemonic language")?
+                        .into();
+                }
             }
             ("transaction", Some(arguments)) => {
                 options.subcommand = Some("transaction".into());
-                options.parse(arguments, &["createrawtransaction", "network", "signrawtransaction"]);
+                options.parse(
+                    arguments,
+                    &["chain", "createrawtransaction", "network", "signrawtransaction"],
+                );
             }
             _ => {}
         };
@@ -643,40 +1442,93 @@ impl CLI for EthereumCLI {
 
     /// Generate the Ethereum wallet and print the relevant fields
     #[cfg_attr(tarpaulin, skip)]
-    fn print(options: Self::Options) -> Result<(), CLIError> {
-        fn output<N: EthereumNetwork, W: EthereumWordlist>(options: EthereumOptions) -> Result<(), CLIError> {
+    fn print_with_entropy(options: Self::Options, source: &mut dyn EntropySource) -> Result<(), CLIError> {
+        fn output<N: EthereumNetwork, W: EthereumWordlist>(
+            options: EthereumOptions,
+            source: &mut dyn EntropySource,
+        ) -> Result<(), CLIError> {
             let wallets = match options.subcommand.as_ref().map(String::as_str) {
-                Some("hd") => {
+                Some("export-backup") => match (&options.mnemonic, &options.backup_password) {
+                    (Some(mnemonic), Some(password)) => {
+                        vec![EthereumWallet::to_backup::<N, W, _>(source, mnemonic, password)?]
+                    }
+                    _ => vec![],
+                },
+                Some("export-seedqr") => match &options.mnemonic {
+                    Some(mnemonic) => vec![EthereumWallet::to_seed_qr::<N, W>(mnemonic, options.compact)?],
+                    None => vec![],
+                },
+                Some("hd") if options.slip39.is_some() => {
                     let password = options.password.as_ref().map(String::as_str);
-                    (0..options.count)
-                        .flat_map(|_| {
-                            // Sample a new HD wallet
-                            let wallet = EthereumWallet::new_hd::<N, W, _>(
-                                &mut StdRng::from_entropy(),
-                                options.word_count,
-                                password,
-                                &options.to_derivation_path(true).unwrap(),
-                            )
-                            .unwrap();
-                            let mnemonic = &wallet.mnemonic.unwrap();
-
-                            // Generate the HD wallet, from `index` to a number of specified `indices`
-                            options
-                                .to_derivation_paths(true)
-                                .iter()
-                                .flat_map(|path| {
-                                    match EthereumWallet::from_mnemonic::<N, W>(
-                                        mnemonic,
-                                        password,
-                                        path.as_ref().unwrap(),
-                                    ) {
-                                        Ok(wallet) => vec![wallet],
-                                        _ => vec![],
-                                    }
-                                })
-                                .collect::<Vec<EthereumWallet>>()
-                        })
-                        .collect()
+                    let groups = options
+                        .slip39
+                        .as_ref()
+                        .unwrap()
+                        .split(',')
+                        .map(|group| Slip39Group::from_str(group.trim()))
+                        .collect::<Result<Vec<Slip39Group>, _>>()?;
+                    let mut wallets = vec![];
+                    for _ in 0..options.count {
+                        // Sample a new HD wallet split into shares
+                        let wallet = EthereumWallet::new_hd_shares::<N, _>(
+                            source,
+                            options.group_threshold,
+                            &groups,
+                            password,
+                            &options.to_derivation_path(true).unwrap(),
+                        )?;
+                        let shares = wallet
+                            .shares
+                            .unwrap()
+                            .into_iter()
+                            .map(|share| share.share)
+                            .collect::<Vec<String>>();
+
+                        // Generate the HD wallet, from `index` to a number of specified `indices`
+                        for path in options.to_derivation_paths(true) {
+                            wallets.push(EthereumWallet::from_shares::<N>(&shares, password, &path.unwrap())?);
+                        }
+                    }
+                    wallets
+                }
+                Some("hd") if options.to_rolls().is_some() => {
+                    let password = options.password.as_ref().map(String::as_str);
+                    let (source, rolls) = options.to_rolls().unwrap();
+
+                    // Generate the HD wallet from the entropy of the rolls
+                    let wallet = EthereumWallet::new_hd_from_rolls::<N, W>(
+                        source,
+                        rolls,
+                        options.word_count,
+                        password,
+                        &options.to_derivation_path(true).unwrap(),
+                    )?;
+                    let mnemonic = &wallet.mnemonic.unwrap();
+
+                    // Generate the HD wallet, from `index` to a number of specified `indices`
+                    let mut wallets = vec![];
+                    for path in options.to_derivation_paths(true) {
+                        wallets.push(EthereumWallet::from_mnemonic::<N, W>(
+                            mnemonic,
+                            password,
+                            &path.unwrap(),
+                        )?);
+                    }
+                    wallets
+                }
+                Some("hd") => {
+                    let paths = options
+                        .to_derivation_paths(true)
+                        .into_iter()
+                        .flatten()
+                        .collect::<Vec<String>>();
+                    EthereumWallet::new_hd::<N, W, _>(
+                        source,
+                        options.count,
+                        options.word_count,
+                        options.password.as_ref().map(String::as_str),
+                        &paths,
+                    )?
                 }
                 Some("import") => {
                     if let Some(private_key) = options.private {
@@ -689,8 +1541,16 @@ impl CLI for EthereumCLI {
                         vec![]
                     }
                 }
-                Some("import-hd") => {
-                    if let Some(mnemonic) = options.mnemonic.clone() {
+                Some("import-hd") | Some("import-backup") => {
+                    if let (Some(mnemonic), Some(address)) = (options.mnemonic.clone(), options.recover.clone()) {
+                        let password = options.password.as_ref().map(String::as_str);
+                        let paths = options
+                            .to_derivation_paths(true)
+                            .into_iter()
+                            .flatten()
+                            .collect::<Vec<String>>();
+                        EthereumWallet::recover::<N, W>(&mnemonic, password, &paths, &address)?
+                    } else if let Some(mnemonic) = options.mnemonic.clone() {
                         fn process_mnemonic<EN: EthereumNetwork, EW: EthereumWordlist>(
                             mnemonic: &String,
                             options: &EthereumOptions,
@@ -708,14 +1568,35 @@ impl CLI for EthereumCLI {
                             Ok(wallets)
                         }
 
-                        process_mnemonic::<N, ChineseSimplified>(&mnemonic, &options)
-                            .or(process_mnemonic::<N, ChineseTraditional>(&mnemonic, &options))
-                            .or(process_mnemonic::<N, English>(&mnemonic, &options))
-                            .or(process_mnemonic::<N, French>(&mnemonic, &options))
-                            .or(process_mnemonic::<N, Italian>(&mnemonic, &options))
-                            .or(process_mnemonic::<N, Japanese>(&mnemonic, &options))
-                            .or(process_mnemonic::<N, Korean>(&mnemonic, &options))
-                            .or(process_mnemonic::<N, Spanish>(&mnemonic, &options))?
+                        process_mnemonic::<N, W>(&mnemonic, &options)?
+                    } else if let Some(seed_qr) = options.seed_qr.clone() {
+                        let mnemonic =
+                            decode_seed_qr::<EthereumMnemonic<N, W>>(&seed_qr).context("ethereum", "decode SeedQR")?;
+                        let mnemonic = mnemonic.to_phrase()?;
+
+                        // Generate the SeedQR wallets, from `index` to a number of specified `indices`
+                        let mut wallets = vec![];
+                        let password = options.password.as_ref().map(String::as_str);
+                        for path in options.to_derivation_paths(true) {
+                            wallets.push(EthereumWallet::from_mnemonic::<N, W>(
+                                &mnemonic,
+                                password,
+                                path.as_ref().unwrap(),
+                            )?);
+                        }
+                        wallets
+                    } else if !options.shares.is_empty() {
+                        // Generate the share wallets, from `index` to a number of specified `indices`
+                        let mut wallets = vec![];
+                        let password = options.password.as_ref().map(String::as_str);
+                        for path in options.to_derivation_paths(true) {
+                            wallets.push(EthereumWallet::from_shares::<N>(
+                                &options.shares,
+                                password,
+                                &path.unwrap(),
+                            )?);
+                        }
+                        wallets
                     } else if let Some(extended_private_key) = options.extended_private_key.clone() {
                         // Generate the extended private keys, from `index` to a number of specified `indices`
                         options
@@ -728,6 +1609,16 @@ impl CLI for EthereumCLI {
                                 }
                             })
                             .collect::<Vec<EthereumWallet>>()
+                    } else if let (Some(extended_public_key), true) =
+                        (options.extended_public_key.clone(), options.watch_only)
+                    {
+                        // Generate the child wallets, from `index` to a number of specified `indices`
+                        EthereumWallet::from_extended_public_key_children::<N>(
+                            &extended_public_key,
+                            &options.path,
+                            options.index,
+                            options.indices,
+                        )?
                     } else if let Some(extended_public_key) = options.extended_public_key.clone() {
                         // Generate the extended public keys, from `index` to a number of specified `indices`
                         options
@@ -746,10 +1637,29 @@ impl CLI for EthereumCLI {
                 }
                 Some("transaction") => {
                     if let Some(transaction_parameters) = options.transaction_parameters.clone() {
-                        let parameters: EthereumInput = from_str(&transaction_parameters)?;
+                        let mut parameters: EthereumInput = from_str(&transaction_parameters)?;
+
+                        // The chain selects the chain id of the transaction, unless it is specified otherwise
+                        if let Some(chain) = &options.chain {
+                            let chain_id = EthereumChain::from_str(chain)
+                                .context("ethereum", "parse chain")?
+                                .chain_id();
+                            match parameters.chain_id {
+                                Some(input_chain_id) if input_chain_id != chain_id => {
+                                    return Err(TransactionError::InvalidChainId(input_chain_id).into())
+                                }
+                                _ => parameters.chain_id = Some(chain_id),
+                            }
+                        }
 
-                        // Note: Raw Ethereum transactions are network agnostic
-                        vec![EthereumWallet::to_raw_transaction::<EthereumMainnet>(parameters)?]
+                        // Note: Raw legacy transactions are network agnostic, unlike EIP-1559 transactions
+                        match parameters.network.as_ref().map(String::as_str) {
+                            Some(Goerli::NAME) => vec![EthereumWallet::to_raw_transaction::<Goerli>(parameters)?],
+                            Some(Kovan::NAME) => vec![EthereumWallet::to_raw_transaction::<Kovan>(parameters)?],
+                            Some(Rinkeby::NAME) => vec![EthereumWallet::to_raw_transaction::<Rinkeby>(parameters)?],
+                            Some(Ropsten::NAME) => vec![EthereumWallet::to_raw_transaction::<Ropsten>(parameters)?],
+                            _ => vec![EthereumWallet::to_raw_transaction::<EthereumMainnet>(parameters)?],
+                        }
                     } else if let (Some(transaction_hex), Some(transaction_private_key)) =
                         (options.transaction_hex.clone(), options.transaction_private_key.clone())
                     {
@@ -785,7 +1695,7 @@ impl CLI for EthereumCLI {
                     }
                 }
                 _ => (0..options.count)
-                    .flat_map(|_| match EthereumWallet::new::<_>(&mut StdRng::from_entropy()) {
+                    .flat_map(|_| match EthereumWallet::new::<_>(source) {
                         Ok(wallet) => vec![wallet],
                         _ => vec![],
                     })
@@ -801,15 +1711,17 @@ impl CLI for EthereumCLI {
         }
 
         match options.language.as_str() {
-            "chinese_simplified" => output::<EthereumMainnet, ChineseSimplified>(options),
-            "chinese_traditional" => output::<EthereumMainnet, ChineseTraditional>(options),
-            "english" => output::<EthereumMainnet, English>(options),
-            "french" => output::<EthereumMainnet, French>(options),
-            "italian" => output::<EthereumMainnet, Italian>(options),
-            "japanese" => output::<EthereumMainnet, Japanese>(options),
-            "korean" => output::<EthereumMainnet, Korean>(options),
-            "spanish" => output::<EthereumMainnet, Spanish>(options),
-            _ => output::<EthereumMainnet, English>(options),
+            "chinese_simplified" => output::<EthereumMainnet, ChineseSimplified>(options, source),
+            "chinese_traditional" => output::<EthereumMainnet, ChineseTraditional>(options, source),
+            "czech" => output::<EthereumMainnet, Czech>(options, source),
+            "english" => output::<EthereumMainnet, English>(options, source),
+            "french" => output::<EthereumMainnet, French>(options, source),
+            "italian" => output::<EthereumMainnet, Italian>(options, source),
+            "japanese" => output::<EthereumMainnet, Japanese>(options, source),
+            "korean" => output::<EthereumMainnet, Korean>(options, source),
+            "portuguese" => output::<EthereumMainnet, Portuguese>(options, source),
+            "spanish" => output::<EthereumMainnet, Spanish>(options, source),
+            _ => output::<EthereumMainnet, English>(options, source),
         }
     }
 }
@@ -1,6 +1,18 @@
+#[cfg(feature = "online")]
+use crate::bitcoin::ElectrumClientError;
+#[cfg(feature = "ledger")]
+use crate::bitcoin::LedgerError;
+#[cfg(feature = "trezor")]
+use crate::bitcoin::TrezorError;
+use crate::bitcoin::{
+    AezeedError, Bip38Error, DescriptorError, ElectrumError, MessageError, PsbtError, TaprootError, VanityError,
+};
+use crate::ethereum::AbiError;
 use crate::model::{
-    AddressError, AmountError, DerivationPathError, ExtendedPrivateKeyError, ExtendedPublicKeyError, MnemonicError,
-    PrivateKeyError, PublicKeyError, TransactionError,
+    compact_seed_qr_decode, compact_seed_qr_encode, seed_qr_decode, seed_qr_encode, AddressError, AmountError, Backup,
+    BackupContents, BackupError, BackupParameters, CrateError, DerivationPathError, EntropyRng, EntropySource,
+    ExtendedPrivateKeyError, ExtendedPublicKeyError, MnemonicEntropy, MnemonicError, NetworkError, PrivateKeyError,
+    PublicKeyError, Slip39Error, Slip39Share, TransactionError, WordlistError,
 };
 
 pub mod bitcoin;
@@ -14,8 +26,14 @@ pub use self::parameters::*;
 use types::*;
 
 use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
+use core::{fmt, str::FromStr};
+use failure::Fail;
+use qrcode::{bits::Bits, render::unicode::Dense1x2, types::QrError, EcLevel, QrCode, Version};
+use rand::rngs::OsRng;
+use serde::Serialize;
+use std::io::IsTerminal;
 
-use crate::model::no_std::{format, String, Vec};
+use crate::model::no_std::{vec, String, ToString, Vec};
 
 pub trait CLI {
     type Options;
@@ -78,46 +96,317 @@ pub trait CLI {
     fn parse(arguments: &ArgMatches) -> Result<Self::Options, CLIError>;
 
     #[cfg_attr(tarpaulin, skip)]
-    fn print(options: Self::Options) -> Result<(), CLIError>;
+    fn print(options: Self::Options) -> Result<(), CLIError> {
+        Self::print_with_entropy(options, &mut OsRng)
+    }
+
+    /// Prints the output of the given options, generating new wallets from the given entropy source.
+    #[cfg_attr(tarpaulin, skip)]
+    fn print_with_entropy(options: Self::Options, source: &mut dyn EntropySource) -> Result<(), CLIError>;
 }
 
 #[derive(Debug, Fail)]
 pub enum CLIError {
     #[fail(display = "{}", _0)]
-    AddressError(AddressError),
+    AbiError(#[cause] AbiError),
+
+    #[fail(display = "{}", _0)]
+    AddressError(#[cause] AddressError),
+
+    #[fail(display = "{}", _0)]
+    AezeedError(#[cause] AezeedError),
+
+    #[fail(display = "{}", _0)]
+    AmountError(#[cause] AmountError),
+
+    #[fail(display = "{}", _0)]
+    BackupError(#[cause] BackupError),
+
+    #[fail(display = "{}", _0)]
+    Bip38Error(#[cause] Bip38Error),
 
     #[fail(display = "{}", _0)]
-    AmountError(AmountError),
+    Crate(#[cause] CrateError),
 
     #[fail(display = "{}: {}", _0, _1)]
-    Crate(&'static str, String),
+    Context(ErrorContext, #[cause] Box<dyn Fail>),
+
+    #[fail(display = "{}", _0)]
+    DerivationPathError(#[cause] DerivationPathError),
+
+    #[fail(display = "{}", _0)]
+    DescriptorError(#[cause] DescriptorError),
 
+    #[cfg(feature = "online")]
     #[fail(display = "{}", _0)]
-    DerivationPathError(DerivationPathError),
+    ElectrumClientError(#[cause] ElectrumClientError),
 
     #[fail(display = "{}", _0)]
-    ExtendedPrivateKeyError(ExtendedPrivateKeyError),
+    ElectrumError(#[cause] ElectrumError),
 
     #[fail(display = "{}", _0)]
-    ExtendedPublicKeyError(ExtendedPublicKeyError),
+    ExtendedPrivateKeyError(#[cause] ExtendedPrivateKeyError),
+
+    #[fail(display = "{}", _0)]
+    ExtendedPublicKeyError(#[cause] ExtendedPublicKeyError),
 
     #[fail(display = "invalid derived mnemonic for a given private spend key")]
     InvalidMnemonicForPrivateSpendKey,
 
+    #[cfg(feature = "ledger")]
+    #[fail(display = "{}", _0)]
+    LedgerError(#[cause] LedgerError),
+
+    #[fail(display = "the passwords do not match")]
+    PasswordMismatch,
+
+    #[fail(display = "{}", _0)]
+    PrivateKeyError(#[cause] PrivateKeyError),
+
+    #[fail(display = "{}", _0)]
+    PsbtError(#[cause] PsbtError),
+
     #[fail(display = "{}", _0)]
-    PrivateKeyError(PrivateKeyError),
+    PublicKeyError(#[cause] PublicKeyError),
 
     #[fail(display = "{}", _0)]
-    PublicKeyError(PublicKeyError),
+    QrError(#[cause] QrError),
 
     #[fail(display = "{}", _0)]
-    MnemonicError(MnemonicError),
+    MessageError(#[cause] MessageError),
 
     #[fail(display = "{}", _0)]
-    TransactionError(TransactionError),
+    MnemonicError(#[cause] MnemonicError),
+
+    #[fail(display = "{}", _0)]
+    NetworkError(#[cause] NetworkError),
+
+    #[fail(display = "{}", _0)]
+    Slip39Error(#[cause] Slip39Error),
+
+    #[fail(display = "{}", _0)]
+    TaprootError(#[cause] TaprootError),
+
+    #[fail(display = "{}", _0)]
+    TransactionError(#[cause] TransactionError),
+
+    #[cfg(feature = "trezor")]
+    #[fail(display = "{}", _0)]
+    TrezorError(#[cause] TrezorError),
+
+    #[fail(display = "no mnemonic recovered for the address {:?}", _0)]
+    UnrecoveredMnemonic(String),
+
+    #[fail(display = "unsupported backup contents: expected a mnemonic")]
+    UnsupportedBackupContents,
 
     #[fail(display = "unsupported mnemonic language")]
     UnsupportedLanguage,
+
+    #[fail(display = "{}", _0)]
+    VanityError(#[cause] VanityError),
+
+    #[fail(display = "{}", _0)]
+    WordlistError(#[cause] WordlistError),
+}
+
+impl CLIError {
+    /// Returns the "did you mean" hints for the mistyped mnemonic words that caused the error.
+    pub fn hints(&self) -> Vec<String> {
+        (self as &dyn Fail)
+            .iter_chain()
+            .filter_map(|cause| cause.downcast_ref::<WordlistError>())
+            .flat_map(|error| match error {
+                WordlistError::InvalidWords(invalid_words) => invalid_words.iter().map(ToString::to_string).collect(),
+                _ => vec![],
+            })
+            .collect()
+    }
+}
+
+/// Represents the currency, operation, and user input of a failed command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorContext {
+    /// The currency of the command
+    pub currency: &'static str,
+    /// The operation that failed, e.g. "parse address"
+    pub operation: &'static str,
+    /// The offending input, omitted for secret material such as private keys and mnemonics
+    pub input: Option<String>,
+}
+
+impl fmt::Display for ErrorContext {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: failed to {}", self.currency, self.operation)?;
+        match &self.input {
+            Some(input) => write!(f, " {:?}", input),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Represents a SLIP-39 share to output
+#[derive(Serialize, Debug, Clone)]
+pub struct Share {
+    /// The group number of the share, starting from 1
+    pub group: u8,
+    /// The member number of the share in its group, starting from 1
+    pub member: u8,
+    /// The share phrase
+    pub share: String,
+}
+
+impl Share {
+    /// Returns the shares to output, ordered by group and member number.
+    pub fn from_shares(shares: &[Slip39Share]) -> Vec<Self> {
+        let mut shares = shares
+            .iter()
+            .map(|share| Self {
+                group: share.group_index + 1,
+                member: share.member_index + 1,
+                share: share.to_string(),
+            })
+            .collect::<Vec<Self>>();
+        shares.sort_by_key(|share| (share.group, share.member));
+        shares
+    }
+}
+
+/// Returns the encrypted backup of the given mnemonic, encrypted with the given password
+/// and salted from the given entropy source.
+pub fn encrypt_backup<E: EntropySource + ?Sized>(
+    source: &mut E,
+    mnemonic: &str,
+    password: &str,
+) -> Result<String, CLIError> {
+    let contents = BackupContents::Mnemonic(mnemonic.to_string());
+    let backup = Backup::encrypt(
+        &mut EntropyRng::new(source),
+        &contents,
+        password,
+        BackupParameters::default(),
+    )?;
+    Ok(backup.to_string())
+}
+
+/// Returns the mnemonic of the given encrypted backup, decrypted with the given password.
+pub fn decrypt_backup(backup: &str, password: &str) -> Result<String, CLIError> {
+    let backup = Backup::from_str(backup)?;
+    match &backup.decrypt(password)? {
+        BackupContents::Mnemonic(mnemonic) => Ok(mnemonic.clone()),
+        BackupContents::Seed(_) => Err(CLIError::UnsupportedBackupContents),
+    }
+}
+
+/// Returns a password prompted from the terminal without echo, entered twice to confirm it.
+pub fn prompt_password() -> Result<String, CLIError> {
+    let password = rpassword::prompt_password("Enter password: ")?;
+    let confirmation = rpassword::prompt_password("Confirm password: ")?;
+    match password == confirmation {
+        true => Ok(password),
+        false => Err(CLIError::PasswordMismatch),
+    }
+}
+
+/// Prints the progress of a seed derivation to the terminal, so that long derivations on slow
+/// hardware are not mistaken for a hang. Nothing is printed unless stderr is a terminal.
+pub fn print_seed_progress(completed: u32, total: u32) {
+    if completed % 128 != 0 && completed != total || !std::io::stderr().is_terminal() {
+        return;
+    }
+    match completed == total {
+        true => eprint!("\r\x1b[K"),
+        false => eprint!("\rDeriving seed... {}%", completed * 100 / total),
+    }
+}
+
+/// Returns the SeedQR of the given mnemonic, as Compact SeedQR hex or Standard SeedQR digits,
+/// along with the SeedQR rendered as a QR code for the terminal.
+pub fn encode_seed_qr<M: MnemonicEntropy>(mnemonic: &M, compact: bool) -> Result<(String, String), CLIError> {
+    match compact {
+        true => {
+            let bytes = compact_seed_qr_encode(mnemonic)?;
+            Ok((hex::encode(&bytes), render_seed_qr(&bytes, compact)?))
+        }
+        false => {
+            let digits = seed_qr_encode(mnemonic)?;
+            let qr_code = render_seed_qr(digits.as_bytes(), compact)?;
+            Ok((digits, qr_code))
+        }
+    }
+}
+
+/// Returns the mnemonic of the given SeedQR, as Compact SeedQR hex or Standard SeedQR digits.
+pub fn decode_seed_qr<M: MnemonicEntropy>(seed_qr: &str) -> Result<M, CLIError> {
+    let seed_qr = seed_qr.trim();
+    match seed_qr.len() {
+        32 | 64 => Ok(compact_seed_qr_decode(&hex::decode(seed_qr)?)?),
+        _ => Ok(seed_qr_decode(seed_qr)?),
+    }
+}
+
+/// Returns the given SeedQR data rendered as a QR code for the terminal, with the version and
+/// encoding mode of the SeedQR specification, in byte mode for a Compact SeedQR and numeric mode otherwise.
+fn render_seed_qr(data: &[u8], compact: bool) -> Result<String, CLIError> {
+    let version = match (compact, data.len()) {
+        (true, 16) => Version::Normal(1),
+        (true, _) => Version::Normal(2),
+        (false, 48) => Version::Normal(2),
+        (false, _) => Version::Normal(3),
+    };
+
+    let mut bits = Bits::new(version);
+    match compact {
+        true => bits.push_byte_data(data)?,
+        false => bits.push_numeric_data(data)?,
+    };
+    bits.push_terminator(EcLevel::L)?;
+
+    let code = QrCode::with_bits(bits, EcLevel::L)?;
+    Ok(code
+        .render::<Dense1x2>()
+        .dark_color(Dense1x2::Light)
+        .light_color(Dense1x2::Dark)
+        .build())
+}
+
+/// Attaches an error context to the error of a result.
+pub trait ResultExt<T> {
+    /// Returns the result with the currency and operation attached to its error.
+    fn context(self, currency: &'static str, operation: &'static str) -> Result<T, CLIError>;
+
+    /// Returns the result with the currency, operation, and offending input attached to its error.
+    fn context_with_input(self, currency: &'static str, operation: &'static str, input: &str) -> Result<T, CLIError>;
+}
+
+impl<T, E: Into<CLIError>> ResultExt<T> for Result<T, E> {
+    fn context(self, currency: &'static str, operation: &'static str) -> Result<T, CLIError> {
+        self.map_err(|error| {
+            let context = ErrorContext {
+                currency,
+                operation,
+                input: None,
+            };
+            CLIError::Context(context, Box::new(error.into()))
+        })
+    }
+
+    fn context_with_input(self, currency: &'static str, operation: &'static str, input: &str) -> Result<T, CLIError> {
+        self.map_err(|error| {
+            let context = ErrorContext {
+                currency,
+                operation,
+                input: Some(input.to_string()),
+            };
+            CLIError::Context(context, Box::new(error.into()))
+        })
+    }
+}
+
+impl From<AbiError> for CLIError {
+    fn from(error: AbiError) -> Self {
+        CLIError::AbiError(error)
+    }
 }
 
 impl From<AddressError> for CLIError {
@@ -126,15 +415,33 @@ impl From<AddressError> for CLIError {
     }
 }
 
+impl From<AezeedError> for CLIError {
+    fn from(error: AezeedError) -> Self {
+        CLIError::AezeedError(error)
+    }
+}
+
 impl From<AmountError> for CLIError {
     fn from(error: AmountError) -> Self {
         CLIError::AmountError(error)
     }
 }
 
+impl From<BackupError> for CLIError {
+    fn from(error: BackupError) -> Self {
+        CLIError::BackupError(error)
+    }
+}
+
+impl From<Bip38Error> for CLIError {
+    fn from(error: Bip38Error) -> Self {
+        CLIError::Bip38Error(error)
+    }
+}
+
 impl From<core::num::ParseIntError> for CLIError {
     fn from(error: core::num::ParseIntError) -> Self {
-        CLIError::Crate("parse_int", format!("{:?}", error))
+        CLIError::Crate(CrateError::from(error))
     }
 }
 
@@ -144,6 +451,25 @@ impl From<DerivationPathError> for CLIError {
     }
 }
 
+impl From<DescriptorError> for CLIError {
+    fn from(error: DescriptorError) -> Self {
+        CLIError::DescriptorError(error)
+    }
+}
+
+#[cfg(feature = "online")]
+impl From<ElectrumClientError> for CLIError {
+    fn from(error: ElectrumClientError) -> Self {
+        CLIError::ElectrumClientError(error)
+    }
+}
+
+impl From<ElectrumError> for CLIError {
+    fn from(error: ElectrumError) -> Self {
+        CLIError::ElectrumError(error)
+    }
+}
+
 impl From<ExtendedPrivateKeyError> for CLIError {
     fn from(error: ExtendedPrivateKeyError) -> Self {
         CLIError::ExtendedPrivateKeyError(error)
@@ -156,9 +482,28 @@ impl From<ExtendedPublicKeyError> for CLIError {
     }
 }
 
+#[cfg(feature = "ledger")]
+impl From<LedgerError> for CLIError {
+    fn from(error: LedgerError) -> Self {
+        CLIError::LedgerError(error)
+    }
+}
+
 impl From<hex::FromHexError> for CLIError {
     fn from(error: hex::FromHexError) -> Self {
-        CLIError::Crate("hex", format!("{:?}", error))
+        CLIError::Crate(CrateError::from(error))
+    }
+}
+
+impl From<std::io::Error> for CLIError {
+    fn from(error: std::io::Error) -> Self {
+        CLIError::Crate(CrateError::from(error))
+    }
+}
+
+impl From<MessageError> for CLIError {
+    fn from(error: MessageError) -> Self {
+        CLIError::MessageError(error)
     }
 }
 
@@ -168,21 +513,51 @@ impl From<MnemonicError> for CLIError {
     }
 }
 
+impl From<NetworkError> for CLIError {
+    fn from(error: NetworkError) -> Self {
+        CLIError::NetworkError(error)
+    }
+}
+
 impl From<PrivateKeyError> for CLIError {
     fn from(error: PrivateKeyError) -> Self {
         CLIError::PrivateKeyError(error)
     }
 }
 
+impl From<PsbtError> for CLIError {
+    fn from(error: PsbtError) -> Self {
+        CLIError::PsbtError(error)
+    }
+}
+
 impl From<PublicKeyError> for CLIError {
     fn from(error: PublicKeyError) -> Self {
         CLIError::PublicKeyError(error)
     }
 }
 
+impl From<QrError> for CLIError {
+    fn from(error: QrError) -> Self {
+        CLIError::QrError(error)
+    }
+}
+
 impl From<serde_json::error::Error> for CLIError {
     fn from(error: serde_json::error::Error) -> Self {
-        CLIError::Crate("serde_json", format!("{:?}", error))
+        CLIError::Crate(CrateError::from(error))
+    }
+}
+
+impl From<Slip39Error> for CLIError {
+    fn from(error: Slip39Error) -> Self {
+        CLIError::Slip39Error(error)
+    }
+}
+
+impl From<TaprootError> for CLIError {
+    fn from(error: TaprootError) -> Self {
+        CLIError::TaprootError(error)
     }
 }
 
@@ -191,3 +566,22 @@ impl From<TransactionError> for CLIError {
         CLIError::TransactionError(error)
     }
 }
+
+#[cfg(feature = "trezor")]
+impl From<TrezorError> for CLIError {
+    fn from(error: TrezorError) -> Self {
+        CLIError::TrezorError(error)
+    }
+}
+
+impl From<VanityError> for CLIError {
+    fn from(error: VanityError) -> Self {
+        CLIError::VanityError(error)
+    }
+}
+
+impl From<WordlistError> for CLIError {
+    fn from(error: WordlistError) -> Self {
+        CLIError::WordlistError(error)
+    }
+}
@@ -1,5 +1,5 @@
-use crate::cli::{flag, option, subcommand, types::*, CLIError, CLI};
-use crate::model::{Mnemonic, PrivateKey, PublicKey};
+use crate::cli::{flag, option, subcommand, types::*, CLIError, ResultExt, CLI};
+use crate::model::{EntropySource, Mnemonic, PrivateKey, PublicKey};
 use crate::monero::{
     format::MoneroFormat, wordlist::*, Mainnet as MoneroMainnet, MoneroAddress, MoneroMnemonic, MoneroNetwork,
     MoneroPublicKey, MoneroWordlist, Stagenet as MoneroStagenet, Testnet as MoneroTestnet,
@@ -8,8 +8,6 @@ use crate::monero::{
 use clap::{ArgMatches, Values};
 use colored::*;
 use core::{fmt, fmt::Display, str::FromStr};
-use rand::{rngs::StdRng, Rng};
-use rand_core::SeedableRng;
 use serde::Serialize;
 
 use crate::model::no_std::{format, vec, String, ToOwned, ToString, Vec};
@@ -38,11 +36,11 @@ struct MoneroWallet {
 }
 
 impl MoneroWallet {
-    pub fn new<N: MoneroNetwork, W: MoneroWordlist, R: Rng>(
-        rng: &mut R,
+    pub fn new<N: MoneroNetwork, W: MoneroWordlist, E: EntropySource + ?Sized>(
+        source: &mut E,
         format: &MoneroFormat,
     ) -> Result<Self, CLIError> {
-        let mnemonic = MoneroMnemonic::<N, W>::new(rng)?;
+        let mnemonic = MoneroMnemonic::<N, W>::new_with_entropy(source)?;
         let private_key = mnemonic.to_private_key(None)?;
         let private_spend_key = private_key.to_private_spend_key();
         let private_view_key = private_key.to_private_view_key();
@@ -68,7 +66,7 @@ impl MoneroWallet {
         mnemonic: &str,
         format: &MoneroFormat,
     ) -> Result<Self, CLIError> {
-        let mnemonic = MoneroMnemonic::<N, W>::from_phrase(&mnemonic)?;
+        let mnemonic = MoneroMnemonic::<N, W>::from_phrase(&mnemonic).context("monero", "parse mnemonic")?;
         let private_key = mnemonic.to_private_key(None)?;
         let private_spend_key = private_key.to_private_spend_key();
         let private_view_key = private_key.to_private_view_key();
@@ -156,7 +154,7 @@ impl MoneroWallet {
     }
 
     pub fn from_address<N: MoneroNetwork>(address: &str) -> Result<Self, CLIError> {
-        let address = MoneroAddress::<N>::from_str(address)?;
+        let address = MoneroAddress::<N>::from_str(address).context_with_input("monero", "parse address", address)?;
         Ok(Self {
             address: Some(address.to_string()),
             network: Some(N::NAME.to_string()),
@@ -450,6 +448,14 @@ impl CLI for MoneroCLI {
                         "public view",
                     ],
                 );
+
+                // Detects the mnemonic language, comparing checksum words if several wordlists match,
+                // unless one is specified
+                if let (Some(mnemonic), None) = (&options.mnemonic, arguments.value_of("language")) {
+                    options.language = detect_language(mnemonic)
+                        .context("monero", "detect mnemonic language")?
+                        .into();
+                }
             }
             _ => {}
         };
@@ -459,27 +465,16 @@ impl CLI for MoneroCLI {
 
     /// Generate the Monero wallet and print the relevant fields
     #[cfg_attr(tarpaulin, skip)]
-    fn print(options: Self::Options) -> Result<(), CLIError> {
-        fn output<N: MoneroNetwork, W: MoneroWordlist>(options: MoneroOptions) -> Result<(), CLIError> {
+    fn print_with_entropy(options: Self::Options, source: &mut dyn EntropySource) -> Result<(), CLIError> {
+        fn output<N: MoneroNetwork, W: MoneroWordlist>(
+            options: MoneroOptions,
+            source: &mut dyn EntropySource,
+        ) -> Result<(), CLIError> {
             let wallets =
                 match options.subcommand.as_ref().map(String::as_str) {
                     Some("import") => {
                         if let Some(mnemonic) = options.mnemonic {
-                            vec![
-                                MoneroWallet::from_mnemonic::<N, ChineseSimplified>(&mnemonic, &options.format)
-                                    .or(MoneroWallet::from_mnemonic::<N, Dutch>(&mnemonic, &options.format))
-                                    .or(MoneroWallet::from_mnemonic::<N, English>(&mnemonic, &options.format))
-                                    .or(MoneroWallet::from_mnemonic::<N, EnglishOld>(&mnemonic, &options.format))
-                                    .or(MoneroWallet::from_mnemonic::<N, Esperanto>(&mnemonic, &options.format))
-                                    .or(MoneroWallet::from_mnemonic::<N, French>(&mnemonic, &options.format))
-                                    .or(MoneroWallet::from_mnemonic::<N, German>(&mnemonic, &options.format))
-                                    .or(MoneroWallet::from_mnemonic::<N, Italian>(&mnemonic, &options.format))
-                                    .or(MoneroWallet::from_mnemonic::<N, Japanese>(&mnemonic, &options.format))
-                                    .or(MoneroWallet::from_mnemonic::<N, Lojban>(&mnemonic, &options.format))
-                                    .or(MoneroWallet::from_mnemonic::<N, Portuguese>(&mnemonic, &options.format))
-                                    .or(MoneroWallet::from_mnemonic::<N, Russian>(&mnemonic, &options.format))
-                                    .or(MoneroWallet::from_mnemonic::<N, Spanish>(&mnemonic, &options.format))?,
-                            ]
+                            vec![MoneroWallet::from_mnemonic::<N, W>(&mnemonic, &options.format)?]
                         } else if let Some(private_spend_key) = options.private_spend_key {
                             vec![MoneroWallet::from_private_spend_key::<N, W>(
                                 &private_spend_key,
@@ -508,11 +503,9 @@ impl CLI for MoneroCLI {
                         }
                     }
                     _ => (0..options.count)
-                        .flat_map(|_| {
-                            match MoneroWallet::new::<N, W, _>(&mut StdRng::from_entropy(), &options.format) {
-                                Ok(wallet) => vec![wallet],
-                                _ => vec![],
-                            }
+                        .flat_map(|_| match MoneroWallet::new::<N, W, _>(source, &options.format) {
+                            Ok(wallet) => vec![wallet],
+                            _ => vec![],
                         })
                         .collect(),
                 };
@@ -527,75 +520,96 @@ impl CLI for MoneroCLI {
 
         match options.language.as_str() {
             "chinese_simplified" => match options.network.as_str() {
-                "testnet" => output::<MoneroTestnet, ChineseSimplified>(options),
-                "stagenet" => output::<MoneroStagenet, ChineseSimplified>(options),
-                _ => output::<MoneroMainnet, ChineseSimplified>(options),
+                "testnet" => output::<MoneroTestnet, ChineseSimplified>(options, source),
+                "stagenet" => output::<MoneroStagenet, ChineseSimplified>(options, source),
+                _ => output::<MoneroMainnet, ChineseSimplified>(options, source),
             },
             "dutch" => match options.network.as_str() {
-                "testnet" => output::<MoneroTestnet, Dutch>(options),
-                "stagenet" => output::<MoneroStagenet, Dutch>(options),
-                _ => output::<MoneroMainnet, Dutch>(options),
+                "testnet" => output::<MoneroTestnet, Dutch>(options, source),
+                "stagenet" => output::<MoneroStagenet, Dutch>(options, source),
+                _ => output::<MoneroMainnet, Dutch>(options, source),
             },
             "english" => match options.network.as_str() {
-                "testnet" => output::<MoneroTestnet, English>(options),
-                "stagenet" => output::<MoneroStagenet, English>(options),
-                _ => output::<MoneroMainnet, English>(options),
+                "testnet" => output::<MoneroTestnet, English>(options, source),
+                "stagenet" => output::<MoneroStagenet, English>(options, source),
+                _ => output::<MoneroMainnet, English>(options, source),
             },
             "english_old" => match options.network.as_str() {
-                "testnet" => output::<MoneroTestnet, EnglishOld>(options),
-                "stagenet" => output::<MoneroStagenet, EnglishOld>(options),
-                _ => output::<MoneroMainnet, EnglishOld>(options),
+                "testnet" => output::<MoneroTestnet, EnglishOld>(options, source),
+                "stagenet" => output::<MoneroStagenet, EnglishOld>(options, source),
+                _ => output::<MoneroMainnet, EnglishOld>(options, source),
             },
             "esperanto" => match options.network.as_str() {
-                "testnet" => output::<MoneroTestnet, Esperanto>(options),
-                "stagenet" => output::<MoneroStagenet, Esperanto>(options),
-                _ => output::<MoneroMainnet, Esperanto>(options),
+                "testnet" => output::<MoneroTestnet, Esperanto>(options, source),
+                "stagenet" => output::<MoneroStagenet, Esperanto>(options, source),
+                _ => output::<MoneroMainnet, Esperanto>(options, source),
             },
             "french" => match options.network.as_str() {
-                "testnet" => output::<MoneroTestnet, French>(options),
-                "stagenet" => output::<MoneroStagenet, French>(options),
-                _ => output::<MoneroMainnet, French>(options),
+                "testnet" => output::<MoneroTestnet, French>(options, source),
+                "stagenet" => output::<MoneroStagenet, French>(options, source),
+                _ => output::<MoneroMainnet, French>(options, source),
             },
             "german" => match options.network.as_str() {
-                "testnet" => output::<MoneroTestnet, German>(options),
-                "stagenet" => output::<MoneroStagenet, German>(options),
-                _ => output::<MoneroMainnet, German>(options),
+                "testnet" => output::<MoneroTestnet, German>(options, source),
+                "stagenet" => output::<MoneroStagenet, German>(options, source),
+                _ => output::<MoneroMainnet, German>(options, source),
             },
             "italian" => match options.network.as_str() {
-                "testnet" => output::<MoneroTestnet, Italian>(options),
-                "stagenet" => output::<MoneroStagenet, Italian>(options),
-                _ => output::<MoneroMainnet, Italian>(options),
+                "testnet" => output::<MoneroTestnet, Italian>(options, source),
+                "stagenet" => output::<MoneroStagenet, Italian>(options, source),
+                _ => output::<MoneroMainnet, Italian>(options, source),
             },
             "japanese" => match options.network.as_str() {
-                "testnet" => output::<MoneroTestnet, Japanese>(options),
-                "stagenet" => output::<MoneroStagenet, Japanese>(options),
-                _ => output::<MoneroMainnet, Japanese>(options),
+                "testnet" => output::<MoneroTestnet, Japanese>(options, source),
+                "stagenet" => output::<MoneroStagenet, Japanese>(options, source),
+                _ => output::<MoneroMainnet, Japanese>(options, source),
             },
             "lojban" => match options.network.as_str() {
-                "testnet" => output::<MoneroTestnet, Lojban>(options),
-                "stagenet" => output::<MoneroStagenet, Lojban>(options),
-                _ => output::<MoneroMainnet, Lojban>(options),
+                "testnet" => output::<MoneroTestnet, Lojban>(options, source),
+                "stagenet" => output::<MoneroStagenet, Lojban>(options, source),
+                _ => output::<MoneroMainnet, Lojban>(options, source),
             },
             "portuguese" => match options.network.as_str() {
-                "testnet" => output::<MoneroTestnet, Portuguese>(options),
-                "stagenet" => output::<MoneroStagenet, Portuguese>(options),
-                _ => output::<MoneroMainnet, Portuguese>(options),
+                "testnet" => output::<MoneroTestnet, Portuguese>(options, source),
+                "stagenet" => output::<MoneroStagenet, Portuguese>(options, source),
+                _ => output::<MoneroMainnet, Portuguese>(options, source),
             },
             "russian" => match options.network.as_str() {
-                "testnet" => output::<MoneroTestnet, Russian>(options),
-                "stagenet" => output::<MoneroStagenet, Russian>(options),
-                _ => output::<MoneroMainnet, Russian>(options),
+                "testnet" => output::<MoneroTestnet, Russian>(options, source),
+                "stagenet" => output::<MoneroStagenet, Russian>(options, source),
+                _ => output::<MoneroMainnet, Russian>(options, source),
             },
             "spanish" => match options.network.as_str() {
-                "testnet" => output::<MoneroTestnet, Spanish>(options),
-                "stagenet" => output::<MoneroStagenet, Spanish>(options),
-                _ => output::<MoneroMainnet, Spanish>(options),
+                "testnet" => output::<MoneroTestnet, Spanish>(options, source),
+                "stagenet" => output::<MoneroStagenet, Spanish>(options, source),
+                _ => output::<MoneroMainnet, Spanish>(options, source),
             },
             _ => match options.network.as_str() {
-                "testnet" => output::<MoneroTestnet, English>(options),
-                "stagenet" => output::<MoneroStagenet, English>(options),
-                _ => output::<MoneroMainnet, English>(options),
+                "testnet" => output::<MoneroTestnet, English>(options, source),
+                "stagenet" => output::<MoneroStagenet, English>(options, source),
+                _ => output::<MoneroMainnet, English>(options, source),
             },
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(arguments: &[&str]) -> Result<MoneroOptions, CLIError> {
+        MoneroCLI::parse(&MoneroCLI::new().get_matches_from(arguments))
+    }
+
+    #[test]
+    fn import_language() {
+        let phrase = ["ajuste"; 25].join(" ");
+        assert!(detect_language(&phrase).is_err());
+        assert!(parse(&["monero", "import", "--mnemonic", &phrase]).is_err());
+
+        let options = parse(&["monero", "import", "--mnemonic", &phrase, "--language", "spanish"]).unwrap();
+        assert_eq!("spanish", options.language);
+        let options = parse(&["monero", "import", "--mnemonic", &phrase, "--language", "portuguese"]).unwrap();
+        assert_eq!("portuguese", options.language);
+    }
+}
@@ -1,3 +1,7 @@
 // Global
 
 pub const JSON: &str = "[json] -j --json 'Prints the generated wallet(s) in JSON format'";
+
+// Bitcoin
+
+pub const URI_BITCOIN: &str = "[uri] --uri 'Prints the BIP21 payment URI of each address'";
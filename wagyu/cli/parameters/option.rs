@@ -5,6 +5,12 @@ use crate::cli::types::*;
 
 // Global
 
+pub const BIP38_BITCOIN: OptionType = (
+    "[bip38] --bip38=[passphrase] 'Generates a wallet with its private key encrypted by a specified BIP38 passphrase'",
+    &[],
+    &[],
+    &[],
+);
 pub const COUNT: OptionType = (
     "[count] -c --count=[count] 'Generates a specified number of wallets'",
     &[],
@@ -20,7 +26,7 @@ pub const DIVERSIFIER_ZCASH: OptionType = (
 pub const FORMAT_BITCOIN: OptionType = (
     "[format] -f --format=[format] 'Generates a wallet with a specified format'",
     &[],
-    &["bech32", "legacy", "segwit"],
+    &["bech32", "legacy", "segwit", "taproot"],
     &[],
 );
 pub const FORMAT_ZCASH: OptionType = (
@@ -42,6 +48,7 @@ pub const LANGUAGE_MONERO: OptionType = (
         "chinese_simplified",
         "dutch",
         "english",
+        "english_old",
         "esperanto",
         "french",
         "german",
@@ -57,7 +64,7 @@ pub const LANGUAGE_MONERO: OptionType = (
 pub const NETWORK_BITCOIN: OptionType = (
     "[network] -n --network=[network] 'Generates a wallet for a specified network'",
     &[],
-    &["mainnet", "testnet"],
+    &["mainnet", "regtest", "signet", "testnet"],
     &[],
 );
 pub const NETWORK_MONERO: OptionType = (
@@ -83,7 +90,7 @@ pub const SUBADDRESS_MONERO: OptionType = (
 
 pub const ADDRESS: OptionType = (
     "[address] --address=[address] 'Imports a partial wallet for a specified address'",
-    &["count", "network", "private", "public"],
+    &["count", "private", "public"],
     &[],
     &[],
 );
@@ -96,7 +103,7 @@ pub const DIVERSIFIER_IMPORT_ZCASH: OptionType = (
 pub const FORMAT_IMPORT_BITCOIN: OptionType = (
     "[format] -f --format=[format] 'Imports a wallet with a specified format'",
     &[],
-    &["bech32", "legacy", "segwit"],
+    &["bech32", "legacy", "segwit", "taproot"],
     &[],
 );
 pub const INTEGRATED_IMPORT_MONERO: OptionType = (
@@ -106,10 +113,10 @@ pub const INTEGRATED_IMPORT_MONERO: OptionType = (
     &[],
 );
 pub const LANGUAGE_IMPORT_MONERO: OptionType = (
-    "[language] -l --language=[language] 'Imports a wallet with a specified mnemonic language (requires private spend key)'",
+    "[language] -l --language=[language] 'Imports a wallet with a specified mnemonic language (detected by default)'",
+    &[],
+    &["chinese_simplified", "dutch", "english", "english_old", "esperanto", "french", "german", "italian", "japanese", "lojban", "portuguese", "russian", "spanish"],
     &[],
-    &["chinese_simplified", "dutch", "english", "esperanto", "french", "german", "italian", "japanese", "lojban", "portuguese", "russian", "spanish"],
-    &["private spend"],
 );
 pub const MNEMONIC_IMPORT_MONERO: OptionType = (
     "[mnemonic] -m --mnemonic=[\"mnemonic\"] 'Imports a wallet for a specified mnemonic (in quotes)'",
@@ -127,7 +134,7 @@ pub const MNEMONIC_IMPORT_MONERO: OptionType = (
 pub const NETWORK_IMPORT_BITCOIN: OptionType = (
     "[network] -n --network=[network] 'Imports a wallet for a specified network'",
     &[],
-    &["mainnet", "testnet"],
+    &["mainnet", "regtest", "signet", "testnet"],
     &[],
 );
 pub const NETWORK_IMPORT_MONERO: OptionType = (
@@ -138,7 +145,7 @@ pub const NETWORK_IMPORT_MONERO: OptionType = (
 );
 pub const PRIVATE: OptionType = (
     "[private] --private=[private key] 'Imports a wallet for a specified private key'",
-    &["address", "count", "network", "public"],
+    &["address", "count", "public"],
     &[],
     &[],
 );
@@ -181,8 +188,20 @@ pub const SUBADDRESS_IMPORT_MONERO: OptionType = (
 
 // HD
 
+pub const AEZEED_HD_BITCOIN: OptionType = (
+    "[aezeed] --aezeed 'Generates an HD wallet with an lnd aezeed cipher seed, enciphered with the password if specified'",
+    &["coin", "dice", "language", "slip39", "word count"],
+    &[],
+    &[],
+);
+pub const COIN_HD: OptionType = (
+    "[coin] --coin=[\"flips\"] 'Generates an HD wallet from the entropy of specified coin flips (in quotes) [e.g. \"HTTH...\"]'",
+    &["count", "dice", "slip39"],
+    &[],
+    &[],
+);
 pub const DERIVATION_BITCOIN: OptionType = (
-    "[derivation] -d --derivation=[\"path\"] 'Generates an HD wallet for a specified derivation path (in quotes) [possible values: bip32, bip44, bip49, \"<custom path>\"]'",
+    "[derivation] -d --derivation=[\"path\"] 'Generates an HD wallet for a specified derivation path (in quotes) [possible values: bip32, bip44, bip49, bip84, bip86, \"<custom path>\"]'",
     &[],
     &[],
     &[],
@@ -199,12 +218,24 @@ pub const DERIVATION_ZCASH: OptionType = (
     &[],
     &[],
 );
+pub const DICE_HD: OptionType = (
+    "[dice] --dice=[\"rolls\"] 'Generates an HD wallet from the entropy of specified dice rolls of 1 to 6 (in quotes) [e.g. \"3516...\"]'",
+    &["coin", "count", "slip39"],
+    &[],
+    &[],
+);
 pub const DIVERSIFIER_HD_ZCASH: OptionType = (
     "[diversifier] --diversifier=[diversifier] 'Generates an HD wallet with a specified Sapling address diversifier'",
     &[],
     &[],
     &[],
 );
+pub const GROUP_THRESHOLD_HD: OptionType = (
+    "[group threshold] --group-threshold=[threshold] 'Generates SLIP-39 shares requiring a specified number of groups (defaults to 1)'",
+    &[],
+    &[],
+    &["slip39"],
+);
 pub const INDEX_HD: OptionType = (
     "[index] -i --index=[index] 'Generates an HD wallet with a specified index'",
     &[],
@@ -223,11 +254,13 @@ pub const LANGUAGE_HD: OptionType = (
     &[
         "chinese_simplified",
         "chinese_traditional",
+        "czech",
         "english",
         "french",
         "italian",
         "japanese",
         "korean",
+        "portuguese",
         "spanish",
     ],
     &[],
@@ -235,7 +268,7 @@ pub const LANGUAGE_HD: OptionType = (
 pub const NETWORK_HD_BITCOIN: OptionType = (
     "[network] -n --network=[network] 'Generates an HD wallet for a specified network'",
     &[],
-    &["mainnet", "testnet"],
+    &["mainnet", "regtest", "signet", "testnet"],
     &[],
 );
 pub const NETWORK_HD_ZCASH: OptionType = (
@@ -250,6 +283,30 @@ pub const PASSWORD_HD: OptionType = (
     &[],
     &[],
 );
+pub const PROMPT_PASSWORD_HD: OptionType = (
+    "[prompt password] --prompt-password 'Generates an HD wallet with a password prompted from the terminal and confirmed'",
+    &["password"],
+    &[],
+    &[],
+);
+pub const PRESET_BITCOIN: OptionType = (
+    "[preset] --preset=[preset] 'Generates an HD wallet with the default derivation path of a specified wallet'",
+    &["derivation"],
+    &["electrum", "ledger-live", "trezor"],
+    &[],
+);
+pub const PRESET_ETHEREUM: OptionType = (
+    "[preset] --preset=[preset] 'Generates an HD wallet with the default derivation path of a specified wallet'",
+    &["derivation"],
+    &["ledger-live", "metamask", "myetherwallet-legacy", "trezor"],
+    &[],
+);
+pub const SLIP39_HD: OptionType = (
+    "[slip39] --slip39=[\"groups\"] 'Generates an HD wallet split into SLIP-39 shares for specified groups of member thresholds and counts (in quotes) [e.g. \"2of3\", \"1of1,2of3\"]'",
+    &["language", "word count"],
+    &[],
+    &[],
+);
 pub const WORD_COUNT: OptionType = (
     "[word count] -w --word-count=[word count] 'Generates an HD wallet with a specified word count'",
     &[],
@@ -260,19 +317,25 @@ pub const WORD_COUNT: OptionType = (
 // Import HD
 
 pub const ACCOUNT: OptionType = (
-    "[account] -a --account=[account] 'Imports an HD wallet for a specified account number for bip44 and bip49 derivations'",
+    "[account] -a --account=[account] 'Imports an HD wallet for a specified account number for bip44, bip49, bip84, and bip86 derivations'",
+    &[],
+    &[],
     &[],
+);
+pub const AEZEED_IMPORT_HD_BITCOIN: OptionType = (
+    "[aezeed] --aezeed=[\"mnemonic\"] 'Imports an HD wallet for a specified lnd aezeed mnemonic (in quotes), deciphered with the password if specified'",
+    &["count", "extended private", "extended public", "mnemonic", "share"],
     &[],
     &[],
 );
 pub const CHAIN: OptionType = (
-    "[chain] -c --chain=[chain] 'Imports an HD wallet for a specified (external/internal) chain for bip44 and bip49 derivations'",
+    "[chain] -c --chain=[chain] 'Imports an HD wallet for a specified (external/internal) chain for bip44, bip49, bip84, and bip86 derivations'",
     &[],
     &["0", "1"],
     &[],
 );
 pub const DERIVATION_IMPORT_BITCOIN: OptionType = (
-    "[derivation] -d --derivation=[\"path\"] 'Imports an HD wallet for a specified derivation path (in quotes) [possible values: bip32, bip44, bip49, \"<custom path>\"]'",
+    "[derivation] -d --derivation=[\"path\"] 'Imports an HD wallet for a specified derivation path (in quotes) [possible values: bip32, bip44, bip49, bip84, bip86, \"<custom path>\"]'",
     &[],
     &[],
     &[],
@@ -297,7 +360,7 @@ pub const DIVERSIFIER_IMPORT_HD_ZCASH: OptionType = (
 );
 pub const EXTENDED_PUBLIC: OptionType = (
     "[extended public] --extended-public=[extended public] 'Imports a partial HD wallet for a specified extended public key'",
-    &["account", "count", "extended private", "index", "mnemonic", "password"],
+    &["account", "count", "extended private", "mnemonic", "password"],
     &[],
     &[],
 );
@@ -311,7 +374,7 @@ pub const EXTENDED_PRIVATE: OptionType = (
 pub const NETWORK_IMPORT_HD_BITCOIN: OptionType = (
     "[network] -n --network=[network] 'Imports an HD wallet for a specified network'",
     &[],
-    &["mainnet", "testnet"],
+    &["mainnet", "regtest", "signet", "testnet"],
     &[],
 );
 pub const INDEX_IMPORT_HD: OptionType = (
@@ -326,18 +389,635 @@ pub const INDICES_IMPORT_HD: OptionType = (
     &[],
     &[],
 );
+pub const LANGUAGE_IMPORT_HD: OptionType = (
+    "[language] -l --language=[language] 'Imports an HD wallet with a specified mnemonic language (detected by default)'",
+    &["extended private", "extended public", "share"],
+    &[
+        "chinese_simplified",
+        "chinese_traditional",
+        "czech",
+        "english",
+        "french",
+        "italian",
+        "japanese",
+        "korean",
+        "portuguese",
+        "spanish",
+    ],
+    &["mnemonic"],
+);
 pub const MNEMONIC: OptionType = (
     "[mnemonic] -m --mnemonic=[\"mnemonic\"] 'Imports an HD wallet for a specified mnemonic (in quotes)'",
     &["count", "extended private", "extended public"],
     &[],
     &[],
 );
+pub const RECOVER_IMPORT_HD: OptionType = (
+    "[recover] --recover=[address] 'Recovers an HD wallet for a specified address, given a mnemonic with up to two unknown words marked by \"?\"'",
+    &["extended private", "extended public", "share"],
+    &[],
+    &["mnemonic"],
+);
+pub const SEED_QR_IMPORT_HD: OptionType = (
+    "[seedqr] --seedqr=[\"seedqr\"] 'Imports an HD wallet for a specified SeedQR, as Standard SeedQR digits or Compact SeedQR hex'",
+    &["aezeed", "count", "extended private", "extended public", "mnemonic", "share"],
+    &[],
+    &[],
+);
+pub const SHARE_IMPORT_HD: OptionType = (
+    "[share] --share=[\"share\"]... 'Imports an HD wallet for specified SLIP-39 shares (in quotes, repeated for each share)'",
+    &["count", "extended private", "extended public", "mnemonic"],
+    &[],
+    &[],
+);
+pub const PRESET_IMPORT_BITCOIN: OptionType = (
+    "[preset] --preset=[preset] 'Imports an HD wallet with the default derivation path of a specified wallet'",
+    &["derivation"],
+    &["electrum", "ledger-live", "trezor"],
+    &[],
+);
+pub const PRESET_IMPORT_ETHEREUM: OptionType = (
+    "[preset] --preset=[preset] 'Imports an HD wallet with the default derivation path of a specified wallet'",
+    &["derivation"],
+    &["ledger-live", "metamask", "myetherwallet-legacy", "trezor"],
+    &[],
+);
 pub const PASSWORD_IMPORT_HD: OptionType = (
     "[password] -p --password=[password] 'Imports an HD wallet with a specified password'",
     &["extended private", "extended public"],
     &[],
     &[],
 );
+pub const PROMPT_PASSWORD_IMPORT_HD: OptionType = (
+    "[prompt password] --prompt-password 'Imports an HD wallet with a password prompted from the terminal and confirmed'",
+    &["extended private", "extended public", "password"],
+    &[],
+    &[],
+);
+pub const WATCH_ONLY_IMPORT_HD: OptionType = (
+    "[watch only] --watch-only 'Imports a watch-only HD wallet of the child addresses of an extended public key, from a specified index to a number of specified indices'",
+    &["extended private"],
+    &[],
+    &["extended public"],
+);
+
+// Backup
+
+pub const BACKUP_IMPORT_BACKUP: OptionType = (
+    "[backup] --backup=[backup] 'Imports an HD wallet for a specified encrypted backup'",
+    &[],
+    &[],
+    &["backup password"],
+);
+pub const BACKUP_PASSWORD_EXPORT_BACKUP: OptionType = (
+    "[backup password] --backup-password=[password] 'Encrypts the backup with a specified password'",
+    &[],
+    &[],
+    &["mnemonic"],
+);
+pub const BACKUP_PASSWORD_IMPORT_BACKUP: OptionType = (
+    "[backup password] --backup-password=[password] 'Decrypts the backup with a specified password'",
+    &[],
+    &[],
+    &["backup"],
+);
+pub const LANGUAGE_EXPORT_BACKUP: OptionType = (
+    "[language] -l --language=[language] 'Exports a backup of a mnemonic with a specified language (detected by default)'",
+    &[],
+    &[
+        "chinese_simplified",
+        "chinese_traditional",
+        "czech",
+        "english",
+        "french",
+        "italian",
+        "japanese",
+        "korean",
+        "portuguese",
+        "spanish",
+    ],
+    &["mnemonic"],
+);
+pub const MNEMONIC_EXPORT_BACKUP: OptionType = (
+    "[mnemonic] -m --mnemonic=[\"mnemonic\"] 'Exports an encrypted backup of a specified mnemonic (in quotes)'",
+    &[],
+    &[],
+    &["backup password"],
+);
+
+// SeedQR
+
+pub const COMPACT_EXPORT_SEED_QR: OptionType = (
+    "[compact] --compact 'Exports a Compact SeedQR, encoding the raw entropy of the mnemonic'",
+    &[],
+    &[],
+    &["mnemonic"],
+);
+pub const LANGUAGE_EXPORT_SEED_QR: OptionType = (
+    "[language] -l --language=[language] 'Exports a SeedQR of a mnemonic with a specified language (detected by default)'",
+    &[],
+    &[
+        "chinese_simplified",
+        "chinese_traditional",
+        "czech",
+        "english",
+        "french",
+        "italian",
+        "japanese",
+        "korean",
+        "portuguese",
+        "spanish",
+    ],
+    &["mnemonic"],
+);
+pub const MNEMONIC_EXPORT_SEED_QR: OptionType = (
+    "[mnemonic] -m --mnemonic=[\"mnemonic\"] 'Exports a SeedQR of a specified 12 or 24-word mnemonic (in quotes)'",
+    &[],
+    &[],
+    &[],
+);
+
+// Convert xpub
+
+pub const EXTENDED_PUBLIC_CONVERT_XPUB: OptionType = (
+    "[extended public] --extended-public=[extended public] 'Converts a specified extended public key'",
+    &[],
+    &[],
+    &[],
+);
+pub const FORMAT_CONVERT_XPUB: OptionType = (
+    "[format] -f --format=[format] 'Converts to a specified extended public key format, or its testnet equivalent (tpub, upub, vpub, Vpub)'",
+    &[],
+    &["xpub", "ypub", "zpub", "Zpub"],
+    &["extended public"],
+);
+
+// Decode transaction
+
+pub const NETWORK_DECODE_TX: OptionType = (
+    "[network] -n --network=[network] 'Decodes the addresses of the outputs for a specified network'",
+    &[],
+    &["mainnet", "regtest", "signet", "testnet"],
+    &[],
+);
+pub const TRANSACTION_DECODE_TX: OptionType = (
+    "[transaction] --transaction=[transaction hex] 'Decodes a specified raw transaction, in its legacy or SegWit serialization'",
+    &[],
+    &[],
+    &[],
+);
+
+// Electrum
+
+pub const ADDRESS_ELECTRUM_BITCOIN: OptionType = (
+    "[address] --address=[address] 'Fetches the balance and unspent outputs of a specified address'",
+    &["broadcast"],
+    &[],
+    &["server"],
+);
+pub const BROADCAST_ELECTRUM_BITCOIN: OptionType = (
+    "[broadcast] --broadcast=[transaction hex] 'Broadcasts a specified signed raw transaction'",
+    &["address"],
+    &[],
+    &["server"],
+);
+pub const NETWORK_ELECTRUM_BITCOIN: OptionType = (
+    "[network] -n --network=[network] 'Parses the address and transaction for a specified network'",
+    &[],
+    &["mainnet", "regtest", "signet", "testnet"],
+    &[],
+);
+pub const SERVER_ELECTRUM_BITCOIN: OptionType = (
+    "[server] --server=[host:port] 'Connects to the Electrum server at a specified TCP address, e.g. 127.0.0.1:50001'",
+    &[],
+    &[],
+    &[],
+);
+
+// Export descriptors
+
+pub const ACCOUNT_EXPORT_DESCRIPTORS: OptionType = (
+    "[account] -a --account=[account] 'Exports the descriptors of a specified account number of the mnemonic'",
+    &[],
+    &[],
+    &["mnemonic"],
+);
+pub const DERIVATION_EXPORT_DESCRIPTORS: OptionType = (
+    "[derivation] -d --derivation=[derivation] 'Exports the descriptors of a specified account derivation of the mnemonic (bip84 by default)'",
+    &[],
+    &["bip49", "bip84", "bip86"],
+    &["mnemonic"],
+);
+pub const EXTENDED_PUBLIC_EXPORT_DESCRIPTORS: OptionType = (
+    "[extended public] --extended-public=[extended public] 'Exports the descriptors of a specified account extended public key'",
+    &["mnemonic"],
+    &[],
+    &[],
+);
+pub const FORMAT_EXPORT_DESCRIPTORS: OptionType = (
+    "[format] -f --format=[format] 'Exports the descriptors for a specified address format (detected by default)'",
+    &[],
+    &["bech32", "segwit", "taproot"],
+    &[],
+);
+pub const MNEMONIC_EXPORT_DESCRIPTORS: OptionType = (
+    "[mnemonic] -m --mnemonic=[\"mnemonic\"] 'Exports the descriptors of an account of a specified mnemonic (in quotes)'",
+    &["extended public"],
+    &[],
+    &[],
+);
+pub const NETWORK_EXPORT_DESCRIPTORS: OptionType = (
+    "[network] -n --network=[network] 'Exports the descriptors for a specified network'",
+    &[],
+    &["mainnet", "regtest", "signet", "testnet"],
+    &[],
+);
+pub const ORIGIN_EXPORT_DESCRIPTORS: OptionType = (
+    "[origin] --origin=[key origin] 'Exports the descriptors with a specified key origin of the extended public key (e.g. [73c5da0a/84h/0h/0h])'",
+    &[],
+    &[],
+    &["extended public"],
+);
+pub const PASSWORD_EXPORT_DESCRIPTORS: OptionType = (
+    "[password] -p --password=[password] 'Exports the descriptors of the mnemonic with a specified password'",
+    &[],
+    &[],
+    &["mnemonic"],
+);
+pub const RANGE_EXPORT_DESCRIPTORS: OptionType = (
+    "[range] --range=[end] 'Exports the descriptors with a specified last index of the imported range (999 by default)'",
+    &[],
+    &[],
+    &[],
+);
+pub const RPC_EXPORT_DESCRIPTORS: OptionType = (
+    "[rpc] --rpc=[rpc] 'Exports the requests of a specified Bitcoin Core RPC (importdescriptors by default)'",
+    &[],
+    &["importdescriptors", "importmulti"],
+    &[],
+);
+pub const TIMESTAMP_EXPORT_DESCRIPTORS: OptionType = (
+    "[timestamp] --timestamp=[timestamp] 'Exports the descriptors with a specified rescan timestamp, as \"now\" or a Unix time (now by default)'",
+    &[],
+    &[],
+    &[],
+);
+
+// Export Electrum
+
+pub const ACCOUNT_EXPORT_ELECTRUM: OptionType = (
+    "[account] -a --account=[account] 'Exports the Electrum wallet of a specified account number of the mnemonic'",
+    &[],
+    &[],
+    &["mnemonic"],
+);
+pub const DERIVATION_EXPORT_ELECTRUM: OptionType = (
+    "[derivation] -d --derivation=[derivation] 'Exports the Electrum wallet of a specified account derivation of the mnemonic (bip84 by default)'",
+    &[],
+    &["bip44", "bip49", "bip84"],
+    &["mnemonic"],
+);
+pub const EXTENDED_PUBLIC_EXPORT_ELECTRUM: OptionType = (
+    "[extended public] --extended-public=[extended public] 'Exports the watch-only Electrum wallet of a specified account extended public key'",
+    &["mnemonic"],
+    &[],
+    &[],
+);
+pub const FORMAT_EXPORT_ELECTRUM: OptionType = (
+    "[format] -f --format=[format] 'Exports the Electrum wallet for a specified address format (detected by default)'",
+    &[],
+    &["bech32", "legacy", "segwit"],
+    &[],
+);
+pub const MNEMONIC_EXPORT_ELECTRUM: OptionType = (
+    "[mnemonic] -m --mnemonic=[\"mnemonic\"] 'Exports the Electrum wallet of an account of a specified mnemonic (in quotes)'",
+    &["extended public"],
+    &[],
+    &[],
+);
+pub const NETWORK_EXPORT_ELECTRUM: OptionType = (
+    "[network] -n --network=[network] 'Exports the Electrum wallet for a specified network'",
+    &[],
+    &["mainnet", "regtest", "signet", "testnet"],
+    &[],
+);
+pub const ORIGIN_EXPORT_ELECTRUM: OptionType = (
+    "[origin] --origin=[key origin] 'Exports the Electrum wallet with a specified key origin of the extended public key (e.g. [73c5da0a/84h/0h/0h])'",
+    &[],
+    &[],
+    &["extended public"],
+);
+pub const PASSWORD_EXPORT_ELECTRUM: OptionType = (
+    "[password] -p --password=[password] 'Exports the Electrum wallet of the mnemonic with a specified password'",
+    &[],
+    &[],
+    &["mnemonic"],
+);
+pub const WATCH_ONLY_EXPORT_ELECTRUM: OptionType = (
+    "[watch only] --watch-only 'Exports a watch-only Electrum wallet of the mnemonic, without its extended private key'",
+    &[],
+    &[],
+    &["mnemonic"],
+);
+
+// Ledger
+
+pub const ACCOUNT_LEDGER_BITCOIN: OptionType = (
+    "[account] -a --account=[account] 'Uses a specified account number of the Ledger device (0 by default)'",
+    &[],
+    &[],
+    &[],
+);
+pub const CHAIN_LEDGER_BITCOIN: OptionType = (
+    "[chain] -c --chain=[chain] 'Verifies the address of a specified (external/internal) chain of the account'",
+    &["psbt"],
+    &["0", "1"],
+    &[],
+);
+pub const DEVICE_LEDGER_BITCOIN: OptionType = (
+    "[device] --device=[path] 'Connects to the Ledger device at a specified hidraw path (detected on Linux by default)'",
+    &[],
+    &[],
+    &[],
+);
+pub const FORMAT_LEDGER_BITCOIN: OptionType = (
+    "[format] -f --format=[format] 'Uses the account of a specified address format (bech32 by default)'",
+    &[],
+    &["bech32", "legacy", "segwit", "taproot"],
+    &[],
+);
+pub const INDEX_LEDGER_BITCOIN: OptionType = (
+    "[index] -i --index=[index] 'Displays a specified address index of the account on the Ledger device and verifies it'",
+    &["psbt"],
+    &[],
+    &[],
+);
+pub const NETWORK_LEDGER_BITCOIN: OptionType = (
+    "[network] -n --network=[network] 'Uses the account and parses the PSBT for a specified network'",
+    &[],
+    &["mainnet", "regtest", "signet", "testnet"],
+    &[],
+);
+pub const PSBT_LEDGER_BITCOIN: OptionType = (
+    "[psbt] --psbt=[psbt] 'Signs the inputs of the account in a specified Base64 PSBT on the Ledger device'",
+    &["chain", "index"],
+    &[],
+    &[],
+);
+
+// Message
+
+pub const FORMAT_MESSAGE_BITCOIN: OptionType = (
+    "[format] -f --format=[format] 'Signs a message for a specified address format (BIP322 for bech32 and taproot)'",
+    &["verifymessage"],
+    &["bech32", "legacy", "segwit", "taproot"],
+    &["signmessage"],
+);
+pub const SIGN_MESSAGE_BITCOIN: OptionType = (
+    "[signmessage] --signmessage=[private key] [message] 'Signs a message with a specified private key'",
+    &["verifymessage"],
+    &[],
+    &[],
+);
+pub const VERIFY_MESSAGE_BITCOIN: OptionType = (
+    "[verifymessage] --verifymessage=[address] [signature] [message] 'Verifies a signed message for a specified address'",
+    &["signmessage"],
+    &[],
+    &[],
+);
+
+// Multisig
+
+pub const NETWORK_MULTISIG_BITCOIN: OptionType = (
+    "[network] -n --network=[network] 'Generates a multisig wallet for a specified network'",
+    &[],
+    &["mainnet", "regtest", "signet", "testnet"],
+    &[],
+);
+pub const PUBLIC_KEY_MULTISIG_BITCOIN: OptionType = (
+    "[public key] -k --public-key=[public key]... 'Generates a multisig wallet for specified compressed public keys (repeated for each public key)'",
+    &[],
+    &[],
+    &["threshold"],
+);
+pub const THRESHOLD_MULTISIG_BITCOIN: OptionType = (
+    "[threshold] -t --threshold=[threshold] 'Generates a multisig wallet requiring a specified number of signatures'",
+    &[],
+    &[],
+    &["public key"],
+);
+
+// Trezor
+
+pub const ACCOUNT_TREZOR_BITCOIN: OptionType = (
+    "[account] -a --account=[account] 'Uses a specified account number of the Trezor device (0 by default)'",
+    &[],
+    &[],
+    &[],
+);
+pub const BRIDGE_TREZOR_BITCOIN: OptionType = (
+    "[bridge] --bridge=[address] 'Connects through Trezor Bridge at a specified address, e.g. 127.0.0.1:21325'",
+    &["device"],
+    &[],
+    &[],
+);
+pub const CHAIN_TREZOR_BITCOIN: OptionType = (
+    "[chain] -c --chain=[chain] 'Verifies the address of a specified (external/internal) chain of the account'",
+    &["psbt"],
+    &["0", "1"],
+    &[],
+);
+pub const DEVICE_TREZOR_BITCOIN: OptionType = (
+    "[device] --device=[path] 'Connects to the Trezor device at a specified hidraw path (detected on Linux by default)'",
+    &["bridge"],
+    &[],
+    &[],
+);
+pub const FORMAT_TREZOR_BITCOIN: OptionType = (
+    "[format] -f --format=[format] 'Uses the account of a specified address format (bech32 by default)'",
+    &[],
+    &["bech32", "legacy", "segwit", "taproot"],
+    &[],
+);
+pub const INDEX_TREZOR_BITCOIN: OptionType = (
+    "[index] -i --index=[index] 'Displays a specified address index of the account on the Trezor device and verifies it'",
+    &["psbt"],
+    &[],
+    &[],
+);
+pub const NETWORK_TREZOR_BITCOIN: OptionType = (
+    "[network] -n --network=[network] 'Uses the account and parses the PSBT for a specified network'",
+    &[],
+    &["mainnet", "regtest", "signet", "testnet"],
+    &[],
+);
+pub const PASSPHRASE_TREZOR_BITCOIN: OptionType = (
+    "[passphrase] --passphrase=[passphrase] 'Uses the hidden wallet of a specified passphrase on the Trezor device'",
+    &[],
+    &[],
+    &[],
+);
+pub const PSBT_TREZOR_BITCOIN: OptionType = (
+    "[psbt] --psbt=[psbt] 'Signs the inputs of the device in a specified Base64 PSBT on the Trezor device'",
+    &["chain", "index"],
+    &[],
+    &[],
+);
+
+// Vanity
+
+pub const FORMAT_VANITY_BITCOIN: OptionType = (
+    "[format] -f --format=[format] 'Searches for an address with a specified format'",
+    &[],
+    &["bech32", "legacy", "segwit", "taproot"],
+    &[],
+);
+pub const NETWORK_VANITY_BITCOIN: OptionType = (
+    "[network] -n --network=[network] 'Searches for an address on a specified network'",
+    &[],
+    &["mainnet", "regtest", "signet", "testnet"],
+    &[],
+);
+pub const PREFIX_VANITY_BITCOIN: OptionType = (
+    "[prefix] --prefix=[prefix] 'Searches for an address starting with a specified prefix (e.g. 1Love, bc1qlove)'",
+    &["regex"],
+    &[],
+    &[],
+);
+pub const REGEX_VANITY_BITCOIN: OptionType = (
+    "[regex] --regex=[regex] 'Searches for an address matching a specified regular expression'",
+    &["prefix"],
+    &[],
+    &[],
+);
+pub const THREADS_VANITY_BITCOIN: OptionType = (
+    "[threads] --threads=[threads] 'Searches with a specified number of threads (default: one per core)'",
+    &[],
+    &[],
+    &[],
+);
+
+// PSBT
+
+pub const COMBINE_PSBT_BITCOIN: OptionType = (
+    "[combine] --combine=[psbt]... 'Combines the signatures and metadata of specified PSBTs of the same transaction'",
+    &["create", "finalize", "sign", "update"],
+    &[],
+    &[],
+);
+
+pub const CREATE_PSBT_BITCOIN: OptionType = (
+    "[create] --create=[inputs] [outputs] 'Generates an unsigned PSBT
+    Inputs format: '[{\"txid\":\"txid\", \"vout\":index},...]'
+    Outputs format: '{\"address\":amount,...}'
+    (Optional: specify sequence, e.g. 144 for a BIP68 relative lock time of 144 blocks)
+    '",
+    &["combine", "finalize", "sign", "update"],
+    &[],
+    &[],
+);
+
+pub const DATA_PSBT_BITCOIN: OptionType = (
+    "[data] --data=[data] 'Adds an OP_RETURN output with specified hex data, up to 80 bytes'",
+    &[],
+    &[],
+    &["create"],
+);
+
+pub const FINALIZE_PSBT_BITCOIN: OptionType = (
+    "[finalize] --finalize=[psbt] 'Finalizes the signed inputs of a PSBT, and extracts its transaction once every input is finalized'",
+    &["combine", "create", "sign", "update"],
+    &[],
+    &[],
+);
+
+pub const MODIFIABLE_PSBT_BITCOIN: OptionType = (
+    "[modifiable] --modifiable=[modifiable]... 'Generates a version 2 PSBT whose inputs or outputs can be added'",
+    &[],
+    &["inputs", "outputs"],
+    &["create"],
+);
+
+pub const LOCK_TIME_PSBT_BITCOIN: OptionType = (
+    "[lock time] --lock-time=[lock time] 'Specify a Bitcoin transaction lock time'",
+    &[],
+    &[],
+    &["create"],
+);
+
+pub const PSBT_VERSION_PSBT_BITCOIN: OptionType = (
+    "[psbt version] --psbt-version=[psbt version] 'Specify a PSBT version'",
+    &[],
+    &["0", "2"],
+    &["create"],
+);
+
+pub const REPLACEABLE_PSBT_BITCOIN: OptionType = (
+    "[replaceable] --replaceable 'Signals BIP125 replace-by-fee on the inputs without a specified sequence'",
+    &[],
+    &[],
+    &["create"],
+);
+
+pub const SIGN_PSBT_BITCOIN: OptionType = (
+    "[sign] --sign=[psbt] [private key] 'Signs the inputs of a PSBT with a specified private key or master extended private key'",
+    &["combine", "create", "finalize", "update"],
+    &[],
+    &[],
+);
+
+pub const UPDATE_PSBT_BITCOIN: OptionType = (
+    "[update] --update=[psbt] [inputs] 'Adds the UTXO, scripts, and key origins of inputs to a PSBT
+    (Adds the inputs to a version 2 PSBT whose inputs are modifiable)
+    Inputs format: '[{\"txid\":\"txid\", \"vout\":index, \"amount\":amount, \"address\":\"address\"},...]'
+    (Optional: specify scriptPubKey, redeemScript, witnessScript, and nonWitnessUtxo)
+    (Optional: specify publickey and origin, e.g. \"[d34db33f/84'/0'/0'/0/0]\", to sign with an extended private key)
+    (Optional: specify sighash, e.g. \"SINGLE|ANYONECANPAY\", to sign with a sighash other than ALL)
+    '",
+    &["combine", "create", "finalize", "sign"],
+    &[],
+    &[],
+);
+
+pub const VERSION_PSBT_BITCOIN: OptionType = (
+    "[version] --version=[version] 'Specify a Bitcoin transaction version'",
+    &[],
+    &[],
+    &["create"],
+);
+
+// Timelock
+
+pub const AFTER_TIMELOCK_BITCOIN: OptionType = (
+    "[after] -a --after=[lock time] 'Generates a timelock wallet spendable after a specified block height or Unix time'",
+    &["older"],
+    &[],
+    &[],
+);
+pub const HTLC_TIMELOCK_BITCOIN: OptionType = (
+    "[htlc] --htlc=[hash] [recipient] [refund] 'Generates a hash timelock contract paying a recipient public key who reveals the preimage of a specified SHA256 hash, or refunding a refund public key once the timelock expires'",
+    &["public key"],
+    &[],
+    &[],
+);
+pub const NETWORK_TIMELOCK_BITCOIN: OptionType = (
+    "[network] -n --network=[network] 'Generates a timelock wallet for a specified network'",
+    &[],
+    &["mainnet", "regtest", "signet", "testnet"],
+    &[],
+);
+pub const OLDER_TIMELOCK_BITCOIN: OptionType = (
+    "[older] -o --older=[blocks] 'Generates a timelock wallet spendable a specified number of blocks after its output confirms'",
+    &["after"],
+    &[],
+    &[],
+);
+pub const PUBLIC_KEY_TIMELOCK_BITCOIN: OptionType = (
+    "[public key] -k --public-key=[public key] 'Generates a timelock wallet for a specified public key'",
+    &["htlc"],
+    &[],
+    &[],
+);
 
 // Transaction
 
@@ -345,8 +1025,9 @@ pub const CREATE_RAW_TRANSACTION_BITCOIN: OptionType = (
     "[createrawtransaction] --createrawtransaction= [inputs] [outputs] 'Generates a raw Bitcoin transaction
     Inputs format: '[{\"txid\":\"txid\", \"vout\":index},...]'
     Outputs format: '{\"address\":amount,...}'
+    (Optional: specify sequence, e.g. 144 for a BIP68 relative lock time of 144 blocks)
     '",
-    &["signrawtransaction"],
+    &["import", "signrawtransaction"],
     &[],
     &[],
 );
@@ -355,12 +1036,42 @@ pub const SIGN_RAW_TRANSACTION_BITCOIN: OptionType = (
     "[signrawtransaction] --signrawtransaction=[transaction hex] [inputs] 'Sign a raw Bitcoin transaction
     Inputs format: '[{\"txid\":\"txid\", \"vout\":index, \"amount\":amount, \"address\":\"address\", \"privatekey\":\"private_key\"},...]'
     (Optional: manually specify scriptPubKey and redeemScript)
+    (P2PK and bare multisig inputs: specify scriptPubKey instead of address)
+    (P2WSH inputs: specify witnessScript, a P2PK or multisig script signed by the key)
+    (P2TR script path inputs: specify leafScript and controlBlock, to sign with a key in the leaf script)
+    (Optional: specify sighash, e.g. \"SINGLE|ANYONECANPAY\", to sign with a sighash other than ALL)
     '",
-    &["createrawtransaction", "lock time", "version"],
+    &["createrawtransaction", "data", "export", "import", "lock time", "replaceable", "version"],
     &[],
     &[],
 );
 
+pub const EXPORT_TRANSACTION_BITCOIN: OptionType = (
+    "[export] --export 'Generates the transaction in the JSON exchange format, to sign on an air-gapped machine
+    Inputs format: '[{\"txid\":\"txid\", \"vout\":index, \"amount\":amount, \"address\":\"address\", \"origin\":\"[fingerprint/path]\"},...]'
+    (P2PK and bare multisig inputs: specify scriptPubKey instead of address)
+    (P2SH and P2WSH inputs: specify redeemScript or witnessScript)
+    (Optional: specify origin, the key origin of the key which signs the input, to sign with a master extended private key)
+    '",
+    &["import", "signrawtransaction"],
+    &[],
+    &["createrawtransaction"],
+);
+
+pub const IMPORT_TRANSACTION_BITCOIN: OptionType = (
+    "[import] --import=[transaction] [private key] 'Signs a transaction in the JSON exchange format with a specified private key or master extended private key'",
+    &["createrawtransaction", "data", "export", "lock time", "replaceable", "signrawtransaction", "version"],
+    &[],
+    &[],
+);
+
+pub const TRANSACTION_DATA_BITCOIN: OptionType = (
+    "[data] --data=[data] 'Adds an OP_RETURN output with specified hex data, up to 80 bytes'",
+    &["signrawtransaction"],
+    &[],
+    &["createrawtransaction"],
+);
+
 pub const TRANSACTION_LOCK_TIME_BITCOIN: OptionType = (
     "[lock time] --lock-time=[lock time] 'Specify a Bitcoin transaction lock time'",
     &["signrawtransaction"],
@@ -368,6 +1079,13 @@ pub const TRANSACTION_LOCK_TIME_BITCOIN: OptionType = (
     &["createrawtransaction"],
 );
 
+pub const TRANSACTION_REPLACEABLE_BITCOIN: OptionType = (
+    "[replaceable] --replaceable 'Signals BIP125 replace-by-fee on the inputs without a specified sequence'",
+    &["signrawtransaction"],
+    &[],
+    &["createrawtransaction"],
+);
+
 pub const TRANSACTION_VERSION_BITCOIN: OptionType = (
     "[version] --version=[version] 'Specify a Bitcoin transaction version'",
     &["signrawtransaction"],
@@ -377,7 +1095,7 @@ pub const TRANSACTION_VERSION_BITCOIN: OptionType = (
 
 pub const CREATE_RAW_TRANSACTION_ETHEREUM: OptionType = (
     "[createrawtransaction] --createrawtransaction= ['{\"to\":\"address\", \"value\":\"value\", \"gas\":\"gas\", \"gasPrice\":\"gas_price\", \"nonce\":nonce, \"network\":\"network\"}'] 'Generates a raw Ethereum transaction
-    (Optional: Add a data field)'",
+    (Optional: Add a data field, a chainId in place of the chain id of the network, an accessList for an EIP-2930 transaction, an erc20 token to send the value in tokens, an erc721 or erc1155 token to transfer the tokenId, a contract call of a function and its args, an initCode in place of the to address to deploy a contract, or maxFeePerGas and maxPriorityFeePerGas in place of gasPrice for an EIP-1559 transaction)'",
     &["network", "signrawtransaction"],
     &[],
     &[],
@@ -390,6 +1108,22 @@ pub const SIGN_RAW_TRANSACTION_ETHEREUM: OptionType = (
     &[],
 );
 
+pub const TRANSACTION_CHAIN_ETHEREUM: OptionType = (
+    "[chain] --chain=[chain] 'Specify an EVM chain of the transaction by name'",
+    &["signrawtransaction"],
+    &[
+        "arbitrum",
+        "avalanche",
+        "bsc",
+        "holesky",
+        "mainnet",
+        "optimism",
+        "polygon",
+        "sepolia",
+    ],
+    &["createrawtransaction"],
+);
+
 pub const TRANSACTION_NETWORK_ETHEREUM: OptionType = (
     "[network] --network=[network] 'Specify an Ethereum transaction network'",
     &["signrawtransaction"],
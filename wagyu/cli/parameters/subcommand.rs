@@ -5,15 +5,173 @@ use clap::AppSettings;
 // Format
 // (name, about, options, settings)
 
+pub const CONVERT_XPUB_BITCOIN: SubCommandType = (
+    "convert-xpub",
+    "Converts an extended public key between SLIP-132 formats (include -h for more options)",
+    &[option::EXTENDED_PUBLIC_CONVERT_XPUB, option::FORMAT_CONVERT_XPUB],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const DECODE_TX_BITCOIN: SubCommandType = (
+    "decode-tx",
+    "Decodes a raw Bitcoin transaction into its inputs, outputs and witnesses (include -h for more options)",
+    &[option::NETWORK_DECODE_TX, option::TRANSACTION_DECODE_TX],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const ELECTRUM_BITCOIN: SubCommandType = (
+    "electrum",
+    "Fetches balances and unspent outputs from an Electrum server and broadcasts transactions (include -h for more options)",
+    &[
+        option::ADDRESS_ELECTRUM_BITCOIN,
+        option::BROADCAST_ELECTRUM_BITCOIN,
+        option::NETWORK_ELECTRUM_BITCOIN,
+        option::SERVER_ELECTRUM_BITCOIN,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const EXPORT_BACKUP_BITCOIN: SubCommandType = (
+    "export-backup",
+    "Exports an encrypted backup of a mnemonic (include -h for more options)",
+    &[
+        option::BACKUP_PASSWORD_EXPORT_BACKUP,
+        option::LANGUAGE_EXPORT_BACKUP,
+        option::MNEMONIC_EXPORT_BACKUP,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const EXPORT_BACKUP_ETHEREUM: SubCommandType = (
+    "export-backup",
+    "Exports an encrypted backup of a mnemonic (include -h for more options)",
+    &[
+        option::BACKUP_PASSWORD_EXPORT_BACKUP,
+        option::LANGUAGE_EXPORT_BACKUP,
+        option::MNEMONIC_EXPORT_BACKUP,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const EXPORT_DESCRIPTORS_BITCOIN: SubCommandType = (
+    "export-descriptors",
+    "Exports the descriptors of an account to import into Bitcoin Core (include -h for more options)",
+    &[
+        option::ACCOUNT_EXPORT_DESCRIPTORS,
+        option::DERIVATION_EXPORT_DESCRIPTORS,
+        option::EXTENDED_PUBLIC_EXPORT_DESCRIPTORS,
+        option::FORMAT_EXPORT_DESCRIPTORS,
+        option::MNEMONIC_EXPORT_DESCRIPTORS,
+        option::NETWORK_EXPORT_DESCRIPTORS,
+        option::ORIGIN_EXPORT_DESCRIPTORS,
+        option::PASSWORD_EXPORT_DESCRIPTORS,
+        option::RANGE_EXPORT_DESCRIPTORS,
+        option::RPC_EXPORT_DESCRIPTORS,
+        option::TIMESTAMP_EXPORT_DESCRIPTORS,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const EXPORT_ELECTRUM_BITCOIN: SubCommandType = (
+    "export-electrum",
+    "Exports the wallet file of an account to open in Electrum (include -h for more options)",
+    &[
+        option::ACCOUNT_EXPORT_ELECTRUM,
+        option::DERIVATION_EXPORT_ELECTRUM,
+        option::EXTENDED_PUBLIC_EXPORT_ELECTRUM,
+        option::FORMAT_EXPORT_ELECTRUM,
+        option::MNEMONIC_EXPORT_ELECTRUM,
+        option::NETWORK_EXPORT_ELECTRUM,
+        option::ORIGIN_EXPORT_ELECTRUM,
+        option::PASSWORD_EXPORT_ELECTRUM,
+        option::WATCH_ONLY_EXPORT_ELECTRUM,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const EXPORT_SEED_QR_BITCOIN: SubCommandType = (
+    "export-seedqr",
+    "Exports a SeedQR of a mnemonic (include -h for more options)",
+    &[
+        option::COMPACT_EXPORT_SEED_QR,
+        option::LANGUAGE_EXPORT_SEED_QR,
+        option::MNEMONIC_EXPORT_SEED_QR,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const EXPORT_SEED_QR_ETHEREUM: SubCommandType = (
+    "export-seedqr",
+    "Exports a SeedQR of a mnemonic (include -h for more options)",
+    &[
+        option::COMPACT_EXPORT_SEED_QR,
+        option::LANGUAGE_EXPORT_SEED_QR,
+        option::MNEMONIC_EXPORT_SEED_QR,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
 pub const HD_BITCOIN: SubCommandType = (
     "hd",
     "Generates an HD wallet (include -h for more options)",
     &[
+        option::AEZEED_HD_BITCOIN,
+        option::COIN_HD,
         option::COUNT,
         option::DERIVATION_BITCOIN,
+        option::DICE_HD,
+        option::GROUP_THRESHOLD_HD,
         option::LANGUAGE_HD,
         option::NETWORK_HD_BITCOIN,
         option::PASSWORD_HD,
+        option::PROMPT_PASSWORD_HD,
+        option::PRESET_BITCOIN,
+        option::SLIP39_HD,
         option::WORD_COUNT,
     ],
     &[
@@ -27,12 +185,18 @@ pub const HD_ETHEREUM: SubCommandType = (
     "hd",
     "Generates an HD wallet (include -h for more options)",
     &[
+        option::COIN_HD,
         option::COUNT,
         option::DERIVATION_ETHEREUM,
+        option::DICE_HD,
+        option::GROUP_THRESHOLD_HD,
         option::INDEX_HD,
         option::INDICES_HD,
         option::LANGUAGE_HD,
         option::PASSWORD_HD,
+        option::PROMPT_PASSWORD_HD,
+        option::PRESET_ETHEREUM,
+        option::SLIP39_HD,
         option::WORD_COUNT,
     ],
     &[
@@ -128,19 +292,70 @@ pub const IMPORT_ZCASH: SubCommandType = (
     ],
 );
 
+pub const IMPORT_BACKUP_BITCOIN: SubCommandType = (
+    "import-backup",
+    "Imports an HD wallet from an encrypted backup (include -h for more options)",
+    &[
+        option::ACCOUNT,
+        option::BACKUP_IMPORT_BACKUP,
+        option::BACKUP_PASSWORD_IMPORT_BACKUP,
+        option::CHAIN,
+        option::DERIVATION_IMPORT_BITCOIN,
+        option::INDEX_IMPORT_HD,
+        option::NETWORK_IMPORT_HD_BITCOIN,
+        option::PASSWORD_IMPORT_HD,
+        option::PRESET_IMPORT_BITCOIN,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const IMPORT_BACKUP_ETHEREUM: SubCommandType = (
+    "import-backup",
+    "Imports an HD wallet from an encrypted backup (include -h for more options)",
+    &[
+        option::BACKUP_IMPORT_BACKUP,
+        option::BACKUP_PASSWORD_IMPORT_BACKUP,
+        option::DERIVATION_IMPORT_ETHEREUM,
+        option::INDEX_IMPORT_HD,
+        option::INDICES_IMPORT_HD,
+        option::PASSWORD_IMPORT_HD,
+        option::PRESET_IMPORT_ETHEREUM,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
 pub const IMPORT_HD_BITCOIN: SubCommandType = (
     "import-hd",
     "Imports an HD wallet (include -h for more options)",
     &[
         option::ACCOUNT,
+        option::AEZEED_IMPORT_HD_BITCOIN,
         option::CHAIN,
         option::DERIVATION_IMPORT_BITCOIN,
         option::EXTENDED_PUBLIC,
         option::EXTENDED_PRIVATE,
         option::NETWORK_IMPORT_HD_BITCOIN,
         option::INDEX_IMPORT_HD,
+        option::INDICES_IMPORT_HD,
+        option::LANGUAGE_IMPORT_HD,
         option::MNEMONIC,
         option::PASSWORD_IMPORT_HD,
+        option::PROMPT_PASSWORD_IMPORT_HD,
+        option::PRESET_IMPORT_BITCOIN,
+        option::RECOVER_IMPORT_HD,
+        option::SEED_QR_IMPORT_HD,
+        option::SHARE_IMPORT_HD,
+        option::WATCH_ONLY_IMPORT_HD,
     ],
     &[
         AppSettings::ColoredHelp,
@@ -159,8 +374,15 @@ pub const IMPORT_HD_ETHEREUM: SubCommandType = (
         option::EXTENDED_PRIVATE,
         option::INDEX_IMPORT_HD,
         option::INDICES_IMPORT_HD,
+        option::LANGUAGE_IMPORT_HD,
         option::MNEMONIC,
         option::PASSWORD_IMPORT_HD,
+        option::PROMPT_PASSWORD_IMPORT_HD,
+        option::PRESET_IMPORT_ETHEREUM,
+        option::RECOVER_IMPORT_HD,
+        option::SEED_QR_IMPORT_HD,
+        option::SHARE_IMPORT_HD,
+        option::WATCH_ONLY_IMPORT_HD,
     ],
     &[
         AppSettings::ColoredHelp,
@@ -189,13 +411,111 @@ pub const IMPORT_HD_ZCASH: SubCommandType = (
     ],
 );
 
+pub const LEDGER_BITCOIN: SubCommandType = (
+    "ledger",
+    "Verifies addresses and signs PSBTs with a Ledger device (include -h for more options)",
+    &[
+        option::ACCOUNT_LEDGER_BITCOIN,
+        option::CHAIN_LEDGER_BITCOIN,
+        option::DEVICE_LEDGER_BITCOIN,
+        option::FORMAT_LEDGER_BITCOIN,
+        option::INDEX_LEDGER_BITCOIN,
+        option::NETWORK_LEDGER_BITCOIN,
+        option::PSBT_LEDGER_BITCOIN,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const MESSAGE_BITCOIN: SubCommandType = (
+    "message",
+    "Signs or verifies a Bitcoin message (include -h for more options)",
+    &[
+        option::FORMAT_MESSAGE_BITCOIN,
+        option::SIGN_MESSAGE_BITCOIN,
+        option::VERIFY_MESSAGE_BITCOIN,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const MULTISIG_BITCOIN: SubCommandType = (
+    "multisig",
+    "Generates a multisig wallet (include -h for more options)",
+    &[
+        option::NETWORK_MULTISIG_BITCOIN,
+        option::PUBLIC_KEY_MULTISIG_BITCOIN,
+        option::THRESHOLD_MULTISIG_BITCOIN,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const PSBT_BITCOIN: SubCommandType = (
+    "psbt",
+    "Creates, updates, signs, combines, and finalizes a Bitcoin PSBT (include -h for more options)",
+    &[
+        option::COMBINE_PSBT_BITCOIN,
+        option::CREATE_PSBT_BITCOIN,
+        option::DATA_PSBT_BITCOIN,
+        option::FINALIZE_PSBT_BITCOIN,
+        option::LOCK_TIME_PSBT_BITCOIN,
+        option::MODIFIABLE_PSBT_BITCOIN,
+        option::PSBT_VERSION_PSBT_BITCOIN,
+        option::REPLACEABLE_PSBT_BITCOIN,
+        option::SIGN_PSBT_BITCOIN,
+        option::UPDATE_PSBT_BITCOIN,
+        option::VERSION_PSBT_BITCOIN,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const TIMELOCK_BITCOIN: SubCommandType = (
+    "timelock",
+    "Generates a timelock or hash timelock contract wallet (include -h for more options)",
+    &[
+        option::AFTER_TIMELOCK_BITCOIN,
+        option::HTLC_TIMELOCK_BITCOIN,
+        option::NETWORK_TIMELOCK_BITCOIN,
+        option::OLDER_TIMELOCK_BITCOIN,
+        option::PUBLIC_KEY_TIMELOCK_BITCOIN,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
 pub const TRANSACTION_BITCOIN: SubCommandType = (
     "transaction",
     "Generates a Bitcoin transaction (include -h for more options)",
     &[
         option::CREATE_RAW_TRANSACTION_BITCOIN,
         option::SIGN_RAW_TRANSACTION_BITCOIN,
+        option::EXPORT_TRANSACTION_BITCOIN,
+        option::IMPORT_TRANSACTION_BITCOIN,
+        option::TRANSACTION_DATA_BITCOIN,
         option::TRANSACTION_LOCK_TIME_BITCOIN,
+        option::TRANSACTION_REPLACEABLE_BITCOIN,
         option::TRANSACTION_VERSION_BITCOIN,
     ],
     &[
@@ -212,6 +532,7 @@ pub const TRANSACTION_ETHEREUM: SubCommandType = (
     &[
         option::CREATE_RAW_TRANSACTION_ETHEREUM,
         option::SIGN_RAW_TRANSACTION_ETHEREUM,
+        option::TRANSACTION_CHAIN_ETHEREUM,
         option::TRANSACTION_NETWORK_ETHEREUM,
     ],
     &[
@@ -239,3 +560,43 @@ pub const TRANSACTION_ZCASH: SubCommandType = (
         AppSettings::ArgRequiredElseHelp,
     ],
 );
+
+pub const TREZOR_BITCOIN: SubCommandType = (
+    "trezor",
+    "Verifies addresses and signs PSBTs with a Trezor device (include -h for more options)",
+    &[
+        option::ACCOUNT_TREZOR_BITCOIN,
+        option::BRIDGE_TREZOR_BITCOIN,
+        option::CHAIN_TREZOR_BITCOIN,
+        option::DEVICE_TREZOR_BITCOIN,
+        option::FORMAT_TREZOR_BITCOIN,
+        option::INDEX_TREZOR_BITCOIN,
+        option::NETWORK_TREZOR_BITCOIN,
+        option::PASSPHRASE_TREZOR_BITCOIN,
+        option::PSBT_TREZOR_BITCOIN,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const VANITY_BITCOIN: SubCommandType = (
+    "vanity",
+    "Searches for a Bitcoin address matching a pattern (include -h for more options)",
+    &[
+        option::FORMAT_VANITY_BITCOIN,
+        option::NETWORK_VANITY_BITCOIN,
+        option::PREFIX_VANITY_BITCOIN,
+        option::REGEX_VANITY_BITCOIN,
+        option::THREADS_VANITY_BITCOIN,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
@@ -1,5 +1,7 @@
-use crate::cli::{flag, option, subcommand, types::*, CLIError, CLI};
-use crate::model::{ExtendedPrivateKey, ExtendedPublicKey, PrivateKey, PublicKey, Transaction};
+use crate::cli::{flag, option, subcommand, types::*, CLIError, ResultExt, CLI};
+use crate::model::{
+    EntropyRng, EntropySource, ExtendedPrivateKey, ExtendedPublicKey, PrivateKey, PublicKey, Transaction,
+};
 use crate::zcash::{
     format::ZcashFormat, initialize_proving_context, initialize_verifying_context, load_sapling_parameters,
     Mainnet as ZcashMainnet, Outpoint, SignatureHash, Testnet as ZcashTestnet, ZcashAddress, ZcashAmount,
@@ -10,8 +12,7 @@ use crate::zcash::{
 use clap::{ArgMatches, Values};
 use colored::*;
 use core::{fmt, fmt::Display, str::FromStr};
-use rand::{rngs::StdRng, Rng};
-use rand_core::SeedableRng;
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 use serde_json::from_str;
 
@@ -41,6 +42,8 @@ struct ZcashWallet {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub path: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub key_origin: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub extended_private_key: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub extended_public_key: Option<String>,
@@ -65,7 +68,11 @@ struct ZcashWallet {
 }
 
 impl ZcashWallet {
-    pub fn new<N: ZcashNetwork, R: Rng>(rng: &mut R, format: &ZcashFormat) -> Result<Self, CLIError> {
+    pub fn new<N: ZcashNetwork, E: EntropySource + ?Sized>(
+        source: &mut E,
+        format: &ZcashFormat,
+    ) -> Result<Self, CLIError> {
+        let rng = &mut EntropyRng::new(source);
         let private_key = match format {
             ZcashFormat::P2PKH => ZcashPrivateKey::<N>::new_p2pkh(rng)?,
             ZcashFormat::Sprout => ZcashPrivateKey::<N>::new_sprout(rng)?,
@@ -85,17 +92,24 @@ impl ZcashWallet {
         })
     }
 
-    pub fn new_hd<N: ZcashNetwork, R: Rng>(rng: &mut R, path: &str, format: &ZcashFormat) -> Result<Self, CLIError> {
-        let seed: [u8; 32] = rng.gen();
+    pub fn new_hd<N: ZcashNetwork, E: EntropySource + ?Sized>(
+        source: &mut E,
+        path: &str,
+        format: &ZcashFormat,
+    ) -> Result<Self, CLIError> {
+        let seed: [u8; 32] = EntropyRng::new(source).gen();
         let master_extended_private_key = ZcashExtendedPrivateKey::<N>::new_master(&seed, format)?;
-        let derivation_path = ZcashDerivationPath::from_str(path)?;
+        let derivation_path =
+            ZcashDerivationPath::from_str(path).context_with_input("zcash", "parse derivation path", path)?;
         let extended_private_key = master_extended_private_key.derive(&derivation_path)?;
+        let key_origin = master_extended_private_key.key_origin(&derivation_path)?;
         let extended_public_key = extended_private_key.to_extended_public_key();
         let private_key = extended_private_key.to_private_key();
         let public_key = extended_public_key.to_public_key();
         let address = public_key.to_address(format)?;
         Ok(Self {
             path: Some(path.to_string()),
+            key_origin: Some(key_origin.to_string()),
             extended_private_key: Some(extended_private_key.to_string()),
             extended_public_key: Some(extended_public_key.to_string()),
             private_key: Some(private_key.to_string()),
@@ -113,9 +127,14 @@ impl ZcashWallet {
         path: &Option<String>,
         format: &ZcashFormat,
     ) -> Result<Self, CLIError> {
-        let mut extended_private_key = ZcashExtendedPrivateKey::<N>::from_str(extended_private_key)?;
+        let mut extended_private_key = ZcashExtendedPrivateKey::<N>::from_str(extended_private_key)
+            .context("zcash", "parse extended private key")?;
         if let Some(derivation_path) = path {
-            let derivation_path = ZcashDerivationPath::from_str(&derivation_path)?;
+            let derivation_path = ZcashDerivationPath::from_str(&derivation_path).context_with_input(
+                "zcash",
+                "parse derivation path",
+                derivation_path,
+            )?;
             extended_private_key = extended_private_key.derive(&derivation_path)?;
         }
         let extended_public_key = extended_private_key.to_extended_public_key();
@@ -141,9 +160,17 @@ impl ZcashWallet {
         path: &Option<String>,
         format: &ZcashFormat,
     ) -> Result<Self, CLIError> {
-        let mut extended_public_key = ZcashExtendedPublicKey::<N>::from_str(extended_public_key)?;
+        let mut extended_public_key = ZcashExtendedPublicKey::<N>::from_str(extended_public_key).context_with_input(
+            "zcash",
+            "parse extended public key",
+            extended_public_key,
+        )?;
         if let Some(derivation_path) = path {
-            let derivation_path = ZcashDerivationPath::from_str(&derivation_path)?;
+            let derivation_path = ZcashDerivationPath::from_str(&derivation_path).context_with_input(
+                "zcash",
+                "parse derivation path",
+                derivation_path,
+            )?;
             extended_public_key = extended_public_key.derive(&derivation_path)?;
         }
         let public_key = extended_public_key.to_public_key();
@@ -161,7 +188,7 @@ impl ZcashWallet {
     }
 
     pub fn from_private_key<N: ZcashNetwork>(private_key: &str, format: &ZcashFormat) -> Result<Self, CLIError> {
-        let private_key = ZcashPrivateKey::<N>::from_str(private_key)?;
+        let private_key = ZcashPrivateKey::<N>::from_str(private_key).context("zcash", "parse private key")?;
         let public_key = private_key.to_public_key();
         let address = public_key.to_address(format)?;
         Ok(Self {
@@ -176,7 +203,8 @@ impl ZcashWallet {
     }
 
     pub fn from_public_key<N: ZcashNetwork>(public_key: &str, format: &ZcashFormat) -> Result<Self, CLIError> {
-        let public_key = ZcashPublicKey::<N>::from_str(public_key)?;
+        let public_key =
+            ZcashPublicKey::<N>::from_str(public_key).context_with_input("zcash", "parse public key", public_key)?;
         let address = public_key.to_address(format)?;
         Ok(Self {
             public_key: Some(public_key.to_string()),
@@ -189,7 +217,7 @@ impl ZcashWallet {
     }
 
     pub fn from_address<N: ZcashNetwork>(address: &str) -> Result<Self, CLIError> {
-        let address = ZcashAddress::<N>::from_str(address)?;
+        let address = ZcashAddress::<N>::from_str(address).context_with_input("zcash", "parse address", &address)?;
         Ok(Self {
             address: Some(address.to_string()),
             format: Some(address.format().to_string()),
@@ -226,7 +254,8 @@ impl ZcashWallet {
 
         for output in outputs {
             let values: Vec<&str> = output.split(":").collect();
-            let address = ZcashAddress::<N>::from_str(values[0])?;
+            let address =
+                ZcashAddress::<N>::from_str(values[0]).context_with_input("zcash", "parse address", values[0])?;
             let amount = ZcashAmount::from_zatoshi(i64::from_str(values[1])?)?;
 
             match &address.format() {
@@ -278,8 +307,10 @@ impl ZcashWallet {
         for input in inputs {
             match (input.amount.clone(), input.address.clone(), input.private_key.clone()) {
                 (Some(amount), Some(address), Some(private_key)) => {
-                    let private_key = ZcashPrivateKey::<N>::from_str(&private_key)?;
-                    let address = ZcashAddress::<N>::from_str(&address)?;
+                    let private_key =
+                        ZcashPrivateKey::<N>::from_str(&private_key).context("zcash", "parse private key")?;
+                    let address =
+                        ZcashAddress::<N>::from_str(&address).context_with_input("zcash", "parse address", &address)?;
 
                     let mut reverse_transaction_id = hex::decode(&input.txid)?;
                     reverse_transaction_id.reverse();
@@ -326,6 +357,10 @@ impl Display for ZcashWallet {
                 Some(path) => format!("      {}                 {}\n", "Path".cyan().bold(), path),
                 _ => "".to_owned(),
             },
+            match &self.key_origin {
+                Some(key_origin) => format!("      {}           {}\n", "Key Origin".cyan().bold(), key_origin),
+                _ => "".to_owned(),
+            },
             match &self.extended_private_key {
                 Some(extended_private_key) => format!(
                     "      {} {}\n",
@@ -759,17 +794,15 @@ impl CLI for ZcashCLI {
 
     /// Generate the Zcash wallet and print the relevant fields
     #[cfg_attr(tarpaulin, skip)]
-    fn print(options: Self::Options) -> Result<(), CLIError> {
-        fn output<N: ZcashNetwork>(options: ZcashOptions) -> Result<(), CLIError> {
+    fn print_with_entropy(options: Self::Options, source: &mut dyn EntropySource) -> Result<(), CLIError> {
+        fn output<N: ZcashNetwork>(options: ZcashOptions, source: &mut dyn EntropySource) -> Result<(), CLIError> {
             let wallets =
                 match options.subcommand.as_ref().map(String::as_str) {
                     Some("hd") => match options.to_derivation_path(true) {
                         Some(path) => (0..options.count)
-                            .flat_map(|_| {
-                                match ZcashWallet::new_hd::<N, _>(&mut StdRng::from_entropy(), &path, &options.format) {
-                                    Ok(wallet) => vec![wallet],
-                                    _ => vec![],
-                                }
+                            .flat_map(|_| match ZcashWallet::new_hd::<N, _>(source, &path, &options.format) {
+                                Ok(wallet) => vec![wallet],
+                                _ => vec![],
                             })
                             .collect(),
                         None => vec![],
@@ -856,12 +889,10 @@ impl CLI for ZcashCLI {
                         }
                     }
                     _ => (0..options.count)
-                        .flat_map(
-                            |_| match ZcashWallet::new::<N, _>(&mut StdRng::from_entropy(), &options.format) {
-                                Ok(wallet) => vec![wallet],
-                                _ => vec![],
-                            },
-                        )
+                        .flat_map(|_| match ZcashWallet::new::<N, _>(source, &options.format) {
+                            Ok(wallet) => vec![wallet],
+                            _ => vec![],
+                        })
                         .collect(),
                 };
 
@@ -874,8 +905,8 @@ impl CLI for ZcashCLI {
         }
 
         match options.network.as_str() {
-            "testnet" => output::<ZcashTestnet>(options),
-            _ => output::<ZcashMainnet>(options),
+            "testnet" => output::<ZcashTestnet>(options, source),
+            _ => output::<ZcashMainnet>(options, source),
         }
     }
 }
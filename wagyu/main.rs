@@ -31,11 +31,17 @@ fn main() -> Result<(), CLIError> {
         .set_term_width(0)
         .get_matches();
 
-    match arguments.subcommand() {
-        ("bitcoin", Some(arguments)) => BitcoinCLI::print(BitcoinCLI::parse(arguments)?),
-        ("ethereum", Some(arguments)) => EthereumCLI::print(EthereumCLI::parse(arguments)?),
-        ("monero", Some(arguments)) => MoneroCLI::print(MoneroCLI::parse(arguments)?),
-        ("zcash", Some(arguments)) => ZcashCLI::print(ZcashCLI::parse(arguments)?),
+    let result = match arguments.subcommand() {
+        ("bitcoin", Some(arguments)) => BitcoinCLI::parse(arguments).and_then(BitcoinCLI::print),
+        ("ethereum", Some(arguments)) => EthereumCLI::parse(arguments).and_then(EthereumCLI::print),
+        ("monero", Some(arguments)) => MoneroCLI::parse(arguments).and_then(MoneroCLI::print),
+        ("zcash", Some(arguments)) => ZcashCLI::parse(arguments).and_then(ZcashCLI::print),
         _ => unreachable!(),
+    };
+
+    if let Err(error) = &result {
+        error.hints().iter().for_each(|hint| eprintln!("{}", hint));
     }
+
+    result
 }
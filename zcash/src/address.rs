@@ -7,7 +7,7 @@ use crate::public_key::{P2PKHViewingKey, SaplingFullViewingKey, SproutViewingKey
 use wagyu_model::no_std::{vec, String, ToString, Vec};
 use wagyu_model::{
     crypto::{checksum, hash160},
-    Address, AddressError, PrivateKey,
+    Address, AddressError, EntropyRng, EntropySource, PrivateKey,
 };
 
 use base58::{FromBase58, ToBase58};
@@ -103,7 +103,17 @@ impl<N: ZcashNetwork> ZcashAddress<N> {
     pub fn sapling(public_key: &SaplingFullViewingKey<N>, format: &ZcashFormat) -> Result<Self, AddressError> {
         // Randomness seeded by `getrandom`, which interfaces with the operating system
         // https://docs.rs/getrandom/
-        let rng = &mut StdRng::from_entropy();
+        Self::sapling_with_entropy(public_key, format, &mut StdRng::from_entropy())
+    }
+
+    /// Returns a shielded address from a given Zcash public key, sampling
+    /// its diversifier, if unspecified, from the given entropy source.
+    pub fn sapling_with_entropy<S: EntropySource + ?Sized>(
+        public_key: &SaplingFullViewingKey<N>,
+        format: &ZcashFormat,
+        source: &mut S,
+    ) -> Result<Self, AddressError> {
+        let rng = &mut EntropyRng::new(source);
 
         let mut data: [u8; 11] = match format {
             ZcashFormat::Sapling(data) => data.unwrap_or(rng.gen()),
@@ -671,6 +681,38 @@ mod tests {
             });
         }
 
+        #[test]
+        fn sapling_with_entropy() {
+            /// A deterministic entropy source counting up from a given byte.
+            struct CountingSource(u8);
+
+            impl EntropySource for CountingSource {
+                fn fill_entropy(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+                    dest.iter_mut().for_each(|byte| {
+                        *byte = self.0;
+                        self.0 = self.0.wrapping_add(1);
+                    });
+                    Ok(())
+                }
+            }
+
+            let private_key = ZcashPrivateKey::<N>::from_str(KEYPAIRS[0].0).unwrap();
+            let public_key = match ZcashPublicKey::<N>::from_private_key(&private_key) {
+                ZcashPublicKey::<N>::Sapling(public_key) => public_key,
+                _ => panic!("expected a Sapling public key"),
+            };
+
+            let format = ZcashFormat::Sapling(None);
+            let address = ZcashAddress::sapling_with_entropy(&public_key, &format, &mut CountingSource(0)).unwrap();
+            let expected_address =
+                ZcashAddress::sapling_with_entropy(&public_key, &format, &mut CountingSource(0)).unwrap();
+            assert_eq!(expected_address, address);
+
+            let other_address =
+                ZcashAddress::sapling_with_entropy(&public_key, &format, &mut CountingSource(100)).unwrap();
+            assert_ne!(address, other_address);
+        }
+
         #[test]
         fn to_str() {
             KEYPAIRS.iter().for_each(|(_, expected_address)| {
@@ -1,8 +1,9 @@
 use crate::network::ZcashNetwork;
 use wagyu_model::derivation_path::{ChildIndex, DerivationPath, DerivationPathError};
-use wagyu_model::no_std::{vec, ToString, Vec};
+use wagyu_model::no_std::{vec, String, ToString, Vec};
 
-use core::{convert::TryFrom, fmt, marker::PhantomData, str::FromStr};
+use core::{cmp::Ordering, convert::TryFrom, fmt, marker::PhantomData, str::FromStr};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
 /// Represents a Zcash derivation path
 #[derive(Clone, PartialEq, Eq)]
@@ -88,6 +89,30 @@ impl<'a, N: ZcashNetwork> TryFrom<&'a [ChildIndex]> for ZcashDerivationPath<N> {
     }
 }
 
+impl<N: ZcashNetwork> PartialOrd for ZcashDerivationPath<N> {
+    /// Returns the lexicographic ordering of the child index vectors of the derivation paths,
+    /// or `None` if either derivation path is invalid.
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self.to_vec(), other.to_vec()) {
+            (Ok(path), Ok(other)) => path.partial_cmp(&other),
+            _ => None,
+        }
+    }
+}
+
+impl<N: ZcashNetwork> Serialize for ZcashDerivationPath<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de, N: ZcashNetwork> Deserialize<'de> for ZcashDerivationPath<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let path = String::deserialize(deserializer)?;
+        Self::from_str(&path).map_err(de::Error::custom)
+    }
+}
+
 impl<N: ZcashNetwork> fmt::Debug for ZcashDerivationPath<N> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         fmt::Display::fmt(&self, f)
@@ -116,6 +141,34 @@ mod tests {
 
     use std::convert::TryInto;
 
+    #[test]
+    fn coin_type() {
+        use super::*;
+        use wagyu_model::coin_type::CoinType;
+
+        assert_eq!(
+            Ok(CoinType::Zcash),
+            ZcashDerivationPath::<Mainnet>::ZIP32Sapling(ChildIndex::Hardened(0)).coin_type()
+        );
+        assert_eq!(
+            Ok(CoinType::Testnet),
+            ZcashDerivationPath::<Testnet>::ZIP32Sapling(ChildIndex::Hardened(0)).coin_type()
+        );
+    }
+
+    #[test]
+    fn serde() {
+        use super::*;
+
+        type N = Mainnet;
+
+        let path = ZcashDerivationPath::<N>::from_str("m/32'/133'/0'").unwrap();
+        let json = serde_json::to_string(&path).unwrap();
+        assert_eq!("\"m/32'/133'/0'\"", json);
+        assert_eq!(path, serde_json::from_str(&json).unwrap());
+        assert!(serde_json::from_str::<ZcashDerivationPath<N>>("\"m/0\"").is_err());
+    }
+
     #[test]
     fn valid_path() {
         use super::*;
@@ -8,12 +8,13 @@ use crate::private_key::{SaplingSpendingKey, ZcashPrivateKey};
 use crate::public_key::ZcashPublicKey;
 use wagyu_model::no_std::{vec, ToString, Vec};
 use wagyu_model::{
-    Address, AddressError, ChildIndex, DerivationPath, ExtendedPrivateKey, ExtendedPrivateKeyError, ExtendedPublicKey,
-    PublicKey,
+    Address, AddressError, ChildIndex, DerivationPath, ExposeSecret, ExtendedPrivateKey, ExtendedPrivateKeyError,
+    ExtendedPublicKey, PublicKey,
 };
 
 use bech32::{Bech32, FromBase32, ToBase32};
 use core::{cmp::Ordering, fmt, fmt::Display, marker::PhantomData, str::FromStr};
+use zeroize::Zeroize;
 
 /// Represents a Zcash extended private key
 #[derive(Debug, Clone)]
@@ -112,10 +113,12 @@ impl<N: ZcashNetwork> Display for ZcashExtendedPrivateKey<N> {
             Ok(_) => (),
             Err(_) => return Err(fmt::Error),
         };
-        match Bech32::new(N::to_extended_private_key_prefix(), data.to_base32()) {
+        let result = match Bech32::new(N::to_extended_private_key_prefix(), data.to_base32()) {
             Ok(key) => write!(f, "{}", key),
             _ => Err(fmt::Error),
-        }
+        };
+        data.zeroize();
+        result
     }
 }
 
@@ -139,6 +142,21 @@ impl<N: ZcashNetwork> Ord for ZcashExtendedPrivateKey<N> {
     }
 }
 
+impl<N: ZcashNetwork> ExposeSecret for ZcashExtendedPrivateKey<N> {
+    fn fmt_secret(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ZcashExtendedPrivateKey")
+            .field("extended_spending_key", &self.extended_spending_key)
+            .field("expsk", &self.extended_spending_key.expsk.expose_secret())
+            .finish()
+    }
+}
+
+impl<N: ZcashNetwork> Zeroize for ZcashExtendedPrivateKey<N> {
+    fn zeroize(&mut self) {
+        self.extended_spending_key.zeroize();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -65,6 +65,11 @@ impl<N: ZcashNetwork> ExtendedPublicKey for ZcashExtendedPublicKey<N> {
     fn to_address(&self, format: &Self::Format) -> Result<Self::Address, AddressError> {
         Self::Address::from_public_key(&self.to_public_key(), format)
     }
+
+    /// Returns the tag of the full viewing key fingerprint, as specified by ZIP32.
+    fn fingerprint(&self) -> [u8; 4] {
+        self.extended_full_viewing_key.fvk_tag()
+    }
 }
 
 impl<N: ZcashNetwork> ZcashExtendedPublicKey<N> {
@@ -17,6 +17,7 @@ use blake2b_simd::{Hash as Blake2bHash, Params as Blake2bParams};
 use byteorder::{ByteOrder, LittleEndian, ReadBytesExt, WriteBytesExt};
 use core::marker::PhantomData;
 use fpe::ff1::{BinaryNumeralString, FF1};
+use zeroize::Zeroize;
 
 pub const ZIP32_SAPLING_MASTER_PERSONALIZATION: &'static [u8; 16] = b"ZcashIP32Sapling";
 pub const ZIP32_SAPLING_FVFP_PERSONALIZATION: &'static [u8; 16] = b"ZcashSaplingFVFP";
@@ -298,6 +299,20 @@ impl<N: ZcashNetwork> core::fmt::Debug for ExtendedSpendingKey<N> {
     }
 }
 
+impl<N: ZcashNetwork> Zeroize for ExtendedSpendingKey<N> {
+    fn zeroize(&mut self) {
+        self.chain_code.0.zeroize();
+        self.expsk.zeroize();
+        self.dk.0.zeroize();
+    }
+}
+
+impl<N: ZcashNetwork> Drop for ExtendedSpendingKey<N> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
 /// Represents a Sapling extended full viewing key
 #[derive(Clone)]
 pub struct ExtendedFullViewingKey<N: ZcashNetwork> {
@@ -310,6 +325,11 @@ pub struct ExtendedFullViewingKey<N: ZcashNetwork> {
 }
 
 impl<N: ZcashNetwork> ExtendedFullViewingKey<N> {
+    /// Returns the tag of the full viewing key fingerprint.
+    pub fn fvk_tag(&self) -> [u8; 4] {
+        FVKFingerprint::from(&self.fvk).tag().0
+    }
+
     pub fn read<R: Read>(mut reader: R) -> io::Result<Self> {
         let depth = reader.read_u8()?;
         let mut tag = [0; 4];
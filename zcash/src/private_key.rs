@@ -1,7 +1,7 @@
 use crate::address::ZcashAddress;
 use crate::format::ZcashFormat;
 use crate::librustzcash::algebra::curve::bls12_381::Bls12;
-use crate::librustzcash::algebra::field::{PrimeField, PrimeFieldRepr};
+use crate::librustzcash::algebra::field::{Field, PrimeField, PrimeFieldRepr};
 use crate::librustzcash::sapling_crypto::{
     jubjub::{FixedGenerators, JubjubEngine, JubjubParams, ToUniform},
     primitives::ProofGenerationKey as SaplingProofGenerationKey,
@@ -13,7 +13,9 @@ use wagyu_model::no_std::{
     io::{self, Read, Write},
     vec, String, ToString, Vec,
 };
-use wagyu_model::{crypto::checksum, Address, AddressError, PrivateKey, PrivateKeyError, PublicKey};
+use wagyu_model::{
+    crypto::checksum, Address, AddressError, ExposeSecret, PrivateKey, PrivateKeyError, PublicKey, Redacted,
+};
 
 use base58::{FromBase58, ToBase58};
 use bech32::{Bech32, FromBase32, ToBase32};
@@ -26,8 +28,9 @@ use core::{
 use failure::AsFail;
 use rand::Rng;
 use secp256k1;
+use zeroize::Zeroize;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq)]
 pub struct P2PKHSpendingKey<N: ZcashNetwork> {
     /// The ECDSA private key
     pub(super) secret_key: secp256k1::SecretKey,
@@ -83,7 +86,7 @@ impl<N: ZcashNetwork> Display for P2PKHSpendingKey<N> {
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct P2SHSpendingKey {}
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct SproutSpendingKey<N: ZcashNetwork> {
     /// Raw encoding of (0000 || 252-bit a_sk)
     pub(super) spending_key: [u8; 32],
@@ -191,6 +194,16 @@ impl<N: ZcashNetwork> SaplingSpendingKey<N> {
 
 impl<N: ZcashNetwork> Debug for SaplingSpendingKey<N> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "SaplingSpendingKey {{ sk: {:?}, ask: {:?}, nsk: {:?}, ovk: {:?} }}",
+            Redacted, Redacted, Redacted, Redacted
+        )
+    }
+}
+
+impl<N: ZcashNetwork> ExposeSecret for SaplingSpendingKey<N> {
+    fn fmt_secret(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
             "SaplingSpendingKey {{ sk: {:?}, ask: {:?}, nsk: {:?}, ovk: {:?} }}",
@@ -274,8 +287,10 @@ impl<N: ZcashNetwork> PrivateKey for ZcashPrivateKey<N> {
 impl<N: ZcashNetwork> ZcashPrivateKey<N> {
     /// Returns a randomly-generated Zcash P2PKH private key.
     pub fn new_p2pkh<R: Rng>(rng: &mut R) -> Result<Self, PrivateKeyError> {
-        let random: [u8; 32] = rng.gen();
-        let secret_key = secp256k1::SecretKey::parse_slice(&random)?;
+        let mut random: [u8; 32] = rng.gen();
+        let secret_key = secp256k1::SecretKey::parse_slice(&random);
+        random.zeroize();
+        let secret_key = secret_key?;
         Ok(ZcashPrivateKey::<N>::P2PKH(P2PKHSpendingKey::<N>::new(
             secret_key, true,
         )))
@@ -420,6 +435,104 @@ impl<N: ZcashNetwork> Display for ZcashPrivateKey<N> {
     }
 }
 
+impl<N: ZcashNetwork> fmt::Debug for P2PKHSpendingKey<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("P2PKHSpendingKey")
+            .field("secret_key", &Redacted)
+            .field("compressed", &self.compressed)
+            .finish()
+    }
+}
+
+impl<N: ZcashNetwork> ExposeSecret for P2PKHSpendingKey<N> {
+    fn fmt_secret(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("P2PKHSpendingKey")
+            .field("secret_key", &self.secret_key)
+            .field("compressed", &self.compressed)
+            .finish()
+    }
+}
+
+impl<N: ZcashNetwork> Zeroize for P2PKHSpendingKey<N> {
+    /// Replaces the secret key with a placeholder, clearing the previous secret key on drop.
+    fn zeroize(&mut self) {
+        self.secret_key = secp256k1::SecretKey::default();
+    }
+}
+
+impl<N: ZcashNetwork> fmt::Debug for SproutSpendingKey<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SproutSpendingKey")
+            .field("spending_key", &Redacted)
+            .finish()
+    }
+}
+
+impl<N: ZcashNetwork> ExposeSecret for SproutSpendingKey<N> {
+    fn fmt_secret(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SproutSpendingKey")
+            .field("spending_key", &self.spending_key)
+            .finish()
+    }
+}
+
+impl<N: ZcashNetwork> Zeroize for SproutSpendingKey<N> {
+    fn zeroize(&mut self) {
+        self.spending_key.zeroize();
+    }
+}
+
+impl<N: ZcashNetwork> Drop for SproutSpendingKey<N> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl<N: ZcashNetwork> Zeroize for SaplingSpendingKey<N> {
+    fn zeroize(&mut self) {
+        if let Some(spending_key) = self.spending_key.as_mut() {
+            spending_key.zeroize();
+        }
+        self.ask = <Bls12 as JubjubEngine>::Fs::zero();
+        self.nsk = <Bls12 as JubjubEngine>::Fs::zero();
+        self.ovk.0.zeroize();
+    }
+}
+
+impl<N: ZcashNetwork> Drop for SaplingSpendingKey<N> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl<N: ZcashNetwork> ExposeSecret for ZcashPrivateKey<N> {
+    fn fmt_secret(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ZcashPrivateKey::P2PKH(spending_key) => {
+                f.debug_tuple("P2PKH").field(&spending_key.expose_secret()).finish()
+            }
+            ZcashPrivateKey::P2SH(spending_key) => f.debug_tuple("P2SH").field(spending_key).finish(),
+            ZcashPrivateKey::Sprout(spending_key) => {
+                f.debug_tuple("Sprout").field(&spending_key.expose_secret()).finish()
+            }
+            ZcashPrivateKey::Sapling(spending_key) => {
+                f.debug_tuple("Sapling").field(&spending_key.expose_secret()).finish()
+            }
+        }
+    }
+}
+
+impl<N: ZcashNetwork> Zeroize for ZcashPrivateKey<N> {
+    fn zeroize(&mut self) {
+        match self {
+            ZcashPrivateKey::P2PKH(spending_key) => spending_key.zeroize(),
+            ZcashPrivateKey::P2SH(_) => (),
+            ZcashPrivateKey::Sprout(spending_key) => spending_key.zeroize(),
+            ZcashPrivateKey::Sapling(spending_key) => spending_key.zeroize(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1756,7 +1756,7 @@ mod tests {
                 .unwrap();
 
             let extended_spend_key = ZcashExtendedPrivateKey::<N>::from_str(input.extended_private_key).unwrap();
-            sapling_spend_key = Some(extended_spend_key.to_extended_spending_key().expsk);
+            sapling_spend_key = Some(extended_spend_key.to_extended_spending_key().expsk.clone());
         }
 
         // Select Output Viewing Key